@@ -0,0 +1,188 @@
+//! Hot-path benchmarks across the workspace
+//!
+//! Groups:
+//! - `interface_key`: ChaCha20-Poly1305 encrypt/decrypt throughput
+//! - `pq_signature`: ML-DSA-65 sign/verify
+//! - `sync`: Automerge sync message generation for N events
+//! - `redb`: batched writes and reads
+//! - `node`: end-to-end `send_message` latency (encrypt, sign, append, persist)
+//!
+//! Run with: ./scripts/run-benches.sh
+
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+use indras_core::{InterfaceEvent, InterfaceId, SimulationIdentity};
+use indras_crypto::{InterfaceKey, PQIdentity};
+use indras_node::{IndrasNode, NodeConfig};
+use indras_storage::{RedbStorage, RedbStorageConfig};
+use indras_sync::InterfaceDocument;
+use redb::TableDefinition;
+
+const BENCH_TABLE: TableDefinition<&[u8], &[u8]> = TableDefinition::new("bench");
+
+// ============================================================================
+// InterfaceKey Throughput
+// ============================================================================
+
+fn bench_interface_key(c: &mut Criterion) {
+    let mut group = c.benchmark_group("interface_key");
+    let key = InterfaceKey::generate(InterfaceId::generate());
+
+    for size in [256usize, 4096, 65536] {
+        let plaintext = vec![0u8; size];
+        let encrypted = key.encrypt(&plaintext).unwrap();
+        group.throughput(Throughput::Bytes(size as u64));
+
+        group.bench_with_input(BenchmarkId::new("encrypt", size), &plaintext, |b, msg| {
+            b.iter(|| key.encrypt(black_box(msg)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("decrypt", size), &encrypted, |b, enc| {
+            b.iter(|| key.decrypt(black_box(enc)).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+// ============================================================================
+// ML-DSA-65 Sign/Verify
+// ============================================================================
+
+fn bench_pq_signature(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pq_signature");
+    let identity = PQIdentity::generate();
+    let public = identity.verifying_key();
+
+    // Typical signed network message size
+    let message = vec![0u8; 1024];
+    let signature = identity.sign(&message);
+
+    group.bench_function("sign_1kb", |b| b.iter(|| identity.sign(black_box(&message))));
+    group.bench_function("verify_1kb", |b| {
+        b.iter(|| public.verify(black_box(&message), black_box(&signature)))
+    });
+
+    group.finish();
+}
+
+// ============================================================================
+// Automerge Sync Generation
+// ============================================================================
+
+fn document_with_events(n: u64) -> InterfaceDocument {
+    let peer = SimulationIdentity::new('A').unwrap();
+    let mut doc = InterfaceDocument::new();
+    doc.add_member(&peer);
+    for seq in 1..=n {
+        let event = InterfaceEvent::message(peer, seq, vec![0u8; 128]);
+        doc.append_event(&event).unwrap();
+    }
+    doc
+}
+
+fn bench_sync(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sync");
+    group.sample_size(20);
+
+    for n in [10u64, 100, 1000] {
+        let mut doc = document_with_events(n);
+        group.throughput(Throughput::Elements(n));
+
+        // First message to a fresh peer (no shared history)
+        group.bench_function(BenchmarkId::new("generate_sync_message", n), |b| {
+            b.iter(|| {
+                let mut state = automerge::sync::State::new();
+                black_box(doc.generate_sync_message(&mut state))
+            })
+        });
+
+        // Full-state save used by the simple merge path
+        group.bench_function(BenchmarkId::new("save", n), |b| b.iter(|| black_box(doc.save())));
+    }
+
+    group.finish();
+}
+
+// ============================================================================
+// redb Batches
+// ============================================================================
+
+fn bench_redb(c: &mut Criterion) {
+    let mut group = c.benchmark_group("redb");
+    let temp = tempfile::tempdir().unwrap();
+    let storage = RedbStorage::open(RedbStorageConfig {
+        db_path: temp.path().join("bench.redb"),
+        ..Default::default()
+    })
+    .unwrap();
+    let value = vec![0u8; 256];
+
+    for batch in [1usize, 100, 1000] {
+        let keys: Vec<[u8; 8]> = (0..batch as u64).map(|i| i.to_be_bytes()).collect();
+        group.throughput(Throughput::Elements(batch as u64));
+
+        group.bench_with_input(BenchmarkId::new("write_batch", batch), &keys, |b, keys| {
+            b.iter(|| {
+                let txn = storage.db().begin_write().unwrap();
+                {
+                    let mut table = txn.open_table(BENCH_TABLE).unwrap();
+                    for key in keys {
+                        table.insert(key.as_slice(), value.as_slice()).unwrap();
+                    }
+                }
+                txn.commit().unwrap();
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("read_batch", batch), &keys, |b, keys| {
+            b.iter(|| {
+                let txn = storage.db().begin_read().unwrap();
+                let table = txn.open_table(BENCH_TABLE).unwrap();
+                for key in keys {
+                    black_box(table.get(key.as_slice()).unwrap());
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
+
+// ============================================================================
+// End-to-end send_message
+// ============================================================================
+
+fn bench_node_send(c: &mut Criterion) {
+    let mut group = c.benchmark_group("node");
+    group.sample_size(20);
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let temp = tempfile::tempdir().unwrap();
+    let (node, interface_id) = rt.block_on(async {
+        let node = IndrasNode::new(NodeConfig::with_data_dir(temp.path()))
+            .await
+            .unwrap();
+        let (interface_id, _) = node.create_interface(Some("bench")).await.unwrap();
+        (node, interface_id)
+    });
+
+    let payload = vec![0u8; 256];
+    group.bench_function("send_message_256b", |b| {
+        b.iter(|| {
+            rt.block_on(node.send_message(&interface_id, black_box(payload.clone())))
+                .unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_interface_key,
+    bench_pq_signature,
+    bench_sync,
+    bench_redb,
+    bench_node_send,
+);
+
+criterion_main!(benches);
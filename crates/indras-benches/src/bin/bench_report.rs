@@ -0,0 +1,24 @@
+//! Print criterion results as JSONL.
+//!
+//! Usage: `bench-report [CRITERION_DIR]` (default `target/criterion`)
+
+use std::path::PathBuf;
+
+fn main() {
+    let dir = std::env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("target/criterion"));
+
+    match indras_benches::collect(&dir) {
+        Ok(records) => {
+            for record in records {
+                println!("{}", record.to_json_line());
+            }
+        }
+        Err(e) => {
+            eprintln!("failed to read {}: {e}", dir.display());
+            std::process::exit(1);
+        }
+    }
+}
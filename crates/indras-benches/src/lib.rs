@@ -0,0 +1,20 @@
+//! Workspace-level benchmark suite for Indra's Network.
+//!
+//! The criterion benches live in `benches/hot_paths.rs` and cover the
+//! hot paths that dominate real workloads:
+//!
+//! - `InterfaceKey` encrypt/decrypt throughput (ChaCha20-Poly1305)
+//! - ML-DSA-65 sign/verify
+//! - Automerge sync message generation for N events
+//! - redb write/read batches
+//! - End-to-end `IndrasNode::send_message` latency
+//!
+//! This library only contains [`report`], which flattens criterion's
+//! output into JSONL so results can be tailed by the dashboard or
+//! compared across runs.
+//!
+//! Run with: `./scripts/run-benches.sh`
+
+pub mod report;
+
+pub use report::{collect, BenchRecord};
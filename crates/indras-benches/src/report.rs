@@ -0,0 +1,140 @@
+//! Flatten criterion results into JSONL records.
+//!
+//! Criterion writes one `new/estimates.json` + `new/benchmark.json` pair
+//! per benchmark under `target/criterion`. [`collect`] walks that tree and
+//! returns one [`BenchRecord`] per benchmark, sorted by ID.
+
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::Path;
+
+/// One benchmark result, as emitted to JSONL.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchRecord {
+    /// Full criterion ID, e.g. `"sync/generate_sync_message/1000"`.
+    pub id: String,
+    /// Benchmark group name.
+    pub group: String,
+    /// Mean time per iteration in nanoseconds.
+    pub mean_ns: f64,
+    /// Median time per iteration in nanoseconds.
+    pub median_ns: f64,
+    /// Standard deviation in nanoseconds.
+    pub std_dev_ns: f64,
+}
+
+impl BenchRecord {
+    /// Serialize as a single JSONL line (without trailing newline).
+    pub fn to_json_line(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+#[derive(Deserialize)]
+struct Estimate {
+    point_estimate: f64,
+}
+
+#[derive(Deserialize)]
+struct Estimates {
+    mean: Estimate,
+    median: Estimate,
+    std_dev: Estimate,
+}
+
+#[derive(Deserialize)]
+struct BenchmarkId {
+    group_id: String,
+    full_id: String,
+}
+
+/// Collect all benchmark results under a criterion output directory.
+///
+/// Directories without a complete `new/` result pair are skipped.
+pub fn collect(criterion_dir: &Path) -> io::Result<Vec<BenchRecord>> {
+    let mut records = Vec::new();
+    walk(criterion_dir, &mut records)?;
+    records.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(records)
+}
+
+fn walk(dir: &Path, records: &mut Vec<BenchRecord>) -> io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !path.is_dir() {
+            continue;
+        }
+        if path.file_name().is_some_and(|n| n == "new") {
+            if let Some(record) = read_result(&path) {
+                records.push(record);
+            }
+        } else if path.file_name().is_some_and(|n| n != "base" && n != "report") {
+            walk(&path, records)?;
+        }
+    }
+    Ok(())
+}
+
+fn read_result(new_dir: &Path) -> Option<BenchRecord> {
+    let estimates: Estimates =
+        serde_json::from_slice(&std::fs::read(new_dir.join("estimates.json")).ok()?).ok()?;
+    let id: BenchmarkId =
+        serde_json::from_slice(&std::fs::read(new_dir.join("benchmark.json")).ok()?).ok()?;
+    Some(BenchRecord {
+        id: id.full_id,
+        group: id.group_id,
+        mean_ns: estimates.mean.point_estimate,
+        median_ns: estimates.median.point_estimate,
+        std_dev_ns: estimates.std_dev.point_estimate,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_result(root: &Path, rel: &str, full_id: &str, mean: f64) {
+        let dir = root.join(rel).join("new");
+        std::fs::create_dir_all(&dir).unwrap();
+        let estimate = |v: f64| serde_json::json!({ "point_estimate": v, "standard_error": 0.0 });
+        std::fs::write(
+            dir.join("estimates.json"),
+            serde_json::json!({
+                "mean": estimate(mean),
+                "median": estimate(mean - 1.0),
+                "std_dev": estimate(2.0),
+            })
+            .to_string(),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("benchmark.json"),
+            serde_json::json!({ "group_id": full_id.split('/').next().unwrap(), "full_id": full_id })
+                .to_string(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_collect_walks_nested_results() {
+        let temp = tempfile::tempdir().unwrap();
+        write_result(temp.path(), "sync/generate/100", "sync/generate/100", 50.0);
+        write_result(temp.path(), "crypto/sign", "crypto/sign", 10.0);
+        // Baselines and HTML reports are ignored
+        write_result(temp.path(), "crypto/sign/base", "crypto/stale", 99.0);
+        std::fs::create_dir_all(temp.path().join("report")).unwrap();
+
+        let records = collect(temp.path()).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].id, "crypto/sign");
+        assert_eq!(records[0].median_ns, 9.0);
+        assert_eq!(records[1].group, "sync");
+    }
+
+    #[test]
+    fn test_incomplete_results_skipped() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(temp.path().join("x/new")).unwrap();
+        assert!(collect(temp.path()).unwrap().is_empty());
+    }
+}
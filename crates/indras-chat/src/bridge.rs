@@ -34,6 +34,23 @@ pub fn default_data_dir() -> PathBuf {
     PathBuf::from(".").join("indras-chat")
 }
 
+/// Ask a background instance using our data directory to show its window.
+///
+/// Returns `true` if one did, in which case this process should exit.
+pub fn show_running_instance() -> bool {
+    indras_network::show_running_instance(&default_data_dir())
+}
+
+/// Serve the agent socket for a background instance.
+#[cfg(unix)]
+pub async fn start_agent_host(
+    network: Arc<IndrasNetwork>,
+) -> Result<indras_network::AgentHost, String> {
+    indras_network::AgentHost::start(network, &default_data_dir())
+        .await
+        .map_err(|e| format!("{e}"))
+}
+
 /// Check if this is the user's first run (no identity keys on disk).
 pub fn is_first_run() -> bool {
     IndrasNetwork::is_first_run(default_data_dir())
@@ -0,0 +1,159 @@
+//! Clipboard image capture for paste-to-share.
+//!
+//! A bitmap pasted into the compose bar (or handed over by a screenshot
+//! tool) is encoded as PNG, uploaded to the home realm filesystem as an
+//! artifact, granted to the realm's members, and posted as an inline image
+//! message so it renders in the conversation.
+//!
+//! Privacy rides on the realm: the chat document syncs encrypted under the
+//! realm's interface key, and the artifact stays in the sender's home
+//! realm behind revocable grants.
+
+use std::io::Cursor;
+
+use image::imageops::{self, FilterType};
+use image::{ImageFormat, Rgba, RgbaImage};
+use indras_network::{AccessMode, ArtifactId, IndrasNetwork, MAX_INLINE_IMAGE_SIZE, Realm};
+
+/// A region of a captured image, in pixels from the top-left corner.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CropRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// An RGBA bitmap taken from the clipboard.
+#[derive(Clone, Debug)]
+pub struct CapturedImage {
+    image: RgbaImage,
+}
+
+impl CapturedImage {
+    /// Wrap raw RGBA pixels; `None` if the buffer doesn't match the size.
+    pub fn from_rgba(width: u32, height: u32, rgba: Vec<u8>) -> Option<Self> {
+        RgbaImage::from_raw(width, height, rgba).map(|image| Self { image })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.image.width()
+    }
+
+    pub fn height(&self) -> u32 {
+        self.image.height()
+    }
+
+    /// Cut out `rect`, clamped to the image. `None` if nothing is left.
+    pub fn crop(&self, rect: CropRect) -> Option<Self> {
+        let x = rect.x.min(self.width());
+        let y = rect.y.min(self.height());
+        let width = rect.width.min(self.width() - x);
+        let height = rect.height.min(self.height() - y);
+        if width == 0 || height == 0 {
+            return None;
+        }
+        let image = imageops::crop_imm(&self.image, x, y, width, height).to_image();
+        Some(Self { image })
+    }
+
+    /// Bounding box of everything that differs from the top-left pixel,
+    /// i.e. the capture without a solid border or window backdrop.
+    pub fn content_bounds(&self) -> Option<CropRect> {
+        let background: Rgba<u8> = *self.image.get_pixel_checked(0, 0)?;
+        let (mut min_x, mut min_y) = (u32::MAX, u32::MAX);
+        let (mut max_x, mut max_y) = (0, 0);
+        for (x, y, pixel) in self.image.enumerate_pixels() {
+            if *pixel != background {
+                min_x = min_x.min(x);
+                min_y = min_y.min(y);
+                max_x = max_x.max(x);
+                max_y = max_y.max(y);
+            }
+        }
+        (min_x <= max_x).then(|| CropRect {
+            x: min_x,
+            y: min_y,
+            width: max_x - min_x + 1,
+            height: max_y - min_y + 1,
+        })
+    }
+
+    /// Crop to [`content_bounds`](Self::content_bounds), or keep the whole
+    /// image if it is a single colour.
+    pub fn trimmed(&self) -> Self {
+        self.content_bounds()
+            .and_then(|rect| self.crop(rect))
+            .unwrap_or_else(|| self.clone())
+    }
+
+    /// Encode as PNG, halving the resolution until it fits inline in a
+    /// chat message.
+    pub fn to_png(&self) -> Result<Vec<u8>, String> {
+        let mut image = self.image.clone();
+        loop {
+            let mut png = Vec::new();
+            image
+                .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+                .map_err(|e| format!("Failed to encode image: {e}"))?;
+            if png.len() <= MAX_INLINE_IMAGE_SIZE || image.width() <= 1 || image.height() <= 1 {
+                return Ok(png);
+            }
+            let (width, height) = (image.width() / 2, image.height() / 2);
+            image = imageops::resize(&image, width, height, FilterType::Triangle);
+        }
+    }
+}
+
+/// The image on the system clipboard, if there is one.
+pub fn read_clipboard_image() -> Result<Option<CapturedImage>, String> {
+    let mut clipboard = arboard::Clipboard::new().map_err(|e| e.to_string())?;
+    match clipboard.get_image() {
+        Ok(data) => Ok(CapturedImage::from_rgba(
+            data.width as u32,
+            data.height as u32,
+            data.bytes.into_owned(),
+        )),
+        Err(arboard::Error::ContentNotAvailable) => Ok(None),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Share `image` into `realm` as `author`.
+///
+/// Uploads the PNG to the home realm, grants every other realm member
+/// revocable access, and posts it as an inline image message.
+pub async fn share_image(
+    network: &IndrasNetwork,
+    realm: &Realm,
+    author: &str,
+    image: CapturedImage,
+) -> Result<ArtifactId, String> {
+    let png = tokio::task::spawn_blocking(move || image.to_png())
+        .await
+        .map_err(|e| format!("Encoding task failed: {e}"))??;
+    let filename = format!("paste-{}.png", chrono::Utc::now().format("%Y%m%d-%H%M%S"));
+
+    let home = network.home_realm().await.map_err(|e| e.to_string())?;
+    let artifact_id = home
+        .upload_data(filename.clone(), png.clone())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let me = network.id();
+    let members = realm.member_list().await.map_err(|e| e.to_string())?;
+    for member in members.into_iter().map(|m| m.id()).filter(|id| *id != me) {
+        if let Err(e) = home
+            .grant_access(&artifact_id, member, AccessMode::Revocable)
+            .await
+        {
+            tracing::warn!(error = %e, "Failed to grant pasted image");
+        }
+    }
+
+    realm
+        .chat_send_image(author, png, "image/png", Some(filename))
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(artifact_id)
+}
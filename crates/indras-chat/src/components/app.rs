@@ -2,9 +2,9 @@
 
 use std::sync::Arc;
 use dioxus::prelude::*;
-use crate::state::{AppPhase, ChatContext, ConversationSummary};
+use crate::state::{AppPhase, ChatContext, ConversationSummary, PopOutHandler};
 use crate::bridge;
-use indras_network::{IndrasNetwork, PeerEvent};
+use indras_network::{IndrasNetwork, PeerEvent, RealmId};
 
 /// Root application component.
 #[component]
@@ -41,7 +41,7 @@ pub fn App() -> Element {
         AppPhase::Loading => rsx! {
             div { class: "loading-screen",
                 div { class: "loading-logo", "I" }
-                div { class: "loading-text", "Loading..." }
+                div { class: "loading-text", {indras_ui::tr("chat-app-loading")} }
             }
         },
         AppPhase::Setup => rsx! {
@@ -140,6 +140,44 @@ fn MainLayout(network: NetworkArc) -> Element {
         });
     });
 
+    // Background mode: keep the node reachable while the window is hidden
+    #[cfg(unix)]
+    {
+        let net = network.clone();
+        use_effect(move || {
+            if !indras_network::background_mode() {
+                return;
+            }
+            let net = net.clone();
+            spawn(async move {
+                let host = match bridge::start_agent_host(net.clone()).await {
+                    Ok(host) => host,
+                    Err(e) => {
+                        tracing::warn!(error = %e, "failed to start agent host");
+                        return;
+                    }
+                };
+                let mut show = host.show_requests();
+                loop {
+                    tokio::select! {
+                        r = show.recv() => {
+                            if matches!(r, Err(tokio::sync::broadcast::error::RecvError::Closed)) {
+                                break;
+                            }
+                            let window = dioxus::desktop::window();
+                            window.set_visible(true);
+                            window.set_focus();
+                        }
+                        _ = host.shutdown_requested() => {
+                            let _ = net.stop().await;
+                            std::process::exit(0);
+                        }
+                    }
+                }
+            });
+        });
+    }
+
     rsx! {
         div { class: "main-layout",
             super::sidebar::Sidebar {}
@@ -163,14 +201,24 @@ fn MainLayout(network: NetworkArc) -> Element {
 
 /// Embeddable chat layout for use in other apps (e.g., indras-workspace).
 /// Accepts an `IndrasNetwork` instance — no identity/setup flow.
+///
+/// `realm` pins the layout to one conversation and hides the sidebar;
+/// `on_pop_out` adds a button to the chat header that hands the active
+/// conversation to the embedder to open elsewhere.
 #[component]
-pub fn ChatLayout(runtime: NetworkArc) -> Element {
+pub fn ChatLayout(
+    runtime: NetworkArc,
+    #[props(default)]
+    realm: Option<RealmId>,
+    #[props(default)]
+    on_pop_out: Option<EventHandler<RealmId>>,
+) -> Element {
     let network = runtime.0;
 
     // Provide shared chat context
     let mut ctx = use_context_provider(|| ChatContext {
         runtime: Signal::new(network.clone()),
-        active_chat: Signal::new(None),
+        active_chat: Signal::new(realm),
         conversations: Signal::new(Vec::new()),
         peers: Signal::new(Vec::new()),
         show_add_contact: Signal::new(false),
@@ -210,9 +258,13 @@ pub fn ChatLayout(runtime: NetworkArc) -> Element {
         });
     });
 
+    use_context_provider(|| PopOutHandler(on_pop_out));
+
     rsx! {
         div { class: "main-layout",
-            super::sidebar::Sidebar {}
+            if realm.is_none() {
+                super::sidebar::Sidebar {}
+            }
             super::chat_view::ChatView {}
 
             if *ctx.show_add_contact.read() {
@@ -4,7 +4,8 @@ use dioxus::prelude::*;
 use futures::StreamExt;
 use indras_network::{Content, RealmId};
 use indras_network::chat_message::{TYPING_EXTENSION_TYPE, TypingIndicator};
-use crate::state::{ChatContext, SystemEventSnapshot};
+use crate::clipboard::{CapturedImage, share_image};
+use crate::state::{ChatContext, PopOutHandler, SystemEventSnapshot};
 use super::message_bubble::DeliveryStatus;
 
 /// A snapshot of a chat message for display.
@@ -18,6 +19,7 @@ struct MessageSnapshot {
     is_edited: bool,
     reply_preview: Option<(String, String)>,
     reactions: Vec<(String, usize)>,
+    image_url: Option<String>,
 }
 
 /// A timeline entry — either a user message or a system event.
@@ -166,6 +168,7 @@ fn ActiveChat(realm_id: RealmId) -> Element {
     let current_timeline = timeline.read().clone();
     let chat_title = chat_name.read().clone();
     let my_id_display = my_id.clone();
+    let pop_out = try_use_context::<PopOutHandler>().and_then(|h| h.0);
 
     rsx! {
         div { class: "chat-view",
@@ -177,6 +180,14 @@ fn ActiveChat(realm_id: RealmId) -> Element {
                 div {
                     h2 { class: "panel-title", "{chat_title}" }
                 }
+                if let Some(handler) = pop_out {
+                    button {
+                        class: "chat-pop-out-btn",
+                        title: "Open in new window",
+                        onclick: move |_| handler.call(realm_id),
+                        "⧉"
+                    }
+                }
             }
 
             // Messages and system events
@@ -232,6 +243,7 @@ fn ActiveChat(realm_id: RealmId) -> Element {
                                             is_edited: msg.is_edited,
                                             reply_preview: msg.reply_preview.clone(),
                                             reactions: msg.reactions.clone(),
+                                            image_url: msg.image_url.clone(),
                                         }
                                     }
                                 }
@@ -268,6 +280,21 @@ fn ActiveChat(realm_id: RealmId) -> Element {
                         }
                     });
                 },
+                on_paste_image: move |image: CapturedImage| {
+                    let runtime = ctx.runtime.read().clone();
+                    send_error.set(None);
+                    spawn(async move {
+                        let Some(realm) = runtime.get_realm_by_id(&realm_id) else {
+                            send_error.set(Some("Realm not found".to_string()));
+                            return;
+                        };
+                        let author = runtime.display_name()
+                            .unwrap_or_else(|| "Anonymous".to_string());
+                        if let Err(e) = share_image(&runtime, &realm, &author, image).await {
+                            send_error.set(Some(format!("Paste failed: {}", e)));
+                        }
+                    });
+                },
                 on_typing: move |_: ()| {
                     let runtime = ctx.runtime.read().clone();
                     spawn(async move {
@@ -309,6 +336,7 @@ fn build_snapshots(state: &indras_network::RealmChatDocument) -> Vec<MessageSnap
             is_edited: msg.is_edited(),
             reply_preview,
             reactions,
+            image_url: msg.image_data_url(),
         }
     }).collect()
 }
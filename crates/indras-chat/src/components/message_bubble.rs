@@ -25,6 +25,9 @@ pub fn MessageBubble(
     reply_preview: Option<(String, String)>,
     #[props(default)]
     reactions: Vec<(String, usize)>,
+    /// `data:` URL of an inline image; `content` becomes its alt text.
+    #[props(default)]
+    image_url: Option<String>,
 ) -> Element {
     let bubble_class = if is_mine { "message-bubble mine" } else { "message-bubble theirs" };
     let status_icon = match status {
@@ -52,7 +55,11 @@ pub fn MessageBubble(
             }
 
             // Message content
-            div { class: "message-content", "{content}" }
+            if let Some(url) = &image_url {
+                img { class: "message-image", src: "{url}", alt: "{content}" }
+            } else {
+                div { class: "message-content", "{content}" }
+            }
 
             // Reactions
             if !reactions.is_empty() {
@@ -2,12 +2,17 @@
 
 use dioxus::prelude::*;
 
+use crate::clipboard::{CapturedImage, read_clipboard_image};
+
 /// Message input component.
 #[component]
 pub fn MessageInput(
     on_send: EventHandler<String>,
     #[props(default)]
     on_typing: Option<EventHandler<()>>,
+    /// Called instead of a text paste when the clipboard holds an image.
+    #[props(default)]
+    on_paste_image: Option<EventHandler<CapturedImage>>,
 ) -> Element {
     let mut text = use_signal(String::new);
 
@@ -26,6 +31,20 @@ pub fn MessageInput(
                     }
                 },
                 onkeydown: move |evt: KeyboardEvent| {
+                    let mods = evt.modifiers();
+                    let paste = (mods.ctrl() || mods.meta())
+                        && matches!(evt.key(), Key::Character(ref c) if c.eq_ignore_ascii_case("v"));
+                    if paste && let Some(ref handler) = on_paste_image {
+                        match read_clipboard_image() {
+                            Ok(Some(image)) => {
+                                evt.prevent_default();
+                                handler.call(image);
+                            }
+                            Ok(None) => {}
+                            Err(e) => tracing::debug!(error = %e, "Clipboard unavailable"),
+                        }
+                        return;
+                    }
                     if evt.key() == Key::Enter && !evt.modifiers().shift() && can_send {
                         evt.prevent_default();
                         let msg = text.read().trim().to_string();
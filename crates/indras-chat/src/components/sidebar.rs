@@ -1,6 +1,7 @@
 //! Sidebar — conversation list with identity display and contact management.
 
 use dioxus::prelude::*;
+use indras_ui::{format_timestamp, tr};
 use crate::state::ChatContext;
 
 /// Sidebar component showing conversations.
@@ -13,7 +14,7 @@ pub fn Sidebar() -> Element {
 
     let display_name = runtime.display_name()
         .map(|s| s.to_string())
-        .unwrap_or_else(|| "Anonymous".to_string());
+        .unwrap_or_else(|| tr("chat-anonymous"));
     let identity_code = runtime.identity_code();
 
     rsx! {
@@ -23,27 +24,27 @@ pub fn Sidebar() -> Element {
                 div { class: "sidebar-identity-name", "{display_name}" }
                 div {
                     class: "sidebar-identity-code",
-                    title: "Click to copy",
+                    title: tr("chat-click-to-copy"),
                     "{identity_code}"
                 }
             }
 
             // Header + add contact button
             div { class: "sidebar-header",
-                div { class: "sidebar-title", "Chats" }
+                div { class: "sidebar-title", {tr("chat-sidebar-title")} }
             }
 
             button {
                 class: "add-contact-button",
                 onclick: move |_| ctx.show_add_contact.set(true),
-                "+ Add Contact"
+                {tr("chat-add-contact")}
             }
 
             // Conversation list
             div { class: "sidebar-conversations",
                 if conversations.is_empty() {
                     div { class: "sidebar-empty",
-                        "No conversations yet.\nAdd a contact to start chatting!"
+                        {tr("chat-no-conversations")}
                     }
                 } else {
                     for convo in conversations.iter() {
@@ -58,9 +59,9 @@ pub fn Sidebar() -> Element {
                             let initial = convo.display_name.chars().next()
                                 .unwrap_or('?').to_uppercase().to_string();
                             let preview = convo.last_message.clone()
-                                .unwrap_or_else(|| "No messages yet".to_string());
+                                .unwrap_or_else(|| tr("chat-no-messages"));
                             let time_str = convo.last_message_time
-                                .map(format_time)
+                                .map(format_timestamp)
                                 .unwrap_or_default();
                             let unread = convo.unread_count;
                             let name = convo.display_name.clone();
@@ -92,11 +93,3 @@ pub fn Sidebar() -> Element {
         }
     }
 }
-
-/// Format a tick timestamp into a human-readable label.
-fn format_time(tick: u64) -> String {
-    if tick == 0 {
-        return String::new();
-    }
-    format!("#{}", tick)
-}
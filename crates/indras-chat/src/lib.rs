@@ -3,6 +3,7 @@
 //! Re-exports components, state, and bridge for embedding in other apps.
 
 pub mod bridge;
+pub mod clipboard;
 pub mod components;
 pub mod state;
 
@@ -1,7 +1,8 @@
 //! Entry point for the Indras Chat desktop app.
 
-use dioxus::desktop::{Config, LogicalPosition, LogicalSize, WindowBuilder};
+use dioxus::desktop::{Config, LogicalPosition, LogicalSize, WindowBuilder, WindowCloseBehaviour};
 
+use indras_chat::bridge;
 use indras_chat::components;
 use indras_chat::CHAT_CSS;
 
@@ -10,6 +11,13 @@ fn main() {
         .with_env_filter("indras_chat=info,indras_network=info")
         .init();
 
+    // A background instance already owns the node: bring it forward instead
+    let background = indras_network::background_mode();
+    if background && bridge::show_running_instance() {
+        tracing::info!("Showing running background instance");
+        return;
+    }
+
     let name = std::env::var("INDRAS_NAME").ok();
 
     let window_title = match &name {
@@ -39,14 +47,17 @@ fn main() {
         wb = wb.with_position(LogicalPosition::new(x, y));
     }
 
+    let mut cfg = Config::new()
+        .with_window(wb)
+        .with_custom_head(format!(
+            r#"<style>{}</style>"#,
+            CHAT_CSS,
+        ));
+    if background {
+        cfg = cfg.with_close_behaviour(WindowCloseBehaviour::WindowHides);
+    }
+
     dioxus::LaunchBuilder::desktop()
-        .with_cfg(
-            Config::new()
-                .with_window(wb)
-                .with_custom_head(format!(
-                    r#"<style>{}</style>"#,
-                    CHAT_CSS,
-                )),
-        )
+        .with_cfg(cfg)
         .launch(components::app::App);
 }
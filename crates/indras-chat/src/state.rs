@@ -59,6 +59,13 @@ pub struct ChatContext {
     pub system_events: Signal<HashMap<RealmId, Vec<SystemEventSnapshot>>>,
 }
 
+/// Handler for opening a conversation in a window of its own.
+///
+/// Provided by embedders that manage several windows; the chat header
+/// shows a pop-out button when it is set.
+#[derive(Clone, Copy)]
+pub struct PopOutHandler(pub Option<EventHandler<RealmId>>);
+
 /// Summary of a conversation for the sidebar.
 #[derive(Clone, Debug)]
 pub struct ConversationSummary {
@@ -10,12 +10,14 @@ pub fn Header(state: CollaborationState) -> Element {
     rsx! {
         header { class: "header",
             div { class: "header-title",
-                h1 { "Collaboration Trio" }
-                span { class: "realm-badge", "Realm: harmony" }
+                h1 { "Collaboration" }
+                if !state.realm_name.is_empty() {
+                    span { class: "realm-badge", "Realm: {state.realm_name}" }
+                }
             }
             div { class: "phase-indicator",
-                span { class: "phase-number", "{state.phase.number()}" }
-                span { "{state.phase.name()}" }
+                span { class: "phase-number", "{state.phase.number}" }
+                span { "{state.phase.name}" }
             }
         }
     }
@@ -28,8 +30,8 @@ pub fn PeerPanel(state: CollaborationState, on_select_pov: EventHandler<Peer>) -
         aside { class: "peer-panel",
             div { class: "sidebar-section",
                 div { class: "panel-title", "Peers" }
-                for peer in Peer::all() {
-                    PeerCard { peer: *peer, state: state.clone(), on_click: on_select_pov }
+                for peer in state.peers.iter() {
+                    PeerCard { peer: peer.clone(), state: state.clone(), on_click: on_select_pov }
                 }
             }
         }
@@ -46,11 +48,12 @@ fn PeerCard(peer: Peer, state: CollaborationState, on_click: EventHandler<Peer>)
     let messages = peer_state.map(|p| p.messages_sent).unwrap_or(0);
 
     let active_class = if online { "active" } else { "" };
+    let clicked = peer.clone();
 
     rsx! {
         div {
             class: "peer-card clickable {active_class}",
-            onclick: move |_| on_click.call(peer),
+            onclick: move |_| on_click.call(clicked.clone()),
             div { class: "peer-header",
                 div { class: "peer-avatar {peer.css_class()}",
                     "{peer.initial()}"
@@ -97,24 +100,26 @@ pub fn VisualizationPanel(state: CollaborationState) -> Element {
     }
 }
 
-/// Network topology visualization (SVG triangle)
+/// Network topology visualization (SVG ring, full mesh)
 #[component]
 fn NetworkView(state: CollaborationState) -> Element {
     let width = 300.0;
     let height = 200.0;
+    let count = state.peers.len();
 
     // Convert normalized positions to SVG coordinates
     let pos = |peer: &Peer| -> (f64, f64) {
-        let (nx, ny) = peer.position();
+        let (nx, ny) = peer.position(count);
         (nx * width, ny * height)
     };
 
-    // Edge lines
-    let edges = [
-        (Peer::A, Peer::B),
-        (Peer::B, Peer::C),
-        (Peer::C, Peer::A),
-    ];
+    // Edge lines between every pair
+    let edges: Vec<(Peer, Peer)> = state
+        .peers
+        .iter()
+        .enumerate()
+        .flat_map(|(i, a)| state.peers[i + 1..].iter().map(move |b| (a.clone(), b.clone())))
+        .collect();
 
     rsx! {
         div { class: "network-view",
@@ -128,8 +133,9 @@ fn NetworkView(state: CollaborationState) -> Element {
                     {
                         let (x1, y1) = pos(from);
                         let (x2, y2) = pos(to);
-                        let is_active = state.active_edges.contains(&(*from, *to))
-                            || state.active_edges.contains(&(*to, *from));
+                        let is_active = state.active_edges.iter().any(|(a, b)| {
+                            (a == from && b == to) || (a == to && b == from)
+                        });
                         let class = if is_active { "network-edge active" } else { "network-edge" };
 
                         rsx! {
@@ -145,15 +151,11 @@ fn NetworkView(state: CollaborationState) -> Element {
                 }
 
                 // Draw peer nodes
-                for peer in Peer::all() {
+                for peer in state.peers.iter() {
                     {
                         let (cx, cy) = pos(peer);
                         let online = state.peer_states.get(peer).map(|p| p.online).unwrap_or(false);
-                        let fill = match peer {
-                            Peer::A => "var(--peer-a)",
-                            Peer::B => "var(--peer-b)",
-                            Peer::C => "var(--peer-c)",
-                        };
+                        let fill = peer.color();
                         let opacity = if online { "1" } else { "0.3" };
 
                         rsx! {
@@ -180,7 +182,7 @@ fn NetworkView(state: CollaborationState) -> Element {
                 // Draw packet animations
                 for packet in state.active_packets.iter() {
                     {
-                        let (px, py) = packet.position();
+                        let (px, py) = packet.position(count);
                         let x = px * width;
                         let y = py * height;
 
@@ -212,19 +214,19 @@ fn QuestBoard(state: CollaborationState) -> Element {
                     title: "Pending",
                     quests: state.quests_by_status(QuestStatus::Pending)
                         .into_iter().cloned().collect(),
-                    highlighted: state.highlighted_quest,
+                    highlighted: state.highlighted_quest.clone(),
                 }
                 QuestColumn {
                     title: "In Progress",
                     quests: state.quests_by_status(QuestStatus::InProgress)
                         .into_iter().cloned().collect(),
-                    highlighted: state.highlighted_quest,
+                    highlighted: state.highlighted_quest.clone(),
                 }
                 QuestColumn {
                     title: "Completed",
                     quests: state.quests_by_status(QuestStatus::Completed)
                         .into_iter().cloned().collect(),
-                    highlighted: state.highlighted_quest,
+                    highlighted: state.highlighted_quest.clone(),
                 }
             }
         }
@@ -233,7 +235,7 @@ fn QuestBoard(state: CollaborationState) -> Element {
 
 /// A single quest column
 #[component]
-fn QuestColumn(title: &'static str, quests: Vec<Quest>, highlighted: Option<u32>) -> Element {
+fn QuestColumn(title: &'static str, quests: Vec<Quest>, highlighted: Option<String>) -> Element {
     let count = quests.len();
 
     rsx! {
@@ -244,7 +246,7 @@ fn QuestColumn(title: &'static str, quests: Vec<Quest>, highlighted: Option<u32>
             }
             div { class: "quest-list",
                 for quest in quests.iter() {
-                    QuestCard { quest: quest.clone(), highlighted: highlighted == Some(quest.id) }
+                    QuestCard { quest: quest.clone(), highlighted: highlighted.as_ref() == Some(&quest.id) }
                 }
             }
         }
@@ -261,9 +263,11 @@ fn QuestCard(quest: Quest, highlighted: bool) -> Element {
             div { class: "quest-title", "{quest.title}" }
             div { class: "quest-meta",
                 span { class: "quest-creator", "by {quest.creator.display_name()}" }
-                div { class: "quest-assignee",
-                    span { class: "assignee-dot {quest.assignee.css_class()}" }
-                    span { "{quest.assignee.display_name()}" }
+                if let Some(assignee) = &quest.assignee {
+                    div { class: "quest-assignee",
+                        span { class: "assignee-dot {assignee.css_class()}" }
+                        span { "{assignee.display_name()}" }
+                    }
                 }
             }
         }
@@ -353,11 +357,15 @@ pub fn ControlBar(
     on_reset: EventHandler<()>,
     on_speed_change: EventHandler<f64>,
 ) -> Element {
-    let is_complete = state.tick >= state.max_tick;
-    let progress = (state.tick as f64 / state.max_tick as f64) * 100.0;
+    let is_complete = state.is_complete();
+    let progress = if state.total_events == 0 {
+        0.0
+    } else {
+        (state.cursor as f64 / state.total_events as f64) * 100.0
+    };
 
     // Phase indicator
-    let phase_name = state.phase.name();
+    let phase_name = if is_complete { "Complete".to_string() } else { state.phase.name.clone() };
 
     rsx! {
         div { class: "floating-controls",
@@ -459,21 +467,21 @@ pub fn POVDashboard(
     on_back: EventHandler<()>,
     on_switch_pov: EventHandler<Peer>,
 ) -> Element {
-    let stats = state.stats_for_peer(peer);
+    let stats = state.stats_for_peer(&peer);
 
     rsx! {
         div { class: "pov-dashboard {peer.css_class()} view-transition view-active",
-            POVHeader { peer: peer, on_back: on_back }
+            POVHeader { peer: peer.clone(), on_back: on_back }
             main { class: "pov-content",
                 div { class: "pov-left-column",
-                    ProfileHero { peer: peer, state: state.clone(), stats: stats }
+                    ProfileHero { peer: peer.clone(), state: state.clone(), stats: stats }
                 }
                 div { class: "pov-center-column",
-                    MyNetworkView { peer: peer, state: state.clone(), on_switch_pov: on_switch_pov }
-                    MyQuestsBoard { peer: peer, state: state.clone() }
+                    MyNetworkView { peer: peer.clone(), state: state.clone(), on_switch_pov: on_switch_pov }
+                    MyQuestsBoard { peer: peer.clone(), state: state.clone() }
                 }
                 div { class: "pov-right-column",
-                    MyContributions { peer: peer, state: state.clone() }
+                    MyContributions { peer: peer.clone(), state: state.clone() }
                     MyActivity { peer: peer, state: state }
                 }
             }
@@ -545,7 +553,7 @@ fn MyNetworkView(peer: Peer, state: CollaborationState, on_switch_pov: EventHand
     let center_y = height / 2.0;
 
     // Get other peers positioned around the center
-    let other_peers: Vec<Peer> = Peer::all().iter().filter(|p| **p != peer).copied().collect();
+    let other_peers: Vec<Peer> = state.peers.iter().filter(|p| **p != peer).cloned().collect();
 
     // Calculate positions for other peers (spread around center)
     let angle_offset = std::f64::consts::PI / 2.0; // Start from top
@@ -569,7 +577,7 @@ fn MyNetworkView(peer: Peer, state: CollaborationState, on_switch_pov: EventHand
                         let other_x = center_x + radius * angle.cos();
                         let other_y = center_y + radius * angle.sin();
                         let is_active = state.active_edges.iter().any(|(from, to)| {
-                            (*from == peer && *to == *other) || (*from == *other && *to == peer)
+                            (*from == peer && to == other) || (from == other && *to == peer)
                         });
                         let class = if is_active { "network-edge active" } else { "network-edge" };
 
@@ -588,11 +596,7 @@ fn MyNetworkView(peer: Peer, state: CollaborationState, on_switch_pov: EventHand
                 // Draw center (ego) node - larger
                 {
                     let online = state.peer_states.get(&peer).map(|p| p.online).unwrap_or(false);
-                    let fill = match peer {
-                        Peer::A => "var(--peer-a)",
-                        Peer::B => "var(--peer-b)",
-                        Peer::C => "var(--peer-c)",
-                    };
+                    let fill = peer.color();
                     let opacity = if online { "1" } else { "0.3" };
 
                     rsx! {
@@ -622,18 +626,14 @@ fn MyNetworkView(peer: Peer, state: CollaborationState, on_switch_pov: EventHand
                         let other_x = center_x + radius * angle.cos();
                         let other_y = center_y + radius * angle.sin();
                         let online = state.peer_states.get(other).map(|p| p.online).unwrap_or(false);
-                        let fill = match other {
-                            Peer::A => "var(--peer-a)",
-                            Peer::B => "var(--peer-b)",
-                            Peer::C => "var(--peer-c)",
-                        };
+                        let fill = other.color();
                         let opacity = if online { "1" } else { "0.3" };
-                        let other_peer = *other;
+                        let other_peer = other.clone();
 
                         rsx! {
                             g {
                                 class: "other-node clickable",
-                                onclick: move |_| on_switch_pov.call(other_peer),
+                                onclick: move |_| on_switch_pov.call(other_peer.clone()),
                                 circle {
                                     class: "peer-node-circle",
                                     cx: "{other_x}",
@@ -695,9 +695,9 @@ fn MyNetworkView(peer: Peer, state: CollaborationState, on_switch_pov: EventHand
 /// My quests board - filtered to quests where peer is creator or assignee
 #[component]
 fn MyQuestsBoard(peer: Peer, state: CollaborationState) -> Element {
-    let pending = state.quests_for_peer_by_status(peer, QuestStatus::Pending);
-    let in_progress = state.quests_for_peer_by_status(peer, QuestStatus::InProgress);
-    let completed = state.quests_for_peer_by_status(peer, QuestStatus::Completed);
+    let pending = state.quests_for_peer_by_status(&peer, QuestStatus::Pending);
+    let in_progress = state.quests_for_peer_by_status(&peer, QuestStatus::InProgress);
+    let completed = state.quests_for_peer_by_status(&peer, QuestStatus::Completed);
 
     rsx! {
         div { class: "my-quests-board",
@@ -708,20 +708,20 @@ fn MyQuestsBoard(peer: Peer, state: CollaborationState) -> Element {
                 MyQuestColumn {
                     title: "Pending",
                     quests: pending.into_iter().cloned().collect(),
-                    peer: peer,
-                    highlighted: state.highlighted_quest,
+                    peer: peer.clone(),
+                    highlighted: state.highlighted_quest.clone(),
                 }
                 MyQuestColumn {
                     title: "In Progress",
                     quests: in_progress.into_iter().cloned().collect(),
-                    peer: peer,
-                    highlighted: state.highlighted_quest,
+                    peer: peer.clone(),
+                    highlighted: state.highlighted_quest.clone(),
                 }
                 MyQuestColumn {
                     title: "Completed",
                     quests: completed.into_iter().cloned().collect(),
-                    peer: peer,
-                    highlighted: state.highlighted_quest,
+                    peer: peer.clone(),
+                    highlighted: state.highlighted_quest.clone(),
                 }
             }
         }
@@ -730,7 +730,7 @@ fn MyQuestsBoard(peer: Peer, state: CollaborationState) -> Element {
 
 /// Quest column for POV view with role badges
 #[component]
-fn MyQuestColumn(title: &'static str, quests: Vec<Quest>, peer: Peer, highlighted: Option<u32>) -> Element {
+fn MyQuestColumn(title: &'static str, quests: Vec<Quest>, peer: Peer, highlighted: Option<String>) -> Element {
     let count = quests.len();
 
     rsx! {
@@ -743,8 +743,8 @@ fn MyQuestColumn(title: &'static str, quests: Vec<Quest>, peer: Peer, highlighte
                 for quest in quests.iter() {
                     MyQuestCard {
                         quest: quest.clone(),
-                        peer: peer,
-                        highlighted: highlighted == Some(quest.id),
+                        peer: peer.clone(),
+                        highlighted: highlighted.as_ref() == Some(&quest.id),
                     }
                 }
             }
@@ -759,7 +759,7 @@ fn MyQuestCard(quest: Quest, peer: Peer, highlighted: bool) -> Element {
 
     // Determine role(s)
     let is_creator = quest.creator == peer;
-    let is_assignee = quest.assignee == peer;
+    let is_assignee = quest.assignee.as_ref() == Some(&peer);
 
     rsx! {
         div { class: "quest-card {highlight_class}",
@@ -792,7 +792,7 @@ fn RoleBadge(role: &'static str) -> Element {
 /// My contributions - sections authored by this peer
 #[component]
 fn MyContributions(peer: Peer, state: CollaborationState) -> Element {
-    let sections = state.sections_for_peer(peer);
+    let sections = state.sections_for_peer(&peer);
 
     rsx! {
         div { class: "my-contributions",
@@ -821,7 +821,7 @@ fn MyContributions(peer: Peer, state: CollaborationState) -> Element {
 /// My activity - events involving this peer
 #[component]
 fn MyActivity(peer: Peer, state: CollaborationState) -> Element {
-    let events = state.events_for_peer(peer);
+    let events = state.events_for_peer(&peer);
 
     rsx! {
         div { class: "my-activity",
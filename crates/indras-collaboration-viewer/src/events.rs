@@ -0,0 +1,138 @@
+// Scenario events for the Collaboration Viewer
+//
+// The subset of the realm-viewer JSONL schema that the collaboration view
+// understands. Unknown event types parse as `Unknown` and are skipped, so
+// any scenario that emits realm-viewer events can be played back here.
+
+use serde::Deserialize;
+
+/// Events read from a scenario's JSONL output
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(tag = "event_type")]
+pub enum CollabEvent {
+    #[serde(rename = "realm_created")]
+    RealmCreated {
+        #[serde(default)]
+        tick: u32,
+        realm_id: String,
+        /// Comma-separated member names
+        #[serde(default)]
+        members: String,
+    },
+
+    #[serde(rename = "realm_alias_set")]
+    RealmAliasSet {
+        #[serde(default)]
+        tick: u32,
+        member: String,
+        alias: String,
+    },
+
+    #[serde(rename = "member_joined")]
+    MemberJoined {
+        #[serde(default)]
+        tick: u32,
+        member: String,
+    },
+
+    #[serde(rename = "member_left")]
+    MemberLeft {
+        #[serde(default)]
+        tick: u32,
+        member: String,
+    },
+
+    #[serde(rename = "quest_created")]
+    QuestCreated {
+        #[serde(default)]
+        tick: u32,
+        quest_id: String,
+        creator: String,
+        #[serde(default)]
+        title: String,
+        /// Member expected to do the work, if the scenario assigns one
+        #[serde(default)]
+        assignee: Option<String>,
+    },
+
+    #[serde(rename = "quest_claim_submitted")]
+    QuestClaimSubmitted {
+        #[serde(default)]
+        tick: u32,
+        quest_id: String,
+        claimant: String,
+    },
+
+    #[serde(rename = "quest_completed")]
+    QuestCompleted {
+        #[serde(default)]
+        tick: u32,
+        quest_id: String,
+    },
+
+    #[serde(rename = "document_edit")]
+    DocumentEdit {
+        #[serde(default)]
+        tick: u32,
+        document_id: String,
+        editor: String,
+        /// Full document content after the edit
+        content: String,
+        /// Document title, shown as the project plan heading
+        #[serde(default)]
+        title: Option<String>,
+    },
+
+    #[serde(rename = "crdt_converged")]
+    CrdtConverged {
+        #[serde(default)]
+        tick: u32,
+        #[serde(default)]
+        members_synced: usize,
+    },
+
+    #[serde(rename = "crdt_conflict")]
+    CrdtConflict {
+        #[serde(default)]
+        tick: u32,
+        #[serde(default)]
+        expected_members: usize,
+        #[serde(default)]
+        actual_members: usize,
+    },
+
+    #[serde(rename = "info")]
+    Info {
+        #[serde(default)]
+        tick: u32,
+        #[serde(default)]
+        message: String,
+        /// Set on phase banners; starts a new phase named by `message`
+        #[serde(default)]
+        phase: Option<u32>,
+    },
+
+    /// Any event type the collaboration view does not show
+    #[serde(other)]
+    Unknown,
+}
+
+impl CollabEvent {
+    /// Scenario tick the event happened at (0 if the line had none)
+    pub fn tick(&self) -> u32 {
+        match self {
+            CollabEvent::RealmCreated { tick, .. }
+            | CollabEvent::RealmAliasSet { tick, .. }
+            | CollabEvent::MemberJoined { tick, .. }
+            | CollabEvent::MemberLeft { tick, .. }
+            | CollabEvent::QuestCreated { tick, .. }
+            | CollabEvent::QuestClaimSubmitted { tick, .. }
+            | CollabEvent::QuestCompleted { tick, .. }
+            | CollabEvent::DocumentEdit { tick, .. }
+            | CollabEvent::CrdtConverged { tick, .. }
+            | CollabEvent::CrdtConflict { tick, .. }
+            | CollabEvent::Info { tick, .. } => *tick,
+            CollabEvent::Unknown => 0,
+        }
+    }
+}
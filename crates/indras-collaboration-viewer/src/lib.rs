@@ -1,5 +1,6 @@
 // Collaboration Viewer Library
 
 pub mod components;
+pub mod events;
 pub mod state;
 pub mod theme;
@@ -1,35 +1,65 @@
 // Collaboration Viewer - Standalone Dioxus App
 //
-// A responsive UI that tracks through the collaboration_trio simulation
-// with realistic interfaces for quests, documents, and peer interactions.
+// A responsive UI that plays back collaboration scenarios (JSONL in the
+// realm-viewer schema, from stdin or a file) with realistic interfaces for
+// quests, documents, and peer interactions.
 
+use std::path::PathBuf;
+use std::sync::OnceLock;
 use std::time::Duration;
 
+use clap::Parser;
 use dioxus::prelude::*;
+use indras_viewer_common::stream::{start_jsonl_stream, StreamSource};
 
 mod components;
+mod events;
 mod state;
 mod theme;
 
 use components::{ControlBar, Header, PeerPanel, POVDashboard, RightPanel, VisualizationPanel};
-use state::{CollaborationState, EventType, Peer, Phase, PlanSection, Quest, QuestStatus, ScenarioData};
+use events::CollabEvent;
+use state::{CollaborationState, Peer};
 use theme::{ThemedRoot, SkinSwitcher};
 
 // Embed CSS
 const SHARED_CSS: &str = indras_ui::SHARED_CSS;
 const STYLES_CSS: &str = include_str!("../assets/styles.css");
 
+/// Global storage for parsed command line arguments
+static ARGS: OnceLock<Args> = OnceLock::new();
+
+/// Command line arguments
+#[derive(Parser, Debug)]
+#[command(name = "collaboration-viewer")]
+#[command(about = "Collaboration scenario viewer for Indras Network")]
+struct Args {
+    /// Path to a JSONL file to read events from (reads from stdin if not provided)
+    #[arg(short, long)]
+    file: Option<PathBuf>,
+
+    /// Initial playback speed (default: 2.0)
+    #[arg(short, long, default_value = "2.0")]
+    speed: f64,
+
+    /// Start playing immediately (default: start paused)
+    #[arg(long)]
+    autoplay: bool,
+}
+
 fn main() {
     // Initialize tracing
     tracing_subscriber::fmt::init();
 
+    ARGS.set(Args::parse()).ok();
+
     // Launch Dioxus desktop app
     dioxus::LaunchBuilder::desktop()
         .with_cfg(
             dioxus::desktop::Config::new()
                 .with_window(
                     dioxus::desktop::WindowBuilder::new()
-                        .with_title("Collaboration Trio - Indras Network")
+                        .with_title("Collaboration Viewer - Indras Network")
                         .with_inner_size(dioxus::desktop::LogicalSize::new(1400, 900))
                         .with_resizable(true),
                 )
@@ -42,27 +72,60 @@ fn main() {
 #[component]
 fn App() -> Element {
     // Main simulation state
-    let mut state = use_signal(CollaborationState::default);
+    let mut state = use_signal(|| {
+        let args = ARGS.get();
+        CollaborationState {
+            speed: args.map(|a| a.speed).unwrap_or(2.0),
+            paused: !args.is_some_and(|a| a.autoplay),
+            ..Default::default()
+        }
+    });
+
+    // Scenario events loaded so far, in order
+    let scenario = use_signal(Vec::<CollabEvent>::new);
+
+    // Scenario loader
+    use_future(move || {
+        let mut state = state;
+        let mut scenario = scenario;
+        async move {
+            let source = match ARGS.get().and_then(|a| a.file.clone()) {
+                Some(path) => StreamSource::file(path),
+                None => StreamSource::stdin(),
+            };
+            let mut rx = start_jsonl_stream::<CollabEvent>(source);
+            while let Some(event) = rx.recv().await {
+                // Skip events this view doesn't show so every step changes something
+                if event == CollabEvent::Unknown {
+                    continue;
+                }
+                state.write().record_loaded(&event);
+                scenario.write().push(event);
+            }
+            state.write().stream_ended = true;
+        }
+    });
 
     // Auto-play loop
-    let state_for_future = state.clone();
     use_future(move || {
-        let mut state = state_for_future.clone();
+        let mut state = state;
         async move {
             let mut accum: f64 = 0.0;
             loop {
                 tokio::time::sleep(Duration::from_millis(50)).await;
 
-                let (paused, tick, max_tick, speed) = {
+                let (paused, cursor, speed) = {
                     let current = state.read();
-                    (current.paused, current.tick, current.max_tick, current.speed)
+                    (current.paused, current.cursor, current.speed)
                 };
 
-                if !paused && tick < max_tick {
-                    accum += 0.05 * speed;
-                    if accum >= 0.5 {
-                        accum -= 0.5;
-                        step_simulation(&mut state);
+                if !paused {
+                    if cursor < scenario.read().len() {
+                        accum += 0.05 * speed;
+                        if accum >= 0.5 {
+                            accum -= 0.5;
+                            step_simulation(&mut state, scenario);
+                        }
                     }
 
                     // Update animations
@@ -74,7 +137,7 @@ fn App() -> Element {
 
     // Event handlers
     let on_step = move |_| {
-        step_simulation(&mut state);
+        step_simulation(&mut state, scenario);
     };
 
     let on_play_pause = move |_| {
@@ -109,10 +172,10 @@ fn App() -> Element {
         ThemedRoot {
             SkinSwitcher {}
             // Conditionally render Overview or POV Dashboard
-            if let Some(pov_peer) = current_state.selected_pov {
+            if let Some(pov_peer) = &current_state.selected_pov {
                 // POV Mode
                 POVDashboard {
-                    peer: pov_peer,
+                    peer: pov_peer.clone(),
                     state: current_state.clone(),
                     on_back: on_back_to_overview,
                     on_switch_pov: on_switch_pov,
@@ -141,234 +204,11 @@ fn App() -> Element {
     }
 }
 
-/// Step the simulation forward by one tick
-fn step_simulation(state: &mut Signal<CollaborationState>) {
-    let tick = {
-        let mut s = state.write();
-        s.tick += 1;
-        // Clear highlight after a tick
-        if s.highlighted_quest.is_some() {
-            s.highlighted_quest = None;
-        }
-        s.tick
+/// Apply the next loaded scenario event, if there is one
+fn step_simulation(state: &mut Signal<CollaborationState>, scenario: Signal<Vec<CollabEvent>>) {
+    let cursor = state.read().cursor;
+    let Some(event) = scenario.read().get(cursor).cloned() else {
+        return;
     };
-
-    match tick {
-        // Phase 1: Setup (ticks 1-5)
-        1 => {
-            let mut s = state.write();
-            s.phase = Phase::Setup;
-            s.add_event(EventType::Setup, "Initializing simulation...".into(), None);
-        }
-        2 => {
-            let mut s = state.write();
-            if let Some(ps) = s.peer_states.get_mut(&Peer::A) {
-                ps.online = true;
-            }
-            s.add_event(EventType::Setup, "A joined the realm".into(), Some(Peer::A));
-        }
-        3 => {
-            let mut s = state.write();
-            if let Some(ps) = s.peer_states.get_mut(&Peer::B) {
-                ps.online = true;
-            }
-            s.add_event(EventType::Setup, "B joined the realm".into(), Some(Peer::B));
-        }
-        4 => {
-            let mut s = state.write();
-            if let Some(ps) = s.peer_states.get_mut(&Peer::C) {
-                ps.online = true;
-            }
-            s.add_event(EventType::Setup, "C joined the realm".into(), Some(Peer::C));
-        }
-        5 => {
-            state.write().add_event(EventType::PhaseComplete, "All peers connected".into(), None);
-        }
-
-        // Phase 2: Quest Creation (ticks 6-17)
-        6 => {
-            let mut s = state.write();
-            s.phase = Phase::QuestCreation;
-            s.add_event(EventType::PhaseComplete, "Starting quest creation phase".into(), None);
-        }
-        7..=12 => {
-            let quest_idx = (tick - 7) as usize;
-            let quests = ScenarioData::quests();
-            if quest_idx < quests.len() {
-                let (creator, title, assignee) = quests[quest_idx];
-                let quest_id = (quest_idx + 1) as u32;
-
-                let mut s = state.write();
-                // Create quest
-                s.quests.push(Quest {
-                    id: quest_id,
-                    title: title.to_string(),
-                    creator,
-                    assignee,
-                    status: QuestStatus::Pending,
-                });
-
-                // Update peer state
-                if let Some(ps) = s.peer_states.get_mut(&creator) {
-                    ps.quests_created += 1;
-                }
-
-                s.highlighted_quest = Some(quest_id);
-                s.add_event(
-                    EventType::QuestCreated,
-                    format!("{} created quest: {}", creator.display_name(), title),
-                    Some(creator),
-                );
-
-                // Send sync packets to other peers
-                for peer in Peer::all() {
-                    if *peer != creator {
-                        s.send_packet(creator, *peer, "quest_sync");
-                        if let Some(ps) = s.peer_states.get_mut(&creator) {
-                            ps.messages_sent += 1;
-                        }
-                    }
-                }
-            }
-        }
-        13..=17 => {
-            // Sync ticks
-            if tick == 13 {
-                state.write().add_event(EventType::Sync, "Synchronizing quest data...".into(), None);
-            }
-        }
-
-        // Phase 3: Document Collaboration (ticks 18-32)
-        18 => {
-            let mut s = state.write();
-            s.phase = Phase::DocumentCollaboration;
-            s.add_event(EventType::PhaseComplete, "Starting document collaboration".into(), None);
-        }
-        20 | 24 | 28 => {
-            let section_idx = match tick {
-                20 => 0,
-                24 => 1,
-                28 => 2,
-                _ => return,
-            };
-            let sections = ScenarioData::plan_sections();
-            if section_idx < sections.len() {
-                let (author, content) = sections[section_idx];
-
-                let mut s = state.write();
-                // Add section
-                s.plan_sections.push(PlanSection {
-                    id: (section_idx + 1) as u32,
-                    author,
-                    content: content.to_string(),
-                });
-
-                // Update peer state
-                if let Some(ps) = s.peer_states.get_mut(&author) {
-                    ps.sections_written += 1;
-                }
-
-                s.add_event(
-                    EventType::DocumentSection,
-                    format!("{} added a section to the project plan", author.display_name()),
-                    Some(author),
-                );
-
-                // Send sync packets
-                for peer in Peer::all() {
-                    if *peer != author {
-                        s.send_packet(author, *peer, "doc_sync");
-                        if let Some(ps) = s.peer_states.get_mut(&author) {
-                            ps.messages_sent += 1;
-                        }
-                    }
-                }
-            }
-        }
-        21..=23 | 25..=27 | 29..=32 => {
-            // Sync ticks between sections
-        }
-
-        // Phase 4: Quest Updates (ticks 33-42)
-        33 => {
-            let mut s = state.write();
-            s.phase = Phase::QuestUpdates;
-            s.add_event(EventType::PhaseComplete, "Starting quest updates".into(), None);
-        }
-        35 => {
-            update_quest_status(state, 1, QuestStatus::InProgress, "started");
-        }
-        36 => {
-            update_quest_status(state, 3, QuestStatus::InProgress, "started");
-        }
-        37 => {
-            update_quest_status(state, 5, QuestStatus::InProgress, "started");
-        }
-        39 => {
-            update_quest_status(state, 1, QuestStatus::Completed, "completed");
-        }
-        41 => {
-            update_quest_status(state, 5, QuestStatus::Completed, "completed");
-        }
-
-        // Phase 5: Verification (ticks 43-50)
-        43 => {
-            let mut s = state.write();
-            s.phase = Phase::Verification;
-            s.add_event(EventType::PhaseComplete, "Verifying document convergence".into(), None);
-        }
-        45 => {
-            state.write().add_event(EventType::Sync, "All 6 quests synchronized".into(), None);
-        }
-        47 => {
-            state.write().add_event(EventType::Sync, "Project plan converged across all peers".into(), None);
-        }
-        50 => {
-            let mut s = state.write();
-            s.phase = Phase::Complete;
-            s.add_event(
-                EventType::PhaseComplete,
-                "Collaboration complete! 100% convergence achieved.".into(),
-                None,
-            );
-        }
-
-        _ => {}
-    }
-}
-
-/// Helper function to update quest status and log event
-fn update_quest_status(state: &mut Signal<CollaborationState>, quest_id: u32, new_status: QuestStatus, action: &str) {
-    // First, get quest info
-    let quest_info = {
-        let s = state.read();
-        s.quests.iter().find(|q| q.id == quest_id).map(|q| {
-            (q.title.clone(), q.assignee)
-        })
-    };
-
-    if let Some((title, assignee)) = quest_info {
-        let mut s = state.write();
-
-        // Update quest status
-        if let Some(quest) = s.quests.iter_mut().find(|q| q.id == quest_id) {
-            quest.status = new_status;
-        }
-
-        s.highlighted_quest = Some(quest_id);
-        s.add_event(
-            EventType::QuestUpdated,
-            format!("{} {} quest: {}", assignee.display_name(), action, title),
-            Some(assignee),
-        );
-
-        // Send sync packets for completed quests
-        if new_status == QuestStatus::Completed {
-            for peer in Peer::all() {
-                if *peer != assignee {
-                    s.send_packet(assignee, *peer, "status_sync");
-                }
-            }
-        }
-    }
+    state.write().apply(&event);
 }
@@ -1,60 +1,73 @@
 // State management for Collaboration Viewer
 
+use std::collections::HashMap;
+use std::f64::consts::{FRAC_PI_2, TAU};
+
 use serde::{Deserialize, Serialize};
 
-/// The three peers in the collaboration scenario
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub enum Peer {
-    A,
-    B,
-    C,
+use crate::events::CollabEvent;
+
+/// CSS class suffixes for peer colours, assigned round-robin by join order
+const PEER_CLASSES: [&str; 3] = ["a", "b", "c"];
+
+/// A scenario member
+///
+/// `slot` is the member's join order; it picks the member's colour and its
+/// place on the network ring.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Peer {
+    pub name: String,
+    pub slot: usize,
 }
 
 impl Peer {
-    pub fn all() -> &'static [Peer] {
-        &[Peer::A, Peer::B, Peer::C]
-    }
-
-    pub fn name(&self) -> &'static str {
-        match self {
-            Peer::A => "a",
-            Peer::B => "b",
-            Peer::C => "c",
+    pub fn new(name: impl Into<String>, slot: usize) -> Self {
+        Self {
+            name: name.into(),
+            slot,
         }
     }
 
-    pub fn display_name(&self) -> &'static str {
-        match self {
-            Peer::A => "A",
-            Peer::B => "B",
-            Peer::C => "C",
+    /// Short names are capitalised ("a" -> "A"); long ids are shortened
+    pub fn display_name(&self) -> String {
+        if self.name.chars().count() > 12 {
+            let short: String = self.name.chars().take(8).collect();
+            return format!("{short}…");
+        }
+        let mut chars = self.name.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => "?".to_string(),
         }
     }
 
     pub fn initial(&self) -> char {
-        match self {
-            Peer::A => 'A',
-            Peer::B => 'B',
-            Peer::C => 'C',
-        }
+        self.name
+            .chars()
+            .next()
+            .map(|c| c.to_ascii_uppercase())
+            .unwrap_or('?')
     }
 
     pub fn css_class(&self) -> &'static str {
-        match self {
-            Peer::A => "a",
-            Peer::B => "b",
-            Peer::C => "c",
-        }
+        PEER_CLASSES[self.slot % PEER_CLASSES.len()]
     }
 
-    /// Position in the triangle visualization (normalized 0-1 coordinates)
-    pub fn position(&self) -> (f64, f64) {
-        match self {
-            Peer::A => (0.5, 0.15),   // Top center
-            Peer::B => (0.15, 0.85),   // Bottom left
-            Peer::C => (0.85, 0.85), // Bottom right
+    /// CSS colour for SVG fills
+    pub fn color(&self) -> &'static str {
+        match self.css_class() {
+            "a" => "var(--peer-a)",
+            "b" => "var(--peer-b)",
+            _ => "var(--peer-c)",
         }
     }
+
+    /// Position on a ring of `count` peers (normalized 0-1 coordinates),
+    /// first peer at the top, then counter-clockwise
+    pub fn position(&self, count: usize) -> (f64, f64) {
+        let angle = -FRAC_PI_2 - (self.slot as f64) * TAU / (count.max(1) as f64);
+        (0.5 + 0.35 * angle.cos(), 0.5 + 0.35 * angle.sin())
+    }
 }
 
 /// Quest status
@@ -78,10 +91,11 @@ impl QuestStatus {
 /// A quest in the quest log
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Quest {
-    pub id: u32,
+    pub id: String,
     pub title: String,
     pub creator: Peer,
-    pub assignee: Peer,
+    /// Set by the scenario, or by the first claim
+    pub assignee: Option<Peer>,
     pub status: QuestStatus,
 }
 
@@ -145,45 +159,41 @@ pub struct PacketAnimation {
 }
 
 impl PacketAnimation {
-    pub fn position(&self) -> (f64, f64) {
-        let (x1, y1) = self.from.position();
-        let (x2, y2) = self.to.position();
+    /// Current position on a ring of `count` peers
+    pub fn position(&self, count: usize) -> (f64, f64) {
+        let (x1, y1) = self.from.position(count);
+        let (x2, y2) = self.to.position(count);
         let t = self.progress;
         (x1 + (x2 - x1) * t, y1 + (y2 - y1) * t)
     }
 }
 
-/// Simulation phase
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub enum Phase {
-    Setup,
-    QuestCreation,
-    DocumentCollaboration,
-    QuestUpdates,
-    Verification,
-    Complete,
+/// Scenario phase, as announced by `info` events carrying a `phase` number
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Phase {
+    pub number: u32,
+    pub name: String,
 }
 
-impl Phase {
-    pub fn number(&self) -> u32 {
-        match self {
-            Phase::Setup => 1,
-            Phase::QuestCreation => 2,
-            Phase::DocumentCollaboration => 3,
-            Phase::QuestUpdates => 4,
-            Phase::Verification => 5,
-            Phase::Complete => 5,
+impl Default for Phase {
+    fn default() -> Self {
+        Self {
+            number: 0,
+            name: "Waiting".to_string(),
         }
     }
+}
 
-    pub fn name(&self) -> &'static str {
-        match self {
-            Phase::Setup => "Setup",
-            Phase::QuestCreation => "Quest Creation",
-            Phase::DocumentCollaboration => "Document Collaboration",
-            Phase::QuestUpdates => "Quest Updates",
-            Phase::Verification => "Verification",
-            Phase::Complete => "Complete",
+impl Phase {
+    /// Phase named by a banner like "Phase 2: Quest creation"
+    fn from_banner(number: u32, message: &str) -> Self {
+        let name = match message.split_once(':') {
+            Some((head, rest)) if head.trim_start().starts_with("Phase") => rest,
+            _ => message,
+        };
+        Self {
+            number,
+            name: name.trim().to_string(),
         }
     }
 }
@@ -211,13 +221,22 @@ pub struct PeerStats {
 #[derive(Clone, Debug, PartialEq)]
 pub struct CollaborationState {
     pub tick: u32,
+    /// Last tick among the loaded events
     pub max_tick: u32,
+    /// Number of loaded events applied so far
+    pub cursor: usize,
+    /// Number of events loaded from the scenario
+    pub total_events: usize,
+    /// Whether the scenario stream has been read to the end
+    pub stream_ended: bool,
     pub phase: Phase,
     pub paused: bool,
     pub speed: f64, // Ticks per second
 
-    // Peer states
-    pub peer_states: std::collections::HashMap<Peer, PeerState>,
+    // Peers, in join order
+    pub realm_name: String,
+    pub peers: Vec<Peer>,
+    pub peer_states: HashMap<Peer, PeerState>,
 
     // Data
     pub quests: Vec<Quest>,
@@ -225,10 +244,13 @@ pub struct CollaborationState {
     pub plan_sections: Vec<PlanSection>,
     pub events: Vec<SimEvent>,
 
+    /// Latest content of each edited document, to find what an edit added
+    documents: HashMap<String, String>,
+
     // Animations
     pub active_packets: Vec<PacketAnimation>,
     pub active_edges: Vec<(Peer, Peer)>,
-    pub highlighted_quest: Option<u32>,
+    pub highlighted_quest: Option<String>,
 
     // POV mode
     pub selected_pov: Option<Peer>,
@@ -236,28 +258,23 @@ pub struct CollaborationState {
 
 impl Default for CollaborationState {
     fn default() -> Self {
-        let mut peer_states = std::collections::HashMap::new();
-        for peer in Peer::all() {
-            peer_states.insert(
-                *peer,
-                PeerState {
-                    online: false,
-                    ..Default::default()
-                },
-            );
-        }
-
         Self {
             tick: 0,
-            max_tick: 50,
-            phase: Phase::Setup,
+            max_tick: 0,
+            cursor: 0,
+            total_events: 0,
+            stream_ended: false,
+            phase: Phase::default(),
             paused: true,
             speed: 2.0,
-            peer_states,
+            realm_name: String::new(),
+            peers: Vec::new(),
+            peer_states: HashMap::new(),
             quests: Vec::new(),
-            plan_title: "Harmony Initiative".to_string(),
+            plan_title: "Project Plan".to_string(),
             plan_sections: Vec::new(),
             events: Vec::new(),
+            documents: HashMap::new(),
             active_packets: Vec::new(),
             active_edges: Vec::new(),
             highlighted_quest: None,
@@ -276,51 +293,58 @@ impl CollaborationState {
     }
 
     /// Filter quests by peer involvement (creator OR assignee)
-    pub fn quests_for_peer(&self, peer: Peer) -> Vec<&Quest> {
+    pub fn quests_for_peer(&self, peer: &Peer) -> Vec<&Quest> {
         self.quests
             .iter()
-            .filter(|q| q.creator == peer || q.assignee == peer)
+            .filter(|q| q.creator == *peer || q.assignee.as_ref() == Some(peer))
             .collect()
     }
 
     /// Filter quests for peer by status
-    pub fn quests_for_peer_by_status(&self, peer: Peer, status: QuestStatus) -> Vec<&Quest> {
-        self.quests
-            .iter()
-            .filter(|q| (q.creator == peer || q.assignee == peer) && q.status == status)
+    pub fn quests_for_peer_by_status(&self, peer: &Peer, status: QuestStatus) -> Vec<&Quest> {
+        self.quests_for_peer(peer)
+            .into_iter()
+            .filter(|q| q.status == status)
             .collect()
     }
 
     /// Filter sections by author
-    pub fn sections_for_peer(&self, peer: Peer) -> Vec<&PlanSection> {
+    pub fn sections_for_peer(&self, peer: &Peer) -> Vec<&PlanSection> {
         self.plan_sections
             .iter()
-            .filter(|s| s.author == peer)
+            .filter(|s| s.author == *peer)
             .collect()
     }
 
     /// Filter events involving peer
-    pub fn events_for_peer(&self, peer: Peer) -> Vec<&SimEvent> {
+    pub fn events_for_peer(&self, peer: &Peer) -> Vec<&SimEvent> {
         self.events
             .iter()
-            .filter(|e| e.peer == Some(peer))
+            .filter(|e| e.peer.as_ref() == Some(peer))
             .collect()
     }
 
     /// Stats for a specific peer
-    pub fn stats_for_peer(&self, peer: Peer) -> PeerStats {
+    pub fn stats_for_peer(&self, peer: &Peer) -> PeerStats {
         let quests = self.quests_for_peer(peer);
+        let assigned = |q: &&&Quest| q.assignee.as_ref() == Some(peer);
         PeerStats {
-            quests_created: quests.iter().filter(|q| q.creator == peer).count(),
-            quests_assigned: quests.iter().filter(|q| q.assignee == peer).count(),
+            quests_created: quests.iter().filter(|q| q.creator == *peer).count(),
+            quests_assigned: quests.iter().filter(assigned).count(),
             quests_completed: quests
                 .iter()
-                .filter(|q| q.assignee == peer && q.status == QuestStatus::Completed)
+                .filter(assigned)
+                .filter(|q| q.status == QuestStatus::Completed)
                 .count(),
             sections_written: self.sections_for_peer(peer).len(),
         }
     }
 
+    /// Whether every event of a fully-read scenario has been applied
+    pub fn is_complete(&self) -> bool {
+        self.stream_ended && self.cursor >= self.total_events
+    }
+
     /// Add an event
     pub fn add_event(&mut self, event_type: EventType, message: String, peer: Option<Peer>) {
         self.events.push(SimEvent {
@@ -338,8 +362,8 @@ impl CollaborationState {
     /// Create a packet animation
     pub fn send_packet(&mut self, from: Peer, to: Peer, message_type: &str) {
         self.active_packets.push(PacketAnimation {
-            from,
-            to,
+            from: from.clone(),
+            to: to.clone(),
             progress: 0.0,
             message_type: message_type.to_string(),
         });
@@ -362,43 +386,225 @@ impl CollaborationState {
         }
     }
 
-    /// Reset to initial state
+    /// Note an event loaded from the scenario (not yet applied)
+    pub fn record_loaded(&mut self, event: &CollabEvent) {
+        self.total_events += 1;
+        self.max_tick = self.max_tick.max(event.tick());
+    }
+
+    /// Reset to initial state, keeping what has been loaded and the speed
     pub fn reset(&mut self) {
-        *self = Self::default();
+        *self = Self {
+            max_tick: self.max_tick,
+            total_events: self.total_events,
+            stream_ended: self.stream_ended,
+            speed: self.speed,
+            ..Self::default()
+        };
     }
-}
 
-/// Predefined scenario data
-pub struct ScenarioData;
-
-impl ScenarioData {
-    /// All quests to be created
-    pub fn quests() -> Vec<(Peer, &'static str, Peer)> {
-        vec![
-            (Peer::A, "Spread kindness in the community", Peer::B),
-            (Peer::A, "Write a gratitude journal", Peer::A),
-            (Peer::B, "Organize a celebration event", Peer::C),
-            (Peer::B, "Create a playlist of uplifting songs", Peer::A),
-            (Peer::C, "Meditate for inner calm", Peer::C),
-            (Peer::C, "Resolve a conflict with compassion", Peer::B),
-        ]
+    /// Get the peer named `name`, adding it if this is its first mention
+    fn peer(&mut self, name: &str) -> Peer {
+        if let Some(peer) = self.peers.iter().find(|p| p.name == name) {
+            return peer.clone();
+        }
+        let peer = Peer::new(name, self.peers.len());
+        self.peers.push(peer.clone());
+        self.peer_states.insert(peer.clone(), PeerState::default());
+        peer
     }
 
-    /// Project plan sections
-    pub fn plan_sections() -> Vec<(Peer, &'static str)> {
-        vec![
-            (
-                Peer::A,
-                "Our mission is to create a world where compassion guides every action. Through acts of kindness, we build bridges between hearts.",
-            ),
-            (
-                Peer::B,
-                "Celebration is our tool for transformation. When we find joy in small moments, we amplify positivity for all.",
-            ),
-            (
-                Peer::C,
-                "Inner calm creates outer harmony. Through mindfulness and understanding, conflicts dissolve into cooperation.",
-            ),
-        ]
+    fn peer_state_mut(&mut self, peer: &Peer) -> &mut PeerState {
+        self.peer_states.entry(peer.clone()).or_default()
+    }
+
+    /// Send sync packets from `from` to every other peer
+    fn broadcast(&mut self, from: &Peer, message_type: &str) {
+        let others: Vec<Peer> = self.peers.iter().filter(|p| *p != from).cloned().collect();
+        for to in others {
+            self.send_packet(from.clone(), to, message_type);
+            self.peer_state_mut(from).messages_sent += 1;
+        }
+    }
+
+    /// Apply the next scenario event
+    pub fn apply(&mut self, event: &CollabEvent) {
+        self.cursor += 1;
+        self.tick = self.tick.max(event.tick());
+        // Clear highlight after a step
+        self.highlighted_quest = None;
+
+        match event {
+            CollabEvent::RealmCreated {
+                realm_id, members, ..
+            } => {
+                self.realm_name = realm_id.chars().take(8).collect();
+                let members: Vec<&str> = members.split(',').filter(|m| !m.is_empty()).collect();
+                for member in &members {
+                    self.peer(member);
+                }
+                self.add_event(
+                    EventType::Setup,
+                    format!("Realm created with {} members", members.len()),
+                    None,
+                );
+            }
+            CollabEvent::RealmAliasSet { member, alias, .. } => {
+                let peer = self.peer(member);
+                self.realm_name = alias.clone();
+                self.add_event(
+                    EventType::Setup,
+                    format!("{} named the realm {}", peer.display_name(), alias),
+                    Some(peer),
+                );
+            }
+            CollabEvent::MemberJoined { member, .. } => {
+                let peer = self.peer(member);
+                self.peer_state_mut(&peer).online = true;
+                self.add_event(
+                    EventType::Setup,
+                    format!("{} joined the realm", peer.display_name()),
+                    Some(peer),
+                );
+            }
+            CollabEvent::MemberLeft { member, .. } => {
+                let peer = self.peer(member);
+                self.peer_state_mut(&peer).online = false;
+                self.add_event(
+                    EventType::Setup,
+                    format!("{} left the realm", peer.display_name()),
+                    Some(peer),
+                );
+            }
+            CollabEvent::QuestCreated {
+                quest_id,
+                creator,
+                title,
+                assignee,
+                ..
+            } => {
+                let creator = self.peer(creator);
+                let assignee = assignee.as_deref().map(|name| self.peer(name));
+                self.quests.push(Quest {
+                    id: quest_id.clone(),
+                    title: title.clone(),
+                    creator: creator.clone(),
+                    assignee: assignee.clone(),
+                    status: QuestStatus::Pending,
+                });
+                self.peer_state_mut(&creator).quests_created += 1;
+                if let Some(assignee) = &assignee {
+                    self.peer_state_mut(assignee).quests_assigned += 1;
+                }
+                self.highlighted_quest = Some(quest_id.clone());
+                self.add_event(
+                    EventType::QuestCreated,
+                    format!("{} created quest: {}", creator.display_name(), title),
+                    Some(creator.clone()),
+                );
+                self.broadcast(&creator, "quest_sync");
+            }
+            CollabEvent::QuestClaimSubmitted {
+                quest_id, claimant, ..
+            } => {
+                let claimant = self.peer(claimant);
+                let Some(quest) = self.quests.iter_mut().find(|q| q.id == *quest_id) else {
+                    return;
+                };
+                quest.status = QuestStatus::InProgress;
+                quest.assignee.get_or_insert_with(|| claimant.clone());
+                let title = quest.title.clone();
+                self.highlighted_quest = Some(quest_id.clone());
+                self.add_event(
+                    EventType::QuestUpdated,
+                    format!("{} started quest: {}", claimant.display_name(), title),
+                    Some(claimant),
+                );
+            }
+            CollabEvent::QuestCompleted { quest_id, .. } => {
+                let Some(quest) = self.quests.iter_mut().find(|q| q.id == *quest_id) else {
+                    return;
+                };
+                quest.status = QuestStatus::Completed;
+                let actor = quest
+                    .assignee
+                    .clone()
+                    .unwrap_or_else(|| quest.creator.clone());
+                let title = quest.title.clone();
+                self.highlighted_quest = Some(quest_id.clone());
+                self.add_event(
+                    EventType::QuestUpdated,
+                    format!("{} completed quest: {}", actor.display_name(), title),
+                    Some(actor.clone()),
+                );
+                self.broadcast(&actor, "status_sync");
+            }
+            CollabEvent::DocumentEdit {
+                document_id,
+                editor,
+                content,
+                title,
+                ..
+            } => {
+                let author = self.peer(editor);
+                if let Some(title) = title {
+                    self.plan_title = title.clone();
+                }
+                // Edits carry the whole document; the section is what this edit appended
+                let previous = self.documents.insert(document_id.clone(), content.clone());
+                let added = previous
+                    .as_deref()
+                    .and_then(|prev| content.strip_prefix(prev))
+                    .unwrap_or(content)
+                    .trim();
+                if added.is_empty() {
+                    return;
+                }
+                self.plan_sections.push(PlanSection {
+                    id: self.plan_sections.len() as u32 + 1,
+                    author: author.clone(),
+                    content: added.to_string(),
+                });
+                self.peer_state_mut(&author).sections_written += 1;
+                self.add_event(
+                    EventType::DocumentSection,
+                    format!(
+                        "{} added a section to the project plan",
+                        author.display_name()
+                    ),
+                    Some(author.clone()),
+                );
+                self.broadcast(&author, "doc_sync");
+            }
+            CollabEvent::CrdtConverged { members_synced, .. } => {
+                self.add_event(
+                    EventType::Sync,
+                    format!("Document converged across {members_synced} peers"),
+                    None,
+                );
+            }
+            CollabEvent::CrdtConflict {
+                expected_members,
+                actual_members,
+                ..
+            } => {
+                self.add_event(
+                    EventType::Sync,
+                    format!(
+                        "Document conflict: {actual_members} of {expected_members} peers agree"
+                    ),
+                    None,
+                );
+            }
+            CollabEvent::Info {
+                message,
+                phase: Some(number),
+                ..
+            } => {
+                self.phase = Phase::from_banner(*number, message);
+                self.add_event(EventType::PhaseComplete, message.clone(), None);
+            }
+            CollabEvent::Info { .. } | CollabEvent::Unknown => {}
+        }
     }
 }
@@ -2,6 +2,8 @@
 
 use thiserror::Error;
 
+use crate::error_code::{ErrorCode, ErrorInfo};
+
 /// Top-level error type for Indras Network
 #[derive(Debug, Error)]
 pub enum IndrasError {
@@ -166,6 +168,136 @@ pub enum InterfaceError {
     NoKey,
 }
 
+impl ErrorInfo for IndrasError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            IndrasError::Identity(e) => e.code(),
+            IndrasError::Routing(e) => e.code(),
+            IndrasError::Storage(e) => e.code(),
+            IndrasError::Crypto(e) => e.code(),
+            IndrasError::Transport(e) => e.code(),
+            IndrasError::Protocol(e) => e.code(),
+            IndrasError::Interface(e) => e.code(),
+        }
+    }
+
+    fn peer(&self) -> Option<&str> {
+        match self {
+            IndrasError::Transport(e) => e.peer(),
+            _ => None,
+        }
+    }
+
+    fn interface(&self) -> Option<&str> {
+        match self {
+            IndrasError::Interface(e) => e.interface(),
+            _ => None,
+        }
+    }
+}
+
+impl ErrorInfo for IdentityError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            IdentityError::NotFound(_) => ErrorCode::NotFound,
+            IdentityError::InvalidFormat(_) | IdentityError::InvalidKeyLength { .. } => {
+                ErrorCode::InvalidIdentity
+            }
+        }
+    }
+}
+
+impl ErrorInfo for RoutingError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            RoutingError::NoRoute | RoutingError::DestinationNotFound => ErrorCode::NoRoute,
+            RoutingError::TtlExpired | RoutingError::AlreadyVisited => ErrorCode::TtlExpired,
+            RoutingError::Timeout(_) => ErrorCode::Timeout,
+        }
+    }
+}
+
+impl ErrorInfo for StorageError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            StorageError::Io(_) => ErrorCode::StorageIo,
+            StorageError::PacketNotFound(_) => ErrorCode::NotFound,
+            StorageError::CapacityExceeded => ErrorCode::CapacityExceeded,
+            StorageError::Serialization(_) => ErrorCode::Serialization,
+            StorageError::Deserialization(_) => ErrorCode::CorruptData,
+        }
+    }
+}
+
+impl ErrorInfo for CryptoError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            CryptoError::InvalidKey(_) => ErrorCode::InvalidKey,
+            CryptoError::SignatureVerificationFailed => ErrorCode::SignatureInvalid,
+            CryptoError::EncryptionFailed(_)
+            | CryptoError::DecryptionFailed(_)
+            | CryptoError::KeyGenerationFailed(_) => ErrorCode::CryptoFailed,
+        }
+    }
+}
+
+impl ErrorInfo for TransportError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            TransportError::ConnectionFailed(_) => ErrorCode::ConnectionFailed,
+            TransportError::ConnectionClosed => ErrorCode::ConnectionClosed,
+            TransportError::SendFailed(_) | TransportError::ReceiveFailed(_) => {
+                ErrorCode::ConnectionFailed
+            }
+            TransportError::PeerNotConnected(_) => ErrorCode::PeerUnreachable,
+            TransportError::AddressResolutionFailed(_) => ErrorCode::InvalidAddress,
+        }
+    }
+
+    fn peer(&self) -> Option<&str> {
+        match self {
+            TransportError::PeerNotConnected(peer) => Some(peer),
+            _ => None,
+        }
+    }
+}
+
+impl ErrorInfo for ProtocolError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            ProtocolError::VersionMismatch { .. } => ErrorCode::VersionMismatch,
+            ProtocolError::HandshakeFailed(_) => ErrorCode::ConnectionFailed,
+            ProtocolError::InvalidMessageFormat(_) | ProtocolError::UnknownMessageType(_) => {
+                ErrorCode::ProtocolViolation
+            }
+        }
+    }
+}
+
+impl ErrorInfo for InterfaceError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            InterfaceError::NotMember => ErrorCode::NotMember,
+            InterfaceError::NotFound(_) => ErrorCode::InterfaceNotFound,
+            InterfaceError::AppendFailed(_) | InterfaceError::DocumentError(_) => {
+                ErrorCode::DocumentLoad
+            }
+            InterfaceError::SyncFailed(_) => ErrorCode::SyncFailed,
+            InterfaceError::InvalidEvent(_) => ErrorCode::InvalidEvent,
+            InterfaceError::MemberExists => ErrorCode::AlreadyExists,
+            InterfaceError::MemberNotFound => ErrorCode::NotFound,
+            InterfaceError::NoKey => ErrorCode::MissingKey,
+        }
+    }
+
+    fn interface(&self) -> Option<&str> {
+        match self {
+            InterfaceError::NotFound(id) => Some(id),
+            _ => None,
+        }
+    }
+}
+
 /// Result type alias for Indras operations
 pub type IndrasResult<T> = Result<T, IndrasError>;
 
@@ -309,4 +441,18 @@ mod tests {
         assert!(msg.contains("Identity error"));
         assert!(msg.contains("test"));
     }
+
+    #[test]
+    fn test_error_info() {
+        let err: IndrasError = TransportError::PeerNotConnected("peer7".to_string()).into();
+        assert_eq!(err.code(), ErrorCode::PeerUnreachable);
+        assert!(err.is_retryable());
+        assert_eq!(err.peer(), Some("peer7"));
+
+        let err: IndrasError = InterfaceError::NotFound("00ab".to_string()).into();
+        assert_eq!(err.code(), ErrorCode::InterfaceNotFound);
+        assert!(!err.is_retryable());
+        assert_eq!(err.interface(), Some("00ab"));
+        assert_eq!(err.detail().interface.as_deref(), Some("00ab"));
+    }
 }
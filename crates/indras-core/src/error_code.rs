@@ -0,0 +1,474 @@
+//! Stable error codes and structured error metadata
+//!
+//! Error types across the stack (transport, storage, sync, node, network)
+//! implement [`ErrorInfo`], which exposes:
+//!
+//! - an [`ErrorCode`] whose number and name never change between versions,
+//!   so callers (and peers, logs, dashboards) can match on it,
+//! - a [`Retryability`] derived from the code, and
+//! - the peer and interface involved, when the failure concerns one.
+//!
+//! When an error crosses a crate boundary that would otherwise flatten it
+//! into a string, it is carried as an [`ErrorDetail`], which keeps all of
+//! the above along with the message.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Whether an operation that failed may succeed if attempted again
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Retryability {
+    /// Retry after a short backoff (dropped connection, timeout)
+    Transient,
+    /// Retry once something changes (node started, peer online, key arrives)
+    Deferred,
+    /// Retrying will fail the same way
+    Permanent,
+}
+
+impl Retryability {
+    /// Whether retrying can help at all
+    pub const fn is_retryable(self) -> bool {
+        !matches!(self, Retryability::Permanent)
+    }
+}
+
+/// Stable identifier for an error condition
+///
+/// Numbers are grouped by layer: 1xxx transport and routing, 2xxx storage,
+/// 3xxx sync, 4xxx crypto and identity, 5xxx node lifecycle, 6xxx realm
+/// and application rules, 7xxx external services, 9xxx other. Codes are
+/// only ever added, never renumbered or reused.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ErrorCode {
+    // Transport and routing
+    ConnectionFailed,
+    ConnectionClosed,
+    PeerUnreachable,
+    DiscoveryFailed,
+    ProtocolViolation,
+    TransportNotRunning,
+    TooManyConnections,
+    AccessDenied,
+    InvalidAddress,
+    BindFailed,
+    NoRoute,
+    TtlExpired,
+    MessageTooLarge,
+
+    // Storage
+    StorageIo,
+    NotFound,
+    CapacityExceeded,
+    DatabaseLocked,
+    CorruptData,
+    Database,
+
+    // Sync
+    SyncFailed,
+    DocumentLoad,
+    InterfaceNotFound,
+    NotMember,
+    PeerNotFound,
+    InvalidEvent,
+
+    // Crypto and identity
+    CryptoFailed,
+    InvalidKey,
+    SignatureInvalid,
+    MissingKey,
+    InvalidIdentity,
+    AuthFailed,
+
+    // Node lifecycle
+    NotStarted,
+    AlreadyStarted,
+    ShutDown,
+    Config,
+    Channel,
+    Timeout,
+    Serialization,
+    Io,
+    VersionMismatch,
+
+    // Realm and application rules
+    InvalidInput,
+    PermissionDenied,
+    Archived,
+    Sandboxed,
+    Full,
+    Expired,
+    AlreadyExists,
+    InvalidOperation,
+    Removed,
+
+    // External services
+    External,
+
+    // Other
+    Internal,
+    Unknown,
+}
+
+impl ErrorCode {
+    /// Every code, in numeric order
+    pub const ALL: &'static [ErrorCode] = &[
+        ErrorCode::ConnectionFailed,
+        ErrorCode::ConnectionClosed,
+        ErrorCode::PeerUnreachable,
+        ErrorCode::DiscoveryFailed,
+        ErrorCode::ProtocolViolation,
+        ErrorCode::TransportNotRunning,
+        ErrorCode::TooManyConnections,
+        ErrorCode::AccessDenied,
+        ErrorCode::InvalidAddress,
+        ErrorCode::BindFailed,
+        ErrorCode::NoRoute,
+        ErrorCode::TtlExpired,
+        ErrorCode::MessageTooLarge,
+        ErrorCode::StorageIo,
+        ErrorCode::NotFound,
+        ErrorCode::CapacityExceeded,
+        ErrorCode::DatabaseLocked,
+        ErrorCode::CorruptData,
+        ErrorCode::Database,
+        ErrorCode::SyncFailed,
+        ErrorCode::DocumentLoad,
+        ErrorCode::InterfaceNotFound,
+        ErrorCode::NotMember,
+        ErrorCode::PeerNotFound,
+        ErrorCode::InvalidEvent,
+        ErrorCode::CryptoFailed,
+        ErrorCode::InvalidKey,
+        ErrorCode::SignatureInvalid,
+        ErrorCode::MissingKey,
+        ErrorCode::InvalidIdentity,
+        ErrorCode::AuthFailed,
+        ErrorCode::NotStarted,
+        ErrorCode::AlreadyStarted,
+        ErrorCode::ShutDown,
+        ErrorCode::Config,
+        ErrorCode::Channel,
+        ErrorCode::Timeout,
+        ErrorCode::Serialization,
+        ErrorCode::Io,
+        ErrorCode::VersionMismatch,
+        ErrorCode::InvalidInput,
+        ErrorCode::PermissionDenied,
+        ErrorCode::Archived,
+        ErrorCode::Sandboxed,
+        ErrorCode::Full,
+        ErrorCode::Expired,
+        ErrorCode::AlreadyExists,
+        ErrorCode::InvalidOperation,
+        ErrorCode::Removed,
+        ErrorCode::External,
+        ErrorCode::Internal,
+        ErrorCode::Unknown,
+    ];
+
+    /// Numeric code, stable across versions
+    pub const fn as_u16(self) -> u16 {
+        match self {
+            ErrorCode::ConnectionFailed => 1001,
+            ErrorCode::ConnectionClosed => 1002,
+            ErrorCode::PeerUnreachable => 1003,
+            ErrorCode::DiscoveryFailed => 1004,
+            ErrorCode::ProtocolViolation => 1005,
+            ErrorCode::TransportNotRunning => 1006,
+            ErrorCode::TooManyConnections => 1007,
+            ErrorCode::AccessDenied => 1008,
+            ErrorCode::InvalidAddress => 1009,
+            ErrorCode::BindFailed => 1010,
+            ErrorCode::NoRoute => 1101,
+            ErrorCode::TtlExpired => 1102,
+            ErrorCode::MessageTooLarge => 1103,
+            ErrorCode::StorageIo => 2001,
+            ErrorCode::NotFound => 2002,
+            ErrorCode::CapacityExceeded => 2003,
+            ErrorCode::DatabaseLocked => 2004,
+            ErrorCode::CorruptData => 2005,
+            ErrorCode::Database => 2006,
+            ErrorCode::SyncFailed => 3001,
+            ErrorCode::DocumentLoad => 3002,
+            ErrorCode::InterfaceNotFound => 3003,
+            ErrorCode::NotMember => 3004,
+            ErrorCode::PeerNotFound => 3005,
+            ErrorCode::InvalidEvent => 3006,
+            ErrorCode::CryptoFailed => 4001,
+            ErrorCode::InvalidKey => 4002,
+            ErrorCode::SignatureInvalid => 4003,
+            ErrorCode::MissingKey => 4004,
+            ErrorCode::InvalidIdentity => 4005,
+            ErrorCode::AuthFailed => 4006,
+            ErrorCode::NotStarted => 5001,
+            ErrorCode::AlreadyStarted => 5002,
+            ErrorCode::ShutDown => 5003,
+            ErrorCode::Config => 5004,
+            ErrorCode::Channel => 5005,
+            ErrorCode::Timeout => 5006,
+            ErrorCode::Serialization => 5007,
+            ErrorCode::Io => 5008,
+            ErrorCode::VersionMismatch => 5009,
+            ErrorCode::InvalidInput => 6001,
+            ErrorCode::PermissionDenied => 6002,
+            ErrorCode::Archived => 6003,
+            ErrorCode::Sandboxed => 6004,
+            ErrorCode::Full => 6005,
+            ErrorCode::Expired => 6006,
+            ErrorCode::AlreadyExists => 6007,
+            ErrorCode::InvalidOperation => 6008,
+            ErrorCode::Removed => 6009,
+            ErrorCode::External => 7001,
+            ErrorCode::Internal => 9001,
+            ErrorCode::Unknown => 9999,
+        }
+    }
+
+    /// Look up a code by number, e.g. one received from a peer
+    pub fn from_u16(code: u16) -> Option<Self> {
+        Self::ALL.iter().copied().find(|c| c.as_u16() == code)
+    }
+
+    /// Dotted name, stable across versions (e.g. `transport.connection_failed`)
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::ConnectionFailed => "transport.connection_failed",
+            ErrorCode::ConnectionClosed => "transport.connection_closed",
+            ErrorCode::PeerUnreachable => "transport.peer_unreachable",
+            ErrorCode::DiscoveryFailed => "transport.discovery_failed",
+            ErrorCode::ProtocolViolation => "transport.protocol_violation",
+            ErrorCode::TransportNotRunning => "transport.not_running",
+            ErrorCode::TooManyConnections => "transport.too_many_connections",
+            ErrorCode::AccessDenied => "transport.access_denied",
+            ErrorCode::InvalidAddress => "transport.invalid_address",
+            ErrorCode::BindFailed => "transport.bind_failed",
+            ErrorCode::NoRoute => "routing.no_route",
+            ErrorCode::TtlExpired => "routing.ttl_expired",
+            ErrorCode::MessageTooLarge => "routing.message_too_large",
+            ErrorCode::StorageIo => "storage.io",
+            ErrorCode::NotFound => "storage.not_found",
+            ErrorCode::CapacityExceeded => "storage.capacity_exceeded",
+            ErrorCode::DatabaseLocked => "storage.database_locked",
+            ErrorCode::CorruptData => "storage.corrupt_data",
+            ErrorCode::Database => "storage.database",
+            ErrorCode::SyncFailed => "sync.failed",
+            ErrorCode::DocumentLoad => "sync.document_load",
+            ErrorCode::InterfaceNotFound => "sync.interface_not_found",
+            ErrorCode::NotMember => "sync.not_member",
+            ErrorCode::PeerNotFound => "sync.peer_not_found",
+            ErrorCode::InvalidEvent => "sync.invalid_event",
+            ErrorCode::CryptoFailed => "crypto.failed",
+            ErrorCode::InvalidKey => "crypto.invalid_key",
+            ErrorCode::SignatureInvalid => "crypto.signature_invalid",
+            ErrorCode::MissingKey => "crypto.missing_key",
+            ErrorCode::InvalidIdentity => "crypto.invalid_identity",
+            ErrorCode::AuthFailed => "crypto.auth_failed",
+            ErrorCode::NotStarted => "node.not_started",
+            ErrorCode::AlreadyStarted => "node.already_started",
+            ErrorCode::ShutDown => "node.shut_down",
+            ErrorCode::Config => "node.config",
+            ErrorCode::Channel => "node.channel",
+            ErrorCode::Timeout => "node.timeout",
+            ErrorCode::Serialization => "node.serialization",
+            ErrorCode::Io => "node.io",
+            ErrorCode::VersionMismatch => "node.version_mismatch",
+            ErrorCode::InvalidInput => "realm.invalid_input",
+            ErrorCode::PermissionDenied => "realm.permission_denied",
+            ErrorCode::Archived => "realm.archived",
+            ErrorCode::Sandboxed => "realm.sandboxed",
+            ErrorCode::Full => "realm.full",
+            ErrorCode::Expired => "realm.expired",
+            ErrorCode::AlreadyExists => "realm.already_exists",
+            ErrorCode::InvalidOperation => "realm.invalid_operation",
+            ErrorCode::Removed => "realm.removed",
+            ErrorCode::External => "external.failed",
+            ErrorCode::Internal => "internal",
+            ErrorCode::Unknown => "unknown",
+        }
+    }
+
+    /// Whether and when an operation failing with this code may be retried
+    pub const fn retryability(self) -> Retryability {
+        match self {
+            ErrorCode::ConnectionFailed
+            | ErrorCode::ConnectionClosed
+            | ErrorCode::DiscoveryFailed
+            | ErrorCode::TooManyConnections
+            | ErrorCode::StorageIo
+            | ErrorCode::Database
+            | ErrorCode::SyncFailed
+            | ErrorCode::Channel
+            | ErrorCode::Timeout
+            | ErrorCode::Io
+            | ErrorCode::External => Retryability::Transient,
+
+            ErrorCode::PeerUnreachable
+            | ErrorCode::TransportNotRunning
+            | ErrorCode::BindFailed
+            | ErrorCode::NoRoute
+            | ErrorCode::CapacityExceeded
+            | ErrorCode::DatabaseLocked
+            | ErrorCode::PeerNotFound
+            | ErrorCode::MissingKey
+            | ErrorCode::NotStarted
+            | ErrorCode::Full => Retryability::Deferred,
+
+            _ => Retryability::Permanent,
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "E{} {}", self.as_u16(), self.as_str())
+    }
+}
+
+/// Structured metadata exposed by error types across the stack
+pub trait ErrorInfo: std::error::Error {
+    /// Stable code for the failure
+    fn code(&self) -> ErrorCode;
+
+    /// Whether and when the failed operation may be retried
+    fn retryability(&self) -> Retryability {
+        self.code().retryability()
+    }
+
+    /// Whether retrying can help at all
+    fn is_retryable(&self) -> bool {
+        self.retryability().is_retryable()
+    }
+
+    /// The peer the failure concerns, if any (as the layer identifies it)
+    fn peer(&self) -> Option<&str> {
+        None
+    }
+
+    /// The interface (hex ID) the failure concerns, if any
+    fn interface(&self) -> Option<&str> {
+        None
+    }
+
+    /// Capture the error's code, message, peer and interface
+    fn detail(&self) -> ErrorDetail
+    where
+        Self: Sized,
+    {
+        ErrorDetail::from_error(self)
+    }
+}
+
+/// An error's code, message and context, carried across layers
+#[derive(Debug, Clone, PartialEq, Eq, Error, Serialize, Deserialize)]
+#[error("{message}")]
+pub struct ErrorDetail {
+    /// Stable code
+    pub code: ErrorCode,
+    /// Human-readable message
+    pub message: String,
+    /// Peer the failure concerns
+    pub peer: Option<String>,
+    /// Interface (hex ID) the failure concerns
+    pub interface: Option<String>,
+}
+
+impl ErrorDetail {
+    /// Create a detail with no peer or interface
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            peer: None,
+            interface: None,
+        }
+    }
+
+    /// Capture another error's metadata
+    pub fn from_error<E: ErrorInfo + ?Sized>(error: &E) -> Self {
+        Self {
+            code: error.code(),
+            message: error.to_string(),
+            peer: error.peer().map(String::from),
+            interface: error.interface().map(String::from),
+        }
+    }
+
+    /// Attach the peer the failure concerns
+    pub fn with_peer(mut self, peer: impl Into<String>) -> Self {
+        self.peer = Some(peer.into());
+        self
+    }
+
+    /// Attach the interface the failure concerns
+    pub fn with_interface(mut self, interface: impl Into<String>) -> Self {
+        self.interface = Some(interface.into());
+        self
+    }
+}
+
+impl ErrorInfo for ErrorDetail {
+    fn code(&self) -> ErrorCode {
+        self.code
+    }
+
+    fn peer(&self) -> Option<&str> {
+        self.peer.as_deref()
+    }
+
+    fn interface(&self) -> Option<&str> {
+        self.interface.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_codes_are_unique_and_round_trip() {
+        let mut numbers = HashSet::new();
+        let mut names = HashSet::new();
+        for code in ErrorCode::ALL {
+            assert!(
+                numbers.insert(code.as_u16()),
+                "duplicate number for {code:?}"
+            );
+            assert!(names.insert(code.as_str()), "duplicate name for {code:?}");
+            assert_eq!(ErrorCode::from_u16(code.as_u16()), Some(*code));
+        }
+        assert_eq!(ErrorCode::from_u16(0), None);
+    }
+
+    #[test]
+    fn test_codes_are_stable() {
+        // These values are part of the public contract; never change them.
+        assert_eq!(ErrorCode::ConnectionFailed.as_u16(), 1001);
+        assert_eq!(ErrorCode::DatabaseLocked.as_u16(), 2004);
+        assert_eq!(ErrorCode::InterfaceNotFound.as_u16(), 3003);
+        assert_eq!(ErrorCode::NotStarted.as_u16(), 5001);
+        assert_eq!(ErrorCode::Archived.as_str(), "realm.archived");
+        assert_eq!(
+            ErrorCode::PeerUnreachable.to_string(),
+            "E1003 transport.peer_unreachable"
+        );
+    }
+
+    #[test]
+    fn test_detail_carries_context() {
+        let detail = ErrorDetail::new(ErrorCode::ConnectionClosed, "closed by peer")
+            .with_peer("ab12cd34")
+            .with_interface("00ff");
+        assert!(detail.is_retryable());
+        assert_eq!(detail.retryability(), Retryability::Transient);
+        assert_eq!(detail.peer(), Some("ab12cd34"));
+        assert_eq!(detail.interface(), Some("00ff"));
+        assert_eq!(detail.to_string(), "closed by peer");
+        assert_eq!(ErrorDetail::from_error(&detail), detail);
+
+        let permanent = ErrorDetail::new(ErrorCode::SignatureInvalid, "bad signature");
+        assert!(!permanent.is_retryable());
+    }
+}
@@ -21,6 +21,7 @@
 //! - [`NetworkEvent`]: Events that occur in the network
 
 pub mod error;
+pub mod error_code;
 pub mod event;
 pub mod identity;
 pub mod interface;
@@ -32,6 +33,7 @@ pub mod transport;
 
 // Re-export main types
 pub use error::*;
+pub use error_code::*;
 pub use event::*;
 pub use identity::*;
 pub use interface::*;
@@ -20,6 +20,8 @@
 //! - [`KeyDistribution`]: Utilities for creating and accepting invites
 //! - [`PQIdentity`]: Post-quantum identity for signing messages
 //! - [`PQKemKeyPair`]: Post-quantum key encapsulation for key exchange
+//! - [`TreeKemMember`]: Tree-based group key rotation for large interfaces
+//! - [`RingKeyPair`]: Ring signatures for anonymous realm posts
 //!
 //! ## Example
 //!
@@ -54,6 +56,7 @@ pub mod interface_key;
 pub mod key_distribution;
 pub mod pq_identity;
 pub mod pq_kem;
+pub mod ring_signature;
 pub mod credential;
 pub mod entropy;
 pub mod pass_story;
@@ -61,8 +64,10 @@ pub mod shamir;
 pub mod steward_share;
 pub mod account_root;
 pub mod device_cert;
+pub mod member_cert;
 pub mod erasure;
 pub mod story_template;
+pub mod tree_kem;
 pub mod word_frequencies;
 
 // Re-exports
@@ -86,6 +91,12 @@ pub use pq_kem::{
     PQ_SHARED_SECRET_SIZE, PQCiphertext, PQEncapsulationKey, PQKemKeyPair,
 };
 
+// Anonymous posting re-exports
+pub use ring_signature::{RingKeyPair, RingPublicKey, RingSignature, anonymous_sender_seed};
+
+// Group key agreement re-exports
+pub use tree_kem::{LeafIndex, NodeIndex, RatchetTree, TreeCommit, TreeKemMember, UpdatePathNode};
+
 // Pass story re-exports
 pub use pass_story::StorySubkeys;
 pub use story_template::{PassStory, StoryStage, StoryTemplate};
@@ -93,6 +104,9 @@ pub use story_template::{PassStory, StoryStage, StoryTemplate};
 // Credential re-exports
 pub use credential::{CredentialV1, SignedCredential, create_credential, parse_credential, verify_credential};
 
+// Organization trust re-exports
+pub use member_cert::{MemberCertificate, org_id};
+
 // Re-export x25519 types for legacy code (deprecated)
 #[deprecated(since = "0.2.0", note = "Use ML-KEM via PQKemKeyPair instead")]
 pub use x25519_dalek::{PublicKey, StaticSecret};
@@ -0,0 +1,186 @@
+//! Member certificate — an organization root's attestation that a
+//! PQ verifying key belongs to one of its members.
+//!
+//! Company and classroom deployments publish a single organization
+//! root `vk` out of band. The organization signs a
+//! `MemberCertificate` for each member's device key, and realms
+//! configured with that root accept (or flag) only senders whose
+//! key chains to it.
+//!
+//! Like [`DeviceCertificate`](crate::device_cert::DeviceCertificate)
+//! the certificate is immutable; reissue it to change the name or
+//! extend the expiry.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::CryptoError;
+use crate::pq_identity::{PQIdentity, PQPublicIdentity, PQSignature};
+
+/// Organization-signed member certificate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemberCertificate {
+    /// Raw PQ verifying-key bytes of the certified member.
+    pub member_vk_bytes: Vec<u8>,
+    /// Human label — "alice@example.com", "Room 12 laptop", etc.
+    pub member_name: String,
+    /// Blake3 digest of the issuing organization root's `vk`.
+    pub org_id: [u8; 32],
+    /// Wall-clock millis when this cert was issued.
+    pub issued_at_millis: i64,
+    /// Wall-clock millis after which the cert is no longer valid.
+    pub expires_at_millis: Option<i64>,
+    /// Root signature over the canonical byte encoding of the
+    /// fields above.
+    pub signature: Vec<u8>,
+}
+
+impl MemberCertificate {
+    /// Produce a certificate for `member_vk_bytes` signed by the
+    /// organization `root`.
+    pub fn sign(
+        member_vk_bytes: Vec<u8>,
+        member_name: impl Into<String>,
+        issued_at_millis: i64,
+        expires_at_millis: Option<i64>,
+        root: &PQIdentity,
+    ) -> Self {
+        let member_name = member_name.into();
+        let org_id = root.user_id();
+        let msg = canonical_message(
+            &member_vk_bytes,
+            &member_name,
+            &org_id,
+            issued_at_millis,
+            expires_at_millis,
+        );
+        Self {
+            member_vk_bytes,
+            member_name,
+            org_id,
+            issued_at_millis,
+            expires_at_millis,
+            signature: root.sign(&msg).to_bytes().to_vec(),
+        }
+    }
+
+    /// Verify this certificate against the organization root's vk.
+    ///
+    /// Checks the issuer binding and signature only; callers decide
+    /// what to do with expired certificates via [`Self::is_expired`].
+    pub fn verify(&self, root_vk: &PQPublicIdentity) -> bool {
+        if self.org_id != org_id(root_vk) {
+            return false;
+        }
+        let msg = canonical_message(
+            &self.member_vk_bytes,
+            &self.member_name,
+            &self.org_id,
+            self.issued_at_millis,
+            self.expires_at_millis,
+        );
+        match PQSignature::from_bytes(self.signature.clone()) {
+            Ok(sig) => root_vk.verify(&msg, &sig),
+            Err(_) => false,
+        }
+    }
+
+    /// Whether the certificate has expired at `now_millis`.
+    pub fn is_expired(&self, now_millis: i64) -> bool {
+        self.expires_at_millis.is_some_and(|at| now_millis >= at)
+    }
+
+    /// Rehydrate the certified member's public key.
+    pub fn member_public_key(&self) -> Result<PQPublicIdentity, CryptoError> {
+        PQPublicIdentity::from_bytes(&self.member_vk_bytes)
+    }
+
+    /// Blake3 digest of the member's verifying key, matching
+    /// `PQIdentity::user_id`.
+    pub fn member_user_id(&self) -> [u8; 32] {
+        *blake3::hash(&self.member_vk_bytes).as_bytes()
+    }
+}
+
+/// Identifier of an organization root: the Blake3 digest of its vk.
+pub fn org_id(root_vk: &PQPublicIdentity) -> [u8; 32] {
+    *blake3::hash(&root_vk.to_bytes()).as_bytes()
+}
+
+/// Canonical byte encoding the certificate signature binds to,
+/// length-prefixed and domain-separated like the device cert.
+fn canonical_message(
+    member_vk_bytes: &[u8],
+    member_name: &str,
+    org_id: &[u8; 32],
+    issued_at_millis: i64,
+    expires_at_millis: Option<i64>,
+) -> Vec<u8> {
+    const DOMAIN: &[u8] = b"indras:member-cert:v1";
+    let name_bytes = member_name.as_bytes();
+    let mut out = Vec::with_capacity(
+        DOMAIN.len() + 4 + member_vk_bytes.len() + 4 + name_bytes.len() + 32 + 8 + 9,
+    );
+    out.extend_from_slice(DOMAIN);
+    out.extend_from_slice(&(member_vk_bytes.len() as u32).to_le_bytes());
+    out.extend_from_slice(member_vk_bytes);
+    out.extend_from_slice(&(name_bytes.len() as u32).to_le_bytes());
+    out.extend_from_slice(name_bytes);
+    out.extend_from_slice(org_id);
+    out.extend_from_slice(&issued_at_millis.to_le_bytes());
+    match expires_at_millis {
+        Some(at) => {
+            out.push(1);
+            out.extend_from_slice(&at.to_le_bytes());
+        }
+        None => out.push(0),
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn org_signed_cert_verifies() {
+        let org = PQIdentity::generate();
+        let member = PQIdentity::generate();
+        let cert = MemberCertificate::sign(
+            member.verifying_key_bytes(),
+            "alice@example.com",
+            1_700_000_000_000,
+            None,
+            &org,
+        );
+        assert!(cert.verify(&org.verifying_key()));
+        assert_eq!(cert.member_user_id(), member.user_id());
+        assert_eq!(cert.org_id, org_id(&org.verifying_key()));
+    }
+
+    #[test]
+    fn other_org_and_tampering_reject() {
+        let org = PQIdentity::generate();
+        let other = PQIdentity::generate();
+        let member = PQIdentity::generate();
+        let cert = MemberCertificate::sign(
+            member.verifying_key_bytes(),
+            "bob",
+            1,
+            Some(10),
+            &org,
+        );
+        assert!(!cert.verify(&other.verifying_key()));
+
+        let mut forged = cert.clone();
+        forged.expires_at_millis = None;
+        assert!(!forged.verify(&org.verifying_key()));
+    }
+
+    #[test]
+    fn expiry() {
+        let org = PQIdentity::generate();
+        let cert = MemberCertificate::sign(vec![1, 2, 3], "x", 1, Some(100), &org);
+        assert!(!cert.is_expired(99));
+        assert!(cert.is_expired(100));
+    }
+}
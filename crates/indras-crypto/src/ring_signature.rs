@@ -0,0 +1,274 @@
+//! Ring signatures for anonymous posting
+//!
+//! A ring signature proves that *some* member of a ring of public keys
+//! signed a message without revealing which one. Realms use it for
+//! anonymous channels: a post carries a ring signature over the realm's
+//! member ring instead of the sender's ML-DSA signature, so recipients
+//! know it came from a member but not from whom.
+//!
+//! The scheme is the Abe–Ohkubo–Suzuki (AOS) ring of Schnorr signatures
+//! over Ristretto255. Signatures are unlinkable: two posts by the same
+//! member cannot be told apart from posts by two different members.
+//! Anonymity holds unconditionally; unforgeability rests on discrete log,
+//! so unlike the rest of the stack this is not post-quantum.
+//!
+//! Signature size is `32 * (ring size + 1)` bytes.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! let me = RingKeyPair::generate();
+//! let ring = vec![alice.public_key(), me.public_key(), bob.public_key()];
+//!
+//! let signature = me.sign(b"retro feedback", &ring)?;
+//! assert!(signature.verify(b"retro feedback", &ring));
+//! ```
+
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::Scalar;
+use indras_core::InterfaceId;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+use crate::error::CryptoError;
+
+/// BLAKE3 context for challenge hashing
+const CHALLENGE_CONTEXT: &str = "indras ring-signature challenge v1";
+
+/// BLAKE3 context for deriving ring keys from other secret material
+const KEY_CONTEXT: &str = "indras ring-signature key v1";
+
+/// BLAKE3 context for a realm's shared anonymous sender identity
+const ANONYMOUS_SENDER_CONTEXT: &str = "indras anonymous sender v1";
+
+/// Largest ring accepted by [`RingSignature::verify`]
+pub const MAX_RING_SIZE: usize = 4096;
+
+/// A member's public key in a ring (compressed Ristretto point)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct RingPublicKey(pub [u8; 32]);
+
+impl RingPublicKey {
+    fn point(&self) -> Option<RistrettoPoint> {
+        CompressedRistretto(self.0).decompress()
+    }
+}
+
+/// A ring signing keypair
+pub struct RingKeyPair {
+    secret: Scalar,
+    public: RingPublicKey,
+}
+
+impl RingKeyPair {
+    /// Generate a random keypair
+    pub fn generate() -> Self {
+        let mut wide = [0u8; 64];
+        rand::rng().fill_bytes(&mut wide);
+        let keypair = Self::from_scalar(Scalar::from_bytes_mod_order_wide(&wide));
+        wide.zeroize();
+        keypair
+    }
+
+    /// Derive a keypair deterministically from secret material
+    ///
+    /// `context` separates keys derived from the same secret, e.g. one
+    /// ring key per realm so rings in different realms cannot be linked.
+    pub fn derive(secret: &[u8], context: &[u8]) -> Self {
+        let mut hasher = blake3::Hasher::new_derive_key(KEY_CONTEXT);
+        hasher.update(&(secret.len() as u64).to_le_bytes());
+        hasher.update(secret);
+        hasher.update(context);
+        let mut wide = [0u8; 64];
+        hasher.finalize_xof().fill(&mut wide);
+        let keypair = Self::from_scalar(Scalar::from_bytes_mod_order_wide(&wide));
+        wide.zeroize();
+        keypair
+    }
+
+    fn from_scalar(secret: Scalar) -> Self {
+        let public = RingPublicKey((secret * RISTRETTO_BASEPOINT_POINT).compress().to_bytes());
+        Self { secret, public }
+    }
+
+    /// The public key to publish in the ring
+    pub fn public_key(&self) -> RingPublicKey {
+        self.public
+    }
+
+    /// Sign `message` as one anonymous member of `ring`
+    ///
+    /// The ring must contain this keypair's public key and at most
+    /// [`MAX_RING_SIZE`] keys. Order matters: the verifier must use the
+    /// same ring in the same order.
+    pub fn sign(&self, message: &[u8], ring: &[RingPublicKey]) -> Result<RingSignature, CryptoError> {
+        if ring.len() > MAX_RING_SIZE {
+            return Err(CryptoError::InvalidKey(format!(
+                "ring has {} keys, more than {MAX_RING_SIZE}",
+                ring.len()
+            )));
+        }
+        let signer = ring
+            .iter()
+            .position(|k| *k == self.public)
+            .ok_or_else(|| CryptoError::InvalidKey("signer is not in the ring".to_string()))?;
+        let points = decompress_ring(ring)?;
+        let n = ring.len();
+
+        let mut challenges = vec![Scalar::ZERO; n];
+        let mut responses = vec![Scalar::ZERO; n];
+
+        // The nonce reveals the secret key given the signature; wipe it
+        let mut alpha = random_scalar();
+        challenges[(signer + 1) % n] = challenge(ring, message, &(alpha * RISTRETTO_BASEPOINT_POINT));
+
+        let mut i = (signer + 1) % n;
+        while i != signer {
+            responses[i] = random_scalar();
+            let commitment = responses[i] * RISTRETTO_BASEPOINT_POINT + challenges[i] * points[i];
+            challenges[(i + 1) % n] = challenge(ring, message, &commitment);
+            i = (i + 1) % n;
+        }
+        responses[signer] = alpha - challenges[signer] * self.secret;
+        alpha.zeroize();
+
+        Ok(RingSignature {
+            challenge: challenges[0].to_bytes(),
+            responses: responses.iter().map(Scalar::to_bytes).collect(),
+        })
+    }
+}
+
+impl Drop for RingKeyPair {
+    fn drop(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
+impl std::fmt::Debug for RingKeyPair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RingKeyPair")
+            .field("public", &hex::encode(&self.public.0[..8]))
+            .finish_non_exhaustive()
+    }
+}
+
+/// A signature by one unidentified member of a ring
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RingSignature {
+    /// The first challenge in the ring
+    pub challenge: [u8; 32],
+    /// One response per ring member
+    pub responses: Vec<[u8; 32]>,
+}
+
+impl RingSignature {
+    /// Whether some member of `ring` signed `message`
+    pub fn verify(&self, message: &[u8], ring: &[RingPublicKey]) -> bool {
+        if ring.is_empty() || ring.len() > MAX_RING_SIZE || self.responses.len() != ring.len() {
+            return false;
+        }
+        let Ok(points) = decompress_ring(ring) else {
+            return false;
+        };
+        let Some(first) = Option::<Scalar>::from(Scalar::from_canonical_bytes(self.challenge)) else {
+            return false;
+        };
+
+        let mut c = first;
+        for (response, point) in self.responses.iter().zip(&points) {
+            let Some(r) = Option::<Scalar>::from(Scalar::from_canonical_bytes(*response)) else {
+                return false;
+            };
+            c = challenge(ring, message, &(r * RISTRETTO_BASEPOINT_POINT + c * point));
+        }
+        c == first
+    }
+}
+
+/// Seed of a realm's shared anonymous sender identity
+///
+/// Anonymous posts need a sender field; every member derives the same
+/// pseudonymous identity from the interface id so the field says nothing.
+pub fn anonymous_sender_seed(interface_id: &InterfaceId) -> [u8; 32] {
+    blake3::derive_key(ANONYMOUS_SENDER_CONTEXT, interface_id.as_bytes())
+}
+
+fn decompress_ring(ring: &[RingPublicKey]) -> Result<Vec<RistrettoPoint>, CryptoError> {
+    ring.iter()
+        .map(|k| {
+            k.point()
+                .ok_or_else(|| CryptoError::InvalidKey("invalid ring public key".to_string()))
+        })
+        .collect()
+}
+
+fn random_scalar() -> Scalar {
+    let mut wide = [0u8; 64];
+    rand::rng().fill_bytes(&mut wide);
+    Scalar::from_bytes_mod_order_wide(&wide)
+}
+
+/// Hash the ring, message, and commitment to a challenge scalar
+fn challenge(ring: &[RingPublicKey], message: &[u8], commitment: &RistrettoPoint) -> Scalar {
+    let mut hasher = blake3::Hasher::new_derive_key(CHALLENGE_CONTEXT);
+    hasher.update(&(ring.len() as u64).to_le_bytes());
+    for key in ring {
+        hasher.update(&key.0);
+    }
+    hasher.update(&(message.len() as u64).to_le_bytes());
+    hasher.update(message);
+    hasher.update(commitment.compress().as_bytes());
+    let mut wide = [0u8; 64];
+    hasher.finalize_xof().fill(&mut wide);
+    Scalar::from_bytes_mod_order_wide(&wide)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ring_of(n: usize) -> (Vec<RingKeyPair>, Vec<RingPublicKey>) {
+        let keys: Vec<_> = (0..n).map(|_| RingKeyPair::generate()).collect();
+        let ring = keys.iter().map(RingKeyPair::public_key).collect();
+        (keys, ring)
+    }
+
+    #[test]
+    fn test_any_member_can_sign() {
+        let (keys, ring) = ring_of(4);
+        for key in &keys {
+            let sig = key.sign(b"feedback", &ring).unwrap();
+            assert!(sig.verify(b"feedback", &ring));
+            assert!(!sig.verify(b"tampered", &ring));
+        }
+    }
+
+    #[test]
+    fn test_outsider_and_wrong_ring_rejected() {
+        let (keys, ring) = ring_of(3);
+        let outsider = RingKeyPair::generate();
+        assert!(outsider.sign(b"hi", &ring).is_err());
+
+        let sig = keys[0].sign(b"hi", &ring).unwrap();
+        let mut other = ring.clone();
+        other[2] = outsider.public_key();
+        assert!(!sig.verify(b"hi", &other));
+        assert!(!sig.verify(b"hi", &ring[..2]));
+
+        let oversized = vec![keys[0].public_key(); MAX_RING_SIZE + 1];
+        assert!(keys[0].sign(b"hi", &oversized).is_err());
+    }
+
+    #[test]
+    fn test_derive_is_deterministic_per_context() {
+        let a = RingKeyPair::derive(b"secret", b"realm-a");
+        assert_eq!(a.public_key(), RingKeyPair::derive(b"secret", b"realm-a").public_key());
+        assert_ne!(a.public_key(), RingKeyPair::derive(b"secret", b"realm-b").public_key());
+
+        let ring = vec![a.public_key()];
+        assert!(a.sign(b"solo", &ring).unwrap().verify(b"solo", &ring));
+    }
+}
@@ -0,0 +1,655 @@
+//! Tree-based group key agreement for large interfaces (TreeKEM-style)
+//!
+//! Handing a fresh [`InterfaceKey`] to every member with
+//! [`InterfaceKey::encapsulate_for`] costs one ML-KEM encapsulation per
+//! member. For a 500-member realm that is 500 encapsulations (and ~550 KB
+//! of ciphertext) on every rotation.
+//!
+//! This module arranges members as the leaves of a binary ratchet tree.
+//! Every node on a member's path to the root holds an ML-KEM-768 keypair,
+//! and a member knows the private keys on its own path. A rotation (a
+//! [`TreeCommit`]) refreshes only the committer's path: each new path
+//! secret is encapsulated once to the sibling subtree, so a full tree
+//! rotates in `O(log n)` encapsulations — 9 for 500 members.
+//!
+//! ## Layout
+//!
+//! Nodes live in a flat array in the usual left-balanced order: leaf `i`
+//! is node `2i`, and the tree grows by doubling. A node is blank when no
+//! one is guaranteed to hold its private key; encrypting to a blank node
+//! falls back to its non-blank descendants (its *resolution*).
+//!
+//! ## Membership
+//!
+//! Adds and removes ride inside a commit. A removed member's leaf and
+//! every node above it are blanked, so the commit's fresh path never
+//! encrypts to a key it knew. An added member's path is blanked as well,
+//! so the commit reaches it through its leaf key alone. Blanks are
+//! repaired as members commit; until then rotations cost a little more.
+//!
+//! ## Key derivation
+//!
+//! Path secrets chain upward with BLAKE3 `derive_key`. Since ML-KEM keys
+//! cannot be derived from a seed here, each path node's decapsulation key
+//! is generated fresh and sealed under its path secret; anyone who learns
+//! the path secret can open it. The root's path secret, mixed with the
+//! interface and epoch, becomes the epoch's [`InterfaceKey`].
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! let (mut alice, mut tree) = TreeKemMember::create(interface_id, PQKemKeyPair::generate());
+//!
+//! // Alice adds Bob and rotates in one commit.
+//! let bob_kem = PQKemKeyPair::generate();
+//! let welcome_tree = tree.clone();
+//! let commit = alice.commit(&mut tree, &[], vec![bob_kem.encapsulation_key_bytes()])?;
+//!
+//! // Bob joins from the pre-commit tree and the commit.
+//! let mut bob_tree = welcome_tree;
+//! let bob = TreeKemMember::join(interface_id, &mut bob_tree, &commit, bob_kem)?;
+//! assert_eq!(alice.interface_key().as_bytes(), bob.interface_key().as_bytes());
+//! ```
+
+use std::collections::BTreeMap;
+
+use indras_core::InterfaceId;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+use crate::error::CryptoError;
+use crate::interface_key::{EncapsulatedKey, EncryptedData, InterfaceKey, KEY_SIZE};
+use crate::pq_kem::{PQEncapsulationKey, PQKemKeyPair};
+
+/// BLAKE3 context for chaining a path secret to its parent
+const PATH_CONTEXT: &str = "indras tree-kem path v1";
+
+/// BLAKE3 context for the key that seals a node's decapsulation key
+const NODE_CONTEXT: &str = "indras tree-kem node v1";
+
+/// BLAKE3 context for the epoch's interface key
+const EPOCH_CONTEXT: &str = "indras tree-kem epoch v1";
+
+/// Position of a member's leaf in the tree
+pub type LeafIndex = u32;
+
+/// Index of a node in the flat tree array
+pub type NodeIndex = u32;
+
+// ============================================================
+// Tree math (left-balanced array layout)
+// ============================================================
+
+fn level(x: NodeIndex) -> u32 {
+    x.trailing_ones()
+}
+
+fn leaf_node(leaf: LeafIndex) -> NodeIndex {
+    2 * leaf
+}
+
+fn left(x: NodeIndex) -> NodeIndex {
+    x ^ (1 << (level(x) - 1))
+}
+
+fn right(x: NodeIndex) -> NodeIndex {
+    x ^ (3 << (level(x) - 1))
+}
+
+fn parent(x: NodeIndex) -> NodeIndex {
+    let k = level(x);
+    let b = (x >> (k + 1)) & 1;
+    (x | (1 << k)) ^ (b << (k + 1))
+}
+
+fn sibling(x: NodeIndex) -> NodeIndex {
+    let p = parent(x);
+    if x < p { right(p) } else { left(p) }
+}
+
+/// Whether `x` lies in the subtree rooted at `ancestor`
+fn in_subtree(ancestor: NodeIndex, x: NodeIndex) -> bool {
+    let span = (1u32 << level(ancestor)) - 1;
+    x >= ancestor - span && x <= ancestor + span
+}
+
+fn derive(context: &str, material: &[u8]) -> [u8; KEY_SIZE] {
+    blake3::derive_key(context, material)
+}
+
+// ============================================================
+// Public tree
+// ============================================================
+
+/// The public half of a ratchet tree, identical for every member
+///
+/// Holds each node's ML-KEM encapsulation key (or nothing, if blank).
+/// Members keep their own copy and apply every [`TreeCommit`] in order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RatchetTree {
+    nodes: Vec<Option<Vec<u8>>>,
+}
+
+impl RatchetTree {
+    /// Create an empty tree
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of leaf slots (occupied or not); always a power of two or zero
+    pub fn leaf_capacity(&self) -> u32 {
+        (self.nodes.len() as u32).div_ceil(2)
+    }
+
+    /// Occupied leaves, in order
+    pub fn members(&self) -> impl Iterator<Item = LeafIndex> + '_ {
+        (0..self.leaf_capacity()).filter(|&leaf| self.node(leaf_node(leaf)).is_some())
+    }
+
+    /// Number of members
+    pub fn member_count(&self) -> usize {
+        self.members().count()
+    }
+
+    /// A member's leaf encapsulation key
+    pub fn leaf_key(&self, leaf: LeafIndex) -> Option<&[u8]> {
+        self.node(leaf_node(leaf))
+    }
+
+    /// The leaf holding an encapsulation key, if any
+    pub fn find_leaf(&self, encapsulation_key: &[u8]) -> Option<LeafIndex> {
+        self.members()
+            .find(|&leaf| self.leaf_key(leaf) == Some(encapsulation_key))
+    }
+
+    fn root(&self) -> NodeIndex {
+        self.leaf_capacity() - 1
+    }
+
+    fn node(&self, x: NodeIndex) -> Option<&[u8]> {
+        self.nodes.get(x as usize).and_then(|n| n.as_deref())
+    }
+
+    fn set_node(&mut self, x: NodeIndex, key: Option<Vec<u8>>) {
+        self.nodes[x as usize] = key;
+    }
+
+    /// Nodes from `x`'s parent up to the root
+    fn direct_path(&self, x: NodeIndex) -> Vec<NodeIndex> {
+        let root = self.root();
+        let mut path = Vec::new();
+        let mut node = x;
+        while node != root {
+            node = parent(node);
+            path.push(node);
+        }
+        path
+    }
+
+    /// Non-blank nodes covering the subtree at `x`
+    fn resolution(&self, x: NodeIndex) -> Vec<NodeIndex> {
+        if self.node(x).is_some() {
+            vec![x]
+        } else if level(x) == 0 {
+            Vec::new()
+        } else {
+            let mut nodes = self.resolution(left(x));
+            nodes.extend(self.resolution(right(x)));
+            nodes
+        }
+    }
+
+    fn blank_path(&mut self, leaf: LeafIndex) {
+        for node in self.direct_path(leaf_node(leaf)) {
+            self.set_node(node, None);
+        }
+    }
+
+    /// Place a new member in the first free leaf, doubling the tree if full
+    fn add_leaf(&mut self, encapsulation_key: Vec<u8>) -> LeafIndex {
+        let capacity = self.leaf_capacity();
+        let leaf = match (0..capacity).find(|&l| self.node(leaf_node(l)).is_none()) {
+            Some(free) => free,
+            None if capacity == 0 => {
+                self.nodes.push(None);
+                0
+            }
+            None => {
+                self.nodes.resize((4 * capacity - 1) as usize, None);
+                capacity
+            }
+        };
+        self.set_node(leaf_node(leaf), Some(encapsulation_key));
+        self.blank_path(leaf);
+        leaf
+    }
+
+    fn remove_leaf(&mut self, leaf: LeafIndex) -> Result<(), CryptoError> {
+        if self.leaf_key(leaf).is_none() {
+            return Err(CryptoError::InvalidKey(format!("no member at leaf {leaf}")));
+        }
+        self.set_node(leaf_node(leaf), None);
+        self.blank_path(leaf);
+        Ok(())
+    }
+
+    /// Apply a commit's removes and adds, in that order
+    fn apply_membership(&mut self, commit: &TreeCommit) -> Result<(), CryptoError> {
+        for &leaf in &commit.removes {
+            self.remove_leaf(leaf)?;
+        }
+        for key in &commit.adds {
+            self.add_leaf(key.clone());
+        }
+        Ok(())
+    }
+}
+
+// ============================================================
+// Commits
+// ============================================================
+
+/// One refreshed node on a committer's path
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdatePathNode {
+    /// The node being refreshed
+    pub node: NodeIndex,
+    /// The node's new encapsulation key
+    pub encapsulation_key: Vec<u8>,
+    /// The node's new decapsulation key, sealed under its path secret
+    pub sealed_key: EncryptedData,
+    /// The path secret, encapsulated to each node in the sibling
+    /// subtree's resolution
+    pub secrets: Vec<(NodeIndex, EncapsulatedKey)>,
+}
+
+/// A rotation of the group key, with any membership changes
+///
+/// Broadcast to all members (and, with the pre-commit tree, to anyone
+/// being added). Every member applies commits in epoch order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreeCommit {
+    /// The epoch this commit starts
+    pub epoch: u64,
+    /// The committing member's leaf
+    pub sender: LeafIndex,
+    /// Leaves removed by this commit
+    pub removes: Vec<LeafIndex>,
+    /// Encapsulation keys of members added by this commit
+    pub adds: Vec<Vec<u8>>,
+    /// The committer's new leaf encapsulation key
+    pub leaf_key: Vec<u8>,
+    /// Refreshed nodes, from just above the committer's leaf to the root
+    pub path: Vec<UpdatePathNode>,
+}
+
+impl TreeCommit {
+    /// Number of ML-KEM encapsulations in this commit
+    pub fn encapsulation_count(&self) -> usize {
+        self.path.iter().map(|n| n.secrets.len()).sum()
+    }
+}
+
+// ============================================================
+// Member state
+// ============================================================
+
+/// One member's private view of the tree
+///
+/// Holds the decapsulation keys for the member's own leaf and whichever
+/// nodes on its path it currently knows, plus the current epoch secret.
+pub struct TreeKemMember {
+    interface_id: InterfaceId,
+    leaf: LeafIndex,
+    keys: BTreeMap<NodeIndex, PQKemKeyPair>,
+    epoch: u64,
+    epoch_secret: [u8; KEY_SIZE],
+}
+
+impl TreeKemMember {
+    /// Start a new group with this member as its only leaf
+    pub fn create(interface_id: InterfaceId, leaf_kem: PQKemKeyPair) -> (Self, RatchetTree) {
+        let mut tree = RatchetTree::new();
+        let leaf = tree.add_leaf(leaf_kem.encapsulation_key_bytes());
+        let mut epoch_secret = [0u8; KEY_SIZE];
+        rand::rng().fill_bytes(&mut epoch_secret);
+        let member = Self {
+            interface_id,
+            leaf,
+            keys: BTreeMap::from([(leaf_node(leaf), leaf_kem)]),
+            epoch: 0,
+            epoch_secret,
+        };
+        (member, tree)
+    }
+
+    /// Join from the tree as it was before `commit`, and the commit that
+    /// added `leaf_kem`'s encapsulation key
+    ///
+    /// `tree` is advanced to the commit's epoch.
+    pub fn join(
+        interface_id: InterfaceId,
+        tree: &mut RatchetTree,
+        commit: &TreeCommit,
+        leaf_kem: PQKemKeyPair,
+    ) -> Result<Self, CryptoError> {
+        let ek = leaf_kem.encapsulation_key_bytes();
+        if !commit.adds.contains(&ek) {
+            return Err(CryptoError::KeyExchangeFailed(
+                "commit does not add this member".to_string(),
+            ));
+        }
+        let mut staged = tree.clone();
+        staged.apply_membership(commit)?;
+        let leaf = staged
+            .find_leaf(&ek)
+            .ok_or_else(|| CryptoError::KeyExchangeFailed("leaf not found".to_string()))?;
+
+        let mut member = Self {
+            interface_id,
+            leaf,
+            keys: BTreeMap::from([(leaf_node(leaf), leaf_kem)]),
+            epoch: commit.epoch.saturating_sub(1),
+            epoch_secret: [0u8; KEY_SIZE],
+        };
+        member.process_commit(tree, commit)?;
+        Ok(member)
+    }
+
+    /// This member's leaf
+    pub fn leaf(&self) -> LeafIndex {
+        self.leaf
+    }
+
+    /// Current epoch
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// The interface key for the current epoch
+    pub fn interface_key(&self) -> InterfaceKey {
+        InterfaceKey::from_bytes(self.epoch_secret, self.interface_id)
+    }
+
+    /// Rotate the group key, applying `removes` and `adds` first
+    ///
+    /// Refreshes this member's leaf and every node on its path. `tree` is
+    /// advanced to the new epoch; broadcast the returned commit.
+    pub fn commit(
+        &mut self,
+        tree: &mut RatchetTree,
+        removes: &[LeafIndex],
+        adds: Vec<Vec<u8>>,
+    ) -> Result<TreeCommit, CryptoError> {
+        if removes.contains(&self.leaf) {
+            return Err(CryptoError::InvalidKey(
+                "a member cannot remove itself in its own commit".to_string(),
+            ));
+        }
+        let mut commit = TreeCommit {
+            epoch: self.epoch + 1,
+            sender: self.leaf,
+            removes: removes.to_vec(),
+            adds,
+            leaf_key: Vec::new(),
+            path: Vec::new(),
+        };
+
+        let mut staged = tree.clone();
+        staged.apply_membership(&commit)?;
+
+        let leaf_kem = PQKemKeyPair::generate();
+        commit.leaf_key = leaf_kem.encapsulation_key_bytes();
+        let mut keys = BTreeMap::from([(leaf_node(self.leaf), leaf_kem)]);
+
+        let mut path_secret = [0u8; KEY_SIZE];
+        rand::rng().fill_bytes(&mut path_secret);
+        let mut child = leaf_node(self.leaf);
+        for node in staged.direct_path(child) {
+            path_secret = derive(PATH_CONTEXT, &path_secret);
+            let node_kem = PQKemKeyPair::generate();
+            let sealed_key = self
+                .seal_key(&path_secret)
+                .encrypt(node_kem.decapsulation_key_bytes().as_slice())?;
+
+            let secret = InterfaceKey::from_bytes(path_secret, self.interface_id);
+            let secrets = staged
+                .resolution(sibling(child))
+                .into_iter()
+                .map(|recipient| {
+                    let ek = PQEncapsulationKey::from_bytes(staged.node(recipient).unwrap_or_default())?;
+                    Ok((recipient, secret.encapsulate_for(&ek)?))
+                })
+                .collect::<Result<Vec<_>, CryptoError>>()?;
+
+            commit.path.push(UpdatePathNode {
+                node,
+                encapsulation_key: node_kem.encapsulation_key_bytes(),
+                sealed_key,
+                secrets,
+            });
+            keys.insert(node, node_kem);
+            child = node;
+        }
+
+        apply_path(&mut staged, &commit);
+        self.keys = keys;
+        self.advance(commit.epoch, &path_secret);
+        path_secret.zeroize();
+        *tree = staged;
+        Ok(commit)
+    }
+
+    /// Apply another member's commit
+    ///
+    /// `tree` is only changed if the commit applies cleanly.
+    pub fn process_commit(
+        &mut self,
+        tree: &mut RatchetTree,
+        commit: &TreeCommit,
+    ) -> Result<(), CryptoError> {
+        if commit.epoch != self.epoch + 1 {
+            return Err(CryptoError::KeyExchangeFailed(format!(
+                "commit for epoch {} does not follow epoch {}",
+                commit.epoch, self.epoch
+            )));
+        }
+        if commit.sender == self.leaf {
+            return Err(CryptoError::KeyExchangeFailed(
+                "cannot process our own commit".to_string(),
+            ));
+        }
+        if commit.removes.contains(&self.leaf) {
+            return Err(CryptoError::KeyExchangeFailed(
+                "this member was removed".to_string(),
+            ));
+        }
+
+        let mut staged = tree.clone();
+        staged.apply_membership(commit)?;
+
+        let mine = leaf_node(self.leaf);
+        let start = commit
+            .path
+            .iter()
+            .position(|p| in_subtree(p.node, mine))
+            .ok_or_else(|| CryptoError::KeyExchangeFailed("commit path misses this member".to_string()))?;
+
+        let mut path_secret = self.open_path_secret(&commit.path[start])?;
+        let mut opened = Vec::new();
+        for (i, update) in commit.path[start..].iter().enumerate() {
+            if i > 0 {
+                path_secret = derive(PATH_CONTEXT, &path_secret);
+            }
+            let mut dk = self.seal_key(&path_secret).decrypt(&update.sealed_key)?;
+            let kem = PQKemKeyPair::from_keypair_bytes(&dk, &update.encapsulation_key);
+            dk.zeroize();
+            opened.push((update.node, kem?));
+        }
+
+        apply_path(&mut staged, commit);
+        // Keys for blanked nodes are no longer shared with anyone.
+        self.keys.retain(|&node, _| staged.node(node).is_some());
+        self.keys.extend(opened);
+        self.advance(commit.epoch, &path_secret);
+        path_secret.zeroize();
+        *tree = staged;
+        Ok(())
+    }
+
+    /// Decrypt the path secret for a node using any key this member holds
+    /// for the sibling subtree
+    fn open_path_secret(&self, update: &UpdatePathNode) -> Result<[u8; KEY_SIZE], CryptoError> {
+        let (recipient, encapsulated) = update
+            .secrets
+            .iter()
+            .find(|(recipient, _)| self.keys.contains_key(recipient))
+            .ok_or_else(|| CryptoError::KeyExchangeFailed("no key for this commit".to_string()))?;
+        let secret = InterfaceKey::decapsulate(encapsulated, &self.keys[recipient])?;
+        Ok(*secret.as_bytes())
+    }
+
+    fn seal_key(&self, path_secret: &[u8; KEY_SIZE]) -> InterfaceKey {
+        InterfaceKey::from_bytes(derive(NODE_CONTEXT, path_secret), self.interface_id)
+    }
+
+    fn advance(&mut self, epoch: u64, root_secret: &[u8; KEY_SIZE]) {
+        let mut material = Vec::with_capacity(KEY_SIZE * 2 + 8);
+        material.extend_from_slice(root_secret);
+        material.extend_from_slice(self.interface_id.as_bytes());
+        material.extend_from_slice(&epoch.to_le_bytes());
+        self.epoch_secret = derive(EPOCH_CONTEXT, &material);
+        material.zeroize();
+        self.epoch = epoch;
+    }
+}
+
+impl Drop for TreeKemMember {
+    fn drop(&mut self) {
+        self.epoch_secret.zeroize();
+    }
+}
+
+impl std::fmt::Debug for TreeKemMember {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TreeKemMember")
+            .field("leaf", &self.leaf)
+            .field("epoch", &self.epoch)
+            .field("known_nodes", &self.keys.keys().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Write a commit's refreshed keys into the tree
+fn apply_path(tree: &mut RatchetTree, commit: &TreeCommit) {
+    tree.set_node(leaf_node(commit.sender), Some(commit.leaf_key.clone()));
+    for update in &commit.path {
+        tree.set_node(update.node, Some(update.encapsulation_key.clone()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interface() -> InterfaceId {
+        InterfaceId::new([7u8; 32])
+    }
+
+    /// Build a group of `n` members, each added by member 0 in turn
+    fn group(n: usize) -> (Vec<TreeKemMember>, Vec<RatchetTree>) {
+        let (founder, tree) = TreeKemMember::create(interface(), PQKemKeyPair::generate());
+        let mut members = vec![founder];
+        let mut trees = vec![tree];
+        for _ in 1..n {
+            let kem = PQKemKeyPair::generate();
+            let mut welcome = trees[0].clone();
+            let commit = members[0]
+                .commit(&mut trees[0], &[], vec![kem.encapsulation_key_bytes()])
+                .unwrap();
+            for (member, tree) in members.iter_mut().zip(trees.iter_mut()).skip(1) {
+                member.process_commit(tree, &commit).unwrap();
+            }
+            members.push(TreeKemMember::join(interface(), &mut welcome, &commit, kem).unwrap());
+            trees.push(welcome);
+        }
+        (members, trees)
+    }
+
+    fn assert_agree(members: &[TreeKemMember]) {
+        let key = members[0].interface_key();
+        for member in members {
+            assert_eq!(member.interface_key().as_bytes(), key.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_tree_math() {
+        assert_eq!(parent(0), 1);
+        assert_eq!(parent(2), 1);
+        assert_eq!(parent(1), 3);
+        assert_eq!(parent(5), 3);
+        assert_eq!(sibling(0), 2);
+        assert_eq!(sibling(5), 1);
+        assert!(in_subtree(3, 6));
+        assert!(!in_subtree(1, 4));
+    }
+
+    #[test]
+    fn test_group_agrees_on_key() {
+        let (members, trees) = group(5);
+        assert_agree(&members);
+        assert_eq!(trees[0].member_count(), 5);
+        assert_eq!(trees[0].leaf_capacity(), 8);
+        assert!(trees.iter().all(|t| t == &trees[0]));
+    }
+
+    #[test]
+    fn test_rotation_is_logarithmic() {
+        let (mut members, mut trees) = group(8);
+        // Let every member refresh its path so no node is blank.
+        for i in 0..members.len() {
+            let commit = members[i].commit(&mut trees[i], &[], vec![]).unwrap();
+            for j in (0..members.len()).filter(|&j| j != i) {
+                members[j].process_commit(&mut trees[j], &commit).unwrap();
+            }
+        }
+        assert_agree(&members);
+
+        let commit = members[3].commit(&mut trees[3], &[], vec![]).unwrap();
+        assert_eq!(commit.encapsulation_count(), 3);
+    }
+
+    #[test]
+    fn test_removed_member_is_locked_out() {
+        let (mut members, mut trees) = group(4);
+        let before = members[0].interface_key();
+
+        let commit = members[0].commit(&mut trees[0], &[2], vec![]).unwrap();
+        assert!(members[2].process_commit(&mut trees[2].clone(), &commit).is_err());
+        for j in [1, 3] {
+            members[j].process_commit(&mut trees[j], &commit).unwrap();
+        }
+
+        let removed = members.remove(2);
+        assert_agree(&members);
+        assert_ne!(members[0].interface_key().as_bytes(), before.as_bytes());
+        assert_ne!(removed.interface_key().as_bytes(), members[0].interface_key().as_bytes());
+        assert!(commit.path.iter().all(|p| p.secrets.iter().all(|(r, _)| *r != leaf_node(2))));
+    }
+
+    #[test]
+    fn test_out_of_order_commit_rejected() {
+        let (mut members, mut trees) = group(2);
+        let first = members[0].commit(&mut trees[0], &[], vec![]).unwrap();
+        let second = members[0].commit(&mut trees[0], &[], vec![]).unwrap();
+        let tree_before = trees[1].clone();
+
+        assert!(members[1].process_commit(&mut trees[1], &second).is_err());
+        assert_eq!(trees[1], tree_before);
+        members[1].process_commit(&mut trees[1], &first).unwrap();
+        members[1].process_commit(&mut trees[1], &second).unwrap();
+        assert_agree(&members);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::components::*;
 use crate::layout::compute_layout;
 use crate::runner::document_runner::DocumentRunner;
+use crate::runner::fleet::FleetPoller;
 use crate::runner::{MetricsUpdate, ScenarioRunner};
 use crate::state::*;
 use crate::theme::{SkinSwitcher, ThemedRoot};
@@ -46,6 +47,9 @@ pub fn App() -> Element {
     let mut discovery_state = use_signal(DiscoveryState::new);
     let mut discovery_cancel_token: Signal<Option<Arc<Mutex<bool>>>> = use_signal(|| None);
 
+    // Fleet view state (nodes from INDRAS_FLEET_NODES, if set)
+    let mut fleet_state = use_signal(FleetState::from_env);
+
     // Channel for receiving metrics updates from background task
     let mut cancel_token: Signal<Option<Arc<Mutex<bool>>>> = use_signal(|| None);
 
@@ -301,6 +305,29 @@ pub fn App() -> Element {
         }
     });
 
+    // Fleet poll loop: keeps polling on every tab so alerts stay current
+    let _fleet_poll_loop = use_future(move || async move {
+        let poller = FleetPoller::new();
+        loop {
+            let (should_poll, interval_secs) = {
+                let state = fleet_state.read();
+                (
+                    state.polling && !state.nodes.is_empty(),
+                    state.poll_interval_secs,
+                )
+            };
+
+            if should_poll {
+                let targets = fleet_state.read().targets();
+                let results = poller.poll_all(targets).await;
+                fleet_state.write().record_round(results);
+                tokio::time::sleep(std::time::Duration::from_secs(interval_secs.max(1))).await;
+            } else {
+                tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+            }
+        }
+    });
+
     rsx! {
         ThemedRoot {
             SkinSwitcher {}
@@ -706,6 +733,12 @@ pub fn App() -> Element {
                         }
                     }
                 },
+                Tab::Fleet => rsx! {
+                    // Full-width content for Fleet tab
+                    div { class: "content", style: "padding: 0;",
+                        FleetView { state: fleet_state }
+                    }
+                },
             }
         }
 
@@ -717,6 +750,7 @@ pub fn App() -> Element {
                     &document_state.read(),
                     &sync_engine_state.read(),
                     &discovery_state.read(),
+                    &fleet_state.read(),
                     &metrics(),
                     running(),
                     selected_scenario().as_deref(),
@@ -807,6 +841,14 @@ pub fn App() -> Element {
                                 }
                             }
                         }
+                        Tab::Fleet => {
+                            // Poll every node once
+                            spawn(async move {
+                                let targets = fleet_state.read().targets();
+                                let results = FleetPoller::new().poll_all(targets).await;
+                                fleet_state.write().record_round(results);
+                            });
+                        }
                         _ => {} // No step for SyncEngine/Metrics
                     }
                 },
@@ -993,6 +1035,10 @@ pub fn App() -> Element {
                                 });
                             }
                         }
+                        Tab::Fleet => {
+                            let polling = fleet_state.read().polling;
+                            fleet_state.write().polling = !polling;
+                        }
                     }
                 },
                 on_reset: move |_| {
@@ -1034,6 +1080,9 @@ pub fn App() -> Element {
                             }
                             discovery_state.write().reset();
                         }
+                        Tab::Fleet => {
+                            fleet_state.write().reset();
+                        }
                     }
                 },
                 on_speed_change: move |speed: f64| {
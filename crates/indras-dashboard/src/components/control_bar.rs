@@ -5,6 +5,7 @@
 
 use crate::state::UnifiedPlaybackState;
 use dioxus::prelude::*;
+use dioxus::prelude::Key;
 
 /// Unified control bar that appears at the bottom of the screen
 #[component]
@@ -146,8 +147,57 @@ pub fn UnifiedControlBar(
                             }
                         }
                     }
+
+                    LogFilterControl {}
                 }
             }
         }
     }
 }
+
+/// Text field for the process-wide log filter (`RUST_LOG` syntax).
+///
+/// Applies on Enter or blur; an invalid filter is kept in the field and
+/// marked until it is fixed.
+#[component]
+fn LogFilterControl() -> Element {
+    let mut filter = use_signal(|| indras_logging::current_filter().unwrap_or_default());
+    let mut error = use_signal(|| None::<String>);
+
+    let mut apply = move || {
+        let directives = filter.read().trim().to_string();
+        if directives.is_empty() {
+            return;
+        }
+        match indras_logging::set_filter(&directives) {
+            Ok(()) => error.set(None),
+            Err(e) => error.set(Some(e.to_string())),
+        }
+    };
+
+    let title = error
+        .read()
+        .clone()
+        .unwrap_or_else(|| "Log filter, e.g. info,indras_sync=trace".to_string());
+
+    rsx! {
+        div { class: "log-filter-control",
+            span { class: "log-filter-label", "Log" }
+            input {
+                r#type: "text",
+                class: if error.read().is_some() { "log-filter-input invalid" } else { "log-filter-input" },
+                value: "{filter}",
+                placeholder: "info",
+                title: "{title}",
+                "aria-label": "Log filter",
+                oninput: move |e| filter.set(e.value()),
+                onkeydown: move |e| {
+                    if e.key() == Key::Enter {
+                        apply();
+                    }
+                },
+                onblur: move |_| apply(),
+            }
+        }
+    }
+}
@@ -0,0 +1,430 @@
+//! Fleet Dashboard Components
+//!
+//! Live monitoring of real nodes over their admin API:
+//! - FleetSidebar - node registration and the node list
+//! - FleetOverview - fleet totals and active alerts
+//! - NodeDetail - per-node health and history charts
+//! - ThresholdSettings - alert thresholds and poll interval
+
+use super::charts::LineChart;
+use super::EventLog;
+use crate::state::fleet::{format_bytes, FleetAlert, FleetNode, FleetState, NodeStatus};
+use crate::state::EventType;
+use dioxus::prelude::*;
+
+/// Main Fleet tab view with node list and monitoring content
+/// Polling is started/stopped from the unified bottom control bar
+#[component]
+pub fn FleetView(state: Signal<FleetState>) -> Element {
+    let events = state.read().events.clone();
+    let selected = state.read().selected_node().cloned();
+
+    rsx! {
+        div { class: "fleet-view",
+            FleetSidebar { state }
+
+            main { class: "fleet-main",
+                FleetOverview { state }
+
+                if let Some(node) = selected {
+                    NodeDetail { node }
+                }
+
+                ThresholdSettings { state }
+
+                EventLog { events }
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Sidebar
+// ============================================================================
+
+/// Node registration form and list of monitored nodes
+#[component]
+fn FleetSidebar(state: Signal<FleetState>) -> Element {
+    let mut endpoint = use_signal(String::new);
+    let mut token = use_signal(String::new);
+    let selected = state.read().selected.clone();
+    let nodes: Vec<(FleetNode, bool)> = state
+        .read()
+        .nodes
+        .iter()
+        .map(|node| {
+            (
+                node.clone(),
+                selected.as_deref() == Some(node.endpoint.as_str()),
+            )
+        })
+        .collect();
+
+    let mut add_node = move || {
+        let token_value = token().trim().to_string();
+        let token_value = (!token_value.is_empty()).then_some(token_value);
+        if state.write().add_node(&endpoint(), token_value) {
+            endpoint.set(String::new());
+        }
+    };
+
+    rsx! {
+        aside { class: "fleet-sidebar",
+            div { class: "sidebar-header",
+                h2 { "Node Fleet" }
+                p { class: "sidebar-subtitle", "Nodes polled over their admin API" }
+            }
+
+            div { class: "fleet-add-node",
+                input {
+                    class: "fleet-input",
+                    placeholder: "http://host:port",
+                    value: "{endpoint}",
+                    oninput: move |e| endpoint.set(e.value()),
+                    onkeydown: move |e| {
+                        if e.key() == Key::Enter {
+                            add_node();
+                        }
+                    },
+                }
+                input {
+                    class: "fleet-input",
+                    r#type: "password",
+                    placeholder: "Admin token (for /stats)",
+                    value: "{token}",
+                    oninput: move |e| token.set(e.value()),
+                }
+                button {
+                    class: "fleet-add-btn",
+                    disabled: endpoint().trim().is_empty(),
+                    onclick: move |_| add_node(),
+                    "Add Node"
+                }
+            }
+
+            nav { class: "fleet-node-list",
+                if nodes.is_empty() {
+                    div { class: "empty-state",
+                        p { "No nodes registered" }
+                    }
+                }
+                for (node, selected) in nodes {
+                    FleetNodeCard { key: "{node.endpoint}", node, selected, state }
+                }
+            }
+        }
+    }
+}
+
+/// Card for one node in the sidebar list
+#[component]
+fn FleetNodeCard(node: FleetNode, selected: bool, state: Signal<FleetState>) -> Element {
+    let select_endpoint = node.endpoint.clone();
+    let remove_endpoint = node.endpoint.clone();
+    let summary = match node.stats() {
+        Some(stats) => format!(
+            "{} peers · lag {} · {}",
+            stats.peer_count,
+            node.sync_lag().unwrap_or(0),
+            format_bytes(stats.total_storage_bytes)
+        ),
+        None if node.status == NodeStatus::Up => "health only (no token)".to_string(),
+        None => node
+            .last_error
+            .clone()
+            .unwrap_or_else(|| "waiting for poll".to_string()),
+    };
+
+    rsx! {
+        div {
+            class: if selected { "fleet-node-card selected" } else { "fleet-node-card" },
+            onclick: move |_| state.write().selected = Some(select_endpoint.clone()),
+
+            span { class: "fleet-status-dot status-{node.status.css_class()}" }
+            div { class: "fleet-node-info",
+                div { class: "fleet-node-name", "{node.name()}" }
+                div { class: "fleet-node-summary", "{summary}" }
+            }
+            button {
+                class: "fleet-remove-btn",
+                title: "Stop monitoring this node",
+                onclick: move |e| {
+                    e.stop_propagation();
+                    state.write().remove_node(&remove_endpoint);
+                },
+                "×"
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Overview
+// ============================================================================
+
+/// Fleet totals and the list of active alerts
+#[component]
+fn FleetOverview(state: Signal<FleetState>) -> Element {
+    let fleet = state.read();
+    let total = fleet.nodes.len();
+    let up = fleet
+        .nodes
+        .iter()
+        .filter(|n| n.status == NodeStatus::Up)
+        .count();
+    let peers: usize = fleet
+        .nodes
+        .iter()
+        .filter_map(|n| n.stats())
+        .map(|s| s.peer_count)
+        .sum();
+    let storage: u64 = fleet
+        .nodes
+        .iter()
+        .filter_map(|n| n.stats())
+        .map(|s| s.total_storage_bytes)
+        .sum();
+    let max_lag = fleet
+        .nodes
+        .iter()
+        .filter_map(|n| n.sync_lag())
+        .max()
+        .unwrap_or(0);
+    let alerts = fleet.alerts();
+    let alert_count = alerts.len();
+
+    rsx! {
+        div { class: "fleet-overview",
+            div { class: "metrics-grid",
+                div { class: "metric-card",
+                    h3 { "Nodes Up" }
+                    div { class: "metric-value", "{up} / {total}" }
+                }
+                div { class: "metric-card",
+                    h3 { "Total Peers" }
+                    div { class: "metric-value", "{peers}" }
+                }
+                div { class: "metric-card",
+                    h3 { "Max Sync Lag" }
+                    div { class: "metric-value", "{max_lag}" }
+                }
+                div { class: "metric-card",
+                    h3 { "Total Storage" }
+                    div { class: "metric-value", "{format_bytes(storage)}" }
+                }
+            }
+
+            div { class: "fleet-alerts",
+                h3 { "Active Alerts ({alert_count})" }
+                if alerts.is_empty() {
+                    div { class: "fleet-alert alert-clear", "All nodes within thresholds" }
+                }
+                for alert in alerts {
+                    FleetAlertRow { alert }
+                }
+            }
+        }
+    }
+}
+
+/// One active alert
+#[component]
+fn FleetAlertRow(alert: FleetAlert) -> Element {
+    let class = match alert.severity {
+        EventType::Error => "fleet-alert alert-error",
+        _ => "fleet-alert alert-warning",
+    };
+
+    rsx! {
+        div { class: "{class}", "{alert.message}" }
+    }
+}
+
+// ============================================================================
+// Node Detail
+// ============================================================================
+
+/// Health and history charts for the selected node
+#[component]
+fn NodeDetail(node: FleetNode) -> Element {
+    let uptime = node
+        .latest
+        .as_ref()
+        .map(|report| format_uptime(report.uptime_secs))
+        .unwrap_or_else(|| "—".to_string());
+    let interfaces = node.stats().map(|s| s.interface_count).unwrap_or(0);
+    let events = node.stats().map(|s| s.total_events).unwrap_or(0);
+    let last_seen = node
+        .last_seen
+        .map(|t| t.format("%H:%M:%S").to_string())
+        .unwrap_or_else(|| "never".to_string());
+    let has_history = !node.history.is_empty();
+
+    rsx! {
+        div { class: "fleet-node-detail",
+            div { class: "fleet-detail-header",
+                h2 { "{node.name()}" }
+                p { class: "fleet-detail-subtitle",
+                    "{node.endpoint} · {node.status.label()} · last seen {last_seen}"
+                }
+            }
+
+            div { class: "fleet-node-stats",
+                span { "Uptime: {uptime}" }
+                span { "Interfaces: {interfaces}" }
+                span { "Events: {events}" }
+                span { "Errors: {node.total_errors} ({node.consecutive_failures} in a row)" }
+            }
+
+            if let Some(err) = &node.last_error {
+                div { class: "fleet-alert alert-error", "Last error: {err}" }
+            }
+
+            if has_history {
+                div { class: "fleet-charts",
+                    div { class: "fleet-chart",
+                        h3 { "Peers" }
+                        LineChart {
+                            data: node.series(|s| s.peer_count as f64),
+                            width: 360,
+                            height: 140,
+                            y_min: Some(0.0),
+                            fill: true,
+                        }
+                    }
+                    div { class: "fleet-chart",
+                        h3 { "Sync Lag (events)" }
+                        LineChart {
+                            data: node.series(|s| s.sync_lag as f64),
+                            width: 360,
+                            height: 140,
+                            y_min: Some(0.0),
+                            fill: true,
+                            color: "var(--accent-warning)".to_string(),
+                        }
+                    }
+                    div { class: "fleet-chart",
+                        h3 { "Storage (MiB)" }
+                        LineChart {
+                            data: node.series(|s| s.storage_bytes as f64 / (1024.0 * 1024.0)),
+                            width: 360,
+                            height: 140,
+                            y_min: Some(0.0),
+                            fill: true,
+                            color: "var(--accent-success)".to_string(),
+                        }
+                    }
+                    div { class: "fleet-chart",
+                        h3 { "Events" }
+                        LineChart {
+                            data: node.series(|s| s.total_events as f64),
+                            width: 360,
+                            height: 140,
+                            y_min: Some(0.0),
+                        }
+                    }
+                }
+            } else if node.status == NodeStatus::Up {
+                p { class: "fleet-hint",
+                    "History needs /stats — add the node with its admin token."
+                }
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Settings
+// ============================================================================
+
+/// Editable alert thresholds and poll interval
+#[component]
+fn ThresholdSettings(state: Signal<FleetState>) -> Element {
+    let thresholds = state.read().thresholds.clone();
+    let interval = state.read().poll_interval_secs;
+    let max_storage_mib = thresholds.max_storage_bytes / (1024 * 1024);
+
+    rsx! {
+        div { class: "fleet-thresholds",
+            h3 { "Alert Thresholds" }
+            div { class: "fleet-threshold-grid",
+                label {
+                    "Min peers"
+                    input {
+                        r#type: "number",
+                        min: "0",
+                        value: "{thresholds.min_peers}",
+                        oninput: move |e| {
+                            if let Ok(v) = e.value().parse() {
+                                state.write().thresholds.min_peers = v;
+                            }
+                        },
+                    }
+                }
+                label {
+                    "Max sync lag (events)"
+                    input {
+                        r#type: "number",
+                        min: "0",
+                        value: "{thresholds.max_sync_lag}",
+                        oninput: move |e| {
+                            if let Ok(v) = e.value().parse() {
+                                state.write().thresholds.max_sync_lag = v;
+                            }
+                        },
+                    }
+                }
+                label {
+                    "Max storage (MiB)"
+                    input {
+                        r#type: "number",
+                        min: "1",
+                        value: "{max_storage_mib}",
+                        oninput: move |e| {
+                            if let Ok(v) = e.value().parse::<u64>() {
+                                state.write().thresholds.max_storage_bytes = v * 1024 * 1024;
+                            }
+                        },
+                    }
+                }
+                label {
+                    "Failed polls before alert"
+                    input {
+                        r#type: "number",
+                        min: "1",
+                        value: "{thresholds.max_consecutive_errors}",
+                        oninput: move |e| {
+                            if let Ok(v) = e.value().parse::<u32>() {
+                                state.write().thresholds.max_consecutive_errors = v.max(1);
+                            }
+                        },
+                    }
+                }
+                label {
+                    "Poll interval (s)"
+                    input {
+                        r#type: "number",
+                        min: "1",
+                        value: "{interval}",
+                        oninput: move |e| {
+                            if let Ok(v) = e.value().parse::<u64>() {
+                                state.write().poll_interval_secs = v.max(1);
+                            }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn format_uptime(secs: u64) -> String {
+    let (days, hours, minutes) = (secs / 86_400, (secs % 86_400) / 3600, (secs % 3600) / 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m {}s", minutes, secs % 60)
+    }
+}
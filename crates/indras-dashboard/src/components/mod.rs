@@ -9,12 +9,14 @@ pub mod charts;
 pub mod control_bar;
 pub mod discovery;
 pub mod documents;
+pub mod fleet;
 pub mod panels;
 pub mod sync_engine;
 
 pub use control_bar::UnifiedControlBar;
 pub use discovery::DiscoveryView;
 pub use documents::DocumentsView;
+pub use fleet::FleetView;
 pub use sync_engine::SyncEngineView;
 
 /// Header component with dashboard title
@@ -372,6 +374,29 @@ pub fn MetricsPanel(metrics: SimMetrics) -> Element {
                             }
                         }
                     }
+
+                    if metrics.avg_link_delay_ms > 0.0 {
+                        div {
+                            class: "metric-card",
+                            h3 { "Avg Link Delay" }
+                            div {
+                                class: "metric-value",
+                                "{metrics.avg_link_delay_ms:.1} ms"
+                            }
+                        }
+                    }
+
+                    if metrics.link_losses > 0 {
+                        div {
+                            class: "metric-card",
+                            h3 { "Link Losses" }
+                            div {
+                                class: "metric-value",
+                                style: "color: var(--accent-warning);",
+                                "{metrics.link_losses}"
+                            }
+                        }
+                    }
                 }
 
                 // Show placeholder if no metrics yet
@@ -454,7 +479,7 @@ fn format_event_type(event_type: &EventType) -> &'static str {
 // Tab Navigation Components
 // ============================================================================
 
-/// Tab bar for switching between Metrics, Simulations, Documents, SyncEngine, and Discovery views
+/// Tab bar for switching between Metrics, Simulations, Documents, SyncEngine, Discovery, and Fleet views
 #[component]
 pub fn TabBar(current_tab: Tab, on_select: EventHandler<Tab>) -> Element {
     rsx! {
@@ -484,6 +509,11 @@ pub fn TabBar(current_tab: Tab, on_select: EventHandler<Tab>) -> Element {
                 onclick: move |_| on_select.call(Tab::Discovery),
                 "Discovery"
             }
+            button {
+                class: if current_tab == Tab::Fleet { "tab-btn active" } else { "tab-btn" },
+                onclick: move |_| on_select.call(Tab::Fleet),
+                "Fleet"
+            }
         }
     }
 }
@@ -25,8 +25,12 @@ fn load_icon() -> Option<Icon> {
 }
 
 fn main() {
-    // Initialize tracing for logging
-    tracing_subscriber::fmt::init();
+    // Initialize tracing through indras-logging so the control bar can
+    // change the filter at runtime
+    let _guard = indras_logging::IndrasSubscriberBuilder::new()
+        .with_config(indras_logging::LogConfig::development())
+        .with_level("info")
+        .init();
 
     // Load the app icon
     let icon = load_icon();
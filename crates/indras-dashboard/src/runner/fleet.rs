@@ -0,0 +1,126 @@
+//! Fleet poller
+//!
+//! Polls each node's admin API (`GET /health`, and `GET /stats` when a
+//! bearer token is configured) and turns the responses into `NodeReport`s.
+
+use crate::state::fleet::{NodeReport, NodeStats};
+use serde::Deserialize;
+use std::time::Duration;
+
+/// Per-request timeout; a slow node counts as a failed poll
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// `GET /health` response body
+#[derive(Debug, Deserialize)]
+struct HealthResponse {
+    status: String,
+    uptime_secs: u64,
+    #[serde(default)]
+    display_name: String,
+}
+
+/// `GET /stats` response body
+#[derive(Debug, Deserialize)]
+struct StatsResponse {
+    peer_count: usize,
+    interface_count: usize,
+    total_events: usize,
+    total_storage_bytes: u64,
+}
+
+/// Polls node admin endpoints
+pub struct FleetPoller {
+    client: reqwest::Client,
+}
+
+impl FleetPoller {
+    /// Creates a poller with the default request timeout
+    pub fn new() -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Self { client }
+    }
+
+    /// Polls every target once, returning one result per endpoint
+    pub async fn poll_all(
+        &self,
+        targets: Vec<(String, Option<String>)>,
+    ) -> Vec<(String, Result<NodeReport, String>)> {
+        let mut handles = Vec::with_capacity(targets.len());
+        for (endpoint, token) in targets {
+            let client = self.client.clone();
+            handles.push(tokio::spawn(async move {
+                let result = poll_node(&client, &endpoint, token.as_deref()).await;
+                (endpoint, result)
+            }));
+        }
+
+        let mut results = Vec::with_capacity(handles.len());
+        for handle in handles {
+            if let Ok(result) = handle.await {
+                results.push(result);
+            }
+        }
+        results
+    }
+}
+
+impl Default for FleetPoller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Polls one node
+///
+/// The health check must succeed and report `ok`. Stats are only fetched
+/// with a token; without one the node is reported as up with no stats.
+async fn poll_node(
+    client: &reqwest::Client,
+    endpoint: &str,
+    token: Option<&str>,
+) -> Result<NodeReport, String> {
+    let health: HealthResponse = client
+        .get(format!("{}/health", endpoint))
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| format!("health check failed: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("invalid health response: {}", e))?;
+
+    if health.status != "ok" {
+        return Err(format!("node reports status '{}'", health.status));
+    }
+
+    let stats = match token {
+        Some(token) => {
+            let stats: StatsResponse = client
+                .get(format!("{}/stats", endpoint))
+                .bearer_auth(token)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .map_err(|e| format!("stats request failed: {}", e))?
+                .json()
+                .await
+                .map_err(|e| format!("invalid stats response: {}", e))?;
+            Some(NodeStats {
+                peer_count: stats.peer_count,
+                interface_count: stats.interface_count,
+                total_events: stats.total_events,
+                total_storage_bytes: stats.total_storage_bytes,
+            })
+        }
+        None => None,
+    };
+
+    Ok(NodeReport {
+        display_name: health.display_name,
+        uptime_secs: health.uptime_secs,
+        stats,
+    })
+}
@@ -1,4 +1,5 @@
 pub mod document_runner;
+pub mod fleet;
 
 use crate::state::{EventType, SimEvent, SimMetrics, StressLevel, TestResult};
 use chrono::Utc;
@@ -213,12 +214,20 @@ impl ScenarioRunner {
                     name: "prophet_stress.lua",
                     description: "Tests PRoPHET probabilistic routing with encounter history, transitive probability, and decay verification.",
                 },
+                ScenarioInfo {
+                    name: "link_profiles.lua",
+                    description: "Compares delivery under datacenter, broadband, rural LTE, and satellite link presets, plus geographic propagation delay.",
+                },
             ]),
             ("Resilience", vec![
                 ScenarioInfo {
                     name: "chaos_monkey.lua",
                     description: "General chaos testing with random peer failures, network disruptions, and recovery verification.",
                 },
+                ScenarioInfo {
+                    name: "adversarial_peers.lua",
+                    description: "Runs replaying, equivocating, ack-dropping, and spamming peers against honest ones and verifies each is detected.",
+                },
                 ScenarioInfo {
                     name: "hub_failure.lua",
                     description: "Tests network resilience when hub nodes fail, verifying alternate route discovery.",
@@ -627,6 +636,8 @@ fn has_metrics_fields(fields: &serde_json::Value) -> bool {
     // Routing/messaging metrics
     fields.get("messages_sent").is_some()
         || fields.get("delivery_rate").is_some()
+        || fields.get("link_losses").is_some()
+        || fields.get("avg_link_delay_ms").is_some()
         // PQ signature metrics
         || fields.get("total_signatures_created").is_some()
         || fields.get("pq_signatures_created").is_some()
@@ -696,6 +707,14 @@ fn extract_metrics(fields: &serde_json::Value, tick: u64) -> SimMetrics {
             .get("avg_hops")
             .and_then(|v| v.as_f64())
             .unwrap_or(0.0),
+        link_losses: fields
+            .get("link_losses")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0),
+        avg_link_delay_ms: fields
+            .get("avg_link_delay_ms")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.0),
         backprops_completed: fields
             .get("backprops_completed")
             .and_then(|v| v.as_u64())
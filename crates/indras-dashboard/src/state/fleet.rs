@@ -0,0 +1,460 @@
+//! Fleet state for the Fleet dashboard tab.
+//!
+//! Tracks a set of real nodes polled over their admin API: the latest
+//! health of each node, a bounded history of samples for charts, and the
+//! alert thresholds the health is checked against.
+
+use crate::state::{DataPoint, EventType, SimEvent};
+use chrono::{DateTime, Utc};
+
+/// Environment variable listing node admin endpoints, comma-separated
+pub const FLEET_NODES_ENV: &str = "INDRAS_FLEET_NODES";
+
+/// Environment variable holding the bearer token for fleet endpoints
+pub const FLEET_TOKEN_ENV: &str = "INDRAS_FLEET_TOKEN";
+
+/// Samples kept per node for the history charts
+const MAX_SAMPLES: usize = 120;
+
+/// Events kept in the fleet event log
+const MAX_EVENTS: usize = 500;
+
+/// Reachability of a node as of the last poll
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NodeStatus {
+    /// Not polled yet
+    #[default]
+    Unknown,
+    /// Last poll succeeded
+    Up,
+    /// Last poll failed
+    Down,
+}
+
+impl NodeStatus {
+    /// Get display label for the status
+    pub fn label(&self) -> &'static str {
+        match self {
+            NodeStatus::Unknown => "Unknown",
+            NodeStatus::Up => "Up",
+            NodeStatus::Down => "Down",
+        }
+    }
+
+    /// Get CSS class suffix for the status
+    pub fn css_class(&self) -> &'static str {
+        match self {
+            NodeStatus::Unknown => "unknown",
+            NodeStatus::Up => "up",
+            NodeStatus::Down => "down",
+        }
+    }
+}
+
+/// What one successful poll of a node returned
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NodeReport {
+    /// Name the node reports for itself
+    pub display_name: String,
+    /// Seconds since the node started
+    pub uptime_secs: u64,
+    /// Stats, if the endpoint accepted our token
+    pub stats: Option<NodeStats>,
+}
+
+/// Counters from the node's `/stats` endpoint
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NodeStats {
+    pub peer_count: usize,
+    pub interface_count: usize,
+    pub total_events: usize,
+    pub total_storage_bytes: u64,
+}
+
+/// One point in a node's history
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeSample {
+    /// Poll round the sample was taken in
+    pub round: u64,
+    pub peer_count: usize,
+    pub total_events: usize,
+    pub storage_bytes: u64,
+    /// Events behind the most advanced node in the same round
+    pub sync_lag: u64,
+}
+
+/// A node being monitored
+#[derive(Clone, Debug, PartialEq)]
+pub struct FleetNode {
+    /// Admin API base URL, e.g. `http://10.0.0.5:9090`
+    pub endpoint: String,
+    /// Bearer token for the authenticated endpoints
+    pub token: Option<String>,
+    pub status: NodeStatus,
+    /// Latest successful report
+    pub latest: Option<NodeReport>,
+    /// Bounded history of samples, oldest first
+    pub history: Vec<NodeSample>,
+    /// Failed polls since the last success
+    pub consecutive_failures: u32,
+    /// Failed polls since monitoring started
+    pub total_errors: u64,
+    pub last_error: Option<String>,
+    pub last_seen: Option<DateTime<Utc>>,
+}
+
+impl FleetNode {
+    /// Create a node that has not been polled yet
+    pub fn new(endpoint: impl Into<String>, token: Option<String>) -> Self {
+        Self {
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+            token,
+            status: NodeStatus::Unknown,
+            latest: None,
+            history: Vec::new(),
+            consecutive_failures: 0,
+            total_errors: 0,
+            last_error: None,
+            last_seen: None,
+        }
+    }
+
+    /// Name to show for the node: its reported name, else the endpoint
+    pub fn name(&self) -> &str {
+        self.latest
+            .as_ref()
+            .map(|report| report.display_name.as_str())
+            .filter(|name| !name.is_empty())
+            .unwrap_or(&self.endpoint)
+    }
+
+    /// Latest stats, if any poll has returned them
+    pub fn stats(&self) -> Option<&NodeStats> {
+        self.latest
+            .as_ref()
+            .and_then(|report| report.stats.as_ref())
+    }
+
+    /// Sync lag from the most recent sample
+    pub fn sync_lag(&self) -> Option<u64> {
+        self.history.last().map(|sample| sample.sync_lag)
+    }
+
+    /// History of one sample field as chart points
+    pub fn series(&self, field: impl Fn(&NodeSample) -> f64) -> Vec<DataPoint> {
+        self.history
+            .iter()
+            .map(|sample| DataPoint {
+                x: sample.round as f64,
+                y: field(sample),
+            })
+            .collect()
+    }
+
+    fn clear_history(&mut self) {
+        self.status = NodeStatus::Unknown;
+        self.latest = None;
+        self.history.clear();
+        self.consecutive_failures = 0;
+        self.total_errors = 0;
+        self.last_error = None;
+        self.last_seen = None;
+    }
+}
+
+/// Limits a node's health is checked against
+#[derive(Clone, Debug, PartialEq)]
+pub struct AlertThresholds {
+    /// Alert when a node has fewer peers than this
+    pub min_peers: usize,
+    /// Alert when a node is more than this many events behind
+    pub max_sync_lag: u64,
+    /// Alert when a node stores more than this many bytes
+    pub max_storage_bytes: u64,
+    /// Alert after this many failed polls in a row
+    pub max_consecutive_errors: u32,
+}
+
+impl Default for AlertThresholds {
+    fn default() -> Self {
+        Self {
+            min_peers: 1,
+            max_sync_lag: 100,
+            max_storage_bytes: 1024 * 1024 * 1024,
+            max_consecutive_errors: 3,
+        }
+    }
+}
+
+/// Which threshold an alert crossed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AlertKind {
+    Unreachable,
+    LowPeers,
+    SyncLag,
+    Storage,
+}
+
+/// A threshold currently crossed by a node
+#[derive(Clone, Debug, PartialEq)]
+pub struct FleetAlert {
+    /// Endpoint of the node the alert is for
+    pub endpoint: String,
+    pub kind: AlertKind,
+    pub severity: EventType,
+    pub message: String,
+}
+
+/// State for the Fleet tab
+#[derive(Clone, Debug, Default)]
+pub struct FleetState {
+    pub nodes: Vec<FleetNode>,
+    pub thresholds: AlertThresholds,
+    /// Whether the background poller is running
+    pub polling: bool,
+    /// Seconds between poll rounds
+    pub poll_interval_secs: u64,
+    /// Completed poll rounds
+    pub rounds: u64,
+    /// Endpoint whose charts are shown
+    pub selected: Option<String>,
+    /// Status changes and raised alerts, oldest first
+    pub events: Vec<SimEvent>,
+}
+
+impl FleetState {
+    /// Create an empty fleet
+    pub fn new() -> Self {
+        Self {
+            poll_interval_secs: 5,
+            ..Default::default()
+        }
+    }
+
+    /// Create a fleet from `INDRAS_FLEET_NODES` and `INDRAS_FLEET_TOKEN`
+    ///
+    /// Polling starts immediately when any nodes are configured.
+    pub fn from_env() -> Self {
+        let mut state = Self::new();
+        let token = std::env::var(FLEET_TOKEN_ENV)
+            .ok()
+            .filter(|t| !t.is_empty());
+        if let Ok(endpoints) = std::env::var(FLEET_NODES_ENV) {
+            for endpoint in endpoints.split(',').map(str::trim) {
+                state.add_node(endpoint, token.clone());
+            }
+        }
+        state.polling = !state.nodes.is_empty();
+        state
+    }
+
+    /// Register a node; ignores empty and duplicate endpoints
+    pub fn add_node(&mut self, endpoint: &str, token: Option<String>) -> bool {
+        let node = FleetNode::new(endpoint.trim(), token);
+        if node.endpoint.is_empty() || self.node(&node.endpoint).is_some() {
+            return false;
+        }
+        if self.selected.is_none() {
+            self.selected = Some(node.endpoint.clone());
+        }
+        self.nodes.push(node);
+        true
+    }
+
+    /// Stop monitoring a node
+    pub fn remove_node(&mut self, endpoint: &str) {
+        self.nodes.retain(|node| node.endpoint != endpoint);
+        if self.selected.as_deref() == Some(endpoint) {
+            self.selected = self.nodes.first().map(|node| node.endpoint.clone());
+        }
+    }
+
+    /// Look up a node by endpoint
+    pub fn node(&self, endpoint: &str) -> Option<&FleetNode> {
+        self.nodes.iter().find(|node| node.endpoint == endpoint)
+    }
+
+    /// Node whose charts are shown
+    pub fn selected_node(&self) -> Option<&FleetNode> {
+        self.selected
+            .as_deref()
+            .and_then(|endpoint| self.node(endpoint))
+    }
+
+    /// Endpoints and tokens to poll this round
+    pub fn targets(&self) -> Vec<(String, Option<String>)> {
+        self.nodes
+            .iter()
+            .map(|node| (node.endpoint.clone(), node.token.clone()))
+            .collect()
+    }
+
+    /// Apply the results of one poll round
+    ///
+    /// `results` is keyed by endpoint; nodes removed while the round was in
+    /// flight are skipped. Sync lag is measured against the node with the
+    /// most events this round.
+    pub fn record_round(&mut self, results: Vec<(String, Result<NodeReport, String>)>) {
+        let alerts_before = self.alerts();
+        self.rounds += 1;
+        let round = self.rounds;
+        let now = Utc::now();
+
+        let fleet_max_events = results
+            .iter()
+            .filter_map(|(_, result)| result.as_ref().ok()?.stats.as_ref())
+            .map(|stats| stats.total_events)
+            .max()
+            .unwrap_or(0);
+
+        let mut events = Vec::new();
+        for (endpoint, result) in results {
+            let Some(node) = self.nodes.iter_mut().find(|n| n.endpoint == endpoint) else {
+                continue;
+            };
+            let previous = node.status;
+
+            match result {
+                Ok(report) => {
+                    if let Some(stats) = &report.stats {
+                        node.history.push(NodeSample {
+                            round,
+                            peer_count: stats.peer_count,
+                            total_events: stats.total_events,
+                            storage_bytes: stats.total_storage_bytes,
+                            sync_lag: (fleet_max_events - stats.total_events) as u64,
+                        });
+                        if node.history.len() > MAX_SAMPLES {
+                            node.history.remove(0);
+                        }
+                    }
+                    node.latest = Some(report);
+                    node.status = NodeStatus::Up;
+                    node.consecutive_failures = 0;
+                    node.last_seen = Some(now);
+                    if previous == NodeStatus::Down {
+                        events.push((
+                            EventType::Success,
+                            format!("{} is reachable again", node.name()),
+                        ));
+                    }
+                }
+                Err(err) => {
+                    node.status = NodeStatus::Down;
+                    node.consecutive_failures += 1;
+                    node.total_errors += 1;
+                    if previous != NodeStatus::Down {
+                        events.push((EventType::Warning, format!("{}: {}", node.name(), err)));
+                    }
+                    node.last_error = Some(err);
+                }
+            }
+        }
+
+        for alert in self.alerts() {
+            let already_raised = alerts_before
+                .iter()
+                .any(|a| a.endpoint == alert.endpoint && a.kind == alert.kind);
+            if !already_raised {
+                events.push((alert.severity, alert.message));
+            }
+        }
+
+        for (event_type, description) in events {
+            self.add_event(event_type, description);
+        }
+    }
+
+    /// Thresholds currently crossed across the fleet
+    pub fn alerts(&self) -> Vec<FleetAlert> {
+        let limits = &self.thresholds;
+        let mut alerts = Vec::new();
+
+        for node in &self.nodes {
+            let mut raise = |kind, severity, message: String| {
+                alerts.push(FleetAlert {
+                    endpoint: node.endpoint.clone(),
+                    kind,
+                    severity,
+                    message: format!("{}: {}", node.name(), message),
+                });
+            };
+
+            if node.consecutive_failures >= limits.max_consecutive_errors {
+                raise(
+                    AlertKind::Unreachable,
+                    EventType::Error,
+                    format!("{} failed polls in a row", node.consecutive_failures),
+                );
+            }
+            if node.status != NodeStatus::Up {
+                continue;
+            }
+            if let Some(stats) = node.stats() {
+                if stats.peer_count < limits.min_peers {
+                    raise(
+                        AlertKind::LowPeers,
+                        EventType::Warning,
+                        format!("{} peers (min {})", stats.peer_count, limits.min_peers),
+                    );
+                }
+                if stats.total_storage_bytes > limits.max_storage_bytes {
+                    raise(
+                        AlertKind::Storage,
+                        EventType::Warning,
+                        format!(
+                            "storage {} over {}",
+                            format_bytes(stats.total_storage_bytes),
+                            format_bytes(limits.max_storage_bytes)
+                        ),
+                    );
+                }
+            }
+            if let Some(lag) = node.sync_lag().filter(|lag| *lag > limits.max_sync_lag) {
+                raise(
+                    AlertKind::SyncLag,
+                    EventType::Warning,
+                    format!("{} events behind (max {})", lag, limits.max_sync_lag),
+                );
+            }
+        }
+
+        alerts
+    }
+
+    fn add_event(&mut self, event_type: EventType, description: String) {
+        self.events.push(SimEvent {
+            tick: self.rounds,
+            event_type,
+            description,
+        });
+        if self.events.len() > MAX_EVENTS {
+            self.events.remove(0);
+        }
+    }
+
+    /// Clear history and counters, keeping the registered nodes
+    pub fn reset(&mut self) {
+        for node in &mut self.nodes {
+            node.clear_history();
+        }
+        self.rounds = 0;
+        self.events.clear();
+    }
+}
+
+/// Format a byte count for display
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
@@ -319,5 +319,16 @@ pub fn format_network_event(event: &NetworkEvent) -> (String, String, String) {
             format!("{}", tick),
             format!("{} invite failed: {}", peer, reason),
         ),
+        NetworkEvent::Misbehavior {
+            suspect,
+            observer,
+            kind,
+            tick,
+            ..
+        } => (
+            "error".to_string(),
+            format!("{}", tick),
+            format!("{} caught {} misbehaving: {}", observer, suspect, kind),
+        ),
     }
 }
@@ -3,12 +3,14 @@ use serde::{Deserialize, Serialize};
 
 pub mod discovery;
 pub mod document;
+pub mod fleet;
 pub mod instance;
 pub mod sync_engine;
 pub mod unified;
 
 pub use discovery::DiscoveryState;
 pub use document::DocumentState;
+pub use fleet::FleetState;
 pub use instance::{format_network_event, InstanceState, PacketAnimation};
 pub use sync_engine::SyncEngineState;
 pub use unified::UnifiedPlaybackState;
@@ -194,6 +196,8 @@ pub enum Tab {
     SyncEngine,
     /// Discovery scenario dashboards
     Discovery,
+    /// Live monitoring of real nodes
+    Fleet,
 }
 
 /// Event severity/type for display purposes
@@ -219,6 +223,10 @@ pub struct SimMetrics {
     pub avg_latency_ticks: u64,
     pub avg_hops: f64,
 
+    // Link model statistics
+    pub link_losses: u64,
+    pub avg_link_delay_ms: f64,
+
     // Backpropagation statistics
     pub backprops_completed: u64,
     pub backprops_timed_out: u64,
@@ -330,6 +338,10 @@ impl SimMetrics {
         merge_field!(avg_latency_ticks);
         merge_float!(avg_hops);
 
+        // Link model
+        merge_field!(link_losses);
+        merge_float!(avg_link_delay_ms);
+
         // Backpropagation
         merge_field!(backprops_completed);
         merge_field!(backprops_timed_out);
@@ -2,7 +2,7 @@
 //!
 //! Provides a common interface across all tab types for the unified control bar.
 
-use super::{DiscoveryState, DocumentState, InstanceState, SyncEngineState, SimMetrics, Tab};
+use super::{DiscoveryState, DocumentState, FleetState, InstanceState, SyncEngineState, SimMetrics, Tab};
 
 /// Which context is currently active
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
@@ -14,6 +14,7 @@ pub enum ActiveContext {
     SyncEngine,
     Metrics,
     Discovery,
+    Fleet,
 }
 
 impl ActiveContext {
@@ -26,6 +27,7 @@ impl ActiveContext {
             ActiveContext::SyncEngine => "SyncEngine Stress Test",
             ActiveContext::Metrics => "Stress Test",
             ActiveContext::Discovery => "Discovery Test",
+            ActiveContext::Fleet => "Node Fleet",
         }
     }
 
@@ -38,6 +40,7 @@ impl ActiveContext {
             ActiveContext::SyncEngine => "⚡",
             ActiveContext::Metrics => "📊",
             ActiveContext::Discovery => "🔍",
+            ActiveContext::Fleet => "🛰",
         }
     }
 }
@@ -197,6 +200,30 @@ impl UnifiedPlaybackState {
         }
     }
 
+    /// Create state from the Fleet tab
+    ///
+    /// Play/pause toggles polling, step polls once, reset clears history.
+    pub fn from_fleet(state: &FleetState) -> Self {
+        let has_nodes = !state.nodes.is_empty();
+
+        Self {
+            context: ActiveContext::Fleet,
+            context_name: format!("{} nodes", state.nodes.len()),
+            is_active: has_nodes,
+            is_running: has_nodes && state.polling,
+            is_paused: has_nodes && !state.polling,
+            current_tick: state.rounds,
+            max_ticks: 0,
+            playback_speed: 1.0,
+            can_step: has_nodes && !state.polling,
+            can_play: has_nodes,
+            can_reset: state.rounds > 0,
+            has_speed_control: false,
+            has_stress_control: false,
+            stress_level: String::new(),
+        }
+    }
+
     /// Get the current state based on active tab
     pub fn from_tab(
         tab: Tab,
@@ -204,6 +231,7 @@ impl UnifiedPlaybackState {
         document_state: &DocumentState,
         sync_engine_state: &SyncEngineState,
         discovery_state: &DiscoveryState,
+        fleet_state: &FleetState,
         metrics: &SimMetrics,
         metrics_running: bool,
         metrics_scenario: Option<&str>,
@@ -214,6 +242,7 @@ impl UnifiedPlaybackState {
             Tab::Documents => Self::from_documents(document_state),
             Tab::SyncEngine => Self::from_sync_engine(sync_engine_state),
             Tab::Discovery => Self::from_discovery(discovery_state),
+            Tab::Fleet => Self::from_fleet(fleet_state),
             Tab::Metrics => Self::from_metrics(metrics, metrics_running, metrics_scenario, metrics_stress_level),
         }
     }
@@ -10,6 +10,7 @@ use indras_sync_engine::{HomeRealmIntentions, HomeRealmNotes};
 use crate::state::{AsyncStatus, ContactView, ContactSentiment, EventDirection, EventLogEntry, GenesisState, GenesisStep, NoteView, IntentionAttentionView, IntentionClaimView, IntentionStatus, IntentionView};
 use indras_ui::{ArtifactDisplayInfo, ArtifactDisplayStatus, ContactInviteOverlay, ThemedRoot};
 
+use super::device_pairing::{LinkDeviceScreen, PairDeviceScreen};
 use super::display_name::DisplayNameScreen;
 use super::home_realm::HomeRealmScreen;
 use super::note_editor::NoteEditorOverlay;
@@ -149,8 +150,8 @@ pub fn App() -> Element {
                                             claimant_name: None,
                                             verified: c.verified,
                                             has_proof: c.has_proof(),
-                                            submitted_at: chrono::DateTime::from_timestamp_millis(c.submitted_at_millis)
-                                                .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+                                            submitted_at: u64::try_from(c.submitted_at_millis)
+                                                .map(indras_ui::format_datetime)
                                                 .unwrap_or_default(),
                                         }
                                     }).collect();
@@ -355,6 +356,12 @@ pub fn App() -> Element {
                     GenesisStep::PeerRealm(peer_id) => rsx! {
                         PeerRealmScreen { state, network, peer_id }
                     },
+                    GenesisStep::PairDevice => rsx! {
+                        PairDeviceScreen { state, network }
+                    },
+                    GenesisStep::LinkDevice => rsx! {
+                        LinkDeviceScreen { state, network }
+                    },
                 }
 
                 if pass_story_active {
@@ -556,8 +563,8 @@ pub async fn create_identity_and_load(
                                 claimant_name: None,
                                 verified: c.verified,
                                 has_proof: c.has_proof(),
-                                submitted_at: chrono::DateTime::from_timestamp_millis(c.submitted_at_millis)
-                                    .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+                                submitted_at: u64::try_from(c.submitted_at_millis)
+                                    .map(indras_ui::format_datetime)
                                     .unwrap_or_default(),
                             }
                         }).collect();
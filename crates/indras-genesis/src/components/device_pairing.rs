@@ -0,0 +1,514 @@
+//! Device pairing screens - link a second device to an identity.
+//!
+//! The existing device shows a pairing code and QR; the new device enters
+//! the code, receives the identity, restarts with it, and both sides wait
+//! until they see the same home realm before finishing.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use dioxus::prelude::*;
+use indras_network::{IndrasNetwork, PairingCode};
+
+use crate::state::{EventDirection, GenesisState, GenesisStep, PairingPhase};
+
+use super::app::{default_data_dir, log_event};
+use super::home_realm::refresh_home_realm_data;
+
+/// How long the existing device waits for the new device to enter the code.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// How long either side waits for the identity or the home realm to sync.
+const SYNC_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Checklist shown on the existing device.
+const PAIR_STEPS: &[(PairingPhase, &str)] = &[
+    (PairingPhase::Connecting, "Waiting for the new device"),
+    (PairingPhase::TransferringIdentity, "Sending your identity"),
+    (
+        PairingPhase::VerifyingHomeRealm,
+        "Checking both devices see your home realm",
+    ),
+];
+
+/// Checklist shown on the new device.
+const LINK_STEPS: &[(PairingPhase, &str)] = &[
+    (PairingPhase::Connecting, "Connecting to your other device"),
+    (
+        PairingPhase::TransferringIdentity,
+        "Receiving your identity",
+    ),
+    (PairingPhase::Restarting, "Restarting with your identity"),
+    (
+        PairingPhase::VerifyingHomeRealm,
+        "Checking your home realm is here",
+    ),
+];
+
+/// Record a failed pairing.
+fn fail(state: &mut Signal<GenesisState>, message: String) {
+    tracing::error!("Device pairing failed: {}", message);
+    log_event(state, EventDirection::System, format!("ERROR: {}", message));
+    state.write().pairing_phase = PairingPhase::Failed(message);
+}
+
+/// Run the existing-device side: show a code, grant, wait for confirmation.
+async fn run_pairing(mut state: Signal<GenesisState>, network: Signal<Option<Arc<IndrasNetwork>>>) {
+    let net = {
+        let guard = network.read();
+        guard.as_ref().cloned()
+    };
+    let Some(net) = net else {
+        fail(&mut state, "Network is not running".to_string());
+        return;
+    };
+
+    {
+        let mut s = state.write();
+        s.pairing_phase = PairingPhase::Connecting;
+        s.pairing_code = None;
+        s.pairing_qr_svg = None;
+        s.pairing_peer_device = None;
+    }
+
+    let pairing = match net.start_device_pairing().await {
+        Ok(pairing) => pairing,
+        Err(e) => {
+            fail(&mut state, format!("Could not start pairing: {}", e));
+            return;
+        }
+    };
+    let code = pairing.code().clone();
+    {
+        let mut s = state.write();
+        s.pairing_code = Some(code.encode());
+        s.pairing_qr_svg = code.to_qr_svg().ok();
+    }
+    log_event(
+        &mut state,
+        EventDirection::System,
+        "Pairing code ready, waiting for new device...",
+    );
+
+    let request = match pairing.wait_for_request(REQUEST_TIMEOUT).await {
+        Ok(request) => request,
+        Err(e) => {
+            fail(&mut state, format!("No device joined: {}", e));
+            return;
+        }
+    };
+    {
+        let mut s = state.write();
+        s.pairing_peer_device = Some(request.device_name.clone());
+        s.pairing_phase = PairingPhase::TransferringIdentity;
+    }
+    log_event(
+        &mut state,
+        EventDirection::Received,
+        format!("Device '{}' connected", request.device_name),
+    );
+
+    if let Err(e) = net.grant_device_pairing(&pairing, &request).await {
+        fail(&mut state, format!("Could not send identity: {}", e));
+        return;
+    }
+    log_event(
+        &mut state,
+        EventDirection::Sent,
+        format!("Identity sent to '{}'", request.device_name),
+    );
+    state.write().pairing_phase = PairingPhase::VerifyingHomeRealm;
+
+    if let Err(e) = net
+        .wait_for_device_link(request.device_id, SYNC_TIMEOUT)
+        .await
+    {
+        fail(
+            &mut state,
+            format!("New device never reached the home realm: {}", e),
+        );
+        return;
+    }
+    let _ = net.finish_device_pairing(pairing).await;
+
+    log_event(
+        &mut state,
+        EventDirection::Received,
+        format!("'{}' confirmed your home realm", request.device_name),
+    );
+    state.write().pairing_phase = PairingPhase::Complete;
+}
+
+/// Run the new-device side: request, receive identity, restart, verify.
+async fn run_link(
+    mut state: Signal<GenesisState>,
+    mut network: Signal<Option<Arc<IndrasNetwork>>>,
+) {
+    let (code_input, device_name) = {
+        let s = state.read();
+        (
+            s.pairing_code_input.clone(),
+            s.pairing_device_name.trim().to_string(),
+        )
+    };
+    let code = match PairingCode::parse(&code_input) {
+        Ok(code) => code,
+        Err(_) => {
+            fail(
+                &mut state,
+                "Invalid pairing code. Paste the indrapair1... code from your other device."
+                    .to_string(),
+            );
+            return;
+        }
+    };
+    let device_name = if device_name.is_empty() {
+        "New device".to_string()
+    } else {
+        device_name
+    };
+
+    let data_dir = default_data_dir();
+    let _ = std::fs::create_dir_all(&data_dir);
+
+    {
+        let mut s = state.write();
+        s.pairing_phase = PairingPhase::Connecting;
+        s.pairing_peer_device = None;
+    }
+    log_event(&mut state, EventDirection::System, "Creating device key...");
+
+    // 1. A fresh device key joins the pairing realm and asks for the identity
+    let net = match IndrasNetwork::new(&data_dir).await {
+        Ok(net) => net,
+        Err(e) => {
+            fail(&mut state, format!("Could not create device key: {}", e));
+            return;
+        }
+    };
+    if let Err(e) = net.start().await {
+        tracing::warn!(error = %e, "Failed to start network (non-fatal)");
+    }
+
+    let pairing = match net.request_device_pairing(&code, device_name).await {
+        Ok(pairing) => pairing,
+        Err(e) => {
+            fail(
+                &mut state,
+                format!("Could not reach your other device: {}", e),
+            );
+            return;
+        }
+    };
+    log_event(&mut state, EventDirection::Sent, "Pairing request sent");
+    state.write().pairing_phase = PairingPhase::TransferringIdentity;
+
+    let grant = match pairing.wait_for_grant(net.id(), REQUEST_TIMEOUT).await {
+        Ok(grant) => grant,
+        Err(e) => {
+            fail(&mut state, format!("No identity received: {}", e));
+            return;
+        }
+    };
+    let account_name = grant.display_name.clone().unwrap_or_default();
+    log_event(
+        &mut state,
+        EventDirection::Received,
+        format!("Received identity for '{}'", account_name),
+    );
+
+    // 2. Install the identity and restart as a linked device
+    state.write().pairing_phase = PairingPhase::Restarting;
+    let _ = net.finish_device_pairing(pairing).await;
+    if let Err(e) = net.stop().await {
+        tracing::warn!(error = %e, "Failed to stop network before linking");
+    }
+    drop(net);
+
+    if let Err(e) = IndrasNetwork::link_device(&data_dir, &grant).await {
+        fail(&mut state, format!("Could not install identity: {}", e));
+        return;
+    }
+    let net = match IndrasNetwork::new(&data_dir).await {
+        Ok(net) => net,
+        Err(e) => {
+            fail(&mut state, format!("Could not restart: {}", e));
+            return;
+        }
+    };
+    if let Err(e) = net.start().await {
+        tracing::warn!(error = %e, "Failed to start network (non-fatal)");
+    }
+    log_event(
+        &mut state,
+        EventDirection::System,
+        "Restarted with linked identity",
+    );
+
+    // 3. Wait until the account's home realm syncs here, then confirm it
+    state.write().pairing_phase = PairingPhase::VerifyingHomeRealm;
+    if let Err(e) = net.confirm_device_link(SYNC_TIMEOUT).await {
+        fail(&mut state, format!("Home realm did not sync: {}", e));
+        return;
+    }
+    log_event(&mut state, EventDirection::Sent, "Home realm verified");
+
+    let _ = net.join_contacts_realm().await;
+    refresh_home_realm_data(&net, &mut state).await;
+
+    let id_short: String = net
+        .id()
+        .iter()
+        .take(8)
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    {
+        let mut s = state.write();
+        s.display_name = net.display_name().unwrap_or(account_name);
+        s.member_id_short = Some(id_short);
+        s.invite_code_uri = Some(net.identity_uri());
+        s.pairing_phase = PairingPhase::Complete;
+    }
+    network.set(Some(net));
+}
+
+/// Live checklist of pairing progress.
+#[component]
+fn PairingChecklist(phase: PairingPhase, steps: Vec<(PairingPhase, String)>) -> Element {
+    let current = phase.ordinal();
+    let failed = matches!(phase, PairingPhase::Failed(_));
+    let rows: Vec<(&str, String)> = steps
+        .into_iter()
+        .map(|(step, label)| {
+            let class = if current > step.ordinal() {
+                "pairing-step done"
+            } else if current == step.ordinal() && !failed {
+                "pairing-step active"
+            } else {
+                "pairing-step"
+            };
+            (class, label)
+        })
+        .collect();
+
+    rsx! {
+        ul {
+            class: "pairing-checklist",
+            for (class, label) in rows {
+                li {
+                    class: "{class}",
+                    span { class: "pairing-step-dot" }
+                    "{label}"
+                }
+            }
+        }
+    }
+}
+
+/// Existing device: show the pairing code and follow the new device's progress.
+#[component]
+pub fn PairDeviceScreen(
+    mut state: Signal<GenesisState>,
+    network: Signal<Option<Arc<IndrasNetwork>>>,
+) -> Element {
+    use_effect(move || {
+        spawn(async move {
+            run_pairing(state, network).await;
+        });
+    });
+
+    let s = state.read();
+    let phase = s.pairing_phase.clone();
+    let pairing_code = s.pairing_code.clone();
+    let qr_svg = s.pairing_qr_svg.clone();
+    let peer_device = s.pairing_peer_device.clone();
+    drop(s);
+
+    let steps: Vec<(PairingPhase, String)> = PAIR_STEPS
+        .iter()
+        .map(|(phase, label)| (phase.clone(), label.to_string()))
+        .collect();
+
+    rsx! {
+        div {
+            class: "genesis-screen",
+
+            div {
+                class: "genesis-card pairing-card",
+
+                h1 {
+                    class: "genesis-title",
+                    "Add a device"
+                }
+                p {
+                    class: "genesis-hint",
+                    "On your new device, choose \"I already have an account\" and enter this code"
+                }
+
+                if phase == PairingPhase::Connecting {
+                    if let Some(svg) = qr_svg {
+                        div {
+                            class: "pairing-qr",
+                            dangerous_inner_html: "{svg}",
+                        }
+                    }
+                    if let Some(pairing_code) = pairing_code {
+                        code {
+                            class: "pairing-code",
+                            "{pairing_code}"
+                        }
+                    }
+                }
+
+                if let Some(name) = peer_device {
+                    p {
+                        class: "genesis-subtitle",
+                        "Pairing with {name}"
+                    }
+                }
+
+                PairingChecklist { phase: phase.clone(), steps }
+
+                if let PairingPhase::Failed(err) = &phase {
+                    p {
+                        class: "genesis-error",
+                        "{err}"
+                    }
+                }
+
+                div {
+                    class: "genesis-actions",
+
+                    if phase == PairingPhase::Complete {
+                        button {
+                            class: "genesis-btn-primary",
+                            onclick: move |_| {
+                                state.write().pairing_phase = PairingPhase::Idle;
+                                state.write().step = GenesisStep::HomeRealm;
+                            },
+                            "Done"
+                        }
+                    } else {
+                        button {
+                            class: "genesis-btn-secondary",
+                            onclick: move |_| {
+                                let mut s = state.write();
+                                s.pairing_phase = PairingPhase::Idle;
+                                s.pairing_code = None;
+                                s.pairing_qr_svg = None;
+                                s.step = GenesisStep::HomeRealm;
+                            },
+                            "Cancel"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// New device: enter a pairing code and follow the link to completion.
+#[component]
+pub fn LinkDeviceScreen(
+    mut state: Signal<GenesisState>,
+    network: Signal<Option<Arc<IndrasNetwork>>>,
+) -> Element {
+    let s = state.read();
+    let phase = s.pairing_phase.clone();
+    let code_empty = s.pairing_code_input.trim().is_empty();
+    drop(s);
+    let running = phase.is_active();
+    let can_link = !running && !code_empty && phase != PairingPhase::Complete;
+
+    let steps: Vec<(PairingPhase, String)> = LINK_STEPS
+        .iter()
+        .map(|(phase, label)| (phase.clone(), label.to_string()))
+        .collect();
+
+    rsx! {
+        div {
+            class: "genesis-screen",
+
+            div {
+                class: "genesis-card pairing-card",
+
+                h1 {
+                    class: "genesis-title",
+                    "Link this device"
+                }
+                p {
+                    class: "genesis-hint",
+                    "On your other device, open your home realm and choose \"Add a device\""
+                }
+
+                input {
+                    class: "genesis-input",
+                    r#type: "text",
+                    placeholder: "indrapair1...",
+                    autofocus: true,
+                    disabled: running,
+                    value: "{state.read().pairing_code_input}",
+                    oninput: move |evt| {
+                        state.write().pairing_code_input = evt.value();
+                    },
+                }
+                input {
+                    class: "genesis-input",
+                    r#type: "text",
+                    placeholder: "Name this device (e.g. Laptop)",
+                    disabled: running,
+                    value: "{state.read().pairing_device_name}",
+                    oninput: move |evt| {
+                        state.write().pairing_device_name = evt.value();
+                    },
+                }
+
+                if phase != PairingPhase::Idle {
+                    PairingChecklist { phase: phase.clone(), steps }
+                }
+
+                if let PairingPhase::Failed(err) = &phase {
+                    p {
+                        class: "genesis-error",
+                        "{err}"
+                    }
+                }
+
+                div {
+                    class: "genesis-actions",
+
+                    if phase == PairingPhase::Complete {
+                        button {
+                            class: "genesis-btn-primary",
+                            onclick: move |_| {
+                                state.write().pairing_phase = PairingPhase::Idle;
+                                state.write().step = GenesisStep::HomeRealm;
+                            },
+                            "Open home realm"
+                        }
+                    } else {
+                        button {
+                            class: "genesis-btn-primary",
+                            disabled: !can_link,
+                            onclick: move |_| {
+                                spawn(async move {
+                                    run_link(state, network).await;
+                                });
+                            },
+                            if running { "Linking..." } else { "Link device" }
+                        }
+                        button {
+                            class: "genesis-btn-secondary",
+                            disabled: running,
+                            onclick: move |_| {
+                                state.write().pairing_phase = PairingPhase::Idle;
+                                state.write().step = GenesisStep::DisplayName;
+                            },
+                            "Back"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -5,7 +5,7 @@ use std::sync::Arc;
 use dioxus::prelude::*;
 use indras_network::IndrasNetwork;
 
-use crate::state::{AsyncStatus, GenesisState};
+use crate::state::{AsyncStatus, GenesisState, GenesisStep};
 
 use super::app::create_identity_and_load;
 
@@ -84,6 +84,15 @@ pub fn DisplayNameScreen(
                             "Continue"
                         }
                     }
+
+                    button {
+                        class: "genesis-btn-secondary",
+                        disabled: is_loading,
+                        onclick: move |_| {
+                            state.write().step = GenesisStep::LinkDevice;
+                        },
+                        "I already have an account on another device"
+                    }
                 }
             }
         }
@@ -6,7 +6,7 @@ use dioxus::prelude::*;
 use indras_network::IndrasNetwork;
 use indras_sync_engine::{HomeRealmIntentions, HomeRealmNotes};
 
-use indras_ui::{ArtifactDisplayInfo, ArtifactDisplayStatus, ArtifactGallery};
+use indras_ui::{tr, ArtifactDisplayInfo, ArtifactDisplayStatus, ArtifactGallery};
 
 use crate::state::{ContactView, ContactSentiment, EventDirection, GenesisState, GenesisStep, NoteView, IntentionAttentionView, IntentionClaimView, IntentionStatus, IntentionView};
 
@@ -16,7 +16,7 @@ fn hex_id(id: &[u8; 16]) -> String {
 }
 
 /// Refresh quests and notes from the network into state.
-async fn refresh_home_realm_data(
+pub(super) async fn refresh_home_realm_data(
     network: &Arc<IndrasNetwork>,
     state: &mut Signal<GenesisState>,
 ) {
@@ -40,8 +40,8 @@ async fn refresh_home_realm_data(
                         claimant_name: None, // TODO: resolve from contacts
                         verified: c.verified,
                         has_proof: c.has_proof(),
-                        submitted_at: chrono::DateTime::from_timestamp_millis(c.submitted_at_millis)
-                            .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+                        submitted_at: u64::try_from(c.submitted_at_millis)
+                            .map(indras_ui::format_datetime)
                             .unwrap_or_default(),
                     }
                 }).collect();
@@ -163,6 +163,10 @@ pub fn HomeRealmScreen(
     let event_log = s.event_log.clone();
     let has_content = !notes.is_empty() || quest_count > 1;
     drop(s);
+    let can_add_device = network
+        .read()
+        .as_ref()
+        .is_some_and(|net| !net.is_linked_device());
 
     // Periodically refresh contacts and save world view.
     // The inbox listener creates DM realms in the background; this poll
@@ -251,6 +255,15 @@ pub fn HomeRealmScreen(
                         class: "home-member-id",
                         "{member_id}"
                     }
+                    if can_add_device {
+                        button {
+                            class: "genesis-btn-secondary",
+                            onclick: move |_| {
+                                state.write().step = GenesisStep::PairDevice;
+                            },
+                            "Add a device"
+                        }
+                    }
                 }
             }
 
@@ -302,7 +315,7 @@ pub fn HomeRealmScreen(
                         if quests.is_empty() {
                             div {
                                 class: "panel-empty",
-                                "No quests yet."
+                                {tr("genesis-no-quests")}
                             }
                         } else {
                             div {
@@ -397,8 +410,8 @@ pub fn HomeRealmScreen(
                         if notes.is_empty() && !note_form_open {
                             div {
                                 class: "panel-empty",
-                                p { "No notes yet." }
-                                p { class: "panel-empty-hint", "Create your first note to get started." }
+                                p { {tr("genesis-no-notes")} }
+                                p { class: "panel-empty-hint", {tr("genesis-no-notes-hint")} }
                             }
                         } else {
                             div {
@@ -428,8 +441,8 @@ pub fn HomeRealmScreen(
                         if artifacts.is_empty() {
                             div {
                                 class: "panel-empty",
-                                p { "No artifacts yet." }
-                                p { class: "panel-empty-hint", "Share files in a realm to see them here." }
+                                p { {tr("genesis-no-artifacts")} }
+                                p { class: "panel-empty-hint", {tr("genesis-no-artifacts-hint")} }
                             }
                         } else {
                             ArtifactGallery { artifacts: artifacts.clone() }
@@ -442,15 +455,15 @@ pub fn HomeRealmScreen(
 
                         div {
                             class: "panel-header",
-                            h2 { class: "panel-title", "Tokens of Gratitude" }
+                            h2 { class: "panel-title", {tr("genesis-tokens-title")} }
                             span { class: "panel-count", "{token_count}" }
                         }
 
                         if tokens.is_empty() {
                             div {
                                 class: "panel-empty",
-                                p { "No tokens yet." }
-                                p { class: "panel-empty-hint", "Receive blessings to earn tokens of gratitude." }
+                                p { {tr("genesis-no-tokens")} }
+                                p { class: "panel-empty-hint", {tr("genesis-no-tokens-hint")} }
                             }
                         } else {
                             div {
@@ -545,7 +558,7 @@ pub fn HomeRealmScreen(
                         if contacts.is_empty() {
                             div {
                                 class: "contacts-empty",
-                                "No connections yet. Share your invite link to get started."
+                                {tr("genesis-no-connections")}
                             }
                         } else {
                             div {
@@ -580,7 +593,7 @@ pub fn HomeRealmScreen(
                     class: "event-log-list",
 
                     if event_log.is_empty() {
-                        span { class: "event-log-msg", "No events yet." }
+                        span { class: "event-log-msg", {tr("genesis-no-events")} }
                     }
 
                     for entry in event_log.iter() {
@@ -1,6 +1,7 @@
 //! UI components for the Genesis flow.
 
 mod app;
+mod device_pairing;
 mod display_name;
 mod home_realm;
 mod note_editor;
@@ -12,6 +13,7 @@ mod story_stage;
 mod welcome;
 
 pub use app::App;
+pub use device_pairing::{LinkDeviceScreen, PairDeviceScreen};
 pub use display_name::DisplayNameScreen;
 pub use home_realm::HomeRealmScreen;
 pub use note_editor::NoteEditorOverlay;
@@ -78,8 +78,8 @@ async fn load_shared_realm_data(
                             claimant_name: None,
                             verified: c.verified,
                             has_proof: c.has_proof(),
-                            submitted_at: chrono::DateTime::from_timestamp_millis(c.submitted_at_millis)
-                                .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+                            submitted_at: u64::try_from(c.submitted_at_millis)
+                                .map(indras_ui::format_datetime)
                                 .unwrap_or_default(),
                         }
                     }).collect();
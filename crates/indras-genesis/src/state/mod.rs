@@ -13,6 +13,10 @@ pub enum GenesisStep {
     HomeRealm,
     /// Peer realm chat view with a specific contact.
     PeerRealm([u8; 32]),
+    /// Existing device: show a pairing code and link a new device.
+    PairDevice,
+    /// New device: enter a pairing code from an existing device.
+    LinkDevice,
 }
 
 /// Status of an async operation.
@@ -26,6 +30,50 @@ pub enum AsyncStatus {
     Error(String),
 }
 
+/// Live status of a device pairing, on either device.
+///
+/// Each phase names the work currently in progress; phases advance in
+/// declaration order and `Failed` can happen at any point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PairingPhase {
+    /// Nothing started yet.
+    Idle,
+    /// Waiting for the other device to join the pairing realm.
+    Connecting,
+    /// Identity material is being sent (existing device) or received (new device).
+    TransferringIdentity,
+    /// New device is restarting with the account identity.
+    Restarting,
+    /// Waiting for both devices to see the shared home realm.
+    VerifyingHomeRealm,
+    /// Both devices see the home realm.
+    Complete,
+    /// Pairing failed or timed out.
+    Failed(String),
+}
+
+impl PairingPhase {
+    /// Position in the flow, used to mark checklist steps done.
+    pub fn ordinal(&self) -> u8 {
+        match self {
+            PairingPhase::Idle | PairingPhase::Failed(_) => 0,
+            PairingPhase::Connecting => 1,
+            PairingPhase::TransferringIdentity => 2,
+            PairingPhase::Restarting => 3,
+            PairingPhase::VerifyingHomeRealm => 4,
+            PairingPhase::Complete => 5,
+        }
+    }
+
+    /// Whether the pairing is still running.
+    pub fn is_active(&self) -> bool {
+        !matches!(
+            self,
+            PairingPhase::Idle | PairingPhase::Complete | PairingPhase::Failed(_)
+        )
+    }
+}
+
 /// Status of an intention for display purposes.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum IntentionStatus {
@@ -298,6 +346,18 @@ pub struct GenesisState {
     pub intention_editor_description: String,
     /// Whether to show rendered markdown (true) or raw (false) in view mode.
     pub intention_editor_preview_mode: bool,
+    /// Live status of device pairing.
+    pub pairing_phase: PairingPhase,
+    /// Pairing code shown on the existing device.
+    pub pairing_code: Option<String>,
+    /// SVG QR code for the pairing code.
+    pub pairing_qr_svg: Option<String>,
+    /// Pairing code typed on the new device.
+    pub pairing_code_input: String,
+    /// Name for this device, sent with the pairing request.
+    pub pairing_device_name: String,
+    /// Name of the other device once connected.
+    pub pairing_peer_device: Option<String>,
 }
 
 impl Default for GenesisState {
@@ -347,6 +407,12 @@ impl Default for GenesisState {
             intention_editor_title: String::new(),
             intention_editor_description: String::new(),
             intention_editor_preview_mode: true,
+            pairing_phase: PairingPhase::Idle,
+            pairing_code: None,
+            pairing_qr_svg: None,
+            pairing_code_input: String::new(),
+            pairing_device_name: String::new(),
+            pairing_peer_device: None,
         }
     }
 }
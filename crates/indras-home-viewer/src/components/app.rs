@@ -114,7 +114,7 @@ fn Header(state: Signal<AppState>) -> Element {
 
 /// Floating playback controls.
 #[component]
-fn PlaybackControls(state: Signal<AppState>) -> Element {
+pub fn PlaybackControls(state: Signal<AppState>) -> Element {
     let mut state_write = state;
     let state_read = state.read();
 
@@ -5,6 +5,7 @@ mod app;
 mod artifacts;
 mod content;
 mod notes_panel;
+mod perspectives;
 mod quests_panel;
 mod sidebar;
 mod stats_panel;
@@ -14,6 +15,7 @@ pub use app::*;
 pub use artifacts::*;
 pub use content::*;
 pub use notes_panel::*;
+pub use perspectives::*;
 pub use quests_panel::*;
 pub use sidebar::*;
 pub use stats_panel::*;
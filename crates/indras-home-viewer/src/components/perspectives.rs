@@ -0,0 +1,99 @@
+//! Side-by-side view of several members playing through the same scenario.
+
+use dioxus::prelude::*;
+
+use crate::state::AppState;
+use crate::theme::ThemedRoot;
+
+use super::{ActivityFeed, NotesPanel, PlaybackControls, QuestsPanel};
+
+/// Root component for `--compare` mode.
+///
+/// `timeline` sees every event and drives the playback controls; each of
+/// `views` is one member's first-person state at the same position.
+#[component]
+pub fn PerspectivesApp(timeline: Signal<AppState>, views: Vec<Signal<AppState>>) -> Element {
+    let columns = format!("grid-template-columns: repeat({}, minmax(0, 1fr));", views.len());
+    let count = views.len();
+
+    rsx! {
+        ThemedRoot {
+            div {
+                class: "home-viewer",
+
+                header {
+                    class: "header",
+                    div {
+                        class: "header-left",
+                        h1 {
+                            class: "header-title",
+                            "Perspectives"
+                        }
+                        span {
+                            class: "header-member",
+                            "{count} members"
+                        }
+                    }
+                }
+
+                main {
+                    class: "perspectives-grid",
+                    style: "{columns}",
+
+                    for (i, state) in views.into_iter().enumerate() {
+                        PerspectiveColumn { key: "{i}", state }
+                    }
+                }
+
+                PlaybackControls { state: timeline }
+            }
+        }
+    }
+}
+
+/// One member's column: status, intentions, notes, and activity.
+#[component]
+fn PerspectiveColumn(state: Signal<AppState>) -> Element {
+    let state_read = state.read();
+    let member = state_read
+        .selected_member
+        .as_deref()
+        .unwrap_or("Unknown")
+        .to_string();
+    let session_active = state_read.is_session_active();
+    let sync_healthy = state_read.is_sync_healthy();
+    let total_events = state_read.total_events;
+
+    rsx! {
+        div {
+            class: "perspective-column",
+
+            div {
+                class: "perspective-header",
+                span {
+                    class: "perspective-member",
+                    "{member}"
+                }
+                span {
+                    class: "perspective-events",
+                    "{total_events} events"
+                }
+                div {
+                    class: if session_active { "status-indicator status-active" } else { "status-indicator status-inactive" },
+                    span { class: "status-dot" }
+                }
+                div {
+                    class: if sync_healthy { "status-indicator status-synced" } else { "status-indicator status-conflict" },
+                    span {
+                        class: "status-icon",
+                        if sync_healthy { "✓" } else { "!" }
+                    }
+                }
+            }
+
+            QuestsPanel { state }
+            NotesPanel { state }
+            ActivityFeed { state }
+        }
+    }
+}
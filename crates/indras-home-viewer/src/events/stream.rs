@@ -131,12 +131,8 @@ async fn read_lines<R: tokio::io::AsyncRead + Unpin>(
 
         if let Some(event) = event {
             // Apply member filter if set
-            if let Some(filter) = member_filter {
-                if let Some(member) = event.member() {
-                    if member != filter {
-                        continue;
-                    }
-                }
+            if member_filter.is_some_and(|filter| !event.is_visible_to(filter)) {
+                continue;
             }
 
             // Send to channel (buffering is handled by main.rs)
@@ -164,12 +160,8 @@ pub fn replay_from_position(
     let mut count = 0;
     for event in buf.iter().skip(start_pos) {
         // Apply member filter if set
-        if let Some(filter) = member_filter {
-            if let Some(member) = event.member() {
-                if member != filter {
-                    continue;
-                }
-            }
+        if member_filter.is_some_and(|filter| !event.is_visible_to(filter)) {
+            continue;
         }
 
         if tx.send(event.clone()).is_err() {
@@ -214,4 +206,19 @@ mod tests {
         clear_buffer();
         assert_eq!(buffer_len(), 0);
     }
+
+    #[test]
+    fn test_member_visibility() {
+        let note = parse_event_line(
+            r#"{"event_type":"session_started","member":"A","realm_id":"r1","tick":3}"#,
+        )
+        .unwrap();
+        assert!(note.is_visible_to("A"));
+        assert!(!note.is_visible_to("B"));
+
+        let info = HomeRealmEvent::Info {
+            message: "phase 2".to_string(),
+        };
+        assert!(info.is_visible_to("A") && info.is_visible_to("B"));
+    }
 }
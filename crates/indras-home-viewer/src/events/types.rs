@@ -224,6 +224,13 @@ impl HomeRealmEvent {
         }
     }
 
+    /// Returns whether this event belongs in `member`'s view.
+    ///
+    /// Events without a member (info lines) are visible to everyone.
+    pub fn is_visible_to(&self, member: &str) -> bool {
+        self.member().is_none_or(|m| m == member)
+    }
+
     /// Returns a short description of this event for the activity feed.
     pub fn description(&self) -> String {
         match self {
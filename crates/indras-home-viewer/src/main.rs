@@ -1,7 +1,8 @@
 //! Entry point for the home realm viewer.
 //!
 //! This Dioxus desktop application plays through home realm Lua scenarios
-//! from a single user's perspective.
+//! from a single user's perspective, or from several members' perspectives
+//! side by side with `--compare`.
 
 use std::path::PathBuf;
 use std::sync::OnceLock;
@@ -11,7 +12,7 @@ use dioxus::desktop::{Config, LogicalSize, WindowBuilder};
 use dioxus::prelude::*;
 use tokio::time::{sleep, Duration};
 
-use indras_home_viewer::components::App;
+use indras_home_viewer::components::{App, PerspectivesApp};
 use indras_home_viewer::events::{start_stream, HomeRealmEvent, StreamConfig};
 use indras_home_viewer::playback;
 use indras_home_viewer::state::AppState;
@@ -28,6 +29,9 @@ static FILE_PATH: OnceLock<Option<PathBuf>> = OnceLock::new();
 /// Global storage for the member filter argument.
 static MEMBER_FILTER: OnceLock<Option<String>> = OnceLock::new();
 
+/// Global storage for the members compared side by side.
+static COMPARE_MEMBERS: OnceLock<Vec<String>> = OnceLock::new();
+
 /// Command line arguments.
 #[derive(Parser, Debug)]
 #[command(name = "indras-home-viewer")]
@@ -41,6 +45,10 @@ struct Args {
     #[arg(short, long)]
     member: Option<String>,
 
+    /// Show these members' perspectives side by side, in lock-step (comma-separated)
+    #[arg(long, value_delimiter = ',', conflicts_with = "member")]
+    compare: Vec<String>,
+
     /// Initial playback speed (default: 1.0)
     #[arg(short, long, default_value = "1.0")]
     speed: f32,
@@ -65,6 +73,7 @@ fn main() {
     // Store args in global state
     FILE_PATH.set(args.file).ok();
     MEMBER_FILTER.set(args.member).ok();
+    COMPARE_MEMBERS.set(args.compare).ok();
 
     // Set initial playback state
     playback::set_speed(args.speed);
@@ -99,6 +108,20 @@ fn RootApp() -> Element {
     // Create state signal
     let state = use_signal(AppState::new);
 
+    // One extra state per compared member, all fed from the same stream
+    let views = use_hook(|| {
+        COMPARE_MEMBERS
+            .get()
+            .map(|members| {
+                members
+                    .iter()
+                    .map(|m| Signal::new(AppState::for_member(m.as_str())))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+    });
+    let compare_views = views.clone();
+
     // Request shutdown on unmount
     use_drop(|| {
         tracing::info!("Shutting down Home Realm Viewer");
@@ -107,12 +130,17 @@ fn RootApp() -> Element {
 
     // Start the event stream processor
     let _stream_handle = use_resource(move || {
-        let mut state = state;
+        let mut targets = Targets::new(state, &compare_views);
 
         async move {
             // Get configuration from global state
             let file_path = FILE_PATH.get().and_then(|p| p.clone());
-            let member_filter = MEMBER_FILTER.get().and_then(|m| m.clone());
+            // Compare mode needs every member's events; each view filters its own
+            let member_filter = if targets.is_compare() {
+                None
+            } else {
+                MEMBER_FILTER.get().and_then(|m| m.clone())
+            };
 
             let config = StreamConfig {
                 file_path,
@@ -148,12 +176,12 @@ fn RootApp() -> Element {
                         break; // Step requested
                     }
                     if playback::take_reset_request() {
-                        state.write().reset();
+                        targets.reset();
                     }
                     sleep(Duration::from_millis(50)).await;
                 }
 
-                process_event(&mut state, event);
+                targets.process(event);
 
                 // Delay between events when playing
                 if !playback::is_paused() {
@@ -180,7 +208,7 @@ fn RootApp() -> Element {
 
                     if playback::take_reset_request() {
                         // Reset to beginning
-                        state.write().reset();
+                        targets.reset();
                         replay_pos = 0;
                         break;
                     }
@@ -208,14 +236,14 @@ fn RootApp() -> Element {
 
                     // Check for reset
                     if playback::take_reset_request() {
-                        state.write().reset();
+                        targets.reset();
                         replay_pos = 0;
                         break;
                     }
 
                     // Process event at current position
                     let event = events[replay_pos].clone();
-                    process_event(&mut state, event);
+                    targets.process(event);
                     replay_pos += 1;
 
                     // If paused, wait for next step or unpause
@@ -233,15 +261,59 @@ fn RootApp() -> Element {
         }
     });
 
-    rsx! {
-        App { state }
+    if views.is_empty() {
+        rsx! {
+            App { state }
+        }
+    } else {
+        rsx! {
+            PerspectivesApp { timeline: state, views }
+        }
+    }
+}
+
+/// The states an event stream drives, each with an optional member filter.
+///
+/// The timeline state sees every event; compared members see only their own.
+/// All of them advance from the same buffer position, so they stay in lock-step.
+struct Targets {
+    states: Vec<(Signal<AppState>, Option<String>)>,
+}
+
+impl Targets {
+    fn new(timeline: Signal<AppState>, views: &[Signal<AppState>]) -> Self {
+        let mut states = vec![(timeline, None)];
+        states.extend(
+            views
+                .iter()
+                .map(|view| (*view, view.peek().selected_member.clone())),
+        );
+        Self { states }
+    }
+
+    fn is_compare(&self) -> bool {
+        self.states.len() > 1
+    }
+
+    fn process(&mut self, event: HomeRealmEvent) {
+        log_event(&event);
+        for (state, filter) in &mut self.states {
+            if filter.as_deref().is_none_or(|m| event.is_visible_to(m)) {
+                state.write().process_event(event.clone());
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        for (state, _) in &mut self.states {
+            state.write().reset();
+        }
     }
 }
 
-/// Processes a single event, updating state.
-fn process_event(state: &mut Signal<AppState>, event: HomeRealmEvent) {
-    // Log significant events
-    match &event {
+/// Logs significant events.
+fn log_event(event: &HomeRealmEvent) {
+    match event {
         HomeRealmEvent::SessionStarted { member, .. } => {
             tracing::info!("Session started for member: {}", member);
         }
@@ -256,7 +328,4 @@ fn process_event(state: &mut Signal<AppState>, event: HomeRealmEvent) {
         }
         _ => {}
     }
-
-    // Update state
-    state.write().process_event(event);
 }
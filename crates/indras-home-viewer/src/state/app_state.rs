@@ -152,6 +152,14 @@ impl AppState {
         }
     }
 
+    /// Creates a state that views the scenario as `member`.
+    pub fn for_member(member: impl Into<String>) -> Self {
+        Self {
+            selected_member: Some(member.into()),
+            ..Self::new()
+        }
+    }
+
     /// Processes a home realm event.
     pub fn process_event(&mut self, event: HomeRealmEvent) {
         // Update tick
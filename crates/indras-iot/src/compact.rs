@@ -68,6 +68,10 @@ pub enum CompactMessageType {
     SyncResponse = 5,
     /// Presence announcement
     Presence = 6,
+    /// Compact profile feature negotiation
+    Hello = 7,
+    /// Signature over a batch of compact profile messages
+    BatchSignature = 8,
 }
 
 impl TryFrom<u8> for CompactMessageType {
@@ -82,6 +86,8 @@ impl TryFrom<u8> for CompactMessageType {
             4 => Ok(Self::SyncRequest),
             5 => Ok(Self::SyncResponse),
             6 => Ok(Self::Presence),
+            7 => Ok(Self::Hello),
+            8 => Ok(Self::BatchSignature),
             _ => Err(CompactError::InvalidMessageType(value)),
         }
     }
@@ -287,7 +293,7 @@ impl CompactMessage {
 }
 
 /// Encode a varint
-fn encode_varint<W: Write>(w: &mut W, mut value: u64) -> Result<(), CompactError> {
+pub(crate) fn encode_varint<W: Write>(w: &mut W, mut value: u64) -> Result<(), CompactError> {
     loop {
         let mut byte = (value & 0x7F) as u8;
         value >>= 7;
@@ -303,7 +309,7 @@ fn encode_varint<W: Write>(w: &mut W, mut value: u64) -> Result<(), CompactError
 }
 
 /// Decode a varint with proper overflow checking
-fn decode_varint<R: Read>(r: &mut R) -> Result<u64, CompactError> {
+pub(crate) fn decode_varint<R: Read>(r: &mut R) -> Result<u64, CompactError> {
     let mut result = 0u64;
     let mut shift = 0u32;
 
@@ -0,0 +1,604 @@
+//! # Duty Cycle Controller
+//!
+//! Drives a node through scheduled wake/sync windows. Between windows the
+//! node is dormant with its transport down; each window is a bounded burst:
+//!
+//! ```text
+//! wake transport -> sync selected realms -> flush outbox -> sleep transport
+//! ```
+//!
+//! Bursts start on a [`WakeSchedule`] or from a [`WakeHandle`] (a button,
+//! a sensor interrupt, a pending-message threshold). Each burst is capped
+//! by `burst_timeout` and a byte allowance, and the controller keeps
+//! rolling power (awake time) and bandwidth budgets: once a budget window
+//! is spent, further wakes are refused until it rolls over, except for
+//! [`WakeReason::Urgent`] wakes, which only respect the per-burst caps.
+//!
+//! The node is abstracted by [`DormantNode`] so this crate stays free of
+//! storage and sync dependencies.
+//!
+//! ```ignore
+//! let controller = DutyCycleController::new(node, ControllerConfig {
+//!     realms: vec![sensor_realm],
+//!     ..ControllerConfig::default()
+//! });
+//! let wake = controller.handle();
+//! tokio::spawn(controller.run(shutdown_rx));
+//!
+//! // From an interrupt handler:
+//! wake.trigger_urgent();
+//! ```
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use indras_core::InterfaceId;
+use thiserror::Error;
+use tokio::sync::{broadcast, mpsc};
+use tracing::{debug, info, warn};
+
+/// When the controller wakes the node on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WakeSchedule {
+    /// Wake at a fixed interval, starting immediately
+    Every(Duration),
+    /// Only wake on external triggers
+    Manual,
+}
+
+/// Why a burst was started
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WakeReason {
+    /// The schedule came due
+    Scheduled,
+    /// An external trigger, subject to the power and bandwidth budgets
+    Triggered,
+    /// An external trigger that bypasses the rolling budgets
+    Urgent,
+}
+
+/// Duty cycle controller configuration
+#[derive(Debug, Clone)]
+pub struct ControllerConfig {
+    /// Wake schedule
+    pub schedule: WakeSchedule,
+    /// Realms synced in each burst, in priority order
+    pub realms: Vec<InterfaceId>,
+    /// Longest a single burst may keep the node awake
+    pub burst_timeout: Duration,
+    /// Length of the rolling window for the power and bandwidth budgets
+    pub budget_window: Duration,
+    /// Awake time allowed per budget window
+    pub max_awake_per_window: Duration,
+    /// Bytes a single burst may transfer
+    pub max_bytes_per_burst: u64,
+    /// Bytes allowed per budget window
+    pub max_bytes_per_window: u64,
+    /// Battery threshold below which the schedule interval doubles (0.0 - 1.0)
+    pub low_battery_threshold: f32,
+}
+
+impl Default for ControllerConfig {
+    fn default() -> Self {
+        Self {
+            schedule: WakeSchedule::Every(Duration::from_secs(300)),
+            realms: Vec::new(),
+            burst_timeout: Duration::from_secs(30),
+            budget_window: Duration::from_secs(3600),
+            max_awake_per_window: Duration::from_secs(360), // 10% duty
+            max_bytes_per_burst: 256 * 1024,
+            max_bytes_per_window: 2 * 1024 * 1024,
+            low_battery_threshold: 0.2,
+        }
+    }
+}
+
+impl ControllerConfig {
+    /// Aggressive power saving: hourly bursts, ~1% duty
+    pub fn low_power() -> Self {
+        Self {
+            schedule: WakeSchedule::Every(Duration::from_secs(3600)),
+            burst_timeout: Duration::from_secs(20),
+            max_awake_per_window: Duration::from_secs(40),
+            max_bytes_per_burst: 64 * 1024,
+            max_bytes_per_window: 128 * 1024,
+            low_battery_threshold: 0.1,
+            ..Self::default()
+        }
+    }
+
+    /// Sync the given realms in each burst
+    pub fn with_realms(mut self, realms: impl IntoIterator<Item = InterfaceId>) -> Self {
+        self.realms = realms.into_iter().collect();
+        self
+    }
+}
+
+/// Result of flushing the outbox
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OutboxFlush {
+    /// Messages sent
+    pub messages: usize,
+    /// Bytes sent
+    pub bytes: u64,
+}
+
+/// A node the controller can put to sleep and wake
+///
+/// Implementations bring the transport up in [`wake`](Self::wake) and take
+/// it down in [`sleep`](Self::sleep). `sleep` is called after every burst,
+/// including ones that failed or timed out.
+#[async_trait]
+pub trait DormantNode: Send + Sync {
+    /// Node error type
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Bring the transport up
+    async fn wake(&self) -> Result<(), Self::Error>;
+
+    /// Sync one realm, transferring at most about `byte_budget` bytes
+    ///
+    /// Returns the bytes actually transferred.
+    async fn sync_realm(&self, realm: &InterfaceId, byte_budget: u64) -> Result<u64, Self::Error>;
+
+    /// Send queued outgoing messages within `byte_budget`
+    async fn flush_outbox(&self, byte_budget: u64) -> Result<OutboxFlush, Self::Error>;
+
+    /// Take the transport down
+    async fn sleep(&self) -> Result<(), Self::Error>;
+}
+
+/// Duty cycle controller errors
+#[derive(Debug, Error)]
+pub enum ControllerError {
+    #[error("Power budget exhausted: awake {awake:?} of {max:?} this window")]
+    PowerBudgetExhausted { awake: Duration, max: Duration },
+    #[error("Bandwidth budget exhausted: {used} of {max} bytes this window")]
+    BandwidthBudgetExhausted { used: u64, max: u64 },
+    #[error("Node error: {0}")]
+    Node(#[source] Box<dyn std::error::Error + Send + Sync>),
+}
+
+/// What a burst did
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BurstReport {
+    /// Why the burst ran
+    pub reason: WakeReason,
+    /// Realms synced, in order
+    pub synced: Vec<InterfaceId>,
+    /// Realms skipped because the byte allowance or time ran out
+    pub skipped: Vec<InterfaceId>,
+    /// Outbox flush result
+    pub flushed: OutboxFlush,
+    /// Total bytes transferred
+    pub bytes: u64,
+    /// How long the node was awake
+    pub awake: Duration,
+    /// Whether the burst hit `burst_timeout`
+    pub timed_out: bool,
+}
+
+/// Wakes a [`DutyCycleController`] from outside
+#[derive(Debug, Clone)]
+pub struct WakeHandle {
+    tx: mpsc::Sender<WakeReason>,
+}
+
+impl WakeHandle {
+    /// Request a burst, subject to the rolling budgets
+    ///
+    /// Triggers arriving while a burst is queued are coalesced.
+    pub fn trigger(&self) {
+        let _ = self.tx.try_send(WakeReason::Triggered);
+    }
+
+    /// Request a burst that bypasses the rolling budgets
+    pub fn trigger_urgent(&self) {
+        let _ = self.tx.try_send(WakeReason::Urgent);
+    }
+}
+
+/// One past burst, for budget accounting
+#[derive(Debug, Clone, Copy)]
+struct BurstUsage {
+    ended: Instant,
+    awake: Duration,
+    bytes: u64,
+}
+
+/// Scheduled wake/sync controller for a dormant node.
+///
+/// See module documentation for the burst sequence and budgets.
+pub struct DutyCycleController<N: DormantNode> {
+    node: Arc<N>,
+    config: ControllerConfig,
+    history: VecDeque<BurstUsage>,
+    battery_level: f32,
+    wake_tx: mpsc::Sender<WakeReason>,
+    wake_rx: mpsc::Receiver<WakeReason>,
+}
+
+impl<N: DormantNode> DutyCycleController<N> {
+    /// Create a controller. The node is assumed to be dormant.
+    pub fn new(node: Arc<N>, config: ControllerConfig) -> Self {
+        let (wake_tx, wake_rx) = mpsc::channel(1);
+        info!(
+            realms = config.realms.len(),
+            schedule = ?config.schedule,
+            "Duty cycle controller initialized"
+        );
+        Self {
+            node,
+            config,
+            history: VecDeque::new(),
+            battery_level: 1.0,
+            wake_tx,
+            wake_rx,
+        }
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &ControllerConfig {
+        &self.config
+    }
+
+    /// A handle for waking the controller from outside
+    pub fn handle(&self) -> WakeHandle {
+        WakeHandle {
+            tx: self.wake_tx.clone(),
+        }
+    }
+
+    /// Update battery level (0.0 - 1.0)
+    pub fn set_battery_level(&mut self, level: f32) {
+        self.battery_level = level.clamp(0.0, 1.0);
+    }
+
+    /// Interval until the next scheduled wake, if any
+    ///
+    /// Doubled while the battery is below `low_battery_threshold`.
+    pub fn wake_interval(&self) -> Option<Duration> {
+        match self.config.schedule {
+            WakeSchedule::Every(interval) => {
+                if self.battery_level < self.config.low_battery_threshold {
+                    Some(interval.saturating_mul(2)) // Double interval on low battery
+                } else {
+                    Some(interval)
+                }
+            }
+            WakeSchedule::Manual => None,
+        }
+    }
+
+    /// Awake time spent in the current budget window
+    pub fn awake_in_window(&mut self) -> Duration {
+        self.prune_history();
+        self.history.iter().map(|b| b.awake).sum()
+    }
+
+    /// Bytes transferred in the current budget window
+    pub fn bytes_in_window(&mut self) -> u64 {
+        self.prune_history();
+        self.history.iter().map(|b| b.bytes).sum()
+    }
+
+    fn prune_history(&mut self) {
+        let window = self.config.budget_window;
+        while self
+            .history
+            .front()
+            .is_some_and(|b| b.ended.elapsed() >= window)
+        {
+            self.history.pop_front();
+        }
+    }
+
+    /// Byte allowance for a burst, or an error if a budget is spent
+    fn allowance(&mut self, reason: WakeReason) -> Result<u64, ControllerError> {
+        if reason == WakeReason::Urgent {
+            return Ok(self.config.max_bytes_per_burst);
+        }
+
+        let awake = self.awake_in_window();
+        if awake >= self.config.max_awake_per_window {
+            return Err(ControllerError::PowerBudgetExhausted {
+                awake,
+                max: self.config.max_awake_per_window,
+            });
+        }
+
+        let used = self.bytes_in_window();
+        let remaining = self.config.max_bytes_per_window.saturating_sub(used);
+        if remaining == 0 {
+            return Err(ControllerError::BandwidthBudgetExhausted {
+                used,
+                max: self.config.max_bytes_per_window,
+            });
+        }
+        Ok(remaining.min(self.config.max_bytes_per_burst))
+    }
+
+    /// Run one wake/sync/flush/sleep burst
+    ///
+    /// The node is put back to sleep even if syncing fails or times out.
+    pub async fn run_burst(&mut self, reason: WakeReason) -> Result<BurstReport, ControllerError> {
+        let allowance = self.allowance(reason)?;
+        let started = Instant::now();
+        debug!(?reason, allowance, "Waking node");
+
+        let mut report = BurstReport {
+            reason,
+            synced: Vec::new(),
+            skipped: Vec::new(),
+            flushed: OutboxFlush::default(),
+            bytes: 0,
+            awake: Duration::ZERO,
+            timed_out: false,
+        };
+
+        let result = match self.node.wake().await {
+            Ok(()) => {
+                let work = burst(self.node.as_ref(), &self.config.realms, allowance, &mut report);
+                match tokio::time::timeout(self.config.burst_timeout, work).await {
+                    Ok(result) => result,
+                    Err(_) => {
+                        report.timed_out = true;
+                        Ok(())
+                    }
+                }
+            }
+            Err(e) => Err(e),
+        };
+
+        let slept = self.node.sleep().await;
+
+        // Realms the burst never reached
+        let done = report.synced.len() + report.skipped.len();
+        report.skipped.extend(self.config.realms.iter().skip(done).copied());
+        report.awake = started.elapsed();
+        self.history.push_back(BurstUsage {
+            ended: Instant::now(),
+            awake: report.awake,
+            bytes: report.bytes,
+        });
+
+        result.map_err(|e| ControllerError::Node(Box::new(e)))?;
+        slept.map_err(|e| ControllerError::Node(Box::new(e)))?;
+
+        debug!(
+            synced = report.synced.len(),
+            skipped = report.skipped.len(),
+            flushed = report.flushed.messages,
+            bytes = report.bytes,
+            awake_ms = report.awake.as_millis() as u64,
+            timed_out = report.timed_out,
+            "Burst complete, node asleep"
+        );
+        Ok(report)
+    }
+
+    /// Drive bursts from the schedule and wake triggers until shutdown
+    pub async fn run(mut self, mut shutdown: broadcast::Receiver<()>) {
+        let mut next_wake = tokio::time::Instant::now();
+
+        loop {
+            let scheduled = self.wake_interval().is_some();
+            let reason = tokio::select! {
+                _ = shutdown.recv() => break,
+                Some(reason) = self.wake_rx.recv() => reason,
+                _ = tokio::time::sleep_until(next_wake), if scheduled => WakeReason::Scheduled,
+            };
+
+            match self.run_burst(reason).await {
+                Ok(_) => {}
+                Err(
+                    e @ (ControllerError::PowerBudgetExhausted { .. }
+                    | ControllerError::BandwidthBudgetExhausted { .. }),
+                ) => {
+                    debug!(?reason, error = %e, "Skipping wake");
+                }
+                Err(e) => warn!(?reason, error = %e, "Burst failed"),
+            }
+
+            if let Some(interval) = self.wake_interval() {
+                next_wake = tokio::time::Instant::now() + interval;
+            }
+        }
+
+        debug!("Duty cycle controller stopped");
+    }
+}
+
+/// The sync and flush part of a burst, recording progress into `report`
+async fn burst<N: DormantNode>(
+    node: &N,
+    realms: &[InterfaceId],
+    allowance: u64,
+    report: &mut BurstReport,
+) -> Result<(), N::Error> {
+    for realm in realms {
+        let remaining = allowance.saturating_sub(report.bytes);
+        if remaining == 0 {
+            report.skipped.push(*realm);
+            continue;
+        }
+        let bytes = node.sync_realm(realm, remaining).await?;
+        report.bytes += bytes;
+        report.synced.push(*realm);
+    }
+
+    let remaining = allowance.saturating_sub(report.bytes);
+    if remaining > 0 {
+        report.flushed = node.flush_outbox(remaining).await?;
+        report.bytes += report.flushed.bytes;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Error)]
+    #[error("mock failure")]
+    struct MockError;
+
+    #[derive(Default)]
+    struct MockNode {
+        calls: Mutex<Vec<String>>,
+        bytes_per_sync: u64,
+        sync_delay: Duration,
+    }
+
+    impl MockNode {
+        fn calls(&self) -> Vec<String> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl DormantNode for MockNode {
+        type Error = MockError;
+
+        async fn wake(&self) -> Result<(), MockError> {
+            self.calls.lock().unwrap().push("wake".into());
+            Ok(())
+        }
+
+        async fn sync_realm(&self, realm: &InterfaceId, byte_budget: u64) -> Result<u64, MockError> {
+            self.calls.lock().unwrap().push(format!("sync {}", realm.as_bytes()[0]));
+            tokio::time::sleep(self.sync_delay).await;
+            Ok(self.bytes_per_sync.min(byte_budget))
+        }
+
+        async fn flush_outbox(&self, _byte_budget: u64) -> Result<OutboxFlush, MockError> {
+            self.calls.lock().unwrap().push("flush".into());
+            Ok(OutboxFlush { messages: 2, bytes: 10 })
+        }
+
+        async fn sleep(&self) -> Result<(), MockError> {
+            self.calls.lock().unwrap().push("sleep".into());
+            Ok(())
+        }
+    }
+
+    fn realms(n: u8) -> Vec<InterfaceId> {
+        (1..=n).map(|i| InterfaceId::new([i; 32])).collect()
+    }
+
+    #[tokio::test]
+    async fn test_burst_syncs_flushes_and_sleeps() {
+        let node = Arc::new(MockNode {
+            bytes_per_sync: 100,
+            ..Default::default()
+        });
+        let config = ControllerConfig::default().with_realms(realms(2));
+        let mut controller = DutyCycleController::new(Arc::clone(&node), config);
+
+        let report = controller.run_burst(WakeReason::Scheduled).await.unwrap();
+        assert_eq!(node.calls(), vec!["wake", "sync 1", "sync 2", "flush", "sleep"]);
+        assert_eq!(report.synced, realms(2));
+        assert!(report.skipped.is_empty());
+        assert_eq!(report.flushed.messages, 2);
+        assert_eq!(report.bytes, 210);
+        assert_eq!(controller.bytes_in_window(), 210);
+    }
+
+    #[tokio::test]
+    async fn test_byte_allowance_skips_remaining_realms() {
+        let node = Arc::new(MockNode {
+            bytes_per_sync: 100,
+            ..Default::default()
+        });
+        let config = ControllerConfig {
+            max_bytes_per_burst: 100,
+            ..ControllerConfig::default().with_realms(realms(3))
+        };
+        let mut controller = DutyCycleController::new(Arc::clone(&node), config);
+
+        let report = controller.run_burst(WakeReason::Scheduled).await.unwrap();
+        assert_eq!(report.synced, realms(1));
+        assert_eq!(report.skipped, realms(3)[1..].to_vec());
+        assert_eq!(node.calls(), vec!["wake", "sync 1", "sleep"]);
+    }
+
+    #[tokio::test]
+    async fn test_spent_budget_refuses_all_but_urgent() {
+        let node = Arc::new(MockNode {
+            bytes_per_sync: 100,
+            ..Default::default()
+        });
+        let config = ControllerConfig {
+            max_bytes_per_window: 100,
+            ..ControllerConfig::default().with_realms(realms(1))
+        };
+        let mut controller = DutyCycleController::new(Arc::clone(&node), config);
+
+        controller.run_burst(WakeReason::Scheduled).await.unwrap();
+        assert!(matches!(
+            controller.run_burst(WakeReason::Triggered).await,
+            Err(ControllerError::BandwidthBudgetExhausted { used: 100, max: 100 })
+        ));
+        assert!(controller.run_burst(WakeReason::Urgent).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_timeout_bounds_burst_and_still_sleeps() {
+        let node = Arc::new(MockNode {
+            bytes_per_sync: 1,
+            sync_delay: Duration::from_millis(200),
+            ..Default::default()
+        });
+        let config = ControllerConfig {
+            burst_timeout: Duration::from_millis(20),
+            ..ControllerConfig::default().with_realms(realms(2))
+        };
+        let mut controller = DutyCycleController::new(Arc::clone(&node), config);
+
+        let report = controller.run_burst(WakeReason::Scheduled).await.unwrap();
+        assert!(report.timed_out);
+        assert!(report.synced.is_empty());
+        assert_eq!(report.skipped, realms(2));
+        assert_eq!(node.calls().last().map(String::as_str), Some("sleep"));
+    }
+
+    #[tokio::test]
+    async fn test_manual_schedule_wakes_on_trigger() {
+        let node = Arc::new(MockNode::default());
+        let config = ControllerConfig {
+            schedule: WakeSchedule::Manual,
+            ..ControllerConfig::default().with_realms(realms(1))
+        };
+        let controller = DutyCycleController::new(Arc::clone(&node), config);
+        let wake = controller.handle();
+        let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
+        let task = tokio::spawn(controller.run(shutdown_rx));
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(node.calls().is_empty());
+
+        wake.trigger();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(node.calls(), vec!["wake", "sync 1", "flush", "sleep"]);
+
+        shutdown_tx.send(()).unwrap();
+        task.await.unwrap();
+    }
+
+    #[test]
+    fn test_low_battery_doubles_interval() {
+        let node = Arc::new(MockNode::default());
+        let config = ControllerConfig {
+            schedule: WakeSchedule::Every(Duration::from_secs(60)),
+            ..ControllerConfig::default()
+        };
+        let mut controller = DutyCycleController::new(node, config);
+        assert_eq!(controller.wake_interval(), Some(Duration::from_secs(60)));
+
+        controller.set_battery_level(0.05);
+        assert_eq!(controller.wake_interval(), Some(Duration::from_secs(120)));
+    }
+}
@@ -9,9 +9,12 @@
 //!
 //! - Low-memory mode
 //! - Duty cycling support
-//! - Compact wire format
+//! - Compact wire format and reduced-overhead message profile
 //! - Power management
+//! - Scheduled wake/sync windows for dormant nodes
 
 pub mod compact;
+pub mod controller;
 pub mod duty_cycle;
 pub mod low_memory;
+pub mod profile;
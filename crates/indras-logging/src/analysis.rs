@@ -0,0 +1,656 @@
+//! Log analysis over JSONL output from multiple instances
+//!
+//! Each peer instance writes its own JSONL log. This module loads several of
+//! those files into one [`LogSet`], stitches records that share a
+//! correlation `trace_id` into [`Trace`]s, and answers the questions that
+//! otherwise take a lot of grepping:
+//!
+//! - [`LogSet::packet_path`]: every hop a packet took, in forwarding order
+//! - [`LogSet::sync_rounds`]: every sync round that touched an interface
+//! - [`LogSet::query`]: records matching a [`LogQuery`]
+//!
+//! ```ignore
+//! use indras_logging::analysis::{LogQuery, LogSet};
+//!
+//! let logs = LogSet::load(&["logs/A.log", "logs/B.log", "logs/C.log"])?;
+//! if let Some(path) = logs.packet_path("0041#3") {
+//!     for hop in &path.hops {
+//!         println!("{} hop={} {}", hop.instance, hop.hop_count, hop.message);
+//!     }
+//! }
+//! ```
+//!
+//! Records are read in the shape the JSONL layers write: event fields either
+//! flattened to the root or nested under `fields`, with the current span in
+//! `span` and the span stack in `spans`. Fields from enclosing spans are
+//! inherited, innermost winning, so correlation IDs recorded on a span apply
+//! to every event inside it.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde_json::{Map, Value};
+
+use crate::correlation::{fields, spans};
+
+/// Errors from loading logs for analysis
+#[derive(Debug, thiserror::Error)]
+pub enum AnalysisError {
+    /// A log file could not be read
+    #[error("failed to read {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// Field names that identify the interface a record is about
+const INTERFACE_FIELDS: &[&str] = &[fields::INTERFACE_ID, "interface"];
+
+/// Span names that mark document sync work
+const SYNC_SPANS: &[&str] = &[
+    spans::SYNC_DOCUMENT,
+    spans::MERGE_CHANGES,
+    spans::SYNC_ENGINE_DOCUMENT_SYNC,
+];
+
+/// One parsed log line
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogRecord {
+    /// Instance (file) the line came from
+    pub instance: String,
+    /// 1-based line number within the instance's file
+    pub line: usize,
+    /// Event timestamp, if present and parseable
+    pub timestamp: Option<DateTime<Utc>>,
+    /// Level (`INFO`, `DEBUG`, ...)
+    pub level: String,
+    /// Event target (module path)
+    pub target: String,
+    /// Event message
+    pub message: String,
+    /// Names of the enclosing spans, outermost first
+    pub span_names: Vec<String>,
+    /// Event fields merged with inherited span fields
+    pub fields: Map<String, Value>,
+}
+
+impl LogRecord {
+    /// Parse a JSONL line. Returns `None` for lines that aren't JSON objects.
+    pub fn parse(instance: &str, line: usize, text: &str) -> Option<Self> {
+        let Value::Object(mut root) = serde_json::from_str::<Value>(text).ok()? else {
+            return None;
+        };
+
+        let mut merged = Map::new();
+        let mut span_names = Vec::new();
+        let mut absorb_span = |span: Map<String, Value>, merged: &mut Map<String, Value>| {
+            for (key, value) in span {
+                if key == "name" {
+                    if let Value::String(name) = value {
+                        if span_names.last() != Some(&name) {
+                            span_names.push(name);
+                        }
+                    }
+                } else {
+                    merged.insert(key, value);
+                }
+            }
+        };
+
+        // Outer spans first, so inner spans and the event override them
+        if let Some(Value::Array(list)) = root.remove("spans") {
+            for span in list {
+                if let Value::Object(span) = span {
+                    absorb_span(span, &mut merged);
+                }
+            }
+        }
+        if let Some(Value::Object(span)) = root.remove("span") {
+            absorb_span(span, &mut merged);
+        }
+        if let Some(Value::Object(event_fields)) = root.remove("fields") {
+            merged.extend(event_fields);
+        }
+
+        let mut take_str = |key: &str| match root.remove(key) {
+            Some(Value::String(s)) => s,
+            _ => String::new(),
+        };
+        let timestamp = DateTime::parse_from_rfc3339(&take_str("timestamp"))
+            .ok()
+            .map(|t| t.with_timezone(&Utc));
+        let level = take_str("level");
+        let target = take_str("target");
+
+        // Whatever is left at the root is flattened event fields
+        merged.extend(root);
+        let message = match merged.remove("message") {
+            Some(Value::String(s)) => s,
+            Some(other) => other.to_string(),
+            None => String::new(),
+        };
+
+        Some(Self {
+            instance: instance.to_string(),
+            line,
+            timestamp,
+            level,
+            target,
+            message,
+            span_names,
+            fields: merged,
+        })
+    }
+
+    /// A field as a string (numbers and booleans are formatted)
+    pub fn field(&self, name: &str) -> Option<String> {
+        match self.fields.get(name)? {
+            Value::String(s) => Some(s.clone()),
+            Value::Null => None,
+            other => Some(other.to_string()),
+        }
+    }
+
+    /// Correlation trace ID
+    pub fn trace_id(&self) -> Option<String> {
+        self.field(fields::TRACE_ID)
+    }
+
+    /// Correlation span ID
+    pub fn span_id(&self) -> Option<String> {
+        self.field(fields::SPAN_ID)
+    }
+
+    /// Parent span ID
+    pub fn parent_span_id(&self) -> Option<String> {
+        self.field(fields::PARENT_SPAN_ID)
+    }
+
+    /// Packet ID
+    pub fn packet_id(&self) -> Option<String> {
+        self.field(fields::PACKET_ID)
+    }
+
+    /// Hop count
+    pub fn hop_count(&self) -> Option<u32> {
+        self.field(fields::HOP_COUNT)?.parse().ok()
+    }
+
+    /// Peer that wrote the record, falling back to the instance name
+    pub fn peer(&self) -> String {
+        self.field(fields::PEER_ID)
+            .unwrap_or_else(|| self.instance.clone())
+    }
+
+    /// Whether this record was logged inside a span with this name
+    pub fn in_span(&self, name: &str) -> bool {
+        self.span_names.iter().any(|s| s == name)
+    }
+
+    /// Whether any interface field matches `interface`.
+    ///
+    /// Either side may be a prefix of the other, since some call sites log a
+    /// short hex form.
+    pub fn is_about_interface(&self, interface: &str) -> bool {
+        INTERFACE_FIELDS.iter().filter_map(|f| self.field(f)).any(|value| {
+            !value.is_empty() && (value.starts_with(interface) || interface.starts_with(&value))
+        })
+    }
+}
+
+/// Filter over log records. Every set criterion must match.
+#[derive(Debug, Clone, Default)]
+pub struct LogQuery {
+    trace_id: Option<String>,
+    packet_id: Option<String>,
+    peer: Option<String>,
+    span: Option<String>,
+    interface: Option<String>,
+    fields: Vec<(String, String)>,
+    message: Option<String>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+}
+
+impl LogQuery {
+    /// Match everything
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only records in this trace
+    pub fn trace(mut self, trace_id: impl Into<String>) -> Self {
+        self.trace_id = Some(trace_id.into());
+        self
+    }
+
+    /// Only records about this packet
+    pub fn packet(mut self, packet_id: impl Into<String>) -> Self {
+        self.packet_id = Some(packet_id.into());
+        self
+    }
+
+    /// Only records from this peer
+    pub fn peer(mut self, peer: impl Into<String>) -> Self {
+        self.peer = Some(peer.into());
+        self
+    }
+
+    /// Only records inside a span with this name
+    pub fn span(mut self, name: impl Into<String>) -> Self {
+        self.span = Some(name.into());
+        self
+    }
+
+    /// Only records about this interface
+    pub fn interface(mut self, interface: impl Into<String>) -> Self {
+        self.interface = Some(interface.into());
+        self
+    }
+
+    /// Only records where `name` equals `value`
+    pub fn field(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.fields.push((name.into(), value.into()));
+        self
+    }
+
+    /// Only records whose message contains `text`
+    pub fn message_contains(mut self, text: impl Into<String>) -> Self {
+        self.message = Some(text.into());
+        self
+    }
+
+    /// Only records at or after `time`
+    pub fn since(mut self, time: DateTime<Utc>) -> Self {
+        self.since = Some(time);
+        self
+    }
+
+    /// Only records at or before `time`
+    pub fn until(mut self, time: DateTime<Utc>) -> Self {
+        self.until = Some(time);
+        self
+    }
+
+    /// Parse a `name=value` criterion as used on the command line.
+    ///
+    /// `trace`, `packet`, `peer`, `span`, `interface`, and `message` map to
+    /// the dedicated filters; any other name is an exact field match.
+    pub fn with_criterion(self, criterion: &str) -> Option<Self> {
+        let (name, value) = criterion.split_once('=')?;
+        Some(match name {
+            "trace" | fields::TRACE_ID => self.trace(value),
+            "packet" | fields::PACKET_ID => self.packet(value),
+            "peer" | fields::PEER_ID => self.peer(value),
+            "span" => self.span(value),
+            "interface" => self.interface(value),
+            "message" => self.message_contains(value),
+            _ => self.field(name, value),
+        })
+    }
+
+    /// Whether a record satisfies this query
+    pub fn matches(&self, record: &LogRecord) -> bool {
+        let eq = |want: &Option<String>, got: Option<String>| {
+            want.as_ref().is_none_or(|w| got.as_deref() == Some(w.as_str()))
+        };
+        eq(&self.trace_id, record.trace_id())
+            && eq(&self.packet_id, record.packet_id())
+            && self.peer.as_ref().is_none_or(|p| record.peer() == *p)
+            && self.span.as_ref().is_none_or(|s| record.in_span(s))
+            && self.interface.as_ref().is_none_or(|i| record.is_about_interface(i))
+            && self.message.as_ref().is_none_or(|m| record.message.contains(m.as_str()))
+            && self
+                .fields
+                .iter()
+                .all(|(name, value)| record.field(name).as_deref() == Some(value.as_str()))
+            && self.since.is_none_or(|t| record.timestamp.is_some_and(|r| r >= t))
+            && self.until.is_none_or(|t| record.timestamp.is_some_and(|r| r <= t))
+    }
+}
+
+/// All records sharing one correlation trace ID
+#[derive(Debug, Clone)]
+pub struct Trace<'a> {
+    pub trace_id: String,
+    /// Records in time order
+    pub records: Vec<&'a LogRecord>,
+}
+
+impl Trace<'_> {
+    /// Peers that logged anything in this trace, in order of first appearance
+    pub fn peers(&self) -> Vec<String> {
+        let mut seen = HashSet::new();
+        self.records
+            .iter()
+            .map(|r| r.peer())
+            .filter(|p| seen.insert(p.clone()))
+            .collect()
+    }
+
+    /// Time from the first to the last record, if both have timestamps
+    pub fn duration(&self) -> Option<chrono::Duration> {
+        let first = self.records.first()?.timestamp?;
+        let last = self.records.last()?.timestamp?;
+        Some(last - first)
+    }
+}
+
+/// One hop on a packet's path
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hop {
+    pub instance: String,
+    pub peer: String,
+    pub hop_count: Option<u32>,
+    pub span_id: Option<String>,
+    pub parent_span_id: Option<String>,
+    pub timestamp: Option<DateTime<Utc>>,
+    pub message: String,
+    /// Routing decision, if logged
+    pub decision: Option<String>,
+}
+
+impl Hop {
+    fn from_record(record: &LogRecord) -> Self {
+        Self {
+            instance: record.instance.clone(),
+            peer: record.peer(),
+            hop_count: record.hop_count(),
+            span_id: record.span_id(),
+            parent_span_id: record.parent_span_id(),
+            timestamp: record.timestamp,
+            message: record.message.clone(),
+            decision: record.field(fields::DECISION),
+        }
+    }
+}
+
+/// A packet's journey across instances
+#[derive(Debug, Clone)]
+pub struct PacketPath {
+    pub packet_id: String,
+    /// Traces the packet was seen in
+    pub trace_ids: Vec<String>,
+    /// One entry per record, in forwarding order
+    pub hops: Vec<Hop>,
+}
+
+impl PacketPath {
+    /// Peers in the order the packet reached them
+    pub fn route(&self) -> Vec<String> {
+        let mut route: Vec<String> = Vec::new();
+        for hop in &self.hops {
+            if route.last() != Some(&hop.peer) {
+                route.push(hop.peer.clone());
+            }
+        }
+        route
+    }
+}
+
+/// A sync round for one interface
+#[derive(Debug, Clone)]
+pub struct SyncRound<'a> {
+    /// Trace ID, or the span ID for rounds logged without one
+    pub id: String,
+    pub peers: Vec<String>,
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    /// Records in time order
+    pub records: Vec<&'a LogRecord>,
+}
+
+/// Records from one or more instances, merged in time order
+#[derive(Debug, Clone, Default)]
+pub struct LogSet {
+    records: Vec<LogRecord>,
+}
+
+impl LogSet {
+    /// Empty set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load JSONL files, one instance per file named after its file stem
+    pub fn load<P: AsRef<Path>>(paths: &[P]) -> Result<Self, AnalysisError> {
+        let mut set = Self::new();
+        for path in paths {
+            let path = path.as_ref();
+            let io_err = |source| AnalysisError::Io {
+                path: path.display().to_string(),
+                source,
+            };
+            let file = File::open(path).map_err(io_err)?;
+            let instance = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string());
+            set.add_reader(&instance, BufReader::new(file))
+                .map_err(io_err)?;
+        }
+        Ok(set)
+    }
+
+    /// Add an instance's JSONL. Lines that aren't JSON objects are skipped.
+    pub fn add_reader(&mut self, instance: &str, reader: impl BufRead) -> std::io::Result<()> {
+        for (i, line) in reader.lines().enumerate() {
+            if let Some(record) = LogRecord::parse(instance, i + 1, &line?) {
+                self.records.push(record);
+            }
+        }
+        // Stable sort keeps per-file order for records with equal or
+        // missing timestamps
+        self.records.sort_by_key(|r| r.timestamp);
+        Ok(())
+    }
+
+    /// All records in time order
+    pub fn records(&self) -> &[LogRecord] {
+        &self.records
+    }
+
+    /// Records matching a query, in time order
+    pub fn query(&self, query: &LogQuery) -> Vec<&LogRecord> {
+        self.records.iter().filter(|r| query.matches(r)).collect()
+    }
+
+    /// Group correlated records into traces, ordered by first record
+    pub fn traces(&self) -> Vec<Trace<'_>> {
+        let mut order: Vec<String> = Vec::new();
+        let mut by_trace: HashMap<String, Vec<&LogRecord>> = HashMap::new();
+        for record in &self.records {
+            if let Some(trace_id) = record.trace_id() {
+                by_trace
+                    .entry(trace_id.clone())
+                    .or_insert_with(|| {
+                        order.push(trace_id);
+                        Vec::new()
+                    })
+                    .push(record);
+            }
+        }
+        order
+            .into_iter()
+            .map(|trace_id| {
+                let records = by_trace.remove(&trace_id).unwrap_or_default();
+                Trace { trace_id, records }
+            })
+            .collect()
+    }
+
+    /// All records for one trace
+    pub fn trace(&self, trace_id: &str) -> Option<Trace<'_>> {
+        let records = self.query(&LogQuery::new().trace(trace_id));
+        (!records.is_empty()).then(|| Trace {
+            trace_id: trace_id.to_string(),
+            records,
+        })
+    }
+
+    /// The full path of a packet.
+    ///
+    /// Starts from records tagged with the packet ID, then pulls in every
+    /// record from the same traces so hops that only logged the trace are
+    /// included. Hops are ordered by hop count, then time.
+    pub fn packet_path(&self, packet_id: &str) -> Option<PacketPath> {
+        let tagged = self.query(&LogQuery::new().packet(packet_id));
+        if tagged.is_empty() {
+            return None;
+        }
+
+        let mut trace_ids: Vec<String> = Vec::new();
+        for trace_id in tagged.iter().filter_map(|r| r.trace_id()) {
+            if !trace_ids.contains(&trace_id) {
+                trace_ids.push(trace_id);
+            }
+        }
+
+        let mut records: Vec<&LogRecord> = self
+            .records
+            .iter()
+            .filter(|r| {
+                r.packet_id().as_deref() == Some(packet_id)
+                    || r.trace_id().is_some_and(|t| trace_ids.contains(&t))
+            })
+            .collect();
+        records.sort_by_key(|r| (r.hop_count().unwrap_or(u32::MAX), r.timestamp));
+
+        Some(PacketPath {
+            packet_id: packet_id.to_string(),
+            trace_ids,
+            hops: records.into_iter().map(Hop::from_record).collect(),
+        })
+    }
+
+    /// Sync rounds that touched an interface, ordered by start time.
+    ///
+    /// A round is every sync-span record sharing a trace ID (or span ID, if
+    /// the round wasn't correlated) with at least one record about the
+    /// interface.
+    pub fn sync_rounds(&self, interface: &str) -> Vec<SyncRound<'_>> {
+        let mut rounds: BTreeMap<String, Vec<&LogRecord>> = BTreeMap::new();
+        let round_id = |r: &LogRecord| r.trace_id().or_else(|| r.span_id());
+
+        let sync_records: Vec<&LogRecord> = self
+            .records
+            .iter()
+            .filter(|r| SYNC_SPANS.iter().any(|s| r.in_span(s)))
+            .collect();
+        let touching: HashSet<String> = sync_records
+            .iter()
+            .filter(|r| r.is_about_interface(interface))
+            .filter_map(|r| round_id(r))
+            .collect();
+
+        for record in sync_records {
+            if let Some(id) = round_id(record).filter(|id| touching.contains(id)) {
+                rounds.entry(id).or_default().push(record);
+            }
+        }
+
+        let mut rounds: Vec<SyncRound<'_>> = rounds
+            .into_iter()
+            .map(|(id, records)| {
+                let trace = Trace {
+                    trace_id: id.clone(),
+                    records,
+                };
+                SyncRound {
+                    id,
+                    peers: trace.peers(),
+                    start: trace.records.first().and_then(|r| r.timestamp),
+                    end: trace.records.last().and_then(|r| r.timestamp),
+                    records: trace.records,
+                }
+            })
+            .collect();
+        rounds.sort_by_key(|r| r.start);
+        rounds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn logs() -> LogSet {
+        let a = r#"{"timestamp":"2026-01-01T00:00:00Z","level":"INFO","target":"indras_routing","message":"Sending","trace_id":"t1","span_id":"s1","packet_id":"0041#3","hop_count":0,"span":{"name":"route_packet","peer_id":"A"}}
+{"timestamp":"2026-01-01T00:00:05Z","level":"DEBUG","target":"indras_sync","fields":{"message":"Merging","interface_id":"abcd1234ef"},"spans":[{"name":"sync_document","trace_id":"t2","peer_id":"A"},{"name":"merge_changes"}]}
+not json"#;
+        let b = r#"{"timestamp":"2026-01-01T00:00:01Z","level":"INFO","target":"indras_routing","message":"Relaying","trace_id":"t1","span_id":"s2","parent_span_id":"s1","hop_count":1,"decision":"relay","peer_id":"B"}
+{"timestamp":"2026-01-01T00:00:06Z","level":"DEBUG","target":"indras_sync","message":"Applied","spans":[{"name":"sync_document","trace_id":"t2","peer_id":"B"}]}"#;
+        let c = r#"{"timestamp":"2026-01-01T00:00:02Z","level":"INFO","target":"indras_routing","message":"Delivered","trace_id":"t1","span_id":"s3","parent_span_id":"s2","hop_count":2,"peer_id":"C"}"#;
+
+        let mut set = LogSet::new();
+        set.add_reader("A", a.as_bytes()).unwrap();
+        set.add_reader("B", b.as_bytes()).unwrap();
+        set.add_reader("C", c.as_bytes()).unwrap();
+        set
+    }
+
+    #[test]
+    fn test_parse_merges_span_and_event_fields() {
+        let set = logs();
+        let merge = set
+            .records()
+            .iter()
+            .find(|r| r.message == "Merging")
+            .unwrap();
+        assert_eq!(merge.instance, "A");
+        assert_eq!(merge.line, 2);
+        assert_eq!(merge.span_names, vec!["sync_document", "merge_changes"]);
+        assert_eq!(merge.trace_id().as_deref(), Some("t2"));
+        assert_eq!(merge.peer(), "A");
+        assert!(merge.is_about_interface("abcd"));
+        assert!(!merge.is_about_interface("ffff"));
+        assert_eq!(set.records().len(), 5);
+    }
+
+    #[test]
+    fn test_packet_path_stitches_instances() {
+        let set = logs();
+        let path = set.packet_path("0041#3").unwrap();
+        assert_eq!(path.trace_ids, vec!["t1"]);
+        assert_eq!(path.route(), vec!["A", "B", "C"]);
+        assert_eq!(path.hops[1].decision.as_deref(), Some("relay"));
+        assert_eq!(path.hops[2].parent_span_id.as_deref(), Some("s2"));
+        assert!(set.packet_path("nope").is_none());
+    }
+
+    #[test]
+    fn test_sync_rounds_for_interface() {
+        let set = logs();
+        let rounds = set.sync_rounds("abcd1234");
+        assert_eq!(rounds.len(), 1);
+        assert_eq!(rounds[0].id, "t2");
+        assert_eq!(rounds[0].peers, vec!["A", "B"]);
+        assert_eq!(rounds[0].records.len(), 2);
+        assert!(set.sync_rounds("ffff").is_empty());
+    }
+
+    #[test]
+    fn test_query_and_traces() {
+        let set = logs();
+        let traces = set.traces();
+        assert_eq!(traces.len(), 2);
+        assert_eq!(traces[0].peers(), vec!["A", "B", "C"]);
+        assert_eq!(traces[0].duration(), Some(chrono::Duration::seconds(2)));
+
+        let query = LogQuery::new()
+            .with_criterion("peer=B")
+            .unwrap()
+            .with_criterion("decision=relay")
+            .unwrap();
+        let hits = set.query(&query);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].message, "Relaying");
+        assert!(LogQuery::new().with_criterion("no-equals").is_none());
+        assert_eq!(set.query(&LogQuery::new().span(spans::ROUTE_PACKET)).len(), 1);
+    }
+}
@@ -0,0 +1,123 @@
+//! Query JSONL logs from one or more instances.
+//!
+//! Usage:
+//!
+//! ```bash
+//! # Every hop of a packet, across all instances
+//! indras-logq packet 0041#3 logs/*.log
+//!
+//! # One correlated trace, or a summary of all of them
+//! indras-logq trace 6f1c... logs/*.log
+//! indras-logq traces logs/*.log
+//!
+//! # All sync rounds that touched an interface
+//! indras-logq sync abcd1234 logs/*.log
+//!
+//! # Records matching name=value criteria (peer, span, trace, packet,
+//! # interface, message, or any field)
+//! indras-logq query peer=B span=relay_packet logs/*.log
+//! ```
+
+use std::process::ExitCode;
+
+use indras_logging::analysis::{LogQuery, LogRecord, LogSet};
+
+const USAGE: &str = "usage: indras-logq <packet ID | trace ID | traces | sync INTERFACE | query [NAME=VALUE...]> FILE...";
+
+fn time(record: &LogRecord) -> String {
+    record
+        .timestamp
+        .map(|t| t.format("%H:%M:%S%.3f").to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn print_record(record: &LogRecord) {
+    println!(
+        "{} {:>5} [{}] {}:{} {}",
+        time(record),
+        record.level,
+        record.peer(),
+        record.instance,
+        record.line,
+        record.message
+    );
+}
+
+fn load(files: &[String]) -> Result<LogSet, String> {
+    if files.is_empty() {
+        return Err(USAGE.to_string());
+    }
+    LogSet::load(files).map_err(|e| e.to_string())
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let (command, rest) = args.split_first().ok_or(USAGE)?;
+    match command.as_str() {
+        "packet" => {
+            let (packet_id, files) = rest.split_first().ok_or(USAGE)?;
+            let logs = load(files)?;
+            let path = logs
+                .packet_path(packet_id)
+                .ok_or_else(|| format!("packet {} not found", packet_id))?;
+            println!("packet {} via {}", path.packet_id, path.route().join(" -> "));
+            for hop in &path.hops {
+                let hop_count = hop.hop_count.map(|h| h.to_string()).unwrap_or_default();
+                let decision = hop.decision.as_deref().unwrap_or("");
+                println!("  hop {:>2} [{}] {} {}", hop_count, hop.peer, hop.message, decision);
+            }
+        }
+        "trace" => {
+            let (trace_id, files) = rest.split_first().ok_or(USAGE)?;
+            let logs = load(files)?;
+            let trace = logs
+                .trace(trace_id)
+                .ok_or_else(|| format!("trace {} not found", trace_id))?;
+            println!("trace {} across {}", trace.trace_id, trace.peers().join(", "));
+            trace.records.iter().for_each(|r| print_record(r));
+        }
+        "traces" => {
+            let logs = load(rest)?;
+            for trace in logs.traces() {
+                let millis = trace.duration().map(|d| d.num_milliseconds()).unwrap_or(0);
+                println!(
+                    "{} {:>4} records {:>6}ms {}",
+                    trace.trace_id,
+                    trace.records.len(),
+                    millis,
+                    trace.peers().join(",")
+                );
+            }
+        }
+        "sync" => {
+            let (interface, files) = rest.split_first().ok_or(USAGE)?;
+            let logs = load(files)?;
+            for round in logs.sync_rounds(interface) {
+                println!("round {} across {}", round.id, round.peers.join(", "));
+                round.records.iter().for_each(|r| print_record(r));
+            }
+        }
+        "query" => {
+            let (criteria, files): (Vec<String>, Vec<String>) =
+                rest.iter().cloned().partition(|a| a.contains('='));
+            let query = criteria
+                .iter()
+                .try_fold(LogQuery::new(), |q, c| q.with_criterion(c))
+                .ok_or(USAGE)?;
+            let logs = load(&files)?;
+            logs.query(&query).into_iter().for_each(print_record);
+        }
+        _ => return Err(USAGE.to_string()),
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -2,9 +2,14 @@
 
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::time::Duration;
 
+use indras_core::PeerIdentity;
 use serde::{Deserialize, Serialize};
 
+/// Resource attribute identifying the peer that emitted traces and metrics
+pub const PEER_ID_ATTRIBUTE: &str = "indras.peer_id";
+
 /// Main logging configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LogConfig {
@@ -209,6 +214,20 @@ pub struct OtelConfig {
     pub sample_ratio: f64,
     /// Additional resource attributes
     pub resource_attributes: HashMap<String, String>,
+    /// Whether to export OTLP metrics alongside traces
+    #[serde(default = "default_true")]
+    pub metrics_enabled: bool,
+    /// How often metrics are pushed to the collector, in seconds
+    #[serde(default = "default_metrics_interval")]
+    pub metrics_interval_secs: u64,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_metrics_interval() -> u64 {
+    30
 }
 
 impl Default for OtelConfig {
@@ -221,6 +240,8 @@ impl Default for OtelConfig {
                 .unwrap_or_else(|_| "indras-network".to_string()),
             sample_ratio: 1.0,
             resource_attributes: HashMap::new(),
+            metrics_enabled: true,
+            metrics_interval_secs: default_metrics_interval(),
         }
     }
 }
@@ -248,6 +269,37 @@ impl OtelConfig {
         self.sample_ratio = ratio.clamp(0.0, 1.0);
         self
     }
+
+    /// Add a resource attribute to traces and metrics
+    pub fn with_resource_attribute(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.resource_attributes.insert(key.into(), value.into());
+        self
+    }
+
+    /// Identify this node's telemetry by peer
+    ///
+    /// Sets the `indras.peer_id` resource attribute. Without it, the peer in
+    /// the active [`PeerContextGuard`](crate::PeerContextGuard) at init time
+    /// is used, if any.
+    pub fn with_peer_identity<I: PeerIdentity>(self, identity: &I) -> Self {
+        self.with_resource_attribute(PEER_ID_ATTRIBUTE, identity.short_id())
+    }
+
+    /// Enable or disable metrics export
+    pub fn with_metrics(mut self, enabled: bool) -> Self {
+        self.metrics_enabled = enabled;
+        self
+    }
+
+    /// Set the metrics export interval
+    pub fn with_metrics_interval(mut self, interval: Duration) -> Self {
+        self.metrics_interval_secs = interval.as_secs().max(1);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -279,6 +331,20 @@ mod tests {
         assert!(config.otel.enabled);
     }
 
+    #[test]
+    fn test_otel_peer_identity_attribute() {
+        let peer = indras_core::SimulationIdentity::new('B').unwrap();
+        let config = OtelConfig::default()
+            .with_peer_identity(&peer)
+            .with_metrics_interval(Duration::from_millis(10));
+        assert_eq!(
+            config.resource_attributes.get(PEER_ID_ATTRIBUTE).map(String::as_str),
+            Some("B")
+        );
+        assert!(config.metrics_enabled);
+        assert_eq!(config.metrics_interval_secs, 1);
+    }
+
     #[test]
     fn test_otel_config_from_env() {
         let config = OtelConfig::from_env();
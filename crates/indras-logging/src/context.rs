@@ -1,13 +1,20 @@
-//! Peer context injection for multi-instance logging
+//! Peer and interface context injection for multi-instance logging
 //!
 //! This module provides thread-local storage for peer identity context,
-//! allowing automatic injection of peer_id into all log entries within a scope.
+//! allowing automatic injection of peer_id into all log entries within a scope,
+//! and the same for the interface (realm) an operation belongs to. Interfaces
+//! can be muted to silence a noisy realm while debugging others.
 
 use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::fmt::Display;
 
 use indras_core::PeerIdentity;
+use parking_lot::RwLock;
 use uuid::Uuid;
 
+use crate::correlation::spans;
+
 /// Peer context data stored in thread-local storage
 #[derive(Debug, Clone)]
 pub struct PeerContextData {
@@ -148,6 +155,124 @@ macro_rules! with_peer_context {
     }};
 }
 
+/// Interface context data stored in thread-local storage or on a span
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterfaceContextData {
+    /// The interface ID as logged (usually short hex)
+    pub interface_id: String,
+    /// Human-readable realm alias, if known
+    pub realm: Option<String>,
+}
+
+impl InterfaceContextData {
+    /// Whether this interface is currently muted
+    ///
+    /// A mute entry matches the realm alias exactly or the interface ID by
+    /// prefix, so a short hex ID mutes the full one and vice versa.
+    pub fn is_muted(&self) -> bool {
+        let muted = MUTED_INTERFACES.read();
+        !muted.is_empty()
+            && muted.iter().any(|m| {
+                self.realm.as_deref() == Some(m.as_str())
+                    || (!self.interface_id.is_empty()
+                        && (self.interface_id.starts_with(m.as_str())
+                            || m.starts_with(&self.interface_id)))
+            })
+    }
+}
+
+thread_local! {
+    static INTERFACE_CONTEXT: RefCell<Option<InterfaceContextData>> = const { RefCell::new(None) };
+}
+
+/// Interfaces whose events are dropped by `InterfaceContextLayer`
+static MUTED_INTERFACES: RwLock<BTreeSet<String>> = RwLock::new(BTreeSet::new());
+
+/// RAII guard for interface context
+///
+/// Works like [`PeerContextGuard`] for the interface (realm) being operated
+/// on. The guard is thread-local, so it only covers synchronous code; for
+/// async realm operations, instrument the future with [`interface_span`]
+/// instead.
+///
+/// # Example
+///
+/// ```ignore
+/// use indras_logging::context::InterfaceContextGuard;
+///
+/// let _guard = InterfaceContextGuard::new(realm.id(), realm.name());
+/// tracing::debug!("Merging changes");
+/// ```
+pub struct InterfaceContextGuard {
+    previous: Option<InterfaceContextData>,
+}
+
+impl InterfaceContextGuard {
+    /// Set the interface for all log entries in the current scope
+    pub fn new(interface_id: impl Display, realm: Option<&str>) -> Self {
+        let previous = INTERFACE_CONTEXT.with(|ctx| ctx.borrow().clone());
+        let new_ctx = InterfaceContextData {
+            interface_id: interface_id.to_string(),
+            realm: realm.map(str::to_string),
+        };
+        INTERFACE_CONTEXT.with(|ctx| *ctx.borrow_mut() = Some(new_ctx));
+        Self { previous }
+    }
+
+    /// Get the current interface context (if any)
+    pub fn current() -> Option<InterfaceContextData> {
+        INTERFACE_CONTEXT.with(|ctx| ctx.borrow().clone())
+    }
+}
+
+impl Drop for InterfaceContextGuard {
+    fn drop(&mut self) {
+        INTERFACE_CONTEXT.with(|ctx| *ctx.borrow_mut() = self.previous.take());
+    }
+}
+
+/// Create a span that scopes everything inside it to an interface
+///
+/// The span records `interface_id` and `realm`, so they appear on every
+/// JSONL line logged within it.
+///
+/// ```ignore
+/// use tracing::Instrument;
+///
+/// async { realm.sync().await }
+///     .instrument(interface_span(realm.id(), realm.name()))
+///     .await;
+/// ```
+pub fn interface_span(interface_id: impl Display, realm: Option<&str>) -> tracing::Span {
+    tracing::info_span!(
+        spans::REALM_OPERATION,
+        interface_id = %interface_id,
+        realm = realm.unwrap_or_default(),
+    )
+}
+
+/// Mute an interface by ID (or ID prefix) or realm alias
+///
+/// Events logged in a muted interface's context are dropped by every
+/// output until it is unmuted.
+pub fn mute_interface(interface: impl Into<String>) {
+    MUTED_INTERFACES.write().insert(interface.into());
+}
+
+/// Unmute an interface. Returns `false` if it wasn't muted.
+pub fn unmute_interface(interface: &str) -> bool {
+    MUTED_INTERFACES.write().remove(interface)
+}
+
+/// Currently muted interfaces and aliases
+pub fn muted_interfaces() -> Vec<String> {
+    MUTED_INTERFACES.read().iter().cloned().collect()
+}
+
+pub(crate) fn any_interface_muted() -> bool {
+    !MUTED_INTERFACES.read().is_empty()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -194,6 +319,42 @@ mod tests {
         assert!(PeerContextGuard::current_peer_id().is_none());
     }
 
+    #[test]
+    fn test_interface_context_guard() {
+        assert!(InterfaceContextGuard::current().is_none());
+        {
+            let _outer = InterfaceContextGuard::new("aaaa1111", Some("garden"));
+            {
+                let _inner = InterfaceContextGuard::new("bbbb2222", None);
+                assert_eq!(InterfaceContextGuard::current().unwrap().interface_id, "bbbb2222");
+            }
+            let ctx = InterfaceContextGuard::current().unwrap();
+            assert_eq!(ctx.interface_id, "aaaa1111");
+            assert_eq!(ctx.realm.as_deref(), Some("garden"));
+        }
+        assert!(InterfaceContextGuard::current().is_none());
+    }
+
+    #[test]
+    fn test_mute_matches_prefix_and_alias() {
+        let ctx = InterfaceContextData {
+            interface_id: "c0ffee00deadbeef".to_string(),
+            realm: Some("noisy-realm".to_string()),
+        };
+        assert!(!ctx.is_muted());
+
+        mute_interface("c0ffee00");
+        assert!(ctx.is_muted());
+        assert!(unmute_interface("c0ffee00"));
+        assert!(!ctx.is_muted());
+
+        mute_interface("noisy-realm");
+        assert!(ctx.is_muted());
+        assert!(muted_interfaces().contains(&"noisy-realm".to_string()));
+        unmute_interface("noisy-realm");
+        assert!(!unmute_interface("noisy-realm"));
+    }
+
     #[test]
     fn test_with_instance_id() {
         let peer = SimulationIdentity::new('X').unwrap();
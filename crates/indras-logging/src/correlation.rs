@@ -174,6 +174,8 @@ pub mod fields {
     pub const MESSAGE_ID: &str = "message_id";
     pub const HOP_COUNT: &str = "hop_count";
     pub const PEER_ID: &str = "peer_id";
+    pub const INTERFACE_ID: &str = "interface_id";
+    pub const REALM: &str = "realm";
     pub const SOURCE: &str = "source";
     pub const DESTINATION: &str = "destination";
     pub const DECISION: &str = "decision";
@@ -215,6 +217,9 @@ pub mod spans {
     pub const GOSSIP_RECEIVE: &str = "gossip_receive";
     pub const GOSSIP_JOIN_TOPIC: &str = "gossip_join_topic";
 
+    // Interface scoping
+    pub const REALM_OPERATION: &str = "realm_operation";
+
     // Messaging spans
     pub const SEND_MESSAGE: &str = "send_message";
     pub const RECEIVE_MESSAGE: &str = "receive_message";
@@ -0,0 +1,92 @@
+//! Runtime log filter reconfiguration
+//!
+//! [`IndrasSubscriberBuilder::init`](crate::IndrasSubscriberBuilder::init)
+//! installs its level filter behind a reload layer, so verbosity can be
+//! changed on a running process without a restart:
+//!
+//! ```ignore
+//! indras_logging::set_filter("info,indras_sync=trace")?;
+//! ```
+//!
+//! Directives use the same syntax as `RUST_LOG`.
+
+use std::sync::OnceLock;
+
+use tracing_subscriber::{EnvFilter, Registry, reload};
+
+/// Errors from changing the log filter
+#[derive(Debug, thiserror::Error)]
+pub enum FilterError {
+    /// Logging was not initialized through `IndrasSubscriberBuilder`
+    #[error("log filter is not reloadable: logging was not initialized by indras-logging")]
+    NotInstalled,
+
+    /// The directives could not be parsed
+    #[error("invalid log filter: {0}")]
+    Invalid(String),
+
+    /// The subscriber holding the filter is gone
+    #[error("failed to reload log filter: {0}")]
+    Reload(String),
+}
+
+type FilterHandle = reload::Handle<EnvFilter, Registry>;
+
+/// Handle to the global subscriber's filter. The first initialized
+/// subscriber wins, matching the global default.
+static HANDLE: OnceLock<FilterHandle> = OnceLock::new();
+
+/// Wrap the base filter in a reload layer and remember its handle
+pub(crate) fn reloadable(filter: EnvFilter) -> reload::Layer<EnvFilter, Registry> {
+    let (layer, handle) = reload::Layer::new(filter);
+    let _ = HANDLE.set(handle);
+    layer
+}
+
+fn parse(directives: &str) -> Result<EnvFilter, FilterError> {
+    EnvFilter::try_new(directives).map_err(|e| FilterError::Invalid(e.to_string()))
+}
+
+fn apply(handle: &FilterHandle, directives: &str) -> Result<(), FilterError> {
+    let filter = parse(directives)?;
+    handle
+        .reload(filter)
+        .map_err(|e| FilterError::Reload(e.to_string()))
+}
+
+/// Replace the global log filter, e.g. `"indras_sync=trace"`
+pub fn set_filter(directives: &str) -> Result<(), FilterError> {
+    let handle = HANDLE.get().ok_or(FilterError::NotInstalled)?;
+    apply(handle, directives)?;
+    tracing::info!(filter = directives, "Log filter changed");
+    Ok(())
+}
+
+/// The global log filter's current directives
+pub fn current_filter() -> Option<String> {
+    HANDLE.get()?.with_current(|f| f.to_string()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_invalid_directives() {
+        assert!(matches!(parse("indras_sync=loud"), Err(FilterError::Invalid(_))));
+        assert!(parse("info,indras_sync=trace").is_ok());
+    }
+
+    #[test]
+    fn test_reload_changes_filter() {
+        let (layer, handle) = reload::Layer::new(EnvFilter::new("info"));
+        let subscriber = Registry::default().with(layer);
+        tracing::subscriber::with_default(subscriber, || {
+            assert!(!tracing::enabled!(tracing::Level::DEBUG));
+            apply(&handle, "debug").unwrap();
+            assert!(tracing::enabled!(tracing::Level::DEBUG));
+            assert_eq!(handle.with_current(|f| f.to_string()).unwrap(), "debug");
+        });
+    }
+}
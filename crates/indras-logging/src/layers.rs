@@ -3,13 +3,18 @@
 //! This module provides layers that inject peer context and other
 //! Indras-specific fields into tracing events.
 
+use tracing::field::{Field, Visit};
 use tracing::{Event, Subscriber, span};
 use tracing_subscriber::{
     layer::{Context, Layer},
     registry::LookupSpan,
 };
 
-use crate::context::{PeerContextData, PeerContextGuard};
+use crate::context::{
+    InterfaceContextData, InterfaceContextGuard, PeerContextData, PeerContextGuard,
+    any_interface_muted,
+};
+use crate::correlation::fields;
 
 /// Layer that injects peer context into spans and events
 ///
@@ -56,6 +61,113 @@ where
     }
 }
 
+/// Layer that scopes spans to an interface and drops events from muted ones
+///
+/// A span gets an interface if it records `interface_id` (and optionally
+/// `realm`) itself, as [`interface_span`](crate::context::interface_span)
+/// does, or if it is created under an [`InterfaceContextGuard`]. An event
+/// belongs to the interface named in its own fields, else the innermost
+/// scoped span, else the active guard. Events in a muted interface are
+/// disabled for every output.
+pub struct InterfaceContextLayer;
+
+impl InterfaceContextLayer {
+    /// Create a new interface context layer
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for InterfaceContextLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Extension data stored on spans scoped to an interface
+#[derive(Debug, Clone)]
+pub struct InterfaceContextExtension {
+    pub data: InterfaceContextData,
+}
+
+/// Collects interface fields from span attributes or event fields
+#[derive(Default)]
+struct InterfaceFieldVisitor {
+    interface_id: Option<String>,
+    realm: Option<String>,
+}
+
+impl InterfaceFieldVisitor {
+    fn record(&mut self, field: &Field, value: String) {
+        match field.name() {
+            fields::INTERFACE_ID => self.interface_id = Some(value),
+            fields::REALM if !value.is_empty() => self.realm = Some(value),
+            _ => {}
+        }
+    }
+
+    fn into_data(self) -> Option<InterfaceContextData> {
+        Some(InterfaceContextData {
+            interface_id: self.interface_id?,
+            realm: self.realm,
+        })
+    }
+}
+
+impl Visit for InterfaceFieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record(field, value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.record(field, format!("{:?}", value));
+    }
+}
+
+impl<S> Layer<S> for InterfaceContextLayer
+where
+    S: Subscriber + for<'lookup> LookupSpan<'lookup>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        let mut visitor = InterfaceFieldVisitor::default();
+        attrs.record(&mut visitor);
+        let data = visitor.into_data().or_else(InterfaceContextGuard::current);
+        if let (Some(data), Some(span)) = (data, ctx.span(id)) {
+            span.extensions_mut().insert(InterfaceContextExtension { data });
+        }
+    }
+
+    fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        let mut visitor = InterfaceFieldVisitor::default();
+        values.record(&mut visitor);
+        if let (Some(data), Some(span)) = (visitor.into_data(), ctx.span(id)) {
+            span.extensions_mut().replace(InterfaceContextExtension { data });
+        }
+    }
+
+    fn event_enabled(&self, event: &Event<'_>, ctx: Context<'_, S>) -> bool {
+        // Fast path: nothing muted
+        if !any_interface_muted() {
+            return true;
+        }
+
+        let mut visitor = InterfaceFieldVisitor::default();
+        event.record(&mut visitor);
+        let data = visitor
+            .into_data()
+            .or_else(|| {
+                ctx.event_scope(event)?.find_map(|span| {
+                    span.extensions()
+                        .get::<InterfaceContextExtension>()
+                        .map(|ext| ext.data.clone())
+                })
+            })
+            .or_else(InterfaceContextGuard::current);
+
+        !data.is_some_and(|d| d.is_muted())
+    }
+}
+
 /// Layer that adds timestamp formatting
 pub struct TimestampLayer {
     /// Whether to use RFC3339 format (default) or Unix timestamp
@@ -134,6 +246,46 @@ mod tests {
         let _layer = PeerContextLayer::new();
     }
 
+    #[test]
+    fn test_interface_layer_mutes_scoped_events() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tracing_subscriber::layer::SubscriberExt;
+
+        struct CountLayer(Arc<AtomicUsize>);
+        impl<S: Subscriber> Layer<S> for CountLayer {
+            fn on_event(&self, _event: &Event<'_>, _ctx: Context<'_, S>) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let subscriber = tracing_subscriber::Registry::default()
+            .with(InterfaceContextLayer::new())
+            .with(CountLayer(Arc::clone(&count)));
+
+        tracing::subscriber::with_default(subscriber, || {
+            crate::context::mute_interface("5e1ec7ed");
+
+            let muted = crate::context::interface_span("5e1ec7ed00112233", Some("busy"));
+            muted.in_scope(|| tracing::info!("dropped"));
+            tracing::info!(interface_id = "5e1ec7ed00112233", "dropped by field");
+            {
+                let _guard = InterfaceContextGuard::new("5e1ec7ed00112233", None);
+                tracing::info!("dropped by guard");
+            }
+
+            let quiet = crate::context::interface_span("0a0a0a0a", Some("quiet"));
+            quiet.in_scope(|| tracing::info!("kept"));
+            tracing::info!("kept outside any interface");
+
+            crate::context::unmute_interface("5e1ec7ed");
+            muted.in_scope(|| tracing::info!("kept after unmute"));
+        });
+
+        assert_eq!(count.load(Ordering::SeqCst), 3);
+    }
+
     #[test]
     fn test_peer_context_extension() {
         let peer = SimulationIdentity::new('A').unwrap();
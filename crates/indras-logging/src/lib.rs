@@ -9,6 +9,7 @@
 //! - **Multi-Instance Correlation**: Track packets across peer instances with correlation IDs
 //! - **Peer Context Injection**: Automatically include peer identity in all logs
 //! - **OpenTelemetry Integration**: Distributed tracing with Jaeger/Zipkin/OTLP
+//! - **Metrics**: OTLP counters and histograms through the [`metrics`] facade
 //! - **File Rotation**: Daily/hourly log rotation via tracing-appender
 //!
 //! # Quick Start
@@ -41,6 +42,16 @@
 //! tracing::info!("Processing packet");
 //! ```
 //!
+//! # Interface Context
+//!
+//! Realm operations run inside [`interface_span`], which tags every log line
+//! with `interface_id` and `realm`. [`mute_interface`] silences one noisy
+//! realm without touching the level filter:
+//!
+//! ```ignore
+//! indras_logging::mute_interface("home");
+//! ```
+//!
 //! # Correlation IDs
 //!
 //! Use [`CorrelationContext`] to track packets across instances:
@@ -61,16 +72,54 @@
 //!     "Routing packet"
 //! );
 //! ```
+//!
+//! # Metrics
+//!
+//! Record through [`metrics`]; calls are no-ops until OpenTelemetry is
+//! enabled on the builder, which also starts OTLP metrics export:
+//!
+//! ```ignore
+//! use indras_logging::metrics;
+//!
+//! metrics::record_sync_duration(interface_hex, elapsed);
+//! let _timer = metrics::timer(metrics::names::STORAGE_OP_DURATION, vec![]);
+//! ```
+//!
+//! # Runtime Filter Changes
+//!
+//! [`set_filter`] swaps the level filter on a running process:
+//!
+//! ```ignore
+//! indras_logging::set_filter("info,indras_sync=trace")?;
+//! ```
+//!
+//! # Log Analysis
+//!
+//! The [`analysis`] module loads JSONL from several instances, stitches
+//! records by correlation ID, and answers queries such as a packet's full
+//! path or every sync round for an interface. The `indras-logq` binary
+//! exposes the same queries on the command line.
 
+pub mod analysis;
 pub mod config;
 pub mod context;
 pub mod correlation;
+pub mod filter;
 pub mod layers;
+pub mod metrics;
 pub mod otel;
 
-pub use config::{ConsoleConfig, FileConfig, JsonlConfig, LogConfig, OtelConfig, RotationStrategy};
-pub use context::{PeerContextData, PeerContextGuard, PeerType};
+pub use analysis::{AnalysisError, LogQuery, LogRecord, LogSet, PacketPath, SyncRound, Trace};
+pub use config::{
+    ConsoleConfig, FileConfig, JsonlConfig, LogConfig, OtelConfig, PEER_ID_ATTRIBUTE,
+    RotationStrategy,
+};
+pub use context::{
+    InterfaceContextData, InterfaceContextGuard, PeerContextData, PeerContextGuard, PeerType,
+    interface_span, mute_interface, muted_interfaces, unmute_interface,
+};
 pub use correlation::{CorrelationContext, CorrelationExt, fields, spans};
+pub use filter::{FilterError, current_filter, set_filter};
 pub use tracing_appender::rolling::Rotation;
 
 use std::fs::{self, File};
@@ -135,6 +184,8 @@ impl IndrasSubscriberBuilder {
     /// This sets the subscriber as the global default and returns a guard
     /// that must be kept alive for the duration of the program (for file output).
     ///
+    /// The level filter can be changed afterwards with [`set_filter`].
+    ///
     /// # Panics
     ///
     /// Panics if a global subscriber has already been set.
@@ -178,10 +229,18 @@ impl IndrasSubscriberBuilder {
             }
         };
 
-        // Build the base registry with env filter and peer context
+        // Build the base registry with a reloadable env filter and peer context
         let registry = Registry::default()
-            .with(env_filter)
-            .with(layers::PeerContextLayer::new());
+            .with(filter::reloadable(env_filter))
+            .with(layers::PeerContextLayer::new())
+            .with(layers::InterfaceContextLayer::new());
+
+        // Metrics don't need a subscriber layer; install the meter provider
+        if self.config.otel.enabled && self.config.otel.metrics_enabled {
+            if let Err(e) = otel::init_otel_metrics(&self.config.otel) {
+                eprintln!("Warning: Failed to initialize OpenTelemetry metrics: {}", e);
+            }
+        }
 
         // Build based on configuration
         // We use separate match arms for pretty vs JSONL console to satisfy the type system
@@ -490,7 +549,8 @@ pub fn init_testing() {
 
 /// Shutdown OpenTelemetry gracefully
 ///
-/// Call this before your application exits to ensure all traces are exported.
+/// Call this before your application exits to ensure all traces and metrics
+/// are exported.
 pub fn shutdown() {
     otel::shutdown_otel();
 }
@@ -0,0 +1,189 @@
+//! Lightweight metrics facade over OpenTelemetry
+//!
+//! Crates record counters and histograms through the functions here without
+//! depending on OpenTelemetry directly. Until [`init_otel_metrics`] installs a
+//! meter provider every call is a no-op, so instrumented hot paths cost
+//! nothing in tests and in processes that don't export metrics.
+//!
+//! Instruments are looked up by name on each call rather than cached, because
+//! an instrument created before the provider is installed stays a no-op
+//! forever.
+//!
+//! [`init_otel_metrics`]: crate::otel::init_otel_metrics
+
+use std::time::{Duration, Instant};
+
+pub use opentelemetry::KeyValue;
+
+/// Meter name used for all Indras instruments
+pub const METER_NAME: &str = "indras-network";
+
+/// Standard metric names
+pub mod names {
+    /// Histogram: seconds spent syncing one interface with its members
+    pub const SYNC_DURATION: &str = "indras.sync.duration";
+    /// Counter: completed interface sync rounds
+    pub const SYNC_ROUNDS: &str = "indras.sync.rounds";
+    /// Histogram: seconds between a message's creation and local delivery
+    pub const MESSAGE_LATENCY: &str = "indras.message.latency";
+    /// Counter: messages sent from this node
+    pub const MESSAGES_SENT: &str = "indras.messages.sent";
+    /// Counter: messages delivered to this node
+    pub const MESSAGES_RECEIVED: &str = "indras.messages.received";
+    /// Counter: incoming events dropped because they were already delivered
+    pub const DUPLICATES_SUPPRESSED: &str = "indras.events.duplicates_suppressed";
+    /// Histogram: seconds per storage operation
+    pub const STORAGE_OP_DURATION: &str = "indras.storage.op.duration";
+    /// Counter: supervised background tasks that panicked or exited unexpectedly
+    pub const TASK_INCIDENTS: &str = "indras.tasks.incidents";
+}
+
+/// Standard attribute keys
+pub mod attrs {
+    /// Interface (realm) the measurement belongs to
+    pub const INTERFACE: &str = "interface";
+    /// Storage operation name (e.g. "append_event")
+    pub const OPERATION: &str = "operation";
+    /// Outcome of the measured operation ("ok" or "error")
+    pub const OUTCOME: &str = "outcome";
+    /// Background task name (e.g. "sync")
+    pub const TASK: &str = "task";
+}
+
+fn meter() -> opentelemetry::metrics::Meter {
+    opentelemetry::global::meter(METER_NAME)
+}
+
+/// Add `value` to the counter `name`
+pub fn add(name: &'static str, value: u64, attributes: &[KeyValue]) {
+    meter().u64_counter(name).build().add(value, attributes);
+}
+
+/// Increment the counter `name` by one
+pub fn increment(name: &'static str, attributes: &[KeyValue]) {
+    add(name, 1, attributes);
+}
+
+/// Record a value in the histogram `name`
+pub fn record(name: &'static str, value: f64, attributes: &[KeyValue]) {
+    meter().f64_histogram(name).build().record(value, attributes);
+}
+
+/// Record a duration, in seconds, in the histogram `name`
+pub fn record_duration(name: &'static str, duration: Duration, attributes: &[KeyValue]) {
+    record(name, duration.as_secs_f64(), attributes);
+}
+
+/// Start timing; the elapsed time is recorded in `name` when the timer drops
+///
+/// # Example
+///
+/// ```ignore
+/// let _timer = metrics::timer(names::SYNC_DURATION, vec![KeyValue::new("interface", id)]);
+/// sync().await?; // recorded on every exit path
+/// ```
+pub fn timer(name: &'static str, attributes: Vec<KeyValue>) -> Timer {
+    Timer {
+        name,
+        attributes,
+        start: Instant::now(),
+        recorded: false,
+    }
+}
+
+/// Records elapsed time into a histogram when dropped
+pub struct Timer {
+    name: &'static str,
+    attributes: Vec<KeyValue>,
+    start: Instant,
+    recorded: bool,
+}
+
+impl Timer {
+    /// Add an attribute before the measurement is recorded
+    pub fn attribute(&mut self, attribute: KeyValue) {
+        self.attributes.push(attribute);
+    }
+
+    /// Record now and return the elapsed time
+    pub fn stop(mut self) -> Duration {
+        self.finish()
+    }
+
+    fn finish(&mut self) -> Duration {
+        let elapsed = self.start.elapsed();
+        if !self.recorded {
+            self.recorded = true;
+            record_duration(self.name, elapsed, &self.attributes);
+        }
+        elapsed
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+/// Record how long syncing an interface took
+pub fn record_sync_duration(interface: impl Into<String>, duration: Duration) {
+    let attributes = [KeyValue::new(attrs::INTERFACE, interface.into())];
+    record_duration(names::SYNC_DURATION, duration, &attributes);
+    increment(names::SYNC_ROUNDS, &attributes);
+}
+
+/// Record end-to-end latency of a delivered message
+pub fn record_message_latency(interface: impl Into<String>, latency: Duration) {
+    let attributes = [KeyValue::new(attrs::INTERFACE, interface.into())];
+    record_duration(names::MESSAGE_LATENCY, latency, &attributes);
+    increment(names::MESSAGES_RECEIVED, &attributes);
+}
+
+/// Record an incoming event dropped as a duplicate
+pub fn record_duplicate_suppressed(interface: impl Into<String>) {
+    let attributes = [KeyValue::new(attrs::INTERFACE, interface.into())];
+    increment(names::DUPLICATES_SUPPRESSED, &attributes);
+}
+
+/// Record a supervised task crash and whether it was restarted
+pub fn record_task_incident(task: &'static str, restarted: bool) {
+    let attributes = [
+        KeyValue::new(attrs::TASK, task),
+        KeyValue::new(attrs::OUTCOME, if restarted { "restarted" } else { "gave_up" }),
+    ];
+    increment(names::TASK_INCIDENTS, &attributes);
+}
+
+/// Record the timing of a storage operation
+pub fn record_storage_op(operation: &'static str, duration: Duration, ok: bool) {
+    let attributes = [
+        KeyValue::new(attrs::OPERATION, operation),
+        KeyValue::new(attrs::OUTCOME, if ok { "ok" } else { "error" }),
+    ];
+    record_duration(names::STORAGE_OP_DURATION, duration, &attributes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_noop_without_provider() {
+        // No provider installed: all calls must be harmless
+        increment(names::MESSAGES_SENT, &[]);
+        record_sync_duration("abcd", Duration::from_millis(5));
+        record_message_latency("abcd", Duration::from_millis(5));
+        record_duplicate_suppressed("abcd");
+        record_storage_op("append_event", Duration::from_micros(40), true);
+        record_task_incident("sync", true);
+    }
+
+    #[test]
+    fn test_timer_records_once() {
+        let timer = timer(names::SYNC_DURATION, vec![KeyValue::new(attrs::INTERFACE, "abcd")]);
+        std::thread::sleep(Duration::from_millis(2));
+        let elapsed = timer.stop();
+        assert!(elapsed >= Duration::from_millis(2));
+    }
+}
@@ -1,18 +1,26 @@
-//! OpenTelemetry integration for distributed tracing
+//! OpenTelemetry integration for distributed tracing and metrics
 //!
 //! This module provides OpenTelemetry tracer setup and integration
-//! with the tracing ecosystem.
+//! with the tracing ecosystem, plus the OTLP meter provider behind
+//! [`crate::metrics`].
 
 use opentelemetry::trace::TracerProvider as _;
 use opentelemetry_otlp::WithExportConfig;
 use opentelemetry_sdk::{
     Resource,
+    metrics::{PeriodicReader, SdkMeterProvider},
     trace::{RandomIdGenerator, Sampler, TracerProvider},
 };
+use parking_lot::Mutex;
+use std::time::Duration;
 use tracing_opentelemetry::OpenTelemetryLayer;
 use tracing_subscriber::registry::LookupSpan;
 
-use crate::config::OtelConfig;
+use crate::config::{OtelConfig, PEER_ID_ATTRIBUTE};
+use crate::context::PeerContextGuard;
+
+/// Installed meter provider, kept so `shutdown_otel` can flush it
+static METER_PROVIDER: Mutex<Option<SdkMeterProvider>> = Mutex::new(None);
 
 /// Error type for OpenTelemetry setup
 #[derive(Debug, thiserror::Error)]
@@ -37,17 +45,7 @@ pub fn init_otel_layer<S>(
 where
     S: tracing::Subscriber + for<'span> LookupSpan<'span>,
 {
-    // Build resource attributes
-    let mut resource_attrs = vec![opentelemetry::KeyValue::new(
-        "service.name",
-        config.service_name.clone(),
-    )];
-
-    for (key, value) in &config.resource_attributes {
-        resource_attrs.push(opentelemetry::KeyValue::new(key.clone(), value.clone()));
-    }
-
-    let resource = Resource::new(resource_attrs);
+    let resource = build_resource(config);
 
     // Create OTLP exporter
     let exporter = opentelemetry_otlp::SpanExporter::builder()
@@ -86,10 +84,64 @@ where
     Ok(layer)
 }
 
-/// Shutdown OpenTelemetry, flushing any pending traces
+/// Build the resource shared by traces and metrics
+///
+/// Includes the service name, configured attributes, and the peer identity
+/// (from config, else the current [`PeerContextGuard`]).
+fn build_resource(config: &OtelConfig) -> Resource {
+    let mut resource_attrs = vec![opentelemetry::KeyValue::new(
+        "service.name",
+        config.service_name.clone(),
+    )];
+
+    for (key, value) in &config.resource_attributes {
+        resource_attrs.push(opentelemetry::KeyValue::new(key.clone(), value.clone()));
+    }
+
+    if !config.resource_attributes.contains_key(PEER_ID_ATTRIBUTE) {
+        if let Some(peer_id) = PeerContextGuard::current_peer_id() {
+            resource_attrs.push(opentelemetry::KeyValue::new(PEER_ID_ATTRIBUTE, peer_id));
+        }
+    }
+
+    Resource::new(resource_attrs)
+}
+
+/// Initialize OTLP metrics export and install the global meter provider
+///
+/// Once installed, everything recorded through [`crate::metrics`] is pushed
+/// to the collector every `metrics_interval_secs`.
+pub fn init_otel_metrics(config: &OtelConfig) -> Result<(), OtelError> {
+    let exporter = opentelemetry_otlp::MetricExporter::builder()
+        .with_tonic()
+        .with_endpoint(&config.endpoint)
+        .build()
+        .map_err(|e| OtelError::ExporterError(e.to_string()))?;
+
+    let reader = PeriodicReader::builder(exporter, opentelemetry_sdk::runtime::Tokio)
+        .with_interval(Duration::from_secs(config.metrics_interval_secs.max(1)))
+        .build();
+
+    let provider = SdkMeterProvider::builder()
+        .with_reader(reader)
+        .with_resource(build_resource(config))
+        .build();
+
+    opentelemetry::global::set_meter_provider(provider.clone());
+    *METER_PROVIDER.lock() = Some(provider);
+
+    Ok(())
+}
+
+/// Shutdown OpenTelemetry, flushing any pending traces and metrics
 ///
 /// Call this before your application exits to ensure all traces are exported.
 pub fn shutdown_otel() {
+    if let Some(provider) = METER_PROVIDER.lock().take() {
+        if let Err(e) = provider.shutdown() {
+            eprintln!("Failed to shut down OpenTelemetry metrics: {e}");
+        }
+    }
     opentelemetry::global::shutdown_tracer_provider();
 }
 
@@ -116,6 +168,19 @@ mod tests {
         assert!(!config.service_name.is_empty());
     }
 
+    #[test]
+    fn test_resource_includes_peer_identity() {
+        let peer = indras_core::SimulationIdentity::new('C').unwrap();
+        let config = OtelConfig::default().with_peer_identity(&peer);
+        let resource = build_resource(&config);
+        assert_eq!(
+            resource
+                .get(opentelemetry::Key::from_static_str(PEER_ID_ATTRIBUTE))
+                .map(|v| v.to_string()),
+            Some("C".to_string())
+        );
+    }
+
     // Note: Full OTel integration tests require a running collector
     // and are better suited for integration tests
 }
@@ -0,0 +1,531 @@
+//! Background agent mode: one node per data directory, many UIs.
+//!
+//! Desktop apps normally stop their node when the window closes, so the
+//! member stops receiving messages. In agent mode the process that owns
+//! the node keeps running with its window hidden, and starts an
+//! [`AgentHost`] on a Unix socket in the data directory. UIs and tools
+//! attach to it with an [`AgentClient`]:
+//!
+//! - a second launch of the app finds the host, sends
+//!   [`AgentRequest::Show`] to bring the hidden window back (with all
+//!   state intact, since the node never stopped), and exits;
+//! - a tool sends [`AgentRequest::Attach`] to get the node's status and a
+//!   live stream of [`EventLine`]s, and drives it with
+//!   [`AgentRequest::Command`] lines in the [`ControlCommand`] syntax;
+//! - [`AgentRequest::SetLogFilter`] changes the host's log verbosity
+//!   without a restart.
+//!
+//! Protocol: newline-delimited JSON over `{data_dir}/agent.sock`. Requests
+//! are tagged by `"op"` and replies by `"reply"`:
+//!
+//! ```text
+//! {"op":"attach","client":"indras-cli"}
+//! ← {"reply":"attached","member_id":"…","display_name":"Ada","realms":[…],"clients":1}
+//! ← {"reply":"event","realm":"…","event":{"id":"…","kind":"text","text":"hi",…}}
+//! {"op":"command","realm":"…","line":"send hello"}
+//! ← {"reply":"ok"}
+//! ```
+//!
+//! The socket is only reachable by local users with access to the data
+//! directory; there is no further authentication.
+
+use crate::error::{IndraError, Result};
+use crate::event_feed::{ControlCommand, EventLine};
+use crate::network::{IndrasNetwork, RealmId};
+use crate::realm::convert_event_to_message;
+use futures::StreamExt;
+use indras_core::InterfaceId;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::net::unix::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+/// Socket filename inside the data directory.
+pub const AGENT_SOCKET_FILENAME: &str = "agent.sock";
+
+/// Path of the agent socket for a data directory.
+pub fn agent_socket_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(AGENT_SOCKET_FILENAME)
+}
+
+/// A request to the agent host.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum AgentRequest {
+    /// Attach as a client and start receiving events.
+    Attach {
+        /// Client name, for logs.
+        client: String,
+    },
+    /// Stop receiving events. Closing the connection also detaches.
+    Detach,
+    /// Bring the host's window back.
+    Show,
+    /// Report the host's status without attaching.
+    Status,
+    /// Run a control command in a realm.
+    Command {
+        /// Hex-encoded realm id.
+        realm: String,
+        /// Command line, as parsed by [`ControlCommand::parse`].
+        line: String,
+    },
+    /// Replace the host's log filter (`RUST_LOG` syntax).
+    SetLogFilter {
+        /// Filter directives, e.g. `info,indras_sync=trace`.
+        filter: String,
+    },
+    /// Stop the node and exit the host process.
+    Shutdown,
+}
+
+/// A reply from the agent host.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "reply", rename_all = "snake_case")]
+pub enum AgentReply {
+    /// Attached; the node's current status.
+    Attached(AgentStatus),
+    /// The node's current status.
+    Status(AgentStatus),
+    /// The request succeeded.
+    Ok,
+    /// A message arrived in a realm. Only sent to attached clients.
+    Event {
+        /// Hex-encoded realm id.
+        realm: String,
+        /// The message.
+        event: EventLine,
+    },
+    /// The request failed.
+    Error {
+        /// What went wrong.
+        message: String,
+    },
+}
+
+/// What an attaching UI needs to restore its state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentStatus {
+    /// Hex-encoded member id of the node.
+    pub member_id: String,
+    /// The node's display name.
+    pub display_name: Option<String>,
+    /// Conversation realms.
+    pub realms: Vec<AgentRealm>,
+    /// Number of attached clients.
+    pub clients: usize,
+}
+
+/// One realm in an [`AgentStatus`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentRealm {
+    /// Hex-encoded realm id.
+    pub id: String,
+    /// Realm name, if known.
+    pub name: Option<String>,
+}
+
+/// Serves the agent socket for a running network.
+///
+/// Dropping the host stops serving and removes the socket file.
+pub struct AgentHost {
+    socket_path: PathBuf,
+    clients: Arc<AtomicUsize>,
+    show_tx: broadcast::Sender<()>,
+    shutdown: CancellationToken,
+    task: JoinHandle<()>,
+}
+
+impl AgentHost {
+    /// Start serving `network` on the agent socket in `data_dir`.
+    ///
+    /// Fails with [`IndraError::InvalidOperation`] if another host is
+    /// already serving that directory. A socket left by a crashed host is
+    /// replaced.
+    pub async fn start(network: Arc<IndrasNetwork>, data_dir: &Path) -> Result<Self> {
+        let socket_path = agent_socket_path(data_dir);
+        if UnixStream::connect(&socket_path).await.is_ok() {
+            return Err(IndraError::InvalidOperation(format!(
+                "an agent is already running at {}",
+                socket_path.display()
+            )));
+        }
+        let _ = tokio::fs::remove_file(&socket_path).await;
+        let listener = UnixListener::bind(&socket_path)?;
+        tracing::info!(path = %socket_path.display(), "agent socket listening");
+
+        let clients = Arc::new(AtomicUsize::new(0));
+        let (show_tx, _) = broadcast::channel(8);
+        let shutdown = CancellationToken::new();
+        let server = Server {
+            network,
+            clients: Arc::clone(&clients),
+            show_tx: show_tx.clone(),
+            shutdown: shutdown.clone(),
+        };
+        let task = tokio::spawn(server.run(listener));
+
+        Ok(Self {
+            socket_path,
+            clients,
+            show_tx,
+            shutdown,
+            task,
+        })
+    }
+
+    /// Number of attached clients.
+    pub fn clients(&self) -> usize {
+        self.clients.load(Ordering::Relaxed)
+    }
+
+    /// Receive a value each time a client asks for the window to be shown.
+    pub fn show_requests(&self) -> broadcast::Receiver<()> {
+        self.show_tx.subscribe()
+    }
+
+    /// Wait until a client asks the host to shut down.
+    pub async fn shutdown_requested(&self) {
+        self.shutdown.cancelled().await
+    }
+}
+
+impl Drop for AgentHost {
+    fn drop(&mut self) {
+        self.task.abort();
+        let _ = std::fs::remove_file(&self.socket_path);
+    }
+}
+
+/// State shared by the accept loop and connections.
+#[derive(Clone)]
+struct Server {
+    network: Arc<IndrasNetwork>,
+    clients: Arc<AtomicUsize>,
+    show_tx: broadcast::Sender<()>,
+    shutdown: CancellationToken,
+}
+
+impl Server {
+    async fn run(self, listener: UnixListener) {
+        loop {
+            let stream = match listener.accept().await {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    tracing::debug!(error = %e, "agent accept error");
+                    continue;
+                }
+            };
+            let server = self.clone();
+            tokio::spawn(async move {
+                if let Err(e) = server.serve(stream).await {
+                    tracing::debug!(error = %e, "agent connection error");
+                }
+            });
+        }
+    }
+
+    async fn serve(&self, stream: UnixStream) -> Result<()> {
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let mut attachment: Option<Attachment> = None;
+
+        loop {
+            let request = tokio::select! {
+                line = lines.next_line() => match line? {
+                    Some(line) => line,
+                    None => break,
+                },
+                Some(event) = next_event(&mut attachment) => {
+                    write_reply(&mut writer, &event).await?;
+                    continue;
+                }
+            };
+
+            let reply = match serde_json::from_str::<AgentRequest>(&request) {
+                Ok(AgentRequest::Attach { client }) => {
+                    if attachment.is_none() {
+                        tracing::info!(%client, "agent client attached");
+                        attachment = Some(Attachment::new(self));
+                    }
+                    AgentReply::Attached(self.status())
+                }
+                Ok(AgentRequest::Detach) => {
+                    attachment = None;
+                    AgentReply::Ok
+                }
+                Ok(AgentRequest::Show) => {
+                    let _ = self.show_tx.send(());
+                    AgentReply::Ok
+                }
+                Ok(AgentRequest::Status) => AgentReply::Status(self.status()),
+                Ok(AgentRequest::Command { realm, line }) => {
+                    match self.command(&realm, &line).await {
+                        Ok(()) => AgentReply::Ok,
+                        Err(e) => AgentReply::Error {
+                            message: e.to_string(),
+                        },
+                    }
+                }
+                Ok(AgentRequest::SetLogFilter { filter }) => match indras_logging::set_filter(&filter) {
+                    Ok(()) => AgentReply::Ok,
+                    Err(e) => AgentReply::Error {
+                        message: e.to_string(),
+                    },
+                },
+                Ok(AgentRequest::Shutdown) => {
+                    self.shutdown.cancel();
+                    AgentReply::Ok
+                }
+                Err(e) => AgentReply::Error {
+                    message: format!("bad request: {e}"),
+                },
+            };
+            write_reply(&mut writer, &reply).await?;
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> AgentStatus {
+        let realms = self
+            .network
+            .conversation_realms()
+            .into_iter()
+            .map(|id| AgentRealm {
+                id: hex::encode(id.as_bytes()),
+                name: self.network.get_realm_by_id(&id).and_then(|r| r.name().map(str::to_string)),
+            })
+            .collect();
+        AgentStatus {
+            member_id: hex::encode(self.network.id()),
+            display_name: self.network.display_name(),
+            realms,
+            clients: self.clients.load(Ordering::Relaxed),
+        }
+    }
+
+    async fn command(&self, realm: &str, line: &str) -> Result<()> {
+        let realm_id = parse_realm_id(realm)?;
+        let realm = self
+            .network
+            .get_realm_by_id(&realm_id)
+            .ok_or_else(|| IndraError::InvalidOperation(format!("unknown realm {realm}")))?;
+        realm.execute(ControlCommand::parse(line)?).await?;
+        Ok(())
+    }
+}
+
+/// An attached client's event subscription.
+struct Attachment {
+    events: futures::stream::BoxStream<'static, AgentReply>,
+    clients: Arc<AtomicUsize>,
+}
+
+impl Attachment {
+    fn new(server: &Server) -> Self {
+        server.clients.fetch_add(1, Ordering::Relaxed);
+        let network = Arc::clone(&server.network);
+        let events = async_stream::stream! {
+            let mut events = std::pin::pin!(network.events());
+            while let Some(event) = events.next().await {
+                let realm_id = event.realm_id;
+                if let Some(message) = convert_event_to_message(event.event, realm_id) {
+                    yield AgentReply::Event {
+                        realm: hex::encode(realm_id.as_bytes()),
+                        event: EventLine::from_message(&message),
+                    };
+                }
+            }
+        };
+        Self {
+            events: events.boxed(),
+            clients: Arc::clone(&server.clients),
+        }
+    }
+}
+
+impl Drop for Attachment {
+    fn drop(&mut self) {
+        self.clients.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// The next event for an attached client; pending forever if detached.
+async fn next_event(attachment: &mut Option<Attachment>) -> Option<AgentReply> {
+    match attachment {
+        Some(attachment) => attachment.events.next().await,
+        None => std::future::pending().await,
+    }
+}
+
+async fn write_reply(writer: &mut OwnedWriteHalf, reply: &AgentReply) -> Result<()> {
+    let mut line =
+        serde_json::to_vec(reply).map_err(|e| IndraError::Serialization(e.to_string()))?;
+    line.push(b'\n');
+    writer.write_all(&line).await?;
+    Ok(())
+}
+
+fn parse_realm_id(hex_id: &str) -> Result<RealmId> {
+    let bytes: [u8; 32] = hex::decode(hex_id)
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| IndraError::InvalidOperation(format!("invalid realm id {hex_id:?}")))?;
+    Ok(InterfaceId::new(bytes))
+}
+
+/// A connection to a running [`AgentHost`].
+pub struct AgentClient {
+    lines: Lines<BufReader<OwnedReadHalf>>,
+    writer: OwnedWriteHalf,
+    /// Events received while waiting for a reply.
+    pending_events: VecDeque<(String, EventLine)>,
+}
+
+impl AgentClient {
+    /// Connect to the host serving `data_dir`, if one is running.
+    pub async fn connect(data_dir: &Path) -> Result<Option<Self>> {
+        let stream = match UnixStream::connect(agent_socket_path(data_dir)).await {
+            Ok(stream) => stream,
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    std::io::ErrorKind::NotFound | std::io::ErrorKind::ConnectionRefused
+                ) =>
+            {
+                return Ok(None);
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let (reader, writer) = stream.into_split();
+        Ok(Some(Self {
+            lines: BufReader::new(reader).lines(),
+            writer,
+            pending_events: VecDeque::new(),
+        }))
+    }
+
+    /// Ask the host serving `data_dir` to show its window.
+    ///
+    /// Returns `false` if no host is running.
+    pub async fn show_running(data_dir: &Path) -> Result<bool> {
+        match Self::connect(data_dir).await? {
+            Some(mut client) => client.request(&AgentRequest::Show).await.map(|_| true),
+            None => Ok(false),
+        }
+    }
+
+    /// Send a request and wait for its reply.
+    ///
+    /// Events that arrive first are kept for [`next_event`](Self::next_event).
+    /// [`AgentReply::Error`] is returned as [`IndraError::InvalidOperation`].
+    pub async fn request(&mut self, request: &AgentRequest) -> Result<AgentReply> {
+        let mut line =
+            serde_json::to_vec(request).map_err(|e| IndraError::Serialization(e.to_string()))?;
+        line.push(b'\n');
+        self.writer.write_all(&line).await?;
+
+        loop {
+            match self.read_reply().await? {
+                AgentReply::Event { realm, event } => self.pending_events.push_back((realm, event)),
+                AgentReply::Error { message } => return Err(IndraError::InvalidOperation(message)),
+                reply => return Ok(reply),
+            }
+        }
+    }
+
+    /// Attach and return the node's status.
+    pub async fn attach(&mut self, client: impl Into<String>) -> Result<AgentStatus> {
+        match self
+            .request(&AgentRequest::Attach {
+                client: client.into(),
+            })
+            .await?
+        {
+            AgentReply::Attached(status) => Ok(status),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    /// Change the host's log filter, e.g. `indras_sync=trace`.
+    pub async fn set_log_filter(&mut self, filter: impl Into<String>) -> Result<()> {
+        self.request(&AgentRequest::SetLogFilter {
+            filter: filter.into(),
+        })
+        .await
+        .map(|_| ())
+    }
+
+    /// Wait for the next event. Returns `None` when the host goes away.
+    ///
+    /// Only attached clients receive events.
+    pub async fn next_event(&mut self) -> Result<Option<(String, EventLine)>> {
+        if let Some(event) = self.pending_events.pop_front() {
+            return Ok(Some(event));
+        }
+        loop {
+            match self.read_reply().await {
+                Ok(AgentReply::Event { realm, event }) => return Ok(Some((realm, event))),
+                Ok(_) => continue,
+                Err(IndraError::NotConnected) => return Ok(None),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    async fn read_reply(&mut self) -> Result<AgentReply> {
+        let line = self.lines.next_line().await?.ok_or(IndraError::NotConnected)?;
+        serde_json::from_str(&line).map_err(|e| IndraError::Serialization(e.to_string()))
+    }
+}
+
+fn unexpected(reply: AgentReply) -> IndraError {
+    IndraError::InvalidOperation(format!("unexpected agent reply: {reply:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wire_format() {
+        let request: AgentRequest =
+            serde_json::from_str(r#"{"op":"command","realm":"ab","line":"send hi"}"#).unwrap();
+        assert_eq!(
+            request,
+            AgentRequest::Command {
+                realm: "ab".to_string(),
+                line: "send hi".to_string(),
+            }
+        );
+        assert_eq!(serde_json::to_string(&AgentReply::Ok).unwrap(), r#"{"reply":"ok"}"#);
+        let request: AgentRequest =
+            serde_json::from_str(r#"{"op":"set_log_filter","filter":"indras_sync=trace"}"#).unwrap();
+        assert_eq!(
+            request,
+            AgentRequest::SetLogFilter {
+                filter: "indras_sync=trace".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_realm_id() {
+        let id = InterfaceId::new([9; 32]);
+        assert_eq!(parse_realm_id(&hex::encode(id.as_bytes())).unwrap(), id);
+        assert!(parse_realm_id("abcd").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_connect_without_host() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(AgentClient::connect(dir.path()).await.unwrap().is_none());
+    }
+}
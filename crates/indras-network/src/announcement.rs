@@ -0,0 +1,315 @@
+//! Realm-wide announcements that members acknowledge.
+//!
+//! Admins post an [`Announcement`] into the realm's [`AnnouncementDocument`],
+//! optionally scheduled for later and optionally expiring. Each member
+//! acknowledges it once; the acknowledgments sync with the document, so an
+//! admin can see who has and has not read it ([`AnnouncementSummary`]).
+//! Members who have not acknowledged an active announcement get periodic
+//! [`AnnouncementReminder`]s from
+//! [`Realm::announcement_reminders`](crate::Realm::announcement_reminders).
+//!
+//! Only announcements written by admins of the realm's membership log are
+//! shown; the caller passes the admin check in, as with the archive state.
+
+use crate::document::DocumentSchema;
+use crate::member::MemberId;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Document name for announcements within a realm.
+pub const ANNOUNCEMENT_DOC: &str = "_announcements";
+
+/// Longest announcement title accepted, in bytes.
+pub const MAX_ANNOUNCEMENT_TITLE_LEN: usize = 256;
+
+/// Unique identifier for an announcement.
+pub type AnnouncementId = String;
+
+/// An announcement posted by an admin.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Announcement {
+    /// Unique ID.
+    pub id: AnnouncementId,
+    /// Admin who posted it.
+    pub author: MemberId,
+    /// Short headline.
+    pub title: String,
+    /// Full text.
+    pub body: String,
+    /// When it was posted (milliseconds since epoch).
+    pub posted_at_millis: u64,
+    /// When it becomes visible (milliseconds since epoch).
+    pub publish_at_millis: u64,
+    /// When it stops being shown, if ever (milliseconds since epoch).
+    #[serde(default)]
+    pub expires_at_millis: Option<u64>,
+}
+
+impl Announcement {
+    /// Whether the announcement is published and not yet expired at `now`.
+    pub fn is_active(&self, now_millis: u64) -> bool {
+        self.publish_at_millis <= now_millis
+            && self.expires_at_millis.is_none_or(|e| now_millis < e)
+    }
+}
+
+/// Who has acknowledged an announcement, for its author.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnnouncementSummary {
+    /// The announcement.
+    pub announcement: Announcement,
+    /// Members who acknowledged, with when (milliseconds since epoch).
+    pub acknowledged: BTreeMap<MemberId, u64>,
+    /// Members who have not acknowledged yet.
+    pub pending: BTreeSet<MemberId>,
+}
+
+impl AnnouncementSummary {
+    /// Fraction of members who acknowledged (1.0 if there is no one to ask).
+    pub fn acknowledged_ratio(&self) -> f32 {
+        let total = self.acknowledged.len() + self.pending.len();
+        if total == 0 {
+            return 1.0;
+        }
+        self.acknowledged.len() as f32 / total as f32
+    }
+}
+
+/// A nudge to acknowledge an announcement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnnouncementReminder {
+    /// The unacknowledged announcement.
+    pub announcement: Announcement,
+    /// How long it has been published without an acknowledgment.
+    pub outstanding_millis: u64,
+}
+
+/// CRDT document of a realm's announcements and acknowledgments.
+///
+/// # Example
+///
+/// ```ignore
+/// // As an admin:
+/// let id = realm.post_announcement("Office closed", "Friday is a holiday.", None, None).await?;
+///
+/// // As a member:
+/// for announcement in realm.pending_announcements().await? {
+///     realm.acknowledge_announcement(&announcement.id).await?;
+/// }
+///
+/// // Back as the admin:
+/// let summary = realm.announcement_summary(&id).await?;
+/// println!("{} still to acknowledge", summary.pending.len());
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnnouncementDocument {
+    /// Announcements keyed by ID.
+    pub announcements: BTreeMap<AnnouncementId, Announcement>,
+    /// Acknowledgment time per announcement and member.
+    pub acks: BTreeMap<AnnouncementId, BTreeMap<MemberId, u64>>,
+}
+
+impl AnnouncementDocument {
+    /// Create an empty announcement board.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an announcement. Returns `false` if the ID is taken.
+    pub fn post(&mut self, announcement: Announcement) -> bool {
+        if self.announcements.contains_key(&announcement.id) {
+            return false;
+        }
+        self.announcements
+            .insert(announcement.id.clone(), announcement);
+        true
+    }
+
+    /// Record a member's acknowledgment.
+    ///
+    /// Returns `false` if the announcement is unknown or not active, or
+    /// the member already acknowledged it.
+    pub fn acknowledge(&mut self, id: &str, member: MemberId, now_millis: u64) -> bool {
+        if !self
+            .announcements
+            .get(id)
+            .is_some_and(|a| a.is_active(now_millis))
+        {
+            return false;
+        }
+        let acks = self.acks.entry(id.to_string()).or_default();
+        if acks.contains_key(&member) {
+            return false;
+        }
+        acks.insert(member, now_millis);
+        true
+    }
+
+    /// Whether a member acknowledged an announcement.
+    pub fn is_acknowledged(&self, id: &str, member: &MemberId) -> bool {
+        self.acks
+            .get(id)
+            .is_some_and(|acks| acks.contains_key(member))
+    }
+
+    /// Active announcements by authorized authors, oldest first.
+    pub fn active(
+        &self,
+        now_millis: u64,
+        authorized: impl Fn(&MemberId) -> bool,
+    ) -> Vec<&Announcement> {
+        let mut active: Vec<&Announcement> = self
+            .announcements
+            .values()
+            .filter(|a| a.is_active(now_millis) && authorized(&a.author))
+            .collect();
+        active.sort_by(|a, b| (a.publish_at_millis, &a.id).cmp(&(b.publish_at_millis, &b.id)));
+        active
+    }
+
+    /// Active announcements `member` still has to acknowledge.
+    ///
+    /// A member is never asked to acknowledge their own announcement.
+    pub fn pending_for(
+        &self,
+        member: &MemberId,
+        now_millis: u64,
+        authorized: impl Fn(&MemberId) -> bool,
+    ) -> Vec<&Announcement> {
+        self.active(now_millis, authorized)
+            .into_iter()
+            .filter(|a| &a.author != member && !self.is_acknowledged(&a.id, member))
+            .collect()
+    }
+
+    /// Reminders for everything `member` has not acknowledged.
+    pub fn reminders_for(
+        &self,
+        member: &MemberId,
+        now_millis: u64,
+        authorized: impl Fn(&MemberId) -> bool,
+    ) -> Vec<AnnouncementReminder> {
+        self.pending_for(member, now_millis, authorized)
+            .into_iter()
+            .map(|a| AnnouncementReminder {
+                announcement: a.clone(),
+                outstanding_millis: now_millis.saturating_sub(a.publish_at_millis),
+            })
+            .collect()
+    }
+
+    /// Acknowledgment summary of one announcement against `members`.
+    ///
+    /// The author is left out of `pending`.
+    pub fn summary(&self, id: &str, members: &BTreeSet<MemberId>) -> Option<AnnouncementSummary> {
+        let announcement = self.announcements.get(id)?;
+        let acknowledged = self.acks.get(id).cloned().unwrap_or_default();
+        let pending = members
+            .iter()
+            .filter(|m| **m != announcement.author && !acknowledged.contains_key(*m))
+            .copied()
+            .collect();
+        Some(AnnouncementSummary {
+            announcement: announcement.clone(),
+            acknowledged,
+            pending,
+        })
+    }
+}
+
+impl DocumentSchema for AnnouncementDocument {
+    /// Union of announcements; acknowledgments keep the earliest time.
+    fn merge(&mut self, remote: Self) {
+        for announcement in remote.announcements.into_values() {
+            self.post(announcement);
+        }
+        for (id, acks) in remote.acks {
+            let local = self.acks.entry(id).or_default();
+            for (member, at) in acks {
+                let entry = local.entry(member).or_insert(at);
+                *entry = (*entry).min(at);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADMIN: MemberId = [1u8; 32];
+    const ALICE: MemberId = [2u8; 32];
+    const BOB: MemberId = [3u8; 32];
+
+    fn announcement(id: &str, author: MemberId, at: u64, expires: Option<u64>) -> Announcement {
+        Announcement {
+            id: id.to_string(),
+            author,
+            title: "Heads up".to_string(),
+            body: "Read me".to_string(),
+            posted_at_millis: 0,
+            publish_at_millis: at,
+            expires_at_millis: expires,
+        }
+    }
+
+    fn admin_only(member: &MemberId) -> bool {
+        *member == ADMIN
+    }
+
+    #[test]
+    fn test_schedule_and_expiry() {
+        let mut doc = AnnouncementDocument::new();
+        doc.post(announcement("later", ADMIN, 100, None));
+        doc.post(announcement("brief", ADMIN, 0, Some(50)));
+
+        let ids = |now| -> Vec<String> {
+            doc.active(now, admin_only)
+                .iter()
+                .map(|a| a.id.clone())
+                .collect()
+        };
+        assert_eq!(ids(10), vec!["brief"]);
+        assert_eq!(ids(100), vec!["later"]);
+    }
+
+    #[test]
+    fn test_acknowledgment_and_summary() {
+        let mut doc = AnnouncementDocument::new();
+        doc.post(announcement("a1", ADMIN, 0, None));
+        // Posted by a non-admin: never shown
+        doc.post(announcement("forged", ALICE, 0, None));
+
+        assert_eq!(doc.pending_for(&ALICE, 10, admin_only).len(), 1);
+        assert!(doc.pending_for(&ADMIN, 10, admin_only).is_empty());
+        assert!(!doc.acknowledge("missing", ALICE, 10));
+        assert!(doc.acknowledge("a1", ALICE, 10));
+        assert!(!doc.acknowledge("a1", ALICE, 20));
+        assert!(doc.pending_for(&ALICE, 20, admin_only).is_empty());
+
+        let members = BTreeSet::from([ADMIN, ALICE, BOB]);
+        let summary = doc.summary("a1", &members).unwrap();
+        let acked: Vec<MemberId> = summary.acknowledged.keys().copied().collect();
+        assert_eq!(acked, vec![ALICE]);
+        assert_eq!(summary.pending, BTreeSet::from([BOB]));
+        assert_eq!(summary.acknowledged_ratio(), 0.5);
+
+        let reminders = doc.reminders_for(&BOB, 30, admin_only);
+        assert_eq!(reminders.len(), 1);
+        assert_eq!(reminders[0].outstanding_millis, 30);
+    }
+
+    #[test]
+    fn test_merge_keeps_earliest_ack() {
+        let mut a = AnnouncementDocument::new();
+        a.post(announcement("a1", ADMIN, 0, None));
+        let mut b = a.clone();
+        a.acknowledge("a1", ALICE, 20);
+        b.acknowledge("a1", ALICE, 10);
+        b.acknowledge("a1", BOB, 15);
+
+        a.merge(b);
+        assert_eq!(a.acks["a1"][&ALICE], 10);
+        assert!(a.is_acknowledged("a1", &BOB));
+    }
+}
@@ -0,0 +1,231 @@
+//! Anonymous posting within a realm.
+//!
+//! Members who want to post anonymously publish a ring public key in the
+//! realm's [`AnonymityRingDocument`]. The keys together form the ring:
+//! an anonymous post is ring-signed over it, so every member can check
+//! that it came from someone in the ring without learning who.
+//!
+//! Each member's key is derived from its node identity and the realm id,
+//! so it is stable across restarts and unlinkable between realms. The
+//! ring is applied to the local node periodically, alongside guest
+//! access, and each node accepts anonymous posts only over keys it knows.
+//!
+//! Every published key is a [`RingEntry`] signed with the member's
+//! ML-DSA-65 identity over the realm id. Merges keep any validly signed
+//! entry, but only entries signed by the key the realm's membership log
+//! pins for a current member make it into the ring, so nobody can replace
+//! another member's key or add keys for outsiders.
+//!
+//! Posts are delivered to [`Realm::messages`](crate::Realm::messages)
+//! like any other, with the realm's shared anonymous sender in place of
+//! the author.
+//!
+//! The ring signature hides the author in the message itself, not on the
+//! network. A post reaches the realm through one member picked at random,
+//! which relays it and can see where it came from, and timing or sync with
+//! the author can give it away to others; see `indras_node::anonymous`.
+
+use crate::document::DocumentSchema;
+use crate::member::MemberId;
+use crate::membership_log::MembershipState;
+use indras_core::InterfaceId;
+use indras_crypto::RingPublicKey;
+use indras_crypto::pq_identity::{PQIdentity, PQPublicIdentity, PQSignature};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Document name for a realm's anonymity ring.
+pub const ANONYMITY_RING_DOC: &str = "_anonymity_ring";
+
+/// Domain separation tag for ring entry signatures.
+const RING_ENTRY_DOMAIN: &[u8] = b"indras:anonymity-ring:v1";
+
+/// A member's ring key, signed by the member.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RingEntry {
+    /// Realm the key was published for.
+    pub realm: InterfaceId,
+    /// The ring key.
+    pub key: RingPublicKey,
+    /// Member's ML-DSA-65 verifying key.
+    pub verifying_key: Vec<u8>,
+    /// Signature over the realm, member and ring key.
+    pub signature: Vec<u8>,
+}
+
+impl RingEntry {
+    /// Sign `key` as `member`'s ring key in `realm`.
+    pub fn sign(
+        realm: InterfaceId,
+        member: MemberId,
+        key: RingPublicKey,
+        identity: &PQIdentity,
+    ) -> Self {
+        let signature = identity.sign(&signing_bytes(&realm, &member, &key));
+        Self {
+            realm,
+            key,
+            verifying_key: identity.verifying_key_bytes(),
+            signature: signature.to_bytes().to_vec(),
+        }
+    }
+
+    /// Whether the signature is valid for `member`.
+    pub fn verify(&self, member: &MemberId) -> bool {
+        let (Ok(verifying_key), Ok(signature)) = (
+            PQPublicIdentity::from_bytes(&self.verifying_key),
+            PQSignature::from_bytes(self.signature.clone()),
+        ) else {
+            return false;
+        };
+        verifying_key.verify(&signing_bytes(&self.realm, member, &self.key), &signature)
+    }
+}
+
+/// Domain-separated bytes a ring entry signature covers.
+fn signing_bytes(realm: &InterfaceId, member: &MemberId, key: &RingPublicKey) -> Vec<u8> {
+    let mut bytes = RING_ENTRY_DOMAIN.to_vec();
+    bytes.extend_from_slice(realm.as_bytes());
+    bytes.extend_from_slice(member);
+    bytes.extend_from_slice(&key.0);
+    bytes
+}
+
+/// CRDT document listing the ring keys of members who opted in.
+///
+/// # Example
+///
+/// ```ignore
+/// realm.join_anonymity_ring().await?;
+/// realm.send_anonymously("The retro ran long again.").await?;
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnonymityRingDocument {
+    /// Validly signed entries per member. A member normally has one; any
+    /// others were signed by keys the membership log does not pin.
+    pub entries: BTreeMap<MemberId, Vec<RingEntry>>,
+}
+
+impl AnonymityRingDocument {
+    /// Create an empty ring.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Publish a member's signed ring key. Returns `false` if it was
+    /// already there or its signature is invalid.
+    pub fn join(&mut self, member: MemberId, entry: RingEntry) -> bool {
+        if !entry.verify(&member) {
+            return false;
+        }
+        let entries = self.entries.entry(member).or_default();
+        if entries.iter().any(|e| {
+            e.realm == entry.realm && e.key == entry.key && e.verifying_key == entry.verifying_key
+        }) {
+            return false;
+        }
+        entries.push(entry);
+        true
+    }
+
+    /// Ring keys of current members of `realm`, each signed by the key the
+    /// membership log pins for that member.
+    pub fn verified(
+        &self,
+        realm: &InterfaceId,
+        membership: &MembershipState,
+    ) -> BTreeMap<MemberId, RingPublicKey> {
+        self.entries
+            .iter()
+            .filter(|(member, _)| membership.is_member(member))
+            .filter_map(|(member, entries)| {
+                let pinned = membership.keys.get(member)?;
+                let entry = entries.iter().find(|e| {
+                    e.realm == *realm && &e.verifying_key == pinned && e.verify(member)
+                })?;
+                Some((*member, entry.key))
+            })
+            .collect()
+    }
+
+    /// Whether a member has a verified ring key in `realm`.
+    pub fn contains(
+        &self,
+        member: &MemberId,
+        realm: &InterfaceId,
+        membership: &MembershipState,
+    ) -> bool {
+        self.verified(realm, membership).contains_key(member)
+    }
+
+    /// The verified ring keys of `realm`.
+    pub fn ring(&self, realm: &InterfaceId, membership: &MembershipState) -> Vec<RingPublicKey> {
+        self.verified(realm, membership).into_values().collect()
+    }
+}
+
+impl DocumentSchema for AnonymityRingDocument {
+    /// Union of validly signed entries. Which entry counts for a member is
+    /// decided against the membership log when the ring is read.
+    fn merge(&mut self, remote: Self) {
+        for (member, entries) in remote.entries {
+            for entry in entries {
+                self.join(member, entry);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::membership_log::{MembershipAction, MembershipLogDocument, SignedMembershipEntry};
+
+    #[test]
+    fn test_join_and_merge() {
+        let realm = InterfaceId([7; 32]);
+        let (alice, bob, mallory) = ([1u8; 32], [2u8; 32], [3u8; 32]);
+        let (alice_pq, bob_pq, mallory_pq) = (
+            PQIdentity::generate(),
+            PQIdentity::generate(),
+            PQIdentity::generate(),
+        );
+        let (a, b) = (RingPublicKey([1; 32]), RingPublicKey([2; 32]));
+
+        let mut log = MembershipLogDocument::new();
+        let create = log.next_entry(alice, MembershipAction::Create, 0);
+        log.append(SignedMembershipEntry::sign(create, &alice_pq));
+        let add = MembershipAction::Add {
+            member: bob,
+            admin: false,
+            key: bob_pq.verifying_key_bytes(),
+        };
+        let add = log.next_entry(alice, add, 1);
+        log.append(SignedMembershipEntry::sign(add, &alice_pq));
+        let membership = log.state();
+
+        let mut local = AnonymityRingDocument::new();
+        assert!(local.join(alice, RingEntry::sign(realm, alice, a, &alice_pq)));
+        assert!(!local.join(alice, RingEntry::sign(realm, alice, a, &alice_pq)));
+
+        let mut remote = AnonymityRingDocument::new();
+        remote.join(bob, RingEntry::sign(realm, bob, b, &bob_pq));
+        // Keys signed by mallory for alice and for mallory; neither counts
+        let forged = RingPublicKey([9; 32]);
+        remote.join(alice, RingEntry::sign(realm, alice, forged, &mallory_pq));
+        remote.join(
+            mallory,
+            RingEntry::sign(realm, mallory, forged, &mallory_pq),
+        );
+        // Signed for bob but tampered with
+        let mut tampered = RingEntry::sign(realm, bob, forged, &bob_pq);
+        tampered.key = RingPublicKey([8; 32]);
+        assert!(!remote.join(bob, tampered));
+        local.merge(remote);
+
+        assert!(local.contains(&bob, &realm, &membership));
+        assert!(!local.contains(&mallory, &realm, &membership));
+        assert_eq!(local.ring(&realm, &membership), vec![a, b]);
+        assert!(local.ring(&InterfaceId([8; 32]), &membership).is_empty());
+    }
+}
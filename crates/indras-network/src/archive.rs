@@ -0,0 +1,136 @@
+//! Realm archiving: freezing a finished realm as a read-only record.
+//!
+//! An admin archives a realm by recording an [`ArchiveChange`] in its
+//! [`ArchiveDocument`]. While archived, the local node rejects every write
+//! to the realm (messages and documents alike, with
+//! [`IndraError::RealmArchived`](crate::IndraError::RealmArchived)), but
+//! incoming events still sync so members who were offline can catch up.
+//! An admin can unarchive the realm the same way.
+//!
+//! Changes are kept as an append-only list so that one written by someone
+//! who is not an admin can be skipped without losing the change before it.
+//! Admins are those of the realm's signed membership log; the check runs
+//! whenever the network applies the document to the node.
+
+use crate::document::DocumentSchema;
+use crate::member::MemberId;
+use serde::{Deserialize, Serialize};
+
+/// Document name for the archive state within a realm.
+pub const ARCHIVE_DOC: &str = "_archive";
+
+/// One archive or unarchive of a realm.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveChange {
+    /// Whether the realm became archived (`false` for unarchive).
+    pub archived: bool,
+    /// Member who made the change.
+    pub by: MemberId,
+    /// Why, as the member wrote it.
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// When the change was made (milliseconds since epoch).
+    pub at_millis: u64,
+}
+
+/// CRDT document of a realm's archive history.
+///
+/// # Example
+///
+/// ```ignore
+/// realm.archive("Project shipped").await?;
+/// assert!(realm.is_archived());
+/// assert!(realm.send("too late").await.is_err());
+///
+/// realm.unarchive().await?;
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArchiveDocument {
+    /// Every change, in the order this node learned of them.
+    pub changes: Vec<ArchiveChange>,
+}
+
+impl ArchiveDocument {
+    /// Create an empty archive history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a change. Returns `false` if it was already recorded.
+    pub fn record(&mut self, change: ArchiveChange) -> bool {
+        if self.changes.contains(&change) {
+            return false;
+        }
+        self.changes.push(change);
+        true
+    }
+
+    /// The change in effect: the latest one made by an authorized member.
+    pub fn current(&self, authorized: impl Fn(&MemberId) -> bool) -> Option<&ArchiveChange> {
+        self.changes
+            .iter()
+            .filter(|c| authorized(&c.by))
+            .max_by_key(|c| (c.at_millis, c.by))
+    }
+
+    /// Whether the realm is archived, counting only authorized changes.
+    pub fn is_archived(&self, authorized: impl Fn(&MemberId) -> bool) -> bool {
+        self.current(authorized).is_some_and(|c| c.archived)
+    }
+}
+
+impl DocumentSchema for ArchiveDocument {
+    /// Union of changes.
+    fn merge(&mut self, remote: Self) {
+        for change in remote.changes {
+            self.record(change);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADMIN: MemberId = [1u8; 32];
+    const OTHER: MemberId = [2u8; 32];
+
+    fn change(archived: bool, by: MemberId, at_millis: u64) -> ArchiveChange {
+        ArchiveChange {
+            archived,
+            by,
+            reason: None,
+            at_millis,
+        }
+    }
+
+    #[test]
+    fn test_latest_authorized_change_wins() {
+        let admin_only = |m: &MemberId| *m == ADMIN;
+        let mut doc = ArchiveDocument::new();
+        assert!(!doc.is_archived(admin_only));
+
+        assert!(doc.record(change(true, ADMIN, 10)));
+        assert!(!doc.record(change(true, ADMIN, 10)));
+        assert!(doc.is_archived(admin_only));
+
+        // A non-admin unarchiving later is ignored
+        doc.record(change(false, OTHER, 20));
+        assert!(doc.is_archived(admin_only));
+
+        doc.record(change(false, ADMIN, 30));
+        assert!(!doc.is_archived(admin_only));
+    }
+
+    #[test]
+    fn test_merge_is_union() {
+        let mut a = ArchiveDocument::new();
+        a.record(change(true, ADMIN, 10));
+        let mut b = a.clone();
+        b.record(change(false, ADMIN, 20));
+
+        a.merge(b);
+        assert_eq!(a.changes.len(), 2);
+        assert!(!a.is_archived(|_| true));
+    }
+}
@@ -16,6 +16,7 @@ use crate::access::{
 };
 use crate::artifact::ArtifactId;
 use crate::encryption::EncryptedArtifactKey;
+use crate::geo::{GeoPrecision, Geohash};
 use crate::member::MemberId;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -89,6 +90,14 @@ impl HomeArtifactEntry {
     pub fn short_hash(&self) -> String {
         self.hash_hex()[..8].to_string()
     }
+
+    /// Coarse geo-tag for sharing this artifact's location.
+    ///
+    /// The exact location stays in the owner's index; only the geohash
+    /// cell at `precision` should leave it.
+    pub fn geotag(&self, precision: GeoPrecision) -> Option<Geohash> {
+        self.location.as_ref().map(|l| l.geohash(precision))
+    }
 }
 
 /// The home-realm artifact index document.
@@ -300,6 +309,19 @@ impl ArtifactIndex {
     pub fn active_artifacts(&self) -> impl Iterator<Item = &HomeArtifactEntry> {
         self.artifacts.values().filter(|e| e.status.is_active())
     }
+
+    /// Active artifacts located within `radius_km` of `center`, nearest first.
+    pub fn near(&self, center: &GeoLocation, radius_km: f64) -> Vec<(&HomeArtifactEntry, f64)> {
+        let mut found: Vec<_> = self
+            .active_artifacts()
+            .filter_map(|e| {
+                let distance = e.location.as_ref()?.distance_km(center);
+                (distance <= radius_km).then_some((e, distance))
+            })
+            .collect();
+        found.sort_by(|a, b| a.1.total_cmp(&b.1));
+        found
+    }
 }
 
 #[cfg(test)]
@@ -686,4 +708,28 @@ mod tests {
         // Same point should be 0
         assert_eq!(london.distance_km(&london), 0.0);
     }
+
+    #[test]
+    fn test_near_filters_and_sorts_by_distance() {
+        let paris = GeoLocation { lat: 48.8566, lng: 2.3522 };
+        let versailles = GeoLocation { lat: 48.8049, lng: 2.1204 };
+        let mut index = ArtifactIndex::default();
+
+        let mut far = test_entry();
+        far.location = Some(GeoLocation { lat: 51.5074, lng: -0.1278 });
+        index.store(far);
+
+        let mut near = test_entry();
+        near.id = other_id();
+        near.location = Some(versailles);
+        index.store(near);
+
+        let found = index.near(&paris, 50.0);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0.id, other_id());
+        assert_eq!(
+            found[0].0.geotag(GeoPrecision::Region).unwrap().as_str(),
+            "u09"
+        );
+    }
 }
@@ -0,0 +1,37 @@
+//! Launch helpers for apps that can run as a background agent.
+//!
+//! With `--background` (or `INDRAS_BACKGROUND=1`) an app hides its window
+//! on close instead of stopping the node, and serves an
+//! [`AgentHost`](crate::AgentHost) so a second launch can bring the window
+//! back. There is no tray icon: relaunching the app is how a hidden window
+//! is shown again.
+
+use std::path::Path;
+
+/// Whether the app was asked to run as a background agent.
+pub fn background_mode() -> bool {
+    std::env::args().any(|a| a == "--background")
+        || std::env::var("INDRAS_BACKGROUND").is_ok_and(|v| v == "1")
+}
+
+/// Ask a background instance using `data_dir` to show its window.
+///
+/// Blocks on a short-lived runtime, so call it before the app starts its
+/// own. Returns `true` if an instance did, in which case this process
+/// should exit.
+#[cfg(unix)]
+pub fn show_running_instance(data_dir: &Path) -> bool {
+    let Ok(rt) = tokio::runtime::Builder::new_current_thread().enable_all().build() else {
+        return false;
+    };
+    rt.block_on(crate::AgentClient::show_running(data_dir))
+        .unwrap_or(false)
+}
+
+/// Ask a background instance using `data_dir` to show its window.
+///
+/// The agent socket is Unix-only, so there is never one to ask.
+#[cfg(not(unix))]
+pub fn show_running_instance(_data_dir: &Path) -> bool {
+    false
+}
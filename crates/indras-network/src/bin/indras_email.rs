@@ -0,0 +1,132 @@
+//! Run a node's email gateway.
+//!
+//! Opens the node in `--data-dir`, emails notifications and digests to
+//! the subscribers in `--config` through the local `sendmail`, and posts
+//! the replies the MTA delivers into `--maildir` back into their realms.
+//! The config file is an `EmailGatewayConfig` in JSON and is re-read on
+//! every poll, so subscribers and their delegation tokens can be added
+//! without a restart.
+//!
+//! Usage:
+//!
+//! ```bash
+//! indras-email --data-dir ~/.indras --config email.json --maildir /var/mail/indras
+//!
+//! # Check for replies every 10 seconds, with a non-default sendmail
+//! indras-email --data-dir ~/.indras --config email.json --maildir /var/mail/indras \
+//!     --poll 10 --sendmail /usr/bin/msmtp
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+
+use indras_network::{EmailGateway, EmailGatewayConfig, IndrasNetwork, SendmailTransport};
+
+const USAGE: &str = "usage: indras-email --data-dir DIR --config FILE --maildir DIR \
+                     [--poll SECONDS] [--sendmail PATH]";
+
+/// Seconds between Maildir checks unless `--poll` says otherwise.
+const DEFAULT_POLL_SECS: u64 = 30;
+
+struct Args {
+    data_dir: PathBuf,
+    config: PathBuf,
+    maildir: PathBuf,
+    poll: Duration,
+    sendmail: Option<PathBuf>,
+}
+
+fn parse_args(args: &[String]) -> Result<Args, String> {
+    let mut data_dir = None;
+    let mut config = None;
+    let mut maildir = None;
+    let mut poll = Duration::from_secs(DEFAULT_POLL_SECS);
+    let mut sendmail = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().cloned().ok_or_else(|| USAGE.to_string());
+        match arg.as_str() {
+            "--data-dir" => data_dir = Some(PathBuf::from(value()?)),
+            "--config" => config = Some(PathBuf::from(value()?)),
+            "--maildir" => maildir = Some(PathBuf::from(value()?)),
+            "--poll" => {
+                let secs: u64 = value()?
+                    .parse()
+                    .map_err(|_| "--poll expects a number of seconds".to_string())?;
+                poll = Duration::from_secs(secs.max(1));
+            }
+            "--sendmail" => sendmail = Some(PathBuf::from(value()?)),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    Ok(Args {
+        data_dir: data_dir.ok_or(USAGE)?,
+        config: config.ok_or(USAGE)?,
+        maildir: maildir.ok_or(USAGE)?,
+        poll,
+        sendmail,
+    })
+}
+
+fn load_config(path: &Path) -> Result<EmailGatewayConfig, String> {
+    let json = std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    serde_json::from_str(&json).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+async fn run(args: Args) -> Result<(), String> {
+    let config = load_config(&args.config)?;
+    let network = IndrasNetwork::new(&args.data_dir)
+        .await
+        .map_err(|e| e.to_string())?;
+    network.start().await.map_err(|e| e.to_string())?;
+
+    let transport = args
+        .sendmail
+        .map(SendmailTransport::new)
+        .unwrap_or_default();
+    let gateway = EmailGateway::new(Arc::clone(&network), config, transport);
+    let relay = Arc::clone(&gateway).spawn();
+    println!(
+        "email gateway running as {}",
+        hex::encode(network.identity().id())
+    );
+
+    loop {
+        match load_config(&args.config) {
+            Ok(config) => gateway.set_config(config),
+            Err(e) => eprintln!("keeping previous config: {e}"),
+        }
+        match gateway.receive_maildir(&args.maildir).await {
+            Ok(0) => {}
+            Ok(posted) => println!("{posted} replies posted"),
+            Err(e) => eprintln!("{}: {e}", args.maildir.display()),
+        }
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            _ = tokio::time::sleep(args.poll) => {}
+        }
+    }
+
+    relay.abort();
+    network.stop().await.map_err(|e| e.to_string())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match parse_args(&args) {
+        Ok(args) => run(args).await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,447 @@
+//! Realm calendars and iCalendar (ICS) feeds.
+//!
+//! Members add [`CalendarEntry`]s (meetups, work days, deadlines agreed in
+//! chat) to the realm's [`CalendarDocument`]. A [`CalendarExporter`] turns
+//! a realm snapshot into an [`IcsFeed`] that calendar apps can subscribe
+//! to: the calendar entries, the admins' announcements (from their publish
+//! time until they expire), and anything an app layer adds with
+//! [`CalendarExporter::with_source`], such as quest deadlines.
+//!
+//! Rendering is deterministic for a given realm state (`DTSTAMP` comes
+//! from the entry, not the clock), so a feed only changes when the realm
+//! does and can be regenerated on every change without churning
+//! subscribers.
+//!
+//! # Example
+//!
+//! ```ignore
+//! realm.add_calendar_entry("Garden work day", "Bring gloves", None, start, Some(end), false).await?;
+//!
+//! let ics = CalendarExporter::new().export(&realm).await?;
+//! std::fs::write("garden.ics", ics)?;
+//! ```
+
+use crate::announcement::{AnnouncementDocument, ANNOUNCEMENT_DOC};
+use crate::document::DocumentSchema;
+use crate::error::Result;
+use crate::member::MemberId;
+use crate::membership_log::{MembershipLogDocument, MEMBERSHIP_LOG_DOC};
+use crate::realm::Realm;
+use crate::snapshot::RealmSnapshot;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Document name for a realm's calendar.
+pub const CALENDAR_DOC: &str = "calendar";
+
+/// Longest calendar entry title accepted, in bytes.
+pub const MAX_CALENDAR_TITLE_LEN: usize = 256;
+
+/// Longest line in an ICS file, in bytes, before it is folded.
+const ICS_LINE_LIMIT: usize = 75;
+
+/// Unique identifier for a calendar entry.
+pub type CalendarEntryId = String;
+
+/// An event on a realm's calendar.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CalendarEntry {
+    /// Unique ID.
+    pub id: CalendarEntryId,
+    /// Member who added it.
+    pub author: MemberId,
+    /// Short title.
+    pub title: String,
+    /// Longer description.
+    #[serde(default)]
+    pub description: String,
+    /// Where it takes place, if anywhere in particular.
+    #[serde(default)]
+    pub location: Option<String>,
+    /// Start (milliseconds since epoch; midnight UTC of the day if all-day).
+    pub start_millis: u64,
+    /// End (milliseconds since epoch), if known.
+    #[serde(default)]
+    pub end_millis: Option<u64>,
+    /// Whether the entry spans whole days rather than a time range.
+    #[serde(default)]
+    pub all_day: bool,
+    /// Last change (milliseconds since epoch); the newest version wins.
+    pub updated_at_millis: u64,
+    /// Whether the entry was removed (tombstone).
+    #[serde(default)]
+    pub removed: bool,
+}
+
+/// A realm's calendar.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CalendarDocument {
+    /// Entries keyed by ID, including removed ones.
+    pub entries: BTreeMap<CalendarEntryId, CalendarEntry>,
+}
+
+impl CalendarDocument {
+    /// Create an empty calendar.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or replace an entry, keeping whichever version is newer.
+    pub fn upsert(&mut self, entry: CalendarEntry) {
+        match self.entries.get(&entry.id) {
+            Some(existing) if !newer(&entry, existing) => {}
+            _ => {
+                self.entries.insert(entry.id.clone(), entry);
+            }
+        }
+    }
+
+    /// Mark an entry removed. Returns `false` if there is no such entry.
+    pub fn remove(&mut self, id: &str, now_millis: u64) -> bool {
+        match self.entries.get_mut(id) {
+            Some(entry) if !entry.removed => {
+                entry.removed = true;
+                entry.updated_at_millis = entry.updated_at_millis.max(now_millis);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// An entry that has not been removed.
+    pub fn get(&self, id: &str) -> Option<&CalendarEntry> {
+        self.entries.get(id).filter(|e| !e.removed)
+    }
+
+    /// Entries that have not been removed, earliest first.
+    pub fn upcoming(&self) -> Vec<&CalendarEntry> {
+        let mut entries: Vec<&CalendarEntry> =
+            self.entries.values().filter(|e| !e.removed).collect();
+        entries.sort_by(|a, b| (a.start_millis, &a.id).cmp(&(b.start_millis, &b.id)));
+        entries
+    }
+}
+
+/// Whether `a` supersedes `b`: later change wins, removal breaks ties.
+fn newer(a: &CalendarEntry, b: &CalendarEntry) -> bool {
+    (a.updated_at_millis, a.removed) > (b.updated_at_millis, b.removed)
+}
+
+impl DocumentSchema for CalendarDocument {
+    /// Union of entries; for each entry the newest version wins.
+    fn merge(&mut self, remote: Self) {
+        for entry in remote.entries.into_values() {
+            self.upsert(entry);
+        }
+    }
+}
+
+/// One event in an ICS feed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IcsEvent {
+    /// Stable unique ID, so calendar apps update rather than duplicate it.
+    pub uid: String,
+    /// Title.
+    pub summary: String,
+    /// Longer description.
+    pub description: Option<String>,
+    /// Location.
+    pub location: Option<String>,
+    /// Start (milliseconds since epoch).
+    pub start_millis: u64,
+    /// End (milliseconds since epoch), if any.
+    pub end_millis: Option<u64>,
+    /// Whether the event spans whole days.
+    pub all_day: bool,
+    /// When the event last changed (milliseconds since epoch).
+    pub stamp_millis: u64,
+}
+
+/// A calendar feed for one realm.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IcsFeed {
+    /// Calendar name shown by calendar apps.
+    pub name: String,
+    /// Events, in the order they are written.
+    pub events: Vec<IcsEvent>,
+}
+
+impl IcsFeed {
+    /// Render the feed as an iCalendar (RFC 5545) document.
+    pub fn to_ics(&self) -> String {
+        let mut out = String::new();
+        line(&mut out, "BEGIN:VCALENDAR");
+        line(&mut out, "VERSION:2.0");
+        line(&mut out, "PRODID:-//Indra's Network//Realm Calendar//EN");
+        line(&mut out, "CALSCALE:GREGORIAN");
+        line(&mut out, "METHOD:PUBLISH");
+        line(&mut out, &format!("X-WR-CALNAME:{}", escape(&self.name)));
+        for event in &self.events {
+            line(&mut out, "BEGIN:VEVENT");
+            line(&mut out, &format!("UID:{}", escape(&event.uid)));
+            line(
+                &mut out,
+                &format!("DTSTAMP:{}", date_time(event.stamp_millis)),
+            );
+            if event.all_day {
+                line(
+                    &mut out,
+                    &format!("DTSTART;VALUE=DATE:{}", date(event.start_millis)),
+                );
+                if let Some(end) = event.end_millis {
+                    line(&mut out, &format!("DTEND;VALUE=DATE:{}", date(end)));
+                }
+            } else {
+                line(
+                    &mut out,
+                    &format!("DTSTART:{}", date_time(event.start_millis)),
+                );
+                if let Some(end) = event.end_millis {
+                    line(&mut out, &format!("DTEND:{}", date_time(end)));
+                }
+            }
+            line(&mut out, &format!("SUMMARY:{}", escape(&event.summary)));
+            if let Some(description) = event.description.as_deref().filter(|d| !d.is_empty()) {
+                line(&mut out, &format!("DESCRIPTION:{}", escape(description)));
+            }
+            if let Some(location) = &event.location {
+                line(&mut out, &format!("LOCATION:{}", escape(location)));
+            }
+            line(&mut out, "END:VEVENT");
+        }
+        line(&mut out, "END:VCALENDAR");
+        out
+    }
+}
+
+type EventSource = Box<dyn Fn(&RealmSnapshot) -> Vec<IcsEvent> + Send + Sync>;
+
+/// Builds ICS feeds from realm snapshots.
+///
+/// Calendar entries and announcements are always included; app layers add
+/// their own events with [`with_source`](Self::with_source).
+#[derive(Default)]
+pub struct CalendarExporter {
+    sources: Vec<EventSource>,
+}
+
+impl CalendarExporter {
+    /// Create an exporter for calendar entries and announcements.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add events derived from a snapshot, e.g. deadlines from an app document.
+    pub fn with_source(
+        mut self,
+        source: impl Fn(&RealmSnapshot) -> Vec<IcsEvent> + Send + Sync + 'static,
+    ) -> Self {
+        self.sources.push(Box::new(source));
+        self
+    }
+
+    /// Snapshot a realm and render its feed as ICS.
+    pub async fn export(&self, realm: &Realm) -> Result<String> {
+        let snapshot = realm.snapshot().await?;
+        let name = realm.name().map(String::from);
+        Ok(self.feed(&snapshot, name).to_ics())
+    }
+
+    /// Collect a snapshot's events into a feed, ordered by start time.
+    ///
+    /// `name` defaults to `Realm <id prefix>`.
+    pub fn feed(&self, snapshot: &RealmSnapshot, name: Option<String>) -> IcsFeed {
+        let realm_hex = hex::encode(snapshot.realm_id().as_bytes());
+        let mut events = Vec::new();
+
+        let calendar = snapshot.document::<CalendarDocument>(CALENDAR_DOC);
+        events.extend(calendar.upcoming().into_iter().map(|entry| IcsEvent {
+            uid: format!("calendar-{}@{realm_hex}", entry.id),
+            summary: entry.title.clone(),
+            description: Some(entry.description.clone()),
+            location: entry.location.clone(),
+            start_millis: entry.start_millis,
+            end_millis: entry.end_millis,
+            all_day: entry.all_day,
+            stamp_millis: entry.updated_at_millis,
+        }));
+
+        let admins = snapshot
+            .document::<MembershipLogDocument>(MEMBERSHIP_LOG_DOC)
+            .state()
+            .admins;
+        let announcements = snapshot.document::<AnnouncementDocument>(ANNOUNCEMENT_DOC);
+        events.extend(
+            announcements
+                .announcements
+                .values()
+                .filter(|a| admins.contains(&a.author))
+                .map(|a| IcsEvent {
+                    uid: format!("announcement-{}@{realm_hex}", a.id),
+                    summary: format!("Announcement: {}", a.title),
+                    description: Some(a.body.clone()),
+                    location: None,
+                    start_millis: a.publish_at_millis,
+                    end_millis: a.expires_at_millis,
+                    all_day: false,
+                    stamp_millis: a.posted_at_millis,
+                }),
+        );
+
+        for source in &self.sources {
+            events.extend(source(snapshot));
+        }
+        events.sort_by(|a, b| (a.start_millis, &a.uid).cmp(&(b.start_millis, &b.uid)));
+
+        IcsFeed {
+            name: name.unwrap_or_else(|| format!("Realm {}", &realm_hex[..8])),
+            events,
+        }
+    }
+}
+
+/// Append a content line, folded at 75 bytes and terminated with CRLF.
+fn line(out: &mut String, content: &str) {
+    let mut width = 0;
+    for c in content.chars() {
+        if width + c.len_utf8() > ICS_LINE_LIMIT {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+/// Escape a TEXT value.
+fn escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn utc(millis: u64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(millis as i64).unwrap_or_default()
+}
+
+fn date_time(millis: u64) -> String {
+    utc(millis).format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn date(millis: u64) -> String {
+    utc(millis).format("%Y%m%d").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: MemberId = [1u8; 32];
+
+    fn entry(id: &str, start: u64, updated: u64) -> CalendarEntry {
+        CalendarEntry {
+            id: id.to_string(),
+            author: ALICE,
+            title: format!("Event {id}"),
+            description: String::new(),
+            location: None,
+            start_millis: start,
+            end_millis: None,
+            all_day: false,
+            updated_at_millis: updated,
+            removed: false,
+        }
+    }
+
+    #[test]
+    fn test_merge_newest_version_and_removal_win() {
+        let mut local = CalendarDocument::new();
+        local.upsert(entry("a", 100, 1));
+        local.upsert(entry("b", 50, 1));
+
+        let mut remote = local.clone();
+        let mut moved = entry("a", 200, 2);
+        moved.title = "Moved".to_string();
+        remote.upsert(moved);
+        assert!(remote.remove("b", 1));
+
+        local.merge(remote);
+        assert_eq!(local.get("a").unwrap().title, "Moved");
+        assert!(local.get("b").is_none());
+
+        // An older copy does not resurrect or revert anything
+        local.merge({
+            let mut stale = CalendarDocument::new();
+            stale.upsert(entry("a", 100, 1));
+            stale.upsert(entry("b", 50, 1));
+            stale
+        });
+        assert_eq!(local.get("a").unwrap().start_millis, 200);
+        assert!(local.get("b").is_none());
+    }
+
+    #[test]
+    fn test_ics_rendering() {
+        let feed = IcsFeed {
+            name: "Garden".to_string(),
+            events: vec![
+                IcsEvent {
+                    uid: "calendar-1@abc".to_string(),
+                    summary: "Work day; bring gloves, tools".to_string(),
+                    description: Some("Line one\nLine two".to_string()),
+                    location: Some("Plot 4".to_string()),
+                    // 2025-06-01T09:00:00Z
+                    start_millis: 1_748_768_400_000,
+                    end_millis: Some(1_748_779_200_000),
+                    all_day: false,
+                    stamp_millis: 1_748_768_400_000,
+                },
+                IcsEvent {
+                    uid: "quest-2@abc".to_string(),
+                    summary: "Quest due".to_string(),
+                    description: None,
+                    location: None,
+                    start_millis: 1_748_768_400_000,
+                    end_millis: None,
+                    all_day: true,
+                    stamp_millis: 0,
+                },
+            ],
+        };
+        let ics = feed.to_ics();
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.contains("DTSTART:20250601T090000Z\r\n"));
+        assert!(ics.contains("DTEND:20250601T120000Z\r\n"));
+        assert!(ics.contains("SUMMARY:Work day\\; bring gloves\\, tools\r\n"));
+        assert!(ics.contains("DESCRIPTION:Line one\\nLine two\r\n"));
+        assert!(ics.contains("DTSTART;VALUE=DATE:20250601\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+    }
+
+    #[test]
+    fn test_long_lines_are_folded() {
+        let mut out = String::new();
+        line(&mut out, &format!("SUMMARY:{}", "é".repeat(60)));
+        for physical in out.split("\r\n") {
+            assert!(physical.len() <= ICS_LINE_LIMIT);
+        }
+        assert_eq!(
+            out.replace("\r\n ", ""),
+            format!("SUMMARY:{}\r\n", "é".repeat(60))
+        );
+    }
+}
@@ -5,13 +5,28 @@
 //! deleted at any time, with edit history accessible via the versions field.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
 use crate::document::DocumentSchema;
+use crate::tombstone::TombstoneCompaction;
+use crate::util::now_millis;
+use indras_crypto::pq_identity::{PQIdentity, PQPublicIdentity, PQSignature};
+use iroh::{PublicKey, SecretKey, Signature};
+
+/// Domain separation tag for chat authorship signatures.
+const AUTHORSHIP_DOMAIN: &[u8] = b"indras/chat-authorship/v1";
+
+/// Domain separation tag for poll ballot signatures.
+const BALLOT_DOMAIN: &[u8] = b"indras/chat-ballot/v1";
 
 /// Unique message identifier (realm_id + tick + member_id or UUID).
 pub type ChatMessageId = String;
 
+/// Largest image embedded inline in a chat message, in bytes.
+///
+/// Larger images are stored as blobs and referenced by hash.
+pub const MAX_INLINE_IMAGE_SIZE: usize = 2 * 1024 * 1024;
+
 /// A single version of a chat message.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct ChatMessageVersion {
@@ -92,6 +107,20 @@ pub enum EditableMessageType {
         #[serde(default)]
         description: String,
     },
+    /// Poll that realm members vote on.
+    ///
+    /// Ballots are kept in [`EditableChatMessage::votes`].
+    Poll {
+        /// The question asked.
+        question: String,
+        /// Answer options; ballots refer to them by index.
+        options: Vec<String>,
+        /// Whether a member may choose more than one option.
+        multiple: bool,
+        /// When voting closes (milliseconds since epoch), if ever.
+        #[serde(default)]
+        closes_at: Option<u64>,
+    },
 }
 
 /// An item in a gallery (image, video, or file).
@@ -111,12 +140,114 @@ pub struct GalleryItem {
     pub dimensions: Option<(u32, u32)>,
 }
 
+/// A member's vote on a poll.
+///
+/// Each member has at most one ballot per poll; a newer ballot replaces
+/// the older one. A ballot with no choices withdraws the vote.
+///
+/// Ballots are signed like [`Authorship`], but with the voter's node key.
+/// A voter's member ID is that key, so a ballot only counts under the ID
+/// of the member who signed it.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PollBallot {
+    /// Indices of the chosen options.
+    pub choices: BTreeSet<usize>,
+    /// When the ballot was cast (milliseconds since epoch).
+    pub cast_at: u64,
+    /// Voter's signature over the poll, voter, choices and cast time.
+    #[serde(default)]
+    pub signature: Option<Signature>,
+}
+
+impl PollBallot {
+    /// Whether this ballot replaces `other` from the same voter.
+    ///
+    /// The later ballot wins; ties break on the choices so every peer
+    /// keeps the same one.
+    fn supersedes(&self, other: &Self) -> bool {
+        (self.cast_at, &self.choices) > (other.cast_at, &other.choices)
+    }
+}
+
+/// Votes for one poll option.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PollOptionTally {
+    /// The option text.
+    pub option: String,
+    /// Number of members who chose it.
+    pub votes: usize,
+    /// Hex member IDs of those members, sorted.
+    pub voters: Vec<String>,
+}
+
+/// Results of a poll at a point in time.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PollTally {
+    /// ID of the poll message.
+    pub message_id: ChatMessageId,
+    /// The question asked.
+    pub question: String,
+    /// Per-option results, in option order.
+    pub options: Vec<PollOptionTally>,
+    /// Number of members with a counted ballot.
+    pub voters: usize,
+    /// When voting closes (milliseconds since epoch), if ever.
+    pub closes_at: Option<u64>,
+    /// Whether voting had closed when the tally was taken.
+    pub closed: bool,
+}
+
+impl PollTally {
+    /// Export the results as CSV, one row per option.
+    ///
+    /// Columns are `option,votes,voters`, with voters separated by spaces.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("option,votes,voters\n");
+        for option in &self.options {
+            csv.push_str(&format!(
+                "{},{},{}\n",
+                csv_field(&option.option),
+                option.votes,
+                option.voters.join(" "),
+            ));
+        }
+        csv
+    }
+}
+
+/// Quote a CSV field if it contains a separator, quote, or newline.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// A voter's node key, from their hex member ID.
+fn voter_key(voter: &str) -> Option<PublicKey> {
+    let bytes: [u8; 32] = hex::decode(voter).ok()?.try_into().ok()?;
+    PublicKey::from_bytes(&bytes).ok()
+}
+
 impl Default for EditableMessageType {
     fn default() -> Self {
         Self::Text
     }
 }
 
+/// An author's PQ signature over a message as first sent.
+///
+/// Binds the message id, realm, author id, creation time, and original
+/// content, so it still verifies after later edits or deletion.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Authorship {
+    /// Author's ML-DSA-65 verifying key.
+    pub author_vk: Vec<u8>,
+    /// Signature over the message's canonical original form.
+    pub signature: Vec<u8>,
+}
+
 /// A CRDT-backed chat message with version history.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct EditableChatMessage {
@@ -148,6 +279,12 @@ pub struct EditableChatMessage {
     /// Reactions on this message: emoji string -> list of author IDs.
     #[serde(default)]
     pub reactions: HashMap<String, Vec<String>>,
+    /// Author's signature, for messages sent by signing clients.
+    #[serde(default)]
+    pub authorship: Option<Authorship>,
+    /// Poll ballots: hex member ID -> that member's latest ballot.
+    #[serde(default)]
+    pub votes: HashMap<String, PollBallot>,
 }
 
 impl EditableChatMessage {
@@ -172,6 +309,8 @@ impl EditableChatMessage {
             message_type,
             reply_to: None,
             reactions: HashMap::new(),
+            authorship: None,
+            votes: HashMap::new(),
         }
     }
 
@@ -181,6 +320,51 @@ impl EditableChatMessage {
         self
     }
 
+    /// Sign the message as its author. Call before the first edit.
+    pub fn signed_by(mut self, identity: &PQIdentity) -> Self {
+        let signature = identity.sign(&self.authorship_bytes());
+        self.authorship = Some(Authorship {
+            author_vk: identity.verifying_key_bytes(),
+            signature: signature.to_bytes().to_vec(),
+        });
+        self
+    }
+
+    /// Whether the message carries a valid author signature.
+    pub fn verify_authorship(&self) -> bool {
+        let Some(authorship) = &self.authorship else {
+            return false;
+        };
+        let (Ok(vk), Ok(sig)) = (
+            PQPublicIdentity::from_bytes(&authorship.author_vk),
+            PQSignature::from_bytes(authorship.signature.clone()),
+        ) else {
+            return false;
+        };
+        vk.verify(&self.authorship_bytes(), &sig)
+    }
+
+    /// Content as first sent, before any edit or deletion.
+    pub fn original_content(&self) -> &str {
+        self.versions
+            .first()
+            .map(|v| v.content.as_str())
+            .unwrap_or(&self.current_content)
+    }
+
+    /// Canonical bytes an authorship signature binds to.
+    fn authorship_bytes(&self) -> Vec<u8> {
+        let body = (
+            AUTHORSHIP_DOMAIN,
+            &self.id,
+            &self.realm_id,
+            &self.author_id,
+            self.created_at,
+            self.original_content(),
+        );
+        postcard::to_allocvec(&body).unwrap_or_default()
+    }
+
     /// Create a new text message.
     pub fn new_text(
         id: ChatMessageId,
@@ -306,6 +490,182 @@ impl EditableChatMessage {
         )
     }
 
+    /// Create a new poll message.
+    ///
+    /// The question doubles as the message content.
+    pub fn new_poll(
+        id: ChatMessageId,
+        realm_id: String,
+        author: String,
+        created_at: u64,
+        question: String,
+        options: Vec<String>,
+        multiple: bool,
+        closes_at: Option<u64>,
+    ) -> Self {
+        Self::new(
+            id,
+            realm_id,
+            author,
+            question.clone(),
+            created_at,
+            EditableMessageType::Poll {
+                question,
+                options,
+                multiple,
+                closes_at,
+            },
+        )
+    }
+
+    /// Check if this is a poll message.
+    pub fn is_poll(&self) -> bool {
+        matches!(self.message_type, EditableMessageType::Poll { .. })
+    }
+
+    /// Whether this is a poll still accepting votes at `now_millis`.
+    pub fn poll_open(&self, now_millis: u64) -> bool {
+        match &self.message_type {
+            EditableMessageType::Poll { closes_at, .. } => {
+                !self.is_deleted && closes_at.is_none_or(|at| now_millis < at)
+            }
+            _ => false,
+        }
+    }
+
+    /// Cast, change, or (with no choices) withdraw a member's vote, signed
+    /// with the voter's node key.
+    ///
+    /// Returns false if the poll is closed, a choice is out of range, more
+    /// than one option is chosen on a single-choice poll, or the voter
+    /// already has a newer ballot.
+    pub fn cast_vote(&mut self, voter: &SecretKey, choices: BTreeSet<usize>, tick: u64) -> bool {
+        let voter_id = hex::encode(voter.public().as_bytes());
+        let signature = voter.sign(&self.ballot_bytes(&voter_id, &choices, tick));
+        let ballot = PollBallot {
+            choices,
+            cast_at: tick,
+            signature: Some(signature),
+        };
+        self.merge_ballot(&voter_id, ballot, Some(tick))
+    }
+
+    /// Keep a ballot if it is valid and newer than the voter's current one.
+    ///
+    /// Remote ballots go through here too, so an invalid one cannot
+    /// displace a valid vote. With `now_millis`, the poll must also still
+    /// be open by this node's clock, whatever time the ballot claims.
+    fn merge_ballot(&mut self, voter: &str, ballot: PollBallot, now_millis: Option<u64>) -> bool {
+        if now_millis.is_some_and(|now| !self.poll_open(now))
+            || !self.ballot_valid(voter, &ballot)
+            || self.votes.get(voter).is_some_and(|b| !ballot.supersedes(b))
+        {
+            return false;
+        }
+        self.votes.insert(voter.to_string(), ballot);
+        true
+    }
+
+    /// Whether a ballot fits the poll, was cast before it closed, and is
+    /// signed by `voter`.
+    fn ballot_valid(&self, voter: &str, ballot: &PollBallot) -> bool {
+        let EditableMessageType::Poll {
+            options, multiple, ..
+        } = &self.message_type
+        else {
+            return false;
+        };
+        let (Some(signature), Some(voter_key)) = (&ballot.signature, voter_key(voter)) else {
+            return false;
+        };
+        self.poll_open(ballot.cast_at)
+            && ballot.choices.iter().all(|&c| c < options.len())
+            && (*multiple || ballot.choices.len() <= 1)
+            && voter_key
+                .verify(&self.ballot_bytes(voter, &ballot.choices, ballot.cast_at), signature)
+                .is_ok()
+    }
+
+    /// Canonical bytes a ballot signature binds to.
+    fn ballot_bytes(&self, voter: &str, choices: &BTreeSet<usize>, cast_at: u64) -> Vec<u8> {
+        let body = (BALLOT_DOMAIN, &self.id, &self.realm_id, voter, choices, cast_at);
+        postcard::to_allocvec(&body).unwrap_or_default()
+    }
+
+    /// Merge another copy's ballots into this message.
+    fn merge_votes(&mut self, votes: HashMap<String, PollBallot>, now_millis: Option<u64>) {
+        for (voter, ballot) in votes {
+            self.merge_ballot(&voter, ballot, now_millis);
+        }
+    }
+
+    /// Take in a copy of a message this node has not seen before.
+    ///
+    /// Its ballots are checked but not against this node's clock: they
+    /// were accepted on the peer it came from, and a member who joins
+    /// after a poll closed still sees its votes.
+    fn with_checked_votes(mut self) -> Self {
+        let votes = std::mem::take(&mut self.votes);
+        self.merge_votes(votes, None);
+        self
+    }
+
+    /// Replace this message with a newer copy, keeping the ballots
+    /// already accepted here and checking the copy's against `now_millis`.
+    fn replace_keeping_votes(&mut self, mut newer: Self, now_millis: u64) {
+        let remote_votes = std::mem::take(&mut newer.votes);
+        newer.votes = std::mem::take(&mut self.votes);
+        *self = newer;
+        self.merge_votes(remote_votes, Some(now_millis));
+    }
+
+    /// Tally the poll's votes as of `now_millis`.
+    ///
+    /// Returns `None` if this is not a poll.
+    pub fn poll_tally(&self, now_millis: u64) -> Option<PollTally> {
+        let EditableMessageType::Poll {
+            question,
+            options,
+            closes_at,
+            ..
+        } = &self.message_type
+        else {
+            return None;
+        };
+        let mut tally: Vec<PollOptionTally> = options
+            .iter()
+            .map(|option| PollOptionTally {
+                option: option.clone(),
+                votes: 0,
+                voters: Vec::new(),
+            })
+            .collect();
+        let mut voters = 0;
+        for (voter, ballot) in &self.votes {
+            if ballot.choices.is_empty() {
+                continue;
+            }
+            voters += 1;
+            for &choice in &ballot.choices {
+                if let Some(option) = tally.get_mut(choice) {
+                    option.votes += 1;
+                    option.voters.push(voter.clone());
+                }
+            }
+        }
+        for option in &mut tally {
+            option.voters.sort();
+        }
+        Some(PollTally {
+            message_id: self.id.clone(),
+            question: question.clone(),
+            options: tally,
+            voters,
+            closes_at: *closes_at,
+            closed: !self.poll_open(now_millis),
+        })
+    }
+
     /// Check if this is an inline image message.
     pub fn is_image(&self) -> bool {
         matches!(self.message_type, EditableMessageType::Image { .. })
@@ -365,6 +725,14 @@ impl EditableChatMessage {
         true
     }
 
+    /// Tick at which the message was deleted, if it has been.
+    pub fn deleted_at(&self) -> Option<u64> {
+        if !self.is_deleted {
+            return None;
+        }
+        Some(self.versions.last().map(|v| v.edited_at).unwrap_or(self.created_at))
+    }
+
     /// Check if this message has been edited.
     pub fn is_edited(&self) -> bool {
         !self.versions.is_empty()
@@ -454,6 +822,72 @@ pub enum ChatDelta {
         emoji: String,
         author: String,
     },
+    /// A member cast or changed their ballot on a poll.
+    CastVote {
+        msg_id: ChatMessageId,
+        voter: String,
+        ballot: PollBallot,
+    },
+}
+
+/// A position in a chat's `(created_at, id)` message order.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ChatCursor {
+    /// Creation time of the message at this position.
+    pub created_at: u64,
+    /// Id of the message at this position.
+    pub id: ChatMessageId,
+}
+
+impl ChatCursor {
+    /// The cursor at a message.
+    pub fn of(msg: &EditableChatMessage) -> Self {
+        Self {
+            created_at: msg.created_at,
+            id: msg.id.clone(),
+        }
+    }
+
+    /// Whether the cursor sorts after `msg`.
+    fn is_after(&self, msg: &EditableChatMessage) -> bool {
+        (msg.created_at, msg.id.as_str()) < (self.created_at, self.id.as_str())
+    }
+
+    /// Whether the cursor sorts before `msg`.
+    fn is_before(&self, msg: &EditableChatMessage) -> bool {
+        (msg.created_at, msg.id.as_str()) > (self.created_at, self.id.as_str())
+    }
+}
+
+/// A contiguous run of visible messages, oldest first.
+#[derive(Debug)]
+pub struct ChatPage<'a> {
+    /// The messages in the page.
+    pub messages: Vec<&'a EditableChatMessage>,
+    /// Number of visible messages older than the page.
+    pub earlier: usize,
+    /// Number of visible messages newer than the page.
+    pub later: usize,
+}
+
+impl<'a> ChatPage<'a> {
+    fn slice(visible: Vec<&'a EditableChatMessage>, start: usize, end: usize) -> Self {
+        Self {
+            earlier: start,
+            later: visible.len() - end,
+            messages: visible[start..end].to_vec(),
+        }
+    }
+
+    /// Cursor at the oldest message in the page.
+    pub fn first_cursor(&self) -> Option<ChatCursor> {
+        self.messages.first().map(|m| ChatCursor::of(m))
+    }
+
+    /// Cursor at the newest message in the page.
+    pub fn last_cursor(&self) -> Option<ChatCursor> {
+        self.messages.last().map(|m| ChatCursor::of(m))
+    }
 }
 
 /// The realm chat document containing all messages.
@@ -527,6 +961,37 @@ impl RealmChatDocument {
         visible
     }
 
+    /// Up to `limit` visible messages ending just before `before`.
+    ///
+    /// With no cursor, returns the newest messages.
+    pub fn page_before(&self, before: Option<&ChatCursor>, limit: usize) -> ChatPage<'_> {
+        let visible = self.visible_messages();
+        let end = match before {
+            Some(c) => visible.partition_point(|m| c.is_after(m)),
+            None => visible.len(),
+        };
+        ChatPage::slice(visible, end.saturating_sub(limit), end)
+    }
+
+    /// Up to `limit` visible messages starting just after `after`.
+    pub fn page_after(&self, after: &ChatCursor, limit: usize) -> ChatPage<'_> {
+        let visible = self.visible_messages();
+        let start = visible.partition_point(|m| !after.is_before(m));
+        let end = start.saturating_add(limit).min(visible.len());
+        ChatPage::slice(visible, start, end)
+    }
+
+    /// Up to `limit` visible messages centred on `timestamp_millis`.
+    ///
+    /// The page is shifted to stay full near either end of the history.
+    pub fn page_around(&self, timestamp_millis: u64, limit: usize) -> ChatPage<'_> {
+        let visible = self.visible_messages();
+        let pivot = visible.partition_point(|m| m.created_at < timestamp_millis);
+        let end = pivot.saturating_add(limit - limit / 2).min(visible.len());
+        let start = end.saturating_sub(limit);
+        ChatPage::slice(visible, start, end)
+    }
+
     /// Get message count (including deleted).
     pub fn total_count(&self) -> usize {
         self.messages.len()
@@ -572,17 +1037,19 @@ impl RealmChatDocument {
 
             let prefer_remote = (msg.is_deleted && !existing.is_deleted)
                 || msg.version_count() > existing.version_count();
+            let now = now_millis();
             if prefer_remote {
-                *existing = msg;
+                existing.replace_keeping_votes(msg, now);
                 existing.reactions = merged_reactions;
             } else {
                 existing.reactions = merged_reactions;
+                existing.merge_votes(msg.votes, Some(now));
             }
             return true;
         }
 
         // New message — O(1) insert
-        self.messages.insert(msg.id.clone(), msg);
+        self.messages.insert(msg.id.clone(), msg.with_checked_votes());
         true
     }
 
@@ -618,6 +1085,13 @@ impl RealmChatDocument {
                     false
                 }
             }
+            ChatDelta::CastVote { msg_id, voter, ballot } => {
+                if let Some(msg) = self.messages.get_mut(&msg_id) {
+                    msg.merge_ballot(&voter, ballot, Some(now_millis()))
+                } else {
+                    false
+                }
+            }
         }
     }
 
@@ -636,6 +1110,20 @@ impl RealmChatDocument {
         }
         false
     }
+
+    /// Cast a vote on a poll message, signed with the voter's node key.
+    pub fn cast_vote(
+        &mut self,
+        msg_id: &str,
+        voter: &SecretKey,
+        choices: BTreeSet<usize>,
+        tick: u64,
+    ) -> bool {
+        if let Some(msg) = self.get_message_mut(msg_id) {
+            return msg.cast_vote(voter, choices, tick);
+        }
+        false
+    }
 }
 
 impl DocumentSchema for RealmChatDocument {
@@ -694,6 +1182,16 @@ impl DocumentSchema for RealmChatDocument {
                             }
                         }
                     }
+                    // Check for new or changed ballots
+                    for (voter, ballot) in &new_msg.votes {
+                        if old_msg.votes.get(voter) != Some(ballot) {
+                            deltas.push(ChatDelta::CastVote {
+                                msg_id: id.clone(),
+                                voter: voter.clone(),
+                                ballot: ballot.clone(),
+                            });
+                        }
+                    }
                 }
             }
         }
@@ -723,7 +1221,9 @@ impl DocumentSchema for RealmChatDocument {
     /// Merge remote chat state with local state using set-union on message IDs.
     ///
     /// For messages present on both sides, keeps the version with more edits,
-    /// or the deleted version if either side deleted it. Reactions are unioned.
+    /// or the deleted version if either side deleted it. Reactions are unioned;
+    /// poll ballots keep each voter's latest signed ballot, and new ballots
+    /// for a known poll count only while it is open by this node's clock.
     fn merge(&mut self, remote: Self) {
         for (id, remote_msg) in remote.messages {
             match self.messages.get(&id) {
@@ -742,24 +1242,52 @@ impl DocumentSchema for RealmChatDocument {
                     // Choose which message version to keep
                     let prefer_remote = remote_msg.is_deleted && !local_msg.is_deleted
                         || remote_msg.version_count() > local_msg.version_count();
+                    let now = now_millis();
+                    let local = self.messages.get_mut(&id).unwrap();
                     if prefer_remote {
-                        let mut winner = remote_msg;
-                        winner.reactions = merged_reactions;
-                        self.messages.insert(id, winner);
+                        local.replace_keeping_votes(remote_msg, now);
+                        local.reactions = merged_reactions;
                     } else {
-                        // Keep local but update reactions
-                        self.messages.get_mut(&id).unwrap().reactions = merged_reactions;
+                        // Keep local but update reactions and ballots
+                        local.reactions = merged_reactions;
+                        local.merge_votes(remote_msg.votes, Some(now));
                     }
                 }
                 None => {
                     // Remote has a message we don't — add it
-                    self.messages.insert(id, remote_msg);
+                    self.messages.insert(id, remote_msg.with_checked_votes());
                 }
             }
         }
     }
 }
 
+impl TombstoneCompaction for RealmChatDocument {
+    fn tombstone_count(&self) -> usize {
+        self.messages.values().filter(|m| m.is_deleted).count()
+    }
+
+    fn compact_tombstones(&mut self, horizon_millis: u64) -> usize {
+        self.compact_tombstones_except(horizon_millis, &BTreeSet::new())
+    }
+}
+
+impl RealmChatDocument {
+    /// Drop tombstones deleted before `horizon_millis`, keeping any message
+    /// in `held` (e.g. under a legal hold).
+    pub fn compact_tombstones_except(
+        &mut self,
+        horizon_millis: u64,
+        held: &BTreeSet<ChatMessageId>,
+    ) -> usize {
+        let before = self.messages.len();
+        self.messages.retain(|id, m| {
+            held.contains(id) || m.deleted_at().is_none_or(|at| at >= horizon_millis)
+        });
+        before - self.messages.len()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1089,6 +1617,72 @@ mod tests {
         assert!(local.get_message("msg-1").unwrap().is_deleted);
     }
 
+    #[test]
+    fn test_compact_tombstones_respects_horizon() {
+        let mut doc = RealmChatDocument::new();
+        for (id, deleted_at) in [("old", Some(200)), ("recent", Some(900)), ("live", None)] {
+            let mut msg = EditableChatMessage::new_text(
+                id.into(), "realm".into(), "zephyr".into(), "Hello".into(), 100,
+            );
+            if let Some(tick) = deleted_at {
+                msg.delete(tick);
+            }
+            doc.add_message(msg);
+        }
+        assert_eq!(doc.tombstone_count(), 2);
+
+        assert_eq!(doc.compact_tombstones(500), 1);
+        assert!(doc.get_message("old").is_none());
+        assert!(doc.get_message("recent").is_some());
+        assert!(doc.get_message("live").is_some());
+
+        // A stale peer re-sending the tombstone does not resurrect the message
+        let mut stale = RealmChatDocument::new();
+        let mut old = EditableChatMessage::new_text(
+            "old".into(), "realm".into(), "zephyr".into(), "Hello".into(), 100,
+        );
+        old.delete(200);
+        stale.add_message(old);
+        doc.merge(stale);
+        assert_eq!(doc.visible_count(), 1);
+    }
+
+    #[test]
+    fn test_compact_tombstones_keeps_held() {
+        let mut doc = RealmChatDocument::new();
+        for id in ["held", "free"] {
+            let mut msg = EditableChatMessage::new_text(
+                id.into(), "realm".into(), "zephyr".into(), "Hello".into(), 100,
+            );
+            msg.delete(200);
+            doc.add_message(msg);
+        }
+
+        let held = BTreeSet::from(["held".to_string()]);
+        assert_eq!(doc.compact_tombstones_except(500, &held), 1);
+        assert!(doc.get_message("held").is_some());
+        assert!(doc.get_message("free").is_none());
+    }
+
+    #[test]
+    fn test_authorship_survives_edit_and_delete() {
+        let identity = PQIdentity::generate();
+        let mut msg = EditableChatMessage::new_text(
+            "msg-1".into(), "realm".into(), "zephyr".into(), "Original".into(), 100,
+        )
+        .with_author_id("aa".into())
+        .signed_by(&identity);
+        assert!(msg.verify_authorship());
+
+        msg.edit("Edited".into(), 200);
+        msg.delete(300);
+        assert_eq!(msg.original_content(), "Original");
+        assert!(msg.verify_authorship());
+
+        msg.versions[0].content = "Forged".into();
+        assert!(!msg.verify_authorship());
+    }
+
     #[test]
     fn test_merge_concurrent_messages_both_preserved() {
         // Simulates the exact bug: Love and Joy each send while disconnected
@@ -1176,6 +1770,39 @@ mod tests {
         assert!(doc.reply_preview("nonexistent").is_none());
     }
 
+    #[test]
+    fn test_pagination() {
+        let mut doc = RealmChatDocument::new();
+        for i in 0..10u64 {
+            doc.add_message(EditableChatMessage::new_text(
+                format!("msg-{i}"),
+                "realm-1".to_string(),
+                "alice".to_string(),
+                format!("message {i}"),
+                i * 100,
+            ));
+        }
+        doc.delete_message("msg-9", "alice", 2000);
+        let ids = |page: &ChatPage<'_>| page.messages.iter().map(|m| m.id.clone()).collect::<Vec<_>>();
+
+        let latest = doc.page_before(None, 3);
+        assert_eq!(ids(&latest), ["msg-6", "msg-7", "msg-8"]);
+        assert_eq!((latest.earlier, latest.later), (6, 0));
+
+        let older = doc.page_before(latest.first_cursor().as_ref(), 4);
+        assert_eq!(ids(&older), ["msg-2", "msg-3", "msg-4", "msg-5"]);
+        assert_eq!((older.earlier, older.later), (2, 3));
+
+        let newer = doc.page_after(&older.last_cursor().unwrap(), 2);
+        assert_eq!(ids(&newer), ["msg-6", "msg-7"]);
+        assert_eq!(newer.later, 1);
+
+        let around = doc.page_around(450, 4);
+        assert_eq!(ids(&around), ["msg-3", "msg-4", "msg-5", "msg-6"]);
+        let at_end = doc.page_around(10_000, 4);
+        assert_eq!(ids(&at_end), ["msg-5", "msg-6", "msg-7", "msg-8"]);
+    }
+
     #[test]
     fn test_merge_unions_reactions() {
         let mut local = RealmChatDocument::new();
@@ -1318,6 +1945,120 @@ mod tests {
         assert_eq!(restored.get_message("msg-1").unwrap().current_content, "Hello");
         assert_eq!(restored.get_message("msg-2").unwrap().current_content, "World");
     }
+
+    fn poll(multiple: bool, closes_at: Option<u64>) -> EditableChatMessage {
+        EditableChatMessage::new_poll(
+            "poll-1".into(), "realm".into(), "alice".into(), 100,
+            "Lunch?".into(), vec!["Pizza".into(), "Tacos, spicy".into()], multiple, closes_at,
+        )
+    }
+
+    fn voter(n: u8) -> (SecretKey, String) {
+        let key = SecretKey::from_bytes(&[n; 32]);
+        let id = hex::encode(key.public().as_bytes());
+        (key, id)
+    }
+
+    #[test]
+    fn test_poll_single_choice_voting() {
+        let ((alice, alice_id), (bob, bob_id), (carol, _)) = (voter(1), voter(2), voter(3));
+        let mut msg = poll(false, Some(1000));
+        assert!(msg.is_poll());
+        assert!(msg.cast_vote(&alice, BTreeSet::from([0]), 200));
+        assert!(msg.cast_vote(&bob, BTreeSet::from([1]), 210));
+        // Too many choices, out of range, after close, and stale ballots
+        assert!(!msg.cast_vote(&bob, BTreeSet::from([0, 1]), 300));
+        assert!(!msg.cast_vote(&bob, BTreeSet::from([2]), 300));
+        assert!(!msg.cast_vote(&carol, BTreeSet::from([0]), 1000));
+        assert!(!msg.cast_vote(&alice, BTreeSet::from([1]), 150));
+
+        // Bob changes his mind
+        assert!(msg.cast_vote(&bob, BTreeSet::from([0]), 400));
+        let tally = msg.poll_tally(500).unwrap();
+        assert_eq!(tally.voters, 2);
+        assert_eq!(tally.options[0].votes, 2);
+        let mut voters = vec![alice_id, bob_id];
+        voters.sort();
+        assert_eq!(tally.options[0].voters, voters);
+        assert_eq!(tally.options[1].votes, 0);
+        assert!(!tally.closed);
+        assert!(msg.poll_tally(1000).unwrap().closed);
+
+        // Withdrawing leaves the ballot but no longer counts it
+        assert!(msg.cast_vote(&alice, BTreeSet::new(), 600));
+        assert_eq!(msg.poll_tally(700).unwrap().voters, 1);
+    }
+
+    #[test]
+    fn test_poll_merge_keeps_latest_signed_ballot() {
+        let ((bob, bob_id), (carol, carol_id), (_, mallory_id)) = (voter(2), voter(3), voter(4));
+        let mut local = RealmChatDocument::new();
+        local.add_message(poll(true, None));
+        let mut remote = local.clone();
+
+        local.cast_vote("poll-1", &bob, BTreeSet::from([0]), 200);
+        remote.cast_vote("poll-1", &bob, BTreeSet::from([0, 1]), 300);
+        remote.cast_vote("poll-1", &carol, BTreeSet::from([1]), 250);
+        let votes = &mut remote.get_message_mut("poll-1").unwrap().votes;
+        // Bob's ballot filed under Mallory's ID, and an unsigned one
+        let copied = votes[&bob_id].clone();
+        votes.insert(mallory_id.clone(), copied);
+        let unsigned = PollBallot { choices: BTreeSet::from([0]), cast_at: 260, signature: None };
+        votes.insert(carol_id.clone(), unsigned);
+
+        local.merge(remote);
+        let msg = local.get_message("poll-1").unwrap();
+        assert_eq!(msg.votes[&bob_id].choices, BTreeSet::from([0, 1]));
+        assert!(!msg.votes.contains_key(&carol_id));
+        assert!(!msg.votes.contains_key(&mallory_id));
+    }
+
+    #[test]
+    fn test_poll_close_checked_against_receiver_clock() {
+        let (bob, bob_id) = voter(2);
+        // Closed long ago by this node's clock
+        let mut local = RealmChatDocument::new();
+        local.add_message(poll(false, Some(1000)));
+        let mut remote = local.clone();
+        // A ballot claiming to be cast before the close
+        remote.cast_vote("poll-1", &bob, BTreeSet::from([1]), 500);
+
+        let delta = RealmChatDocument::extract_delta(&local, &remote).unwrap();
+        assert!(!local.apply_delta(&delta));
+        local.merge(remote.clone());
+        assert!(local.get_message("poll-1").unwrap().votes.is_empty());
+
+        // A node that never saw the poll keeps the votes it arrived with
+        let mut late = RealmChatDocument::new();
+        late.merge(remote);
+        assert!(late.get_message("poll-1").unwrap().votes.contains_key(&bob_id));
+    }
+
+    #[test]
+    fn test_extract_delta_vote_roundtrip() {
+        let (bob, bob_id) = voter(2);
+        let mut old = RealmChatDocument::new();
+        old.add_message(poll(false, None));
+
+        let mut new = old.clone();
+        new.cast_vote("poll-1", &bob, BTreeSet::from([1]), 200);
+
+        let delta_bytes = RealmChatDocument::extract_delta(&old, &new).unwrap();
+        assert!(old.apply_delta(&delta_bytes));
+        assert_eq!(old.get_message("poll-1").unwrap().votes[&bob_id].choices, BTreeSet::from([1]));
+    }
+
+    #[test]
+    fn test_poll_tally_csv_export() {
+        let (bob, bob_id) = voter(2);
+        let mut msg = poll(false, Some(1000));
+        msg.cast_vote(&bob, BTreeSet::from([1]), 200);
+        let csv = msg.poll_tally(300).unwrap().to_csv();
+        assert_eq!(csv, format!("option,votes,voters\nPizza,0,\n\"Tacos, spicy\",1,{bob_id}\n"));
+        assert!(EditableChatMessage::new_text(
+            "msg-1".into(), "realm".into(), "alice".into(), "Hello".into(), 100,
+        ).poll_tally(0).is_none());
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -3,7 +3,7 @@
 //! Provides sensible defaults with the ability to customize behavior
 //! through the builder pattern.
 
-use indras_node::NodeConfig;
+use indras_node::{BudgetConfig, NodeConfig, DEFAULT_CONNECT_TIMEOUT, DEFAULT_REQUEST_TIMEOUT};
 use std::path::PathBuf;
 use std::time::Duration;
 
@@ -88,6 +88,19 @@ pub struct NetworkConfig {
     pub poll_interval: Duration,
     /// How often to save the world view snapshot (default 30s).
     pub save_interval: Duration,
+    /// How often to garbage-collect document tombstones (default 1h).
+    pub compaction_interval: Duration,
+    /// How often to generate realm digests (default 24h).
+    pub digest_interval: Duration,
+    /// Size limit of the avatar/emoji asset cache in bytes (default 64MB).
+    pub asset_cache_bytes: u64,
+    /// How long a single peer connection attempt may take (default 10s).
+    pub connect_timeout: Duration,
+    /// How long a single request to a peer may take, and the default
+    /// bound on [`join`](crate::IndrasNetwork::join)'s bootstrap (default 15s).
+    pub request_timeout: Duration,
+    /// Connection caps, idle reaping and on-demand dialing.
+    pub connection_budget: BudgetConfig,
     /// Underlying node configuration.
     pub(crate) node_config: Option<NodeConfig>,
 }
@@ -107,6 +120,12 @@ impl Default for NetworkConfig {
             local_only: false,
             poll_interval: Duration::from_secs(2),
             save_interval: Duration::from_secs(30),
+            compaction_interval: Duration::from_secs(60 * 60),
+            digest_interval: Duration::from_secs(24 * 60 * 60),
+            asset_cache_bytes: 64 * 1024 * 1024,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            connection_budget: BudgetConfig::default(),
             node_config: None,
         }
     }
@@ -147,6 +166,9 @@ impl NetworkConfig {
         }
 
         config
+            .with_connect_timeout(self.connect_timeout)
+            .with_request_timeout(self.request_timeout)
+            .with_connection_budget(self.connection_budget.clone())
     }
 }
 
@@ -236,6 +258,44 @@ impl NetworkBuilder {
         self
     }
 
+    /// Set how often to garbage-collect document tombstones (default 1h).
+    pub fn compaction_interval(mut self, interval: Duration) -> Self {
+        self.config.compaction_interval = interval;
+        self
+    }
+
+    /// Set how often to generate realm digests (default 24h).
+    pub fn digest_interval(mut self, interval: Duration) -> Self {
+        self.config.digest_interval = interval;
+        self
+    }
+
+    /// Set the size limit of the avatar/emoji asset cache (default 64MB).
+    pub fn asset_cache_bytes(mut self, bytes: u64) -> Self {
+        self.config.asset_cache_bytes = bytes;
+        self
+    }
+
+    /// Set how long a single peer connection attempt may take (default 10s).
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.config.connect_timeout = timeout;
+        self
+    }
+
+    /// Set how long a single request to a peer may take (default 15s).
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.config.request_timeout = timeout;
+        self
+    }
+
+    /// Set connection caps, idle reaping and on-demand dialing.
+    ///
+    /// Use [`BudgetConfig::unlimited`] to keep every connection open.
+    pub fn connection_budget(mut self, budget: BudgetConfig) -> Self {
+        self.config.connection_budget = budget;
+        self
+    }
+
     /// Use a custom node configuration.
     ///
     /// This is an escape hatch for advanced users who need full control
@@ -316,4 +376,22 @@ mod tests {
         assert_eq!(config.display_name, Some("Alice".to_string()));
         assert!(config.enforce_pq_signatures);
     }
+
+    #[test]
+    fn test_timeouts_reach_node_config() {
+        let config = NetworkConfig::default();
+        assert_eq!(config.connect_timeout, DEFAULT_CONNECT_TIMEOUT);
+        assert_eq!(config.request_timeout, DEFAULT_REQUEST_TIMEOUT);
+
+        let config = NetworkBuilder::new()
+            .data_dir("/tmp/test")
+            .connect_timeout(Duration::from_secs(3))
+            .request_timeout(Duration::from_secs(4))
+            .connection_budget(BudgetConfig::unlimited())
+            .build_config();
+        let node = config.to_node_config();
+        assert_eq!(node.connect_timeout, Duration::from_secs(3));
+        assert_eq!(node.request_timeout, Duration::from_secs(4));
+        assert_eq!(node.connection_budget, BudgetConfig::unlimited());
+    }
 }
@@ -0,0 +1,170 @@
+//! In-band realm invitations delivered to existing contacts.
+//!
+//! Instead of copy-pasting invite codes, a member can invite a contact
+//! directly with `realm.invite_contact(contact_id)`. The invite key is
+//! ML-KEM-wrapped to the contact's encapsulation key and delivered over the
+//! pairwise DM realm as a `Content::Extension` message. On the receiving
+//! side the network surfaces an [`InviteEvent::Received`]; accepting joins
+//! the realm automatically and notifies the inviter.
+
+use crate::member::MemberId;
+use crate::message::Content;
+use crate::network::RealmId;
+use serde::{Deserialize, Serialize};
+
+/// Extension type identifier for contact invite messages.
+pub const CONTACT_INVITE_TYPE_ID: &str = "indras-network/contact-invite/v1";
+
+/// A realm invitation addressed to one contact.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContactInvite {
+    /// The realm being offered.
+    pub realm_id: RealmId,
+    /// Display name of the realm, if known.
+    pub realm_name: Option<String>,
+    /// Who sent the invitation.
+    pub inviter: MemberId,
+    /// Invite URI carrying the ML-KEM-wrapped interface key.
+    pub invite: String,
+    /// When the invitation was sent (milliseconds since epoch).
+    pub sent_at_millis: u64,
+}
+
+/// Wire format for contact invite traffic on a DM realm.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ContactInviteMessage {
+    /// An invitation from the sender to the recipient.
+    Invite(ContactInvite),
+    /// The recipient's answer to an earlier invitation.
+    Response {
+        /// The realm that was offered.
+        realm_id: RealmId,
+        /// Whether the invitation was accepted.
+        accepted: bool,
+    },
+}
+
+impl ContactInviteMessage {
+    /// Serialize into a generic `Content::Extension`.
+    pub fn to_content(&self) -> Content {
+        let payload = postcard::to_allocvec(self).expect("ContactInviteMessage serialization");
+        Content::Extension {
+            type_id: CONTACT_INVITE_TYPE_ID.to_string(),
+            payload,
+        }
+    }
+
+    /// Parse a `Content::Extension` back into an invite message.
+    ///
+    /// Returns `None` for other content types or malformed payloads.
+    pub fn from_content(content: &Content) -> Option<Self> {
+        match content {
+            Content::Extension { type_id, payload } if type_id == CONTACT_INVITE_TYPE_ID => {
+                postcard::from_bytes(payload).ok()
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Invitation lifecycle events surfaced by `IndrasNetwork::invite_events()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InviteEvent {
+    /// A contact invited us to a realm. Answer with `accept_invite` or
+    /// `decline_invite`.
+    Received(ContactInvite),
+    /// A contact accepted one of our invitations.
+    Accepted {
+        /// The realm that was offered.
+        realm_id: RealmId,
+        /// The contact who accepted.
+        by: MemberId,
+    },
+    /// A contact declined one of our invitations.
+    Declined {
+        /// The realm that was offered.
+        realm_id: RealmId,
+        /// The contact who declined.
+        by: MemberId,
+    },
+}
+
+impl InviteEvent {
+    /// Build the event for a message received from `sender`.
+    pub(crate) fn from_message(sender: MemberId, message: ContactInviteMessage) -> Self {
+        match message {
+            ContactInviteMessage::Invite(invite) => InviteEvent::Received(invite),
+            ContactInviteMessage::Response {
+                realm_id,
+                accepted: true,
+            } => InviteEvent::Accepted {
+                realm_id,
+                by: sender,
+            },
+            ContactInviteMessage::Response {
+                realm_id,
+                accepted: false,
+            } => InviteEvent::Declined {
+                realm_id,
+                by: sender,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indras_core::InterfaceId;
+
+    fn invite() -> ContactInvite {
+        ContactInvite {
+            realm_id: InterfaceId::new([4u8; 32]),
+            realm_name: Some("Garden".to_string()),
+            inviter: [1u8; 32],
+            invite: "indra:realm:abc".to_string(),
+            sent_at_millis: 42,
+        }
+    }
+
+    #[test]
+    fn test_content_roundtrip() {
+        let content = ContactInviteMessage::Invite(invite()).to_content();
+        match ContactInviteMessage::from_content(&content) {
+            Some(ContactInviteMessage::Invite(decoded)) => assert_eq!(decoded, invite()),
+            other => panic!("unexpected: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_other_content_is_ignored() {
+        assert!(ContactInviteMessage::from_content(&Content::Text("hi".into())).is_none());
+        let other = Content::Extension {
+            type_id: "other/v1".to_string(),
+            payload: vec![1, 2, 3],
+        };
+        assert!(ContactInviteMessage::from_content(&other).is_none());
+    }
+
+    #[test]
+    fn test_response_events() {
+        let realm_id = InterfaceId::new([4u8; 32]);
+        let by = [2u8; 32];
+        let accepted = ContactInviteMessage::Response {
+            realm_id,
+            accepted: true,
+        };
+        assert_eq!(
+            InviteEvent::from_message(by, accepted),
+            InviteEvent::Accepted { realm_id, by }
+        );
+        let declined = ContactInviteMessage::Response {
+            realm_id,
+            accepted: false,
+        };
+        assert_eq!(
+            InviteEvent::from_message(by, declined),
+            InviteEvent::Declined { realm_id, by }
+        );
+    }
+}
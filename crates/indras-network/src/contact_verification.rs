@@ -0,0 +1,341 @@
+//! Out-of-band contact verification with safety numbers.
+//!
+//! Two contacts compare a [`SafetyNumber`], derived from both members'
+//! ML-DSA verifying keys, in person or over a channel they trust, or one
+//! scans the other's QR code. Once it matches,
+//! [`IndrasNetwork::mark_contact_verified`](crate::IndrasNetwork::mark_contact_verified)
+//! pins the contact's key in the home realm's [`ContactVerificationDocument`],
+//! so every device of the member sees the contact as verified.
+//!
+//! If a verified contact later announces a different key, their status
+//! becomes [`VerificationStatus::KeyChanged`] and the contact poller emits
+//! [`PeerEvent::ContactKeyChanged`](crate::PeerEvent::ContactKeyChanged).
+
+use crate::document::DocumentSchema;
+use crate::member::MemberId;
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Document name for contact verification state within the home realm.
+pub const CONTACT_VERIFICATION_DOC: &str = "_contact_verification";
+
+/// BLAKE3 key-derivation context for safety number fingerprints.
+const SAFETY_NUMBER_CONTEXT: &str = "indras safety number v1";
+
+/// Leading byte of a safety number QR payload.
+const QR_VERSION: u8 = 1;
+
+/// Five-digit groups contributed by each member.
+const GROUPS_PER_MEMBER: usize = 6;
+
+/// Bytes of fingerprint behind each five-digit group.
+const BYTES_PER_GROUP: usize = 5;
+
+/// A short number two contacts compare to confirm each other's keys.
+///
+/// Both sides compute the same number: the members' fingerprints are
+/// ordered by member ID, not by who asks.
+///
+/// # Example
+///
+/// ```ignore
+/// let number = network.safety_number(&friend_id)?;
+/// println!("{number}"); // "12345 67890 ... 24680"
+/// // ...read it out, or show `number.qr_payload()` as a QR code...
+/// network.mark_contact_verified(&friend_id, VerificationMethod::SafetyNumber).await?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SafetyNumber {
+    fingerprints: [[u8; 32]; 2],
+}
+
+impl SafetyNumber {
+    /// Safety number for two members, each with their ML-DSA verifying key.
+    pub fn new(a: (&MemberId, &[u8]), b: (&MemberId, &[u8])) -> Self {
+        let (first, second) = if a.0 <= b.0 { (a, b) } else { (b, a) };
+        Self {
+            fingerprints: [
+                fingerprint(first.0, first.1),
+                fingerprint(second.0, second.1),
+            ],
+        }
+    }
+
+    /// The number as twelve five-digit groups.
+    pub fn groups(&self) -> Vec<String> {
+        self.fingerprints
+            .iter()
+            .flat_map(|fp| {
+                fp.chunks(BYTES_PER_GROUP)
+                    .take(GROUPS_PER_MEMBER)
+                    .map(|chunk| {
+                        let value = chunk.iter().fold(0u64, |acc, b| (acc << 8) | u64::from(*b));
+                        format!("{:05}", value % 100_000)
+                    })
+            })
+            .collect()
+    }
+
+    /// Bytes to encode in a QR code for the other side to scan.
+    pub fn qr_payload(&self) -> Vec<u8> {
+        let mut payload = Vec::with_capacity(1 + 64);
+        payload.push(QR_VERSION);
+        payload.extend_from_slice(&self.fingerprints[0]);
+        payload.extend_from_slice(&self.fingerprints[1]);
+        payload
+    }
+
+    /// Whether a scanned QR payload shows the same safety number.
+    pub fn matches_qr(&self, payload: &[u8]) -> bool {
+        payload == self.qr_payload().as_slice()
+    }
+
+    /// Generate a QR code image of [`qr_payload`](Self::qr_payload).
+    #[cfg(feature = "qr")]
+    pub fn to_qr(&self) -> crate::error::Result<image::DynamicImage> {
+        use qrcode::QrCode;
+
+        let code = QrCode::new(self.qr_payload()).map_err(|e| {
+            crate::error::IndraError::Crypto(format!("Failed to generate QR code: {}", e))
+        })?;
+        let image = code.render::<image::Luma<u8>>().build();
+        Ok(image::DynamicImage::ImageLuma8(image))
+    }
+}
+
+impl fmt::Display for SafetyNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.groups().join(" "))
+    }
+}
+
+fn fingerprint(member: &MemberId, verifying_key: &[u8]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new_derive_key(SAFETY_NUMBER_CONTEXT);
+    hasher.update(member);
+    hasher.update(verifying_key);
+    *hasher.finalize().as_bytes()
+}
+
+/// Hash identifying a verifying key in verification records.
+pub fn key_hash(verifying_key: &[u8]) -> [u8; 32] {
+    *blake3::hash(verifying_key).as_bytes()
+}
+
+/// How a contact was verified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VerificationMethod {
+    /// The safety number was compared by reading or listening.
+    SafetyNumber,
+    /// One side scanned the other's safety number QR code.
+    QrScan,
+}
+
+/// Verification state kept for one contact.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerificationRecord {
+    /// Hash of the verifying key that was compared.
+    pub key_hash: [u8; 32],
+    /// How it was verified; `None` once the verification was cleared.
+    pub method: Option<VerificationMethod>,
+    /// When the record last changed (milliseconds since epoch).
+    pub updated_at_millis: u64,
+}
+
+/// Whether a contact's keys have been confirmed out of band.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationStatus {
+    /// Never verified, or the verification was cleared.
+    Unverified,
+    /// Verified, and the contact still uses the compared key.
+    Verified {
+        /// How it was verified.
+        method: VerificationMethod,
+        /// When (milliseconds since epoch).
+        verified_at_millis: u64,
+    },
+    /// Verified once, but the contact now announces a different key.
+    /// Compare safety numbers again before trusting them.
+    KeyChanged {
+        /// When the old key was verified (milliseconds since epoch).
+        verified_at_millis: u64,
+    },
+}
+
+impl VerificationStatus {
+    /// Whether the contact is verified with their current key.
+    pub fn is_verified(&self) -> bool {
+        matches!(self, Self::Verified { .. })
+    }
+}
+
+/// Home-realm document of verified contacts.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContactVerificationDocument {
+    /// Records keyed by contact.
+    pub records: BTreeMap<MemberId, VerificationRecord>,
+}
+
+impl ContactVerificationDocument {
+    /// Create an empty document.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record `contact` as verified with `verifying_key`.
+    pub fn verify(
+        &mut self,
+        contact: MemberId,
+        verifying_key: &[u8],
+        method: VerificationMethod,
+        now_millis: u64,
+    ) {
+        self.records.insert(
+            contact,
+            VerificationRecord {
+                key_hash: key_hash(verifying_key),
+                method: Some(method),
+                updated_at_millis: now_millis,
+            },
+        );
+    }
+
+    /// Clear a contact's verification. Returns `false` if they weren't
+    /// verified.
+    pub fn clear(&mut self, contact: &MemberId, now_millis: u64) -> bool {
+        match self.records.get_mut(contact).filter(|r| r.method.is_some()) {
+            Some(record) => {
+                record.method = None;
+                record.updated_at_millis = now_millis;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// A contact's status, given the verifying key they announce now.
+    ///
+    /// With no current key known, a verified contact stays verified.
+    pub fn status(&self, contact: &MemberId, current_key: Option<&[u8]>) -> VerificationStatus {
+        let Some(record) = self.records.get(contact) else {
+            return VerificationStatus::Unverified;
+        };
+        let Some(method) = record.method else {
+            return VerificationStatus::Unverified;
+        };
+        match current_key {
+            Some(key) if key_hash(key) != record.key_hash => VerificationStatus::KeyChanged {
+                verified_at_millis: record.updated_at_millis,
+            },
+            _ => VerificationStatus::Verified {
+                method,
+                verified_at_millis: record.updated_at_millis,
+            },
+        }
+    }
+}
+
+impl DocumentSchema for ContactVerificationDocument {
+    /// Per-contact LWW merge.
+    fn merge(&mut self, remote: Self) {
+        for (contact, record) in remote.records {
+            match self.records.get(&contact) {
+                Some(local) if local.updated_at_millis >= record.updated_at_millis => {}
+                _ => {
+                    self.records.insert(contact, record);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: MemberId = [1u8; 32];
+    const BOB: MemberId = [2u8; 32];
+    const ALICE_KEY: &[u8] = b"alice-key";
+    const BOB_KEY: &[u8] = b"bob-key";
+    const NEW_KEY: &[u8] = b"new-key";
+
+    #[test]
+    fn test_safety_number_is_symmetric() {
+        let ab = SafetyNumber::new((&ALICE, ALICE_KEY), (&BOB, BOB_KEY));
+        let ba = SafetyNumber::new((&BOB, BOB_KEY), (&ALICE, ALICE_KEY));
+        assert_eq!(ab, ba);
+        assert_eq!(ab.to_string(), ba.to_string());
+
+        let groups = ab.groups();
+        assert_eq!(groups.len(), 2 * GROUPS_PER_MEMBER);
+        assert!(
+            groups
+                .iter()
+                .all(|g| g.len() == 5 && g.chars().all(|c| c.is_ascii_digit()))
+        );
+    }
+
+    #[test]
+    fn test_safety_number_changes_with_key() {
+        let before = SafetyNumber::new((&ALICE, ALICE_KEY), (&BOB, BOB_KEY));
+        let after = SafetyNumber::new((&ALICE, ALICE_KEY), (&BOB, NEW_KEY));
+        assert_ne!(before, after);
+        assert_ne!(before.to_string(), after.to_string());
+    }
+
+    #[test]
+    fn test_qr_payload_round_trip() {
+        let number = SafetyNumber::new((&ALICE, ALICE_KEY), (&BOB, BOB_KEY));
+        let other = SafetyNumber::new((&ALICE, ALICE_KEY), (&BOB, NEW_KEY));
+        assert!(number.matches_qr(&number.qr_payload()));
+        assert!(!number.matches_qr(&other.qr_payload()));
+        assert!(!number.matches_qr(&[]));
+    }
+
+    #[test]
+    fn test_status_tracks_key_changes() {
+        let mut doc = ContactVerificationDocument::new();
+        assert_eq!(
+            doc.status(&BOB, Some(BOB_KEY)),
+            VerificationStatus::Unverified
+        );
+
+        doc.verify(BOB, BOB_KEY, VerificationMethod::QrScan, 10);
+        assert!(doc.status(&BOB, Some(BOB_KEY)).is_verified());
+        assert!(doc.status(&BOB, None).is_verified());
+        assert_eq!(
+            doc.status(&BOB, Some(NEW_KEY)),
+            VerificationStatus::KeyChanged {
+                verified_at_millis: 10
+            }
+        );
+
+        assert!(doc.clear(&BOB, 20));
+        assert!(!doc.clear(&BOB, 30));
+        assert_eq!(
+            doc.status(&BOB, Some(NEW_KEY)),
+            VerificationStatus::Unverified
+        );
+    }
+
+    #[test]
+    fn test_merge_keeps_latest_record() {
+        let mut local = ContactVerificationDocument::new();
+        local.verify(BOB, BOB_KEY, VerificationMethod::SafetyNumber, 10);
+
+        let mut remote = local.clone();
+        remote.clear(&BOB, 20);
+        remote.verify(ALICE, ALICE_KEY, VerificationMethod::QrScan, 5);
+
+        local.merge(remote.clone());
+        assert_eq!(local.status(&BOB, None), VerificationStatus::Unverified);
+        assert!(local.status(&ALICE, None).is_verified());
+
+        // An older record never wins
+        let mut stale = ContactVerificationDocument::new();
+        stale.verify(BOB, BOB_KEY, VerificationMethod::SafetyNumber, 15);
+        local.merge(stale);
+        assert_eq!(local.status(&BOB, None), VerificationStatus::Unverified);
+    }
+}
@@ -0,0 +1,423 @@
+//! Device pairing — linking a second device to an existing identity.
+//!
+//! The existing device shows a pairing code (text or QR). The code
+//! carries the account's MemberId and a random secret; both devices
+//! derive a private pairing realm from it, so nobody without the code
+//! can find or read the exchange.
+//!
+//! # How It Works
+//!
+//! 1. Existing device calls `start_device_pairing()` → shows the code
+//! 2. New device calls `request_device_pairing(code, name)`, which joins
+//!    the pairing realm and writes a [`PairingRequest`]
+//! 3. Existing device sees the request and calls `grant_device_pairing()`,
+//!    which writes a [`PairingGrant`] carrying the PQ identity and lists
+//!    the new device in the home realm's [`LinkedDevicesDocument`]
+//! 4. New device stops, calls `IndrasNetwork::link_device(data_dir, grant)`,
+//!    restarts, and calls `confirm_device_link()` once it sees itself in
+//!    the account's home realm
+//! 5. Existing device's `wait_for_device_link()` returns once the
+//!    confirmation syncs back — both devices now see the home realm
+//!
+//! The new device keeps its own transport key (unique `MemberId`) and
+//! shares the account's PQ identity and home realm.
+
+use crate::document::{Document, DocumentSchema};
+use crate::error::{IndraError, Result};
+use crate::member::MemberId;
+use crate::network::RealmId;
+use crate::util::now_millis;
+
+use bech32::{Bech32m, Hrp};
+use indras_core::InterfaceId;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Human-readable part for pairing codes.
+const PAIRING_HRP: &str = "indrapair";
+
+/// Length of the random pairing secret.
+const SECRET_LEN: usize = 16;
+
+/// Document name for the pairing exchange inside the pairing realm.
+pub const DEVICE_PAIRING_DOC: &str = "_device_pairing";
+
+/// Document name for the linked-device list in the home realm.
+pub const LINKED_DEVICES_DOC: &str = "_linked_devices";
+
+/// A one-time code for pairing a new device with an account.
+///
+/// # Format
+///
+/// ```text
+/// indrapair1<bech32m(account_id || secret)>
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct PairingCode {
+    account_id: MemberId,
+    secret: [u8; SECRET_LEN],
+}
+
+impl PairingCode {
+    /// Generate a fresh code for the given account.
+    pub fn generate(account_id: MemberId) -> Self {
+        Self {
+            account_id,
+            secret: rand::random(),
+        }
+    }
+
+    /// The account (existing device) this code pairs with.
+    pub fn account_id(&self) -> MemberId {
+        self.account_id
+    }
+
+    /// Deterministic ID of the pairing realm both devices join.
+    pub fn realm_id(&self) -> RealmId {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"device-pairing-v1:");
+        hasher.update(&self.account_id);
+        hasher.update(&self.secret);
+        InterfaceId::new(*hasher.finalize().as_bytes())
+    }
+
+    /// Key seed for the pairing realm; only holders of the code know it.
+    pub fn key_seed(&self) -> [u8; 32] {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"device-pairing-key-v1:");
+        hasher.update(&self.account_id);
+        hasher.update(&self.secret);
+        *hasher.finalize().as_bytes()
+    }
+
+    /// Encode as a bech32m string.
+    pub fn encode(&self) -> String {
+        let hrp = Hrp::parse(PAIRING_HRP).expect("valid HRP");
+        let mut data = Vec::with_capacity(32 + SECRET_LEN);
+        data.extend_from_slice(&self.account_id);
+        data.extend_from_slice(&self.secret);
+        bech32::encode::<Bech32m>(hrp, &data).expect("valid encoding")
+    }
+
+    /// Parse from a bech32m string.
+    pub fn parse(s: &str) -> Result<Self> {
+        let (hrp, data) = bech32::decode(s.trim()).map_err(|e| IndraError::InvalidInvite {
+            reason: format!("Invalid pairing code: {}", e),
+        })?;
+
+        if !hrp.as_str().eq_ignore_ascii_case(PAIRING_HRP) {
+            return Err(IndraError::InvalidInvite {
+                reason: format!(
+                    "Invalid pairing code HRP: expected '{}', got '{}'",
+                    PAIRING_HRP,
+                    hrp.as_str()
+                ),
+            });
+        }
+
+        if data.len() != 32 + SECRET_LEN {
+            return Err(IndraError::InvalidInvite {
+                reason: format!(
+                    "Invalid pairing code: expected {} bytes, got {}",
+                    32 + SECRET_LEN,
+                    data.len()
+                ),
+            });
+        }
+
+        let mut account_id = [0u8; 32];
+        account_id.copy_from_slice(&data[..32]);
+        let mut secret = [0u8; SECRET_LEN];
+        secret.copy_from_slice(&data[32..]);
+
+        Ok(Self { account_id, secret })
+    }
+
+    /// Generate a QR code image for this pairing code.
+    #[cfg(feature = "qr")]
+    pub fn to_qr(&self) -> Result<image::DynamicImage> {
+        use qrcode::QrCode;
+
+        let code = QrCode::new(self.encode().as_bytes())
+            .map_err(|e| IndraError::Artifact(format!("Failed to generate QR code: {}", e)))?;
+
+        let image = code.render::<image::Luma<u8>>().build();
+        Ok(image::DynamicImage::ImageLuma8(image))
+    }
+
+    /// Render the pairing code as an SVG QR code, for UIs that embed markup.
+    #[cfg(feature = "qr")]
+    pub fn to_qr_svg(&self) -> Result<String> {
+        use qrcode::QrCode;
+        use qrcode::render::svg;
+
+        let code = QrCode::new(self.encode().as_bytes())
+            .map_err(|e| IndraError::Artifact(format!("Failed to generate QR code: {}", e)))?;
+
+        Ok(code.render::<svg::Color>().min_dimensions(200, 200).build())
+    }
+}
+
+impl fmt::Debug for PairingCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Never print the secret
+        f.debug_struct("PairingCode")
+            .field("account_id", &hex::encode(&self.account_id[..8]))
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Display for PairingCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.encode())
+    }
+}
+
+impl FromStr for PairingCode {
+    type Err = IndraError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+/// Written by the new device to ask for the account's identity.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PairingRequest {
+    /// The new device's own MemberId (its transport key).
+    pub device_id: MemberId,
+    /// Human label shown on the existing device, e.g. "Laptop".
+    pub device_name: String,
+    /// When the request was made (milliseconds since epoch).
+    pub requested_at_millis: u64,
+}
+
+/// Written by the existing device in answer to a request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PairingGrant {
+    /// Device the grant was issued for.
+    pub device_id: MemberId,
+    /// Account whose home realm the new device should use.
+    pub account_id: MemberId,
+    /// Account display name.
+    pub display_name: Option<String>,
+    /// PQ identity from `export_pq_identity()`.
+    pub identity: Vec<u8>,
+}
+
+/// The pairing exchange, stored in the pairing realm.
+///
+/// Each side writes only its own field, so merge keeps whichever side
+/// has a value.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DevicePairingDocument {
+    pub request: Option<PairingRequest>,
+    pub grant: Option<PairingGrant>,
+}
+
+impl DocumentSchema for DevicePairingDocument {
+    fn merge(&mut self, remote: Self) {
+        if remote.request.is_some() {
+            self.request = remote.request;
+        }
+        if remote.grant.is_some() {
+            self.grant = remote.grant;
+        }
+    }
+}
+
+/// An in-progress pairing on either device.
+pub struct DevicePairing {
+    pub(crate) code: PairingCode,
+    pub(crate) doc: Document<DevicePairingDocument>,
+}
+
+impl DevicePairing {
+    /// The code this pairing was started with.
+    pub fn code(&self) -> &PairingCode {
+        &self.code
+    }
+
+    /// Current request, if the new device has written one.
+    pub async fn request(&self) -> Option<PairingRequest> {
+        let _ = self.doc.refresh().await;
+        self.doc.read().await.request.clone()
+    }
+
+    /// Current grant, if the existing device has written one.
+    pub async fn grant(&self) -> Option<PairingGrant> {
+        let _ = self.doc.refresh().await;
+        self.doc.read().await.grant.clone()
+    }
+
+    /// Wait until the new device's request arrives.
+    pub async fn wait_for_request(&self, timeout: std::time::Duration) -> Result<PairingRequest> {
+        poll_until(timeout, move || self.request()).await
+    }
+
+    /// Wait until the existing device's grant for `device_id` arrives.
+    pub async fn wait_for_grant(
+        &self,
+        device_id: MemberId,
+        timeout: std::time::Duration,
+    ) -> Result<PairingGrant> {
+        poll_until(timeout, move || async move {
+            self.grant()
+                .await
+                .filter(|grant| grant.device_id == device_id)
+        })
+        .await
+    }
+}
+
+/// One device listed in the home realm.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkedDevice {
+    /// Human label, e.g. "Laptop".
+    pub device_name: String,
+    /// When the device was linked (milliseconds since epoch).
+    pub linked_at_millis: u64,
+    /// Set by the device itself once it can read the home realm.
+    pub confirmed: bool,
+}
+
+/// Devices linked to the account, stored in the home realm.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkedDevicesDocument {
+    pub devices: BTreeMap<MemberId, LinkedDevice>,
+}
+
+impl LinkedDevicesDocument {
+    /// Whether `device_id` has confirmed it sees the home realm.
+    pub fn is_confirmed(&self, device_id: &MemberId) -> bool {
+        self.devices.get(device_id).is_some_and(|d| d.confirmed)
+    }
+}
+
+impl DocumentSchema for LinkedDevicesDocument {
+    /// Union of devices; confirmation is sticky.
+    fn merge(&mut self, remote: Self) {
+        for (id, device) in remote.devices {
+            match self.devices.get_mut(&id) {
+                Some(local) => local.confirmed |= device.confirmed,
+                None => {
+                    self.devices.insert(id, device);
+                }
+            }
+        }
+    }
+}
+
+/// Poll `check` every 500ms until it yields a value or `timeout` passes.
+pub(crate) async fn poll_until<T, F, Fut>(timeout: std::time::Duration, mut check: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Option<T>>,
+{
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        if let Some(value) = check().await {
+            return Ok(value);
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return Err(IndraError::Timeout);
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account_id() -> MemberId {
+        [7u8; 32]
+    }
+
+    fn device(confirmed: bool) -> LinkedDevice {
+        LinkedDevice {
+            device_name: "Laptop".to_string(),
+            linked_at_millis: 1,
+            confirmed,
+        }
+    }
+
+    #[test]
+    fn test_code_roundtrip() {
+        let code = PairingCode::generate(account_id());
+        let encoded = code.encode();
+        assert!(encoded.starts_with("indrapair1"));
+
+        let parsed = PairingCode::parse(&encoded).unwrap();
+        assert_eq!(parsed, code);
+        assert_eq!(parsed.account_id(), account_id());
+        assert_eq!(parsed.realm_id(), code.realm_id());
+    }
+
+    #[test]
+    fn test_codes_derive_distinct_realms() {
+        let a = PairingCode::generate(account_id());
+        let b = PairingCode::generate(account_id());
+        assert_ne!(a.realm_id(), b.realm_id());
+        assert_ne!(a.key_seed(), b.key_seed());
+    }
+
+    #[test]
+    fn test_rejects_identity_code() {
+        let identity = crate::IdentityCode::from_member_id(account_id()).encode();
+        assert!(PairingCode::parse(&identity).is_err());
+    }
+
+    #[test]
+    fn test_debug_hides_secret() {
+        let code = PairingCode::generate(account_id());
+        let debug = format!("{:?}", code);
+        assert!(!debug.contains(&code.encode()));
+        assert!(!debug.contains("secret"));
+    }
+
+    #[test]
+    fn test_pairing_merge_keeps_both_sides() {
+        let request = PairingRequest {
+            device_id: [2u8; 32],
+            device_name: "Laptop".to_string(),
+            requested_at_millis: 1,
+        };
+        let grant = PairingGrant {
+            device_id: [2u8; 32],
+            account_id: account_id(),
+            display_name: Some("Zephyr".to_string()),
+            identity: vec![1, 2, 3],
+        };
+
+        let mut local = DevicePairingDocument {
+            request: None,
+            grant: Some(grant.clone()),
+        };
+        local.merge(DevicePairingDocument {
+            request: Some(request.clone()),
+            grant: None,
+        });
+
+        assert_eq!(local.request, Some(request));
+        assert_eq!(local.grant, Some(grant));
+    }
+
+    #[test]
+    fn test_linked_devices_confirmation_is_sticky() {
+        let id = [2u8; 32];
+        let mut local = LinkedDevicesDocument::default();
+        local.devices.insert(id, device(true));
+
+        let mut remote = LinkedDevicesDocument::default();
+        remote.devices.insert(id, device(false));
+        remote.devices.insert([3u8; 32], device(false));
+        local.merge(remote);
+
+        assert!(local.is_confirmed(&id));
+        assert!(!local.is_confirmed(&[3u8; 32]));
+        assert_eq!(local.devices.len(), 2);
+    }
+}
@@ -0,0 +1,492 @@
+//! Per-realm notification digests.
+//!
+//! Muted or low-priority realms can opt into periodic digests instead of
+//! per-message notifications. Each digest summarizes what happened in a
+//! realm since the previous one — new messages, completed quests, new
+//! artifacts — and is surfaced as a [`GlobalEvent`] on the
+//! [`DigestGenerator::subscribe`] channel. Optionally, a rendered summary is
+//! also posted into the home realm as a system message.
+//!
+//! Counting and rendering are both pluggable: app layers supply a
+//! [`DigestClassifier`] to recognize their own `Content::Extension`
+//! payloads (e.g. quest completion) and a [`DigestTemplate`] to control the
+//! text posted to the home realm.
+
+use crate::message::{Content, Message};
+use crate::network::{GlobalEvent, RealmId};
+use crate::snapshot::RealmSnapshot;
+
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use indras_core::InterfaceEvent;
+use indras_node::ReceivedEvent;
+use indras_transport::IrohIdentity;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use tokio::sync::broadcast;
+
+/// Event type carried by digest `GlobalEvent`s.
+pub const DIGEST_EVENT_TYPE: &str = "indras-network/realm-digest/v1";
+
+/// Summary of realm activity over one digest period.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RealmDigest {
+    /// The realm being summarized.
+    pub realm_id: RealmId,
+    /// Human-readable realm name, if known.
+    pub realm_name: Option<String>,
+    /// Start of the summarized period (exclusive).
+    pub since: DateTime<Utc>,
+    /// End of the summarized period (inclusive).
+    pub until: DateTime<Utc>,
+    /// Number of new chat messages.
+    pub new_messages: usize,
+    /// Number of quests completed.
+    pub completed_quests: usize,
+    /// Number of artifacts shared.
+    pub new_artifacts: usize,
+    /// Number of distinct members who posted anything.
+    pub active_members: usize,
+}
+
+impl RealmDigest {
+    /// Summarize messages in `(since, until]` using a classifier.
+    pub fn summarize<'a>(
+        realm_id: RealmId,
+        realm_name: Option<String>,
+        messages: impl IntoIterator<Item = &'a Message>,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        classifier: &dyn DigestClassifier,
+    ) -> Self {
+        let mut digest = Self {
+            realm_id,
+            realm_name,
+            since,
+            until,
+            new_messages: 0,
+            completed_quests: 0,
+            new_artifacts: 0,
+            active_members: 0,
+        };
+        let mut senders = HashSet::new();
+
+        for msg in messages {
+            if msg.timestamp <= since || msg.timestamp > until {
+                continue;
+            }
+            let Some(item) = classifier.classify(&msg.content) else {
+                continue;
+            };
+            senders.insert(msg.sender.id());
+            match item {
+                DigestItem::Message => digest.new_messages += 1,
+                DigestItem::Artifact => digest.new_artifacts += 1,
+                DigestItem::QuestCompleted => digest.completed_quests += 1,
+            }
+        }
+
+        digest.active_members = senders.len();
+        digest
+    }
+
+    /// Summarize a realm snapshot over `(since, until]`.
+    pub fn from_snapshot(
+        snapshot: &RealmSnapshot,
+        realm_name: Option<String>,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        classifier: &dyn DigestClassifier,
+    ) -> Self {
+        Self::summarize(
+            snapshot.realm_id(),
+            realm_name,
+            snapshot.messages(),
+            since,
+            until,
+            classifier,
+        )
+    }
+
+    /// Whether nothing happened during the period.
+    pub fn is_empty(&self) -> bool {
+        self.new_messages == 0 && self.completed_quests == 0 && self.new_artifacts == 0
+    }
+
+    /// Wrap this digest in a `GlobalEvent` attributed to `local`.
+    ///
+    /// The digest is carried as a postcard-encoded `Custom` event of type
+    /// [`DIGEST_EVENT_TYPE`].
+    pub fn to_global_event(&self, local: IrohIdentity) -> GlobalEvent {
+        let payload = postcard::to_allocvec(self).unwrap_or_default();
+        let sequence = self.until.timestamp_millis().max(0) as u64;
+        GlobalEvent {
+            realm_id: self.realm_id,
+            event: ReceivedEvent {
+                interface_id: self.realm_id,
+                event: InterfaceEvent::custom(
+                    local,
+                    sequence,
+                    DIGEST_EVENT_TYPE.to_string(),
+                    payload,
+                ),
+            },
+        }
+    }
+
+    /// Extract a digest from a `GlobalEvent`, if it carries one.
+    pub fn from_global_event(event: &GlobalEvent) -> Option<Self> {
+        match &event.event.event {
+            InterfaceEvent::Custom {
+                event_type,
+                payload,
+                ..
+            } if event_type == DIGEST_EVENT_TYPE => postcard::from_bytes(payload).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// What a single message counts as in a digest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestItem {
+    /// An ordinary chat message.
+    Message,
+    /// A newly shared artifact.
+    Artifact,
+    /// A quest reaching completion.
+    QuestCompleted,
+}
+
+/// Decides how each message contributes to a digest.
+///
+/// Return `None` to leave a message out of the digest entirely.
+pub trait DigestClassifier: Send + Sync {
+    /// Classify one message's content.
+    fn classify(&self, content: &Content) -> Option<DigestItem>;
+}
+
+/// Classifier for built-in content types.
+///
+/// Artifact-bearing variants count as artifacts; system notices, reactions,
+/// and unrecognized extensions are skipped; everything else is a message.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultDigestClassifier;
+
+impl DigestClassifier for DefaultDigestClassifier {
+    fn classify(&self, content: &Content) -> Option<DigestItem> {
+        match content {
+            Content::Artifact(_)
+            | Content::Image { .. }
+            | Content::InlineArtifact { .. }
+            | Content::Gallery { .. }
+            | Content::ArtifactGranted { .. } => Some(DigestItem::Artifact),
+            Content::Text(_)
+            | Content::TextWithPreviews { .. }
+            | Content::Binary { .. }
+            | Content::Sticker(_) => Some(DigestItem::Message),
+            _ => None,
+        }
+    }
+}
+
+/// Renders a digest into the text posted to the home realm.
+///
+/// Return `None` to skip posting for a digest.
+pub trait DigestTemplate: Send + Sync {
+    /// Render a digest as a one-message summary.
+    fn render(&self, digest: &RealmDigest) -> Option<String>;
+}
+
+/// Default English summary, e.g. "Garden: 12 new messages, 1 completed quest".
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultDigestTemplate;
+
+impl DigestTemplate for DefaultDigestTemplate {
+    fn render(&self, digest: &RealmDigest) -> Option<String> {
+        if digest.is_empty() {
+            return None;
+        }
+
+        fn count(n: usize, singular: &str, plural: &str) -> Option<String> {
+            match n {
+                0 => None,
+                1 => Some(format!("1 {singular}")),
+                n => Some(format!("{n} {plural}")),
+            }
+        }
+
+        let parts: Vec<String> = [
+            count(digest.new_messages, "new message", "new messages"),
+            count(digest.completed_quests, "completed quest", "completed quests"),
+            count(digest.new_artifacts, "new artifact", "new artifacts"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        let name = digest.realm_name.as_deref().unwrap_or("Realm");
+        Some(format!("{name}: {}", parts.join(", ")))
+    }
+}
+
+/// Tracks which realms are digested and publishes the results.
+///
+/// Owned by `IndrasNetwork`; access it via `network.digests()`. Digests are
+/// generated periodically (see `NetworkConfig::digest_interval`) or on
+/// demand with `network.generate_digests()`.
+pub struct DigestGenerator {
+    /// Enabled realms, mapped to the end of their last digest period.
+    realms: DashMap<RealmId, DateTime<Utc>>,
+    classifier: RwLock<Arc<dyn DigestClassifier>>,
+    template: RwLock<Arc<dyn DigestTemplate>>,
+    post_to_home: AtomicBool,
+    event_tx: broadcast::Sender<GlobalEvent>,
+}
+
+impl DigestGenerator {
+    /// Create a generator with the default classifier and template.
+    pub fn new() -> Self {
+        let (event_tx, _) = broadcast::channel(64);
+        Self {
+            realms: DashMap::new(),
+            classifier: RwLock::new(Arc::new(DefaultDigestClassifier)),
+            template: RwLock::new(Arc::new(DefaultDigestTemplate)),
+            post_to_home: AtomicBool::new(false),
+            event_tx,
+        }
+    }
+
+    /// Start digesting a realm. The first digest covers activity from now on.
+    pub fn enable(&self, realm_id: RealmId) {
+        self.realms.entry(realm_id).or_insert_with(Utc::now);
+    }
+
+    /// Stop digesting a realm.
+    pub fn disable(&self, realm_id: &RealmId) {
+        self.realms.remove(realm_id);
+    }
+
+    /// Whether a realm is digested.
+    pub fn is_enabled(&self, realm_id: &RealmId) -> bool {
+        self.realms.contains_key(realm_id)
+    }
+
+    /// All digested realms.
+    pub fn enabled_realms(&self) -> Vec<RealmId> {
+        self.realms.iter().map(|r| *r.key()).collect()
+    }
+
+    /// Replace the classifier used to count messages.
+    pub fn set_classifier(&self, classifier: impl DigestClassifier + 'static) {
+        *self.classifier.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(classifier);
+    }
+
+    /// Replace the template used for home realm summaries.
+    pub fn set_template(&self, template: impl DigestTemplate + 'static) {
+        *self.template.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(template);
+    }
+
+    /// Also post rendered digests into the home realm (default off).
+    pub fn set_post_to_home(&self, enabled: bool) {
+        self.post_to_home.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Whether rendered digests are posted into the home realm.
+    pub fn posts_to_home(&self) -> bool {
+        self.post_to_home.load(Ordering::Relaxed)
+    }
+
+    /// Subscribe to digest events.
+    ///
+    /// Use [`RealmDigest::from_global_event`] to decode them.
+    pub fn subscribe(&self) -> broadcast::Receiver<GlobalEvent> {
+        self.event_tx.subscribe()
+    }
+
+    /// Start of the next digest period for a realm.
+    pub fn period_start(&self, realm_id: &RealmId) -> Option<DateTime<Utc>> {
+        self.realms.get(realm_id).map(|r| *r.value())
+    }
+
+    /// Summarize a snapshot from the realm's last period end up to `until`,
+    /// and advance the period. Returns `None` if the realm is not enabled.
+    pub(crate) fn advance(
+        &self,
+        snapshot: &RealmSnapshot,
+        realm_name: Option<String>,
+        until: DateTime<Utc>,
+    ) -> Option<RealmDigest> {
+        let mut since = self.realms.get_mut(&snapshot.realm_id())?;
+        let classifier = Arc::clone(&self.classifier.read().unwrap_or_else(|e| e.into_inner()));
+        let digest =
+            RealmDigest::from_snapshot(snapshot, realm_name, *since, until, classifier.as_ref());
+        *since = until;
+        Some(digest)
+    }
+
+    /// Render a digest with the current template.
+    pub(crate) fn render(&self, digest: &RealmDigest) -> Option<String> {
+        let template = Arc::clone(&self.template.read().unwrap_or_else(|e| e.into_inner()));
+        template.render(digest)
+    }
+
+    /// Publish a digest event to subscribers.
+    pub(crate) fn publish(&self, event: GlobalEvent) {
+        let _ = self.event_tx.send(event);
+    }
+}
+
+impl Default for DigestGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for DigestGenerator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DigestGenerator")
+            .field("realms", &self.realms.len())
+            .field("post_to_home", &self.posts_to_home())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::member::Member;
+    use crate::message::MessageId;
+    use chrono::Duration;
+    use indras_core::{EventId, InterfaceId};
+
+    fn identity() -> IrohIdentity {
+        IrohIdentity::new(iroh::SecretKey::generate(&mut rand::rng()).public())
+    }
+
+    fn realm() -> RealmId {
+        InterfaceId::new([3u8; 32])
+    }
+
+    fn message(sender: IrohIdentity, seq: u64, content: Content, at: DateTime<Utc>) -> Message {
+        Message::new(
+            MessageId::new(realm(), EventId::from_peer(&sender, seq)),
+            Member::new(sender),
+            content,
+            at,
+        )
+    }
+
+    #[test]
+    fn test_summarize_counts_within_period() {
+        let (a, b) = (identity(), identity());
+        let start = Utc::now();
+        let end = start + Duration::hours(24);
+        let messages = vec![
+            message(a, 1, Content::Text("before".into()), start - Duration::minutes(1)),
+            message(a, 2, Content::Text("one".into()), start + Duration::minutes(1)),
+            message(b, 3, Content::Text("two".into()), start + Duration::hours(2)),
+            message(b, 4, Content::System("joined".into()), start + Duration::hours(3)),
+            message(a, 5, Content::Text("after".into()), end + Duration::minutes(1)),
+        ];
+
+        let digest = RealmDigest::summarize(
+            realm(),
+            Some("Garden".into()),
+            &messages,
+            start,
+            end,
+            &DefaultDigestClassifier,
+        );
+        assert_eq!(digest.new_messages, 2);
+        assert_eq!(digest.active_members, 2);
+        assert_eq!(digest.new_artifacts, 0);
+        assert!(!digest.is_empty());
+    }
+
+    #[test]
+    fn test_custom_classifier_counts_quests() {
+        struct Quests;
+        impl DigestClassifier for Quests {
+            fn classify(&self, content: &Content) -> Option<DigestItem> {
+                match content {
+                    Content::Extension { type_id, .. } if type_id == "quest" => {
+                        Some(DigestItem::QuestCompleted)
+                    }
+                    other => DefaultDigestClassifier.classify(other),
+                }
+            }
+        }
+
+        let a = identity();
+        let start = Utc::now();
+        let ext = Content::Extension {
+            type_id: "quest".into(),
+            payload: vec![],
+        };
+        let messages = vec![message(a, 1, ext, start + Duration::seconds(1))];
+        let digest = RealmDigest::summarize(
+            realm(),
+            None,
+            &messages,
+            start,
+            start + Duration::hours(1),
+            &Quests,
+        );
+        assert_eq!(digest.completed_quests, 1);
+        assert_eq!(digest.new_messages, 0);
+    }
+
+    #[test]
+    fn test_default_template() {
+        let now = Utc::now();
+        let mut digest = RealmDigest::summarize(
+            realm(),
+            None,
+            std::iter::empty(),
+            now,
+            now,
+            &DefaultDigestClassifier,
+        );
+        assert_eq!(DefaultDigestTemplate.render(&digest), None);
+
+        digest.realm_name = Some("Garden".into());
+        digest.new_messages = 12;
+        digest.completed_quests = 1;
+        assert_eq!(
+            DefaultDigestTemplate.render(&digest).as_deref(),
+            Some("Garden: 12 new messages, 1 completed quest")
+        );
+    }
+
+    #[test]
+    fn test_global_event_roundtrip() {
+        let now = Utc::now();
+        let mut digest = RealmDigest::summarize(
+            realm(),
+            None,
+            std::iter::empty(),
+            now,
+            now,
+            &DefaultDigestClassifier,
+        );
+        digest.new_artifacts = 3;
+
+        let event = digest.to_global_event(identity());
+        assert_eq!(event.realm_id, realm());
+        assert_eq!(RealmDigest::from_global_event(&event), Some(digest));
+    }
+
+    #[test]
+    fn test_generator_enable_disable() {
+        let generator = DigestGenerator::new();
+        assert!(!generator.is_enabled(&realm()));
+        generator.enable(realm());
+        assert!(generator.is_enabled(&realm()));
+        assert!(generator.period_start(&realm()).is_some());
+        generator.disable(&realm());
+        assert!(generator.enabled_realms().is_empty());
+    }
+}
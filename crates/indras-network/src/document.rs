@@ -3,9 +3,11 @@
 //! Documents provide type-safe access to Automerge-backed data structures
 //! that automatically synchronize across all realm members.
 
+use crate::document_budget;
 use crate::error::{IndraError, Result};
 use crate::member::Member;
 use crate::network::RealmId;
+use crate::tombstone::TombstoneCompaction;
 
 use futures::Stream;
 use indras_core::{InterfaceEvent, PeerIdentity};
@@ -44,6 +46,55 @@ struct DocumentDelta {
 /// Magic byte used to identify a `DocumentDelta` on the wire.
 const DELTA_MAGIC: u8 = 0xDD;
 
+/// Apply one event payload to a document state if it targets `name`.
+///
+/// Understands both the compact delta and the full-state envelope formats.
+/// Returns `true` if the payload belonged to this document and was applied.
+pub(crate) fn apply_event_content<T: DocumentSchema>(state: &mut T, name: &str, content: &[u8]) -> bool {
+    if let Ok(delta) = postcard::from_bytes::<DocumentDelta>(content) {
+        if delta.magic == DELTA_MAGIC && delta.doc_name == name {
+            return state.apply_delta(&delta.delta);
+        }
+    }
+    if let Ok(envelope) = postcard::from_bytes::<DocumentEnvelope>(content) {
+        if envelope.doc_name != name {
+            return false;
+        }
+        if let Ok(remote_state) = postcard::from_bytes::<T>(&envelope.payload) {
+            state.merge(remote_state);
+            return true;
+        }
+    }
+    false
+}
+
+/// Name of the document an event payload targets, if it is document traffic.
+///
+/// Recognizes both the compact delta and the full-state envelope formats.
+/// Empty names are rejected, since short non-document payloads can decode
+/// as an envelope with an empty name.
+pub(crate) fn event_document_name(content: &[u8]) -> Option<String> {
+    if let Ok(delta) = postcard::from_bytes::<DocumentDelta>(content) {
+        if delta.magic == DELTA_MAGIC && !delta.doc_name.is_empty() {
+            return Some(delta.doc_name);
+        }
+    }
+    postcard::from_bytes::<DocumentEnvelope>(content)
+        .ok()
+        .map(|envelope| envelope.doc_name)
+        .filter(|name| !name.is_empty())
+}
+
+/// Encode a full-state envelope for a document (used by tests).
+#[cfg(test)]
+pub(crate) fn encode_envelope<T: DocumentSchema>(name: &str, state: &T) -> Vec<u8> {
+    let envelope = DocumentEnvelope {
+        doc_name: name.to_string(),
+        payload: postcard::to_allocvec(state).unwrap(),
+    };
+    postcard::to_allocvec(&envelope).unwrap()
+}
+
 /// Trait for document schemas that can be stored in a `Document<T>`.
 ///
 /// Provides a `merge` method for reconciling local and remote state.
@@ -88,6 +139,16 @@ pub trait DocumentSchema: Default + Clone + Serialize + DeserializeOwned + Send
     fn apply_delta(&mut self, _delta: &[u8]) -> bool {
         false
     }
+
+    /// Tag recorded for this type in the realm's document registry.
+    ///
+    /// Default: the type's name without module path or generics, e.g.
+    /// `"QuestLog"`. Override to keep the tag stable across renames.
+    fn type_tag() -> &'static str {
+        let full = std::any::type_name::<Self>();
+        let base = full.split('<').next().unwrap_or(full);
+        base.rsplit("::").next().unwrap_or(base)
+    }
 }
 
 /// Convenience macro to implement `DocumentSchema` with default merge (replacement).
@@ -329,6 +390,7 @@ impl<T: DocumentSchema> Document<T> {
                         let merged = state.read().await.clone();
                         if let Ok(data) = postcard::to_allocvec(&merged) {
                             let _ = node.storage().interface_store().set_document_data(&storage_key, &data);
+                            document_budget::budgets().record(realm_id, &name, data.len());
                         }
                         let _ = change_tx.send(DocumentChange {
                             new_state: merged,
@@ -390,6 +452,7 @@ impl<T: DocumentSchema> Document<T> {
                                         };
                                         if let Ok(data) = postcard::to_allocvec(&merged) {
                                             let _ = node.storage().interface_store().set_document_data(&storage_key, &data);
+                                            document_budget::budgets().record(realm_id, &name, data.len());
                                         }
                                         let _ = change_tx.send(DocumentChange {
                                             new_state: merged,
@@ -413,6 +476,7 @@ impl<T: DocumentSchema> Document<T> {
                                         };
                                         if let Ok(data) = postcard::to_allocvec(&merged) {
                                             let _ = node.storage().interface_store().set_document_data(&storage_key, &data);
+                                            document_budget::budgets().record(realm_id, &name, data.len());
                                         }
                                         let _ = change_tx.send(DocumentChange {
                                             new_state: merged,
@@ -432,6 +496,7 @@ impl<T: DocumentSchema> Document<T> {
                                     };
                                     if let Ok(data) = postcard::to_allocvec(&merged) {
                                         let _ = node.storage().interface_store().set_document_data(&storage_key, &data);
+                                        document_budget::budgets().record(realm_id, &name, data.len());
                                     }
                                     let _ = change_tx.send(DocumentChange {
                                         new_state: merged,
@@ -569,6 +634,7 @@ impl<T: DocumentSchema> Document<T> {
             .storage()
             .interface_store()
             .set_document_data(&key, &data)?;
+        document_budget::budgets().record(self.realm_id, &self.name, data.len());
         Ok(())
     }
 
@@ -713,6 +779,7 @@ impl<T: DocumentSchema> Document<T> {
     where
         F: FnOnce(&mut T),
     {
+        self.ensure_writable()?;
         let realm_short: String = self.realm_id.as_bytes().iter().take(8).map(|b| format!("{:02x}", b)).collect();
 
         let (new_state, message) = {
@@ -786,6 +853,7 @@ impl<T: DocumentSchema> Document<T> {
     where
         F: FnOnce(&mut T) -> std::result::Result<R, IndraError>,
     {
+        self.ensure_writable()?;
         let realm_short: String = self
             .realm_id
             .as_bytes()
@@ -872,6 +940,7 @@ impl<T: DocumentSchema> Document<T> {
     where
         F: FnOnce(&mut T) -> R,
     {
+        self.ensure_writable()?;
         let (result, new_state, message) = {
             let mut state = self.state.write().await;
             let old = state.clone();
@@ -920,6 +989,16 @@ impl<T: DocumentSchema> Document<T> {
         Ok(result)
     }
 
+    /// Fail if the realm is archived; archived realms reject local writes.
+    fn ensure_writable(&self) -> Result<()> {
+        if self.node.is_archived(&self.realm_id) {
+            return Err(IndraError::RealmArchived {
+                id: hex::encode(self.realm_id.as_bytes()),
+            });
+        }
+        Ok(())
+    }
+
     /// Subscribe to document changes.
     ///
     /// Returns a stream that yields `DocumentChange` events whenever
@@ -955,6 +1034,44 @@ impl<T: DocumentSchema> Document<T> {
     }
 }
 
+impl<T: TombstoneCompaction> Document<T> {
+    /// Drop tombstones deleted before `horizon_millis`.
+    ///
+    /// Compaction is local: removals cannot travel through a set-union
+    /// merge, so every member compacts its own copy against the shared
+    /// horizon. The pruned state is persisted and local subscribers are
+    /// notified, but nothing is sent to the network.
+    ///
+    /// Returns the number of tombstones removed.
+    pub async fn compact_tombstones(&self, horizon_millis: u64) -> Result<usize> {
+        self.compact_with(|state| state.compact_tombstones(horizon_millis)).await
+    }
+
+    /// Run a local compaction pass that reports how many entries it removed.
+    pub(crate) async fn compact_with<F>(&self, compact: F) -> Result<usize>
+    where
+        F: FnOnce(&mut T) -> usize,
+    {
+        let (removed, new_state) = {
+            let mut state = self.state.write().await;
+            let removed = compact(&mut *state);
+            (removed, state.clone())
+        };
+
+        if removed > 0 {
+            self.persist(&new_state).await?;
+            debug!(doc_name = %self.name, removed, "Compacted document tombstones");
+            let _ = self.change_tx.send(DocumentChange {
+                new_state,
+                author: None,
+                is_remote: false,
+            });
+        }
+
+        Ok(removed)
+    }
+}
+
 impl<T: DocumentSchema> Clone for Document<T> {
     fn clone(&self) -> Self {
         Self {
@@ -0,0 +1,389 @@
+//! Size and change-count budgets for documents.
+//!
+//! Every document write goes out as a full-state envelope or a delta and
+//! is replayed by peers catching up, so a document that keeps growing
+//! (years of chat history in one document, a task board nobody prunes)
+//! slowly makes every sync slower without failing anything.
+//!
+//! [`DocumentBudgets`] tracks the persisted size of each document and how
+//! many times it changed since this process started (or since it was last
+//! split). When a document passes its [`DocumentBudget`], a warning is
+//! logged and a [`BudgetAlert`] is broadcast once, so apps can tell the
+//! user or split the document with
+//! [`Realm::split_document`](crate::Realm::split_document), which moves a
+//! part of it (e.g. old chat history) into an archive document linked from
+//! the realm's document registry.
+//!
+//! Budgets are process-wide and reached through
+//! `network.document_budgets()`; stats are kept per realm and document.
+
+use crate::network::RealmId;
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use tokio::sync::broadcast;
+
+/// Default size past which a document is over budget (1 MiB).
+pub const DEFAULT_MAX_DOCUMENT_BYTES: usize = 1024 * 1024;
+
+/// Default number of changes past which a document is over budget.
+pub const DEFAULT_MAX_DOCUMENT_CHANGES: u64 = 10_000;
+
+static BUDGETS: LazyLock<DocumentBudgets> = LazyLock::new(DocumentBudgets::new);
+
+/// The process-wide document budgets.
+pub fn budgets() -> &'static DocumentBudgets {
+    &BUDGETS
+}
+
+/// Thresholds past which a document is reported as too large.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DocumentBudget {
+    /// Persisted size in bytes.
+    pub max_bytes: usize,
+    /// Changes since tracking started or the last split.
+    pub max_changes: u64,
+}
+
+impl Default for DocumentBudget {
+    fn default() -> Self {
+        Self {
+            max_bytes: DEFAULT_MAX_DOCUMENT_BYTES,
+            max_changes: DEFAULT_MAX_DOCUMENT_CHANGES,
+        }
+    }
+}
+
+/// Size and activity of one document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentStats {
+    /// Realm the document belongs to.
+    pub realm_id: RealmId,
+    /// Document name within the realm.
+    pub name: String,
+    /// Persisted size after the latest change, in bytes.
+    pub bytes: usize,
+    /// Changes since tracking started or the last split.
+    pub changes: u64,
+}
+
+/// Which budget a document went over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetExceeded {
+    /// The document is larger than `max_bytes`.
+    Bytes {
+        /// Current size.
+        bytes: usize,
+        /// The budget.
+        limit: usize,
+    },
+    /// The document changed more than `max_changes` times.
+    Changes {
+        /// Changes counted.
+        changes: u64,
+        /// The budget.
+        limit: u64,
+    },
+}
+
+/// A document went over its budget.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BudgetAlert {
+    /// Realm the document belongs to.
+    pub realm_id: RealmId,
+    /// Document name within the realm.
+    pub name: String,
+    /// What was exceeded.
+    pub exceeded: BudgetExceeded,
+}
+
+impl BudgetAlert {
+    /// A short suggestion for what to do about the alert.
+    pub fn guidance(&self) -> String {
+        match self.exceeded {
+            BudgetExceeded::Bytes { bytes, limit } => format!(
+                "document '{}' is {} KiB (budget {} KiB); move old entries into an archive document with Realm::split_document",
+                self.name,
+                bytes / 1024,
+                limit / 1024
+            ),
+            BudgetExceeded::Changes { changes, limit } => format!(
+                "document '{}' changed {changes} times (budget {limit}); consider splitting rarely edited parts into an archive document",
+                self.name
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Tracked {
+    bytes: usize,
+    changes: u64,
+    bytes_alerted: bool,
+    changes_alerted: bool,
+}
+
+#[derive(Default)]
+struct Inner {
+    default_budget: DocumentBudget,
+    /// Budgets for particular document names, e.g. a larger one for chat.
+    overrides: HashMap<String, DocumentBudget>,
+    documents: HashMap<(RealmId, String), Tracked>,
+}
+
+/// Tracks document sizes and change counts and alerts on budget overruns.
+pub struct DocumentBudgets {
+    inner: Mutex<Inner>,
+    alert_tx: broadcast::Sender<BudgetAlert>,
+}
+
+impl DocumentBudgets {
+    /// Create a tracker with default budgets.
+    pub fn new() -> Self {
+        let (alert_tx, _) = broadcast::channel(64);
+        Self {
+            inner: Mutex::new(Inner::default()),
+            alert_tx,
+        }
+    }
+
+    /// The budget for documents without an override.
+    pub fn default_budget(&self) -> DocumentBudget {
+        self.inner
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .default_budget
+    }
+
+    /// Set the budget for documents without an override.
+    pub fn set_default_budget(&self, budget: DocumentBudget) {
+        self.inner
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .default_budget = budget;
+    }
+
+    /// Set the budget for documents with this name, in every realm.
+    pub fn set_budget(&self, name: impl Into<String>, budget: DocumentBudget) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.overrides.insert(name.into(), budget);
+    }
+
+    /// The budget that applies to documents with this name.
+    pub fn budget(&self, name: &str) -> DocumentBudget {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner
+            .overrides
+            .get(name)
+            .copied()
+            .unwrap_or(inner.default_budget)
+    }
+
+    /// Subscribe to budget alerts.
+    ///
+    /// Each budget alerts once when crossed; a size alert re-arms when the
+    /// document shrinks back under budget, a change alert when it is split.
+    pub fn subscribe(&self) -> broadcast::Receiver<BudgetAlert> {
+        self.alert_tx.subscribe()
+    }
+
+    /// Stats for one document, if it changed since tracking started.
+    pub fn stats(&self, realm_id: &RealmId, name: &str) -> Option<DocumentStats> {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner
+            .documents
+            .get(&(*realm_id, name.to_string()))
+            .map(|tracked| DocumentStats {
+                realm_id: *realm_id,
+                name: name.to_string(),
+                bytes: tracked.bytes,
+                changes: tracked.changes,
+            })
+    }
+
+    /// Stats for every tracked document in a realm, largest first.
+    pub fn realm_stats(&self, realm_id: &RealmId) -> Vec<DocumentStats> {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let mut stats: Vec<DocumentStats> = inner
+            .documents
+            .iter()
+            .filter(|((realm, _), _)| realm == realm_id)
+            .map(|((realm, name), tracked)| DocumentStats {
+                realm_id: *realm,
+                name: name.clone(),
+                bytes: tracked.bytes,
+                changes: tracked.changes,
+            })
+            .collect();
+        stats.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+        stats
+    }
+
+    /// Record that a document was persisted at `bytes` bytes.
+    ///
+    /// Returns the alerts raised by this change, which are also logged and
+    /// broadcast.
+    pub fn record(&self, realm_id: RealmId, name: &str, bytes: usize) -> Vec<BudgetAlert> {
+        let mut alerts = Vec::new();
+        {
+            let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+            let budget = inner
+                .overrides
+                .get(name)
+                .copied()
+                .unwrap_or(inner.default_budget);
+            let tracked = inner
+                .documents
+                .entry((realm_id, name.to_string()))
+                .or_default();
+            tracked.bytes = bytes;
+            tracked.changes += 1;
+
+            if bytes > budget.max_bytes {
+                if !tracked.bytes_alerted {
+                    tracked.bytes_alerted = true;
+                    alerts.push(BudgetExceeded::Bytes {
+                        bytes,
+                        limit: budget.max_bytes,
+                    });
+                }
+            } else {
+                tracked.bytes_alerted = false;
+            }
+            if tracked.changes > budget.max_changes && !tracked.changes_alerted {
+                tracked.changes_alerted = true;
+                alerts.push(BudgetExceeded::Changes {
+                    changes: tracked.changes,
+                    limit: budget.max_changes,
+                });
+            }
+        }
+
+        alerts
+            .into_iter()
+            .map(|exceeded| {
+                let alert = BudgetAlert {
+                    realm_id,
+                    name: name.to_string(),
+                    exceeded,
+                };
+                tracing::warn!(
+                    realm = %hex::encode(&realm_id.as_bytes()[..8]),
+                    doc_name = name,
+                    "{}",
+                    alert.guidance()
+                );
+                let _ = self.alert_tx.send(alert.clone());
+                alert
+            })
+            .collect()
+    }
+
+    /// Restart a document's change count, e.g. after it was split.
+    pub fn reset_changes(&self, realm_id: &RealmId, name: &str) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(tracked) = inner.documents.get_mut(&(*realm_id, name.to_string())) {
+            tracked.changes = 0;
+            tracked.changes_alerted = false;
+        }
+    }
+}
+
+impl Default for DocumentBudgets {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Outcome of [`Realm::split_document`](crate::Realm::split_document).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentSplit {
+    /// Name of the archive document the part was moved into.
+    pub archive: String,
+    /// Size of the live document before the split, in bytes.
+    pub bytes_before: usize,
+    /// Size of the live document after the split, in bytes.
+    pub bytes_after: usize,
+    /// Size of the archive document after the split, in bytes.
+    pub archive_bytes: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn realm(n: u8) -> RealmId {
+        RealmId::new([n; 32])
+    }
+
+    #[test]
+    fn test_size_alert_fires_once_and_rearms() {
+        let budgets = DocumentBudgets::new();
+        budgets.set_default_budget(DocumentBudget {
+            max_bytes: 100,
+            max_changes: 1_000,
+        });
+        let mut rx = budgets.subscribe();
+
+        assert!(budgets.record(realm(1), "chat", 50).is_empty());
+        let alerts = budgets.record(realm(1), "chat", 150);
+        assert_eq!(
+            alerts[0].exceeded,
+            BudgetExceeded::Bytes {
+                bytes: 150,
+                limit: 100
+            }
+        );
+        assert_eq!(rx.try_recv().unwrap(), alerts[0]);
+        assert!(budgets.record(realm(1), "chat", 160).is_empty());
+
+        // Shrinking under budget re-arms the alert
+        assert!(budgets.record(realm(1), "chat", 40).is_empty());
+        assert_eq!(budgets.record(realm(1), "chat", 120).len(), 1);
+
+        // Other realms are tracked separately
+        assert_eq!(budgets.stats(&realm(1), "chat").unwrap().changes, 5);
+        assert!(budgets.stats(&realm(2), "chat").is_none());
+    }
+
+    #[test]
+    fn test_change_budget_and_overrides() {
+        let budgets = DocumentBudgets::new();
+        budgets.set_default_budget(DocumentBudget {
+            max_bytes: 1_000,
+            max_changes: 2,
+        });
+        budgets.set_budget(
+            "chat",
+            DocumentBudget {
+                max_bytes: 1_000,
+                max_changes: 10,
+            },
+        );
+
+        for _ in 0..3 {
+            budgets.record(realm(1), "chat", 10);
+        }
+        budgets.record(realm(1), "tasks", 10);
+        budgets.record(realm(1), "tasks", 10);
+        let alerts = budgets.record(realm(1), "tasks", 20);
+        assert_eq!(
+            alerts,
+            vec![BudgetAlert {
+                realm_id: realm(1),
+                name: "tasks".to_string(),
+                exceeded: BudgetExceeded::Changes {
+                    changes: 3,
+                    limit: 2
+                },
+            }]
+        );
+
+        budgets.reset_changes(&realm(1), "tasks");
+        assert_eq!(budgets.stats(&realm(1), "tasks").unwrap().changes, 0);
+
+        let stats = budgets.realm_stats(&realm(1));
+        assert_eq!(stats[0].name, "tasks");
+        assert_eq!(stats[1].name, "chat");
+    }
+}
@@ -1,11 +1,12 @@
 //! Document registry for tracking named documents in a realm.
 //!
 //! This module provides a CRDT document that tracks the names
-//! of all documents that have been created in a realm, enabling
-//! `Realm::documents()` to list them.
+//! of all documents that have been created in a realm, and the type
+//! each was first opened as, enabling `Realm::documents()` to list them
+//! and `Realm::document_events()` to report creations and removals.
 
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 /// CRDT document for tracking document names within a realm.
 ///
@@ -28,6 +29,60 @@ pub struct DocumentRegistryDocument {
     ///
     /// Uses BTreeSet for deterministic ordering across peers.
     pub names: BTreeSet<String>,
+    /// Type tag per document name (see `DocumentSchema::type_tag`).
+    ///
+    /// Names registered before types were tracked have no entry.
+    #[serde(default)]
+    pub types: BTreeMap<String, String>,
+    /// Archive documents split off each document, by document name.
+    #[serde(default)]
+    pub archives: BTreeMap<String, BTreeSet<String>>,
+}
+
+/// A registered document and the type it was created as.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentEntry {
+    /// Document name within the realm.
+    pub name: String,
+    /// Type tag, if known.
+    pub type_tag: Option<String>,
+}
+
+/// A document appearing in or leaving a realm's registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DocumentEvent {
+    /// A document was opened for the first time, here or by a peer.
+    Created {
+        /// The new document.
+        entry: DocumentEntry,
+    },
+    /// A document was removed from the registry.
+    Removed {
+        /// Name of the removed document.
+        name: String,
+    },
+}
+
+impl DocumentEvent {
+    /// Document events between two states of a registry.
+    pub fn diff(
+        old: &DocumentRegistryDocument,
+        new: &DocumentRegistryDocument,
+    ) -> Vec<DocumentEvent> {
+        let mut events: Vec<DocumentEvent> = new
+            .names
+            .difference(&old.names)
+            .map(|name| DocumentEvent::Created {
+                entry: new.entry(name),
+            })
+            .collect();
+        events.extend(
+            old.names
+                .difference(&new.names)
+                .map(|name| DocumentEvent::Removed { name: name.clone() }),
+        );
+        events
+    }
 }
 
 impl DocumentRegistryDocument {
@@ -43,13 +98,50 @@ impl DocumentRegistryDocument {
         self.names.insert(name.into())
     }
 
+    /// Register a document name with its type tag.
+    ///
+    /// The first known tag is kept, so a document opened under different
+    /// types (e.g. a view onto part of it) stays listed as its original
+    /// type. Returns true if the name was newly inserted.
+    pub fn register_typed(&mut self, name: impl Into<String>, type_tag: &str) -> bool {
+        let name = name.into();
+        self.types
+            .entry(name.clone())
+            .or_insert_with(|| type_tag.to_string());
+        self.names.insert(name)
+    }
+
     /// Remove a document name from the registry.
     ///
     /// Returns true if the name was present.
     pub fn unregister(&mut self, name: &str) -> bool {
+        self.types.remove(name);
         self.names.remove(name)
     }
 
+    /// Link an archive document to the document it was split from.
+    ///
+    /// Returns true if the link is new.
+    pub fn link_archive(&mut self, name: &str, archive: impl Into<String>) -> bool {
+        self.archives
+            .entry(name.to_string())
+            .or_default()
+            .insert(archive.into())
+    }
+
+    /// Archive documents split off a document, sorted by name.
+    pub fn archives_of(&self, name: &str) -> Vec<&str> {
+        self.archives
+            .get(name)
+            .map(|archives| archives.iter().map(|s| s.as_str()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Type tag of a registered document, if known.
+    pub fn type_of(&self, name: &str) -> Option<&str> {
+        self.types.get(name).map(String::as_str)
+    }
+
     /// Check if a document name is registered.
     pub fn contains(&self, name: &str) -> bool {
         self.names.contains(name)
@@ -60,6 +152,27 @@ impl DocumentRegistryDocument {
         self.names.iter().map(|s| s.as_str()).collect()
     }
 
+    /// Get all registered documents with their type tags, sorted by name.
+    pub fn entries(&self) -> Vec<DocumentEntry> {
+        self.names.iter().map(|name| self.entry(name)).collect()
+    }
+
+    /// Names of the documents registered with this type tag.
+    pub fn names_of_type(&self, type_tag: &str) -> Vec<&str> {
+        self.names
+            .iter()
+            .filter(|name| self.type_of(name) == Some(type_tag))
+            .map(|s| s.as_str())
+            .collect()
+    }
+
+    fn entry(&self, name: &str) -> DocumentEntry {
+        DocumentEntry {
+            name: name.to_string(),
+            type_tag: self.type_of(name).map(String::from),
+        }
+    }
+
     /// Get the number of registered documents.
     pub fn count(&self) -> usize {
         self.names.len()
@@ -106,4 +219,64 @@ mod tests {
         let names = registry.document_names();
         assert_eq!(names, vec!["alpha", "middle", "zebra"]);
     }
+
+    #[test]
+    fn test_first_type_tag_is_kept() {
+        let mut registry = DocumentRegistryDocument::new();
+
+        registry.register("legacy");
+        assert!(registry.register_typed("quests", "QuestDocument"));
+        assert!(!registry.register_typed("quests", "QuestSummary"));
+        assert!(!registry.register_typed("legacy", "NoteDocument"));
+
+        assert_eq!(registry.type_of("quests"), Some("QuestDocument"));
+        assert_eq!(registry.type_of("legacy"), Some("NoteDocument"));
+        assert_eq!(registry.names_of_type("QuestDocument"), vec!["quests"]);
+
+        registry.unregister("quests");
+        assert_eq!(registry.type_of("quests"), None);
+    }
+
+    #[test]
+    fn test_archive_links() {
+        let mut registry = DocumentRegistryDocument::new();
+        registry.register_typed("chat", "RealmChatDocument");
+
+        assert!(registry.link_archive("chat", "chat-archive-2025"));
+        assert!(registry.link_archive("chat", "chat-archive-2024"));
+        assert!(!registry.link_archive("chat", "chat-archive-2025"));
+
+        assert_eq!(
+            registry.archives_of("chat"),
+            vec!["chat-archive-2024", "chat-archive-2025"]
+        );
+        assert!(registry.archives_of("notes").is_empty());
+    }
+
+    #[test]
+    fn test_diff_reports_created_and_removed() {
+        let mut old = DocumentRegistryDocument::new();
+        old.register_typed("notes", "NoteDocument");
+        old.register_typed("quests", "QuestDocument");
+
+        let mut new = old.clone();
+        new.unregister("notes");
+        new.register_typed("polls", "PollDocument");
+
+        assert_eq!(
+            DocumentEvent::diff(&old, &new),
+            vec![
+                DocumentEvent::Created {
+                    entry: DocumentEntry {
+                        name: "polls".to_string(),
+                        type_tag: Some("PollDocument".to_string()),
+                    },
+                },
+                DocumentEvent::Removed {
+                    name: "notes".to_string(),
+                },
+            ]
+        );
+        assert!(DocumentEvent::diff(&new, &new).is_empty());
+    }
 }
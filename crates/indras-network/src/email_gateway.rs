@@ -0,0 +1,817 @@
+//! Email bridge for realm notifications and replies.
+//!
+//! A node running headless (e.g. a community server) can relay realm
+//! activity to members who prefer email. For each configured
+//! [`EmailSubscriber`], the [`EmailGateway`] sends:
+//!
+//! - notification emails for new messages in their realms (all messages,
+//!   or only those mentioning them), and
+//! - digest emails when the network generates a [`RealmDigest`] for one of
+//!   their realms.
+//!
+//! Every email carries a per-realm, per-member reply address of the form
+//! `reply+<token>@<reply_domain>`. The token holds short prefixes of the
+//! realm and member IDs and a MAC keyed from the node's secret, so it
+//! cannot be forged or moved to another realm. When the member replies,
+//! [`EmailGateway::receive`] checks the token and the sender address,
+//! strips quoted text and signatures, and asks the member's own node to
+//! post the reply under a delegation token the member issued to the
+//! gateway ([`Capability::PostMessages`]), so the message is sent and
+//! signed by the member, not the gateway. The member's node must be
+//! online, and tokens lapse when it restarts.
+//!
+//! Outbound mail goes through a [`MailTransport`]; [`SendmailTransport`]
+//! hands it to the local MTA's `sendmail`. Inbound mail is read from a
+//! Maildir the MTA delivers the reply domain to
+//! ([`EmailGateway::receive_maildir`]). The `indras-email` binary runs
+//! both against a node's data directory and a JSON config file.
+//!
+//! # Example
+//!
+//! ```ignore
+//! // On the member's node, once per realm:
+//! let token = network.node()
+//!     .issue_delegation(gateway_peer, &realm_id, [Capability::PostMessages], ttl)
+//!     .await?;
+//!
+//! // On the gateway:
+//! let gateway = EmailGateway::new(Arc::clone(&network), config, SendmailTransport::default());
+//! let task = Arc::clone(&gateway).spawn();
+//! loop {
+//!     gateway.receive_maildir(Path::new("/var/mail/indras")).await?;
+//!     tokio::time::sleep(Duration::from_secs(30)).await;
+//! }
+//! ```
+
+use crate::digest::{DefaultDigestTemplate, DigestTemplate, RealmDigest};
+use crate::error::{IndraError, Result};
+use crate::event_bus::{EventFilter, EventKind};
+use crate::member::MemberId;
+use crate::message::{Content, Message, MessagePayload};
+use crate::network::{IndrasNetwork, RealmId};
+use crate::notifications::{
+    DefaultNotificationClassifier, NotificationClassifier, NotificationContext, NotificationKind,
+};
+use crate::realm::serialize_payload;
+
+use async_trait::async_trait;
+use base64::Engine;
+use indras_node::{Capability, DelegatedAction, DelegatedRequest, DelegationToken};
+use indras_transport::IrohIdentity;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{Arc, RwLock};
+use tokio::io::AsyncWriteExt;
+use tokio::task::JoinHandle;
+
+/// Local part prefix of reply addresses.
+const REPLY_PREFIX: &str = "reply+";
+
+/// Bytes of the realm and member IDs kept in a reply token.
+const TOKEN_ID_BYTES: usize = 8;
+
+/// Bytes of MAC in a reply token.
+const TOKEN_MAC_BYTES: usize = 8;
+
+/// Key derivation context for reply token MACs.
+const TOKEN_KEY_CONTEXT: &str = "indras-network email gateway reply token v1";
+
+/// Longest reply accepted from email, in characters.
+const MAX_REPLY_CHARS: usize = 4000;
+
+/// Where [`SendmailTransport`] looks for `sendmail` by default.
+const DEFAULT_SENDMAIL: &str = "/usr/sbin/sendmail";
+
+/// An email to send.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutgoingEmail {
+    /// Sender address.
+    pub from: String,
+    /// Recipient address.
+    pub to: String,
+    /// Address replies should go to.
+    pub reply_to: String,
+    /// Subject line.
+    pub subject: String,
+    /// Plain-text body.
+    pub body: String,
+}
+
+impl OutgoingEmail {
+    /// The email as an RFC 5322 message with a UTF-8 plain-text body.
+    ///
+    /// Line breaks are stripped from header values so a subject or name
+    /// cannot inject headers; non-ASCII subjects are RFC 2047 encoded.
+    pub fn to_rfc5322(&self) -> String {
+        let subject = header_value(&self.subject);
+        let subject = if subject.is_ascii() {
+            subject
+        } else {
+            let encoded = base64::engine::general_purpose::STANDARD.encode(subject);
+            format!("=?UTF-8?B?{encoded}?=")
+        };
+        format!(
+            "From: {}\nTo: {}\nReply-To: {}\nSubject: {subject}\nDate: {}\n\
+             MIME-Version: 1.0\nContent-Type: text/plain; charset=utf-8\n\
+             Content-Transfer-Encoding: 8bit\n\n{}\n",
+            header_value(&self.from),
+            header_value(&self.to),
+            header_value(&self.reply_to),
+            chrono::Utc::now().to_rfc2822(),
+            self.body,
+        )
+    }
+}
+
+/// An email received by the app's mail handling.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InboundEmail {
+    /// Sender, as an address or `Name <address>`.
+    pub from: String,
+    /// Recipients, as addresses or `Name <address>`.
+    pub to: Vec<String>,
+    /// Plain-text body.
+    pub body: String,
+}
+
+impl InboundEmail {
+    /// Parse a raw RFC 5322 message, as delivered to a Maildir.
+    ///
+    /// Recipients are taken from `To`, `Cc` and `Delivered-To`. The body
+    /// is the message's (or its first `text/plain` part's) text, with
+    /// quoted-printable or base64 transfer encoding undone. Returns `None`
+    /// if there is no `From` header.
+    pub fn parse(raw: &str) -> Option<Self> {
+        let raw = raw.replace("\r\n", "\n");
+        let (headers, body) = split_headers(&raw);
+        let from = header(&headers, "from")?.to_string();
+        let to = ["to", "cc", "delivered-to"]
+            .iter()
+            .flat_map(|name| headers.iter().filter(move |(n, _)| n == name))
+            .flat_map(|(_, value)| value.split(','))
+            .map(|address| address.trim().to_string())
+            .filter(|address| !address.is_empty())
+            .collect();
+        Some(Self {
+            from,
+            to,
+            body: plain_text(&headers, body),
+        })
+    }
+}
+
+/// Sends email, e.g. over SMTP or a provider API.
+#[async_trait]
+pub trait MailTransport: Send + Sync {
+    /// Send one email.
+    async fn send(&self, email: &OutgoingEmail) -> Result<()>;
+}
+
+/// Sends email by piping it to the local MTA's `sendmail` (Postfix, Exim,
+/// msmtp and OpenSMTPD all provide one).
+#[derive(Debug, Clone)]
+pub struct SendmailTransport {
+    program: PathBuf,
+}
+
+impl Default for SendmailTransport {
+    fn default() -> Self {
+        Self::new(DEFAULT_SENDMAIL)
+    }
+}
+
+impl SendmailTransport {
+    /// Use the `sendmail` program at `program`.
+    pub fn new(program: impl Into<PathBuf>) -> Self {
+        Self {
+            program: program.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl MailTransport for SendmailTransport {
+    async fn send(&self, email: &OutgoingEmail) -> Result<()> {
+        let failed = |e: std::io::Error| {
+            IndraError::Email(format!("{}: {e}", self.program.display()))
+        };
+        let mut child = tokio::process::Command::new(&self.program)
+            .args(["-t", "-i"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+            .map_err(failed)?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(email.to_rfc5322().as_bytes())
+                .await
+                .map_err(failed)?;
+        }
+        let status = child.wait().await.map_err(failed)?;
+        if !status.success() {
+            return Err(IndraError::Email(format!(
+                "{} exited with {status}",
+                self.program.display()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Which messages a subscriber is emailed about.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EmailNotify {
+    /// No per-message emails.
+    Off,
+    /// Only messages that mention the member.
+    #[default]
+    Mentions,
+    /// Every message from someone else.
+    All,
+}
+
+/// A member who receives realm activity by email.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmailSubscriber {
+    /// The member.
+    pub member: MemberId,
+    /// Display name, used for mentions and for replies posted to the realm.
+    pub name: String,
+    /// Email address; replies are only accepted from it.
+    pub address: String,
+    /// Realms (hex IDs) the member is emailed about and may reply into.
+    pub realms: BTreeSet<String>,
+    /// Which messages trigger an email.
+    #[serde(default)]
+    pub notify: EmailNotify,
+    /// Whether digests are emailed.
+    #[serde(default)]
+    pub digests: bool,
+    /// Delegation tokens the member's node issued to the gateway, letting
+    /// it post their replies. Replies into realms without one are refused.
+    #[serde(default)]
+    pub delegations: Vec<DelegationToken>,
+}
+
+impl EmailSubscriber {
+    /// Whether the member follows a realm.
+    pub fn follows(&self, realm_id: &RealmId) -> bool {
+        self.realms.contains(&hex::encode(realm_id.as_bytes()))
+    }
+
+    /// The longest-lived token letting `gateway` post into a realm.
+    pub fn posting_token(
+        &self,
+        realm_id: &RealmId,
+        gateway: &IrohIdentity,
+    ) -> Option<&DelegationToken> {
+        self.delegations
+            .iter()
+            .filter(|t| t.delegate == *gateway && t.covers(realm_id, Capability::PostMessages))
+            .max_by_key(|t| t.expires_at_millis)
+    }
+}
+
+/// Gateway settings, typically loaded from the daemon's config file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmailGatewayConfig {
+    /// Address emails are sent from.
+    pub from: String,
+    /// Domain whose mail is delivered to the gateway, e.g. `"mail.example.org"`.
+    pub reply_domain: String,
+    /// Members who receive email.
+    pub subscribers: Vec<EmailSubscriber>,
+}
+
+/// Relays realm activity to members by email and posts their replies.
+pub struct EmailGateway {
+    network: Arc<IndrasNetwork>,
+    config: RwLock<EmailGatewayConfig>,
+    transport: Arc<dyn MailTransport>,
+    key: [u8; 32],
+}
+
+impl EmailGateway {
+    /// Create a gateway for a network.
+    pub fn new(
+        network: Arc<IndrasNetwork>,
+        config: EmailGatewayConfig,
+        transport: impl MailTransport + 'static,
+    ) -> Arc<Self> {
+        let key = blake3::derive_key(TOKEN_KEY_CONTEXT, &network.node().secret_key().to_bytes());
+        Arc::new(Self {
+            network,
+            config: RwLock::new(config),
+            transport: Arc::new(transport),
+            key,
+        })
+    }
+
+    /// Current settings.
+    pub fn config(&self) -> EmailGatewayConfig {
+        self.config
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Replace the settings, e.g. after the config file changed.
+    pub fn set_config(&self, config: EmailGatewayConfig) {
+        *self.config.write().unwrap_or_else(|e| e.into_inner()) = config;
+    }
+
+    /// The address a member replies to for a realm.
+    pub fn reply_address(&self, realm_id: &RealmId, member: &MemberId) -> String {
+        let domain = self
+            .config
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .reply_domain
+            .clone();
+        format!(
+            "{REPLY_PREFIX}{}@{domain}",
+            reply_token(&self.key, realm_id, member)
+        )
+    }
+
+    /// Email subscribers about a message, as their settings allow.
+    ///
+    /// Returns how many emails were sent.
+    pub async fn notify(&self, message: &Message) -> Result<usize> {
+        let realm_id = message.id.interface_id;
+        let realm_name = self.realm_name(&realm_id);
+        let Some(text) = message.content.as_text() else {
+            return Ok(0);
+        };
+
+        let mut emails = Vec::new();
+        for subscriber in self.config().subscribers {
+            if subscriber.notify == EmailNotify::Off || !subscriber.follows(&realm_id) {
+                continue;
+            }
+            let ctx = NotificationContext {
+                my_id: subscriber.member,
+                my_name: Some(subscriber.name.clone()),
+                realm_name: Some(realm_name.clone()),
+                is_dm: false,
+            };
+            let Some(kind) = DefaultNotificationClassifier.classify(message, &ctx) else {
+                continue;
+            };
+            if kind != NotificationKind::Mention && subscriber.notify == EmailNotify::Mentions {
+                continue;
+            }
+            let sender = message.sender.name();
+            let subject = if kind == NotificationKind::Mention {
+                format!("[{realm_name}] {sender} mentioned you")
+            } else {
+                format!("[{realm_name}] New message from {sender}")
+            };
+            emails.push(self.email(
+                &subscriber,
+                &realm_id,
+                subject,
+                format!("{sender} wrote in {realm_name}:\n\n{text}"),
+            ));
+        }
+        self.send_all(emails).await
+    }
+
+    /// Email a digest to the subscribers who want digests for its realm.
+    ///
+    /// Returns how many emails were sent.
+    pub async fn send_digest(&self, digest: &RealmDigest) -> Result<usize> {
+        let Some(summary) = DefaultDigestTemplate.render(digest) else {
+            return Ok(0);
+        };
+        let realm_name = digest
+            .realm_name
+            .clone()
+            .unwrap_or_else(|| self.realm_name(&digest.realm_id));
+
+        let emails: Vec<OutgoingEmail> = self
+            .config()
+            .subscribers
+            .iter()
+            .filter(|s| s.digests && s.follows(&digest.realm_id))
+            .map(|subscriber| {
+                self.email(
+                    subscriber,
+                    &digest.realm_id,
+                    format!("[{realm_name}] Digest"),
+                    format!(
+                        "{summary}\n\nSince {}.",
+                        digest.since.format("%Y-%m-%d %H:%M UTC")
+                    ),
+                )
+            })
+            .collect();
+        self.send_all(emails).await
+    }
+
+    /// Post a reply received by email into its realm, as the member.
+    ///
+    /// The email must be addressed to a valid reply address and come from
+    /// the address of the member the token was issued to. The reply is
+    /// sent to the member's node as a delegated request; that node checks
+    /// the delegation token and posts the message. Nothing is posted if
+    /// the member's node is offline or no longer holds the token.
+    pub async fn receive(&self, email: &InboundEmail) -> Result<()> {
+        let config = self.config();
+        let (subscriber, realm_id) = email
+            .to
+            .iter()
+            .find_map(|to| self.resolve(&config, &bare_address(to)))
+            .ok_or_else(|| IndraError::Email("no valid reply address".to_string()))?;
+
+        if !bare_address(&email.from).eq_ignore_ascii_case(&subscriber.address) {
+            return Err(IndraError::Email(format!(
+                "reply from {} does not match the subscriber's address",
+                bare_address(&email.from)
+            )));
+        }
+        let text = reply_text(&email.body);
+        if text.is_empty() {
+            return Err(IndraError::Email("reply has no text".to_string()));
+        }
+
+        let node = self.network.node();
+        let token = subscriber
+            .posting_token(&realm_id, node.identity())
+            .ok_or_else(|| {
+                IndraError::Email(format!("{} has not delegated posting", subscriber.name))
+            })?;
+        let owner = iroh::PublicKey::from_bytes(&subscriber.member)
+            .map_err(|e| IndraError::Email(format!("invalid member id: {e}")))?;
+        let content = serialize_payload(&MessagePayload::new(Content::Text(text)))?;
+        let request = DelegatedRequest {
+            token: token.clone(),
+            interface_id: realm_id,
+            action: DelegatedAction::PostMessage { content },
+        };
+        node.send_delegated_request(&IrohIdentity::from(owner), request)
+            .await?;
+        Ok(())
+    }
+
+    /// Post every reply delivered to a Maildir since the last call.
+    ///
+    /// Each message in `new/` is handed to [`receive`](Self::receive) and
+    /// then moved to `cur/` marked as seen, whether or not it was posted.
+    /// Returns how many replies were posted.
+    pub async fn receive_maildir(&self, maildir: &Path) -> Result<usize> {
+        let mut entries = tokio::fs::read_dir(maildir.join("new")).await?;
+        let mut posted = 0;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let raw = tokio::fs::read(&path).await?;
+            let outcome = match InboundEmail::parse(&String::from_utf8_lossy(&raw)) {
+                Some(email) => self.receive(&email).await,
+                None => Err(IndraError::Email("not an email".to_string())),
+            };
+            match outcome {
+                Ok(()) => posted += 1,
+                Err(e) => tracing::warn!(path = %path.display(), error = %e, "email reply dropped"),
+            }
+            let seen = format!("{}:2,S", entry.file_name().to_string_lossy());
+            tokio::fs::rename(&path, maildir.join("cur").join(seen)).await?;
+        }
+        Ok(posted)
+    }
+
+    /// Relay messages and digests until the network's event sources close.
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        let mut messages = self
+            .network
+            .event_bus()
+            .subscribe(EventFilter::all().kind(EventKind::Message));
+        let mut digests = self.network.digests().subscribe();
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    event = messages.recv() => {
+                        let Some(event) = event else { break };
+                        let realm_id = event.realm_id();
+                        let Some(message) =
+                            crate::realm::convert_event_to_message(event.event.event, realm_id)
+                        else {
+                            continue;
+                        };
+                        if let Err(e) = self.notify(&message).await {
+                            tracing::warn!(error = %e, "failed to email notification");
+                        }
+                    }
+                    event = digests.recv() => {
+                        let event = match event {
+                            Ok(event) => event,
+                            Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                            Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                        };
+                        let Some(digest) = RealmDigest::from_global_event(&event) else {
+                            continue;
+                        };
+                        if let Err(e) = self.send_digest(&digest).await {
+                            tracing::warn!(error = %e, "failed to email digest");
+                        }
+                    }
+                }
+            }
+            tracing::debug!("email gateway stopped");
+        })
+    }
+
+    fn realm_name(&self, realm_id: &RealmId) -> String {
+        self.network
+            .get_realm_by_id(realm_id)
+            .and_then(|r| r.name().map(String::from))
+            .unwrap_or_else(|| format!("Realm {}", &hex::encode(realm_id.as_bytes())[..8]))
+    }
+
+    fn email(
+        &self,
+        subscriber: &EmailSubscriber,
+        realm_id: &RealmId,
+        subject: String,
+        body: String,
+    ) -> OutgoingEmail {
+        let from = self
+            .config
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .from
+            .clone();
+        OutgoingEmail {
+            from,
+            to: subscriber.address.clone(),
+            reply_to: self.reply_address(realm_id, &subscriber.member),
+            subject,
+            body: format!("{body}\n\n-- \nReply to this email to post in the realm."),
+        }
+    }
+
+    async fn send_all(&self, emails: Vec<OutgoingEmail>) -> Result<usize> {
+        let mut sent = 0;
+        for email in &emails {
+            match self.transport.send(email).await {
+                Ok(()) => sent += 1,
+                Err(e) => tracing::warn!(to = %email.to, error = %e, "failed to send email"),
+            }
+        }
+        Ok(sent)
+    }
+
+    /// The subscriber and realm a reply address was issued for.
+    fn resolve(
+        &self,
+        config: &EmailGatewayConfig,
+        address: &str,
+    ) -> Option<(EmailSubscriber, RealmId)> {
+        let (local, domain) = address.rsplit_once('@')?;
+        if !domain.eq_ignore_ascii_case(&config.reply_domain) {
+            return None;
+        }
+        let token = local.strip_prefix(REPLY_PREFIX)?.to_ascii_lowercase();
+        config.subscribers.iter().find_map(|subscriber| {
+            subscriber.realms.iter().find_map(|realm_hex| {
+                let bytes: [u8; 32] = hex::decode(realm_hex).ok()?.try_into().ok()?;
+                let realm_id = RealmId::new(bytes);
+                (reply_token(&self.key, &realm_id, &subscriber.member) == token)
+                    .then(|| (subscriber.clone(), realm_id))
+            })
+        })
+    }
+}
+
+/// Hex token binding a reply address to a realm and member.
+fn reply_token(key: &[u8; 32], realm_id: &RealmId, member: &MemberId) -> String {
+    let mut hasher = blake3::Hasher::new_keyed(key);
+    hasher.update(realm_id.as_bytes());
+    hasher.update(member);
+    let mac = hasher.finalize();
+
+    let mut token = Vec::with_capacity(2 * TOKEN_ID_BYTES + TOKEN_MAC_BYTES);
+    token.extend_from_slice(&realm_id.as_bytes()[..TOKEN_ID_BYTES]);
+    token.extend_from_slice(&member[..TOKEN_ID_BYTES]);
+    token.extend_from_slice(&mac.as_bytes()[..TOKEN_MAC_BYTES]);
+    hex::encode(token)
+}
+
+/// The address in `Name <address>`, or the input trimmed.
+fn bare_address(field: &str) -> String {
+    let field = field.trim();
+    match (field.rfind('<'), field.rfind('>')) {
+        (Some(start), Some(end)) if start < end => field[start + 1..end].trim().to_string(),
+        _ => field.to_string(),
+    }
+}
+
+/// A header value on one line.
+fn header_value(value: &str) -> String {
+    value.replace(['\r', '\n'], " ")
+}
+
+/// Unfolded `(lowercase name, value)` headers and the body of a message.
+fn split_headers(raw: &str) -> (Vec<(String, String)>, &str) {
+    let (head, body) = raw.split_once("\n\n").unwrap_or((raw, ""));
+    let mut headers: Vec<(String, String)> = Vec::new();
+    for line in head.lines() {
+        if line.starts_with([' ', '\t']) {
+            if let Some((_, value)) = headers.last_mut() {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+        } else if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+        }
+    }
+    (headers, body)
+}
+
+/// The first value of a header.
+fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, value)| value.as_str())
+}
+
+/// The plain text of a message or MIME part body.
+fn plain_text(headers: &[(String, String)], body: &str) -> String {
+    let content_type = header(headers, "content-type").unwrap_or("text/plain");
+    if content_type.to_ascii_lowercase().starts_with("multipart/") {
+        let Some(boundary) = content_type.split(';').find_map(|param| {
+            let (key, value) = param.split_once('=')?;
+            key.trim()
+                .eq_ignore_ascii_case("boundary")
+                .then(|| value.trim().trim_matches('"').to_string())
+        }) else {
+            return String::new();
+        };
+        return body
+            .split(&format!("--{boundary}"))
+            .skip(1)
+            .map(|part| split_headers(part.trim_start_matches('\n')))
+            .find(|(part_headers, _)| {
+                header(part_headers, "content-type").is_none_or(|t| {
+                    let t = t.to_ascii_lowercase();
+                    t.starts_with("text/plain") || t.starts_with("multipart/")
+                })
+            })
+            .map(|(part_headers, part_body)| plain_text(&part_headers, part_body))
+            .unwrap_or_default();
+    }
+    match header(headers, "content-transfer-encoding").map(str::to_ascii_lowercase) {
+        Some(encoding) if encoding == "quoted-printable" => decode_quoted_printable(body),
+        Some(encoding) if encoding == "base64" => {
+            let compact: String = body.split_whitespace().collect();
+            base64::engine::general_purpose::STANDARD
+                .decode(compact)
+                .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+                .unwrap_or_default()
+        }
+        _ => body.to_string(),
+    }
+}
+
+/// Undo quoted-printable transfer encoding.
+fn decode_quoted_printable(body: &str) -> String {
+    let mut bytes = Vec::with_capacity(body.len());
+    for line in body.split('\n') {
+        let (line, soft_break) = match line.trim_end().strip_suffix('=') {
+            Some(line) => (line, true),
+            None => (line.trim_end(), false),
+        };
+        let mut rest = line.as_bytes();
+        while let Some((&byte, tail)) = rest.split_first() {
+            let escaped = (byte == b'=')
+                .then(|| tail.get(..2))
+                .flatten()
+                .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+            match escaped {
+                Some(decoded) => {
+                    bytes.push(decoded);
+                    rest = &tail[2..];
+                }
+                None => {
+                    bytes.push(byte);
+                    rest = tail;
+                }
+            }
+        }
+        if !soft_break {
+            bytes.push(b'\n');
+        }
+    }
+    String::from_utf8_lossy(&bytes).trim_end_matches('\n').to_string()
+}
+
+/// The new text of an email reply, without quoted history or signature.
+fn reply_text(body: &str) -> String {
+    let mut lines = Vec::new();
+    for line in body.lines() {
+        let trimmed = line.trim_end();
+        let is_attribution = trimmed.starts_with("On ") && trimmed.ends_with("wrote:");
+        if trimmed == "--" || is_attribution {
+            break;
+        }
+        if trimmed.starts_with("-----Original Message-----") {
+            break;
+        }
+        if trimmed.starts_with('>') {
+            continue;
+        }
+        lines.push(trimmed);
+    }
+    let text = lines.join("\n").trim().to_string();
+    text.chars().take(MAX_REPLY_CHARS).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reply_token_is_bound_to_realm_and_member() {
+        let key = [7u8; 32];
+        let realm = RealmId::new([1; 32]);
+        let member: MemberId = [2; 32];
+
+        let token = reply_token(&key, &realm, &member);
+        assert_eq!(token.len(), 2 * (2 * TOKEN_ID_BYTES + TOKEN_MAC_BYTES));
+        assert!(format!("{REPLY_PREFIX}{token}").len() <= 64);
+        assert_eq!(token, reply_token(&key, &realm, &member));
+
+        assert_ne!(token, reply_token(&key, &RealmId::new([3; 32]), &member));
+        assert_ne!(token, reply_token(&key, &realm, &[4; 32]));
+        assert_ne!(token, reply_token(&[8u8; 32], &realm, &member));
+    }
+
+    #[test]
+    fn test_reply_text_strips_quotes_and_signature() {
+        let body = "Sounds good, see you there!\n\
+                    \n\
+                    On Tue, 3 Jun 2025 at 10:00, Garden <notify@example.org> wrote:\n\
+                    > Ada wrote in Garden:\n\
+                    > Meet at 9?\n";
+        assert_eq!(reply_text(body), "Sounds good, see you there!");
+
+        let body = "Yes\n> quoted\nTwo lines\n-- \nGrace\nSent from my phone";
+        assert_eq!(reply_text(body), "Yes\nTwo lines");
+        assert_eq!(reply_text("> only quoted"), "");
+    }
+
+    #[test]
+    fn test_parse_inbound_email() {
+        let raw = "From: Ada <ada@example.org>\r\n\
+                   To: reply+abc@mail.example.org,\r\n \"Grace\" <grace@example.org>\r\n\
+                   Content-Type: multipart/alternative; boundary=\"b1\"\r\n\
+                   \r\n\
+                   --b1\r\n\
+                   Content-Type: text/plain; charset=utf-8\r\n\
+                   Content-Transfer-Encoding: quoted-printable\r\n\
+                   \r\n\
+                   Caf=C3=A9 at nine, a very long line that was soft-=\r\n\
+                   wrapped\r\n\
+                   --b1\r\n\
+                   Content-Type: text/html\r\n\
+                   \r\n\
+                   <p>ignored</p>\r\n\
+                   --b1--\r\n";
+        let email = InboundEmail::parse(raw).unwrap();
+        assert_eq!(email.from, "Ada <ada@example.org>");
+        assert_eq!(email.to[0], "reply+abc@mail.example.org");
+        assert_eq!(bare_address(&email.to[1]), "grace@example.org");
+        assert_eq!(email.body, "Café at nine, a very long line that was soft-wrapped");
+        assert!(InboundEmail::parse("Subject: no sender\n\nhi").is_none());
+    }
+
+    #[test]
+    fn test_outgoing_headers_cannot_be_injected() {
+        let email = OutgoingEmail {
+            from: "notify@example.org".to_string(),
+            to: "ada@example.org".to_string(),
+            reply_to: "reply+abc@mail.example.org".to_string(),
+            subject: "Hi\nBcc: victim@example.org".to_string(),
+            body: "Hello".to_string(),
+        };
+        let message = email.to_rfc5322();
+        assert!(message.contains("Subject: Hi Bcc: victim@example.org\n"));
+        assert!(!message.contains("\nBcc:"));
+        assert!(message.ends_with("\n\nHello\n"));
+
+        let email = OutgoingEmail {
+            subject: "Café".to_string(),
+            ..email
+        };
+        assert!(email.to_rfc5322().contains("Subject: =?UTF-8?B?Q2Fmw6k=?=\n"));
+    }
+
+    #[test]
+    fn test_bare_address() {
+        assert_eq!(bare_address("Ada <ada@example.org>"), "ada@example.org");
+        assert_eq!(bare_address("  grace@example.org "), "grace@example.org");
+    }
+}
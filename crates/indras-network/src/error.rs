@@ -3,6 +3,7 @@
 //! Provides user-friendly, actionable error messages that wrap
 //! the underlying infrastructure errors.
 
+use indras_core::error_code::{ErrorCode, ErrorDetail, ErrorInfo};
 use indras_node::NodeError;
 use indras_storage::StorageError;
 use std::io;
@@ -38,6 +39,10 @@ pub enum IndraError {
     #[error("Realm not found: {id}")]
     RealmNotFound { id: String },
 
+    /// The realm is archived and accepts no new writes.
+    #[error("Realm is archived: {id}")]
+    RealmArchived { id: String },
+
     /// The specified document was not found.
     #[error("Document not found: {name}")]
     DocumentNotFound { name: String },
@@ -62,6 +67,18 @@ pub enum IndraError {
     #[error("Not a member of this realm")]
     NotMember,
 
+    /// The alias handle is malformed.
+    #[error("Invalid alias: {reason}")]
+    InvalidAlias { reason: String },
+
+    /// The alias handle is already registered to another realm.
+    #[error("Alias @{alias} is already taken")]
+    AliasTaken { alias: String },
+
+    /// No reachable realm publishes the alias handle.
+    #[error("Alias @{alias} not found")]
+    AliasNotFound { alias: String },
+
     /// Invalid operation for the current state.
     #[error("Invalid operation: {0}")]
     InvalidOperation(String),
@@ -73,6 +90,11 @@ pub enum IndraError {
     #[error("Network error: {0}")]
     Network(String),
 
+    /// Failure reported by the node's transport or sync layer, with its
+    /// code and the peer or realm involved.
+    #[error("{0}")]
+    Infrastructure(ErrorDetail),
+
     /// Storage layer error.
     #[error("Storage error: {0}")]
     Storage(StorageError),
@@ -109,6 +131,14 @@ pub enum IndraError {
     #[error("Artifact error: {0}")]
     Artifact(String),
 
+    /// Message translation error.
+    #[error("Translation error: {0}")]
+    Translation(String),
+
+    /// Email gateway error.
+    #[error("Email error: {0}")]
+    Email(String),
+
     /// Story authentication error.
     #[error("Story authentication error: {reason}")]
     StoryAuth { reason: String },
@@ -133,6 +163,57 @@ impl IndraError {
     }
 }
 
+impl ErrorInfo for IndraError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            Self::InvalidInvite { .. } | Self::InvalidAlias { .. } => ErrorCode::InvalidInput,
+            Self::InviteExpired => ErrorCode::Expired,
+            Self::RealmFull => ErrorCode::Full,
+            Self::RemovedFromRealm => ErrorCode::Removed,
+            Self::RealmNotFound { .. } => ErrorCode::InterfaceNotFound,
+            Self::RealmArchived { .. } => ErrorCode::Archived,
+            Self::DocumentNotFound { .. } | Self::AliasNotFound { .. } => ErrorCode::NotFound,
+            Self::NotConnected => ErrorCode::TransportNotRunning,
+            Self::NotStarted | Self::ContactsRealmNotJoined => ErrorCode::NotStarted,
+            Self::AlreadyStarted => ErrorCode::AlreadyStarted,
+            Self::Timeout => ErrorCode::Timeout,
+            Self::NotMember => ErrorCode::NotMember,
+            Self::AliasTaken { .. } => ErrorCode::AlreadyExists,
+            Self::InvalidOperation(_) => ErrorCode::InvalidOperation,
+            Self::Network(_) => ErrorCode::ConnectionFailed,
+            Self::Infrastructure(d) => d.code,
+            Self::Storage(e) => e.code(),
+            Self::DatabaseLocked => ErrorCode::DatabaseLocked,
+            Self::Sync(_) => ErrorCode::SyncFailed,
+            Self::Crypto(_) => ErrorCode::CryptoFailed,
+            Self::Serialization(_) => ErrorCode::Serialization,
+            Self::Io(_) => ErrorCode::Io,
+            Self::Config(_) => ErrorCode::Config,
+            Self::Schema(_) => ErrorCode::CorruptData,
+            Self::Artifact(_) => ErrorCode::Internal,
+            Self::Translation(_) | Self::Email(_) => ErrorCode::External,
+            Self::StoryAuth { .. } => ErrorCode::AuthFailed,
+            Self::NoPeerInRealm => ErrorCode::PeerNotFound,
+            Self::AlreadyShutDown => ErrorCode::ShutDown,
+        }
+    }
+
+    fn peer(&self) -> Option<&str> {
+        match self {
+            Self::Infrastructure(d) => d.peer.as_deref(),
+            _ => None,
+        }
+    }
+
+    fn interface(&self) -> Option<&str> {
+        match self {
+            Self::Infrastructure(d) => d.interface.as_deref(),
+            Self::RealmNotFound { id } | Self::RealmArchived { id } => Some(id),
+            _ => None,
+        }
+    }
+}
+
 impl From<StorageError> for IndraError {
     fn from(e: StorageError) -> Self {
         if e.is_locked() {
@@ -149,15 +230,19 @@ impl From<NodeError> for IndraError {
             NodeError::InterfaceNotFound(id) => IndraError::RealmNotFound { id },
             NodeError::NotStarted => IndraError::NotStarted,
             NodeError::AlreadyStarted => IndraError::AlreadyStarted,
-            NodeError::Transport(s) => IndraError::Network(s),
+            NodeError::Transport(d) | NodeError::Sync(d) => IndraError::Infrastructure(d),
             NodeError::Storage(e) => IndraError::from(e),
-            NodeError::Sync(s) => IndraError::Sync(s),
             NodeError::Crypto(s) => IndraError::Crypto(s),
             NodeError::Serialization(s) => IndraError::Serialization(s),
             NodeError::Config(s) => IndraError::Config(s),
             NodeError::Io(s) => IndraError::Io(io::Error::other(s)),
             NodeError::StoryAuth(s) => IndraError::StoryAuth { reason: s },
-            _ => IndraError::Network(e.to_string()),
+            NodeError::Archived(id) => IndraError::RealmArchived { id },
+            NodeError::Timeout { .. } => IndraError::Timeout,
+            NodeError::Sandboxed(id) => {
+                IndraError::InvalidOperation(format!("realm {id} is a local sandbox"))
+            }
+            _ => IndraError::Infrastructure(e.detail()),
         }
     }
 }
@@ -198,4 +283,22 @@ mod tests {
         };
         assert!(err.to_string().contains("malformed"));
     }
+
+    #[test]
+    fn test_node_errors_keep_code_and_context() {
+        let detail = ErrorDetail::new(ErrorCode::ConnectionClosed, "closed").with_peer("ab12");
+        let err = IndraError::from(NodeError::Transport(detail));
+        assert_eq!(err.code(), ErrorCode::ConnectionClosed);
+        assert!(err.is_retryable());
+        assert_eq!(err.peer(), Some("ab12"));
+
+        let err = IndraError::from(NodeError::InterfaceNotFound("00ff".to_string()));
+        assert_eq!(err.code(), ErrorCode::InterfaceNotFound);
+        assert_eq!(err.interface(), Some("00ff"));
+        assert!(!err.is_retryable());
+
+        let err = IndraError::from(NodeError::Channel("lagged".to_string()));
+        assert_eq!(err.code(), ErrorCode::Channel);
+        assert!(err.is_retryable());
+    }
 }
@@ -0,0 +1,540 @@
+//! Typed, filterable, replayable event bus.
+//!
+//! [`IndrasNetwork::events`](crate::IndrasNetwork::events) hands every
+//! consumer every event. The [`EventBus`] sits behind it and adds what UI
+//! layers and bots need on top:
+//!
+//! - **Filters**: subscribe by [`EventKind`], realm set, and member with an
+//!   [`EventFilter`].
+//! - **Ordering**: every published event gets a bus-wide, strictly
+//!   increasing `sequence`, assigned and broadcast under one lock, so
+//!   events of a realm are always delivered in the order they were
+//!   published. `realm_sequence` numbers each realm's events contiguously.
+//! - **Replay**: the bus keeps a bounded history. A consumer that stored
+//!   the last `sequence` it handled resumes with
+//!   [`EventBus::subscribe_from`] and receives everything it missed before
+//!   live events. If the history no longer reaches back that far, or the
+//!   network restarted since, the resume fails with
+//!   [`IndraError::InvalidOperation`] so the consumer knows to resync from
+//!   a snapshot instead of silently skipping events.
+
+use crate::error::{IndraError, Result};
+use crate::member::{Member, MemberId};
+use crate::network::{GlobalEvent, RealmId};
+
+use futures::Stream;
+use indras_core::InterfaceEvent;
+use indras_transport::IrohIdentity;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+
+/// Events retained for replay by default.
+pub const DEFAULT_EVENT_HISTORY: usize = 4096;
+
+/// Coarse type of a bus event, used for filtering.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    /// A realm message.
+    Message,
+    /// A member joined, left, was invited or removed.
+    Membership,
+    /// A presence update.
+    Presence,
+    /// An application-defined event of the given type.
+    Custom(String),
+    /// An internal sync marker.
+    SyncMarker,
+}
+
+impl EventKind {
+    /// Classify an interface event.
+    pub fn of(event: &InterfaceEvent<IrohIdentity>) -> Self {
+        match event {
+            InterfaceEvent::Message { .. } => Self::Message,
+            InterfaceEvent::MembershipChange { .. } => Self::Membership,
+            InterfaceEvent::Presence { .. } => Self::Presence,
+            InterfaceEvent::Custom { event_type, .. } => Self::Custom(event_type.clone()),
+            InterfaceEvent::SyncMarker { .. } => Self::SyncMarker,
+        }
+    }
+}
+
+/// The member an event originates from: the sender, the peer whose
+/// presence changed, or the actor of a membership change.
+pub fn event_member(event: &InterfaceEvent<IrohIdentity>) -> Option<MemberId> {
+    let identity = match event {
+        InterfaceEvent::Message { sender, .. } | InterfaceEvent::Custom { sender, .. } => sender,
+        InterfaceEvent::Presence { peer, .. } | InterfaceEvent::SyncMarker { peer, .. } => peer,
+        InterfaceEvent::MembershipChange { change, .. } => change.actor()?,
+    };
+    Some(Member::new(*identity).id())
+}
+
+/// One event as delivered by the bus.
+#[derive(Debug, Clone)]
+pub struct BusEvent {
+    /// Bus-wide sequence number, strictly increasing from 1.
+    pub sequence: u64,
+    /// Position of this event within its realm, contiguous from 1.
+    pub realm_sequence: u64,
+    /// Coarse event type.
+    pub kind: EventKind,
+    /// The event itself.
+    pub event: GlobalEvent,
+}
+
+impl BusEvent {
+    /// The realm this event belongs to.
+    pub fn realm_id(&self) -> RealmId {
+        self.event.realm_id
+    }
+
+    /// The member this event originates from, if any.
+    pub fn member(&self) -> Option<MemberId> {
+        event_member(&self.event.event.event)
+    }
+}
+
+/// Which events a subscription receives.
+///
+/// Each criterion left unset matches everything; set criteria must all
+/// match.
+///
+/// # Example
+///
+/// ```ignore
+/// let filter = EventFilter::all()
+///     .kind(EventKind::Message)
+///     .realm(realm.id())
+///     .member(alice.id());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventFilter {
+    kinds: Option<HashSet<EventKind>>,
+    realms: Option<HashSet<RealmId>>,
+    member: Option<MemberId>,
+}
+
+impl EventFilter {
+    /// A filter that matches every event.
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Also accept events of `kind`.
+    pub fn kind(mut self, kind: EventKind) -> Self {
+        self.kinds.get_or_insert_with(HashSet::new).insert(kind);
+        self
+    }
+
+    /// Also accept events from `realm_id`.
+    pub fn realm(mut self, realm_id: RealmId) -> Self {
+        self.realms
+            .get_or_insert_with(HashSet::new)
+            .insert(realm_id);
+        self
+    }
+
+    /// Accept events from any realm in `realm_ids`.
+    pub fn realms(mut self, realm_ids: impl IntoIterator<Item = RealmId>) -> Self {
+        self.realms
+            .get_or_insert_with(HashSet::new)
+            .extend(realm_ids);
+        self
+    }
+
+    /// Only accept events originating from `member`.
+    pub fn member(mut self, member: MemberId) -> Self {
+        self.member = Some(member);
+        self
+    }
+
+    /// Whether an event passes this filter.
+    pub fn matches(&self, event: &BusEvent) -> bool {
+        self.kinds.as_ref().is_none_or(|k| k.contains(&event.kind))
+            && self
+                .realms
+                .as_ref()
+                .is_none_or(|r| r.contains(&event.realm_id()))
+            && self.member.is_none_or(|m| event.member() == Some(m))
+    }
+}
+
+/// Sequencing state and replay history, shared with subscriptions.
+struct BusState {
+    next_sequence: u64,
+    realm_sequences: HashMap<RealmId, u64>,
+    history: VecDeque<BusEvent>,
+    capacity: usize,
+}
+
+impl BusState {
+    /// Retained events after `sequence`, oldest first.
+    fn since(&self, sequence: u64) -> impl Iterator<Item = &BusEvent> {
+        self.history.iter().filter(move |e| e.sequence > sequence)
+    }
+}
+
+/// Sequenced fan-out of `GlobalEvent`s to filtered subscribers.
+///
+/// Owned by `IndrasNetwork`; access it via `network.event_bus()`. The
+/// network publishes every realm event it forwards, plus digests.
+pub struct EventBus {
+    state: Arc<Mutex<BusState>>,
+    event_tx: broadcast::Sender<BusEvent>,
+}
+
+impl EventBus {
+    /// Create a bus retaining [`DEFAULT_EVENT_HISTORY`] events for replay.
+    pub fn new() -> Self {
+        Self::with_history(DEFAULT_EVENT_HISTORY)
+    }
+
+    /// Create a bus retaining up to `capacity` events for replay.
+    pub fn with_history(capacity: usize) -> Self {
+        let (event_tx, _) = broadcast::channel(256);
+        Self {
+            state: Arc::new(Mutex::new(BusState {
+                next_sequence: 1,
+                realm_sequences: HashMap::new(),
+                history: VecDeque::with_capacity(capacity.min(DEFAULT_EVENT_HISTORY)),
+                capacity,
+            })),
+            event_tx,
+        }
+    }
+
+    /// Sequence and deliver an event. Returns its bus sequence number.
+    pub fn publish(&self, event: GlobalEvent) -> u64 {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let sequence = state.next_sequence;
+        state.next_sequence += 1;
+        let realm_sequence = state.realm_sequences.entry(event.realm_id).or_default();
+        *realm_sequence += 1;
+
+        let bus_event = BusEvent {
+            sequence,
+            realm_sequence: *realm_sequence,
+            kind: EventKind::of(&event.event.event),
+            event,
+        };
+        if state.capacity > 0 {
+            if state.history.len() == state.capacity {
+                state.history.pop_front();
+            }
+            state.history.push_back(bus_event.clone());
+        }
+        // Sent under the lock so delivery order matches sequence order
+        let _ = self.event_tx.send(bus_event);
+        sequence
+    }
+
+    /// Sequence number of the most recently published event (0 if none).
+    pub fn last_sequence(&self) -> u64 {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.next_sequence - 1
+    }
+
+    /// Sequence number of the oldest event still available for replay.
+    pub fn oldest_retained(&self) -> Option<u64> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.history.front().map(|e| e.sequence)
+    }
+
+    /// Subscribe to events published from now on.
+    pub fn subscribe(&self, filter: EventFilter) -> EventSubscription {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        EventSubscription {
+            filter,
+            rx: self.event_tx.subscribe(),
+            pending: VecDeque::new(),
+            last_sequence: state.next_sequence - 1,
+            state: Arc::clone(&self.state),
+        }
+    }
+
+    /// Subscribe to every event after `sequence`: retained history first,
+    /// then live events, without gaps or duplicates.
+    ///
+    /// Pass the last `sequence` the consumer handled (0 to start from the
+    /// oldest retained event). Fails if events after `sequence` have
+    /// already been evicted from the history, or if `sequence` is ahead of
+    /// the bus because the network restarted in between.
+    pub fn subscribe_from(&self, filter: EventFilter, sequence: u64) -> Result<EventSubscription> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let last = state.next_sequence - 1;
+        let oldest = state
+            .history
+            .front()
+            .map_or(state.next_sequence, |e| e.sequence);
+        if sequence > last {
+            // The bus restarted since the consumer saved its position
+            return Err(IndraError::InvalidOperation(format!(
+                "sequence {sequence} is ahead of the event bus (last {last})"
+            )));
+        }
+        if sequence != 0 && sequence + 1 < oldest {
+            return Err(IndraError::InvalidOperation(format!(
+                "events {} to {} are no longer retained",
+                sequence + 1,
+                oldest - 1
+            )));
+        }
+
+        // Subscribing under the lock means nothing falls between the
+        // replayed history and the first live event
+        let pending = state
+            .since(sequence)
+            .filter(|e| filter.matches(e))
+            .cloned()
+            .collect();
+        Ok(EventSubscription {
+            filter,
+            rx: self.event_tx.subscribe(),
+            pending,
+            last_sequence: last,
+            state: Arc::clone(&self.state),
+        })
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for EventBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventBus")
+            .field("last_sequence", &self.last_sequence())
+            .field("receivers", &self.event_tx.receiver_count())
+            .finish()
+    }
+}
+
+/// A filtered, ordered view of the bus.
+///
+/// Receives with [`recv`](Self::recv) or as a stream via
+/// [`into_stream`](Self::into_stream). If the subscriber falls behind the
+/// live channel, missed events are recovered from the bus history.
+pub struct EventSubscription {
+    filter: EventFilter,
+    rx: broadcast::Receiver<BusEvent>,
+    /// Replayed events not yet handed out.
+    pending: VecDeque<BusEvent>,
+    /// Highest sequence already handed out or replayed.
+    last_sequence: u64,
+    state: Arc<Mutex<BusState>>,
+}
+
+impl EventSubscription {
+    /// The filter this subscription applies.
+    pub fn filter(&self) -> &EventFilter {
+        &self.filter
+    }
+
+    /// Next matching event, or `None` once the bus is dropped.
+    pub async fn recv(&mut self) -> Option<BusEvent> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(event);
+            }
+            match self.rx.recv().await {
+                Ok(event) => {
+                    if event.sequence <= self.last_sequence {
+                        continue;
+                    }
+                    self.last_sequence = event.sequence;
+                    if self.filter.matches(&event) {
+                        return Some(event);
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => self.backfill(),
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// Turn the subscription into a stream of matching events.
+    pub fn into_stream(mut self) -> impl Stream<Item = BusEvent> + Send {
+        async_stream::stream! {
+            while let Some(event) = self.recv().await {
+                yield event;
+            }
+        }
+    }
+
+    /// Recover events dropped by the live channel from the bus history.
+    fn backfill(&mut self) {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        for event in state.since(self.last_sequence) {
+            if self.filter.matches(event) {
+                self.pending.push_back(event.clone());
+            }
+            self.last_sequence = event.sequence;
+        }
+    }
+}
+
+impl std::fmt::Debug for EventSubscription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventSubscription")
+            .field("filter", &self.filter)
+            .field("last_sequence", &self.last_sequence)
+            .field("pending", &self.pending.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indras_core::InterfaceId;
+    use indras_node::ReceivedEvent;
+
+    fn identity() -> IrohIdentity {
+        IrohIdentity::new(iroh::SecretKey::generate(&mut rand::rng()).public())
+    }
+
+    fn realm(n: u8) -> RealmId {
+        InterfaceId::new([n; 32])
+    }
+
+    fn message(realm_id: RealmId, sender: IrohIdentity, seq: u64) -> GlobalEvent {
+        GlobalEvent {
+            realm_id,
+            event: ReceivedEvent {
+                interface_id: realm_id,
+                event: InterfaceEvent::message(sender, seq, b"hi".to_vec()),
+            },
+        }
+    }
+
+    fn custom(realm_id: RealmId, sender: IrohIdentity, event_type: &str) -> GlobalEvent {
+        GlobalEvent {
+            realm_id,
+            event: ReceivedEvent {
+                interface_id: realm_id,
+                event: InterfaceEvent::custom(sender, 0, event_type.to_string(), Vec::new()),
+            },
+        }
+    }
+
+    #[test]
+    fn test_sequences_are_global_and_per_realm() {
+        let bus = EventBus::new();
+        let alice = identity();
+        assert_eq!(bus.publish(message(realm(1), alice, 1)), 1);
+        assert_eq!(bus.publish(message(realm(2), alice, 2)), 2);
+        assert_eq!(bus.publish(message(realm(1), alice, 3)), 3);
+        assert_eq!(bus.last_sequence(), 3);
+
+        let state = bus.state.lock().unwrap();
+        let realm_one: Vec<u64> = state
+            .history
+            .iter()
+            .filter(|e| e.realm_id() == realm(1))
+            .map(|e| e.realm_sequence)
+            .collect();
+        assert_eq!(realm_one, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_filter_by_kind_realm_and_member() {
+        let alice = identity();
+        let bob = identity();
+        let bus = EventBus::new();
+        bus.publish(message(realm(1), alice, 1));
+        let event = bus.state.lock().unwrap().history[0].clone();
+
+        assert!(EventFilter::all().matches(&event));
+        assert!(EventFilter::all().kind(EventKind::Message).matches(&event));
+        assert!(!EventFilter::all().kind(EventKind::Presence).matches(&event));
+        let both = EventFilter::all().realms([realm(2), realm(1)]);
+        assert!(both.matches(&event));
+        assert!(!EventFilter::all().realm(realm(2)).matches(&event));
+        let (alice, bob) = (Member::new(alice).id(), Member::new(bob).id());
+        assert!(EventFilter::all().member(alice).matches(&event));
+        assert!(!EventFilter::all().member(bob).matches(&event));
+    }
+
+    #[test]
+    fn test_custom_kind_carries_type() {
+        let bus = EventBus::new();
+        bus.publish(custom(realm(1), identity(), "app/quest"));
+        let event = bus.state.lock().unwrap().history[0].clone();
+
+        let quest = EventFilter::all().kind(EventKind::Custom("app/quest".into()));
+        let other = EventFilter::all().kind(EventKind::Custom("app/other".into()));
+        assert!(quest.matches(&event));
+        assert!(!other.matches(&event));
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_receives_live_matching_events() {
+        let bus = EventBus::new();
+        let alice = identity();
+        bus.publish(message(realm(1), alice, 1));
+
+        let mut sub = bus.subscribe(EventFilter::all().realm(realm(1)));
+        bus.publish(message(realm(2), alice, 2));
+        bus.publish(message(realm(1), alice, 3));
+
+        let event = sub.recv().await.unwrap();
+        assert_eq!(event.sequence, 3);
+        assert_eq!(event.realm_sequence, 2);
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_from_replays_then_goes_live() {
+        let bus = EventBus::new();
+        let alice = identity();
+        for seq in 1..=3 {
+            bus.publish(message(realm(1), alice, seq));
+        }
+
+        let mut sub = bus.subscribe_from(EventFilter::all(), 1).unwrap();
+        bus.publish(message(realm(1), alice, 4));
+
+        let mut seen = Vec::new();
+        for _ in 0..3 {
+            seen.push(sub.recv().await.unwrap().sequence);
+        }
+        assert_eq!(seen, vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_subscribe_from_detects_evicted_history() {
+        let bus = EventBus::with_history(2);
+        let alice = identity();
+        for seq in 1..=5 {
+            bus.publish(message(realm(1), alice, seq));
+        }
+        assert_eq!(bus.oldest_retained(), Some(4));
+
+        assert!(bus.subscribe_from(EventFilter::all(), 1).is_err());
+        assert!(bus.subscribe_from(EventFilter::all(), 3).is_ok());
+        // Fully caught up consumers can always resume
+        assert!(bus.subscribe_from(EventFilter::all(), 5).is_ok());
+        // A position saved before a restart is ahead of the new bus
+        assert!(bus.subscribe_from(EventFilter::all(), 9).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_lagged_subscription_backfills_from_history() {
+        let bus = EventBus::new();
+        let alice = identity();
+        let mut sub = bus.subscribe(EventFilter::all());
+
+        // Overflow the live channel
+        for seq in 1..=300 {
+            bus.publish(message(realm(1), alice, seq));
+        }
+
+        for expected in 1..=300 {
+            assert_eq!(sub.recv().await.unwrap().sequence, expected);
+        }
+    }
+}
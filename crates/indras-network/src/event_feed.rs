@@ -0,0 +1,302 @@
+//! Line-oriented realm event feed and control commands.
+//!
+//! Shell automation wants two things from a realm: a log it can follow
+//! with `tail -f`, and somewhere to write commands. [`EventLine`] renders a
+//! message as one JSON object per line, and [`ControlCommand`] parses one
+//! line of text into an action that [`Realm::execute`](crate::Realm::execute)
+//! carries out.
+//!
+//! On disk, [`EventFeedWriter`] keeps an append-only `.jsonl` file that
+//! [`Realm::follow_event_feed`](crate::Realm::follow_event_feed) extends as
+//! messages arrive, and [`ControlFile`] hands back the lines appended to a
+//! plain file since it was last read, so `echo "send hi" >> control` works.
+//!
+//! Commands are one per line, verb first:
+//!
+//! ```text
+//! send Hello from cron
+//! reply 00000000000a1b2c:7 Thanks!
+//! react 00000000000a1b2c:7 👍
+//! ```
+//!
+//! Verbs this crate does not know are kept as [`ControlCommand::Other`]
+//! so higher layers (intentions, notes) can handle their own.
+
+use crate::error::{IndraError, Result};
+use crate::message::{Content, Message};
+use indras_core::EventId;
+use serde::{Deserialize, Serialize};
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+/// One realm message as a JSONL record.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventLine {
+    /// Message id, as accepted by `reply` and `react` commands.
+    pub id: String,
+    /// Sender's member id, hex encoded.
+    pub sender: String,
+    /// Sender's display name.
+    pub sender_name: String,
+    /// When the message was sent (RFC 3339).
+    pub timestamp: String,
+    /// Content kind, e.g. `"text"` or `"reaction"`.
+    pub kind: String,
+    /// Text content, or the emoji for reactions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Id of the message this one replies to or reacts to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_to: Option<String>,
+}
+
+impl EventLine {
+    /// Build the record for a message.
+    pub fn from_message(message: &Message) -> Self {
+        let (text, target) = match &message.content {
+            Content::Reaction { target, emoji } => (Some(emoji.clone()), Some(target.event_id)),
+            content => (content.as_text().map(str::to_string), None),
+        };
+        Self {
+            id: format_event_id(&message.id.event_id),
+            sender: hex::encode(message.sender.id()),
+            sender_name: message.sender.name(),
+            timestamp: message.timestamp.to_rfc3339(),
+            kind: content_kind(&message.content).to_string(),
+            text,
+            reply_to: target
+                .or(message.reply_to.map(|r| r.event_id))
+                .map(|id| format_event_id(&id)),
+        }
+    }
+
+    /// The record as one line of JSON, newline included.
+    pub fn to_jsonl(&self) -> String {
+        let mut line = serde_json::to_string(self).unwrap_or_default();
+        line.push('\n');
+        line
+    }
+}
+
+/// A command written to a realm's control channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ControlCommand {
+    /// Send a text message.
+    Send(String),
+    /// Reply to a message.
+    Reply {
+        /// Message being replied to.
+        to: EventId,
+        /// Reply text.
+        text: String,
+    },
+    /// React to a message.
+    React {
+        /// Message being reacted to.
+        to: EventId,
+        /// The reaction.
+        emoji: String,
+    },
+    /// A verb for a higher layer to handle.
+    Other {
+        /// The verb.
+        verb: String,
+        /// Everything after the verb, trimmed.
+        args: String,
+    },
+}
+
+impl ControlCommand {
+    /// Parse one command line.
+    pub fn parse(line: &str) -> Result<Self> {
+        let line = line.trim();
+        let (verb, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let rest = rest.trim();
+        let required = |what: &str, value: &str| {
+            if value.is_empty() {
+                Err(IndraError::InvalidOperation(format!("{verb}: missing {what}")))
+            } else {
+                Ok(value.to_string())
+            }
+        };
+        match verb {
+            "" => Err(IndraError::InvalidOperation("empty command".to_string())),
+            "send" => Ok(Self::Send(required("text", rest)?)),
+            "reply" | "react" => {
+                let (id, arg) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+                let to = parse_event_id(id)?;
+                Ok(if verb == "reply" {
+                    Self::Reply { to, text: required("text", arg.trim())? }
+                } else {
+                    Self::React { to, emoji: required("emoji", arg.trim())? }
+                })
+            }
+            _ => Ok(Self::Other {
+                verb: verb.to_string(),
+                args: rest.to_string(),
+            }),
+        }
+    }
+}
+
+/// Append-only JSONL file of a realm's messages.
+pub struct EventFeedWriter {
+    file: tokio::fs::File,
+}
+
+impl EventFeedWriter {
+    /// Create or truncate the feed at `path`, starting it with `history`.
+    pub async fn create(path: &Path, history: &[Message]) -> Result<Self> {
+        let mut writer = Self {
+            file: tokio::fs::File::create(path).await?,
+        };
+        for message in history {
+            writer.append(message).await?;
+        }
+        Ok(writer)
+    }
+
+    /// Append one message and flush it, so followers see it at once.
+    pub async fn append(&mut self, message: &Message) -> Result<()> {
+        let line = EventLine::from_message(message).to_jsonl();
+        self.file.write_all(line.as_bytes()).await?;
+        self.file.flush().await?;
+        Ok(())
+    }
+}
+
+/// A plain file that commands are appended to.
+///
+/// Only complete lines are returned; a partly written last line waits for
+/// its newline. If the file shrinks it was truncated or replaced, and is
+/// read again from the start.
+#[derive(Debug, Clone)]
+pub struct ControlFile {
+    path: PathBuf,
+    offset: u64,
+}
+
+impl ControlFile {
+    /// Read commands from `path`, skipping whatever it already holds.
+    pub async fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let offset = match tokio::fs::metadata(&path).await {
+            Ok(meta) => meta.len(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                tokio::fs::File::create(&path).await?;
+                0
+            }
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self { path, offset })
+    }
+
+    /// The non-empty lines appended since the last call.
+    pub async fn read_lines(&mut self) -> Result<Vec<String>> {
+        let mut file = tokio::fs::File::open(&self.path).await?;
+        if file.metadata().await?.len() < self.offset {
+            self.offset = 0;
+        }
+        file.seek(SeekFrom::Start(self.offset)).await?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes).await?;
+
+        let Some(end) = bytes.iter().rposition(|&b| b == b'\n') else {
+            return Ok(Vec::new());
+        };
+        self.offset += end as u64 + 1;
+        Ok(String::from_utf8_lossy(&bytes[..end])
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+}
+
+/// Format an event id as `<sender_hash hex>:<sequence>`.
+pub fn format_event_id(id: &EventId) -> String {
+    format!("{:016x}:{}", id.sender_hash, id.sequence)
+}
+
+/// Parse an event id written by [`format_event_id`].
+pub fn parse_event_id(text: &str) -> Result<EventId> {
+    let invalid = || IndraError::InvalidOperation(format!("invalid message id {text:?}"));
+    let (hash, sequence) = text.split_once(':').ok_or_else(invalid)?;
+    Ok(EventId::new(
+        u64::from_str_radix(hash, 16).map_err(|_| invalid())?,
+        sequence.parse().map_err(|_| invalid())?,
+    ))
+}
+
+fn content_kind(content: &Content) -> &'static str {
+    match content {
+        Content::Text(_) | Content::TextWithPreviews { .. } => "text",
+        Content::Reaction { .. } => "reaction",
+        Content::System(_) => "system",
+        Content::Artifact(_) | Content::InlineArtifact { .. } => "artifact",
+        Content::Image { .. } | Content::Gallery { .. } => "image",
+        Content::Sticker(_) => "sticker",
+        Content::Extension { .. } => "extension",
+        _ => "other",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(
+            ControlCommand::parse("send  hello there \n").unwrap(),
+            ControlCommand::Send("hello there".to_string())
+        );
+
+        let id = EventId::new(0xa1b2c, 7);
+        let line = format!("react {} 👍", format_event_id(&id));
+        assert_eq!(
+            ControlCommand::parse(&line).unwrap(),
+            ControlCommand::React { to: id, emoji: "👍".to_string() }
+        );
+
+        assert_eq!(
+            ControlCommand::parse("intention Fix the fence").unwrap(),
+            ControlCommand::Other {
+                verb: "intention".to_string(),
+                args: "Fix the fence".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_incomplete_commands() {
+        assert!(ControlCommand::parse("").is_err());
+        assert!(ControlCommand::parse("send").is_err());
+        assert!(ControlCommand::parse("reply 12:3").is_err());
+        assert!(ControlCommand::parse("reply nonsense hi").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_control_file_returns_appended_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("control");
+        std::fs::write(&path, "send before we opened\n").unwrap();
+        let mut control = ControlFile::open(&path).await.unwrap();
+        assert!(control.read_lines().await.unwrap().is_empty());
+
+        let append = |text: &str| {
+            use std::io::Write;
+            let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+            file.write_all(text.as_bytes()).unwrap();
+        };
+        append("send one\n\nsend tw");
+        assert_eq!(control.read_lines().await.unwrap(), vec!["send one"]);
+        append("o\n");
+        assert_eq!(control.read_lines().await.unwrap(), vec!["send two"]);
+
+        std::fs::write(&path, "send three\n").unwrap();
+        assert_eq!(control.read_lines().await.unwrap(), vec!["send three"]);
+    }
+}
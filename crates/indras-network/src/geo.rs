@@ -0,0 +1,208 @@
+//! Coarse geo-tags for events, artifacts, and realm advertisements.
+//!
+//! Locations leave the device only as [geohashes](https://en.wikipedia.org/wiki/Geohash)
+//! cut to a [`GeoPrecision`]. The finest level is
+//! [`GeoPrecision::Neighborhood`] (~1 km cells), so a tag never pins
+//! anyone down to a building. Proximity queries treat a tag as its whole
+//! cell: a tag matches "within 50 km" if any part of its cell might.
+
+use crate::artifact_index::GeoLocation;
+use serde::{Deserialize, Serialize};
+
+/// Geohash base-32 alphabet.
+const BASE32: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+/// How precisely a location is shared.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum GeoPrecision {
+    /// 3 characters, cells of roughly 156 km.
+    Region,
+    /// 4 characters, cells of roughly 39 km.
+    #[default]
+    Area,
+    /// 5 characters, cells of roughly 5 km.
+    Locality,
+    /// 6 characters, cells of roughly 1.2 km.
+    Neighborhood,
+}
+
+impl GeoPrecision {
+    /// Geohash length for this precision.
+    pub fn chars(self) -> usize {
+        match self {
+            GeoPrecision::Region => 3,
+            GeoPrecision::Area => 4,
+            GeoPrecision::Locality => 5,
+            GeoPrecision::Neighborhood => 6,
+        }
+    }
+}
+
+/// A geohash of at most [`GeoPrecision::Neighborhood`] precision.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Geohash(String);
+
+impl Geohash {
+    /// Encode a location at the given precision.
+    pub fn encode(location: &GeoLocation, precision: GeoPrecision) -> Self {
+        let (mut lat, mut lng) = ((-90.0, 90.0), (-180.0, 180.0));
+        let mut out = String::with_capacity(precision.chars());
+        let mut even = true;
+        for _ in 0..precision.chars() {
+            let mut index = 0usize;
+            for _ in 0..5 {
+                let (range, value) = if even {
+                    (&mut lng, location.lng)
+                } else {
+                    (&mut lat, location.lat)
+                };
+                let mid = (range.0 + range.1) / 2.0;
+                index <<= 1;
+                if value >= mid {
+                    index |= 1;
+                    range.0 = mid;
+                } else {
+                    range.1 = mid;
+                }
+                even = !even;
+            }
+            out.push(BASE32[index] as char);
+        }
+        Self(out)
+    }
+
+    /// Parse a geohash, cutting it to [`GeoPrecision::Neighborhood`].
+    ///
+    /// Returns `None` for empty strings or characters outside the
+    /// geohash alphabet.
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.to_ascii_lowercase();
+        if s.is_empty() || !s.bytes().all(|b| BASE32.contains(&b)) {
+            return None;
+        }
+        Some(Self(s.chars().take(GeoPrecision::Neighborhood.chars()).collect()))
+    }
+
+    /// The geohash string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Cut to a coarser precision. Already-coarser tags are unchanged.
+    pub fn coarsen(&self, precision: GeoPrecision) -> Self {
+        Self(self.0.chars().take(precision.chars()).collect())
+    }
+
+    /// Latitude and longitude ranges of the cell.
+    ///
+    /// Decoding stops at the first character outside the alphabet, so
+    /// malformed tags from peers decode to a larger cell.
+    fn bounds(&self) -> ((f64, f64), (f64, f64)) {
+        let (mut lat, mut lng) = ((-90.0, 90.0), (-180.0, 180.0));
+        let mut even = true;
+        for byte in self.0.bytes().take(GeoPrecision::Neighborhood.chars()) {
+            let Some(index) = BASE32.iter().position(|b| *b == byte) else {
+                break;
+            };
+            for bit in (0..5).rev() {
+                let range: &mut (f64, f64) = if even { &mut lng } else { &mut lat };
+                let mid = (range.0 + range.1) / 2.0;
+                if index & (1 << bit) != 0 {
+                    range.0 = mid;
+                } else {
+                    range.1 = mid;
+                }
+                even = !even;
+            }
+        }
+        (lat, lng)
+    }
+
+    /// Center of the cell.
+    pub fn center(&self) -> GeoLocation {
+        let (lat, lng) = self.bounds();
+        GeoLocation {
+            lat: (lat.0 + lat.1) / 2.0,
+            lng: (lng.0 + lng.1) / 2.0,
+        }
+    }
+
+    /// Distance from the cell center to its farthest corner, in km.
+    pub fn radius_km(&self) -> f64 {
+        let (lat, lng) = self.bounds();
+        let center = self.center();
+        [lat.0, lat.1]
+            .into_iter()
+            .flat_map(|la| [lng.0, lng.1].map(|lo| GeoLocation { lat: la, lng: lo }))
+            .map(|corner| center.distance_km(&corner))
+            .fold(0.0, f64::max)
+    }
+
+    /// Approximate distance between a point and the cell center, in km.
+    pub fn distance_km(&self, point: &GeoLocation) -> f64 {
+        self.center().distance_km(point)
+    }
+
+    /// Whether any part of the cell may lie within `radius_km` of `point`.
+    pub fn within_km(&self, point: &GeoLocation, radius_km: f64) -> bool {
+        self.distance_km(point) - self.radius_km() <= radius_km
+    }
+}
+
+impl std::fmt::Display for Geohash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl GeoLocation {
+    /// Coarse geo-tag for this location.
+    pub fn geohash(&self, precision: GeoPrecision) -> Geohash {
+        Geohash::encode(self, precision)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COPENHAGEN: GeoLocation = GeoLocation { lat: 55.6761, lng: 12.5683 };
+
+    #[test]
+    fn test_encode_matches_reference() {
+        // Reference value from the geohash paper's example point
+        let point = GeoLocation { lat: 57.64911, lng: 10.40744 };
+        assert_eq!(point.geohash(GeoPrecision::Neighborhood).as_str(), "u4pruy");
+        assert_eq!(point.geohash(GeoPrecision::Region).as_str(), "u4p");
+    }
+
+    #[test]
+    fn test_center_is_inside_cell() {
+        let tag = COPENHAGEN.geohash(GeoPrecision::Locality);
+        assert!(tag.distance_km(&COPENHAGEN) <= tag.radius_km());
+        assert_eq!(tag.center().geohash(GeoPrecision::Locality), tag);
+    }
+
+    #[test]
+    fn test_parse_caps_precision_and_rejects_junk() {
+        let tag = Geohash::parse("U4PRUYDQQVJ").unwrap();
+        assert_eq!(tag.as_str(), "u4pruy");
+        assert_eq!(tag.coarsen(GeoPrecision::Area).as_str(), "u4pr");
+        assert!(Geohash::parse("").is_none());
+        assert!(Geohash::parse("u4a").is_none()); // 'a' is not in the alphabet
+    }
+
+    #[test]
+    fn test_within_accounts_for_cell_size() {
+        // Malmö is ~28 km from Copenhagen
+        let malmo = GeoLocation { lat: 55.6050, lng: 13.0038 };
+        let tag = malmo.geohash(GeoPrecision::Neighborhood);
+        assert!(tag.within_km(&COPENHAGEN, 50.0));
+        assert!(!tag.within_km(&COPENHAGEN, 20.0));
+
+        // A region-level cell is large enough to match anything nearby
+        let coarse = malmo.geohash(GeoPrecision::Region);
+        assert!(coarse.within_km(&COPENHAGEN, 20.0));
+    }
+}
@@ -0,0 +1,248 @@
+//! Read-only guest access to a realm.
+//!
+//! A guest is an outside peer (a reviewer, an auditor) admitted to a realm
+//! for a limited time without becoming a full member. Grants are recorded
+//! in a [`GuestAccessDocument`] inside the realm itself, using the same
+//! [`AccessGrant`] / [`AccessMode::Timed`] types as artifact sharing, so
+//! every member sees who was admitted, to which documents, and until when.
+//!
+//! Enforcement happens on each member's node: guests are registered with
+//! the node's guest registry, which drops any event or sync state a guest
+//! pushes and stops syncing to it once the grant lapses. The guest's invite
+//! key is ML-KEM-wrapped to the guest alone, so the link is useless to
+//! anyone else.
+//!
+//! The document scope travels with the grant and the invite so the guest's
+//! client knows which documents it was given. It is not a cryptographic
+//! boundary: all documents in a realm share one interface key, which the
+//! guest holds like a member. Once a grant lapses or is revoked, an admin
+//! rotates the realm key (see [`crate::key_rotation`]) and wraps the new
+//! one only to members and remaining guests.
+
+use crate::access::{AccessGrant, AccessMode};
+use crate::document::DocumentSchema;
+use crate::member::MemberId;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Document name for guest grants within a realm.
+pub const GUEST_ACCESS_DOC: &str = "_guest_access";
+
+/// Milliseconds in one day.
+pub const DAY_MILLIS: u64 = 24 * 60 * 60 * 1000;
+
+/// What a guest invite lets the guest see.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuestScope {
+    /// Names of the documents the guest may read.
+    pub documents: BTreeSet<String>,
+    /// When access lapses (milliseconds since epoch).
+    pub expires_at_millis: u64,
+}
+
+impl GuestScope {
+    /// Whether the scope covers a document.
+    pub fn allows(&self, document: &str) -> bool {
+        self.documents.contains(document)
+    }
+
+    /// Whether access has lapsed at `now_millis`.
+    pub fn is_expired(&self, now_millis: u64) -> bool {
+        now_millis >= self.expires_at_millis
+    }
+}
+
+/// One guest's admission to a realm.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuestGrant {
+    /// The underlying timed grant (grantee is the guest).
+    pub grant: AccessGrant,
+    /// Documents the guest may read.
+    pub documents: BTreeSet<String>,
+    /// Whether a member revoked the grant early.
+    pub revoked: bool,
+    /// When this grant was last changed (milliseconds since epoch).
+    pub updated_at_millis: u64,
+}
+
+impl GuestGrant {
+    /// The guest this grant admits.
+    pub fn guest(&self) -> MemberId {
+        self.grant.grantee
+    }
+
+    /// When access lapses (milliseconds since epoch).
+    pub fn expires_at_millis(&self) -> u64 {
+        match self.grant.mode {
+            AccessMode::Timed { expires_at } => expires_at.max(0) as u64,
+            _ => 0,
+        }
+    }
+
+    /// Whether the guest may still read at `now_millis`.
+    pub fn is_active(&self, now_millis: u64) -> bool {
+        !self.revoked && !self.grant.mode.is_expired(now_millis as i64)
+    }
+
+    /// When the guest's access ended, if it has by `now_millis`: when the
+    /// grant was revoked or lapsed, whichever came first, and never later
+    /// than `now_millis`.
+    pub fn ended_at_millis(&self, now_millis: u64) -> Option<u64> {
+        let expires = self.expires_at_millis();
+        if self.revoked {
+            Some(self.updated_at_millis.min(expires).min(now_millis))
+        } else if self.grant.mode.is_expired(now_millis as i64) {
+            Some(expires)
+        } else {
+            None
+        }
+    }
+
+    /// The guest's scope, as carried in its invite.
+    pub fn scope(&self) -> GuestScope {
+        GuestScope {
+            documents: self.documents.clone(),
+            expires_at_millis: self.expires_at_millis(),
+        }
+    }
+}
+
+/// CRDT document listing a realm's guests.
+///
+/// # Example
+///
+/// ```ignore
+/// let invite = realm.create_guest_invite(reviewer_id, ["design-notes"], 7).await?;
+/// for grant in realm.guests().await? {
+///     println!("{:?} until {}", grant.guest(), grant.expires_at_millis());
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GuestAccessDocument {
+    /// Grants keyed by guest.
+    pub grants: BTreeMap<MemberId, GuestGrant>,
+}
+
+impl GuestAccessDocument {
+    /// Create an empty guest list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Admit (or re-admit) a guest, replacing any earlier grant.
+    pub fn grant(
+        &mut self,
+        guest: MemberId,
+        documents: BTreeSet<String>,
+        expires_at_millis: u64,
+        granted_by: MemberId,
+        now_millis: u64,
+    ) -> &GuestGrant {
+        let grant = GuestGrant {
+            grant: AccessGrant {
+                grantee: guest,
+                mode: AccessMode::Timed {
+                    expires_at: expires_at_millis as i64,
+                },
+                granted_at: now_millis as i64,
+                granted_by,
+            },
+            documents,
+            revoked: false,
+            updated_at_millis: now_millis,
+        };
+        self.grants.insert(guest, grant);
+        &self.grants[&guest]
+    }
+
+    /// Revoke a guest's access. Returns `false` if it had no live grant.
+    pub fn revoke(&mut self, guest: &MemberId, now_millis: u64) -> bool {
+        match self.grants.get_mut(guest).filter(|g| !g.revoked) {
+            Some(grant) => {
+                grant.revoked = true;
+                grant.updated_at_millis = now_millis;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The grant for a guest, if any.
+    pub fn get(&self, guest: &MemberId) -> Option<&GuestGrant> {
+        self.grants.get(guest)
+    }
+
+    /// Whether a peer was ever admitted as a guest (active or not).
+    pub fn is_guest(&self, peer: &MemberId) -> bool {
+        self.grants.contains_key(peer)
+    }
+
+    /// Grants that are still active at `now_millis`.
+    pub fn active(&self, now_millis: u64) -> impl Iterator<Item = &GuestGrant> {
+        self.grants.values().filter(move |g| g.is_active(now_millis))
+    }
+}
+
+impl DocumentSchema for GuestAccessDocument {
+    /// Per-guest LWW merge.
+    fn merge(&mut self, remote: Self) {
+        for (guest, grant) in remote.grants {
+            match self.grants.get(&guest) {
+                Some(local) if local.updated_at_millis >= grant.updated_at_millis => {}
+                _ => {
+                    self.grants.insert(guest, grant);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn docs(names: &[&str]) -> BTreeSet<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_grant_expires() {
+        let mut doc = GuestAccessDocument::new();
+        let grant = doc.grant([2u8; 32], docs(&["notes"]), 1_000, [1u8; 32], 10);
+
+        assert_eq!(grant.guest(), [2u8; 32]);
+        assert_eq!(grant.expires_at_millis(), 1_000);
+        assert!(grant.scope().allows("notes"));
+        assert!(!grant.scope().allows("chat"));
+        assert_eq!(doc.active(999).count(), 1);
+        assert_eq!(doc.active(1_000).count(), 0);
+        assert_eq!(grant.ended_at_millis(999), None);
+        assert_eq!(grant.ended_at_millis(5_000), Some(1_000));
+    }
+
+    #[test]
+    fn test_revoke() {
+        let mut doc = GuestAccessDocument::new();
+        doc.grant([2u8; 32], docs(&["notes"]), u64::MAX / 2, [1u8; 32], 10);
+
+        assert!(doc.revoke(&[2u8; 32], 20));
+        assert!(!doc.revoke(&[2u8; 32], 30));
+        assert!(doc.is_guest(&[2u8; 32]));
+        assert_eq!(doc.active(20).count(), 0);
+        let grant = doc.get(&[2u8; 32]).unwrap();
+        assert_eq!(grant.ended_at_millis(30), Some(20));
+        // A revocation dated ahead of this clock ends access now
+        assert_eq!(grant.ended_at_millis(15), Some(15));
+    }
+
+    #[test]
+    fn test_merge_keeps_newer_grant() {
+        let mut a = GuestAccessDocument::new();
+        a.grant([2u8; 32], docs(&["notes"]), 1_000, [1u8; 32], 10);
+        let mut b = a.clone();
+        b.revoke(&[2u8; 32], 20);
+
+        a.merge(b);
+        assert!(a.get(&[2u8; 32]).unwrap().revoked);
+    }
+}
@@ -19,7 +19,9 @@ use crate::artifact_sync::ArtifactSyncRegistry;
 use crate::document::Document;
 use crate::error::{IndraError, Result};
 use crate::member::MemberId;
+use crate::message::{Content, MessagePayload};
 use crate::network::RealmId;
+use crate::settings::{SettingChange, SettingsDocument, SETTINGS_DOC};
 use crate::util::guess_mime_type;
 use indras_core::InterfaceId;
 use indras_node::IndrasNode;
@@ -298,8 +300,30 @@ impl HomeRealm {
             .await
             .map_err(|e| IndraError::Artifact(format!("Failed to read file: {}", e)))?;
 
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unnamed")
+            .to_string();
+
+        self.upload_data(name, file_data).await
+    }
+
+    /// Upload in-memory content to the home realm filesystem.
+    ///
+    /// Like [`upload`](Self::upload), with the MIME type guessed from the
+    /// name's extension. Hashing runs on a blocking thread so several
+    /// uploads can proceed side by side without stalling the runtime.
+    pub async fn upload_data(&self, name: impl Into<String>, data: Vec<u8>) -> Result<ArtifactId> {
+        let name = name.into();
+
         // Compute BLAKE3 hash
-        let hash = blake3::hash(&file_data);
+        let (file_data, hash) = tokio::task::spawn_blocking(move || {
+            let hash = blake3::hash(&data);
+            (data, hash)
+        })
+        .await
+        .map_err(|e| IndraError::Artifact(format!("Hashing task failed: {}", e)))?;
         let id = ArtifactId::Blob(*hash.as_bytes());
 
         // Store blob
@@ -310,14 +334,8 @@ impl HomeRealm {
             .await
             .map_err(|e| IndraError::Artifact(format!("Failed to store blob: {}", e)))?;
 
-        // Get filename and metadata
-        let name = path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("unnamed")
-            .to_string();
         let size = file_data.len() as u64;
-        let mime_type = path
+        let mime_type = Path::new(&name)
             .extension()
             .and_then(|ext| ext.to_str())
             .map(guess_mime_type);
@@ -586,6 +604,90 @@ impl HomeRealm {
         self.document::<crate::contacts::ContactsDocument>("contacts").await
     }
 
+    /// Get the document of contacts verified by safety number.
+    ///
+    /// Shared by all of this member's devices, like contacts.
+    pub async fn contact_verifications(
+        &self,
+    ) -> Result<Document<crate::contact_verification::ContactVerificationDocument>> {
+        self.document(crate::contact_verification::CONTACT_VERIFICATION_DOC)
+            .await
+    }
+
+    // ============================================================
+    // Settings
+    // ============================================================
+
+    /// Get the cross-device settings document.
+    ///
+    /// Each app stores its settings under its own namespace; all devices
+    /// for this member share the same document.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let settings = home.settings().await?;
+    /// settings.update(|s| s.set("chat", "notifications", &false)).await?;
+    /// ```
+    pub async fn settings(&self) -> Result<Document<SettingsDocument>> {
+        self.document::<SettingsDocument>(SETTINGS_DOC).await
+    }
+
+    /// Stream of setting changes within one namespace.
+    ///
+    /// Yields one [`SettingChange`] per key whose value changed, whether
+    /// edited locally or synced from another device.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut changes = Box::pin(home.setting_changes("workspace").await?);
+    /// while let Some(change) = changes.next().await {
+    ///     if change.key == "theme" { apply_theme(change.value); }
+    /// }
+    /// ```
+    pub async fn setting_changes(
+        &self,
+        namespace: impl Into<String>,
+    ) -> Result<impl futures::Stream<Item = SettingChange> + Send + 'static> {
+        let doc = self.settings().await?;
+        let namespace = namespace.into();
+        let mut rx = doc.subscribe();
+        let mut last = doc.read().await.clone();
+
+        Ok(async_stream::stream! {
+            // Hold the document so its change channel stays open.
+            let _doc = doc;
+            loop {
+                match rx.recv().await {
+                    Ok(change) => {
+                        for setting in last.changes_to(&change.new_state, Some(&namespace)) {
+                            yield setting;
+                        }
+                        last = change.new_state;
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    // ============================================================
+    // Messages
+    // ============================================================
+
+    /// Post a system message into the home realm.
+    ///
+    /// Used for synthetic notices such as realm digests; syncs to all of
+    /// this member's devices like any other home realm event.
+    pub async fn post_system(&self, text: impl Into<String>) -> Result<()> {
+        let payload = MessagePayload::new(Content::System(text.into()));
+        let bytes = postcard::to_allocvec(&payload).map_err(IndraError::from)?;
+        self.node.send_message(&self.id, bytes).await?;
+        Ok(())
+    }
+
     // ============================================================
     // Escape hatches
     // ============================================================
@@ -4,6 +4,7 @@
 
 use crate::artifact::ArtifactId;
 use crate::error::{IndraError, Result};
+use crate::guest_access::GuestScope;
 use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use indras_core::InterfaceId;
 use indras_node::InviteKey;
@@ -13,6 +14,9 @@ use std::str::FromStr;
 /// The URI scheme prefix for invite codes.
 const INVITE_PREFIX: &str = "indra:realm:";
 
+/// The URI scheme prefix for read-only guest invite codes.
+const GUEST_INVITE_PREFIX: &str = "indra:guest:";
+
 /// Internal serialization format for invites (supports artifact-backed realms).
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 struct InvitePayload {
@@ -20,6 +24,14 @@ struct InvitePayload {
     artifact_id: Option<ArtifactId>,
 }
 
+/// Serialization format for guest invites.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct GuestInvitePayload {
+    key: InviteKey,
+    artifact_id: Option<ArtifactId>,
+    scope: GuestScope,
+}
+
 /// A human-shareable invite code for joining a realm.
 ///
 /// Invite codes can be shared as text, QR codes, or links.
@@ -44,17 +56,18 @@ struct InvitePayload {
 pub struct InviteCode {
     inner: InviteKey,
     artifact_id: Option<ArtifactId>,
+    guest: Option<GuestScope>,
 }
 
 impl InviteCode {
     /// Create a new invite code from an invite key.
     pub fn new(key: InviteKey) -> Self {
-        Self { inner: key, artifact_id: None }
+        Self { inner: key, artifact_id: None, guest: None }
     }
 
     /// Create a new invite code with an artifact ID for artifact-backed realms.
     pub fn new_with_artifact(key: InviteKey, artifact_id: ArtifactId) -> Self {
-        Self { inner: key, artifact_id: Some(artifact_id) }
+        Self { inner: key, artifact_id: Some(artifact_id), guest: None }
     }
 
     /// Get the artifact ID if this is an artifact-backed realm.
@@ -62,6 +75,17 @@ impl InviteCode {
         self.artifact_id.as_ref()
     }
 
+    /// Mark this invite as a read-only guest invite with the given scope.
+    pub fn with_guest_scope(mut self, scope: GuestScope) -> Self {
+        self.guest = Some(scope);
+        self
+    }
+
+    /// The guest scope, if this is a read-only guest invite.
+    pub fn guest_scope(&self) -> Option<&GuestScope> {
+        self.guest.as_ref()
+    }
+
     /// Parse an invite code from a string.
     ///
     /// Accepts both the full URI format (`indra:realm:...`) and
@@ -69,12 +93,25 @@ impl InviteCode {
     pub fn parse(s: &str) -> Result<Self> {
         let s = s.trim();
 
+        if let Some(stripped) = s.strip_prefix(GUEST_INVITE_PREFIX) {
+            let bytes = URL_SAFE_NO_PAD.decode(stripped)?;
+            let payload: GuestInvitePayload =
+                postcard::from_bytes(&bytes).map_err(|e| IndraError::InvalidInvite {
+                    reason: format!("Invalid guest invite data: {}", e),
+                })?;
+            return Ok(Self {
+                inner: payload.key,
+                artifact_id: payload.artifact_id,
+                guest: Some(payload.scope),
+            });
+        }
+
         // Strip the prefix if present
         let base64_part = if let Some(stripped) = s.strip_prefix(INVITE_PREFIX) {
             stripped
         } else if s.starts_with("indra:") {
             return Err(IndraError::InvalidInvite {
-                reason: "Unknown invite type (expected 'realm')".to_string(),
+                reason: "Unknown invite type (expected 'realm' or 'guest')".to_string(),
             });
         } else {
             s
@@ -88,6 +125,7 @@ impl InviteCode {
             return Ok(Self {
                 inner: payload.key,
                 artifact_id: payload.artifact_id,
+                guest: None,
             });
         }
 
@@ -96,7 +134,7 @@ impl InviteCode {
             reason: format!("Invalid invite data: {}", e),
         })?;
 
-        Ok(Self { inner: key, artifact_id: None })
+        Ok(Self { inner: key, artifact_id: None, guest: None })
     }
 
     /// Get the realm ID this invite is for.
@@ -106,16 +144,27 @@ impl InviteCode {
 
     /// Convert to a shareable string in URI format.
     pub fn to_uri(&self) -> String {
-        format!("{}{}", INVITE_PREFIX, self.to_base64())
+        let prefix = if self.guest.is_some() { GUEST_INVITE_PREFIX } else { INVITE_PREFIX };
+        format!("{}{}", prefix, self.to_base64())
     }
 
     /// Convert to raw base64-encoded format.
+    ///
+    /// Guest invites only round-trip through [`to_uri`](Self::to_uri), since
+    /// the prefix is what marks them as guest invites.
     pub fn to_base64(&self) -> String {
-        let payload = InvitePayload {
-            key: self.inner.clone(),
-            artifact_id: self.artifact_id,
-        };
-        let bytes = postcard::to_allocvec(&payload).expect("serialization should not fail");
+        let bytes = match &self.guest {
+            Some(scope) => postcard::to_allocvec(&GuestInvitePayload {
+                key: self.inner.clone(),
+                artifact_id: self.artifact_id,
+                scope: scope.clone(),
+            }),
+            None => postcard::to_allocvec(&InvitePayload {
+                key: self.inner.clone(),
+                artifact_id: self.artifact_id,
+            }),
+        }
+        .expect("serialization should not fail");
         URL_SAFE_NO_PAD.encode(&bytes)
     }
 
@@ -159,6 +208,7 @@ impl fmt::Debug for InviteCode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("InviteCode")
             .field("realm_id", &hex::encode(&self.inner.interface_id.as_bytes()[..8]))
+            .field("guest", &self.guest.is_some())
             .finish()
     }
 }
@@ -221,5 +271,25 @@ mod tests {
     #[test]
     fn test_invite_prefix() {
         assert!(INVITE_PREFIX.starts_with("indra:"));
+        assert!(GUEST_INVITE_PREFIX.starts_with("indra:"));
+    }
+
+    #[test]
+    fn test_guest_invite_roundtrip() {
+        let scope = GuestScope {
+            documents: ["notes".to_string()].into_iter().collect(),
+            expires_at_millis: 1_000,
+        };
+        let code = InviteCode::new(InviteKey::new(InterfaceId::new([3u8; 32])))
+            .with_guest_scope(scope.clone());
+        let uri = code.to_uri();
+        assert!(uri.starts_with(GUEST_INVITE_PREFIX));
+
+        let parsed = InviteCode::parse(&uri).unwrap();
+        assert_eq!(parsed.realm_id(), InterfaceId::new([3u8; 32]));
+        assert_eq!(parsed.guest_scope(), Some(&scope));
+
+        let plain = InviteCode::new(InviteKey::new(InterfaceId::new([3u8; 32])));
+        assert!(InviteCode::parse(&plain.to_uri()).unwrap().guest_scope().is_none());
     }
 }
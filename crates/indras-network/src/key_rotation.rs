@@ -0,0 +1,547 @@
+//! Realm key rotation.
+//!
+//! A realm's first interface key is derived from its artifact id, so
+//! anyone who ever held an invite can derive it again. Rotating replaces
+//! it with a random key that members receive instead of derive: the admin
+//! who rotates wraps the new key with ML-KEM to each member who keeps
+//! access and records both in the realm's [`KeyRotationDocument`].
+//!
+//! - A [`KeyRotation`] names its epoch (1 for the first rotation; the
+//!   invite-derived key is epoch 0), why it happened, and a hash of the
+//!   new key. Only rotations signed by a current admin, with the key the
+//!   membership log pins for them, count.
+//! - A [`KeyShare`] is the new key wrapped to one recipient. Admins
+//!   holding the key add shares for members and guests who lack one, such
+//!   as those who joined later, and a share is only used if it unwraps to
+//!   the key the rotation hashed.
+//! - The highest epoch is current. Concurrent rotations to the same
+//!   epoch are settled by the lower rotation hash.
+//!
+//! The document reaches members who do not hold the new key yet, because
+//! document sync is not encrypted with the realm key. Each node adopts the
+//! current key once it finds a share for itself, and keeps decrypting
+//! with the keys it replaced (see `IndrasNode::rotate_interface_key`).
+//!
+//! Rotation protects the realm's events from then on. Members removed
+//! from the interface also stop receiving document sync, which is what
+//! keeps later document changes from them.
+
+use crate::document::DocumentSchema;
+use crate::member::MemberId;
+use crate::membership_log::{EntryHash, MembershipState};
+use indras_core::InterfaceId;
+use indras_crypto::pq_identity::{PQIdentity, PQPublicIdentity, PQSignature};
+use indras_crypto::{InterfaceKey, KeyDistribution, KeyInvite, PQEncapsulationKey, PQKemKeyPair};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Document name for a realm's key rotations.
+pub const KEY_ROTATION_DOC: &str = "_key_rotations";
+
+/// Domain separation tag for rotation hashes and signatures.
+const ROTATION_DOMAIN: &[u8] = b"indras:key-rotation:v1";
+
+/// Domain separation tag for key share hashes and signatures.
+const SHARE_DOMAIN: &[u8] = b"indras:key-share:v1";
+
+/// Domain separation tag for realm key hashes.
+const KEY_HASH_DOMAIN: &[u8] = b"indras:realm-key:v1";
+
+/// Hash identifying a realm key without revealing it.
+pub fn key_hash(key: &InterfaceKey) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(KEY_HASH_DOMAIN);
+    hasher.update(key.as_bytes());
+    *hasher.finalize().as_bytes()
+}
+
+/// Why a realm key was rotated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RotationReason {
+    /// Members were pruned from the realm.
+    Pruned(Vec<MemberId>),
+    /// Guest grants lapsed or were revoked.
+    GuestsLapsed(Vec<MemberId>),
+    /// An admin rotated the key by hand.
+    Manual,
+}
+
+/// A new realm key, before signing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyRotation {
+    /// Realm the key is for.
+    pub realm: InterfaceId,
+    /// 1 for the first rotation, one more for each after.
+    pub epoch: u64,
+    /// Admin who rotated.
+    pub by: MemberId,
+    /// Why.
+    pub reason: RotationReason,
+    /// [`key_hash`] of the new key.
+    pub key_hash: [u8; 32],
+    /// When the key was rotated (milliseconds since epoch).
+    pub at_millis: u64,
+}
+
+impl KeyRotation {
+    /// Domain-separated bytes the hash and signature cover.
+    fn signing_bytes(&self) -> Vec<u8> {
+        let mut bytes = ROTATION_DOMAIN.to_vec();
+        bytes.extend(postcard::to_allocvec(self).unwrap_or_default());
+        bytes
+    }
+}
+
+/// Outcome of rotating a realm key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RotatedKey {
+    /// Epoch of the new key.
+    pub epoch: u64,
+    /// Members and guests the key could not be wrapped to yet, because
+    /// their ML-KEM key is unknown.
+    pub unreachable: Vec<MemberId>,
+}
+
+/// A rotation signed by the admin who made it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedKeyRotation {
+    /// The rotation.
+    pub rotation: KeyRotation,
+    /// Signer's ML-DSA-65 verifying key.
+    pub verifying_key: Vec<u8>,
+    /// Signature over the rotation.
+    pub signature: Vec<u8>,
+}
+
+impl SignedKeyRotation {
+    /// Sign `rotation` with the rotating admin's identity.
+    pub fn sign(rotation: KeyRotation, identity: &PQIdentity) -> Self {
+        let signature = identity.sign(&rotation.signing_bytes());
+        Self {
+            rotation,
+            verifying_key: identity.verifying_key_bytes(),
+            signature: signature.to_bytes().to_vec(),
+        }
+    }
+
+    /// Hash identifying the rotation.
+    pub fn hash(&self) -> EntryHash {
+        *blake3::hash(&self.rotation.signing_bytes()).as_bytes()
+    }
+
+    /// Whether the signature is valid for the embedded key.
+    pub fn verify(&self) -> bool {
+        verify_signature(&self.verifying_key, &self.rotation.signing_bytes(), &self.signature)
+    }
+}
+
+/// A rotated key wrapped to one recipient, before signing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyShare {
+    /// Hash of the rotation the key belongs to.
+    pub rotation: EntryHash,
+    /// Member or guest the key is wrapped to.
+    pub recipient: MemberId,
+    /// Member who wrapped it.
+    pub by: MemberId,
+    /// The key as a serialized ML-KEM [`KeyInvite`].
+    pub wrapped: Vec<u8>,
+}
+
+impl KeyShare {
+    /// Wrap `key` to `recipient`'s encapsulation key.
+    pub fn wrap(
+        rotation: EntryHash,
+        recipient: MemberId,
+        by: MemberId,
+        key: &InterfaceKey,
+        encapsulation_key: &PQEncapsulationKey,
+    ) -> Option<Self> {
+        let wrapped = KeyDistribution::create_invite(key, encapsulation_key)
+            .and_then(|invite| invite.to_bytes())
+            .ok()?;
+        Some(Self {
+            rotation,
+            recipient,
+            by,
+            wrapped,
+        })
+    }
+
+    /// Unwrap the key with the recipient's KEM keypair, if it matches
+    /// `expected` (a [`key_hash`]).
+    pub fn open(&self, keypair: &PQKemKeyPair, expected: &[u8; 32]) -> Option<InterfaceKey> {
+        let invite = KeyInvite::from_bytes(&self.wrapped).ok()?;
+        let key = KeyDistribution::accept_invite(&invite, keypair).ok()?;
+        (key_hash(&key) == *expected).then_some(key)
+    }
+
+    /// Domain-separated bytes the hash and signature cover.
+    fn signing_bytes(&self) -> Vec<u8> {
+        let mut bytes = SHARE_DOMAIN.to_vec();
+        bytes.extend(postcard::to_allocvec(self).unwrap_or_default());
+        bytes
+    }
+}
+
+/// A key share signed by the member who wrapped it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedKeyShare {
+    /// The share.
+    pub share: KeyShare,
+    /// Signer's ML-DSA-65 verifying key.
+    pub verifying_key: Vec<u8>,
+    /// Signature over the share.
+    pub signature: Vec<u8>,
+}
+
+impl SignedKeyShare {
+    /// Sign `share` with the wrapping member's identity.
+    pub fn sign(share: KeyShare, identity: &PQIdentity) -> Self {
+        let signature = identity.sign(&share.signing_bytes());
+        Self {
+            share,
+            verifying_key: identity.verifying_key_bytes(),
+            signature: signature.to_bytes().to_vec(),
+        }
+    }
+
+    /// Hash identifying the share.
+    pub fn hash(&self) -> EntryHash {
+        *blake3::hash(&self.share.signing_bytes()).as_bytes()
+    }
+
+    /// Whether the signature is valid for the embedded key.
+    pub fn verify(&self) -> bool {
+        verify_signature(&self.verifying_key, &self.share.signing_bytes(), &self.signature)
+    }
+}
+
+fn verify_signature(key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    let (Ok(key), Ok(signature)) = (
+        PQPublicIdentity::from_bytes(key),
+        PQSignature::from_bytes(signature.to_vec()),
+    ) else {
+        return false;
+    };
+    key.verify(message, &signature)
+}
+
+/// Whether `verifying_key` is the one the membership log pins for `member`.
+fn pinned(membership: &MembershipState, member: &MemberId, verifying_key: &[u8]) -> bool {
+    membership.keys.get(member).is_some_and(|k| k == verifying_key)
+}
+
+/// CRDT document holding a realm's key rotations and key shares.
+///
+/// # Example
+///
+/// ```ignore
+/// let rotated = realm.rotate_key(RotationReason::Manual).await?;
+/// for member in rotated.unreachable {
+///     println!("no ML-KEM key for {member:?} yet");
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KeyRotationDocument {
+    /// Signed rotations keyed by hash.
+    pub rotations: BTreeMap<EntryHash, SignedKeyRotation>,
+    /// Signed key shares keyed by hash.
+    pub shares: BTreeMap<EntryHash, SignedKeyShare>,
+}
+
+impl KeyRotationDocument {
+    /// Create an empty document.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a validly signed rotation. Returns `false` if the signature is
+    /// invalid or the rotation is already present.
+    pub fn add_rotation(&mut self, signed: SignedKeyRotation) -> bool {
+        if !signed.verify() {
+            return false;
+        }
+        let hash = signed.hash();
+        if self.rotations.contains_key(&hash) {
+            return false;
+        }
+        self.rotations.insert(hash, signed);
+        true
+    }
+
+    /// Add a validly signed share. Returns `false` if the signature is
+    /// invalid or the share is already present.
+    pub fn add_share(&mut self, signed: SignedKeyShare) -> bool {
+        if !signed.verify() {
+            return false;
+        }
+        let hash = signed.hash();
+        if self.shares.contains_key(&hash) {
+            return false;
+        }
+        self.shares.insert(hash, signed);
+        true
+    }
+
+    /// Rotations of `realm` signed by a current admin with their pinned
+    /// key, oldest epoch first.
+    pub fn history(
+        &self,
+        realm: &InterfaceId,
+        membership: &MembershipState,
+    ) -> Vec<&SignedKeyRotation> {
+        let mut rotations: Vec<(EntryHash, &SignedKeyRotation)> = self
+            .rotations
+            .iter()
+            .filter(|(_, s)| {
+                s.rotation.realm == *realm
+                    && membership.is_admin(&s.rotation.by)
+                    && pinned(membership, &s.rotation.by, &s.verifying_key)
+            })
+            .map(|(hash, s)| (*hash, s))
+            .collect();
+        rotations.sort_by_key(|(hash, s)| (s.rotation.epoch, std::cmp::Reverse(*hash)));
+        rotations.into_iter().map(|(_, s)| s).collect()
+    }
+
+    /// The current rotation: the highest trusted epoch, the lower hash
+    /// winning a tie. `None` while the realm uses its invite-derived key.
+    pub fn current(
+        &self,
+        realm: &InterfaceId,
+        membership: &MembershipState,
+    ) -> Option<&SignedKeyRotation> {
+        self.history(realm, membership).pop()
+    }
+
+    /// Epoch the next rotation of `realm` should claim.
+    pub fn next_epoch(&self, realm: &InterfaceId, membership: &MembershipState) -> u64 {
+        self.current(realm, membership)
+            .map_or(1, |s| s.rotation.epoch + 1)
+    }
+
+    /// Epoch of the rotation that introduced the key with hash `hash`, or
+    /// 0 if no rotation did (the invite-derived key).
+    pub fn epoch_of(&self, hash: &[u8; 32]) -> u64 {
+        self.rotations
+            .values()
+            .filter(|s| s.rotation.key_hash == *hash)
+            .map(|s| s.rotation.epoch)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Shares of `rotation` wrapped to `recipient` by a current member
+    /// with their pinned key.
+    pub fn shares_for<'a>(
+        &'a self,
+        rotation: &'a EntryHash,
+        recipient: &'a MemberId,
+        membership: &'a MembershipState,
+    ) -> impl Iterator<Item = &'a KeyShare> + 'a {
+        self.shares
+            .values()
+            .filter(move |s| {
+                s.share.rotation == *rotation
+                    && s.share.recipient == *recipient
+                    && membership.is_member(&s.share.by)
+                    && pinned(membership, &s.share.by, &s.verifying_key)
+            })
+            .map(|s| &s.share)
+    }
+}
+
+impl DocumentSchema for KeyRotationDocument {
+    /// Union of rotations and shares, each signature verified.
+    fn merge(&mut self, remote: Self) {
+        for signed in remote.rotations.into_values() {
+            self.add_rotation(signed);
+        }
+        for signed in remote.shares.into_values() {
+            self.add_share(signed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Signer {
+        id: MemberId,
+        identity: PQIdentity,
+        kem: PQKemKeyPair,
+    }
+
+    impl Signer {
+        fn new(n: u8) -> Self {
+            Self {
+                id: [n; 32],
+                identity: PQIdentity::generate(),
+                kem: PQKemKeyPair::generate(),
+            }
+        }
+
+        fn rotate(
+            &self,
+            doc: &mut KeyRotationDocument,
+            epoch: u64,
+            key: &InterfaceKey,
+        ) -> EntryHash {
+            let rotation = KeyRotation {
+                realm: realm(),
+                epoch,
+                by: self.id,
+                reason: RotationReason::Manual,
+                key_hash: key_hash(key),
+                at_millis: 10,
+            };
+            let signed = SignedKeyRotation::sign(rotation, &self.identity);
+            let hash = signed.hash();
+            assert!(doc.add_rotation(signed));
+            hash
+        }
+
+        fn share(
+            &self,
+            doc: &mut KeyRotationDocument,
+            rotation: EntryHash,
+            key: &InterfaceKey,
+            to: &Signer,
+        ) {
+            let encapsulation_key = to.kem.encapsulation_key();
+            let share = KeyShare::wrap(rotation, to.id, self.id, key, &encapsulation_key).unwrap();
+            assert!(doc.add_share(SignedKeyShare::sign(share, &self.identity)));
+        }
+    }
+
+    fn realm() -> InterfaceId {
+        InterfaceId::new([7; 32])
+    }
+
+    fn membership(admins: &[&Signer], members: &[&Signer]) -> MembershipState {
+        let mut state = MembershipState::default();
+        for signer in admins.iter().chain(members) {
+            state.members.insert(signer.id);
+            state.keys.insert(signer.id, signer.identity.verifying_key_bytes());
+        }
+        state.admins = admins.iter().map(|s| s.id).collect();
+        state
+    }
+
+    #[test]
+    fn test_share_unwraps_to_rotated_key() {
+        let (admin, alice) = (Signer::new(1), Signer::new(2));
+        let state = membership(&[&admin], &[&alice]);
+        let mut doc = KeyRotationDocument::new();
+        assert_eq!(doc.next_epoch(&realm(), &state), 1);
+
+        let key = InterfaceKey::generate(realm());
+        let rotation = admin.rotate(&mut doc, 1, &key);
+        admin.share(&mut doc, rotation, &key, &alice);
+
+        let current = doc.current(&realm(), &state).unwrap();
+        assert_eq!(current.hash(), rotation);
+        assert_eq!(doc.epoch_of(&key_hash(&key)), 1);
+        assert_eq!(doc.next_epoch(&realm(), &state), 2);
+
+        let share = doc.shares_for(&rotation, &alice.id, &state).next().unwrap();
+        let opened = share.open(&alice.kem, &current.rotation.key_hash).unwrap();
+        assert_eq!(opened.as_bytes(), key.as_bytes());
+        // Wrapped to Alice only
+        assert!(share.open(&admin.kem, &current.rotation.key_hash).is_none());
+    }
+
+    #[test]
+    fn test_only_admin_rotations_count() {
+        let (admin, alice) = (Signer::new(1), Signer::new(2));
+        let state = membership(&[&admin], &[&alice]);
+        let mut doc = KeyRotationDocument::new();
+
+        alice.rotate(&mut doc, 1, &InterfaceKey::generate(realm()));
+        assert!(doc.current(&realm(), &state).is_none());
+
+        // Claiming to be the admin with another key
+        let rotation = KeyRotation {
+            realm: realm(),
+            epoch: 1,
+            by: admin.id,
+            reason: RotationReason::Manual,
+            key_hash: [0; 32],
+            at_millis: 10,
+        };
+        assert!(doc.add_rotation(SignedKeyRotation::sign(rotation, &alice.identity)));
+        assert!(doc.current(&realm(), &state).is_none());
+
+        let hash = admin.rotate(&mut doc, 1, &InterfaceKey::generate(realm()));
+        assert_eq!(doc.current(&realm(), &state).unwrap().hash(), hash);
+    }
+
+    #[test]
+    fn test_tampered_entries_rejected() {
+        let (admin, alice) = (Signer::new(1), Signer::new(2));
+        let key = InterfaceKey::generate(realm());
+        let mut doc = KeyRotationDocument::new();
+        let rotation = admin.rotate(&mut doc, 1, &key);
+
+        let mut signed = doc.rotations[&rotation].clone();
+        signed.rotation.epoch = 9;
+        assert!(!doc.add_rotation(signed));
+
+        let share =
+            KeyShare::wrap(rotation, alice.id, admin.id, &key, &alice.kem.encapsulation_key())
+                .unwrap();
+        let mut signed = SignedKeyShare::sign(share, &admin.identity);
+        signed.share.recipient = admin.id;
+        assert!(!doc.add_share(signed));
+    }
+
+    #[test]
+    fn test_wrong_key_share_ignored() {
+        let (admin, alice) = (Signer::new(1), Signer::new(2));
+        let state = membership(&[&admin], &[&alice]);
+        let mut doc = KeyRotationDocument::new();
+        let key = InterfaceKey::generate(realm());
+        let rotation = admin.rotate(&mut doc, 1, &key);
+
+        // A member wraps some other key for this rotation
+        admin.share(&mut doc, rotation, &InterfaceKey::generate(realm()), &alice);
+        let expected = key_hash(&key);
+        assert!(
+            doc.shares_for(&rotation, &alice.id, &state)
+                .all(|s| s.open(&alice.kem, &expected).is_none())
+        );
+    }
+
+    #[test]
+    fn test_highest_epoch_then_lowest_hash_wins() {
+        let (owner, admin) = (Signer::new(1), Signer::new(2));
+        let state = membership(&[&owner, &admin], &[]);
+        let mut doc = KeyRotationDocument::new();
+        owner.rotate(&mut doc, 1, &InterfaceKey::generate(realm()));
+        let a = owner.rotate(&mut doc, 2, &InterfaceKey::generate(realm()));
+        let b = admin.rotate(&mut doc, 2, &InterfaceKey::generate(realm()));
+
+        let current = doc.current(&realm(), &state).unwrap();
+        assert_eq!(current.hash(), a.min(b));
+        assert_eq!(doc.history(&realm(), &state).len(), 3);
+        assert_eq!(doc.next_epoch(&realm(), &state), 3);
+    }
+
+    #[test]
+    fn test_merge_is_union() {
+        let (admin, alice) = (Signer::new(1), Signer::new(2));
+        let state = membership(&[&admin], &[&alice]);
+        let key = InterfaceKey::generate(realm());
+        let mut remote = KeyRotationDocument::new();
+        let rotation = admin.rotate(&mut remote, 1, &key);
+        admin.share(&mut remote, rotation, &key, &alice);
+
+        let mut local = KeyRotationDocument::new();
+        local.merge(remote.clone());
+        local.merge(remote);
+        assert_eq!(local.rotations.len(), 1);
+        assert_eq!(local.shares_for(&rotation, &alice.id, &state).count(), 1);
+    }
+}
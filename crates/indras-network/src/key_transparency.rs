@@ -0,0 +1,663 @@
+//! Append-only log of member verifying keys.
+//!
+//! A member's ML-DSA verifying key is announced over gossip, so a peer in
+//! the middle could swap it without anyone noticing. A realm's
+//! [`KeyLogDocument`] records every key a member publishes as a
+//! [`SignedKeyEntry`] of `(member, verifying key, epoch)`, signed by the
+//! new key itself, so every member sees the same key history.
+//!
+//! Rules, checked when the log is audited:
+//!
+//! - A member's first trusted key is the one the membership log pinned
+//!   when they were added. An entry for anyone the log has no key for is
+//!   not trusted on first use.
+//! - An entry with any other key must be cross-signed by the member's
+//!   previous trusted key, or endorsed by a majority of the realm's other
+//!   admins.
+//! - Two trusted entries for the same member and epoch with different keys
+//!   are a conflict; neither is trusted.
+//!
+//! Entries that fail these checks never replace a trusted key, even when
+//! they claim the same epoch. They stay in the log and show up in
+//! [`KeyAudit::flagged`], so every member is warned about them.
+//!
+//! The log is informational. The membership log keeps checking each
+//! member's entries against the key their `Create` or `Add` entry pinned,
+//! so a trusted key change here does not let the new key write
+//! membership entries.
+
+use crate::document::DocumentSchema;
+use crate::member::MemberId;
+use crate::membership_log::{EntryHash, MembershipState};
+use indras_crypto::pq_identity::{PQIdentity, PQPublicIdentity, PQSignature};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Document name for the key log within a realm.
+pub const KEY_LOG_DOC: &str = "_key_log";
+
+/// Domain separation tag for key entry hashes and self-signatures.
+const KEY_ENTRY_DOMAIN: &[u8] = b"indras:keylog:v1";
+
+/// Domain separation tag for endorsements of a key entry.
+const KEY_ENDORSE_DOMAIN: &[u8] = b"indras:keylog:endorse:v1";
+
+/// A member's verifying key as of some epoch, before signing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyEntry {
+    /// The member the key belongs to.
+    pub member: MemberId,
+    /// The member's ML-DSA-65 verifying key.
+    pub verifying_key: Vec<u8>,
+    /// 0 for the member's first key, one more for each change.
+    pub epoch: u64,
+    /// When the key was published (milliseconds since epoch).
+    pub at_millis: u64,
+}
+
+impl KeyEntry {
+    /// Hash identifying this entry.
+    pub fn hash(&self) -> EntryHash {
+        *blake3::hash(&self.signing_bytes()).as_bytes()
+    }
+
+    /// Domain-separated bytes the hash and self-signature cover.
+    fn signing_bytes(&self) -> Vec<u8> {
+        let mut bytes = KEY_ENTRY_DOMAIN.to_vec();
+        bytes.extend(postcard::to_allocvec(self).unwrap_or_default());
+        bytes
+    }
+}
+
+/// A signature vouching for a key entry: by the member's previous key
+/// (a cross-signature), or by an admin.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyEndorsement {
+    /// Member who signed.
+    pub signer: MemberId,
+    /// Signer's ML-DSA-65 verifying key.
+    pub verifying_key: Vec<u8>,
+    /// Signature over the entry hash.
+    pub signature: Vec<u8>,
+}
+
+impl KeyEndorsement {
+    /// Endorse the entry with hash `entry` as `signer`.
+    pub fn sign(signer: MemberId, entry: &EntryHash, identity: &PQIdentity) -> Self {
+        let signature = identity.sign(&endorse_bytes(entry));
+        Self {
+            signer,
+            verifying_key: identity.verifying_key_bytes(),
+            signature: signature.to_bytes().to_vec(),
+        }
+    }
+
+    /// Whether the signature is valid for the entry with hash `entry`.
+    pub fn verify(&self, entry: &EntryHash) -> bool {
+        verify_signature(&self.verifying_key, &endorse_bytes(entry), &self.signature)
+    }
+}
+
+fn endorse_bytes(entry: &EntryHash) -> Vec<u8> {
+    let mut bytes = KEY_ENDORSE_DOMAIN.to_vec();
+    bytes.extend_from_slice(entry);
+    bytes
+}
+
+fn verify_signature(key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    let (Ok(key), Ok(signature)) = (
+        PQPublicIdentity::from_bytes(key),
+        PQSignature::from_bytes(signature.to_vec()),
+    ) else {
+        return false;
+    };
+    key.verify(message, &signature)
+}
+
+/// A key entry signed by the key it announces, with its endorsements.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedKeyEntry {
+    /// The key.
+    pub entry: KeyEntry,
+    /// Signature by `entry.verifying_key`, proving possession.
+    pub signature: Vec<u8>,
+    /// Cross-signatures and admin endorsements.
+    pub endorsements: Vec<KeyEndorsement>,
+}
+
+impl SignedKeyEntry {
+    /// Sign `entry` with the identity holding its key.
+    pub fn sign(entry: KeyEntry, identity: &PQIdentity) -> Self {
+        let signature = identity.sign(&entry.signing_bytes());
+        Self {
+            entry,
+            signature: signature.to_bytes().to_vec(),
+            endorsements: Vec::new(),
+        }
+    }
+
+    /// Hash identifying the entry.
+    pub fn hash(&self) -> EntryHash {
+        self.entry.hash()
+    }
+
+    /// Whether the entry is signed by the key it announces.
+    pub fn verify(&self) -> bool {
+        verify_signature(
+            &self.entry.verifying_key,
+            &self.entry.signing_bytes(),
+            &self.signature,
+        )
+    }
+
+    /// Cross-sign with the member's previous identity.
+    pub fn cross_sign(&mut self, previous: &PQIdentity) -> bool {
+        let endorsement = KeyEndorsement::sign(self.entry.member, &self.hash(), previous);
+        self.add_endorsement(endorsement)
+    }
+
+    /// Add a valid endorsement not already present.
+    fn add_endorsement(&mut self, endorsement: KeyEndorsement) -> bool {
+        if self.endorsed_by(&endorsement.signer, &endorsement.verifying_key)
+            || !endorsement.verify(&self.hash())
+        {
+            return false;
+        }
+        self.endorsements.push(endorsement);
+        true
+    }
+
+    /// Whether `signer` endorsed the entry with `key`.
+    fn endorsed_by(&self, signer: &MemberId, key: &[u8]) -> bool {
+        self.endorsements
+            .iter()
+            .any(|e| e.signer == *signer && e.verifying_key == key)
+    }
+}
+
+/// Whether a key entry is trusted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyChangeStatus {
+    /// The member's first key, matching the key their `Create` or `Add`
+    /// entry pinned.
+    Initial,
+    /// Cross-signed by the member's previous trusted key.
+    CrossSigned,
+    /// Endorsed by a majority of the realm's other admins.
+    AdminQuorum {
+        /// Admins who endorsed it.
+        endorsements: usize,
+    },
+    /// Neither cross-signed nor endorsed by enough admins. The key may
+    /// have been substituted.
+    Unverified,
+    /// Another trusted entry claims a different key for the same member
+    /// and epoch.
+    Conflict,
+}
+
+impl KeyChangeStatus {
+    /// Whether the key is trusted.
+    pub fn is_trusted(&self) -> bool {
+        matches!(
+            self,
+            Self::Initial | Self::CrossSigned | Self::AdminQuorum { .. }
+        )
+    }
+}
+
+/// One entry of an audited key log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyChangeRecord {
+    /// Hash of the entry.
+    pub hash: EntryHash,
+    /// The signed entry.
+    pub entry: SignedKeyEntry,
+    /// Whether the entry is trusted, and why.
+    pub status: KeyChangeStatus,
+}
+
+/// Result of auditing a key log.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyAudit {
+    /// Every entry, oldest first.
+    pub records: Vec<KeyChangeRecord>,
+    /// Each member's latest trusted key.
+    pub current: BTreeMap<MemberId, Vec<u8>>,
+}
+
+impl KeyAudit {
+    /// Entries members should be warned about.
+    pub fn flagged(&self) -> impl Iterator<Item = &KeyChangeRecord> {
+        self.records.iter().filter(|r| !r.status.is_trusted())
+    }
+
+    /// A member's latest trusted key.
+    pub fn current_key(&self, member: &MemberId) -> Option<&[u8]> {
+        self.current.get(member).map(Vec::as_slice)
+    }
+
+    /// Status of a member's most recent entry, if they published any.
+    pub fn latest_status(&self, member: &MemberId) -> Option<KeyChangeStatus> {
+        self.records
+            .iter()
+            .filter(|r| r.entry.entry.member == *member)
+            .max_by_key(|r| (r.entry.entry.epoch, r.hash))
+            .map(|r| r.status)
+    }
+}
+
+/// Endorsements needed from `admins` other admins: a strict majority.
+pub fn admin_quorum(admins: usize) -> usize {
+    admins / 2 + 1
+}
+
+/// CRDT document holding a realm's key log.
+///
+/// # Example
+///
+/// ```ignore
+/// realm.publish_key().await?;
+///
+/// for record in realm.key_audit().await?.flagged() {
+///     println!("unverified key for {:?} at epoch {}",
+///         record.entry.entry.member, record.entry.entry.epoch);
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KeyLogDocument {
+    /// Signed entries keyed by hash.
+    pub entries: BTreeMap<EntryHash, SignedKeyEntry>,
+}
+
+impl KeyLogDocument {
+    /// Create an empty log.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the log has no entries yet.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// A member's entry with the highest epoch.
+    pub fn latest(&self, member: &MemberId) -> Option<&SignedKeyEntry> {
+        self.entries
+            .iter()
+            .filter(|(_, s)| s.entry.member == *member)
+            .max_by_key(|(hash, s)| (s.entry.epoch, **hash))
+            .map(|(_, s)| s)
+    }
+
+    /// Build a member's next entry.
+    pub fn next_entry(&self, member: MemberId, verifying_key: Vec<u8>, at_millis: u64) -> KeyEntry {
+        KeyEntry {
+            member,
+            verifying_key,
+            epoch: self.latest(&member).map_or(0, |s| s.entry.epoch + 1),
+            at_millis,
+        }
+    }
+
+    /// Append a signed entry, keeping its valid endorsements.
+    ///
+    /// An entry already present gains any endorsements it lacked. Returns
+    /// `false` if the self-signature is invalid or nothing was added.
+    pub fn append(&mut self, mut signed: SignedKeyEntry) -> bool {
+        if !signed.verify() {
+            return false;
+        }
+        let hash = signed.hash();
+        let endorsements = std::mem::take(&mut signed.endorsements);
+        let mut added = false;
+        let existing = self.entries.entry(hash).or_insert_with(|| {
+            added = true;
+            signed
+        });
+        for endorsement in endorsements {
+            added |= existing.add_endorsement(endorsement);
+        }
+        added
+    }
+
+    /// Add an endorsement to the entry with hash `entry`.
+    ///
+    /// Returns `false` if the entry is unknown, the signature is invalid,
+    /// or the signer already endorsed it with that key.
+    pub fn endorse(&mut self, entry: &EntryHash, endorsement: KeyEndorsement) -> bool {
+        self.entries
+            .get_mut(entry)
+            .is_some_and(|signed| signed.add_endorsement(endorsement))
+    }
+
+    /// Check every entry against the rules, given the realm's membership
+    /// according to the signed membership log.
+    pub fn audit(&self, membership: &MembershipState) -> KeyAudit {
+        // Admin endorsements count with the admin's current key, which
+        // may itself come from the log: replay until the keys settle.
+        let mut audit = self.replay(membership, &membership.keys);
+        for _ in 0..self.entries.len() {
+            let next = self.replay(membership, &audit.current);
+            if next.current == audit.current {
+                return next;
+            }
+            audit = next;
+        }
+        audit
+    }
+
+    /// Replay each member's entries in epoch order.
+    fn replay(
+        &self,
+        membership: &MembershipState,
+        admin_keys: &BTreeMap<MemberId, Vec<u8>>,
+    ) -> KeyAudit {
+        let mut by_member: BTreeMap<MemberId, EpochEntries<'_>> = BTreeMap::new();
+        for (hash, signed) in &self.entries {
+            by_member
+                .entry(signed.entry.member)
+                .or_default()
+                .entry(signed.entry.epoch)
+                .or_default()
+                .push((hash, signed));
+        }
+
+        let mut current = membership.keys.clone();
+        let mut records = Vec::new();
+        for (member, epochs) in by_member {
+            let mut trusted = membership.keys.get(&member).cloned();
+            let mut first = true;
+            for entries in epochs.into_values() {
+                let statuses: Vec<KeyChangeStatus> = entries
+                    .iter()
+                    .map(|(_, signed)| {
+                        entry_status(signed, trusted.as_deref(), first, membership, admin_keys)
+                    })
+                    .collect();
+                // Only trusted entries compete; an unverified one can't
+                // knock out the member's real key by claiming its epoch.
+                let accepted: BTreeSet<&Vec<u8>> = entries
+                    .iter()
+                    .zip(&statuses)
+                    .filter(|(_, status)| status.is_trusted())
+                    .map(|((_, signed), _)| &signed.entry.verifying_key)
+                    .collect();
+                let conflict = accepted.len() > 1;
+                if conflict {
+                    trusted = None;
+                } else if let Some(key) = accepted.first() {
+                    trusted = Some((*key).clone());
+                }
+                for ((hash, signed), status) in entries.into_iter().zip(statuses) {
+                    let status = if conflict && status.is_trusted() {
+                        KeyChangeStatus::Conflict
+                    } else {
+                        status
+                    };
+                    records.push(KeyChangeRecord {
+                        hash: *hash,
+                        entry: signed.clone(),
+                        status,
+                    });
+                }
+                first = false;
+            }
+            match trusted {
+                Some(key) => current.insert(member, key),
+                None => current.remove(&member),
+            };
+        }
+        records.sort_by_key(|r| (r.entry.entry.at_millis, r.hash));
+        KeyAudit { records, current }
+    }
+}
+
+/// A member's entries grouped by epoch.
+type EpochEntries<'a> = BTreeMap<u64, Vec<(&'a EntryHash, &'a SignedKeyEntry)>>;
+
+/// Status of an entry without a conflicting sibling.
+fn entry_status(
+    signed: &SignedKeyEntry,
+    trusted: Option<&[u8]>,
+    first: bool,
+    membership: &MembershipState,
+    admin_keys: &BTreeMap<MemberId, Vec<u8>>,
+) -> KeyChangeStatus {
+    let member = signed.entry.member;
+    let key = signed.entry.verifying_key.as_slice();
+    match trusted {
+        Some(previous) if previous == key && first => return KeyChangeStatus::Initial,
+        Some(previous) if previous == key || signed.endorsed_by(&member, previous) => {
+            return KeyChangeStatus::CrossSigned;
+        }
+        _ => {}
+    }
+
+    let admins: Vec<&MemberId> = membership.admins.iter().filter(|a| **a != member).collect();
+    let endorsements = admins
+        .iter()
+        .filter(|admin| {
+            admin_keys
+                .get(*admin)
+                .is_some_and(|k| signed.endorsed_by(admin, k))
+        })
+        .count();
+    if !admins.is_empty() && endorsements >= admin_quorum(admins.len()) {
+        KeyChangeStatus::AdminQuorum { endorsements }
+    } else {
+        KeyChangeStatus::Unverified
+    }
+}
+
+impl DocumentSchema for KeyLogDocument {
+    /// Union of entries and their endorsements, each signature verified.
+    fn merge(&mut self, remote: Self) {
+        for signed in remote.entries.into_values() {
+            self.append(signed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Signer {
+        id: MemberId,
+        identity: PQIdentity,
+    }
+
+    impl Signer {
+        fn new(n: u8) -> Self {
+            Self {
+                id: [n; 32],
+                identity: PQIdentity::generate(),
+            }
+        }
+
+        fn key(&self) -> Vec<u8> {
+            self.identity.verifying_key_bytes()
+        }
+
+        /// Publish `identity`'s key as this member's next entry.
+        fn publish(&self, doc: &mut KeyLogDocument, identity: &PQIdentity) -> EntryHash {
+            let entry = doc.next_entry(self.id, identity.verifying_key_bytes(), 10);
+            let signed = SignedKeyEntry::sign(entry, identity);
+            let hash = signed.hash();
+            assert!(doc.append(signed));
+            hash
+        }
+
+        fn endorse(&self, doc: &mut KeyLogDocument, entry: &EntryHash) -> bool {
+            doc.endorse(entry, KeyEndorsement::sign(self.id, entry, &self.identity))
+        }
+    }
+
+    fn membership(admins: &[&Signer], members: &[&Signer]) -> MembershipState {
+        let mut state = MembershipState::default();
+        for signer in admins.iter().chain(members) {
+            state.members.insert(signer.id);
+            state.keys.insert(signer.id, signer.key());
+        }
+        state.admins = admins.iter().map(|s| s.id).collect();
+        state
+    }
+
+    #[test]
+    fn test_initial_and_cross_signed_keys() {
+        let alice = Signer::new(1);
+        let state = membership(&[], &[&alice]);
+        let mut doc = KeyLogDocument::new();
+        alice.publish(&mut doc, &alice.identity);
+
+        let rotated = PQIdentity::generate();
+        let entry = doc.next_entry(alice.id, rotated.verifying_key_bytes(), 20);
+        assert_eq!(entry.epoch, 1);
+        let mut signed = SignedKeyEntry::sign(entry, &rotated);
+        assert!(signed.cross_sign(&alice.identity));
+        assert!(doc.append(signed));
+
+        let audit = doc.audit(&state);
+        let statuses: Vec<_> = audit.records.iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            vec![KeyChangeStatus::Initial, KeyChangeStatus::CrossSigned]
+        );
+        assert_eq!(
+            audit.current_key(&alice.id),
+            Some(rotated.verifying_key_bytes().as_slice())
+        );
+        assert_eq!(audit.flagged().count(), 0);
+    }
+
+    #[test]
+    fn test_substituted_key_flagged_until_admin_quorum() {
+        let (owner, admin, alice) = (Signer::new(1), Signer::new(2), Signer::new(3));
+        let state = membership(&[&owner, &admin], &[&alice]);
+        let mut doc = KeyLogDocument::new();
+        alice.publish(&mut doc, &alice.identity);
+
+        // A new key nobody vouched for
+        let hash = alice.publish(&mut doc, &PQIdentity::generate());
+        let audit = doc.audit(&state);
+        assert_eq!(
+            audit.latest_status(&alice.id),
+            Some(KeyChangeStatus::Unverified)
+        );
+        assert_eq!(audit.flagged().count(), 1);
+        assert_eq!(audit.current_key(&alice.id), Some(alice.key().as_slice()));
+
+        // One of two admins is not a majority
+        assert!(owner.endorse(&mut doc, &hash));
+        assert!(!owner.endorse(&mut doc, &hash));
+        assert!(
+            !doc.audit(&state)
+                .latest_status(&alice.id)
+                .unwrap()
+                .is_trusted()
+        );
+
+        // Members who are not admins don't count
+        assert!(Signer::new(4).endorse(&mut doc, &hash));
+        assert!(
+            !doc.audit(&state)
+                .latest_status(&alice.id)
+                .unwrap()
+                .is_trusted()
+        );
+
+        assert!(admin.endorse(&mut doc, &hash));
+        assert_eq!(
+            doc.audit(&state).latest_status(&alice.id),
+            Some(KeyChangeStatus::AdminQuorum { endorsements: 2 })
+        );
+    }
+
+    #[test]
+    fn test_pinned_key_mismatch_and_conflict() {
+        let alice = Signer::new(1);
+        let state = membership(&[], &[&alice]);
+
+        // First entry differs from the key the membership log pinned
+        let mut doc = KeyLogDocument::new();
+        alice.publish(&mut doc, &PQIdentity::generate());
+        assert_eq!(
+            doc.audit(&state).latest_status(&alice.id),
+            Some(KeyChangeStatus::Unverified)
+        );
+
+        // An unvouched key claiming the same epoch doesn't displace hers
+        let mut doc = KeyLogDocument::new();
+        let mut fork = doc.clone();
+        alice.publish(&mut doc, &alice.identity);
+        alice.publish(&mut fork, &PQIdentity::generate());
+        doc.merge(fork);
+        let audit = doc.audit(&state);
+        assert_eq!(audit.flagged().count(), 1);
+        let hers = audit
+            .records
+            .iter()
+            .find(|r| r.entry.entry.verifying_key == alice.key())
+            .unwrap();
+        assert_eq!(hers.status, KeyChangeStatus::Initial);
+        assert_eq!(audit.current_key(&alice.id), Some(alice.key().as_slice()));
+
+        // Two keys for the same epoch, both cross-signed by her old key
+        let mut fork = doc.clone();
+        for doc in [&mut doc, &mut fork] {
+            let rotated = PQIdentity::generate();
+            let entry = doc.next_entry(alice.id, rotated.verifying_key_bytes(), 20);
+            let mut signed = SignedKeyEntry::sign(entry, &rotated);
+            assert!(signed.cross_sign(&alice.identity));
+            assert!(doc.append(signed));
+        }
+        doc.merge(fork);
+        let audit = doc.audit(&state);
+        let conflicts = audit
+            .records
+            .iter()
+            .filter(|r| r.status == KeyChangeStatus::Conflict)
+            .count();
+        assert_eq!(conflicts, 2);
+        assert_eq!(audit.current_key(&alice.id), None);
+    }
+
+    #[test]
+    fn test_unpinned_member_not_trusted_on_first_use() {
+        let (alice, mallory) = (Signer::new(1), Signer::new(2));
+        let state = membership(&[], &[&alice]);
+        let mut doc = KeyLogDocument::new();
+        mallory.publish(&mut doc, &mallory.identity);
+
+        let audit = doc.audit(&state);
+        assert_eq!(
+            audit.latest_status(&mallory.id),
+            Some(KeyChangeStatus::Unverified)
+        );
+        assert_eq!(audit.current_key(&mallory.id), None);
+    }
+
+    #[test]
+    fn test_forged_entries_and_endorsements_rejected() {
+        let (owner, alice) = (Signer::new(1), Signer::new(2));
+        let mut doc = KeyLogDocument::new();
+
+        // Self-signature by a key other than the one announced
+        let entry = doc.next_entry(alice.id, alice.key(), 10);
+        assert!(!doc.append(SignedKeyEntry::sign(entry, &owner.identity)));
+
+        let hash = alice.publish(&mut doc, &alice.identity);
+        let mut forged = KeyEndorsement::sign(owner.id, &hash, &owner.identity);
+        forged.verifying_key = alice.key();
+        assert!(!doc.endorse(&hash, forged));
+        assert!(!owner.endorse(&mut doc, &[0; 32]));
+
+        // Endorsements merge into the existing entry
+        let mut remote = doc.clone();
+        assert!(owner.endorse(&mut remote, &hash));
+        doc.merge(remote);
+        assert_eq!(doc.entries.len(), 1);
+        assert_eq!(doc.entries[&hash].endorsements.len(), 1);
+    }
+}
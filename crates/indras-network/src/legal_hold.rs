@@ -0,0 +1,416 @@
+//! Legal holds on chat messages, for community moderation.
+//!
+//! Moderators can place a hold on a flagged message so it survives
+//! tombstone compaction even after its author deletes it. Holds live in a
+//! [`LegalHoldDocument`] inside the realm, so every member's node learns
+//! which messages must be kept, and moderators see each other's holds as
+//! [`HoldEvent`]s as the document syncs.
+//!
+//! A moderator can export the held messages as a [`HoldExport`]: each
+//! message with its full edit history and its author's signature, wrapped
+//! in a signature from the exporting moderator. Anyone holding the export
+//! can check both without access to the realm.
+//!
+//! The moderator set starts empty; the first member to appoint a moderator
+//! becomes one. After that only moderators may appoint moderators or place,
+//! release, or export holds. Entries written by non-moderators are dropped
+//! on merge. Like guest scopes, this is enforced by honest clients and is
+//! not a cryptographic boundary; the export signatures are.
+
+use crate::chat_message::{ChatMessageId, EditableChatMessage};
+use crate::document::DocumentSchema;
+use crate::member::MemberId;
+use crate::network::RealmId;
+use indras_crypto::pq_identity::{PQIdentity, PQPublicIdentity, PQSignature};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Document name for legal holds within a realm.
+pub const LEGAL_HOLD_DOC: &str = "_legal_hold";
+
+/// Domain separation tag for moderator export signatures.
+const EXPORT_DOMAIN: &[u8] = b"indras/legal-hold-export/v1";
+
+/// A hold on one message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Hold {
+    /// Moderator who placed the hold.
+    pub placed_by: MemberId,
+    /// Why the message was flagged.
+    pub reason: String,
+    /// When the hold was placed (milliseconds since epoch).
+    pub placed_at_millis: u64,
+    /// Moderator who released the hold, if it was released.
+    pub released_by: Option<MemberId>,
+    /// When this hold was last changed (milliseconds since epoch).
+    pub updated_at_millis: u64,
+}
+
+impl Hold {
+    /// Whether the hold still protects its message.
+    pub fn is_active(&self) -> bool {
+        self.released_by.is_none()
+    }
+}
+
+/// CRDT document of a realm's moderators and legal holds.
+///
+/// # Example
+///
+/// ```ignore
+/// realm.appoint_moderator(my_id).await?;
+/// realm.place_legal_hold(&message_id, "harassment report #12").await?;
+/// let export = realm.export_held_messages().await?;
+/// assert!(export.verify());
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LegalHoldDocument {
+    /// Members allowed to manage holds.
+    pub moderators: BTreeSet<MemberId>,
+    /// Holds keyed by chat message.
+    pub holds: BTreeMap<ChatMessageId, Hold>,
+}
+
+impl LegalHoldDocument {
+    /// Create an empty hold list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether a member may manage holds.
+    pub fn is_moderator(&self, member: &MemberId) -> bool {
+        self.moderators.contains(member)
+    }
+
+    /// Appoint a moderator.
+    ///
+    /// While no moderator exists, `by` is appointed along with `member`.
+    /// Returns `false` if `by` may not appoint or `member` already was one.
+    pub fn appoint(&mut self, by: MemberId, member: MemberId) -> bool {
+        if self.moderators.is_empty() {
+            self.moderators.insert(by);
+        } else if !self.is_moderator(&by) {
+            return false;
+        }
+        self.moderators.insert(member)
+    }
+
+    /// Place a hold on a message.
+    ///
+    /// Returns `false` if `by` is not a moderator or the message is
+    /// already held.
+    pub fn place(
+        &mut self,
+        by: MemberId,
+        message_id: ChatMessageId,
+        reason: String,
+        now_millis: u64,
+    ) -> bool {
+        if !self.is_moderator(&by) || self.is_held(&message_id) {
+            return false;
+        }
+        self.holds.insert(
+            message_id,
+            Hold {
+                placed_by: by,
+                reason,
+                placed_at_millis: now_millis,
+                released_by: None,
+                updated_at_millis: now_millis,
+            },
+        );
+        true
+    }
+
+    /// Release a hold so the message may be pruned again.
+    ///
+    /// Returns `false` if `by` is not a moderator or the message is not held.
+    pub fn release(&mut self, by: MemberId, message_id: &str, now_millis: u64) -> bool {
+        if !self.is_moderator(&by) {
+            return false;
+        }
+        match self.holds.get_mut(message_id).filter(|h| h.is_active()) {
+            Some(hold) => {
+                hold.released_by = Some(by);
+                hold.updated_at_millis = now_millis;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Whether a message is under an active hold.
+    pub fn is_held(&self, message_id: &str) -> bool {
+        self.holds.get(message_id).is_some_and(Hold::is_active)
+    }
+
+    /// Ids of all messages under an active hold.
+    pub fn held_ids(&self) -> BTreeSet<ChatMessageId> {
+        self.active().map(|(id, _)| id.clone()).collect()
+    }
+
+    /// Active holds.
+    pub fn active(&self) -> impl Iterator<Item = (&ChatMessageId, &Hold)> {
+        self.holds.iter().filter(|(_, h)| h.is_active())
+    }
+
+    /// Whether a hold was written by moderators only.
+    fn is_authorized(&self, hold: &Hold) -> bool {
+        self.is_moderator(&hold.placed_by)
+            && hold.released_by.is_none_or(|by| self.is_moderator(&by))
+    }
+}
+
+impl DocumentSchema for LegalHoldDocument {
+    /// Moderator union, then per-message LWW over holds written by moderators.
+    fn merge(&mut self, remote: Self) {
+        self.moderators.extend(remote.moderators);
+        for (id, hold) in remote.holds {
+            if !self.is_authorized(&hold) {
+                continue;
+            }
+            match self.holds.get(&id) {
+                Some(local) if local.updated_at_millis >= hold.updated_at_millis => {}
+                _ => {
+                    self.holds.insert(id, hold);
+                }
+            }
+        }
+    }
+}
+
+/// A change to a realm's holds, as seen by a moderator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HoldEvent {
+    /// A member became a moderator.
+    ModeratorAppointed {
+        /// The new moderator.
+        member: MemberId,
+    },
+    /// A message was put under hold.
+    Placed {
+        /// The held message.
+        message_id: ChatMessageId,
+        /// The hold as placed.
+        hold: Hold,
+    },
+    /// A hold was released.
+    Released {
+        /// The released message.
+        message_id: ChatMessageId,
+        /// Moderator who released it.
+        by: MemberId,
+    },
+}
+
+impl HoldEvent {
+    /// Hold events between two states of a hold document.
+    pub fn diff(old: &LegalHoldDocument, new: &LegalHoldDocument) -> Vec<HoldEvent> {
+        let mut events: Vec<HoldEvent> = new
+            .moderators
+            .difference(&old.moderators)
+            .map(|member| HoldEvent::ModeratorAppointed { member: *member })
+            .collect();
+
+        for (id, hold) in &new.holds {
+            let before = old.holds.get(id);
+            if hold.is_active() && !before.is_some_and(Hold::is_active) {
+                events.push(HoldEvent::Placed {
+                    message_id: id.clone(),
+                    hold: hold.clone(),
+                });
+            }
+            if let Some(by) = hold.released_by {
+                if before.is_none_or(Hold::is_active) {
+                    events.push(HoldEvent::Released {
+                        message_id: id.clone(),
+                        by,
+                    });
+                }
+            }
+        }
+        events
+    }
+}
+
+/// One held message in an export.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HeldMessage {
+    /// The message, with edit history and author signature.
+    pub message: EditableChatMessage,
+    /// The hold that preserved it.
+    pub hold: Hold,
+}
+
+impl HeldMessage {
+    /// Whether the message's author signature is valid.
+    pub fn authorship_verified(&self) -> bool {
+        self.message.verify_authorship()
+    }
+}
+
+/// Held messages exported by a moderator, signed for evidence.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HoldExport {
+    /// Realm the messages were held in.
+    pub realm_id: RealmId,
+    /// Moderator who made the export.
+    pub exported_by: MemberId,
+    /// When the export was made (milliseconds since epoch).
+    pub exported_at_millis: u64,
+    /// The held messages, oldest first.
+    pub messages: Vec<HeldMessage>,
+    /// Exporting moderator's ML-DSA-65 verifying key.
+    pub exporter_vk: Vec<u8>,
+    /// Moderator's signature over the export.
+    pub signature: Vec<u8>,
+}
+
+impl HoldExport {
+    /// Build and sign an export.
+    pub fn sign(
+        realm_id: RealmId,
+        exported_by: MemberId,
+        messages: Vec<HeldMessage>,
+        exported_at_millis: u64,
+        identity: &PQIdentity,
+    ) -> Self {
+        let mut export = Self {
+            realm_id,
+            exported_by,
+            exported_at_millis,
+            messages,
+            exporter_vk: identity.verifying_key_bytes(),
+            signature: Vec::new(),
+        };
+        export.signature = identity.sign(&export.signing_bytes()).to_bytes().to_vec();
+        export
+    }
+
+    /// Whether the moderator's signature over the export is valid.
+    ///
+    /// Authorship of each message is checked separately with
+    /// [`HeldMessage::authorship_verified`], since messages from clients
+    /// that do not sign are still worth preserving.
+    pub fn verify(&self) -> bool {
+        let (Ok(vk), Ok(sig)) = (
+            PQPublicIdentity::from_bytes(&self.exporter_vk),
+            PQSignature::from_bytes(self.signature.clone()),
+        ) else {
+            return false;
+        };
+        vk.verify(&self.signing_bytes(), &sig)
+    }
+
+    /// Canonical bytes the moderator signs.
+    ///
+    /// Reactions are left out: they are not evidence and their map order
+    /// is not stable across a round trip.
+    fn signing_bytes(&self) -> Vec<u8> {
+        let messages: Vec<_> = self
+            .messages
+            .iter()
+            .map(|held| {
+                let m = &held.message;
+                (
+                    &m.id,
+                    &m.author,
+                    &m.author_id,
+                    m.created_at,
+                    &m.current_content,
+                    &m.versions,
+                    m.is_deleted,
+                    &m.authorship,
+                    &held.hold,
+                )
+            })
+            .collect();
+        let body = (
+            EXPORT_DOMAIN,
+            &self.realm_id,
+            &self.exported_by,
+            self.exported_at_millis,
+            &self.exporter_vk,
+            messages,
+        );
+        postcard::to_allocvec(&body).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOD: MemberId = [1u8; 32];
+    const OTHER: MemberId = [2u8; 32];
+
+    fn moderated() -> LegalHoldDocument {
+        let mut doc = LegalHoldDocument::new();
+        assert!(doc.appoint(MOD, MOD));
+        doc
+    }
+
+    #[test]
+    fn test_only_moderators_hold() {
+        let mut doc = moderated();
+        assert!(!doc.appoint(OTHER, OTHER));
+        assert!(!doc.place(OTHER, "m1".into(), "spam".into(), 10));
+
+        assert!(doc.place(MOD, "m1".into(), "spam".into(), 10));
+        assert!(!doc.place(MOD, "m1".into(), "again".into(), 20));
+        assert!(doc.is_held("m1"));
+
+        assert!(!doc.release(OTHER, "m1", 30));
+        assert!(doc.release(MOD, "m1", 30));
+        assert!(doc.held_ids().is_empty());
+    }
+
+    #[test]
+    fn test_merge_drops_unauthorized_holds() {
+        let mut a = moderated();
+        let mut b = a.clone();
+        b.place(MOD, "m1".into(), "spam".into(), 10);
+        b.holds.insert(
+            "m2".into(),
+            Hold {
+                placed_by: OTHER,
+                reason: "forged".into(),
+                placed_at_millis: 10,
+                released_by: None,
+                updated_at_millis: 10,
+            },
+        );
+
+        let before = a.clone();
+        a.merge(b);
+        assert_eq!(a.held_ids(), BTreeSet::from(["m1".to_string()]));
+
+        let events = HoldEvent::diff(&before, &a);
+        assert!(matches!(&events[..], [HoldEvent::Placed { message_id, .. }] if message_id == "m1"));
+    }
+
+    #[test]
+    fn test_export_signature() {
+        let mut doc = moderated();
+        doc.place(MOD, "m1".into(), "spam".into(), 10);
+        let author = PQIdentity::generate();
+        let message = EditableChatMessage::new_text(
+            "m1".into(), "realm".into(), "zephyr".into(), "Hello".into(), 5,
+        )
+        .signed_by(&author);
+
+        let moderator = PQIdentity::generate();
+        let mut export = HoldExport::sign(
+            indras_core::InterfaceId::new([9u8; 32]),
+            MOD,
+            vec![HeldMessage { message, hold: doc.holds["m1"].clone() }],
+            100,
+            &moderator,
+        );
+        assert!(export.verify());
+        assert!(export.messages[0].authorship_verified());
+
+        export.messages[0].hold.reason = "edited".into();
+        assert!(!export.verify());
+    }
+}
@@ -101,32 +101,70 @@
 // Modules — generic P2P platform SDK
 pub mod artifact;
 pub mod access;
+#[cfg(unix)]
+pub mod agent_host;
+pub mod announcement;
+pub mod anonymity;
+pub mod archive;
+pub mod background;
 pub mod encryption;
 pub mod artifact_index;
 pub mod artifact_recovery;
 pub mod artifact_sync;
+pub mod calendar;
 pub mod chat_message;
 pub mod config;
+pub mod contact_invite;
+pub mod contact_verification;
 pub mod contacts;
+pub mod device_pairing;
+pub mod digest;
 pub mod direct_connect;
 pub mod document;
+pub mod document_budget;
 pub mod document_registry;
+pub mod email_gateway;
 pub mod encounter;
 pub mod error;
 pub mod escape;
+pub mod event_bus;
+pub mod event_feed;
+pub mod geo;
+pub mod guest_access;
 pub mod home_realm;
 pub mod identity_code;
 pub mod invite;
+pub mod key_rotation;
+pub mod key_transparency;
+pub mod legal_hold;
+pub mod link_preview;
 pub mod member;
+pub mod membership_log;
 pub mod message;
+pub mod mirror;
+pub mod moderation;
 pub mod network;
+pub mod notifications;
 pub mod peering;
+pub mod profile;
+pub mod pruning;
 pub mod read_tracker;
 pub mod realm;
 pub mod realm_alias;
+pub mod realm_organization;
+pub mod realm_restructure;
+pub mod rehearsal;
+pub mod security_info;
 pub mod sentiment;
+pub mod settings;
+pub mod snapshot;
+pub mod spam;
+pub mod sticker_pack;
 pub mod stream;
 pub mod system_event;
+pub mod time_series;
+pub mod tombstone;
+pub mod translation;
 pub(crate) mod util;
 pub mod world_view;
 
@@ -140,33 +178,143 @@ pub use access::{
 pub use artifact_index::{ArtifactIndex, GeoLocation, HomeArtifactEntry};
 pub use artifact_recovery::{ArtifactRecoveryRequest, ArtifactRecoveryResponse, RecoverableArtifact, RecoveryManifest};
 pub use chat_message::{
-    ChatAck, ChatAckDocument, ChatDelta, ChatMessageId, ChatMessageVersion, DeliveryStatus,
-    EditableChatMessage, EditableMessageType, RealmChatDocument,
+    Authorship, ChatAck, ChatAckDocument, ChatCursor, ChatDelta, ChatMessageId, ChatMessageVersion,
+    ChatPage, DeliveryStatus, EditableChatMessage, EditableMessageType, PollBallot, PollOptionTally,
+    PollTally, RealmChatDocument, MAX_INLINE_IMAGE_SIZE,
 };
 pub use config::{NetworkBuilder, NetworkConfig, Preset};
+pub use contact_invite::{ContactInvite, ContactInviteMessage, InviteEvent, CONTACT_INVITE_TYPE_ID};
+pub use contact_verification::{
+    ContactVerificationDocument, SafetyNumber, VerificationMethod, VerificationRecord,
+    VerificationStatus, CONTACT_VERIFICATION_DOC,
+};
 pub use contacts::{ContactEntry, ContactStatus, ContactsDocument, ContactsRealm};
+pub use digest::{
+    DefaultDigestClassifier, DefaultDigestTemplate, DigestClassifier, DigestGenerator, DigestItem,
+    DigestTemplate, RealmDigest, DIGEST_EVENT_TYPE,
+};
 pub use direct_connect::{KeyExchangeStatus, PendingKeyExchange};
 pub use artifact_sync::{artifact_interface_id, artifact_key_seed, ArtifactSyncRegistry};
+pub use device_pairing::{
+    DevicePairing, DevicePairingDocument, LinkedDevice, LinkedDevicesDocument, PairingCode,
+    PairingGrant, PairingRequest, DEVICE_PAIRING_DOC, LINKED_DEVICES_DOC,
+};
 pub use encounter::{EncounterExchangePayload, EncounterHandle};
 pub use identity_code::IdentityCode;
+pub use calendar::{
+    CalendarDocument, CalendarEntry, CalendarEntryId, CalendarExporter, IcsEvent, IcsFeed,
+    CALENDAR_DOC,
+};
 pub use document::{Document, DocumentChange, DocumentSchema};
+pub use document_budget::{
+    BudgetAlert, BudgetExceeded, DocumentBudget, DocumentBudgets, DocumentSplit, DocumentStats,
+    DEFAULT_MAX_DOCUMENT_BYTES, DEFAULT_MAX_DOCUMENT_CHANGES,
+};
+pub use email_gateway::{
+    EmailGateway, EmailGatewayConfig, EmailNotify, EmailSubscriber, InboundEmail, MailTransport,
+    OutgoingEmail, SendmailTransport,
+};
+#[cfg(unix)]
+pub use agent_host::{AgentClient, AgentHost, AgentReply, AgentRequest, AgentStatus};
+pub use background::{background_mode, show_running_instance};
 pub use error::{IndraError, Result};
+pub use indras_core::error_code::{ErrorCode, ErrorDetail, ErrorInfo, Retryability};
+pub use event_bus::{
+    event_member, BusEvent, EventBus, EventFilter, EventKind, EventSubscription,
+    DEFAULT_EVENT_HISTORY,
+};
+pub use event_feed::{ControlCommand, ControlFile, EventFeedWriter, EventLine};
+pub use geo::{GeoPrecision, Geohash};
+pub use announcement::{
+    Announcement, AnnouncementDocument, AnnouncementId, AnnouncementReminder, AnnouncementSummary,
+    ANNOUNCEMENT_DOC,
+};
+pub use anonymity::{AnonymityRingDocument, RingEntry, ANONYMITY_RING_DOC};
+pub use archive::{ArchiveChange, ArchiveDocument, ARCHIVE_DOC};
+pub use guest_access::{GuestAccessDocument, GuestGrant, GuestScope, GUEST_ACCESS_DOC};
 pub use home_realm::{home_realm_id, HomeArtifactMetadata, HomeRealm};
 pub use invite::InviteCode;
-pub use member::{Member, MemberEvent, MemberId, MemberInfo};
+pub use key_rotation::{
+    key_hash, KeyRotation, KeyRotationDocument, KeyShare, RotatedKey, RotationReason,
+    SignedKeyRotation, SignedKeyShare, KEY_ROTATION_DOC,
+};
+pub use key_transparency::{
+    KeyAudit, KeyChangeRecord, KeyChangeStatus, KeyEndorsement, KeyEntry, KeyLogDocument,
+    SignedKeyEntry, KEY_LOG_DOC,
+};
+pub use legal_hold::{HeldMessage, Hold, HoldEvent, HoldExport, LegalHoldDocument, LEGAL_HOLD_DOC};
+pub use link_preview::{LinkPreview, PreviewImage, SignedLinkPreview};
+pub use member::{Member, MemberEvent, MemberId, MemberInfo, MemberSyncStatus};
+pub use membership_log::{
+    EntryHash, MembershipAction, MembershipEntry, MembershipLogDocument, MembershipRecord,
+    MembershipState, OwnershipChange, SignedMembershipEntry, DEFAULT_SUCCESSION_DAYS,
+    MAX_CLOCK_SKEW_MILLIS, MEMBERSHIP_LOG_DOC, OWNER_HEARTBEAT_MILLIS,
+};
 pub use message::{Content, Message, MessageId};
+pub use mirror::{
+    write_mirror, MirrorExporter, MirrorFile, MirrorReport, PublishListDocument, MIRROR_MANIFEST,
+    PUBLISH_DOC,
+};
+pub use moderation::{
+    ContentFilter, ContentFilters, HideAction, KeywordFilter, ModeratedMessage, ModerationDocument,
+    PatternFilter, Report, Visibility, Warning, MODERATION_DOC,
+};
 pub use network::{IndrasNetwork, RealmId};
+pub use notifications::{
+    DefaultNotificationClassifier, Notification, NotificationAction, NotificationCenter,
+    NotificationClassifier, NotificationContext, NotificationKind, NotificationLevel,
+    NotificationPreferences, NotificationResponse, NotificationSink, NOTIFICATION_SETTINGS_KEY,
+};
+#[cfg(feature = "desktop-notifications")]
+pub use notifications::DesktopNotifier;
+pub use profile::{Profile, ProfileDocument, SignedProfile, PROFILE_DOC};
+pub use pruning::{
+    InactiveMember, PruneDocument, PruneProposal, PrunedMember, Rejoin, RejoinHistory,
+    DEFAULT_INACTIVE_DAYS, DEFAULT_PRUNE_GRACE_MILLIS, PRUNE_DOC,
+};
 pub use read_tracker::ReadTrackerDocument;
-pub use document_registry::DocumentRegistryDocument;
+pub use sticker_pack::{
+    PackEntry, StickerKind, StickerPack, StickerPackDocument, StickerRef, STICKER_PACK_DOC,
+};
+pub use document_registry::{DocumentEntry, DocumentEvent, DocumentRegistryDocument};
 pub use network::{GlobalEvent, IdentityBackup};
 pub use peering::{PeerEvent, PeerInfo};
 pub use realm::Realm;
+pub use security_info::{
+    InviteEvent, InviteEventKind, MemberSecurity, PqVerification, RealmKeyInfo,
+    RealmSecurityInfo, TrustRootInfo,
+};
+pub use settings::{SettingChange, SettingEntry, SettingsDocument, SETTINGS_DOC};
+pub use snapshot::RealmSnapshot;
+pub use spam::{
+    SenderStanding, SpamDetector, SpamScore, SpamSensitivity, SpamSettings, SpamSignal,
+    SpamVerdict, SPAM_SETTINGS_KEY,
+};
 pub use system_event::SystemEvent;
-pub use realm_alias::{RealmAlias, RealmAliasDocument, MAX_ALIAS_LENGTH};
+pub use translation::{Translations, Translator};
+pub use time_series::{
+    Bucket, RetentionPolicy, SeriesKey, Tier, TimeSeriesDocument, TIME_SERIES_DOC,
+};
+pub use tombstone::{SyncHorizonDocument, TombstoneCompaction};
+pub use realm_alias::{
+    normalize_handle, AliasDirectoryDocument, AliasRecord, RealmAlias, RealmAliasDocument,
+    ALIAS_DIRECTORY_DOC, MAX_ALIAS_LENGTH, MAX_HANDLE_LENGTH,
+};
+pub use realm_restructure::{RealmRestructure, ReplayKind, SplitFilter};
+pub use rehearsal::Rehearsal;
+pub use realm_organization::{
+    FolderId, RealmFolder, RealmLayout, RealmOrganizationDocument, RealmPlacement,
+    REALM_ORGANIZATION_DOC,
+};
 pub use sentiment::{
     RelayedSentiment, SentimentRelayDocument, SentimentView, DEFAULT_RELAY_ATTENUATION,
 };
 pub use world_view::WorldView;
+pub use indras_crypto::MemberCertificate;
+pub use indras_node::{
+    BackupSummary, BudgetConfig, MemberTrust, RestoreSummary, TrustPolicy, VerifyReport,
+    restore_backup, verify_backup,
+};
 
 // Explicit DocumentSchema impls for indras-network types.
 // RealmChatDocument has a custom impl with merge (in chat_message.rs).
@@ -204,11 +352,12 @@ pub use indras_artifacts::{
 /// ```
 pub mod prelude {
     pub use crate::{
-        ArtifactDownload, ArtifactIndex, GeoLocation, HomeArtifactEntry,
-        Content, Document, DocumentSchema, EditableChatMessage, GlobalEvent,
+        ArtifactDownload, ArtifactIndex, GeoLocation, GeoPrecision, Geohash, HomeArtifactEntry,
+        BusEvent, Content, Document, DocumentSchema, EditableChatMessage, EventFilter, EventKind,
+        GlobalEvent,
         HomeRealm, IdentityBackup, IdentityCode, IndraError, IndrasNetwork, InviteCode, Member,
         MemberEvent, MemberInfo, Message, PeerEvent, PeerInfo, Preset, Realm, RealmAlias,
-        RealmAliasDocument, RealmChatDocument, RealmId, Result,
+        RealmAliasDocument, RealmChatDocument, RealmId, RealmSnapshot, Result,
     };
 
     // Re-export futures StreamExt for convenient stream iteration
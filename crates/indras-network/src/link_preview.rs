@@ -0,0 +1,265 @@
+//! Sender-generated link previews.
+//!
+//! When every receiver fetches a pasted URL to build its own preview, the
+//! page is downloaded once per member and the site learns who is in the
+//! conversation. Instead, the sender builds a [`LinkPreview`] once (title,
+//! description, and an optional image stored as a blob), signs it, and
+//! attaches it to the message as
+//! [`Content::TextWithPreviews`](crate::Content::TextWithPreviews).
+//! Receivers render the card without touching the URL.
+//!
+//! Previews are opt-in: nothing is fetched unless the application builds
+//! a preview (for example with [`LinkPreview::from_html`] on a page it
+//! fetched) and sends it with
+//! [`Realm::send_with_previews`](crate::Realm::send_with_previews).
+//!
+//! A signature proves who made a card, not that the card is truthful; a
+//! sender can describe a page however they like, just as they can describe
+//! it in text. Receivers should show cards as coming from the sender.
+
+use indras_crypto::pq_identity::{PQIdentity, PQPublicIdentity, PQSignature};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+/// Longest preview title kept, in characters.
+pub const MAX_TITLE_CHARS: usize = 300;
+
+/// Longest preview description kept, in characters.
+pub const MAX_DESCRIPTION_CHARS: usize = 1000;
+
+/// Largest preview image accepted, in bytes.
+pub const MAX_PREVIEW_IMAGE_SIZE: u64 = 256 * 1024;
+
+/// Most previews attached to one message.
+pub const MAX_PREVIEWS_PER_MESSAGE: usize = 4;
+
+static META_TAG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<meta\s[^>]*>").expect("valid regex"));
+static ATTRIBUTE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?is)([a-z][a-z0-9:_-]*)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).expect("valid regex")
+});
+static TITLE_TAG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<title[^>]*>(.*?)</title>").expect("valid regex"));
+
+/// A preview image stored as a blob.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreviewImage {
+    /// BLAKE3 hash of the image blob.
+    pub hash: [u8; 32],
+    /// Image MIME type.
+    pub mime_type: String,
+    /// Image size in bytes.
+    pub size: u64,
+}
+
+/// A preview card for one URL.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkPreview {
+    /// The URL the card describes.
+    pub url: String,
+    /// Page title.
+    pub title: Option<String>,
+    /// Page description.
+    pub description: Option<String>,
+    /// Site name (e.g. from `og:site_name`).
+    pub site_name: Option<String>,
+    /// Preview image, stored as a blob by the sender.
+    pub image: Option<PreviewImage>,
+    /// URL of the page's preview image, for the sender to fetch and store.
+    ///
+    /// Cleared before signing; receivers never see or fetch it.
+    #[serde(skip)]
+    pub image_url: Option<String>,
+}
+
+impl LinkPreview {
+    /// Build a preview from a fetched page's HTML.
+    ///
+    /// Reads Open Graph and Twitter card metadata, falling back to the
+    /// `<title>` element and the `description` meta tag. The image is not
+    /// fetched: its URL is left in [`image_url`](Self::image_url).
+    pub fn from_html(url: impl Into<String>, html: &str) -> Self {
+        let mut meta = std::collections::HashMap::new();
+        for tag in META_TAG.find_iter(html) {
+            let mut key = None;
+            let mut content = None;
+            for attr in ATTRIBUTE.captures_iter(tag.as_str()) {
+                let value = attr.get(2).or(attr.get(3)).map_or("", |m| m.as_str());
+                match attr[1].to_ascii_lowercase().as_str() {
+                    "property" | "name" => key = Some(value.to_ascii_lowercase()),
+                    "content" => content = Some(decode_entities(value)),
+                    _ => {}
+                }
+            }
+            if let (Some(key), Some(content)) = (key, content) {
+                meta.entry(key).or_insert(content);
+            }
+        }
+        let first = |keys: &[&str]| {
+            keys.iter()
+                .find_map(|k| meta.get(*k))
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+
+        let title = first(&["og:title", "twitter:title"]).or_else(|| {
+            TITLE_TAG
+                .captures(html)
+                .map(|c| decode_entities(c[1].trim()))
+                .filter(|t| !t.is_empty())
+        });
+
+        Self {
+            url: url.into(),
+            title: title.map(|t| truncate(t, MAX_TITLE_CHARS)),
+            description: first(&["og:description", "twitter:description", "description"])
+                .map(|d| truncate(d, MAX_DESCRIPTION_CHARS)),
+            site_name: first(&["og:site_name"]).map(|s| truncate(s, MAX_TITLE_CHARS)),
+            image: None,
+            image_url: first(&["og:image", "twitter:image"]),
+        }
+    }
+
+    /// Whether the card has anything to show besides the URL.
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none() && self.description.is_none() && self.image.is_none()
+    }
+
+    /// Sign the card as its sender.
+    pub fn sign(mut self, identity: &PQIdentity) -> SignedLinkPreview {
+        self.image_url = None;
+        let signature = identity.sign(&signing_bytes(&self));
+        SignedLinkPreview {
+            preview: self,
+            signer_vk: identity.verifying_key_bytes(),
+            signature: signature.to_bytes().to_vec(),
+        }
+    }
+}
+
+/// A preview card signed by the member who generated it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedLinkPreview {
+    /// The card.
+    pub preview: LinkPreview,
+    /// Sender's ML-DSA-65 verifying key.
+    pub signer_vk: Vec<u8>,
+    /// Signature over the card.
+    pub signature: Vec<u8>,
+}
+
+impl SignedLinkPreview {
+    /// Whether the signature over the card is valid.
+    pub fn verify(&self) -> bool {
+        let (Ok(vk), Ok(sig)) = (
+            PQPublicIdentity::from_bytes(&self.signer_vk),
+            PQSignature::from_bytes(self.signature.clone()),
+        ) else {
+            return false;
+        };
+        vk.verify(&signing_bytes(&self.preview), &sig)
+    }
+}
+
+/// Domain-separated bytes the preview signature covers.
+fn signing_bytes(preview: &LinkPreview) -> Vec<u8> {
+    const DOMAIN: &[u8] = b"indras:link-preview:v1";
+    let mut bytes = DOMAIN.to_vec();
+    bytes.extend(postcard::to_allocvec(preview).unwrap_or_default());
+    bytes
+}
+
+/// The `http(s)` URLs in a message's text, in order.
+///
+/// Trailing punctuation that usually ends a sentence rather than a URL is
+/// trimmed.
+pub fn extract_urls(text: &str) -> Vec<&str> {
+    text.split_whitespace()
+        .filter(|word| word.starts_with("https://") || word.starts_with("http://"))
+        .map(|word| word.trim_end_matches(['.', ',', ';', ':', '!', '?', ')', ']', '>', '"', '\'']))
+        .collect()
+}
+
+fn truncate(text: String, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => text[..end].to_string(),
+        None => text,
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<html><head>
+        <title>Fallback title</title>
+        <meta property="og:title" content="Indra&#39;s Net" />
+        <meta name="description" content="Plain description">
+        <meta property='og:description' content='Every jewel reflects every other &amp; more'>
+        <meta property="og:image" content="https://example.com/net.png">
+        </head></html>"#;
+
+    #[test]
+    fn test_from_html_prefers_open_graph() {
+        let preview = LinkPreview::from_html("https://example.com", PAGE);
+        assert_eq!(preview.title.as_deref(), Some("Indra's Net"));
+        assert_eq!(
+            preview.description.as_deref(),
+            Some("Every jewel reflects every other & more")
+        );
+        assert_eq!(preview.image_url.as_deref(), Some("https://example.com/net.png"));
+
+        let bare = LinkPreview::from_html("https://example.com", "<title> Only </title>");
+        assert_eq!(bare.title.as_deref(), Some("Only"));
+        assert!(bare.description.is_none());
+    }
+
+    #[test]
+    fn test_signed_preview_detects_tampering() {
+        let identity = PQIdentity::generate();
+        let signed = LinkPreview::from_html("https://example.com", PAGE).sign(&identity);
+        assert!(signed.verify());
+        assert!(signed.preview.image_url.is_none());
+
+        let mut tampered = signed.clone();
+        tampered.preview.title = Some("Something else".to_string());
+        assert!(!tampered.verify());
+    }
+
+    #[test]
+    fn test_verified_previews_require_url_in_text() {
+        let identity = PQIdentity::generate();
+        let card = |url: &str| LinkPreview {
+            url: url.to_string(),
+            title: Some("Title".to_string()),
+            ..Default::default()
+        };
+        let content = crate::Content::TextWithPreviews {
+            text: "look https://a.example".to_string(),
+            previews: vec![
+                card("https://a.example").sign(&identity),
+                card("https://elsewhere.example").sign(&identity),
+            ],
+        };
+        let shown = content.verified_previews();
+        assert_eq!(shown.len(), 1);
+        assert_eq!(shown[0].url, "https://a.example");
+        assert_eq!(content.as_text(), Some("look https://a.example"));
+    }
+
+    #[test]
+    fn test_extract_urls() {
+        let text = "see https://a.example/x, and (http://b.example/y). not ftp://c";
+        assert_eq!(extract_urls(text), vec!["https://a.example/x", "http://b.example/y"]);
+    }
+}
@@ -7,6 +7,7 @@ use indras_core::{PeerIdentity, PresenceStatus};
 use indras_transport::IrohIdentity;
 use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::time::Duration;
 
 /// Unique identifier for a member (32-byte public key hash).
 /// Bridged to `indras_artifacts::PlayerId` for type compatibility.
@@ -212,6 +213,44 @@ impl MemberInfo {
     }
 }
 
+/// How up to date a realm member is, as seen from this device.
+///
+/// Returned by [`Realm::member_sync_status`](crate::Realm::member_sync_status)
+/// for member lists.
+#[derive(Debug, Clone)]
+pub struct MemberSyncStatus {
+    /// The member.
+    pub member: Member,
+    /// Whether a connection to the member is open.
+    pub connected: bool,
+    /// When we last exchanged sync state with the member in this realm
+    /// (milliseconds since epoch), if ever since startup.
+    pub last_sync_millis: Option<u64>,
+    /// Smoothed round-trip time, if ever measured.
+    pub round_trip: Option<Duration>,
+    /// Our changes in this realm the member has not acknowledged yet.
+    pub changes_behind: usize,
+}
+
+impl MemberSyncStatus {
+    /// Whether the member has every change we have sent.
+    pub fn is_caught_up(&self) -> bool {
+        self.changes_behind == 0
+    }
+}
+
+impl From<indras_node::MemberSyncStatus> for MemberSyncStatus {
+    fn from(status: indras_node::MemberSyncStatus) -> Self {
+        Self {
+            member: Member::new(status.peer),
+            connected: status.connected,
+            last_sync_millis: status.last_sync_millis.map(|t| t.max(0) as u64),
+            round_trip: status.rtt,
+            changes_behind: status.pending_events,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,868 @@
+//! Signed, append-only membership history.
+//!
+//! The interface's member map is a plain CRDT: any peer can add or remove
+//! anyone. A realm's [`MembershipLogDocument`] records every add and
+//! remove as a [`SignedMembershipEntry`] instead, so members can check who
+//! changed the membership and whether they were allowed to.
+//!
+//! Entries form a hash-linked DAG: each entry names the log heads it was
+//! written on top of, and concurrent appends simply become two heads that
+//! the next entry joins. The log is replayed in `(seq, hash)` order to get
+//! the current [`MembershipState`].
+//!
+//! Permissions:
+//!
+//! - The first entry is a `Create`; its author becomes the first admin.
+//!   A log accepts exactly one `Create`.
+//! - Any member may add a member; only admins may add an admin.
+//! - Members may remove themselves; only admins may remove others.
+//! - The creator is the realm's first owner. The owner may hand ownership
+//!   to another member and set a succession list; the new owner becomes an
+//!   admin.
+//! - If the owner writes nothing (not even an `OwnerHeartbeat`) for the
+//!   succession period, the first successor may claim ownership; the next
+//!   one after another period, and so on. When the owner has left, the
+//!   first successor may claim at once. Without a succession list the
+//!   other admins are the successors.
+//! - A claim's own timestamp is chosen by the claimant, so a node also
+//!   checks it against its own clock when the claim arrives: the claim is
+//!   dropped if it is dated more than [`MAX_CLOCK_SKEW_MILLIS`] ahead, or
+//!   if the owner's latest activity in the whole log leaves the succession
+//!   period unfinished by the node's clock. A dropped claim can arrive
+//!   again with the next sync and is checked afresh.
+//!
+//! Each entry must be authorized by the state of its own ancestors, and is
+//! signed with its author's ML-DSA-65 identity. The creator's key is pinned
+//! by the `Create`; every other member's key is pinned by the `Add` that
+//! admitted them, so nobody can sign as a member before that member has
+//! written anything themselves. Entries failing any of
+//! these checks are dropped on merge; entries that were valid on their own
+//! branch but lose authority in the merged replay (for example, an admin
+//! removed concurrently) stay in the log and show up as rejected in
+//! [`MembershipLogDocument::history`].
+
+use crate::document::DocumentSchema;
+use crate::guest_access::DAY_MILLIS;
+use crate::member::MemberId;
+use crate::util::now_millis;
+use indras_crypto::pq_identity::{PQIdentity, PQPublicIdentity, PQSignature};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Document name for the membership log within a realm.
+pub const MEMBERSHIP_LOG_DOC: &str = "_membership_log";
+
+/// Domain separation tag for membership entry hashes and signatures.
+const ENTRY_DOMAIN: &[u8] = b"indras:membership:v1";
+
+/// Days the owner may stay silent before the first successor may claim
+/// ownership, unless the succession list sets another period.
+pub const DEFAULT_SUCCESSION_DAYS: u32 = 30;
+
+/// How often an active owner records an `OwnerHeartbeat`.
+pub const OWNER_HEARTBEAT_MILLIS: u64 = DAY_MILLIS;
+
+/// How far ahead of this node's clock an ownership claim may be dated.
+pub const MAX_CLOCK_SKEW_MILLIS: u64 = 5 * 60 * 1000;
+
+/// Hash identifying a membership entry.
+pub type EntryHash = [u8; 32];
+
+/// A change to a realm's membership.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MembershipAction {
+    /// Start the log; the author becomes its first admin.
+    Create,
+    /// Add a member.
+    Add {
+        /// The new member.
+        member: MemberId,
+        /// Whether the new member is an admin.
+        admin: bool,
+        /// The new member's ML-DSA-65 verifying key, pinned for their
+        /// entries.
+        key: Vec<u8>,
+    },
+    /// Remove a member (or leave, when the author removes themself).
+    Remove {
+        /// The member removed.
+        member: MemberId,
+    },
+    /// Hand ownership to another member, who also becomes an admin.
+    TransferOwnership {
+        /// The new owner.
+        to: MemberId,
+    },
+    /// Set who takes over, in order, if the owner goes silent or leaves.
+    SetSuccession {
+        /// Successors, first in line first.
+        successors: Vec<MemberId>,
+        /// Days of owner silence before the first successor may claim.
+        inactive_days: u32,
+    },
+    /// The owner is still active.
+    OwnerHeartbeat,
+    /// Take over ownership as a successor.
+    ClaimOwnership,
+}
+
+/// One membership change, before signing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MembershipEntry {
+    /// Log heads this entry was written on, sorted.
+    pub parents: Vec<EntryHash>,
+    /// One more than the highest parent `seq`; 0 for the first entry.
+    pub seq: u64,
+    /// Member who made the change.
+    pub actor: MemberId,
+    /// The change.
+    pub action: MembershipAction,
+    /// When the change was made (milliseconds since epoch).
+    pub at_millis: u64,
+}
+
+impl MembershipEntry {
+    /// Hash identifying this entry.
+    pub fn hash(&self) -> EntryHash {
+        *blake3::hash(&self.signing_bytes()).as_bytes()
+    }
+
+    /// Domain-separated bytes the hash and signature cover.
+    fn signing_bytes(&self) -> Vec<u8> {
+        let mut bytes = ENTRY_DOMAIN.to_vec();
+        bytes.extend(postcard::to_allocvec(self).unwrap_or_default());
+        bytes
+    }
+}
+
+/// A membership entry signed by its actor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedMembershipEntry {
+    /// The change.
+    pub entry: MembershipEntry,
+    /// Actor's ML-DSA-65 verifying key.
+    pub verifying_key: Vec<u8>,
+    /// Signature over the entry.
+    pub signature: Vec<u8>,
+}
+
+impl SignedMembershipEntry {
+    /// Sign `entry` as its actor.
+    pub fn sign(entry: MembershipEntry, identity: &PQIdentity) -> Self {
+        let signature = identity.sign(&entry.signing_bytes());
+        Self {
+            entry,
+            verifying_key: identity.verifying_key_bytes(),
+            signature: signature.to_bytes().to_vec(),
+        }
+    }
+
+    /// Hash identifying the entry.
+    pub fn hash(&self) -> EntryHash {
+        self.entry.hash()
+    }
+
+    /// Whether the signature is valid for the entry.
+    pub fn verify(&self) -> bool {
+        let (Ok(key), Ok(signature)) = (
+            PQPublicIdentity::from_bytes(&self.verifying_key),
+            PQSignature::from_bytes(self.signature.clone()),
+        ) else {
+            return false;
+        };
+        key.verify(&self.entry.signing_bytes(), &signature)
+    }
+}
+
+/// Membership as of some point in the log.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MembershipState {
+    /// Current members.
+    pub members: BTreeSet<MemberId>,
+    /// Current admins (always also members).
+    pub admins: BTreeSet<MemberId>,
+    /// Verifying key pinned per member by their `Create` or `Add`.
+    pub keys: BTreeMap<MemberId, Vec<u8>>,
+    /// Current owner: the creator, until ownership changes hands.
+    pub owner: Option<MemberId>,
+    /// Succession list set by the owner, first in line first.
+    pub successors: Vec<MemberId>,
+    /// Succession period set by the owner, in days.
+    pub inactive_days: Option<u32>,
+    /// When the owner last wrote to the log (milliseconds since epoch).
+    pub owner_active_millis: u64,
+}
+
+impl MembershipState {
+    /// Whether a member is in the realm.
+    pub fn is_member(&self, member: &MemberId) -> bool {
+        self.members.contains(member)
+    }
+
+    /// Whether a member is an admin.
+    pub fn is_admin(&self, member: &MemberId) -> bool {
+        self.admins.contains(member)
+    }
+
+    /// Whether a member owns the realm.
+    pub fn is_owner(&self, member: &MemberId) -> bool {
+        self.owner == Some(*member)
+    }
+
+    /// Days of owner silence before the first successor may claim.
+    pub fn succession_days(&self) -> u32 {
+        self.inactive_days.unwrap_or(DEFAULT_SUCCESSION_DAYS)
+    }
+
+    /// Members who may succeed the owner, in order: the succession list,
+    /// or the admins if none was set, limited to current members.
+    pub fn eligible_successors(&self) -> Vec<MemberId> {
+        let candidates: Vec<MemberId> = if self.successors.is_empty() {
+            self.admins.iter().copied().collect()
+        } else {
+            self.successors.clone()
+        };
+        candidates
+            .into_iter()
+            .filter(|m| self.is_member(m) && !self.is_owner(m))
+            .collect()
+    }
+
+    /// Earliest time (milliseconds since epoch) `member` may claim
+    /// ownership, or `None` if they are not an eligible successor.
+    pub fn claim_allowed_at(&self, member: &MemberId) -> Option<u64> {
+        let position = self.eligible_successors().iter().position(|m| m == member)?;
+        let owner_left = self.owner.is_none_or(|owner| !self.is_member(&owner));
+        let periods = position as u64 + u64::from(!owner_left);
+        let period = u64::from(self.succession_days()) * DAY_MILLIS;
+        Some(self.owner_active_millis.saturating_add(periods * period))
+    }
+
+    /// Whether this state allows the entry's author to make its change.
+    pub fn permits(&self, signed: &SignedMembershipEntry) -> bool {
+        let entry = &signed.entry;
+        if entry.action == MembershipAction::Create {
+            return entry.parents.is_empty() && self.members.is_empty();
+        }
+        if self.keys.get(&entry.actor) != Some(&signed.verifying_key) {
+            return false;
+        }
+        match &entry.action {
+            MembershipAction::Create => false,
+            MembershipAction::Add { member, admin, key } => {
+                self.is_member(&entry.actor)
+                    && !self.is_member(member)
+                    && (!admin || self.is_admin(&entry.actor))
+                    && PQPublicIdentity::from_bytes(key).is_ok()
+            }
+            MembershipAction::Remove { member } => {
+                self.is_member(member) && (*member == entry.actor || self.is_admin(&entry.actor))
+            }
+            MembershipAction::TransferOwnership { to } => {
+                self.is_owner(&entry.actor) && self.is_member(to) && *to != entry.actor
+            }
+            MembershipAction::SetSuccession {
+                successors,
+                inactive_days,
+            } => {
+                self.is_owner(&entry.actor)
+                    && *inactive_days > 0
+                    && !successors.contains(&entry.actor)
+            }
+            MembershipAction::OwnerHeartbeat => self.is_owner(&entry.actor),
+            MembershipAction::ClaimOwnership => self
+                .claim_allowed_at(&entry.actor)
+                .is_some_and(|at| entry.at_millis >= at),
+        }
+    }
+
+    /// Apply a permitted entry.
+    fn apply(&mut self, signed: &SignedMembershipEntry) {
+        let entry = &signed.entry;
+        if self.is_owner(&entry.actor) {
+            self.owner_active_millis = self.owner_active_millis.max(entry.at_millis);
+        }
+        match &entry.action {
+            MembershipAction::Create => {
+                self.members.insert(entry.actor);
+                self.admins.insert(entry.actor);
+                self.keys.insert(entry.actor, signed.verifying_key.clone());
+                self.owner = Some(entry.actor);
+                self.owner_active_millis = entry.at_millis;
+            }
+            MembershipAction::Add { member, admin, key } => {
+                self.members.insert(*member);
+                self.keys.insert(*member, key.clone());
+                if *admin {
+                    self.admins.insert(*member);
+                }
+            }
+            MembershipAction::Remove { member } => {
+                self.members.remove(member);
+                self.admins.remove(member);
+            }
+            MembershipAction::TransferOwnership { to } => self.hand_over(*to, entry.at_millis),
+            MembershipAction::SetSuccession {
+                successors,
+                inactive_days,
+            } => {
+                let mut seen = BTreeSet::new();
+                self.successors = successors.iter().filter(|m| seen.insert(**m)).copied().collect();
+                self.inactive_days = Some(*inactive_days);
+            }
+            MembershipAction::OwnerHeartbeat => {}
+            MembershipAction::ClaimOwnership => self.hand_over(entry.actor, entry.at_millis),
+        }
+    }
+
+    /// Make `owner` the owner and an admin.
+    fn hand_over(&mut self, owner: MemberId, at_millis: u64) {
+        self.owner = Some(owner);
+        self.admins.insert(owner);
+        self.owner_active_millis = at_millis;
+        self.successors.retain(|m| *m != owner);
+    }
+}
+
+/// One entry of the audited membership history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MembershipRecord {
+    /// Hash of the entry.
+    pub hash: EntryHash,
+    /// The signed entry.
+    pub entry: SignedMembershipEntry,
+    /// Whether the entry took effect in the merged replay.
+    pub accepted: bool,
+}
+
+/// A change of owner recorded in the membership log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnershipChange {
+    /// Hash of the entry that made the change.
+    pub hash: EntryHash,
+    /// The owner before.
+    pub previous: Option<MemberId>,
+    /// The new owner.
+    pub owner: MemberId,
+    /// Whether a successor claimed it (`false` for a handoff).
+    pub succession: bool,
+    /// When it happened (milliseconds since epoch).
+    pub at_millis: u64,
+}
+
+/// CRDT document holding a realm's signed membership log.
+///
+/// # Example
+///
+/// ```ignore
+/// realm.record_member_added(peer_id, false).await?;
+///
+/// for record in realm.membership_history().await? {
+///     println!("{:?} by {:?}: accepted = {}",
+///         record.entry.entry.action, record.entry.entry.actor, record.accepted);
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MembershipLogDocument {
+    /// Signed entries keyed by hash.
+    pub entries: BTreeMap<EntryHash, SignedMembershipEntry>,
+}
+
+impl MembershipLogDocument {
+    /// Create an empty log.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the log has no entries yet.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Entries no other entry builds on, sorted.
+    pub fn heads(&self) -> Vec<EntryHash> {
+        let referenced: BTreeSet<&EntryHash> = self
+            .entries
+            .values()
+            .flat_map(|s| &s.entry.parents)
+            .collect();
+        self.entries
+            .keys()
+            .filter(|hash| !referenced.contains(hash))
+            .copied()
+            .collect()
+    }
+
+    /// Build the next entry on top of the current heads.
+    pub fn next_entry(
+        &self,
+        actor: MemberId,
+        action: MembershipAction,
+        at_millis: u64,
+    ) -> MembershipEntry {
+        let parents = self.heads();
+        MembershipEntry {
+            seq: self.next_seq(&parents),
+            parents,
+            actor,
+            action,
+            at_millis,
+        }
+    }
+
+    /// Append a signed entry.
+    ///
+    /// Returns `false` if it is already present, its signature is invalid,
+    /// its parents are unknown, or its ancestors do not authorize it. A
+    /// `Create` is only accepted into an empty log: a second parentless
+    /// root would otherwise compete for seq 0 and could take over the
+    /// realm by grinding a lower hash. A `ClaimOwnership` must also be due
+    /// by this node's clock (see [`Self::claim_due`]).
+    pub fn append(&mut self, signed: SignedMembershipEntry) -> bool {
+        self.append_at(signed, now_millis())
+    }
+
+    /// [`Self::append`] with `now_millis` as this node's clock.
+    fn append_at(&mut self, signed: SignedMembershipEntry, now_millis: u64) -> bool {
+        let hash = signed.hash();
+        let entry = &signed.entry;
+        if self.entries.contains_key(&hash)
+            || (entry.action == MembershipAction::Create && !self.entries.is_empty())
+            || !entry.parents.iter().all(|p| self.entries.contains_key(p))
+            || entry.seq != self.next_seq(&entry.parents)
+            || !signed.verify()
+        {
+            return false;
+        }
+        if !self.state_at(&entry.parents).permits(&signed) {
+            return false;
+        }
+        if entry.action == MembershipAction::ClaimOwnership
+            && !self.claim_due(&entry.actor, entry.at_millis, now_millis)
+        {
+            return false;
+        }
+        self.entries.insert(hash, signed);
+        true
+    }
+
+    /// Whether `claimant` may take over ownership now, by this node's clock.
+    ///
+    /// The claim must not be dated more than [`MAX_CLOCK_SKEW_MILLIS`]
+    /// ahead of `now_millis`, and the succession period must have run out
+    /// by `now_millis` counting from the owner's latest activity anywhere
+    /// in the log, not only among the claim's ancestors.
+    pub fn claim_due(&self, claimant: &MemberId, at_millis: u64, now_millis: u64) -> bool {
+        at_millis <= now_millis.saturating_add(MAX_CLOCK_SKEW_MILLIS)
+            && self
+                .state()
+                .claim_allowed_at(claimant)
+                .is_some_and(|at| now_millis >= at)
+    }
+
+    /// Current membership, replaying the whole log.
+    pub fn state(&self) -> MembershipState {
+        self.replay(self.entries.keys().copied().collect()).0
+    }
+
+    /// The full history in replay order, each entry marked accepted or not.
+    pub fn history(&self) -> Vec<MembershipRecord> {
+        let (_, records) = self.replay(self.entries.keys().copied().collect());
+        records
+    }
+
+    /// Every change of owner after creation, in replay order.
+    pub fn ownership_changes(&self) -> Vec<OwnershipChange> {
+        let mut owner = None;
+        let mut changes = Vec::new();
+        for record in self.history().into_iter().filter(|r| r.accepted) {
+            let entry = &record.entry.entry;
+            let (new_owner, succession) = match &entry.action {
+                MembershipAction::Create => {
+                    owner = Some(entry.actor);
+                    continue;
+                }
+                MembershipAction::TransferOwnership { to } => (*to, false),
+                MembershipAction::ClaimOwnership => (entry.actor, true),
+                _ => continue,
+            };
+            changes.push(OwnershipChange {
+                hash: record.hash,
+                previous: owner.replace(new_owner),
+                owner: new_owner,
+                succession,
+                at_millis: entry.at_millis,
+            });
+        }
+        changes
+    }
+
+    /// Re-check every signature and authorization in the log.
+    ///
+    /// Returns the hashes of entries that fail, empty if the log is sound.
+    /// A log with more than one `Create` fails on every `Create`.
+    pub fn verify(&self) -> Vec<EntryHash> {
+        let creates = self
+            .entries
+            .values()
+            .filter(|s| s.entry.action == MembershipAction::Create)
+            .count();
+        self.entries
+            .iter()
+            .filter(|(hash, signed)| {
+                signed.hash() != **hash
+                    || (signed.entry.action == MembershipAction::Create && creates > 1)
+                    || !signed.verify()
+                    || !signed
+                        .entry
+                        .parents
+                        .iter()
+                        .all(|p| self.entries.contains_key(p))
+                    || !self.state_at(&signed.entry.parents).permits(signed)
+            })
+            .map(|(hash, _)| *hash)
+            .collect()
+    }
+
+    fn next_seq(&self, parents: &[EntryHash]) -> u64 {
+        parents
+            .iter()
+            .filter_map(|p| self.entries.get(p))
+            .map(|s| s.entry.seq + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Membership after the given entries and all their ancestors.
+    fn state_at(&self, heads: &[EntryHash]) -> MembershipState {
+        let mut ancestors = BTreeSet::new();
+        let mut pending: Vec<EntryHash> = heads.to_vec();
+        while let Some(hash) = pending.pop() {
+            if let Some(signed) = self.entries.get(&hash)
+                && ancestors.insert(hash)
+            {
+                pending.extend(&signed.entry.parents);
+            }
+        }
+        self.replay(ancestors).0
+    }
+
+    /// Replay a set of entries in `(seq, hash)` order.
+    fn replay(&self, hashes: BTreeSet<EntryHash>) -> (MembershipState, Vec<MembershipRecord>) {
+        let mut ordered: Vec<(&EntryHash, &SignedMembershipEntry)> = hashes
+            .iter()
+            .filter_map(|hash| self.entries.get_key_value(hash))
+            .collect();
+        ordered.sort_by_key(|(hash, signed)| (signed.entry.seq, **hash));
+
+        let mut state = MembershipState::default();
+        let records = ordered
+            .into_iter()
+            .map(|(hash, signed)| {
+                let accepted = state.permits(signed);
+                if accepted {
+                    state.apply(signed);
+                }
+                MembershipRecord {
+                    hash: *hash,
+                    entry: signed.clone(),
+                    accepted,
+                }
+            })
+            .collect();
+        (state, records)
+    }
+}
+
+impl DocumentSchema for MembershipLogDocument {
+    /// Union of entries, each verified against its own ancestors.
+    fn merge(&mut self, remote: Self) {
+        let mut incoming: Vec<SignedMembershipEntry> = remote.entries.into_values().collect();
+        // Parents always have a lower seq, so they are appended first
+        incoming.sort_by_key(|s| s.entry.seq);
+        for signed in incoming {
+            self.append(signed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Signer {
+        id: MemberId,
+        identity: PQIdentity,
+    }
+
+    impl Signer {
+        fn new(n: u8) -> Self {
+            Self {
+                id: [n; 32],
+                identity: PQIdentity::generate(),
+            }
+        }
+
+        fn write(&self, doc: &mut MembershipLogDocument, action: MembershipAction) -> bool {
+            self.write_at(doc, action, 10)
+        }
+
+        fn write_at(
+            &self,
+            doc: &mut MembershipLogDocument,
+            action: MembershipAction,
+            at_millis: u64,
+        ) -> bool {
+            let entry = doc.next_entry(self.id, action, at_millis);
+            doc.append(SignedMembershipEntry::sign(entry, &self.identity))
+        }
+    }
+
+    fn add(member: &Signer, admin: bool) -> MembershipAction {
+        MembershipAction::Add {
+            member: member.id,
+            admin,
+            key: member.identity.verifying_key_bytes(),
+        }
+    }
+
+    fn remove(member: &Signer) -> MembershipAction {
+        MembershipAction::Remove { member: member.id }
+    }
+
+    #[test]
+    fn test_permissions() {
+        let (owner, alice, bob) = (Signer::new(1), Signer::new(2), Signer::new(3));
+        let mut doc = MembershipLogDocument::new();
+
+        // Outsiders cannot write before or after creation
+        assert!(!alice.write(&mut doc, add(&bob, false)));
+        assert!(owner.write(&mut doc, MembershipAction::Create));
+        assert!(!alice.write(&mut doc, MembershipAction::Create));
+        assert!(!alice.write(&mut doc, add(&alice, false)));
+
+        assert!(owner.write(&mut doc, add(&alice, false)));
+        // Members may add members, but not admins
+        assert!(!alice.write(&mut doc, add(&bob, true)));
+        assert!(alice.write(&mut doc, add(&bob, false)));
+        // Only admins remove others; anyone may leave
+        assert!(!alice.write(&mut doc, remove(&bob)));
+        assert!(bob.write(&mut doc, remove(&bob)));
+        assert!(owner.write(&mut doc, remove(&alice)));
+
+        let state = doc.state();
+        assert_eq!(state.members, BTreeSet::from([owner.id]));
+        assert!(doc.verify().is_empty());
+        assert!(doc.history().iter().all(|r| r.accepted));
+    }
+
+    #[test]
+    fn test_second_create_cannot_replace_first() {
+        let (owner, mallory) = (Signer::new(1), Signer::new(2));
+        let mut local = MembershipLogDocument::new();
+        owner.write(&mut local, MembershipAction::Create);
+
+        // A second root, whatever its hash, is refused directly and on merge
+        let create = MembershipEntry {
+            parents: Vec::new(),
+            seq: 0,
+            actor: mallory.id,
+            action: MembershipAction::Create,
+            at_millis: 0,
+        };
+        let signed = SignedMembershipEntry::sign(create, &mallory.identity);
+        assert!(!local.clone().append(signed.clone()));
+
+        let mut remote = MembershipLogDocument::new();
+        remote.entries.insert(signed.hash(), signed.clone());
+        remote.entries.extend(local.entries.clone());
+        assert_eq!(remote.verify().len(), 2);
+
+        local.merge(remote);
+        assert_eq!(local.entries.len(), 1);
+        assert!(local.state().is_owner(&owner.id));
+        assert!(!local.state().is_member(&mallory.id));
+    }
+
+    #[test]
+    fn test_forged_and_rekeyed_entries_rejected() {
+        let (owner, alice) = (Signer::new(1), Signer::new(2));
+        let mut doc = MembershipLogDocument::new();
+        owner.write(&mut doc, MembershipAction::Create);
+
+        // Signed by someone else's key under the owner's id
+        let forged = doc.next_entry(owner.id, add(&alice, true), 10);
+        assert!(!doc.append(SignedMembershipEntry::sign(forged, &alice.identity)));
+
+        // Tampered after signing
+        let entry = doc.next_entry(owner.id, add(&alice, false), 10);
+        let mut signed = SignedMembershipEntry::sign(entry, &owner.identity);
+        signed.entry.action = add(&alice, true);
+        assert!(!doc.append(signed));
+        assert_eq!(doc.entries.len(), 1);
+    }
+
+    #[test]
+    fn test_keys_pinned_by_add() {
+        let (owner, alice, mallory) = (Signer::new(1), Signer::new(2), Signer::new(3));
+        let mut doc = MembershipLogDocument::new();
+        owner.write(&mut doc, MembershipAction::Create);
+        assert!(owner.write(&mut doc, add(&alice, true)));
+        assert_eq!(
+            doc.state().keys.get(&alice.id),
+            Some(&alice.identity.verifying_key_bytes())
+        );
+
+        // Alice has not written anything yet; signing as her still fails
+        let impersonated = doc.next_entry(alice.id, add(&mallory, true), 10);
+        assert!(!doc.append(SignedMembershipEntry::sign(impersonated, &mallory.identity)));
+        assert!(alice.write(&mut doc, add(&mallory, false)));
+        assert!(!doc.state().is_admin(&mallory.id));
+    }
+
+    #[test]
+    fn test_merge_drops_unauthorized_entries() {
+        let (owner, alice, bob) = (Signer::new(1), Signer::new(2), Signer::new(3));
+        let mut local = MembershipLogDocument::new();
+        owner.write(&mut local, MembershipAction::Create);
+        owner.write(&mut local, add(&alice, false));
+
+        let mut remote = local.clone();
+        alice.write(&mut remote, add(&bob, false));
+        // Bypass append: alice removing the owner is not allowed
+        let entry = remote.next_entry(alice.id, remove(&owner), 10);
+        let signed = SignedMembershipEntry::sign(entry, &alice.identity);
+        remote.entries.insert(signed.hash(), signed);
+        assert_eq!(remote.verify().len(), 1);
+
+        local.merge(remote);
+        assert_eq!(local.entries.len(), 3);
+        assert!(local.state().is_member(&bob.id));
+        assert!(local.state().is_member(&owner.id));
+    }
+
+    #[test]
+    fn test_concurrent_branches_replay() {
+        let (owner, alice, bob) = (Signer::new(1), Signer::new(2), Signer::new(3));
+        let mut a = MembershipLogDocument::new();
+        owner.write(&mut a, MembershipAction::Create);
+        owner.write(&mut a, add(&alice, false));
+        let mut b = a.clone();
+
+        // Alice adds bob while the owner concurrently removes alice
+        alice.write(&mut a, add(&bob, false));
+        owner.write(&mut b, remove(&alice));
+        a.merge(b.clone());
+        b.merge(a.clone());
+
+        assert_eq!(a.heads().len(), 2);
+        assert_eq!(a.state(), b.state());
+        assert!(!a.state().is_member(&alice.id));
+
+        // A later entry joins both heads
+        assert!(owner.write(&mut a, add(&alice, false)));
+        assert_eq!(a.heads().len(), 1);
+    }
+
+    #[test]
+    fn test_ownership_transfer() {
+        let (owner, alice, bob) = (Signer::new(1), Signer::new(2), Signer::new(3));
+        let mut doc = MembershipLogDocument::new();
+        owner.write(&mut doc, MembershipAction::Create);
+        owner.write(&mut doc, add(&alice, false));
+        assert!(doc.state().is_owner(&owner.id));
+
+        // Only to members, and only by the owner
+        let to = |m: &Signer| MembershipAction::TransferOwnership { to: m.id };
+        assert!(!owner.write(&mut doc, to(&bob)));
+        assert!(!alice.write(&mut doc, to(&alice)));
+        assert!(owner.write(&mut doc, to(&alice)));
+
+        let state = doc.state();
+        assert!(state.is_owner(&alice.id));
+        assert!(state.is_admin(&alice.id));
+        assert!(!owner.write(&mut doc, to(&owner)));
+
+        let changes = doc.ownership_changes();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].previous, Some(owner.id));
+        assert_eq!(changes[0].owner, alice.id);
+        assert!(!changes[0].succession);
+    }
+
+    #[test]
+    fn test_succession_after_inactivity() {
+        let (owner, alice, bob) = (Signer::new(1), Signer::new(2), Signer::new(3));
+        let mut doc = MembershipLogDocument::new();
+        owner.write_at(&mut doc, MembershipAction::Create, 0);
+        owner.write_at(&mut doc, add(&alice, false), 0);
+        owner.write_at(&mut doc, add(&bob, false), 0);
+        let plan = MembershipAction::SetSuccession {
+            successors: vec![bob.id, alice.id],
+            inactive_days: 1,
+        };
+        assert!(owner.write_at(&mut doc, plan, 0));
+        assert_eq!(doc.state().claim_allowed_at(&bob.id), Some(DAY_MILLIS));
+        assert_eq!(doc.state().claim_allowed_at(&alice.id), Some(2 * DAY_MILLIS));
+
+        // Too early, and a heartbeat restarts the clock
+        assert!(!bob.write_at(&mut doc, MembershipAction::ClaimOwnership, DAY_MILLIS - 1));
+        assert!(owner.write_at(&mut doc, MembershipAction::OwnerHeartbeat, 100));
+        assert!(!bob.write_at(&mut doc, MembershipAction::ClaimOwnership, DAY_MILLIS));
+        assert!(!alice.write_at(&mut doc, MembershipAction::ClaimOwnership, DAY_MILLIS + 100));
+
+        assert!(bob.write_at(&mut doc, MembershipAction::ClaimOwnership, DAY_MILLIS + 100));
+        let state = doc.state();
+        assert!(state.is_owner(&bob.id));
+        assert!(state.is_admin(&bob.id));
+        assert_eq!(state.eligible_successors(), vec![alice.id]);
+        assert!(!owner.write_at(&mut doc, MembershipAction::OwnerHeartbeat, DAY_MILLIS + 200));
+        assert!(doc.ownership_changes()[0].succession);
+    }
+
+    #[test]
+    fn test_admin_succeeds_owner_who_left() {
+        let (owner, alice, bob) = (Signer::new(1), Signer::new(2), Signer::new(3));
+        let mut doc = MembershipLogDocument::new();
+        owner.write_at(&mut doc, MembershipAction::Create, 0);
+        owner.write_at(&mut doc, add(&alice, true), 0);
+        owner.write_at(&mut doc, add(&bob, false), 0);
+        assert!(owner.write_at(&mut doc, remove(&owner), 50));
+
+        // Without a succession list, admins succeed; members don't
+        assert_eq!(doc.state().eligible_successors(), vec![alice.id]);
+        assert!(!bob.write_at(&mut doc, MembershipAction::ClaimOwnership, 60));
+        assert!(alice.write_at(&mut doc, MembershipAction::ClaimOwnership, 60));
+        assert!(doc.state().is_owner(&alice.id));
+    }
+
+    #[test]
+    fn test_claim_checked_against_receiver_clock() {
+        let (owner, bob) = (Signer::new(1), Signer::new(2));
+        let mut doc = MembershipLogDocument::new();
+        let now = now_millis();
+        owner.write_at(&mut doc, MembershipAction::Create, now);
+        owner.write_at(&mut doc, add(&bob, true), now);
+        assert_eq!(doc.state().claim_allowed_at(&bob.id), Some(now + 30 * DAY_MILLIS));
+
+        // Dated past the succession period, but the period hasn't run out here
+        let claim = |doc: &MembershipLogDocument, at_millis| {
+            let entry = doc.next_entry(bob.id, MembershipAction::ClaimOwnership, at_millis);
+            SignedMembershipEntry::sign(entry, &bob.identity)
+        };
+        let future = claim(&doc, now + 31 * DAY_MILLIS);
+        assert!(doc.state_at(&future.entry.parents).permits(&future));
+        assert!(!doc.clone().append_at(future.clone(), now + DAY_MILLIS));
+        let mut remote = MembershipLogDocument::new();
+        remote.entries.insert(future.hash(), future.clone());
+        let mut merged = doc.clone();
+        merged.merge(remote);
+        assert!(!merged.state().is_owner(&bob.id));
+
+        // Once due by the receiver's clock, a claim within the skew is accepted
+        let later = now + 31 * DAY_MILLIS - MAX_CLOCK_SKEW_MILLIS;
+        assert!(!doc.clone().append_at(future, later - 1));
+        assert!(doc.append_at(claim(&doc, now + 31 * DAY_MILLIS), later));
+        assert!(doc.state().is_owner(&bob.id));
+    }
+}
@@ -3,6 +3,7 @@
 //! Provides simplified message types that wrap the underlying
 //! messaging infrastructure.
 
+use crate::geo::Geohash;
 use crate::member::{Member, MemberId};
 use chrono::{DateTime, Utc};
 use indras_core::{EventId, InterfaceId};
@@ -44,6 +45,8 @@ pub struct Message {
     pub timestamp: DateTime<Utc>,
     /// Optional message this is replying to.
     pub reply_to: Option<MessageId>,
+    /// Coarse location the sender attached, if any.
+    pub geotag: Option<Geohash>,
 }
 
 impl Message {
@@ -60,6 +63,7 @@ impl Message {
             content,
             timestamp,
             reply_to: None,
+            geotag: None,
         }
     }
 
@@ -77,6 +81,7 @@ impl Message {
             content,
             timestamp,
             reply_to: Some(reply_to),
+            geotag: None,
         }
     }
 }
@@ -91,6 +96,16 @@ pub struct MessagePayload {
     pub content: Content,
     /// Optional message ID this is replying to.
     pub reply_to: Option<MessageId>,
+    /// Optional coarse geo-tag. Appended last so older peers, which
+    /// ignore trailing bytes, still decode the content.
+    pub geotag: Option<Geohash>,
+}
+
+/// Payload layout before geo-tags were added.
+#[derive(Deserialize)]
+struct LegacyMessagePayload {
+    content: Content,
+    reply_to: Option<MessageId>,
 }
 
 impl MessagePayload {
@@ -99,6 +114,7 @@ impl MessagePayload {
         Self {
             content,
             reply_to: None,
+            geotag: None,
         }
     }
 
@@ -107,6 +123,43 @@ impl MessagePayload {
         Self {
             content,
             reply_to: Some(reply_to),
+            geotag: None,
+        }
+    }
+
+    /// Attach a coarse geo-tag.
+    pub fn with_geotag(mut self, geotag: Geohash) -> Self {
+        self.geotag = Some(geotag);
+        self
+    }
+
+    /// Decode a payload, accepting the layout without a geo-tag.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if let Ok(payload) = postcard::from_bytes::<MessagePayload>(bytes) {
+            return Some(payload);
+        }
+        let legacy = postcard::from_bytes::<LegacyMessagePayload>(bytes).ok()?;
+        Some(Self {
+            content: legacy.content,
+            reply_to: legacy.reply_to,
+            geotag: None,
+        })
+    }
+
+    /// Convert into a [`Message`] with the given envelope fields.
+    pub(crate) fn into_message(
+        self,
+        id: MessageId,
+        sender: Member,
+        timestamp: DateTime<Utc>,
+    ) -> Message {
+        Message {
+            id,
+            sender,
+            content: self.content,
+            timestamp,
+            reply_to: self.reply_to,
+            geotag: self.geotag,
         }
     }
 }
@@ -235,6 +288,23 @@ pub enum Content {
         /// The recovery manifest listing available artifacts.
         manifest: crate::artifact_recovery::RecoveryManifest,
     },
+
+    /// An entry from one of the realm's sticker packs.
+    ///
+    /// Render by fetching the hash through the asset cache; custom emoji
+    /// in text and reactions use `:shortcode:` instead.
+    Sticker(crate::sticker_pack::StickerRef),
+
+    /// Text with sender-generated preview cards for the URLs it contains.
+    ///
+    /// Render only [`Content::verified_previews`]; receivers never fetch
+    /// the URLs themselves.
+    TextWithPreviews {
+        /// The message text.
+        text: String,
+        /// Signed preview cards, one per previewed URL.
+        previews: Vec<crate::link_preview::SignedLinkPreview>,
+    },
 }
 
 /// Reference to an item in a gallery.
@@ -258,7 +328,7 @@ impl Content {
     /// Get the text content if this is a text message.
     pub fn as_text(&self) -> Option<&str> {
         match self {
-            Content::Text(text) => Some(text),
+            Content::Text(text) | Content::TextWithPreviews { text, .. } => Some(text),
             _ => None,
         }
     }
@@ -339,6 +409,31 @@ impl Content {
         matches!(self, Content::Gallery { .. })
     }
 
+    /// Get the sticker reference if this is a sticker message.
+    pub fn as_sticker(&self) -> Option<&crate::sticker_pack::StickerRef> {
+        match self {
+            Content::Sticker(sticker) => Some(sticker),
+            _ => None,
+        }
+    }
+
+    /// Preview cards safe to render for a message with previews.
+    ///
+    /// Keeps cards with a valid signature whose URL appears in the text,
+    /// up to [`MAX_PREVIEWS_PER_MESSAGE`](crate::link_preview::MAX_PREVIEWS_PER_MESSAGE).
+    pub fn verified_previews(&self) -> Vec<&crate::link_preview::LinkPreview> {
+        let Content::TextWithPreviews { text, previews } = self else {
+            return Vec::new();
+        };
+        let urls = crate::link_preview::extract_urls(text);
+        previews
+            .iter()
+            .filter(|p| urls.contains(&p.preview.url.as_str()) && p.verify())
+            .map(|p| &p.preview)
+            .take(crate::link_preview::MAX_PREVIEWS_PER_MESSAGE)
+            .collect()
+    }
+
     /// Check if this is any kind of inline displayable image.
     ///
     /// Returns true for embedded images and inline artifacts with image MIME types.
@@ -499,6 +594,30 @@ mod tests {
         assert!(reply_to.is_some());
     }
 
+    #[test]
+    fn test_payload_decodes_with_and_without_geotag() {
+        #[derive(Serialize)]
+        struct Legacy {
+            content: Content,
+            reply_to: Option<MessageId>,
+        }
+        let legacy = postcard::to_allocvec(&Legacy {
+            content: Content::Text("old".into()),
+            reply_to: None,
+        })
+        .unwrap();
+        let decoded = MessagePayload::from_bytes(&legacy).unwrap();
+        assert_eq!(decoded.content.as_text(), Some("old"));
+        assert!(decoded.geotag.is_none());
+
+        let tag = Geohash::parse("u4pr").unwrap();
+        let bytes = postcard::to_allocvec(
+            &MessagePayload::new(Content::Text("new".into())).with_geotag(tag.clone()),
+        )
+        .unwrap();
+        assert_eq!(MessagePayload::from_bytes(&bytes).unwrap().geotag, Some(tag));
+    }
+
     #[test]
     fn test_content_image() {
         let content = Content::Image {
@@ -0,0 +1,389 @@
+//! Read-only static mirrors of a realm.
+//!
+//! Public communities can publish a realm as a bundle of static HTML and
+//! JSON files that can be hosted anywhere. What is published is decided by
+//! the realm's moderators in a synced [`PublishListDocument`]: the chat
+//! and/or named documents. Anything not on the list never leaves the node.
+//!
+//! A [`MirrorExporter`] renders a [`RealmSnapshot`] into files and writes
+//! them to a directory, alongside a `manifest.json` of content hashes.
+//! Re-exporting only rewrites files whose content changed and removes
+//! files that are no longer published, so a mirror can be regenerated on
+//! every realm change and synced to a web host cheaply.
+//!
+//! Chat messages hidden by a moderator are left out, as are deleted
+//! messages and non-text messages (images, files, proofs). Documents are
+//! exported as their JSON form, so only document types registered with
+//! [`MirrorExporter::with_document`] can be published.
+//!
+//! # Example
+//!
+//! ```ignore
+//! realm.publish_chat(true).await?;
+//! realm.publish_document("notes", true).await?;
+//!
+//! let exporter = MirrorExporter::new().with_document::<NoteDocument>("notes");
+//! let report = exporter.export(&realm, "site/").await?;
+//! println!("{} written, {} unchanged", report.written.len(), report.unchanged);
+//! ```
+
+use crate::chat_message::{EditableMessageType, RealmChatDocument};
+use crate::document::DocumentSchema;
+use crate::error::{IndraError, Result};
+use crate::legal_hold::{LegalHoldDocument, LEGAL_HOLD_DOC};
+use crate::moderation::{moderate, ContentFilters, ModerationDocument, Visibility, MODERATION_DOC};
+use crate::realm::Realm;
+use crate::snapshot::RealmSnapshot;
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// Document name for the realm's publish allow-list.
+pub const PUBLISH_DOC: &str = "_publish";
+
+/// Name of the manifest written at the root of a mirror.
+pub const MIRROR_MANIFEST: &str = "manifest.json";
+
+/// What moderators allow to be published in a realm's mirror.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublishListDocument {
+    /// Public title of the mirror, if set.
+    pub title: Option<String>,
+    /// Whether the chat is published.
+    pub chat: bool,
+    /// Names of published documents.
+    pub documents: BTreeSet<String>,
+}
+
+impl PublishListDocument {
+    /// Whether a named document may be published.
+    pub fn allows(&self, name: &str) -> bool {
+        self.documents.contains(name)
+    }
+
+    /// Whether anything is published at all.
+    pub fn is_empty(&self) -> bool {
+        !self.chat && self.documents.is_empty()
+    }
+}
+
+impl DocumentSchema for PublishListDocument {}
+
+/// One file of a rendered mirror.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MirrorFile {
+    /// Path relative to the mirror root, `/`-separated.
+    pub path: String,
+    /// File contents.
+    pub bytes: Vec<u8>,
+}
+
+/// Outcome of writing a mirror to disk.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MirrorReport {
+    /// Files created or rewritten, relative to the mirror root.
+    pub written: Vec<String>,
+    /// Files left as they were.
+    pub unchanged: usize,
+    /// Files deleted because they are no longer published.
+    pub removed: Vec<String>,
+}
+
+impl MirrorReport {
+    /// Whether the export changed anything on disk.
+    pub fn is_unchanged(&self) -> bool {
+        self.written.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Content hashes of a written mirror, stored as `manifest.json`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct MirrorManifest {
+    /// BLAKE3 hash (hex) per file path.
+    files: BTreeMap<String, String>,
+}
+
+/// A chat message as published.
+#[derive(Debug, Clone, Serialize)]
+struct PublishedMessage<'a> {
+    id: &'a str,
+    author: &'a str,
+    created_at: u64,
+    text: &'a str,
+    edited: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reply_to: Option<&'a str>,
+}
+
+type RenderDocument =
+    Box<dyn Fn(&RealmSnapshot, &str) -> serde_json::Result<serde_json::Value> + Send + Sync>;
+
+/// Renders realms into static mirrors.
+pub struct MirrorExporter {
+    documents: BTreeMap<String, RenderDocument>,
+}
+
+impl MirrorExporter {
+    /// An exporter that publishes the chat only.
+    pub fn new() -> Self {
+        Self {
+            documents: BTreeMap::new(),
+        }
+    }
+
+    /// Export the document `name` as type `T` when the realm publishes it.
+    pub fn with_document<T: DocumentSchema>(mut self, name: impl Into<String>) -> Self {
+        self.documents.insert(
+            name.into(),
+            Box::new(|snapshot, name| serde_json::to_value(&*snapshot.document::<T>(name))),
+        );
+        self
+    }
+
+    /// Snapshot a realm and write its mirror to `dir`.
+    pub async fn export(&self, realm: &Realm, dir: impl AsRef<Path>) -> Result<MirrorReport> {
+        let snapshot = realm.snapshot().await?;
+        let files = self.render(&snapshot)?;
+        Ok(write_mirror(dir.as_ref(), &files)?)
+    }
+
+    /// Render the published parts of a snapshot.
+    ///
+    /// Output is deterministic for a given realm state, which is what lets
+    /// [`write_mirror`] skip unchanged files.
+    pub fn render(&self, snapshot: &RealmSnapshot) -> Result<Vec<MirrorFile>> {
+        let publish = snapshot.document::<PublishListDocument>(PUBLISH_DOC);
+        let realm_hex = hex::encode(snapshot.realm_id().as_bytes());
+        let title = publish
+            .title
+            .clone()
+            .unwrap_or_else(|| format!("Realm {}", &realm_hex[..8]));
+        let mut files = Vec::new();
+        let mut sections = Vec::new();
+
+        if publish.chat {
+            let chat = snapshot.document::<RealmChatDocument>("chat");
+            let moderation = snapshot.document::<ModerationDocument>(MODERATION_DOC);
+            let holds = snapshot.document::<LegalHoldDocument>(LEGAL_HOLD_DOC);
+            let moderated = moderate(
+                &chat,
+                &moderation,
+                &holds.moderators,
+                &ContentFilters::new(),
+            );
+            let messages: Vec<PublishedMessage<'_>> = moderated
+                .iter()
+                .filter(|m| matches!(m.visibility, Visibility::Visible))
+                .filter(|m| matches!(m.message.message_type, EditableMessageType::Text))
+                .map(|m| PublishedMessage {
+                    id: &m.message.id,
+                    author: &m.message.author,
+                    created_at: m.message.created_at,
+                    text: &m.message.current_content,
+                    edited: m.message.is_edited(),
+                    reply_to: m.message.reply_to.as_deref(),
+                })
+                .collect();
+
+            files.push(MirrorFile {
+                path: "chat.json".to_string(),
+                bytes: serde_json::to_vec_pretty(&messages).map_err(json_error)?,
+            });
+            files.push(MirrorFile {
+                path: "chat.html".to_string(),
+                bytes: page(&title, "Chat", &chat_html(&messages)).into_bytes(),
+            });
+            sections.push((
+                "chat.html".to_string(),
+                format!("Chat ({} messages)", messages.len()),
+            ));
+        }
+
+        for (name, render) in &self.documents {
+            if !publish.allows(name) {
+                continue;
+            }
+            let value = render(snapshot, name).map_err(json_error)?;
+            let slug = slug(name);
+            let json = serde_json::to_string_pretty(&value).map_err(json_error)?;
+            files.push(MirrorFile {
+                path: format!("docs/{slug}.json"),
+                bytes: json.clone().into_bytes(),
+            });
+            files.push(MirrorFile {
+                path: format!("docs/{slug}.html"),
+                bytes: page(&title, name, &format!("<pre>{}</pre>", escape(&json))).into_bytes(),
+            });
+            sections.push((format!("docs/{slug}.html"), name.clone()));
+        }
+
+        let list: String = sections
+            .iter()
+            .map(|(href, label)| format!("<li><a href=\"{href}\">{}</a></li>\n", escape(label)))
+            .collect();
+        files.push(MirrorFile {
+            path: "index.html".to_string(),
+            bytes: page(&title, &title, &format!("<ul>\n{list}</ul>")).into_bytes(),
+        });
+        Ok(files)
+    }
+}
+
+impl Default for MirrorExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Write rendered files to `dir`, touching only what changed.
+///
+/// Files whose hash matches the previous `manifest.json` are skipped;
+/// files listed there but no longer rendered are deleted.
+pub fn write_mirror(dir: &Path, files: &[MirrorFile]) -> std::io::Result<MirrorReport> {
+    let manifest_path = dir.join(MIRROR_MANIFEST);
+    let previous: MirrorManifest = std::fs::read(&manifest_path)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default();
+
+    let mut report = MirrorReport::default();
+    let mut manifest = MirrorManifest::default();
+    for file in files {
+        let hash = blake3::hash(&file.bytes).to_hex().to_string();
+        let target = dir.join(&file.path);
+        if previous.files.get(&file.path) == Some(&hash) && target.exists() {
+            report.unchanged += 1;
+        } else {
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&target, &file.bytes)?;
+            report.written.push(file.path.clone());
+        }
+        manifest.files.insert(file.path.clone(), hash);
+    }
+
+    for path in previous.files.keys() {
+        if !manifest.files.contains_key(path) {
+            match std::fs::remove_file(dir.join(path)) {
+                Ok(()) => report.removed.push(path.clone()),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    let manifest_bytes = serde_json::to_vec_pretty(&manifest).map_err(std::io::Error::other)?;
+    std::fs::write(manifest_path, manifest_bytes)?;
+    Ok(report)
+}
+
+fn json_error(e: serde_json::Error) -> IndraError {
+    IndraError::Serialization(e.to_string())
+}
+
+fn chat_html(messages: &[PublishedMessage<'_>]) -> String {
+    let mut html = String::from("<ol class=\"chat\">\n");
+    for m in messages {
+        let at = chrono::DateTime::from_timestamp_millis(m.created_at as i64)
+            .map(|t| t.format("%Y-%m-%d %H:%M UTC").to_string())
+            .unwrap_or_default();
+        html.push_str(&format!(
+            "<li id=\"{}\"><b>{}</b> <time>{at}</time>{}<p>{}</p></li>\n",
+            escape(m.id),
+            escape(m.author),
+            if m.edited { " <i>(edited)</i>" } else { "" },
+            escape(m.text),
+        ));
+    }
+    html.push_str("</ol>");
+    html
+}
+
+fn page(site_title: &str, heading: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{}</title></head>\n<body><h1>{}</h1>\n{body}\n</body></html>\n",
+        escape(site_title),
+        escape(heading)
+    )
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// A document name made safe for use as a file name.
+fn slug(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, text: &str) -> MirrorFile {
+        MirrorFile {
+            path: path.to_string(),
+            bytes: text.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_write_mirror_is_incremental() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = write_mirror(
+            dir.path(),
+            &[file("index.html", "a"), file("docs/notes.json", "{}")],
+        )
+        .unwrap();
+        assert_eq!(first.written, vec!["index.html", "docs/notes.json"]);
+
+        let second = write_mirror(
+            dir.path(),
+            &[file("index.html", "b"), file("docs/notes.json", "{}")],
+        )
+        .unwrap();
+        assert_eq!(second.written, vec!["index.html"]);
+        assert_eq!(second.unchanged, 1);
+
+        // Unpublished documents disappear from the mirror
+        let third = write_mirror(dir.path(), &[file("index.html", "b")]).unwrap();
+        assert!(third.written.is_empty());
+        assert_eq!(third.removed, vec!["docs/notes.json"]);
+        assert!(!dir.path().join("docs/notes.json").exists());
+        assert!(
+            write_mirror(dir.path(), &[file("index.html", "b")])
+                .unwrap()
+                .is_unchanged()
+        );
+    }
+
+    #[test]
+    fn test_escape_and_slug() {
+        assert_eq!(
+            escape("<b>\"hi\" & 'bye'</b>"),
+            "&lt;b&gt;&quot;hi&quot; &amp; &#39;bye&#39;&lt;/b&gt;"
+        );
+        assert_eq!(slug("../quests 2024"), "___quests_2024");
+    }
+}
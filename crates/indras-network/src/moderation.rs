@@ -0,0 +1,486 @@
+//! Content moderation: member reports, moderator actions, local filters.
+//!
+//! Three layers decide whether a chat message is shown:
+//!
+//! - **Reports**: any member can report a message. Reports are aggregated
+//!   per message in the realm's [`ModerationDocument`] so moderators can
+//!   triage the most-reported first.
+//! - **Moderator actions**: moderators can hide a message for everyone or
+//!   warn a member. Actions are recorded in the same document; when reading,
+//!   only actions taken by the realm's moderators (the set kept in the
+//!   [`LegalHoldDocument`](crate::legal_hold::LegalHoldDocument)) count, so
+//!   a member who writes a hide without the role hides nothing.
+//! - **Local filters**: each client can register [`ContentFilter`]s
+//!   (keywords, patterns, or any classifier such as an ML model) that hide
+//!   messages on this device only. Nothing about them is synced.
+//!
+//! [`moderate`] combines all three into a [`ModeratedMessage`] per message.
+
+use crate::chat_message::{ChatMessageId, EditableChatMessage, RealmChatDocument};
+use crate::document::DocumentSchema;
+use crate::member::MemberId;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Arc, RwLock};
+
+/// Document name for moderation state within a realm.
+pub const MODERATION_DOC: &str = "_moderation";
+
+/// A member's report of one message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Report {
+    /// Why the member reported the message.
+    pub reason: String,
+    /// When the report was made (milliseconds since epoch).
+    pub reported_at_millis: u64,
+}
+
+/// A moderator's decision to hide (or unhide) a message for everyone.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HideAction {
+    /// Reason shown in place of the message.
+    pub reason: String,
+    /// Whether the message is hidden.
+    pub hidden: bool,
+    /// When this action was last changed (milliseconds since epoch).
+    pub updated_at_millis: u64,
+}
+
+/// A moderator's warning to a member.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Warning {
+    /// The warned member.
+    pub member: MemberId,
+    /// Moderator who issued the warning.
+    pub by: MemberId,
+    /// What the warning is about.
+    pub reason: String,
+    /// When the warning was issued (milliseconds since epoch).
+    pub issued_at_millis: u64,
+}
+
+/// CRDT document of a realm's reports and moderator actions.
+///
+/// # Example
+///
+/// ```ignore
+/// realm.report_message(&message_id, "spam").await?;
+///
+/// // As a moderator:
+/// for (id, _reports) in realm.moderation().await?.read().await.flagged(3) {
+///     realm.hide_message_for_all(&id, "removed after reports").await?;
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModerationDocument {
+    /// Reports keyed by message, then by reporter.
+    pub reports: BTreeMap<ChatMessageId, BTreeMap<MemberId, Report>>,
+    /// Hide actions keyed by message, then by the member who took them.
+    pub hidden: BTreeMap<ChatMessageId, BTreeMap<MemberId, HideAction>>,
+    /// Warnings issued to members.
+    pub warnings: BTreeSet<Warning>,
+}
+
+impl ModerationDocument {
+    /// Create an empty moderation document.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a member's report. Returns `false` if they already reported it.
+    pub fn report(
+        &mut self,
+        reporter: MemberId,
+        message_id: ChatMessageId,
+        reason: String,
+        now_millis: u64,
+    ) -> bool {
+        let reports = self.reports.entry(message_id).or_default();
+        if reports.contains_key(&reporter) {
+            return false;
+        }
+        reports.insert(
+            reporter,
+            Report {
+                reason,
+                reported_at_millis: now_millis,
+            },
+        );
+        true
+    }
+
+    /// Number of members who reported a message.
+    pub fn report_count(&self, message_id: &str) -> usize {
+        self.reports.get(message_id).map_or(0, BTreeMap::len)
+    }
+
+    /// Messages with at least `min_reports` reports, most-reported first.
+    pub fn flagged(&self, min_reports: usize) -> Vec<(ChatMessageId, usize)> {
+        let mut flagged: Vec<_> = self
+            .reports
+            .iter()
+            .map(|(id, reports)| (id.clone(), reports.len()))
+            .filter(|(_, count)| *count >= min_reports.max(1))
+            .collect();
+        flagged.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        flagged
+    }
+
+    /// Hide or unhide a message for everyone.
+    pub fn set_hidden(
+        &mut self,
+        by: MemberId,
+        message_id: ChatMessageId,
+        hidden: bool,
+        reason: String,
+        now_millis: u64,
+    ) {
+        self.hidden.entry(message_id).or_default().insert(
+            by,
+            HideAction {
+                reason,
+                hidden,
+                updated_at_millis: now_millis,
+            },
+        );
+    }
+
+    /// Record a warning to a member.
+    pub fn warn(&mut self, by: MemberId, member: MemberId, reason: String, now_millis: u64) {
+        self.warnings.insert(Warning {
+            member,
+            by,
+            reason,
+            issued_at_millis: now_millis,
+        });
+    }
+
+    /// The hide in effect for a message and the moderator behind it.
+    ///
+    /// The latest action by any of `moderators` wins; actions by anyone
+    /// else are ignored.
+    pub fn hide_for(
+        &self,
+        message_id: &str,
+        moderators: &BTreeSet<MemberId>,
+    ) -> Option<(MemberId, &HideAction)> {
+        self.hidden
+            .get(message_id)?
+            .iter()
+            .filter(|(by, _)| moderators.contains(*by))
+            .max_by_key(|(by, a)| (a.updated_at_millis, **by))
+            .filter(|(_, a)| a.hidden)
+            .map(|(by, a)| (*by, a))
+    }
+
+    /// Warnings a member received from `moderators`, oldest first.
+    pub fn warnings_for(&self, member: &MemberId, moderators: &BTreeSet<MemberId>) -> Vec<&Warning> {
+        let mut warnings: Vec<_> = self
+            .warnings
+            .iter()
+            .filter(|w| &w.member == member && moderators.contains(&w.by))
+            .collect();
+        warnings.sort_by_key(|w| w.issued_at_millis);
+        warnings
+    }
+}
+
+impl DocumentSchema for ModerationDocument {
+    /// Report and warning union, per-member LWW over hide actions.
+    fn merge(&mut self, remote: Self) {
+        for (id, reports) in remote.reports {
+            let local = self.reports.entry(id).or_default();
+            for (reporter, report) in reports {
+                local.entry(reporter).or_insert(report);
+            }
+        }
+        for (id, actions) in remote.hidden {
+            let local = self.hidden.entry(id).or_default();
+            for (by, action) in actions {
+                match local.get(&by) {
+                    Some(mine) if mine.updated_at_millis >= action.updated_at_millis => {}
+                    _ => {
+                        local.insert(by, action);
+                    }
+                }
+            }
+        }
+        self.warnings.extend(remote.warnings);
+    }
+}
+
+/// A client-side check that can hide a message on this device.
+///
+/// Implemented for closures, so a classifier (an ML model, a blocklist
+/// service) plugs in as `filters.add("toxicity", |m: &EditableChatMessage| ...)`.
+pub trait ContentFilter: Send + Sync {
+    /// Reason to hide the message, or `None` to show it.
+    fn check(&self, message: &EditableChatMessage) -> Option<String>;
+}
+
+impl<F> ContentFilter for F
+where
+    F: Fn(&EditableChatMessage) -> Option<String> + Send + Sync,
+{
+    fn check(&self, message: &EditableChatMessage) -> Option<String> {
+        self(message)
+    }
+}
+
+/// Hides messages containing any of a list of words (case-insensitive).
+#[derive(Debug, Clone)]
+pub struct KeywordFilter {
+    keywords: Vec<String>,
+}
+
+impl KeywordFilter {
+    /// Create a filter for the given keywords.
+    pub fn new<I, S>(keywords: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            keywords: keywords
+                .into_iter()
+                .map(|k| k.as_ref().to_lowercase())
+                .filter(|k| !k.is_empty())
+                .collect(),
+        }
+    }
+}
+
+impl ContentFilter for KeywordFilter {
+    fn check(&self, message: &EditableChatMessage) -> Option<String> {
+        let content = message.current_content.to_lowercase();
+        self.keywords
+            .iter()
+            .find(|k| content.contains(k.as_str()))
+            .map(|k| format!("contains \"{k}\""))
+    }
+}
+
+/// Hides messages matching a regular expression.
+#[derive(Debug, Clone)]
+pub struct PatternFilter {
+    pattern: Regex,
+}
+
+impl PatternFilter {
+    /// Compile a filter from a regular expression.
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        Ok(Self {
+            pattern: Regex::new(pattern)?,
+        })
+    }
+}
+
+impl ContentFilter for PatternFilter {
+    fn check(&self, message: &EditableChatMessage) -> Option<String> {
+        self.pattern
+            .is_match(&message.current_content)
+            .then(|| format!("matches /{}/", self.pattern.as_str()))
+    }
+}
+
+/// The local filters registered on this device.
+///
+/// Owned by `IndrasNetwork`; access it via `network.content_filters()`.
+#[derive(Default)]
+pub struct ContentFilters {
+    filters: RwLock<Vec<(String, Arc<dyn ContentFilter>)>>,
+}
+
+impl ContentFilters {
+    /// Create an empty filter set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a filter under a name, replacing any filter of that name.
+    pub fn add(&self, name: impl Into<String>, filter: impl ContentFilter + 'static) {
+        let name = name.into();
+        let mut filters = self.filters.write().unwrap_or_else(|e| e.into_inner());
+        filters.retain(|(n, _)| n != &name);
+        filters.push((name, Arc::new(filter)));
+    }
+
+    /// Remove a filter. Returns `false` if none had that name.
+    pub fn remove(&self, name: &str) -> bool {
+        let mut filters = self.filters.write().unwrap_or_else(|e| e.into_inner());
+        let before = filters.len();
+        filters.retain(|(n, _)| n != name);
+        filters.len() != before
+    }
+
+    /// Names of the registered filters, in the order they run.
+    pub fn names(&self) -> Vec<String> {
+        let filters = self.filters.read().unwrap_or_else(|e| e.into_inner());
+        filters.iter().map(|(n, _)| n.clone()).collect()
+    }
+
+    /// The first filter that hides a message, with its reason.
+    pub fn check(&self, message: &EditableChatMessage) -> Option<(String, String)> {
+        let filters = self.filters.read().unwrap_or_else(|e| e.into_inner());
+        filters
+            .iter()
+            .find_map(|(name, f)| f.check(message).map(|reason| (name.clone(), reason)))
+    }
+}
+
+impl std::fmt::Debug for ContentFilters {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ContentFilters")
+            .field("filters", &self.names())
+            .finish()
+    }
+}
+
+/// Whether and why a message is shown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Visibility {
+    /// Shown normally.
+    Visible,
+    /// Hidden for everyone by a moderator.
+    HiddenByModerator {
+        /// The moderator.
+        by: MemberId,
+        /// The moderator's reason.
+        reason: String,
+    },
+    /// Hidden on this device by a local filter.
+    HiddenByFilter {
+        /// Name of the filter.
+        filter: String,
+        /// The filter's reason.
+        reason: String,
+    },
+}
+
+impl Visibility {
+    /// Whether the message is shown.
+    pub fn is_visible(&self) -> bool {
+        matches!(self, Visibility::Visible)
+    }
+}
+
+/// A chat message with its moderation state.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModeratedMessage {
+    /// The message.
+    pub message: EditableChatMessage,
+    /// Whether and why it is shown.
+    pub visibility: Visibility,
+    /// Number of members who reported it.
+    pub reports: usize,
+}
+
+/// Apply moderator hides and local filters to a realm's visible messages.
+///
+/// Moderator hides take precedence over local filters. Messages come back
+/// sorted by `(created_at, id)`.
+pub fn moderate(
+    chat: &RealmChatDocument,
+    moderation: &ModerationDocument,
+    moderators: &BTreeSet<MemberId>,
+    filters: &ContentFilters,
+) -> Vec<ModeratedMessage> {
+    chat.visible_messages()
+        .into_iter()
+        .map(|message| {
+            let visibility = if let Some((by, hide)) = moderation.hide_for(&message.id, moderators) {
+                Visibility::HiddenByModerator {
+                    by,
+                    reason: hide.reason.clone(),
+                }
+            } else if let Some((filter, reason)) = filters.check(message) {
+                Visibility::HiddenByFilter { filter, reason }
+            } else {
+                Visibility::Visible
+            };
+            ModeratedMessage {
+                message: message.clone(),
+                visibility,
+                reports: moderation.report_count(&message.id),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOD: MemberId = [1u8; 32];
+    const MEMBER: MemberId = [2u8; 32];
+
+    fn text(id: &str, content: &str, at: u64) -> EditableChatMessage {
+        EditableChatMessage::new_text(id.into(), "realm".into(), "zephyr".into(), content.into(), at)
+    }
+
+    #[test]
+    fn test_reports_aggregate_per_reporter() {
+        let mut a = ModerationDocument::new();
+        assert!(a.report(MEMBER, "m1".into(), "spam".into(), 10));
+        assert!(!a.report(MEMBER, "m1".into(), "spam".into(), 20));
+
+        let mut b = ModerationDocument::new();
+        b.report(MOD, "m1".into(), "abuse".into(), 15);
+        b.report(MOD, "m2".into(), "abuse".into(), 15);
+        a.merge(b);
+
+        assert_eq!(a.report_count("m1"), 2);
+        assert_eq!(a.flagged(2), vec![("m1".to_string(), 2)]);
+        assert_eq!(a.flagged(0).len(), 2);
+    }
+
+    #[test]
+    fn test_only_moderator_actions_count() {
+        let moderators = BTreeSet::from([MOD]);
+        let mut doc = ModerationDocument::new();
+        doc.set_hidden(MEMBER, "m1".into(), true, "mine".into(), 10);
+        doc.warn(MEMBER, MOD, "nope".into(), 10);
+        assert!(doc.hide_for("m1", &moderators).is_none());
+        assert!(doc.warnings_for(&MOD, &moderators).is_empty());
+
+        doc.set_hidden(MOD, "m1".into(), true, "abuse".into(), 20);
+        doc.warn(MOD, MEMBER, "first warning".into(), 20);
+        assert_eq!(doc.hide_for("m1", &moderators).unwrap().1.reason, "abuse");
+        doc.set_hidden(MEMBER, "m1".into(), false, String::new(), 30);
+        assert!(doc.hide_for("m1", &moderators).is_some());
+        assert_eq!(doc.warnings_for(&MEMBER, &moderators).len(), 1);
+
+        let mut stale = ModerationDocument::new();
+        stale.set_hidden(MOD, "m1".into(), false, String::new(), 15);
+        doc.merge(stale);
+        assert!(doc.hide_for("m1", &moderators).is_some());
+    }
+
+    #[test]
+    fn test_moderate_applies_hides_then_filters() {
+        let mut chat = RealmChatDocument::new();
+        chat.add_message(text("m1", "hello", 1));
+        chat.add_message(text("m2", "Buy CHEAP pills", 2));
+        chat.add_message(text("m3", "call 555-0100", 3));
+
+        let mut moderation = ModerationDocument::new();
+        moderation.set_hidden(MOD, "m1".into(), true, "off topic".into(), 10);
+
+        let filters = ContentFilters::new();
+        filters.add("spam", KeywordFilter::new(["cheap"]));
+        filters.add("phones", PatternFilter::new(r"\d{3}-\d{4}").unwrap());
+        filters.add("never", |_: &EditableChatMessage| None);
+
+        let moderated = moderate(&chat, &moderation, &BTreeSet::from([MOD]), &filters);
+        assert!(matches!(moderated[0].visibility, Visibility::HiddenByModerator { .. }));
+        assert_eq!(
+            moderated[1].visibility,
+            Visibility::HiddenByFilter { filter: "spam".into(), reason: "contains \"cheap\"".into() }
+        );
+        assert!(matches!(&moderated[2].visibility, Visibility::HiddenByFilter { filter, .. } if filter == "phones"));
+
+        assert!(filters.remove("phones"));
+        assert!(filters.check(chat.get_message("m3").unwrap()).is_none());
+    }
+}
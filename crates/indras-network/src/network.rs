@@ -3,10 +3,31 @@
 //! Provides a high-level API for building P2P applications on Indra's Network.
 
 use crate::config::{NetworkBuilder, NetworkConfig, Preset};
+use crate::contact_verification::{SafetyNumber, VerificationMethod, VerificationStatus};
 use crate::contacts::ContactsRealm;
+use crate::contact_invite::{ContactInvite, ContactInviteMessage, InviteEvent};
+use crate::digest::{DigestGenerator, RealmDigest};
+use crate::event_bus::EventBus;
+use crate::notifications::{
+    NotificationCenter, NotificationContext, NotificationPreferences, NOTIFICATION_SETTINGS_KEY,
+};
+use crate::moderation::ContentFilters;
+use crate::spam::{SpamDetector, SpamSettings, SPAM_SETTINGS_KEY};
+use crate::translation::Translations;
 use crate::direct_connect::{
     inbox_key_seed, inbox_realm_id, is_initiator, ConnectionNotify, GroupInvite, InboxMessage,
 };
+use crate::device_pairing::{
+    self, DEVICE_PAIRING_DOC, DevicePairing, LINKED_DEVICES_DOC, LinkedDevice,
+    LinkedDevicesDocument, PairingCode, PairingGrant, PairingRequest,
+};
+use crate::digest::{DigestGenerator, RealmDigest};
+use crate::direct_connect::{
+    ConnectionNotify, GroupInvite, InboxMessage, inbox_key_seed, inbox_realm_id, is_initiator,
+};
+use crate::document::Document;
+use crate::document_budget::DocumentBudgets;
+use crate::document_registry::DocumentRegistryDocument;
 use crate::encounter;
 use crate::error::{IndraError, Result};
 use crate::home_realm::{home_key_seed, home_realm_id, HomeRealm};
@@ -14,20 +35,33 @@ use crate::artifact_sync::{artifact_interface_id, artifact_key_seed};
 use crate::identity_code::IdentityCode;
 use crate::invite::InviteCode;
 use crate::member::{Member, MemberId};
+use crate::profile::{Profile, SignedProfile, MAX_PROFILE_FIELD_LEN};
 use crate::realm::Realm;
+use crate::realm_alias::{
+    normalize_handle, AliasDirectoryDocument, AliasRecord, ALIAS_DIRECTORY_DOC,
+};
+use crate::realm_organization::{RealmOrganizationDocument, REALM_ORGANIZATION_DOC};
+use crate::rehearsal::{rehearsed_payloads, Rehearsal};
+use crate::util::now_millis;
+use crate::realm_restructure::{merge_includes, RealmRestructure, ReplayKind, SplitFilter, REGISTRY_DOC};
+use crate::document_registry::DocumentRegistryDocument;
 use crate::artifact::{generate_tree_id, dm_story_id, ArtifactId};
 use indras_artifacts::AccessMode;
 
 use dashmap::DashMap;
 use indras_core::{InterfaceId, PeerIdentity};
-use indras_node::{IndrasNode, ReceivedEvent};
-use indras_storage::CompositeStorage;
+use indras_crypto::PQPublicIdentity;
+use indras_node::{
+    deterministic_realm_id, validate_peer_set, BackupSummary, IndrasNode, ReceivedEvent,
+};
+use indras_storage::{AssetCache, AssetCacheConfig, CompositeStorage};
 use indras_transport::IrohIdentity;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeSet;
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::OnceCell;
 use tokio::sync::{broadcast, watch, Mutex, Notify, RwLock};
 use tokio::task::JoinHandle;
@@ -73,6 +107,13 @@ struct UserProfile {
     /// Display name for this user.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     display_name: Option<String>,
+    /// The profile last published to shared realms.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    profile: Option<Profile>,
+    /// Account whose home realm this device uses, when linked via
+    /// device pairing. `None` means this device owns its home realm.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    home_owner: Option<MemberId>,
 }
 
 /// Filename for the persisted user profile.
@@ -117,6 +158,8 @@ pub struct IndrasNetwork {
     display_name_override: std::sync::RwLock<Option<String>>,
     /// Our identity.
     identity: Member,
+    /// Account whose home realm we use (ourselves unless linked).
+    home_owner: MemberId,
 
     // ── Peering state ────────────────────────────────────────────
     /// Watch channel sender for the current peer list.
@@ -143,6 +186,28 @@ pub struct IndrasNetwork {
     /// Time-throttled map of peers we've re-notified (avoids spam).
     /// Value is the last re-notification attempt time.
     re_notified_peers: Arc<DashMap<MemberId, std::time::Instant>>,
+    /// Per-realm notification digests.
+    digests: DigestGenerator,
+    /// Sequenced, filterable fan-out of realm events.
+    event_bus: EventBus,
+    /// OS notifications for incoming messages.
+    notifications: Arc<NotificationCenter>,
+    /// Local content filters applied to moderated message views.
+    content_filters: ContentFilters,
+    /// Local spam scoring of incoming chat messages.
+    spam: SpamDetector,
+    /// Translator for displaying messages in the reader's language.
+    translations: Translations,
+    /// Our profile, published to every shared realm.
+    own_profile: std::sync::RwLock<Profile>,
+    /// Newest verified profile seen for each peer.
+    profiles: Arc<DashMap<MemberId, Profile>>,
+    /// Content-addressed cache for avatars and custom emoji.
+    assets: Arc<AssetCache>,
+    /// Contact invitations received but not yet answered, by realm.
+    pending_invites: Arc<DashMap<RealmId, ContactInvite>>,
+    /// Broadcast channel for contact invitation events.
+    invite_tx: broadcast::Sender<InviteEvent>,
 }
 
 /// Internal realm state.
@@ -204,18 +269,29 @@ impl IndrasNetwork {
     /// Create a network instance with the given configuration.
     pub async fn with_config(mut config: NetworkConfig) -> Result<Arc<Self>> {
         // Load persisted profile (display name, etc.) if it exists
+        let stored = Self::load_profile(&config.data_dir);
+        let linked_owner = stored.as_ref().and_then(|p| p.home_owner);
         if config.display_name.is_none() {
-            if let Some(profile) = Self::load_profile(&config.data_dir) {
+            if let Some(profile) = &stored {
                 if profile.display_name.is_some() {
-                    config.display_name = profile.display_name;
+                    config.display_name = profile.display_name.clone();
                 }
             }
         }
 
+        // A configured name that differs from the published one is a change
+        let mut own_profile = stored.and_then(|p| p.profile).unwrap_or_default();
+        if own_profile.display_name != config.display_name {
+            own_profile.display_name = config.display_name.clone();
+            own_profile.updated_at_millis = chrono::Utc::now().timestamp_millis() as u64;
+        }
+
         // Persist display name if one was provided
         if config.display_name.is_some() {
             let profile = UserProfile {
                 display_name: config.display_name.clone(),
+                profile: Some(own_profile.clone()),
+                home_owner: linked_owner,
             };
             // Best-effort persistence — don't fail network creation if this fails
             let _ = Self::save_profile(&config.data_dir, &profile);
@@ -224,10 +300,18 @@ impl IndrasNetwork {
         let node_config = config.to_node_config();
         let node = IndrasNode::new(node_config).await?;
 
+        let assets = AssetCache::new(AssetCacheConfig {
+            max_bytes: config.asset_cache_bytes,
+            ..AssetCacheConfig::with_base_dir(config.data_dir.join("assets"))
+        })
+        .await?;
+
         let identity = Member::new(*node.identity());
+        let home_owner = linked_owner.unwrap_or_else(|| identity.id());
 
         let (peers_tx, peers_rx) = watch::channel(Vec::new());
         let (peer_event_tx, _) = broadcast::channel(256);
+        let (invite_tx, _) = broadcast::channel(64);
 
         Ok(Arc::new(Self {
             inner: Arc::new(node),
@@ -238,6 +322,7 @@ impl IndrasNetwork {
             config,
             display_name_override: std::sync::RwLock::new(None),
             identity,
+            home_owner,
             peers_tx,
             peers_rx,
             peer_event_tx,
@@ -247,6 +332,17 @@ impl IndrasNetwork {
             relay_blob_endpoint: OnceCell::new(),
             shutdown_called: AtomicBool::new(false),
             re_notified_peers: Arc::new(DashMap::new()),
+            digests: DigestGenerator::new(),
+            event_bus: EventBus::new(),
+            notifications: Arc::new(NotificationCenter::new()),
+            content_filters: ContentFilters::new(),
+            spam: SpamDetector::new(),
+            translations: Translations::new(),
+            own_profile: std::sync::RwLock::new(own_profile),
+            profiles: Arc::new(DashMap::new()),
+            assets: Arc::new(assets),
+            pending_invites: Arc::new(DashMap::new()),
+            invite_tx,
         }))
     }
 
@@ -264,6 +360,19 @@ impl IndrasNetwork {
         &self.identity
     }
 
+    /// The account whose home realm this device uses.
+    ///
+    /// Equal to [`id`](Self::id) unless this device was linked to another
+    /// device's account via [`link_device`](Self::link_device).
+    pub fn home_owner(&self) -> MemberId {
+        self.home_owner
+    }
+
+    /// Whether this device was linked to another device's account.
+    pub fn is_linked_device(&self) -> bool {
+        self.home_owner != self.id()
+    }
+
     /// Get the display name for this network instance.
     pub fn display_name(&self) -> Option<String> {
         if let Ok(guard) = self.display_name_override.read() {
@@ -275,26 +384,154 @@ impl IndrasNetwork {
     }
 
     /// Set the display name for this network instance.
+    ///
+    /// Also updates the display name in our [`profile`](Self::profile).
     pub async fn set_display_name(&self, name: impl Into<String>) -> Result<()> {
-        let name = name.into();
+        let mut profile = self.profile();
+        profile.display_name = Some(name.into());
+        self.set_profile(profile).await
+    }
+
+    /// Get our profile as published to shared realms.
+    pub fn profile(&self) -> Profile {
+        self.own_profile
+            .read()
+            .map(|p| p.clone())
+            .unwrap_or_default()
+    }
+
+    /// Update our profile and publish it to every shared realm.
+    ///
+    /// The profile is signed with our PQ identity and persisted; realms
+    /// joined later receive it on the next profile sync. The update time is
+    /// set here, so the caller's `updated_at_millis` is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// network.set_profile(Profile {
+    ///     display_name: Some("Ada".into()),
+    ///     avatar: Some(avatar_artifact_id),
+    ///     pronouns: Some("she/her".into()),
+    ///     status: Some("heads down until Friday".into()),
+    ///     ..Default::default()
+    /// }).await?;
+    /// ```
+    pub async fn set_profile(&self, mut profile: Profile) -> Result<()> {
+        if !profile.is_valid() {
+            return Err(IndraError::InvalidOperation(format!(
+                "profile fields must be at most {MAX_PROFILE_FIELD_LEN} bytes"
+            )));
+        }
+        let previous = self.profile().updated_at_millis;
+        profile.updated_at_millis =
+            (chrono::Utc::now().timestamp_millis() as u64).max(previous + 1);
+
         if let Ok(mut guard) = self.display_name_override.write() {
-            *guard = Some(name.clone());
+            *guard = profile.display_name.clone();
+        }
+        if let Ok(mut guard) = self.own_profile.write() {
+            *guard = profile.clone();
         }
 
         // Persist to disk
-        let profile = UserProfile {
-            display_name: Some(name.clone()),
+        let stored = UserProfile {
+            display_name: profile.display_name.clone(),
+            profile: Some(profile.clone()),
+            home_owner: self.is_linked_device().then_some(self.home_owner),
         };
-        Self::save_profile(&self.config.data_dir, &profile)?;
+        Self::save_profile(&self.config.data_dir, &stored)?;
 
         // Broadcast to peers via discovery service if running
-        if let Some(transport) = self.inner.transport().await {
+        if let Some(name) = profile.display_name
+            && let Some(transport) = self.inner.transport().await
+        {
             transport.discovery_service().set_display_name(name).await;
         }
 
+        self.sync_profiles().await;
         Ok(())
     }
 
+    /// Cache for avatars, custom emoji and other small shared assets.
+    ///
+    /// UI crates resolve asset hashes through it so each asset is fetched
+    /// from peers once and reused across realms and restarts.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// if let Some(ArtifactId::Blob(hash)) = profile.avatar {
+    ///     let bytes = network.assets().get_or_fetch(hash, || fetch_avatar(hash)).await?;
+    /// }
+    /// ```
+    pub fn assets(&self) -> &Arc<AssetCache> {
+        &self.assets
+    }
+
+    /// Get the newest profile a peer has published in any shared realm.
+    pub fn profile_of(&self, member_id: &MemberId) -> Option<Profile> {
+        self.profiles.get(member_id).map(|p| p.clone())
+    }
+
+    /// Publish our profile to every shared realm and refresh peer profiles.
+    ///
+    /// Called periodically by the network. Emits
+    /// [`PeerEvent::ProfileChanged`] for each peer whose cached profile was
+    /// replaced, and returns how many were.
+    pub async fn sync_profiles(&self) -> usize {
+        let my_id = self.id();
+        let mine = SignedProfile::sign(my_id, self.profile(), self.inner.pq_identity());
+        let mut latest: std::collections::HashMap<MemberId, Profile> =
+            std::collections::HashMap::new();
+
+        for realm_id in self.conversation_realms() {
+            let Some(realm) = self.get_realm_by_id(&realm_id) else {
+                continue;
+            };
+            let doc = match realm.profiles().await {
+                Ok(doc) => doc,
+                Err(e) => {
+                    tracing::debug!(error = %e, "profile document unavailable");
+                    continue;
+                }
+            };
+            let stale = doc.read().await.get(&my_id) != Some(&mine.profile);
+            if stale {
+                let _ = doc.update(|d| {
+                    d.publish(mine.clone());
+                }).await;
+            }
+
+            let guard = doc.read().await;
+            for (member, signed) in &guard.profiles {
+                if *member == my_id {
+                    continue;
+                }
+                let newer = latest
+                    .get(member)
+                    .is_none_or(|p| p.updated_at_millis < signed.profile.updated_at_millis);
+                if newer {
+                    latest.insert(*member, signed.profile.clone());
+                }
+            }
+        }
+
+        let mut changed = 0;
+        for (member_id, profile) in latest {
+            let newer = self
+                .profiles
+                .get(&member_id)
+                .is_none_or(|p| p.updated_at_millis < profile.updated_at_millis);
+            if newer {
+                self.profiles.insert(member_id, profile.clone());
+                let _ = self.peer_event_tx.send(PeerEvent::ProfileChanged { member_id, profile });
+                changed += 1;
+            }
+        }
+        changed
+    }
+
     /// Check if the given data directory contains an existing identity.
     ///
     /// Returns `true` if no identity keys exist yet (first run).
@@ -396,6 +633,41 @@ impl IndrasNetwork {
             self.peer_event_tx.clone(),
             self.peering_cancel.clone(),
         );
+        let h6 = crate::peering::tasks::spawn_tombstone_compactor(
+            Arc::clone(self),
+            self.peering_cancel.clone(),
+            self.config.compaction_interval,
+        );
+        let h7 = crate::peering::tasks::spawn_digest_generator(
+            Arc::clone(self),
+            self.peering_cancel.clone(),
+            self.config.digest_interval,
+        );
+        let h8 = crate::peering::tasks::spawn_invite_watcher(
+            Arc::clone(self),
+            self.peering_cancel.clone(),
+            self.config.poll_interval,
+        );
+        let h9 = crate::peering::tasks::spawn_guest_enforcer(
+            Arc::clone(self),
+            self.peering_cancel.clone(),
+            self.config.poll_interval,
+        );
+        let h10 = crate::peering::tasks::spawn_delegation_executor(
+            Arc::clone(self),
+            self.peering_cancel.clone(),
+        );
+        let h11 = crate::peering::tasks::spawn_profile_sync(
+            Arc::clone(self),
+            self.peering_cancel.clone(),
+            self.config.poll_interval,
+            Arc::clone(&self.poll_notify),
+        );
+        let h12 = crate::peering::tasks::spawn_notification_dispatcher(
+            Arc::clone(self),
+            self.peering_cancel.clone(),
+            self.config.poll_interval,
+        );
 
         // Spawn inbox gossip listener: detect peers joining our inbox via gossip
         // discovery and auto-connect (creates DM realm + adds contact).
@@ -415,7 +687,7 @@ impl IndrasNetwork {
         };
 
         let mut handles = self.peering_tasks.lock().await;
-        handles.extend([h1, h2, h3, h4]);
+        handles.extend([h1, h2, h3, h4, h6, h7, h8, h9, h10, h11, h12]);
         if let Some(h5) = h5 {
             handles.push(h5);
         }
@@ -524,7 +796,7 @@ impl IndrasNetwork {
     /// in previous sessions. Without this, the sidebar shows empty after restart.
     async fn restore_realms(&self) {
         let my_id = self.id();
-        let home_id = home_realm_id(my_id);
+        let home_id = home_realm_id(self.home_owner);
         let inbox_id = inbox_realm_id(my_id);
 
         for interface_id in self.inner.list_interfaces() {
@@ -721,8 +993,9 @@ impl IndrasNetwork {
                     };
 
                     let peer_id = notify.sender_id;
+                    // Same as `dm_realm_id`; the artifact ID is needed below
                     let dm_artifact_id = dm_story_id(my_id, peer_id);
-                    let dm_realm_id = crate::artifact_sync::artifact_interface_id(&dm_artifact_id);
+                    let dm_realm_id = artifact_interface_id(&dm_artifact_id);
 
                     // Check if we already have this DM realm (idempotent)
                     if realms.contains_key(&dm_realm_id) {
@@ -888,6 +1161,15 @@ impl IndrasNetwork {
         Ok(())
     }
 
+    /// Back up the data directory into `dest`.
+    ///
+    /// Backups into the same directory only store what changed since the
+    /// last one. Safe to call while the network is running; restore with
+    /// [`restore_backup`](crate::restore_backup) into an empty directory.
+    pub async fn backup(&self, dest: impl AsRef<Path>) -> Result<BackupSummary> {
+        Ok(self.inner.backup(dest).await?)
+    }
+
     /// Check if the network is running.
     pub fn is_running(&self) -> bool {
         self.inner.is_started()
@@ -900,6 +1182,8 @@ impl IndrasNetwork {
     /// Create a new realm.
     ///
     /// Creates a new collaborative space that others can join via invite code.
+    /// The realm's signed membership log is started with this node as its
+    /// creator and first admin.
     ///
     /// # Arguments
     ///
@@ -912,7 +1196,9 @@ impl IndrasNetwork {
     /// println!("Invite code: {}", realm.invite_code());
     /// ```
     pub async fn create_realm(&self, name: &str) -> Result<Realm> {
-        self.create_realm_with_artifact(generate_tree_id(), name).await
+        let realm = self.create_realm_with_artifact(generate_tree_id(), name).await?;
+        realm.start_membership_log().await?;
+        Ok(realm)
     }
 
     /// Create (or idempotently re-open) a realm for a caller-supplied artifact id.
@@ -984,21 +1270,56 @@ impl IndrasNetwork {
     ///
     /// # Arguments
     ///
-    /// * `invite` - The invite code (can be a string or InviteCode)
+    /// * `invite` - The invite code (can be a string or InviteCode), or an
+    ///   alias handle like `@project-alpha` (see [`Self::resolve_alias`])
     ///
     /// # Example
     ///
     /// ```ignore
     /// let realm = network.join("indra:realm:abc123...").await?;
+    /// let realm = network.join("@project-alpha").await?;
     /// ```
     pub async fn join(&self, invite: impl AsRef<str>) -> Result<Realm> {
+        self.join_with_timeout(invite, self.config.request_timeout)
+            .await
+    }
+
+    /// Join a realm, waiting at most `timeout` for its bootstrap peers.
+    ///
+    /// The realm is joined locally before any peer is contacted, so a
+    /// stalled or unreachable inviter never makes this hang: once `timeout`
+    /// elapses the realm is returned and catches up through gossip and
+    /// periodic sync. Dropping the returned future is safe; joining again
+    /// resumes where it left off.
+    pub async fn join_with_timeout(
+        &self,
+        invite: impl AsRef<str>,
+        timeout: Duration,
+    ) -> Result<Realm> {
         // Ensure network is started
         if !self.is_running() {
             self.inner.start().await?;
         }
 
-        let invite_code = InviteCode::parse(invite.as_ref())?;
-        let interface_id = self.inner.join_interface(invite_code.invite_key().clone()).await?;
+        let invite = invite.as_ref().trim();
+        let invite_code = if invite.starts_with('@') {
+            let record = self
+                .resolve_alias(invite)
+                .await?
+                .ok_or_else(|| IndraError::AliasNotFound {
+                    alias: invite.trim_start_matches('@').to_string(),
+                })?;
+            if let Some(realm) = self.get_realm_by_id(&record.realm_id) {
+                return Ok(realm);
+            }
+            InviteCode::parse(&record.invite)?
+        } else {
+            InviteCode::parse(invite)?
+        };
+        let interface_id = self
+            .inner
+            .join_interface_with_timeout(invite_code.invite_key().clone(), timeout)
+            .await?;
 
         // Derive interface encryption key from artifact seed.
         // Realm invites use deterministic keys derived from the artifact ID
@@ -1039,6 +1360,363 @@ impl IndrasNetwork {
         ))
     }
 
+    // ============================================================
+    // Contact invitations
+    // ============================================================
+
+    /// Subscribe to contact invitation events.
+    ///
+    /// Emits [`InviteEvent::Received`] when a contact invites us via
+    /// `realm.invite_contact()`, and `Accepted` / `Declined` when a contact
+    /// answers one of our invitations.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut invites = network.invite_events();
+    /// while let Ok(InviteEvent::Received(invite)) = invites.recv().await {
+    ///     let realm = network.accept_invite(&invite.realm_id).await?;
+    /// }
+    /// ```
+    pub fn invite_events(&self) -> broadcast::Receiver<InviteEvent> {
+        self.invite_tx.subscribe()
+    }
+
+    /// Invitations received from contacts that have not been answered.
+    pub fn pending_invites(&self) -> Vec<ContactInvite> {
+        self.pending_invites.iter().map(|e| e.value().clone()).collect()
+    }
+
+    /// Accept a pending invitation: join the realm and notify the inviter.
+    pub async fn accept_invite(&self, realm_id: &RealmId) -> Result<Realm> {
+        let (_, invite) = self.pending_invites.remove(realm_id).ok_or_else(|| {
+            IndraError::InvalidOperation("no pending invite for this realm".to_string())
+        })?;
+
+        let realm = match self.join(&invite.invite).await {
+            Ok(realm) => realm,
+            Err(e) => {
+                // Keep the invite so the user can retry.
+                self.pending_invites.insert(*realm_id, invite);
+                return Err(e);
+            }
+        };
+        if let Some(name) = &invite.realm_name {
+            if let Some(mut state) = self.realms.get_mut(realm_id) {
+                state.name.get_or_insert_with(|| name.clone());
+            }
+        }
+
+        self.send_invite_response(&invite, true).await;
+        Ok(realm)
+    }
+
+    /// Decline a pending invitation and notify the inviter.
+    pub async fn decline_invite(&self, realm_id: &RealmId) -> Result<()> {
+        let (_, invite) = self.pending_invites.remove(realm_id).ok_or_else(|| {
+            IndraError::InvalidOperation("no pending invite for this realm".to_string())
+        })?;
+        self.send_invite_response(&invite, false).await;
+        Ok(())
+    }
+
+    /// Record an invite message received on a DM realm.
+    pub(crate) fn handle_invite_message(&self, sender: MemberId, message: ContactInviteMessage) {
+        if sender == self.id() {
+            return;
+        }
+        if let ContactInviteMessage::Invite(invite) = &message {
+            // Ignore invites for realms we are already in, and re-deliveries.
+            if invite.inviter != sender
+                || self.realms.contains_key(&invite.realm_id)
+                || self.pending_invites.contains_key(&invite.realm_id)
+            {
+                return;
+            }
+            self.pending_invites.insert(invite.realm_id, invite.clone());
+        }
+        let _ = self.invite_tx.send(InviteEvent::from_message(sender, message));
+    }
+
+    /// Best-effort answer to an invitation over the DM realm.
+    async fn send_invite_response(&self, invite: &ContactInvite, accepted: bool) {
+        let dm_realm_id = Self::dm_realm_id(self.id(), invite.inviter);
+        let response = ContactInviteMessage::Response {
+            realm_id: invite.realm_id,
+            accepted,
+        };
+        let payload = crate::message::MessagePayload::new(response.to_content());
+        let result = match crate::realm::serialize_payload(&payload) {
+            Ok(bytes) => self.inner.send_message(&dm_realm_id, bytes).await.map_err(IndraError::from),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            tracing::debug!(error = %e, "Failed to send invite response (non-fatal)");
+        }
+    }
+
+    // ============================================================
+    // Alias handles
+    // ============================================================
+
+    /// Register a resolvable handle (e.g. `@project-alpha`) for a realm.
+    ///
+    /// The registration is published to the realm's own alias directory and
+    /// to every conversation realm, so any contact who shares a realm with
+    /// us can resolve the handle and `join("@project-alpha")`. Fails with
+    /// [`IndraError::AliasTaken`] if any directory we can see already maps
+    /// the handle to a different realm.
+    ///
+    /// Returns the normalized handle (without `@`).
+    pub async fn register_alias(&self, realm: &Realm, handle: &str) -> Result<String> {
+        let handle = normalize_handle(handle)?;
+        let invite = realm.invite_code().ok_or_else(|| {
+            IndraError::InvalidOperation("realm has no invite code to publish".to_string())
+        })?;
+
+        if let Some(existing) = self.resolve_alias(&handle).await? {
+            if existing.realm_id != realm.id() {
+                return Err(IndraError::AliasTaken { alias: handle });
+            }
+        }
+
+        let record = AliasRecord {
+            realm_id: realm.id(),
+            invite: invite.to_uri(),
+            owner: self.id(),
+            registered_at_millis: chrono::Utc::now().timestamp_millis().max(0) as u64,
+        };
+
+        let mut targets = self.conversation_realms();
+        if !targets.contains(&realm.id()) {
+            targets.push(realm.id());
+        }
+        for realm_id in targets {
+            let Some(target) = self.get_realm_by_id(&realm_id) else {
+                continue;
+            };
+            let dir = target
+                .document::<AliasDirectoryDocument>(ALIAS_DIRECTORY_DOC)
+                .await?;
+            dir.try_update(|d| d.register(&handle, record.clone())).await?;
+        }
+
+        Ok(handle)
+    }
+
+    /// Resolve an alias handle to a realm registration.
+    ///
+    /// Searches the alias directories of all loaded realms. Directories
+    /// only contain records synced from members we share a realm with, so
+    /// a handle resolves once its owner (or anyone who has synced the
+    /// record) has been reachable. If directories disagree, the earliest
+    /// registration wins.
+    pub async fn resolve_alias(&self, handle: &str) -> Result<Option<AliasRecord>> {
+        let handle = normalize_handle(handle)?;
+        let mut best: Option<AliasRecord> = None;
+
+        for realm_id in self.realms() {
+            let Some(realm) = self.get_realm_by_id(&realm_id) else {
+                continue;
+            };
+            let Ok(dir) = realm
+                .document::<AliasDirectoryDocument>(ALIAS_DIRECTORY_DOC)
+                .await
+            else {
+                continue;
+            };
+            let guard = dir.read().await;
+            if let Some(record) = guard.resolve(&handle) {
+                if best.as_ref().is_none_or(|b| record.precedes(b)) {
+                    best = Some(record.clone());
+                }
+            }
+        }
+
+        Ok(best)
+    }
+
+    // ============================================================
+    // Merging and splitting
+    // ============================================================
+
+    /// Merge two realms into a new realm named `name`.
+    ///
+    /// Document updates and messages from both realms are replayed into the
+    /// new realm in timestamp order, and every member of either realm
+    /// (except guests) is invited to it. The source realms are untouched.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let merged = network.merge_realms(&alpha, &beta, "Alpha + Beta").await?;
+    /// println!("replayed {} events", merged.replayed);
+    /// ```
+    pub async fn merge_realms(&self, a: &Realm, b: &Realm, name: &str) -> Result<RealmRestructure> {
+        if a.id() == b.id() {
+            return Err(IndraError::InvalidOperation(
+                "cannot merge a realm with itself".to_string(),
+            ));
+        }
+
+        let mut events = self.replay_source(a).await?;
+        events.extend(self.replay_source(b).await?);
+        events.sort_by_key(|(timestamp, _, _)| *timestamp);
+        events.retain(|(_, kind, _)| merge_includes(kind));
+
+        let mut members = self.restructure_members(a).await?;
+        members.extend(self.restructure_members(b).await?);
+
+        let realm = self.create_realm(name).await?;
+        self.restructure_into(realm, events, members).await
+    }
+
+    /// Split part of a realm into a new realm named `name`.
+    ///
+    /// Only the documents, messages, and artifact announcements selected
+    /// by `filter` are copied, and only the selected members (all
+    /// non-guest members by default) are invited. The source realm is
+    /// untouched.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let filter = SplitFilter::documents(["research-quests"]).with_artifacts();
+    /// let split = network.split_realm(&realm, "Research", filter).await?;
+    /// ```
+    pub async fn split_realm(
+        &self,
+        realm: &Realm,
+        name: &str,
+        filter: SplitFilter,
+    ) -> Result<RealmRestructure> {
+        let mut events = self.replay_source(realm).await?;
+        events.retain(|(_, kind, _)| filter.includes(kind));
+
+        let mut members = self.restructure_members(realm).await?;
+        members.retain(|m| filter.invites(m));
+
+        let target = self.create_realm(name).await?;
+        self.restructure_into(target, events, members).await
+    }
+
+    /// Clone a realm into a local-only sandbox for rehearsal.
+    ///
+    /// The sandbox starts with the source's full history and is never
+    /// synced, persisted, or announced to peers. It is not listed among
+    /// the loaded realms. See [`Rehearsal`] for discarding it or applying
+    /// changes back.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let rehearsal = network.rehearse(&realm).await?;
+    /// // ... experiment in rehearsal.sandbox() ...
+    /// rehearsal.discard().await?;
+    /// ```
+    pub async fn rehearse(&self, realm: &Realm) -> Result<Rehearsal> {
+        let sandbox_id = self.inner.create_sandbox_interface().await?;
+
+        let source_events = self.inner.document_events(&realm.id()).await?;
+        let history = rehearsed_payloads(source_events, 0);
+        let baseline = history.len();
+        for (_, content) in history {
+            self.inner.send_message(&sandbox_id, content).await?;
+        }
+
+        let sandbox = Realm::from_id_with_chat_doc(
+            sandbox_id,
+            realm.name().map(|n| format!("{n} (rehearsal)")),
+            None,
+            Arc::clone(&self.inner),
+            Arc::new(OnceCell::new()),
+        );
+        Ok(Rehearsal::new(realm.clone(), sandbox, baseline, Arc::clone(&self.inner)))
+    }
+
+    /// Message payloads of a realm with their timestamps and kinds.
+    async fn replay_source(
+        &self,
+        realm: &Realm,
+    ) -> Result<Vec<(chrono::DateTime<chrono::Utc>, ReplayKind, Vec<u8>)>> {
+        let events = self.inner.document_events(&realm.id()).await?;
+        Ok(events
+            .into_iter()
+            .filter_map(|event| match event {
+                indras_core::InterfaceEvent::Message {
+                    content, timestamp, ..
+                } => Some((timestamp, ReplayKind::classify(&content), content)),
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// Members of a realm to carry over: everyone but us and its guests.
+    async fn restructure_members(&self, realm: &Realm) -> Result<BTreeSet<MemberId>> {
+        let guests: BTreeSet<MemberId> = realm.guests().await?.iter().map(|g| g.guest()).collect();
+        let my_id = self.id();
+        Ok(realm
+            .member_list()
+            .await?
+            .into_iter()
+            .map(|m| m.id())
+            .filter(|id| *id != my_id && !guests.contains(id))
+            .collect())
+    }
+
+    /// Replay events into a new realm, rebuild its registry, and invite members.
+    async fn restructure_into(
+        &self,
+        realm: Realm,
+        events: Vec<(chrono::DateTime<chrono::Utc>, ReplayKind, Vec<u8>)>,
+        members: BTreeSet<MemberId>,
+    ) -> Result<RealmRestructure> {
+        let mut replayed = 0;
+        let mut documents = BTreeSet::new();
+        for (_, kind, content) in events {
+            self.inner.send_message(&realm.id(), content).await?;
+            if let Some(name) = kind.document() {
+                documents.insert(name.to_string());
+            }
+            replayed += 1;
+        }
+
+        let registry = crate::document::Document::<DocumentRegistryDocument>::new(
+            realm.id(),
+            REGISTRY_DOC.to_string(),
+            Arc::clone(&self.inner),
+        )
+        .await?;
+        let names: Vec<String> = documents.iter().filter(|n| !n.starts_with('_')).cloned().collect();
+        registry
+            .update(|r| {
+                for name in names {
+                    r.register(name);
+                }
+            })
+            .await?;
+
+        let mut invited = Vec::new();
+        let mut not_invited = Vec::new();
+        for member in members {
+            match realm.invite_contact(member).await {
+                Ok(()) => invited.push(member),
+                Err(e) => {
+                    tracing::debug!(error = %e, "could not re-invite member to restructured realm");
+                    not_invited.push(member);
+                }
+            }
+        }
+
+        Ok(RealmRestructure {
+            realm,
+            replayed,
+            documents,
+            invited,
+            not_invited,
+        })
+    }
+
     /// Get a realm by ID.
     ///
     /// Returns None if the realm is not loaded.
@@ -1065,7 +1743,7 @@ impl IndrasNetwork {
     /// appear in the chat sidebar.
     pub fn conversation_realms(&self) -> Vec<RealmId> {
         let my_id = self.id();
-        let home_id = home_realm_id(my_id);
+        let home_id = home_realm_id(self.home_owner);
         let inbox_id = inbox_realm_id(my_id);
 
         self.realms
@@ -1075,9 +1753,9 @@ impl IndrasNetwork {
                 if id == home_id || id == inbox_id {
                     return false;
                 }
-                // Filter out internal realms (peer inboxes, artifact-sync)
+                // Filter out internal realms (peer inboxes, artifact-sync, pairing)
                 match r.value().name.as_deref() {
-                    Some("PeerInbox") | Some("artifact-sync") => false,
+                    Some("PeerInbox") | Some("artifact-sync") | Some("Pairing") => false,
                     _ => true,
                 }
             })
@@ -1170,6 +1848,7 @@ impl IndrasNetwork {
         }
 
         // 1. Use dm_story_id for canonical DM identity, derive interface ID from it
+        //    (see `dm_realm_id` for why this isn't `deterministic_realm_id`)
         let artifact_id = dm_story_id(my_id, peer_id);
         let realm_id = artifact_interface_id(&artifact_id);
 
@@ -1749,6 +2428,15 @@ impl IndrasNetwork {
         IdentityCode::from_member_id(self.id()).to_uri(self.display_name().as_deref())
     }
 
+    /// Install this device's certificate from an organization root.
+    ///
+    /// The certificate is shared with peers after connecting, so realms
+    /// pinned to that organization (see [`Realm::set_trust_root`]) accept
+    /// this device as a verified member.
+    pub fn set_member_certificate(&self, certificate: crate::MemberCertificate) {
+        self.inner.set_member_certificate(certificate);
+    }
+
     /// Create an encounter for in-person peer discovery.
     ///
     /// Generates a 6-digit code, joins the encounter gossip topic,
@@ -1899,11 +2587,24 @@ impl IndrasNetwork {
     // Peer-based realm operations
     // ============================================================
 
-    /// Compute a deterministic realm ID from a set of peers.
+    /// Realm ID of the DM between two members.
     ///
-    /// The same set of peers always produces the same realm ID,
-    /// regardless of the order they're provided in.
-    fn compute_realm_id_for_peers(peers: &[MemberId]) -> RealmId {
+    /// DMs don't use [`deterministic_realm_id`]: a DM realm is the sync
+    /// interface of its story artifact ([`dm_story_id`]), so its ID must stay
+    /// [`artifact_interface_id`] for artifact sync to find it. It is also
+    /// keyed by member IDs, which is all the inbox notification that opens
+    /// a DM carries, rather than by ML-DSA keys.
+    fn dm_realm_id(a: MemberId, b: MemberId) -> RealmId {
+        artifact_interface_id(&dm_story_id(a, b))
+    }
+
+    /// Realm ID peer-set realms were created under before
+    /// [`deterministic_realm_id`] (`realm-peers-v1`).
+    ///
+    /// It hashes transport IDs rather than ML-DSA keys and carries no key
+    /// seed. Realms that already exist under it are still opened; new ones
+    /// never are.
+    fn legacy_realm_id_for_peers(peers: &[MemberId]) -> RealmId {
         let sorted: BTreeSet<&MemberId> = peers.iter().collect();
         let mut hasher = blake3::Hasher::new();
         hasher.update(b"realm-peers-v1:");
@@ -1913,11 +2614,54 @@ impl IndrasNetwork {
         InterfaceId::new(*hasher.finalize().as_bytes())
     }
 
-    /// Normalize a peer set to a canonical sorted form.
-    fn normalize_peers(peers: &[MemberId]) -> Vec<MemberId> {
-        let mut sorted: Vec<MemberId> = peers.to_vec();
-        sorted.sort();
-        sorted.dedup();
+    /// ML-DSA verifying keys of a peer set, from the peer registry.
+    fn peer_set_keys(&self, peers: &[MemberId]) -> Result<Vec<PQPublicIdentity>> {
+        let my_id = self.id();
+        peers
+            .iter()
+            .map(|peer| {
+                if *peer == my_id {
+                    return Ok(self.inner.pq_identity().verifying_key());
+                }
+                let unknown = || {
+                    IndraError::InvalidOperation(format!(
+                        "peer {} has no known ML-DSA key",
+                        hex::encode(&peer[..8])
+                    ))
+                };
+                let identity = iroh::PublicKey::from_bytes(peer)
+                    .map(IrohIdentity::from)
+                    .map_err(|_| unknown())?;
+                let key = self
+                    .inner
+                    .storage()
+                    .peer_registry()
+                    .get(&identity)?
+                    .and_then(|record| record.pq_verifying_key)
+                    .ok_or_else(unknown)?;
+                PQPublicIdentity::from_bytes(&key).map_err(|_| unknown())
+            })
+            .collect()
+    }
+
+    /// A loaded realm by ID.
+    fn loaded_realm(&self, realm_id: &RealmId) -> Option<Realm> {
+        self.realms.get(realm_id).map(|state| {
+            Realm::from_id_with_chat_doc(
+                *realm_id,
+                state.name.clone(),
+                state.artifact_id.clone(),
+                Arc::clone(&self.inner),
+                Arc::clone(&state.chat_doc),
+            )
+        })
+    }
+
+    /// Normalize a peer set to a canonical sorted form.
+    fn normalize_peers(peers: &[MemberId]) -> Vec<MemberId> {
+        let mut sorted: Vec<MemberId> = peers.to_vec();
+        sorted.sort();
+        sorted.dedup();
         sorted
     }
 
@@ -1925,7 +2669,10 @@ impl IndrasNetwork {
     ///
     /// This is the primary way to access realms in the "tag friends" pattern.
     /// The peer set IS the realm identity - the same peers always return
-    /// the same realm.
+    /// the same realm. Its ID and key come from the members' ML-DSA keys via
+    /// [`deterministic_realm_id`], so every peer's key must be in the peer
+    /// registry. A realm created under the earlier `realm-peers-v1` ID is
+    /// returned if this node still has it.
     ///
     /// **Important:** All peers must be in your contacts before you can create
     /// a realm with them. Join the contacts realm and add contacts first.
@@ -1937,7 +2684,9 @@ impl IndrasNetwork {
     /// # Errors
     ///
     /// Returns an error if:
-    /// - The peer set doesn't include yourself
+    /// - The peer set doesn't include yourself, or has fewer than
+    ///   [`MIN_PEER_SET_SIZE`](indras_node::MIN_PEER_SET_SIZE) peers
+    /// - A peer's ML-DSA key is not known
     /// - You haven't joined the contacts realm yet
     /// - Any peer (other than yourself) is not in your contacts
     ///
@@ -1968,11 +2717,16 @@ impl IndrasNetwork {
 
         // Normalize and compute ID
         let normalized = Self::normalize_peers(&peers);
-        let realm_id = Self::compute_realm_id_for_peers(&normalized);
+        if let Some(realm) = self.loaded_realm(&Self::legacy_realm_id_for_peers(&normalized)) {
+            return Ok(realm);
+        }
+        let keys = self.peer_set_keys(&normalized)?;
+        validate_peer_set(&keys).map_err(|e| IndraError::InvalidOperation(e.to_string()))?;
+        let (realm_id, _) = deterministic_realm_id(&keys);
 
         // Check if already loaded (skip contact validation for existing realms)
-        if let Some(state) = self.realms.get(&realm_id) {
-            return Ok(Realm::from_id_with_chat_doc(realm_id, state.name.clone(), state.artifact_id.clone(), Arc::clone(&self.inner), Arc::clone(&state.chat_doc)));
+        if let Some(realm) = self.loaded_realm(&realm_id) {
+            return Ok(realm);
         }
 
         // Enforce: all peers must be contacts before creating a new realm
@@ -2010,10 +2764,10 @@ impl IndrasNetwork {
             self.inner.start().await?;
         }
 
-        // Create the realm with deterministic ID
+        // Create the realm with deterministic ID and key
         let (interface_id, invite_key) = self
             .inner
-            .create_interface_with_id(realm_id, None)
+            .create_peer_set_interface(&keys, None, Vec::new())
             .await?;
 
         // Cache the realm state
@@ -2040,11 +2794,10 @@ impl IndrasNetwork {
     /// * `peers` - The set of member IDs that define this realm
     pub fn get_realm(&self, peers: &[MemberId]) -> Option<Realm> {
         let normalized = Self::normalize_peers(peers);
-        let realm_id = Self::compute_realm_id_for_peers(&normalized);
-
-        self.realms.get(&realm_id).map(|state| {
-            Realm::from_id_with_chat_doc(realm_id, state.name.clone(), state.artifact_id.clone(), Arc::clone(&self.inner), Arc::clone(&state.chat_doc))
-        })
+        self.peer_set_keys(&normalized)
+            .ok()
+            .and_then(|keys| self.loaded_realm(&deterministic_realm_id(&keys).0))
+            .or_else(|| self.loaded_realm(&Self::legacy_realm_id_for_peers(&normalized)))
     }
 
     // ============================================================
@@ -2202,16 +2955,31 @@ impl IndrasNetwork {
             self.inner.start().await?;
         }
 
-        // Get the deterministic home realm ID
-        let realm_id = home_realm_id(self.id());
-
-        // Create the home realm interface with deterministic key
-        // No bootstrap peers needed — this is our own home realm.
-        let seed = home_key_seed(&self.id());
-        let (_interface_id, _invite_key) = self
+        // Get the deterministic home realm ID (the account's, on a linked device)
+        let realm_id = home_realm_id(self.home_owner);
+
+        // Create the home realm interface with deterministic key.
+        // Our own home realm needs no bootstrap peers; a linked device
+        // bootstraps from the account's device.
+        let seed = home_key_seed(&self.home_owner);
+        let bootstrap = if self.is_linked_device() {
+            vec![
+                iroh::PublicKey::from_bytes(&self.home_owner)
+                    .map_err(|e| IndraError::Crypto(format!("Invalid home owner key: {}", e)))?,
+            ]
+        } else {
+            vec![]
+        };
+        let (interface_id, _invite_key) = self
             .inner
-            .create_interface_with_seed(realm_id, &seed, Some("Home"), vec![])
+            .create_interface_with_seed(realm_id, &seed, Some("Home"), bootstrap.clone())
             .await?;
+        for owner_key in bootstrap {
+            let _ = self
+                .inner
+                .add_member(&interface_id, IrohIdentity::from(owner_key))
+                .await;
+        }
 
         // Cache the realm state
         self.realms.insert(
@@ -2235,6 +3003,27 @@ impl IndrasNetwork {
         Ok(home)
     }
 
+    /// Get the member's realm organization (pins, order, folders).
+    ///
+    /// Stored in the home realm, so every device and frontend for this
+    /// member shows the same realm list structure.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let org = network.realm_organization().await?;
+    /// org.update(|o| o.pin(realm.id(), true)).await?;
+    /// let layout = org.read().await.layout(&network.realms());
+    /// ```
+    pub async fn realm_organization(
+        &self,
+    ) -> Result<crate::document::Document<RealmOrganizationDocument>> {
+        self.home_realm()
+            .await?
+            .document::<RealmOrganizationDocument>(REALM_ORGANIZATION_DOC)
+            .await
+    }
+
     /// Get the home realm if already initialized.
     ///
     /// Returns None if `home_realm()` hasn't been called yet.
@@ -2291,6 +3080,258 @@ impl IndrasNetwork {
         }
     }
 
+    // ============================================================
+    // Digests
+    // ============================================================
+
+    /// Digest settings: which realms are digested, how, and where to.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// network.digests().enable(realm.id());
+    /// network.digests().set_post_to_home(true);
+    /// let mut digests = network.digests().subscribe();
+    /// ```
+    pub fn digests(&self) -> &DigestGenerator {
+        &self.digests
+    }
+
+    // ============================================================
+    // Event bus
+    // ============================================================
+
+    /// The typed event bus: filtered, ordered, replayable realm events.
+    ///
+    /// Every event from `events()` is published here once peering is
+    /// running, along with digests. Consumers that persist the last
+    /// `sequence` they handled can resume without missing events.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let filter = EventFilter::all().kind(EventKind::Message).realm(realm.id());
+    /// let mut sub = network.event_bus().subscribe_from(filter, saved_sequence)?;
+    /// while let Some(event) = sub.recv().await {
+    ///     saved_sequence = event.sequence;
+    /// }
+    /// ```
+    pub fn event_bus(&self) -> &EventBus {
+        &self.event_bus
+    }
+
+    // ============================================================
+    // Notifications
+    // ============================================================
+
+    /// Notification settings and streams.
+    ///
+    /// While the network runs, every incoming message in a conversation
+    /// realm is offered to the center, which publishes and shows those its
+    /// preferences allow.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let center = network.notifications();
+    /// center.set_preferences(network.load_notification_preferences("chat").await?);
+    /// center.add_sink(DesktopNotifier::new("Indra Chat", center));
+    ///
+    /// let mut responses = center.responses();
+    /// while let Ok(response) = responses.recv().await {
+    ///     open_message(response.message_id);
+    /// }
+    /// ```
+    pub fn notifications(&self) -> &Arc<NotificationCenter> {
+        &self.notifications
+    }
+
+    /// Load an app's notification preferences from the home realm settings.
+    ///
+    /// Returns the defaults if the app has not saved any.
+    pub async fn load_notification_preferences(
+        &self,
+        app: &str,
+    ) -> Result<NotificationPreferences> {
+        let settings = self.home_realm().await?.settings().await?;
+        let prefs = settings
+            .read()
+            .await
+            .get(app, NOTIFICATION_SETTINGS_KEY)
+            .unwrap_or_default();
+        Ok(prefs)
+    }
+
+    /// Save an app's notification preferences to the home realm settings
+    /// and apply them.
+    pub async fn save_notification_preferences(
+        &self,
+        app: &str,
+        preferences: NotificationPreferences,
+    ) -> Result<()> {
+        let settings = self.home_realm().await?.settings().await?;
+        settings
+            .update(|s| s.set(app, NOTIFICATION_SETTINGS_KEY, &preferences))
+            .await?;
+        self.notifications.set_preferences(preferences);
+        Ok(())
+    }
+
+    /// What the notification classifier knows about a realm.
+    pub(crate) fn notification_context(&self, realm_id: &RealmId) -> NotificationContext {
+        NotificationContext {
+            my_id: self.id(),
+            my_name: self.profile().display_name.or_else(|| self.display_name()),
+            realm_name: self.realms.get(realm_id).and_then(|r| r.name.clone()),
+            is_dm: self.dm_peer_for_realm(realm_id).is_some(),
+        }
+    }
+
+    /// Content filters that hide messages on this device only.
+    ///
+    /// Pass to [`Realm::moderated_messages`](crate::Realm::moderated_messages).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// network.content_filters().add("spam", KeywordFilter::new(["giveaway"]));
+    /// network.content_filters().add("toxicity", move |m: &EditableChatMessage| model.score(m));
+    /// ```
+    pub fn content_filters(&self) -> &ContentFilters {
+        &self.content_filters
+    }
+
+    /// Spam scoring for incoming chat messages on this device.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// network.spam().set_settings(network.load_spam_settings("chat").await?);
+    /// if network.spam().score(&msg).probable {
+    ///     collapse(&msg);
+    /// }
+    /// ```
+    pub fn spam(&self) -> &SpamDetector {
+        &self.spam
+    }
+
+    /// Load an app's spam settings from the home realm settings.
+    ///
+    /// Returns the defaults if the app has not saved any.
+    pub async fn load_spam_settings(&self, app: &str) -> Result<SpamSettings> {
+        let settings = self.home_realm().await?.settings().await?;
+        let spam = settings
+            .read()
+            .await
+            .get(app, SPAM_SETTINGS_KEY)
+            .unwrap_or_default();
+        Ok(spam)
+    }
+
+    /// Save an app's spam settings to the home realm settings and apply them.
+    pub async fn save_spam_settings(&self, app: &str, spam: SpamSettings) -> Result<()> {
+        let settings = self.home_realm().await?.settings().await?;
+        settings
+            .update(|s| s.set(app, SPAM_SETTINGS_KEY, &spam))
+            .await?;
+        self.spam.set_settings(spam);
+        Ok(())
+    }
+
+    /// Size and change budgets for documents, with alerts when one grows
+    /// too large.
+    ///
+    /// Budgets are shared by every network in the process.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// network.document_budgets().set_budget("chat", DocumentBudget {
+    ///     max_bytes: 4 * 1024 * 1024,
+    ///     ..Default::default()
+    /// });
+    /// let mut alerts = network.document_budgets().subscribe();
+    /// while let Ok(alert) = alerts.recv().await {
+    ///     warn_user(alert.guidance());
+    /// }
+    /// ```
+    pub fn document_budgets(&self) -> &'static DocumentBudgets {
+        crate::document_budget::budgets()
+    }
+
+    /// The translator used to show messages in another language.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// network.translations().set_translator(MyTranslator::new(api_key));
+    /// ```
+    pub fn translations(&self) -> &Translations {
+        &self.translations
+    }
+
+    /// Translate a chat message's text into `language` for display.
+    ///
+    /// Served from the local cache when this exact text was translated
+    /// before; otherwise the registered translator is called and the
+    /// result cached.
+    pub async fn translate_message(
+        &self,
+        message_id: &str,
+        text: &str,
+        language: &str,
+    ) -> Result<String> {
+        self.translations
+            .translate(self.inner.storage().translations(), message_id, text, language)
+            .await
+    }
+
+    /// A cached translation of a chat message's text, if still current.
+    pub fn cached_translation(&self, message_id: &str, text: &str, language: &str) -> Option<String> {
+        self.translations
+            .cached(self.inner.storage().translations(), message_id, text, language)
+    }
+
+    /// Generate digests for all enabled realms now.
+    ///
+    /// Each digest covers activity since the realm's previous digest.
+    /// Non-empty digests are published to `digests().subscribe()` and, if
+    /// enabled, posted into the home realm as a system message. Runs
+    /// automatically every `NetworkConfig::digest_interval`.
+    pub async fn generate_digests(&self) -> Result<Vec<RealmDigest>> {
+        let mut produced = Vec::new();
+        let until = chrono::Utc::now();
+
+        for realm_id in self.digests.enabled_realms() {
+            let Some(realm) = self.get_realm_by_id(&realm_id) else {
+                continue;
+            };
+            let snapshot = realm.snapshot().await?;
+            let name = realm.name().map(str::to_string);
+            let Some(digest) = self.digests.advance(&snapshot, name, until) else {
+                continue;
+            };
+            if digest.is_empty() {
+                continue;
+            }
+
+            let event = digest.to_global_event(*self.inner.identity());
+            self.event_bus.publish(event.clone());
+            self.digests.publish(event);
+
+            if self.digests.posts_to_home() {
+                if let Some(text) = self.digests.render(&digest) {
+                    let home = self.home_realm().await?;
+                    home.post_system(text).await?;
+                }
+            }
+
+            produced.push(digest);
+        }
+
+        Ok(produced)
+    }
+
     // ============================================================
     // Identity export/import
     // ============================================================
@@ -2515,6 +3556,253 @@ impl IndrasNetwork {
         Ok(())
     }
 
+    // ============================================================
+    // Device pairing
+    // ============================================================
+
+    /// Start pairing a new device with this account.
+    ///
+    /// Joins a private pairing realm derived from a fresh code. Show the
+    /// code (or its QR) to the new device, then wait for its request with
+    /// [`DevicePairing::wait_for_request`] and answer with
+    /// [`grant_device_pairing`](Self::grant_device_pairing).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let pairing = network.start_device_pairing().await?;
+    /// println!("Enter on the new device: {}", pairing.code());
+    /// let request = pairing.wait_for_request(Duration::from_secs(300)).await?;
+    /// network.grant_device_pairing(&pairing, &request).await?;
+    /// network.wait_for_device_link(request.device_id, Duration::from_secs(120)).await?;
+    /// ```
+    pub async fn start_device_pairing(&self) -> Result<DevicePairing> {
+        if self.is_linked_device() {
+            return Err(IndraError::InvalidOperation(
+                "Only the account's original device can pair new devices".to_string(),
+            ));
+        }
+        if !self.is_running() {
+            self.inner.start().await?;
+        }
+
+        let code = PairingCode::generate(self.id());
+        let realm_id = code.realm_id();
+        self.inner
+            .create_interface_with_seed(realm_id, &code.key_seed(), Some("Pairing"), vec![])
+            .await?;
+
+        let doc = Document::new(
+            realm_id,
+            DEVICE_PAIRING_DOC.to_string(),
+            Arc::clone(&self.inner),
+        )
+        .await?;
+        tracing::info!(realm = %hex::encode(&realm_id.as_bytes()[..8]), "Started device pairing");
+
+        Ok(DevicePairing { code, doc })
+    }
+
+    /// Answer a pairing request with this account's identity.
+    ///
+    /// Writes a [`PairingGrant`] with the PQ identity and display name,
+    /// admits the new device to the home realm, and lists it (unconfirmed)
+    /// in the home realm's linked-device document.
+    pub async fn grant_device_pairing(
+        &self,
+        pairing: &DevicePairing,
+        request: &PairingRequest,
+    ) -> Result<()> {
+        let device_key = iroh::PublicKey::from_bytes(&request.device_id)
+            .map_err(|e| IndraError::Crypto(format!("Invalid device key: {}", e)))?;
+        let device_identity = IrohIdentity::from(device_key);
+        let _ = self
+            .inner
+            .add_member(&pairing.code.realm_id(), device_identity)
+            .await;
+
+        // Admit the device to the home realm before it comes looking
+        let home = self.home_realm().await?;
+        let _ = self.inner.add_member(&home.id(), device_identity).await;
+
+        let linked = home
+            .document::<LinkedDevicesDocument>(LINKED_DEVICES_DOC)
+            .await?;
+        let my_id = self.id();
+        let now = now_millis();
+        let request = request.clone();
+        linked
+            .update(|doc| {
+                doc.devices.entry(my_id).or_insert(LinkedDevice {
+                    device_name: "Original device".to_string(),
+                    linked_at_millis: now,
+                    confirmed: true,
+                });
+                doc.devices
+                    .entry(request.device_id)
+                    .or_insert(LinkedDevice {
+                        device_name: request.device_name.clone(),
+                        linked_at_millis: now,
+                        confirmed: false,
+                    });
+            })
+            .await?;
+
+        let grant = PairingGrant {
+            device_id: request.device_id,
+            account_id: my_id,
+            display_name: self.display_name(),
+            identity: self.export_pq_identity().await?,
+        };
+        pairing.doc.update(|doc| doc.grant = Some(grant)).await?;
+
+        tracing::info!(
+            device = %hex::encode(&request.device_id[..8]),
+            "Granted device pairing"
+        );
+        Ok(())
+    }
+
+    /// Wait until a paired device confirms it can read the home realm.
+    pub async fn wait_for_device_link(
+        &self,
+        device_id: MemberId,
+        timeout: std::time::Duration,
+    ) -> Result<()> {
+        let home = self.home_realm().await?;
+        let linked = home
+            .document::<LinkedDevicesDocument>(LINKED_DEVICES_DOC)
+            .await?;
+        let linked = &linked;
+        device_pairing::poll_until(timeout, move || async move {
+            let _ = linked.refresh().await;
+            linked.read().await.is_confirmed(&device_id).then_some(())
+        })
+        .await
+    }
+
+    /// Ask an existing device for its account identity (new device side).
+    ///
+    /// Joins the pairing realm named by `code` and writes a
+    /// [`PairingRequest`]. Wait for the answer with
+    /// [`DevicePairing::wait_for_grant`], then stop this network and call
+    /// [`link_device`](Self::link_device).
+    pub async fn request_device_pairing(
+        &self,
+        code: &PairingCode,
+        device_name: impl Into<String>,
+    ) -> Result<DevicePairing> {
+        if code.account_id() == self.id() {
+            return Err(IndraError::InvalidOperation(
+                "Cannot pair a device with itself".to_string(),
+            ));
+        }
+        if !self.is_running() {
+            self.inner.start().await?;
+        }
+
+        let account_key = iroh::PublicKey::from_bytes(&code.account_id())
+            .map_err(|e| IndraError::Crypto(format!("Invalid account key: {}", e)))?;
+        if let Err(e) = self.inner.connect_to_peer(&code.account_id()).await {
+            tracing::debug!(error = %e, "Transport connect to account device failed");
+        }
+
+        let realm_id = code.realm_id();
+        let (interface_id, _invite_key) = self
+            .inner
+            .create_interface_with_seed(
+                realm_id,
+                &code.key_seed(),
+                Some("Pairing"),
+                vec![account_key],
+            )
+            .await?;
+        let _ = self
+            .inner
+            .add_member(&interface_id, IrohIdentity::from(account_key))
+            .await;
+
+        let doc = Document::new(
+            realm_id,
+            DEVICE_PAIRING_DOC.to_string(),
+            Arc::clone(&self.inner),
+        )
+        .await?;
+        let request = PairingRequest {
+            device_id: self.id(),
+            device_name: device_name.into(),
+            requested_at_millis: now_millis(),
+        };
+        doc.update(|d| d.request = Some(request)).await?;
+
+        Ok(DevicePairing {
+            code: code.clone(),
+            doc,
+        })
+    }
+
+    /// Leave a pairing realm once the exchange is finished.
+    pub async fn finish_device_pairing(&self, pairing: DevicePairing) -> Result<()> {
+        self.inner.leave_interface(&pairing.code.realm_id()).await?;
+        Ok(())
+    }
+
+    /// Install a pairing grant into a data directory (new device side).
+    ///
+    /// Imports the account's PQ identity and records the account as this
+    /// device's home owner. The network using `data_dir` must be stopped
+    /// first; the next `IndrasNetwork::new(data_dir)` joins the account's
+    /// home realm. Finish with [`confirm_device_link`](Self::confirm_device_link).
+    pub async fn link_device(data_dir: impl AsRef<Path>, grant: &PairingGrant) -> Result<()> {
+        let data_dir = data_dir.as_ref();
+        Self::import_pq_identity(data_dir, &grant.identity).await?;
+
+        let mut stored = Self::load_profile(data_dir).unwrap_or_default();
+        stored.home_owner = Some(grant.account_id);
+        if grant.display_name.is_some() {
+            stored.display_name = grant.display_name.clone();
+        }
+        Self::save_profile(data_dir, &stored)
+    }
+
+    /// Confirm this linked device can read the account's home realm.
+    ///
+    /// Waits until the home realm's linked-device document lists this
+    /// device — proof that home realm state has synced from the account —
+    /// then marks it confirmed so the original device sees it too.
+    pub async fn confirm_device_link(&self, timeout: std::time::Duration) -> Result<()> {
+        if !self.is_linked_device() {
+            return Err(IndraError::InvalidOperation(
+                "This device is not linked to another account".to_string(),
+            ));
+        }
+
+        let home = self.home_realm().await?;
+        let linked = home
+            .document::<LinkedDevicesDocument>(LINKED_DEVICES_DOC)
+            .await?;
+        let my_id = self.id();
+        let linked = &linked;
+        device_pairing::poll_until(timeout, move || async move {
+            let _ = linked.refresh().await;
+            linked
+                .read()
+                .await
+                .devices
+                .contains_key(&my_id)
+                .then_some(())
+        })
+        .await?;
+
+        linked
+            .update(|doc| {
+                if let Some(device) = doc.devices.get_mut(&my_id) {
+                    device.confirmed = true;
+                }
+            })
+            .await
+    }
+
     // ============================================================
     // Escape hatches
     // ============================================================
@@ -2683,6 +3971,29 @@ impl IndrasNetwork {
         Ok(removed)
     }
 
+    /// Keep the connection to a contact open regardless of the connection budget.
+    ///
+    /// Pinned contacts are never closed as idle or over a cap, and are
+    /// dialled on demand even when the node is at its connection limit.
+    /// Pins are held in memory; pin again after a restart.
+    pub fn pin_contact(&self, member_id: &MemberId) -> Result<()> {
+        let public_key = iroh::PublicKey::from_bytes(member_id)
+            .map_err(|e| IndraError::Crypto(format!("Invalid peer key: {}", e)))?;
+        self.inner
+            .connection_budget()
+            .pin(IrohIdentity::from(public_key));
+        Ok(())
+    }
+
+    /// Stop pinning a contact. Returns `false` if it wasn't pinned.
+    pub fn unpin_contact(&self, member_id: &MemberId) -> bool {
+        iroh::PublicKey::from_bytes(member_id).is_ok_and(|public_key| {
+            self.inner
+                .connection_budget()
+                .unpin(&IrohIdentity::from(public_key))
+        })
+    }
+
     /// Update sentiment toward a contact (-1, 0, or +1). Clamped to [-1, 1].
     ///
     /// Emits [`PeerEvent::SentimentChanged`] on success.
@@ -2721,6 +4032,90 @@ impl IndrasNetwork {
         Ok(contacts.get_contact_entry(member_id).await)
     }
 
+    // ============================================================
+    // Contact verification
+    // ============================================================
+
+    /// A contact's ML-DSA verifying key, as last announced via gossip.
+    pub fn contact_verifying_key(&self, member_id: &MemberId) -> Option<Vec<u8>> {
+        let public_key = iroh::PublicKey::from_bytes(member_id).ok()?;
+        self.inner
+            .storage()
+            .peer_registry()
+            .get(&IrohIdentity::from(public_key))
+            .ok()
+            .flatten()
+            .and_then(|record| record.pq_verifying_key)
+    }
+
+    /// The safety number to compare with a contact out of band.
+    ///
+    /// Fails if the contact has not announced a verifying key yet.
+    pub fn safety_number(&self, member_id: &MemberId) -> Result<SafetyNumber> {
+        let theirs = self.contact_verifying_key(member_id).ok_or_else(|| {
+            IndraError::InvalidOperation("Contact has not announced a verifying key".to_string())
+        })?;
+        let mine = self.inner.pq_identity().verifying_key_bytes();
+        Ok(SafetyNumber::new((&self.id(), &mine), (member_id, &theirs)))
+    }
+
+    /// Mark a contact verified after comparing safety numbers.
+    ///
+    /// Pins the contact's current verifying key; if it changes later the
+    /// contact shows as [`VerificationStatus::KeyChanged`].
+    pub async fn mark_contact_verified(
+        &self,
+        member_id: &MemberId,
+        method: VerificationMethod,
+    ) -> Result<()> {
+        let key = self.contact_verifying_key(member_id).ok_or_else(|| {
+            IndraError::InvalidOperation("Contact has not announced a verifying key".to_string())
+        })?;
+        let contact = *member_id;
+        let now = now_millis();
+        self.home_realm()
+            .await?
+            .contact_verifications()
+            .await?
+            .update(|doc| doc.verify(contact, &key, method, now))
+            .await?;
+        Ok(())
+    }
+
+    /// Check a scanned safety number QR code and, if it matches, mark the
+    /// contact verified.
+    ///
+    /// Returns `false` (and changes nothing) on a mismatch.
+    pub async fn verify_contact_qr(&self, member_id: &MemberId, payload: &[u8]) -> Result<bool> {
+        if !self.safety_number(member_id)?.matches_qr(payload) {
+            return Ok(false);
+        }
+        self.mark_contact_verified(member_id, VerificationMethod::QrScan)
+            .await?;
+        Ok(true)
+    }
+
+    /// Forget a contact's verification. Returns `false` if they weren't
+    /// verified.
+    pub async fn clear_contact_verification(&self, member_id: &MemberId) -> Result<bool> {
+        let contact = *member_id;
+        let now = now_millis();
+        self.home_realm()
+            .await?
+            .contact_verifications()
+            .await?
+            .try_update(|doc| Ok(doc.clear(&contact, now)))
+            .await
+    }
+
+    /// Whether a contact is verified with the key they announce now.
+    pub async fn verification_status(&self, member_id: &MemberId) -> Result<VerificationStatus> {
+        let current = self.contact_verifying_key(member_id);
+        let doc = self.home_realm().await?.contact_verifications().await?;
+        let status = doc.read().await.status(member_id, current.as_deref());
+        Ok(status)
+    }
+
     /// Build an aggregated sentiment view about a member from direct + relayed signals.
     pub async fn sentiment_view(
         &self,
@@ -2805,6 +4200,8 @@ mod hex {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::guest_access::DAY_MILLIS;
+    use crate::key_rotation::{key_hash, RotationReason};
     use tempfile::TempDir;
 
     #[tokio::test]
@@ -2826,4 +4223,109 @@ mod tests {
 
         assert_eq!(network.display_name(), Some("Test Node".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_peer_realm_needs_known_keys() {
+        let temp = TempDir::new().unwrap();
+        let network = IndrasNetwork::new(temp.path()).await.unwrap();
+        let me = network.id();
+
+        let alone = network.realm(vec![me, me]).await.unwrap_err();
+        assert!(alone.to_string().contains("at least"), "{alone}");
+
+        let stranger = *iroh::SecretKey::generate(&mut rand::rng()).public().as_bytes();
+        let unknown = network.realm(vec![me, stranger]).await.unwrap_err();
+        assert!(unknown.to_string().contains("ML-DSA"), "{unknown}");
+        assert!(network.get_realm(&[me, stranger]).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_empty_membership_log_grants_no_admin() {
+        let (temp, other_temp) = (TempDir::new().unwrap(), TempDir::new().unwrap());
+        let network = IndrasNetwork::new(temp.path()).await.unwrap();
+        let other = IndrasNetwork::new(other_temp.path()).await.unwrap();
+        let other_identity = IrohIdentity::from(iroh::PublicKey::from_bytes(&other.id()).unwrap());
+        let mut record = indras_storage::PeerRecord::new(other.id().to_vec());
+        record.pq_verifying_key = Some(other.inner.pq_identity().verifying_key_bytes());
+        network.inner.storage().peer_registry().upsert(&other_identity, &record).unwrap();
+
+        // Peer-set realms are not created through `create_realm`, so their
+        // log stays empty until it syncs from another member
+        let realm = network.realm(vec![network.id(), other.id()]).await.unwrap();
+        assert!(realm.membership_log().await.unwrap().read().await.is_empty());
+        assert!(realm.record_member_added(other.id(), true).await.is_err());
+        assert!(realm.record_member_removed(other.id()).await.is_err());
+
+        let log = realm.membership_log().await.unwrap();
+        assert!(log.read().await.is_empty());
+        assert!(!realm.verified_membership().await.unwrap().is_admin(&network.id()));
+    }
+
+    #[tokio::test]
+    async fn test_rotate_key_wraps_new_key_for_members() {
+        let (temp, other_temp) = (TempDir::new().unwrap(), TempDir::new().unwrap());
+        let network = IndrasNetwork::new(temp.path()).await.unwrap();
+        let other = IndrasNetwork::new(other_temp.path()).await.unwrap();
+        let other_identity = IrohIdentity::from(iroh::PublicKey::from_bytes(&other.id()).unwrap());
+        let mut record = indras_storage::PeerRecord::new(other.id().to_vec());
+        record.pq_verifying_key = Some(other.inner.pq_identity().verifying_key_bytes());
+        record.pq_encapsulation_key = Some(other.inner.encapsulation_key().to_bytes());
+        network.inner.storage().peer_registry().upsert(&other_identity, &record).unwrap();
+
+        let realm = network.create_realm("Rotating").await.unwrap();
+        realm.record_member_added(other.id(), false).await.unwrap();
+        let old = network.inner.interface_key(&realm.id()).unwrap();
+
+        let rotated = realm.rotate_key(RotationReason::Manual).await.unwrap();
+        assert_eq!(rotated.epoch, 1);
+        assert!(rotated.unreachable.is_empty());
+        let new = network.inner.interface_key(&realm.id()).unwrap();
+        assert_ne!(new.as_bytes(), old.as_bytes());
+        // Already on the current key
+        assert!(!realm.apply_key_rotation().await.unwrap());
+        let info = realm.security_info().await.unwrap();
+        assert_eq!(info.key.epoch, 1);
+        assert_eq!(info.key.rotations.len(), 1);
+        assert!(!info.key.invite_derived);
+
+        let membership = realm.verified_membership().await.unwrap();
+        let doc = realm.key_rotations().await.unwrap();
+        let guard = doc.read().await;
+        let current = guard.current(&realm.id(), &membership).unwrap();
+        assert_eq!(current.rotation.key_hash, key_hash(&new));
+        let share = guard
+            .shares_for(&current.hash(), &other.id(), &membership)
+            .next()
+            .unwrap();
+        let opened = share
+            .open(other.inner.pq_kem_keypair(), &current.rotation.key_hash)
+            .unwrap();
+        assert_eq!(opened.as_bytes(), new.as_bytes());
+    }
+
+    #[tokio::test]
+    async fn test_key_rotated_once_after_guest_revoked() {
+        let temp = TempDir::new().unwrap();
+        let network = IndrasNetwork::new(temp.path()).await.unwrap();
+        let realm = network.create_realm("Guests").await.unwrap();
+        let guest = [9u8; 32];
+        let now = now_millis();
+        realm
+            .guest_access()
+            .await
+            .unwrap()
+            .update(|d| {
+                d.grant(guest, BTreeSet::new(), now + DAY_MILLIS, network.id(), now);
+                d.revoke(&guest, now);
+            })
+            .await
+            .unwrap();
+
+        let old = network.inner.interface_key(&realm.id()).unwrap();
+        assert_eq!(realm.rotate_key_after_guests().await.unwrap(), vec![guest]);
+        let new = network.inner.interface_key(&realm.id()).unwrap();
+        assert_ne!(new.as_bytes(), old.as_bytes());
+        // The current key was established after the guest left
+        assert!(realm.rotate_key_after_guests().await.unwrap().is_empty());
+    }
 }
@@ -0,0 +1,478 @@
+//! OS notifications for incoming realm activity.
+//!
+//! Instead of polling realm state, desktop apps let the
+//! [`NotificationCenter`] watch conversation realms and turn interesting
+//! messages into [`Notification`]s: mentions, direct messages, and
+//! whatever app layers recognize in their own content (e.g. an intention
+//! being assigned). Each notification is published on
+//! [`NotificationCenter::subscribe`] and handed to every registered
+//! [`NotificationSink`]. With the `desktop-notifications` feature,
+//! [`DesktopNotifier`] shows them through the platform's notification
+//! service, with action buttons where the platform supports them.
+//!
+//! What gets through is controlled by [`NotificationPreferences`]: a level
+//! per realm plus kinds to suppress. Preferences are stored per app in the
+//! home realm's settings, so each app filters independently and the
+//! choice follows the member across devices.
+
+use crate::member::MemberId;
+use crate::message::{Content, Message, MessageId};
+use crate::network::RealmId;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Arc, RwLock};
+use tokio::sync::broadcast;
+
+/// Settings key for notification preferences, under each app's namespace.
+pub const NOTIFICATION_SETTINGS_KEY: &str = "notifications";
+
+/// Longest notification body, in characters.
+const MAX_BODY_CHARS: usize = 200;
+
+/// Why a message produced a notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum NotificationKind {
+    /// The local member was mentioned by name.
+    Mention,
+    /// A message in a direct-message realm.
+    DirectMessage,
+    /// Work was assigned to the local member (recognized by app layers).
+    Assigned,
+    /// Any other message.
+    Message,
+}
+
+impl NotificationKind {
+    /// Whether this kind is addressed to the local member specifically.
+    pub fn is_personal(self) -> bool {
+        !matches!(self, NotificationKind::Message)
+    }
+}
+
+/// How much of a realm's activity notifies.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NotificationLevel {
+    /// Every notifying message.
+    #[default]
+    All,
+    /// Only mentions, direct messages, and assignments.
+    Personal,
+    /// Nothing.
+    Muted,
+}
+
+/// Which notifications an app wants to show.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationPreferences {
+    /// Level for realms without an override.
+    #[serde(default)]
+    pub default_level: NotificationLevel,
+    /// Per-realm overrides, keyed by hex-encoded realm id.
+    #[serde(default)]
+    pub realms: BTreeMap<String, NotificationLevel>,
+    /// Kinds never shown, regardless of level.
+    #[serde(default)]
+    pub suppressed: BTreeSet<NotificationKind>,
+}
+
+impl NotificationPreferences {
+    /// The level that applies to a realm.
+    pub fn level(&self, realm_id: &RealmId) -> NotificationLevel {
+        self.realms
+            .get(&hex::encode(realm_id.as_bytes()))
+            .copied()
+            .unwrap_or(self.default_level)
+    }
+
+    /// Override the level for one realm.
+    pub fn set_level(&mut self, realm_id: &RealmId, level: NotificationLevel) {
+        self.realms.insert(hex::encode(realm_id.as_bytes()), level);
+    }
+
+    /// Drop a realm's override, falling back to the default level.
+    pub fn clear_level(&mut self, realm_id: &RealmId) {
+        self.realms.remove(&hex::encode(realm_id.as_bytes()));
+    }
+
+    /// Whether a notification of `kind` in `realm_id` should be shown.
+    pub fn allows(&self, realm_id: &RealmId, kind: NotificationKind) -> bool {
+        if self.suppressed.contains(&kind) {
+            return false;
+        }
+        match self.level(realm_id) {
+            NotificationLevel::All => true,
+            NotificationLevel::Personal => kind.is_personal(),
+            NotificationLevel::Muted => false,
+        }
+    }
+}
+
+/// A button offered on a notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NotificationAction {
+    /// Open the realm at the message.
+    Open,
+    /// Start a reply to the message.
+    Reply,
+    /// Mark the realm read up to the message.
+    MarkRead,
+}
+
+impl NotificationAction {
+    /// Stable identifier passed to the platform.
+    pub fn id(self) -> &'static str {
+        match self {
+            NotificationAction::Open => "open",
+            NotificationAction::Reply => "reply",
+            NotificationAction::MarkRead => "mark-read",
+        }
+    }
+
+    /// Button label.
+    pub fn label(self) -> &'static str {
+        match self {
+            NotificationAction::Open => "Open",
+            NotificationAction::Reply => "Reply",
+            NotificationAction::MarkRead => "Mark as read",
+        }
+    }
+
+    /// Look up an action by [`id`](Self::id).
+    pub fn from_id(id: &str) -> Option<Self> {
+        [Self::Open, Self::Reply, Self::MarkRead]
+            .into_iter()
+            .find(|a| a.id() == id)
+    }
+}
+
+/// A notification ready to show.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    /// Realm the message arrived in.
+    pub realm_id: RealmId,
+    /// The message that triggered it.
+    pub message_id: MessageId,
+    /// Why it notifies.
+    pub kind: NotificationKind,
+    /// Headline, e.g. "Ada in Garden Club".
+    pub title: String,
+    /// Message preview.
+    pub body: String,
+    /// Buttons to offer.
+    pub actions: Vec<NotificationAction>,
+}
+
+/// The member's choice on a shown notification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotificationResponse {
+    /// Realm of the notification.
+    pub realm_id: RealmId,
+    /// Message of the notification.
+    pub message_id: MessageId,
+    /// The chosen action.
+    pub action: NotificationAction,
+}
+
+/// What a classifier knows about where a message arrived.
+#[derive(Debug, Clone)]
+pub struct NotificationContext {
+    /// The local member.
+    pub my_id: MemberId,
+    /// The local member's display name, for mention detection.
+    pub my_name: Option<String>,
+    /// Realm name, if known.
+    pub realm_name: Option<String>,
+    /// Whether the realm is a direct-message realm.
+    pub is_dm: bool,
+}
+
+/// Decides whether and why a message notifies.
+///
+/// App layers replace the default to recognize their own
+/// `Content::Extension` payloads (e.g. [`NotificationKind::Assigned`]).
+pub trait NotificationClassifier: Send + Sync {
+    /// Classify one incoming message. `None` means no notification.
+    fn classify(&self, message: &Message, ctx: &NotificationContext) -> Option<NotificationKind>;
+}
+
+/// Classifier for built-in content types.
+///
+/// Skips the member's own messages and anything without text. A text
+/// message that contains `@name` for the member's display name is a
+/// mention; otherwise it is a direct message in DM realms and an ordinary
+/// message elsewhere.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultNotificationClassifier;
+
+impl NotificationClassifier for DefaultNotificationClassifier {
+    fn classify(&self, message: &Message, ctx: &NotificationContext) -> Option<NotificationKind> {
+        if message.sender.id() == ctx.my_id {
+            return None;
+        }
+        let text = message.content.as_text()?;
+        let mentioned = ctx.my_name.as_deref().is_some_and(|name| {
+            !name.is_empty() && text.to_lowercase().contains(&format!("@{}", name.to_lowercase()))
+        });
+        Some(if mentioned {
+            NotificationKind::Mention
+        } else if ctx.is_dm {
+            NotificationKind::DirectMessage
+        } else {
+            NotificationKind::Message
+        })
+    }
+}
+
+/// Somewhere notifications are shown.
+pub trait NotificationSink: Send + Sync {
+    /// Show one notification. Must not block.
+    fn show(&self, notification: &Notification);
+}
+
+/// Turns incoming messages into notifications according to preferences.
+pub struct NotificationCenter {
+    preferences: RwLock<NotificationPreferences>,
+    classifier: RwLock<Arc<dyn NotificationClassifier>>,
+    sinks: RwLock<Vec<Arc<dyn NotificationSink>>>,
+    event_tx: broadcast::Sender<Notification>,
+    response_tx: broadcast::Sender<NotificationResponse>,
+}
+
+impl NotificationCenter {
+    /// Create a center with default preferences and classifier, and no sinks.
+    pub fn new() -> Self {
+        let (event_tx, _) = broadcast::channel(64);
+        let (response_tx, _) = broadcast::channel(64);
+        Self {
+            preferences: RwLock::new(NotificationPreferences::default()),
+            classifier: RwLock::new(Arc::new(DefaultNotificationClassifier)),
+            sinks: RwLock::new(Vec::new()),
+            event_tx,
+            response_tx,
+        }
+    }
+
+    /// Current preferences.
+    pub fn preferences(&self) -> NotificationPreferences {
+        self.preferences.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Replace the preferences.
+    ///
+    /// To persist them per app, use
+    /// [`IndrasNetwork::save_notification_preferences`](crate::IndrasNetwork::save_notification_preferences).
+    pub fn set_preferences(&self, preferences: NotificationPreferences) {
+        *self.preferences.write().unwrap_or_else(|e| e.into_inner()) = preferences;
+    }
+
+    /// Replace the classifier.
+    pub fn set_classifier(&self, classifier: impl NotificationClassifier + 'static) {
+        *self.classifier.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(classifier);
+    }
+
+    /// Show notifications through another sink.
+    pub fn add_sink(&self, sink: impl NotificationSink + 'static) {
+        self.sinks
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(Arc::new(sink));
+    }
+
+    /// Subscribe to notifications that passed the preferences.
+    pub fn subscribe(&self) -> broadcast::Receiver<Notification> {
+        self.event_tx.subscribe()
+    }
+
+    /// Subscribe to actions chosen on shown notifications.
+    pub fn responses(&self) -> broadcast::Receiver<NotificationResponse> {
+        self.response_tx.subscribe()
+    }
+
+    /// Report an action chosen on a notification. Called by sinks.
+    pub fn respond(&self, response: NotificationResponse) {
+        let _ = self.response_tx.send(response);
+    }
+
+    /// Classify a message and, if preferences allow, publish and show it.
+    pub fn dispatch(&self, message: &Message, ctx: &NotificationContext) -> Option<Notification> {
+        let classifier = Arc::clone(&self.classifier.read().unwrap_or_else(|e| e.into_inner()));
+        let kind = classifier.classify(message, ctx)?;
+        let realm_id = message.id.interface_id;
+        if !self.preferences().allows(&realm_id, kind) {
+            return None;
+        }
+
+        let notification = build_notification(message, ctx, kind);
+        let _ = self.event_tx.send(notification.clone());
+        let sinks = self.sinks.read().unwrap_or_else(|e| e.into_inner()).clone();
+        for sink in sinks {
+            sink.show(&notification);
+        }
+        Some(notification)
+    }
+}
+
+impl Default for NotificationCenter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn build_notification(
+    message: &Message,
+    ctx: &NotificationContext,
+    kind: NotificationKind,
+) -> Notification {
+    let sender = message.sender.name();
+    let title = match (&ctx.realm_name, ctx.is_dm) {
+        (Some(realm), false) => format!("{sender} in {realm}"),
+        _ => sender,
+    };
+    let body = match &message.content {
+        Content::Extension { .. } | Content::System(_) => String::new(),
+        content => content.as_text().unwrap_or_default().to_string(),
+    };
+    let body = match body.char_indices().nth(MAX_BODY_CHARS) {
+        Some((end, _)) => format!("{}…", &body[..end]),
+        None => body,
+    };
+    Notification {
+        realm_id: message.id.interface_id,
+        message_id: message.id,
+        kind,
+        title,
+        body,
+        actions: vec![
+            NotificationAction::Open,
+            NotificationAction::Reply,
+            NotificationAction::MarkRead,
+        ],
+    }
+}
+
+/// Shows notifications through the operating system.
+///
+/// On Linux and the BSDs, chosen actions are reported on
+/// [`NotificationCenter::responses`]; other platforms show the
+/// notification without buttons.
+#[cfg(feature = "desktop-notifications")]
+pub struct DesktopNotifier {
+    app_name: String,
+    center: std::sync::Weak<NotificationCenter>,
+}
+
+#[cfg(feature = "desktop-notifications")]
+impl DesktopNotifier {
+    /// Create a notifier that shows notifications under `app_name`.
+    ///
+    /// Responses are reported to `center`.
+    pub fn new(app_name: impl Into<String>, center: &Arc<NotificationCenter>) -> Self {
+        Self {
+            app_name: app_name.into(),
+            center: Arc::downgrade(center),
+        }
+    }
+}
+
+#[cfg(feature = "desktop-notifications")]
+impl NotificationSink for DesktopNotifier {
+    fn show(&self, notification: &Notification) {
+        let mut os = notify_rust::Notification::new();
+        os.appname(&self.app_name)
+            .summary(&notification.title)
+            .body(&notification.body);
+
+        #[cfg(all(unix, not(target_os = "macos")))]
+        {
+            for action in &notification.actions {
+                os.action(action.id(), action.label());
+            }
+            let center = self.center.clone();
+            let (realm_id, message_id) = (notification.realm_id, notification.message_id);
+            tokio::task::spawn_blocking(move || match os.show() {
+                Ok(handle) => handle.wait_for_action(|id| {
+                    if let (Some(action), Some(center)) =
+                        (NotificationAction::from_id(id), center.upgrade())
+                    {
+                        center.respond(NotificationResponse {
+                            realm_id,
+                            message_id,
+                            action,
+                        });
+                    }
+                }),
+                Err(e) => tracing::debug!(error = %e, "failed to show notification"),
+            });
+        }
+
+        #[cfg(not(all(unix, not(target_os = "macos"))))]
+        if let Err(e) = os.show() {
+            tracing::debug!(error = %e, "failed to show notification");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::member::Member;
+    use chrono::Utc;
+    use indras_core::{EventId, InterfaceId};
+    use indras_transport::IrohIdentity;
+
+    fn member() -> Member {
+        Member::new(IrohIdentity::new(iroh::SecretKey::generate(&mut rand::rng()).public()))
+    }
+
+    fn message(sender: &Member, text: &str) -> Message {
+        let id = MessageId::new(InterfaceId::new([7; 32]), EventId::new(1, 1));
+        Message::new(id, sender.clone(), Content::Text(text.to_string()), Utc::now())
+    }
+
+    fn ctx(me: &Member, is_dm: bool) -> NotificationContext {
+        NotificationContext {
+            my_id: me.id(),
+            my_name: Some("Ada".to_string()),
+            realm_name: Some("Garden".to_string()),
+            is_dm,
+        }
+    }
+
+    #[test]
+    fn test_default_classifier() {
+        let (me, peer) = (member(), member());
+        let classify = |m: &Message, dm| DefaultNotificationClassifier.classify(m, &ctx(&me, dm));
+
+        assert_eq!(classify(&message(&peer, "hi @ada!"), false), Some(NotificationKind::Mention));
+        assert_eq!(classify(&message(&peer, "hi"), true), Some(NotificationKind::DirectMessage));
+        assert_eq!(classify(&message(&peer, "hi"), false), Some(NotificationKind::Message));
+        assert_eq!(classify(&message(&me, "@ada"), false), None);
+    }
+
+    #[test]
+    fn test_preferences_filter_by_realm_and_kind() {
+        let (me, peer) = (member(), member());
+        let center = NotificationCenter::new();
+        let mut prefs = NotificationPreferences::default();
+        prefs.set_level(&InterfaceId::new([7; 32]), NotificationLevel::Personal);
+        center.set_preferences(prefs);
+
+        assert!(center.dispatch(&message(&peer, "chatter"), &ctx(&me, false)).is_none());
+        let shown = center.dispatch(&message(&peer, "@Ada look"), &ctx(&me, false)).unwrap();
+        assert_eq!(shown.title, format!("{} in Garden", peer.name()));
+
+        let mut prefs = center.preferences();
+        prefs.suppressed.insert(NotificationKind::Mention);
+        center.set_preferences(prefs);
+        assert!(center.dispatch(&message(&peer, "@Ada look"), &ctx(&me, false)).is_none());
+    }
+
+    #[test]
+    fn test_preferences_round_trip_as_settings_json() {
+        let mut prefs = NotificationPreferences::default();
+        prefs.set_level(&InterfaceId::new([1; 32]), NotificationLevel::Muted);
+        prefs.suppressed.insert(NotificationKind::Assigned);
+        let json = serde_json::to_string(&prefs).unwrap();
+        assert_eq!(serde_json::from_str::<NotificationPreferences>(&json).unwrap(), prefs);
+    }
+}
@@ -8,6 +8,7 @@ pub(crate) mod tasks;
 use crate::contacts::ContactStatus;
 use crate::member::MemberId;
 use crate::network::{GlobalEvent, RealmId};
+use crate::profile::Profile;
 
 // Re-export ContactStatus for consumer convenience
 pub use crate::contacts::ContactStatus as PeerStatus;
@@ -70,6 +71,21 @@ pub enum PeerEvent {
         /// New sentiment value (-1, 0, or 1).
         sentiment: i8,
     },
+    /// A peer published a new profile.
+    ProfileChanged {
+        /// Identity of the peer whose profile changed.
+        member_id: MemberId,
+        /// The new profile.
+        profile: Profile,
+    },
+    /// A contact verified by safety number now announces a different key.
+    ///
+    /// Emitted once per change; compare safety numbers again before
+    /// trusting the contact.
+    ContactKeyChanged {
+        /// Identity of the contact whose key changed.
+        member_id: MemberId,
+    },
     /// Non-fatal warning.
     Warning(String),
 }
@@ -1,6 +1,6 @@
 //! Background tasks for the peering lifecycle within `IndrasNetwork`.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -9,8 +9,10 @@ use tokio::sync::{broadcast, watch, Notify};
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 
+use crate::contact_invite::ContactInviteMessage;
+use crate::contact_verification::VerificationStatus;
 use crate::member::MemberId;
-use crate::network::IndrasNetwork;
+use crate::network::{IndrasNetwork, RealmId};
 
 use super::{PeerEvent, PeerInfo};
 
@@ -21,7 +23,8 @@ pub(crate) fn short_id(id: &MemberId) -> String {
 }
 
 /// Polls the contacts realm every `interval`, diffs against the previous set,
-/// and emits `PeerConnected` / `PeerDisconnected` / `PeersChanged` events.
+/// and emits `PeerConnected` / `PeerDisconnected` / `PeersChanged` events,
+/// plus `ContactKeyChanged` when a verified contact's key changes.
 ///
 /// Also listens on `poll_notify` to allow on-demand immediate poll cycles
 /// via [`IndrasNetwork::refresh_peers()`].
@@ -35,6 +38,7 @@ pub(crate) fn spawn_contact_poller(
 ) -> JoinHandle<()> {
     tokio::spawn(async move {
         let mut known: HashMap<MemberId, PeerInfo> = HashMap::new();
+        let mut key_warned: HashSet<MemberId> = HashSet::new();
         let mut ticker = tokio::time::interval(interval);
         ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
 
@@ -59,17 +63,22 @@ pub(crate) fn spawn_contact_poller(
                     continue;
                 }
 
+                // A published profile name beats the one seen at connect time
+                let profile_name = network.profile_of(cid).and_then(|p| p.display_name);
+
                 if let Some(existing) = known.get(cid) {
                     let entry = contacts_realm.get_contact_entry(cid).await;
                     let mut updated = existing.clone();
+                    if let Some(name) = profile_name {
+                        updated.display_name = name;
+                    }
                     updated.sentiment = entry.as_ref().map(|e| e.sentiment).unwrap_or(0);
                     updated.status = entry.as_ref().map(|e| e.status).unwrap_or_default();
                     current.insert(*cid, updated);
                 } else {
                     let entry = contacts_realm.get_contact_entry(cid).await;
-                    let display_name = entry
-                        .as_ref()
-                        .and_then(|e| e.display_name.clone())
+                    let display_name = profile_name
+                        .or_else(|| entry.as_ref().and_then(|e| e.display_name.clone()))
                         .unwrap_or_else(|| short_id(cid));
 
                     let info = PeerInfo {
@@ -101,6 +110,25 @@ pub(crate) fn spawn_contact_poller(
                 let _ = peers_tx.send(peers_vec);
             }
 
+            // Warn once per key change of a contact verified by safety number
+            if let Ok(home) = network.home_realm().await
+                && let Ok(doc) = home.contact_verifications().await
+            {
+                let verifications = doc.read().await;
+                for cid in current.keys() {
+                    let key = network.contact_verifying_key(cid);
+                    let changed = matches!(
+                        verifications.status(cid, key.as_deref()),
+                        VerificationStatus::KeyChanged { .. }
+                    );
+                    if !changed {
+                        key_warned.remove(cid);
+                    } else if key_warned.insert(*cid) {
+                        let _ = event_tx.send(PeerEvent::ContactKeyChanged { member_id: *cid });
+                    }
+                }
+            }
+
             known = current;
         }
 
@@ -109,7 +137,7 @@ pub(crate) fn spawn_contact_poller(
 }
 
 /// Forwards raw `GlobalEvent`s from the network's event stream into the
-/// peering broadcast channel.
+/// peering broadcast channel and the network's event bus.
 pub(crate) fn spawn_event_forwarder(
     network: Arc<IndrasNetwork>,
     event_tx: broadcast::Sender<PeerEvent>,
@@ -124,6 +152,7 @@ pub(crate) fn spawn_event_forwarder(
                 maybe_event = stream.next() => {
                     match maybe_event {
                         Some(ge) => {
+                            network.event_bus().publish(ge.clone());
                             let _ = event_tx.send(PeerEvent::NetworkEvent(ge));
                         }
                         None => break,
@@ -169,6 +198,368 @@ pub(crate) fn spawn_periodic_saver(
     })
 }
 
+/// Compacts document tombstones in every loaded realm every `interval`.
+///
+/// Each pass also refreshes this node's sync horizon, so members that
+/// stay online keep the realm's compaction horizon moving forward.
+pub(crate) fn spawn_tombstone_compactor(
+    network: Arc<IndrasNetwork>,
+    cancel: CancellationToken,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        // Skip the first immediate tick
+        ticker.tick().await;
+
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                _ = ticker.tick() => {}
+            }
+
+            for realm_id in network.realms() {
+                let Some(realm) = network.get_realm_by_id(&realm_id) else {
+                    continue;
+                };
+                match realm.compact_tombstones().await {
+                    Ok(0) => {}
+                    Ok(removed) => tracing::debug!(removed, "compacted realm tombstones"),
+                    Err(e) => tracing::debug!(error = %e, "tombstone compaction failed"),
+                }
+            }
+        }
+
+        tracing::debug!("tombstone compactor stopped");
+    })
+}
+
+/// Applies every realm's guest grants, anonymity ring and archive state to
+/// the local node every `interval`.
+///
+/// Grants and revocations made by other members arrive through the realm's
+/// guest access document; this keeps the node's read-only registry in step,
+/// and on an admin's node rotates the realm key once a guest's access ends.
+/// Ring keys published by other members, and archives by other admins, are
+/// picked up the same way. Each pass also publishes this node's verifying
+/// key to the realm's key log, once per realm and key, keeps realm
+/// ownership current (owner heartbeats, successor claims), and adopts or
+/// passes on a rotated realm key.
+pub(crate) fn spawn_guest_enforcer(
+    network: Arc<IndrasNetwork>,
+    cancel: CancellationToken,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                _ = ticker.tick() => {}
+            }
+
+            for realm_id in network.conversation_realms() {
+                let Some(realm) = network.get_realm_by_id(&realm_id) else {
+                    continue;
+                };
+                if let Err(e) = realm.enforce_guest_access().await {
+                    tracing::debug!(error = %e, "guest access enforcement failed");
+                }
+                match realm.rotate_key_after_guests().await {
+                    Ok(lapsed) if !lapsed.is_empty() => {
+                        let guests = lapsed.len();
+                        tracing::info!(guests, "rotated realm key after guest access ended");
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::debug!(error = %e, "guest key rotation failed"),
+                }
+                if let Err(e) = realm.apply_anonymity_ring().await {
+                    tracing::debug!(error = %e, "anonymity ring refresh failed");
+                }
+                if let Err(e) = realm.apply_archive_state().await {
+                    tracing::debug!(error = %e, "archive state refresh failed");
+                }
+                if let Err(e) = realm.publish_key().await {
+                    tracing::debug!(error = %e, "key log publish failed");
+                }
+                match realm.apply_key_rotation().await {
+                    Ok(true) => tracing::info!("adopted rotated realm key"),
+                    Ok(false) => {}
+                    Err(e) => tracing::debug!(error = %e, "key rotation refresh failed"),
+                }
+                match realm.maintain_ownership().await {
+                    Ok(true) => tracing::info!("claimed realm ownership as successor"),
+                    Ok(false) => {}
+                    Err(e) => tracing::debug!(error = %e, "ownership upkeep failed"),
+                }
+            }
+        }
+
+        tracing::debug!("guest enforcer stopped");
+    })
+}
+
+/// Publishes our profile to every shared realm and refreshes peer profiles
+/// every `interval`.
+///
+/// This is how a profile reaches realms joined after it was set, and how
+/// profile changes from peers land in the cache the contact poller reads.
+pub(crate) fn spawn_profile_sync(
+    network: Arc<IndrasNetwork>,
+    cancel: CancellationToken,
+    interval: Duration,
+    poll_notify: Arc<Notify>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                _ = ticker.tick() => {}
+            }
+
+            if network.sync_profiles().await > 0 {
+                // Let the contact poller pick up new names right away
+                poll_notify.notify_one();
+            }
+        }
+
+        tracing::debug!("profile sync stopped");
+    })
+}
+
+/// Carries out delegated actions the node has authorized.
+///
+/// The node checks each delegate's token and records the audit trail; this
+/// task performs what was allowed on the owner's behalf.
+pub(crate) fn spawn_delegation_executor(
+    network: Arc<IndrasNetwork>,
+    cancel: CancellationToken,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut actions = network.node().delegated_actions();
+
+        loop {
+            let authorized = tokio::select! {
+                _ = cancel.cancelled() => break,
+                r = actions.recv() => match r {
+                    Ok(authorized) => authorized,
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+            };
+
+            let result = match authorized.action {
+                indras_node::DelegatedAction::ResendInvite { invitee } => {
+                    match network.get_realm_by_id(&authorized.interface_id) {
+                        Some(realm) => realm.invite_contact(invitee).await,
+                        None => continue,
+                    }
+                }
+                indras_node::DelegatedAction::PostMessage { content } => network
+                    .node()
+                    .send_message(&authorized.interface_id, content)
+                    .await
+                    .map(|_| ())
+                    .map_err(Into::into),
+            };
+            if let Err(e) = result {
+                tracing::debug!(error = %e, "delegated action failed");
+            }
+        }
+
+        tracing::debug!("delegation executor stopped");
+    })
+}
+
+/// Generates realm digests every `interval`.
+///
+/// Only realms enabled via `network.digests().enable(..)` are summarized;
+/// a pass with nothing enabled is a no-op.
+pub(crate) fn spawn_digest_generator(
+    network: Arc<IndrasNetwork>,
+    cancel: CancellationToken,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        // Skip the first immediate tick
+        ticker.tick().await;
+
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                _ = ticker.tick() => {}
+            }
+
+            match network.generate_digests().await {
+                Ok(digests) if digests.is_empty() => {}
+                Ok(digests) => tracing::debug!(count = digests.len(), "generated realm digests"),
+                Err(e) => tracing::debug!(error = %e, "digest generation failed"),
+            }
+        }
+
+        tracing::debug!("digest generator stopped");
+    })
+}
+
+/// Watches DM realms for in-band contact invitations.
+///
+/// Every `interval`, subscribes to any conversation realm not yet watched
+/// (invites arrive on DM realms, which are conversation realms), and
+/// forwards decoded invite traffic to the network's invite handler.
+pub(crate) fn spawn_invite_watcher(
+    network: Arc<IndrasNetwork>,
+    cancel: CancellationToken,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut watched: HashMap<RealmId, JoinHandle<()>> = HashMap::new();
+
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                _ = ticker.tick() => {}
+            }
+
+            watched.retain(|_, handle| !handle.is_finished());
+            for realm_id in network.conversation_realms() {
+                if watched.contains_key(&realm_id) {
+                    continue;
+                }
+                let Ok(rx) = network.node().events(&realm_id) else {
+                    continue;
+                };
+                let handle = tokio::spawn(forward_invites(
+                    Arc::downgrade(&network),
+                    realm_id,
+                    rx,
+                    cancel.clone(),
+                ));
+                watched.insert(realm_id, handle);
+            }
+        }
+
+        for (_, handle) in watched.drain() {
+            handle.abort();
+        }
+        tracing::debug!("invite watcher stopped");
+    })
+}
+
+/// Forward invite messages from one DM realm until cancelled or closed.
+async fn forward_invites(
+    network: std::sync::Weak<IndrasNetwork>,
+    realm_id: RealmId,
+    mut rx: broadcast::Receiver<indras_node::ReceivedEvent>,
+    cancel: CancellationToken,
+) {
+    loop {
+        let received = tokio::select! {
+            _ = cancel.cancelled() => break,
+            r = rx.recv() => r,
+        };
+        let event = match received {
+            Ok(event) => event,
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+        let Some(message) = crate::realm::convert_event_to_message(event, realm_id) else {
+            continue;
+        };
+        let Some(invite) = ContactInviteMessage::from_content(&message.content) else {
+            continue;
+        };
+        let Some(network) = network.upgrade() else {
+            break;
+        };
+        network.handle_invite_message(message.sender.id(), invite);
+    }
+}
+
+/// Offers incoming conversation messages to the notification center.
+///
+/// Every `interval`, subscribes to any conversation realm not yet watched,
+/// like [`spawn_invite_watcher`].
+pub(crate) fn spawn_notification_dispatcher(
+    network: Arc<IndrasNetwork>,
+    cancel: CancellationToken,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut watched: HashMap<RealmId, JoinHandle<()>> = HashMap::new();
+
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                _ = ticker.tick() => {}
+            }
+
+            watched.retain(|_, handle| !handle.is_finished());
+            for realm_id in network.conversation_realms() {
+                if watched.contains_key(&realm_id) {
+                    continue;
+                }
+                let Ok(rx) = network.node().events(&realm_id) else {
+                    continue;
+                };
+                let handle = tokio::spawn(forward_notifications(
+                    Arc::downgrade(&network),
+                    realm_id,
+                    rx,
+                    cancel.clone(),
+                ));
+                watched.insert(realm_id, handle);
+            }
+        }
+
+        for (_, handle) in watched.drain() {
+            handle.abort();
+        }
+        tracing::debug!("notification dispatcher stopped");
+    })
+}
+
+/// Dispatch messages from one realm until cancelled or closed.
+async fn forward_notifications(
+    network: std::sync::Weak<IndrasNetwork>,
+    realm_id: RealmId,
+    mut rx: broadcast::Receiver<indras_node::ReceivedEvent>,
+    cancel: CancellationToken,
+) {
+    loop {
+        let received = tokio::select! {
+            _ = cancel.cancelled() => break,
+            r = rx.recv() => r,
+        };
+        let event = match received {
+            Ok(event) => event,
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+        let Some(message) = crate::realm::convert_event_to_message(event, realm_id) else {
+            continue;
+        };
+        let Some(network) = network.upgrade() else {
+            break;
+        };
+        let ctx = network.notification_context(&realm_id);
+        network.notifications().dispatch(&message, &ctx);
+    }
+}
+
 /// Lightweight supervisor that checks task health every 30 seconds.
 pub(crate) fn spawn_task_supervisor(
     event_tx: broadcast::Sender<PeerEvent>,
@@ -0,0 +1,216 @@
+//! Network-wide member profiles.
+//!
+//! A profile (display name, avatar, pronouns, status message) belongs to a
+//! member, not to a realm, but there is no global document to keep it in.
+//! Instead every member publishes the same [`SignedProfile`] into a
+//! [`ProfileDocument`] in each realm it shares, and the network copies it
+//! into new realms as they appear. Readers take the newest valid profile
+//! for each member across all realms.
+//!
+//! Profiles are signed with the owner's ML-DSA-65 identity, so members
+//! cannot rewrite each other's names. The verifying key is pinned per
+//! member the first time a realm sees a profile (trust on first use);
+//! later profiles under a different key are dropped on merge.
+
+use crate::artifact::ArtifactId;
+use crate::document::DocumentSchema;
+use crate::member::MemberId;
+use indras_crypto::pq_identity::{PQIdentity, PQPublicIdentity, PQSignature};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Document name for member profiles within a realm.
+pub const PROFILE_DOC: &str = "_profiles";
+
+/// Longest display name, pronouns, or status message accepted, in bytes.
+pub const MAX_PROFILE_FIELD_LEN: usize = 256;
+
+/// What a member shows about themself.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profile {
+    /// Human-readable display name.
+    #[serde(default)]
+    pub display_name: Option<String>,
+    /// Avatar image, shared as an artifact.
+    #[serde(default)]
+    pub avatar: Option<ArtifactId>,
+    /// Pronouns, as the member wrote them.
+    #[serde(default)]
+    pub pronouns: Option<String>,
+    /// Free-form status message.
+    #[serde(default)]
+    pub status: Option<String>,
+    /// When the profile was last changed (milliseconds since epoch).
+    pub updated_at_millis: u64,
+}
+
+impl Profile {
+    /// Whether every text field fits within [`MAX_PROFILE_FIELD_LEN`].
+    pub fn is_valid(&self) -> bool {
+        [&self.display_name, &self.pronouns, &self.status]
+            .into_iter()
+            .flatten()
+            .all(|field| field.len() <= MAX_PROFILE_FIELD_LEN)
+    }
+}
+
+/// A profile signed by the member it describes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedProfile {
+    /// The member the profile describes.
+    pub member: MemberId,
+    /// The profile.
+    pub profile: Profile,
+    /// Owner's ML-DSA-65 verifying key.
+    pub verifying_key: Vec<u8>,
+    /// Signature over the member id and profile.
+    pub signature: Vec<u8>,
+}
+
+impl SignedProfile {
+    /// Sign `profile` as `member`.
+    pub fn sign(member: MemberId, profile: Profile, identity: &PQIdentity) -> Self {
+        let signature = identity.sign(&signing_bytes(&member, &profile));
+        Self {
+            member,
+            profile,
+            verifying_key: identity.verifying_key_bytes(),
+            signature: signature.to_bytes().to_vec(),
+        }
+    }
+
+    /// Whether the signature is valid and the profile well-formed.
+    pub fn verify(&self) -> bool {
+        if !self.profile.is_valid() {
+            return false;
+        }
+        let (Ok(key), Ok(signature)) = (
+            PQPublicIdentity::from_bytes(&self.verifying_key),
+            PQSignature::from_bytes(self.signature.clone()),
+        ) else {
+            return false;
+        };
+        key.verify(&signing_bytes(&self.member, &self.profile), &signature)
+    }
+}
+
+/// Domain-separated bytes the profile signature covers.
+fn signing_bytes(member: &MemberId, profile: &Profile) -> Vec<u8> {
+    const DOMAIN: &[u8] = b"indras:profile:v1";
+    let mut bytes = DOMAIN.to_vec();
+    bytes.extend_from_slice(member);
+    bytes.extend(postcard::to_allocvec(profile).unwrap_or_default());
+    bytes
+}
+
+/// CRDT document holding the profiles of a realm's members.
+///
+/// # Example
+///
+/// ```ignore
+/// network.set_profile(Profile {
+///     display_name: Some("Ada".into()),
+///     pronouns: Some("she/her".into()),
+///     ..Default::default()
+/// }).await?;
+///
+/// if let Some(profile) = network.profile_of(&peer_id) {
+///     println!("{:?} ({:?})", profile.display_name, profile.pronouns);
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProfileDocument {
+    /// Latest signed profile per member.
+    pub profiles: BTreeMap<MemberId, SignedProfile>,
+}
+
+impl ProfileDocument {
+    /// Create an empty profile document.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store a profile if it is valid and newer than the one held.
+    ///
+    /// Returns `false` if it was rejected or not newer.
+    pub fn publish(&mut self, signed: SignedProfile) -> bool {
+        if !signed.verify() {
+            return false;
+        }
+        match self.profiles.get(&signed.member) {
+            Some(local) if local.verifying_key != signed.verifying_key => false,
+            Some(local) if local.profile.updated_at_millis >= signed.profile.updated_at_millis => {
+                false
+            }
+            _ => {
+                self.profiles.insert(signed.member, signed);
+                true
+            }
+        }
+    }
+
+    /// A member's profile, if published.
+    pub fn get(&self, member: &MemberId) -> Option<&Profile> {
+        self.profiles.get(member).map(|s| &s.profile)
+    }
+}
+
+impl DocumentSchema for ProfileDocument {
+    /// Per-member LWW merge over verified profiles.
+    fn merge(&mut self, remote: Self) {
+        for signed in remote.profiles.into_values() {
+            self.publish(signed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(name: &str, at: u64) -> Profile {
+        Profile {
+            display_name: Some(name.to_string()),
+            updated_at_millis: at,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_newer_profile_wins() {
+        let identity = PQIdentity::generate();
+        let member = [1u8; 32];
+        let mut doc = ProfileDocument::new();
+
+        assert!(doc.publish(SignedProfile::sign(member, profile("Ada", 10), &identity)));
+        assert!(!doc.publish(SignedProfile::sign(member, profile("Old", 5), &identity)));
+
+        let mut remote = ProfileDocument::new();
+        remote.publish(SignedProfile::sign(member, profile("Ada L.", 20), &identity));
+        doc.merge(remote);
+        assert_eq!(doc.get(&member).unwrap().display_name.as_deref(), Some("Ada L."));
+    }
+
+    #[test]
+    fn test_forged_and_rekeyed_profiles_rejected() {
+        let owner = PQIdentity::generate();
+        let member = [1u8; 32];
+        let mut doc = ProfileDocument::new();
+        doc.publish(SignedProfile::sign(member, profile("Ada", 10), &owner));
+
+        let mut tampered = SignedProfile::sign(member, profile("Ada", 20), &owner);
+        tampered.profile.display_name = Some("Mallory".to_string());
+        assert!(!doc.publish(tampered));
+
+        let impostor = PQIdentity::generate();
+        assert!(!doc.publish(SignedProfile::sign(member, profile("Mallory", 30), &impostor)));
+        assert_eq!(doc.get(&member).unwrap().display_name.as_deref(), Some("Ada"));
+    }
+
+    #[test]
+    fn test_oversized_fields_rejected() {
+        let identity = PQIdentity::generate();
+        let long = profile(&"x".repeat(MAX_PROFILE_FIELD_LEN + 1), 1);
+        assert!(!SignedProfile::sign([1u8; 32], long, &identity).verify());
+    }
+}
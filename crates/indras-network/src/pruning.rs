@@ -0,0 +1,372 @@
+//! Pruning inactive members from a realm.
+//!
+//! Members who stopped syncing long ago still show up in member lists,
+//! receive every event and hold back the tombstone compaction horizon.
+//! Admins prune them in two steps recorded in the realm's
+//! [`PruneDocument`]:
+//!
+//! 1. An admin proposes pruning every member with no activity for N days:
+//!    no sync horizon newer than that (see
+//!    [`SyncHorizonDocument`](crate::tombstone::SyncHorizonDocument)), and
+//!    no membership-log entry joining them since.
+//! 2. After a grace period an admin confirms; in a realm with several
+//!    admins it must be a different admin than the proposer. Members who
+//!    became active during the grace period are spared.
+//!
+//! Confirmed members are removed from the signed membership log and the
+//! interface. A pruned member can be re-invited later; the re-invite
+//! records whether they see the realm's full history again or only what
+//! came after they rejoined. With [`RejoinHistory::SinceRejoin`] their
+//! node leaves older messages out of the realm's message reads; the data
+//! itself still syncs.
+//!
+//! Confirming a prune also rotates the realm key (see
+//! [`crate::key_rotation`]): the new key is wrapped to the members who
+//! remain, so a pruned member who kept the old key can't read events sent
+//! after the prune.
+
+use crate::document::DocumentSchema;
+use crate::guest_access::DAY_MILLIS;
+use crate::member::MemberId;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Document name for prune proposals and pruned members within a realm.
+pub const PRUNE_DOC: &str = "_prune";
+
+/// Days without activity after which a member counts as inactive.
+pub const DEFAULT_INACTIVE_DAYS: u32 = 90;
+
+/// How long a prune proposal waits before it can be confirmed.
+pub const DEFAULT_PRUNE_GRACE_MILLIS: u64 = 7 * DAY_MILLIS;
+
+/// A member with no recent activity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InactiveMember {
+    /// The member.
+    pub member: MemberId,
+    /// Their latest activity (milliseconds since epoch), if any is known.
+    pub last_active_millis: Option<u64>,
+}
+
+/// Members whose latest activity is before `cutoff_millis`, or unknown.
+pub fn find_inactive(
+    members: impl IntoIterator<Item = MemberId>,
+    last_active: impl Fn(&MemberId) -> Option<u64>,
+    cutoff_millis: u64,
+) -> Vec<InactiveMember> {
+    members
+        .into_iter()
+        .map(|member| InactiveMember {
+            member,
+            last_active_millis: last_active(&member),
+        })
+        .filter(|m| m.last_active_millis.is_none_or(|at| at < cutoff_millis))
+        .collect()
+}
+
+/// How much history a re-invited member gets back.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RejoinHistory {
+    /// Everything, as before they were pruned.
+    #[default]
+    Full,
+    /// Only what was posted after they rejoined.
+    SinceRejoin,
+}
+
+/// A proposal to prune inactive members.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PruneProposal {
+    /// Unique proposal ID.
+    pub id: String,
+    /// Admin who proposed it.
+    pub proposed_by: MemberId,
+    /// Members to prune, as found inactive when proposed.
+    pub members: BTreeSet<MemberId>,
+    /// When it was proposed (milliseconds since epoch).
+    pub proposed_at_millis: u64,
+    /// Earliest confirmation time (milliseconds since epoch).
+    pub confirm_after_millis: u64,
+    /// Admin who confirmed it, and the members actually pruned.
+    pub confirmed: Option<(MemberId, BTreeSet<MemberId>)>,
+    /// Admin who cancelled it.
+    pub cancelled_by: Option<MemberId>,
+    /// When the proposal last changed (milliseconds since epoch).
+    pub updated_at_millis: u64,
+}
+
+impl PruneProposal {
+    /// Whether the proposal is neither confirmed nor cancelled.
+    pub fn is_open(&self) -> bool {
+        self.confirmed.is_none() && self.cancelled_by.is_none()
+    }
+
+    /// Whether `admin` may confirm the proposal at `now_millis`, in a realm
+    /// with `admin_count` admins.
+    pub fn can_confirm(&self, admin: &MemberId, admin_count: usize, now_millis: u64) -> bool {
+        self.is_open()
+            && now_millis >= self.confirm_after_millis
+            && (admin_count < 2 || *admin != self.proposed_by)
+    }
+}
+
+/// Someone invited back after being pruned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rejoin {
+    /// Member who re-invited them.
+    pub by: MemberId,
+    /// When (milliseconds since epoch).
+    pub at_millis: u64,
+    /// How much history they get back.
+    pub history: RejoinHistory,
+}
+
+/// A member removed by a prune.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrunedMember {
+    /// The member.
+    pub member: MemberId,
+    /// The proposal that pruned them.
+    pub proposal: String,
+    /// When they were pruned (milliseconds since epoch).
+    pub pruned_at_millis: u64,
+    /// Their latest activity before the prune, if known.
+    pub last_active_millis: Option<u64>,
+    /// Set once they were re-invited.
+    pub rejoin: Option<Rejoin>,
+    /// When the record last changed (milliseconds since epoch).
+    pub updated_at_millis: u64,
+}
+
+/// CRDT document of a realm's prune proposals and pruned members.
+///
+/// # Example
+///
+/// ```ignore
+/// if let Some(id) = realm.propose_prune(DEFAULT_INACTIVE_DAYS, None).await? {
+///     // ...a week later, another admin:
+///     let pruned = realm.confirm_prune(&id).await?;
+/// }
+///
+/// realm.reinvite(old_friend, RejoinHistory::SinceRejoin).await?;
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PruneDocument {
+    /// Proposals keyed by ID.
+    pub proposals: BTreeMap<String, PruneProposal>,
+    /// Pruned members, including those re-invited since.
+    pub pruned: BTreeMap<MemberId, PrunedMember>,
+}
+
+impl PruneDocument {
+    /// Create an empty document.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a proposal. Returns `false` if its ID is taken.
+    pub fn propose(&mut self, proposal: PruneProposal) -> bool {
+        if self.proposals.contains_key(&proposal.id) {
+            return false;
+        }
+        self.proposals.insert(proposal.id.clone(), proposal);
+        true
+    }
+
+    /// Open proposals, oldest first.
+    pub fn open_proposals(&self) -> Vec<&PruneProposal> {
+        let mut open: Vec<_> = self.proposals.values().filter(|p| p.is_open()).collect();
+        open.sort_by_key(|p| p.proposed_at_millis);
+        open
+    }
+
+    /// Record a confirmed proposal and the members it pruned.
+    ///
+    /// Returns `false` if the proposal is unknown or no longer open.
+    pub fn confirm(
+        &mut self,
+        id: &str,
+        admin: MemberId,
+        pruned: Vec<InactiveMember>,
+        now_millis: u64,
+    ) -> bool {
+        let Some(proposal) = self.proposals.get_mut(id).filter(|p| p.is_open()) else {
+            return false;
+        };
+        let members = pruned.iter().map(|m| m.member).collect();
+        proposal.confirmed = Some((admin, members));
+        proposal.updated_at_millis = now_millis;
+        for m in pruned {
+            self.pruned.insert(
+                m.member,
+                PrunedMember {
+                    member: m.member,
+                    proposal: id.to_string(),
+                    pruned_at_millis: now_millis,
+                    last_active_millis: m.last_active_millis,
+                    rejoin: None,
+                    updated_at_millis: now_millis,
+                },
+            );
+        }
+        true
+    }
+
+    /// Cancel an open proposal. Returns `false` if it is not open.
+    pub fn cancel(&mut self, id: &str, admin: MemberId, now_millis: u64) -> bool {
+        let Some(proposal) = self.proposals.get_mut(id).filter(|p| p.is_open()) else {
+            return false;
+        };
+        proposal.cancelled_by = Some(admin);
+        proposal.updated_at_millis = now_millis;
+        true
+    }
+
+    /// Record that a pruned member was re-invited.
+    ///
+    /// Returns `false` if they were not pruned or already rejoined.
+    pub fn rejoin(&mut self, member: &MemberId, rejoin: Rejoin) -> bool {
+        let Some(record) = self.pruned.get_mut(member).filter(|r| r.rejoin.is_none()) else {
+            return false;
+        };
+        record.updated_at_millis = rejoin.at_millis;
+        record.rejoin = Some(rejoin);
+        true
+    }
+
+    /// Whether a member is pruned and not re-invited.
+    pub fn is_pruned(&self, member: &MemberId) -> bool {
+        self.pruned.get(member).is_some_and(|r| r.rejoin.is_none())
+    }
+
+    /// Earliest time (milliseconds since epoch) whose history a member
+    /// may see, or `None` for all of it.
+    pub fn history_visible_from(&self, member: &MemberId) -> Option<u64> {
+        let rejoin = self.pruned.get(member)?.rejoin?;
+        (rejoin.history == RejoinHistory::SinceRejoin).then_some(rejoin.at_millis)
+    }
+}
+
+impl DocumentSchema for PruneDocument {
+    /// Per-proposal and per-member LWW merge.
+    fn merge(&mut self, remote: Self) {
+        for (id, proposal) in remote.proposals {
+            match self.proposals.get(&id) {
+                Some(local) if local.updated_at_millis >= proposal.updated_at_millis => {}
+                _ => {
+                    self.proposals.insert(id, proposal);
+                }
+            }
+        }
+        for (member, record) in remote.pruned {
+            match self.pruned.get(&member) {
+                Some(local) if local.updated_at_millis >= record.updated_at_millis => {}
+                _ => {
+                    self.pruned.insert(member, record);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADMIN: MemberId = [1u8; 32];
+    const OTHER_ADMIN: MemberId = [2u8; 32];
+    const IDLE: MemberId = [3u8; 32];
+
+    fn proposal(id: &str) -> PruneProposal {
+        PruneProposal {
+            id: id.to_string(),
+            proposed_by: ADMIN,
+            members: BTreeSet::from([IDLE]),
+            proposed_at_millis: 100,
+            confirm_after_millis: 200,
+            confirmed: None,
+            cancelled_by: None,
+            updated_at_millis: 100,
+        }
+    }
+
+    fn idle() -> InactiveMember {
+        InactiveMember {
+            member: IDLE,
+            last_active_millis: Some(5),
+        }
+    }
+
+    #[test]
+    fn test_find_inactive() {
+        let active = |m: &MemberId| match *m {
+            ADMIN => Some(500),
+            IDLE => Some(5),
+            _ => None,
+        };
+        let found = find_inactive([ADMIN, OTHER_ADMIN, IDLE], active, 100);
+        let members: Vec<MemberId> = found.iter().map(|m| m.member).collect();
+        assert_eq!(members, vec![OTHER_ADMIN, IDLE]);
+        assert_eq!(found[0].last_active_millis, None);
+    }
+
+    #[test]
+    fn test_confirmation_rules() {
+        let p = proposal("p1");
+        // Not before the grace period ends
+        assert!(!p.can_confirm(&OTHER_ADMIN, 2, 199));
+        // Another admin is needed when there is one
+        assert!(!p.can_confirm(&ADMIN, 2, 200));
+        assert!(p.can_confirm(&OTHER_ADMIN, 2, 200));
+        // A sole admin confirms their own proposal
+        assert!(p.can_confirm(&ADMIN, 1, 200));
+
+        let mut doc = PruneDocument::new();
+        assert!(doc.propose(p.clone()));
+        assert!(!doc.propose(p));
+        assert!(doc.confirm("p1", OTHER_ADMIN, vec![idle()], 250));
+        assert!(!doc.confirm("p1", OTHER_ADMIN, vec![idle()], 260));
+        assert!(!doc.cancel("p1", ADMIN, 270));
+        assert!(doc.is_pruned(&IDLE));
+        assert!(doc.open_proposals().is_empty());
+    }
+
+    #[test]
+    fn test_rejoin_history() {
+        let mut doc = PruneDocument::new();
+        doc.propose(proposal("p1"));
+        doc.confirm("p1", ADMIN, vec![idle()], 250);
+
+        let rejoin = Rejoin {
+            by: ADMIN,
+            at_millis: 400,
+            history: RejoinHistory::SinceRejoin,
+        };
+        assert!(!doc.rejoin(&ADMIN, rejoin));
+        assert!(doc.rejoin(&IDLE, rejoin));
+        assert!(!doc.rejoin(&IDLE, rejoin));
+        assert!(!doc.is_pruned(&IDLE));
+        assert_eq!(doc.history_visible_from(&IDLE), Some(400));
+        assert_eq!(doc.history_visible_from(&ADMIN), None);
+    }
+
+    #[test]
+    fn test_merge_keeps_latest() {
+        let mut local = PruneDocument::new();
+        local.propose(proposal("p1"));
+        local.propose(proposal("p2"));
+
+        let mut remote = local.clone();
+        remote.cancel("p1", OTHER_ADMIN, 300);
+        local.confirm("p2", OTHER_ADMIN, vec![idle()], 250);
+
+        local.merge(remote.clone());
+        remote.merge(local.clone());
+        for doc in [&local, &remote] {
+            assert_eq!(doc.proposals["p1"].cancelled_by, Some(OTHER_ADMIN));
+            assert!(doc.proposals["p2"].confirmed.is_some());
+            assert!(doc.is_pruned(&IDLE));
+        }
+    }
+}
@@ -3,32 +3,90 @@
 //! A Realm wraps an N-peer interface and provides a high-level API
 //! for messaging, documents, and artifact sharing.
 
-use crate::artifact::{ArtifactDownload, ArtifactId, DownloadProgress};
-use crate::document::Document;
+use crate::announcement::{
+    Announcement, AnnouncementDocument, AnnouncementId, AnnouncementReminder, AnnouncementSummary,
+    ANNOUNCEMENT_DOC, MAX_ANNOUNCEMENT_TITLE_LEN,
+};
+use crate::anonymity::{AnonymityRingDocument, RingEntry, ANONYMITY_RING_DOC};
+use crate::calendar::{
+    CalendarDocument, CalendarEntry, CalendarEntryId, CalendarExporter, CALENDAR_DOC,
+    MAX_CALENDAR_TITLE_LEN,
+};
+use crate::archive::{ArchiveChange, ArchiveDocument, ARCHIVE_DOC};
+use crate::artifact::{dm_story_id, ArtifactDownload, ArtifactId, DownloadProgress};
+use crate::artifact_sync::{artifact_interface_id, artifact_key_seed};
+use crate::contact_invite::{ContactInvite, ContactInviteMessage};
+use crate::document::{Document, DocumentSchema};
+use crate::document_budget::{self, DocumentSplit, DocumentStats};
+use crate::document_registry::{DocumentEntry, DocumentEvent, DocumentRegistryDocument};
 use crate::error::{IndraError, Result};
+use crate::event_feed::{ControlCommand, EventFeedWriter, EventLine};
+use crate::guest_access::{GuestAccessDocument, GuestGrant, DAY_MILLIS, GUEST_ACCESS_DOC};
 use crate::invite::InviteCode;
-use crate::member::{Member, MemberEvent, MemberId, MemberInfo};
+use crate::key_rotation::{
+    key_hash, KeyRotation, KeyRotationDocument, KeyShare, RotatedKey, RotationReason,
+    SignedKeyRotation, SignedKeyShare, KEY_ROTATION_DOC,
+};
+use crate::key_transparency::{
+    KeyAudit, KeyEndorsement, KeyLogDocument, SignedKeyEntry, KEY_LOG_DOC,
+};
+use crate::link_preview::{
+    extract_urls, LinkPreview, PreviewImage, MAX_PREVIEWS_PER_MESSAGE, MAX_PREVIEW_IMAGE_SIZE,
+};
+use crate::legal_hold::{HeldMessage, HoldEvent, HoldExport, LegalHoldDocument, LEGAL_HOLD_DOC};
+use crate::moderation::{
+    moderate, ContentFilters, ModeratedMessage, ModerationDocument, MODERATION_DOC,
+};
+use crate::member::{Member, MemberEvent, MemberId, MemberInfo, MemberSyncStatus};
+use crate::membership_log::{
+    EntryHash, MembershipAction, MembershipLogDocument, MembershipRecord, MembershipState,
+    OwnershipChange, SignedMembershipEntry, MEMBERSHIP_LOG_DOC, OWNER_HEARTBEAT_MILLIS,
+};
+use crate::mirror::{PublishListDocument, PUBLISH_DOC};
 use crate::message::{Content, ContentReference, Message, MessageId, MessagePayload};
 use crate::network::RealmId;
+use crate::profile::{ProfileDocument, PROFILE_DOC};
+use crate::pruning::{
+    find_inactive, InactiveMember, PruneDocument, PruneProposal, PrunedMember, Rejoin,
+    RejoinHistory, DEFAULT_PRUNE_GRACE_MILLIS, PRUNE_DOC,
+};
+use crate::security_info::{
+    invite_history, MemberSecurity, PqVerification, RealmKeyInfo, RealmSecurityInfo,
+    TrustRootInfo,
+};
+use crate::snapshot::RealmSnapshot;
+use crate::sticker_pack::{
+    PackEntry, StickerKind, StickerPackDocument, StickerRef, MAX_STICKER_SIZE, STICKER_PACK_DOC,
+};
+use crate::tombstone::{
+    SyncHorizonDocument, TombstoneCompaction, DEFAULT_TOMBSTONE_GRACE_MILLIS, SYNC_HORIZON_DOC,
+};
 use crate::access::AccessMode;
-use crate::artifact_index::HomeArtifactEntry;
+use crate::artifact_index::{GeoLocation, HomeArtifactEntry};
+use crate::geo::GeoPrecision;
 use crate::home_realm::HomeRealm;
 use crate::stream::broadcast_to_stream;
-use crate::util::guess_mime_type;
+use crate::util::{guess_mime_type, now_millis};
 
 use futures::Stream;
 use indras_core::{InterfaceEvent, MembershipChange, PeerIdentity};
-use indras_node::{IndrasNode, ReceivedEvent};
+use indras_crypto::PQPublicIdentity;
+use indras_logging::metrics;
+use indras_node::{AnonymousPost, IndrasNode, ReceivedEvent, TrustPolicy, ANONYMOUS_EVENT_TYPE};
 use indras_storage::ContentRef;
 use indras_transport::{IrohIdentity, PeerEvent};
 use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::watch;
 use tokio::sync::OnceCell;
 use crate::system_event::SystemEvent;
-use crate::chat_message::{RealmChatDocument, EditableChatMessage, EditableMessageType, ChatMessageId};
-use tracing::debug;
+use crate::time_series::{
+    Bucket, RetentionPolicy, SeriesKey, Tier, TimeSeriesDocument, TIME_SERIES_DOC,
+};
+use crate::chat_message::{RealmChatDocument, EditableChatMessage, EditableMessageType, ChatMessageId, PollTally, MAX_INLINE_IMAGE_SIZE};
+use tracing::{debug, Instrument};
 
 /// A collaborative realm.
 ///
@@ -120,6 +178,14 @@ impl Realm {
         self.name.as_deref()
     }
 
+    /// Tracing span that scopes log output to this realm.
+    ///
+    /// Carries the realm's `interface_id` and name, so the realm can be
+    /// singled out in logs or silenced with `indras_logging::mute_interface`.
+    pub fn log_span(&self) -> tracing::Span {
+        indras_logging::interface_span(self.id, self.name())
+    }
+
     /// Get the artifact ID for this realm (if known).
     pub fn artifact_id(&self) -> Option<&ArtifactId> {
         self.artifact_id.as_ref()
@@ -132,6 +198,11 @@ impl Realm {
         self.invite.as_ref()
     }
 
+    /// Get our member ID.
+    pub fn member_id(&self) -> MemberId {
+        Member::new(*self.node.identity()).id()
+    }
+
     // ============================================================
     // Messaging
     // ============================================================
@@ -153,12 +224,20 @@ impl Realm {
     /// ```
     pub async fn send(&self, content: impl Into<Content>) -> Result<MessageId> {
         let content = content.into();
-        let payload = MessagePayload::new(content);
-        let bytes = serialize_payload(&payload)?;
+        async move {
+            let payload = MessagePayload::new(content);
+            let bytes = serialize_payload(&payload)?;
 
-        let event_id = self.node.send_message(&self.id, bytes).await?;
+            let event_id = self.node.send_message(&self.id, bytes).await?;
+            metrics::increment(
+                metrics::names::MESSAGES_SENT,
+                &[metrics::KeyValue::new(metrics::attrs::INTERFACE, self.id.to_string())],
+            );
 
-        Ok(MessageId::new(self.id, event_id))
+            Ok(MessageId::new(self.id, event_id))
+        }
+        .instrument(self.log_span())
+        .await
     }
 
     /// Send a reply to another message.
@@ -173,12 +252,102 @@ impl Realm {
         content: impl Into<Content>,
     ) -> Result<MessageId> {
         let content = content.into();
-        let payload = MessagePayload::reply(content, reply_to);
-        let bytes = serialize_payload(&payload)?;
+        async move {
+            let payload = MessagePayload::reply(content, reply_to);
+            let bytes = serialize_payload(&payload)?;
 
-        let event_id = self.node.send_message(&self.id, bytes).await?;
+            let event_id = self.node.send_message(&self.id, bytes).await?;
+            metrics::increment(
+                metrics::names::MESSAGES_SENT,
+                &[metrics::KeyValue::new(metrics::attrs::INTERFACE, self.id.to_string())],
+            );
 
-        Ok(MessageId::new(self.id, event_id))
+            Ok(MessageId::new(self.id, event_id))
+        }
+        .instrument(self.log_span())
+        .await
+    }
+
+    /// Send a message tagged with a coarse location.
+    ///
+    /// Only the geohash cell at `precision` is sent, never the exact
+    /// coordinates.
+    pub async fn send_geotagged(
+        &self,
+        content: impl Into<Content>,
+        location: &GeoLocation,
+        precision: GeoPrecision,
+    ) -> Result<MessageId> {
+        let content = content.into();
+        async move {
+            let payload = MessagePayload::new(content).with_geotag(location.geohash(precision));
+            let bytes = serialize_payload(&payload)?;
+
+            let event_id = self.node.send_message(&self.id, bytes).await?;
+            metrics::increment(
+                metrics::names::MESSAGES_SENT,
+                &[metrics::KeyValue::new(metrics::attrs::INTERFACE, self.id.to_string())],
+            );
+
+            Ok(MessageId::new(self.id, event_id))
+        }
+        .instrument(self.log_span())
+        .await
+    }
+
+    /// Invite an existing contact to this realm over our DM channel.
+    ///
+    /// The invite key is ML-KEM-wrapped to the contact's encapsulation key
+    /// (learned when we connected), so only they can use it. The contact
+    /// receives an `InviteEvent::Received` and can accept with one call.
+    /// Requires an established DM realm with the contact.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// realm.invite_contact(contact_id).await?;
+    /// ```
+    pub async fn invite_contact(&self, contact: MemberId) -> Result<()> {
+        let code = self.invite_code_for(contact).await?;
+
+        let my_id = Member::new(*self.node.identity()).id();
+        let invite = ContactInviteMessage::Invite(ContactInvite {
+            realm_id: self.id,
+            realm_name: self.name.clone(),
+            inviter: my_id,
+            invite: code.to_uri(),
+            sent_at_millis: now_millis(),
+        });
+
+        let dm_realm_id = artifact_interface_id(&dm_story_id(my_id, contact));
+        let bytes = serialize_payload(&MessagePayload::new(invite.to_content()))?;
+        self.node.send_message(&dm_realm_id, bytes).await?;
+        Ok(())
+    }
+
+    /// Build an invite whose key is ML-KEM-wrapped to one peer.
+    ///
+    /// Requires the peer's encapsulation key to be in the peer registry.
+    async fn invite_code_for(&self, peer: MemberId) -> Result<InviteCode> {
+        let identity = member_identity(&peer)?;
+
+        let encapsulation_key = self
+            .node
+            .storage()
+            .peer_registry()
+            .get(&identity)?
+            .and_then(|record| record.pq_encapsulation_key)
+            .ok_or_else(|| {
+                IndraError::InvalidOperation("contact has no known ML-KEM key".to_string())
+            })?;
+        let encapsulation_key = indras_crypto::PQEncapsulationKey::from_bytes(&encapsulation_key)
+            .map_err(|e| IndraError::Crypto(e.to_string()))?;
+
+        let key = self.node.create_invite_for(&self.id, &encapsulation_key).await?;
+        Ok(match &self.artifact_id {
+            Some(artifact_id) => InviteCode::new_with_artifact(key, artifact_id.clone()),
+            None => InviteCode::new(key),
+        })
     }
 
     /// Get a stream of incoming messages.
@@ -236,10 +405,64 @@ impl Realm {
         .await
     }
 
+    /// Enable or disable causal delivery for messages sent to this realm.
+    ///
+    /// With causal delivery on, each outgoing message carries the messages
+    /// it causally follows, and members hold it back until those have been
+    /// delivered — a reply never shows up before its parent. This trades a
+    /// little latency for ordering, so it is off by default.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// realm.set_causal_delivery(true);
+    /// let parent = realm.send("Question?").await?;
+    /// realm.reply(parent, "Answer.").await?;
+    /// ```
+    pub fn set_causal_delivery(&self, enabled: bool) {
+        self.node.set_causal_delivery(&self.id, enabled);
+    }
+
+    /// Whether causal delivery is enabled for this realm.
+    pub fn causal_delivery(&self) -> bool {
+        self.node.causal_delivery_enabled(&self.id)
+    }
+
+    /// Require members of this realm to hold a certificate from an
+    /// organization root.
+    ///
+    /// Messages from members without a valid [`MemberCertificate`] signed
+    /// by `root_vk` are flagged or dropped, depending on `policy`. Install
+    /// this device's own certificate with
+    /// [`IndrasNetwork::set_member_certificate`](crate::IndrasNetwork::set_member_certificate)
+    /// so other members can verify it. The setting is local to this device.
+    ///
+    /// [`MemberCertificate`]: crate::MemberCertificate
+    pub fn set_trust_root(&self, root_vk: PQPublicIdentity, policy: TrustPolicy) {
+        self.node.set_trust_root(&self.id, root_vk, policy);
+    }
+
+    /// Stop checking members against an organization root.
+    pub fn clear_trust_root(&self) -> bool {
+        self.node.clear_trust_root(&self.id)
+    }
+
+    /// User ids of members seen without a valid organization certificate.
+    pub fn unverified_members(&self) -> Vec<[u8; 32]> {
+        self.node.unverified_members(&self.id)
+    }
+
     /// Get messages since a specific sequence number.
+    ///
+    /// After a re-invite with [`RejoinHistory::SinceRejoin`], messages from
+    /// before the rejoin are left out (see
+    /// [`history_visible_from`](Self::history_visible_from)); the same goes
+    /// for [`all_messages`](Self::all_messages) and
+    /// [`search_messages`](Self::search_messages).
     pub async fn messages_since(&self, since: u64) -> Result<Vec<Message>> {
         let events = self.node.events_since(&self.id, since).await?;
         let realm_id = self.id;
+        let visible_from = self.own_history_visible_from().await?;
 
         Ok(events
             .into_iter()
@@ -251,6 +474,7 @@ impl Realm {
                 };
                 convert_event_to_message(received, realm_id)
             })
+            .filter(|msg| is_visible(msg, visible_from))
             .collect())
     }
 
@@ -262,6 +486,7 @@ impl Realm {
     pub async fn all_messages(&self) -> Result<Vec<Message>> {
         let events = self.node.document_events(&self.id).await?;
         let realm_id = self.id;
+        let visible_from = self.own_history_visible_from().await?;
 
         Ok(events
             .into_iter()
@@ -272,9 +497,71 @@ impl Realm {
                 };
                 convert_event_to_message(received, realm_id)
             })
+            .filter(|msg| is_visible(msg, visible_from))
+            .collect())
+    }
+
+    /// Render the realm's messages as JSONL, one [`EventLine`] per message.
+    ///
+    /// To keep a file current, use [`follow_event_feed`](Self::follow_event_feed).
+    pub async fn event_log(&self) -> Result<String> {
+        Ok(self
+            .all_messages()
+            .await?
+            .iter()
+            .map(|m| EventLine::from_message(m).to_jsonl())
             .collect())
     }
 
+    /// Write the realm's messages to a JSONL file at `path` and append new
+    /// ones as they arrive, so the file can be followed with `tail -f`.
+    ///
+    /// The file is rewritten from the realm's history first. Runs until the
+    /// realm's event stream ends or a write fails.
+    pub async fn follow_event_feed(&self, path: &Path) -> Result<()> {
+        // Subscribe before reading history so nothing falls in between
+        let mut live = std::pin::pin!(self.messages());
+        let history = self.all_messages().await?;
+        let mut seen: BTreeSet<MessageId> = history.iter().map(|m| m.id).collect();
+        let mut feed = EventFeedWriter::create(path, &history).await?;
+
+        use futures::StreamExt;
+        while let Some(message) = live.next().await {
+            if seen.remove(&message.id) {
+                continue;
+            }
+            feed.append(&message).await?;
+        }
+        Ok(())
+    }
+
+    /// Carry out a control command, returning the message it sent.
+    ///
+    /// [`ControlCommand::Other`] is rejected; layers that add verbs should
+    /// handle theirs before calling this.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// for line in control_input.lines() {
+    ///     realm.execute(ControlCommand::parse(line)?).await?;
+    /// }
+    /// ```
+    pub async fn execute(&self, command: ControlCommand) -> Result<MessageId> {
+        match command {
+            ControlCommand::Send(text) => self.send(text).await,
+            ControlCommand::Reply { to, text } => {
+                self.reply(MessageId::new(self.id, to), text).await
+            }
+            ControlCommand::React { to, emoji } => {
+                self.react(MessageId::new(self.id, to), emoji).await
+            }
+            ControlCommand::Other { verb, .. } => Err(IndraError::InvalidOperation(format!(
+                "unknown command {verb:?}"
+            ))),
+        }
+    }
+
     /// Search messages by text content.
     ///
     /// Performs case-insensitive full-text search across all messages
@@ -296,6 +583,7 @@ impl Realm {
         let events = self.node.events_since(&self.id, 0).await?;
         let realm_id = self.id;
         let query_lower = query.to_lowercase();
+        let visible_from = self.own_history_visible_from().await?;
 
         Ok(events
             .into_iter()
@@ -306,6 +594,7 @@ impl Realm {
                 };
                 convert_event_to_message(received, realm_id)
             })
+            .filter(|msg| is_visible(msg, visible_from))
             .filter(|msg| {
                 if let Some(text) = msg.content.as_text() {
                     text.to_lowercase().contains(&query_lower)
@@ -316,6 +605,26 @@ impl Realm {
             .collect())
     }
 
+    /// Capture an immutable, point-in-time view of the realm.
+    ///
+    /// Messages, members, and documents read through the returned
+    /// [`RealmSnapshot`] all reflect the same CRDT state, even if sync
+    /// merges land while a UI is rendering. Take one snapshot per render
+    /// pass and read everything from it.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let snap = realm.snapshot().await?;
+    /// let messages = snap.messages();
+    /// let quests = snap.document::<QuestLog>("quests");
+    /// // `messages` and `quests` are guaranteed to agree with each other
+    /// ```
+    pub async fn snapshot(&self) -> Result<RealmSnapshot> {
+        let snapshot = self.node.document_snapshot(&self.id).await?;
+        Ok(RealmSnapshot::from_interface_snapshot(snapshot))
+    }
+
     // ============================================================
     // CRDT Chat
     // ============================================================
@@ -341,7 +650,9 @@ impl Realm {
             author.to_string(),
             text,
             now_millis(),
-        ).with_author_id(author_id);
+        )
+        .with_author_id(author_id)
+        .signed_by(self.node.pq_identity());
         doc.update(|chat| chat.add_message(msg)).await?;
         Ok(id)
     }
@@ -359,7 +670,9 @@ impl Realm {
             now_millis(),
             EditableMessageType::Text,
             parent_id.to_string(),
-        ).with_author_id(author_id);
+        )
+        .with_author_id(author_id)
+        .signed_by(self.node.pq_identity());
         doc.update(|chat| chat.add_message(msg)).await?;
         Ok(id)
     }
@@ -371,6 +684,277 @@ impl Realm {
         Ok(result)
     }
 
+    /// Remove a reaction via the CRDT chat document.
+    pub async fn chat_unreact(&self, author: &str, msg_id: &str, emoji: &str) -> Result<bool> {
+        let doc = self.chat_doc().await?;
+        let result = doc.transaction(|chat| chat.remove_reaction(msg_id, author, emoji)).await?;
+        Ok(result)
+    }
+
+    /// Edit one of our own messages in the CRDT chat document.
+    ///
+    /// Returns `false` if the message is not ours, is deleted, or is unchanged.
+    pub async fn chat_edit(&self, msg_id: &str, new_content: String) -> Result<bool> {
+        let doc = self.chat_doc().await?;
+        let member_id = hex::encode(self.node.identity().as_bytes());
+        let now = now_millis();
+        let result = doc
+            .transaction(|chat| chat.edit_message(msg_id, &member_id, new_content, now))
+            .await?;
+        Ok(result)
+    }
+
+    /// Delete one of our own messages from the CRDT chat document.
+    ///
+    /// Returns `false` if the message is not ours or already deleted.
+    pub async fn chat_delete(&self, msg_id: &str) -> Result<bool> {
+        let doc = self.chat_doc().await?;
+        let member_id = hex::encode(self.node.identity().as_bytes());
+        let now = now_millis();
+        let result = doc
+            .transaction(|chat| chat.delete_message(msg_id, &member_id, now))
+            .await?;
+        Ok(result)
+    }
+
+    /// Send an image via the CRDT chat document.
+    ///
+    /// Images up to [`MAX_INLINE_IMAGE_SIZE`] are embedded as base64; larger
+    /// ones are stored as a blob and referenced by hash.
+    pub async fn chat_send_image(
+        &self,
+        author: &str,
+        data: Vec<u8>,
+        mime_type: impl Into<String>,
+        filename: Option<String>,
+    ) -> Result<ChatMessageId> {
+        use base64::Engine;
+
+        let (inline_data, artifact_hash) = if data.len() <= MAX_INLINE_IMAGE_SIZE {
+            (Some(base64::engine::general_purpose::STANDARD.encode(&data)), None)
+        } else {
+            self.node
+                .storage()
+                .store_blob(&data)
+                .await
+                .map_err(|e| IndraError::Artifact(format!("Failed to store image: {}", e)))?;
+            (None, Some(blake3::hash(&data).to_hex().to_string()))
+        };
+
+        let doc = self.chat_doc().await?;
+        let id = generate_chat_id();
+        let author_id = hex::encode(self.node.identity().as_bytes());
+        let msg = EditableChatMessage::new_image(
+            id.clone(),
+            hex::encode(self.id.as_bytes()),
+            author.to_string(),
+            now_millis(),
+            mime_type.into(),
+            inline_data,
+            artifact_hash,
+            filename,
+            None,
+            None,
+        )
+        .with_author_id(author_id)
+        .signed_by(self.node.pq_identity());
+        doc.update(|chat| chat.add_message(msg)).await?;
+        Ok(id)
+    }
+
+    /// Send a poll via the CRDT chat document.
+    ///
+    /// Needs at least two non-empty options; `closes_at` (milliseconds
+    /// since epoch), if given, must be in the future.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let poll = realm
+    ///     .chat_send_poll("Alice", "Lunch?".into(), vec!["Pizza".into(), "Tacos".into()], false, None)
+    ///     .await?;
+    /// realm.chat_vote(&poll, [1]).await?;
+    ///
+    /// let mut results = std::pin::pin!(realm.poll_results(&poll).await?);
+    /// while let Some(tally) = results.next().await {
+    ///     println!("{} voted so far", tally.voters);
+    /// }
+    /// ```
+    pub async fn chat_send_poll(
+        &self,
+        author: &str,
+        question: String,
+        options: Vec<String>,
+        multiple: bool,
+        closes_at: Option<u64>,
+    ) -> Result<ChatMessageId> {
+        if options.len() < 2 || options.iter().any(|o| o.trim().is_empty()) {
+            return Err(IndraError::InvalidOperation(
+                "a poll needs at least two non-empty options".to_string(),
+            ));
+        }
+        let now = now_millis();
+        if closes_at.is_some_and(|at| at <= now) {
+            return Err(IndraError::InvalidOperation(
+                "poll close time is in the past".to_string(),
+            ));
+        }
+
+        let doc = self.chat_doc().await?;
+        let id = generate_chat_id();
+        let author_id = hex::encode(self.node.identity().as_bytes());
+        let msg = EditableChatMessage::new_poll(
+            id.clone(),
+            hex::encode(self.id.as_bytes()),
+            author.to_string(),
+            now,
+            question,
+            options,
+            multiple,
+            closes_at,
+        )
+        .with_author_id(author_id)
+        .signed_by(self.node.pq_identity());
+        doc.update(|chat| chat.add_message(msg)).await?;
+        Ok(id)
+    }
+
+    /// Vote on a poll, replacing any earlier vote of ours.
+    ///
+    /// Pass no choices to withdraw the vote. Returns `false` if the poll
+    /// is closed or the choices are not valid for it.
+    pub async fn chat_vote(
+        &self,
+        msg_id: &str,
+        choices: impl IntoIterator<Item = usize>,
+    ) -> Result<bool> {
+        let doc = self.chat_doc().await?;
+        let voter = self.node.secret_key();
+        let choices = choices.into_iter().collect();
+        let now = now_millis();
+        let result = doc
+            .transaction(|chat| chat.cast_vote(msg_id, voter, choices, now))
+            .await?;
+        Ok(result)
+    }
+
+    /// Current results of a poll.
+    pub async fn poll_tally(&self, msg_id: &str) -> Result<PollTally> {
+        let doc = self.chat_doc().await?;
+        doc.read()
+            .await
+            .get_message(msg_id)
+            .and_then(|msg| msg.poll_tally(now_millis()))
+            .ok_or_else(|| IndraError::InvalidOperation(format!("no poll {msg_id} in this realm")))
+    }
+
+    /// Stream of a poll's results, updated live as votes arrive.
+    ///
+    /// Yields the current tally first, then a new one whenever the tally
+    /// changes, including once when voting closes.
+    pub async fn poll_results(
+        &self,
+        msg_id: &str,
+    ) -> Result<impl Stream<Item = PollTally> + Send + 'static> {
+        let rx = self.chat_doc().await?.subscribe();
+        let mut last = self.poll_tally(msg_id).await?;
+        let msg_id = msg_id.to_string();
+
+        Ok(async_stream::stream! {
+            use futures::StreamExt;
+            let mut changes = broadcast_to_stream(rx);
+            yield last.clone();
+            loop {
+                let close_in = last
+                    .closes_at
+                    .filter(|_| !last.closed)
+                    .map(|at| std::time::Duration::from_millis(at.saturating_sub(now_millis())));
+                let tally = tokio::select! {
+                    change = changes.next() => {
+                        let Some(change) = change else { break };
+                        change
+                            .new_state
+                            .get_message(&msg_id)
+                            .and_then(|msg| msg.poll_tally(now_millis()))
+                    }
+                    _ = tokio::time::sleep(close_in.unwrap_or_default()), if close_in.is_some() => {
+                        Some(PollTally { closed: true, ..last.clone() })
+                    }
+                };
+                if let Some(tally) = tally
+                    && tally != last
+                {
+                    last = tally;
+                    yield last.clone();
+                }
+            }
+        })
+    }
+
+    // ============================================================
+    // Tombstone Compaction
+    // ============================================================
+
+    /// Record our sync horizon and compute the realm's compaction horizon.
+    ///
+    /// Publishes "synced through now" for this node, then returns the
+    /// oldest horizon across all current members minus
+    /// [`DEFAULT_TOMBSTONE_GRACE_MILLIS`]. Returns `None` while any member
+    /// has not yet reported, in which case nothing should be pruned.
+    pub async fn compaction_horizon(&self) -> Result<Option<u64>> {
+        let horizons = Document::<SyncHorizonDocument>::new(
+            self.id,
+            SYNC_HORIZON_DOC.to_string(),
+            Arc::clone(&self.node),
+        )
+        .await?;
+
+        let my_id = Member::new(*self.node.identity()).id();
+        let now = now_millis();
+        horizons.update(|d| d.record(my_id, now)).await?;
+
+        let members: Vec<MemberId> = self.member_list().await?.iter().map(|m| m.id()).collect();
+        let horizon = horizons
+            .read()
+            .await
+            .compaction_horizon(&members, DEFAULT_TOMBSTONE_GRACE_MILLIS);
+        Ok(horizon)
+    }
+
+    /// Compact tombstones in a named document against the realm's horizon.
+    ///
+    /// Returns the number of tombstones removed (0 if some member has not
+    /// yet reported a horizon).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let removed = realm.compact_document::<IntentionDocument>("intentions").await?;
+    /// ```
+    pub async fn compact_document<T: TombstoneCompaction>(&self, name: &str) -> Result<usize> {
+        let Some(horizon) = self.compaction_horizon().await? else {
+            return Ok(0);
+        };
+        let doc = Document::<T>::new(self.id, name.to_string(), Arc::clone(&self.node)).await?;
+        doc.compact_tombstones(horizon).await
+    }
+
+    /// Compact deleted messages in the realm's CRDT chat document.
+    ///
+    /// Messages under a legal hold are kept. Called periodically by the
+    /// network; safe to call manually.
+    /// Returns the number of tombstones removed.
+    pub async fn compact_tombstones(&self) -> Result<usize> {
+        let Some(horizon) = self.compaction_horizon().await? else {
+            return Ok(0);
+        };
+        let held = self.legal_hold().await?.read().await.held_ids();
+        self.chat_doc()
+            .await?
+            .compact_with(|chat| chat.compact_tombstones_except(horizon, &held))
+            .await
+    }
+
     // ============================================================
     // Read Tracking
     // ============================================================
@@ -426,233 +1010,2220 @@ impl Realm {
     pub async fn unread_count(&self, member: &MemberId) -> Result<usize> {
         use crate::read_tracker::ReadTrackerDocument;
 
-        let doc = self.document::<ReadTrackerDocument>("read_tracker").await?;
-        let last_read = doc.read().await.last_read_seq(member);
+        let doc = self.document::<ReadTrackerDocument>("read_tracker").await?;
+        let last_read = doc.read().await.last_read_seq(member);
+
+        // Use all_messages() to include CRDT-synced messages from remote peers
+        let total = self.all_messages().await?.len() as u64;
+
+        Ok(total.saturating_sub(last_read) as usize)
+    }
+
+    /// Get the sequence number of the last message read by a member.
+    ///
+    /// Returns 0 if the member has never marked the realm as read.
+    pub async fn last_read_seq(&self, member: &MemberId) -> Result<u64> {
+        use crate::read_tracker::ReadTrackerDocument;
+
+        let doc = self.document::<ReadTrackerDocument>("read_tracker").await?;
+        Ok(doc.read().await.last_read_seq(member))
+    }
+
+    // ============================================================
+    // Members
+    // ============================================================
+
+    /// Get a stream of member events (joins, leaves, updates).
+    ///
+    /// This stream includes both CRDT-based membership changes and
+    /// gossip-based discovery events. Use this to react to members
+    /// joining or leaving the realm in real-time.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut events = realm.member_events();
+    /// while let Some(event) = events.next().await {
+    ///     match event {
+    ///         MemberEvent::Joined(member) => println!("{} joined", member.name()),
+    ///         MemberEvent::Left(member) => println!("{} left", member.name()),
+    ///         MemberEvent::Discovered(info) => println!("Discovered {} via gossip", info.member.name()),
+    ///         _ => {}
+    ///     }
+    /// }
+    /// ```
+    pub fn member_events(&self) -> impl Stream<Item = MemberEvent> + Send + '_ {
+        let rx = self.node.events(&self.id).ok();
+
+        async_stream::stream! {
+            if let Some(rx) = rx {
+                let mut stream = broadcast_to_stream(rx);
+                use futures::StreamExt;
+
+                while let Some(event) = stream.next().await {
+                    if let Some(member_event) = convert_event_to_member_event(event) {
+                        yield member_event;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Get a stream of member events (alias for member_events).
+    #[deprecated(since = "0.1.0", note = "Use member_events() instead")]
+    pub fn members(&self) -> impl Stream<Item = MemberEvent> + Send + '_ {
+        self.member_events()
+    }
+
+    /// Get the current list of realm members.
+    ///
+    /// Returns all known members including those discovered via gossip.
+    /// Use `member_list_with_info()` to get PQ keys for secure communication.
+    pub async fn member_list(&self) -> Result<Vec<Member>> {
+        let identities: Vec<IrohIdentity> = self.node.members(&self.id).await?;
+
+        Ok(identities.into_iter().map(Member::new).collect())
+    }
+
+    /// Get realm members with extended info including PQ keys.
+    ///
+    /// Returns member information discovered via gossip, including
+    /// post-quantum cryptographic keys for secure direct communication.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let members = realm.member_list_with_info().await?;
+    /// for info in members {
+    ///     println!("{}: has PQ keys = {}", info.member.name(), info.has_pq_keys());
+    /// }
+    /// ```
+    pub async fn member_list_with_info(&self) -> Result<Vec<MemberInfo>> {
+        let peer_infos = self.node.members_with_info(&self.id).await?;
+
+        Ok(peer_infos
+            .into_iter()
+            .map(MemberInfo::from_realm_peer_info)
+            .collect())
+    }
+
+    /// How up to date each other member of the realm is.
+    ///
+    /// For member lists: per member, whether we are connected, when we
+    /// last synced, the measured round-trip time, and how many of our
+    /// changes they have yet to receive.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// for status in realm.member_sync_status().await? {
+    ///     let rtt = status.round_trip.map(|d| d.as_millis());
+    ///     println!("{}: {:?} ms, {} behind", status.member.name(), rtt, status.changes_behind);
+    /// }
+    /// ```
+    pub async fn member_sync_status(&self) -> Result<Vec<MemberSyncStatus>> {
+        let statuses = self.node.member_sync_status(&self.id).await?;
+        Ok(statuses.into_iter().map(MemberSyncStatus::from).collect())
+    }
+
+    /// Get the number of members in the realm.
+    pub async fn member_count(&self) -> Result<usize> {
+        Ok(self.node.members(&self.id).await?.len())
+    }
+
+    // ============================================================
+    // Presence
+    // ============================================================
+
+    /// Get the list of currently online members.
+    ///
+    /// Returns members whose presence status is not `Offline`.
+    /// This is based on gossip discovery - members visible through
+    /// the gossip layer are considered online.
+    pub async fn online_members(&self) -> Result<Vec<Member>> {
+        // Members visible through the gossip layer are online
+        let identities: Vec<IrohIdentity> = self.node.members(&self.id).await?;
+
+        Ok(identities
+            .into_iter()
+            .map(|id| {
+                let mut member = Member::new(id);
+                member.set_presence(indras_core::PresenceStatus::Online);
+                member
+            })
+            .collect())
+    }
+
+    /// Check if a specific member is currently reachable (online).
+    ///
+    /// Returns true if the member is visible in the gossip layer.
+    pub async fn is_member_online(&self, member_id: &MemberId) -> Result<bool> {
+        let members = self.node.members(&self.id).await?;
+        Ok(members.iter().any(|id| {
+            let mut bytes = [0u8; 32];
+            let id_bytes = id.as_bytes();
+            bytes.copy_from_slice(&id_bytes[..32.min(id_bytes.len())]);
+            &bytes == member_id
+        }))
+    }
+
+    // ============================================================
+    // Documents
+    // ============================================================
+
+    /// Get or create a typed document in this realm.
+    ///
+    /// Documents are CRDT-backed data structures that automatically
+    /// synchronize across all realm members.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The document type (must implement `DocumentSchema`)
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Unique name for the document within this realm
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// #[derive(Default, Clone, Serialize, Deserialize, DocumentSchema)]
+    /// struct QuestLog {
+    ///     quests: Vec<Quest>,
+    /// }
+    ///
+    /// let doc = realm.document::<QuestLog>("quests").await?;
+    ///
+    /// // Read current state
+    /// let quests = doc.read();
+    ///
+    /// // Make changes (auto-synced)
+    /// doc.update(|q| q.quests.push(Quest::new("Defeat dragon"))).await?;
+    /// ```
+    pub async fn document<T: DocumentSchema>(
+        &self,
+        name: &str,
+    ) -> Result<Document<T>> {
+        async move {
+            // Auto-register the document name and type (skip internal documents)
+            if !name.starts_with('_') {
+                let registry = self.document_registry().await?;
+                let type_tag = T::type_tag();
+                let known = registry.read().await.type_of(name).is_some();
+                if !known {
+                    let name_owned = name.to_string();
+                    registry
+                        .update(|d| {
+                            d.register_typed(name_owned, type_tag);
+                        })
+                        .await?;
+                }
+            }
+
+            Document::new(self.id, name.to_string(), Arc::clone(&self.node)).await
+        }
+        .instrument(self.log_span())
+        .await
+    }
+
+    /// The realm's registry of named documents.
+    async fn document_registry(&self) -> Result<Document<DocumentRegistryDocument>> {
+        Document::new(
+            self.id,
+            crate::realm_restructure::REGISTRY_DOC.to_string(),
+            Arc::clone(&self.node),
+        )
+        .await
+    }
+
+    /// List all named documents in this realm.
+    ///
+    /// Returns the names of documents that have been opened via `document()`.
+    /// Internal documents (prefixed with `_`) are excluded.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let names = realm.document_names().await?;
+    /// for name in names {
+    ///     println!("Document: {}", name);
+    /// }
+    /// ```
+    pub async fn document_names(&self) -> Result<Vec<String>> {
+        let registry = self.document_registry().await?;
+        let guard = registry.read().await;
+        Ok(guard.document_names().into_iter().map(String::from).collect())
+    }
+
+    /// List all named documents in this realm with their type tags.
+    ///
+    /// The tag is the one recorded when the document was first opened
+    /// (see `DocumentSchema::type_tag`); documents registered before types
+    /// were tracked have none until they are next opened.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// for entry in realm.documents().await? {
+    ///     println!("{} ({})", entry.name, entry.type_tag.as_deref().unwrap_or("?"));
+    /// }
+    /// ```
+    pub async fn documents(&self) -> Result<Vec<DocumentEntry>> {
+        Ok(self.document_registry().await?.read().await.entries())
+    }
+
+    /// Names of the documents in this realm created as type `T`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// for name in realm.documents_of::<QuestDocument>().await? {
+    ///     let quests = realm.document::<QuestDocument>(&name).await?;
+    /// }
+    /// ```
+    pub async fn documents_of<T: DocumentSchema>(&self) -> Result<Vec<String>> {
+        let registry = self.document_registry().await?;
+        let guard = registry.read().await;
+        Ok(guard
+            .names_of_type(T::type_tag())
+            .into_iter()
+            .map(String::from)
+            .collect())
+    }
+
+    /// Check if a named document exists in this realm.
+    pub async fn has_document(&self, name: &str) -> Result<bool> {
+        Ok(self.document_registry().await?.read().await.contains(name))
+    }
+
+    /// Remove a named document from this realm's registry.
+    ///
+    /// The document stops being listed for all members. Its contents stay
+    /// in the realm's history, and opening it again via `document()`
+    /// registers it anew. Returns `false` if it was not registered.
+    pub async fn remove_document(&self, name: &str) -> Result<bool> {
+        let registry = self.document_registry().await?;
+        if !registry.read().await.contains(name) {
+            return Ok(false);
+        }
+        let name = name.to_string();
+        registry
+            .update(|d| {
+                d.unregister(&name);
+            })
+            .await?;
+        Ok(true)
+    }
+
+    /// Size and change count of this realm's documents, largest first.
+    ///
+    /// Covers documents written or synced since this process started; see
+    /// [`DocumentBudgets`](crate::DocumentBudgets) for the alert thresholds.
+    pub fn document_stats(&self) -> Vec<DocumentStats> {
+        document_budget::budgets().realm_stats(&self.id)
+    }
+
+    /// Move part of a document into an archive document.
+    ///
+    /// `split` is called with the document and the archive and moves
+    /// whatever should be archived (e.g. chat messages older than a year)
+    /// from one to the other. It runs twice: once on a copy to fill the
+    /// archive, then on the live document to remove the moved part, so it
+    /// must select the same entries both times. The archive is written
+    /// first, so an interrupted split leaves entries duplicated rather than
+    /// lost. The archive is linked from the realm's document registry (see
+    /// [`document_archives`](Self::document_archives)) and the document's
+    /// change budget restarts.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let cutoff = now_millis() - YEAR_MILLIS;
+    /// let split = realm
+    ///     .split_document::<NoteLog, NoteLog, _>("notes", "notes-2024", |live, archive| {
+    ///         let (old, recent): (Vec<_>, Vec<_>) =
+    ///             live.notes.drain(..).partition(|n| n.created_at < cutoff);
+    ///         live.notes = recent;
+    ///         archive.notes.extend(old);
+    ///     })
+    ///     .await?;
+    /// println!("notes: {} -> {} bytes", split.bytes_before, split.bytes_after);
+    /// ```
+    pub async fn split_document<T, A, F>(
+        &self,
+        name: &str,
+        archive: &str,
+        split: F,
+    ) -> Result<DocumentSplit>
+    where
+        T: DocumentSchema,
+        A: DocumentSchema,
+        F: Fn(&mut T, &mut A),
+    {
+        if archive == name || archive.starts_with('_') {
+            return Err(IndraError::InvalidOperation(format!(
+                "cannot split '{name}' into '{archive}'"
+            )));
+        }
+        let live = self.document::<T>(name).await?;
+        let archived = self.document::<A>(archive).await?;
+
+        let snapshot = live.read().await.clone();
+        let bytes_before = postcard::to_allocvec(&snapshot)?.len();
+        archived
+            .update(|a| {
+                let mut copy = snapshot;
+                split(&mut copy, a);
+            })
+            .await?;
+        live.update(|t| split(t, &mut A::default())).await?;
+
+        let registry = self.document_registry().await?;
+        let (name_owned, archive_owned) = (name.to_string(), archive.to_string());
+        registry
+            .update(|d| {
+                d.link_archive(&name_owned, archive_owned);
+            })
+            .await?;
+        document_budget::budgets().reset_changes(&self.id, name);
+
+        Ok(DocumentSplit {
+            archive: archive.to_string(),
+            bytes_before,
+            bytes_after: postcard::to_allocvec(&*live.read().await)?.len(),
+            archive_bytes: postcard::to_allocvec(&*archived.read().await)?.len(),
+        })
+    }
+
+    /// Archive documents split off a document with
+    /// [`split_document`](Self::split_document).
+    pub async fn document_archives(&self, name: &str) -> Result<Vec<String>> {
+        let registry = self.document_registry().await?;
+        let guard = registry.read().await;
+        Ok(guard.archives_of(name).into_iter().map(String::from).collect())
+    }
+
+    /// Stream of documents created in or removed from this realm, locally
+    /// or by other members.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut events = std::pin::pin!(realm.document_events().await?);
+    /// while let Some(event) = events.next().await {
+    ///     match event {
+    ///         DocumentEvent::Created { entry } => browser.add(entry),
+    ///         DocumentEvent::Removed { name } => browser.remove(&name),
+    ///     }
+    /// }
+    /// ```
+    pub async fn document_events(
+        &self,
+    ) -> Result<impl Stream<Item = DocumentEvent> + Send + 'static> {
+        let registry = self.document_registry().await?;
+        let mut rx = registry.subscribe();
+        let mut last = registry.read().await.clone();
+
+        Ok(async_stream::stream! {
+            // Hold the document so its change channel stays open.
+            let _registry = registry;
+            loop {
+                match rx.recv().await {
+                    Ok(change) => {
+                        for event in DocumentEvent::diff(&last, &change.new_state) {
+                            yield event;
+                        }
+                        last = change.new_state;
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    // ============================================================
+    // Guest Access
+    // ============================================================
+
+    /// Get the guest access document for this realm.
+    pub async fn guest_access(&self) -> Result<Document<GuestAccessDocument>> {
+        Document::new(self.id, GUEST_ACCESS_DOC.to_string(), Arc::clone(&self.node)).await
+    }
+
+    /// Invite an outside peer as a read-only guest for `days` days.
+    ///
+    /// The guest may read the named documents; anything it writes is
+    /// dropped by members, and members stop syncing to it once the grant
+    /// lapses. The invite key is ML-KEM-wrapped to the guest, so the
+    /// guest's encapsulation key must already be known (e.g. from a prior
+    /// connection).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let invite = realm.create_guest_invite(reviewer_id, ["design-notes"], 7).await?;
+    /// send_out_of_band(invite.to_uri());
+    /// ```
+    pub async fn create_guest_invite<I, S>(
+        &self,
+        guest: MemberId,
+        documents: I,
+        days: u32,
+    ) -> Result<InviteCode>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let documents: BTreeSet<String> = documents.into_iter().map(Into::into).collect();
+        if documents.is_empty() {
+            return Err(IndraError::InvalidOperation(
+                "guest invite must name at least one document".to_string(),
+            ));
+        }
+        if days == 0 {
+            return Err(IndraError::InvalidOperation(
+                "guest invite must last at least one day".to_string(),
+            ));
+        }
+
+        let code = self.invite_code_for(guest).await?;
+        let now = now_millis();
+        let expires_at = now.saturating_add(u64::from(days) * DAY_MILLIS);
+        let my_id = Member::new(*self.node.identity()).id();
+
+        let doc = self.guest_access().await?;
+        let scope = doc
+            .try_update(|d| Ok(d.grant(guest, documents, expires_at, my_id, now).scope()))
+            .await?;
+        self.node.grant_guest(&self.id, member_identity(&guest)?, expires_at);
+
+        Ok(code.with_guest_scope(scope))
+    }
+
+    /// List all guest grants for this realm, including lapsed ones.
+    pub async fn guests(&self) -> Result<Vec<GuestGrant>> {
+        let doc = self.guest_access().await?;
+        let guard = doc.read().await;
+        Ok(guard.grants.values().cloned().collect())
+    }
+
+    /// Revoke a guest's access before it lapses.
+    ///
+    /// On an admin's node this also rotates the realm key right away;
+    /// otherwise an admin's node does on its next guest enforcement pass
+    /// (see [`rotate_key_after_guests`](Self::rotate_key_after_guests)).
+    /// Returns `false` if the peer had no live grant.
+    pub async fn revoke_guest(&self, guest: MemberId) -> Result<bool> {
+        let doc = self.guest_access().await?;
+        let now = now_millis();
+        let revoked = doc.try_update(|d| Ok(d.revoke(&guest, now))).await?;
+        if revoked {
+            self.node.grant_guest(&self.id, member_identity(&guest)?, 0);
+            self.rotate_key_after_guests().await?;
+        }
+        Ok(revoked)
+    }
+
+    /// Apply this realm's guest grants to the local node.
+    ///
+    /// Called periodically by the network so grants made or revoked by
+    /// other members take effect here. Returns the number of active guests.
+    pub async fn enforce_guest_access(&self) -> Result<usize> {
+        let doc = self.guest_access().await?;
+        let now = now_millis();
+        let guard = doc.read().await;
+        let mut active = 0;
+        for grant in guard.grants.values() {
+            let Ok(identity) = member_identity(&grant.guest()) else {
+                continue;
+            };
+            if grant.is_active(now) {
+                active += 1;
+                self.node.grant_guest(&self.id, identity, grant.expires_at_millis());
+            } else {
+                self.node.grant_guest(&self.id, identity, 0);
+            }
+        }
+        Ok(active)
+    }
+
+    /// Rotate the realm key away from guests whose access ended while
+    /// they held it. Admin only; other members get an empty list.
+    ///
+    /// Called periodically by the network. A guest holds the realm key
+    /// like a member, so once its grant lapses or is revoked after the
+    /// current key was established, the key is rotated (see
+    /// [`rotate_key`](Self::rotate_key)) and wrapped only to members and
+    /// the remaining guests. Returns the guests rotated away from.
+    pub async fn rotate_key_after_guests(&self) -> Result<Vec<MemberId>> {
+        let my_id = self.member_id();
+        let membership = self.verified_membership().await?;
+        if !membership.is_admin(&my_id) {
+            return Ok(Vec::new());
+        }
+        let established = self
+            .key_rotations()
+            .await?
+            .read()
+            .await
+            .current(&self.id, &membership)
+            .map_or(0, |s| s.rotation.at_millis);
+        let now = now_millis();
+        let lapsed: Vec<MemberId> = self
+            .guest_access()
+            .await?
+            .read()
+            .await
+            .grants
+            .values()
+            .filter(|g| g.ended_at_millis(now).is_some_and(|t| t > established))
+            .map(GuestGrant::guest)
+            .collect();
+        if !lapsed.is_empty() {
+            self.rotate_key(RotationReason::GuestsLapsed(lapsed.clone())).await?;
+        }
+        Ok(lapsed)
+    }
+
+    // ============================================================
+    // Profiles
+    // ============================================================
+
+    /// Get the realm's member profile document.
+    ///
+    /// The network publishes and reads profiles here automatically; see
+    /// [`IndrasNetwork::set_profile`](crate::IndrasNetwork::set_profile).
+    pub async fn profiles(&self) -> Result<Document<ProfileDocument>> {
+        Document::new(self.id, PROFILE_DOC.to_string(), Arc::clone(&self.node)).await
+    }
+
+    // ============================================================
+    // Anonymous Posting
+    // ============================================================
+
+    /// Get the realm's anonymity ring document.
+    pub async fn anonymity_ring(&self) -> Result<Document<AnonymityRingDocument>> {
+        Document::new(self.id, ANONYMITY_RING_DOC.to_string(), Arc::clone(&self.node)).await
+    }
+
+    /// Publish this member's ring key so it can post anonymously.
+    ///
+    /// Joining also widens everyone else's anonymity set, so members may
+    /// join without ever posting. Returns `false` if already joined.
+    pub async fn join_anonymity_ring(&self) -> Result<bool> {
+        let my_id = Member::new(*self.node.identity()).id();
+        let key = self.node.anonymity_ring_key(&self.id);
+        let entry = RingEntry::sign(self.id, my_id, key, self.node.pq_identity());
+        let joined = self
+            .anonymity_ring()
+            .await?
+            .try_update(|d| Ok(d.join(my_id, entry)))
+            .await?;
+        self.apply_anonymity_ring().await?;
+        Ok(joined)
+    }
+
+    /// Send a message without revealing which ring member wrote it.
+    ///
+    /// Requires having joined the ring, at least one other member in it,
+    /// and a connected member to relay the post. Members see the message
+    /// from the realm's shared anonymous sender; the relaying member can
+    /// tell which peer it came from (see [`crate::anonymity`]).
+    pub async fn send_anonymously(&self, content: impl Into<Content>) -> Result<MessageId> {
+        let my_id = Member::new(*self.node.identity()).id();
+        let membership = self.verified_membership().await?;
+        let ring = self.anonymity_ring().await?;
+        if !ring.read().await.contains(&my_id, &self.id, &membership) {
+            return Err(IndraError::InvalidOperation(
+                "join the anonymity ring before posting anonymously".to_string(),
+            ));
+        }
+        self.apply_anonymity_ring().await?;
+
+        let bytes = serialize_payload(&MessagePayload::new(content.into()))?;
+        let event_id = self.node.send_anonymous(&self.id, bytes).await?;
+        Ok(MessageId::new(self.id, event_id))
+    }
+
+    /// Apply this realm's anonymity ring to the local node.
+    ///
+    /// Called periodically by the network so keys published by other
+    /// members are accepted here. Only keys signed by a current member of
+    /// the membership log count. Returns the ring size.
+    pub async fn apply_anonymity_ring(&self) -> Result<usize> {
+        let membership = self.verified_membership().await?;
+        let ring = self
+            .anonymity_ring()
+            .await?
+            .read()
+            .await
+            .ring(&self.id, &membership);
+        let size = ring.len();
+        self.node.set_anonymity_ring(&self.id, ring);
+        Ok(size)
+    }
+
+    // ============================================================
+    // Membership Log
+    // ============================================================
+
+    /// Get the realm's signed membership log.
+    pub async fn membership_log(&self) -> Result<Document<MembershipLogDocument>> {
+        Document::new(self.id, MEMBERSHIP_LOG_DOC.to_string(), Arc::clone(&self.node)).await
+    }
+
+    /// Record a member joining, signed by this node.
+    ///
+    /// Only admins may add admins. The entry pins the member's ML-DSA
+    /// verifying key, so it must already be in the peer registry. Fails if
+    /// the log has no entries yet: only the realm's creator starts it, when
+    /// the realm is created. Returns `false` if `member` already was a
+    /// member.
+    pub async fn record_member_added(&self, member: MemberId, admin: bool) -> Result<bool> {
+        let key = self
+            .node
+            .storage()
+            .peer_registry()
+            .get(&member_identity(&member)?)?
+            .and_then(|record| record.pq_verifying_key)
+            .ok_or_else(|| {
+                IndraError::InvalidOperation("member has no known ML-DSA key".to_string())
+            })?;
+        self.record_membership(MembershipAction::Add { member, admin, key }).await
+    }
+
+    /// Record a member leaving (or being removed), signed by this node.
+    ///
+    /// Members may remove themselves; only admins may remove others.
+    /// Returns `false` if `member` was not a member.
+    pub async fn record_member_removed(&self, member: MemberId) -> Result<bool> {
+        self.record_membership(MembershipAction::Remove { member }).await
+    }
+
+    /// Membership according to the signed log.
+    pub async fn verified_membership(&self) -> Result<MembershipState> {
+        Ok(self.membership_log().await?.read().await.state())
+    }
+
+    /// Audit the membership log: every entry in replay order, marked
+    /// accepted or rejected.
+    pub async fn membership_history(&self) -> Result<Vec<MembershipRecord>> {
+        Ok(self.membership_log().await?.read().await.history())
+    }
+
+    // ============================================================
+    // Ownership
+    // ============================================================
+
+    /// The realm's owner according to the membership log.
+    pub async fn owner(&self) -> Result<Option<MemberId>> {
+        Ok(self.verified_membership().await?.owner)
+    }
+
+    /// Hand ownership to another member. Owner only.
+    ///
+    /// The handoff is signed and recorded in the membership log, and `to`
+    /// becomes an admin. Returns `false` if `to` already is the owner.
+    pub async fn transfer_ownership(&self, to: MemberId) -> Result<bool> {
+        self.record_membership(MembershipAction::TransferOwnership { to }).await
+    }
+
+    /// Set who succeeds the owner, in order, after `inactive_days` without
+    /// any entry from them. Owner only.
+    ///
+    /// With an empty list the other admins are the successors. Returns
+    /// `false` if the plan is unchanged.
+    pub async fn set_succession(
+        &self,
+        successors: Vec<MemberId>,
+        inactive_days: u32,
+    ) -> Result<bool> {
+        self.record_membership(MembershipAction::SetSuccession {
+            successors,
+            inactive_days,
+        })
+        .await
+    }
+
+    /// Keep ownership current.
+    ///
+    /// As owner, records a heartbeat once a day. As a successor, claims
+    /// ownership once the owner has been silent for the succession period
+    /// or has left. Called periodically by the network; returns whether
+    /// this node became the owner.
+    pub async fn maintain_ownership(&self) -> Result<bool> {
+        let my_id = self.member_id();
+        let state = self.verified_membership().await?;
+        let now = now_millis();
+        if state.is_owner(&my_id) {
+            if now.saturating_sub(state.owner_active_millis) >= OWNER_HEARTBEAT_MILLIS {
+                self.record_membership(MembershipAction::OwnerHeartbeat).await?;
+            }
+            return Ok(false);
+        }
+        match state.claim_allowed_at(&my_id) {
+            Some(at) if now >= at => self.record_membership(MembershipAction::ClaimOwnership).await,
+            _ => Ok(false),
+        }
+    }
+
+    /// Stream of ownership changes as they reach this node, by handoff or
+    /// succession. Changes already in the log are not repeated.
+    pub async fn ownership_events(
+        &self,
+    ) -> Result<impl Stream<Item = OwnershipChange> + Send + 'static> {
+        use tokio::sync::broadcast::error::RecvError;
+
+        let log = self.membership_log().await?;
+        let mut rx = log.subscribe();
+        let mut seen: BTreeSet<EntryHash> = log
+            .read()
+            .await
+            .ownership_changes()
+            .into_iter()
+            .map(|c| c.hash)
+            .collect();
+
+        Ok(async_stream::stream! {
+            loop {
+                let changes = match rx.recv().await {
+                    Ok(change) => change.new_state.ownership_changes(),
+                    Err(RecvError::Lagged(_)) => log.read().await.ownership_changes(),
+                    Err(RecvError::Closed) => break,
+                };
+                for change in changes {
+                    if seen.insert(change.hash) {
+                        yield change;
+                    }
+                }
+            }
+        })
+    }
+
+    // ============================================================
+    // Key Transparency
+    // ============================================================
+
+    /// Get the realm's key transparency log.
+    pub async fn key_log(&self) -> Result<Document<KeyLogDocument>> {
+        Document::new(self.id, KEY_LOG_DOC.to_string(), Arc::clone(&self.node)).await
+    }
+
+    /// Publish this node's verifying key to the key log.
+    ///
+    /// A member's first key is trusted if it is the one their `Create` or
+    /// `Add` entry in the membership log pinned. A different key is flagged
+    /// to every member until it is cross-signed by the old key or endorsed
+    /// by a majority of the other admins (see
+    /// [`endorse_key_change`](Self::endorse_key_change)). The log is
+    /// informational: membership entries are still checked against the
+    /// pinned key only. Returns `false` if the key already is this member's
+    /// latest entry.
+    pub async fn publish_key(&self) -> Result<bool> {
+        let my_id = self.member_id();
+        let identity = self.node.pq_identity();
+        let key = identity.verifying_key_bytes();
+        let now = now_millis();
+        self.key_log()
+            .await?
+            .try_update(|d| {
+                if d.latest(&my_id).is_some_and(|s| s.entry.verifying_key == key) {
+                    return Ok(false);
+                }
+                let entry = d.next_entry(my_id, key, now);
+                Ok(d.append(SignedKeyEntry::sign(entry, identity)))
+            })
+            .await
+    }
+
+    /// Endorse a member's key change as an admin.
+    ///
+    /// Returns `false` if this node already endorsed the entry.
+    pub async fn endorse_key_change(&self, entry: EntryHash) -> Result<bool> {
+        let my_id = self.member_id();
+        require_admin(&self.verified_membership().await?.admins, &my_id)?;
+        let endorsement = KeyEndorsement::sign(my_id, &entry, self.node.pq_identity());
+        self.key_log()
+            .await?
+            .try_update(|d| {
+                if !d.entries.contains_key(&entry) {
+                    return Err(IndraError::InvalidOperation(
+                        "unknown key log entry".to_string(),
+                    ));
+                }
+                Ok(d.endorse(&entry, endorsement))
+            })
+            .await
+    }
+
+    /// Audit the key log against the signed membership log.
+    ///
+    /// [`KeyAudit::flagged`] lists key changes members should be warned
+    /// about.
+    pub async fn key_audit(&self) -> Result<KeyAudit> {
+        let membership = self.verified_membership().await?;
+        Ok(self.key_log().await?.read().await.audit(&membership))
+    }
+
+    // ============================================================
+    // Key Rotation
+    // ============================================================
+
+    /// Get the realm's key rotation document.
+    pub async fn key_rotations(&self) -> Result<Document<KeyRotationDocument>> {
+        Document::new(self.id, KEY_ROTATION_DOC.to_string(), Arc::clone(&self.node)).await
+    }
+
+    /// Replace the realm key with a random one. Admin only.
+    ///
+    /// The new key is wrapped to every member and active guest whose
+    /// ML-KEM key is known. The rest are returned in
+    /// [`RotatedKey::unreachable`]; an admin wraps the key for them once
+    /// their key turns up (see [`apply_key_rotation`](Self::apply_key_rotation)).
+    /// Events sent from now on use the new key.
+    pub async fn rotate_key(&self, reason: RotationReason) -> Result<RotatedKey> {
+        let my_id = self.member_id();
+        let membership = self.verified_membership().await?;
+        require_admin(&membership.admins, &my_id)?;
+
+        let key = indras_crypto::InterfaceKey::generate(self.id);
+        let doc = self.key_rotations().await?;
+        let epoch = doc.read().await.next_epoch(&self.id, &membership);
+        let rotation = KeyRotation {
+            realm: self.id,
+            epoch,
+            by: my_id,
+            reason,
+            key_hash: key_hash(&key),
+            at_millis: now_millis(),
+        };
+        let signed = SignedKeyRotation::sign(rotation, self.node.pq_identity());
+        let recipients = self.key_recipients(&membership).await?;
+        let (shares, unreachable) = self.wrap_key(&signed.hash(), &key, recipients)?;
+        doc.update(|d| {
+            d.add_rotation(signed);
+            for share in shares {
+                d.add_share(share);
+            }
+        })
+        .await?;
+        self.node.rotate_interface_key(&self.id, key)?;
+        Ok(RotatedKey { epoch, unreachable })
+    }
+
+    /// Adopt the realm's current key, and as an admin wrap it for those
+    /// who lack it.
+    ///
+    /// Called periodically by the network. A node switches to the current
+    /// key once it finds a share wrapped to it, and never back to an older
+    /// one. An admin holding the current key wraps it for members and
+    /// active guests without a share, such as those who joined after the
+    /// rotation. Returns `true` if this node adopted a new key.
+    pub async fn apply_key_rotation(&self) -> Result<bool> {
+        let my_id = self.member_id();
+        let membership = self.verified_membership().await?;
+        let doc = self.key_rotations().await?;
+        let Some(current) = doc.read().await.current(&self.id, &membership).cloned() else {
+            return Ok(false);
+        };
+        let rotation = current.hash();
+        let expected = current.rotation.key_hash;
+
+        let mut adopted = false;
+        let held = self.node.interface_key(&self.id).map(|k| key_hash(&k));
+        if held != Some(expected) {
+            let key = {
+                let guard = doc.read().await;
+                let newer = held.is_none_or(|h| guard.epoch_of(&h) <= current.rotation.epoch);
+                if newer {
+                    guard
+                        .shares_for(&rotation, &my_id, &membership)
+                        .find_map(|s| s.open(self.node.pq_kem_keypair(), &expected))
+                } else {
+                    None
+                }
+            };
+            let Some(key) = key else {
+                return Ok(false);
+            };
+            self.node.rotate_interface_key(&self.id, key)?;
+            adopted = true;
+        }
+
+        if !membership.is_admin(&my_id) {
+            return Ok(adopted);
+        }
+        let recipients = self.key_recipients(&membership).await?;
+        let missing: Vec<MemberId> = {
+            let guard = doc.read().await;
+            recipients
+                .into_iter()
+                .filter(|r| guard.shares_for(&rotation, r, &membership).next().is_none())
+                .collect()
+        };
+        let Some(key) = self.node.interface_key(&self.id) else {
+            return Ok(adopted);
+        };
+        let (shares, _) = self.wrap_key(&rotation, &key, missing)?;
+        if !shares.is_empty() {
+            doc.update(|d| {
+                for share in shares {
+                    d.add_share(share);
+                }
+            })
+            .await?;
+        }
+        Ok(adopted)
+    }
+
+    /// Members and active guests a realm key is wrapped to.
+    async fn key_recipients(&self, membership: &MembershipState) -> Result<BTreeSet<MemberId>> {
+        let now = now_millis();
+        let guests = self.guest_access().await?;
+        let guard = guests.read().await;
+        Ok(membership
+            .members
+            .iter()
+            .copied()
+            .chain(guard.active(now).map(|g| g.guest()))
+            .collect())
+    }
+
+    /// Wrap `key` to each recipient whose ML-KEM key is known, signed by
+    /// this node. Returns the shares and the recipients left out.
+    fn wrap_key(
+        &self,
+        rotation: &EntryHash,
+        key: &indras_crypto::InterfaceKey,
+        recipients: impl IntoIterator<Item = MemberId>,
+    ) -> Result<(Vec<SignedKeyShare>, Vec<MemberId>)> {
+        let my_id = self.member_id();
+        let mut shares = Vec::new();
+        let mut unreachable = Vec::new();
+        for recipient in recipients {
+            let encapsulation_key = if recipient == my_id {
+                Some(self.node.encapsulation_key())
+            } else {
+                self.known_encapsulation_key(&recipient)?
+            };
+            let share = encapsulation_key
+                .and_then(|ek| KeyShare::wrap(*rotation, recipient, my_id, key, &ek));
+            match share {
+                Some(share) => shares.push(SignedKeyShare::sign(share, self.node.pq_identity())),
+                None => unreachable.push(recipient),
+            }
+        }
+        Ok((shares, unreachable))
+    }
+
+    /// A peer's ML-KEM encapsulation key from the peer registry.
+    fn known_encapsulation_key(
+        &self,
+        member: &MemberId,
+    ) -> Result<Option<indras_crypto::PQEncapsulationKey>> {
+        let Ok(identity) = member_identity(member) else {
+            return Ok(None);
+        };
+        Ok(self
+            .node
+            .storage()
+            .peer_registry()
+            .get(&identity)?
+            .and_then(|record| record.pq_encapsulation_key)
+            .and_then(|bytes| indras_crypto::PQEncapsulationKey::from_bytes(&bytes).ok()))
+    }
+
+    /// Encryption and membership security summary for a details panel.
+    ///
+    /// Aggregates the realm key (fingerprint, epoch, whether it is derived
+    /// from the invite), each known member's PQ key, key log and
+    /// certificate checks against the signed membership log, and the
+    /// admission and guest invite history. See [`RealmSecurityInfo`].
+    pub async fn security_info(&self) -> Result<RealmSecurityInfo> {
+        let key = self.node.interface_key(&self.id);
+        let invite_derived = match (&key, &self.artifact_id) {
+            (Some(key), Some(artifact_id)) => {
+                let seed = artifact_key_seed(artifact_id);
+                indras_crypto::InterfaceKey::from_seed(&seed, self.id).as_bytes() == key.as_bytes()
+            }
+            _ => false,
+        };
+        let log = self.membership_log().await?;
+        let (state, history, invalid_log_entries) = {
+            let guard = log.read().await;
+            (guard.state(), guard.history(), guard.verify().len())
+        };
+
+        let (epoch, rotations) = {
+            let doc = self.key_rotations().await?;
+            let guard = doc.read().await;
+            let epoch = key.as_ref().map_or(0, |k| guard.epoch_of(&key_hash(k)));
+            let rotations: Vec<KeyRotation> = guard
+                .history(&self.id, &state)
+                .into_iter()
+                .map(|s| s.rotation.clone())
+                .collect();
+            (epoch, rotations)
+        };
+        let rotated_at = key.as_ref().and_then(|k| {
+            let hash = key_hash(k);
+            rotations.iter().find(|r| r.key_hash == hash).map(|r| r.at_millis)
+        });
+        let established_at_millis = rotated_at.or_else(|| {
+            self.node
+                .storage()
+                .interface_store()
+                .get(&self.id)
+                .ok()
+                .flatten()
+                .map(|record| record.created_at_millis.max(0) as u64)
+        });
+        let key = RealmKeyInfo {
+            encrypted: key.is_some(),
+            fingerprint: key.as_ref().map(|k| RealmKeyInfo::fingerprint_of(k.as_bytes())),
+            established_at_millis,
+            invite_derived,
+            epoch,
+            rotations,
+        };
+
+        let key_audit = self.key_log().await?.read().await.audit(&state);
+        let trust_root = self
+            .node
+            .trust_root(&self.id)
+            .map(|(org_id, policy)| TrustRootInfo { org_id, policy });
+
+        let me = self.member_id();
+        let members = self
+            .member_list_with_info()
+            .await?
+            .into_iter()
+            .filter(|info| info.member.id() != me)
+            .map(|info| {
+                let id = info.member.id();
+                let vk = info.pq_verifying_key.as_deref();
+                let pinned = state.keys.get(&id).map(Vec::as_slice);
+                MemberSecurity {
+                    has_pq_keys: info.has_pq_keys(),
+                    pq: PqVerification::check(&id, vk, pinned, &history),
+                    key_change: key_audit.latest_status(&id),
+                    trust: trust_root
+                        .and(vk)
+                        .map(|vk| self.node.member_trust(&self.id, vk)),
+                    in_signed_log: state.is_member(&id),
+                    admin: state.is_admin(&id),
+                    member: info.member,
+                }
+            })
+            .collect();
+
+        let guests = self.guests().await?;
+        Ok(RealmSecurityInfo {
+            realm_id: self.id,
+            key,
+            trust_root,
+            members,
+            invites: invite_history(&history, &guests),
+            invalid_log_entries,
+        })
+    }
+
+    async fn record_membership(&self, action: MembershipAction) -> Result<bool> {
+        let my_id = Member::new(*self.node.identity()).id();
+        let identity = self.node.pq_identity();
+        let now = now_millis();
+        self.membership_log()
+            .await?
+            .try_update(|d| {
+                // Writing the `Create` here would make any node that hasn't
+                // synced the log yet the realm's owner
+                if d.is_empty() {
+                    return Err(IndraError::InvalidOperation(
+                        "membership log has not been started".to_string(),
+                    ));
+                }
+                let state = d.state();
+                let unchanged = match &action {
+                    MembershipAction::Add { member, .. } => state.is_member(member),
+                    MembershipAction::Remove { member } => !state.is_member(member),
+                    MembershipAction::TransferOwnership { to } => state.is_owner(to),
+                    MembershipAction::SetSuccession {
+                        successors,
+                        inactive_days,
+                    } => {
+                        state.successors == *successors
+                            && state.inactive_days == Some(*inactive_days)
+                    }
+                    MembershipAction::ClaimOwnership => state.is_owner(&my_id),
+                    MembershipAction::Create | MembershipAction::OwnerHeartbeat => false,
+                };
+                if unchanged {
+                    return Ok(false);
+                }
+                let entry = d.next_entry(my_id, action, now);
+                if !d.append(SignedMembershipEntry::sign(entry, identity)) {
+                    return Err(IndraError::InvalidOperation(
+                        "not permitted to change membership".to_string(),
+                    ));
+                }
+                Ok(true)
+            })
+            .await
+    }
+
+    /// Start the membership log with this node as its creator and first
+    /// admin. Does nothing if the log already has entries.
+    pub(crate) async fn start_membership_log(&self) -> Result<()> {
+        let my_id = Member::new(*self.node.identity()).id();
+        let identity = self.node.pq_identity();
+        let now = now_millis();
+        self.membership_log()
+            .await?
+            .update(|d| {
+                if d.is_empty() {
+                    let create = d.next_entry(my_id, MembershipAction::Create, now);
+                    d.append(SignedMembershipEntry::sign(create, identity));
+                }
+            })
+            .await
+    }
+
+    // ============================================================
+    // Inactive Member Pruning
+    // ============================================================
+
+    /// Get the realm's prune proposals and pruned members.
+    pub async fn prune_document(&self) -> Result<Document<PruneDocument>> {
+        Document::new(self.id, PRUNE_DOC.to_string(), Arc::clone(&self.node)).await
+    }
+
+    /// Members with no activity in the last `inactive_days` days.
+    ///
+    /// A member's activity is the newest of their sync horizon, their
+    /// latest membership-log entry (or the one adding them), and the last
+    /// sync this node had with them. This node is never listed.
+    pub async fn inactive_members(&self, inactive_days: u32) -> Result<Vec<InactiveMember>> {
+        let cutoff = now_millis().saturating_sub(u64::from(inactive_days) * DAY_MILLIS);
+        self.inactive_since(cutoff).await
+    }
+
+    /// Propose pruning every member inactive for `inactive_days` days.
+    /// Admin only.
+    ///
+    /// The proposal may be confirmed once `grace_millis` (default
+    /// [`DEFAULT_PRUNE_GRACE_MILLIS`]) has passed. Returns the proposal ID,
+    /// or `None` if no member is inactive.
+    pub async fn propose_prune(
+        &self,
+        inactive_days: u32,
+        grace_millis: Option<u64>,
+    ) -> Result<Option<String>> {
+        let my_id = self.member_id();
+        require_admin(&self.verified_membership().await?.admins, &my_id)?;
+
+        let inactive = self.inactive_members(inactive_days).await?;
+        if inactive.is_empty() {
+            return Ok(None);
+        }
+        let now = now_millis();
+        let grace = grace_millis.unwrap_or(DEFAULT_PRUNE_GRACE_MILLIS);
+        let proposal = PruneProposal {
+            id: generate_chat_id(),
+            proposed_by: my_id,
+            members: inactive.iter().map(|m| m.member).collect(),
+            proposed_at_millis: now,
+            confirm_after_millis: now.saturating_add(grace),
+            confirmed: None,
+            cancelled_by: None,
+            updated_at_millis: now,
+        };
+        let id = proposal.id.clone();
+        self.prune_document()
+            .await?
+            .update(|d| {
+                d.propose(proposal);
+            })
+            .await?;
+        Ok(Some(id))
+    }
+
+    /// Confirm a prune proposal after its grace period. Admin only; with
+    /// several admins, not the one who proposed it.
+    ///
+    /// Members active since the proposal are spared. The rest are removed
+    /// from the membership log and the interface, and returned. The realm
+    /// key is then rotated (see [`rotate_key`](Self::rotate_key)), so they
+    /// can't read events sent after the prune with a key they kept.
+    pub async fn confirm_prune(&self, id: &str) -> Result<Vec<MemberId>> {
+        let my_id = self.member_id();
+        let admins = self.verified_membership().await?.admins;
+        require_admin(&admins, &my_id)?;
+
+        let doc = self.prune_document().await?;
+        let proposal = doc.read().await.proposals.get(id).cloned().ok_or_else(|| {
+            IndraError::InvalidOperation(format!("unknown prune proposal {id}"))
+        })?;
+        if !proposal.can_confirm(&my_id, admins.len(), now_millis()) {
+            return Err(IndraError::InvalidOperation(
+                "prune proposal is closed, still in its grace period, or needs another admin"
+                    .to_string(),
+            ));
+        }
+
+        let still_inactive: Vec<InactiveMember> = self
+            .inactive_since(proposal.proposed_at_millis)
+            .await?
+            .into_iter()
+            .filter(|m| proposal.members.contains(&m.member))
+            .collect();
+        for inactive in &still_inactive {
+            self.record_member_removed(inactive.member).await?;
+            if let Ok(identity) = member_identity(&inactive.member) {
+                self.node.remove_member(&self.id, &identity).await?;
+            }
+        }
+
+        let pruned: Vec<MemberId> = still_inactive.iter().map(|m| m.member).collect();
+        let now = now_millis();
+        doc.try_update(|d| Ok(d.confirm(id, my_id, still_inactive, now))).await?;
+        if !pruned.is_empty() {
+            self.rotate_key(RotationReason::Pruned(pruned.clone())).await?;
+        }
+        Ok(pruned)
+    }
+
+    /// Cancel an open prune proposal. Admin only.
+    ///
+    /// Returns `false` if the proposal is unknown or no longer open.
+    pub async fn cancel_prune(&self, id: &str) -> Result<bool> {
+        let my_id = self.member_id();
+        require_admin(&self.verified_membership().await?.admins, &my_id)?;
+        let now = now_millis();
+        self.prune_document()
+            .await?
+            .try_update(|d| Ok(d.cancel(id, my_id, now)))
+            .await
+    }
+
+    /// Open prune proposals, oldest first.
+    pub async fn pending_prunes(&self) -> Result<Vec<PruneProposal>> {
+        let doc = self.prune_document().await?;
+        let guard = doc.read().await;
+        Ok(guard.open_proposals().into_iter().cloned().collect())
+    }
+
+    /// Members pruned and not re-invited since.
+    pub async fn pruned_members(&self) -> Result<Vec<PrunedMember>> {
+        let doc = self.prune_document().await?;
+        let guard = doc.read().await;
+        Ok(guard
+            .pruned
+            .values()
+            .filter(|r| r.rejoin.is_none())
+            .cloned()
+            .collect())
+    }
+
+    /// Invite a pruned member back.
+    ///
+    /// Records them in the membership log, adds them to the interface and
+    /// sends the invite over the DM realm (see
+    /// [`invite_contact`](Self::invite_contact)). `history` decides whether
+    /// their node shows them the realm's full history again or only what
+    /// came after they rejoined; see
+    /// [`history_visible_from`](Self::history_visible_from).
+    pub async fn reinvite(&self, member: MemberId, history: RejoinHistory) -> Result<()> {
+        let doc = self.prune_document().await?;
+        if !doc.read().await.is_pruned(&member) {
+            return Err(IndraError::InvalidOperation(
+                "member was not pruned from this realm".to_string(),
+            ));
+        }
+
+        self.record_member_added(member, false).await?;
+        self.node.add_member(&self.id, member_identity(&member)?).await?;
+        self.invite_contact(member).await?;
+
+        let rejoin = Rejoin {
+            by: self.member_id(),
+            at_millis: now_millis(),
+            history,
+        };
+        doc.update(|d| {
+            d.rejoin(&member, rejoin);
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Earliest time (milliseconds since epoch) whose history `member`
+    /// should be shown, or `None` for all of it.
+    ///
+    /// Only set for members re-invited with [`RejoinHistory::SinceRejoin`].
+    /// The data itself still syncs to them; their node leaves older
+    /// messages out of [`all_messages`](Self::all_messages),
+    /// [`messages_since`](Self::messages_since) and
+    /// [`search_messages`](Self::search_messages).
+    pub async fn history_visible_from(&self, member: &MemberId) -> Result<Option<u64>> {
+        let doc = self.prune_document().await?;
+        let visible_from = doc.read().await.history_visible_from(member);
+        Ok(visible_from)
+    }
+
+    /// [`history_visible_from`](Self::history_visible_from) for this node.
+    async fn own_history_visible_from(&self) -> Result<Option<u64>> {
+        self.history_visible_from(&self.member_id()).await
+    }
+
+    async fn inactive_since(&self, cutoff_millis: u64) -> Result<Vec<InactiveMember>> {
+        let mut last_active: BTreeMap<MemberId, u64> = BTreeMap::new();
+        let mut seen = |member: MemberId, at: u64| {
+            let latest = last_active.entry(member).or_default();
+            *latest = (*latest).max(at);
+        };
+
+        let horizons = Document::<SyncHorizonDocument>::new(
+            self.id,
+            SYNC_HORIZON_DOC.to_string(),
+            Arc::clone(&self.node),
+        )
+        .await?;
+        for (member, at) in &horizons.read().await.horizons {
+            seen(*member, *at);
+        }
+
+        let log = self.membership_log().await?;
+        let (state, history) = {
+            let guard = log.read().await;
+            (guard.state(), guard.history())
+        };
+        for record in history.iter().filter(|r| r.accepted) {
+            let entry = &record.entry.entry;
+            seen(entry.actor, entry.at_millis);
+            if let MembershipAction::Add { member, .. } = &entry.action {
+                seen(*member, entry.at_millis);
+            }
+        }
+
+        for status in self.member_sync_status().await? {
+            if let Some(at) = status.last_sync_millis {
+                seen(status.member.id(), at);
+            }
+        }
+
+        let my_id = self.member_id();
+        let members = state.members.into_iter().filter(|m| *m != my_id);
+        Ok(find_inactive(members, |m| last_active.get(m).copied(), cutoff_millis))
+    }
+
+    // ============================================================
+    // Archiving
+    // ============================================================
+
+    /// Get the realm's archive document.
+    pub async fn archive_document(&self) -> Result<Document<ArchiveDocument>> {
+        Document::new(self.id, ARCHIVE_DOC.to_string(), Arc::clone(&self.node)).await
+    }
+
+    /// Freeze the realm as a read-only archive.
+    ///
+    /// Only admins of the membership log may archive. Afterwards every
+    /// local write to the realm fails with [`IndraError::RealmArchived`],
+    /// on this node and on every member's once the change syncs, while
+    /// incoming events keep syncing for late readers. Returns `false` if
+    /// the realm already was archived.
+    pub async fn archive(&self, reason: impl Into<String>) -> Result<bool> {
+        let my_id = Member::new(*self.node.identity()).id();
+        require_admin(&self.verified_membership().await?.admins, &my_id)?;
+        if self.is_archived() {
+            return Ok(false);
+        }
+
+        let change = ArchiveChange {
+            archived: true,
+            by: my_id,
+            reason: Some(reason.into()),
+            at_millis: now_millis(),
+        };
+        self.archive_document()
+            .await?
+            .update(|d| {
+                d.record(change);
+            })
+            .await?;
+        self.node.set_archived(&self.id, true);
+        Ok(true)
+    }
+
+    /// Reopen an archived realm for writes. Admins only.
+    ///
+    /// Returns `false` if the realm was not archived.
+    pub async fn unarchive(&self) -> Result<bool> {
+        let my_id = Member::new(*self.node.identity()).id();
+        require_admin(&self.verified_membership().await?.admins, &my_id)?;
+        if !self.is_archived() {
+            return Ok(false);
+        }
+
+        let change = ArchiveChange {
+            archived: false,
+            by: my_id,
+            reason: None,
+            at_millis: now_millis(),
+        };
+        // Lift the local freeze first so the archive document can be written
+        self.node.set_archived(&self.id, false);
+        let updated = self
+            .archive_document()
+            .await?
+            .update(|d| {
+                d.record(change);
+            })
+            .await;
+        if let Err(e) = updated {
+            self.node.set_archived(&self.id, true);
+            return Err(e);
+        }
+        Ok(true)
+    }
+
+    /// Whether the realm is archived (read-only) on this node.
+    pub fn is_archived(&self) -> bool {
+        self.node.is_archived(&self.id)
+    }
+
+    /// Apply this realm's archive document to the local node.
+    ///
+    /// Called periodically by the network so archives and unarchives made
+    /// by other admins take effect here. Changes by members who are not
+    /// admins are ignored. Returns whether the realm is archived.
+    pub async fn apply_archive_state(&self) -> Result<bool> {
+        let admins = self.verified_membership().await?.admins;
+        let archived = self
+            .archive_document()
+            .await?
+            .read()
+            .await
+            .is_archived(|m| admins.contains(m));
+        self.node.set_archived(&self.id, archived);
+        Ok(archived)
+    }
+
+    // ============================================================
+    // Announcements
+    // ============================================================
+
+    /// Get the realm's announcement document.
+    pub async fn announcements_document(&self) -> Result<Document<AnnouncementDocument>> {
+        Document::new(self.id, ANNOUNCEMENT_DOC.to_string(), Arc::clone(&self.node)).await
+    }
+
+    /// Post an announcement every member is asked to acknowledge. Admins only.
+    ///
+    /// `publish_at_millis` schedules it for later (default: now);
+    /// `expires_at_millis` stops it being shown after that time.
+    pub async fn post_announcement(
+        &self,
+        title: impl Into<String>,
+        body: impl Into<String>,
+        publish_at_millis: Option<u64>,
+        expires_at_millis: Option<u64>,
+    ) -> Result<AnnouncementId> {
+        let my_id = Member::new(*self.node.identity()).id();
+        require_admin(&self.verified_membership().await?.admins, &my_id)?;
+
+        let title = title.into();
+        if title.len() > MAX_ANNOUNCEMENT_TITLE_LEN {
+            return Err(IndraError::InvalidOperation(format!(
+                "announcement title longer than {MAX_ANNOUNCEMENT_TITLE_LEN} bytes"
+            )));
+        }
+        let now = now_millis();
+        let publish_at = publish_at_millis.unwrap_or(now);
+        if expires_at_millis.is_some_and(|e| e <= publish_at) {
+            return Err(IndraError::InvalidOperation(
+                "announcement must expire after it is published".to_string(),
+            ));
+        }
+
+        let announcement = Announcement {
+            id: generate_chat_id(),
+            author: my_id,
+            title,
+            body: body.into(),
+            posted_at_millis: now,
+            publish_at_millis: publish_at,
+            expires_at_millis,
+        };
+        let id = announcement.id.clone();
+        self.announcements_document()
+            .await?
+            .update(|d| {
+                d.post(announcement);
+            })
+            .await?;
+        Ok(id)
+    }
+
+    /// Acknowledge an announcement as this member.
+    ///
+    /// Returns `false` if it was already acknowledged.
+    pub async fn acknowledge_announcement(&self, id: &str) -> Result<bool> {
+        let my_id = Member::new(*self.node.identity()).id();
+        let now = now_millis();
+        self.announcements_document()
+            .await?
+            .try_update(|d| {
+                if d.is_acknowledged(id, &my_id) {
+                    return Ok(false);
+                }
+                if !d.acknowledge(id, my_id, now) {
+                    return Err(IndraError::InvalidOperation(format!(
+                        "no active announcement {id} in this realm"
+                    )));
+                }
+                Ok(true)
+            })
+            .await
+    }
+
+    /// Active announcements from the realm's admins, oldest first.
+    pub async fn announcements(&self) -> Result<Vec<Announcement>> {
+        let admins = self.verified_membership().await?.admins;
+        let doc = self.announcements_document().await?;
+        let active = doc
+            .read()
+            .await
+            .active(now_millis(), |m| admins.contains(m))
+            .into_iter()
+            .cloned()
+            .collect();
+        Ok(active)
+    }
+
+    /// Active announcements this member has not acknowledged yet.
+    pub async fn pending_announcements(&self) -> Result<Vec<Announcement>> {
+        let my_id = Member::new(*self.node.identity()).id();
+        let admins = self.verified_membership().await?.admins;
+        let doc = self.announcements_document().await?;
+        let pending = doc
+            .read()
+            .await
+            .pending_for(&my_id, now_millis(), |m| admins.contains(m))
+            .into_iter()
+            .cloned()
+            .collect();
+        Ok(pending)
+    }
+
+    /// Who has and has not acknowledged an announcement. Admins only.
+    ///
+    /// Members are those of the signed membership log.
+    pub async fn announcement_summary(&self, id: &str) -> Result<AnnouncementSummary> {
+        let my_id = Member::new(*self.node.identity()).id();
+        let membership = self.verified_membership().await?;
+        require_admin(&membership.admins, &my_id)?;
+        self.announcements_document()
+            .await?
+            .read()
+            .await
+            .summary(id, &membership.members)
+            .ok_or_else(|| {
+                IndraError::InvalidOperation(format!("no announcement {id} in this realm"))
+            })
+    }
+
+    /// Stream of reminders for announcements this member has not
+    /// acknowledged, one per outstanding announcement every `every`.
+    ///
+    /// The first round is yielded immediately.
+    pub async fn announcement_reminders(
+        &self,
+        every: std::time::Duration,
+    ) -> Result<impl Stream<Item = AnnouncementReminder> + Send + 'static> {
+        let my_id = Member::new(*self.node.identity()).id();
+        let doc = self.announcements_document().await?;
+        let log = self.membership_log().await?;
+
+        Ok(async_stream::stream! {
+            let mut ticker = tokio::time::interval(every);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                let admins = log.read().await.state().admins;
+                let reminders = doc
+                    .read()
+                    .await
+                    .reminders_for(&my_id, now_millis(), |m| admins.contains(m));
+                for reminder in reminders {
+                    yield reminder;
+                }
+            }
+        })
+    }
+
+    // ============================================================
+    // Calendar
+    // ============================================================
+
+    /// Get the realm's calendar document.
+    pub async fn calendar(&self) -> Result<Document<CalendarDocument>> {
+        self.document::<CalendarDocument>(CALENDAR_DOC).await
+    }
+
+    /// Add an event to the realm's calendar.
+    ///
+    /// For all-day entries `start_millis` (and `end_millis`, exclusive) are
+    /// taken as UTC dates.
+    pub async fn add_calendar_entry(
+        &self,
+        title: impl Into<String>,
+        description: impl Into<String>,
+        location: Option<String>,
+        start_millis: u64,
+        end_millis: Option<u64>,
+        all_day: bool,
+    ) -> Result<CalendarEntryId> {
+        let title = title.into();
+        if title.len() > MAX_CALENDAR_TITLE_LEN {
+            return Err(IndraError::InvalidOperation(format!(
+                "calendar entry title longer than {MAX_CALENDAR_TITLE_LEN} bytes"
+            )));
+        }
+        if end_millis.is_some_and(|end| end < start_millis) {
+            return Err(IndraError::InvalidOperation(
+                "calendar entry must not end before it starts".to_string(),
+            ));
+        }
+
+        let entry = CalendarEntry {
+            id: generate_chat_id(),
+            author: Member::new(*self.node.identity()).id(),
+            title,
+            description: description.into(),
+            location,
+            start_millis,
+            end_millis,
+            all_day,
+            updated_at_millis: now_millis(),
+            removed: false,
+        };
+        let id = entry.id.clone();
+        self.calendar().await?.update(|d| d.upsert(entry)).await?;
+        Ok(id)
+    }
+
+    /// Remove a calendar entry. Only its author or an admin may remove it.
+    ///
+    /// Returns `false` if there was no such entry.
+    pub async fn remove_calendar_entry(&self, id: &str) -> Result<bool> {
+        let my_id = Member::new(*self.node.identity()).id();
+        let doc = self.calendar().await?;
+        let author = match doc.read().await.get(id) {
+            Some(entry) => entry.author,
+            None => return Ok(false),
+        };
+        if author != my_id {
+            require_admin(&self.verified_membership().await?.admins, &my_id)?;
+        }
+        let now = now_millis();
+        doc.try_update(|d| Ok(d.remove(id, now))).await
+    }
+
+    /// Calendar entries that have not been removed, earliest first.
+    pub async fn calendar_entries(&self) -> Result<Vec<CalendarEntry>> {
+        let doc = self.calendar().await?;
+        let entries = doc.read().await.upcoming().into_iter().cloned().collect();
+        Ok(entries)
+    }
+
+    /// The realm's calendar entries and announcements as an ICS feed.
+    ///
+    /// Use a [`CalendarExporter`] directly to add app events such as quest
+    /// deadlines.
+    pub async fn calendar_feed(&self) -> Result<String> {
+        CalendarExporter::new().export(self).await
+    }
+
+    // ============================================================
+    // Legal Holds
+    // ============================================================
+
+    /// Get the realm's legal hold document.
+    pub async fn legal_hold(&self) -> Result<Document<LegalHoldDocument>> {
+        Document::new(self.id, LEGAL_HOLD_DOC.to_string(), Arc::clone(&self.node)).await
+    }
+
+    /// Appoint a moderator.
+    ///
+    /// The first appointment in a realm also makes the caller a moderator;
+    /// after that only moderators may appoint. Returns `false` if `member`
+    /// already was one.
+    pub async fn appoint_moderator(&self, member: MemberId) -> Result<bool> {
+        let my_id = Member::new(*self.node.identity()).id();
+        self.legal_hold()
+            .await?
+            .try_update(|d| {
+                if !d.moderators.is_empty() && !d.is_moderator(&my_id) {
+                    return Err(IndraError::InvalidOperation(
+                        "only moderators can appoint moderators".to_string(),
+                    ));
+                }
+                Ok(d.appoint(my_id, member))
+            })
+            .await
+    }
+
+    /// Put a chat message under legal hold so it is never pruned.
+    ///
+    /// Returns `false` if the message was already held.
+    pub async fn place_legal_hold(&self, message_id: &str, reason: impl Into<String>) -> Result<bool> {
+        if self.chat_doc().await?.read().await.get_message(message_id).is_none() {
+            return Err(IndraError::InvalidOperation(format!(
+                "no chat message {message_id} in this realm"
+            )));
+        }
+        let my_id = Member::new(*self.node.identity()).id();
+        let reason = reason.into();
+        let now = now_millis();
+        self.legal_hold()
+            .await?
+            .try_update(|d| {
+                require_moderator(d, &my_id)?;
+                Ok(d.place(my_id, message_id.to_string(), reason, now))
+            })
+            .await
+    }
+
+    /// Release a legal hold. Returns `false` if the message was not held.
+    pub async fn release_legal_hold(&self, message_id: &str) -> Result<bool> {
+        let my_id = Member::new(*self.node.identity()).id();
+        let now = now_millis();
+        self.legal_hold()
+            .await?
+            .try_update(|d| {
+                require_moderator(d, &my_id)?;
+                Ok(d.release(my_id, message_id, now))
+            })
+            .await
+    }
 
-        // Use all_messages() to include CRDT-synced messages from remote peers
-        let total = self.all_messages().await?.len() as u64;
+    /// Export every held message, signed by this node as moderator.
+    pub async fn export_held_messages(&self) -> Result<HoldExport> {
+        let my_id = Member::new(*self.node.identity()).id();
+        let holds = self.legal_hold().await?.read().await.clone();
+        require_moderator(&holds, &my_id)?;
+
+        let chat = self.chat_doc().await?.read().await.clone();
+        let mut messages: Vec<HeldMessage> = holds
+            .active()
+            .filter_map(|(id, hold)| {
+                chat.get_message(id).map(|message| HeldMessage {
+                    message: message.clone(),
+                    hold: hold.clone(),
+                })
+            })
+            .collect();
+        messages.sort_by(|a, b| {
+            (a.message.created_at, &a.message.id).cmp(&(b.message.created_at, &b.message.id))
+        });
 
-        Ok(total.saturating_sub(last_read) as usize)
+        Ok(HoldExport::sign(self.id, my_id, messages, now_millis(), self.node.pq_identity()))
     }
 
-    /// Get the sequence number of the last message read by a member.
-    ///
-    /// Returns 0 if the member has never marked the realm as read.
-    pub async fn last_read_seq(&self, member: &MemberId) -> Result<u64> {
-        use crate::read_tracker::ReadTrackerDocument;
-
-        let doc = self.document::<ReadTrackerDocument>("read_tracker").await?;
-        Ok(doc.read().await.last_read_seq(member))
+    /// Stream of hold changes, local and from other moderators.
+    pub async fn hold_events(&self) -> Result<impl Stream<Item = HoldEvent> + Send + 'static> {
+        let doc = self.legal_hold().await?;
+        let mut rx = doc.subscribe();
+        let mut last = doc.read().await.clone();
+
+        Ok(async_stream::stream! {
+            // Hold the document so its change channel stays open.
+            let _doc = doc;
+            loop {
+                match rx.recv().await {
+                    Ok(change) => {
+                        for event in HoldEvent::diff(&last, &change.new_state) {
+                            yield event;
+                        }
+                        last = change.new_state;
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
     }
 
     // ============================================================
-    // Members
+    // Moderation
     // ============================================================
 
-    /// Get a stream of member events (joins, leaves, updates).
-    ///
-    /// This stream includes both CRDT-based membership changes and
-    /// gossip-based discovery events. Use this to react to members
-    /// joining or leaving the realm in real-time.
-    ///
-    /// # Example
-    ///
-    /// ```ignore
-    /// let mut events = realm.member_events();
-    /// while let Some(event) = events.next().await {
-    ///     match event {
-    ///         MemberEvent::Joined(member) => println!("{} joined", member.name()),
-    ///         MemberEvent::Left(member) => println!("{} left", member.name()),
-    ///         MemberEvent::Discovered(info) => println!("Discovered {} via gossip", info.member.name()),
-    ///         _ => {}
-    ///     }
-    /// }
-    /// ```
-    pub fn member_events(&self) -> impl Stream<Item = MemberEvent> + Send + '_ {
-        let rx = self.node.events(&self.id).ok();
+    /// Get the realm's moderation document (reports and moderator actions).
+    pub async fn moderation(&self) -> Result<Document<ModerationDocument>> {
+        Document::new(self.id, MODERATION_DOC.to_string(), Arc::clone(&self.node)).await
+    }
 
-        async_stream::stream! {
-            if let Some(rx) = rx {
-                let mut stream = broadcast_to_stream(rx);
-                use futures::StreamExt;
+    /// The realm's moderators.
+    pub async fn moderators(&self) -> Result<BTreeSet<MemberId>> {
+        Ok(self.legal_hold().await?.read().await.moderators.clone())
+    }
 
-                while let Some(event) = stream.next().await {
-                    if let Some(member_event) = convert_event_to_member_event(event) {
-                        yield member_event;
-                    }
-                }
-            }
+    /// Report a chat message to the realm's moderators.
+    ///
+    /// Returns `false` if this member already reported it.
+    pub async fn report_message(&self, message_id: &str, reason: impl Into<String>) -> Result<bool> {
+        if self.chat_doc().await?.read().await.get_message(message_id).is_none() {
+            return Err(IndraError::InvalidOperation(format!(
+                "no chat message {message_id} in this realm"
+            )));
         }
+        let my_id = Member::new(*self.node.identity()).id();
+        let reason = reason.into();
+        let now = now_millis();
+        self.moderation()
+            .await?
+            .try_update(|d| Ok(d.report(my_id, message_id.to_string(), reason, now)))
+            .await
     }
 
-    /// Get a stream of member events (alias for member_events).
-    #[deprecated(since = "0.1.0", note = "Use member_events() instead")]
-    pub fn members(&self) -> impl Stream<Item = MemberEvent> + Send + '_ {
-        self.member_events()
+    /// Hide a chat message for every member. Moderators only.
+    pub async fn hide_message_for_all(&self, message_id: &str, reason: impl Into<String>) -> Result<()> {
+        self.set_message_hidden(message_id, true, reason.into()).await
     }
 
-    /// Get the current list of realm members.
-    ///
-    /// Returns all known members including those discovered via gossip.
-    /// Use `member_list_with_info()` to get PQ keys for secure communication.
-    pub async fn member_list(&self) -> Result<Vec<Member>> {
-        let identities: Vec<IrohIdentity> = self.node.members(&self.id).await?;
+    /// Undo [`hide_message_for_all`](Self::hide_message_for_all). Moderators only.
+    pub async fn unhide_message(&self, message_id: &str) -> Result<()> {
+        self.set_message_hidden(message_id, false, String::new()).await
+    }
 
-        Ok(identities.into_iter().map(Member::new).collect())
+    async fn set_message_hidden(&self, message_id: &str, hidden: bool, reason: String) -> Result<()> {
+        let my_id = Member::new(*self.node.identity()).id();
+        require_moderator(&*self.legal_hold().await?.read().await, &my_id)?;
+        let now = now_millis();
+        self.moderation()
+            .await?
+            .update(|d| d.set_hidden(my_id, message_id.to_string(), hidden, reason, now))
+            .await
     }
 
-    /// Get realm members with extended info including PQ keys.
-    ///
-    /// Returns member information discovered via gossip, including
-    /// post-quantum cryptographic keys for secure direct communication.
+    /// Warn a member. Moderators only.
+    pub async fn warn_member(&self, member: MemberId, reason: impl Into<String>) -> Result<()> {
+        let my_id = Member::new(*self.node.identity()).id();
+        require_moderator(&*self.legal_hold().await?.read().await, &my_id)?;
+        let reason = reason.into();
+        let now = now_millis();
+        self.moderation()
+            .await?
+            .update(|d| d.warn(my_id, member, reason, now))
+            .await
+    }
+
+    /// Visible chat messages with moderator hides and `filters` applied.
     ///
     /// # Example
     ///
     /// ```ignore
-    /// let members = realm.member_list_with_info().await?;
-    /// for info in members {
-    ///     println!("{}: has PQ keys = {}", info.member.name(), info.has_pq_keys());
+    /// network.content_filters().add("spam", KeywordFilter::new(["giveaway"]));
+    /// for m in realm.moderated_messages(network.content_filters()).await? {
+    ///     if m.visibility.is_visible() {
+    ///         println!("{}", m.message.current_content);
+    ///     }
     /// }
     /// ```
-    pub async fn member_list_with_info(&self) -> Result<Vec<MemberInfo>> {
-        let peer_infos = self.node.members_with_info(&self.id).await?;
+    pub async fn moderated_messages(&self, filters: &ContentFilters) -> Result<Vec<ModeratedMessage>> {
+        let moderators = self.moderators().await?;
+        let moderation = self.moderation().await?.read().await.clone();
+        let chat = self.chat_doc().await?.read().await.clone();
+        Ok(moderate(&chat, &moderation, &moderators, filters))
+    }
 
-        Ok(peer_infos
-            .into_iter()
-            .map(MemberInfo::from_realm_peer_info)
-            .collect())
+    // ============================================================
+    // Public Mirror
+    // ============================================================
+
+    /// Get the realm's publish allow-list for static mirrors.
+    ///
+    /// See [`MirrorExporter`](crate::MirrorExporter) for exporting.
+    pub async fn publish_list(&self) -> Result<Document<PublishListDocument>> {
+        Document::new(self.id, PUBLISH_DOC.to_string(), Arc::clone(&self.node)).await
     }
 
-    /// Get the number of members in the realm.
-    pub async fn member_count(&self) -> Result<usize> {
-        Ok(self.node.members(&self.id).await?.len())
+    /// Publish or unpublish the chat in the realm's mirror. Moderators only.
+    pub async fn publish_chat(&self, published: bool) -> Result<()> {
+        let my_id = Member::new(*self.node.identity()).id();
+        require_moderator(&*self.legal_hold().await?.read().await, &my_id)?;
+        self.publish_list().await?.update(|d| d.chat = published).await
+    }
+
+    /// Publish or unpublish a named document in the realm's mirror.
+    /// Moderators only.
+    pub async fn publish_document(&self, name: &str, published: bool) -> Result<()> {
+        let my_id = Member::new(*self.node.identity()).id();
+        require_moderator(&*self.legal_hold().await?.read().await, &my_id)?;
+        let name = name.to_string();
+        self.publish_list()
+            .await?
+            .update(|d| {
+                if published {
+                    d.documents.insert(name);
+                } else {
+                    d.documents.remove(&name);
+                }
+            })
+            .await
     }
 
     // ============================================================
-    // Presence
+    // Sticker Packs
     // ============================================================
 
-    /// Get the list of currently online members.
-    ///
-    /// Returns members whose presence status is not `Offline`.
-    /// This is based on gossip discovery - members visible through
-    /// the gossip layer are considered online.
-    pub async fn online_members(&self) -> Result<Vec<Member>> {
-        // Members visible through the gossip layer are online
-        let identities: Vec<IrohIdentity> = self.node.members(&self.id).await?;
+    /// Get the realm's sticker and emoji pack document.
+    pub async fn sticker_packs(&self) -> Result<Document<StickerPackDocument>> {
+        Document::new(self.id, STICKER_PACK_DOC.to_string(), Arc::clone(&self.node)).await
+    }
 
-        Ok(identities
-            .into_iter()
-            .map(|id| {
-                let mut member = Member::new(id);
-                member.set_presence(indras_core::PresenceStatus::Online);
-                member
-            })
-            .collect())
+    /// Create a sticker pack, or retitle an existing one. Moderators only.
+    pub async fn create_sticker_pack(&self, pack: &str, title: impl Into<String>) -> Result<()> {
+        let my_id = Member::new(*self.node.identity()).id();
+        require_moderator(&*self.legal_hold().await?.read().await, &my_id)?;
+        let title = title.into();
+        let now = now_millis();
+        self.sticker_packs()
+            .await?
+            .update(|d| d.upsert_pack(pack, title, my_id, now))
+            .await
     }
 
-    /// Check if a specific member is currently reachable (online).
+    /// Retire a sticker pack. Moderators only.
+    pub async fn archive_sticker_pack(&self, pack: &str) -> Result<bool> {
+        let my_id = Member::new(*self.node.identity()).id();
+        require_moderator(&*self.legal_hold().await?.read().await, &my_id)?;
+        let now = now_millis();
+        self.sticker_packs()
+            .await?
+            .try_update(|d| Ok(d.archive_pack(pack, my_id, now)))
+            .await
+    }
+
+    /// Upload an image into a pack under `name`. Moderators only.
     ///
-    /// Returns true if the member is visible in the gossip layer.
-    pub async fn is_member_online(&self, member_id: &MemberId) -> Result<bool> {
-        let members = self.node.members(&self.id).await?;
-        Ok(members.iter().any(|id| {
-            let mut bytes = [0u8; 32];
-            let id_bytes = id.as_bytes();
-            bytes.copy_from_slice(&id_bytes[..32.min(id_bytes.len())]);
-            &bytes == member_id
-        }))
+    /// The image is stored as a blob and the pack records its hash;
+    /// returns the hash. Replaces any existing entry with that name.
+    pub async fn add_sticker(
+        &self,
+        pack: &str,
+        name: &str,
+        data: Vec<u8>,
+        mime_type: impl Into<String>,
+        kind: StickerKind,
+    ) -> Result<[u8; 32]> {
+        let my_id = Member::new(*self.node.identity()).id();
+        require_moderator(&*self.legal_hold().await?.read().await, &my_id)?;
+        if name.is_empty() || name.contains([':', '/']) || name.contains(char::is_whitespace) {
+            return Err(IndraError::InvalidOperation(format!(
+                "invalid sticker name {name:?}"
+            )));
+        }
+        if data.len() as u64 > MAX_STICKER_SIZE {
+            return Err(IndraError::InvalidOperation(format!(
+                "stickers must be at most {MAX_STICKER_SIZE} bytes"
+            )));
+        }
+
+        let content_ref = self.node.storage().store_blob(&data).await?;
+        let entry = PackEntry {
+            hash: content_ref.hash,
+            mime_type: mime_type.into(),
+            size: content_ref.size,
+            kind,
+            removed: false,
+            updated_by: my_id,
+            updated_at_millis: now_millis(),
+        };
+        let added = self
+            .sticker_packs()
+            .await?
+            .try_update(|d| Ok(d.put_entry(pack, name, entry)))
+            .await?;
+        if !added {
+            return Err(IndraError::InvalidOperation(format!("no sticker pack {pack}")));
+        }
+        Ok(content_ref.hash)
+    }
+
+    /// Remove an entry from a pack. Moderators only.
+    pub async fn remove_sticker(&self, pack: &str, name: &str) -> Result<bool> {
+        let my_id = Member::new(*self.node.identity()).id();
+        require_moderator(&*self.legal_hold().await?.read().await, &my_id)?;
+        let now = now_millis();
+        self.sticker_packs()
+            .await?
+            .try_update(|d| Ok(d.remove_entry(pack, name, my_id, now)))
+            .await
+    }
+
+    /// Send a pack entry as a message.
+    pub async fn send_sticker(&self, pack: &str, name: &str) -> Result<MessageId> {
+        let moderators = self.moderators().await?;
+        let sticker = {
+            let doc = self.sticker_packs().await?;
+            let guard = doc.read().await;
+            let entry = guard.entry(pack, name, &moderators).ok_or_else(|| {
+                IndraError::InvalidOperation(format!("no sticker {pack}/{name}"))
+            })?;
+            StickerRef {
+                pack: pack.to_string(),
+                name: name.to_string(),
+                hash: entry.hash,
+                mime_type: entry.mime_type.clone(),
+            }
+        };
+        self.send(Content::Sticker(sticker)).await
+    }
+
+    /// Resolve a `:shortcode:` or `:pack/shortcode:` to a custom emoji.
+    pub async fn resolve_emoji(&self, shortcode: &str) -> Result<Option<StickerRef>> {
+        let moderators = self.moderators().await?;
+        Ok(self
+            .sticker_packs()
+            .await?
+            .read()
+            .await
+            .resolve_shortcode(shortcode, &moderators))
     }
 
     // ============================================================
-    // Documents
+    // Link Previews
     // ============================================================
 
-    /// Get or create a typed document in this realm.
-    ///
-    /// Documents are CRDT-backed data structures that automatically
-    /// synchronize across all realm members.
-    ///
-    /// # Type Parameters
-    ///
-    /// * `T` - The document type (must implement `DocumentSchema`)
-    ///
-    /// # Arguments
+    /// Store a preview image as a blob, for attaching to a [`LinkPreview`].
+    pub async fn store_preview_image(
+        &self,
+        data: Vec<u8>,
+        mime_type: impl Into<String>,
+    ) -> Result<PreviewImage> {
+        if data.len() as u64 > MAX_PREVIEW_IMAGE_SIZE {
+            return Err(IndraError::InvalidOperation(format!(
+                "preview images must be at most {MAX_PREVIEW_IMAGE_SIZE} bytes"
+            )));
+        }
+        let content_ref = self.node.storage().store_blob(&data).await?;
+        Ok(PreviewImage {
+            hash: content_ref.hash,
+            mime_type: mime_type.into(),
+            size: content_ref.size,
+        })
+    }
+
+    /// Send text with preview cards for the URLs it contains.
     ///
-    /// * `name` - Unique name for the document within this realm
+    /// Each card is signed with this node's identity. Cards whose URL does
+    /// not appear in `text`, and empty cards, are dropped; with none left
+    /// this sends plain text.
     ///
     /// # Example
     ///
     /// ```ignore
-    /// #[derive(Default, Clone, Serialize, Deserialize, DocumentSchema)]
-    /// struct QuestLog {
-    ///     quests: Vec<Quest>,
-    /// }
-    ///
-    /// let doc = realm.document::<QuestLog>("quests").await?;
-    ///
-    /// // Read current state
-    /// let quests = doc.read();
-    ///
-    /// // Make changes (auto-synced)
-    /// doc.update(|q| q.quests.push(Quest::new("Defeat dragon"))).await?;
+    /// let text = "Worth a read: https://example.com/post";
+    /// let html = my_http_client.get("https://example.com/post").await?;
+    /// let preview = LinkPreview::from_html("https://example.com/post", &html);
+    /// realm.send_with_previews(text, vec![preview]).await?;
     /// ```
-    pub async fn document<T: crate::document::DocumentSchema>(
+    pub async fn send_with_previews(
         &self,
-        name: &str,
-    ) -> Result<Document<T>> {
-        // Auto-register the document name (skip internal documents)
-        if !name.starts_with('_') {
-            let registry = Document::<crate::document_registry::DocumentRegistryDocument>::new(
-                self.id,
-                "_registry".to_string(),
-                Arc::clone(&self.node),
-            )
-            .await?;
-            let name_owned = name.to_string();
-            registry
-                .update(|d| {
-                    d.register(name_owned);
-                })
-                .await?;
+        text: impl Into<String>,
+        previews: Vec<LinkPreview>,
+    ) -> Result<MessageId> {
+        let text = text.into();
+        let urls = extract_urls(&text);
+        let previews: Vec<_> = previews
+            .into_iter()
+            .filter(|p| !p.is_empty() && urls.contains(&p.url.as_str()))
+            .take(MAX_PREVIEWS_PER_MESSAGE)
+            .map(|p| p.sign(self.node.pq_identity()))
+            .collect();
+        if previews.is_empty() {
+            return self.send(Content::Text(text)).await;
         }
-
-        Document::new(self.id, name.to_string(), Arc::clone(&self.node)).await
+        self.send(Content::TextWithPreviews { text, previews }).await
     }
 
-    /// List all named documents in this realm.
-    ///
-    /// Returns the names of documents that have been opened via `document()`.
-    /// Internal documents (prefixed with `_`) are excluded.
+    // ============================================================
+    // Time Series
+    // ============================================================
+
+    /// Get the realm's time-series document.
     ///
-    /// # Example
+    /// Sensor data realms use it as a bus: each member appends samples
+    /// to its own series and everyone can query them at raw, minute, or
+    /// hour resolution.
+    pub async fn time_series(&self) -> Result<Document<TimeSeriesDocument>> {
+        Document::new(self.id, TIME_SERIES_DOC.to_string(), Arc::clone(&self.node)).await
+    }
+
+    /// Append a sample to this member's series for `metric`, stamped now.
+    pub async fn record_sample(&self, metric: &str, value: f64) -> Result<bool> {
+        self.record_samples(metric, [(now_millis(), value)]).await
+    }
+
+    /// Append timestamped samples to this member's series for `metric`.
     ///
-    /// ```ignore
-    /// let names = realm.document_names().await?;
-    /// for name in names {
-    ///     println!("Document: {}", name);
-    /// }
-    /// ```
-    pub async fn document_names(&self) -> Result<Vec<String>> {
-        let registry = Document::<crate::document_registry::DocumentRegistryDocument>::new(
-            self.id,
-            "_registry".to_string(),
-            Arc::clone(&self.node),
-        )
-        .await?;
-        let guard = registry.read().await;
-        Ok(guard.document_names().into_iter().map(String::from).collect())
+    /// Use this to publish readings buffered while offline. Returns
+    /// `true` if any sample was new.
+    pub async fn record_samples(
+        &self,
+        metric: &str,
+        samples: impl IntoIterator<Item = (u64, f64)>,
+    ) -> Result<bool> {
+        let key = SeriesKey::new(Member::new(*self.node.identity()).id(), metric);
+        let samples: Vec<_> = samples.into_iter().collect();
+        self.time_series()
+            .await?
+            .try_update(|d| {
+                Ok(samples
+                    .iter()
+                    .fold(false, |added, (at, v)| added | d.record(key.clone(), *at, *v)))
+            })
+            .await
     }
 
-    /// Check if a named document exists in this realm.
-    pub async fn has_document(&self, name: &str) -> Result<bool> {
-        let registry = Document::<crate::document_registry::DocumentRegistryDocument>::new(
-            self.id,
-            "_registry".to_string(),
-            Arc::clone(&self.node),
-        )
-        .await?;
-        Ok(registry.read().await.contains(name))
+    /// Buckets of a member's series in `[from_millis, to_millis)`.
+    pub async fn query_series(
+        &self,
+        source: MemberId,
+        metric: &str,
+        from_millis: u64,
+        to_millis: u64,
+        tier: Tier,
+    ) -> Result<Vec<Bucket>> {
+        let key = SeriesKey::new(source, metric);
+        let doc = self.time_series().await?;
+        let guard = doc.read().await;
+        Ok(guard.range(&key, from_millis, to_millis, tier))
+    }
+
+    /// Set how long raw samples and rollups are kept in this realm.
+    pub async fn set_series_retention(&self, retention: RetentionPolicy) -> Result<()> {
+        let now = now_millis();
+        self.time_series()
+            .await?
+            .update(|d| {
+                d.set_retention(retention, now);
+            })
+            .await
+    }
+
+    /// Apply the realm's retention policy to the time-series document.
+    ///
+    /// Returns the number of samples and rollup buckets removed.
+    pub async fn compact_time_series(&self) -> Result<usize> {
+        let now = now_millis();
+        self.time_series()
+            .await?
+            .try_update(|d| Ok(d.compact(now)))
+            .await
     }
 
     // ============================================================
@@ -1215,11 +3786,23 @@ impl std::fmt::Debug for Realm {
 // Helper functions
 // ============================================================
 
-fn serialize_payload(payload: &MessagePayload) -> Result<Vec<u8>> {
+/// Whether a message is at or after the rejoin history limit, if any.
+fn is_visible(message: &Message, visible_from: Option<u64>) -> bool {
+    visible_from.is_none_or(|from| message.timestamp.timestamp_millis() >= from as i64)
+}
+
+/// Convert a member ID into the transport identity used by the node.
+fn member_identity(id: &MemberId) -> Result<IrohIdentity> {
+    let public_key = iroh::PublicKey::from_bytes(id)
+        .map_err(|e| IndraError::InvalidOperation(format!("invalid contact id: {e}")))?;
+    Ok(IrohIdentity::from(public_key))
+}
+
+pub(crate) fn serialize_payload(payload: &MessagePayload) -> Result<Vec<u8>> {
     postcard::to_allocvec(payload).map_err(IndraError::from)
 }
 
-fn convert_event_to_message(event: ReceivedEvent, realm_id: RealmId) -> Option<Message> {
+pub(crate) fn convert_event_to_message(event: ReceivedEvent, realm_id: RealmId) -> Option<Message> {
     // Match on the InterfaceEvent enum to extract message data
     match &event.event {
         InterfaceEvent::Message {
@@ -1232,12 +3815,8 @@ fn convert_event_to_message(event: ReceivedEvent, realm_id: RealmId) -> Option<M
             let msg_id = MessageId::new(realm_id, *id);
 
             // Try to deserialize as MessagePayload first (new format with reply support)
-            if let Ok(payload) = postcard::from_bytes::<MessagePayload>(content) {
-                if let Some(reply_to) = payload.reply_to {
-                    return Some(Message::reply(msg_id, member, payload.content, *timestamp, reply_to));
-                } else {
-                    return Some(Message::new(msg_id, member, payload.content, *timestamp));
-                }
+            if let Some(payload) = MessagePayload::from_bytes(content) {
+                return Some(payload.into_message(msg_id, member, *timestamp));
             }
 
             // Fall back to deserializing as plain Content (legacy format)
@@ -1247,20 +3826,24 @@ fn convert_event_to_message(event: ReceivedEvent, realm_id: RealmId) -> Option<M
         InterfaceEvent::Custom {
             id,
             sender,
+            event_type,
             payload,
             timestamp,
-            ..
         } => {
             let member = Member::new(*sender);
             let msg_id = MessageId::new(realm_id, *id);
 
+            // Anonymous posts wrap the payload in a ring-signed envelope,
+            // already verified by the node
+            let payload = &if event_type == ANONYMOUS_EVENT_TYPE {
+                AnonymousPost::from_bytes(payload).ok()?.content
+            } else {
+                payload.clone()
+            };
+
             // Try to deserialize as MessagePayload first (new format with reply support)
-            if let Ok(msg_payload) = postcard::from_bytes::<MessagePayload>(payload) {
-                if let Some(reply_to) = msg_payload.reply_to {
-                    return Some(Message::reply(msg_id, member, msg_payload.content, *timestamp, reply_to));
-                } else {
-                    return Some(Message::new(msg_id, member, msg_payload.content, *timestamp));
-                }
+            if let Some(msg_payload) = MessagePayload::from_bytes(payload) {
+                return Some(msg_payload.into_message(msg_id, member, *timestamp));
             }
 
             // Fall back to deserializing as plain Content (legacy format)
@@ -1317,18 +3900,57 @@ fn generate_chat_id() -> ChatMessageId {
     format!("{ts:016x}-{random:016x}")
 }
 
-/// Get current time in milliseconds since UNIX epoch.
-fn now_millis() -> u64 {
-    std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as u64
+/// Fail unless `member` is one of the realm's admins.
+fn require_admin(admins: &BTreeSet<MemberId>, member: &MemberId) -> Result<()> {
+    if admins.contains(member) {
+        Ok(())
+    } else {
+        Err(IndraError::InvalidOperation(
+            "only admins can do that".to_string(),
+        ))
+    }
+}
+
+/// Fail unless `member` is one of the realm's moderators.
+fn require_moderator(holds: &LegalHoldDocument, member: &MemberId) -> Result<()> {
+    if holds.is_moderator(member) {
+        Ok(())
+    } else {
+        Err(IndraError::InvalidOperation(
+            "only moderators can do that".to_string(),
+        ))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use indras_crypto::PQIdentity;
 
     // Tests would require setting up a full node, which is complex
     // Integration tests are more appropriate
+
+    #[test]
+    fn test_non_admin_cannot_archive() {
+        let (owner, bob) = ([1; 32], [2; 32]);
+        let (owner_key, bob_key) = (PQIdentity::generate(), PQIdentity::generate());
+        let mut log = MembershipLogDocument::new();
+
+        // An empty log has no admins; nobody may archive
+        assert!(require_admin(&log.state().admins, &bob).is_err());
+
+        let create = log.next_entry(owner, MembershipAction::Create, 0);
+        log.append(SignedMembershipEntry::sign(create, &owner_key));
+        let add = MembershipAction::Add {
+            member: bob,
+            admin: false,
+            key: bob_key.verifying_key_bytes(),
+        };
+        let add = log.next_entry(owner, add, 1);
+        assert!(log.append(SignedMembershipEntry::sign(add, &owner_key)));
+
+        let admins = log.state().admins;
+        assert!(require_admin(&admins, &bob).is_err());
+        assert!(require_admin(&admins, &owner).is_ok());
+    }
 }
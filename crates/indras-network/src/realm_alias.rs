@@ -3,12 +3,29 @@
 //! Each realm can have a mutually editable alias that all members can modify.
 //! The alias is stored as a CRDT document, providing Last-Writer-Wins semantics
 //! via Automerge's native merge behavior.
-
+//!
+//! Separately, realms can be registered under a short handle (`@project-alpha`)
+//! in an [`AliasDirectoryDocument`]. Directories are shared realm documents,
+//! so any member of a realm carrying the record can resolve the handle to a
+//! realm ID and invite code. Within one directory a handle maps to exactly
+//! one realm; concurrent registrations resolve first-come-first-served.
+
+use crate::document::DocumentSchema;
+use crate::error::{IndraError, Result};
+use crate::member::MemberId;
+use crate::network::RealmId;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Maximum length for a realm alias (in characters, Unicode allowed).
 pub const MAX_ALIAS_LENGTH: usize = 77;
 
+/// Maximum length for a resolvable alias handle (without the `@`).
+pub const MAX_HANDLE_LENGTH: usize = 32;
+
+/// Document name for the alias directory in each realm.
+pub const ALIAS_DIRECTORY_DOC: &str = "alias_directory";
+
 /// A realm alias - a CRDT-synchronized nickname for a realm.
 ///
 /// All realm members can edit the alias, with automatic conflict resolution
@@ -137,6 +154,141 @@ impl RealmAliasDocument {
     }
 }
 
+/// Normalize and validate an alias handle.
+///
+/// Strips a leading `@` and lowercases. Handles must be 1 to
+/// [`MAX_HANDLE_LENGTH`] characters of `a-z`, `0-9`, `-`, or `_`.
+///
+/// ```ignore
+/// assert_eq!(normalize_handle("@Project-Alpha")?, "project-alpha");
+/// ```
+pub fn normalize_handle(handle: &str) -> Result<String> {
+    let handle = handle.trim();
+    let handle = handle.strip_prefix('@').unwrap_or(handle).to_lowercase();
+
+    if handle.is_empty() || handle.chars().count() > MAX_HANDLE_LENGTH {
+        return Err(IndraError::InvalidAlias {
+            reason: format!("handle must be 1-{MAX_HANDLE_LENGTH} characters"),
+        });
+    }
+    if let Some(bad) = handle
+        .chars()
+        .find(|c| !(c.is_ascii_lowercase() || c.is_ascii_digit() || *c == '-' || *c == '_'))
+    {
+        return Err(IndraError::InvalidAlias {
+            reason: format!("invalid character {bad:?}"),
+        });
+    }
+    Ok(handle)
+}
+
+/// A handle registration pointing at a joinable realm.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AliasRecord {
+    /// The realm the handle resolves to.
+    pub realm_id: RealmId,
+    /// Invite URI for joining the realm.
+    pub invite: String,
+    /// Member who registered the handle.
+    pub owner: MemberId,
+    /// When the handle was registered (milliseconds since epoch).
+    pub registered_at_millis: u64,
+}
+
+impl AliasRecord {
+    /// Whether this record wins over `other` for the same handle.
+    ///
+    /// Earlier registration wins; ties go to the smaller realm ID so all
+    /// members converge on the same owner.
+    pub fn precedes(&self, other: &AliasRecord) -> bool {
+        (self.registered_at_millis, self.realm_id) < (other.registered_at_millis, other.realm_id)
+    }
+}
+
+/// Directory of alias handles published in a realm.
+///
+/// # Example
+///
+/// ```ignore
+/// let dir = realm.document::<AliasDirectoryDocument>(ALIAS_DIRECTORY_DOC).await?;
+/// if let Some(record) = dir.read().await.resolve("@project-alpha") {
+///     network.join(&record.invite).await?;
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AliasDirectoryDocument {
+    /// Normalized handle → registration.
+    pub entries: BTreeMap<String, AliasRecord>,
+}
+
+impl AliasDirectoryDocument {
+    /// Create an empty directory.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a handle for a realm.
+    ///
+    /// Re-registering the same realm refreshes its invite. Fails with
+    /// [`IndraError::AliasTaken`] if the handle belongs to another realm.
+    pub fn register(&mut self, handle: &str, record: AliasRecord) -> Result<()> {
+        let handle = normalize_handle(handle)?;
+        match self.entries.get_mut(&handle) {
+            Some(existing) if existing.realm_id != record.realm_id => Err(IndraError::AliasTaken {
+                alias: handle,
+            }),
+            Some(existing) => {
+                existing.invite = record.invite;
+                Ok(())
+            }
+            None => {
+                self.entries.insert(handle, record);
+                Ok(())
+            }
+        }
+    }
+
+    /// Remove a handle. Returns the removed record, if any.
+    pub fn unregister(&mut self, handle: &str) -> Option<AliasRecord> {
+        let handle = normalize_handle(handle).ok()?;
+        self.entries.remove(&handle)
+    }
+
+    /// Resolve a handle (with or without `@`) to its registration.
+    pub fn resolve(&self, handle: &str) -> Option<&AliasRecord> {
+        let handle = normalize_handle(handle).ok()?;
+        self.entries.get(&handle)
+    }
+
+    /// Handles registered for a realm.
+    pub fn handles_for(&self, realm_id: &RealmId) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter(|(_, r)| r.realm_id == *realm_id)
+            .map(|(h, _)| h.as_str())
+            .collect()
+    }
+}
+
+impl DocumentSchema for AliasDirectoryDocument {
+    /// Per-handle merge: the earliest registration wins a collision.
+    fn merge(&mut self, remote: Self) {
+        for (handle, record) in remote.entries {
+            match self.entries.get(&handle) {
+                Some(local) if local.realm_id == record.realm_id => {
+                    if local.registered_at_millis < record.registered_at_millis {
+                        self.entries.insert(handle, record);
+                    }
+                }
+                Some(local) if local.precedes(&record) => {}
+                _ => {
+                    self.entries.insert(handle, record);
+                }
+            }
+        }
+    }
+}
+
 /// Truncate an alias to the maximum allowed length.
 fn truncate_alias(alias: &str) -> String {
     alias.chars().take(MAX_ALIAS_LENGTH).collect()
@@ -227,4 +379,60 @@ mod tests {
 
         assert_eq!(doc.get(), deserialized.get());
     }
+
+    fn record(realm: u8, at: u64) -> AliasRecord {
+        AliasRecord {
+            realm_id: indras_core::InterfaceId::new([realm; 32]),
+            invite: format!("indra:realm:{realm}"),
+            owner: [9u8; 32],
+            registered_at_millis: at,
+        }
+    }
+
+    #[test]
+    fn test_normalize_handle() {
+        assert_eq!(normalize_handle("@Project-Alpha").unwrap(), "project-alpha");
+        assert_eq!(normalize_handle("team_7").unwrap(), "team_7");
+        assert!(normalize_handle("@").is_err());
+        assert!(normalize_handle("has space").is_err());
+        assert!(normalize_handle(&"a".repeat(MAX_HANDLE_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_directory_register_and_resolve() {
+        let mut dir = AliasDirectoryDocument::new();
+        dir.register("@alpha", record(1, 10)).unwrap();
+
+        assert_eq!(dir.resolve("ALPHA").unwrap().realm_id, record(1, 0).realm_id);
+        assert!(dir.resolve("beta").is_none());
+        assert_eq!(dir.handles_for(&record(1, 0).realm_id), vec!["alpha"]);
+    }
+
+    #[test]
+    fn test_directory_collision_rejected() {
+        let mut dir = AliasDirectoryDocument::new();
+        dir.register("alpha", record(1, 10)).unwrap();
+
+        let err = dir.register("@Alpha", record(2, 20)).unwrap_err();
+        assert!(matches!(err, IndraError::AliasTaken { alias } if alias == "alpha"));
+
+        // Same realm may re-register
+        dir.register("alpha", record(1, 30)).unwrap();
+    }
+
+    #[test]
+    fn test_directory_merge_first_registration_wins() {
+        let mut a = AliasDirectoryDocument::new();
+        a.register("alpha", record(2, 20)).unwrap();
+        let mut b = AliasDirectoryDocument::new();
+        b.register("alpha", record(1, 10)).unwrap();
+
+        let mut ab = a.clone();
+        ab.merge(b.clone());
+        let mut ba = b;
+        ba.merge(a);
+
+        assert_eq!(ab.resolve("alpha"), ba.resolve("alpha"));
+        assert_eq!(ab.resolve("alpha").unwrap().registered_at_millis, 10);
+    }
 }
@@ -0,0 +1,340 @@
+//! Realm organization - pins, manual order, and folders.
+//!
+//! Stored as a document in the home realm so every device and frontend
+//! belonging to the same member shows the same realm list structure.
+//! Each realm placement and each folder is merged independently with
+//! last-writer-wins semantics, so reordering on one device and pinning on
+//! another both survive.
+
+use crate::document::DocumentSchema;
+use crate::network::RealmId;
+use crate::util::now_millis;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Document name for realm organization in the home realm.
+pub const REALM_ORGANIZATION_DOC: &str = "realm_organization";
+
+/// Identifier for a realm folder.
+pub type FolderId = String;
+
+/// Where one realm sits in the member's realm list.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RealmPlacement {
+    /// Whether the realm is pinned to the top.
+    pub pinned: bool,
+    /// Folder the realm is grouped under, if any.
+    pub folder: Option<FolderId>,
+    /// Sort key within its group; lower sorts first.
+    pub position: f64,
+    /// When this placement was last changed (milliseconds since epoch).
+    pub updated_at_millis: u64,
+}
+
+/// A named folder grouping realms.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RealmFolder {
+    /// Display name.
+    pub name: String,
+    /// Sort key among folders; lower sorts first.
+    pub position: f64,
+    /// Whether the folder was deleted (kept so deletion syncs).
+    pub deleted: bool,
+    /// When this folder was last changed (milliseconds since epoch).
+    pub updated_at_millis: u64,
+}
+
+/// Realm list grouped for display.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RealmLayout {
+    /// Pinned realms, in order.
+    pub pinned: Vec<RealmId>,
+    /// Folders in order, each with its realms in order.
+    pub folders: Vec<(FolderId, String, Vec<RealmId>)>,
+    /// Realms that are neither pinned nor in a folder, in order.
+    pub ungrouped: Vec<RealmId>,
+}
+
+/// CRDT document holding the member's realm list organization.
+///
+/// # Example
+///
+/// ```ignore
+/// let org = network.realm_organization().await?;
+/// org.update(|o| {
+///     o.pin(realm_id, true);
+///     let folder = o.create_folder("Work");
+///     o.move_to_folder(other_id, Some(folder));
+/// }).await?;
+///
+/// let layout = org.read().await.layout(&network.realms());
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RealmOrganizationDocument {
+    /// Per-realm placement.
+    pub placements: BTreeMap<RealmId, RealmPlacement>,
+    /// Folders by ID.
+    pub folders: BTreeMap<FolderId, RealmFolder>,
+}
+
+impl RealmOrganizationDocument {
+    /// Create an empty organization document.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Placement for a realm, if it has been organized.
+    pub fn placement(&self, realm_id: &RealmId) -> Option<&RealmPlacement> {
+        self.placements.get(realm_id)
+    }
+
+    /// Whether a realm is pinned.
+    pub fn is_pinned(&self, realm_id: &RealmId) -> bool {
+        self.placements.get(realm_id).is_some_and(|p| p.pinned)
+    }
+
+    /// Pin or unpin a realm.
+    pub fn pin(&mut self, realm_id: RealmId, pinned: bool) {
+        self.edit(realm_id, |p| p.pinned = pinned);
+    }
+
+    /// Set a realm's sort key directly.
+    pub fn set_position(&mut self, realm_id: RealmId, position: f64) {
+        self.edit(realm_id, |p| p.position = position);
+    }
+
+    /// Move a realm between two neighbours (either may be absent).
+    ///
+    /// Picks a sort key halfway between the neighbours' keys, so only the
+    /// moved realm's placement changes.
+    pub fn move_between(
+        &mut self,
+        realm_id: RealmId,
+        before: Option<&RealmId>,
+        after: Option<&RealmId>,
+    ) {
+        let lo = before.and_then(|id| self.placements.get(id)).map(|p| p.position);
+        let hi = after.and_then(|id| self.placements.get(id)).map(|p| p.position);
+        let position = match (lo, hi) {
+            (Some(lo), Some(hi)) => (lo + hi) / 2.0,
+            (Some(lo), None) => lo + 1.0,
+            (None, Some(hi)) => hi - 1.0,
+            (None, None) => 0.0,
+        };
+        self.set_position(realm_id, position);
+    }
+
+    /// Put a realm in a folder, or take it out with `None`.
+    pub fn move_to_folder(&mut self, realm_id: RealmId, folder: Option<FolderId>) {
+        self.edit(realm_id, |p| p.folder = folder);
+    }
+
+    /// Create a folder at the end of the folder list and return its ID.
+    pub fn create_folder(&mut self, name: impl Into<String>) -> FolderId {
+        let id: FolderId = format!("{:016x}", rand::random::<u64>());
+        let position = self
+            .live_folders()
+            .map(|(_, f)| f.position)
+            .fold(0.0, f64::max)
+            + 1.0;
+        self.folders.insert(
+            id.clone(),
+            RealmFolder {
+                name: name.into(),
+                position,
+                deleted: false,
+                updated_at_millis: now_millis(),
+            },
+        );
+        id
+    }
+
+    /// Rename a folder. Returns `false` if it does not exist.
+    pub fn rename_folder(&mut self, folder: &str, name: impl Into<String>) -> bool {
+        match self.folders.get_mut(folder).filter(|f| !f.deleted) {
+            Some(f) => {
+                f.name = name.into();
+                f.updated_at_millis = now_millis();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Set a folder's sort key. Returns `false` if it does not exist.
+    pub fn set_folder_position(&mut self, folder: &str, position: f64) -> bool {
+        match self.folders.get_mut(folder).filter(|f| !f.deleted) {
+            Some(f) => {
+                f.position = position;
+                f.updated_at_millis = now_millis();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Delete a folder. Its realms fall back to the ungrouped list.
+    pub fn delete_folder(&mut self, folder: &str) -> bool {
+        match self.folders.get_mut(folder).filter(|f| !f.deleted) {
+            Some(f) => {
+                f.deleted = true;
+                f.updated_at_millis = now_millis();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Folders that have not been deleted, in order.
+    pub fn live_folders(&self) -> impl Iterator<Item = (&FolderId, &RealmFolder)> {
+        let mut folders: Vec<_> = self.folders.iter().filter(|(_, f)| !f.deleted).collect();
+        folders.sort_by(|a, b| a.1.position.total_cmp(&b.1.position).then(a.0.cmp(b.0)));
+        folders.into_iter()
+    }
+
+    /// Group and order a realm list for display.
+    ///
+    /// Realms without a placement sort after organized ones, in the order
+    /// given. Placements for realms not in `realms` are ignored, and realms
+    /// in a deleted folder are treated as ungrouped.
+    pub fn layout(&self, realms: &[RealmId]) -> RealmLayout {
+        let sort_key = |id: &RealmId, index: usize| match self.placements.get(id) {
+            Some(p) => (0u8, p.position, index),
+            None => (1u8, 0.0, index),
+        };
+        let mut ordered: Vec<(usize, &RealmId)> = realms.iter().enumerate().collect();
+        ordered.sort_by(|(ia, a), (ib, b)| {
+            let (ga, pa, xa) = sort_key(a, *ia);
+            let (gb, pb, xb) = sort_key(b, *ib);
+            ga.cmp(&gb).then(pa.total_cmp(&pb)).then(xa.cmp(&xb))
+        });
+
+        let mut layout = RealmLayout {
+            folders: self
+                .live_folders()
+                .map(|(id, f)| (id.clone(), f.name.clone(), Vec::new()))
+                .collect(),
+            ..Default::default()
+        };
+
+        for (_, id) in ordered {
+            let placement = self.placements.get(id);
+            if placement.is_some_and(|p| p.pinned) {
+                layout.pinned.push(*id);
+                continue;
+            }
+            let folder = placement
+                .and_then(|p| p.folder.as_ref())
+                .and_then(|f| layout.folders.iter_mut().find(|(fid, _, _)| fid == f));
+            match folder {
+                Some((_, _, members)) => members.push(*id),
+                None => layout.ungrouped.push(*id),
+            }
+        }
+
+        layout
+    }
+
+    fn edit(&mut self, realm_id: RealmId, f: impl FnOnce(&mut RealmPlacement)) {
+        let placement = self.placements.entry(realm_id).or_insert(RealmPlacement {
+            pinned: false,
+            folder: None,
+            position: 0.0,
+            updated_at_millis: 0,
+        });
+        f(placement);
+        placement.updated_at_millis = now_millis();
+    }
+}
+
+impl DocumentSchema for RealmOrganizationDocument {
+    /// Per-realm and per-folder LWW merge.
+    fn merge(&mut self, remote: Self) {
+        for (id, placement) in remote.placements {
+            match self.placements.get(&id) {
+                Some(local) if local.updated_at_millis >= placement.updated_at_millis => {}
+                _ => {
+                    self.placements.insert(id, placement);
+                }
+            }
+        }
+        for (id, folder) in remote.folders {
+            match self.folders.get(&id) {
+                Some(local) if local.updated_at_millis >= folder.updated_at_millis => {}
+                _ => {
+                    self.folders.insert(id, folder);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indras_core::InterfaceId;
+
+    fn realm(n: u8) -> RealmId {
+        InterfaceId::new([n; 32])
+    }
+
+    #[test]
+    fn test_unorganized_realms_keep_input_order() {
+        let doc = RealmOrganizationDocument::new();
+        let layout = doc.layout(&[realm(3), realm(1), realm(2)]);
+        assert_eq!(layout.ungrouped, vec![realm(3), realm(1), realm(2)]);
+        assert!(layout.pinned.is_empty());
+    }
+
+    #[test]
+    fn test_pinned_and_folders() {
+        let mut doc = RealmOrganizationDocument::new();
+        doc.pin(realm(2), true);
+        let work = doc.create_folder("Work");
+        doc.move_to_folder(realm(3), Some(work.clone()));
+
+        let layout = doc.layout(&[realm(1), realm(2), realm(3)]);
+        assert_eq!(layout.pinned, vec![realm(2)]);
+        assert_eq!(layout.folders, vec![(work, "Work".to_string(), vec![realm(3)])]);
+        assert_eq!(layout.ungrouped, vec![realm(1)]);
+    }
+
+    #[test]
+    fn test_move_between() {
+        let mut doc = RealmOrganizationDocument::new();
+        doc.set_position(realm(1), 1.0);
+        doc.set_position(realm(2), 2.0);
+        doc.move_between(realm(3), Some(&realm(1)), Some(&realm(2)));
+
+        let layout = doc.layout(&[realm(2), realm(3), realm(1)]);
+        assert_eq!(layout.ungrouped, vec![realm(1), realm(3), realm(2)]);
+    }
+
+    #[test]
+    fn test_deleted_folder_releases_realms() {
+        let mut doc = RealmOrganizationDocument::new();
+        let folder = doc.create_folder("Old");
+        doc.move_to_folder(realm(1), Some(folder.clone()));
+        assert!(doc.delete_folder(&folder));
+
+        let layout = doc.layout(&[realm(1)]);
+        assert!(layout.folders.is_empty());
+        assert_eq!(layout.ungrouped, vec![realm(1)]);
+        assert!(!doc.rename_folder(&folder, "New"));
+    }
+
+    #[test]
+    fn test_merge_keeps_newer_entries() {
+        let mut a = RealmOrganizationDocument::new();
+        a.pin(realm(1), true);
+        let mut b = a.clone();
+
+        b.placements.get_mut(&realm(1)).unwrap().updated_at_millis += 10;
+        b.placements.get_mut(&realm(1)).unwrap().pinned = false;
+        a.pin(realm(2), true);
+
+        a.merge(b);
+        assert!(!a.is_pinned(&realm(1)));
+        assert!(a.is_pinned(&realm(2)));
+    }
+}
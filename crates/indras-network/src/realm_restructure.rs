@@ -0,0 +1,221 @@
+//! Realm merging and splitting.
+//!
+//! Both operations build a brand-new realm and replay history into it
+//! rather than mutating the sources, so the originals stay intact until
+//! their members choose to leave them.
+//!
+//! - **Merge** replays every document update and message from two realms,
+//!   interleaved by timestamp. Each document reconciles through its own
+//!   [`DocumentSchema::merge`](crate::document::DocumentSchema::merge) when
+//!   it is next opened, exactly as it would for a newly joined peer.
+//! - **Split** replays only what a [`SplitFilter`] selects: named documents
+//!   (chat, quests, notes, ...), plain messages, and artifact announcements.
+//!
+//! Members of the sources are then re-invited to the new realm with
+//! ML-KEM-wrapped invites over their DM realms. Guests are never re-invited.
+//! Replayed events are authored by the member running the tool; attribution
+//! stored inside document state (chat authors, quest creators) is preserved.
+
+use crate::document::event_document_name;
+use crate::guest_access::GUEST_ACCESS_DOC;
+use crate::member::MemberId;
+use crate::message::{Content, MessagePayload};
+use crate::realm::Realm;
+use crate::tombstone::SYNC_HORIZON_DOC;
+use indras_core::InterfaceEvent;
+use indras_transport::IrohIdentity;
+use std::collections::BTreeSet;
+
+/// Documents that describe one realm's own bookkeeping and are never
+/// carried over: the document registry is rebuilt, and sync horizons and
+/// guest grants belong to the source's membership.
+const REALM_LOCAL_DOCS: &[&str] = &[REGISTRY_DOC, SYNC_HORIZON_DOC, GUEST_ACCESS_DOC];
+
+/// Name of the realm's document registry.
+pub(crate) const REGISTRY_DOC: &str = "_registry";
+
+/// What kind of traffic an event payload carries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayKind {
+    /// An update to the named document.
+    Document(String),
+    /// A shared-artifact announcement.
+    Artifact,
+    /// Any other realm message.
+    Message,
+    /// Not recognized; never replayed.
+    Other,
+}
+
+impl ReplayKind {
+    /// Classify a raw event payload.
+    pub fn classify(content: &[u8]) -> Self {
+        if let Some(name) = event_document_name(content) {
+            return ReplayKind::Document(name);
+        }
+        if let Some(payload) = MessagePayload::from_bytes(content) {
+            return match payload.content {
+                Content::Artifact(_) | Content::ArtifactRecalled { .. } => ReplayKind::Artifact,
+                _ => ReplayKind::Message,
+            };
+        }
+        match postcard::from_bytes::<InterfaceEvent<IrohIdentity>>(content) {
+            Ok(InterfaceEvent::Custom { event_type, .. }) if event_type == "artifact:shared" => {
+                ReplayKind::Artifact
+            }
+            _ => ReplayKind::Other,
+        }
+    }
+
+    /// The document this payload updates, if any.
+    pub fn document(&self) -> Option<&str> {
+        match self {
+            ReplayKind::Document(name) => Some(name),
+            _ => None,
+        }
+    }
+}
+
+/// Selects what a split carries into the new realm.
+///
+/// # Example
+///
+/// ```ignore
+/// let filter = SplitFilter::documents(["chat", "quests"])
+///     .with_artifacts()
+///     .members([alice, bob]);
+/// let split = network.split_realm(&realm, "Research", filter).await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SplitFilter {
+    /// Names of documents to copy.
+    pub documents: BTreeSet<String>,
+    /// Whether to copy plain messages.
+    pub messages: bool,
+    /// Whether to copy artifact announcements.
+    pub artifacts: bool,
+    /// Members to re-invite; `None` re-invites every source member.
+    pub members: Option<BTreeSet<MemberId>>,
+}
+
+impl SplitFilter {
+    /// Copy the named documents and nothing else.
+    pub fn documents<I, S>(names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            documents: names.into_iter().map(Into::into).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Also copy plain messages.
+    pub fn with_messages(mut self) -> Self {
+        self.messages = true;
+        self
+    }
+
+    /// Also copy artifact announcements.
+    pub fn with_artifacts(mut self) -> Self {
+        self.artifacts = true;
+        self
+    }
+
+    /// Only re-invite these members.
+    pub fn members(mut self, members: impl IntoIterator<Item = MemberId>) -> Self {
+        self.members = Some(members.into_iter().collect());
+        self
+    }
+
+    /// Whether an event of this kind is carried over.
+    pub fn includes(&self, kind: &ReplayKind) -> bool {
+        match kind {
+            ReplayKind::Document(name) => self.documents.contains(name),
+            ReplayKind::Artifact => self.artifacts,
+            ReplayKind::Message => self.messages,
+            ReplayKind::Other => false,
+        }
+    }
+
+    /// Whether a source member should be re-invited.
+    pub fn invites(&self, member: &MemberId) -> bool {
+        self.members.as_ref().is_none_or(|m| m.contains(member))
+    }
+}
+
+/// Whether a merge carries an event of this kind over.
+pub(crate) fn merge_includes(kind: &ReplayKind) -> bool {
+    match kind {
+        ReplayKind::Document(name) => !REALM_LOCAL_DOCS.contains(&name.as_str()),
+        ReplayKind::Artifact | ReplayKind::Message => true,
+        ReplayKind::Other => false,
+    }
+}
+
+/// Outcome of a merge or split.
+pub struct RealmRestructure {
+    /// The newly created realm.
+    pub realm: Realm,
+    /// Number of events replayed into it.
+    pub replayed: usize,
+    /// Documents that received at least one update.
+    pub documents: BTreeSet<String>,
+    /// Members sent an invitation.
+    pub invited: Vec<MemberId>,
+    /// Members that could not be invited (e.g. no known ML-KEM key).
+    pub not_invited: Vec<MemberId>,
+}
+
+impl std::fmt::Debug for RealmRestructure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RealmRestructure")
+            .field("realm", &self.realm.id())
+            .field("replayed", &self.replayed)
+            .field("documents", &self.documents)
+            .field("invited", &self.invited.len())
+            .field("not_invited", &self.not_invited.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::encode_envelope;
+    use crate::document_registry::DocumentRegistryDocument;
+
+    #[test]
+    fn test_classify_document_envelope() {
+        let bytes = encode_envelope("quests", &DocumentRegistryDocument::default());
+        assert_eq!(ReplayKind::classify(&bytes), ReplayKind::Document("quests".into()));
+    }
+
+    #[test]
+    fn test_classify_message() {
+        let payload = MessagePayload::new(Content::System("joined".into()));
+        let bytes = postcard::to_allocvec(&payload).unwrap();
+        assert_eq!(ReplayKind::classify(&bytes), ReplayKind::Message);
+    }
+
+    #[test]
+    fn test_split_filter() {
+        let filter = SplitFilter::documents(["chat"]).with_artifacts().members([[1u8; 32]]);
+        assert!(filter.includes(&ReplayKind::Document("chat".into())));
+        assert!(!filter.includes(&ReplayKind::Document("quests".into())));
+        assert!(filter.includes(&ReplayKind::Artifact));
+        assert!(!filter.includes(&ReplayKind::Message));
+        assert!(filter.invites(&[1u8; 32]));
+        assert!(!filter.invites(&[2u8; 32]));
+        assert!(SplitFilter::default().invites(&[2u8; 32]));
+    }
+
+    #[test]
+    fn test_merge_skips_realm_local_documents() {
+        assert!(merge_includes(&ReplayKind::Document("chat".into())));
+        assert!(!merge_includes(&ReplayKind::Document("_registry".into())));
+        assert!(!merge_includes(&ReplayKind::Document("_guest_access".into())));
+        assert!(!merge_includes(&ReplayKind::Other));
+    }
+}
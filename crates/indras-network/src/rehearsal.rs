@@ -0,0 +1,161 @@
+//! Rehearsal mode: a local-only copy of a realm for trying things out.
+//!
+//! [`IndrasNetwork::rehearse`](crate::IndrasNetwork::rehearse) clones a
+//! realm's history into a sandbox interface on this node. The sandbox is
+//! never persisted, never joins gossip, and is skipped by the sync task, so
+//! nothing done in it reaches peers. The sandbox is a regular [`Realm`]:
+//! documents, chat, and extension traits all work against it unchanged.
+//!
+//! When done, either [`Rehearsal::discard`] the sandbox or
+//! [`Rehearsal::apply`] a selection of what changed back to the source
+//! realm. Changes are replayed the same way a split replays history, so
+//! each document reconciles through its own
+//! [`DocumentSchema::merge`](crate::document::DocumentSchema::merge).
+
+use crate::error::Result;
+use crate::realm::Realm;
+use crate::realm_restructure::{ReplayKind, SplitFilter};
+use indras_core::InterfaceEvent;
+use indras_node::IndrasNode;
+use indras_transport::IrohIdentity;
+use std::sync::Arc;
+
+/// A sandboxed, local-only copy of a realm.
+///
+/// # Example
+///
+/// ```ignore
+/// let rehearsal = network.rehearse(&realm).await?;
+/// let quests = rehearsal.sandbox().document::<QuestDocument>("quests").await?;
+/// quests.update(|q| q.add(draft)).await?;
+///
+/// // Keep the quest changes, drop everything else.
+/// rehearsal.apply(&SplitFilter::documents(["quests"])).await?;
+/// rehearsal.discard().await?;
+/// ```
+pub struct Rehearsal {
+    source: Realm,
+    sandbox: Realm,
+    baseline: usize,
+    node: Arc<IndrasNode>,
+}
+
+impl Rehearsal {
+    pub(crate) fn new(source: Realm, sandbox: Realm, baseline: usize, node: Arc<IndrasNode>) -> Self {
+        Self {
+            source,
+            sandbox,
+            baseline,
+            node,
+        }
+    }
+
+    /// The realm being rehearsed.
+    pub fn source(&self) -> &Realm {
+        &self.source
+    }
+
+    /// The local-only copy. Nothing done here is synced.
+    pub fn sandbox(&self) -> &Realm {
+        &self.sandbox
+    }
+
+    /// Kinds of the changes made in the sandbox since it was cloned.
+    pub async fn changes(&self) -> Result<Vec<ReplayKind>> {
+        let events = self.node.document_events(&self.sandbox.id()).await?;
+        Ok(rehearsed_payloads(events, self.baseline)
+            .into_iter()
+            .map(|(kind, _)| kind)
+            .collect())
+    }
+
+    /// Replay the sandbox changes selected by `filter` into the source realm.
+    ///
+    /// Returns the number of events applied. The sandbox is left as is, so
+    /// this can be called again with a different filter; already-applied
+    /// document updates merge idempotently.
+    pub async fn apply(&self, filter: &SplitFilter) -> Result<usize> {
+        let events = self.node.document_events(&self.sandbox.id()).await?;
+        let mut applied = 0;
+        for (kind, content) in rehearsed_payloads(events, self.baseline) {
+            if filter.includes(&kind) {
+                self.node.send_message(&self.source.id(), content).await?;
+                applied += 1;
+            }
+        }
+        Ok(applied)
+    }
+
+    /// Drop the sandbox and everything done in it.
+    pub async fn discard(self) -> Result<()> {
+        self.node.leave_interface(&self.sandbox.id()).await?;
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for Rehearsal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Rehearsal")
+            .field("source", &self.source.id())
+            .field("sandbox", &self.sandbox.id())
+            .field("baseline", &self.baseline)
+            .finish()
+    }
+}
+
+/// Message payloads appended after the first `baseline` messages.
+pub(crate) fn rehearsed_payloads(
+    events: Vec<InterfaceEvent<IrohIdentity>>,
+    baseline: usize,
+) -> Vec<(ReplayKind, Vec<u8>)> {
+    events
+        .into_iter()
+        .filter_map(|event| match event {
+            InterfaceEvent::Message { content, .. } => Some(content),
+            _ => None,
+        })
+        .skip(baseline)
+        .map(|content| (ReplayKind::classify(&content), content))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::encode_envelope;
+    use crate::document_registry::DocumentRegistryDocument;
+    use crate::message::{Content, MessagePayload};
+
+    fn message(seq: u64, content: Vec<u8>) -> InterfaceEvent<IrohIdentity> {
+        let sender = IrohIdentity::new(iroh::SecretKey::generate(&mut rand::rng()).public());
+        InterfaceEvent::message(sender, seq, content)
+    }
+
+    #[test]
+    fn test_rehearsed_payloads_skip_baseline() {
+        let chat = postcard::to_allocvec(&MessagePayload::new(Content::System("hi".into()))).unwrap();
+        let quests = encode_envelope("quests", &DocumentRegistryDocument::default());
+        let events = vec![
+            message(1, chat.clone()),
+            message(2, chat.clone()),
+            message(3, quests.clone()),
+        ];
+
+        let changes = rehearsed_payloads(events, 2);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].0, ReplayKind::Document("quests".into()));
+        assert_eq!(changes[0].1, quests);
+    }
+
+    #[test]
+    fn test_filter_selects_changes() {
+        let chat = postcard::to_allocvec(&MessagePayload::new(Content::System("hi".into()))).unwrap();
+        let quests = encode_envelope("quests", &DocumentRegistryDocument::default());
+        let changes = rehearsed_payloads(vec![message(1, chat), message(2, quests)], 0);
+
+        let filter = SplitFilter::documents(["quests"]);
+        let kept: Vec<_> = changes.iter().filter(|(k, _)| filter.includes(k)).collect();
+        assert_eq!(kept.len(), 1);
+        assert!(rehearsed_payloads(vec![], 3).is_empty());
+    }
+}
@@ -0,0 +1,439 @@
+//! Encryption and membership security summary for a realm.
+//!
+//! [`Realm::security_info`](crate::Realm::security_info) collects what a
+//! security details panel shows in one call: the realm key, how far each
+//! member is verified, and the history of admissions and guest invites.
+//!
+//! [`RealmKeyInfo`] reports the key's epoch: 0 for the key the realm was
+//! created or joined with, one more for each rotation in the realm's
+//! [`KeyRotationDocument`](crate::KeyRotationDocument), whose history it
+//! lists.
+
+use crate::guest_access::GuestGrant;
+use crate::key_rotation::KeyRotation;
+use crate::key_transparency::KeyChangeStatus;
+use crate::member::{Member, MemberId};
+use crate::membership_log::{MembershipAction, MembershipRecord};
+use crate::network::RealmId;
+
+use indras_node::{MemberTrust, TrustPolicy};
+use std::collections::BTreeSet;
+
+/// The realm's interface key, as held by this node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RealmKeyInfo {
+    /// Whether this node holds an interface key for the realm.
+    pub encrypted: bool,
+    /// Short hex digest of the key, for comparing devices without
+    /// revealing the key itself.
+    pub fingerprint: Option<String>,
+    /// When the key was established (milliseconds since epoch): when it
+    /// was rotated in, or for epoch 0 when this node first recorded the
+    /// realm.
+    pub established_at_millis: Option<u64>,
+    /// Whether the key is derived from the realm's artifact ID, which
+    /// means anyone who ever held an invite can derive it.
+    pub invite_derived: bool,
+    /// Key generation: 0 until the realm key is first rotated.
+    pub epoch: u64,
+    /// Rotations signed by a current admin, oldest first.
+    pub rotations: Vec<KeyRotation>,
+}
+
+impl RealmKeyInfo {
+    /// Fingerprint of raw key bytes: the first 8 bytes of their BLAKE3
+    /// hash, hex-encoded.
+    pub fn fingerprint_of(key: &[u8]) -> String {
+        hex::encode(&blake3::hash(key).as_bytes()[..8])
+    }
+}
+
+/// How far a member's post-quantum identity is confirmed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PqVerification {
+    /// The member has not announced an ML-DSA verifying key.
+    NoKeys,
+    /// A key was announced, but the membership log neither pins a key for
+    /// the member nor has entries they signed.
+    Announced,
+    /// The announced key is the one the membership log pinned for the
+    /// member and signed their accepted entries.
+    Verified,
+    /// The membership log pinned, or the member signed entries with, a
+    /// different key than the one they announce.
+    Mismatch,
+}
+
+impl PqVerification {
+    /// Compare a member's announced verifying key with the key the
+    /// membership log pinned for them (`MembershipState::keys`) and the
+    /// keys that signed their accepted membership-log entries.
+    pub fn check(
+        member: &MemberId,
+        announced_key: Option<&[u8]>,
+        pinned_key: Option<&[u8]>,
+        history: &[MembershipRecord],
+    ) -> Self {
+        let Some(announced) = announced_key else {
+            return Self::NoKeys;
+        };
+        let mut keys = history
+            .iter()
+            .filter(|r| r.accepted && r.entry.entry.actor == *member)
+            .map(|r| r.entry.verifying_key.as_slice())
+            .chain(pinned_key)
+            .peekable();
+        if keys.peek().is_none() {
+            return Self::Announced;
+        }
+        if keys.all(|k| k == announced) {
+            Self::Verified
+        } else {
+            Self::Mismatch
+        }
+    }
+}
+
+/// Security status of one realm member.
+#[derive(Debug, Clone)]
+pub struct MemberSecurity {
+    /// The member.
+    pub member: Member,
+    /// Whether the member announced both ML-KEM and ML-DSA keys.
+    pub has_pq_keys: bool,
+    /// Post-quantum identity check against the membership log.
+    pub pq: PqVerification,
+    /// Status of the member's latest key log entry, if they published
+    /// one.
+    pub key_change: Option<KeyChangeStatus>,
+    /// Organization certificate check, if the member announced a
+    /// verifying key to check.
+    pub trust: Option<MemberTrust>,
+    /// Whether the signed membership log lists the member.
+    pub in_signed_log: bool,
+    /// Whether the signed membership log lists the member as an admin.
+    pub admin: bool,
+}
+
+/// The organization root a realm is pinned to on this device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrustRootInfo {
+    /// Organization ID (hash of the root verifying key).
+    pub org_id: [u8; 32],
+    /// What happens to members without a certificate.
+    pub policy: TrustPolicy,
+}
+
+/// What happened in an [`InviteEvent`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InviteEventKind {
+    /// The membership log was started; the actor became its first admin.
+    Created,
+    /// A member was admitted.
+    Added {
+        /// The new member.
+        member: MemberId,
+        /// Whether they were admitted as an admin.
+        admin: bool,
+    },
+    /// A member left or was removed.
+    Removed {
+        /// The member removed.
+        member: MemberId,
+    },
+    /// Ownership changed hands.
+    OwnerChanged {
+        /// The new owner.
+        owner: MemberId,
+        /// Whether a successor claimed it after the owner went silent or
+        /// left.
+        succession: bool,
+    },
+    /// An outside peer was invited as a read-only guest.
+    Guest {
+        /// The guest.
+        guest: MemberId,
+        /// Documents the guest may read.
+        documents: BTreeSet<String>,
+        /// When access lapses (milliseconds since epoch).
+        expires_at_millis: u64,
+        /// Whether a member revoked the grant early.
+        revoked: bool,
+    },
+}
+
+/// One entry of a realm's invite history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InviteEvent {
+    /// When it happened (milliseconds since epoch).
+    pub at_millis: u64,
+    /// Who did it.
+    pub actor: MemberId,
+    /// What happened.
+    pub kind: InviteEventKind,
+    /// Whether it took effect; membership-log entries that fail the
+    /// log's authorization rules are listed but not accepted.
+    pub accepted: bool,
+}
+
+/// Builds the invite history from the membership log and guest grants,
+/// oldest first. Succession lists and owner heartbeats are left out.
+pub fn invite_history(history: &[MembershipRecord], guests: &[GuestGrant]) -> Vec<InviteEvent> {
+    let log = history.iter().filter_map(|record| {
+        let entry = &record.entry.entry;
+        let kind = match &entry.action {
+            MembershipAction::Create => InviteEventKind::Created,
+            MembershipAction::Add { member, admin, .. } => InviteEventKind::Added {
+                member: *member,
+                admin: *admin,
+            },
+            MembershipAction::Remove { member } => InviteEventKind::Removed { member: *member },
+            MembershipAction::TransferOwnership { to } => InviteEventKind::OwnerChanged {
+                owner: *to,
+                succession: false,
+            },
+            MembershipAction::ClaimOwnership => InviteEventKind::OwnerChanged {
+                owner: entry.actor,
+                succession: true,
+            },
+            MembershipAction::SetSuccession { .. } | MembershipAction::OwnerHeartbeat => {
+                return None;
+            }
+        };
+        Some(InviteEvent {
+            at_millis: entry.at_millis,
+            actor: entry.actor,
+            kind,
+            accepted: record.accepted,
+        })
+    });
+    let invites = guests.iter().map(|grant| InviteEvent {
+        at_millis: grant.grant.granted_at.max(0) as u64,
+        actor: grant.grant.granted_by,
+        kind: InviteEventKind::Guest {
+            guest: grant.guest(),
+            documents: grant.documents.clone(),
+            expires_at_millis: grant.expires_at_millis(),
+            revoked: grant.revoked,
+        },
+        accepted: true,
+    });
+    let mut events: Vec<InviteEvent> = log.chain(invites).collect();
+    events.sort_by_key(|e| e.at_millis);
+    events
+}
+
+/// Everything a realm security details panel shows.
+///
+/// # Example
+///
+/// ```ignore
+/// let info = realm.security_info().await?;
+/// println!("key {:?} since {:?}", info.key.fingerprint, info.key.established_at_millis);
+/// for m in &info.members {
+///     println!("{}: {:?}", m.member.name(), m.pq);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RealmSecurityInfo {
+    /// The realm.
+    pub realm_id: RealmId,
+    /// The realm key.
+    pub key: RealmKeyInfo,
+    /// Organization root, if this device pinned one.
+    pub trust_root: Option<TrustRootInfo>,
+    /// Known members other than this node.
+    pub members: Vec<MemberSecurity>,
+    /// Admissions, removals and guest invites, oldest first.
+    pub invites: Vec<InviteEvent>,
+    /// Membership-log entries that failed signature or authorization
+    /// checks.
+    pub invalid_log_entries: usize,
+}
+
+impl RealmSecurityInfo {
+    /// Members whose post-quantum identity is confirmed.
+    pub fn verified_members(&self) -> impl Iterator<Item = &MemberSecurity> {
+        self.members
+            .iter()
+            .filter(|m| m.pq == PqVerification::Verified)
+    }
+
+    /// Members that need attention: key mismatches, unverified key
+    /// changes, or members an organization root flags or rejects.
+    pub fn suspicious_members(&self) -> impl Iterator<Item = &MemberSecurity> {
+        self.members.iter().filter(|m| {
+            m.pq == PqVerification::Mismatch
+                || m.key_change.is_some_and(|s| !s.is_trusted())
+                || matches!(m.trust, Some(MemberTrust::Flagged | MemberTrust::Rejected))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::access::{AccessGrant, AccessMode};
+    use crate::membership_log::{MembershipEntry, SignedMembershipEntry};
+    use indras_crypto::pq_identity::PQIdentity;
+
+    fn record(
+        actor: MemberId,
+        action: MembershipAction,
+        at_millis: u64,
+        identity: &PQIdentity,
+        accepted: bool,
+    ) -> MembershipRecord {
+        let entry = MembershipEntry {
+            parents: Vec::new(),
+            seq: 0,
+            actor,
+            action,
+            at_millis,
+        };
+        let signed = SignedMembershipEntry::sign(entry, identity);
+        MembershipRecord {
+            hash: signed.hash(),
+            entry: signed,
+            accepted,
+        }
+    }
+
+    #[test]
+    fn test_pq_verification() {
+        let (alice, bob) = ([1; 32], [2; 32]);
+        let (alice_key, other_key) = (PQIdentity::generate(), PQIdentity::generate());
+        let announced = alice_key.verifying_key_bytes();
+        let history = vec![
+            record(alice, MembershipAction::Create, 1, &alice_key, true),
+            record(
+                alice,
+                MembershipAction::Add {
+                    member: bob,
+                    admin: false,
+                    key: other_key.verifying_key_bytes(),
+                },
+                2,
+                &alice_key,
+                true,
+            ),
+        ];
+
+        let bob_key = other_key.verifying_key_bytes();
+        let pinned = Some(bob_key.as_slice());
+        assert_eq!(
+            PqVerification::check(&alice, None, None, &history),
+            PqVerification::NoKeys
+        );
+        assert_eq!(
+            PqVerification::check(&alice, Some(announced.as_slice()), None, &history),
+            PqVerification::Verified
+        );
+        // Bob never signed anything and has no pinned key
+        assert_eq!(
+            PqVerification::check(&bob, Some(announced.as_slice()), None, &history),
+            PqVerification::Announced
+        );
+        // Added by an admin, who pinned his key
+        assert_eq!(
+            PqVerification::check(&bob, pinned, pinned, &history),
+            PqVerification::Verified
+        );
+        assert_eq!(
+            PqVerification::check(&bob, Some(announced.as_slice()), pinned, &history),
+            PqVerification::Mismatch
+        );
+        // Rejected entries don't count
+        let forged = vec![record(
+            bob,
+            MembershipAction::Remove { member: alice },
+            3,
+            &other_key,
+            false,
+        )];
+        assert_eq!(
+            PqVerification::check(&bob, Some(announced.as_slice()), None, &forged),
+            PqVerification::Announced
+        );
+        let mut mixed = history.clone();
+        mixed.push(record(
+            alice,
+            MembershipAction::Remove { member: bob },
+            3,
+            &other_key,
+            true,
+        ));
+        assert_eq!(
+            PqVerification::check(&alice, Some(announced.as_slice()), None, &mixed),
+            PqVerification::Mismatch
+        );
+    }
+
+    #[test]
+    fn test_invite_history_merges_log_and_guests() {
+        let (alice, bob, guest) = ([1; 32], [2; 32], [9; 32]);
+        let key = PQIdentity::generate();
+        let history = vec![
+            record(alice, MembershipAction::Create, 10, &key, true),
+            record(
+                alice,
+                MembershipAction::Add {
+                    member: bob,
+                    admin: true,
+                    key: key.verifying_key_bytes(),
+                },
+                30,
+                &key,
+                true,
+            ),
+            record(
+                bob,
+                MembershipAction::Remove { member: alice },
+                40,
+                &key,
+                false,
+            ),
+        ];
+        let grant = GuestGrant {
+            grant: AccessGrant {
+                grantee: guest,
+                mode: AccessMode::Timed { expires_at: 500 },
+                granted_at: 20,
+                granted_by: alice,
+            },
+            documents: BTreeSet::from(["notes".to_string()]),
+            revoked: true,
+            updated_at_millis: 25,
+        };
+
+        let events = invite_history(&history, &[grant]);
+        let at: Vec<u64> = events.iter().map(|e| e.at_millis).collect();
+        assert_eq!(at, vec![10, 20, 30, 40]);
+        assert_eq!(events[0].kind, InviteEventKind::Created);
+        assert_eq!(
+            events[1].kind,
+            InviteEventKind::Guest {
+                guest,
+                documents: BTreeSet::from(["notes".to_string()]),
+                expires_at_millis: 500,
+                revoked: true,
+            }
+        );
+        assert_eq!(
+            events[2].kind,
+            InviteEventKind::Added {
+                member: bob,
+                admin: true
+            }
+        );
+        assert!(!events[3].accepted);
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable_and_short() {
+        let a = RealmKeyInfo::fingerprint_of(&[7; 32]);
+        assert_eq!(a.len(), 16);
+        assert_eq!(a, RealmKeyInfo::fingerprint_of(&[7; 32]));
+        assert_ne!(a, RealmKeyInfo::fingerprint_of(&[8; 32]));
+    }
+}
@@ -0,0 +1,259 @@
+//! Cross-device settings stored in the home realm.
+//!
+//! Each app keeps its own namespaced section ("workspace", "chat",
+//! "genesis", ...) inside a single [`SettingsDocument`]. Because the
+//! document lives in the home realm, every device belonging to the same
+//! member sees the same settings. Merges are last-writer-wins per key, so
+//! two devices editing different keys never clobber each other.
+//!
+//! Values are stored as JSON strings so each app can use its own types
+//! without the document needing to know about them.
+
+use crate::document::DocumentSchema;
+use crate::util::now_millis;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Document name for the settings document in the home realm.
+pub const SETTINGS_DOC: &str = "settings";
+
+/// A single setting value with its write time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SettingEntry {
+    /// JSON-encoded value, or `None` if the key was removed.
+    pub value: Option<String>,
+    /// When the value was written (milliseconds since epoch).
+    pub updated_at_millis: u64,
+}
+
+impl SettingEntry {
+    /// Whether this entry should replace `other` under LWW.
+    ///
+    /// Ties on timestamp are broken by comparing values so that every
+    /// device converges on the same winner.
+    fn supersedes(&self, other: &SettingEntry) -> bool {
+        (self.updated_at_millis, &self.value) > (other.updated_at_millis, &other.value)
+    }
+}
+
+/// A setting that changed between two document states.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SettingChange {
+    /// The app namespace the key belongs to.
+    pub namespace: String,
+    /// The setting key.
+    pub key: String,
+    /// New JSON value, or `None` if the key was removed.
+    pub value: Option<String>,
+}
+
+/// CRDT document holding namespaced, per-key LWW settings.
+///
+/// # Example
+///
+/// ```ignore
+/// let settings = home.settings().await?;
+/// settings.update(|s| s.set("workspace", "theme", &"dark")).await?;
+///
+/// let theme: Option<String> = settings.read().await.get("workspace", "theme");
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SettingsDocument {
+    /// Namespace → key → entry.
+    pub sections: BTreeMap<String, BTreeMap<String, SettingEntry>>,
+}
+
+impl SettingsDocument {
+    /// Create an empty settings document.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a value, serialized as JSON, stamped with the current time.
+    pub fn set<T: Serialize + ?Sized>(&mut self, namespace: &str, key: &str, value: &T) {
+        let json = serde_json::to_string(value).ok();
+        self.write(namespace, key, json, now_millis());
+    }
+
+    /// Remove a key. The removal syncs like any other write.
+    pub fn remove(&mut self, namespace: &str, key: &str) {
+        self.write(namespace, key, None, now_millis());
+    }
+
+    /// Write a raw entry if it is newer than the current one.
+    ///
+    /// Returns `true` if the entry was stored.
+    pub fn write(
+        &mut self,
+        namespace: &str,
+        key: &str,
+        value: Option<String>,
+        updated_at_millis: u64,
+    ) -> bool {
+        let entry = SettingEntry {
+            value,
+            updated_at_millis,
+        };
+        let section = self.sections.entry(namespace.to_string()).or_default();
+        match section.get(key) {
+            Some(existing) if !entry.supersedes(existing) => false,
+            _ => {
+                section.insert(key.to_string(), entry);
+                true
+            }
+        }
+    }
+
+    /// Read a value, deserializing from JSON.
+    ///
+    /// Returns `None` if the key is missing, removed, or of a different type.
+    pub fn get<T: DeserializeOwned>(&self, namespace: &str, key: &str) -> Option<T> {
+        let json = self.get_raw(namespace, key)?;
+        serde_json::from_str(json).ok()
+    }
+
+    /// Read a value's raw JSON.
+    pub fn get_raw(&self, namespace: &str, key: &str) -> Option<&str> {
+        self.sections
+            .get(namespace)?
+            .get(key)?
+            .value
+            .as_deref()
+    }
+
+    /// Whether a live (non-removed) value exists for a key.
+    pub fn contains(&self, namespace: &str, key: &str) -> bool {
+        self.get_raw(namespace, key).is_some()
+    }
+
+    /// Live keys and raw JSON values in one namespace, in key order.
+    pub fn section(&self, namespace: &str) -> Vec<(&str, &str)> {
+        self.sections
+            .get(namespace)
+            .map(|section| {
+                section
+                    .iter()
+                    .filter_map(|(k, e)| Some((k.as_str(), e.value.as_deref()?)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// All namespaces that have ever been written.
+    pub fn namespaces(&self) -> Vec<&str> {
+        self.sections.keys().map(String::as_str).collect()
+    }
+
+    /// Settings in `namespace` (or all namespaces if `None`) that differ
+    /// between `self` and a newer state.
+    pub fn changes_to(&self, newer: &SettingsDocument, namespace: Option<&str>) -> Vec<SettingChange> {
+        let mut changes = Vec::new();
+        for (ns, section) in &newer.sections {
+            if namespace.is_some_and(|n| n != ns) {
+                continue;
+            }
+            let old = self.sections.get(ns);
+            for (key, entry) in section {
+                if old.and_then(|s| s.get(key)) != Some(entry) {
+                    changes.push(SettingChange {
+                        namespace: ns.clone(),
+                        key: key.clone(),
+                        value: entry.value.clone(),
+                    });
+                }
+            }
+        }
+        changes
+    }
+}
+
+impl DocumentSchema for SettingsDocument {
+    /// Per-key LWW merge across all namespaces.
+    fn merge(&mut self, remote: Self) {
+        for (namespace, section) in remote.sections {
+            for (key, entry) in section {
+                self.write(&namespace, &key, entry.value, entry.updated_at_millis);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_and_get_typed() {
+        let mut doc = SettingsDocument::new();
+        doc.set("workspace", "theme", "dark");
+        doc.set("chat", "notify", &true);
+
+        assert_eq!(doc.get::<String>("workspace", "theme").as_deref(), Some("dark"));
+        assert_eq!(doc.get::<bool>("chat", "notify"), Some(true));
+        assert_eq!(doc.get::<bool>("workspace", "notify"), None);
+    }
+
+    #[test]
+    fn test_namespaces_are_isolated() {
+        let mut doc = SettingsDocument::new();
+        doc.write("workspace", "theme", Some("\"dark\"".into()), 1);
+        doc.write("genesis", "theme", Some("\"light\"".into()), 1);
+
+        assert_eq!(doc.section("workspace"), vec![("theme", "\"dark\"")]);
+        assert_eq!(doc.namespaces(), vec!["genesis", "workspace"]);
+    }
+
+    #[test]
+    fn test_older_write_is_ignored() {
+        let mut doc = SettingsDocument::new();
+        assert!(doc.write("chat", "k", Some("2".into()), 20));
+        assert!(!doc.write("chat", "k", Some("1".into()), 10));
+        assert_eq!(doc.get::<u32>("chat", "k"), Some(2));
+    }
+
+    #[test]
+    fn test_remove_is_tombstoned() {
+        let mut doc = SettingsDocument::new();
+        doc.write("chat", "k", Some("1".into()), 10);
+        doc.write("chat", "k", None, 20);
+        assert!(!doc.contains("chat", "k"));
+        assert!(doc.section("chat").is_empty());
+    }
+
+    #[test]
+    fn test_merge_is_per_key_lww() {
+        let mut a = SettingsDocument::new();
+        a.write("workspace", "theme", Some("\"dark\"".into()), 10);
+        a.write("workspace", "zoom", Some("1".into()), 30);
+
+        let mut b = SettingsDocument::new();
+        b.write("workspace", "theme", Some("\"light\"".into()), 20);
+        b.write("workspace", "zoom", Some("2".into()), 5);
+
+        let mut ab = a.clone();
+        ab.merge(b.clone());
+        let mut ba = b;
+        ba.merge(a);
+
+        assert_eq!(ab.get::<String>("workspace", "theme").as_deref(), Some("light"));
+        assert_eq!(ab.get::<u32>("workspace", "zoom"), Some(1));
+        assert_eq!(ab.sections, ba.sections);
+    }
+
+    #[test]
+    fn test_changes_to_filters_namespace() {
+        let old = SettingsDocument::new();
+        let mut new = old.clone();
+        new.write("chat", "a", Some("1".into()), 1);
+        new.write("workspace", "b", Some("2".into()), 1);
+
+        let all = old.changes_to(&new, None);
+        assert_eq!(all.len(), 2);
+
+        let chat = old.changes_to(&new, Some("chat"));
+        assert_eq!(chat.len(), 1);
+        assert_eq!(chat[0].key, "a");
+        assert!(new.changes_to(&new, None).is_empty());
+    }
+}
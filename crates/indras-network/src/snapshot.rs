@@ -0,0 +1,250 @@
+//! Snapshot-consistent realm reads.
+//!
+//! A [`RealmSnapshot`] freezes the realm's CRDT state at one instant.
+//! Everything read from it — messages, members, typed documents — is
+//! derived from the same set of Automerge events, so a render pass never
+//! mixes pre- and post-merge state.
+
+use crate::document::{apply_event_content, DocumentSchema};
+use crate::member::{Member, MemberId};
+use crate::message::Message;
+use crate::network::RealmId;
+use crate::realm::convert_event_to_message;
+
+use chrono::{DateTime, Utc};
+use indras_core::InterfaceEvent;
+use indras_node::{InterfaceSnapshot, ReceivedEvent};
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// An immutable, point-in-time view of a realm.
+///
+/// Cheap to clone: clones share the captured events and any documents
+/// already decoded from them.
+///
+/// # Example
+///
+/// ```ignore
+/// let snap = realm.snapshot().await?;
+/// for msg in snap.messages() {
+///     println!("{}: {:?}", msg.sender.name(), msg.content);
+/// }
+/// let notes = snap.document::<NotesDocument>("notes");
+/// ```
+#[derive(Clone)]
+pub struct RealmSnapshot {
+    inner: Arc<SnapshotInner>,
+}
+
+struct SnapshotInner {
+    realm_id: RealmId,
+    taken_at: DateTime<Utc>,
+    heads: Vec<[u8; 32]>,
+    events: Vec<InterfaceEvent<indras_transport::IrohIdentity>>,
+    members: Vec<Member>,
+    messages: Vec<Message>,
+    /// Documents decoded on first access, keyed by (type, name)
+    documents: Mutex<HashMap<(TypeId, String), Arc<dyn Any + Send + Sync>>>,
+}
+
+impl RealmSnapshot {
+    /// Build a snapshot from a node-level interface snapshot.
+    pub(crate) fn from_interface_snapshot(snapshot: InterfaceSnapshot) -> Self {
+        let realm_id = snapshot.interface_id;
+        let messages = snapshot
+            .events
+            .iter()
+            .cloned()
+            .filter_map(|event| {
+                convert_event_to_message(
+                    ReceivedEvent {
+                        interface_id: realm_id,
+                        event,
+                    },
+                    realm_id,
+                )
+            })
+            .collect();
+
+        Self {
+            inner: Arc::new(SnapshotInner {
+                realm_id,
+                taken_at: Utc::now(),
+                heads: snapshot.heads,
+                events: snapshot.events,
+                members: snapshot.members.into_iter().map(Member::new).collect(),
+                messages,
+                documents: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    /// The realm this snapshot was taken from.
+    pub fn realm_id(&self) -> RealmId {
+        self.inner.realm_id
+    }
+
+    /// When the snapshot was taken.
+    pub fn taken_at(&self) -> DateTime<Utc> {
+        self.inner.taken_at
+    }
+
+    /// Automerge heads identifying the captured state.
+    ///
+    /// Two snapshots with equal heads describe identical realm state,
+    /// which lets UIs skip re-rendering when nothing changed.
+    pub fn heads(&self) -> &[[u8; 32]] {
+        &self.inner.heads
+    }
+
+    /// Whether this snapshot describes the same state as another.
+    pub fn same_state_as(&self, other: &RealmSnapshot) -> bool {
+        self.inner.realm_id == other.inner.realm_id && self.inner.heads == other.inner.heads
+    }
+
+    /// Number of events in the captured document.
+    pub fn event_count(&self) -> usize {
+        self.inner.events.len()
+    }
+
+    /// Messages in the captured document, in document order.
+    pub fn messages(&self) -> &[Message] {
+        &self.inner.messages
+    }
+
+    /// Members recorded in the captured document.
+    pub fn members(&self) -> &[Member] {
+        &self.inner.members
+    }
+
+    /// Whether a member was recorded in the captured document.
+    pub fn is_member(&self, id: &MemberId) -> bool {
+        self.inner.members.iter().any(|m| m.id() == *id)
+    }
+
+    /// Read a typed document as it was when the snapshot was taken.
+    ///
+    /// The document is rebuilt by replaying its updates from the captured
+    /// events, starting from `T::default()`. The result is cached, so
+    /// repeated reads within a render pass are free.
+    pub fn document<T: DocumentSchema>(&self, name: &str) -> Arc<T> {
+        let key = (TypeId::of::<T>(), name.to_string());
+        let mut cache = self
+            .inner
+            .documents
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+
+        if let Some(Ok(doc)) = cache.get(&key).map(|c| Arc::clone(c).downcast::<T>()) {
+            return doc;
+        }
+
+        let mut state = T::default();
+        for event in &self.inner.events {
+            if let InterfaceEvent::Message { content, .. } = event {
+                apply_event_content(&mut state, name, content);
+            }
+        }
+
+        let doc = Arc::new(state);
+        cache.insert(key, Arc::clone(&doc) as Arc<dyn Any + Send + Sync>);
+        doc
+    }
+}
+
+impl std::fmt::Debug for RealmSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RealmSnapshot")
+            .field("realm_id", &self.inner.realm_id)
+            .field("taken_at", &self.inner.taken_at)
+            .field("heads", &self.inner.heads.len())
+            .field("events", &self.inner.events.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::encode_envelope;
+    use crate::read_tracker::ReadTrackerDocument;
+    use indras_core::InterfaceId;
+    use indras_transport::IrohIdentity;
+
+    fn identity() -> IrohIdentity {
+        IrohIdentity::new(iroh::SecretKey::generate(&mut rand::rng()).public())
+    }
+
+    fn message(sender: IrohIdentity, seq: u64, content: Vec<u8>) -> InterfaceEvent<IrohIdentity> {
+        InterfaceEvent::message(sender, seq, content)
+    }
+
+    fn snapshot(events: Vec<InterfaceEvent<IrohIdentity>>, members: Vec<IrohIdentity>) -> RealmSnapshot {
+        RealmSnapshot::from_interface_snapshot(InterfaceSnapshot {
+            interface_id: InterfaceId::new([1u8; 32]),
+            events,
+            members,
+            heads: vec![[9u8; 32]],
+        })
+    }
+
+    #[test]
+    fn test_document_replays_updates_in_order() {
+        let a = identity();
+        let mut first = ReadTrackerDocument::new();
+        first.mark_read([1u8; 32], 3);
+        let mut second = first.clone();
+        second.mark_read([1u8; 32], 7);
+
+        let snap = snapshot(
+            vec![
+                message(a, 1, encode_envelope("read_tracker", &first)),
+                message(a, 2, encode_envelope("read_tracker", &second)),
+            ],
+            vec![a],
+        );
+
+        let doc = snap.document::<ReadTrackerDocument>("read_tracker");
+        assert_eq!(doc.last_read_seq(&[1u8; 32]), 7);
+    }
+
+    #[test]
+    fn test_document_ignores_other_names() {
+        let a = identity();
+        let mut other = ReadTrackerDocument::new();
+        other.mark_read([2u8; 32], 5);
+
+        let snap = snapshot(vec![message(a, 1, encode_envelope("other", &other))], vec![a]);
+        let doc = snap.document::<ReadTrackerDocument>("read_tracker");
+        assert_eq!(doc.last_read_seq(&[2u8; 32]), 0);
+    }
+
+    #[test]
+    fn test_document_is_cached() {
+        let snap = snapshot(vec![], vec![]);
+        let first = snap.document::<ReadTrackerDocument>("read_tracker");
+        let second = snap.document::<ReadTrackerDocument>("read_tracker");
+        assert!(Arc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn test_members_and_equality() {
+        let a = identity();
+        let member = Member::new(a);
+        let snap = snapshot(vec![], vec![a]);
+        assert!(snap.is_member(&member.id()));
+        assert_eq!(snap.members().len(), 1);
+
+        let same = snapshot(vec![], vec![a]);
+        assert!(snap.same_state_as(&same));
+    }
+
+    #[test]
+    fn test_clones_share_state() {
+        let snap = snapshot(vec![], vec![]);
+        let clone = snap.clone();
+        let doc = snap.document::<ReadTrackerDocument>("read_tracker");
+        assert!(Arc::ptr_eq(&doc, &clone.document::<ReadTrackerDocument>("read_tracker")));
+    }
+}
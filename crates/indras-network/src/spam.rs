@@ -0,0 +1,539 @@
+//! Local spam detection for incoming chat messages.
+//!
+//! The [`SpamDetector`] scores each incoming message with a few cheap
+//! heuristics and flags probable spam so apps can demote it (collapse it,
+//! sort it last, skip the notification) instead of hiding it outright:
+//!
+//! - **Rate**: how many messages the sender posted in the last minute,
+//!   against a per-sender allowance that shrinks each time the sender is
+//!   flagged.
+//! - **Duplicates**: how often the same text (case and spacing ignored)
+//!   was seen recently, from anyone, in any realm.
+//! - **Link density**: how much of the message is links.
+//!
+//! How readily a score counts as spam is set by a [`SpamSensitivity`] per
+//! realm in [`SpamSettings`], stored per app in the home realm's settings
+//! like notification preferences. Everything else stays on this device:
+//! nothing about scores or flagged senders is synced.
+//!
+//! Flagged messages are also published as [`SpamVerdict`]s and counted in
+//! each sender's [`SenderStanding`], for apps that keep their own
+//! reputation (e.g. recording a sentiment).
+
+use crate::chat_message::{ChatMessageId, EditableChatMessage};
+use crate::network::RealmId;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Mutex;
+use std::sync::RwLock;
+use tokio::sync::broadcast;
+
+/// Settings key for spam settings, under each app's namespace.
+pub const SPAM_SETTINGS_KEY: &str = "spam";
+
+/// Window of the rate heuristic, in milliseconds.
+const RATE_WINDOW_MILLIS: u64 = 60_000;
+
+/// How long a message's text counts towards duplicates, in milliseconds.
+const DUPLICATE_WINDOW_MILLIS: u64 = 10 * 60_000;
+
+/// Shorter normalized texts ("ok", "thanks!") never count as duplicates.
+const MIN_DUPLICATE_CHARS: usize = 12;
+
+/// Most scored messages remembered before the oldest are forgotten.
+const MAX_TRACKED_MESSAGES: usize = 10_000;
+
+/// Most times a sender's allowance is halved.
+const MAX_RATE_PENALTY: u32 = 3;
+
+/// How readily a realm's messages are flagged as spam.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpamSensitivity {
+    /// Never flag anything.
+    Off,
+    /// Flag only blatant spam.
+    Low,
+    /// A balance suited to most realms.
+    #[default]
+    Medium,
+    /// Flag anything suspicious.
+    High,
+}
+
+impl SpamSensitivity {
+    /// Lowest score flagged as probable spam, or `None` when off.
+    pub fn threshold(self) -> Option<f32> {
+        match self {
+            SpamSensitivity::Off => None,
+            SpamSensitivity::Low => Some(0.85),
+            SpamSensitivity::Medium => Some(0.65),
+            SpamSensitivity::High => Some(0.45),
+        }
+    }
+}
+
+fn default_max_messages_per_minute() -> u32 {
+    12
+}
+
+/// How an app wants incoming messages checked for spam.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpamSettings {
+    /// Sensitivity for realms without an override.
+    #[serde(default)]
+    pub default_sensitivity: SpamSensitivity,
+    /// Per-realm overrides, keyed by hex-encoded realm id.
+    #[serde(default)]
+    pub realms: BTreeMap<String, SpamSensitivity>,
+    /// Messages per minute a sender in good standing may post.
+    #[serde(default = "default_max_messages_per_minute")]
+    pub max_messages_per_minute: u32,
+}
+
+impl Default for SpamSettings {
+    fn default() -> Self {
+        Self {
+            default_sensitivity: SpamSensitivity::default(),
+            realms: BTreeMap::new(),
+            max_messages_per_minute: default_max_messages_per_minute(),
+        }
+    }
+}
+
+impl SpamSettings {
+    /// The sensitivity that applies to a realm.
+    pub fn sensitivity(&self, realm_id: &RealmId) -> SpamSensitivity {
+        self.sensitivity_for(&hex::encode(realm_id.as_bytes()))
+    }
+
+    /// Override the sensitivity for one realm.
+    pub fn set_sensitivity(&mut self, realm_id: &RealmId, sensitivity: SpamSensitivity) {
+        self.realms
+            .insert(hex::encode(realm_id.as_bytes()), sensitivity);
+    }
+
+    /// Drop a realm's override, falling back to the default sensitivity.
+    pub fn clear_sensitivity(&mut self, realm_id: &RealmId) {
+        self.realms.remove(&hex::encode(realm_id.as_bytes()));
+    }
+
+    /// The sensitivity for a hex-encoded realm id, as chat messages carry it.
+    fn sensitivity_for(&self, realm_hex: &str) -> SpamSensitivity {
+        self.realms
+            .get(realm_hex)
+            .copied()
+            .unwrap_or(self.default_sensitivity)
+    }
+}
+
+/// One heuristic that contributed to a score.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpamSignal {
+    /// The sender posted more than their allowance in the last minute.
+    Rate {
+        /// Messages in the window, this one included.
+        count: u32,
+        /// The sender's allowance.
+        allowed: u32,
+    },
+    /// The same text was seen recently.
+    Duplicate {
+        /// How many times before this message.
+        repeats: u32,
+    },
+    /// Much of the message is links.
+    Links {
+        /// Number of links.
+        count: u32,
+        /// Links per word.
+        density: f32,
+    },
+}
+
+impl SpamSignal {
+    /// This signal's contribution, from 0.0 to 1.0.
+    pub fn weight(&self) -> f32 {
+        match *self {
+            SpamSignal::Rate { count, allowed } => {
+                let excess = count.saturating_sub(allowed) as f32 / allowed.max(1) as f32;
+                0.5 + 0.5 * excess.min(1.0)
+            }
+            SpamSignal::Duplicate { repeats } => match repeats {
+                0 => 0.0,
+                1 => 0.4,
+                2 => 0.7,
+                _ => 0.9,
+            },
+            SpamSignal::Links { count, density } => {
+                let weight = (density * 1.2).min(0.8);
+                if count >= 3 { weight.max(0.6) } else { weight }
+            }
+        }
+    }
+}
+
+/// How spam-like a message looks.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpamScore {
+    /// Combined score, from 0.0 (clean) to 1.0.
+    pub score: f32,
+    /// The heuristics that fired.
+    pub signals: Vec<SpamSignal>,
+    /// Whether the score reaches the realm's threshold.
+    pub probable: bool,
+}
+
+/// A message flagged as probable spam.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpamVerdict {
+    /// Hex-encoded realm id of the message.
+    pub realm_id: String,
+    /// The flagged message.
+    pub message_id: ChatMessageId,
+    /// Who sent it (hex member id, or display name for unsigned messages).
+    pub sender: String,
+    /// Its score.
+    pub score: SpamScore,
+}
+
+/// What this device has seen of a sender.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SenderStanding {
+    /// Messages scored.
+    pub scored: u32,
+    /// Messages flagged as probable spam.
+    pub flagged: u32,
+}
+
+impl SenderStanding {
+    /// Share of the sender's messages that were not flagged (1.0 if none
+    /// were scored).
+    pub fn trust(&self) -> f32 {
+        if self.scored == 0 {
+            return 1.0;
+        }
+        1.0 - self.flagged as f32 / self.scored as f32
+    }
+
+    /// Messages per minute the sender may post before the rate heuristic
+    /// fires: the base allowance, halved for each flagged message up to
+    /// three times.
+    pub fn allowance(&self, max_messages_per_minute: u32) -> u32 {
+        (max_messages_per_minute >> self.flagged.min(MAX_RATE_PENALTY)).max(1)
+    }
+}
+
+#[derive(Default)]
+struct DetectorState {
+    /// Scores of messages already seen, so re-rendering never re-counts.
+    scores: HashMap<ChatMessageId, SpamScore>,
+    /// Scored message ids, oldest first, for forgetting.
+    order: VecDeque<ChatMessageId>,
+    /// Recent message times per (realm, sender).
+    recent: HashMap<(String, String), VecDeque<u64>>,
+    /// Recent times each normalized text was seen.
+    texts: HashMap<blake3::Hash, VecDeque<u64>>,
+    standings: HashMap<String, SenderStanding>,
+}
+
+/// Scores incoming messages and remembers what it has seen.
+///
+/// Owned by `IndrasNetwork`; access it via `network.spam()`.
+///
+/// # Example
+///
+/// ```ignore
+/// let spam = network.spam();
+/// spam.set_settings(network.load_spam_settings("chat").await?);
+///
+/// for msg in realm.chat_doc().await?.read().await.visible_messages() {
+///     let demoted = msg.author_id.as_deref() != Some(&my_id) && spam.score(msg).probable;
+///     render(msg, demoted);
+/// }
+/// ```
+pub struct SpamDetector {
+    settings: RwLock<SpamSettings>,
+    state: Mutex<DetectorState>,
+    verdict_tx: broadcast::Sender<SpamVerdict>,
+}
+
+impl Default for SpamDetector {
+    fn default() -> Self {
+        let (verdict_tx, _) = broadcast::channel(64);
+        Self {
+            settings: RwLock::new(SpamSettings::default()),
+            state: Mutex::new(DetectorState::default()),
+            verdict_tx,
+        }
+    }
+}
+
+impl SpamDetector {
+    /// Create a detector with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The settings in use.
+    pub fn settings(&self) -> SpamSettings {
+        self.settings
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Replace the settings. Messages already scored keep their score.
+    pub fn set_settings(&self, settings: SpamSettings) {
+        *self.settings.write().unwrap_or_else(|e| e.into_inner()) = settings;
+    }
+
+    /// Flagged messages, as they are scored.
+    pub fn subscribe(&self) -> broadcast::Receiver<SpamVerdict> {
+        self.verdict_tx.subscribe()
+    }
+
+    /// What this device has seen of a sender.
+    pub fn standing(&self, sender: &str) -> SenderStanding {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.standings.get(sender).copied().unwrap_or_default()
+    }
+
+    /// Score a message, recording it on first sight.
+    ///
+    /// Meant for other members' messages; the message's own creation time
+    /// drives the rate and duplicate windows, so history loaded late is
+    /// judged as it arrived. Scoring the same message again returns the
+    /// first score without counting it twice.
+    pub fn score(&self, message: &EditableChatMessage) -> SpamScore {
+        let settings = self.settings();
+        let sender = message
+            .author_id
+            .clone()
+            .unwrap_or_else(|| message.author.clone());
+        let at = message.created_at;
+
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(score) = state.scores.get(&message.id) {
+            return score.clone();
+        }
+        let Some(threshold) = settings.sensitivity_for(&message.realm_id).threshold() else {
+            return SpamScore::default();
+        };
+
+        let mut signals = Vec::new();
+        let standing = state.standings.get(&sender).copied().unwrap_or_default();
+
+        let allowed = standing.allowance(settings.max_messages_per_minute);
+        let times = state
+            .recent
+            .entry((message.realm_id.clone(), sender.clone()))
+            .or_default();
+        let count = within(times, at, RATE_WINDOW_MILLIS) + 1;
+        record(times, at, RATE_WINDOW_MILLIS);
+        if count > allowed {
+            signals.push(SpamSignal::Rate { count, allowed });
+        }
+
+        let normalized = normalize(&message.current_content);
+        if normalized.chars().count() >= MIN_DUPLICATE_CHARS {
+            let seen = state
+                .texts
+                .entry(blake3::hash(normalized.as_bytes()))
+                .or_default();
+            let repeats = within(seen, at, DUPLICATE_WINDOW_MILLIS);
+            record(seen, at, DUPLICATE_WINDOW_MILLIS);
+            if repeats > 0 {
+                signals.push(SpamSignal::Duplicate { repeats });
+            }
+        }
+
+        let (links, words) = count_links(&message.current_content);
+        if links > 0 {
+            signals.push(SpamSignal::Links {
+                count: links,
+                density: links as f32 / words.max(1) as f32,
+            });
+        }
+
+        // Independent signals combine like probabilities: each one closes
+        // part of the remaining gap to 1.0.
+        let score = 1.0 - signals.iter().map(|s| 1.0 - s.weight()).product::<f32>();
+        let probable = score >= threshold;
+        let result = SpamScore {
+            score,
+            signals,
+            probable,
+        };
+
+        let standing = state.standings.entry(sender.clone()).or_default();
+        standing.scored += 1;
+        if probable {
+            standing.flagged += 1;
+        }
+        state.scores.insert(message.id.clone(), result.clone());
+        state.order.push_back(message.id.clone());
+        if state.order.len() > MAX_TRACKED_MESSAGES {
+            state.forget_oldest(at);
+        }
+        drop(state);
+
+        if probable {
+            let _ = self.verdict_tx.send(SpamVerdict {
+                realm_id: message.realm_id.clone(),
+                message_id: message.id.clone(),
+                sender,
+                score: result.clone(),
+            });
+        }
+        result
+    }
+}
+
+impl DetectorState {
+    /// Forget the oldest scored message and any windows that have lapsed.
+    fn forget_oldest(&mut self, now: u64) {
+        if let Some(id) = self.order.pop_front() {
+            self.scores.remove(&id);
+        }
+        self.recent
+            .retain(|_, times| times.iter().any(|t| t.abs_diff(now) < RATE_WINDOW_MILLIS));
+        self.texts.retain(|_, times| {
+            times
+                .iter()
+                .any(|t| t.abs_diff(now) < DUPLICATE_WINDOW_MILLIS)
+        });
+    }
+}
+
+impl std::fmt::Debug for SpamDetector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpamDetector")
+            .field("settings", &self.settings())
+            .finish()
+    }
+}
+
+/// How many recorded times fall within `window` of `at`, either side.
+///
+/// Either side, because older history can be scored after newer messages.
+fn within(times: &VecDeque<u64>, at: u64, window: u64) -> u32 {
+    times.iter().filter(|t| t.abs_diff(at) < window).count() as u32
+}
+
+/// Record a time, dropping those too old to matter to the newest one.
+fn record(times: &mut VecDeque<u64>, at: u64, window: u64) {
+    times.push_back(at);
+    let newest = times.iter().copied().max().unwrap_or(at);
+    times.retain(|t| newest - t < window);
+}
+
+/// Lowercase text with runs of whitespace collapsed to one space.
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Number of links and of words in a text.
+fn count_links(text: &str) -> (u32, u32) {
+    let mut links = 0;
+    let mut words = 0;
+    for word in text.split_whitespace() {
+        words += 1;
+        let word = word.to_ascii_lowercase();
+        if word.starts_with("http://") || word.starts_with("https://") || word.starts_with("www.") {
+            links += 1;
+        }
+    }
+    (links, words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(id: &str, author: &str, content: &str, at: u64) -> EditableChatMessage {
+        EditableChatMessage::new_text(id.into(), "realm".into(), author.into(), content.into(), at)
+            .with_author_id(author.to_string())
+    }
+
+    #[test]
+    fn test_ordinary_conversation_is_clean() {
+        let detector = SpamDetector::new();
+        let score = detector.score(&text("m1", "ada", "See you at the garden on Friday?", 0));
+        assert!(score.signals.is_empty());
+        assert!(!score.probable);
+        assert!(!detector.score(&text("m2", "bob", "ok", 10)).probable);
+        assert!(!detector.score(&text("m3", "cy", "ok", 20)).probable);
+    }
+
+    #[test]
+    fn test_flooding_tightens_allowance() {
+        let detector = SpamDetector::new();
+        let flagged = (0..30)
+            .map(|i| {
+                let msg = text(
+                    &format!("m{i}"),
+                    "flood",
+                    &format!("message number {i}"),
+                    i * 100,
+                );
+                detector.score(&msg)
+            })
+            .filter(|s| s.probable)
+            .count();
+        assert!(flagged > 0);
+
+        let standing = detector.standing("flood");
+        assert_eq!(standing.scored, 30);
+        assert_eq!(standing.flagged as usize, flagged);
+        assert!(standing.trust() < 1.0);
+        assert_eq!(standing.allowance(12), 1);
+        assert_eq!(detector.standing("someone").allowance(12), 12);
+    }
+
+    #[test]
+    fn test_duplicates_and_links_flag() {
+        let detector = SpamDetector::new();
+        let mut verdicts = detector.subscribe();
+        let pitch = "Free coins at https://scam.example claim now";
+        detector.score(&text("m1", "a", pitch, 0));
+        detector.score(&text("m2", "b", &pitch.to_uppercase(), 1_000));
+        let third = detector.score(&text("m3", "c", pitch, 2_000));
+        assert!(third.probable);
+        assert!(
+            third
+                .signals
+                .contains(&SpamSignal::Duplicate { repeats: 2 })
+        );
+        assert_eq!(verdicts.try_recv().unwrap().message_id, "m3");
+
+        let links = detector.score(&text("m4", "d", "https://a.example https://b.example", 0));
+        assert!(links.probable);
+
+        // Scoring again neither re-counts nor changes the result
+        assert_eq!(detector.score(&text("m3", "c", pitch, 2_000)), third);
+        assert_eq!(detector.standing("c").scored, 1);
+    }
+
+    #[test]
+    fn test_per_realm_sensitivity() {
+        let detector = SpamDetector::new();
+        let link = "check https://example.com";
+        assert!(!detector.score(&text("m1", "a", link, 0)).probable);
+
+        let mut settings = SpamSettings::default();
+        settings
+            .realms
+            .insert("realm".into(), SpamSensitivity::High);
+        detector.set_settings(settings.clone());
+        let other = "see https://example.org";
+        assert!(detector.score(&text("m2", "a", other, 0)).probable);
+
+        settings.realms.insert("realm".into(), SpamSensitivity::Off);
+        detector.set_settings(settings);
+        let off = detector.score(&text("m3", "a", "https://x.example https://y.example", 0));
+        assert_eq!(off, SpamScore::default());
+    }
+}
@@ -0,0 +1,329 @@
+//! Custom emoji and sticker packs.
+//!
+//! A realm's [`StickerPackDocument`] lists its packs; each pack maps
+//! short names to content-addressed images stored as blobs. Messages
+//! reference an entry with [`Content::Sticker`](crate::Content::Sticker)
+//! (or a `:shortcode:` in text and reactions for emoji), and chat UIs
+//! resolve the hash through the network's
+//! [asset cache](crate::IndrasNetwork::assets) to render it inline.
+//!
+//! Packs are managed by the realm's moderators. Like moderation actions,
+//! every change records who made it and readers ignore changes by anyone
+//! outside the moderator set, so a member who edits the document directly
+//! changes nothing that others see.
+
+use crate::document::DocumentSchema;
+use crate::member::MemberId;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Document name for sticker packs within a realm.
+pub const STICKER_PACK_DOC: &str = "_sticker_packs";
+
+/// Largest image accepted into a pack, in bytes.
+pub const MAX_STICKER_SIZE: u64 = 512 * 1024;
+
+/// How a pack entry is meant to be shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StickerKind {
+    /// Small, inline with text and usable as a reaction.
+    Emoji,
+    /// Large, sent as a message of its own.
+    Sticker,
+}
+
+/// One named image in a pack.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackEntry {
+    /// BLAKE3 hash of the image blob.
+    pub hash: [u8; 32],
+    /// Image MIME type.
+    pub mime_type: String,
+    /// Image size in bytes.
+    pub size: u64,
+    /// How the entry is shown.
+    pub kind: StickerKind,
+    /// Whether the entry was removed.
+    pub removed: bool,
+    /// Member who last changed the entry.
+    pub updated_by: MemberId,
+    /// When the entry was last changed (milliseconds since epoch).
+    pub updated_at_millis: u64,
+}
+
+/// A named collection of emoji and stickers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StickerPack {
+    /// Display title.
+    pub title: String,
+    /// Whether the pack was retired.
+    pub archived: bool,
+    /// Member who last changed the title or archive flag.
+    pub updated_by: MemberId,
+    /// When the title or archive flag last changed (milliseconds since epoch).
+    pub updated_at_millis: u64,
+    /// Entries keyed by short name.
+    pub entries: BTreeMap<String, PackEntry>,
+}
+
+impl StickerPack {
+    /// Live entries changed by one of `moderators`.
+    pub fn entries<'a>(
+        &'a self,
+        moderators: &'a BTreeSet<MemberId>,
+    ) -> impl Iterator<Item = (&'a str, &'a PackEntry)> + 'a {
+        self.entries
+            .iter()
+            .filter(|(_, e)| !e.removed && moderators.contains(&e.updated_by))
+            .map(|(name, e)| (name.as_str(), e))
+    }
+}
+
+/// A reference to one pack entry, as carried in a message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StickerRef {
+    /// Pack identifier.
+    pub pack: String,
+    /// Entry short name.
+    pub name: String,
+    /// Image hash at send time, so the message renders even if the entry
+    /// is later replaced or removed.
+    pub hash: [u8; 32],
+    /// Image MIME type.
+    pub mime_type: String,
+}
+
+/// CRDT document of a realm's sticker and emoji packs.
+///
+/// # Example
+///
+/// ```ignore
+/// // As a moderator:
+/// realm.create_sticker_pack("team", "Team emoji").await?;
+/// realm.add_sticker("team", "shipit", png_bytes, "image/png", StickerKind::Emoji).await?;
+///
+/// // Anyone:
+/// realm.send_sticker("team", "shipit").await?;
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StickerPackDocument {
+    /// Packs keyed by identifier.
+    pub packs: BTreeMap<String, StickerPack>,
+}
+
+impl StickerPackDocument {
+    /// Create an empty document.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a pack, or retitle and unarchive an existing one.
+    pub fn upsert_pack(&mut self, id: &str, title: String, by: MemberId, now_millis: u64) {
+        let pack = self.packs.entry(id.to_string()).or_insert_with(|| StickerPack {
+            title: String::new(),
+            archived: false,
+            updated_by: by,
+            updated_at_millis: 0,
+            entries: BTreeMap::new(),
+        });
+        pack.title = title;
+        pack.archived = false;
+        pack.updated_by = by;
+        pack.updated_at_millis = now_millis;
+    }
+
+    /// Retire a pack. Returns `false` if it does not exist.
+    ///
+    /// Messages that already reference its entries keep rendering.
+    pub fn archive_pack(&mut self, id: &str, by: MemberId, now_millis: u64) -> bool {
+        match self.packs.get_mut(id) {
+            Some(pack) => {
+                pack.archived = true;
+                pack.updated_by = by;
+                pack.updated_at_millis = now_millis;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Add or replace an entry. Returns `false` if the pack does not exist.
+    pub fn put_entry(&mut self, pack: &str, name: &str, entry: PackEntry) -> bool {
+        match self.packs.get_mut(pack) {
+            Some(pack) => {
+                pack.entries.insert(name.to_string(), entry);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove an entry. Returns `false` if it was not there.
+    pub fn remove_entry(&mut self, pack: &str, name: &str, by: MemberId, now_millis: u64) -> bool {
+        match self
+            .packs
+            .get_mut(pack)
+            .and_then(|p| p.entries.get_mut(name))
+            .filter(|e| !e.removed)
+        {
+            Some(entry) => {
+                entry.removed = true;
+                entry.updated_by = by;
+                entry.updated_at_millis = now_millis;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Live packs whose current state was set by one of `moderators`.
+    pub fn packs<'a>(
+        &'a self,
+        moderators: &'a BTreeSet<MemberId>,
+    ) -> impl Iterator<Item = (&'a str, &'a StickerPack)> + 'a {
+        self.packs
+            .iter()
+            .filter(|(_, p)| !p.archived && moderators.contains(&p.updated_by))
+            .map(|(id, p)| (id.as_str(), p))
+    }
+
+    /// Look up a live entry.
+    pub fn entry(
+        &self,
+        pack: &str,
+        name: &str,
+        moderators: &BTreeSet<MemberId>,
+    ) -> Option<&PackEntry> {
+        self.packs(moderators)
+            .find(|(id, _)| *id == pack)
+            .and_then(|(_, p)| p.entries(moderators).find(|(n, _)| *n == name))
+            .map(|(_, e)| e)
+    }
+
+    /// Resolve a `:shortcode:` to an emoji entry in any live pack.
+    ///
+    /// `:pack/name:` picks a specific pack; a bare `:name:` takes the first
+    /// pack (by identifier) that has it.
+    pub fn resolve_shortcode(
+        &self,
+        shortcode: &str,
+        moderators: &BTreeSet<MemberId>,
+    ) -> Option<StickerRef> {
+        let inner = shortcode.strip_prefix(':')?.strip_suffix(':')?;
+        let (pack_filter, name) = match inner.split_once('/') {
+            Some((pack, name)) => (Some(pack), name),
+            None => (None, inner),
+        };
+        self.packs(moderators)
+            .filter(|(id, _)| pack_filter.is_none_or(|p| p == *id))
+            .find_map(|(id, pack)| {
+                pack.entries(moderators)
+                    .find(|(n, e)| *n == name && e.kind == StickerKind::Emoji)
+                    .map(|(n, e)| StickerRef {
+                        pack: id.to_string(),
+                        name: n.to_string(),
+                        hash: e.hash,
+                        mime_type: e.mime_type.clone(),
+                    })
+            })
+    }
+}
+
+impl DocumentSchema for StickerPackDocument {
+    /// Per-pack LWW over title and archive flag, per-entry LWW within packs.
+    fn merge(&mut self, remote: Self) {
+        for (id, remote_pack) in remote.packs {
+            let Some(local) = self.packs.get_mut(&id) else {
+                self.packs.insert(id, remote_pack);
+                continue;
+            };
+            if remote_pack.updated_at_millis > local.updated_at_millis {
+                local.title = remote_pack.title;
+                local.archived = remote_pack.archived;
+                local.updated_by = remote_pack.updated_by;
+                local.updated_at_millis = remote_pack.updated_at_millis;
+            }
+            for (name, entry) in remote_pack.entries {
+                match local.entries.get(&name) {
+                    Some(mine) if mine.updated_at_millis >= entry.updated_at_millis => {}
+                    _ => {
+                        local.entries.insert(name, entry);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOD: MemberId = [1u8; 32];
+    const MEMBER: MemberId = [2u8; 32];
+
+    fn entry(hash: u8, kind: StickerKind, by: MemberId, at: u64) -> PackEntry {
+        PackEntry {
+            hash: [hash; 32],
+            mime_type: "image/png".to_string(),
+            size: 10,
+            kind,
+            removed: false,
+            updated_by: by,
+            updated_at_millis: at,
+        }
+    }
+
+    fn mods() -> BTreeSet<MemberId> {
+        [MOD].into_iter().collect()
+    }
+
+    #[test]
+    fn test_only_moderator_changes_count() {
+        let mut doc = StickerPackDocument::new();
+        doc.upsert_pack("team", "Team".to_string(), MOD, 1);
+        doc.put_entry("team", "shipit", entry(7, StickerKind::Emoji, MOD, 2));
+        doc.put_entry("team", "rude", entry(8, StickerKind::Emoji, MEMBER, 3));
+
+        assert!(doc.entry("team", "shipit", &mods()).is_some());
+        assert!(doc.entry("team", "rude", &mods()).is_none());
+
+        doc.upsert_pack("rogue", "Rogue".to_string(), MEMBER, 4);
+        assert_eq!(doc.packs(&mods()).count(), 1);
+    }
+
+    #[test]
+    fn test_resolve_shortcode() {
+        let mut doc = StickerPackDocument::new();
+        doc.upsert_pack("a", "A".to_string(), MOD, 1);
+        doc.upsert_pack("b", "B".to_string(), MOD, 1);
+        doc.put_entry("a", "wave", entry(1, StickerKind::Sticker, MOD, 2));
+        doc.put_entry("b", "wave", entry(2, StickerKind::Emoji, MOD, 2));
+
+        let bare = doc.resolve_shortcode(":wave:", &mods()).unwrap();
+        assert_eq!((bare.pack.as_str(), bare.hash), ("b", [2; 32]));
+        assert!(doc.resolve_shortcode(":a/wave:", &mods()).is_none());
+        assert!(doc.resolve_shortcode("wave", &mods()).is_none());
+
+        doc.remove_entry("b", "wave", MOD, 3);
+        assert!(doc.resolve_shortcode(":wave:", &mods()).is_none());
+    }
+
+    #[test]
+    fn test_merge_is_lww_per_entry() {
+        let mut local = StickerPackDocument::new();
+        local.upsert_pack("team", "Team".to_string(), MOD, 1);
+        local.put_entry("team", "party", entry(1, StickerKind::Emoji, MOD, 5));
+
+        let mut remote = local.clone();
+        remote.put_entry("team", "party", entry(2, StickerKind::Emoji, MOD, 9));
+        remote.put_entry("team", "cake", entry(3, StickerKind::Sticker, MOD, 9));
+        remote.upsert_pack("team", "Team v2".to_string(), MOD, 9);
+
+        local.merge(remote);
+        let pack = &local.packs["team"];
+        assert_eq!(pack.title, "Team v2");
+        assert_eq!(pack.entries["party"].hash, [2; 32]);
+        assert!(pack.entries.contains_key("cake"));
+    }
+}
@@ -0,0 +1,527 @@
+//! Time-series document for sensor data realms.
+//!
+//! Each member appends numeric samples to its own series, keyed by
+//! source and metric name ("temperature", "humidity", ...). Samples are
+//! append-only and sync as small deltas, so a realm works as a P2P
+//! sensor data bus.
+//!
+//! Every node keeps three tiers per series: raw samples, 1-minute and
+//! 1-hour rollups. Rollups are computed locally as samples arrive, and
+//! [`TimeSeriesDocument::compact`] applies the realm's
+//! [`RetentionPolicy`], dropping old raw samples while their rollups
+//! live on. Once raw samples are compacted, the series' raw horizon
+//! moves forward and older samples arriving from peers are ignored, so
+//! they are never counted twice. Rollups for compacted periods merge by
+//! taking the bucket with more samples.
+
+use crate::document::DocumentSchema;
+use crate::member::MemberId;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Document name for the time-series document in a realm.
+pub const TIME_SERIES_DOC: &str = "time-series";
+
+/// Length of a minute rollup bucket in milliseconds.
+pub const MINUTE_MILLIS: u64 = 60_000;
+
+/// Length of an hour rollup bucket in milliseconds.
+pub const HOUR_MILLIS: u64 = 3_600_000;
+
+/// Resolution of a series query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Tier {
+    /// Individual samples.
+    Raw,
+    /// 1-minute rollups.
+    Minute,
+    /// 1-hour rollups.
+    Hour,
+}
+
+impl Tier {
+    /// Bucket length in milliseconds (1 for raw samples).
+    pub fn bucket_millis(self) -> u64 {
+        match self {
+            Tier::Raw => 1,
+            Tier::Minute => MINUTE_MILLIS,
+            Tier::Hour => HOUR_MILLIS,
+        }
+    }
+
+    /// The coarsest tier that still returns at least `max_points` buckets
+    /// over `span_millis`, falling back to raw for short spans.
+    pub fn for_span(span_millis: u64, max_points: u64) -> Tier {
+        let max_points = max_points.max(1);
+        if span_millis / HOUR_MILLIS >= max_points {
+            Tier::Hour
+        } else if span_millis / MINUTE_MILLIS >= max_points {
+            Tier::Minute
+        } else {
+            Tier::Raw
+        }
+    }
+}
+
+/// A series: one metric reported by one source.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct SeriesKey {
+    /// The member that reports the samples.
+    pub source: MemberId,
+    /// Metric name, e.g. "temperature".
+    pub metric: String,
+}
+
+impl SeriesKey {
+    /// Create a series key.
+    pub fn new(source: MemberId, metric: impl Into<String>) -> Self {
+        Self {
+            source,
+            metric: metric.into(),
+        }
+    }
+}
+
+/// Aggregate of the samples in one time bucket.
+///
+/// Raw queries return one bucket per sample with `count == 1`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Bucket {
+    /// Bucket start (milliseconds since epoch).
+    pub start_millis: u64,
+    /// Number of samples.
+    pub count: u64,
+    /// Sum of sample values.
+    pub sum: f64,
+    /// Smallest sample value.
+    pub min: f64,
+    /// Largest sample value.
+    pub max: f64,
+}
+
+impl Bucket {
+    fn single(start_millis: u64, value: f64) -> Self {
+        Self {
+            start_millis,
+            count: 1,
+            sum: value,
+            min: value,
+            max: value,
+        }
+    }
+
+    fn add(&mut self, value: f64) {
+        self.count += 1;
+        self.sum += value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+
+    /// Mean sample value.
+    pub fn mean(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.sum / self.count as f64
+        }
+    }
+
+    /// Whether this bucket should replace `other` when merging rollups
+    /// for a compacted period. Ties break on the sum so every node picks
+    /// the same bucket.
+    fn supersedes(&self, other: &Bucket) -> bool {
+        self.count
+            .cmp(&other.count)
+            .then(self.sum.total_cmp(&other.sum))
+            .is_gt()
+    }
+}
+
+/// How long each tier is kept by [`TimeSeriesDocument::compact`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    /// Raw sample retention in milliseconds.
+    pub raw_millis: u64,
+    /// Minute rollup retention in milliseconds.
+    pub minute_millis: u64,
+    /// Hour rollup retention in milliseconds, or `None` to keep forever.
+    pub hour_millis: Option<u64>,
+}
+
+impl Default for RetentionPolicy {
+    /// Raw for a day, minutes for 30 days, hours forever.
+    fn default() -> Self {
+        Self {
+            raw_millis: 24 * HOUR_MILLIS,
+            minute_millis: 30 * 24 * HOUR_MILLIS,
+            hour_millis: None,
+        }
+    }
+}
+
+/// Samples and rollups of one series.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Series {
+    /// Raw samples: timestamp → value.
+    pub raw: BTreeMap<u64, f64>,
+    /// Minute rollups by bucket start.
+    pub minutes: BTreeMap<u64, Bucket>,
+    /// Hour rollups by bucket start.
+    pub hours: BTreeMap<u64, Bucket>,
+    /// Raw samples before this time have been compacted away.
+    pub raw_horizon_millis: u64,
+}
+
+impl Series {
+    /// Add a sample, updating the rollups.
+    ///
+    /// Returns `false` if the sample is older than the raw horizon or
+    /// already present. Two different values at the same timestamp
+    /// resolve to the larger one.
+    fn insert(&mut self, at_millis: u64, value: f64) -> bool {
+        if at_millis < self.raw_horizon_millis || !value.is_finite() {
+            return false;
+        }
+        match self.raw.get(&at_millis).copied() {
+            None => {
+                self.raw.insert(at_millis, value);
+                fold(&mut self.minutes, MINUTE_MILLIS, at_millis, value);
+                fold(&mut self.hours, HOUR_MILLIS, at_millis, value);
+                true
+            }
+            Some(existing) if value.total_cmp(&existing).is_gt() => {
+                self.raw.insert(at_millis, value);
+                self.rebuild(at_millis, existing, value);
+                true
+            }
+            Some(_) => false,
+        }
+    }
+
+    /// Recompute the rollups containing `at_millis` after its value
+    /// changed from `old` to `new`.
+    fn rebuild(&mut self, at_millis: u64, old: f64, new: f64) {
+        let horizon = self.raw_horizon_millis;
+        for (size, buckets) in [(MINUTE_MILLIS, &mut self.minutes), (HOUR_MILLIS, &mut self.hours)] {
+            let start = at_millis - at_millis % size;
+            if start >= horizon {
+                // Fully covered by raw samples
+                let mut values = self.raw.range(start..start + size).map(|(_, v)| *v);
+                if let Some(first) = values.next() {
+                    let mut bucket = Bucket::single(start, first);
+                    values.for_each(|v| bucket.add(v));
+                    buckets.insert(start, bucket);
+                }
+            } else if let Some(bucket) = buckets.get_mut(&start) {
+                bucket.sum += new - old;
+                bucket.max = bucket.max.max(new);
+            }
+        }
+    }
+
+    /// Merge rollups from a peer for periods without local raw coverage.
+    fn merge_rollups(&mut self, remote: &Series) {
+        let horizon = self.raw_horizon_millis.max(remote.raw_horizon_millis);
+        for (size, ours, theirs) in [
+            (MINUTE_MILLIS, &mut self.minutes, &remote.minutes),
+            (HOUR_MILLIS, &mut self.hours, &remote.hours),
+        ] {
+            for (start, bucket) in theirs {
+                if start + size > horizon {
+                    continue; // Derived from raw samples on both sides
+                }
+                match ours.get(start) {
+                    Some(existing) if !bucket.supersedes(existing) => {}
+                    _ => {
+                        ours.insert(*start, *bucket);
+                    }
+                }
+            }
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.raw.is_empty() && self.minutes.is_empty() && self.hours.is_empty()
+    }
+}
+
+/// Add a value to the bucket of `size` containing `at_millis`.
+fn fold(buckets: &mut BTreeMap<u64, Bucket>, size: u64, at_millis: u64, value: f64) {
+    let start = at_millis - at_millis % size;
+    buckets
+        .entry(start)
+        .and_modify(|b| b.add(value))
+        .or_insert_with(|| Bucket::single(start, value));
+}
+
+/// Samples added since an earlier document state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TimeSeriesDelta {
+    samples: Vec<(SeriesKey, u64, f64)>,
+}
+
+/// CRDT document holding per-source numeric time series.
+///
+/// # Example
+///
+/// ```ignore
+/// realm.record_sample("temperature", 21.5).await?;
+///
+/// let hourly = realm
+///     .query_series(sensor_id, "temperature", from, to, Tier::Hour)
+///     .await?;
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TimeSeriesDocument {
+    /// All series in the realm.
+    pub series: BTreeMap<SeriesKey, Series>,
+    /// Retention applied by [`Self::compact`].
+    pub retention: RetentionPolicy,
+    /// When the retention policy was last changed (milliseconds since epoch).
+    pub retention_updated_at_millis: u64,
+}
+
+impl TimeSeriesDocument {
+    /// Create an empty time-series document.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a sample. Returns `false` if it was ignored.
+    pub fn record(&mut self, key: SeriesKey, at_millis: u64, value: f64) -> bool {
+        let series = self.series.entry(key.clone()).or_default();
+        let added = series.insert(at_millis, value);
+        if series.is_empty() {
+            self.series.remove(&key);
+        }
+        added
+    }
+
+    /// Replace the retention policy if `updated_at_millis` is newer.
+    pub fn set_retention(&mut self, retention: RetentionPolicy, updated_at_millis: u64) -> bool {
+        if (updated_at_millis, retention) > (self.retention_updated_at_millis, self.retention) {
+            self.retention = retention;
+            self.retention_updated_at_millis = updated_at_millis;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// All series keys, in order.
+    pub fn keys(&self) -> impl Iterator<Item = &SeriesKey> {
+        self.series.keys()
+    }
+
+    /// Metric names reported by a source.
+    pub fn metrics(&self, source: &MemberId) -> Vec<&str> {
+        self.series
+            .keys()
+            .filter(|k| &k.source == source)
+            .map(|k| k.metric.as_str())
+            .collect()
+    }
+
+    /// Most recent raw sample in a series.
+    pub fn latest(&self, key: &SeriesKey) -> Option<(u64, f64)> {
+        let (at, value) = self.series.get(key)?.raw.last_key_value()?;
+        Some((*at, *value))
+    }
+
+    /// Buckets of a series in `[from_millis, to_millis)` at the given tier.
+    ///
+    /// Rollup buckets are included if they start in the range.
+    pub fn range(&self, key: &SeriesKey, from_millis: u64, to_millis: u64, tier: Tier) -> Vec<Bucket> {
+        let Some(series) = self.series.get(key) else {
+            return Vec::new();
+        };
+        if from_millis >= to_millis {
+            return Vec::new();
+        }
+        match tier {
+            Tier::Raw => series
+                .raw
+                .range(from_millis..to_millis)
+                .map(|(at, v)| Bucket::single(*at, *v))
+                .collect(),
+            Tier::Minute => series.minutes.range(from_millis..to_millis).map(|(_, b)| *b).collect(),
+            Tier::Hour => series.hours.range(from_millis..to_millis).map(|(_, b)| *b).collect(),
+        }
+    }
+
+    /// Apply the retention policy as of `now_millis`.
+    ///
+    /// Raw samples are dropped in whole minutes so the minute rollups at
+    /// the horizon stay complete. Returns the number of samples and
+    /// buckets removed.
+    pub fn compact(&mut self, now_millis: u64) -> usize {
+        let retention = self.retention;
+        let raw_cutoff = now_millis.saturating_sub(retention.raw_millis);
+        let raw_cutoff = raw_cutoff - raw_cutoff % MINUTE_MILLIS;
+        let minute_cutoff = now_millis.saturating_sub(retention.minute_millis);
+        let hour_cutoff = retention.hour_millis.map(|h| now_millis.saturating_sub(h));
+
+        let mut removed = 0;
+        for series in self.series.values_mut() {
+            let before = series.raw.len() + series.minutes.len() + series.hours.len();
+            if raw_cutoff > series.raw_horizon_millis {
+                series.raw = series.raw.split_off(&raw_cutoff);
+                series.raw_horizon_millis = raw_cutoff;
+            }
+            series.minutes = series.minutes.split_off(&minute_cutoff);
+            if let Some(cutoff) = hour_cutoff {
+                series.hours = series.hours.split_off(&cutoff);
+            }
+            removed += before - (series.raw.len() + series.minutes.len() + series.hours.len());
+        }
+        self.series.retain(|_, s| !s.is_empty());
+        removed
+    }
+}
+
+impl DocumentSchema for TimeSeriesDocument {
+    /// Union of raw samples above each series' horizon, LWW retention,
+    /// and larger-count rollups for compacted periods.
+    fn merge(&mut self, remote: Self) {
+        self.set_retention(remote.retention, remote.retention_updated_at_millis);
+        for (key, remote_series) in remote.series {
+            let series = self.series.entry(key).or_default();
+            for (at, value) in &remote_series.raw {
+                series.insert(*at, *value);
+            }
+            series.merge_rollups(&remote_series);
+        }
+        self.series.retain(|_, s| !s.is_empty());
+    }
+
+    /// New raw samples only; compaction and retention changes send the
+    /// full state.
+    fn extract_delta(old: &Self, new: &Self) -> Option<Vec<u8>> {
+        if old.retention_updated_at_millis != new.retention_updated_at_millis {
+            return None;
+        }
+        let mut samples = Vec::new();
+        for (key, series) in &new.series {
+            let old_series = old.series.get(key);
+            if old_series.is_some_and(|s| s.raw_horizon_millis != series.raw_horizon_millis) {
+                return None;
+            }
+            for (at, value) in &series.raw {
+                if old_series.and_then(|s| s.raw.get(at)) != Some(value) {
+                    samples.push((key.clone(), *at, *value));
+                }
+            }
+        }
+        if samples.is_empty() {
+            return None;
+        }
+        postcard::to_allocvec(&TimeSeriesDelta { samples }).ok()
+    }
+
+    fn apply_delta(&mut self, delta: &[u8]) -> bool {
+        let Ok(delta) = postcard::from_bytes::<TimeSeriesDelta>(delta) else {
+            return false;
+        };
+        delta
+            .samples
+            .into_iter()
+            .fold(false, |changed, (key, at, value)| changed | self.record(key, at, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key() -> SeriesKey {
+        SeriesKey::new([1; 32], "temperature")
+    }
+
+    #[test]
+    fn test_rollups_follow_samples() {
+        let mut doc = TimeSeriesDocument::new();
+        doc.record(key(), 0, 10.0);
+        doc.record(key(), 30_000, 20.0);
+        doc.record(key(), 60_000, 30.0);
+
+        let minutes = doc.range(&key(), 0, HOUR_MILLIS, Tier::Minute);
+        assert_eq!(minutes.len(), 2);
+        assert_eq!(minutes[0].count, 2);
+        assert_eq!(minutes[0].mean(), 15.0);
+        assert_eq!((minutes[0].min, minutes[0].max), (10.0, 20.0));
+
+        let hours = doc.range(&key(), 0, HOUR_MILLIS, Tier::Hour);
+        assert_eq!(hours.len(), 1);
+        assert_eq!(hours[0].count, 3);
+        assert_eq!(doc.latest(&key()), Some((60_000, 30.0)));
+        assert_eq!(doc.range(&key(), 0, 60_000, Tier::Raw).len(), 2);
+    }
+
+    #[test]
+    fn test_conflicting_sample_resolves_to_larger() {
+        let mut a = TimeSeriesDocument::new();
+        a.record(key(), 5, 1.0);
+        let mut b = TimeSeriesDocument::new();
+        b.record(key(), 5, 2.0);
+
+        let mut ab = a.clone();
+        ab.merge(b.clone());
+        let mut ba = b;
+        ba.merge(a);
+
+        assert_eq!(ab.series, ba.series);
+        let minute = ab.range(&key(), 0, MINUTE_MILLIS, Tier::Minute)[0];
+        assert_eq!((minute.count, minute.sum), (1, 2.0));
+    }
+
+    #[test]
+    fn test_compaction_keeps_rollups_and_ignores_stale_samples() {
+        let mut doc = TimeSeriesDocument::new();
+        doc.set_retention(
+            RetentionPolicy {
+                raw_millis: HOUR_MILLIS,
+                ..RetentionPolicy::default()
+            },
+            1,
+        );
+        doc.record(key(), 0, 1.0);
+        doc.record(key(), 2 * HOUR_MILLIS, 2.0);
+        let peer = doc.clone();
+
+        assert_eq!(doc.compact(2 * HOUR_MILLIS + 1), 1);
+        assert_eq!(doc.range(&key(), 0, 3 * HOUR_MILLIS, Tier::Raw).len(), 1);
+        assert_eq!(doc.range(&key(), 0, MINUTE_MILLIS, Tier::Minute)[0].count, 1);
+
+        // A peer that hasn't compacted doesn't resurrect or double-count it
+        doc.merge(peer);
+        assert_eq!(doc.range(&key(), 0, 3 * HOUR_MILLIS, Tier::Raw).len(), 1);
+        assert_eq!(doc.range(&key(), 0, HOUR_MILLIS, Tier::Hour)[0].count, 1);
+        assert!(!doc.record(key(), 10, 5.0));
+    }
+
+    #[test]
+    fn test_delta_carries_new_samples() {
+        let mut old = TimeSeriesDocument::new();
+        old.record(key(), 1, 1.0);
+        let mut new = old.clone();
+        new.record(key(), 2, 2.0);
+        new.record(SeriesKey::new([2; 32], "humidity"), 2, 40.0);
+
+        let delta = TimeSeriesDocument::extract_delta(&old, &new).unwrap();
+        let mut replica = old.clone();
+        assert!(replica.apply_delta(&delta));
+        assert_eq!(replica.series, new.series);
+        assert_eq!(replica.metrics(&[2; 32]), vec!["humidity"]);
+
+        let mut compacted = new.clone();
+        compacted.compact(10 * 24 * HOUR_MILLIS);
+        assert!(TimeSeriesDocument::extract_delta(&new, &compacted).is_none());
+    }
+
+    #[test]
+    fn test_tier_for_span() {
+        assert_eq!(Tier::for_span(10 * MINUTE_MILLIS, 100), Tier::Raw);
+        assert_eq!(Tier::for_span(24 * HOUR_MILLIS, 100), Tier::Minute);
+        assert_eq!(Tier::for_span(30 * 24 * HOUR_MILLIS, 100), Tier::Hour);
+    }
+}
@@ -0,0 +1,166 @@
+//! Tombstone compaction for long-lived documents.
+//!
+//! Deleted chat messages and intentions stay in their documents as
+//! tombstones so that a concurrent merge from a peer that has not yet
+//! seen the delete cannot resurrect them. Once every member has synced
+//! past the delete, the tombstone has done its job and can be dropped.
+//!
+//! Members coordinate through a [`SyncHorizonDocument`]: each one
+//! periodically records the time up to which it has merged realm state.
+//! The minimum across all current members (minus a grace period) is the
+//! compaction horizon — tombstones older than it are safe to remove.
+
+use crate::document::DocumentSchema;
+use crate::member::MemberId;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Name of the internal document that stores member sync horizons.
+pub const SYNC_HORIZON_DOC: &str = "_sync_horizon";
+
+/// Safety margin subtracted from the coordinated horizon (24 hours).
+///
+/// Covers clock skew between members and updates still in flight when a
+/// member recorded its horizon.
+pub const DEFAULT_TOMBSTONE_GRACE_MILLIS: u64 = 24 * 60 * 60 * 1000;
+
+/// A document whose deleted entries can be garbage-collected.
+///
+/// Implementations must only remove entries that are deleted *and* whose
+/// deletion time is strictly before the horizon. Live entries are never
+/// touched.
+pub trait TombstoneCompaction: DocumentSchema {
+    /// Number of tombstones currently held in the document.
+    fn tombstone_count(&self) -> usize;
+
+    /// Remove tombstones deleted before `horizon_millis`.
+    ///
+    /// Returns the number of entries removed.
+    fn compact_tombstones(&mut self, horizon_millis: u64) -> usize;
+}
+
+/// CRDT document recording how far each member has synced.
+///
+/// # Example
+///
+/// ```ignore
+/// let horizons = realm.document::<SyncHorizonDocument>(SYNC_HORIZON_DOC).await?;
+/// horizons.update(|d| d.record(my_id, now_millis)).await?;
+///
+/// let members: Vec<MemberId> = realm.member_list().await?.iter().map(|m| m.id()).collect();
+/// let horizon = horizons.read().await.compaction_horizon(&members, DEFAULT_TOMBSTONE_GRACE_MILLIS);
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncHorizonDocument {
+    /// Latest synced-through timestamp (Unix millis) per member.
+    pub horizons: HashMap<MemberId, u64>,
+}
+
+impl SyncHorizonDocument {
+    /// Create an empty horizon document.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that a member has merged all state up to `millis`.
+    ///
+    /// Only advances forward; an older timestamp is ignored.
+    pub fn record(&mut self, member: MemberId, millis: u64) {
+        let entry = self.horizons.entry(member).or_insert(0);
+        if millis > *entry {
+            *entry = millis;
+        }
+    }
+
+    /// Get a member's recorded horizon, if it has reported one.
+    pub fn horizon_of(&self, member: &MemberId) -> Option<u64> {
+        self.horizons.get(member).copied()
+    }
+
+    /// Compute the horizon before which tombstones may be pruned.
+    ///
+    /// Returns `None` if `members` is empty or any member has not yet
+    /// reported — a silent member might still hold a live copy of a
+    /// deleted entry, so nothing is safe to prune.
+    pub fn compaction_horizon(&self, members: &[MemberId], grace_millis: u64) -> Option<u64> {
+        if members.is_empty() {
+            return None;
+        }
+        let mut oldest = u64::MAX;
+        for member in members {
+            oldest = oldest.min(self.horizon_of(member)?);
+        }
+        Some(oldest.saturating_sub(grace_millis))
+    }
+}
+
+impl DocumentSchema for SyncHorizonDocument {
+    /// Max-wins merge: for each member, keep the later horizon.
+    fn merge(&mut self, remote: Self) {
+        for (member, millis) in remote.horizons {
+            self.record(member, millis);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member_a() -> MemberId {
+        [1u8; 32]
+    }
+
+    fn member_b() -> MemberId {
+        [2u8; 32]
+    }
+
+    #[test]
+    fn test_record_only_advances() {
+        let mut doc = SyncHorizonDocument::new();
+        doc.record(member_a(), 100);
+        doc.record(member_a(), 50);
+        assert_eq!(doc.horizon_of(&member_a()), Some(100));
+    }
+
+    #[test]
+    fn test_horizon_is_oldest_member_minus_grace() {
+        let mut doc = SyncHorizonDocument::new();
+        doc.record(member_a(), 1_000);
+        doc.record(member_b(), 400);
+
+        assert_eq!(doc.compaction_horizon(&[member_a(), member_b()], 100), Some(300));
+        assert_eq!(doc.compaction_horizon(&[member_a()], 100), Some(900));
+    }
+
+    #[test]
+    fn test_horizon_requires_every_member() {
+        let mut doc = SyncHorizonDocument::new();
+        doc.record(member_a(), 1_000);
+
+        assert_eq!(doc.compaction_horizon(&[member_a(), member_b()], 0), None);
+        assert_eq!(doc.compaction_horizon(&[], 0), None);
+    }
+
+    #[test]
+    fn test_grace_saturates() {
+        let mut doc = SyncHorizonDocument::new();
+        doc.record(member_a(), 10);
+        assert_eq!(doc.compaction_horizon(&[member_a()], 100), Some(0));
+    }
+
+    #[test]
+    fn test_merge_max_wins() {
+        let mut local = SyncHorizonDocument::new();
+        local.record(member_a(), 10);
+        local.record(member_b(), 30);
+
+        let mut remote = SyncHorizonDocument::new();
+        remote.record(member_a(), 20);
+        remote.record(member_b(), 5);
+
+        local.merge(remote);
+        assert_eq!(local.horizon_of(&member_a()), Some(20));
+        assert_eq!(local.horizon_of(&member_b()), Some(30));
+    }
+}
@@ -0,0 +1,224 @@
+//! Pluggable translation of chat messages for display.
+//!
+//! Apps register a [`Translator`] (a local model or a call to an external
+//! service) on the network's [`Translations`], reached through
+//! `network.translations()`. Chat components then ask
+//! [`IndrasNetwork::translate_message`](crate::IndrasNetwork::translate_message)
+//! for a message in the reader's language and can toggle between it and
+//! the original.
+//!
+//! Translations are cached per message and language in the node's local
+//! storage and are never synced. A cached translation is only reused while
+//! the message text is unchanged, so an edited message is translated again.
+
+use crate::error::{IndraError, Result};
+
+use async_trait::async_trait;
+use indras_storage::{TranslationRecord, TranslationStore};
+use std::sync::{Arc, RwLock};
+
+/// Translates text into another language.
+///
+/// # Example
+///
+/// ```ignore
+/// struct LocalModel(Model);
+///
+/// #[async_trait]
+/// impl Translator for LocalModel {
+///     async fn translate(&self, text: &str, language: &str) -> Result<String> {
+///         self.0
+///             .run(text, language)
+///             .await
+///             .map_err(|e| IndraError::Translation(e.to_string()))
+///     }
+/// }
+///
+/// network.translations().set_translator(LocalModel(model));
+/// let text = network.translate_message(&msg.id, &msg.current_content, "es").await?;
+/// ```
+#[async_trait]
+pub trait Translator: Send + Sync {
+    /// Translate `text` into `language` (a BCP 47 tag such as `"es"`).
+    async fn translate(&self, text: &str, language: &str) -> Result<String>;
+}
+
+/// The translator registered on this device.
+///
+/// Owned by `IndrasNetwork`; access it via `network.translations()`.
+#[derive(Default)]
+pub struct Translations {
+    translator: RwLock<Option<Arc<dyn Translator>>>,
+}
+
+impl Translations {
+    /// Create a registry with no translator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the translator, replacing any earlier one.
+    pub fn set_translator(&self, translator: impl Translator + 'static) {
+        *self.translator.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(translator));
+    }
+
+    /// Remove the translator. Cached translations are still served.
+    pub fn clear_translator(&self) {
+        *self.translator.write().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    /// Whether a translator is registered.
+    pub fn has_translator(&self) -> bool {
+        self.translator
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_some()
+    }
+
+    /// The cached translation of `text`, if one was made from this exact text.
+    pub fn cached(
+        &self,
+        store: &TranslationStore,
+        message_id: &str,
+        text: &str,
+        language: &str,
+    ) -> Option<String> {
+        store
+            .get(message_id, language)
+            .ok()
+            .flatten()
+            .filter(|record| record.matches(text))
+            .map(|record| record.text)
+    }
+
+    /// Translate a message's text, serving and filling the cache.
+    ///
+    /// Fails with [`IndraError::Translation`] if nothing is cached and no
+    /// translator is registered, or if the translator fails.
+    pub async fn translate(
+        &self,
+        store: &TranslationStore,
+        message_id: &str,
+        text: &str,
+        language: &str,
+    ) -> Result<String> {
+        if let Some(cached) = self.cached(store, message_id, text, language) {
+            return Ok(cached);
+        }
+        let translator = self
+            .translator
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+            .ok_or_else(|| IndraError::Translation("no translator registered".to_string()))?;
+
+        let translated = translator.translate(text, language).await?;
+        let record = TranslationRecord::new(text, translated.clone());
+        if let Err(e) = store.put(message_id, language, &record) {
+            tracing::debug!(error = %e, "failed to cache translation");
+        }
+        Ok(translated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indras_storage::InMemoryStructuredStore;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Upper-cases text and counts how often it was asked.
+    #[derive(Default)]
+    struct Shout(Arc<AtomicUsize>);
+
+    #[async_trait]
+    impl Translator for Shout {
+        async fn translate(&self, text: &str, language: &str) -> Result<String> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(format!("[{language}] {}", text.to_uppercase()))
+        }
+    }
+
+    fn store() -> TranslationStore {
+        TranslationStore::new(Arc::new(InMemoryStructuredStore::new()))
+    }
+
+    #[tokio::test]
+    async fn test_translations_are_cached_per_language() {
+        let store = store();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let translations = Translations::new();
+        translations.set_translator(Shout(Arc::clone(&calls)));
+
+        let first = translations
+            .translate(&store, "m1", "hi", "es")
+            .await
+            .unwrap();
+        let again = translations
+            .translate(&store, "m1", "hi", "es")
+            .await
+            .unwrap();
+        assert_eq!(first, "[es] HI");
+        assert_eq!(again, first);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        translations
+            .translate(&store, "m1", "hi", "fr")
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_edit_invalidates_cache() {
+        let store = store();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let translations = Translations::new();
+        translations.set_translator(Shout(Arc::clone(&calls)));
+
+        translations
+            .translate(&store, "m1", "hi", "es")
+            .await
+            .unwrap();
+        assert!(
+            translations
+                .cached(&store, "m1", "hi there", "es")
+                .is_none()
+        );
+        let edited = translations
+            .translate(&store, "m1", "hi there", "es")
+            .await
+            .unwrap();
+        assert_eq!(edited, "[es] HI THERE");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cache_outlives_translator() {
+        let store = store();
+        let translations = Translations::new();
+        assert!(
+            translations
+                .translate(&store, "m1", "hi", "es")
+                .await
+                .is_err()
+        );
+
+        translations.set_translator(Shout::default());
+        translations
+            .translate(&store, "m1", "hi", "es")
+            .await
+            .unwrap();
+        translations.clear_translator();
+        assert!(!translations.has_translator());
+
+        let cached = translations.translate(&store, "m1", "hi", "es").await;
+        assert_eq!(cached.unwrap(), "[es] HI");
+        assert!(
+            translations
+                .translate(&store, "m2", "yo", "es")
+                .await
+                .is_err()
+        );
+    }
+}
@@ -65,3 +65,11 @@ pub(crate) fn guess_mime_type(ext: &str) -> String {
     }
     .to_string()
 }
+
+/// Get current time in milliseconds since UNIX epoch.
+pub(crate) fn now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
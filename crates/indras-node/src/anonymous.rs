@@ -0,0 +1,229 @@
+//! Sender-anonymous interface events
+//!
+//! Some realms (anonymous feedback, whistleblowing channels) need posts
+//! that are readable by every member but cannot be traced to one. Such a
+//! post differs from a normal event in three ways:
+//!
+//! - **No sender identity.** The event's sender is a pseudonymous identity
+//!   derived from the interface id, identical for every member, so the
+//!   field carries no information.
+//! - **Ring signature instead of ML-DSA.** The payload is an
+//!   [`AnonymousPost`] signed with a [`RingSignature`] over the realm's
+//!   anonymity ring: recipients learn that *some* ring member wrote it,
+//!   and nothing more.
+//! - **Unsigned envelope.** The wire message is sent as a bare
+//!   [`NetworkMessage::AnonymousEvent`](crate::NetworkMessage::AnonymousEvent)
+//!   rather than a [`SignedNetworkMessage`](crate::SignedNetworkMessage),
+//!   which would name the sender's PQ key.
+//!
+//! The event is still encrypted with the interface key, so only members
+//! can read it.
+//!
+//! A peer always sees which connection delivered a message, so the author
+//! sends the post to a single member, picked at random, as a
+//! [`NetworkMessage::AnonymousRelay`](crate::NetworkMessage::AnonymousRelay).
+//! That member forwards it to all the others, who receive it from the
+//! relay. This is one hop, not a mix network:
+//!
+//! - The relay learns the author's transport identity.
+//! - The post sits in the author's event log like any other, so a member
+//!   that syncs with the author before the relay's copy arrives may get it
+//!   from the author.
+//! - Someone watching the network can match the author's outgoing message
+//!   to the relay's fan-out by timing.
+//!
+//! Treat posts as unattributed by the realm's members, not as unlinkable.
+//!
+//! Like the guest registry, [`AnonymityRings`] is local to each node and
+//! not persisted; the network layer rebuilds it from the realm's ring
+//! document.
+
+use std::collections::BTreeSet;
+
+use dashmap::DashMap;
+use indras_core::InterfaceId;
+use indras_crypto::{PQIdentity, RingKeyPair, RingPublicKey, RingSignature, anonymous_sender_seed};
+use indras_transport::IrohIdentity;
+use serde::{Deserialize, Serialize};
+
+/// Custom event type carried by anonymous posts.
+pub const ANONYMOUS_EVENT_TYPE: &str = "indras/anonymous";
+
+/// Domain separator for the bytes covered by the ring signature.
+const SIGNING_DOMAIN: &[u8] = b"indras-anonymous-post-v1";
+
+/// Smallest ring a post may be signed over.
+///
+/// A ring of one names its signer, so posting is refused until at least
+/// one other member has joined the ring.
+pub const MIN_RING_SIZE: usize = 2;
+
+/// The payload of an anonymous event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnonymousPost {
+    /// Application content
+    pub content: Vec<u8>,
+    /// The ring the post was signed over, in signing order
+    pub ring: Vec<RingPublicKey>,
+    /// Ring signature by one member of `ring`
+    pub signature: RingSignature,
+}
+
+impl AnonymousPost {
+    /// Sign `content` as an anonymous member of `ring`.
+    ///
+    /// The signature binds the interface and event sequence so a post
+    /// cannot be replayed into another realm or under another event id.
+    pub fn sign(
+        keypair: &RingKeyPair,
+        interface_id: &InterfaceId,
+        sequence: u64,
+        content: Vec<u8>,
+        ring: Vec<RingPublicKey>,
+    ) -> Result<Self, indras_crypto::CryptoError> {
+        let message = signing_bytes(interface_id, sequence, &content);
+        let signature = keypair.sign(&message, &ring)?;
+        Ok(Self {
+            content,
+            ring,
+            signature,
+        })
+    }
+
+    /// Whether the signature is valid for this interface and sequence.
+    ///
+    /// This only checks the signature against the ring the post carries;
+    /// callers must also check that ring against the members they accept.
+    pub fn verify(&self, interface_id: &InterfaceId, sequence: u64) -> bool {
+        self.ring.len() >= MIN_RING_SIZE
+            && self
+                .signature
+                .verify(&signing_bytes(interface_id, sequence, &self.content), &self.ring)
+    }
+
+    /// Serialize to bytes
+    pub fn to_bytes(&self) -> Result<Vec<u8>, postcard::Error> {
+        postcard::to_allocvec(self)
+    }
+
+    /// Deserialize from bytes
+    pub fn from_bytes(data: &[u8]) -> Result<Self, postcard::Error> {
+        postcard::from_bytes(data)
+    }
+}
+
+fn signing_bytes(interface_id: &InterfaceId, sequence: u64, content: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(SIGNING_DOMAIN.len() + 32 + 8 + content.len());
+    bytes.extend_from_slice(SIGNING_DOMAIN);
+    bytes.extend_from_slice(interface_id.as_bytes());
+    bytes.extend_from_slice(&sequence.to_le_bytes());
+    bytes.extend_from_slice(content);
+    bytes
+}
+
+/// The shared pseudonymous sender of anonymous events in an interface.
+pub fn anonymous_sender(interface_id: &InterfaceId) -> IrohIdentity {
+    IrohIdentity::from(iroh::SecretKey::from_bytes(&anonymous_sender_seed(interface_id)).public())
+}
+
+/// This node's ring keypair for an interface.
+///
+/// Derived from the PQ signing key with the interface id as context, so it
+/// survives restarts without extra key storage and keys in different
+/// realms cannot be linked to each other.
+pub fn ring_keypair(pq_identity: &PQIdentity, interface_id: &InterfaceId) -> RingKeyPair {
+    RingKeyPair::derive(pq_identity.signing_key_bytes().as_slice(), interface_id.as_bytes())
+}
+
+/// Per-interface anonymity rings.
+#[derive(Default)]
+pub struct AnonymityRings {
+    rings: DashMap<InterfaceId, BTreeSet<RingPublicKey>>,
+}
+
+impl AnonymityRings {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the ring for an interface.
+    pub fn set(&self, interface_id: InterfaceId, ring: impl IntoIterator<Item = RingPublicKey>) {
+        self.rings.insert(interface_id, ring.into_iter().collect());
+    }
+
+    /// Drop the ring for an interface, disabling anonymous events in it.
+    pub fn forget(&self, interface_id: &InterfaceId) {
+        self.rings.remove(interface_id);
+    }
+
+    /// The ring for an interface in canonical (sorted) order.
+    pub fn get(&self, interface_id: &InterfaceId) -> Vec<RingPublicKey> {
+        self.rings
+            .get(interface_id)
+            .map(|ring| ring.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Whether every key in `ring` belongs to the interface's ring.
+    ///
+    /// A post signed over a subset is accepted so members whose view of
+    /// the ring lags behind can still post; a post naming any unknown key
+    /// is not, since an outsider could otherwise sign over a ring of its
+    /// own keys.
+    pub fn admits(&self, interface_id: &InterfaceId, ring: &[RingPublicKey]) -> bool {
+        self.rings
+            .get(interface_id)
+            .is_some_and(|known| ring.iter().all(|key| known.contains(key)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_post_verifies_only_in_context() {
+        let interface = InterfaceId::new([7; 32]);
+        let me = RingKeyPair::generate();
+        let other = RingKeyPair::generate();
+        let ring = vec![me.public_key(), other.public_key()];
+
+        let post = AnonymousPost::sign(&me, &interface, 3, b"retro".to_vec(), ring).unwrap();
+        assert!(post.verify(&interface, 3));
+        assert!(!post.verify(&interface, 4));
+        assert!(!post.verify(&InterfaceId::new([8; 32]), 3));
+
+        let decoded = AnonymousPost::from_bytes(&post.to_bytes().unwrap()).unwrap();
+        assert_eq!(decoded, post);
+    }
+
+    #[test]
+    fn test_single_member_ring_rejected() {
+        let interface = InterfaceId::new([7; 32]);
+        let me = RingKeyPair::generate();
+        let post = AnonymousPost::sign(&me, &interface, 1, b"hi".to_vec(), vec![me.public_key()]).unwrap();
+        assert!(!post.verify(&interface, 1));
+    }
+
+    #[test]
+    fn test_rings_admit_subsets_only() {
+        let rings = AnonymityRings::new();
+        let interface = InterfaceId::new([1; 32]);
+        let (a, b, c) = (
+            RingKeyPair::generate().public_key(),
+            RingKeyPair::generate().public_key(),
+            RingKeyPair::generate().public_key(),
+        );
+        assert!(!rings.admits(&interface, &[a]));
+
+        rings.set(interface, [a, b]);
+        assert!(rings.admits(&interface, &[a, b]));
+        assert!(rings.admits(&interface, &[b]));
+        assert!(!rings.admits(&interface, &[a, c]));
+        assert_eq!(rings.get(&interface).len(), 2);
+
+        assert_eq!(anonymous_sender(&interface), anonymous_sender(&interface));
+        assert_ne!(anonymous_sender(&interface), anonymous_sender(&InterfaceId::new([2; 32])));
+    }
+}
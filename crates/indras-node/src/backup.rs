@@ -0,0 +1,722 @@
+//! Differential, content-addressed backups of the data directory
+//!
+//! A backup directory holds content-addressed chunks and one manifest per
+//! backup taken into it:
+//!
+//! ```text
+//! <backup>/objects/<2 hex>/<blake3 hex>      chunk of up to 4 MiB
+//! <backup>/manifests/<created-millis>.json   BackupManifest
+//! ```
+//!
+//! Files are split into fixed-size chunks and only chunks the directory does
+//! not hold yet are written, so a grown log or a new blob costs only the new
+//! data. Every manifest is complete on its own: restoring needs just that
+//! manifest and the objects it names.
+//!
+//! Backups are safe to take while the node is running:
+//! - the structured database is exported table by table through the storage
+//!   layer instead of copying the redb file, and rebuilt on restore;
+//! - append-only logs are cut at their last complete frame;
+//! - other redb files (the DTN bundle queue) are skipped.
+//!
+//! Keys are only backed up encrypted. The passphrase- or story-encrypted
+//! keystore (`*.enc` with its salt) is copied like any other file, but the
+//! plaintext key files of an unencrypted keystore are left out, and so are
+//! the interface keys the structured database holds in the clear (see
+//! `CompositeStorage::export_structured`). Both are listed in
+//! [`BackupSummary::keys_skipped`]: a backup directory is meant to live on
+//! other disks and hosts, and must not carry the node's identity or realm
+//! keys in the clear. Such a node's keys have to be copied separately, or
+//! it restores as a new identity; restored interfaces have no key until
+//! the node is invited back.
+//!
+//! Restores stream: each chunk is checked and written as it is read, into
+//! a `.partial` file that is renamed into place once the whole file
+//! matches its hash.
+
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use indras_storage::StructuredStore;
+use indras_storage::structured::{ALL_TABLES, RedbStorage, RedbStorageConfig, ScanResults, Table};
+
+use crate::error::{NodeError, NodeResult};
+use crate::guest::now_millis;
+use crate::keystore::PLAINTEXT_KEY_FILES;
+
+/// Version written to new manifests
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// Files are stored as chunks of at most this many bytes
+const CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+const OBJECTS_DIR: &str = "objects";
+const MANIFESTS_DIR: &str = "manifests";
+
+/// Everything needed to restore one backup
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    /// Format version, [`MANIFEST_VERSION`] when written by this build
+    pub version: u32,
+    /// When the backup was taken (milliseconds since epoch)
+    pub created_at_millis: u64,
+    /// File name of the previous manifest in the same backup directory
+    pub parent: Option<String>,
+    /// Files copied from the data directory
+    pub files: Vec<BackupFile>,
+    /// Export of the structured database, rebuilt at its `path` on restore
+    pub structured: Option<BackupFile>,
+}
+
+/// A backed-up file, stored as content-addressed chunks
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupFile {
+    /// Path relative to the data directory, `/`-separated
+    pub path: String,
+    /// Size in bytes
+    pub size: u64,
+    /// BLAKE3 hash of the whole file (hex)
+    pub hash: String,
+    /// BLAKE3 hashes of its chunks, in order (hex)
+    pub chunks: Vec<String>,
+}
+
+/// Outcome of [`IndrasNode::backup`](crate::IndrasNode::backup)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BackupSummary {
+    /// The manifest that was written
+    pub manifest: PathBuf,
+    /// Files in the backup, including the structured export
+    pub files: usize,
+    /// Total size of those files
+    pub bytes: u64,
+    /// Chunks the backup directory did not hold yet
+    pub chunks_written: usize,
+    /// Size of those chunks
+    pub bytes_written: u64,
+    /// Unencrypted key files left out of the backup, and structured
+    /// entries an interface key was removed from (`<table>/<hex key>`)
+    pub keys_skipped: Vec<String>,
+}
+
+/// Outcome of [`verify_backup`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerifyReport {
+    /// The manifest that was checked
+    pub manifest: PathBuf,
+    /// Files checked, including the structured export
+    pub files: usize,
+    /// Chunks named by the manifest but absent
+    pub missing_chunks: Vec<String>,
+    /// Chunks whose contents do not match their name
+    pub corrupt_chunks: Vec<String>,
+    /// Files that cannot be restored intact
+    pub corrupt_files: Vec<String>,
+}
+
+impl VerifyReport {
+    /// Whether every file can be restored intact
+    pub fn is_ok(&self) -> bool {
+        self.corrupt_files.is_empty()
+    }
+}
+
+/// Outcome of [`restore_backup`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RestoreSummary {
+    /// The manifest that was restored
+    pub manifest: PathBuf,
+    /// Files written to the data directory
+    pub files: usize,
+    /// Total size of those files
+    pub bytes: u64,
+    /// Structured entries written to the rebuilt database
+    pub structured_entries: usize,
+}
+
+/// What a backup of a node's data directory copies
+pub(crate) struct BackupSource {
+    pub data_dir: PathBuf,
+    /// Directory of the interface event logs
+    pub log_dir: PathBuf,
+    /// The node log file
+    pub node_log: PathBuf,
+    /// Database path relative to the data directory, and its export
+    pub structured: Option<(String, Vec<u8>)>,
+}
+
+impl BackupSource {
+    /// Whether `path` is an append-only log to cut at a frame boundary
+    fn is_framed(&self, path: &Path) -> bool {
+        path == self.node_log
+            || (path.parent() == Some(&self.log_dir)
+                && path.extension().is_some_and(|e| e == "log"))
+    }
+}
+
+/// Encode a structured export for [`BackupSource::structured`]
+pub(crate) fn encode_structured(tables: Vec<(Table, ScanResults)>) -> NodeResult<Vec<u8>> {
+    let tables: Vec<(&str, ScanResults)> = tables
+        .into_iter()
+        .map(|(table, entries)| (table.name(), entries))
+        .collect();
+    postcard::to_allocvec(&tables).map_err(|e| NodeError::Serialization(e.to_string()))
+}
+
+/// Write a backup of `source` into `dest`
+///
+/// Blocking; run it off the async runtime.
+pub(crate) fn write_backup(source: &BackupSource, dest: &Path) -> NodeResult<BackupSummary> {
+    let objects = dest.join(OBJECTS_DIR);
+    let manifests = dest.join(MANIFESTS_DIR);
+    fs::create_dir_all(&objects).map_err(io_error)?;
+    fs::create_dir_all(&manifests).map_err(io_error)?;
+
+    let mut summary = BackupSummary::default();
+    let mut paths = Vec::new();
+    collect_files(&source.data_dir, dest, &mut paths).map_err(io_error)?;
+    paths.sort();
+
+    let mut files = Vec::with_capacity(paths.len());
+    for path in paths {
+        let Some(relative) = relative_path(&source.data_dir, &path) else {
+            continue;
+        };
+        if PLAINTEXT_KEY_FILES.contains(&relative.as_str()) {
+            summary.keys_skipped.push(relative);
+            continue;
+        }
+        let limit = if source.is_framed(&path) {
+            framed_len(&path)
+        } else {
+            fs::metadata(&path).map(|m| m.len())
+        };
+        let file = limit.and_then(|limit| Ok(File::open(&path)?.take(limit)));
+        let reader = match file {
+            Ok(reader) => reader,
+            // Removed since the walk, e.g. a deleted blob
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(io_error(e)),
+        };
+        files.push(store_file(&objects, relative, reader, &mut summary)?);
+    }
+
+    let structured = match &source.structured {
+        Some((path, export)) => Some(store_file(
+            &objects,
+            path.clone(),
+            export.as_slice(),
+            &mut summary,
+        )?),
+        None => None,
+    };
+
+    // Keep manifest names unique when backups land in the same millisecond
+    let mut created_at_millis = now_millis();
+    while manifests.join(format!("{created_at_millis}.json")).exists() {
+        created_at_millis += 1;
+    }
+    let manifest = BackupManifest {
+        version: MANIFEST_VERSION,
+        created_at_millis,
+        parent: latest_manifest(&manifests)?
+            .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned())),
+        files,
+        structured,
+    };
+    let json = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| NodeError::Serialization(e.to_string()))?;
+    summary.manifest = manifests.join(format!("{created_at_millis}.json"));
+    write_atomic(&summary.manifest, &json).map_err(io_error)?;
+    Ok(summary)
+}
+
+/// Check that a backup's objects are present and match its manifest
+///
+/// Checks the latest manifest in `backup_dir` unless `manifest` names one.
+pub async fn verify_backup(
+    backup_dir: impl AsRef<Path>,
+    manifest: Option<&Path>,
+) -> NodeResult<VerifyReport> {
+    let backup_dir = backup_dir.as_ref().to_path_buf();
+    let manifest = manifest.map(Path::to_path_buf);
+    blocking(move || {
+        let (manifest_path, manifest) = load_manifest(&backup_dir, manifest.as_deref())?;
+        let objects = backup_dir.join(OBJECTS_DIR);
+        let mut report = VerifyReport {
+            manifest: manifest_path,
+            ..VerifyReport::default()
+        };
+        let mut missing = HashSet::new();
+        let mut corrupt = HashSet::new();
+
+        for file in manifest.files.iter().chain(&manifest.structured) {
+            report.files += 1;
+            let mut hasher = blake3::Hasher::new();
+            let mut size = 0u64;
+            let mut intact = true;
+            for chunk in &file.chunks {
+                match read_chunk(&objects, chunk) {
+                    Ok(Some(bytes)) => {
+                        hasher.update(&bytes);
+                        size += bytes.len() as u64;
+                    }
+                    Ok(None) => {
+                        intact = false;
+                        if corrupt.insert(chunk.clone()) {
+                            report.corrupt_chunks.push(chunk.clone());
+                        }
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                        intact = false;
+                        if missing.insert(chunk.clone()) {
+                            report.missing_chunks.push(chunk.clone());
+                        }
+                    }
+                    Err(e) => return Err(io_error(e)),
+                }
+            }
+            if !intact || size != file.size || hasher.finalize().to_hex().as_str() != file.hash {
+                report.corrupt_files.push(file.path.clone());
+            }
+        }
+        Ok(report)
+    })
+    .await
+}
+
+/// Restore a backup into an empty or missing `data_dir`
+///
+/// Restores the latest manifest in `backup_dir` unless `manifest` names
+/// one. Every chunk is checked as it is written, and the structured
+/// database is rebuilt from its export. Fails with
+/// [`NodeError::CorruptBackup`] on the first object that does not match.
+pub async fn restore_backup(
+    backup_dir: impl AsRef<Path>,
+    manifest: Option<&Path>,
+    data_dir: impl AsRef<Path>,
+) -> NodeResult<RestoreSummary> {
+    let backup_dir = backup_dir.as_ref().to_path_buf();
+    let manifest = manifest.map(Path::to_path_buf);
+    let data_dir = data_dir.as_ref().to_path_buf();
+    blocking(move || {
+        if fs::read_dir(&data_dir).is_ok_and(|mut entries| entries.next().is_some()) {
+            return Err(NodeError::Config(format!(
+                "restore target {} is not empty",
+                data_dir.display()
+            )));
+        }
+        let (manifest_path, manifest) = load_manifest(&backup_dir, manifest.as_deref())?;
+        let objects = backup_dir.join(OBJECTS_DIR);
+        let mut summary = RestoreSummary {
+            manifest: manifest_path,
+            ..RestoreSummary::default()
+        };
+
+        for file in &manifest.files {
+            let target = data_dir.join(safe_relative(&file.path)?);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).map_err(io_error)?;
+            }
+            restore_file(&objects, file, &target)?;
+            summary.files += 1;
+            summary.bytes += file.size;
+        }
+
+        if let Some(file) = &manifest.structured {
+            // Decoded as a whole, as it was written
+            let mut export = Vec::new();
+            copy_chunks(&objects, file, &mut export)?;
+            let tables: Vec<(String, ScanResults)> = postcard::from_bytes(&export)
+                .map_err(|e| NodeError::CorruptBackup(format!("structured export: {e}")))?;
+            let db = RedbStorage::open(RedbStorageConfig {
+                db_path: data_dir.join(safe_relative(&file.path)?),
+                ..RedbStorageConfig::default()
+            })?;
+            for (name, entries) in tables {
+                let Some(table) = ALL_TABLES.iter().find(|t| t.name() == name) else {
+                    warn!(table = %name, "Skipping unknown table in backup");
+                    continue;
+                };
+                for (key, value) in &entries {
+                    db.put(*table, key, value)?;
+                }
+                summary.structured_entries += entries.len();
+            }
+            summary.files += 1;
+            summary.bytes += file.size;
+        }
+        Ok(summary)
+    })
+    .await
+}
+
+async fn blocking<T: Send + 'static>(
+    f: impl FnOnce() -> NodeResult<T> + Send + 'static,
+) -> NodeResult<T> {
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| NodeError::Io(e.to_string()))?
+}
+
+/// Chunk `reader` into `objects`, writing only chunks not already there
+fn store_file(
+    objects: &Path,
+    path: String,
+    mut reader: impl Read,
+    summary: &mut BackupSummary,
+) -> NodeResult<BackupFile> {
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    let mut hasher = blake3::Hasher::new();
+    let mut chunks = Vec::new();
+    let mut size = 0u64;
+    loop {
+        let len = read_full(&mut reader, &mut buf).map_err(io_error)?;
+        if len == 0 {
+            break;
+        }
+        let chunk = &buf[..len];
+        hasher.update(chunk);
+        let hash = blake3::hash(chunk).to_hex().to_string();
+        let object = object_path(objects, &hash);
+        if !object.exists() {
+            fs::create_dir_all(object.parent().unwrap_or(objects)).map_err(io_error)?;
+            write_atomic(&object, chunk).map_err(io_error)?;
+            summary.chunks_written += 1;
+            summary.bytes_written += len as u64;
+        }
+        chunks.push(hash);
+        size += len as u64;
+        if len < CHUNK_SIZE {
+            break;
+        }
+    }
+    summary.files += 1;
+    summary.bytes += size;
+    Ok(BackupFile {
+        path,
+        size,
+        hash: hasher.finalize().to_hex().to_string(),
+        chunks,
+    })
+}
+
+/// Write `file` to `target` through a `.partial` file, renamed into place
+/// only once every chunk and the whole file check out
+fn restore_file(objects: &Path, file: &BackupFile, target: &Path) -> NodeResult<()> {
+    let mut partial = target.as_os_str().to_owned();
+    partial.push(".partial");
+    let partial = PathBuf::from(partial);
+    let result = File::create(&partial).map_err(io_error).and_then(|mut out| {
+        copy_chunks(objects, file, &mut out)?;
+        out.sync_all().map_err(io_error)
+    });
+    match result {
+        Ok(()) => fs::rename(&partial, target).map_err(io_error),
+        Err(e) => {
+            let _ = fs::remove_file(&partial);
+            Err(e)
+        }
+    }
+}
+
+/// Check every chunk of `file` and write it to `out`, one chunk at a time
+fn copy_chunks(objects: &Path, file: &BackupFile, out: &mut impl Write) -> NodeResult<()> {
+    let mut hasher = blake3::Hasher::new();
+    let mut size = 0u64;
+    for chunk in &file.chunks {
+        match read_chunk(objects, chunk) {
+            Ok(Some(bytes)) => {
+                hasher.update(&bytes);
+                size += bytes.len() as u64;
+                out.write_all(&bytes).map_err(io_error)?;
+            }
+            Ok(None) => {
+                return Err(NodeError::CorruptBackup(format!(
+                    "chunk {chunk} of {} is damaged",
+                    file.path
+                )));
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                return Err(NodeError::CorruptBackup(format!(
+                    "chunk {chunk} of {} is missing",
+                    file.path
+                )));
+            }
+            Err(e) => return Err(io_error(e)),
+        }
+    }
+    if size != file.size || hasher.finalize().to_hex().as_str() != file.hash {
+        return Err(NodeError::CorruptBackup(format!(
+            "{} does not match its hash",
+            file.path
+        )));
+    }
+    Ok(())
+}
+
+/// Read a chunk, or `None` if its contents do not match its name
+fn read_chunk(objects: &Path, hash: &str) -> io::Result<Option<Vec<u8>>> {
+    let bytes = fs::read(object_path(objects, hash))?;
+    Ok((blake3::hash(&bytes).to_hex().as_str() == hash).then_some(bytes))
+}
+
+fn object_path(objects: &Path, hash: &str) -> PathBuf {
+    objects.join(hash.get(..2).unwrap_or("00")).join(hash)
+}
+
+/// Load `manifest`, or the latest manifest in `backup_dir`
+fn load_manifest(
+    backup_dir: &Path,
+    manifest: Option<&Path>,
+) -> NodeResult<(PathBuf, BackupManifest)> {
+    let manifests = backup_dir.join(MANIFESTS_DIR);
+    let path = match manifest {
+        Some(path) if path.exists() => path.to_path_buf(),
+        Some(name) => manifests.join(name),
+        None => latest_manifest(&manifests)?
+            .ok_or_else(|| NodeError::Config(format!("no backups in {}", backup_dir.display())))?,
+    };
+    let bytes = fs::read(&path).map_err(|e| NodeError::Io(format!("{}: {e}", path.display())))?;
+    let manifest: BackupManifest = serde_json::from_slice(&bytes)
+        .map_err(|e| NodeError::CorruptBackup(format!("{}: {e}", path.display())))?;
+    if manifest.version > MANIFEST_VERSION {
+        return Err(NodeError::Config(format!(
+            "backup manifest version {} is newer than supported ({MANIFEST_VERSION})",
+            manifest.version
+        )));
+    }
+    Ok((path, manifest))
+}
+
+/// The manifest with the highest timestamp, if any
+fn latest_manifest(manifests: &Path) -> NodeResult<Option<PathBuf>> {
+    let entries = match fs::read_dir(manifests) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(io_error(e)),
+    };
+    let mut latest: Option<(u64, PathBuf)> = None;
+    for entry in entries {
+        let path = entry.map_err(io_error)?.path();
+        let Some(millis) = path
+            .file_name()
+            .and_then(|n| n.to_str()?.strip_suffix(".json")?.parse::<u64>().ok())
+        else {
+            continue;
+        };
+        if latest.as_ref().is_none_or(|(m, _)| millis > *m) {
+            latest = Some((millis, path));
+        }
+    }
+    Ok(latest.map(|(_, path)| path))
+}
+
+/// Regular files under `dir` worth backing up, skipping `exclude`
+fn collect_files(dir: &Path, exclude: &Path, out: &mut Vec<PathBuf>) -> io::Result<()> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    for entry in entries {
+        let entry = entry?;
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if path == exclude || name.contains(".corrupt-") {
+            continue;
+        }
+        if file_type.is_dir() {
+            collect_files(&path, exclude, out)?;
+        } else if file_type.is_file() && !path.extension().is_some_and(|e| e == "redb") {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Length of the complete length-prefixed frames at the start of a log
+fn framed_len(path: &Path) -> io::Result<u64> {
+    let mut file = File::open(path)?;
+    let size = file.metadata()?.len();
+    let mut offset = 0u64;
+    let mut len_buf = [0u8; 4];
+    while offset + 4 <= size {
+        file.seek(SeekFrom::Start(offset))?;
+        file.read_exact(&mut len_buf)?;
+        let len = u32::from_be_bytes(len_buf) as u64;
+        if len == 0 || offset + 4 + len > size {
+            break;
+        }
+        offset += 4 + len;
+    }
+    Ok(offset)
+}
+
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+fn write_atomic(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".partial");
+    fs::write(&tmp, bytes)?;
+    fs::rename(&tmp, path)
+}
+
+/// `path` relative to `base` with `/` separators
+fn relative_path(base: &Path, path: &Path) -> Option<String> {
+    let parts: Vec<_> = path
+        .strip_prefix(base)
+        .ok()?
+        .components()
+        .map(|c| c.as_os_str().to_str())
+        .collect::<Option<_>>()?;
+    Some(parts.join("/"))
+}
+
+/// Reject manifest paths that would escape the restore target
+fn safe_relative(path: &str) -> NodeResult<PathBuf> {
+    let relative = PathBuf::from(path);
+    if relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)))
+    {
+        Ok(relative)
+    } else {
+        Err(NodeError::CorruptBackup(format!("unsafe path {path}")))
+    }
+}
+
+fn io_error(e: io::Error) -> NodeError {
+    NodeError::Io(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn source(data_dir: &Path) -> BackupSource {
+        BackupSource {
+            data_dir: data_dir.to_path_buf(),
+            log_dir: data_dir.join("storage/logs"),
+            node_log: data_dir.join("storage/node_log.bin"),
+            structured: None,
+        }
+    }
+
+    fn frame(payload: &[u8]) -> Vec<u8> {
+        let mut bytes = (payload.len() as u32).to_be_bytes().to_vec();
+        bytes.extend_from_slice(payload);
+        bytes
+    }
+
+    #[tokio::test]
+    async fn test_backup_is_differential_and_restores() {
+        let data = TempDir::new().unwrap();
+        let backups = TempDir::new().unwrap();
+        fs::create_dir_all(data.path().join("storage/logs")).unwrap();
+        fs::write(data.path().join("keystore.bin"), b"keys").unwrap();
+        fs::write(data.path().join("dtn.redb"), b"skipped").unwrap();
+        fs::write(data.path().join("identity_sk.pq"), b"plaintext secret").unwrap();
+        let log = data.path().join("storage/logs/ab.log");
+        let mut log_bytes = frame(b"first");
+        // A torn frame being written concurrently is left out
+        log_bytes.extend_from_slice(&[0, 0, 0, 9, 1]);
+        fs::write(&log, &log_bytes).unwrap();
+
+        let first = write_backup(&source(data.path()), backups.path()).unwrap();
+        assert_eq!(first.files, 2);
+        assert_eq!(first.chunks_written, 2);
+        assert_eq!(first.keys_skipped, vec!["identity_sk.pq".to_string()]);
+
+        // Nothing changed: nothing new is stored
+        let second = write_backup(&source(data.path()), backups.path()).unwrap();
+        assert_eq!(second.chunks_written, 0);
+        assert_ne!(first.manifest, second.manifest);
+
+        fs::write(data.path().join("keystore.bin"), b"new keys").unwrap();
+        let third = write_backup(&source(data.path()), backups.path()).unwrap();
+        assert_eq!(third.chunks_written, 1);
+
+        let report = verify_backup(backups.path(), None).await.unwrap();
+        assert!(report.is_ok());
+        assert_eq!(report.manifest, third.manifest);
+
+        let target = TempDir::new().unwrap();
+        let restored = restore_backup(backups.path(), None, target.path())
+            .await
+            .unwrap();
+        assert_eq!(restored.files, 2);
+        assert_eq!(
+            fs::read(target.path().join("keystore.bin")).unwrap(),
+            b"new keys"
+        );
+        assert_eq!(
+            fs::read(target.path().join("storage/logs/ab.log")).unwrap(),
+            frame(b"first")
+        );
+        assert!(!target.path().join("dtn.redb").exists());
+        assert!(!target.path().join("identity_sk.pq").exists());
+        assert!(!target.path().join("keystore.bin.partial").exists());
+
+        // Restoring the first manifest brings back the old keystore
+        let older = TempDir::new().unwrap();
+        restore_backup(backups.path(), Some(&first.manifest), older.path())
+            .await
+            .unwrap();
+        assert_eq!(
+            fs::read(older.path().join("keystore.bin")).unwrap(),
+            b"keys"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_detects_damaged_chunks() {
+        let data = TempDir::new().unwrap();
+        let backups = TempDir::new().unwrap();
+        fs::write(data.path().join("keystore.bin"), b"keys").unwrap();
+        write_backup(&source(data.path()), backups.path()).unwrap();
+
+        let hash = blake3::hash(b"keys").to_hex().to_string();
+        fs::write(
+            object_path(&backups.path().join(OBJECTS_DIR), &hash),
+            b"oops",
+        )
+        .unwrap();
+
+        let report = verify_backup(backups.path(), None).await.unwrap();
+        assert!(!report.is_ok());
+        assert_eq!(report.corrupt_chunks, vec![hash]);
+        assert_eq!(report.corrupt_files, vec!["keystore.bin".to_string()]);
+
+        let target = TempDir::new().unwrap();
+        assert!(matches!(
+            restore_backup(backups.path(), None, target.path()).await,
+            Err(NodeError::CorruptBackup(_))
+        ));
+    }
+
+    #[test]
+    fn test_rejects_escaping_paths() {
+        assert!(safe_relative("storage/logs/ab.log").is_ok());
+        assert!(safe_relative("../etc/passwd").is_err());
+        assert!(safe_relative("/etc/passwd").is_err());
+    }
+}
@@ -0,0 +1,162 @@
+//! Take, verify and restore differential backups of a node's data directory.
+//!
+//! Usage:
+//!
+//! ```bash
+//! # Back up; repeated runs into the same directory only add changed chunks
+//! indras-backup backup --data-dir ~/.indras --dest /mnt/backups/indras
+//!
+//! # Check the latest backup (or one manifest) against its objects
+//! indras-backup verify /mnt/backups/indras [--manifest 1760000000000.json]
+//!
+//! # Restore into an empty data directory
+//! indras-backup restore /mnt/backups/indras --data-dir ~/.indras-restored
+//! ```
+//!
+//! `backup` opens the node itself, so it needs the node to be stopped; a
+//! running node can back itself up with `IndrasNode::backup`.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use indras_node::{IndrasNode, NodeConfig, restore_backup, verify_backup};
+
+const USAGE: &str = "usage: indras-backup backup --data-dir DIR --dest DIR\n       \
+                     indras-backup verify BACKUP_DIR [--manifest FILE]\n       \
+                     indras-backup restore BACKUP_DIR --data-dir DIR [--manifest FILE]";
+
+enum Command {
+    Backup {
+        data_dir: PathBuf,
+        dest: PathBuf,
+    },
+    Verify {
+        backup_dir: PathBuf,
+        manifest: Option<PathBuf>,
+    },
+    Restore {
+        backup_dir: PathBuf,
+        data_dir: PathBuf,
+        manifest: Option<PathBuf>,
+    },
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut args = args.iter();
+    let command = args.next().ok_or(USAGE)?;
+    let mut backup_dir = None;
+    let mut data_dir = None;
+    let mut dest = None;
+    let mut manifest = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .map(PathBuf::from)
+                .ok_or_else(|| USAGE.to_string())
+        };
+        match arg.as_str() {
+            "--data-dir" => data_dir = Some(value()?),
+            "--dest" => dest = Some(value()?),
+            "--manifest" => manifest = Some(value()?),
+            _ if !arg.starts_with('-') && backup_dir.is_none() => {
+                backup_dir = Some(PathBuf::from(arg))
+            }
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    match command.as_str() {
+        "backup" => Ok(Command::Backup {
+            data_dir: data_dir.ok_or(USAGE)?,
+            dest: dest.ok_or(USAGE)?,
+        }),
+        "verify" => Ok(Command::Verify {
+            backup_dir: backup_dir.ok_or(USAGE)?,
+            manifest,
+        }),
+        "restore" => Ok(Command::Restore {
+            backup_dir: backup_dir.ok_or(USAGE)?,
+            data_dir: data_dir.ok_or(USAGE)?,
+            manifest,
+        }),
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+async fn run(command: Command) -> Result<(), String> {
+    match command {
+        Command::Backup { data_dir, dest } => {
+            let node = IndrasNode::new(NodeConfig::with_data_dir(&data_dir))
+                .await
+                .map_err(|e| e.to_string())?;
+            let summary = node.backup(&dest).await.map_err(|e| e.to_string())?;
+            println!(
+                "{}: {} files, {} bytes ({} new chunks, {} bytes written)",
+                summary.manifest.display(),
+                summary.files,
+                summary.bytes,
+                summary.chunks_written,
+                summary.bytes_written
+            );
+            for key in &summary.keys_skipped {
+                eprintln!("{key}: unencrypted key not backed up");
+            }
+        }
+        Command::Verify {
+            backup_dir,
+            manifest,
+        } => {
+            let report = verify_backup(&backup_dir, manifest.as_deref())
+                .await
+                .map_err(|e| e.to_string())?;
+            for chunk in &report.missing_chunks {
+                println!("missing chunk {chunk}");
+            }
+            for chunk in &report.corrupt_chunks {
+                println!("corrupt chunk {chunk}");
+            }
+            for file in &report.corrupt_files {
+                println!("cannot restore {file}");
+            }
+            if !report.is_ok() {
+                return Err(format!("{}: backup is damaged", report.manifest.display()));
+            }
+            println!("{}: {} files ok", report.manifest.display(), report.files);
+        }
+        Command::Restore {
+            backup_dir,
+            data_dir,
+            manifest,
+        } => {
+            let summary = restore_backup(&backup_dir, manifest.as_deref(), &data_dir)
+                .await
+                .map_err(|e| e.to_string())?;
+            println!(
+                "{}: restored {} files, {} bytes, {} database entries into {}",
+                summary.manifest.display(),
+                summary.files,
+                summary.bytes,
+                summary.structured_entries,
+                data_dir.display()
+            );
+        }
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match parse_args(&args) {
+        Ok(command) => run(command).await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Pretty-print transport packet captures.
+//!
+//! Usage:
+//!
+//! ```bash
+//! # One line per message: time, local -> peer, size, message summary
+//! indras-capture captures/*.icap
+//!
+//! # Full NetworkMessage contents, plus decrypted events if captured
+//! indras-capture -v captures/*.icap
+//!
+//! # Only traffic with one peer (short hex id prefix)
+//! indras-capture --peer 1a2b captures/*.icap
+//! ```
+
+use std::process::ExitCode;
+
+use indras_node::capture::render_record;
+use indras_transport::CaptureReader;
+
+const USAGE: &str = "usage: indras-capture [-v] [--peer ID] FILE...";
+
+fn run(args: &[String]) -> Result<(), String> {
+    let mut verbose = false;
+    let mut peer: Option<String> = None;
+    let mut files = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-v" | "--verbose" => verbose = true,
+            "--peer" => peer = Some(args.next().ok_or(USAGE)?.to_lowercase()),
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ => files.push(arg),
+        }
+    }
+    if files.is_empty() {
+        return Err(USAGE.to_string());
+    }
+
+    for file in files {
+        let reader = CaptureReader::open(file).map_err(|e| format!("{file}: {e}"))?;
+        for record in reader {
+            let record = record.map_err(|e| format!("{file}: {e}"))?;
+            if peer.as_ref().is_some_and(|p| !record.peer_short_id().starts_with(p.as_str())) {
+                continue;
+            }
+            println!("{}", render_record(&record, verbose));
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! Stores [`Bundle<IrohIdentity>`] in a redb database for offline peer delivery.
 //! Bundles survive node restarts, ensuring messages to offline peers are never lost.
+//! Ephemeral nodes use [`BundleStore::in_memory`], which drops bundles on shutdown.
 //!
 //! ## Tables
 //!
@@ -72,6 +73,20 @@ impl BundleStore {
             NodeError::Io(format!("Failed to open DTN database: {e}"))
         })?;
 
+        Self::init(db)
+    }
+
+    /// Create a volatile bundle store backed by memory
+    pub fn in_memory() -> NodeResult<Self> {
+        let db = Database::builder()
+            .create_with_backend(redb::backends::InMemoryBackend::new())
+            .map_err(|e| NodeError::Io(format!("Failed to create in-memory DTN database: {e}")))?;
+
+        Self::init(db)
+    }
+
+    /// Create the tables and wrap the database
+    fn init(db: Database) -> NodeResult<Self> {
         // Initialize tables
         let txn = db.begin_write().map_err(|e| {
             NodeError::Io(format!("Failed to begin DTN table init: {e}"))
@@ -405,6 +420,19 @@ mod tests {
         assert_eq!(retrieved.packet.payload.as_bytes(), b"hello");
     }
 
+    #[test]
+    fn test_in_memory_store() {
+        let store = BundleStore::in_memory().unwrap();
+        let dst = make_identity(2);
+
+        store
+            .store_bundle(&make_bundle(make_identity(1), dst, b"volatile", 3600))
+            .unwrap();
+
+        assert_eq!(store.count().unwrap(), 1);
+        assert_eq!(store.pending_for(&dst).unwrap().len(), 1);
+    }
+
     #[test]
     fn test_pending_for() {
         let (store, _dir) = temp_store();
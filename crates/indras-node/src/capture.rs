@@ -0,0 +1,240 @@
+//! Decoding and decryption for transport packet captures
+//!
+//! The transport records raw message bytes (see
+//! [`indras_transport::capture`]). This module knows their format: it
+//! decrypts interface events with the node's keys while capturing, and
+//! renders captured [`NetworkMessage`]s for the `indras-capture` reader.
+
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use indras_core::{InterfaceEvent, InterfaceId};
+use indras_crypto::{EncryptedData, InterfaceKey};
+use indras_transport::{CaptureDecryptor, CaptureRecord, IrohIdentity};
+
+use crate::message_handler::{InterfaceEventMessage, NetworkMessage, SignedNetworkMessage};
+
+/// A captured message after decoding
+#[derive(Debug, Clone)]
+pub enum DecodedMessage {
+    /// PQ-signed message
+    Signed {
+        /// Sender's short verifying key fingerprint
+        signer: String,
+        /// The signed message
+        message: NetworkMessage,
+    },
+    /// Unsigned message (legacy or anonymous)
+    Unsigned(NetworkMessage),
+    /// Bytes that aren't a node message (e.g. relay traffic)
+    Unknown,
+}
+
+impl DecodedMessage {
+    /// Decode raw captured bytes
+    pub fn decode(data: &[u8]) -> Self {
+        if let Ok(signed) = SignedNetworkMessage::from_bytes(data) {
+            let signer = signed
+                .sender_verifying_key
+                .iter()
+                .take(4)
+                .map(|b| format!("{b:02x}"))
+                .collect();
+            return DecodedMessage::Signed {
+                signer,
+                message: signed.message,
+            };
+        }
+        match NetworkMessage::from_bytes(data) {
+            Ok(message) => DecodedMessage::Unsigned(message),
+            Err(_) => DecodedMessage::Unknown,
+        }
+    }
+
+    /// The network message, if decoding succeeded
+    pub fn message(&self) -> Option<&NetworkMessage> {
+        match self {
+            DecodedMessage::Signed { message, .. } | DecodedMessage::Unsigned(message) => {
+                Some(message)
+            }
+            DecodedMessage::Unknown => None,
+        }
+    }
+}
+
+/// The encrypted event carried by a message, if any
+fn encrypted_event(message: &NetworkMessage) -> Option<&InterfaceEventMessage> {
+    match message {
+        NetworkMessage::InterfaceEvent(msg)
+        | NetworkMessage::AnonymousEvent(msg)
+        | NetworkMessage::AnonymousRelay(msg) => Some(msg),
+        NetworkMessage::CausalEvent(msg) => Some(&msg.event),
+        _ => None,
+    }
+}
+
+/// Decrypts captured interface events with the node's interface keys
+pub struct NodeCaptureDecryptor {
+    interface_keys: Arc<DashMap<InterfaceId, InterfaceKey>>,
+}
+
+impl NodeCaptureDecryptor {
+    /// Create a decryptor sharing the node's key map
+    pub fn new(interface_keys: Arc<DashMap<InterfaceId, InterfaceKey>>) -> Self {
+        Self { interface_keys }
+    }
+}
+
+impl CaptureDecryptor for NodeCaptureDecryptor {
+    fn decrypt(&self, data: &[u8]) -> Option<Vec<u8>> {
+        let decoded = DecodedMessage::decode(data);
+        let event = encrypted_event(decoded.message()?)?;
+        let key = self.interface_keys.get(&event.interface_id)?;
+        key.decrypt(&EncryptedData {
+            nonce: event.nonce,
+            ciphertext: event.ciphertext.clone(),
+        })
+        .ok()
+    }
+}
+
+/// One-line summary of a network message
+pub fn summarize(message: &NetworkMessage) -> String {
+    match message {
+        NetworkMessage::InterfaceEvent(m) => format!(
+            "InterfaceEvent interface={} event={:?} {}B",
+            m.interface_id,
+            m.event_id,
+            m.ciphertext.len()
+        ),
+        NetworkMessage::CausalEvent(m) => format!(
+            "CausalEvent interface={} event={:?} deps={} {}B",
+            m.event.interface_id,
+            m.event.event_id,
+            m.deps.len(),
+            m.event.ciphertext.len()
+        ),
+        NetworkMessage::AnonymousEvent(m) => format!(
+            "AnonymousEvent interface={} event={:?} {}B",
+            m.interface_id,
+            m.event_id,
+            m.ciphertext.len()
+        ),
+        NetworkMessage::AnonymousRelay(m) => format!(
+            "AnonymousRelay interface={} event={:?} {}B",
+            m.interface_id,
+            m.event_id,
+            m.ciphertext.len()
+        ),
+        NetworkMessage::SyncRequest(m) => {
+            format!("SyncRequest interface={} sync={}B", m.interface_id, m.sync_data.len())
+        }
+        NetworkMessage::SyncResponse(m) => {
+            format!("SyncResponse interface={} sync={}B", m.interface_id, m.sync_data.len())
+        }
+        NetworkMessage::EventAck(m) => {
+            format!("EventAck interface={} up_to={:?}", m.interface_id, m.up_to)
+        }
+        NetworkMessage::DtnBundle(_) => "DtnBundle".to_string(),
+        NetworkMessage::DtnCustody(_) => "DtnCustody".to_string(),
+        NetworkMessage::Delegated(_) => "Delegated".to_string(),
+        NetworkMessage::Hello(h) => format!(
+            "Hello v{} (min {}) caps=[{}] agent={}{}",
+            h.protocol_version,
+            h.min_protocol_version,
+            h.capabilities,
+            h.agent,
+            if h.reply { " reply" } else { "" }
+        ),
+        NetworkMessage::MemberCertificate(c) => {
+            format!("MemberCertificate member={}", c.member_name)
+        }
+    }
+}
+
+/// Render a capture record for humans
+///
+/// The header line has the timestamp, direction, peers, size, and message
+/// summary. With `verbose`, the full message and any decrypted event follow.
+pub fn render_record(record: &CaptureRecord, verbose: bool) -> String {
+    let decoded = DecodedMessage::decode(&record.data);
+    let summary = match &decoded {
+        DecodedMessage::Signed { signer, message } => {
+            format!("{} [signed {}]", summarize(message), signer)
+        }
+        DecodedMessage::Unsigned(message) => format!("{} [unsigned]", summarize(message)),
+        DecodedMessage::Unknown => "<not a node message>".to_string(),
+    };
+
+    let mut out = format!(
+        "{} {} {} {} {}B  {}",
+        record.timestamp.format("%H:%M:%S%.6f"),
+        record.local_short_id(),
+        record.direction,
+        record.peer_short_id(),
+        record.data.len(),
+        summary
+    );
+
+    if verbose {
+        if let Some(message) = decoded.message() {
+            let _ = write!(out, "\n{message:#?}");
+        }
+        if let Some(plaintext) = &record.plaintext {
+            match postcard::from_bytes::<InterfaceEvent<IrohIdentity>>(plaintext) {
+                Ok(event) => {
+                    let _ = write!(out, "\ndecrypted: {event:#?}");
+                }
+                Err(_) => {
+                    let _ = write!(out, "\ndecrypted: {} bytes (undecodable)", plaintext.len());
+                }
+            }
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message_handler::EventAckMessage;
+    use indras_core::EventId;
+
+    #[test]
+    fn test_decode_unsigned_and_unknown() {
+        let msg = NetworkMessage::EventAck(EventAckMessage {
+            interface_id: InterfaceId::new([7; 32]),
+            up_to: EventId::new(1, 3),
+        });
+        let bytes = msg.to_bytes().unwrap();
+        assert!(matches!(
+            DecodedMessage::decode(&bytes),
+            DecodedMessage::Unsigned(NetworkMessage::EventAck(_))
+        ));
+        assert!(summarize(&msg).starts_with("EventAck"));
+        assert!(matches!(DecodedMessage::decode(&[0xff; 3]), DecodedMessage::Unknown));
+    }
+
+    #[test]
+    fn test_decryptor_opens_interface_event() {
+        let interface_id = InterfaceId::new([9; 32]);
+        let key = InterfaceKey::generate(interface_id);
+        let encrypted = key.encrypt(b"secret").unwrap();
+        let msg = NetworkMessage::InterfaceEvent(InterfaceEventMessage::new(
+            interface_id,
+            encrypted.ciphertext,
+            EventId::new(1, 1),
+            encrypted.nonce,
+        ));
+
+        let keys = Arc::new(DashMap::new());
+        let decryptor = NodeCaptureDecryptor::new(Arc::clone(&keys));
+        let bytes = msg.to_bytes().unwrap();
+        assert!(decryptor.decrypt(&bytes).is_none());
+
+        keys.insert(interface_id, key);
+        assert_eq!(decryptor.decrypt(&bytes).as_deref(), Some(&b"secret"[..]));
+    }
+}
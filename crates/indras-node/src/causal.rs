@@ -0,0 +1,604 @@
+//! Causal delivery for application events
+//!
+//! Realms that opt in to causal delivery attach the [`EventId`]s an event
+//! causally depends on (the sender's delivered frontier at send time). On
+//! the receiving side, events whose dependencies have not yet been delivered
+//! locally are held back until every dependency arrives, so a reply is never
+//! surfaced before its parent.
+//!
+//! A node keeps a buffer only for interfaces it opted in to, or that
+//! received an event carrying dependencies. Events delivered on other
+//! interfaces are not tracked, so the first dependent event on such an
+//! interface may wait for dependencies delivered before it arrived.
+//!
+//! Buffering is bounded: an event that waits longer than
+//! [`CausalConfig::max_wait`], or that would push the buffer past
+//! [`CausalConfig::max_pending`], is released anyway. Causal order is a
+//! best-effort guarantee layered over an unreliable transport — a lost
+//! dependency must never wedge the realm.
+//!
+//! ## Flow
+//!
+//! ```text
+//! send:    deps = frontier()  →  CausalEvent { event, deps }  →  mark_delivered(event)
+//! receive: offer(event, deps) →  [ready events in causal order] →  append + broadcast
+//! tick:    expire(now)        →  [timed-out events]            →  append + broadcast
+//! ```
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use dashmap::{DashMap, DashSet};
+use indras_core::{EventId, InterfaceEvent, InterfaceId};
+use indras_transport::IrohIdentity;
+
+/// Default maximum number of events held back per interface.
+pub const DEFAULT_MAX_PENDING: usize = 1024;
+
+/// Default time an event may wait for missing dependencies.
+pub const DEFAULT_MAX_WAIT: Duration = Duration::from_secs(30);
+
+/// Default number of delivered event IDs remembered per interface.
+pub const DEFAULT_MAX_TRACKED: usize = 8192;
+
+/// Maximum number of dependencies attached to a single outgoing event.
+///
+/// The frontier of a busy realm can grow wide when many members post
+/// concurrently; capping it keeps wire overhead predictable.
+pub const MAX_DEPS_PER_EVENT: usize = 16;
+
+/// Tuning knobs for causal buffering.
+#[derive(Debug, Clone)]
+pub struct CausalConfig {
+    /// Maximum number of events held back per interface
+    pub max_pending: usize,
+    /// Maximum time an event waits before being released regardless
+    pub max_wait: Duration,
+    /// Number of delivered event IDs remembered per interface
+    pub max_tracked: usize,
+}
+
+impl Default for CausalConfig {
+    fn default() -> Self {
+        Self {
+            max_pending: DEFAULT_MAX_PENDING,
+            max_wait: DEFAULT_MAX_WAIT,
+            max_tracked: DEFAULT_MAX_TRACKED,
+        }
+    }
+}
+
+/// An event held back until its dependencies are delivered.
+struct PendingEvent<T> {
+    item: T,
+    missing: HashSet<EventId>,
+    deps: Vec<EventId>,
+    received_at: Instant,
+}
+
+/// Per-interface causal delivery buffer.
+///
+/// Generic over the buffered payload so the ordering logic can be
+/// exercised without constructing full interface events.
+pub struct CausalBuffer<T> {
+    config: CausalConfig,
+    /// Delivered event IDs, bounded by `config.max_tracked`
+    delivered: HashSet<EventId>,
+    /// Insertion order of `delivered`, for FIFO eviction
+    delivered_order: VecDeque<EventId>,
+    /// Delivered events not yet referenced by a later delivered event
+    frontier: HashSet<EventId>,
+    /// Dependencies attached to locally authored events still in
+    /// `delivered`, for retransmission
+    sent: HashMap<EventId, Vec<EventId>>,
+    /// Events waiting on at least one dependency
+    pending: HashMap<EventId, PendingEvent<T>>,
+    /// Reverse index: missing dependency → events waiting on it
+    waiting_on: HashMap<EventId, HashSet<EventId>>,
+}
+
+impl<T> CausalBuffer<T> {
+    /// Create an empty buffer.
+    pub fn new(config: CausalConfig) -> Self {
+        Self {
+            config,
+            delivered: HashSet::new(),
+            delivered_order: VecDeque::new(),
+            frontier: HashSet::new(),
+            sent: HashMap::new(),
+            pending: HashMap::new(),
+            waiting_on: HashMap::new(),
+        }
+    }
+
+    /// Whether an event has already been delivered locally.
+    pub fn is_delivered(&self, id: &EventId) -> bool {
+        self.delivered.contains(id)
+    }
+
+    /// Number of events currently held back.
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// The current causal frontier, capped at [`MAX_DEPS_PER_EVENT`].
+    ///
+    /// These are the dependencies a locally authored event should carry.
+    pub fn frontier(&self) -> Vec<EventId> {
+        let mut heads: Vec<EventId> = self.frontier.iter().copied().collect();
+        heads.sort();
+        heads.truncate(MAX_DEPS_PER_EVENT);
+        heads
+    }
+
+    /// Record an event as delivered (locally authored or released).
+    pub fn mark_delivered(&mut self, id: EventId, deps: &[EventId]) {
+        if !self.delivered.insert(id) {
+            return;
+        }
+        self.delivered_order.push_back(id);
+        while self.delivered_order.len() > self.config.max_tracked {
+            if let Some(old) = self.delivered_order.pop_front() {
+                self.delivered.remove(&old);
+                self.frontier.remove(&old);
+                self.sent.remove(&old);
+            }
+        }
+        for dep in deps {
+            self.frontier.remove(dep);
+        }
+        self.frontier.insert(id);
+    }
+
+    /// Record a locally authored event as delivered, keeping the
+    /// dependencies it was sent with for as long as it is tracked.
+    pub fn mark_sent(&mut self, id: EventId, deps: Vec<EventId>) {
+        self.mark_delivered(id, &deps);
+        if !deps.is_empty() && self.delivered.contains(&id) {
+            self.sent.insert(id, deps);
+        }
+    }
+
+    /// Dependencies a locally authored event was sent with.
+    pub fn sent_deps(&self, id: &EventId) -> Option<&Vec<EventId>> {
+        self.sent.get(id)
+    }
+
+    /// Offer an incoming event.
+    ///
+    /// Returns every event that became deliverable as a result, in an
+    /// order that respects the declared dependencies. Duplicates of an
+    /// already delivered or already pending event are dropped.
+    pub fn offer(&mut self, id: EventId, deps: Vec<EventId>, item: T, now: Instant) -> Vec<T> {
+        if self.delivered.contains(&id) || self.pending.contains_key(&id) {
+            return Vec::new();
+        }
+
+        let missing: HashSet<EventId> = deps
+            .iter()
+            .filter(|d| **d != id && !self.delivered.contains(d))
+            .copied()
+            .collect();
+
+        if missing.is_empty() {
+            self.mark_delivered(id, &deps);
+            let mut ready = vec![item];
+            self.release_dependents(id, &mut ready);
+            return ready;
+        }
+
+        for dep in &missing {
+            self.waiting_on.entry(*dep).or_default().insert(id);
+        }
+        self.pending.insert(
+            id,
+            PendingEvent {
+                item,
+                missing,
+                deps,
+                received_at: now,
+            },
+        );
+
+        let mut ready = Vec::new();
+        while self.pending.len() > self.config.max_pending {
+            let Some(oldest) = self
+                .pending
+                .iter()
+                .min_by_key(|(_, p)| p.received_at)
+                .map(|(id, _)| *id)
+            else {
+                break;
+            };
+            self.force_release(oldest, &mut ready);
+        }
+        ready
+    }
+
+    /// Release events that have waited longer than the configured limit.
+    pub fn expire(&mut self, now: Instant) -> Vec<T> {
+        let mut expired: Vec<(Instant, EventId)> = self
+            .pending
+            .iter()
+            .filter(|(_, p)| now.saturating_duration_since(p.received_at) >= self.config.max_wait)
+            .map(|(id, p)| (p.received_at, *id))
+            .collect();
+        expired.sort();
+
+        let mut ready = Vec::new();
+        for (_, id) in expired {
+            self.force_release(id, &mut ready);
+        }
+        ready
+    }
+
+    /// Deliver a pending event even though dependencies are still missing.
+    fn force_release(&mut self, id: EventId, ready: &mut Vec<T>) {
+        let Some(pending) = self.pending.remove(&id) else {
+            return;
+        };
+        for dep in &pending.missing {
+            if let Some(waiters) = self.waiting_on.get_mut(dep) {
+                waiters.remove(&id);
+                if waiters.is_empty() {
+                    self.waiting_on.remove(dep);
+                }
+            }
+        }
+        self.mark_delivered(id, &pending.deps);
+        ready.push(pending.item);
+        self.release_dependents(id, ready);
+    }
+
+    /// Release every pending event transitively unblocked by `delivered`.
+    fn release_dependents(&mut self, delivered: EventId, ready: &mut Vec<T>) {
+        let mut queue = VecDeque::from([delivered]);
+        while let Some(dep) = queue.pop_front() {
+            let Some(waiters) = self.waiting_on.remove(&dep) else {
+                continue;
+            };
+            let mut waiters: Vec<EventId> = waiters.into_iter().collect();
+            waiters.sort();
+            for waiter in waiters {
+                let unblocked = match self.pending.get_mut(&waiter) {
+                    Some(p) => {
+                        p.missing.remove(&dep);
+                        p.missing.is_empty()
+                    }
+                    None => false,
+                };
+                if unblocked && let Some(p) = self.pending.remove(&waiter) {
+                    self.mark_delivered(waiter, &p.deps);
+                    ready.push(p.item);
+                    queue.push_back(waiter);
+                }
+            }
+        }
+    }
+}
+
+/// Node-wide causal delivery state across all interfaces.
+///
+/// Senders only attach dependencies for interfaces that have opted in via
+/// [`set_enabled`](Self::set_enabled). Receivers honour dependencies on any
+/// event that carries them, so one member opting in is enough for their
+/// events to arrive in order everywhere. Interfaces that neither opted in
+/// nor received such an event get no buffer.
+pub struct CausalTracker {
+    config: CausalConfig,
+    enabled: DashSet<InterfaceId>,
+    buffers: DashMap<InterfaceId, Mutex<CausalBuffer<InterfaceEvent<IrohIdentity>>>>,
+}
+
+impl CausalTracker {
+    /// Create a tracker with the given buffering limits.
+    pub fn new(config: CausalConfig) -> Self {
+        Self {
+            config,
+            enabled: DashSet::new(),
+            buffers: DashMap::new(),
+        }
+    }
+
+    /// Opt an interface in or out of causal delivery for outgoing events.
+    pub fn set_enabled(&self, interface_id: InterfaceId, enabled: bool) {
+        if enabled {
+            self.enabled.insert(interface_id);
+        } else {
+            self.enabled.remove(&interface_id);
+        }
+    }
+
+    /// Whether outgoing events on this interface carry dependencies.
+    pub fn is_enabled(&self, interface_id: &InterfaceId) -> bool {
+        self.enabled.contains(interface_id)
+    }
+
+    /// Run `f` against the buffer for an interface, creating it on demand.
+    fn with_buffer<R>(
+        &self,
+        interface_id: InterfaceId,
+        f: impl FnOnce(&mut CausalBuffer<InterfaceEvent<IrohIdentity>>) -> R,
+    ) -> R {
+        let entry = self
+            .buffers
+            .entry(interface_id)
+            .or_insert_with(|| Mutex::new(CausalBuffer::new(self.config.clone())));
+        let mut buffer = entry.lock().unwrap_or_else(|e| e.into_inner());
+        f(&mut buffer)
+    }
+
+    /// Run `f` against the buffer for an interface that has one or has
+    /// opted in. Returns `None` for any other interface.
+    fn with_tracked_buffer<R>(
+        &self,
+        interface_id: InterfaceId,
+        f: impl FnOnce(&mut CausalBuffer<InterfaceEvent<IrohIdentity>>) -> R,
+    ) -> Option<R> {
+        if self.is_enabled(&interface_id) {
+            return Some(self.with_buffer(interface_id, f));
+        }
+        let entry = self.buffers.get(&interface_id)?;
+        let mut buffer = entry.lock().unwrap_or_else(|e| e.into_inner());
+        Some(f(&mut buffer))
+    }
+
+    /// Dependencies for a new locally authored event.
+    ///
+    /// Returns an empty list when the interface has not opted in.
+    pub fn deps_for_send(&self, interface_id: &InterfaceId) -> Vec<EventId> {
+        if !self.is_enabled(interface_id) {
+            return Vec::new();
+        }
+        self.with_buffer(*interface_id, |b| b.frontier())
+    }
+
+    /// Record a locally authored event as delivered, on a tracked
+    /// interface.
+    pub fn record_local(&self, interface_id: InterfaceId, event_id: EventId, deps: Vec<EventId>) {
+        self.with_tracked_buffer(interface_id, |b| b.mark_sent(event_id, deps));
+    }
+
+    /// Dependencies originally attached to a locally authored event, while
+    /// it is still among the interface's tracked events.
+    pub fn sent_deps(&self, interface_id: &InterfaceId, event_id: &EventId) -> Option<Vec<EventId>> {
+        let buffer = self.buffers.get(interface_id)?;
+        let buffer = buffer.lock().unwrap_or_else(|e| e.into_inner());
+        buffer.sent_deps(event_id).cloned()
+    }
+
+    /// Record an event that arrived without dependencies, on a tracked
+    /// interface.
+    pub fn record_received(&self, interface_id: InterfaceId, event_id: EventId) {
+        self.with_tracked_buffer(interface_id, |b| b.mark_delivered(event_id, &[]));
+    }
+
+    /// Offer an incoming event carrying dependencies.
+    pub fn offer(
+        &self,
+        interface_id: InterfaceId,
+        event_id: EventId,
+        deps: Vec<EventId>,
+        event: InterfaceEvent<IrohIdentity>,
+    ) -> Vec<InterfaceEvent<IrohIdentity>> {
+        self.with_buffer(interface_id, |b| b.offer(event_id, deps, event, Instant::now()))
+    }
+
+    /// Release timed-out events across all interfaces.
+    pub fn expire(&self) -> Vec<(InterfaceId, InterfaceEvent<IrohIdentity>)> {
+        let now = Instant::now();
+        let mut released = Vec::new();
+        for entry in self.buffers.iter() {
+            let mut buffer = entry.value().lock().unwrap_or_else(|e| e.into_inner());
+            if buffer.pending_len() == 0 {
+                continue;
+            }
+            released.extend(buffer.expire(now).into_iter().map(|e| (*entry.key(), e)));
+        }
+        released
+    }
+
+    /// Number of events currently held back on an interface.
+    pub fn pending_len(&self, interface_id: &InterfaceId) -> usize {
+        self.buffers
+            .get(interface_id)
+            .map(|b| b.lock().unwrap_or_else(|e| e.into_inner()).pending_len())
+            .unwrap_or(0)
+    }
+
+    /// Drop all state for an interface (on leave).
+    pub fn forget(&self, interface_id: &InterfaceId) {
+        self.enabled.remove(interface_id);
+        self.buffers.remove(interface_id);
+    }
+}
+
+impl Default for CausalTracker {
+    fn default() -> Self {
+        Self::new(CausalConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(n: u64) -> EventId {
+        EventId::new(0xA, n)
+    }
+
+    fn buffer() -> CausalBuffer<u64> {
+        CausalBuffer::new(CausalConfig::default())
+    }
+
+    #[test]
+    fn test_event_without_deps_delivers_immediately() {
+        let mut b = buffer();
+        let ready = b.offer(id(1), vec![], 1, Instant::now());
+        assert_eq!(ready, vec![1]);
+        assert!(b.is_delivered(&id(1)));
+    }
+
+    #[test]
+    fn test_reply_waits_for_parent() {
+        let mut b = buffer();
+        let now = Instant::now();
+
+        // Reply arrives first
+        assert!(b.offer(id(2), vec![id(1)], 2, now).is_empty());
+        assert_eq!(b.pending_len(), 1);
+
+        // Parent arrives: both released, parent first
+        let ready = b.offer(id(1), vec![], 1, now);
+        assert_eq!(ready, vec![1, 2]);
+        assert_eq!(b.pending_len(), 0);
+    }
+
+    #[test]
+    fn test_transitive_release_chain() {
+        let mut b = buffer();
+        let now = Instant::now();
+        assert!(b.offer(id(3), vec![id(2)], 3, now).is_empty());
+        assert!(b.offer(id(2), vec![id(1)], 2, now).is_empty());
+        assert_eq!(b.offer(id(1), vec![], 1, now), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_multiple_deps_all_required() {
+        let mut b = buffer();
+        let now = Instant::now();
+        assert!(b.offer(id(3), vec![id(1), id(2)], 3, now).is_empty());
+        assert_eq!(b.offer(id(1), vec![], 1, now), vec![1]);
+        assert_eq!(b.offer(id(2), vec![], 2, now), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_duplicates_are_dropped() {
+        let mut b = buffer();
+        let now = Instant::now();
+        assert_eq!(b.offer(id(1), vec![], 1, now), vec![1]);
+        assert!(b.offer(id(1), vec![], 1, now).is_empty());
+
+        assert!(b.offer(id(3), vec![id(2)], 3, now).is_empty());
+        assert!(b.offer(id(3), vec![id(2)], 3, now).is_empty());
+        assert_eq!(b.pending_len(), 1);
+    }
+
+    #[test]
+    fn test_expire_releases_stuck_events() {
+        let config = CausalConfig {
+            max_wait: Duration::from_secs(5),
+            ..Default::default()
+        };
+        let mut b = CausalBuffer::new(config);
+        let start = Instant::now();
+
+        assert!(b.offer(id(2), vec![id(1)], 2, start).is_empty());
+        assert!(b.expire(start + Duration::from_secs(1)).is_empty());
+        assert_eq!(b.expire(start + Duration::from_secs(5)), vec![2]);
+        assert_eq!(b.pending_len(), 0);
+
+        // A late-arriving parent is still delivered on its own
+        assert_eq!(b.offer(id(1), vec![], 1, start), vec![1]);
+    }
+
+    #[test]
+    fn test_overflow_releases_oldest() {
+        let config = CausalConfig {
+            max_pending: 2,
+            ..Default::default()
+        };
+        let mut b = CausalBuffer::new(config);
+        let start = Instant::now();
+
+        assert!(b.offer(id(10), vec![id(1)], 10, start).is_empty());
+        assert!(b.offer(id(11), vec![id(1)], 11, start + Duration::from_millis(1)).is_empty());
+        let ready = b.offer(id(12), vec![id(1)], 12, start + Duration::from_millis(2));
+        assert_eq!(ready, vec![10]);
+        assert_eq!(b.pending_len(), 2);
+    }
+
+    #[test]
+    fn test_frontier_tracks_heads() {
+        let mut b = buffer();
+        b.mark_delivered(id(1), &[]);
+        b.mark_delivered(id(2), &[]);
+        assert_eq!(b.frontier(), vec![id(1), id(2)]);
+
+        b.mark_delivered(id(3), &[id(1), id(2)]);
+        assert_eq!(b.frontier(), vec![id(3)]);
+    }
+
+    #[test]
+    fn test_frontier_is_capped() {
+        let mut b = buffer();
+        for n in 0..(MAX_DEPS_PER_EVENT as u64 * 2) {
+            b.mark_delivered(id(n), &[]);
+        }
+        assert_eq!(b.frontier().len(), MAX_DEPS_PER_EVENT);
+    }
+
+    #[test]
+    fn test_tracked_ids_are_bounded() {
+        let config = CausalConfig {
+            max_tracked: 3,
+            ..Default::default()
+        };
+        let mut b: CausalBuffer<u64> = CausalBuffer::new(config);
+        for n in 1..=5 {
+            b.mark_delivered(id(n), &[]);
+        }
+        assert!(!b.is_delivered(&id(1)));
+        assert!(!b.is_delivered(&id(2)));
+        assert!(b.is_delivered(&id(5)));
+    }
+
+    #[test]
+    fn test_tracker_opt_in_controls_send_deps() {
+        let tracker = CausalTracker::default();
+        let iid = InterfaceId::new([7u8; 32]);
+
+        tracker.set_enabled(iid, true);
+        tracker.record_local(iid, id(1), vec![]);
+        assert_eq!(tracker.deps_for_send(&iid), vec![id(1)]);
+
+        tracker.set_enabled(iid, false);
+        assert!(tracker.deps_for_send(&iid).is_empty());
+
+        tracker.forget(&iid);
+        assert!(!tracker.is_enabled(&iid));
+        assert!(tracker.deps_for_send(&iid).is_empty());
+    }
+
+    #[test]
+    fn test_tracker_skips_untracked_interfaces() {
+        let tracker = CausalTracker::default();
+        let iid = InterfaceId::new([7u8; 32]);
+
+        tracker.record_received(iid, id(1));
+        tracker.record_local(iid, id(2), vec![id(1)]);
+        assert!(tracker.buffers.is_empty());
+        assert_eq!(tracker.sent_deps(&iid, &id(2)), None);
+
+        tracker.set_enabled(iid, true);
+        tracker.record_local(iid, id(3), vec![id(2)]);
+        assert_eq!(tracker.sent_deps(&iid, &id(3)), Some(vec![id(2)]));
+    }
+
+    #[test]
+    fn test_sent_deps_evicted_with_tracked_ids() {
+        let config = CausalConfig {
+            max_tracked: 2,
+            ..Default::default()
+        };
+        let mut b: CausalBuffer<u64> = CausalBuffer::new(config);
+        b.mark_sent(id(2), vec![id(1)]);
+        assert_eq!(b.sent_deps(&id(2)), Some(&vec![id(1)]));
+
+        b.mark_sent(id(3), vec![id(2)]);
+        b.mark_sent(id(4), vec![id(3)]);
+        assert_eq!(b.sent_deps(&id(2)), None);
+        assert_eq!(b.sent_deps(&id(4)), Some(&vec![id(3)]));
+    }
+}
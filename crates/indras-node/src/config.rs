@@ -1,10 +1,14 @@
 //! Configuration for the node coordinator
 
 use std::path::PathBuf;
+use std::time::Duration;
 
 use indras_dtn::DtnConfig;
 use indras_storage::CompositeStorageConfig;
-use indras_transport::AdapterConfig;
+use indras_transport::{AccessConfig, AdapterConfig};
+
+use crate::connection_budget::BudgetConfig;
+use crate::supervisor::RestartPolicy;
 
 /// Configuration for an IndrasNode
 #[derive(Debug, Clone)]
@@ -39,8 +43,37 @@ pub struct NodeConfig {
     /// Controls store-and-forward behavior for offline peers:
     /// custody transfer, epidemic routing, bundle expiration, and strategy selection.
     pub dtn: DtnConfig,
+    /// Maximum number of gossip topics rejoined concurrently on startup
+    pub interface_load_concurrency: usize,
+    /// Run without touching disk
+    ///
+    /// Keys are generated fresh and held in memory, storage and the DTN
+    /// bundle store are volatile, and the embedded relay is not started.
+    /// `data_dir` is ignored. See [`NodeConfig::ephemeral`].
+    pub ephemeral: bool,
+    /// How long a single outbound connection attempt may take
+    ///
+    /// Applies to bootstrap and explicit peer connects. A stalled peer
+    /// fails the attempt with [`NodeError::Timeout`](crate::NodeError::Timeout)
+    /// instead of blocking the caller.
+    pub connect_timeout: Duration,
+    /// How long a single request to a peer (sync, delegation, topic join) may take
+    pub request_timeout: Duration,
+    /// Connection caps, idle reaping and on-demand dialing
+    ///
+    /// See [`crate::connection_budget`]. Use [`BudgetConfig::unlimited`] to
+    /// keep every connection open.
+    pub connection_budget: BudgetConfig,
+    /// Backoff and give-up limits for restarting crashed background tasks
+    pub restart_policy: RestartPolicy,
 }
 
+/// Default for [`NodeConfig::connect_timeout`]
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default for [`NodeConfig::request_timeout`]
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
 impl Default for NodeConfig {
     fn default() -> Self {
         let data_dir = PathBuf::from("./indras-data");
@@ -55,6 +88,12 @@ impl Default for NodeConfig {
             passphrase: None,
             homepage_port: None,
             dtn: DtnConfig::default(),
+            interface_load_concurrency: 32,
+            ephemeral: false,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            connection_budget: BudgetConfig::default(),
+            restart_policy: RestartPolicy::default(),
         }
     }
 }
@@ -74,6 +113,26 @@ impl NodeConfig {
             passphrase: None,
             homepage_port: None,
             dtn: DtnConfig::default(),
+            interface_load_concurrency: 32,
+            ephemeral: false,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            connection_budget: BudgetConfig::default(),
+            restart_policy: RestartPolicy::default(),
+        }
+    }
+
+    /// Create a configuration for a node with zero disk I/O
+    ///
+    /// Useful for unit tests, bots and CI: every restart is a brand-new
+    /// identity with empty storage, and dropping the node leaves nothing
+    /// behind.
+    pub fn ephemeral() -> Self {
+        Self {
+            data_dir: PathBuf::new(),
+            storage: CompositeStorageConfig::in_memory(),
+            ephemeral: true,
+            ..Self::default()
         }
     }
 
@@ -89,6 +148,12 @@ impl NodeConfig {
         self
     }
 
+    /// Restrict which peers may connect (see [`AccessConfig`])
+    pub fn with_access(mut self, access: AccessConfig) -> Self {
+        self.transport.access = access;
+        self
+    }
+
     /// Set the storage configuration
     pub fn with_storage(mut self, storage: CompositeStorageConfig) -> Self {
         self.storage = storage;
@@ -134,6 +199,36 @@ impl NodeConfig {
         self
     }
 
+    /// Set how many gossip topics are rejoined concurrently on startup
+    pub fn with_interface_load_concurrency(mut self, limit: usize) -> Self {
+        self.interface_load_concurrency = limit;
+        self
+    }
+
+    /// Set how long an outbound connection attempt may take
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Set how long a single request to a peer may take
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Set the connection budget (caps, idle reaping, on-demand dialing)
+    pub fn with_connection_budget(mut self, budget: BudgetConfig) -> Self {
+        self.connection_budget = budget;
+        self
+    }
+
+    /// Set how crashed background tasks are restarted
+    pub fn with_restart_policy(mut self, policy: RestartPolicy) -> Self {
+        self.restart_policy = policy;
+        self
+    }
+
     /// Set the DTN configuration for offline peer delivery
     pub fn with_dtn(mut self, dtn: DtnConfig) -> Self {
         self.dtn = dtn;
@@ -0,0 +1,365 @@
+//! Connection budget: caps, priorities and idle reaping
+//!
+//! A node in many interfaces would otherwise keep a connection open to
+//! every member it has ever synced with. [`ConnectionBudget`] decides which
+//! connections are worth holding:
+//!
+//! - **Pinned peers** (typically contacts) are always kept and may always
+//!   be dialled.
+//! - **Idle connections** — no interface traffic in either direction for
+//!   [`BudgetConfig::idle_timeout`] — are closed.
+//! - **Per-interface cap**: each interface keeps at most
+//!   [`BudgetConfig::max_per_interface`] of its members connected, the
+//!   best-ranked ones. A peer is kept if any interface it shares with us
+//!   ranks it that high.
+//! - **Global cap**: beyond [`BudgetConfig::max_connections`] the
+//!   lowest-ranked connections are closed.
+//!
+//! Peers rank by pinned, then whether they share an interface with
+//! traffic in the last [`BudgetConfig::active_window`], then by their own
+//! last traffic. Sync exchanges don't count as traffic: the sync task talks
+//! to every connected member, so they would keep everything alive.
+//!
+//! The node runs [`ConnectionBudget::plan`] as a periodic job and closes
+//! what it returns. When a send targets a member that isn't connected, the
+//! node dials it on demand if [`ConnectionBudget::may_dial`] allows.
+//! The transport's own `max_connections` remains the hard limit; this is
+//! the softer target the node steers towards.
+
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use dashmap::{DashMap, DashSet};
+use indras_core::InterfaceId;
+use indras_transport::IrohIdentity;
+
+/// Tuning for the [`ConnectionBudget`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BudgetConfig {
+    /// Connections the node aims to stay under (pinned peers excepted)
+    pub max_connections: usize,
+    /// Members of a single interface kept connected
+    pub max_per_interface: usize,
+    /// Close connections without interface traffic for this long
+    pub idle_timeout: Duration,
+    /// Interfaces with traffic this recently rank their members higher
+    pub active_window: Duration,
+    /// How often connections are checked against the budget
+    pub reap_interval: Duration,
+    /// Dial unconnected members when a send targets them
+    pub dial_on_demand: bool,
+}
+
+impl Default for BudgetConfig {
+    fn default() -> Self {
+        Self {
+            max_connections: 64,
+            max_per_interface: 8,
+            idle_timeout: Duration::from_secs(5 * 60),
+            active_window: Duration::from_secs(15 * 60),
+            reap_interval: Duration::from_secs(30),
+            dial_on_demand: true,
+        }
+    }
+}
+
+impl BudgetConfig {
+    /// Never close connections and never dial on demand
+    ///
+    /// Matches the node's behaviour before connection budgets existed.
+    pub fn unlimited() -> Self {
+        Self {
+            max_connections: usize::MAX,
+            max_per_interface: usize::MAX,
+            idle_timeout: Duration::MAX,
+            dial_on_demand: false,
+            ..Self::default()
+        }
+    }
+}
+
+/// Rank of a connected peer; larger is kept first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Priority {
+    pinned: bool,
+    active_interface: bool,
+    last_active_millis: i64,
+}
+
+/// Tracks traffic and pinned peers, and plans which connections to close
+///
+/// Kept in memory only; after a restart every connection starts with a
+/// fresh idle grace period.
+#[derive(Debug, Default)]
+pub struct ConnectionBudget {
+    config: BudgetConfig,
+    pinned: DashSet<IrohIdentity>,
+    peer_activity: DashMap<IrohIdentity, i64>,
+    interface_activity: DashMap<InterfaceId, i64>,
+    dialing: DashSet<IrohIdentity>,
+}
+
+impl ConnectionBudget {
+    /// Create a budget with the given tuning
+    pub fn new(config: BudgetConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// The budget's tuning
+    pub fn config(&self) -> &BudgetConfig {
+        &self.config
+    }
+
+    /// Always keep a connection to `peer` and allow dialling it
+    pub fn pin(&self, peer: IrohIdentity) {
+        self.pinned.insert(peer);
+    }
+
+    /// Stop pinning `peer`. Returns `false` if it wasn't pinned.
+    pub fn unpin(&self, peer: &IrohIdentity) -> bool {
+        self.pinned.remove(peer).is_some()
+    }
+
+    /// Whether `peer` is pinned
+    pub fn is_pinned(&self, peer: &IrohIdentity) -> bool {
+        self.pinned.contains(peer)
+    }
+
+    /// All pinned peers
+    pub fn pinned(&self) -> Vec<IrohIdentity> {
+        self.pinned.iter().map(|p| *p).collect()
+    }
+
+    /// Record interface traffic with `peer`
+    pub fn touch(&self, interface_id: InterfaceId, peer: IrohIdentity, at_millis: i64) {
+        self.touch_interface(interface_id, at_millis);
+        self.peer_activity
+            .entry(peer)
+            .and_modify(|t| *t = (*t).max(at_millis))
+            .or_insert(at_millis);
+    }
+
+    /// Record traffic on an interface not tied to one peer (e.g. a local send)
+    pub fn touch_interface(&self, interface_id: InterfaceId, at_millis: i64) {
+        self.interface_activity
+            .entry(interface_id)
+            .and_modify(|t| *t = (*t).max(at_millis))
+            .or_insert(at_millis);
+    }
+
+    /// When we last had interface traffic with `peer`
+    pub fn last_active(&self, peer: &IrohIdentity) -> Option<i64> {
+        self.peer_activity.get(peer).map(|t| *t)
+    }
+
+    /// Drop activity for an interface we left
+    pub fn forget(&self, interface_id: &InterfaceId) {
+        self.interface_activity.remove(interface_id);
+    }
+
+    /// Whether a new connection to `peer` fits the budget
+    ///
+    /// Pinned peers always fit; others only while fewer than
+    /// `max_connections` are open.
+    pub fn may_dial(&self, peer: &IrohIdentity, connected: usize) -> bool {
+        self.is_pinned(peer) || connected < self.config.max_connections
+    }
+
+    /// Claim an on-demand dial to `peer`
+    ///
+    /// Returns `false` if a dial is already in flight, so concurrent sends
+    /// don't dial the same peer twice. Release with [`Self::end_dial`].
+    pub fn begin_dial(&self, peer: IrohIdentity) -> bool {
+        self.dialing.insert(peer)
+    }
+
+    /// Release a dial claimed with [`Self::begin_dial`]
+    pub fn end_dial(&self, peer: &IrohIdentity) {
+        self.dialing.remove(peer);
+    }
+
+    /// Decide which of the `connected` peers to disconnect
+    ///
+    /// `members` lists the members of each interface we belong to.
+    /// Connections seen here for the first time count as active now, so
+    /// they get a full `idle_timeout` before they can be reaped.
+    pub fn plan(
+        &self,
+        connected: &[IrohIdentity],
+        members: &HashMap<InterfaceId, HashSet<IrohIdentity>>,
+        now_millis: i64,
+    ) -> Vec<IrohIdentity> {
+        let idle_millis = i64::try_from(self.config.idle_timeout.as_millis()).unwrap_or(i64::MAX);
+        let window_millis =
+            i64::try_from(self.config.active_window.as_millis()).unwrap_or(i64::MAX);
+        let active_interfaces: HashSet<InterfaceId> = self
+            .interface_activity
+            .iter()
+            .filter(|e| now_millis.saturating_sub(*e.value()) <= window_millis)
+            .map(|e| *e.key())
+            .collect();
+
+        let mut evict = Vec::new();
+        let mut candidates: HashMap<IrohIdentity, Priority> = HashMap::new();
+        for peer in connected {
+            let last = *self.peer_activity.entry(*peer).or_insert(now_millis);
+            let pinned = self.is_pinned(peer);
+            if !pinned && now_millis.saturating_sub(last) > idle_millis {
+                evict.push(*peer);
+                continue;
+            }
+            let active_interface = members
+                .iter()
+                .any(|(id, m)| m.contains(peer) && active_interfaces.contains(id));
+            candidates.insert(
+                *peer,
+                Priority {
+                    pinned,
+                    active_interface,
+                    last_active_millis: last,
+                },
+            );
+        }
+
+        // Keep the best-ranked members of each interface, plus pinned peers
+        // and peers we share no interface with
+        let mut wanted: HashSet<IrohIdentity> = candidates
+            .iter()
+            .filter(|(peer, priority)| {
+                priority.pinned || !members.values().any(|m| m.contains(*peer))
+            })
+            .map(|(peer, _)| *peer)
+            .collect();
+        for interface_members in members.values() {
+            let mut ranked: Vec<(&IrohIdentity, &Priority)> = candidates
+                .iter()
+                .filter(|(peer, _)| interface_members.contains(*peer))
+                .collect();
+            ranked.sort_by_key(|(peer, priority)| {
+                (Reverse(**priority), *peer.public_key().as_bytes())
+            });
+            wanted.extend(
+                ranked
+                    .into_iter()
+                    .take(self.config.max_per_interface)
+                    .map(|(peer, _)| *peer),
+            );
+        }
+
+        let mut kept: Vec<(IrohIdentity, Priority)> = Vec::new();
+        for (peer, priority) in candidates {
+            if wanted.contains(&peer) {
+                kept.push((peer, priority));
+            } else {
+                evict.push(peer);
+            }
+        }
+
+        // Trim to the global cap, lowest-ranked first
+        kept.sort_by_key(|(peer, priority)| (Reverse(*priority), *peer.public_key().as_bytes()));
+        let mut budget = self.config.max_connections;
+        for (peer, priority) in kept {
+            if priority.pinned {
+                continue;
+            }
+            if budget == 0 {
+                evict.push(peer);
+            } else {
+                budget -= 1;
+            }
+        }
+
+        evict.sort_by_key(|peer| *peer.public_key().as_bytes());
+        evict
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn peer() -> IrohIdentity {
+        IrohIdentity::new(iroh::SecretKey::generate(&mut rand::rng()).public())
+    }
+
+    fn config() -> BudgetConfig {
+        BudgetConfig {
+            max_connections: 3,
+            max_per_interface: 2,
+            idle_timeout: Duration::from_secs(60),
+            active_window: Duration::from_secs(600),
+            ..BudgetConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_idle_connections_reaped_unless_pinned() {
+        let budget = ConnectionBudget::new(config());
+        let (idle, pinned, fresh) = (peer(), peer(), peer());
+        let realm = InterfaceId::new([1; 32]);
+        budget.touch(realm, idle, 0);
+        budget.touch(realm, pinned, 0);
+        budget.pin(pinned);
+
+        // `fresh` is seen for the first time and gets a grace period
+        let evict = budget.plan(&[idle, pinned, fresh], &HashMap::new(), 120_000);
+        assert_eq!(evict, vec![idle]);
+        assert!(
+            budget
+                .plan(&[pinned, fresh], &HashMap::new(), 150_000)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_per_interface_cap_keeps_most_recent_members() {
+        let budget = ConnectionBudget::new(config());
+        let realm = InterfaceId::new([1; 32]);
+        let peers: Vec<IrohIdentity> = (0..3).map(|_| peer()).collect();
+        for (i, p) in peers.iter().enumerate() {
+            budget.touch(realm, *p, 1_000 * i as i64);
+        }
+        let members = HashMap::from([(realm, peers.iter().copied().collect())]);
+
+        assert_eq!(budget.plan(&peers, &members, 5_000), vec![peers[0]]);
+    }
+
+    #[test]
+    fn test_global_cap_prefers_active_interfaces() {
+        let budget = ConnectionBudget::new(config());
+        let (busy, quiet) = (InterfaceId::new([1; 32]), InterfaceId::new([2; 32]));
+        let busy_peers = [peer(), peer()];
+        let quiet_peers = [peer(), peer()];
+        for p in busy_peers {
+            budget.touch(busy, p, 960_000);
+        }
+        // More recent traffic, but the interface itself went quiet long ago
+        budget.touch(quiet, quiet_peers[0], 0);
+        budget.touch(quiet, quiet_peers[1], 0);
+        budget.peer_activity.insert(quiet_peers[0], 990_000);
+        budget.peer_activity.insert(quiet_peers[1], 990_000);
+
+        let members = HashMap::from([
+            (busy, busy_peers.into_iter().collect()),
+            (quiet, quiet_peers.into_iter().collect()),
+        ]);
+        let connected: Vec<IrohIdentity> = busy_peers.into_iter().chain(quiet_peers).collect();
+        let evict = budget.plan(&connected, &members, 1_000_000);
+        assert_eq!(evict.len(), 1);
+        assert!(quiet_peers.contains(&evict[0]));
+
+        assert!(budget.may_dial(&peer(), 2));
+        assert!(!budget.may_dial(&peer(), 3));
+        let contact = peer();
+        budget.pin(contact);
+        assert!(budget.may_dial(&contact, 3));
+        assert!(budget.begin_dial(contact));
+        assert!(!budget.begin_dial(contact));
+        budget.end_dial(&contact);
+        assert!(budget.begin_dial(contact));
+    }
+}
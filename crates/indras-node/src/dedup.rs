@@ -0,0 +1,257 @@
+//! Duplicate suppression for incoming events
+//!
+//! Gossip and direct sends can both deliver the same event, and a peer
+//! that reconnects may replay events we already have. Before an event is
+//! appended and broadcast, the message handler claims its [`EventId`] with
+//! [`EventDedup::admit`]; a second claim for the same ID is a duplicate and
+//! is dropped.
+//!
+//! Each interface keeps a bounded window of recently delivered IDs
+//! ([`SeenWindow`]), mirrored to storage so it survives a restart. The
+//! window is loaded lazily the first time an interface receives an event.
+//!
+//! ## Flow
+//!
+//! ```text
+//! receive: admit(id) ─┬─ true  → append + broadcast (release(id) if append fails)
+//!                     └─ false → dropped, counted in suppressed()
+//! send:    append → admit(id)    (our own events echoed back are dropped)
+//! ```
+
+use std::collections::{HashSet, VecDeque};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use dashmap::DashMap;
+use tracing::debug;
+
+use indras_core::{EventId, InterfaceId};
+use indras_logging::metrics;
+use indras_storage::CompositeStorage;
+use indras_transport::IrohIdentity;
+
+/// Default number of delivered event IDs remembered per interface.
+pub const DEFAULT_DEDUP_WINDOW: usize = 8192;
+
+/// Bounded set of recently delivered event IDs, evicted oldest first.
+#[derive(Debug, Clone)]
+pub struct SeenWindow {
+    capacity: usize,
+    ids: HashSet<EventId>,
+    order: VecDeque<EventId>,
+}
+
+impl SeenWindow {
+    /// Create an empty window.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ids: HashSet::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    /// Whether an ID is in the window.
+    pub fn contains(&self, id: &EventId) -> bool {
+        self.ids.contains(id)
+    }
+
+    /// Number of IDs in the window.
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Whether the window is empty.
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// Add an ID, returning `false` if it was already present.
+    pub fn insert(&mut self, id: EventId) -> bool {
+        if !self.ids.insert(id) {
+            return false;
+        }
+        self.order.push_back(id);
+        true
+    }
+
+    /// Remove an ID, returning whether it was present.
+    pub fn remove(&mut self, id: &EventId) -> bool {
+        if !self.ids.remove(id) {
+            return false;
+        }
+        self.order.retain(|o| o != id);
+        true
+    }
+
+    /// Drop the oldest IDs beyond capacity and return them.
+    pub fn evict(&mut self) -> Vec<EventId> {
+        let mut evicted = Vec::new();
+        while self.order.len() > self.capacity {
+            if let Some(old) = self.order.pop_front() {
+                self.ids.remove(&old);
+                evicted.push(old);
+            }
+        }
+        evicted
+    }
+}
+
+/// Node-wide duplicate suppression across all interfaces.
+pub struct EventDedup {
+    capacity: usize,
+    windows: DashMap<InterfaceId, SeenWindow>,
+    storage: Arc<CompositeStorage<IrohIdentity>>,
+    suppressed: AtomicU64,
+}
+
+impl EventDedup {
+    /// Create a dedup cache persisting its windows to `storage`.
+    pub fn new(storage: Arc<CompositeStorage<IrohIdentity>>, capacity: usize) -> Self {
+        Self {
+            capacity,
+            windows: DashMap::new(),
+            storage,
+            suppressed: AtomicU64::new(0),
+        }
+    }
+
+    /// Claim an event for delivery.
+    ///
+    /// Returns `false` if the event was already delivered; the duplicate is
+    /// counted in [`suppressed`](Self::suppressed) and in metrics.
+    pub fn admit(&self, interface_id: InterfaceId, event_id: EventId) -> bool {
+        let evicted = {
+            let mut window = self
+                .windows
+                .entry(interface_id)
+                .or_insert_with(|| self.load(&interface_id));
+            if !window.insert(event_id) {
+                drop(window);
+                self.suppressed.fetch_add(1, Ordering::Relaxed);
+                metrics::record_duplicate_suppressed(interface_id.to_string());
+                return false;
+            }
+            window.evict()
+        };
+
+        let seen = self.storage.seen_events();
+        if let Err(e) = seen.insert(&interface_id, event_id) {
+            debug!(error = %e, "Failed to persist seen event");
+        }
+        for old in evicted {
+            if let Err(e) = seen.remove(&interface_id, &old) {
+                debug!(error = %e, "Failed to evict seen event");
+            }
+        }
+        true
+    }
+
+    /// Give up a claim whose delivery failed, so a later copy is accepted.
+    pub fn release(&self, interface_id: &InterfaceId, event_id: &EventId) {
+        if let Some(mut window) = self.windows.get_mut(interface_id) {
+            window.remove(event_id);
+        }
+        if let Err(e) = self.storage.seen_events().remove(interface_id, event_id) {
+            debug!(error = %e, "Failed to release seen event");
+        }
+    }
+
+    /// Drop an interface's in-memory window (it stays in storage).
+    pub fn forget(&self, interface_id: &InterfaceId) {
+        self.windows.remove(interface_id);
+    }
+
+    /// Number of duplicate events dropped since the node started.
+    pub fn suppressed(&self) -> u64 {
+        self.suppressed.load(Ordering::Relaxed)
+    }
+
+    /// Rebuild an interface's window from storage.
+    fn load(&self, interface_id: &InterfaceId) -> SeenWindow {
+        let mut window = SeenWindow::new(self.capacity);
+        match self.storage.seen_events().load(interface_id) {
+            Ok(ids) => {
+                for id in ids {
+                    window.insert(id);
+                }
+                // The stored window may predate a smaller capacity
+                for old in window.evict() {
+                    let _ = self.storage.seen_events().remove(interface_id, &old);
+                }
+            }
+            Err(e) => debug!(error = %e, "Failed to load seen events"),
+        }
+        window
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indras_storage::CompositeStorageConfig;
+
+    async fn storage() -> Arc<CompositeStorage<IrohIdentity>> {
+        Arc::new(
+            CompositeStorage::new(CompositeStorageConfig::in_memory())
+                .await
+                .unwrap(),
+        )
+    }
+
+    fn iid(n: u8) -> InterfaceId {
+        InterfaceId::new([n; 32])
+    }
+
+    #[test]
+    fn test_window_evicts_oldest() {
+        let mut window = SeenWindow::new(2);
+        assert!(window.insert(EventId::new(1, 1)));
+        assert!(!window.insert(EventId::new(1, 1)));
+        assert!(window.insert(EventId::new(1, 2)));
+        assert!(window.insert(EventId::new(1, 3)));
+
+        assert_eq!(window.evict(), vec![EventId::new(1, 1)]);
+        assert_eq!(window.len(), 2);
+        assert!(!window.contains(&EventId::new(1, 1)));
+        assert!(window.contains(&EventId::new(1, 3)));
+    }
+
+    #[tokio::test]
+    async fn test_admit_suppresses_duplicates() {
+        let dedup = EventDedup::new(storage().await, DEFAULT_DEDUP_WINDOW);
+        let id = EventId::new(7, 1);
+
+        assert!(dedup.admit(iid(1), id));
+        assert!(!dedup.admit(iid(1), id));
+        // The same ID in another interface is a different event
+        assert!(dedup.admit(iid(2), id));
+        assert_eq!(dedup.suppressed(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_release_allows_retry() {
+        let dedup = EventDedup::new(storage().await, DEFAULT_DEDUP_WINDOW);
+        let id = EventId::new(7, 1);
+
+        assert!(dedup.admit(iid(1), id));
+        dedup.release(&iid(1), &id);
+        assert!(dedup.admit(iid(1), id));
+        assert_eq!(dedup.suppressed(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_window_survives_restart() {
+        let storage = storage().await;
+        let first = EventDedup::new(Arc::clone(&storage), 2);
+        for seq in 1..=3 {
+            first.admit(iid(1), EventId::new(7, seq));
+        }
+        drop(first);
+
+        let restarted = EventDedup::new(storage, 2);
+        assert!(!restarted.admit(iid(1), EventId::new(7, 3)));
+        // Evicted before the restart, so accepted again
+        assert!(restarted.admit(iid(1), EventId::new(7, 1)));
+    }
+}
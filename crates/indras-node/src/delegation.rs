@@ -0,0 +1,549 @@
+//! Scoped, time-limited delegation for remote assistance
+//!
+//! A node owner can let a trusted contact act on their node for a while,
+//! e.g. "re-send invites for realm X for 24h". The owner issues a
+//! [`DelegationToken`] signed with their PQ identity that names the
+//! delegate, one interface, a set of [`Capability`]s, and an expiry.
+//!
+//! The delegate sends a [`DelegatedRequest`] carrying the token and the
+//! action. The message handler checks it against the registry:
+//!
+//! - the token was issued by this node and is still registered,
+//! - it has not been revoked and has not expired,
+//! - the signature verifies against this node's PQ identity,
+//! - the sender is the named delegate,
+//! - the action's capability and interface are in scope.
+//!
+//! Every attempt, allowed or not, is recorded in the audit trail and in
+//! the node log. Allowed actions are published as [`AuthorizedAction`]s
+//! for the owner's application layer to carry out.
+//!
+//! The in-memory audit trail keeps the latest [`DEFAULT_AUDIT_CAPACITY`]
+//! entries. Any peer can send requests naming tokens this node never
+//! issued, so after recording one such refusal from a sender, further ones
+//! within [`UNKNOWN_TOKEN_WINDOW_MILLIS`] are refused as
+//! [`DenialReason::Throttled`] without being recorded; the next recorded
+//! entry from that sender carries the count.
+//!
+//! The registry is not persisted: a restart voids every outstanding token
+//! (fail closed), while the node log keeps the audit history.
+
+use std::collections::{BTreeSet, VecDeque};
+use std::sync::Mutex;
+
+use dashmap::{DashMap, DashSet};
+use indras_core::InterfaceId;
+use indras_crypto::{PQIdentity, PQPublicIdentity, PQSignature};
+use indras_transport::IrohIdentity;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+/// Domain separation tag for delegation token signatures
+const DELEGATION_DOMAIN: &[u8] = b"indras/delegation/v1";
+
+/// Capacity of the authorized-action channel
+const ACTION_CHANNEL_CAPACITY: usize = 64;
+
+/// Audit entries kept in memory, oldest evicted first
+pub const DEFAULT_AUDIT_CAPACITY: usize = 1024;
+
+/// How long unknown-token refusals from one sender are aggregated
+pub const UNKNOWN_TOKEN_WINDOW_MILLIS: u64 = 60_000;
+
+/// Identifier of a delegation token
+pub type DelegationId = [u8; 16];
+
+/// Something a delegate may do on the owner's behalf
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Capability {
+    /// Re-send realm invites to existing contacts
+    ResendInvites,
+    /// Post messages to the realm as the owner
+    PostMessages,
+}
+
+impl std::fmt::Display for Capability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Capability::ResendInvites => write!(f, "resend-invites"),
+            Capability::PostMessages => write!(f, "post-messages"),
+        }
+    }
+}
+
+/// A signed grant of capabilities on one interface to one delegate
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DelegationToken {
+    /// Random token identifier
+    pub id: DelegationId,
+    /// Owner's ML-DSA-65 verifying key
+    pub delegator_vk: Vec<u8>,
+    /// Peer allowed to use this token
+    pub delegate: IrohIdentity,
+    /// Interface the token is scoped to
+    pub interface_id: InterfaceId,
+    /// What the delegate may do
+    pub capabilities: BTreeSet<Capability>,
+    /// When the token was issued (milliseconds since epoch)
+    pub issued_at_millis: u64,
+    /// When the token lapses (milliseconds since epoch)
+    pub expires_at_millis: u64,
+    /// Owner's signature over all fields above
+    pub signature: Vec<u8>,
+}
+
+impl DelegationToken {
+    /// Issue a token signed by `owner`
+    pub fn issue(
+        owner: &PQIdentity,
+        delegate: IrohIdentity,
+        interface_id: InterfaceId,
+        capabilities: BTreeSet<Capability>,
+        issued_at_millis: u64,
+        expires_at_millis: u64,
+    ) -> Self {
+        let mut id = [0u8; 16];
+        rand::rng().fill_bytes(&mut id);
+        let mut token = Self {
+            id,
+            delegator_vk: owner.verifying_key_bytes(),
+            delegate,
+            interface_id,
+            capabilities,
+            issued_at_millis,
+            expires_at_millis,
+            signature: Vec::new(),
+        };
+        token.signature = owner.sign(&token.signing_bytes()).to_bytes().to_vec();
+        token
+    }
+
+    /// Whether the token was signed by `delegator`
+    pub fn verify(&self, delegator: &PQPublicIdentity) -> bool {
+        if self.delegator_vk != delegator.to_bytes() {
+            return false;
+        }
+        match PQSignature::from_bytes(self.signature.clone()) {
+            Ok(sig) => delegator.verify(&self.signing_bytes(), &sig),
+            Err(_) => false,
+        }
+    }
+
+    /// Whether the token has lapsed at `now_millis`
+    pub fn is_expired(&self, now_millis: u64) -> bool {
+        now_millis >= self.expires_at_millis
+    }
+
+    /// Whether the token covers a capability on an interface
+    pub fn covers(&self, interface_id: &InterfaceId, capability: Capability) -> bool {
+        self.interface_id == *interface_id && self.capabilities.contains(&capability)
+    }
+
+    /// Canonical bytes the signature binds to
+    fn signing_bytes(&self) -> Vec<u8> {
+        let body = (
+            DELEGATION_DOMAIN,
+            &self.id,
+            &self.delegator_vk,
+            &self.delegate,
+            &self.interface_id,
+            &self.capabilities,
+            self.issued_at_millis,
+            self.expires_at_millis,
+        );
+        postcard::to_allocvec(&body).unwrap_or_default()
+    }
+}
+
+/// An action a delegate asks the owner's node to perform
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DelegatedAction {
+    /// Re-send the interface's invite to a contact (by member id)
+    ResendInvite {
+        /// The contact to invite
+        invitee: [u8; 32],
+    },
+    /// Post a message payload to the interface
+    PostMessage {
+        /// Raw message payload
+        content: Vec<u8>,
+    },
+}
+
+impl DelegatedAction {
+    /// The capability this action needs
+    pub fn capability(&self) -> Capability {
+        match self {
+            DelegatedAction::ResendInvite { .. } => Capability::ResendInvites,
+            DelegatedAction::PostMessage { .. } => Capability::PostMessages,
+        }
+    }
+}
+
+/// Wire message: a delegate acting under a token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DelegatedRequest {
+    /// The token being exercised
+    pub token: DelegationToken,
+    /// Interface the action targets
+    pub interface_id: InterfaceId,
+    /// What the delegate wants done
+    pub action: DelegatedAction,
+}
+
+/// An action that passed authorization, for the owner to carry out
+#[derive(Debug, Clone)]
+pub struct AuthorizedAction {
+    /// Token it was authorized under
+    pub token_id: DelegationId,
+    /// Delegate who requested it
+    pub delegate: IrohIdentity,
+    /// Interface it applies to
+    pub interface_id: InterfaceId,
+    /// The action itself
+    pub action: DelegatedAction,
+}
+
+/// Why a delegated request was refused
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum DenialReason {
+    #[error("token not issued by this node")]
+    UnknownToken,
+    #[error("token was revoked")]
+    Revoked,
+    #[error("token has expired")]
+    Expired,
+    #[error("token signature is invalid")]
+    BadSignature,
+    #[error("sender is not the token's delegate")]
+    WrongDelegate,
+    #[error("action is outside the token's scope")]
+    OutOfScope,
+    #[error("repeated unknown token from this sender")]
+    Throttled,
+}
+
+/// One entry in the delegation audit trail
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditEntry {
+    /// Token the request named
+    pub token_id: DelegationId,
+    /// Peer that sent the request
+    pub sender: IrohIdentity,
+    /// Interface the action targeted
+    pub interface_id: InterfaceId,
+    /// Capability the action needed
+    pub capability: Capability,
+    /// When the request was handled (milliseconds since epoch)
+    pub at_millis: u64,
+    /// `None` if allowed, otherwise why it was refused
+    pub denied: Option<DenialReason>,
+    /// Unknown-token refusals from the sender throttled since their last
+    /// recorded entry
+    pub throttled: u64,
+}
+
+impl AuditEntry {
+    /// Whether the action was allowed
+    pub fn allowed(&self) -> bool {
+        self.denied.is_none()
+    }
+}
+
+/// When a sender's unknown-token window started, and how many refusals
+/// were throttled in it
+#[derive(Debug, Clone, Copy, Default)]
+struct UnknownTokenWindow {
+    started_at_millis: u64,
+    throttled: u64,
+}
+
+/// Tokens issued by this node, their revocations, and the audit trail
+pub struct DelegationRegistry {
+    tokens: DashMap<DelegationId, DelegationToken>,
+    revoked: DashSet<DelegationId>,
+    audit: Mutex<VecDeque<AuditEntry>>,
+    audit_capacity: usize,
+    unknown: DashMap<IrohIdentity, UnknownTokenWindow>,
+    actions_tx: broadcast::Sender<AuthorizedAction>,
+}
+
+impl Default for DelegationRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DelegationRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::with_audit_capacity(DEFAULT_AUDIT_CAPACITY)
+    }
+
+    /// Create an empty registry keeping at most `capacity` audit entries
+    pub fn with_audit_capacity(capacity: usize) -> Self {
+        let (actions_tx, _) = broadcast::channel(ACTION_CHANNEL_CAPACITY);
+        Self {
+            tokens: DashMap::new(),
+            revoked: DashSet::new(),
+            audit: Mutex::new(VecDeque::new()),
+            audit_capacity: capacity.max(1),
+            unknown: DashMap::new(),
+            actions_tx,
+        }
+    }
+
+    /// Record a token this node issued
+    pub fn register(&self, token: DelegationToken) {
+        self.tokens.insert(token.id, token);
+    }
+
+    /// Revoke a token. Returns `false` if it was not live.
+    pub fn revoke(&self, id: &DelegationId) -> bool {
+        self.tokens.remove(id).is_some() && self.revoked.insert(*id)
+    }
+
+    /// Tokens still usable at `now_millis`
+    pub fn active(&self, now_millis: u64) -> Vec<DelegationToken> {
+        self.tokens
+            .iter()
+            .filter(|t| !t.is_expired(now_millis))
+            .map(|t| t.value().clone())
+            .collect()
+    }
+
+    /// The latest recorded delegated requests, oldest first
+    pub fn audit(&self) -> Vec<AuditEntry> {
+        self.audit
+            .lock()
+            .map(|a| a.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Subscribe to actions that pass authorization
+    pub fn subscribe(&self) -> broadcast::Receiver<AuthorizedAction> {
+        self.actions_tx.subscribe()
+    }
+
+    /// Check a request from `sender`, record it, and publish it if allowed
+    ///
+    /// Returns [`DenialReason::Throttled`], without recording anything,
+    /// for repeated unknown-token requests from one sender.
+    pub fn authorize(
+        &self,
+        request: &DelegatedRequest,
+        sender: &IrohIdentity,
+        owner: &PQPublicIdentity,
+        now_millis: u64,
+    ) -> Result<(), DenialReason> {
+        let token = &request.token;
+        let capability = request.action.capability();
+        let outcome = self.check(request, sender, owner, capability, now_millis);
+        if outcome == Err(DenialReason::UnknownToken) && self.throttle(sender, now_millis) {
+            return Err(DenialReason::Throttled);
+        }
+
+        let throttled = self
+            .unknown
+            .get_mut(sender)
+            .map(|mut w| std::mem::take(&mut w.throttled))
+            .unwrap_or(0);
+        if let Ok(mut audit) = self.audit.lock() {
+            if audit.len() >= self.audit_capacity {
+                audit.pop_front();
+            }
+            audit.push_back(AuditEntry {
+                token_id: token.id,
+                sender: *sender,
+                interface_id: request.interface_id,
+                capability,
+                at_millis: now_millis,
+                denied: outcome.err(),
+                throttled,
+            });
+        }
+
+        if outcome.is_ok() {
+            let _ = self.actions_tx.send(AuthorizedAction {
+                token_id: token.id,
+                delegate: *sender,
+                interface_id: request.interface_id,
+                action: request.action.clone(),
+            });
+        }
+        outcome
+    }
+
+    /// Count an unknown-token refusal from `sender`; `true` if it falls in
+    /// the sender's current window and should not be recorded
+    fn throttle(&self, sender: &IrohIdentity, now_millis: u64) -> bool {
+        let fresh = |w: &UnknownTokenWindow| {
+            now_millis < w.started_at_millis.saturating_add(UNKNOWN_TOKEN_WINDOW_MILLIS)
+        };
+        if let Some(mut window) = self.unknown.get_mut(sender)
+            && fresh(&window)
+        {
+            window.throttled += 1;
+            return true;
+        }
+        // Start a new window, carrying over the last one's count; other
+        // senders' lapsed windows are forgotten
+        let throttled = self.unknown.remove(sender).map_or(0, |(_, w)| w.throttled);
+        self.unknown.retain(|_, w| fresh(w));
+        self.unknown.insert(
+            *sender,
+            UnknownTokenWindow {
+                started_at_millis: now_millis,
+                throttled,
+            },
+        );
+        false
+    }
+
+    fn check(
+        &self,
+        request: &DelegatedRequest,
+        sender: &IrohIdentity,
+        owner: &PQPublicIdentity,
+        capability: Capability,
+        now_millis: u64,
+    ) -> Result<(), DenialReason> {
+        let token = &request.token;
+        if self.revoked.contains(&token.id) {
+            return Err(DenialReason::Revoked);
+        }
+        // Only the exact token we issued counts, not a re-signed variant
+        if self.tokens.get(&token.id).is_none_or(|t| *t != *token) {
+            return Err(DenialReason::UnknownToken);
+        }
+        if !token.verify(owner) {
+            return Err(DenialReason::BadSignature);
+        }
+        if token.is_expired(now_millis) {
+            return Err(DenialReason::Expired);
+        }
+        if token.delegate != *sender {
+            return Err(DenialReason::WrongDelegate);
+        }
+        if !token.covers(&request.interface_id, capability) {
+            return Err(DenialReason::OutOfScope);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn peer() -> IrohIdentity {
+        IrohIdentity::from(iroh::SecretKey::generate(&mut rand::rng()).public())
+    }
+
+    fn realm() -> InterfaceId {
+        InterfaceId::new([1; 32])
+    }
+
+    fn resend() -> DelegatedAction {
+        DelegatedAction::ResendInvite { invitee: [3; 32] }
+    }
+
+    fn request(token: &DelegationToken, action: DelegatedAction) -> DelegatedRequest {
+        DelegatedRequest {
+            token: token.clone(),
+            interface_id: realm(),
+            action,
+        }
+    }
+
+    fn setup() -> (PQIdentity, IrohIdentity, DelegationRegistry, DelegationToken) {
+        let owner = PQIdentity::generate();
+        let delegate = peer();
+        let token = DelegationToken::issue(
+            &owner,
+            delegate,
+            realm(),
+            [Capability::ResendInvites].into_iter().collect(),
+            0,
+            1_000,
+        );
+        let registry = DelegationRegistry::new();
+        registry.register(token.clone());
+        (owner, delegate, registry, token)
+    }
+
+    #[test]
+    fn test_allowed_action_is_published_and_audited() {
+        let (owner, delegate, registry, token) = setup();
+        let mut actions = registry.subscribe();
+        let request = request(&token, resend());
+
+        assert_eq!(registry.authorize(&request, &delegate, &owner.verifying_key(), 10), Ok(()));
+        assert_eq!(actions.try_recv().unwrap().action, resend());
+        assert!(registry.audit()[0].allowed());
+    }
+
+    #[test]
+    fn test_scope_delegate_and_expiry_enforced() {
+        let (owner, delegate, registry, token) = setup();
+        let vk = owner.verifying_key();
+
+        let post = request(&token, DelegatedAction::PostMessage { content: vec![1] });
+        assert_eq!(registry.authorize(&post, &delegate, &vk, 10), Err(DenialReason::OutOfScope));
+        let elsewhere = DelegatedRequest {
+            interface_id: InterfaceId::new([2; 32]),
+            ..request(&token, resend())
+        };
+        assert_eq!(registry.authorize(&elsewhere, &delegate, &vk, 10), Err(DenialReason::OutOfScope));
+
+        let request = request(&token, resend());
+        assert_eq!(registry.authorize(&request, &peer(), &vk, 10), Err(DenialReason::WrongDelegate));
+        assert_eq!(registry.authorize(&request, &delegate, &vk, 1_000), Err(DenialReason::Expired));
+        assert_eq!(registry.audit().len(), 4);
+        assert!(registry.audit().iter().all(|e| !e.allowed()));
+    }
+
+    #[test]
+    fn test_revoked_and_tampered_tokens_rejected() {
+        let (owner, delegate, registry, token) = setup();
+        let vk = owner.verifying_key();
+
+        let mut widened = token.clone();
+        widened.capabilities.insert(Capability::PostMessages);
+        let tampered = request(&widened, resend());
+        assert_eq!(registry.authorize(&tampered, &delegate, &vk, 10), Err(DenialReason::UnknownToken));
+
+        assert!(registry.revoke(&token.id));
+        assert!(!registry.revoke(&token.id));
+        let request = request(&token, resend());
+        assert_eq!(registry.authorize(&request, &delegate, &vk, 10), Err(DenialReason::Revoked));
+    }
+
+    #[test]
+    fn test_audit_bounded_and_unknown_tokens_throttled() {
+        let (owner, delegate, _, token) = setup();
+        let vk = owner.verifying_key();
+        let registry = DelegationRegistry::with_audit_capacity(2);
+        let stranger = peer();
+        let unknown = request(&token, resend());
+
+        let (refused, throttled) = (Err(DenialReason::UnknownToken), Err(DenialReason::Throttled));
+        assert_eq!(registry.authorize(&unknown, &stranger, &vk, 0), refused);
+        assert_eq!(registry.authorize(&unknown, &stranger, &vk, 10), throttled);
+        assert_eq!(registry.authorize(&unknown, &stranger, &vk, 20), throttled);
+        assert_eq!(registry.audit().len(), 1);
+
+        // A new window records again, carrying the throttled count
+        let later = UNKNOWN_TOKEN_WINDOW_MILLIS;
+        assert!(registry.authorize(&unknown, &stranger, &vk, later).is_err());
+        assert_eq!(registry.audit()[1].throttled, 2);
+
+        registry.register(token.clone());
+        assert_eq!(registry.authorize(&unknown, &delegate, &vk, later), Ok(()));
+        let audit = registry.audit();
+        assert_eq!(audit.len(), 2);
+        assert_eq!(audit[0].throttled, 2);
+        assert!(audit[1].allowed());
+    }
+}
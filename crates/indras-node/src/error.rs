@@ -1,21 +1,25 @@
 //! Error types for the node coordinator
 
+use std::time::Duration;
+
+use indras_core::InterfaceId;
+use indras_core::error_code::{ErrorCode, ErrorDetail, ErrorInfo};
 use thiserror::Error;
 
 /// Errors that can occur in the node coordinator
 #[derive(Debug, Error)]
 pub enum NodeError {
-    /// Transport layer error
+    /// Transport layer error, with the peer involved when known
     #[error("Transport error: {0}")]
-    Transport(String),
+    Transport(ErrorDetail),
 
     /// Storage error
     #[error("Storage error: {0}")]
     Storage(#[from] indras_storage::StorageError),
 
-    /// Sync error
+    /// Sync error, with the interface involved when known
     #[error("Sync error: {0}")]
-    Sync(String),
+    Sync(ErrorDetail),
 
     /// Interface not found
     #[error("Interface not found: {0}")]
@@ -56,17 +60,98 @@ pub enum NodeError {
     /// Story authentication error
     #[error("Story auth error: {0}")]
     StoryAuth(String),
+
+    /// Operation needs the network but the interface is a local sandbox
+    #[error("Interface is a local sandbox: {0}")]
+    Sandboxed(String),
+
+    /// Interface is archived and accepts no new writes
+    #[error("Interface is archived: {0}")]
+    Archived(String),
+
+    /// A network operation did not complete in time
+    #[error("{operation} timed out after {after:?}")]
+    Timeout {
+        /// What was being waited on (e.g. "connect", "sync request")
+        operation: &'static str,
+        /// The limit that was exceeded
+        after: Duration,
+    },
+
+    /// A backup is missing objects or does not match its manifest
+    #[error("Backup is corrupt: {0}")]
+    CorruptBackup(String),
+}
+
+impl NodeError {
+    /// Transport failure talking to `peer` (hex public key)
+    pub(crate) fn transport_to(peer: impl Into<String>, e: &impl ErrorInfo) -> Self {
+        NodeError::Transport(ErrorDetail::from_error(e).with_peer(peer))
+    }
+
+    /// Sync failure within an interface
+    pub(crate) fn sync_in(interface_id: &InterfaceId, e: &impl ErrorInfo) -> Self {
+        NodeError::Sync(
+            ErrorDetail::from_error(e).with_interface(hex::encode(interface_id.as_bytes())),
+        )
+    }
+}
+
+impl ErrorInfo for NodeError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            NodeError::Transport(d) | NodeError::Sync(d) => d.code,
+            NodeError::Storage(e) => e.code(),
+            NodeError::InterfaceNotFound(_) => ErrorCode::InterfaceNotFound,
+            NodeError::NotStarted => ErrorCode::NotStarted,
+            NodeError::AlreadyStarted => ErrorCode::AlreadyStarted,
+            NodeError::Config(_) => ErrorCode::Config,
+            NodeError::Serialization(_) => ErrorCode::Serialization,
+            NodeError::Channel(_) => ErrorCode::Channel,
+            NodeError::Io(_) => ErrorCode::Io,
+            NodeError::Keystore(_) => ErrorCode::MissingKey,
+            NodeError::Crypto(_) => ErrorCode::CryptoFailed,
+            NodeError::StoryAuth(_) => ErrorCode::AuthFailed,
+            NodeError::Sandboxed(_) => ErrorCode::Sandboxed,
+            NodeError::Archived(_) => ErrorCode::Archived,
+            NodeError::Timeout { .. } => ErrorCode::Timeout,
+            NodeError::CorruptBackup(_) => ErrorCode::CorruptData,
+        }
+    }
+
+    fn peer(&self) -> Option<&str> {
+        match self {
+            NodeError::Transport(d) | NodeError::Sync(d) => d.peer.as_deref(),
+            _ => None,
+        }
+    }
+
+    fn interface(&self) -> Option<&str> {
+        match self {
+            NodeError::Transport(d) | NodeError::Sync(d) => d.interface.as_deref(),
+            NodeError::InterfaceNotFound(id)
+            | NodeError::Sandboxed(id)
+            | NodeError::Archived(id) => Some(id),
+            _ => None,
+        }
+    }
 }
 
 impl From<indras_transport::AdapterError> for NodeError {
     fn from(e: indras_transport::AdapterError) -> Self {
-        NodeError::Transport(e.to_string())
+        NodeError::Transport(e.detail())
     }
 }
 
 impl From<indras_core::error::InterfaceError> for NodeError {
     fn from(e: indras_core::error::InterfaceError) -> Self {
-        NodeError::Sync(e.to_string())
+        NodeError::Sync(e.detail())
+    }
+}
+
+impl From<indras_sync::SyncError> for NodeError {
+    fn from(e: indras_sync::SyncError) -> Self {
+        NodeError::Sync(e.detail())
     }
 }
 
@@ -84,3 +169,28 @@ impl From<postcard::Error> for NodeError {
 
 /// Result type alias for node operations
 pub type NodeResult<T> = Result<T, NodeError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_info_keeps_layer_context() {
+        let err = NodeError::transport_to(
+            "ab".repeat(32),
+            &indras_core::error::TransportError::ConnectionClosed,
+        );
+        assert_eq!(err.code(), ErrorCode::ConnectionClosed);
+        assert!(err.is_retryable());
+        assert_eq!(err.peer(), Some("ab".repeat(32).as_str()));
+
+        let iface = InterfaceId::new([7u8; 32]);
+        let err = NodeError::sync_in(&iface, &indras_sync::SyncError::NotMember);
+        assert_eq!(err.code(), ErrorCode::NotMember);
+        assert_eq!(err.interface(), Some(hex::encode([7u8; 32]).as_str()));
+
+        let err = NodeError::from(indras_storage::StorageError::DatabaseLocked);
+        assert_eq!(err.code(), ErrorCode::DatabaseLocked);
+        assert_eq!(err.detail().code, ErrorCode::DatabaseLocked);
+    }
+}
@@ -0,0 +1,146 @@
+//! Read-only guest access for interfaces
+//!
+//! A guest holds the interface key (so it can decrypt what it is sent) but
+//! is not a full member. Members record each guest here together with the
+//! time its access lapses, and the message handler enforces two rules:
+//!
+//! - **No write path.** Events and sync state pushed by a guest are dropped
+//!   before they reach the interface, so a guest can never change shared
+//!   state on a member's node.
+//! - **Limited lifetime.** Once a guest's access has expired, members stop
+//!   answering its sync requests and stop syncing to it.
+//!
+//! The registry is local to each node and not persisted; the network layer
+//! rebuilds it from the realm's guest document. Neither rule takes back the
+//! key a guest already holds: the network layer rotates the interface key
+//! (`IndrasNode::rotate_interface_key`) once a guest's access ends.
+
+use std::collections::HashMap;
+
+use dashmap::DashMap;
+use indras_core::InterfaceId;
+use indras_transport::IrohIdentity;
+
+/// How a peer relates to an interface's guest list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuestStatus {
+    /// Not a guest; normal member rules apply.
+    Member,
+    /// A guest whose read-only access is still valid.
+    Guest,
+    /// A guest whose access has lapsed.
+    Expired,
+}
+
+impl GuestStatus {
+    /// Whether writes from this peer must be rejected.
+    pub fn is_read_only(self) -> bool {
+        !matches!(self, GuestStatus::Member)
+    }
+}
+
+/// Per-interface registry of read-only guests.
+#[derive(Default)]
+pub struct GuestRegistry {
+    guests: DashMap<InterfaceId, HashMap<IrohIdentity, u64>>,
+}
+
+impl GuestRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record (or extend) a guest's access until `expires_at_millis`.
+    pub fn grant(&self, interface_id: InterfaceId, peer: IrohIdentity, expires_at_millis: u64) {
+        self.guests
+            .entry(interface_id)
+            .or_default()
+            .insert(peer, expires_at_millis);
+    }
+
+    /// Remove a guest. Returns `true` if it was present.
+    ///
+    /// A revoked guest is treated as a member again only if it is later
+    /// admitted through a regular invite; callers that want to cut a guest
+    /// off should instead grant it an expiry in the past.
+    pub fn revoke(&self, interface_id: &InterfaceId, peer: &IrohIdentity) -> bool {
+        self.guests
+            .get_mut(interface_id)
+            .is_some_and(|mut guests| guests.remove(peer).is_some())
+    }
+
+    /// Drop all guest state for an interface.
+    pub fn forget(&self, interface_id: &InterfaceId) {
+        self.guests.remove(interface_id);
+    }
+
+    /// Classify a peer at time `now_millis`.
+    pub fn status(&self, interface_id: &InterfaceId, peer: &IrohIdentity, now_millis: u64) -> GuestStatus {
+        match self.guests.get(interface_id).and_then(|g| g.get(peer).copied()) {
+            None => GuestStatus::Member,
+            Some(expires_at) if now_millis >= expires_at => GuestStatus::Expired,
+            Some(_) => GuestStatus::Guest,
+        }
+    }
+
+    /// Guests registered for an interface with their expiry times.
+    pub fn guests(&self, interface_id: &InterfaceId) -> Vec<(IrohIdentity, u64)> {
+        self.guests
+            .get(interface_id)
+            .map(|g| g.iter().map(|(peer, exp)| (*peer, *exp)).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Get current time in milliseconds since UNIX epoch.
+pub(crate) fn now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn peer() -> IrohIdentity {
+        IrohIdentity::from(iroh::SecretKey::generate(&mut rand::rng()).public())
+    }
+
+    #[test]
+    fn test_unknown_peer_is_member() {
+        let registry = GuestRegistry::new();
+        let status = registry.status(&InterfaceId::new([1; 32]), &peer(), 0);
+        assert_eq!(status, GuestStatus::Member);
+        assert!(!status.is_read_only());
+    }
+
+    #[test]
+    fn test_guest_expires() {
+        let registry = GuestRegistry::new();
+        let interface = InterfaceId::new([1; 32]);
+        let guest = peer();
+        registry.grant(interface, guest, 1_000);
+
+        assert_eq!(registry.status(&interface, &guest, 999), GuestStatus::Guest);
+        assert_eq!(registry.status(&interface, &guest, 1_000), GuestStatus::Expired);
+        assert!(registry.status(&interface, &guest, 1_000).is_read_only());
+    }
+
+    #[test]
+    fn test_guests_are_per_interface() {
+        let registry = GuestRegistry::new();
+        let a = InterfaceId::new([1; 32]);
+        let b = InterfaceId::new([2; 32]);
+        let guest = peer();
+        registry.grant(a, guest, u64::MAX);
+
+        assert_eq!(registry.status(&b, &guest, 0), GuestStatus::Member);
+        assert_eq!(registry.guests(&a).len(), 1);
+
+        registry.forget(&a);
+        assert!(registry.guests(&a).is_empty());
+    }
+}
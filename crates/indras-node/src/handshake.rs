@@ -0,0 +1,349 @@
+//! Protocol version negotiation and capability flags
+//!
+//! The first time two nodes exchange messages, each sends a [`HelloMessage`]
+//! carrying the protocol versions it speaks and the optional features it
+//! supports. The receiver negotiates against its own hello and records the
+//! result per peer in [`PeerProtocols`]:
+//!
+//! - **Compatible** peers get the highest shared version and the
+//!   intersection of both capability sets.
+//! - **Incompatible** peers (no version overlap) are logged loudly and
+//!   skipped by the sync task instead of failing silently on every message.
+//!
+//! Optional features are gated with [`PeerProtocols::supports`]. Peers that
+//! haven't sent a hello yet (older nodes never will) are assumed to support
+//! only [`Capabilities::LEGACY`], the features that predate the handshake.
+//!
+//! The registry is in-memory; hellos are exchanged again after a restart.
+
+use std::fmt;
+
+use dashmap::{DashMap, DashSet};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use indras_transport::IrohIdentity;
+
+use crate::message_handler::SIGNED_MESSAGE_VERSION;
+use crate::wire::WIRE_VERSION_LEGACY;
+
+/// Current protocol version spoken by this node
+///
+/// Version 2 introduced the message envelope (see [`crate::wire`]).
+pub const PROTOCOL_VERSION: u16 = 2;
+
+/// Oldest protocol version this node can still talk to
+pub const MIN_PROTOCOL_VERSION: u16 = 1;
+
+/// Optional protocol features, as a bit set
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Capabilities(u32);
+
+impl Capabilities {
+    /// No optional features
+    pub const NONE: Self = Self(0);
+    /// Compressed message payloads
+    pub const COMPRESSION: Self = Self(1 << 0);
+    /// DTN store-and-forward bundles and custody
+    pub const DTN: Self = Self(1 << 1);
+    /// Hybrid classical + post-quantum signatures
+    pub const HYBRID_SIGNATURES: Self = Self(1 << 2);
+    /// `CausalEvent` messages carrying delivery dependencies
+    pub const CAUSAL_DELIVERY: Self = Self(1 << 3);
+    /// `MemberCertificate` messages for organization trust roots
+    pub const MEMBER_CERTIFICATES: Self = Self(1 << 4);
+    /// `AnonymousRelay` messages forwarded to the other members
+    pub const ANONYMOUS_RELAY: Self = Self(1 << 5);
+
+    /// Features every node had before the handshake existed
+    pub const LEGACY: Self = Self(Self::DTN.0 | Self::CAUSAL_DELIVERY.0);
+
+    /// Features this build implements
+    pub fn local() -> Self {
+        Self::DTN | Self::CAUSAL_DELIVERY | Self::MEMBER_CERTIFICATES | Self::ANONYMOUS_RELAY
+    }
+
+    /// Raw bit representation
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+
+    /// Whether all features in `other` are present
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Features present in both sets
+    pub fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    /// Feature names, for logs and diagnostics
+    pub fn names(self) -> Vec<&'static str> {
+        [
+            (Self::COMPRESSION, "compression"),
+            (Self::DTN, "dtn"),
+            (Self::HYBRID_SIGNATURES, "hybrid-signatures"),
+            (Self::CAUSAL_DELIVERY, "causal-delivery"),
+            (Self::MEMBER_CERTIFICATES, "member-certificates"),
+            (Self::ANONYMOUS_RELAY, "anonymous-relay"),
+        ]
+        .into_iter()
+        .filter(|(flag, _)| self.contains(*flag))
+        .map(|(_, name)| name)
+        .collect()
+    }
+}
+
+impl std::ops::BitOr for Capabilities {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl fmt::Display for Capabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.names().join(","))
+    }
+}
+
+/// Handshake sent on first contact with a peer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HelloMessage {
+    /// Highest protocol version the sender speaks
+    pub protocol_version: u16,
+    /// Lowest protocol version the sender accepts
+    pub min_protocol_version: u16,
+    /// `SignedNetworkMessage` version the sender produces
+    pub signed_message_version: u8,
+    /// Optional features the sender supports
+    pub capabilities: Capabilities,
+    /// Free-form software identifier (crate name and version)
+    pub agent: String,
+    /// Whether this hello answers one from the recipient
+    ///
+    /// Replies are never answered, so an exchange is exactly two messages.
+    pub reply: bool,
+}
+
+impl HelloMessage {
+    /// This node's hello
+    pub fn local(reply: bool) -> Self {
+        Self {
+            protocol_version: PROTOCOL_VERSION,
+            min_protocol_version: MIN_PROTOCOL_VERSION,
+            signed_message_version: SIGNED_MESSAGE_VERSION,
+            capabilities: Capabilities::local(),
+            agent: concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION")).to_string(),
+            reply,
+        }
+    }
+}
+
+/// Why a peer's protocol can't be used
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum HandshakeError {
+    #[error("no common protocol version (ours {our_min}..={our_max}, theirs {their_min}..={their_max})")]
+    NoCommonVersion {
+        our_min: u16,
+        our_max: u16,
+        their_min: u16,
+        their_max: u16,
+    },
+
+    #[error("unsupported signed message version {0} (ours {SIGNED_MESSAGE_VERSION})")]
+    SignedMessageVersion(u8),
+}
+
+/// Outcome of negotiating with a peer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerProtocol {
+    /// Highest protocol version both sides speak
+    pub version: u16,
+    /// Features both sides support
+    pub capabilities: Capabilities,
+    /// The peer's software identifier
+    pub agent: String,
+}
+
+/// Negotiate our hello against a peer's
+pub fn negotiate(local: &HelloMessage, remote: &HelloMessage) -> Result<PeerProtocol, HandshakeError> {
+    let version = local.protocol_version.min(remote.protocol_version);
+    if version < local.min_protocol_version || version < remote.min_protocol_version {
+        return Err(HandshakeError::NoCommonVersion {
+            our_min: local.min_protocol_version,
+            our_max: local.protocol_version,
+            their_min: remote.min_protocol_version,
+            their_max: remote.protocol_version,
+        });
+    }
+    if remote.signed_message_version != local.signed_message_version {
+        return Err(HandshakeError::SignedMessageVersion(remote.signed_message_version));
+    }
+    Ok(PeerProtocol {
+        version,
+        capabilities: local.capabilities.intersection(remote.capabilities),
+        agent: remote.agent.clone(),
+    })
+}
+
+/// Per-peer handshake state
+#[derive(Default)]
+pub struct PeerProtocols {
+    negotiated: DashMap<IrohIdentity, Result<PeerProtocol, HandshakeError>>,
+    hello_sent: DashSet<IrohIdentity>,
+}
+
+impl PeerProtocols {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Negotiate with a peer's hello and remember the outcome
+    pub fn record(
+        &self,
+        peer: IrohIdentity,
+        hello: &HelloMessage,
+    ) -> Result<PeerProtocol, HandshakeError> {
+        let outcome = negotiate(&HelloMessage::local(false), hello);
+        self.negotiated.insert(peer, outcome.clone());
+        outcome
+    }
+
+    /// The negotiated protocol with a peer, if the handshake succeeded
+    pub fn get(&self, peer: &IrohIdentity) -> Option<PeerProtocol> {
+        self.negotiated.get(peer).and_then(|o| o.value().clone().ok())
+    }
+
+    /// Whether the handshake with this peer failed
+    pub fn is_incompatible(&self, peer: &IrohIdentity) -> bool {
+        self.negotiated.get(peer).is_some_and(|o| o.is_err())
+    }
+
+    /// Whether an optional feature may be used with this peer
+    ///
+    /// Peers without a completed handshake get [`Capabilities::LEGACY`];
+    /// incompatible peers get nothing.
+    pub fn supports(&self, peer: &IrohIdentity, feature: Capabilities) -> bool {
+        let capabilities = match self.negotiated.get(peer) {
+            Some(outcome) => match outcome.value() {
+                Ok(protocol) => protocol.capabilities,
+                Err(_) => Capabilities::NONE,
+            },
+            None => Capabilities::LEGACY,
+        };
+        capabilities.contains(feature)
+    }
+
+    /// Wire encoding version to use when sending to this peer
+    ///
+    /// Peers without a completed handshake get the version 1 encoding,
+    /// which every node can read.
+    pub fn wire_version(&self, peer: &IrohIdentity) -> u16 {
+        self.get(peer).map_or(WIRE_VERSION_LEGACY, |protocol| protocol.version)
+    }
+
+    /// Mark that we've sent our hello to a peer
+    ///
+    /// Returns `true` the first time, meaning a hello should be sent now.
+    pub fn mark_hello_sent(&self, peer: IrohIdentity) -> bool {
+        self.hello_sent.insert(peer)
+    }
+
+    /// Forget a peer so the handshake runs again on next contact
+    pub fn forget(&self, peer: &IrohIdentity) {
+        self.negotiated.remove(peer);
+        self.hello_sent.remove(peer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iroh::SecretKey;
+
+    fn peer() -> IrohIdentity {
+        IrohIdentity::new(SecretKey::generate(&mut rand::rng()).public())
+    }
+
+    #[test]
+    fn test_negotiate_picks_shared_version_and_features() {
+        let mut remote = HelloMessage::local(false);
+        remote.protocol_version = PROTOCOL_VERSION + 3;
+        remote.capabilities = Capabilities::DTN | Capabilities::COMPRESSION;
+
+        let protocol = negotiate(&HelloMessage::local(false), &remote).unwrap();
+        assert_eq!(protocol.version, PROTOCOL_VERSION);
+        assert_eq!(protocol.capabilities, Capabilities::DTN);
+    }
+
+    #[test]
+    fn test_negotiate_rejects_disjoint_versions() {
+        let mut remote = HelloMessage::local(false);
+        remote.min_protocol_version = PROTOCOL_VERSION + 1;
+        remote.protocol_version = PROTOCOL_VERSION + 2;
+
+        assert!(matches!(
+            negotiate(&HelloMessage::local(false), &remote),
+            Err(HandshakeError::NoCommonVersion { .. })
+        ));
+    }
+
+    #[test]
+    fn test_supports_defaults_and_recorded() {
+        let protocols = PeerProtocols::new();
+        let legacy = peer();
+        assert!(protocols.supports(&legacy, Capabilities::DTN));
+        assert!(!protocols.supports(&legacy, Capabilities::COMPRESSION));
+
+        let modern = peer();
+        let mut hello = HelloMessage::local(false);
+        hello.capabilities = Capabilities::CAUSAL_DELIVERY;
+        protocols.record(modern, &hello).unwrap();
+        assert!(!protocols.supports(&modern, Capabilities::DTN));
+        assert!(protocols.supports(&modern, Capabilities::CAUSAL_DELIVERY));
+
+        let broken = peer();
+        hello.signed_message_version = SIGNED_MESSAGE_VERSION + 1;
+        assert!(protocols.record(broken, &hello).is_err());
+        assert!(protocols.is_incompatible(&broken));
+        assert!(!protocols.supports(&broken, Capabilities::CAUSAL_DELIVERY));
+    }
+
+    #[test]
+    fn test_wire_version_follows_negotiation() {
+        let protocols = PeerProtocols::new();
+        let p = peer();
+        assert_eq!(protocols.wire_version(&p), WIRE_VERSION_LEGACY);
+
+        let mut v1 = HelloMessage::local(false);
+        v1.protocol_version = 1;
+        protocols.record(p, &v1).unwrap();
+        assert_eq!(protocols.wire_version(&p), 1);
+
+        protocols.record(p, &HelloMessage::local(false)).unwrap();
+        assert_eq!(protocols.wire_version(&p), PROTOCOL_VERSION);
+    }
+
+    #[test]
+    fn test_hello_sent_once_until_forgotten() {
+        let protocols = PeerProtocols::new();
+        let p = peer();
+        assert!(protocols.mark_hello_sent(p));
+        assert!(!protocols.mark_hello_sent(p));
+        protocols.forget(&p);
+        assert!(protocols.mark_hello_sent(p));
+    }
+
+    #[test]
+    fn test_capability_names() {
+        assert_eq!(
+            Capabilities::local().to_string(),
+            "dtn,causal-delivery,member-certificates,anonymous-relay"
+        );
+        assert_eq!(Capabilities::NONE.names(), Vec::<&str>::new());
+    }
+}
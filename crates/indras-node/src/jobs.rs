@@ -0,0 +1,509 @@
+//! Periodic background jobs, node-wide or per interface
+//!
+//! Features that need to do something every so often (retention pruning,
+//! digests, heat decay, reminders) register a [`Job`] with the node's
+//! [`JobScheduler`] instead of spawning their own tokio loop. A job is
+//! described by a [`JobSpec`]: a unique name, an interval, optional
+//! jitter, and a [`JobScope`] saying whether it runs once for the node or
+//! once for each loaded interface.
+//!
+//! The scheduler persists when each job last finished (per interface for
+//! interface-scoped jobs) in storage, so a restart picks up where the
+//! previous run left off rather than firing everything at once. Jitter
+//! spreads runs that would otherwise line up, e.g. the same job across
+//! many interfaces.
+//!
+//! ## Shutdown
+//!
+//! When the node stops, no new runs start, [`JobContext::cancelled`]
+//! resolves, and running jobs get a short grace period to return before
+//! they are aborted. A run that was aborted or failed is not recorded, so
+//! it is due again on the next start.
+
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::sync::{broadcast, watch};
+use tokio::task::{JoinHandle, JoinSet};
+use tracing::{debug, warn};
+
+use indras_core::InterfaceId;
+use indras_storage::CompositeStorage;
+use indras_transport::IrohIdentity;
+
+use crate::error::NodeResult;
+use crate::guest::now_millis;
+
+/// How often the scheduler checks for due jobs.
+const TICK: Duration = Duration::from_secs(1);
+
+/// How long running jobs may take to return after shutdown is signalled.
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(2);
+
+/// Where a job runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobScope {
+    /// Once for the whole node.
+    Node,
+    /// Once for each loaded interface, each on its own schedule.
+    EachInterface,
+}
+
+/// How and how often a job runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobSpec {
+    /// Unique name; also the key its last-run times are stored under.
+    pub name: String,
+    /// Time between the end of one run and the start of the next.
+    pub interval: Duration,
+    /// Up to this much is added to each wait, varying per run and scope.
+    pub jitter: Duration,
+    /// Node-wide or per interface.
+    pub scope: JobScope,
+}
+
+impl JobSpec {
+    /// A job that runs once for the node every `interval`.
+    pub fn node(name: impl Into<String>, interval: Duration) -> Self {
+        Self {
+            name: name.into(),
+            interval,
+            jitter: Duration::ZERO,
+            scope: JobScope::Node,
+        }
+    }
+
+    /// A job that runs for each interface every `interval`.
+    pub fn per_interface(name: impl Into<String>, interval: Duration) -> Self {
+        Self {
+            scope: JobScope::EachInterface,
+            ..Self::node(name, interval)
+        }
+    }
+
+    /// Add up to `jitter` to each wait.
+    pub fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// When a run is next due, given when the last one finished.
+    ///
+    /// Jobs that never ran are due right away, plus jitter. The jitter is
+    /// derived from the job, scope and last run, so it differs between
+    /// interfaces and between runs but not between restarts.
+    pub fn next_run(
+        &self,
+        interface_id: Option<&InterfaceId>,
+        last_run_millis: Option<u64>,
+    ) -> u64 {
+        let jitter_millis = self.jitter.as_millis() as u64;
+        let jitter = if jitter_millis == 0 {
+            0
+        } else {
+            let mut hasher = blake3::Hasher::new();
+            hasher.update(self.name.as_bytes());
+            if let Some(id) = interface_id {
+                hasher.update(id.as_bytes());
+            }
+            hasher.update(&last_run_millis.unwrap_or(0).to_be_bytes());
+            let hash = hasher.finalize();
+            let seed = u64::from_be_bytes(hash.as_bytes()[..8].try_into().unwrap_or_default());
+            seed % (jitter_millis + 1)
+        };
+        match last_run_millis {
+            Some(last) => last + self.interval.as_millis() as u64 + jitter,
+            None => jitter,
+        }
+    }
+}
+
+/// What a job run is about.
+#[derive(Debug, Clone)]
+pub struct JobContext {
+    /// The interface, for interface-scoped jobs.
+    pub interface_id: Option<InterfaceId>,
+    /// When the previous run finished (Unix millis), if ever.
+    pub last_run_millis: Option<u64>,
+    shutdown: watch::Receiver<bool>,
+}
+
+impl JobContext {
+    /// Whether the node is shutting down.
+    pub fn is_cancelled(&self) -> bool {
+        *self.shutdown.borrow()
+    }
+
+    /// Resolves when the node starts shutting down.
+    ///
+    /// Long jobs can `select!` on this to stop at a safe point instead of
+    /// being aborted after the grace period.
+    pub async fn cancelled(&self) {
+        let mut shutdown = self.shutdown.clone();
+        let _ = shutdown.wait_for(|stopping| *stopping).await;
+    }
+}
+
+/// Work run periodically by the [`JobScheduler`].
+///
+/// Implemented for async closures, so a job registers as
+/// `jobs.register(spec, |ctx: JobContext| async move { ... })`.
+#[async_trait]
+pub trait Job: Send + Sync {
+    /// Do one run. An error is logged and the run is not recorded.
+    async fn run(&self, ctx: JobContext) -> NodeResult<()>;
+}
+
+#[async_trait]
+impl<F, Fut> Job for F
+where
+    F: Fn(JobContext) -> Fut + Send + Sync,
+    Fut: Future<Output = NodeResult<()>> + Send,
+{
+    async fn run(&self, ctx: JobContext) -> NodeResult<()> {
+        self(ctx).await
+    }
+}
+
+/// A job and the interface it runs for.
+type RunKey = (String, Option<InterfaceId>);
+
+/// A run that is due.
+struct DueRun {
+    key: RunKey,
+    job: Arc<dyn Job>,
+    last_run_millis: Option<u64>,
+}
+
+/// Registry and runner of the node's periodic jobs.
+///
+/// Owned by `IndrasNode`; access it via `node.jobs()`. Jobs can be
+/// registered before or after the node starts.
+///
+/// # Example
+///
+/// ```ignore
+/// node.jobs().register(
+///     JobSpec::per_interface("retention", Duration::from_secs(3600))
+///         .with_jitter(Duration::from_secs(300)),
+///     move |ctx: JobContext| {
+///         let node = node.clone();
+///         async move { node.prune(&ctx.interface_id.unwrap()).await }
+///     },
+/// );
+/// ```
+pub struct JobScheduler {
+    jobs: RwLock<HashMap<String, (JobSpec, Arc<dyn Job>)>>,
+    storage: Arc<CompositeStorage<IrohIdentity>>,
+    /// Last finish time per run, loaded from storage on first use.
+    last_runs: Mutex<HashMap<RunKey, Option<u64>>>,
+    running: Mutex<HashSet<RunKey>>,
+}
+
+impl JobScheduler {
+    /// Create a scheduler persisting last-run times to `storage`.
+    pub fn new(storage: Arc<CompositeStorage<IrohIdentity>>) -> Self {
+        Self {
+            jobs: RwLock::new(HashMap::new()),
+            storage,
+            last_runs: Mutex::new(HashMap::new()),
+            running: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Register a job, replacing any job with the same name.
+    pub fn register(&self, spec: JobSpec, job: impl Job + 'static) {
+        let mut jobs = self.jobs.write().unwrap_or_else(|e| e.into_inner());
+        jobs.insert(spec.name.clone(), (spec, Arc::new(job)));
+    }
+
+    /// Remove a job. A run in progress finishes. Returns `false` if no job
+    /// had that name.
+    pub fn unregister(&self, name: &str) -> bool {
+        let mut jobs = self.jobs.write().unwrap_or_else(|e| e.into_inner());
+        jobs.remove(name).is_some()
+    }
+
+    /// Specs of the registered jobs, sorted by name.
+    pub fn specs(&self) -> Vec<JobSpec> {
+        let jobs = self.jobs.read().unwrap_or_else(|e| e.into_inner());
+        let mut specs: Vec<JobSpec> = jobs.values().map(|(spec, _)| spec.clone()).collect();
+        specs.sort_by(|a, b| a.name.cmp(&b.name));
+        specs
+    }
+
+    /// When a job last finished, node-wide or for one interface.
+    pub fn last_run(&self, name: &str, interface_id: Option<&InterfaceId>) -> Option<u64> {
+        self.cached_last_run(&(name.to_string(), interface_id.copied()))
+    }
+
+    /// Drop cached state for an interface that was left.
+    ///
+    /// Stored last-run times are kept, as with other interface state, so
+    /// rejoining continues the schedule.
+    pub fn forget_interface(&self, interface_id: &InterfaceId) {
+        let mut last_runs = self.last_runs.lock().unwrap_or_else(|e| e.into_inner());
+        last_runs.retain(|(_, id), _| id.as_ref() != Some(interface_id));
+    }
+
+    fn cached_last_run(&self, key: &RunKey) -> Option<u64> {
+        let mut last_runs = self.last_runs.lock().unwrap_or_else(|e| e.into_inner());
+        *last_runs.entry(key.clone()).or_insert_with(|| {
+            self.storage
+                .job_runs()
+                .last_run(&key.0, key.1.as_ref())
+                .unwrap_or_else(|e| {
+                    debug!(job = %key.0, error = %e, "Failed to load job last run");
+                    None
+                })
+        })
+    }
+
+    /// Runs due at `now_millis` that are not already running, marked as
+    /// running.
+    fn take_due(&self, now_millis: u64, interfaces: &[InterfaceId]) -> Vec<DueRun> {
+        let candidates: Vec<(RunKey, JobSpec, Arc<dyn Job>)> = {
+            let jobs = self.jobs.read().unwrap_or_else(|e| e.into_inner());
+            jobs.values()
+                .flat_map(|(spec, job)| {
+                    let scopes: Vec<Option<InterfaceId>> = match spec.scope {
+                        JobScope::Node => vec![None],
+                        JobScope::EachInterface => interfaces.iter().copied().map(Some).collect(),
+                    };
+                    scopes
+                        .into_iter()
+                        .map(|scope| ((spec.name.clone(), scope), spec.clone(), Arc::clone(job)))
+                })
+                .collect()
+        };
+
+        let mut due = Vec::new();
+        for (key, spec, job) in candidates {
+            let last_run_millis = self.cached_last_run(&key);
+            if spec.next_run(key.1.as_ref(), last_run_millis) > now_millis {
+                continue;
+            }
+            let mut running = self.running.lock().unwrap_or_else(|e| e.into_inner());
+            if running.insert(key.clone()) {
+                due.push(DueRun {
+                    key,
+                    job,
+                    last_run_millis,
+                });
+            }
+        }
+        due
+    }
+
+    /// Record the end of a run started by `take_due`.
+    ///
+    /// Failed runs wait a full interval in memory but are not persisted.
+    fn finish(&self, key: RunKey, succeeded: bool, at_millis: u64) {
+        self.running
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&key);
+        if succeeded
+            && let Err(e) = self
+                .storage
+                .job_runs()
+                .record_run(&key.0, key.1.as_ref(), at_millis)
+        {
+            warn!(job = %key.0, error = %e, "Failed to persist job run");
+        }
+        self.last_runs
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key, Some(at_millis));
+    }
+
+    /// Run due jobs until shutdown.
+    ///
+    /// `interfaces` lists the interfaces interface-scoped jobs run for; it
+    /// is called on every tick so joined and left interfaces are followed.
+    pub(crate) fn spawn(
+        self: Arc<Self>,
+        interfaces: impl Fn() -> Vec<InterfaceId> + Send + 'static,
+        mut shutdown_rx: broadcast::Receiver<()>,
+    ) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let (cancel_tx, cancel_rx) = watch::channel(false);
+            let mut runs: JoinSet<(RunKey, bool)> = JoinSet::new();
+            let mut ticker = tokio::time::interval(TICK);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                tokio::select! {
+                    _ = shutdown_rx.recv() => break,
+                    Some(done) = runs.join_next(), if !runs.is_empty() => {
+                        if let Ok((key, succeeded)) = done {
+                            self.finish(key, succeeded, now_millis());
+                        }
+                    }
+                    _ = ticker.tick() => {
+                        for run in self.take_due(now_millis(), &interfaces()) {
+                            let ctx = JobContext {
+                                interface_id: run.key.1,
+                                last_run_millis: run.last_run_millis,
+                                shutdown: cancel_rx.clone(),
+                            };
+                            runs.spawn(async move {
+                                let result = run.job.run(ctx).await;
+                                if let Err(e) = &result {
+                                    warn!(job = %run.key.0, error = %e, "Job failed");
+                                }
+                                (run.key, result.is_ok())
+                            });
+                        }
+                    }
+                }
+            }
+
+            let _ = cancel_tx.send(true);
+            let drain = async {
+                while let Some(done) = runs.join_next().await {
+                    if let Ok((key, succeeded)) = done {
+                        self.finish(key, succeeded, now_millis());
+                    }
+                }
+            };
+            if tokio::time::timeout(SHUTDOWN_GRACE, drain).await.is_err() {
+                debug!(
+                    remaining = runs.len(),
+                    "Aborting jobs still running at shutdown"
+                );
+                runs.abort_all();
+            }
+            debug!("Job scheduler stopped");
+        })
+    }
+}
+
+impl std::fmt::Debug for JobScheduler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JobScheduler")
+            .field("jobs", &self.specs())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indras_storage::CompositeStorageConfig;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    async fn scheduler() -> Arc<JobScheduler> {
+        let storage = CompositeStorage::new(CompositeStorageConfig::in_memory())
+            .await
+            .unwrap();
+        Arc::new(JobScheduler::new(Arc::new(storage)))
+    }
+
+    fn iid(n: u8) -> InterfaceId {
+        InterfaceId::new([n; 32])
+    }
+
+    #[test]
+    fn test_jitter_is_bounded_and_spread() {
+        let spec = JobSpec::per_interface("prune", Duration::from_secs(60))
+            .with_jitter(Duration::from_secs(10));
+        let runs: Vec<u64> = (0..8)
+            .map(|n| spec.next_run(Some(&iid(n)), Some(1_000)))
+            .collect();
+        assert!(runs.iter().all(|at| (61_000..=71_000).contains(at)));
+        assert!(runs.iter().any(|at| *at != runs[0]));
+        assert_eq!(spec.next_run(Some(&iid(0)), Some(1_000)), runs[0]);
+
+        let plain = JobSpec::node("digest", Duration::from_secs(60));
+        assert_eq!(plain.next_run(None, None), 0);
+        assert_eq!(plain.next_run(None, Some(1_000)), 61_000);
+    }
+
+    #[tokio::test]
+    async fn test_due_runs_per_interface_and_persist() {
+        let scheduler = scheduler().await;
+        scheduler.register(
+            JobSpec::per_interface("prune", Duration::from_secs(60)),
+            |_ctx: JobContext| async { Ok(()) },
+        );
+        scheduler.register(
+            JobSpec::node("digest", Duration::from_secs(60)),
+            |_ctx: JobContext| async { Ok(()) },
+        );
+
+        let due = scheduler.take_due(1_000, &[iid(1), iid(2)]);
+        assert_eq!(due.len(), 3);
+        // Already running: not handed out twice
+        assert!(scheduler.take_due(1_000, &[iid(1), iid(2)]).is_empty());
+
+        for run in due {
+            scheduler.finish(run.key, true, 1_000);
+        }
+        assert!(scheduler.take_due(30_000, &[iid(1), iid(2)]).is_empty());
+        assert_eq!(scheduler.take_due(61_000, &[iid(1)]).len(), 2);
+
+        // Forgotten state is reloaded from storage
+        scheduler.forget_interface(&iid(2));
+        assert_eq!(scheduler.last_run("prune", Some(&iid(2))), Some(1_000));
+        assert_eq!(scheduler.last_run("digest", None), Some(1_000));
+    }
+
+    #[tokio::test]
+    async fn test_failed_run_is_not_persisted() {
+        let scheduler = scheduler().await;
+        scheduler.register(
+            JobSpec::node("flaky", Duration::from_secs(60)),
+            |_ctx: JobContext| async { Ok(()) },
+        );
+        let run = scheduler.take_due(5_000, &[]).pop().unwrap();
+        scheduler.finish(run.key, false, 5_000);
+
+        assert!(scheduler.take_due(6_000, &[]).is_empty());
+        assert_eq!(
+            scheduler
+                .storage
+                .job_runs()
+                .last_run("flaky", None)
+                .unwrap(),
+            None
+        );
+        assert!(scheduler.unregister("flaky"));
+        assert!(!scheduler.unregister("flaky"));
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_cancels_running_jobs() {
+        let scheduler = scheduler().await;
+        let started = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&started);
+        scheduler.register(
+            JobSpec::node("long", Duration::from_secs(3600)),
+            move |ctx: JobContext| {
+                let counter = Arc::clone(&counter);
+                async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    ctx.cancelled().await;
+                    Err(crate::NodeError::Channel("cancelled".into()))
+                }
+            },
+        );
+
+        let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
+        let handle = Arc::clone(&scheduler).spawn(Vec::new, shutdown_rx);
+        while started.load(Ordering::SeqCst) == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        shutdown_tx.send(()).unwrap();
+        handle.await.unwrap();
+
+        assert_eq!(started.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            scheduler.storage.job_runs().last_run("long", None).unwrap(),
+            None
+        );
+    }
+}
@@ -13,6 +13,9 @@
 //! Keys can be optionally encrypted at rest using passphrase-based encryption:
 //! - Key derivation: Argon2id with secure parameters
 //! - Encryption: ChaCha20-Poly1305 authenticated encryption
+//!
+//! Ephemeral nodes use [`MemoryKeystore`], which keeps freshly generated keys
+//! in memory only.
 
 use std::path::{Path, PathBuf};
 
@@ -44,6 +47,16 @@ const PQ_KEM_DK_FILENAME: &str = "kem_dk.pq";
 /// Filename for the PQ KEM encapsulation key (public)
 const PQ_KEM_EK_FILENAME: &str = "kem_ek.pq";
 
+/// Files [`Keystore`] keeps secret keys in, unencrypted, with their public
+/// halves. Backups leave them out; see [`crate::backup`].
+pub(crate) const PLAINTEXT_KEY_FILES: &[&str] = &[
+    IROH_KEY_FILENAME,
+    PQ_SIGNING_KEY_FILENAME,
+    PQ_VERIFYING_KEY_FILENAME,
+    PQ_KEM_DK_FILENAME,
+    PQ_KEM_EK_FILENAME,
+];
+
 /// Filename suffix for encrypted key files
 const ENCRYPTED_SUFFIX: &str = ".enc";
 
@@ -1261,6 +1274,57 @@ impl Drop for StoryKeystore {
     }
 }
 
+// ========== In-Memory Keystore ==========
+
+/// Volatile keystore for ephemeral nodes
+///
+/// Mirrors the [`Keystore`] load/save API but never touches disk: keys are
+/// generated on first use and live only as long as the keystore.
+#[derive(Default)]
+pub struct MemoryKeystore {
+    iroh: std::sync::Mutex<Option<SecretKey>>,
+    pq_identity: std::sync::Mutex<Option<PQIdentity>>,
+    pq_kem: std::sync::Mutex<Option<PQKemKeyPair>>,
+}
+
+impl MemoryKeystore {
+    /// Create an empty in-memory keystore
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the held iroh key, generating one on first call
+    pub fn load_or_generate_iroh(&self) -> NodeResult<SecretKey> {
+        let mut slot = self.iroh.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(slot
+            .get_or_insert_with(|| SecretKey::generate(&mut rand::rng()))
+            .clone())
+    }
+
+    /// Replace the held iroh key
+    pub fn save_iroh(&self, key: &SecretKey) -> NodeResult<()> {
+        *self.iroh.lock().unwrap_or_else(|e| e.into_inner()) = Some(key.clone());
+        Ok(())
+    }
+
+    /// Return the held PQ identity, generating one on first call
+    pub fn load_or_generate_pq_identity(&self) -> NodeResult<PQIdentity> {
+        let mut slot = self.pq_identity.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(slot.get_or_insert_with(PQIdentity::generate).clone())
+    }
+
+    /// Return the held PQ KEM key pair, generating one on first call
+    pub fn load_or_generate_pq_kem(&self) -> NodeResult<PQKemKeyPair> {
+        let mut slot = self.pq_kem.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(slot.get_or_insert_with(PQKemKeyPair::generate).clone())
+    }
+
+    /// Check if an iroh key has been generated or saved
+    pub fn exists(&self) -> bool {
+        self.iroh.lock().unwrap_or_else(|e| e.into_inner()).is_some()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1706,4 +1770,29 @@ mod tests {
             assert_eq!(key.public(), iroh_public);
         }
     }
+
+    #[test]
+    fn test_memory_keystore_is_stable_and_diskless() {
+        let keystore = MemoryKeystore::new();
+        assert!(!keystore.exists());
+
+        let key1 = keystore.load_or_generate_iroh().unwrap();
+        let key2 = keystore.load_or_generate_iroh().unwrap();
+        assert_eq!(key1.public(), key2.public());
+
+        let pq1 = keystore.load_or_generate_pq_identity().unwrap();
+        let pq2 = keystore.load_or_generate_pq_identity().unwrap();
+        assert_eq!(pq1.verifying_key_bytes(), pq2.verifying_key_bytes());
+
+        let replacement = SecretKey::generate(&mut rand::rng());
+        keystore.save_iroh(&replacement).unwrap();
+        assert_eq!(
+            keystore.load_or_generate_iroh().unwrap().public(),
+            replacement.public()
+        );
+
+        // A fresh keystore yields a fresh identity
+        let other = MemoryKeystore::new().load_or_generate_iroh().unwrap();
+        assert_ne!(other.public(), key1.public());
+    }
 }
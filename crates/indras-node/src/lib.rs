@@ -30,30 +30,81 @@
 //! }
 //! ```
 
+pub mod anonymous;
+pub mod backup;
 pub mod bundle_store;
+pub mod capture;
+pub mod causal;
 mod config;
+pub mod connection_budget;
+pub mod dedup;
+pub mod delegation;
 pub mod delivery_tracker;
 pub mod dtn_manager;
 mod error;
+pub mod guest;
+pub mod handshake;
+pub mod jobs;
 mod keystore;
 pub mod message_handler;
+pub mod peer_set;
+pub mod supervisor;
 pub mod sync_task;
-
-pub use config::NodeConfig;
+pub mod topology;
+pub mod trust;
+pub mod wire;
+
+pub use anonymous::{ANONYMOUS_EVENT_TYPE, AnonymityRings, AnonymousPost, anonymous_sender};
+pub use backup::{
+    BackupFile, BackupManifest, BackupSummary, RestoreSummary, VerifyReport, restore_backup,
+    verify_backup,
+};
+pub use causal::{CausalConfig, CausalTracker};
+pub use dedup::{EventDedup, DEFAULT_DEDUP_WINDOW};
+pub use config::{DEFAULT_CONNECT_TIMEOUT, DEFAULT_REQUEST_TIMEOUT, NodeConfig};
+pub use connection_budget::{BudgetConfig, ConnectionBudget};
+pub use delegation::{
+    AuditEntry, AuthorizedAction, Capability, DelegatedAction, DelegatedRequest,
+    DelegationId, DelegationRegistry, DelegationToken, DenialReason, DEFAULT_AUDIT_CAPACITY,
+    UNKNOWN_TOKEN_WINDOW_MILLIS,
+};
 pub use delivery_tracker::{DeliveryStatus, DeliverySummary, DeliveryTracker};
 pub use error::{NodeError, NodeResult};
-pub use keystore::{EncryptedKeystore, Keystore, StoryKeystore};
+pub use guest::{GuestRegistry, GuestStatus};
+pub use handshake::{
+    Capabilities, HandshakeError, HelloMessage, MIN_PROTOCOL_VERSION, PROTOCOL_VERSION, PeerProtocol,
+    PeerProtocols,
+};
+pub use jobs::{Job, JobContext, JobScheduler, JobScope, JobSpec};
+pub use keystore::{EncryptedKeystore, Keystore, MemoryKeystore, StoryKeystore};
 pub use message_handler::{
-    EventAckMessage, InterfaceEventMessage, InterfaceSyncRequest, InterfaceSyncResponse,
+    CausalEventMessage, EventAckMessage, InterfaceEventMessage, InterfaceSyncRequest,
+    InterfaceSyncResponse,
     NetworkMessage, SIGNED_MESSAGE_VERSION, SignedNetworkMessage,
 };
+pub use peer_set::{
+    MIN_PEER_SET_SIZE, PEER_SET_VERSION, PeerSetError, deterministic_realm_id, validate_peer_set,
+};
+pub use supervisor::{
+    NodeHealth, RestartPolicy, SharedReceiver, Supervisor, TaskHealth, TaskIncident, TaskState,
+    shared_receiver,
+};
+pub use topology::{
+    ConnectionState, MemberSyncStatus, PeerLatency, SyncRecency, TopologyLink, TopologyPeer,
+    TopologyRealm, TopologySnapshot,
+};
+pub use trust::{MemberTrust, TrustPolicy, TrustRoots};
+pub use wire::{WIRE_VERSION_ENVELOPE, WIRE_VERSION_LEGACY, WireError};
 
+use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use bytes::Bytes;
-use dashmap::DashMap;
+use futures::StreamExt;
+use dashmap::{DashMap, DashSet};
 use serde::{Deserialize, Serialize};
 use tokio::sync::{RwLock, broadcast, mpsc};
 use tokio::task::JoinHandle;
@@ -62,11 +113,14 @@ use tracing::{debug, info, instrument, warn};
 use indras_core::transport::Transport;
 use indras_core::{EventId, InterfaceEvent, InterfaceId, NInterfaceTrait, PeerIdentity};
 use indras_crypto::{
-    InterfaceKey, KeyDistribution, KeyInvite, PQEncapsulationKey, PQIdentity, PQKemKeyPair,
+    InterfaceKey, KeyDistribution, KeyInvite, MemberCertificate, PQEncapsulationKey, PQIdentity,
+    PQKemKeyPair, PQPublicIdentity, RingPublicKey,
+};
+use indras_storage::{
+    CompositeStorage, Intent, NodeEvent, NodeLog, StorageComponent, StructuredBackend,
 };
-use indras_storage::{CompositeStorage, NodeEvent, NodeLog};
 use indras_sync::NInterface;
-use indras_transport::{IrohIdentity, IrohNetworkAdapter, PeerEvent};
+use indras_transport::{AccessControl, IrohIdentity, IrohNetworkAdapter, PeerEvent};
 
 use indras_homepage::HomepageServer;
 use message_handler::MessageHandler;
@@ -75,6 +129,9 @@ use sync_task::SyncTask;
 /// Default sync interval in seconds
 const DEFAULT_SYNC_INTERVAL_SECS: u64 = 5;
 
+/// Replaced keys per interface that are still tried when decrypting
+pub const MAX_RETIRED_KEYS: usize = 4;
+
 /// Invite key for joining an interface (post-quantum secure)
 ///
 /// Contains the interface ID, bootstrap peer addresses, and post-quantum
@@ -163,6 +220,17 @@ impl InviteKey {
     }
 }
 
+/// An interface this node belongs to, with its local state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InterfaceSummary {
+    /// The interface
+    pub interface_id: InterfaceId,
+    /// Whether the interface is archived (read-only)
+    pub archived: bool,
+    /// Whether the interface is a local-only sandbox
+    pub sandbox: bool,
+}
+
 /// Event received from an interface
 #[derive(Debug, Clone)]
 pub struct ReceivedEvent {
@@ -172,6 +240,37 @@ pub struct ReceivedEvent {
     pub event: InterfaceEvent<IrohIdentity>,
 }
 
+/// Point-in-time copy of an interface's CRDT state
+///
+/// Captured under a single lock so events, members, and heads are mutually
+/// consistent even while sync merges continue in the background.
+#[derive(Debug, Clone)]
+pub struct InterfaceSnapshot {
+    /// The interface this snapshot was taken from
+    pub interface_id: InterfaceId,
+    /// All events in the Automerge document at capture time
+    pub events: Vec<InterfaceEvent<IrohIdentity>>,
+    /// Members recorded in the document at capture time
+    pub members: Vec<IrohIdentity>,
+    /// Automerge change heads identifying the captured state
+    pub heads: Vec<[u8; 32]>,
+}
+
+/// Timing breakdown of the most recent [`IndrasNode::start`]
+#[derive(Debug, Clone, Default)]
+pub struct StartupMetrics {
+    /// Number of persisted interfaces restored
+    pub interfaces_loaded: usize,
+    /// Time spent starting the transport adapter
+    pub transport: Duration,
+    /// Time spent reading interface records and members from storage
+    pub storage: Duration,
+    /// Time spent rejoining gossip topics (concurrent)
+    pub topic_join: Duration,
+    /// Wall-clock time for the whole start sequence
+    pub total: Duration,
+}
+
 /// State for a loaded interface
 pub struct InterfaceState {
     /// The NInterface managing sync and events
@@ -214,6 +313,9 @@ pub struct IndrasNode {
     interfaces: Arc<DashMap<InterfaceId, InterfaceState>>,
     /// Interface encryption keys
     interface_keys: Arc<DashMap<InterfaceId, InterfaceKey>>,
+    /// Keys replaced by [`rotate_interface_key`](Self::rotate_interface_key),
+    /// newest first, still tried when decrypting
+    retired_keys: Arc<DashMap<InterfaceId, Vec<InterfaceKey>>>,
     /// Transport adapter (None until started)
     transport: RwLock<Option<Arc<IrohNetworkAdapter>>>,
     /// Shutdown signal sender
@@ -234,6 +336,36 @@ pub struct IndrasNode {
     dtn: Arc<dtn_manager::DtnManager>,
     /// Unified delivery status tracker across sync and DTN paths
     delivery_tracker: Arc<DeliveryTracker>,
+    /// Causal delivery state for interfaces that opt in
+    causal: Arc<CausalTracker>,
+    /// Duplicate suppression for incoming events
+    dedup: Arc<EventDedup>,
+    /// Read-only guests per interface
+    guests: Arc<GuestRegistry>,
+    /// Negotiated protocol version and capabilities per peer
+    protocols: Arc<PeerProtocols>,
+    /// Organization trust roots and member certificates per interface
+    trust: Arc<TrustRoots>,
+    /// Last sync time per interface and peer, for topology snapshots
+    sync_recency: Arc<SyncRecency>,
+    /// Smoothed round-trip time per peer, sampled by the sync task
+    latency: Arc<PeerLatency>,
+    /// Connection caps, pinned peers and interface traffic for reaping
+    budget: Arc<ConnectionBudget>,
+    /// Restarts crashed background tasks and tracks their health
+    supervisor: Arc<Supervisor>,
+    /// Periodic background jobs registered by features
+    jobs: Arc<JobScheduler>,
+    /// Local-only sandbox interfaces that never touch storage or the network
+    sandboxes: Arc<DashSet<InterfaceId>>,
+    /// Archived interfaces that reject local writes but keep syncing
+    archived: Arc<DashSet<InterfaceId>>,
+    /// Delegation tokens issued by this node and their audit trail
+    delegations: Arc<DelegationRegistry>,
+    /// Anonymity rings for interfaces that accept anonymous posts
+    anonymity_rings: Arc<AnonymityRings>,
+    /// Timing breakdown of the last start
+    startup_metrics: std::sync::Mutex<Option<StartupMetrics>>,
 }
 
 impl IndrasNode {
@@ -244,18 +376,25 @@ impl IndrasNode {
     #[instrument(skip(config), fields(data_dir = %config.data_dir.display()))]
     pub async fn new(config: NodeConfig) -> NodeResult<Self> {
         // Ensure data directory exists
-        tokio::fs::create_dir_all(&config.data_dir)
-            .await
-            .map_err(|e| NodeError::Io(e.to_string()))?;
+        if !config.ephemeral {
+            tokio::fs::create_dir_all(&config.data_dir)
+                .await
+                .map_err(|e| NodeError::Io(e.to_string()))?;
+        }
 
         // Initialize storage
-        let storage = CompositeStorage::new(config.storage.clone()).await?;
-        let storage = Arc::new(storage);
+        let storage = Self::open_storage(&config).await?;
         let node_log = storage.node_log().clone();
 
         // Load or generate all keys from keystore
         // Use encrypted keystore when passphrase is provided
-        let (secret_key, pq_identity, pq_kem_keypair) = if let Some(ref passphrase) = config.passphrase {
+        let (secret_key, pq_identity, pq_kem_keypair) = if config.ephemeral {
+            let keystore = MemoryKeystore::new();
+            let sk = keystore.load_or_generate_iroh()?;
+            let pq = keystore.load_or_generate_pq_identity()?;
+            let kem = keystore.load_or_generate_pq_kem()?;
+            (sk, pq, kem)
+        } else if let Some(ref passphrase) = config.passphrase {
             let mut keystore = EncryptedKeystore::new(&config.data_dir);
             keystore.unlock(passphrase)?;
             let sk = keystore.load_or_generate_iroh()?;
@@ -282,9 +421,13 @@ impl IndrasNode {
         );
 
         // Initialize DTN subsystem for offline peer delivery
-        let bundle_store = Arc::new(
+        let bundle_store = if config.ephemeral {
+            bundle_store::BundleStore::in_memory()
+        } else {
             bundle_store::BundleStore::open(&config.data_dir.join("dtn.redb"))
-                .map_err(|e| NodeError::Io(format!("Failed to open DTN store: {e}")))?
+        };
+        let bundle_store = Arc::new(
+            bundle_store.map_err(|e| NodeError::Io(format!("Failed to open DTN store: {e}")))?
         );
         let dtn = Arc::new(dtn_manager::DtnManager::new(
             config.dtn.clone(),
@@ -292,6 +435,11 @@ impl IndrasNode {
             identity.clone(),
         ));
 
+        let dedup = Arc::new(EventDedup::new(storage.clone(), DEFAULT_DEDUP_WINDOW));
+        let jobs = Arc::new(JobScheduler::new(storage.clone()));
+        let budget = Arc::new(ConnectionBudget::new(config.connection_budget.clone()));
+        let supervisor = Arc::new(Supervisor::new(config.restart_policy.clone(), node_log.clone()));
+
         Ok(Self {
             config,
             identity,
@@ -302,6 +450,7 @@ impl IndrasNode {
             node_log,
             interfaces: Arc::new(DashMap::new()),
             interface_keys: Arc::new(DashMap::new()),
+            retired_keys: Arc::new(DashMap::new()),
             transport: RwLock::new(None),
             shutdown_tx,
             background_tasks: RwLock::new(Vec::new()),
@@ -312,9 +461,72 @@ impl IndrasNode {
             relay_service: std::sync::OnceLock::new(),
             dtn,
             delivery_tracker: Arc::new(DeliveryTracker::new()),
+            causal: Arc::new(CausalTracker::default()),
+            dedup,
+            guests: Arc::new(GuestRegistry::new()),
+            protocols: Arc::new(PeerProtocols::new()),
+            trust: Arc::new(TrustRoots::new()),
+            sync_recency: Arc::new(SyncRecency::new()),
+            latency: Arc::new(PeerLatency::new()),
+            budget,
+            supervisor,
+            jobs,
+            sandboxes: Arc::new(DashSet::new()),
+            archived: Arc::new(DashSet::new()),
+            delegations: Arc::new(DelegationRegistry::new()),
+            anonymity_rings: Arc::new(AnonymityRings::new()),
+            startup_metrics: std::sync::Mutex::new(None),
         })
     }
 
+    /// Open storage and finish any operations a crash left half-applied
+    ///
+    /// If storage fails to open for any reason other than another process
+    /// holding it, it is reopened with
+    /// [`CompositeStorage::open_with_recovery`], which quarantines corrupted
+    /// files instead of failing. Each quarantined file is recorded in the
+    /// node log.
+    async fn open_storage(config: &NodeConfig) -> NodeResult<Arc<CompositeStorage<IrohIdentity>>> {
+        let storage = match CompositeStorage::new(config.storage.clone()).await {
+            Ok(storage) => storage,
+            Err(e) if e.is_locked() || config.storage.is_in_memory() => return Err(e.into()),
+            Err(e) => {
+                warn!(error = %e, "Storage failed to open, retrying in recovery mode");
+                CompositeStorage::open_with_recovery(config.storage.clone()).await?
+            }
+        };
+        if let Some(repair) = storage.repair_report() {
+            for file in &repair.quarantined {
+                let component = match file.component {
+                    StorageComponent::Database => "database",
+                    StorageComponent::EventLog(_) => "event_log",
+                    StorageComponent::NodeLog => "node_log",
+                };
+                let _ = storage
+                    .node_log()
+                    .append(NodeEvent::StorageQuarantined {
+                        component: component.to_string(),
+                        path: file.quarantined.display().to_string(),
+                        reason: file.reason.clone(),
+                        bytes_lost: file.bytes_lost,
+                    })
+                    .await;
+            }
+            if !repair.lost.is_empty() {
+                warn!(lost = ?repair.lost, "Storage recovered with data loss");
+            }
+        }
+        let report = storage.recover_intents().await?;
+        if report.total() > 0 {
+            warn!(
+                completed = report.completed,
+                rolled_back = report.rolled_back,
+                "Recovered half-applied operations from previous run"
+            );
+        }
+        Ok(Arc::new(storage))
+    }
+
     /// Create a node with a specific iroh identity
     ///
     /// Use this when you have an existing iroh secret key.
@@ -324,16 +536,23 @@ impl IndrasNode {
         config: NodeConfig,
         secret_key: iroh::SecretKey,
     ) -> NodeResult<Self> {
-        tokio::fs::create_dir_all(&config.data_dir)
-            .await
-            .map_err(|e| NodeError::Io(e.to_string()))?;
+        if !config.ephemeral {
+            tokio::fs::create_dir_all(&config.data_dir)
+                .await
+                .map_err(|e| NodeError::Io(e.to_string()))?;
+        }
 
-        let storage = CompositeStorage::new(config.storage.clone()).await?;
-        let storage = Arc::new(storage);
+        let storage = Self::open_storage(&config).await?;
         let node_log = storage.node_log().clone();
 
         // Save iroh key and load/generate PQ keys
-        let (pq_identity, pq_kem_keypair) = if let Some(ref passphrase) = config.passphrase {
+        let (pq_identity, pq_kem_keypair) = if config.ephemeral {
+            let keystore = MemoryKeystore::new();
+            keystore.save_iroh(&secret_key)?;
+            let pq = keystore.load_or_generate_pq_identity()?;
+            let kem = keystore.load_or_generate_pq_kem()?;
+            (pq, kem)
+        } else if let Some(ref passphrase) = config.passphrase {
             let mut keystore = EncryptedKeystore::new(&config.data_dir);
             keystore.unlock(passphrase)?;
             keystore.save_iroh(&secret_key)?;
@@ -359,9 +578,13 @@ impl IndrasNode {
         );
 
         // Initialize DTN subsystem for offline peer delivery
-        let bundle_store = Arc::new(
+        let bundle_store = if config.ephemeral {
+            bundle_store::BundleStore::in_memory()
+        } else {
             bundle_store::BundleStore::open(&config.data_dir.join("dtn.redb"))
-                .map_err(|e| NodeError::Io(format!("Failed to open DTN store: {e}")))?
+        };
+        let bundle_store = Arc::new(
+            bundle_store.map_err(|e| NodeError::Io(format!("Failed to open DTN store: {e}")))?
         );
         let dtn = Arc::new(dtn_manager::DtnManager::new(
             config.dtn.clone(),
@@ -369,6 +592,11 @@ impl IndrasNode {
             identity.clone(),
         ));
 
+        let dedup = Arc::new(EventDedup::new(storage.clone(), DEFAULT_DEDUP_WINDOW));
+        let jobs = Arc::new(JobScheduler::new(storage.clone()));
+        let budget = Arc::new(ConnectionBudget::new(config.connection_budget.clone()));
+        let supervisor = Arc::new(Supervisor::new(config.restart_policy.clone(), node_log.clone()));
+
         Ok(Self {
             config,
             identity,
@@ -379,6 +607,7 @@ impl IndrasNode {
             node_log,
             interfaces: Arc::new(DashMap::new()),
             interface_keys: Arc::new(DashMap::new()),
+            retired_keys: Arc::new(DashMap::new()),
             transport: RwLock::new(None),
             shutdown_tx,
             background_tasks: RwLock::new(Vec::new()),
@@ -389,6 +618,21 @@ impl IndrasNode {
             relay_service: std::sync::OnceLock::new(),
             dtn,
             delivery_tracker: Arc::new(DeliveryTracker::new()),
+            causal: Arc::new(CausalTracker::default()),
+            dedup,
+            guests: Arc::new(GuestRegistry::new()),
+            protocols: Arc::new(PeerProtocols::new()),
+            trust: Arc::new(TrustRoots::new()),
+            sync_recency: Arc::new(SyncRecency::new()),
+            latency: Arc::new(PeerLatency::new()),
+            budget,
+            supervisor,
+            jobs,
+            sandboxes: Arc::new(DashSet::new()),
+            archived: Arc::new(DashSet::new()),
+            delegations: Arc::new(DelegationRegistry::new()),
+            anonymity_rings: Arc::new(AnonymityRings::new()),
+            startup_metrics: std::sync::Mutex::new(None),
         })
     }
 
@@ -401,11 +645,16 @@ impl IndrasNode {
             return Err(NodeError::AlreadyStarted);
         }
 
+        let start_began = Instant::now();
+
         // Start transport adapter
         let adapter =
             IrohNetworkAdapter::new(self.secret_key.clone(), self.config.transport.clone()).await?;
         adapter.start(vec![]).await?;
         let adapter = Arc::new(adapter);
+        adapter.set_capture_decryptor(Arc::new(capture::NodeCaptureDecryptor::new(
+            self.interface_keys.clone(),
+        )));
 
         // Configure discovery service with our PQ keys for realm discovery
         let discovery = adapter.discovery_service();
@@ -420,36 +669,45 @@ impl IndrasNode {
         }
 
         *self.transport.write().await = Some(adapter.clone());
+        let transport_elapsed = start_began.elapsed();
 
         // Load persisted interfaces
-        self.load_persisted_interfaces().await?;
+        let mut metrics = self.load_persisted_interfaces().await?;
+        metrics.transport = transport_elapsed;
 
         // Create message channel for incoming messages
         let (message_tx, message_rx) = mpsc::channel(1024);
 
         // Spawn message receiver task
-        let transport_clone = adapter.clone();
-        let message_tx_clone = message_tx.clone();
-        let mut shutdown_rx = self.shutdown_tx.subscribe();
-        let receiver_task = tokio::spawn(async move {
-            loop {
-                tokio::select! {
-                    _ = shutdown_rx.recv() => break,
-                    result = transport_clone.recv() => {
-                        match result {
-                            Ok((sender, data)) => {
-                                if message_tx_clone.send((sender, data)).await.is_err() {
-                                    break;
+        let receiver_task = {
+            let transport = adapter.clone();
+            let message_tx = message_tx.clone();
+            let shutdown_tx = self.shutdown_tx.clone();
+            self.supervisor.supervise("receiver", &self.shutdown_tx, move || {
+                let transport = transport.clone();
+                let message_tx = message_tx.clone();
+                let mut shutdown_rx = shutdown_tx.subscribe();
+                tokio::spawn(async move {
+                    loop {
+                        tokio::select! {
+                            _ = shutdown_rx.recv() => break,
+                            result = transport.recv() => {
+                                match result {
+                                    Ok((sender, data)) => {
+                                        if message_tx.send((sender, data)).await.is_err() {
+                                            break;
+                                        }
+                                    }
+                                    Err(e) => {
+                                        warn!(error = %e, "Transport receive error");
+                                    }
                                 }
                             }
-                            Err(e) => {
-                                warn!(error = %e, "Transport receive error");
-                            }
                         }
                     }
-                }
-            }
-        });
+                })
+            })
+        };
 
         // Spawn message handler and sync task (both need PQ identity for signing)
         let pq_identity_arc = Arc::new(self.pq_identity.clone());
@@ -457,46 +715,146 @@ impl IndrasNode {
         let (sync_now_tx, sync_now_rx) = mpsc::channel(64);
         let _ = self.sync_now_tx.set(sync_now_tx.clone());
 
-        let handler_task = MessageHandler::spawn(
-            self.identity,
-            self.interface_keys.clone(),
-            self.interfaces.clone(),
-            self.storage.clone(),
-            adapter.clone(),
-            pq_identity_arc.clone(),
-            self.node_log.clone(),
-            self.config.allow_legacy_unsigned,
-            Some(sync_now_tx),
-            self.dtn.clone(),
-            self.shutdown_tx.subscribe(),
-            message_rx,
-        );
+        let handler_task = {
+            let identity = self.identity;
+            let interface_keys = self.interface_keys.clone();
+            let retired_keys = self.retired_keys.clone();
+            let interfaces = self.interfaces.clone();
+            let storage = self.storage.clone();
+            let transport = adapter.clone();
+            let pq_identity = pq_identity_arc.clone();
+            let node_log = self.node_log.clone();
+            let allow_legacy_unsigned = self.config.allow_legacy_unsigned;
+            let dtn = self.dtn.clone();
+            let causal = self.causal.clone();
+            let dedup = self.dedup.clone();
+            let guests = self.guests.clone();
+            let delegations = self.delegations.clone();
+            let anonymity_rings = self.anonymity_rings.clone();
+            let protocols = self.protocols.clone();
+            let trust = self.trust.clone();
+            let sync_recency = self.sync_recency.clone();
+            let budget = self.budget.clone();
+            let shutdown_tx = self.shutdown_tx.clone();
+            let message_rx = supervisor::shared_receiver(message_rx);
+            self.supervisor.supervise("message_handler", &self.shutdown_tx, move || {
+                MessageHandler::spawn(
+                    identity,
+                    interface_keys.clone(),
+                    retired_keys.clone(),
+                    interfaces.clone(),
+                    storage.clone(),
+                    transport.clone(),
+                    pq_identity.clone(),
+                    node_log.clone(),
+                    allow_legacy_unsigned,
+                    Some(sync_now_tx.clone()),
+                    dtn.clone(),
+                    causal.clone(),
+                    dedup.clone(),
+                    guests.clone(),
+                    delegations.clone(),
+                    anonymity_rings.clone(),
+                    protocols.clone(),
+                    trust.clone(),
+                    sync_recency.clone(),
+                    budget.clone(),
+                    shutdown_tx.subscribe(),
+                    message_rx.clone(),
+                )
+            })
+        };
 
         // Spawn sync task
-        let sync_task = SyncTask::spawn(
-            self.identity,
-            pq_identity_arc,
-            adapter.clone(),
-            self.interface_keys.clone(),
-            self.interfaces.clone(),
-            self.storage.clone(),
-            self.node_log.clone(),
-            Duration::from_secs(DEFAULT_SYNC_INTERVAL_SECS),
-            self.shutdown_tx.subscribe(),
-            sync_now_rx,
-            self.dtn.clone(),
-            self.delivery_tracker.clone(),
-        );
+        let sync_task = {
+            let identity = self.identity;
+            let pq_identity = pq_identity_arc;
+            let transport = adapter.clone();
+            let interface_keys = self.interface_keys.clone();
+            let interfaces = self.interfaces.clone();
+            let storage = self.storage.clone();
+            let node_log = self.node_log.clone();
+            let dtn = self.dtn.clone();
+            let delivery_tracker = self.delivery_tracker.clone();
+            let causal = self.causal.clone();
+            let guests = self.guests.clone();
+            let sandboxes = self.sandboxes.clone();
+            let protocols = self.protocols.clone();
+            let latency = self.latency.clone();
+            let shutdown_tx = self.shutdown_tx.clone();
+            let sync_now_rx = supervisor::shared_receiver(sync_now_rx);
+            self.supervisor.supervise("sync", &self.shutdown_tx, move || {
+                SyncTask::spawn(
+                    identity,
+                    pq_identity.clone(),
+                    transport.clone(),
+                    interface_keys.clone(),
+                    interfaces.clone(),
+                    storage.clone(),
+                    node_log.clone(),
+                    Duration::from_secs(DEFAULT_SYNC_INTERVAL_SECS),
+                    shutdown_tx.subscribe(),
+                    sync_now_rx.clone(),
+                    dtn.clone(),
+                    delivery_tracker.clone(),
+                    causal.clone(),
+                    guests.clone(),
+                    sandboxes.clone(),
+                    protocols.clone(),
+                    latency.clone(),
+                )
+            })
+        };
 
         // Spawn realm discovery event handler
-        let realm_discovery_task = Self::spawn_realm_discovery_handler(
-            self.identity,
-            adapter.clone(),
-            self.interfaces.clone(),
-            self.storage.clone(),
-            self.shutdown_tx.subscribe(),
+        let realm_discovery_task = {
+            let identity = self.identity;
+            let transport = adapter.clone();
+            let interfaces = self.interfaces.clone();
+            let storage = self.storage.clone();
+            let shutdown_tx = self.shutdown_tx.clone();
+            self.supervisor.supervise("realm_discovery", &self.shutdown_tx, move || {
+                Self::spawn_realm_discovery_handler(
+                    identity,
+                    transport.clone(),
+                    interfaces.clone(),
+                    storage.clone(),
+                    shutdown_tx.subscribe(),
+                )
+            })
+        };
+
+        // Keep connections within the budget
+        let budget = self.budget.clone();
+        let interfaces = self.interfaces.clone();
+        let budget_transport = adapter.clone();
+        self.jobs.register(
+            JobSpec::node("connection_budget", self.config.connection_budget.reap_interval),
+            move |_ctx: JobContext| {
+                let budget = budget.clone();
+                let interfaces = interfaces.clone();
+                let transport = budget_transport.clone();
+                async move {
+                    Self::apply_connection_budget(&budget, &interfaces, &transport).await;
+                    Ok(())
+                }
+            },
         );
 
+        // Spawn job scheduler
+        let jobs_task = {
+            let jobs = self.jobs.clone();
+            let interfaces = self.interfaces.clone();
+            let shutdown_tx = self.shutdown_tx.clone();
+            self.supervisor.supervise("jobs", &self.shutdown_tx, move || {
+                let interfaces = interfaces.clone();
+                Arc::clone(&jobs).spawn(
+                    move || interfaces.iter().map(|entry| *entry.key()).collect(),
+                    shutdown_tx.subscribe(),
+                )
+            })
+        };
+
         // Store task handles
         {
             let mut tasks = self.background_tasks.write().await;
@@ -504,6 +862,7 @@ impl IndrasNode {
             tasks.push(handler_task);
             tasks.push(sync_task);
             tasks.push(realm_discovery_task);
+            tasks.push(jobs_task);
 
             // Start homepage server if configured
             if let Some(port) = self.config.homepage_port {
@@ -527,53 +886,74 @@ impl IndrasNode {
             }
         }
 
-        // Create embedded relay service
-        let relay_data_dir = self.config.data_dir.join("relay-data");
-        let _ = std::fs::create_dir_all(&relay_data_dir);
-        let config_toml_path = relay_data_dir.join("relay.toml");
-        let owner_hex = hex::encode(self.identity.public_key().as_bytes());
-
-        let mut relay_config = indras_relay::RelayConfig::default();
-        relay_config.data_dir = relay_data_dir;
-        relay_config.owner_player_id = Some(owner_hex);
-
-        match indras_relay::RelayService::new(relay_config).await {
-            Ok(service) => {
-                let service = service
-                    .with_gossip(adapter.gossip().clone())
-                    .with_config_path(config_toml_path);
-                let service = Arc::new(service);
-                let _ = self.relay_service.set(Arc::clone(&service));
-
-                // Spawn bi-stream router
-                if let Some(mut bi_rx) = adapter.take_bi_stream_rx() {
-                    let relay = Arc::clone(&service);
-                    tokio::spawn(async move {
-                        while let Some((peer_id, send, recv)) = bi_rx.recv().await {
-                            let relay = Arc::clone(&relay);
-                            tokio::spawn(async move {
-                                if let Err(e) = relay.handle_bi_stream(peer_id, send, recv).await {
-                                    tracing::debug!(error = %e, "Relay stream ended");
-                                }
-                            });
-                        }
-                    });
-                }
+        // Create embedded relay service (it persists to disk, so ephemeral nodes skip it)
+        if self.config.ephemeral {
+            debug!("Ephemeral node: embedded relay service disabled");
+        } else {
+            let relay_data_dir = self.config.data_dir.join("relay-data");
+            let _ = std::fs::create_dir_all(&relay_data_dir);
+            let config_toml_path = relay_data_dir.join("relay.toml");
+            let owner_hex = hex::encode(self.identity.public_key().as_bytes());
+
+            let mut relay_config = indras_relay::RelayConfig::default();
+            relay_config.data_dir = relay_data_dir;
+            relay_config.owner_player_id = Some(owner_hex);
+
+            match indras_relay::RelayService::new(relay_config).await {
+                Ok(service) => {
+                    let service = service
+                        .with_gossip(adapter.gossip().clone())
+                        .with_config_path(config_toml_path);
+                    let service = Arc::new(service);
+                    let _ = self.relay_service.set(Arc::clone(&service));
+
+                    // Spawn bi-stream router
+                    if let Some(mut bi_rx) = adapter.take_bi_stream_rx() {
+                        let relay = Arc::clone(&service);
+                        tokio::spawn(async move {
+                            while let Some((peer_id, send, recv)) = bi_rx.recv().await {
+                                let relay = Arc::clone(&relay);
+                                tokio::spawn(async move {
+                                    if let Err(e) = relay.handle_bi_stream(peer_id, send, recv).await {
+                                        tracing::debug!(error = %e, "Relay stream ended");
+                                    }
+                                });
+                            }
+                        });
+                    }
 
-                tracing::info!("Embedded relay service started");
-            }
-            Err(e) => {
-                tracing::warn!(error = %e, "Failed to start relay service (non-fatal)");
+                    tracing::info!("Embedded relay service started");
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to start relay service (non-fatal)");
+                }
             }
         }
 
-        info!("Node started");
+        metrics.total = start_began.elapsed();
+        info!(
+            interfaces = metrics.interfaces_loaded,
+            transport_ms = metrics.transport.as_millis() as u64,
+            storage_ms = metrics.storage.as_millis() as u64,
+            topic_join_ms = metrics.topic_join.as_millis() as u64,
+            total_ms = metrics.total.as_millis() as u64,
+            "Node started"
+        );
+        *self.startup_metrics.lock().unwrap_or_else(|e| e.into_inner()) = Some(metrics);
         let _ = self.node_log.append(NodeEvent::NodeStarted {
             identity_fingerprint: *self.identity.public_key().as_bytes(),
         }).await;
         Ok(())
     }
 
+    /// Timing breakdown of the most recent start, if the node has started
+    pub fn startup_metrics(&self) -> Option<StartupMetrics> {
+        self.startup_metrics
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
     /// Get the embedded relay service, if started.
     pub fn relay_service(&self) -> Option<&Arc<indras_relay::RelayService>> {
         self.relay_service.get()
@@ -599,6 +979,55 @@ impl IndrasNode {
         self.homepage_artifacts.get().cloned()
     }
 
+    /// Close connections the budget has no room for
+    ///
+    /// Runs periodically while the node is started; call it to apply a
+    /// change (e.g. unpinning a contact) right away. Returns the peers that
+    /// were disconnected.
+    pub async fn reap_connections(&self) -> NodeResult<Vec<IrohIdentity>> {
+        let transport = self.transport.read().await.clone().ok_or(NodeError::NotStarted)?;
+        Ok(Self::apply_connection_budget(&self.budget, &self.interfaces, &transport).await)
+    }
+
+    async fn apply_connection_budget(
+        budget: &ConnectionBudget,
+        interfaces: &DashMap<InterfaceId, InterfaceState>,
+        transport: &IrohNetworkAdapter,
+    ) -> Vec<IrohIdentity> {
+        let ids: Vec<InterfaceId> = interfaces.iter().map(|entry| *entry.key()).collect();
+        let mut members = HashMap::new();
+        for interface_id in ids {
+            let Some(state) = interfaces.get(&interface_id) else {
+                continue;
+            };
+            let mut interface_members = state.interface.read().await.members();
+            drop(state);
+            interface_members.extend(
+                transport
+                    .discovery_service()
+                    .realm_members(&interface_id)
+                    .into_iter()
+                    .map(|peer_info| peer_info.peer_id),
+            );
+            members.insert(interface_id, interface_members);
+        }
+
+        let connections = transport.connection_manager();
+        let connected = connections.connected_peers();
+        let evict = budget.plan(&connected, &members, guest::now_millis() as i64);
+        for peer in &evict {
+            connections.close_connection(peer);
+        }
+        if !evict.is_empty() {
+            debug!(
+                closed = evict.len(),
+                open = connected.len() - evict.len(),
+                "Closed connections over budget"
+            );
+        }
+        evict
+    }
+
     /// Spawn the realm discovery event handler task
     fn spawn_realm_discovery_handler(
         local_identity: IrohIdentity,
@@ -709,13 +1138,18 @@ impl IndrasNode {
     }
 
     /// Load persisted interfaces from storage
-    async fn load_persisted_interfaces(&self) -> NodeResult<()> {
+    async fn load_persisted_interfaces(&self) -> NodeResult<StartupMetrics> {
+        let storage_began = Instant::now();
         let interface_records = self
             .storage
             .interface_store()
             .all()
             .map_err(NodeError::Storage)?;
 
+        // Phase 1: restore in-memory state from storage. Automerge documents
+        // are not built here — each interface hydrates on first use.
+        let mut topic_joins = Vec::new();
+        let mut loaded = 0;
         for record in interface_records {
             let interface_id = InterfaceId::new(record.interface_id);
 
@@ -724,17 +1158,15 @@ impl IndrasNode {
                 continue;
             }
 
-            // Create NInterface with known ID
-            let mut interface = NInterface::with_id(interface_id, self.identity);
-
             // Load members from storage
-            let members = self
+            let member_records = self
                 .storage
                 .interface_store()
                 .get_members(&interface_id)
                 .map_err(NodeError::Storage)?;
 
-            for member_record in members {
+            let mut members = vec![self.identity];
+            for member_record in member_records {
                 // Reconstruct peer identity from bytes
                 if member_record.peer_id.len() == 32 {
                     let mut key_bytes = [0u8; 32];
@@ -745,7 +1177,7 @@ impl IndrasNode {
 
                     // Don't add ourselves twice
                     if peer_identity != self.identity {
-                        let _ = interface.add_member(peer_identity);
+                        members.push(peer_identity);
                     }
                 }
             }
@@ -762,6 +1194,10 @@ impl IndrasNode {
                 }
             }
 
+            let bootstrap_peers: Vec<iroh::PublicKey> =
+                members[1..].iter().map(|m| *m.public_key()).collect();
+            let interface = NInterface::deferred(interface_id, members);
+
             // Create event channel
             let (event_tx, _) = broadcast::channel(self.config.event_channel_capacity);
             let (sync_tx, _) = broadcast::channel(64);
@@ -773,27 +1209,8 @@ impl IndrasNode {
                 sync_tx,
             };
             self.interfaces.insert(interface_id, state);
-
-            // Re-subscribe to gossip topic for this interface
-            if let Some(transport) = self.transport.read().await.as_ref() {
-                let discovery = transport.discovery_service();
-                let bootstrap_peers: Vec<iroh::PublicKey> = {
-                    let state = self.interfaces.get(&interface_id).unwrap();
-                    let iface = state.interface.read().await;
-                    iface
-                        .members()
-                        .iter()
-                        .filter(|m| **m != self.identity)
-                        .map(|m| *m.public_key())
-                        .collect()
-                };
-                if let Err(e) = discovery
-                    .join_realm_topic(interface_id, bootstrap_peers)
-                    .await
-                {
-                    warn!(error = %e, "Failed to rejoin realm gossip topic on startup");
-                }
-            }
+            topic_joins.push((interface_id, bootstrap_peers));
+            loaded += 1;
 
             debug!(
                 interface = %hex::encode(interface_id.as_bytes()),
@@ -801,9 +1218,34 @@ impl IndrasNode {
                 "Loaded persisted interface"
             );
         }
+        let storage_elapsed = storage_began.elapsed();
+
+        // Phase 2: re-subscribe to gossip topics with bounded concurrency
+        let join_began = Instant::now();
+        if let Some(transport) = self.transport.read().await.as_ref() {
+            let discovery = transport.discovery_service();
+            futures::stream::iter(topic_joins)
+                .for_each_concurrent(
+                    self.config.interface_load_concurrency.max(1),
+                    |(interface_id, bootstrap_peers)| async move {
+                        if let Err(e) = discovery
+                            .join_realm_topic(interface_id, bootstrap_peers)
+                            .await
+                        {
+                            warn!(error = %e, "Failed to rejoin realm gossip topic on startup");
+                        }
+                    },
+                )
+                .await;
+        }
 
         info!(count = self.interfaces.len(), "Loaded persisted interfaces");
-        Ok(())
+        Ok(StartupMetrics {
+            interfaces_loaded: loaded,
+            storage: storage_elapsed,
+            topic_join: join_began.elapsed(),
+            ..Default::default()
+        })
     }
 
     /// Stop the node
@@ -877,26 +1319,117 @@ impl IndrasNode {
         self.started.load(Ordering::SeqCst)
     }
 
+    /// Summarize the state of supervised background tasks
+    ///
+    /// Tasks that panic or exit early are restarted with backoff per
+    /// [`NodeConfig::restart_policy`]; see [`supervisor`].
+    pub async fn health(&self) -> NodeHealth {
+        NodeHealth {
+            started: self.is_started(),
+            transport_up: self.transport.read().await.is_some(),
+            tasks: self.supervisor.tasks(),
+            recent_incidents: self.supervisor.recent_incidents(),
+        }
+    }
+
+    /// Back up the data directory into `dest`
+    ///
+    /// Only chunks `dest` does not already hold are written, and each call
+    /// adds a manifest that [`restore_backup`] can restore on its own; see
+    /// [`backup`] for the layout. Safe to call while the node is running.
+    /// Plaintext key files and the interface keys in the structured
+    /// database are left out and listed in [`BackupSummary::keys_skipped`];
+    /// an encrypted keystore is included.
+    pub async fn backup(&self, dest: impl AsRef<Path>) -> NodeResult<BackupSummary> {
+        let storage_config = &self.config.storage;
+        if self.config.ephemeral || storage_config.is_in_memory() {
+            return Err(NodeError::Config(
+                "an ephemeral node has nothing on disk to back up".to_string(),
+            ));
+        }
+
+        let mut keys_removed = Vec::new();
+        let structured = match &storage_config.structured {
+            StructuredBackend::Redb => {
+                let db_path = storage_config.redb.db_path.strip_prefix(&self.config.data_dir);
+                match db_path.ok().and_then(|p| p.to_str()) {
+                    Some(path) => {
+                        let export = self.storage.export_structured()?;
+                        keys_removed = export.keys_removed;
+                        let export = backup::encode_structured(export.tables)?;
+                        Some((path.replace('\\', "/"), export))
+                    }
+                    None => {
+                        warn!("Structured database is outside the data directory; skipped");
+                        None
+                    }
+                }
+            }
+            StructuredBackend::InMemory | StructuredBackend::Custom(_) => None,
+        };
+        let source = backup::BackupSource {
+            data_dir: self.config.data_dir.clone(),
+            log_dir: storage_config.event_log.base_dir.clone(),
+            node_log: storage_config.base_dir.join("node_log.bin"),
+            structured,
+        };
+
+        let dest = dest.as_ref().to_path_buf();
+        let mut summary = tokio::task::spawn_blocking(move || backup::write_backup(&source, &dest))
+            .await
+            .map_err(|e| NodeError::Io(e.to_string()))??;
+        summary.keys_skipped.extend(keys_removed);
+        info!(
+            manifest = %summary.manifest.display(),
+            files = summary.files,
+            bytes_written = summary.bytes_written,
+            "Backup written"
+        );
+        Ok(summary)
+    }
+
     /// Get the transport adapter (if started)
     pub async fn transport(&self) -> Option<Arc<IrohNetworkAdapter>> {
         self.transport.read().await.clone()
     }
 
+    /// The transport's live access policy (requires a started node)
+    ///
+    /// Use it to allow peers, admit certificates, switch modes, and
+    /// subscribe to rejection events at runtime.
+    pub async fn access_control(&self) -> NodeResult<Arc<AccessControl>> {
+        let transport = self.transport.read().await.clone().ok_or(NodeError::NotStarted)?;
+        Ok(transport.access_control().clone())
+    }
+
+    /// Revoke a peer's access and disconnect it
+    ///
+    /// Returns `true` if the peer had been allowed or certified.
+    pub async fn revoke_peer(&self, key: &iroh::PublicKey) -> NodeResult<bool> {
+        let transport = self.transport.read().await.clone().ok_or(NodeError::NotStarted)?;
+        Ok(transport.revoke_peer(key))
+    }
+
     /// Connect to a peer using their serialized endpoint address.
     ///
     /// Establishes transport-level connectivity, enabling gossip discovery
     /// and CRDT sync for all shared interfaces. This is useful when you
     /// know a peer's address (e.g., from an invite code) but aren't
     /// joining a new interface.
+    ///
+    /// Fails with [`NodeError::Timeout`] after [`NodeConfig::connect_timeout`].
     pub async fn connect_to_bootstrap(&self, bootstrap_bytes: &[u8]) -> NodeResult<()> {
         let addr: iroh::EndpointAddr = postcard::from_bytes(bootstrap_bytes)?;
-        let guard = self.transport.read().await;
-        let transport = guard.as_ref().ok_or(NodeError::NotStarted)?;
-        transport
-            .connection_manager()
-            .connect(addr)
-            .await
-            .map_err(|e| NodeError::Transport(e.to_string()))?;
+        let transport = self.transport.read().await.clone().ok_or(NodeError::NotStarted)?;
+        let peer = hex::encode(addr.id.as_bytes());
+        bounded("connect", self.config.connect_timeout, async {
+            transport
+                .connection_manager()
+                .connect(addr)
+                .await
+                .map_err(|e| NodeError::transport_to(peer, &e))
+        })
+        .await?;
         Ok(())
     }
 
@@ -905,15 +1438,19 @@ impl IndrasNode {
     /// Creates an `EndpointAddr` from the key and connects via iroh relay.
     /// This establishes transport-level connectivity so that gossip messages
     /// and CRDT sync can flow between the two nodes.
+    ///
+    /// Fails with [`NodeError::Timeout`] after [`NodeConfig::connect_timeout`].
     pub async fn connect_to_peer(&self, peer_key_bytes: &[u8; 32]) -> NodeResult<()> {
         let public_key = iroh::PublicKey::from_bytes(peer_key_bytes)
             .map_err(|e| NodeError::Crypto(e.to_string()))?;
-        let guard = self.transport.read().await;
-        let transport = guard.as_ref().ok_or(NodeError::NotStarted)?;
-        transport
-            .connect_by_key_and_handle(public_key)
-            .await
-            .map_err(|e| NodeError::Transport(e.to_string()))?;
+        let transport = self.transport.read().await.clone().ok_or(NodeError::NotStarted)?;
+        bounded("connect", self.config.connect_timeout, async {
+            transport
+                .connect_by_key_and_handle(public_key)
+                .await
+                .map_err(|e| NodeError::transport_to(hex::encode(peer_key_bytes), &e))
+        })
+        .await?;
         Ok(())
     }
 
@@ -936,16 +1473,53 @@ impl IndrasNode {
     ///
     /// Unlike `connect_to_peer` which uses relay discovery (can be slow/unreliable),
     /// this connects using the full address including relay URL.
+    ///
+    /// Fails with [`NodeError::Timeout`] after [`NodeConfig::connect_timeout`].
     pub async fn connect_by_addr(&self, addr: iroh::EndpointAddr) -> NodeResult<()> {
-        let guard = self.transport.read().await;
-        let transport = guard.as_ref().ok_or(NodeError::NotStarted)?;
-        transport
-            .connect_and_handle(addr)
-            .await
-            .map_err(|e| NodeError::Transport(e.to_string()))?;
+        let transport = self.transport.read().await.clone().ok_or(NodeError::NotStarted)?;
+        let peer = hex::encode(addr.id.as_bytes());
+        bounded("connect", self.config.connect_timeout, async {
+            transport
+                .connect_and_handle(addr)
+                .await
+                .map_err(|e| NodeError::transport_to(peer, &e))
+        })
+        .await?;
         Ok(())
     }
 
+    /// Dial `peer` in the background and deliver `bytes` once connected
+    ///
+    /// Used for on-demand dialing; the caller must have claimed the dial
+    /// with [`ConnectionBudget::begin_dial`]. A failed dial is left to the
+    /// sync task and DTN.
+    fn dial_and_send(
+        &self,
+        transport: &Arc<IrohNetworkAdapter>,
+        interface_id: InterfaceId,
+        peer: IrohIdentity,
+        bytes: Vec<u8>,
+    ) {
+        let transport = Arc::clone(transport);
+        let budget = Arc::clone(&self.budget);
+        let timeout = self.config.connect_timeout;
+        tokio::spawn(async move {
+            debug!(peer = %peer.short_id(), "Dialing member on demand");
+            let dial = transport.connect_by_key_and_handle(*peer.public_key());
+            let dialed = tokio::time::timeout(timeout, dial).await;
+            budget.end_dial(&peer);
+            match dialed {
+                Ok(Ok(_)) => {
+                    if transport.send(&peer, bytes).await.is_ok() {
+                        budget.touch(interface_id, peer, guest::now_millis() as i64);
+                    }
+                }
+                Ok(Err(e)) => debug!(peer = %peer.short_id(), error = %e, "On-demand dial failed"),
+                Err(_) => debug!(peer = %peer.short_id(), "On-demand dial timed out"),
+            }
+        });
+    }
+
     /// Get our endpoint address for sharing with peers
     pub async fn endpoint_addr(&self) -> Option<iroh::EndpointAddr> {
         self.transport
@@ -1043,6 +1617,27 @@ impl IndrasNode {
             .await
     }
 
+    /// Create the interface for a peer set, or return it if it exists.
+    ///
+    /// Every member derives the same ID and key from the set's verifying
+    /// keys (see [`peer_set`]), so nobody needs to send an invite. The set
+    /// must pass [`validate_peer_set`] and include this node.
+    pub async fn create_peer_set_interface(
+        &self,
+        peers: &[PQPublicIdentity],
+        name: Option<&str>,
+        bootstrap_peers: Vec<iroh::PublicKey>,
+    ) -> NodeResult<(InterfaceId, InviteKey)> {
+        validate_peer_set(peers).map_err(|e| NodeError::Config(e.to_string()))?;
+        let me = self.pq_identity.verifying_key_bytes();
+        if !peers.iter().any(|peer| peer.to_bytes() == me) {
+            return Err(NodeError::Config(PeerSetError::MissingSelf.to_string()));
+        }
+        let (interface_id, key_seed) = deterministic_realm_id(peers);
+        self.create_interface_with_seed(interface_id, &key_seed, name, bootstrap_peers)
+            .await
+    }
+
     /// Internal helper to set up an interface after creation.
     async fn setup_interface(
         &self,
@@ -1113,6 +1708,69 @@ impl IndrasNode {
         Ok((interface_id, invite))
     }
 
+    /// Create a local-only sandbox interface
+    ///
+    /// A sandbox lives in memory only: it is never written to storage, never
+    /// joins a gossip topic, is skipped by the sync task, and messages sent to
+    /// it are delivered to local subscribers without reaching any peer. It
+    /// cannot be invited to. [`leave_interface`](Self::leave_interface)
+    /// discards it.
+    #[instrument(skip(self))]
+    pub async fn create_sandbox_interface(&self) -> NodeResult<InterfaceId> {
+        let interface = NInterface::new(self.identity);
+        let interface_id = interface.id();
+
+        self.interface_keys
+            .insert(interface_id, InterfaceKey::generate(interface_id));
+
+        let (event_tx, _) = broadcast::channel(self.config.event_channel_capacity);
+        let (sync_tx, _) = broadcast::channel(64);
+        self.sandboxes.insert(interface_id);
+        self.interfaces.insert(
+            interface_id,
+            InterfaceState {
+                interface: RwLock::new(interface),
+                event_tx,
+                sync_tx,
+            },
+        );
+
+        info!(interface_id = %hex::encode(interface_id.as_bytes()), "Sandbox interface created");
+        Ok(interface_id)
+    }
+
+    /// Whether an interface is a local-only sandbox
+    pub fn is_sandbox(&self, interface_id: &InterfaceId) -> bool {
+        self.sandboxes.contains(interface_id)
+    }
+
+    /// Archive or unarchive an interface
+    ///
+    /// An archived interface rejects local sends with
+    /// [`NodeError::Archived`] but still receives and syncs events, so
+    /// members who were offline can catch up. The flag is local and not
+    /// persisted; callers re-apply it from the realm's archive document.
+    pub fn set_archived(&self, interface_id: &InterfaceId, archived: bool) {
+        if archived {
+            self.archived.insert(*interface_id);
+        } else {
+            self.archived.remove(interface_id);
+        }
+    }
+
+    /// Whether an interface is archived
+    pub fn is_archived(&self, interface_id: &InterfaceId) -> bool {
+        self.archived.contains(interface_id)
+    }
+
+    /// Fail if an interface is archived
+    fn ensure_writable(&self, interface_id: &InterfaceId) -> NodeResult<()> {
+        if self.is_archived(interface_id) {
+            return Err(NodeError::Archived(hex::encode(interface_id.as_bytes())));
+        }
+        Ok(())
+    }
+
     /// Create an invite for a specific peer using ML-KEM
     ///
     /// Includes the ML-KEM encapsulated interface key for the invitee,
@@ -1122,6 +1780,10 @@ impl IndrasNode {
         interface_id: &InterfaceId,
         invitee_encapsulation_key: &PQEncapsulationKey,
     ) -> NodeResult<InviteKey> {
+        if self.is_sandbox(interface_id) {
+            return Err(NodeError::Sandboxed(hex::encode(interface_id.as_bytes())));
+        }
+
         // Get interface key
         let interface_key = self
             .interface_keys
@@ -1156,8 +1818,33 @@ impl IndrasNode {
     }
 
     /// Join an existing interface using an invite key
-    #[instrument(skip(self, invite))]
+    ///
+    /// Waits at most [`NodeConfig::request_timeout`] for the bootstrap; see
+    /// [`join_interface_with_timeout`](Self::join_interface_with_timeout).
     pub async fn join_interface(&self, invite: InviteKey) -> NodeResult<InterfaceId> {
+        self.join_interface_with_timeout(invite, self.config.request_timeout)
+            .await
+    }
+
+    /// Join an existing interface, bounding the network bootstrap by `timeout`
+    ///
+    /// Membership is committed locally (journaled, in memory) before any
+    /// network I/O, so the interface is joined even if the bootstrap stalls.
+    /// Bootstrap peers are then dialled concurrently, each bounded by
+    /// [`NodeConfig::connect_timeout`], and the whole bootstrap (connects,
+    /// gossip topic join, initial sync requests) by `timeout`. A stalled
+    /// bootstrap is abandoned with a warning rather than failing the join:
+    /// peers are still picked up through gossip and periodic sync, and the
+    /// bootstrap is idempotent, so calling this again (or restarting) retries it.
+    ///
+    /// Cancel-safe: dropping the future at any await point leaves the node
+    /// consistent. No lock is held across network I/O.
+    #[instrument(skip(self, invite))]
+    pub async fn join_interface_with_timeout(
+        &self,
+        invite: InviteKey,
+        timeout: Duration,
+    ) -> NodeResult<InterfaceId> {
         let interface_id = invite.interface_id;
         let already_joined = self.interfaces.contains_key(&interface_id);
 
@@ -1187,10 +1874,19 @@ impl IndrasNode {
             // Create NInterface with known ID
             let interface = NInterface::with_id(interface_id, self.identity);
 
-            // Persist to storage
-            self.storage.create_interface(interface_id, None)?;
-            self.storage.register_peer(&self.identity, None)?;
-            self.storage.add_member(&interface_id, &self.identity)?;
+            // Persist to storage, journaled so a crash mid-way is completed on restart
+            let encrypted_key = self
+                .interface_keys
+                .get(&interface_id)
+                .map(|key| key.as_bytes().to_vec());
+            let intent = self.storage.journal().begin(Intent::JoinInterface {
+                interface_id,
+                member: self.identity.as_bytes(),
+                encrypted_key: encrypted_key.clone(),
+            })?;
+            self.storage
+                .record_joined_interface(interface_id, &self.identity, encrypted_key)?;
+            self.storage.journal().complete(intent)?;
 
             // Create event channel
             let (event_tx, _) = broadcast::channel(self.config.event_channel_capacity);
@@ -1207,36 +1903,75 @@ impl IndrasNode {
 
         // Always connect to bootstrap peers and set up gossip
         // (needed after restart to re-establish connections and member lists)
-        let mut bootstrap_peer_ids = Vec::new();
-        let mut bootstrap_public_keys = Vec::new();
-        if let Some(transport) = self.transport.read().await.as_ref() {
-            for peer_bytes in &invite.bootstrap_peers {
-                // Deserialize endpoint address using postcard
-                if let Ok(addr) = postcard::from_bytes::<iroh::EndpointAddr>(peer_bytes) {
-                    let peer_id = IrohIdentity::new(addr.id);
-                    debug!(peer = %peer_id.short_id(), "Connecting to bootstrap peer");
-                    bootstrap_public_keys.push(addr.id);
-                    if let Err(e) = transport.connect_and_handle(addr).await {
+        let bootstrap = self.bootstrap_interface(interface_id, &invite.bootstrap_peers);
+        if let Err(e) = bounded("interface bootstrap", timeout, bootstrap).await {
+            warn!(error = %e, "Interface bootstrap incomplete");
+        }
+
+        info!(interface_id = %hex::encode(interface_id.as_bytes()), "Joined interface");
+        Ok(interface_id)
+    }
+
+    /// Connect to an interface's bootstrap peers, join its gossip topic and
+    /// request an initial sync
+    async fn bootstrap_interface(
+        &self,
+        interface_id: InterfaceId,
+        bootstrap_peers: &[Vec<u8>],
+    ) -> NodeResult<()> {
+        let Some(transport) = self.transport.read().await.clone() else {
+            return Ok(());
+        };
+
+        let addrs: Vec<iroh::EndpointAddr> = bootstrap_peers
+            .iter()
+            .filter_map(|bytes| postcard::from_bytes(bytes).ok())
+            .collect();
+        let bootstrap_public_keys = addrs.iter().map(|addr| addr.id).collect();
+        let connects = addrs.into_iter().map(|addr| {
+            let transport = Arc::clone(&transport);
+            async move {
+                let peer_id = IrohIdentity::new(addr.id);
+                debug!(peer = %peer_id.short_id(), "Connecting to bootstrap peer");
+                let connect = async {
+                    transport
+                        .connect_and_handle(addr)
+                        .await
+                        .map_err(|e| NodeError::transport_to(hex::encode(peer_id.as_bytes()), &e))
+                };
+                match bounded("connect", self.config.connect_timeout, connect).await {
+                    Ok(_) => Some(peer_id),
+                    Err(e) => {
                         warn!(error = %e, "Failed to connect to bootstrap peer");
-                    } else {
-                        bootstrap_peer_ids.push(peer_id);
+                        None
                     }
                 }
             }
+        });
+        let bootstrap_peer_ids: Vec<IrohIdentity> = futures::future::join_all(connects)
+            .await
+            .into_iter()
+            .flatten()
+            .collect();
+
+        // Join the realm's gossip topic for peer discovery
+        // This broadcasts our InterfaceJoin with PQ keys and sends IntroductionRequest
+        let discovery = transport.discovery_service();
+        if let Err(e) = discovery
+            .join_realm_topic(interface_id, bootstrap_public_keys)
+            .await
+        {
+            warn!(error = %e, "Failed to join realm gossip topic");
+        }
 
-            // Join the realm's gossip topic for peer discovery
-            // This broadcasts our InterfaceJoin with PQ keys and sends IntroductionRequest
-            let discovery = transport.discovery_service();
-            if let Err(e) = discovery
-                .join_realm_topic(interface_id, bootstrap_public_keys)
-                .await
-            {
-                warn!(error = %e, "Failed to join realm gossip topic");
-            }
-
-            // Send initial sync request ONLY to bootstrap peers we connected to (signed)
-            let state = self.interfaces.get(&interface_id).unwrap();
+        // Build initial sync requests ONLY for bootstrap peers we connected to (signed),
+        // releasing the interface lock before anything goes on the wire
+        let requests = {
+            let state = self.interfaces.get(&interface_id).ok_or_else(|| {
+                NodeError::InterfaceNotFound(hex::encode(interface_id.as_bytes()))
+            })?;
             let mut interface = state.interface.write().await;
+            let mut requests = Vec::new();
             for peer in &bootstrap_peer_ids {
                 if *peer != self.identity && transport.is_connected(peer) {
                     let sync_msg = interface.generate_sync(peer);
@@ -1249,29 +1984,34 @@ impl IndrasNode {
                     let msg = NetworkMessage::SyncRequest(request);
 
                     // Sign the message
-                    if let Ok(msg_bytes) = msg.to_bytes() {
-                        let signature = self.pq_identity.sign(&msg_bytes);
-                        let signed_msg = SignedNetworkMessage {
-                            version: SIGNED_MESSAGE_VERSION,
-                            message: msg,
-                            signature: signature.to_bytes().to_vec(),
-                            sender_verifying_key: self.pq_identity.verifying_key_bytes(),
-                        };
-                        if let Ok(bytes) = signed_msg.to_bytes() {
-                            let _ = transport.send(peer, bytes).await;
-                        }
+                    let version = self.protocols.wire_version(peer);
+                    if let Ok(signed_msg) = SignedNetworkMessage::sign(msg, &self.pq_identity, version)
+                        && let Ok(bytes) = signed_msg.to_bytes()
+                    {
+                        requests.push((*peer, bytes));
                     }
                 }
             }
-        }
+            requests
+        };
 
-        info!(interface_id = %hex::encode(interface_id.as_bytes()), "Joined interface");
-        Ok(interface_id)
+        let sends = requests.into_iter().map(|(peer, bytes)| {
+            let transport = Arc::clone(&transport);
+            async move {
+                let send = async { transport.send(&peer, bytes).await.ok() };
+                if tokio::time::timeout(self.config.request_timeout, send).await.is_err() {
+                    warn!(peer = %peer.short_id(), "Initial sync request timed out");
+                }
+            }
+        });
+        futures::future::join_all(sends).await;
+        Ok(())
     }
 
     /// Leave an interface
     ///
     /// Broadcasts a leave message and cleans up all state for this interface.
+    /// Leaving a sandbox discards it without any network traffic.
     #[instrument(skip_all)]
     pub async fn leave_interface(&self, interface_id: &InterfaceId) -> NodeResult<()> {
         // Check if we're in this interface
@@ -1280,9 +2020,10 @@ impl IndrasNode {
                 interface_id.as_bytes(),
             )));
         }
+        let sandboxed = self.sandboxes.remove(interface_id).is_some();
 
         // Leave the realm's gossip topic (broadcasts leave message)
-        if let Some(transport) = self.transport.read().await.as_ref() {
+        if !sandboxed && let Some(transport) = self.transport.read().await.as_ref() {
             let discovery = transport.discovery_service();
             if let Err(e) = discovery.leave_realm_topic(*interface_id).await {
                 warn!(error = %e, "Failed to leave realm gossip topic");
@@ -1292,6 +2033,15 @@ impl IndrasNode {
         // Remove from memory
         self.interfaces.remove(interface_id);
         self.interface_keys.remove(interface_id);
+        self.retired_keys.remove(interface_id);
+        self.causal.forget(interface_id);
+        self.dedup.forget(interface_id);
+        self.guests.forget(interface_id);
+        self.trust.forget(interface_id);
+        self.sync_recency.forget(interface_id);
+        self.budget.forget(interface_id);
+        self.jobs.forget_interface(interface_id);
+        self.archived.remove(interface_id);
 
         // Note: We don't remove from storage to allow rejoining later
         // The storage can be cleaned up separately if needed
@@ -1307,6 +2057,7 @@ impl IndrasNode {
         interface_id: &InterfaceId,
         content: Vec<u8>,
     ) -> NodeResult<EventId> {
+        self.ensure_writable(interface_id)?;
         let state = self
             .interfaces
             .get(interface_id)
@@ -1315,25 +2066,48 @@ impl IndrasNode {
         // Hold write lock only for event creation + append, then release
         // before doing any network I/O. This prevents blocking Document
         // listeners from draining their broadcast channels.
-        let (event_id, event, targets) = {
+        let (event_id, event, targets, deps) = {
             let mut interface = state.interface.write().await;
             let sequence = interface.event_count() as u64 + 1;
             let event = InterfaceEvent::message(self.identity, sequence, content.clone());
 
+            // Capture the causal frontier before this event joins it
+            let deps = self.causal.deps_for_send(interface_id);
+
             // Append to NInterface (tracks pending delivery + CRDT)
             let event_id = interface.append(event.clone()).await?;
+            self.causal.record_local(*interface_id, event_id, deps.clone());
+            self.dedup.admit(*interface_id, event_id);
 
             // Collect targets while we have the lock
             let targets = interface.members();
 
-            (event_id, event, targets)
+            (event_id, event, targets, deps)
             // write lock released here
         };
 
-        // Persist to storage (no interface lock needed)
+        // Sandboxes stay in memory: deliver locally and stop there
+        if self.is_sandbox(interface_id) {
+            let _ = state.event_tx.send(ReceivedEvent {
+                interface_id: *interface_id,
+                event,
+            });
+            debug!(event_id = ?event_id, "Message kept in sandbox");
+            return Ok(event_id);
+        }
+
+        // Persist to storage (no interface lock needed). The intent is
+        // journaled first so a crash before the append lands is completed
+        // by recovery on the next start.
+        let intent = self.storage.journal().begin(Intent::AppendEvent {
+            interface_id: *interface_id,
+            event_id,
+            payload: content.clone(),
+        })?;
         self.storage
             .append_event(interface_id, event_id, Bytes::from(content.clone()))
             .await?;
+        self.storage.journal().complete(intent)?;
 
         // Broadcast locally (no interface lock needed)
         let received = ReceivedEvent {
@@ -1341,6 +2115,8 @@ impl IndrasNode {
             event: event.clone(),
         };
         let _ = state.event_tx.send(received);
+        self.budget
+            .touch_interface(*interface_id, guest::now_millis() as i64);
 
         // Send encrypted and signed message to connected peers (no interface lock)
         if let Some(transport) = self.transport.read().await.as_ref()
@@ -1359,24 +2135,7 @@ impl IndrasNode {
                 event_id,
                 encrypted.nonce,
             );
-            let network_msg = NetworkMessage::InterfaceEvent(msg);
-
-            // Sign the message with PQ identity
-            let msg_bytes = network_msg
-                .to_bytes()
-                .map_err(|e| NodeError::Serialization(e.to_string()))?;
-            let signature = self.pq_identity.sign(&msg_bytes);
-
-            let signed_msg = SignedNetworkMessage {
-                version: SIGNED_MESSAGE_VERSION,
-                message: network_msg,
-                signature: signature.to_bytes().to_vec(),
-                sender_verifying_key: self.pq_identity.verifying_key_bytes(),
-            };
-
-            let bytes = signed_msg
-                .to_bytes()
-                .map_err(|e| NodeError::Serialization(e.to_string()))?;
+            let network_msg = NetworkMessage::interface_event(msg, deps);
 
             // Also include gossip-discovered peers
             let mut all_targets = targets;
@@ -1384,6 +2143,10 @@ impl IndrasNode {
                 all_targets.insert(peer_info.peer_id);
             }
 
+            // Guests whose access has lapsed no longer receive new events
+            let now = guest::now_millis();
+            all_targets.retain(|m| self.guests.status(interface_id, m, now) != GuestStatus::Expired);
+
             let connected_count: usize = all_targets
                 .iter()
                 .filter(|m| **m != self.identity && transport.is_connected(m))
@@ -1395,31 +2158,80 @@ impl IndrasNode {
                 "send_message dispatch"
             );
 
+            // Sign with PQ identity once per wire encoding the targets speak
+            let mut encoded: HashMap<u16, Vec<u8>> = HashMap::new();
+            let mut encode_for = |member: &IrohIdentity| -> NodeResult<Vec<u8>> {
+                let version = self.protocols.wire_version(member);
+                if let Some(bytes) = encoded.get(&version) {
+                    return Ok(bytes.clone());
+                }
+                let signed_msg =
+                    SignedNetworkMessage::sign(network_msg.clone(), &self.pq_identity, version)
+                        .map_err(|e| NodeError::Serialization(e.to_string()))?;
+                let bytes = signed_msg
+                    .to_bytes()
+                    .map_err(|e| NodeError::Serialization(e.to_string()))?;
+                encoded.insert(version, bytes.clone());
+                Ok(bytes)
+            };
             let mut sent_count = 0u32;
+            let mut unconnected = Vec::new();
             for member in &all_targets {
-                if *member != self.identity && transport.is_connected(member) {
-                    if let Err(e) = transport.send(member, bytes.clone()).await {
-                        // Eager retry: spawn a single delayed retry (500ms) for
-                        // this peer before falling back to the 5-second sync interval.
-                        let transport_retry = transport.clone();
-                        let peer = *member;
-                        let retry_bytes = bytes.clone();
-                        debug!(
-                            peer = %peer.short_id(),
-                            error = %e,
-                            "Send failed, scheduling eager retry in 500ms"
-                        );
-                        tokio::spawn(async move {
-                            tokio::time::sleep(Duration::from_millis(500)).await;
-                            if transport_retry.is_connected(&peer) {
-                                let _ = transport_retry.send(&peer, retry_bytes).await;
-                            }
-                        });
-                    } else {
-                        sent_count += 1;
+                if *member == self.identity {
+                    continue;
+                }
+                if !transport.is_connected(member) {
+                    unconnected.push(*member);
+                    continue;
+                }
+                let bytes = encode_for(member)?;
+                if let Err(e) = transport.send(member, bytes.clone()).await {
+                    // Eager retry: spawn a single delayed retry (500ms) for
+                    // this peer before falling back to the 5-second sync interval.
+                    let transport_retry = transport.clone();
+                    let peer = *member;
+                    let retry_bytes = bytes;
+                    debug!(
+                        peer = %peer.short_id(),
+                        error = %e,
+                        "Send failed, scheduling eager retry in 500ms"
+                    );
+                    tokio::spawn(async move {
+                        tokio::time::sleep(Duration::from_millis(500)).await;
+                        if transport_retry.is_connected(&peer) {
+                            let _ = transport_retry.send(&peer, retry_bytes).await;
+                        }
+                    });
+                } else {
+                    sent_count += 1;
+                    self.budget.touch(*interface_id, *member, now as i64);
+                }
+            }
+
+            // Dial unconnected members on demand, up to the interface's share
+            // of the connection budget; pinned peers are always dialled
+            if self.budget.config().dial_on_demand {
+                let mut room = self
+                    .budget
+                    .config()
+                    .max_per_interface
+                    .saturating_sub(connected_count);
+                let mut open = transport.connection_manager().connected_peers().len();
+                for member in unconnected {
+                    let pinned = self.budget.is_pinned(&member);
+                    if (room == 0 && !pinned) || !self.budget.may_dial(&member, open) {
+                        continue;
+                    }
+                    let bytes = encode_for(&member)?;
+                    if !self.budget.begin_dial(member) {
+                        continue;
                     }
+                    room = room.saturating_sub(1);
+                    open += 1;
+                    self.dial_and_send(transport, *interface_id, member, bytes);
                 }
             }
+
             debug!(
                 event_id = ?event_id,
                 targets = all_targets.len(),
@@ -1432,6 +2244,365 @@ impl IndrasNode {
         Ok(event_id)
     }
 
+    /// Opt an interface in or out of causal delivery
+    ///
+    /// When enabled, outgoing messages carry the events they causally depend
+    /// on and receivers hold them back until those events are delivered.
+    /// This adds latency when dependencies arrive out of order, so it is off
+    /// by default. The setting is local and not persisted.
+    pub fn set_causal_delivery(&self, interface_id: &InterfaceId, enabled: bool) {
+        self.causal.set_enabled(*interface_id, enabled);
+    }
+
+    /// Whether causal delivery is enabled for an interface
+    pub fn causal_delivery_enabled(&self, interface_id: &InterfaceId) -> bool {
+        self.causal.is_enabled(interface_id)
+    }
+
+    /// Number of duplicate incoming events dropped since the node started
+    pub fn duplicates_suppressed(&self) -> u64 {
+        self.dedup.suppressed()
+    }
+
+    /// Admit a peer to an interface as a read-only guest until `expires_at_millis`
+    ///
+    /// Events and sync state sent by the guest are dropped on this node, and
+    /// once access lapses the guest is no longer synced to. The registry is
+    /// local and not persisted.
+    pub fn grant_guest(&self, interface_id: &InterfaceId, peer: IrohIdentity, expires_at_millis: u64) {
+        self.guests.grant(*interface_id, peer, expires_at_millis);
+    }
+
+    /// Remove a peer from an interface's guest list
+    pub fn revoke_guest(&self, interface_id: &InterfaceId, peer: &IrohIdentity) -> bool {
+        self.guests.revoke(interface_id, peer)
+    }
+
+    /// Classify a peer against an interface's guest list
+    pub fn guest_status(&self, interface_id: &InterfaceId, peer: &IrohIdentity) -> GuestStatus {
+        self.guests.status(interface_id, peer, guest::now_millis())
+    }
+
+    /// Protocol negotiated with a peer, once its hello has arrived
+    ///
+    /// `None` before the handshake, for peers running builds without it,
+    /// and for peers whose protocol is incompatible with ours.
+    pub fn peer_protocol(&self, peer: &IrohIdentity) -> Option<PeerProtocol> {
+        self.protocols.get(peer)
+    }
+
+    /// Whether an optional protocol feature may be used with a peer
+    pub fn peer_supports(&self, peer: &IrohIdentity, feature: Capabilities) -> bool {
+        self.protocols.supports(peer, feature)
+    }
+
+    /// Pin an interface to an organization root key
+    ///
+    /// Signed messages in the interface are then checked for a
+    /// [`MemberCertificate`] from the root: unverified senders are flagged
+    /// or dropped per `policy`. The setting is local and not persisted.
+    pub fn set_trust_root(
+        &self,
+        interface_id: &InterfaceId,
+        root_vk: PQPublicIdentity,
+        policy: TrustPolicy,
+    ) {
+        self.trust.set_root(*interface_id, root_vk, policy);
+    }
+
+    /// Remove an interface's organization root. Returns `true` if one was set.
+    pub fn clear_trust_root(&self, interface_id: &InterfaceId) -> bool {
+        self.trust.clear_root(interface_id)
+    }
+
+    /// The organization id and policy an interface is pinned to
+    pub fn trust_root(&self, interface_id: &InterfaceId) -> Option<([u8; 32], TrustPolicy)> {
+        self.trust.root(interface_id)
+    }
+
+    /// Install this node's certificate from an organization
+    ///
+    /// It is shared with peers after the handshake so they can verify us.
+    pub fn set_member_certificate(&self, certificate: MemberCertificate) {
+        self.trust.set_own_certificate(certificate);
+    }
+
+    /// Remember another member's certificate, e.g. one distributed out of band
+    pub fn add_member_certificate(&self, certificate: MemberCertificate) {
+        self.trust.add_certificate(certificate);
+    }
+
+    /// Check a member's verifying key against an interface's trust root
+    pub fn member_trust(&self, interface_id: &InterfaceId, member_vk: &[u8]) -> MemberTrust {
+        self.trust.check(interface_id, member_vk, guest::now_millis() as i64)
+    }
+
+    /// User ids of unverified senders seen in an interface since its root was set
+    pub fn unverified_members(&self, interface_id: &InterfaceId) -> Vec<[u8; 32]> {
+        self.trust.unverified_members(interface_id)
+    }
+
+    /// Let `delegate` exercise `capabilities` on an interface for `ttl`
+    ///
+    /// The returned token is signed with this node's PQ identity; hand it to
+    /// the delegate, who attaches it to each [`DelegatedRequest`]. Tokens are
+    /// only honored while registered here, so a restart voids them.
+    pub async fn issue_delegation(
+        &self,
+        delegate: IrohIdentity,
+        interface_id: &InterfaceId,
+        capabilities: impl IntoIterator<Item = Capability>,
+        ttl: Duration,
+    ) -> NodeResult<DelegationToken> {
+        if !self.interfaces.contains_key(interface_id) {
+            return Err(NodeError::InterfaceNotFound(hex::encode(interface_id.as_bytes())));
+        }
+        if self.is_sandbox(interface_id) {
+            return Err(NodeError::Sandboxed(hex::encode(interface_id.as_bytes())));
+        }
+
+        let now = guest::now_millis();
+        let token = DelegationToken::issue(
+            &self.pq_identity,
+            delegate,
+            *interface_id,
+            capabilities.into_iter().collect(),
+            now,
+            now.saturating_add(ttl.as_millis() as u64),
+        );
+        self.delegations.register(token.clone());
+        let _ = self.node_log.append(NodeEvent::DelegationIssued {
+            token_id: token.id,
+            interface_id: *interface_id,
+            delegate: delegate.as_bytes().to_vec(),
+            expires_at_millis: token.expires_at_millis,
+        }).await;
+        Ok(token)
+    }
+
+    /// Revoke a delegation token before it expires
+    pub async fn revoke_delegation(&self, token_id: &DelegationId) -> bool {
+        let revoked = self.delegations.revoke(token_id);
+        if revoked {
+            let _ = self.node_log.append(NodeEvent::DelegationRevoked {
+                token_id: *token_id,
+            }).await;
+        }
+        revoked
+    }
+
+    /// Delegation tokens that are still usable
+    pub fn delegations(&self) -> Vec<DelegationToken> {
+        self.delegations.active(guest::now_millis())
+    }
+
+    /// The latest delegated requests handled since start, allowed or refused
+    ///
+    /// Keeps the last [`DEFAULT_AUDIT_CAPACITY`] entries; throttled
+    /// unknown-token refusals are counted on the sender's next entry. The
+    /// node log keeps the same entries across restarts.
+    pub fn delegation_audit(&self) -> Vec<AuditEntry> {
+        self.delegations.audit()
+    }
+
+    /// Subscribe to delegated actions that passed authorization
+    ///
+    /// The node only authorizes; the application layer carries the actions out.
+    pub fn delegated_actions(&self) -> broadcast::Receiver<AuthorizedAction> {
+        self.delegations.subscribe()
+    }
+
+    /// Ask `owner`'s node to perform an action under a delegation token
+    pub async fn send_delegated_request(
+        &self,
+        owner: &IrohIdentity,
+        request: DelegatedRequest,
+    ) -> NodeResult<()> {
+        let transport = self.transport.read().await.clone().ok_or(NodeError::NotStarted)?;
+
+        let network_msg = NetworkMessage::Delegated(request);
+        let signed_msg = SignedNetworkMessage::sign(
+            network_msg,
+            &self.pq_identity,
+            self.protocols.wire_version(owner),
+        )
+        .map_err(|e| NodeError::Serialization(e.to_string()))?;
+        let bytes = signed_msg
+            .to_bytes()
+            .map_err(|e| NodeError::Serialization(e.to_string()))?;
+
+        bounded("delegated request", self.config.request_timeout, async {
+            transport
+                .send(owner, bytes)
+                .await
+                .map_err(|e| NodeError::transport_to(hex::encode(owner.as_bytes()), &e))
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Set the anonymity ring for an interface
+    ///
+    /// Anonymous posts are accepted only if signed over keys in this ring.
+    /// The ring is local and not persisted; an empty ring disables
+    /// anonymous posting in the interface.
+    pub fn set_anonymity_ring(
+        &self,
+        interface_id: &InterfaceId,
+        ring: impl IntoIterator<Item = RingPublicKey>,
+    ) {
+        self.anonymity_rings.set(*interface_id, ring);
+    }
+
+    /// This node's ring public key for an interface
+    ///
+    /// Publish it to the other members so they include it in the ring.
+    pub fn anonymity_ring_key(&self, interface_id: &InterfaceId) -> RingPublicKey {
+        anonymous::ring_keypair(&self.pq_identity, interface_id).public_key()
+    }
+
+    /// Post `content` to an interface without revealing this node as the author
+    ///
+    /// The event's sender is the interface's shared [`anonymous_sender`] and
+    /// its payload is an [`AnonymousPost`] ring-signed over the interface's
+    /// anonymity ring, which must contain this node's
+    /// [`anonymity_ring_key`](Self::anonymity_ring_key) and at least one
+    /// other key. The envelope is not PQ-signed.
+    ///
+    /// The post goes to one connected member, picked at random, which
+    /// relays it to the rest; that member sees which connection it came
+    /// from. Fails if no connected member supports relaying. See
+    /// [`anonymous`] for the limits.
+    pub async fn send_anonymous(
+        &self,
+        interface_id: &InterfaceId,
+        content: Vec<u8>,
+    ) -> NodeResult<EventId> {
+        self.ensure_writable(interface_id)?;
+        let state = self
+            .interfaces
+            .get(interface_id)
+            .ok_or_else(|| NodeError::InterfaceNotFound(hex::encode(interface_id.as_bytes())))?;
+
+        let ring = self.anonymity_rings.get(interface_id);
+        if ring.len() < anonymous::MIN_RING_SIZE {
+            return Err(NodeError::Config(format!(
+                "anonymity ring needs at least {} members",
+                anonymous::MIN_RING_SIZE
+            )));
+        }
+        let relay = if self.is_sandbox(interface_id) {
+            None
+        } else {
+            self.anonymous_relay(interface_id).await?
+        };
+
+        // A per-node counter would link posts, so sequences are random
+        let sequence = rand::random::<u64>();
+        let keypair = anonymous::ring_keypair(&self.pq_identity, interface_id);
+        let post = AnonymousPost::sign(&keypair, interface_id, sequence, content, ring)
+            .map_err(|e| NodeError::Crypto(e.to_string()))?;
+        let payload = post
+            .to_bytes()
+            .map_err(|e| NodeError::Serialization(e.to_string()))?;
+        let event = InterfaceEvent::custom(
+            anonymous_sender(interface_id),
+            sequence,
+            ANONYMOUS_EVENT_TYPE.to_string(),
+            payload.clone(),
+        );
+
+        let event_id = state.interface.write().await.append(event.clone()).await?;
+        self.causal.record_received(*interface_id, event_id);
+        self.dedup.admit(*interface_id, event_id);
+
+        if self.is_sandbox(interface_id) {
+            let _ = state.event_tx.send(ReceivedEvent {
+                interface_id: *interface_id,
+                event,
+            });
+            return Ok(event_id);
+        }
+
+        let intent = self.storage.journal().begin(Intent::AppendEvent {
+            interface_id: *interface_id,
+            event_id,
+            payload: payload.clone(),
+        })?;
+        self.storage
+            .append_event(interface_id, event_id, Bytes::from(payload))
+            .await?;
+        self.storage.journal().complete(intent)?;
+
+        let _ = state.event_tx.send(ReceivedEvent {
+            interface_id: *interface_id,
+            event: event.clone(),
+        });
+
+        if let Some(relay) = relay
+            && let Some(transport) = self.transport.read().await.as_ref()
+            && let Some(key) = self.interface_keys.get(interface_id)
+        {
+            let plaintext = postcard::to_allocvec(&event)
+                .map_err(|e| NodeError::Serialization(e.to_string()))?;
+            let encrypted = key
+                .encrypt(&plaintext)
+                .map_err(|e| NodeError::Crypto(e.to_string()))?;
+            let msg = InterfaceEventMessage::new(
+                *interface_id,
+                encrypted.ciphertext,
+                event_id,
+                encrypted.nonce,
+            );
+            let bytes = NetworkMessage::AnonymousRelay(msg)
+                .encode(self.protocols.wire_version(&relay))
+                .map_err(|e| NodeError::Serialization(e.to_string()))?;
+            transport
+                .send(&relay, bytes)
+                .await
+                .map_err(|e| NodeError::transport_to(hex::encode(relay.as_bytes()), &e))?;
+        }
+
+        debug!(event_id = ?event_id, "Anonymous post sent");
+        Ok(event_id)
+    }
+
+    /// Pick the member that relays this node's next anonymous post
+    ///
+    /// Sending the post to every member would show each of them the
+    /// author's connection, so it goes to one connected member, chosen at
+    /// random among those that relay. `None` before the transport starts.
+    async fn anonymous_relay(&self, interface_id: &InterfaceId) -> NodeResult<Option<IrohIdentity>> {
+        let Some(transport) = self.transport.read().await.clone() else {
+            return Ok(None);
+        };
+        let mut members = match self.interfaces.get(interface_id) {
+            Some(state) => state.interface.read().await.members(),
+            None => {
+                return Err(NodeError::InterfaceNotFound(hex::encode(interface_id.as_bytes())));
+            }
+        };
+        for peer_info in transport.discovery_service().realm_members(interface_id) {
+            members.insert(peer_info.peer_id);
+        }
+        let now = guest::now_millis();
+        let relays: Vec<_> = members
+            .into_iter()
+            .filter(|m| {
+                *m != self.identity
+                    && transport.is_connected(m)
+                    && self.guests.status(interface_id, m, now) != GuestStatus::Expired
+                    && self.protocols.supports(m, Capabilities::ANONYMOUS_RELAY)
+            })
+            .collect();
+        if relays.is_empty() {
+            return Err(NodeError::Config(
+                "no connected member can relay anonymous posts".to_string(),
+            ));
+        }
+        Ok(Some(relays[rand::random_range(0..relays.len())]))
+    }
+
     /// Subscribe to events from an interface
     ///
     /// Returns a broadcast receiver that will receive all events.
@@ -1495,10 +2666,40 @@ impl IndrasNode {
         let interface = state.interface.read().await;
         let doc = interface
             .document()
-            .map_err(|e| NodeError::Sync(format!("Document lock: {}", e)))?;
+            .map_err(|e| NodeError::sync_in(interface_id, &e))?;
         Ok(doc.events())
     }
 
+    /// Capture a consistent point-in-time snapshot of an interface's document.
+    ///
+    /// Events, members, and heads are read under one lock, so they describe
+    /// the same document state. Later sync merges do not affect the snapshot.
+    pub async fn document_snapshot(
+        &self,
+        interface_id: &InterfaceId,
+    ) -> NodeResult<InterfaceSnapshot> {
+        let state = self
+            .interfaces
+            .get(interface_id)
+            .ok_or_else(|| NodeError::InterfaceNotFound(hex::encode(interface_id.as_bytes())))?;
+
+        let interface = state.interface.read().await;
+        let mut doc = interface
+            .document_mut()
+            .map_err(|e| NodeError::sync_in(interface_id, &e))?;
+
+        let heads = doc.get_heads().into_iter().map(|h| h.0).collect();
+        let mut members: Vec<IrohIdentity> = doc.members().into_iter().collect();
+        members.sort_by_key(|m| m.as_bytes());
+
+        Ok(InterfaceSnapshot {
+            interface_id: *interface_id,
+            events: doc.events(),
+            members,
+            heads,
+        })
+    }
+
     /// Get all members of an interface
     ///
     /// Returns members from both the CRDT state and discovered peers via gossip.
@@ -1553,6 +2754,85 @@ impl IndrasNode {
         guard.as_ref().map(|t| t.discovery_service().subscribe())
     }
 
+    /// Snapshot the mesh as this node sees it
+    ///
+    /// Includes every peer known from discovery, open connections, or
+    /// interface membership, plus each interface we belong to and when we
+    /// last synced with each of its members. Export the result with
+    /// [`TopologySnapshot::to_json`] or [`TopologySnapshot::to_dot`].
+    pub async fn topology_snapshot(&self) -> TopologySnapshot {
+        let mut snapshot =
+            TopologySnapshot::new(&self.identity, guest::now_millis() as i64);
+
+        if let Some(transport) = self.transport.read().await.as_ref() {
+            for info in transport.known_peers() {
+                snapshot.observe_peer(
+                    &info.identity,
+                    ConnectionState::Discovered,
+                    Some(info.last_seen_millis),
+                );
+            }
+            for peer in transport.connection_manager().connected_peers() {
+                snapshot.observe_peer(&peer, ConnectionState::Connected, None);
+            }
+        }
+
+        for interface_id in self.list_interfaces() {
+            let members = self.members(&interface_id).await.unwrap_or_default();
+            snapshot.add_realm(
+                &interface_id,
+                &members,
+                self.is_sandbox(&interface_id),
+                &self.sync_recency,
+            );
+        }
+
+        snapshot
+    }
+
+    /// How up to date each member of an interface is
+    ///
+    /// Reports, per member other than us, whether we are connected, when
+    /// we last exchanged sync state, the smoothed round-trip time measured
+    /// by the sync task, and how many of our events they have not yet
+    /// acknowledged.
+    pub async fn member_sync_status(
+        &self,
+        interface_id: &InterfaceId,
+    ) -> NodeResult<Vec<MemberSyncStatus>> {
+        let state = self
+            .interfaces
+            .get(interface_id)
+            .ok_or_else(|| NodeError::InterfaceNotFound(hex::encode(interface_id.as_bytes())))?;
+
+        let members: Vec<(IrohIdentity, usize)> = {
+            let interface = state.interface.read().await;
+            interface
+                .members()
+                .into_iter()
+                .filter(|member| *member != self.identity)
+                .map(|member| {
+                    let pending = interface.pending_for(&member).len();
+                    (member, pending)
+                })
+                .collect()
+        };
+
+        let transport = self.transport.read().await;
+        Ok(members
+            .into_iter()
+            .map(|(peer, pending_events)| MemberSyncStatus {
+                peer,
+                connected: transport
+                    .as_ref()
+                    .is_some_and(|t| t.connection_manager().is_connected(&peer)),
+                last_sync_millis: self.sync_recency.last_sync(interface_id, &peer),
+                rtt: self.latency.rtt(&peer),
+                pending_events,
+            })
+            .collect())
+    }
+
     /// Add a member to an interface
     pub async fn add_member(
         &self,
@@ -1567,7 +2847,7 @@ impl IndrasNode {
         let mut interface = state.interface.write().await;
         interface
             .add_member(peer)
-            .map_err(|e| NodeError::Sync(e.to_string()))?;
+            .map_err(|e| NodeError::sync_in(interface_id, &e))?;
 
         // Persist
         self.storage.register_peer(&peer, None)?;
@@ -1582,11 +2862,56 @@ impl IndrasNode {
         Ok(())
     }
 
+    /// Remove a member from an interface
+    ///
+    /// Returns `false` if the peer was not a member. Events still queued
+    /// for the peer are dropped. The interface key stays the same.
+    pub async fn remove_member(
+        &self,
+        interface_id: &InterfaceId,
+        peer: &IrohIdentity,
+    ) -> NodeResult<bool> {
+        let state = self
+            .interfaces
+            .get(interface_id)
+            .ok_or_else(|| NodeError::InterfaceNotFound(hex::encode(interface_id.as_bytes())))?;
+
+        let mut interface = state.interface.write().await;
+        let was_member = interface.members().contains(peer);
+        if was_member {
+            interface
+                .remove_member(peer)
+                .map_err(|e| NodeError::sync_in(interface_id, &e))?;
+        }
+        let stored = self.storage.interface_store().remove_member(interface_id, peer)?;
+        let removed = was_member || stored;
+
+        if removed {
+            info!(peer = %peer.short_id(), "Member removed");
+        }
+        Ok(removed)
+    }
+
     /// Get the storage backend (for advanced operations)
     pub fn storage(&self) -> &CompositeStorage<IrohIdentity> {
         &self.storage
     }
 
+    /// Get the job scheduler for registering periodic background work
+    ///
+    /// Jobs run while the node is started, node-wide or once per loaded
+    /// interface; see [`jobs`] for scheduling and shutdown behaviour.
+    pub fn jobs(&self) -> &JobScheduler {
+        &self.jobs
+    }
+
+    /// Get the connection budget, e.g. to pin contacts
+    ///
+    /// See [`connection_budget`] for how connections are capped and reaped.
+    pub fn connection_budget(&self) -> &Arc<ConnectionBudget> {
+        &self.budget
+    }
+
     /// Get the delivery tracker for querying message delivery status
     ///
     /// Provides a unified view of delivery state across sync and DTN paths.
@@ -1599,6 +2924,18 @@ impl IndrasNode {
         self.interfaces.iter().map(|entry| *entry.key()).collect()
     }
 
+    /// List all loaded interfaces with their archived and sandbox state
+    pub fn list_interface_summaries(&self) -> Vec<InterfaceSummary> {
+        self.interfaces
+            .iter()
+            .map(|entry| InterfaceSummary {
+                interface_id: *entry.key(),
+                archived: self.is_archived(entry.key()),
+                sandbox: self.is_sandbox(entry.key()),
+            })
+            .collect()
+    }
+
     /// Get interface key (for testing/advanced use)
     pub fn interface_key(&self, interface_id: &InterfaceId) -> Option<InterfaceKey> {
         self.interface_keys
@@ -1610,11 +2947,62 @@ impl IndrasNode {
     pub fn set_interface_key(&self, interface_id: InterfaceId, key: InterfaceKey) {
         self.interface_keys.insert(interface_id, key);
     }
+
+    /// Replace an interface's key with a new one
+    ///
+    /// New events are encrypted with `key` and the stored record is
+    /// updated. The old key is kept in memory, with up to
+    /// [`MAX_RETIRED_KEYS`] before it, so events members sent before they
+    /// picked up the new key still decrypt. Returns `false` if `key` already
+    /// is the interface's key.
+    pub fn rotate_interface_key(
+        &self,
+        interface_id: &InterfaceId,
+        key: InterfaceKey,
+    ) -> NodeResult<bool> {
+        if !self.interfaces.contains_key(interface_id) {
+            return Err(NodeError::InterfaceNotFound(hex::encode(interface_id.as_bytes())));
+        }
+        if !self.is_sandbox(interface_id)
+            && let Some(mut record) = self.storage.interface_store().get(interface_id)?
+        {
+            record.encrypted = true;
+            record.encrypted_key = Some(key.as_bytes().to_vec());
+            self.storage.interface_store().upsert(&record)?;
+        }
+        let Some(old) = self.interface_keys.insert(*interface_id, key.clone()) else {
+            return Ok(true);
+        };
+        if old.as_bytes() == key.as_bytes() {
+            return Ok(false);
+        }
+        let mut retired = self.retired_keys.entry(*interface_id).or_default();
+        retired.retain(|k| k.as_bytes() != key.as_bytes());
+        retired.insert(0, old);
+        retired.truncate(MAX_RETIRED_KEYS);
+        info!(interface_id = %hex::encode(interface_id.as_bytes()), "Interface key rotated");
+        Ok(true)
+    }
+}
+
+/// Run `fut`, failing with [`NodeError::Timeout`] if it takes longer than `after`
+///
+/// Dropping `fut` on timeout must leave no partial state behind, so only
+/// wrap operations that are safe to cancel (connects, sends, topic joins).
+async fn bounded<T>(
+    operation: &'static str,
+    after: Duration,
+    fut: impl Future<Output = NodeResult<T>>,
+) -> NodeResult<T> {
+    tokio::time::timeout(after, fut)
+        .await
+        .map_err(|_| NodeError::Timeout { operation, after })?
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use indras_core::error_code::ErrorInfo;
     use tempfile::TempDir;
 
     async fn create_test_node() -> (IndrasNode, TempDir) {
@@ -1651,6 +3039,118 @@ mod tests {
         assert_eq!(identity1, identity2);
     }
 
+    #[tokio::test]
+    async fn test_ephemeral_node() {
+        let node = IndrasNode::new(NodeConfig::ephemeral()).await.unwrap();
+        let other = IndrasNode::new(NodeConfig::ephemeral()).await.unwrap();
+        assert_ne!(node.identity(), other.identity());
+        assert!(node.config.storage.is_in_memory());
+
+        let (interface_id, _) = node.create_interface(Some("Scratch")).await.unwrap();
+        node.send_message(&interface_id, b"volatile".to_vec())
+            .await
+            .unwrap();
+        assert_eq!(node.events_since(&interface_id, 0).await.unwrap().len(), 1);
+
+        node.start().await.unwrap();
+        assert!(node.relay_service.get().is_none());
+        node.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_backup_restores_identity_and_interfaces() {
+        let data_dir = TempDir::new().unwrap();
+        let backup_dir = TempDir::new().unwrap();
+        let restore_dir = TempDir::new().unwrap();
+        let (identity, interface_id, interface_key) = {
+            let node = IndrasNode::new(NodeConfig::with_data_dir(data_dir.path()))
+                .await
+                .unwrap();
+            let interface_id = node.create_interface(Some("Kept")).await.unwrap().0;
+            node.send_message(&interface_id, b"kept".to_vec()).await.unwrap();
+            let summary = node.backup(backup_dir.path()).await.unwrap();
+            assert!(summary.chunks_written > 0);
+            assert!(summary.keys_skipped.contains(&"identity.key".to_string()));
+            assert!(
+                summary
+                    .keys_skipped
+                    .contains(&format!("interfaces/{}", hex::encode(interface_id.as_bytes())))
+            );
+            let key = node.interface_key(&interface_id).unwrap().as_bytes().to_vec();
+            (*node.identity(), interface_id, key)
+        };
+
+        // No object holds the interface key
+        let mut dirs = vec![backup_dir.path().to_path_buf()];
+        while let Some(dir) = dirs.pop() {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    dirs.push(path);
+                } else {
+                    let bytes = std::fs::read(&path).unwrap();
+                    assert!(!bytes.windows(32).any(|w| w == interface_key.as_slice()));
+                }
+            }
+        }
+
+        assert!(verify_backup(backup_dir.path(), None).await.unwrap().is_ok());
+        let restored = restore_backup(backup_dir.path(), None, restore_dir.path())
+            .await
+            .unwrap();
+        assert!(restored.structured_entries > 0);
+        assert!(!restore_dir.path().join("identity.key").exists());
+
+        // Plaintext keys are copied by hand
+        for name in keystore::PLAINTEXT_KEY_FILES {
+            let from = data_dir.path().join(name);
+            if from.exists() {
+                std::fs::copy(from, restore_dir.path().join(name)).unwrap();
+            }
+        }
+        let node = IndrasNode::new(NodeConfig::with_data_dir(restore_dir.path()))
+            .await
+            .unwrap();
+        assert_eq!(*node.identity(), identity);
+        let record = node.storage.interface_store().get(&interface_id).unwrap().unwrap();
+        assert_eq!(record.encrypted_key, None);
+        assert_eq!(node.storage.events_since(&interface_id, 0).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_restart_completes_journaled_send() {
+        let temp_dir = TempDir::new().unwrap();
+        let interface_id;
+        {
+            let node = IndrasNode::new(NodeConfig::with_data_dir(temp_dir.path()))
+                .await
+                .unwrap();
+            interface_id = node.create_interface(None).await.unwrap().0;
+            node.send_message(&interface_id, b"landed".to_vec())
+                .await
+                .unwrap();
+            assert!(node.storage.journal().pending().unwrap().is_empty());
+
+            // Simulate a crash between journaling and the storage append
+            node.storage
+                .journal()
+                .begin(Intent::AppendEvent {
+                    interface_id,
+                    event_id: EventId::new(7, 2),
+                    payload: b"in flight".to_vec(),
+                })
+                .unwrap();
+        }
+
+        let node = IndrasNode::new(NodeConfig::with_data_dir(temp_dir.path()))
+            .await
+            .unwrap();
+        assert!(node.storage.journal().pending().unwrap().is_empty());
+        let stored = node.storage.events_since(&interface_id, 0).await.unwrap();
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[1].payload, Bytes::from_static(b"in flight"));
+    }
+
     #[tokio::test]
     async fn test_start_stop() {
         let (node, _temp) = create_test_node().await;
@@ -1707,6 +3207,23 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_archived_interface_rejects_sends() {
+        let (node, _temp) = create_test_node().await;
+        let (interface_id, _) = node.create_interface(None).await.unwrap();
+
+        node.set_archived(&interface_id, true);
+        assert!(matches!(
+            node.send_message(&interface_id, b"late".to_vec()).await,
+            Err(NodeError::Archived(_))
+        ));
+        let summaries = node.list_interface_summaries();
+        assert!(summaries.iter().any(|s| s.interface_id == interface_id && s.archived));
+
+        node.set_archived(&interface_id, false);
+        assert!(node.send_message(&interface_id, b"back".to_vec()).await.is_ok());
+    }
+
     #[tokio::test]
     async fn test_events_since() {
         let (node, _temp) = create_test_node().await;
@@ -1738,6 +3255,20 @@ mod tests {
         assert_eq!(members[0], *node.identity());
     }
 
+    #[tokio::test]
+    async fn test_remove_member() {
+        let (node, _temp) = create_test_node().await;
+        let (other, _other_temp) = create_test_node().await;
+        let (interface_id, _) = node.create_interface(None).await.unwrap();
+
+        node.add_member(&interface_id, *other.identity()).await.unwrap();
+        assert_eq!(node.members(&interface_id).await.unwrap().len(), 2);
+
+        assert!(node.remove_member(&interface_id, other.identity()).await.unwrap());
+        assert!(!node.remove_member(&interface_id, other.identity()).await.unwrap());
+        assert_eq!(node.members(&interface_id).await.unwrap(), vec![*node.identity()]);
+    }
+
     #[tokio::test]
     async fn test_join_interface() {
         let (node, _temp) = create_test_node().await;
@@ -1748,6 +3279,70 @@ mod tests {
         assert_eq!(joined_id, interface_id);
     }
 
+    #[tokio::test]
+    async fn test_bounded_times_out() {
+        let after = Duration::from_millis(20);
+        let err = bounded("stalled peer", after, std::future::pending::<NodeResult<()>>())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            NodeError::Timeout { operation: "stalled peer", .. }
+        ));
+        assert!(err.is_retryable());
+
+        assert_eq!(bounded("ready", after, async { Ok(7) }).await.unwrap(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_connection_budget_pins() {
+        let temp_dir = TempDir::new().unwrap();
+        let budget = BudgetConfig {
+            max_per_interface: 2,
+            ..BudgetConfig::default()
+        };
+        let config = NodeConfig::with_data_dir(temp_dir.path()).with_connection_budget(budget);
+        let node = IndrasNode::new(config).await.unwrap();
+        assert_eq!(node.connection_budget().config().max_per_interface, 2);
+
+        let contact = IrohIdentity::new(iroh::SecretKey::generate(&mut rand::rng()).public());
+        node.connection_budget().pin(contact);
+        assert!(node.connection_budget().is_pinned(&contact));
+        assert!(matches!(
+            node.reap_connections().await,
+            Err(NodeError::NotStarted)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_health_before_start() {
+        let temp_dir = TempDir::new().unwrap();
+        let node = IndrasNode::new(NodeConfig::with_data_dir(temp_dir.path())).await.unwrap();
+
+        let health = node.health().await;
+        assert!(!health.started);
+        assert!(!health.transport_up);
+        assert!(health.tasks.is_empty());
+        assert!(!health.is_healthy());
+    }
+
+    #[tokio::test]
+    async fn test_join_commits_locally_before_bootstrap() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = NodeConfig::with_data_dir(temp_dir.path())
+            .with_connect_timeout(Duration::from_millis(50))
+            .with_request_timeout(Duration::from_millis(50));
+        let node = IndrasNode::new(config).await.unwrap();
+
+        // An unreachable bootstrap peer must not block the join
+        let invite = InviteKey::new(InterfaceId::generate()).with_bootstrap(vec![0xff; 8]);
+        let interface_id = node
+            .join_interface_with_timeout(invite, Duration::from_millis(50))
+            .await
+            .unwrap();
+        assert!(node.interfaces.contains_key(&interface_id));
+    }
+
     #[tokio::test]
     async fn test_invite_key_serialization() {
         let interface_id = InterfaceId::generate();
@@ -1815,4 +3410,61 @@ mod tests {
 
         node2.stop().await.unwrap();
     }
+
+    #[tokio::test]
+    async fn test_startup_defers_hydration() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let node1 = IndrasNode::new(NodeConfig::with_data_dir(temp_dir.path()))
+            .await
+            .unwrap();
+        let mut ids = Vec::new();
+        for i in 0..120 {
+            let (id, _) = node1.create_interface(Some(&format!("Realm {i}"))).await.unwrap();
+            ids.push(id);
+        }
+        drop(node1);
+
+        let node2 = IndrasNode::new(NodeConfig::with_data_dir(temp_dir.path()))
+            .await
+            .unwrap();
+        assert!(node2.startup_metrics().is_none());
+        node2.start().await.unwrap();
+
+        let metrics = node2.startup_metrics().unwrap();
+        assert_eq!(metrics.interfaces_loaded, 120);
+        assert!(metrics.total >= metrics.storage);
+
+        // Nothing touched yet, so no Automerge documents were built
+        let state = node2.interfaces.get(&ids[0]).unwrap();
+        assert!(!state.interface.read().await.is_hydrated());
+        drop(state);
+
+        // First use hydrates
+        node2.document_snapshot(&ids[0]).await.unwrap();
+        let state = node2.interfaces.get(&ids[0]).unwrap();
+        assert!(state.interface.read().await.is_hydrated());
+        drop(state);
+
+        node2.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rotate_interface_key() {
+        let temp = TempDir::new().unwrap();
+        let node = IndrasNode::new(NodeConfig::with_data_dir(temp.path())).await.unwrap();
+        let interface_id = node.create_interface(Some("Rotating")).await.unwrap().0;
+        let old = node.interface_key(&interface_id).unwrap();
+
+        let new = InterfaceKey::generate(interface_id);
+        assert!(node.rotate_interface_key(&interface_id, new.clone()).unwrap());
+        assert!(!node.rotate_interface_key(&interface_id, new.clone()).unwrap());
+        assert_eq!(node.interface_key(&interface_id).unwrap().as_bytes(), new.as_bytes());
+
+        // The new key is persisted; the old one still decrypts late events
+        let record = node.storage.interface_store().get(&interface_id).unwrap().unwrap();
+        assert_eq!(record.encrypted_key.as_deref(), Some(&new.as_bytes()[..]));
+        let retired = node.retired_keys.get(&interface_id).unwrap();
+        assert_eq!(retired[0].as_bytes(), old.as_bytes());
+    }
 }
@@ -5,6 +5,9 @@
 //! - Sync requests (generate sync response)
 //! - Sync responses (apply incoming sync)
 //! - Event acknowledgments (mark delivered)
+//! - Causal events (buffer until dependencies are delivered, see [`crate::causal`])
+//! - Anonymous events (ring-signed, unsigned envelope, see [`crate::anonymous`])
+//! - Duplicate events (dropped before delivery, see [`crate::dedup`])
 //!
 //! ## Post-Quantum Signatures
 //!
@@ -21,12 +24,27 @@ use tracing::{debug, error, info, warn};
 
 use indras_core::transport::Transport;
 use indras_core::{EventId, InterfaceEvent, InterfaceId, NInterfaceTrait, PeerIdentity};
-use indras_crypto::{InterfaceKey, PQIdentity, PQPublicIdentity, PQSignature};
+use indras_crypto::{InterfaceKey, MemberCertificate, PQIdentity, PQPublicIdentity, PQSignature};
+use indras_logging::metrics;
 use indras_storage::{CompositeStorage, NodeEvent, NodeLog};
 use indras_transport::{IrohIdentity, IrohNetworkAdapter};
 
+use crate::anonymous::{ANONYMOUS_EVENT_TYPE, AnonymityRings, AnonymousPost, anonymous_sender};
+use crate::causal::CausalTracker;
+use crate::connection_budget::ConnectionBudget;
+use crate::dedup::EventDedup;
+use crate::delegation::{DelegatedRequest, DelegationRegistry, DenialReason};
+use crate::guest::{self, GuestRegistry, GuestStatus};
+use crate::handshake::{Capabilities, HelloMessage, PeerProtocols};
+use crate::supervisor::SharedReceiver;
+use crate::topology::SyncRecency;
+use crate::trust::{MemberTrust, TrustRoots};
+use crate::wire::{self, SIGNED_ENVELOPE_MARKER, SignedEnvelope, WireError};
 use crate::{InterfaceState, ReceivedEvent};
 
+/// How often buffered causal events are checked for expiry.
+const CAUSAL_EXPIRE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
 /// Message types for the P2P protocol
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum NetworkMessage {
@@ -42,21 +60,78 @@ pub enum NetworkMessage {
     DtnBundle(crate::dtn_manager::DtnBundleMessage),
     /// DTN custody protocol message
     DtnCustody(crate::dtn_manager::DtnCustodyMessage),
+    /// An encrypted interface event carrying causal dependencies
+    CausalEvent(CausalEventMessage),
+    /// A delegate acting on this node under a delegation token
+    Delegated(DelegatedRequest),
+    /// An encrypted, ring-signed event with no sender identity
+    ///
+    /// Always sent unsigned: a PQ signature would name the sender.
+    AnonymousEvent(InterfaceEventMessage),
+    /// Protocol version and capability handshake
+    Hello(HelloMessage),
+    /// An organization-signed certificate for a member key
+    MemberCertificate(MemberCertificate),
+    /// An anonymous event for the receiver to forward to the other members
+    ///
+    /// Sent unsigned to a single member, which delivers it and passes it on
+    /// as an [`AnonymousEvent`](Self::AnonymousEvent).
+    AnonymousRelay(InterfaceEventMessage),
 }
 
 impl NetworkMessage {
-    /// Serialize to bytes
+    /// Wrap an interface event, attaching causal dependencies if any.
+    ///
+    /// Events without dependencies use the plain `InterfaceEvent` variant
+    /// so realms that never opt in keep the original wire format.
+    pub fn interface_event(msg: InterfaceEventMessage, deps: Vec<EventId>) -> Self {
+        if deps.is_empty() {
+            NetworkMessage::InterfaceEvent(msg)
+        } else {
+            NetworkMessage::CausalEvent(CausalEventMessage { event: msg, deps })
+        }
+    }
+
+    /// The interface a message belongs to, if it is interface-scoped
+    pub fn interface_id(&self) -> Option<InterfaceId> {
+        match self {
+            NetworkMessage::InterfaceEvent(m)
+            | NetworkMessage::AnonymousEvent(m)
+            | NetworkMessage::AnonymousRelay(m) => Some(m.interface_id),
+            NetworkMessage::CausalEvent(m) => Some(m.event.interface_id),
+            NetworkMessage::SyncRequest(m) => Some(m.interface_id),
+            NetworkMessage::SyncResponse(m) => Some(m.interface_id),
+            NetworkMessage::EventAck(m) => Some(m.interface_id),
+            NetworkMessage::Delegated(r) => Some(r.interface_id),
+            NetworkMessage::DtnBundle(_)
+            | NetworkMessage::DtnCustody(_)
+            | NetworkMessage::Hello(_)
+            | NetworkMessage::MemberCertificate(_) => None,
+        }
+    }
+
+    /// Serialize to bytes in the version 1 (bare postcard) encoding
     pub fn to_bytes(&self) -> Result<Vec<u8>, postcard::Error> {
         postcard::to_allocvec(self)
     }
 
-    /// Deserialize from bytes
-    pub fn from_bytes(data: &[u8]) -> Result<Self, postcard::Error> {
-        postcard::from_bytes(data)
+    /// Serialize to bytes for a peer speaking wire `version`
+    ///
+    /// See [`crate::wire`] for the encodings.
+    pub fn encode(&self, version: u16) -> Result<Vec<u8>, WireError> {
+        wire::encode(self, version)
+    }
+
+    /// Deserialize from bytes in any supported encoding
+    pub fn from_bytes(data: &[u8]) -> Result<Self, WireError> {
+        wire::decode(data)
     }
 }
 
 /// Current protocol version for signed messages
+///
+/// This versions the signature scheme, not the message encoding; the
+/// encoding follows the negotiated protocol version (see [`crate::wire`]).
 pub const SIGNED_MESSAGE_VERSION: u8 = 1;
 
 /// A signed network message with ML-DSA-65 signature
@@ -69,7 +144,10 @@ pub const SIGNED_MESSAGE_VERSION: u8 = 1;
 /// - Signature: ~3,309 bytes
 /// - Verifying key: ~1,952 bytes
 /// - Total overhead: ~5.3 KB per message
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// The signature covers the message exactly as encoded on the wire, so a
+/// receiver that skips unknown fields while decoding can still verify it.
+#[derive(Debug, Clone)]
 pub struct SignedNetworkMessage {
     /// Protocol version (must match SIGNED_MESSAGE_VERSION)
     pub version: u8,
@@ -79,17 +157,81 @@ pub struct SignedNetworkMessage {
     pub signature: Vec<u8>,
     /// Sender's verifying key (~1,952 bytes)
     pub sender_verifying_key: Vec<u8>,
+    /// Encoded message the signature covers
+    signed_bytes: Vec<u8>,
+}
+
+/// Version 1 layout of a signed message
+#[derive(Serialize, Deserialize)]
+struct LegacySignedMessage {
+    version: u8,
+    message: NetworkMessage,
+    signature: Vec<u8>,
+    sender_verifying_key: Vec<u8>,
 }
 
 impl SignedNetworkMessage {
-    /// Serialize to bytes
+    /// Encode `message` for wire `version` and sign it
+    pub fn sign(
+        message: NetworkMessage,
+        identity: &PQIdentity,
+        version: u16,
+    ) -> Result<Self, WireError> {
+        let signed_bytes = message.encode(version)?;
+        let signature = identity.sign(&signed_bytes);
+        Ok(Self {
+            version: SIGNED_MESSAGE_VERSION,
+            message,
+            signature: signature.to_bytes().to_vec(),
+            sender_verifying_key: identity.verifying_key_bytes(),
+            signed_bytes,
+        })
+    }
+
+    /// Serialize to bytes, in the encoding the message was signed in
     pub fn to_bytes(&self) -> Result<Vec<u8>, postcard::Error> {
-        postcard::to_allocvec(self)
+        if wire::is_envelope(&self.signed_bytes) {
+            postcard::to_allocvec(&SignedEnvelope {
+                marker: SIGNED_ENVELOPE_MARKER,
+                version: self.version,
+                message: self.signed_bytes.clone(),
+                signature: self.signature.clone(),
+                sender_verifying_key: self.sender_verifying_key.clone(),
+            })
+        } else {
+            postcard::to_allocvec(&LegacySignedMessage {
+                version: self.version,
+                message: self.message.clone(),
+                signature: self.signature.clone(),
+                sender_verifying_key: self.sender_verifying_key.clone(),
+            })
+        }
     }
 
-    /// Deserialize from bytes
-    pub fn from_bytes(data: &[u8]) -> Result<Self, postcard::Error> {
-        postcard::from_bytes(data)
+    /// Deserialize from bytes in any supported encoding
+    pub fn from_bytes(data: &[u8]) -> Result<Self, WireError> {
+        if data.first() == Some(&SIGNED_ENVELOPE_MARKER) {
+            let (signed, _) = postcard::take_from_bytes::<SignedEnvelope>(data)?;
+            if !wire::is_envelope(&signed.message) {
+                return Err(WireError::BadMarker(signed.message.first().copied().unwrap_or(0)));
+            }
+            return Ok(Self {
+                version: signed.version,
+                message: NetworkMessage::from_bytes(&signed.message)?,
+                signature: signed.signature,
+                sender_verifying_key: signed.sender_verifying_key,
+                signed_bytes: signed.message,
+            });
+        }
+
+        let legacy: LegacySignedMessage = postcard::from_bytes(data)?;
+        Ok(Self {
+            signed_bytes: legacy.message.to_bytes()?,
+            version: legacy.version,
+            message: legacy.message,
+            signature: legacy.signature,
+            sender_verifying_key: legacy.sender_verifying_key,
+        })
     }
 
     /// Verify the signature on this message
@@ -110,12 +252,7 @@ impl SignedNetworkMessage {
         let signature = PQSignature::from_bytes(self.signature.clone())
             .map_err(|e| MessageError::InvalidSignature(e.to_string()))?;
 
-        let message_bytes = self
-            .message
-            .to_bytes()
-            .map_err(|e| MessageError::Serialization(e.to_string()))?;
-
-        Ok(verifying_key.verify(&message_bytes, &signature))
+        Ok(verifying_key.verify(&self.signed_bytes, &signature))
     }
 
     /// Get the sender's public identity
@@ -155,6 +292,15 @@ impl InterfaceEventMessage {
     }
 }
 
+/// An interface event that must not be delivered before its dependencies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CausalEventMessage {
+    /// The encrypted event
+    pub event: InterfaceEventMessage,
+    /// Events that must be delivered locally before this one
+    pub deps: Vec<EventId>,
+}
+
 /// A sync request for an interface
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InterfaceSyncRequest {
@@ -196,11 +342,12 @@ pub struct MessageHandler {
 
 /// Inner state shared across concurrent message-handling tasks.
 struct MessageHandlerInner {
-    /// Our identity (reserved for future use)
-    #[allow(dead_code)]
+    /// Our identity
     local_identity: IrohIdentity,
     /// Interface keys for decryption
     interface_keys: Arc<DashMap<InterfaceId, InterfaceKey>>,
+    /// Keys replaced by a rotation, still tried for decryption
+    retired_keys: Arc<DashMap<InterfaceId, Vec<InterfaceKey>>>,
     /// Loaded interfaces
     interfaces: Arc<DashMap<InterfaceId, InterfaceState>>,
     /// Storage
@@ -217,6 +364,24 @@ struct MessageHandlerInner {
     sync_now_tx: Option<mpsc::Sender<InterfaceId>>,
     /// DTN manager for offline peer delivery
     dtn: Arc<crate::dtn_manager::DtnManager>,
+    /// Causal delivery buffers
+    causal: Arc<CausalTracker>,
+    /// Recently delivered event IDs, for dropping duplicates
+    dedup: Arc<EventDedup>,
+    /// Read-only guests per interface
+    guests: Arc<GuestRegistry>,
+    /// Delegation tokens issued by this node
+    delegations: Arc<DelegationRegistry>,
+    /// Anonymity rings per interface
+    anonymity_rings: Arc<AnonymityRings>,
+    /// Negotiated protocol per peer
+    protocols: Arc<PeerProtocols>,
+    /// Organization trust roots per interface
+    trust: Arc<TrustRoots>,
+    /// Last sync time per interface and peer
+    sync_recency: Arc<SyncRecency>,
+    /// Interface traffic for connection reaping
+    budget: Arc<ConnectionBudget>,
 }
 
 impl MessageHandler {
@@ -226,9 +391,11 @@ impl MessageHandler {
     ///
     /// * `allow_legacy_unsigned` - If true, accepts unsigned (legacy) messages with a warning.
     ///   Set to false in production to enforce PQ signatures.
+    #[allow(clippy::too_many_arguments)] // Constructor with many dependencies
     pub fn new(
         local_identity: IrohIdentity,
         interface_keys: Arc<DashMap<InterfaceId, InterfaceKey>>,
+        retired_keys: Arc<DashMap<InterfaceId, Vec<InterfaceKey>>>,
         interfaces: Arc<DashMap<InterfaceId, InterfaceState>>,
         storage: Arc<CompositeStorage<IrohIdentity>>,
         transport: Arc<IrohNetworkAdapter>,
@@ -237,12 +404,22 @@ impl MessageHandler {
         allow_legacy_unsigned: bool,
         sync_now_tx: Option<mpsc::Sender<InterfaceId>>,
         dtn: Arc<crate::dtn_manager::DtnManager>,
+        causal: Arc<CausalTracker>,
+        dedup: Arc<EventDedup>,
+        guests: Arc<GuestRegistry>,
+        delegations: Arc<DelegationRegistry>,
+        anonymity_rings: Arc<AnonymityRings>,
+        protocols: Arc<PeerProtocols>,
+        trust: Arc<TrustRoots>,
+        sync_recency: Arc<SyncRecency>,
+        budget: Arc<ConnectionBudget>,
         shutdown_rx: broadcast::Receiver<()>,
     ) -> Self {
         Self {
             inner: Arc::new(MessageHandlerInner {
                 local_identity,
                 interface_keys,
+                retired_keys,
                 interfaces,
                 storage,
                 transport,
@@ -251,6 +428,15 @@ impl MessageHandler {
                 allow_legacy_unsigned,
                 sync_now_tx,
                 dtn,
+                causal,
+                dedup,
+                guests,
+                delegations,
+                anonymity_rings,
+                protocols,
+                trust,
+                sync_recency,
+                budget,
             }),
             shutdown_rx,
         }
@@ -262,9 +448,11 @@ impl MessageHandler {
     ///
     /// * `allow_legacy_unsigned` - If true, accepts unsigned (legacy) messages with a warning.
     ///   Set to false in production to enforce PQ signatures.
+    #[allow(clippy::too_many_arguments)] // Constructor with many dependencies
     pub fn spawn(
         local_identity: IrohIdentity,
         interface_keys: Arc<DashMap<InterfaceId, InterfaceKey>>,
+        retired_keys: Arc<DashMap<InterfaceId, Vec<InterfaceKey>>>,
         interfaces: Arc<DashMap<InterfaceId, InterfaceState>>,
         storage: Arc<CompositeStorage<IrohIdentity>>,
         transport: Arc<IrohNetworkAdapter>,
@@ -273,12 +461,22 @@ impl MessageHandler {
         allow_legacy_unsigned: bool,
         sync_now_tx: Option<mpsc::Sender<InterfaceId>>,
         dtn: Arc<crate::dtn_manager::DtnManager>,
+        causal: Arc<CausalTracker>,
+        dedup: Arc<EventDedup>,
+        guests: Arc<GuestRegistry>,
+        delegations: Arc<DelegationRegistry>,
+        anonymity_rings: Arc<AnonymityRings>,
+        protocols: Arc<PeerProtocols>,
+        trust: Arc<TrustRoots>,
+        sync_recency: Arc<SyncRecency>,
+        budget: Arc<ConnectionBudget>,
         shutdown_rx: broadcast::Receiver<()>,
-        message_rx: tokio::sync::mpsc::Receiver<(IrohIdentity, Vec<u8>)>,
+        message_rx: SharedReceiver<(IrohIdentity, Vec<u8>)>,
     ) -> JoinHandle<()> {
         let handler = Self::new(
             local_identity,
             interface_keys,
+            retired_keys,
             interfaces,
             storage,
             transport,
@@ -287,6 +485,15 @@ impl MessageHandler {
             allow_legacy_unsigned,
             sync_now_tx,
             dtn,
+            causal,
+            dedup,
+            guests,
+            delegations,
+            anonymity_rings,
+            protocols,
+            trust,
+            sync_recency,
+            budget,
             shutdown_rx,
         );
 
@@ -300,8 +507,12 @@ impl MessageHandler {
     /// Each incoming message is dispatched to a spawned task so that
     /// write-lock contention on one interface doesn't block message
     /// processing for other interfaces.
-    async fn run(mut self, mut message_rx: tokio::sync::mpsc::Receiver<(IrohIdentity, Vec<u8>)>) {
+    async fn run(mut self, message_rx: SharedReceiver<(IrohIdentity, Vec<u8>)>) {
         info!("Message handler started");
+        let mut message_rx = message_rx.lock().await;
+
+        let mut causal_tick = tokio::time::interval(CAUSAL_EXPIRE_INTERVAL);
+        causal_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
 
         loop {
             tokio::select! {
@@ -309,6 +520,23 @@ impl MessageHandler {
                     info!("Message handler shutting down");
                     break;
                 }
+                _ = causal_tick.tick() => {
+                    let released = self.inner.causal.expire();
+                    if !released.is_empty() {
+                        let inner = Arc::clone(&self.inner);
+                        tokio::spawn(async move {
+                            for (interface_id, event) in released {
+                                warn!(
+                                    interface = %hex::encode(interface_id.as_bytes()),
+                                    "Releasing causal event with missing dependencies"
+                                );
+                                if let Err(e) = inner.deliver_event(interface_id, event).await {
+                                    debug!(error = %e, "Failed to deliver expired causal event");
+                                }
+                            }
+                        });
+                    }
+                }
                 Some((sender, data)) = message_rx.recv() => {
                     let inner = Arc::clone(&self.inner);
                     tokio::spawn(async move {
@@ -317,6 +545,10 @@ impl MessageHandler {
                                 MessageError::UnknownInterface(_) => {
                                     debug!(error = %e, "Ignoring message for unknown interface");
                                 }
+                                MessageError::UntrustedMember(_) => {
+                                    // Already logged with the sender by the trust check
+                                    debug!(error = %e, "Dropped message from untrusted member");
+                                }
                                 MessageError::Decryption(_) => {
                                     warn!(
                                         error = %e,
@@ -348,8 +580,32 @@ impl MessageHandlerInner {
         data: Vec<u8>,
     ) -> Result<(), MessageError> {
         // Try to parse as signed message first
-        if let Ok(signed_msg) = SignedNetworkMessage::from_bytes(&data) {
-            return self.handle_signed_message(sender, signed_msg).await;
+        match SignedNetworkMessage::from_bytes(&data) {
+            Ok(signed_msg) => return self.handle_signed_message(sender, signed_msg).await,
+            Err(WireError::UnknownKind { version, kind }) => {
+                // A newer peer sent a message type this build doesn't know
+                debug!(sender = %sender.short_id(), version, kind, "Ignoring unknown message kind");
+                return Ok(());
+            }
+            Err(_) => {}
+        }
+
+        let message = NetworkMessage::from_bytes(&data);
+        if let Err(WireError::UnknownKind { version, kind }) = message {
+            debug!(sender = %sender.short_id(), version, kind, "Ignoring unknown message kind");
+            return Ok(());
+        }
+
+        // Anonymous events are unsigned by design and carry their own
+        // ring signature, so they are accepted even without legacy mode
+        match message {
+            Ok(NetworkMessage::AnonymousEvent(msg)) => {
+                return self.handle_anonymous_event(sender, msg).await.map(|_| ());
+            }
+            Ok(NetworkMessage::AnonymousRelay(msg)) => {
+                return self.handle_anonymous_relay(sender, msg).await;
+            }
+            _ => {}
         }
 
         // Check if legacy unsigned messages are allowed
@@ -362,14 +618,18 @@ impl MessageHandlerInner {
         }
 
         // Fall back to unsigned message (legacy support during transition)
-        let message = NetworkMessage::from_bytes(&data)
-            .map_err(|e| MessageError::Deserialization(e.to_string()))?;
+        let message = message.map_err(|e| MessageError::Deserialization(e.to_string()))?;
 
         warn!(
             sender = %sender.short_id(),
             "Received unsigned message (legacy mode)"
         );
 
+        // Unsigned messages can't chain to a trust root
+        if let Some(interface_id) = message.interface_id() {
+            self.check_member_trust(&sender, &interface_id, &[])?;
+        }
+
         self.dispatch_message(sender, message).await
     }
 
@@ -390,6 +650,10 @@ impl MessageHandlerInner {
             "Verified PQ signature on message"
         );
 
+        if let Some(interface_id) = signed_msg.message.interface_id() {
+            self.check_member_trust(&sender, &interface_id, &signed_msg.sender_verifying_key)?;
+        }
+
         self.dispatch_message(sender, signed_msg.message).await
     }
 
@@ -399,6 +663,10 @@ impl MessageHandlerInner {
         sender: IrohIdentity,
         message: NetworkMessage,
     ) -> Result<(), MessageError> {
+        if !matches!(message, NetworkMessage::Hello(_)) {
+            self.greet(&sender).await;
+        }
+
         match message {
             NetworkMessage::InterfaceEvent(msg) => {
                 let iid = msg.interface_id;
@@ -449,74 +717,478 @@ impl MessageHandlerInner {
             NetworkMessage::DtnBundle(msg) => {
                 self.handle_dtn_bundle(sender.clone(), msg).await
             }
+            NetworkMessage::CausalEvent(msg) => {
+                let iid = msg.event.interface_id;
+                let eid = msg.event.event_id;
+                let size = msg.event.ciphertext.len() as u32;
+                self.handle_causal_event(sender, msg).await?;
+                let _ = self.node_log.append(NodeEvent::EventReceived {
+                    interface_id: iid,
+                    event_id: eid,
+                    sender: sender.as_bytes().to_vec(),
+                    payload_size: size,
+                }).await;
+                Ok(())
+            }
             NetworkMessage::DtnCustody(_msg) => {
                 // Custody protocol messages handled inline for now
                 debug!("Received DTN custody message (not yet implemented)");
                 Ok(())
             }
+            NetworkMessage::Delegated(request) => {
+                self.handle_delegated_request(sender, request).await;
+                Ok(())
+            }
+            NetworkMessage::AnonymousEvent(msg) => {
+                self.handle_anonymous_event(sender, msg).await.map(|_| ())
+            }
+            NetworkMessage::AnonymousRelay(msg) => {
+                self.handle_anonymous_relay(sender, msg).await
+            }
+            NetworkMessage::Hello(hello) => {
+                self.handle_hello(sender, hello).await;
+                Ok(())
+            }
+            NetworkMessage::MemberCertificate(certificate) => {
+                debug!(
+                    sender = %sender.short_id(),
+                    member = %certificate.member_name,
+                    "Received member certificate"
+                );
+                self.trust.add_certificate(certificate);
+                Ok(())
+            }
         }
     }
 
+    /// Apply an interface's trust root to a signed sender
+    ///
+    /// Unverified senders are logged; under `TrustPolicy::Reject` their
+    /// message is refused.
+    fn check_member_trust(
+        &self,
+        sender: &IrohIdentity,
+        interface_id: &InterfaceId,
+        sender_vk: &[u8],
+    ) -> Result<(), MessageError> {
+        match self.trust.check(interface_id, sender_vk, guest::now_millis() as i64) {
+            MemberTrust::Unrooted | MemberTrust::Verified => Ok(()),
+            MemberTrust::Flagged => {
+                warn!(
+                    sender = %sender.short_id(),
+                    interface = %interface_id,
+                    "Message from member without a trust-root certificate"
+                );
+                Ok(())
+            }
+            MemberTrust::Rejected => {
+                warn!(
+                    sender = %sender.short_id(),
+                    interface = %interface_id,
+                    "Rejected message from member without a trust-root certificate"
+                );
+                Err(MessageError::UntrustedMember(*interface_id))
+            }
+        }
+    }
+
+    /// Send our hello on first contact with a peer
+    async fn greet(&self, peer: &IrohIdentity) {
+        if self.protocols.mark_hello_sent(*peer) {
+            let hello = NetworkMessage::Hello(HelloMessage::local(false));
+            if let Err(e) = self.sign_and_send(peer, hello).await {
+                debug!(peer = %peer.short_id(), error = %e, "Failed to send hello");
+            }
+        }
+    }
+
+    /// Record a peer's hello and answer it with ours
+    async fn handle_hello(&self, sender: IrohIdentity, hello: HelloMessage) {
+        match self.protocols.record(sender, &hello) {
+            Ok(protocol) => info!(
+                peer = %sender.short_id(),
+                version = protocol.version,
+                capabilities = %protocol.capabilities,
+                agent = %protocol.agent,
+                "Negotiated peer protocol"
+            ),
+            Err(e) => warn!(
+                peer = %sender.short_id(),
+                agent = %hello.agent,
+                error = %e,
+                "Incompatible peer protocol; syncing with this peer is disabled"
+            ),
+        }
+
+        if !hello.reply {
+            self.protocols.mark_hello_sent(sender);
+            let reply = NetworkMessage::Hello(HelloMessage::local(true));
+            if let Err(e) = self.sign_and_send(&sender, reply).await {
+                debug!(peer = %sender.short_id(), error = %e, "Failed to answer hello");
+            }
+        }
+
+        // Both sides receive a hello, so each presents its own certificates here
+        if self.protocols.supports(&sender, Capabilities::MEMBER_CERTIFICATES) {
+            for certificate in self.trust.own_certificates() {
+                let message = NetworkMessage::MemberCertificate(certificate);
+                if let Err(e) = self.sign_and_send(&sender, message).await {
+                    debug!(peer = %sender.short_id(), error = %e, "Failed to send member certificate");
+                }
+            }
+        }
+    }
+
+    /// Authorize a delegated request and record it in the audit trail
+    ///
+    /// Refusals are not errors for the handler: they are logged and audited
+    /// like allowed actions, and the delegate gets no reply either way.
+    /// Throttled unknown-token refusals are only counted by the registry.
+    async fn handle_delegated_request(&self, sender: IrohIdentity, request: DelegatedRequest) {
+        let outcome = self.delegations.authorize(
+            &request,
+            &sender,
+            &self.pq_identity.verifying_key(),
+            guest::now_millis(),
+        );
+        match &outcome {
+            Err(DenialReason::Throttled) => return,
+            Ok(()) => info!(
+                sender = %sender.short_id(),
+                capability = %request.action.capability(),
+                "Accepted delegated action"
+            ),
+            Err(reason) => warn!(
+                sender = %sender.short_id(),
+                capability = %request.action.capability(),
+                %reason,
+                "Refused delegated action"
+            ),
+        }
+        let _ = self.node_log.append(NodeEvent::DelegatedAction {
+            token_id: request.token.id,
+            interface_id: request.interface_id,
+            sender: sender.as_bytes().to_vec(),
+            capability: request.action.capability().to_string(),
+            denied: outcome.err().map(|r| r.to_string()),
+        }).await;
+    }
+
     /// Handle an incoming interface event
     async fn handle_interface_event(
         &self,
         sender: IrohIdentity,
         msg: InterfaceEventMessage,
     ) -> Result<(), MessageError> {
+        let interface_id = msg.interface_id;
+        let event_id = msg.event_id;
+        if self.rejects_write(&interface_id, &sender) {
+            return Ok(());
+        }
+        self.budget
+            .touch(interface_id, sender, guest::now_millis() as i64);
+        let event = self.decrypt_event(msg)?;
+        if self.impersonates_anonymous(&interface_id, &event) {
+            return Ok(());
+        }
+
+        self.causal.record_received(interface_id, event_id);
+        self.deliver_event(interface_id, event).await?;
+
+        debug!(
+            interface = %hex::encode(interface_id.as_bytes()),
+            event_id = ?event_id,
+            sender = %sender.short_id(),
+            "Received and processed interface event"
+        );
+
+        Ok(())
+    }
+
+    /// Handle an incoming interface event that carries causal dependencies
+    ///
+    /// The event is held back until every dependency has been delivered
+    /// locally; delivering it may in turn release events waiting on it.
+    async fn handle_causal_event(
+        &self,
+        sender: IrohIdentity,
+        msg: CausalEventMessage,
+    ) -> Result<(), MessageError> {
+        let interface_id = msg.event.interface_id;
+        let event_id = msg.event.event_id;
+        if self.rejects_write(&interface_id, &sender) {
+            return Ok(());
+        }
+        self.budget
+            .touch(interface_id, sender, guest::now_millis() as i64);
+        let event = self.decrypt_event(msg.event)?;
+        if self.impersonates_anonymous(&interface_id, &event) {
+            return Ok(());
+        }
+
+        let ready = self.causal.offer(interface_id, event_id, msg.deps, event);
+        if ready.is_empty() {
+            debug!(
+                interface = %hex::encode(interface_id.as_bytes()),
+                event_id = ?event_id,
+                sender = %sender.short_id(),
+                pending = self.causal.pending_len(&interface_id),
+                "Buffered causal event awaiting dependencies"
+            );
+            return Ok(());
+        }
+
+        for event in ready {
+            self.deliver_event(interface_id, event).await?;
+        }
+
+        debug!(
+            interface = %hex::encode(interface_id.as_bytes()),
+            event_id = ?event_id,
+            sender = %sender.short_id(),
+            "Received and processed causal event"
+        );
+
+        Ok(())
+    }
+
+    /// Handle an incoming anonymous event
+    ///
+    /// The event must be a custom event from the interface's anonymous
+    /// sender whose payload is an [`AnonymousPost`] signed over a ring this
+    /// node accepts; anything else is dropped. The delivering peer is only
+    /// logged, never attributed as the author.
+    ///
+    /// Returns whether the event was accepted and not seen before.
+    async fn handle_anonymous_event(
+        &self,
+        sender: IrohIdentity,
+        msg: InterfaceEventMessage,
+    ) -> Result<bool, MessageError> {
+        let interface_id = msg.interface_id;
+        let event_id = msg.event_id;
+        let size = msg.ciphertext.len() as u32;
+        let event = self.decrypt_event(msg)?;
+
+        let post = match &event {
+            InterfaceEvent::Custom {
+                id,
+                sender: event_sender,
+                event_type,
+                payload,
+                ..
+            } if *id == event_id
+                && *event_sender == anonymous_sender(&interface_id)
+                && event_type == ANONYMOUS_EVENT_TYPE =>
+            {
+                AnonymousPost::from_bytes(payload)
+                    .map_err(|e| MessageError::Deserialization(e.to_string()))?
+            }
+            _ => {
+                warn!(
+                    interface = %hex::encode(interface_id.as_bytes()),
+                    relay = %sender.short_id(),
+                    "Dropped malformed anonymous event"
+                );
+                return Ok(false);
+            }
+        };
+
+        if !self.anonymity_rings.admits(&interface_id, &post.ring) {
+            warn!(
+                interface = %hex::encode(interface_id.as_bytes()),
+                relay = %sender.short_id(),
+                "Dropped anonymous event signed over an unknown ring"
+            );
+            return Ok(false);
+        }
+        if !post.verify(&interface_id, event_id.sequence) {
+            return Err(MessageError::SignatureVerificationFailed);
+        }
+
+        self.causal.record_received(interface_id, event_id);
+        if !self.deliver_event(interface_id, event).await? {
+            return Ok(false);
+        }
+
+        let _ = self.node_log.append(NodeEvent::EventReceived {
+            interface_id,
+            event_id,
+            sender: anonymous_sender(&interface_id).as_bytes().to_vec(),
+            payload_size: size,
+        }).await;
+
+        debug!(
+            interface = %hex::encode(interface_id.as_bytes()),
+            event_id = ?event_id,
+            ring = post.ring.len(),
+            "Received and processed anonymous event"
+        );
+
+        Ok(true)
+    }
+
+    /// Handle an anonymous event this node was asked to relay
+    ///
+    /// The event is accepted as if it had arrived directly, then sent on
+    /// to every other connected member, including the peer that delivered
+    /// it, so to them it comes from this node. Only this node learns which
+    /// connection the post came in on.
+    async fn handle_anonymous_relay(
+        &self,
+        sender: IrohIdentity,
+        msg: InterfaceEventMessage,
+    ) -> Result<(), MessageError> {
+        let interface_id = msg.interface_id;
+        if !self.handle_anonymous_event(sender, msg.clone()).await? {
+            return Ok(());
+        }
+
+        let mut targets = match self.interfaces.get(&interface_id) {
+            Some(state) => state.interface.read().await.members(),
+            None => return Ok(()),
+        };
+        for peer_info in self.transport.discovery_service().realm_members(&interface_id) {
+            targets.insert(peer_info.peer_id);
+        }
+        let now = guest::now_millis();
+        targets.retain(|m| self.guests.status(&interface_id, m, now) != GuestStatus::Expired);
+
+        let message = NetworkMessage::AnonymousEvent(msg);
+        for member in &targets {
+            if *member == self.local_identity || !self.transport.is_connected(member) {
+                continue;
+            }
+            let bytes = message
+                .encode(self.protocols.wire_version(member))
+                .map_err(|e| MessageError::Serialization(e.to_string()))?;
+            if let Err(e) = self.transport.send(member, bytes).await {
+                debug!(peer = %member.short_id(), error = %e, "Anonymous relay failed");
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether a signed event claims the interface's anonymous sender
+    ///
+    /// Such events would show up as anonymous posts without a ring
+    /// signature, so they are dropped.
+    fn impersonates_anonymous(
+        &self,
+        interface_id: &InterfaceId,
+        event: &InterfaceEvent<IrohIdentity>,
+    ) -> bool {
+        let impersonates = event.sender() == Some(&anonymous_sender(interface_id));
+        if impersonates {
+            warn!(
+                interface = %hex::encode(interface_id.as_bytes()),
+                "Dropped signed event claiming the anonymous sender"
+            );
+        }
+        impersonates
+    }
+
+    /// Whether a write from `sender` must be dropped because it is a guest
+    fn rejects_write(&self, interface_id: &InterfaceId, sender: &IrohIdentity) -> bool {
+        let status = self.guests.status(interface_id, sender, guest::now_millis());
+        if status.is_read_only() {
+            debug!(
+                interface = %hex::encode(interface_id.as_bytes()),
+                sender = %sender.short_id(),
+                ?status,
+                "Dropped write from read-only guest"
+            );
+        }
+        status.is_read_only()
+    }
+
+    /// Decrypt and deserialize an interface event message
+    fn decrypt_event(
+        &self,
+        msg: InterfaceEventMessage,
+    ) -> Result<InterfaceEvent<IrohIdentity>, MessageError> {
         // Get the interface key
         let key = self
             .interface_keys
             .get(&msg.interface_id)
             .ok_or(MessageError::UnknownInterface(msg.interface_id))?;
 
-        // Decrypt the event
+        // Decrypt the event, falling back to keys replaced by a rotation
+        // the sender may not have picked up yet
         let encrypted = indras_crypto::EncryptedData {
             nonce: msg.nonce,
             ciphertext: msg.ciphertext,
         };
-        let plaintext = key.decrypt(&encrypted).map_err(|e| {
-            debug!(
-                interface = %hex::encode(msg.interface_id.as_bytes()),
-                event_id = ?msg.event_id,
-                "Decryption failed for interface event"
-            );
-            MessageError::Decryption(e.to_string())
+        let plaintext = key.decrypt(&encrypted).or_else(|e| {
+            self.retired_keys
+                .get(&msg.interface_id)
+                .and_then(|retired| retired.iter().find_map(|k| k.decrypt(&encrypted).ok()))
+                .ok_or_else(|| {
+                    debug!(
+                        interface = %hex::encode(msg.interface_id.as_bytes()),
+                        event_id = ?msg.event_id,
+                        "Decryption failed for interface event"
+                    );
+                    MessageError::Decryption(e.to_string())
+                })
         })?;
 
         // Deserialize the event
-        let event: InterfaceEvent<IrohIdentity> = postcard::from_bytes(&plaintext)
-            .map_err(|e| MessageError::Deserialization(e.to_string()))?;
+        postcard::from_bytes(&plaintext).map_err(|e| MessageError::Deserialization(e.to_string()))
+    }
 
+    /// Append a decrypted event to its interface and broadcast it locally
+    ///
+    /// Events already delivered are dropped (see [`crate::dedup`]); returns
+    /// whether this one was new.
+    async fn deliver_event(
+        &self,
+        interface_id: InterfaceId,
+        event: InterfaceEvent<IrohIdentity>,
+    ) -> Result<bool, MessageError> {
         // Get the interface state
         let state = self
             .interfaces
-            .get(&msg.interface_id)
-            .ok_or(MessageError::UnknownInterface(msg.interface_id))?;
+            .get(&interface_id)
+            .ok_or(MessageError::UnknownInterface(interface_id))?;
+
+        // Gossip and direct sends can deliver the same event twice
+        let event_id = event.event_id();
+        if let Some(id) = event_id
+            && !self.dedup.admit(interface_id, id)
+        {
+            debug!(
+                interface = %hex::encode(interface_id.as_bytes()),
+                event_id = ?id,
+                "Suppressed duplicate event"
+            );
+            return Ok(false);
+        }
 
         // Append to interface (this updates pending tracking)
         {
             let mut interface = state.interface.write().await;
-            interface
-                .append(event.clone())
-                .await
-                .map_err(|e| MessageError::AppendFailed(e.to_string()))?;
+            if let Err(e) = interface.append(event.clone()).await {
+                if let Some(id) = event_id {
+                    self.dedup.release(&interface_id, &id);
+                }
+                return Err(MessageError::AppendFailed(e.to_string()));
+            }
+        }
+
+        if let InterfaceEvent::Message { timestamp, .. } = &event {
+            let latency = (chrono::Utc::now() - *timestamp).to_std().unwrap_or_default();
+            metrics::record_message_latency(interface_id.to_string(), latency);
         }
 
         // Broadcast locally
         let received = ReceivedEvent {
-            interface_id: msg.interface_id,
+            interface_id,
             event,
         };
         let _ = state.event_tx.send(received);
 
-        debug!(
-            interface = %hex::encode(msg.interface_id.as_bytes()),
-            event_id = ?msg.event_id,
-            sender = %sender.short_id(),
-            "Received and processed interface event"
-        );
-
-        Ok(())
+        Ok(true)
     }
 
     /// Handle an incoming sync request
@@ -531,6 +1203,17 @@ impl MessageHandlerInner {
             .get(&msg.interface_id)
             .ok_or(MessageError::UnknownInterface(msg.interface_id))?;
 
+        // Guests may pull state while their access lasts but never push it
+        let status = self.guests.status(&msg.interface_id, &sender, guest::now_millis());
+        if status == GuestStatus::Expired {
+            debug!(
+                interface = %hex::encode(msg.interface_id.as_bytes()),
+                sender = %sender.short_id(),
+                "Ignored sync request from expired guest"
+            );
+            return Ok(());
+        }
+
         // Create sync message to merge
         let sync_msg = indras_core::SyncMessage {
             interface_id: msg.interface_id,
@@ -542,10 +1225,12 @@ impl MessageHandlerInner {
         // Merge incoming sync and generate immediate response
         let response_sync = {
             let mut interface = state.interface.write().await;
-            interface
-                .merge_sync(sync_msg)
-                .await
-                .map_err(|e| MessageError::SyncFailed(e.to_string()))?;
+            if !status.is_read_only() {
+                interface
+                    .merge_sync(sync_msg)
+                    .await
+                    .map_err(|e| MessageError::SyncFailed(e.to_string()))?;
+            }
 
             // Ensure sender is tracked as a member so generate_sync produces correct diff
             let _ = interface.add_member(sender);
@@ -557,6 +1242,8 @@ impl MessageHandlerInner {
             // Generate sync response containing state the sender is missing
             interface.generate_sync(&sender)
         };
+        self.sync_recency
+            .record(msg.interface_id, sender, guest::now_millis() as i64);
 
         // Notify Document listeners that CRDT state was updated
         let _ = state.sync_tx.send(());
@@ -598,18 +1285,12 @@ impl MessageHandlerInner {
         peer: &IrohIdentity,
         message: NetworkMessage,
     ) -> Result<(), MessageError> {
-        let message_bytes = message
-            .to_bytes()
-            .map_err(|e| MessageError::Serialization(e.to_string()))?;
-
-        let signature = self.pq_identity.sign(&message_bytes);
-
-        let signed_msg = SignedNetworkMessage {
-            version: SIGNED_MESSAGE_VERSION,
+        let signed_msg = SignedNetworkMessage::sign(
             message,
-            signature: signature.to_bytes().to_vec(),
-            sender_verifying_key: self.pq_identity.verifying_key_bytes(),
-        };
+            &self.pq_identity,
+            self.protocols.wire_version(peer),
+        )
+        .map_err(|e| MessageError::Serialization(e.to_string()))?;
 
         let bytes = signed_msg
             .to_bytes()
@@ -635,6 +1316,10 @@ impl MessageHandlerInner {
             .get(&msg.interface_id)
             .ok_or(MessageError::UnknownInterface(msg.interface_id))?;
 
+        if self.rejects_write(&msg.interface_id, &sender) {
+            return Ok(());
+        }
+
         // Create sync message to merge
         let sync_msg = indras_core::SyncMessage {
             interface_id: msg.interface_id,
@@ -656,6 +1341,8 @@ impl MessageHandlerInner {
             let _ = self.storage.register_peer(&sender, None);
             let _ = self.storage.add_member(&msg.interface_id, &sender);
         }
+        self.sync_recency
+            .record(msg.interface_id, sender, guest::now_millis() as i64);
 
         // Notify Document listeners that CRDT state was updated
         let _ = state.sync_tx.send(());
@@ -805,6 +1492,9 @@ pub enum MessageError {
 
     #[error("Legacy (unsigned) messages are disabled; all messages must be signed")]
     LegacyModeDisabled,
+
+    #[error("Sender has no trust-root certificate for interface {0}")]
+    UntrustedMember(InterfaceId),
 }
 
 #[cfg(test)]
@@ -853,6 +1543,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_causal_event_serialization() {
+        let event_msg = InterfaceEventMessage {
+            interface_id: InterfaceId::generate(),
+            ciphertext: vec![9, 9],
+            event_id: EventId::new(1, 2),
+            nonce: [0u8; 12],
+        };
+
+        let msg = NetworkMessage::interface_event(event_msg, vec![EventId::new(1, 1)]);
+        let bytes = msg.to_bytes().unwrap();
+        let parsed = NetworkMessage::from_bytes(&bytes).unwrap();
+
+        match parsed {
+            NetworkMessage::CausalEvent(c) => {
+                assert_eq!(c.event.event_id, EventId::new(1, 2));
+                assert_eq!(c.deps, vec![EventId::new(1, 1)]);
+            }
+            _ => panic!("Wrong message type"),
+        }
+    }
+
+    #[test]
+    fn test_interface_event_without_deps_keeps_plain_variant() {
+        let event_msg = InterfaceEventMessage {
+            interface_id: InterfaceId::generate(),
+            ciphertext: vec![],
+            event_id: EventId::new(1, 1),
+            nonce: [0u8; 12],
+        };
+
+        let msg = NetworkMessage::interface_event(event_msg, vec![]);
+        assert!(matches!(msg, NetworkMessage::InterfaceEvent(_)));
+    }
+
     #[test]
     fn test_event_ack_serialization() {
         let ack = EventAckMessage {
@@ -871,4 +1596,37 @@ mod tests {
             _ => panic!("Wrong message type"),
         }
     }
+
+    #[test]
+    fn test_hello_serialization() {
+        let msg = NetworkMessage::Hello(HelloMessage::local(true));
+        let bytes = msg.to_bytes().unwrap();
+        let parsed = NetworkMessage::from_bytes(&bytes).unwrap();
+
+        match parsed {
+            NetworkMessage::Hello(h) => {
+                assert_eq!(h, HelloMessage::local(true));
+            }
+            _ => panic!("Wrong message type"),
+        }
+    }
+
+    #[test]
+    fn test_signed_message_verifies_in_both_encodings() {
+        let identity = PQIdentity::generate();
+        let msg = NetworkMessage::EventAck(EventAckMessage {
+            interface_id: InterfaceId::generate(),
+            up_to: EventId::new(2, 7),
+        });
+
+        for version in [wire::WIRE_VERSION_LEGACY, wire::WIRE_VERSION_ENVELOPE] {
+            let signed = SignedNetworkMessage::sign(msg.clone(), &identity, version).unwrap();
+            let bytes = signed.to_bytes().unwrap();
+            assert_eq!(bytes[0] == SIGNED_ENVELOPE_MARKER, version >= wire::WIRE_VERSION_ENVELOPE);
+
+            let parsed = SignedNetworkMessage::from_bytes(&bytes).unwrap();
+            assert!(parsed.verify().unwrap());
+            assert!(matches!(parsed.message, NetworkMessage::EventAck(a) if a.up_to.sequence == 7));
+        }
+    }
 }
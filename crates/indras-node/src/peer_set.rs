@@ -0,0 +1,163 @@
+//! Deterministic interface IDs for peer sets
+//!
+//! A realm defined by who is in it needs no invite: every member computes
+//! the same [`InterfaceId`] and key seed from the members' ML-DSA verifying
+//! keys with [`deterministic_realm_id`], and
+//! [`IndrasNode::create_peer_set_interface`](crate::IndrasNode::create_peer_set_interface)
+//! opens it.
+//!
+//! The canonical form identifies each peer by its user ID (the BLAKE3 hash
+//! of its verifying key, as in `PQIdentity::user_id`), sorted, with
+//! duplicates removed, so order and repeats never change the result. Both
+//! derivations are domain-separated and include [`PEER_SET_VERSION`]; a
+//! future scheme bumps the version and yields new IDs instead of colliding
+//! with old ones.
+//!
+//! The key seed depends only on public keys. It keeps out peers that don't
+//! know the member set, but anyone holding every member's verifying key
+//! can derive it.
+
+use std::collections::BTreeSet;
+
+use indras_core::InterfaceId;
+use indras_crypto::PQPublicIdentity;
+
+/// Version of the peer-set hashing scheme.
+pub const PEER_SET_VERSION: u8 = 1;
+
+/// Smallest valid peer set.
+pub const MIN_PEER_SET_SIZE: usize = 2;
+
+/// BLAKE3 key-derivation context for the interface ID.
+const REALM_ID_CONTEXT: &str = "indras peer-set realm id";
+
+/// BLAKE3 key-derivation context for the interface key seed.
+const KEY_SEED_CONTEXT: &str = "indras peer-set key seed";
+
+/// Why a peer set was rejected by [`validate_peer_set`]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PeerSetError {
+    /// Fewer than [`MIN_PEER_SET_SIZE`] distinct peers
+    #[error("peer set needs at least {MIN_PEER_SET_SIZE} distinct peers, got {0}")]
+    TooFew(usize),
+
+    /// The same verifying key was listed twice (short ID of the key)
+    #[error("peer {0} is listed more than once")]
+    Duplicate(String),
+
+    /// The set does not include the local node
+    #[error("peer set does not include this node")]
+    MissingSelf,
+}
+
+/// User ID of a peer: the BLAKE3 hash of its verifying key
+pub fn peer_user_id(peer: &PQPublicIdentity) -> [u8; 32] {
+    *blake3::hash(&peer.to_bytes()).as_bytes()
+}
+
+/// The canonical form of a peer set: user IDs, sorted and deduplicated
+pub fn canonical_peer_set(peers: &[PQPublicIdentity]) -> Vec<[u8; 32]> {
+    let unique: BTreeSet<[u8; 32]> = peers.iter().map(peer_user_id).collect();
+    unique.into_iter().collect()
+}
+
+/// Interface ID and key seed for a peer set.
+///
+/// Order and duplicates in `peers` don't affect the result. Call
+/// [`validate_peer_set`] first to reject malformed input.
+pub fn deterministic_realm_id(peers: &[PQPublicIdentity]) -> (InterfaceId, [u8; 32]) {
+    let canonical = canonical_peer_set(peers);
+    (
+        InterfaceId::new(derive(REALM_ID_CONTEXT, &canonical)),
+        derive(KEY_SEED_CONTEXT, &canonical),
+    )
+}
+
+/// Whether `interface_id` is the deterministic ID of `peers`
+pub fn is_peer_set_realm(interface_id: &InterfaceId, peers: &[PQPublicIdentity]) -> bool {
+    deterministic_realm_id(peers).0 == *interface_id
+}
+
+/// Check that `peers` lists at least [`MIN_PEER_SET_SIZE`] peers, each once
+pub fn validate_peer_set(peers: &[PQPublicIdentity]) -> Result<(), PeerSetError> {
+    let mut seen = BTreeSet::new();
+    for peer in peers {
+        if !seen.insert(peer_user_id(peer)) {
+            return Err(PeerSetError::Duplicate(peer.short_id()));
+        }
+    }
+    if seen.len() < MIN_PEER_SET_SIZE {
+        return Err(PeerSetError::TooFew(seen.len()));
+    }
+    Ok(())
+}
+
+fn derive(context: &str, canonical: &[[u8; 32]]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new_derive_key(context);
+    hasher.update(&[PEER_SET_VERSION]);
+    hasher.update(&(canonical.len() as u64).to_le_bytes());
+    for user_id in canonical {
+        hasher.update(user_id);
+    }
+    *hasher.finalize().as_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indras_crypto::PQIdentity;
+
+    fn peers(n: usize) -> Vec<PQPublicIdentity> {
+        (0..n)
+            .map(|_| PQIdentity::generate().verifying_key())
+            .collect()
+    }
+
+    #[test]
+    fn test_order_independent() {
+        let set = peers(3);
+        let reversed: Vec<_> = set.iter().rev().cloned().collect();
+        assert_eq!(
+            deterministic_realm_id(&set),
+            deterministic_realm_id(&reversed)
+        );
+
+        let (id, seed) = deterministic_realm_id(&set);
+        assert_ne!(id.as_bytes(), &seed);
+        assert!(is_peer_set_realm(&id, &reversed));
+    }
+
+    #[test]
+    fn test_duplicates_ignored_but_rejected_by_validation() {
+        let set = peers(2);
+        let mut repeated = set.clone();
+        repeated.push(set[0].clone());
+
+        assert_eq!(
+            deterministic_realm_id(&set),
+            deterministic_realm_id(&repeated)
+        );
+        assert_eq!(canonical_peer_set(&repeated).len(), 2);
+        assert_eq!(validate_peer_set(&set), Ok(()));
+        assert_eq!(
+            validate_peer_set(&repeated),
+            Err(PeerSetError::Duplicate(set[0].short_id()))
+        );
+    }
+
+    #[test]
+    fn test_different_sets_differ() {
+        let set = peers(3);
+        let (whole, _) = deterministic_realm_id(&set);
+        let (subset, _) = deterministic_realm_id(&set[..2]);
+        assert_ne!(whole, subset);
+        assert!(!is_peer_set_realm(&whole, &set[..2]));
+    }
+
+    #[test]
+    fn test_too_few_peers() {
+        let set = peers(1);
+        assert_eq!(validate_peer_set(&[]), Err(PeerSetError::TooFew(0)));
+        assert_eq!(validate_peer_set(&set), Err(PeerSetError::TooFew(1)));
+    }
+}
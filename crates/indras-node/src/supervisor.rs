@@ -0,0 +1,411 @@
+//! Supervision of the node's background tasks
+//!
+//! The message receiver, message handler, sync task, realm discovery
+//! handler and job scheduler run for as long as the node is started. A
+//! panic in one of them used to stop that part of the node silently.
+//! [`Supervisor::supervise`] runs each through a monitor that notices when
+//! the task ends before shutdown, records a [`TaskIncident`] in the node
+//! log and the `indras.tasks.incidents` metric, and starts it again after
+//! an exponential backoff (see [`RestartPolicy`]).
+//!
+//! A task that keeps crashing is given up on after
+//! [`RestartPolicy::max_restarts`] consecutive failures; one that ran for
+//! [`RestartPolicy::stable_after`] before failing starts counting afresh.
+//!
+//! Tasks that read from a channel take a [`SharedReceiver`], so the
+//! receiver survives the crash and the restarted task carries on with the
+//! queued messages.
+//!
+//! [`IndrasNode::health`](crate::IndrasNode::health) summarizes the
+//! supervised tasks in a [`NodeHealth`].
+
+use std::any::Any;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use indras_logging::metrics;
+use indras_storage::{NodeEvent, NodeLog};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::{self, error::TryRecvError};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{error, warn};
+
+use crate::guest::now_millis;
+
+/// Incidents kept in memory for [`NodeHealth::recent_incidents`]
+const MAX_RECENT_INCIDENTS: usize = 32;
+
+/// A channel receiver that outlives the task reading it
+///
+/// The task locks it for as long as it runs; a panic releases the lock,
+/// so the restarted task picks up the messages still queued.
+pub type SharedReceiver<T> = Arc<tokio::sync::Mutex<mpsc::Receiver<T>>>;
+
+/// Wrap a receiver so it can be handed to successive task instances
+pub fn shared_receiver<T>(rx: mpsc::Receiver<T>) -> SharedReceiver<T> {
+    Arc::new(tokio::sync::Mutex::new(rx))
+}
+
+/// When and how often crashed tasks are restarted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestartPolicy {
+    /// Wait before the first restart
+    pub initial_backoff: Duration,
+    /// Upper bound on the wait between restarts
+    pub max_backoff: Duration,
+    /// Consecutive failures after which the task is left stopped
+    pub max_restarts: u32,
+    /// A task that ran this long before failing is considered recovered
+    pub stable_after: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            max_restarts: 10,
+            stable_after: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RestartPolicy {
+    /// Wait before restart number `attempt` (1-based) of a failure streak
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// What a supervised task is doing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskState {
+    /// Running normally
+    Running,
+    /// Crashed and waiting out its backoff
+    Restarting,
+    /// Crashed too often; no longer restarted
+    Failed,
+    /// Ended because the node stopped
+    Stopped,
+}
+
+/// A supervised task that panicked or exited while the node was running
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskIncident {
+    /// Task name
+    pub task: String,
+    /// When it happened (Unix millis)
+    pub at_millis: u64,
+    /// Panic message or exit reason
+    pub reason: String,
+    /// Whether the task was given up on
+    pub gave_up: bool,
+}
+
+/// Health of one supervised task
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskHealth {
+    /// Task name
+    pub name: String,
+    /// Current state
+    pub state: TaskState,
+    /// Restarts since the node started
+    pub restarts: u32,
+    /// The most recent incident, if any
+    pub last_incident: Option<TaskIncident>,
+}
+
+/// Summary returned by [`IndrasNode::health`](crate::IndrasNode::health)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeHealth {
+    /// Whether the node is started
+    pub started: bool,
+    /// Whether the transport is up
+    pub transport_up: bool,
+    /// Supervised tasks, sorted by name
+    pub tasks: Vec<TaskHealth>,
+    /// Latest incidents, oldest first
+    pub recent_incidents: Vec<TaskIncident>,
+}
+
+impl NodeHealth {
+    /// Started, transport up and every task running
+    pub fn is_healthy(&self) -> bool {
+        self.started
+            && self.transport_up
+            && self.tasks.iter().all(|t| t.state == TaskState::Running)
+    }
+
+    /// Health of the task called `name`
+    pub fn task(&self, name: &str) -> Option<&TaskHealth> {
+        self.tasks.iter().find(|t| t.name == name)
+    }
+}
+
+/// Restarts crashed background tasks and tracks their health
+pub struct Supervisor {
+    policy: RestartPolicy,
+    node_log: Arc<NodeLog>,
+    tasks: Mutex<BTreeMap<&'static str, TaskHealth>>,
+    incidents: Mutex<VecDeque<TaskIncident>>,
+}
+
+impl Supervisor {
+    /// Create a supervisor that records incidents in `node_log`
+    pub fn new(policy: RestartPolicy, node_log: Arc<NodeLog>) -> Self {
+        Self {
+            policy,
+            node_log,
+            tasks: Mutex::new(BTreeMap::new()),
+            incidents: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// The restart policy
+    pub fn policy(&self) -> &RestartPolicy {
+        &self.policy
+    }
+
+    /// Run the task `spawn` starts, restarting it until `shutdown` fires
+    ///
+    /// `spawn` is called once per run and must start a fresh instance that
+    /// also stops on `shutdown`. The returned handle finishes once the task
+    /// has stopped for good; await it on shutdown as you would the task.
+    pub fn supervise<F>(
+        self: &Arc<Self>,
+        name: &'static str,
+        shutdown: &broadcast::Sender<()>,
+        spawn: F,
+    ) -> JoinHandle<()>
+    where
+        F: Fn() -> JoinHandle<()> + Send + Sync + 'static,
+    {
+        let supervisor = Arc::clone(self);
+        let mut shutdown_rx = shutdown.subscribe();
+        tokio::spawn(async move {
+            let mut streak = 0u32;
+            loop {
+                supervisor.set_state(name, TaskState::Running);
+                let started = Instant::now();
+                let mut task = spawn();
+
+                let outcome = tokio::select! {
+                    outcome = &mut task => outcome,
+                    _ = shutdown_rx.recv() => {
+                        let _ = task.await;
+                        supervisor.set_state(name, TaskState::Stopped);
+                        return;
+                    }
+                };
+                let reason = match outcome {
+                    Err(e) if e.is_panic() => panic_message(e.into_panic()),
+                    Err(_) => {
+                        supervisor.set_state(name, TaskState::Stopped);
+                        return;
+                    }
+                    // Tasks may see the shutdown signal before we do
+                    Ok(()) if !matches!(shutdown_rx.try_recv(), Err(TryRecvError::Empty)) => {
+                        supervisor.set_state(name, TaskState::Stopped);
+                        return;
+                    }
+                    Ok(()) => "exited while the node was running".to_string(),
+                };
+
+                if started.elapsed() >= supervisor.policy.stable_after {
+                    streak = 0;
+                }
+                streak += 1;
+                let gave_up = streak > supervisor.policy.max_restarts;
+                supervisor.record_incident(name, reason, gave_up).await;
+                if gave_up {
+                    error!(
+                        task = name,
+                        failures = streak,
+                        "Background task keeps failing, giving up"
+                    );
+                    return;
+                }
+
+                let backoff = supervisor.policy.backoff(streak);
+                warn!(
+                    task = name,
+                    backoff_ms = backoff.as_millis() as u64,
+                    "Restarting background task"
+                );
+                tokio::select! {
+                    _ = tokio::time::sleep(backoff) => {}
+                    _ = shutdown_rx.recv() => {
+                        supervisor.set_state(name, TaskState::Stopped);
+                        return;
+                    }
+                }
+            }
+        })
+    }
+
+    /// Health of every supervised task, sorted by name
+    pub fn tasks(&self) -> Vec<TaskHealth> {
+        let tasks = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+        tasks.values().cloned().collect()
+    }
+
+    /// Latest incidents, oldest first
+    pub fn recent_incidents(&self) -> Vec<TaskIncident> {
+        let incidents = self.incidents.lock().unwrap_or_else(|e| e.into_inner());
+        incidents.iter().cloned().collect()
+    }
+
+    fn set_state(&self, name: &'static str, state: TaskState) {
+        let mut tasks = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+        tasks
+            .entry(name)
+            .or_insert_with(|| TaskHealth {
+                name: name.to_string(),
+                state,
+                restarts: 0,
+                last_incident: None,
+            })
+            .state = state;
+    }
+
+    async fn record_incident(&self, name: &'static str, reason: String, gave_up: bool) {
+        error!(task = name, reason = %reason, "Background task crashed");
+        let incident = TaskIncident {
+            task: name.to_string(),
+            at_millis: now_millis(),
+            reason: reason.clone(),
+            gave_up,
+        };
+        let restarts = {
+            let mut tasks = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+            let Some(health) = tasks.get_mut(name) else {
+                return;
+            };
+            if !gave_up {
+                health.restarts += 1;
+            }
+            health.state = if gave_up {
+                TaskState::Failed
+            } else {
+                TaskState::Restarting
+            };
+            health.last_incident = Some(incident.clone());
+            health.restarts
+        };
+        {
+            let mut incidents = self.incidents.lock().unwrap_or_else(|e| e.into_inner());
+            if incidents.len() == MAX_RECENT_INCIDENTS {
+                incidents.pop_front();
+            }
+            incidents.push_back(incident);
+        }
+
+        metrics::record_task_incident(name, !gave_up);
+        let _ = self
+            .node_log
+            .append(NodeEvent::TaskIncident {
+                task: name.to_string(),
+                reason,
+                restarts,
+                gave_up,
+            })
+            .await;
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        format!("panicked: {message}")
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        format!("panicked: {message}")
+    } else {
+        "panicked".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    fn supervisor(max_restarts: u32) -> Arc<Supervisor> {
+        let structured = Arc::new(indras_storage::InMemoryStructuredStore::new());
+        let policy = RestartPolicy {
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(4),
+            max_restarts,
+            stable_after: Duration::from_secs(60),
+        };
+        Arc::new(Supervisor::new(
+            policy,
+            Arc::new(NodeLog::in_memory(structured)),
+        ))
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let policy = RestartPolicy::default();
+        assert_eq!(policy.backoff(1), Duration::from_millis(500));
+        assert_eq!(policy.backoff(3), Duration::from_secs(2));
+        assert_eq!(policy.backoff(40), Duration::from_secs(30));
+    }
+
+    #[tokio::test]
+    async fn test_crashed_task_is_restarted() {
+        let supervisor = supervisor(5);
+        let (shutdown_tx, _) = broadcast::channel(1);
+        let runs = Arc::new(AtomicU32::new(0));
+
+        let counter = Arc::clone(&runs);
+        let shutdown = shutdown_tx.clone();
+        let handle = supervisor.supervise("flaky", &shutdown_tx, move || {
+            let run = counter.fetch_add(1, Ordering::SeqCst);
+            let mut shutdown_rx = shutdown.subscribe();
+            tokio::spawn(async move {
+                if run < 2 {
+                    panic!("boom {run}");
+                }
+                let _ = shutdown_rx.recv().await;
+            })
+        });
+
+        while runs.load(Ordering::SeqCst) < 3 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        let health = &supervisor.tasks()[0];
+        assert_eq!(health.state, TaskState::Running);
+        assert_eq!(health.restarts, 2);
+        assert_eq!(
+            health.last_incident.as_ref().map(|i| i.reason.as_str()),
+            Some("panicked: boom 1")
+        );
+        assert_eq!(supervisor.recent_incidents().len(), 2);
+
+        shutdown_tx.send(()).unwrap();
+        handle.await.unwrap();
+        assert_eq!(supervisor.tasks()[0].state, TaskState::Stopped);
+        assert_eq!(supervisor.node_log.current_sequence(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_restarts() {
+        let supervisor = supervisor(1);
+        let (shutdown_tx, _) = broadcast::channel(1);
+
+        let handle = supervisor.supervise("doomed", &shutdown_tx, || tokio::spawn(async {}));
+        handle.await.unwrap();
+
+        let health = &supervisor.tasks()[0];
+        assert_eq!(health.state, TaskState::Failed);
+        assert_eq!(health.restarts, 1);
+        assert!(health.last_incident.as_ref().unwrap().gave_up);
+    }
+}
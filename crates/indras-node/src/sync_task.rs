@@ -13,21 +13,24 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use dashmap::DashMap;
-use tokio::sync::{broadcast, mpsc};
+use dashmap::{DashMap, DashSet};
+use tokio::sync::broadcast;
 use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
 
 use indras_core::transport::Transport;
-use indras_core::{InterfaceId, NInterfaceTrait, PeerIdentity};
+use indras_core::{EventId, InterfaceId, NInterfaceTrait, PeerIdentity};
 use indras_crypto::{InterfaceKey, PQIdentity};
+use indras_logging::metrics;
 use indras_storage::{CompositeStorage, NodeEvent, NodeLog};
 use indras_transport::{IrohIdentity, IrohNetworkAdapter};
 
 use crate::InterfaceState;
+use crate::supervisor::SharedReceiver;
+use crate::topology::PeerLatency;
+use crate::handshake::{Capabilities, HelloMessage, PeerProtocols};
 use crate::message_handler::{
-    InterfaceEventMessage, InterfaceSyncRequest, NetworkMessage, SIGNED_MESSAGE_VERSION,
-    SignedNetworkMessage,
+    InterfaceEventMessage, InterfaceSyncRequest, NetworkMessage, SignedNetworkMessage,
 };
 
 /// Maximum number of events to batch in a single delivery cycle per peer.
@@ -127,7 +130,7 @@ pub struct SyncTask {
     /// Shutdown signal
     shutdown_rx: broadcast::Receiver<()>,
     /// Channel to receive immediate sync requests for specific interfaces
-    sync_now_rx: mpsc::Receiver<InterfaceId>,
+    sync_now_rx: SharedReceiver<InterfaceId>,
     /// Per-peer delivery retry state
     delivery_states: HashMap<IrohIdentity, PeerDeliveryState>,
     /// Sync cycle counter (for periodic maintenance)
@@ -136,6 +139,16 @@ pub struct SyncTask {
     dtn: Arc<crate::dtn_manager::DtnManager>,
     /// Unified delivery status tracker
     delivery_tracker: Arc<crate::delivery_tracker::DeliveryTracker>,
+    /// Causal dependencies of locally authored events (for retransmission)
+    causal: Arc<crate::causal::CausalTracker>,
+    /// Read-only guests per interface
+    guests: Arc<crate::guest::GuestRegistry>,
+    /// Local-only sandbox interfaces (never synced)
+    sandboxes: Arc<DashSet<InterfaceId>>,
+    /// Negotiated protocol per peer
+    protocols: Arc<PeerProtocols>,
+    /// Smoothed round-trip time per peer
+    latency: Arc<PeerLatency>,
 }
 
 impl SyncTask {
@@ -151,9 +164,14 @@ impl SyncTask {
         node_log: Arc<NodeLog>,
         sync_interval: Duration,
         shutdown_rx: broadcast::Receiver<()>,
-        sync_now_rx: mpsc::Receiver<InterfaceId>,
+        sync_now_rx: SharedReceiver<InterfaceId>,
         dtn: Arc<crate::dtn_manager::DtnManager>,
         delivery_tracker: Arc<crate::delivery_tracker::DeliveryTracker>,
+        causal: Arc<crate::causal::CausalTracker>,
+        guests: Arc<crate::guest::GuestRegistry>,
+        sandboxes: Arc<DashSet<InterfaceId>>,
+        protocols: Arc<PeerProtocols>,
+        latency: Arc<PeerLatency>,
     ) -> Self {
         Self {
             local_identity,
@@ -170,6 +188,11 @@ impl SyncTask {
             cycle_count: 0,
             dtn,
             delivery_tracker,
+            causal,
+            guests,
+            sandboxes,
+            protocols,
+            latency,
         }
     }
 
@@ -185,9 +208,14 @@ impl SyncTask {
         node_log: Arc<NodeLog>,
         sync_interval: Duration,
         shutdown_rx: broadcast::Receiver<()>,
-        sync_now_rx: mpsc::Receiver<InterfaceId>,
+        sync_now_rx: SharedReceiver<InterfaceId>,
         dtn: Arc<crate::dtn_manager::DtnManager>,
         delivery_tracker: Arc<crate::delivery_tracker::DeliveryTracker>,
+        causal: Arc<crate::causal::CausalTracker>,
+        guests: Arc<crate::guest::GuestRegistry>,
+        sandboxes: Arc<DashSet<InterfaceId>>,
+        protocols: Arc<PeerProtocols>,
+        latency: Arc<PeerLatency>,
     ) -> JoinHandle<()> {
         let task = Self::new(
             local_identity,
@@ -202,6 +230,11 @@ impl SyncTask {
             sync_now_rx,
             dtn,
             delivery_tracker,
+            causal,
+            guests,
+            sandboxes,
+            protocols,
+            latency,
         );
 
         tokio::spawn(async move {
@@ -217,6 +250,8 @@ impl SyncTask {
         );
 
         let mut interval = tokio::time::interval(self.sync_interval);
+        let sync_now = Arc::clone(&self.sync_now_rx);
+        let mut sync_now_rx = sync_now.lock().await;
 
         loop {
             tokio::select! {
@@ -238,10 +273,10 @@ impl SyncTask {
                         self.attempt_dtn_relay().await;
                     }
                 }
-                Some(interface_id) = self.sync_now_rx.recv() => {
+                Some(interface_id) = sync_now_rx.recv() => {
                     // Drain any additional queued requests and dedup
                     let mut ids = vec![interface_id];
-                    while let Ok(id) = self.sync_now_rx.try_recv() {
+                    while let Ok(id) = sync_now_rx.try_recv() {
                         if !ids.contains(&id) {
                             ids.push(id);
                         }
@@ -265,6 +300,9 @@ impl SyncTask {
     /// Resets backoff state for peers in this interface so the urgent sync
     /// is not blocked by stale failure counts.
     async fn sync_single_interface(&mut self, interface_id: InterfaceId) -> Result<(), SyncError> {
+        if self.sandboxes.contains(&interface_id) {
+            return Ok(());
+        }
         let interfaces = Arc::clone(&self.interfaces);
         let state = match interfaces.get(&interface_id) {
             Some(s) => s,
@@ -301,6 +339,7 @@ impl SyncTask {
 
         let interface_ids: Vec<InterfaceId> = interfaces.iter()
             .map(|entry| *entry.key())
+            .filter(|id| !self.sandboxes.contains(id))
             .collect();
 
         for interface_id in interface_ids {
@@ -341,6 +380,18 @@ impl SyncTask {
         &mut self,
         interface_id: InterfaceId,
         interface: &mut indras_sync::NInterface<IrohIdentity>,
+    ) -> Result<(), SyncError> {
+        let sync_began = Instant::now();
+        let result = self.sync_interface_members(interface_id, interface).await;
+        metrics::record_sync_duration(interface_id.to_string(), sync_began.elapsed());
+        result
+    }
+
+    /// Sync pass over an interface's members, timed by `sync_interface_inner`
+    async fn sync_interface_members(
+        &mut self,
+        interface_id: InterfaceId,
+        interface: &mut indras_sync::NInterface<IrohIdentity>,
     ) -> Result<(), SyncError> {
         let mut members: Vec<IrohIdentity> = interface.members().into_iter().collect();
         let key = self.interface_keys.get(&interface_id);
@@ -357,11 +408,26 @@ impl SyncTask {
             }
         }
 
+        let now = crate::guest::now_millis();
         for member in members {
             if member == self.local_identity {
                 continue;
             }
 
+            // Guests whose access has lapsed are no longer synced to
+            if self.guests.status(&interface_id, &member, now) == crate::guest::GuestStatus::Expired {
+                continue;
+            }
+
+            // Peers that failed the handshake would reject everything we send
+            if self.protocols.is_incompatible(&member) {
+                debug!(
+                    peer = %member.short_id(),
+                    "Skipping peer with incompatible protocol"
+                );
+                continue;
+            }
+
             // Check delivery state (scope the mutable borrow)
             let (is_offline, is_potentially_offline, should_retry) = {
                 let delivery_state = self.delivery_states
@@ -376,7 +442,9 @@ impl SyncTask {
 
             if is_offline {
                 // Hand pending events to DTN for persistent store-and-forward
-                if let Some(ref key) = key {
+                if let Some(ref key) = key
+                    && self.protocols.supports(&member, Capabilities::DTN)
+                {
                     self.handoff_to_dtn(interface, &member, key.value()).await;
                 }
                 debug!(
@@ -434,6 +502,13 @@ impl SyncTask {
                 }
             }
 
+            if self.protocols.mark_hello_sent(member) {
+                let hello = NetworkMessage::Hello(HelloMessage::local(false));
+                if let Err(e) = self.send_signed(&member, hello).await {
+                    debug!(peer = %member.short_id(), error = %e, "Failed to send hello");
+                }
+            }
+
             let mut sync_ok = true;
             if let Err(e) = self.send_sync_request(interface, &member).await {
                 debug!(
@@ -461,6 +536,9 @@ impl SyncTask {
             let delivery_state = self.delivery_states.get_mut(&member).unwrap();
             if sync_ok {
                 delivery_state.record_success();
+                if let Some(rtt) = self.transport.connection_manager().rtt(&member) {
+                    self.latency.record(member, rtt);
+                }
                 // Record encounter for ProphetState delivery probability
                 self.dtn.record_encounter(&member);
             } else {
@@ -471,20 +549,44 @@ impl SyncTask {
         Ok(())
     }
 
-    /// Sign and serialize a network message
-    fn sign_message(&self, message: NetworkMessage) -> Result<Vec<u8>, SyncError> {
-        let message_bytes = message
-            .to_bytes()
-            .map_err(|e| SyncError::Serialization(e.to_string()))?;
+    /// Causal dependencies to attach for a peer
+    ///
+    /// Empty for peers that didn't negotiate causal delivery, so they get a
+    /// plain `InterfaceEvent` they can parse.
+    fn causal_deps(
+        &self,
+        peer: &IrohIdentity,
+        interface_id: &InterfaceId,
+        event_id: &EventId,
+    ) -> Vec<EventId> {
+        if !self.protocols.supports(peer, Capabilities::CAUSAL_DELIVERY) {
+            return Vec::new();
+        }
+        self.causal.sent_deps(interface_id, event_id).unwrap_or_default()
+    }
 
-        let signature = self.pq_identity.sign(&message_bytes);
+    /// Sign a network message and send it to a peer
+    async fn send_signed(
+        &self,
+        peer: &IrohIdentity,
+        message: NetworkMessage,
+    ) -> Result<(), SyncError> {
+        let bytes = self.sign_message(peer, message)?;
+        self.transport
+            .send(peer, bytes)
+            .await
+            .map_err(|e| SyncError::Transport(e.to_string()))
+    }
 
-        let signed_msg = SignedNetworkMessage {
-            version: SIGNED_MESSAGE_VERSION,
-            message,
-            signature: signature.to_bytes().to_vec(),
-            sender_verifying_key: self.pq_identity.verifying_key_bytes(),
-        };
+    /// Sign and serialize a network message in the peer's wire encoding
+    fn sign_message(
+        &self,
+        peer: &IrohIdentity,
+        message: NetworkMessage,
+    ) -> Result<Vec<u8>, SyncError> {
+        let signed_msg =
+            SignedNetworkMessage::sign(message, &self.pq_identity, self.protocols.wire_version(peer))
+                .map_err(|e| SyncError::Serialization(e.to_string()))?;
 
         signed_msg
             .to_bytes()
@@ -510,7 +612,7 @@ impl SyncTask {
         let msg = NetworkMessage::SyncRequest(request);
 
         // Sign and serialize
-        let bytes = self.sign_message(msg)?;
+        let bytes = self.sign_message(peer, msg)?;
 
         // Send to peer
         let bytes_len = bytes.len() as u32;
@@ -581,21 +683,19 @@ impl SyncTask {
                 event_id,
                 encrypted.nonce,
             );
-            let network_msg = NetworkMessage::InterfaceEvent(msg);
-            let message_bytes = match network_msg.to_bytes() {
-                Ok(b) => b,
+            let deps = self.causal_deps(peer, &interface.id(), &event_id);
+            let network_msg = NetworkMessage::interface_event(msg, deps);
+            let signed_msg = match SignedNetworkMessage::sign(
+                network_msg,
+                &self.pq_identity,
+                self.protocols.wire_version(peer),
+            ) {
+                Ok(m) => m,
                 Err(e) => {
                     warn!(error = %e, "Failed to serialize message for DTN");
                     continue;
                 }
             };
-            let signature = self.pq_identity.sign(&message_bytes);
-            let signed_msg = SignedNetworkMessage {
-                version: SIGNED_MESSAGE_VERSION,
-                message: network_msg,
-                signature: signature.to_bytes().to_vec(),
-                sender_verifying_key: self.pq_identity.verifying_key_bytes(),
-            };
 
             match self.dtn.enqueue(&signed_msg, peer.clone(), indras_core::Priority::Normal) {
                 Ok(bundle_id) => {
@@ -738,7 +838,7 @@ impl SyncTask {
             };
 
             let network_msg = NetworkMessage::DtnBundle(dtn_msg);
-            let bytes = match self.sign_message(network_msg) {
+            let bytes = match self.sign_message(&candidate, network_msg) {
                 Ok(b) => b,
                 Err(_) => continue,
             };
@@ -823,8 +923,9 @@ impl SyncTask {
                     encrypted.nonce,
                 );
 
-                let network_msg = NetworkMessage::InterfaceEvent(msg);
-                let bytes = self.sign_message(network_msg)?;
+                let deps = self.causal_deps(peer, &interface.id(), &event_id);
+                let network_msg = NetworkMessage::interface_event(msg, deps);
+                let bytes = self.sign_message(peer, network_msg)?;
 
                 self.transport
                     .send(peer, bytes)
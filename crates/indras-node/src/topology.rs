@@ -0,0 +1,417 @@
+//! Mesh topology snapshots
+//!
+//! [`IndrasNode::topology_snapshot`](crate::IndrasNode::topology_snapshot)
+//! captures what this node knows about the mesh at one moment: every peer
+//! it has heard of with its connection state, the interfaces it belongs to,
+//! and which peers share each interface along with when we last synced
+//! with them there.
+//!
+//! Snapshots export as JSON (one line, tagged `"event_type":
+//! "topology_snapshot"` so the realm viewer can read it from an event
+//! stream) or as a GraphViz `graph` for offline maps.
+//!
+//! For a single interface,
+//! [`IndrasNode::member_sync_status`](crate::IndrasNode::member_sync_status)
+//! reports each member's [`MemberSyncStatus`]: last sync, round-trip time
+//! and how many of our events they have yet to acknowledge.
+
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use dashmap::DashMap;
+use indras_core::{InterfaceId, PeerIdentity};
+use indras_transport::IrohIdentity;
+use serde::{Deserialize, Serialize};
+
+/// How reachable a peer is right now
+///
+/// Ordered from weakest to strongest, so merging observations keeps the
+/// best one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionState {
+    /// Known only as an interface member; not seen on the network
+    Unreachable,
+    /// Seen via discovery but not connected
+    Discovered,
+    /// Open transport connection
+    Connected,
+}
+
+/// A peer in a topology snapshot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopologyPeer {
+    /// Hex-encoded public key
+    pub id: String,
+    /// Short display id
+    pub short_id: String,
+    /// Best connection state observed
+    pub state: ConnectionState,
+    /// When discovery last heard from the peer (Unix millis)
+    pub last_seen_millis: Option<i64>,
+}
+
+/// An interface in a topology snapshot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopologyRealm {
+    /// Hex-encoded interface id
+    pub id: String,
+    /// Number of members other than us
+    pub peer_count: usize,
+    /// Whether this is a local-only sandbox interface
+    pub sandbox: bool,
+}
+
+/// A peer's membership in an interface
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopologyLink {
+    /// Hex-encoded interface id
+    pub realm: String,
+    /// Hex-encoded peer public key
+    pub peer: String,
+    /// When we last exchanged sync state with the peer for this
+    /// interface (Unix millis), if ever since startup
+    pub last_sync_millis: Option<i64>,
+}
+
+/// Graph of known peers and shared interfaces at one moment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event_type", rename = "topology_snapshot")]
+pub struct TopologySnapshot {
+    /// Hex-encoded public key of the node that took the snapshot
+    pub local: String,
+    /// When the snapshot was taken (Unix millis)
+    pub taken_at_millis: i64,
+    /// Known peers, sorted by id
+    pub peers: Vec<TopologyPeer>,
+    /// Interfaces we belong to, sorted by id
+    pub realms: Vec<TopologyRealm>,
+    /// Peer memberships, sorted by interface then peer
+    pub links: Vec<TopologyLink>,
+}
+
+impl TopologySnapshot {
+    /// Start an empty snapshot
+    pub fn new(local: &IrohIdentity, taken_at_millis: i64) -> Self {
+        Self {
+            local: peer_hex(local),
+            taken_at_millis,
+            peers: Vec::new(),
+            realms: Vec::new(),
+            links: Vec::new(),
+        }
+    }
+
+    /// Record a peer observation, keeping the strongest state seen
+    pub fn observe_peer(
+        &mut self,
+        peer: &IrohIdentity,
+        state: ConnectionState,
+        last_seen_millis: Option<i64>,
+    ) {
+        let id = peer_hex(peer);
+        if id == self.local {
+            return;
+        }
+        match self.peers.binary_search_by(|p| p.id.cmp(&id)) {
+            Ok(i) => {
+                let existing = &mut self.peers[i];
+                existing.state = existing.state.max(state);
+                existing.last_seen_millis = existing.last_seen_millis.max(last_seen_millis);
+            }
+            Err(i) => self.peers.insert(
+                i,
+                TopologyPeer {
+                    id,
+                    short_id: peer.short_id(),
+                    state,
+                    last_seen_millis,
+                },
+            ),
+        }
+    }
+
+    /// Add an interface and link each member to it
+    ///
+    /// Members not otherwise observed are added as
+    /// [`ConnectionState::Unreachable`].
+    pub fn add_realm(
+        &mut self,
+        interface_id: &InterfaceId,
+        members: &[IrohIdentity],
+        sandbox: bool,
+        recency: &SyncRecency,
+    ) {
+        let realm = hex::encode(interface_id.as_bytes());
+        let mut peer_count = 0;
+        for member in members {
+            let peer = peer_hex(member);
+            if peer == self.local {
+                continue;
+            }
+            self.observe_peer(member, ConnectionState::Unreachable, None);
+            self.links.push(TopologyLink {
+                realm: realm.clone(),
+                peer,
+                last_sync_millis: recency.last_sync(interface_id, member),
+            });
+            peer_count += 1;
+        }
+        self.realms.push(TopologyRealm {
+            id: realm,
+            peer_count,
+            sandbox,
+        });
+        self.realms.sort_by(|a, b| a.id.cmp(&b.id));
+        self.links
+            .sort_by(|a, b| (&a.realm, &a.peer).cmp(&(&b.realm, &b.peer)));
+    }
+
+    /// Look up a peer by hex id
+    pub fn peer(&self, id: &str) -> Option<&TopologyPeer> {
+        self.peers.iter().find(|p| p.id == id)
+    }
+
+    /// Links not synced within `max_age_millis` of the snapshot
+    pub fn stale_links(&self, max_age_millis: i64) -> Vec<&TopologyLink> {
+        self.links
+            .iter()
+            .filter(|l| match l.last_sync_millis {
+                Some(at) => self.taken_at_millis - at > max_age_millis,
+                None => true,
+            })
+            .collect()
+    }
+
+    /// Serialize as a single JSON line
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// Render as a GraphViz `graph`
+    ///
+    /// Peers are ellipses colored by connection state, interfaces are
+    /// boxes, and membership edges are labelled with sync age (dashed
+    /// when we have never synced with that peer there).
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("graph mesh {\n");
+        let _ = writeln!(
+            out,
+            "  \"{}\" [label=\"{} (local)\", shape=doublecircle];",
+            self.local,
+            &self.local[..8.min(self.local.len())]
+        );
+        for peer in &self.peers {
+            let color = match peer.state {
+                ConnectionState::Connected => "darkgreen",
+                ConnectionState::Discovered => "goldenrod",
+                ConnectionState::Unreachable => "gray",
+            };
+            let _ = writeln!(
+                out,
+                "  \"{}\" [label=\"{}\", color={color}];",
+                peer.id, peer.short_id
+            );
+        }
+        for realm in &self.realms {
+            let _ = writeln!(
+                out,
+                "  \"realm:{}\" [label=\"realm {}\", shape=box{}];",
+                realm.id,
+                &realm.id[..8.min(realm.id.len())],
+                if realm.sandbox { ", style=dotted" } else { "" }
+            );
+            let _ = writeln!(out, "  \"{}\" -- \"realm:{}\";", self.local, realm.id);
+        }
+        for link in &self.links {
+            let attrs = match link.last_sync_millis {
+                Some(at) => format!(
+                    "label=\"{}s\"",
+                    (self.taken_at_millis - at).max(0) / 1000
+                ),
+                None => "style=dashed".to_string(),
+            };
+            let _ = writeln!(
+                out,
+                "  \"realm:{}\" -- \"{}\" [{attrs}];",
+                link.realm, link.peer
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Last sync time per (interface, peer)
+///
+/// Updated by the message handler whenever a sync request or response
+/// from a peer is merged. Kept in memory only.
+#[derive(Debug, Default)]
+pub struct SyncRecency {
+    last: DashMap<(InterfaceId, IrohIdentity), i64>,
+}
+
+impl SyncRecency {
+    /// Create an empty tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a sync exchange with a peer
+    pub fn record(&self, interface_id: InterfaceId, peer: IrohIdentity, at_millis: i64) {
+        self.last
+            .entry((interface_id, peer))
+            .and_modify(|t| *t = (*t).max(at_millis))
+            .or_insert(at_millis);
+    }
+
+    /// When we last synced with a peer for an interface
+    pub fn last_sync(&self, interface_id: &InterfaceId, peer: &IrohIdentity) -> Option<i64> {
+        self.last.get(&(*interface_id, *peer)).map(|t| *t)
+    }
+
+    /// Drop all records for an interface
+    pub fn forget(&self, interface_id: &InterfaceId) {
+        self.last.retain(|(id, _), _| id != interface_id);
+    }
+}
+
+/// Smoothed round-trip time per peer
+///
+/// The sync task samples the transport connection's RTT estimate each
+/// time it syncs with a peer. Samples are smoothed like TCP's SRTT (7/8
+/// previous, 1/8 new) so one slow exchange doesn't swing the figure.
+/// Kept in memory only.
+#[derive(Debug, Default)]
+pub struct PeerLatency {
+    rtt: DashMap<IrohIdentity, Duration>,
+}
+
+impl PeerLatency {
+    /// Create an empty tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fold in an RTT sample for a peer
+    pub fn record(&self, peer: IrohIdentity, sample: Duration) {
+        self.rtt
+            .entry(peer)
+            .and_modify(|rtt| *rtt = (*rtt * 7 + sample) / 8)
+            .or_insert(sample);
+    }
+
+    /// The smoothed RTT to a peer, if it was ever sampled
+    pub fn rtt(&self, peer: &IrohIdentity) -> Option<Duration> {
+        self.rtt.get(peer).map(|rtt| *rtt)
+    }
+}
+
+/// How up to date a member of an interface is, as seen from this node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberSyncStatus {
+    /// The member
+    pub peer: IrohIdentity,
+    /// Whether a transport connection to the member is open
+    pub connected: bool,
+    /// When we last exchanged sync state with the member for this
+    /// interface (Unix millis), if ever since startup
+    pub last_sync_millis: Option<i64>,
+    /// Smoothed round-trip time, if ever measured
+    pub rtt: Option<Duration>,
+    /// Our events in this interface the member has not acknowledged
+    pub pending_events: usize,
+}
+
+fn peer_hex(peer: &IrohIdentity) -> String {
+    hex::encode(peer.public_key().as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iroh::SecretKey;
+
+    fn peer() -> IrohIdentity {
+        IrohIdentity::new(SecretKey::generate(&mut rand::rng()).public())
+    }
+
+    #[test]
+    fn test_observations_keep_strongest_state() {
+        let local = peer();
+        let a = peer();
+        let mut snapshot = TopologySnapshot::new(&local, 10_000);
+        snapshot.observe_peer(&a, ConnectionState::Discovered, Some(9_000));
+        snapshot.observe_peer(&a, ConnectionState::Connected, None);
+        snapshot.observe_peer(&a, ConnectionState::Unreachable, None);
+        snapshot.observe_peer(&local, ConnectionState::Connected, None);
+
+        assert_eq!(snapshot.peers.len(), 1);
+        let seen = snapshot.peer(&peer_hex(&a)).unwrap();
+        assert_eq!(seen.state, ConnectionState::Connected);
+        assert_eq!(seen.last_seen_millis, Some(9_000));
+    }
+
+    #[test]
+    fn test_realm_links_carry_sync_recency() {
+        let local = peer();
+        let (a, b) = (peer(), peer());
+        let realm = InterfaceId::new([4; 32]);
+        let recency = SyncRecency::new();
+        recency.record(realm, a, 8_000);
+        recency.record(realm, a, 7_000);
+
+        let mut snapshot = TopologySnapshot::new(&local, 10_000);
+        snapshot.add_realm(&realm, &[local, a, b], false, &recency);
+
+        assert_eq!(snapshot.realms[0].peer_count, 2);
+        assert_eq!(snapshot.peers.len(), 2);
+        assert!(snapshot.peers.iter().all(|p| p.state == ConnectionState::Unreachable));
+        let link_a = snapshot.links.iter().find(|l| l.peer == peer_hex(&a)).unwrap();
+        assert_eq!(link_a.last_sync_millis, Some(8_000));
+
+        // b never synced; a synced 2s before the snapshot
+        assert_eq!(snapshot.stale_links(5_000).len(), 1);
+        assert_eq!(snapshot.stale_links(1_000).len(), 2);
+
+        recency.forget(&realm);
+        assert_eq!(recency.last_sync(&realm, &a), None);
+    }
+
+    #[test]
+    fn test_latency_is_smoothed() {
+        let a = peer();
+        let latency = PeerLatency::new();
+        assert_eq!(latency.rtt(&a), None);
+
+        latency.record(a, Duration::from_millis(80));
+        assert_eq!(latency.rtt(&a), Some(Duration::from_millis(80)));
+        latency.record(a, Duration::from_millis(160));
+        assert_eq!(latency.rtt(&a), Some(Duration::from_millis(90)));
+    }
+
+    #[test]
+    fn test_exports() {
+        let local = peer();
+        let a = peer();
+        let realm = InterfaceId::new([5; 32]);
+        let recency = SyncRecency::new();
+        recency.record(realm, a, 4_000);
+
+        let mut snapshot = TopologySnapshot::new(&local, 10_000);
+        snapshot.observe_peer(&a, ConnectionState::Connected, None);
+        snapshot.add_realm(&realm, &[a], true, &recency);
+
+        let json = snapshot.to_json().unwrap();
+        assert!(json.contains("\"event_type\":\"topology_snapshot\""));
+        assert!(json.contains("\"state\":\"connected\""));
+        let back: TopologySnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, snapshot);
+
+        let dot = snapshot.to_dot();
+        assert!(dot.starts_with("graph mesh {"));
+        assert!(dot.contains("color=darkgreen"));
+        assert!(dot.contains("style=dotted"));
+        assert!(dot.contains("[label=\"6s\"]"));
+    }
+}
@@ -0,0 +1,265 @@
+//! Organization trust roots for member verification
+//!
+//! Company deployments can pin an interface to an organization root PQ
+//! key. The organization signs a [`MemberCertificate`] for each member's
+//! verifying key, and the message handler checks every signed message for
+//! that interface against it:
+//!
+//! - **Verified** senders hold an unexpired certificate chaining to the
+//!   interface's root.
+//! - **Unverified** senders are either logged and recorded
+//!   ([`TrustPolicy::Flag`]) or dropped ([`TrustPolicy::Reject`]).
+//!
+//! Certificates travel between nodes as `NetworkMessage::MemberCertificate`
+//! after the handshake (see [`Capabilities::MEMBER_CERTIFICATES`]), so a
+//! member only has to be given its own certificate.
+//!
+//! The registry is local to each node and not persisted.
+//!
+//! [`Capabilities::MEMBER_CERTIFICATES`]: crate::handshake::Capabilities::MEMBER_CERTIFICATES
+
+use std::collections::HashSet;
+
+use dashmap::{DashMap, DashSet};
+use indras_core::InterfaceId;
+use indras_crypto::{MemberCertificate, PQPublicIdentity, org_id};
+
+/// What to do with senders that don't chain to the trust root
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TrustPolicy {
+    /// Accept their messages but log and record them
+    #[default]
+    Flag,
+    /// Drop their messages
+    Reject,
+}
+
+/// Outcome of checking a sender against an interface's trust root
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemberTrust {
+    /// The interface has no trust root
+    Unrooted,
+    /// The sender holds a valid certificate from the root
+    Verified,
+    /// Unverified, accepted under [`TrustPolicy::Flag`]
+    Flagged,
+    /// Unverified, dropped under [`TrustPolicy::Reject`]
+    Rejected,
+}
+
+impl MemberTrust {
+    /// Whether the sender's message should be processed
+    pub fn is_accepted(self) -> bool {
+        !matches!(self, MemberTrust::Rejected)
+    }
+}
+
+struct TrustRoot {
+    root_vk: PQPublicIdentity,
+    org_id: [u8; 32],
+    policy: TrustPolicy,
+}
+
+/// Per-interface trust roots and known member certificates
+#[derive(Default)]
+pub struct TrustRoots {
+    roots: DashMap<InterfaceId, TrustRoot>,
+    /// Certificates by (member user id, org id)
+    certificates: DashMap<([u8; 32], [u8; 32]), MemberCertificate>,
+    /// (member user id, org id) pairs whose signature has been checked
+    verified: DashSet<([u8; 32], [u8; 32])>,
+    /// This node's own certificates, shared with peers
+    own: DashMap<[u8; 32], MemberCertificate>,
+    /// Unverified senders seen per interface, by user id
+    flagged: DashMap<InterfaceId, HashSet<[u8; 32]>>,
+}
+
+impl TrustRoots {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pin an interface to an organization root
+    pub fn set_root(&self, interface_id: InterfaceId, root_vk: PQPublicIdentity, policy: TrustPolicy) {
+        let org_id = org_id(&root_vk);
+        self.roots.insert(
+            interface_id,
+            TrustRoot {
+                root_vk,
+                org_id,
+                policy,
+            },
+        );
+        self.flagged.remove(&interface_id);
+    }
+
+    /// Remove an interface's trust root. Returns `true` if one was set.
+    pub fn clear_root(&self, interface_id: &InterfaceId) -> bool {
+        self.flagged.remove(interface_id);
+        self.roots.remove(interface_id).is_some()
+    }
+
+    /// The organization id and policy an interface is pinned to
+    pub fn root(&self, interface_id: &InterfaceId) -> Option<([u8; 32], TrustPolicy)> {
+        self.roots.get(interface_id).map(|r| (r.org_id, r.policy))
+    }
+
+    /// Remember a member's certificate
+    ///
+    /// Signatures are checked lazily against the interface root when the
+    /// member is first seen there.
+    pub fn add_certificate(&self, certificate: MemberCertificate) {
+        let key = (certificate.member_user_id(), certificate.org_id);
+        let replaced = self.certificates.insert(key, certificate);
+        if replaced.is_some() {
+            self.verified.remove(&key);
+        }
+    }
+
+    /// Install a certificate for this node's own key
+    ///
+    /// It is also recorded as a known certificate, and sent to peers that
+    /// support [`Capabilities::MEMBER_CERTIFICATES`](crate::handshake::Capabilities::MEMBER_CERTIFICATES).
+    pub fn set_own_certificate(&self, certificate: MemberCertificate) {
+        self.own.insert(certificate.org_id, certificate.clone());
+        self.add_certificate(certificate);
+    }
+
+    /// This node's own certificates
+    pub fn own_certificates(&self) -> Vec<MemberCertificate> {
+        self.own.iter().map(|c| c.value().clone()).collect()
+    }
+
+    /// Check a sender's verifying key against an interface's trust root
+    ///
+    /// Unverified senders are recorded for [`Self::unverified_members`].
+    pub fn check(&self, interface_id: &InterfaceId, sender_vk: &[u8], now_millis: i64) -> MemberTrust {
+        let Some(root) = self.roots.get(interface_id) else {
+            return MemberTrust::Unrooted;
+        };
+        let user_id = *blake3::hash(sender_vk).as_bytes();
+        if self.is_certified(&root, user_id, now_millis) {
+            return MemberTrust::Verified;
+        }
+        let policy = root.policy;
+        drop(root);
+
+        self.flagged.entry(*interface_id).or_default().insert(user_id);
+        match policy {
+            TrustPolicy::Flag => MemberTrust::Flagged,
+            TrustPolicy::Reject => MemberTrust::Rejected,
+        }
+    }
+
+    fn is_certified(&self, root: &TrustRoot, user_id: [u8; 32], now_millis: i64) -> bool {
+        let key = (user_id, root.org_id);
+        let Some(certificate) = self.certificates.get(&key) else {
+            return false;
+        };
+        if certificate.is_expired(now_millis) {
+            return false;
+        }
+        if self.verified.contains(&key) {
+            return true;
+        }
+        let valid = certificate.member_user_id() == user_id && certificate.verify(&root.root_vk);
+        if valid {
+            self.verified.insert(key);
+        }
+        valid
+    }
+
+    /// User ids of unverified senders seen in an interface since its root was set
+    pub fn unverified_members(&self, interface_id: &InterfaceId) -> Vec<[u8; 32]> {
+        self.flagged
+            .get(interface_id)
+            .map(|f| f.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Drop all trust state for an interface
+    pub fn forget(&self, interface_id: &InterfaceId) {
+        self.clear_root(interface_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indras_crypto::PQIdentity;
+
+    const NOW: i64 = 1_700_000_000_000;
+
+    #[test]
+    fn test_unrooted_interface_accepts_everyone() {
+        let trust = TrustRoots::new();
+        let member = PQIdentity::generate();
+        let status = trust.check(&InterfaceId::new([1; 32]), &member.verifying_key_bytes(), NOW);
+        assert_eq!(status, MemberTrust::Unrooted);
+        assert!(status.is_accepted());
+    }
+
+    #[test]
+    fn test_certified_member_verified_others_rejected() {
+        let trust = TrustRoots::new();
+        let interface = InterfaceId::new([2; 32]);
+        let org = PQIdentity::generate();
+        let member = PQIdentity::generate();
+        let outsider = PQIdentity::generate();
+        trust.set_root(interface, org.verifying_key(), TrustPolicy::Reject);
+
+        trust.add_certificate(MemberCertificate::sign(
+            member.verifying_key_bytes(),
+            "alice",
+            NOW,
+            None,
+            &org,
+        ));
+
+        assert_eq!(
+            trust.check(&interface, &member.verifying_key_bytes(), NOW),
+            MemberTrust::Verified
+        );
+        let status = trust.check(&interface, &outsider.verifying_key_bytes(), NOW);
+        assert_eq!(status, MemberTrust::Rejected);
+        assert!(!status.is_accepted());
+        assert_eq!(trust.unverified_members(&interface), vec![outsider.user_id()]);
+    }
+
+    #[test]
+    fn test_flag_policy_and_foreign_or_expired_certificates() {
+        let trust = TrustRoots::new();
+        let interface = InterfaceId::new([3; 32]);
+        let org = PQIdentity::generate();
+        let other_org = PQIdentity::generate();
+        let member = PQIdentity::generate();
+        trust.set_root(interface, org.verifying_key(), TrustPolicy::Flag);
+
+        // Certificate from another organization doesn't count
+        trust.add_certificate(MemberCertificate::sign(
+            member.verifying_key_bytes(),
+            "bob",
+            NOW,
+            None,
+            &other_org,
+        ));
+        assert_eq!(
+            trust.check(&interface, &member.verifying_key_bytes(), NOW),
+            MemberTrust::Flagged
+        );
+
+        // Nor does an expired one from the right organization
+        trust.add_certificate(MemberCertificate::sign(
+            member.verifying_key_bytes(),
+            "bob",
+            NOW - 10,
+            Some(NOW - 1),
+            &org,
+        ));
+        assert_eq!(
+            trust.check(&interface, &member.verifying_key_bytes(), NOW),
+            MemberTrust::Flagged
+        );
+    }
+}
@@ -0,0 +1,331 @@
+//! Versioned wire envelope for [`NetworkMessage`]
+//!
+//! Protocol version 1 serialized messages as a bare postcard enum, so adding
+//! a field to any payload struct broke every older reader. From version 2 each
+//! message travels in an envelope:
+//!
+//! ```text
+//! ENVELOPE_MARKER | wire version | kind | body (length-prefixed payload)
+//! ```
+//!
+//! The body is decoded tolerantly:
+//!
+//! - **Newer sender, older reader:** fields appended after the ones the
+//!   reader knows are left unread in the body and ignored.
+//! - **Older sender, newer reader:** a body that ends early is padded with
+//!   zero bytes, so missing trailing fields decode as their zero value
+//!   (`None`, empty `Vec`/`String`, `0`, `false`).
+//! - **Unknown kinds** are reported as [`WireError::UnknownKind`] so the
+//!   receiver can drop them instead of treating the peer as broken.
+//!
+//! ## Evolving the format
+//!
+//! - Add fields only at the end of a top-level payload struct (the type
+//!   inside a `NetworkMessage` variant), and only with a zero-valued
+//!   default. Fields appended to nested structs shift the fields after them.
+//! - Add message types as new `NetworkMessage` variants at the end, with the
+//!   next number in [`kinds`]. Kind numbers are never reused.
+//! - The envelope layout itself is frozen; later protocol versions change
+//!   only what goes inside bodies.
+//!
+//! Senders pick the encoding per peer from the negotiated protocol version
+//! (see [`PeerProtocols::wire_version`]): peers that haven't completed the
+//! handshake get the version 1 encoding. Readers accept both.
+//!
+//! [`NetworkMessage`]: crate::message_handler::NetworkMessage
+//! [`PeerProtocols::wire_version`]: crate::handshake::PeerProtocols::wire_version
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::message_handler::NetworkMessage;
+
+/// Protocol version that sent bare postcard enums
+pub const WIRE_VERSION_LEGACY: u16 = 1;
+
+/// First protocol version that sends enveloped messages
+pub const WIRE_VERSION_ENVELOPE: u16 = 2;
+
+/// First byte of an unsigned envelope
+///
+/// Legacy messages start with a one-byte variant index below 0x80, so a
+/// byte with the high bit set can't be mistaken for one.
+pub const ENVELOPE_MARKER: u8 = 0xE1;
+
+/// First byte of a signed envelope
+///
+/// Legacy signed messages start with their signature version (1).
+pub const SIGNED_ENVELOPE_MARKER: u8 = 0xE2;
+
+/// Zero bytes appended to a short body before retrying the decode
+const FIELD_PADDING: usize = 256;
+
+/// Stable message kind numbers, one per `NetworkMessage` variant
+pub mod kinds {
+    pub const INTERFACE_EVENT: u16 = 0;
+    pub const SYNC_REQUEST: u16 = 1;
+    pub const SYNC_RESPONSE: u16 = 2;
+    pub const EVENT_ACK: u16 = 3;
+    pub const DTN_BUNDLE: u16 = 4;
+    pub const DTN_CUSTODY: u16 = 5;
+    pub const CAUSAL_EVENT: u16 = 6;
+    pub const DELEGATED: u16 = 7;
+    pub const ANONYMOUS_EVENT: u16 = 8;
+    pub const HELLO: u16 = 9;
+    pub const MEMBER_CERTIFICATE: u16 = 10;
+    pub const ANONYMOUS_RELAY: u16 = 11;
+}
+
+/// Wire encoding errors
+#[derive(Debug, Error)]
+pub enum WireError {
+    #[error("postcard: {0}")]
+    Postcard(#[from] postcard::Error),
+
+    #[error("not an envelope (first byte {0:#04x})")]
+    BadMarker(u8),
+
+    #[error("unknown message kind {kind} (wire version {version})")]
+    UnknownKind { version: u16, kind: u16 },
+
+    #[error("empty message")]
+    Empty,
+}
+
+/// Outer layout of an enveloped message
+#[derive(Serialize, Deserialize)]
+struct Envelope {
+    marker: u8,
+    version: u16,
+    kind: u16,
+    body: Vec<u8>,
+}
+
+/// Outer layout of a signed, enveloped message
+#[derive(Serialize, Deserialize)]
+pub(crate) struct SignedEnvelope {
+    pub marker: u8,
+    /// Signature scheme version
+    pub version: u8,
+    /// Envelope bytes covered by the signature
+    pub message: Vec<u8>,
+    pub signature: Vec<u8>,
+    pub sender_verifying_key: Vec<u8>,
+}
+
+/// Whether bytes hold an enveloped (version 2+) message
+pub fn is_envelope(data: &[u8]) -> bool {
+    data.first() == Some(&ENVELOPE_MARKER)
+}
+
+/// Encode a message for a peer speaking `version`
+pub fn encode(message: &NetworkMessage, version: u16) -> Result<Vec<u8>, WireError> {
+    if version < WIRE_VERSION_ENVELOPE {
+        return Ok(postcard::to_allocvec(message)?);
+    }
+    let (kind, body) = match message {
+        NetworkMessage::InterfaceEvent(m) => (kinds::INTERFACE_EVENT, postcard::to_allocvec(m)?),
+        NetworkMessage::SyncRequest(m) => (kinds::SYNC_REQUEST, postcard::to_allocvec(m)?),
+        NetworkMessage::SyncResponse(m) => (kinds::SYNC_RESPONSE, postcard::to_allocvec(m)?),
+        NetworkMessage::EventAck(m) => (kinds::EVENT_ACK, postcard::to_allocvec(m)?),
+        NetworkMessage::DtnBundle(m) => (kinds::DTN_BUNDLE, postcard::to_allocvec(m)?),
+        NetworkMessage::DtnCustody(m) => (kinds::DTN_CUSTODY, postcard::to_allocvec(m)?),
+        NetworkMessage::CausalEvent(m) => (kinds::CAUSAL_EVENT, postcard::to_allocvec(m)?),
+        NetworkMessage::Delegated(m) => (kinds::DELEGATED, postcard::to_allocvec(m)?),
+        NetworkMessage::AnonymousEvent(m) => (kinds::ANONYMOUS_EVENT, postcard::to_allocvec(m)?),
+        NetworkMessage::Hello(m) => (kinds::HELLO, postcard::to_allocvec(m)?),
+        NetworkMessage::MemberCertificate(m) => {
+            (kinds::MEMBER_CERTIFICATE, postcard::to_allocvec(m)?)
+        }
+        NetworkMessage::AnonymousRelay(m) => (kinds::ANONYMOUS_RELAY, postcard::to_allocvec(m)?),
+    };
+    let envelope = Envelope {
+        marker: ENVELOPE_MARKER,
+        version: version.max(WIRE_VERSION_ENVELOPE),
+        kind,
+        body,
+    };
+    Ok(postcard::to_allocvec(&envelope)?)
+}
+
+/// Decode a message in either encoding
+pub fn decode(data: &[u8]) -> Result<NetworkMessage, WireError> {
+    match data.first() {
+        None => Err(WireError::Empty),
+        Some(&ENVELOPE_MARKER) => decode_envelope(data),
+        Some(_) => Ok(postcard::from_bytes(data)?),
+    }
+}
+
+fn decode_envelope(data: &[u8]) -> Result<NetworkMessage, WireError> {
+    // Trailing bytes after the envelope are reserved for later versions
+    let (envelope, _) = postcard::take_from_bytes::<Envelope>(data)?;
+    if envelope.marker != ENVELOPE_MARKER {
+        return Err(WireError::BadMarker(envelope.marker));
+    }
+    let body = &envelope.body;
+    Ok(match envelope.kind {
+        kinds::INTERFACE_EVENT => NetworkMessage::InterfaceEvent(decode_body(body)?),
+        kinds::SYNC_REQUEST => NetworkMessage::SyncRequest(decode_body(body)?),
+        kinds::SYNC_RESPONSE => NetworkMessage::SyncResponse(decode_body(body)?),
+        kinds::EVENT_ACK => NetworkMessage::EventAck(decode_body(body)?),
+        kinds::DTN_BUNDLE => NetworkMessage::DtnBundle(decode_body(body)?),
+        kinds::DTN_CUSTODY => NetworkMessage::DtnCustody(decode_body(body)?),
+        kinds::CAUSAL_EVENT => NetworkMessage::CausalEvent(decode_body(body)?),
+        kinds::DELEGATED => NetworkMessage::Delegated(decode_body(body)?),
+        kinds::ANONYMOUS_EVENT => NetworkMessage::AnonymousEvent(decode_body(body)?),
+        kinds::HELLO => NetworkMessage::Hello(decode_body(body)?),
+        kinds::MEMBER_CERTIFICATE => NetworkMessage::MemberCertificate(decode_body(body)?),
+        kinds::ANONYMOUS_RELAY => NetworkMessage::AnonymousRelay(decode_body(body)?),
+        kind => {
+            return Err(WireError::UnknownKind {
+                version: envelope.version,
+                kind,
+            });
+        }
+    })
+}
+
+/// Decode a payload, ignoring unknown trailing fields and zero-filling
+/// missing ones
+pub fn decode_body<T: DeserializeOwned>(body: &[u8]) -> Result<T, WireError> {
+    match postcard::take_from_bytes::<T>(body) {
+        Ok((value, _unknown_fields)) => Ok(value),
+        Err(postcard::Error::DeserializeUnexpectedEnd) => {
+            let mut padded = Vec::with_capacity(body.len() + FIELD_PADDING);
+            padded.extend_from_slice(body);
+            padded.resize(body.len() + FIELD_PADDING, 0);
+            let (value, _) = postcard::take_from_bytes::<T>(&padded)?;
+            Ok(value)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handshake::HelloMessage;
+    use crate::message_handler::{EventAckMessage, InterfaceEventMessage};
+    use indras_core::{EventId, InterfaceId};
+
+    fn sample_messages() -> Vec<NetworkMessage> {
+        let interface_id = InterfaceId::new([4; 32]);
+        vec![
+            NetworkMessage::InterfaceEvent(InterfaceEventMessage::new(
+                interface_id,
+                vec![1, 2, 3],
+                EventId::new(9, 2),
+                [7; 12],
+            )),
+            NetworkMessage::EventAck(EventAckMessage {
+                interface_id,
+                up_to: EventId::new(9, 5),
+            }),
+            NetworkMessage::interface_event(
+                InterfaceEventMessage::new(interface_id, vec![4], EventId::new(3, 1), [1; 12]),
+                vec![EventId::new(9, 2)],
+            ),
+            NetworkMessage::Hello(HelloMessage::local(true)),
+            NetworkMessage::AnonymousRelay(InterfaceEventMessage::new(
+                interface_id,
+                vec![5, 6],
+                EventId::new(2, 8),
+                [3; 12],
+            )),
+        ]
+    }
+
+    #[test]
+    fn test_round_trip_both_versions() {
+        for message in sample_messages() {
+            for version in [WIRE_VERSION_LEGACY, WIRE_VERSION_ENVELOPE] {
+                let bytes = encode(&message, version).unwrap();
+                assert_eq!(is_envelope(&bytes), version >= WIRE_VERSION_ENVELOPE);
+                let decoded = decode(&bytes).unwrap();
+                assert_eq!(
+                    encode(&decoded, WIRE_VERSION_LEGACY).unwrap(),
+                    encode(&message, WIRE_VERSION_LEGACY).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_legacy_encoding_is_pinned() {
+        // Version 1 peers decode bare postcard enums; this must never change
+        let message = NetworkMessage::EventAck(EventAckMessage {
+            interface_id: InterfaceId::new([0; 32]),
+            up_to: EventId::new(1, 2),
+        });
+        let bytes = encode(&message, WIRE_VERSION_LEGACY).unwrap();
+        assert_eq!(bytes, postcard::to_allocvec(&message).unwrap());
+        assert_eq!(bytes[0], kinds::EVENT_ACK as u8);
+    }
+
+    #[test]
+    fn test_unknown_trailing_fields_ignored() {
+        #[derive(Serialize)]
+        struct EventAckV3 {
+            interface_id: InterfaceId,
+            up_to: EventId,
+            extra: Option<u64>,
+            note: String,
+        }
+        let newer = EventAckV3 {
+            interface_id: InterfaceId::new([2; 32]),
+            up_to: EventId::new(5, 6),
+            extra: Some(42),
+            note: "from the future".into(),
+        };
+        let envelope = Envelope {
+            marker: ENVELOPE_MARKER,
+            version: 3,
+            kind: kinds::EVENT_ACK,
+            body: postcard::to_allocvec(&newer).unwrap(),
+        };
+        let bytes = postcard::to_allocvec(&envelope).unwrap();
+
+        match decode(&bytes).unwrap() {
+            NetworkMessage::EventAck(ack) => assert_eq!(ack.up_to, EventId::new(5, 6)),
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_missing_trailing_fields_zero_filled() {
+        #[derive(Serialize)]
+        struct Old {
+            a: u32,
+        }
+        #[derive(Deserialize)]
+        struct New {
+            a: u32,
+            b: Option<u64>,
+            c: Vec<u8>,
+            d: bool,
+        }
+        let decoded: New = decode_body(&postcard::to_allocvec(&Old { a: 17 }).unwrap()).unwrap();
+        assert_eq!(decoded.a, 17);
+        assert_eq!(decoded.b, None);
+        assert!(decoded.c.is_empty());
+        assert!(!decoded.d);
+    }
+
+    #[test]
+    fn test_unknown_kind_reported() {
+        let envelope = Envelope {
+            marker: ENVELOPE_MARKER,
+            version: 3,
+            kind: 4000,
+            body: vec![1, 2, 3],
+        };
+        let bytes = postcard::to_allocvec(&envelope).unwrap();
+        assert!(matches!(
+            decode(&bytes),
+            Err(WireError::UnknownKind { version: 3, kind: 4000 })
+        ));
+        assert!(matches!(decode(&[]), Err(WireError::Empty)));
+    }
+}
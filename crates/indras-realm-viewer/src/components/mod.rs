@@ -3,6 +3,7 @@
 //! Redesigned 3-panel dashboard with frosted glass controls.
 
 use dioxus::prelude::*;
+use indras_ui::{format_number, tr};
 
 use crate::playback;
 use crate::state::{
@@ -100,21 +101,21 @@ pub fn App(state: Signal<AppState>) -> Element {
 /// Simplified header for Overview mode
 #[component]
 fn Header(state: Signal<AppState>) -> Element {
-    let tick = state.read().tick;
-    let total_events = state.read().total_events;
-    let realm_count = state.read().realms.realms.len();
-    let member_count = state.read().all_members().len();
+    let tick = format_number(state.read().tick as i64);
+    let total_events = format_number(state.read().total_events as i64);
+    let realm_count = format_number(state.read().realms.realms.len() as i64);
+    let member_count = format_number(state.read().all_members().len() as i64);
 
     rsx! {
         header { class: "header",
             div { class: "header-left",
-                h1 { class: "app-title", "Realm Viewer" }
+                h1 { class: "app-title", {tr("viewer-title")} }
             }
             div { class: "header-stats",
-                span { class: "stat", "Tick: ", span { class: "stat-value", "{tick}" } }
-                span { class: "stat", "Events: ", span { class: "stat-value", "{total_events}" } }
-                span { class: "stat", "Realms: ", span { class: "stat-value", "{realm_count}" } }
-                span { class: "stat", "Members: ", span { class: "stat-value", "{member_count}" } }
+                span { class: "stat", {tr("viewer-stat-tick")}, " ", span { class: "stat-value", "{tick}" } }
+                span { class: "stat", {tr("viewer-stat-events")}, " ", span { class: "stat-value", "{total_events}" } }
+                span { class: "stat", {tr("viewer-stat-realms")}, " ", span { class: "stat-value", "{realm_count}" } }
+                span { class: "stat", {tr("viewer-stat-members")}, " ", span { class: "stat-value", "{member_count}" } }
             }
         }
     }
@@ -131,6 +132,9 @@ fn LeftPanel(state: Signal<AppState>) -> Element {
             RealmsSection { state }
             MembersSection { state }
             NetworkTopology { state }
+            if !state.read().mesh.is_empty() {
+                MeshMap { state }
+            }
         }
     }
 }
@@ -303,6 +307,12 @@ fn CenterPanel(state: Signal<AppState>) -> Element {
             div { class: "quests-chat-row",
                 div { class: "quests-artifacts-column",
                     IntentionListPanel { state }
+                    if state.read().intentions.has_dependencies() {
+                        IntentionDependencyGraph { state }
+                    }
+                    if state.read().attention.event_count() > 0 {
+                        AttentionHeatmapPanel { state }
+                    }
                     SharedArtifactGalleryPanel { state }
                 }
                 ChatPanel { state }
@@ -434,6 +444,89 @@ fn NetworkTopology(state: Signal<AppState>) -> Element {
     }
 }
 
+/// Live mesh map merged from `topology_snapshot` events
+#[component]
+fn MeshMap(state: Signal<AppState>) -> Element {
+    let state_read = state.read();
+    let mesh = &state_read.mesh;
+    let nodes = mesh.nodes();
+    let edges = mesh.edges();
+
+    let width = 240.0_f64;
+    let height = 180.0_f64;
+    let center_x = width / 2.0;
+    let center_y = height / 2.0;
+    let radius = (height / 2.0 - 20.0).min(width / 2.5);
+    let angle_step = std::f64::consts::PI * 2.0 / nodes.len().max(1) as f64;
+    let angle_offset = -std::f64::consts::PI / 2.0;
+
+    let positions: Vec<(String, f64, f64)> = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, id)| {
+            let angle = angle_offset + (i as f64) * angle_step;
+            (id.clone(), center_x + radius * angle.cos(), center_y + radius * angle.sin())
+        })
+        .collect();
+    let position = |id: &str| positions.iter().find(|(n, _, _)| n == id).map(|(_, x, y)| (*x, *y));
+    let connected = edges.iter().filter(|e| e.state == "connected").count();
+    let lines: Vec<(String, f64, f64, f64, f64, usize)> = edges
+        .iter()
+        .filter_map(|e| {
+            let (x1, y1) = position(&e.a)?;
+            let (x2, y2) = position(&e.b)?;
+            Some((format!("mesh-edge {}", e.state), x1, y1, x2, y2, e.shared_realms))
+        })
+        .collect();
+
+    rsx! {
+        div { class: "network-container",
+            div { class: "network-header",
+                span { class: "network-title", "Mesh Map" }
+                span { class: "panel-count", "{nodes.len()} nodes, {connected} connected" }
+            }
+            div { class: "network-view",
+                svg {
+                    class: "network-svg",
+                    view_box: "0 0 {width} {height}",
+                    preserve_aspect_ratio: "xMidYMid meet",
+
+                    for (class, x1, y1, x2, y2, shared) in lines.iter() {
+                        line {
+                            class: "{class}",
+                            x1: "{x1}",
+                            y1: "{y1}",
+                            x2: "{x2}",
+                            y2: "{y2}",
+                            title { "{shared} shared realms" }
+                        }
+                    }
+
+                    for (id, x, y) in positions.iter() {
+                        {
+                            let label = mesh.label(id);
+                            let observer = mesh.views.contains_key(id);
+                            let node_class = if observer { "mesh-node observer" } else { "mesh-node" };
+
+                            rsx! {
+                                g { class: "{node_class}",
+                                    circle { cx: "{x}", cy: "{y}", r: "10" }
+                                    text {
+                                        class: "peer-node-label",
+                                        x: "{x}",
+                                        y: "{y + 20.0}",
+                                        "{label}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[component]
 fn IntentionListPanel(state: Signal<AppState>) -> Element {
     let state_read = state.read();
@@ -497,6 +590,200 @@ fn IntentionListPanel(state: Signal<AppState>) -> Element {
     }
 }
 
+/// Intention dependency graph, prerequisites to the left, colored by status
+#[component]
+fn IntentionDependencyGraph(state: Signal<AppState>) -> Element {
+    let state_read = state.read();
+    let intentions = &state_read.intentions;
+    let layers = intentions.dependency_layers();
+    let edges = intentions.dependency_edges();
+
+    let node_w = 96.0_f64;
+    let node_h = 22.0_f64;
+    let col_gap = 36.0_f64;
+    let row_gap = 10.0_f64;
+    let pad = 8.0_f64;
+    let rows = layers.iter().map(Vec::len).max().unwrap_or(1).max(1);
+    let width = pad * 2.0 + layers.len() as f64 * (node_w + col_gap) - col_gap;
+    let height = pad * 2.0 + rows as f64 * (node_h + row_gap) - row_gap;
+
+    let positions: Vec<(String, f64, f64)> = layers
+        .iter()
+        .enumerate()
+        .flat_map(|(col, layer)| {
+            let offset = (rows - layer.len()) as f64 * (node_h + row_gap) / 2.0;
+            layer.iter().enumerate().map(move |(row, id)| {
+                (
+                    id.clone(),
+                    pad + col as f64 * (node_w + col_gap),
+                    pad + offset + row as f64 * (node_h + row_gap),
+                )
+            })
+        })
+        .collect();
+    let position = |id: &str| positions.iter().find(|(n, _, _)| n == id).map(|(_, x, y)| (*x, *y));
+
+    let lines: Vec<(&'static str, f64, f64, f64, f64)> = edges
+        .iter()
+        .filter_map(|(prerequisite, dependent)| {
+            let (x1, y1) = position(prerequisite)?;
+            let (x2, y2) = position(dependent)?;
+            let done = intentions
+                .intentions
+                .get(prerequisite)
+                .is_some_and(|i| i.status == IntentionStatus::Completed);
+            let class = if done { "dependency-edge satisfied" } else { "dependency-edge" };
+            Some((class, x1 + node_w, y1 + node_h / 2.0, x2, y2 + node_h / 2.0))
+        })
+        .collect();
+
+    let nodes: Vec<(String, String, f64, f64)> = positions
+        .iter()
+        .filter_map(|(id, x, y)| {
+            let intention = intentions.intentions.get(id)?;
+            let status = match intention.status {
+                IntentionStatus::Open => "open",
+                IntentionStatus::Claimed => "claimed",
+                IntentionStatus::Verified => "verified",
+                IntentionStatus::Completed => "completed",
+            };
+            let blocked = if intentions.is_blocked(id) { " blocked" } else { "" };
+            Some((format!("dependency-node {status}{blocked}"), intention.title.clone(), *x, *y))
+        })
+        .collect();
+    let blocked = positions.iter().filter(|(id, _, _)| intentions.is_blocked(id)).count();
+
+    rsx! {
+        div { class: "dependency-graph-container",
+            div { class: "quest-list-header",
+                span { class: "quest-list-title", "Dependencies" }
+                span { class: "quest-list-sort", "{edges.len()} links • {blocked} blocked" }
+            }
+            div { class: "dependency-graph-view",
+                svg {
+                    class: "dependency-graph-svg",
+                    view_box: "0 0 {width} {height}",
+                    preserve_aspect_ratio: "xMinYMid meet",
+
+                    for (class, x1, y1, x2, y2) in lines.iter() {
+                        line {
+                            class: "{class}",
+                            x1: "{x1}",
+                            y1: "{y1}",
+                            x2: "{x2}",
+                            y2: "{y2}",
+                        }
+                    }
+
+                    for (class, name, x, y) in nodes.iter() {
+                        g { class: "{class}",
+                            rect {
+                                x: "{x}",
+                                y: "{y}",
+                                width: "{node_w}",
+                                height: "{node_h}",
+                                rx: "4",
+                            }
+                            text {
+                                x: "{x + 6.0}",
+                                y: "{y + node_h / 2.0 + 3.0}",
+                                "{truncate_label(name, 14)}"
+                            }
+                            title { "{name}" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Focus time per member over time, shaded by how much attention each
+/// member gave in each slice
+#[component]
+fn AttentionHeatmapPanel(state: Signal<AppState>) -> Element {
+    let heatmap = state.read().attention.heatmap(24);
+
+    let label_w = 56.0_f64;
+    let cell_w = 10.0_f64;
+    let cell_h = 14.0_f64;
+    let gap = 2.0_f64;
+    let width = label_w + heatmap.bucket_count() as f64 * (cell_w + gap);
+    let height = heatmap.members.len().max(1) as f64 * (cell_h + gap);
+    let max_ms = heatmap.max_cell_ms.max(1) as f64;
+
+    let rows: Vec<(String, &'static str, f64, Vec<(f64, f64, u32, u64)>)> = heatmap
+        .members
+        .iter()
+        .zip(heatmap.cells.iter())
+        .enumerate()
+        .map(|(row, (member, cells))| {
+            let y = row as f64 * (cell_h + gap);
+            let cells = cells
+                .iter()
+                .enumerate()
+                .map(|(col, ms)| {
+                    let x = label_w + col as f64 * (cell_w + gap);
+                    let tick = heatmap.start_tick + col as u32 * heatmap.bucket_ticks;
+                    let opacity = if *ms == 0 { 0.06 } else { 0.2 + 0.8 * (*ms as f64 / max_ms) };
+                    (x, opacity, tick, *ms)
+                })
+                .collect();
+            (member_name(member), member_color_var(member), y, cells)
+        })
+        .collect();
+
+    rsx! {
+        div { class: "attention-heatmap-container",
+            div { class: "quest-list-header",
+                span { class: "quest-list-title", "Attention Heatmap" }
+                span { class: "quest-list-sort", "{heatmap.bucket_ticks} ticks per column" }
+            }
+            div { class: "attention-heatmap-view",
+                svg {
+                    class: "attention-heatmap-svg",
+                    view_box: "0 0 {width} {height}",
+                    preserve_aspect_ratio: "xMinYMin meet",
+
+                    for (name, color, y, cells) in rows.iter() {
+                        g { class: "heatmap-row",
+                            text {
+                                class: "heatmap-label",
+                                x: "0",
+                                y: "{y + cell_h - 3.0}",
+                                "{truncate_label(name, 8)}"
+                            }
+                            for (x, opacity, tick, ms) in cells.iter() {
+                                rect {
+                                    class: "heatmap-cell",
+                                    x: "{x}",
+                                    y: "{y}",
+                                    width: "{cell_w}",
+                                    height: "{cell_h}",
+                                    rx: "2",
+                                    fill: "{color}",
+                                    fill_opacity: "{opacity}",
+                                    title { "{name} • tick {tick}: {format_duration_millis(*ms)}" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn truncate_label(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        text.to_string()
+    } else {
+        let mut out: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+        out.push('…');
+        out
+    }
+}
+
 #[component]
 fn IntentionCardWithAttention(intention: IntentionInfo, attention: IntentionAttention, max_attention: u64) -> Element {
     let secs = attention.total_attention_ms as f64 / 1000.0;
@@ -284,6 +284,66 @@ mod tests {
         assert!(matches!(event, StreamEvent::AttentionSwitched { .. }));
     }
 
+    #[test]
+    fn test_parse_quest_dependencies() {
+        let lines = [
+            r#"{"event_type":"quest_created","tick":1,"realm_id":"r1","quest_id":"design","creator":"peer1","title":"Design"}"#,
+            r#"{"event_type":"quest_created","tick":2,"realm_id":"r1","quest_id":"build","creator":"peer1","title":"Build","depends_on":["design"]}"#,
+            r#"{"event_type":"quest_created","tick":3,"realm_id":"r1","quest_id":"ship","creator":"peer2","title":"Ship"}"#,
+            r#"{"event_type":"quest_dependency_added","tick":4,"realm_id":"r1","quest_id":"ship","depends_on":"build"}"#,
+        ];
+        let mut intentions = crate::state::IntentionState::default();
+        for line in lines {
+            let event: StreamEvent = serde_json::from_str(line).unwrap();
+            intentions.process_event(&event);
+        }
+        assert!(intentions.has_dependencies());
+        assert_eq!(intentions.dependency_layers(), [["design"], ["build"], ["ship"]]);
+        assert!(intentions.is_blocked("build"));
+
+        let done = r#"{"event_type":"quest_completed","tick":5,"realm_id":"r1","quest_id":"design"}"#;
+        intentions.process_event(&serde_json::from_str(done).unwrap());
+        assert!(!intentions.is_blocked("build"));
+        assert!(intentions.is_blocked("ship"));
+    }
+
+    #[test]
+    fn test_attention_heatmap_buckets_focus() {
+        let lines = [
+            r#"{"event_type":"attention_switched","tick":0,"member":"peer1","quest_id":"q1"}"#,
+            r#"{"event_type":"attention_switched","tick":5,"member":"peer2","quest_id":"q1"}"#,
+            r#"{"event_type":"attention_cleared","tick":10,"member":"peer1"}"#,
+            r#"{"event_type":"attention_switched","tick":20,"member":"peer1","quest_id":"q2"}"#,
+        ];
+        let mut attention = crate::state::AttentionState::default();
+        for line in lines {
+            let event: StreamEvent = serde_json::from_str(line).unwrap();
+            attention.process_event(&event);
+        }
+
+        let heatmap = attention.heatmap(4);
+        assert_eq!(heatmap.members, ["peer1", "peer2"]);
+        assert_eq!(heatmap.bucket_ticks, 5);
+        // peer1 focused ticks 0-10, peer2 from tick 5 onward (still open)
+        assert_eq!(heatmap.cells[0], [500, 500, 0, 0]);
+        assert_eq!(heatmap.cells[1], [0, 500, 500, 500]);
+        assert_eq!(heatmap.max_cell_ms, 500);
+    }
+
+    #[test]
+    fn test_parse_topology_snapshot() {
+        // Shape produced by IndrasNode::topology_snapshot().to_json()
+        let json = r#"{"event_type":"topology_snapshot","local":"aa","taken_at_millis":5000,"peers":[{"id":"bb","short_id":"bb","state":"connected","last_seen_millis":null}],"realms":[{"id":"r1","peer_count":1,"sandbox":false}],"links":[{"realm":"r1","peer":"bb","last_sync_millis":4000}]}"#;
+        let event: StreamEvent = serde_json::from_str(json).unwrap();
+        let mut mesh = crate::state::MeshState::default();
+        mesh.process_event(&event);
+        assert_eq!(mesh.nodes(), ["aa", "bb"]);
+        let edges = mesh.edges();
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].state, "connected");
+        assert_eq!(edges[0].last_sync_millis, Some(4000));
+    }
+
     #[test]
     fn test_parse_unknown_event() {
         let json = r#"{"event_type":"some_unknown_type","data":"test"}"#;
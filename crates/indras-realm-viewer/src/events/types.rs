@@ -66,10 +66,24 @@ pub enum StreamEvent {
         creator: String,
         #[serde(default)]
         title: String,
+        /// Quests that must complete before this one
+        #[serde(default)]
+        depends_on: Vec<String>,
         #[serde(default)]
         latency_us: f64,
     },
 
+    #[serde(rename = "quest_dependency_added")]
+    QuestDependencyAdded {
+        #[serde(default)]
+        tick: u32,
+        #[serde(default)]
+        realm_id: String,
+        quest_id: String,
+        /// Quest that must complete first
+        depends_on: String,
+    },
+
     #[serde(rename = "quest_claim_submitted")]
     QuestClaimSubmitted {
         #[serde(default)]
@@ -628,6 +642,24 @@ pub enum StreamEvent {
         realm_id: Option<String>,
     },
 
+    // ========== Mesh Events ==========
+    /// Mesh topology as seen by one node (`IndrasNode::topology_snapshot`)
+    #[serde(rename = "topology_snapshot")]
+    TopologySnapshot {
+        #[serde(default)]
+        tick: u32,
+        /// Hex public key of the observing node
+        local: String,
+        #[serde(default)]
+        taken_at_millis: i64,
+        #[serde(default)]
+        peers: Vec<MeshPeer>,
+        #[serde(default)]
+        realms: Vec<MeshRealm>,
+        #[serde(default)]
+        links: Vec<MeshLink>,
+    },
+
     // ========== Info/Log Events ==========
     #[serde(rename = "info")]
     Info {
@@ -658,6 +690,7 @@ impl StreamEvent {
             StreamEvent::RealmAliasSet { tick, .. } => *tick,
             StreamEvent::ProfileUpdated { tick, .. } => *tick,
             StreamEvent::QuestCreated { tick, .. } => *tick,
+            StreamEvent::QuestDependencyAdded { tick, .. } => *tick,
             StreamEvent::QuestClaimSubmitted { tick, .. } => *tick,
             StreamEvent::QuestClaimVerified { tick, .. } => *tick,
             StreamEvent::QuestCompleted { tick, .. } => *tick,
@@ -698,6 +731,7 @@ impl StreamEvent {
             StreamEvent::ArtifactRecoveryRequested { tick, .. } => *tick,
             StreamEvent::ArtifactRecovered { tick, .. } => *tick,
             StreamEvent::DocumentEdit { tick, .. } => *tick,
+            StreamEvent::TopologySnapshot { tick, .. } => *tick,
             StreamEvent::Info { tick, .. } => *tick,
             StreamEvent::Unknown => 0,
         }
@@ -712,6 +746,7 @@ impl StreamEvent {
             StreamEvent::RealmAliasSet { .. } => "realm_alias_set",
             StreamEvent::ProfileUpdated { .. } => "profile_updated",
             StreamEvent::QuestCreated { .. } => "quest_created",
+            StreamEvent::QuestDependencyAdded { .. } => "quest_dependency_added",
             StreamEvent::QuestClaimSubmitted { .. } => "claim_submitted",
             StreamEvent::QuestClaimVerified { .. } => "claim_verified",
             StreamEvent::QuestCompleted { .. } => "quest_completed",
@@ -752,6 +787,7 @@ impl StreamEvent {
             StreamEvent::ArtifactRecoveryRequested { .. } => "artifact_recovery_requested",
             StreamEvent::ArtifactRecovered { .. } => "artifact_recovered",
             StreamEvent::DocumentEdit { .. } => "document_edit",
+            StreamEvent::TopologySnapshot { .. } => "topology_snapshot",
             StreamEvent::Info { .. } => "info",
             StreamEvent::Unknown => "unknown",
         }
@@ -767,6 +803,7 @@ impl StreamEvent {
             | StreamEvent::ProfileUpdated { .. } => EventCategory::Realm,
 
             StreamEvent::QuestCreated { .. }
+            | StreamEvent::QuestDependencyAdded { .. }
             | StreamEvent::QuestClaimSubmitted { .. }
             | StreamEvent::QuestClaimVerified { .. }
             | StreamEvent::QuestCompleted { .. } => EventCategory::Quest,
@@ -813,6 +850,7 @@ impl StreamEvent {
 
             StreamEvent::CrdtConverged { .. }
             | StreamEvent::CrdtConflict { .. }
+            | StreamEvent::TopologySnapshot { .. }
             | StreamEvent::Info { .. }
             | StreamEvent::Unknown => EventCategory::Info,
         }
@@ -886,6 +924,43 @@ pub struct GalleryEventItem {
     pub asset_path: Option<String>,
 }
 
+/// Peer in a topology snapshot
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct MeshPeer {
+    /// Hex public key
+    pub id: String,
+    #[serde(default)]
+    pub short_id: String,
+    /// `connected`, `discovered`, or `unreachable`
+    #[serde(default)]
+    pub state: String,
+    #[serde(default)]
+    pub last_seen_millis: Option<i64>,
+}
+
+/// Realm in a topology snapshot
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct MeshRealm {
+    /// Hex interface id
+    pub id: String,
+    #[serde(default)]
+    pub peer_count: usize,
+    #[serde(default)]
+    pub sandbox: bool,
+}
+
+/// Peer membership in a realm, from a topology snapshot
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct MeshLink {
+    /// Hex interface id
+    pub realm: String,
+    /// Hex public key
+    pub peer: String,
+    /// Last sync with the peer for this realm (Unix millis)
+    #[serde(default)]
+    pub last_sync_millis: Option<i64>,
+}
+
 /// Artifact item in a proof folder
 #[derive(Debug, Clone, Deserialize)]
 pub struct ProofArtifactItem {
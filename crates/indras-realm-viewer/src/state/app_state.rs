@@ -7,7 +7,7 @@ use std::sync::{Arc, Mutex};
 
 use crate::events::{StreamEvent, EventCategory};
 
-use super::{ArtifactState, AttentionState, ChatState, ContactsState, DocumentState, IntentionState, MemberProofDraftState, ProofFolderState, RealmState, TokenState};
+use super::{ArtifactState, AttentionState, ChatState, ContactsState, DocumentState, IntentionState, MemberProofDraftState, MeshState, ProofFolderState, RealmState, TokenState};
 
 /// Global event buffer for replay on reset
 static EVENT_BUFFER: std::sync::OnceLock<Arc<Mutex<Vec<StreamEvent>>>> = std::sync::OnceLock::new();
@@ -210,6 +210,9 @@ impl LoggedEvent {
                 let title_str = if title.is_empty() { quest_id.as_str() } else { title.as_str() };
                 format!("{} created \"{}\"", member_name(creator), title_str)
             }
+            StreamEvent::QuestDependencyAdded { quest_id, depends_on, .. } => {
+                format!("{} now depends on {}", short_id(quest_id), short_id(depends_on))
+            }
             StreamEvent::QuestClaimSubmitted { quest_id, claimant, .. } => {
                 format!("{} claimed {}", member_name(claimant), short_id(quest_id))
             }
@@ -361,6 +364,9 @@ impl LoggedEvent {
             StreamEvent::ArtifactRecovered { artifact_hash, recovered_from, .. } => {
                 format!("Recovered {} from {}", short_id(artifact_hash), member_name(recovered_from))
             }
+            StreamEvent::TopologySnapshot { local, peers, realms, .. } => {
+                format!("Mesh snapshot from {}: {} peers, {} realms", short_id(local), peers.len(), realms.len())
+            }
             StreamEvent::Unknown => "Unknown event".to_string(),
         };
 
@@ -415,6 +421,8 @@ pub struct AppState {
     pub tokens: TokenState,
     /// Document content tracking (CRDT edits)
     pub documents: DocumentState,
+    /// Mesh topology snapshots per observing node
+    pub mesh: MeshState,
     /// Per-member proof folder draft tracking (for V2 multi-column)
     pub member_proof_drafts: MemberProofDraftState,
     /// Recent events for log panel (newest first)
@@ -479,7 +487,8 @@ impl AppState {
                 self.realms.increment_quest_count(realm_id);
             }
 
-            StreamEvent::QuestClaimSubmitted { .. }
+            StreamEvent::QuestDependencyAdded { .. }
+            | StreamEvent::QuestClaimSubmitted { .. }
             | StreamEvent::QuestClaimVerified { .. }
             | StreamEvent::QuestCompleted { .. } => {
                 self.intentions.process_event(&event);
@@ -544,6 +553,10 @@ impl AppState {
                 self.documents.process_event(&event);
             }
 
+            StreamEvent::TopologySnapshot { .. } => {
+                self.mesh.process_event(&event);
+            }
+
             StreamEvent::Info { narrative, .. } => {
                 if let Some(n) = narrative {
                     if !n.is_empty() {
@@ -1,6 +1,7 @@
 //! Attention tracking state
 //!
-//! Tracks member focus on intentions and calculates attention rankings.
+//! Tracks member focus on intentions, calculates attention rankings, and
+//! buckets focus time per member for the attention heatmap.
 
 use std::collections::HashMap;
 
@@ -24,6 +25,27 @@ pub struct IntentionAttention {
     pub currently_focusing: Vec<String>,
 }
 
+/// Focus time per member over time
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AttentionHeatmap {
+    /// Members with any attention events, sorted
+    pub members: Vec<String>,
+    /// Tick the first bucket starts at
+    pub start_tick: u32,
+    /// Ticks covered by each bucket
+    pub bucket_ticks: u32,
+    /// Focus milliseconds, indexed `[member][bucket]`
+    pub cells: Vec<Vec<u64>>,
+    /// Largest cell, for scaling colors
+    pub max_cell_ms: u64,
+}
+
+impl AttentionHeatmap {
+    pub fn bucket_count(&self) -> usize {
+        self.cells.first().map_or(0, Vec::len)
+    }
+}
+
 /// Attention tracking state
 #[derive(Clone, Debug, Default)]
 pub struct AttentionState {
@@ -188,6 +210,59 @@ impl AttentionState {
         members
     }
 
+    /// Bucket each member's focus time into at most `buckets` columns
+    ///
+    /// Spans from the first attention event to the current tick. Open
+    /// focus windows count up to the current tick, so the heatmap follows
+    /// playback like the rankings do.
+    pub fn heatmap(&self, buckets: usize) -> AttentionHeatmap {
+        let Some(start_tick) = self.events.iter().map(|e| e.tick).min() else {
+            return AttentionHeatmap::default();
+        };
+        let end_tick = self
+            .events
+            .iter()
+            .map(|e| e.tick)
+            .max()
+            .unwrap_or(start_tick)
+            .max(self.current_tick);
+        let span = (end_tick - start_tick).max(1);
+        let bucket_ticks = span.div_ceil(buckets.max(1) as u32).max(1);
+        let bucket_count = span.div_ceil(bucket_ticks) as usize;
+
+        let mut members: Vec<String> = self.events.iter().map(|e| e.member.clone()).collect();
+        members.sort();
+        members.dedup();
+
+        let mut events: Vec<&AttentionEvent> = self.events.iter().collect();
+        events.sort_by_key(|e| e.tick);
+
+        let mut cells = vec![vec![0u64; bucket_count]; members.len()];
+        for (row, member) in members.iter().enumerate() {
+            let mut focus_since: Option<u32> = None;
+            for event in events.iter().filter(|e| &e.member == member) {
+                if let Some(from) = focus_since.take() {
+                    add_window(&mut cells[row], start_tick, bucket_ticks, from, event.tick);
+                }
+                if event.intention_id.is_some() {
+                    focus_since = Some(event.tick);
+                }
+            }
+            if let Some(from) = focus_since {
+                add_window(&mut cells[row], start_tick, bucket_ticks, from, end_tick);
+            }
+        }
+
+        let max_cell_ms = cells.iter().flatten().copied().max().unwrap_or(0);
+        AttentionHeatmap {
+            members,
+            start_tick,
+            bucket_ticks,
+            cells,
+            max_cell_ms,
+        }
+    }
+
     /// Get total events
     pub fn event_count(&self) -> usize {
         self.events.len()
@@ -206,3 +281,13 @@ impl AttentionState {
             .collect()
     }
 }
+
+/// Spread the focus window `[from, to)` across buckets, 100 ms per tick
+fn add_window(row: &mut [u64], start_tick: u32, bucket_ticks: u32, from: u32, to: u32) {
+    for (bucket, cell) in row.iter_mut().enumerate() {
+        let bucket_start = start_tick + bucket as u32 * bucket_ticks;
+        let bucket_end = bucket_start + bucket_ticks;
+        let overlap = to.min(bucket_end).saturating_sub(from.max(bucket_start));
+        *cell += overlap as u64 * 100;
+    }
+}
@@ -1,8 +1,9 @@
 //! Intention tracking state
 //!
-//! Tracks intentions with proof-of-service claims.
+//! Tracks intentions with proof-of-service claims and the dependencies
+//! between them.
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
 use crate::events::StreamEvent;
 
@@ -57,6 +58,8 @@ pub struct IntentionInfo {
     pub status: IntentionStatus,
     pub created_at_tick: u32,
     pub completed_at_tick: Option<u32>,
+    /// Intentions that must complete before this one
+    pub depends_on: Vec<String>,
 }
 
 impl IntentionInfo {
@@ -113,6 +116,7 @@ impl IntentionState {
                 quest_id,
                 creator,
                 title,
+                depends_on,
                 ..
             } => {
                 let intention = IntentionInfo {
@@ -128,6 +132,7 @@ impl IntentionState {
                     status: IntentionStatus::Open,
                     created_at_tick: *tick,
                     completed_at_tick: None,
+                    depends_on: Vec::new(),
                 };
 
                 self.intentions.insert(quest_id.clone(), intention);
@@ -135,6 +140,17 @@ impl IntentionState {
                     .entry(realm_id.clone())
                     .or_default()
                     .push(quest_id.clone());
+                for dependency in depends_on {
+                    self.add_dependency(quest_id, dependency);
+                }
+            }
+
+            StreamEvent::QuestDependencyAdded {
+                quest_id,
+                depends_on,
+                ..
+            } => {
+                self.add_dependency(quest_id, depends_on);
             }
 
             StreamEvent::QuestClaimSubmitted {
@@ -184,6 +200,94 @@ impl IntentionState {
         }
     }
 
+    /// Record that `intention_id` waits on `depends_on`
+    ///
+    /// Self-dependencies and duplicates are ignored.
+    fn add_dependency(&mut self, intention_id: &str, depends_on: &str) {
+        if intention_id == depends_on {
+            return;
+        }
+        if let Some(intention) = self.intentions.get_mut(intention_id) {
+            if !intention.depends_on.iter().any(|d| d == depends_on) {
+                intention.depends_on.push(depends_on.to_string());
+            }
+        }
+    }
+
+    /// Whether any intention depends on another
+    pub fn has_dependencies(&self) -> bool {
+        self.intentions.values().any(|i| !i.depends_on.is_empty())
+    }
+
+    /// Whether an intention waits on a dependency that isn't done
+    ///
+    /// Dependencies the viewer has not seen created count as unfinished.
+    pub fn is_blocked(&self, intention_id: &str) -> bool {
+        self.intentions.get(intention_id).is_some_and(|i| {
+            i.depends_on.iter().any(|d| {
+                self.intentions
+                    .get(d)
+                    .is_none_or(|dep| dep.status != IntentionStatus::Completed)
+            })
+        })
+    }
+
+    /// Dependency edges as (prerequisite, dependent) between known intentions
+    pub fn dependency_edges(&self) -> Vec<(String, String)> {
+        let mut edges: Vec<(String, String)> = self
+            .intentions
+            .values()
+            .flat_map(|i| {
+                i.depends_on
+                    .iter()
+                    .filter(|d| self.intentions.contains_key(*d))
+                    .map(|d| (d.clone(), i.intention_id.clone()))
+            })
+            .collect();
+        edges.sort();
+        edges
+    }
+
+    /// Intentions in the dependency graph, layered for drawing
+    ///
+    /// Layer 0 holds intentions with no known prerequisites; every
+    /// other intention sits one layer below its deepest prerequisite.
+    /// Intentions with no edges are left out. Cycles are cut at the
+    /// number of intentions so they can't loop forever.
+    pub fn dependency_layers(&self) -> Vec<Vec<String>> {
+        let edges = self.dependency_edges();
+        let nodes: BTreeSet<&String> = edges.iter().flat_map(|(a, b)| [a, b]).collect();
+        let mut depth: HashMap<&String, usize> = nodes.iter().map(|n| (*n, 0)).collect();
+        for _ in 0..nodes.len() {
+            let mut changed = false;
+            for (prerequisite, dependent) in &edges {
+                let next = depth[prerequisite] + 1;
+                if next > depth[dependent] && next < nodes.len() {
+                    depth.insert(dependent, next);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let layer_count = depth.values().max().map_or(0, |d| d + 1);
+        let mut layers = vec![Vec::new(); layer_count];
+        for node in nodes {
+            layers[depth[node]].push(node.clone());
+        }
+        for layer in &mut layers {
+            layer.sort_by_key(|id| {
+                (
+                    self.intentions.get(id).map_or(0, |i| i.created_at_tick),
+                    id.clone(),
+                )
+            });
+        }
+        layers
+    }
+
     /// Get intentions by status
     pub fn intentions_by_status(&self, status: IntentionStatus) -> Vec<&IntentionInfo> {
         self.intentions
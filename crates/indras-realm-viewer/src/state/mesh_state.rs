@@ -0,0 +1,127 @@
+//! Mesh topology tracking
+//!
+//! Keeps the latest topology snapshot from each observing node and merges
+//! them into one undirected peer graph for the mesh map.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use indras_ui::short_id;
+
+use crate::events::{MeshLink, MeshPeer, MeshRealm, StreamEvent};
+
+/// Latest topology snapshot from one node
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MeshView {
+    pub tick: u32,
+    pub taken_at_millis: i64,
+    pub peers: Vec<MeshPeer>,
+    pub realms: Vec<MeshRealm>,
+    pub links: Vec<MeshLink>,
+}
+
+/// Connection between two nodes in the merged mesh
+#[derive(Clone, Debug, PartialEq)]
+pub struct MeshEdge {
+    pub a: String,
+    pub b: String,
+    /// Best state either side reported: `connected`, `discovered`, or `unreachable`
+    pub state: String,
+    /// Realms the observer shares with the peer
+    pub shared_realms: usize,
+    /// Most recent sync either side reported
+    pub last_sync_millis: Option<i64>,
+}
+
+/// Mesh topology state across all observing nodes
+#[derive(Clone, Debug, Default)]
+pub struct MeshState {
+    /// Latest view per observing node (hex public key)
+    pub views: BTreeMap<String, MeshView>,
+}
+
+fn state_rank(state: &str) -> u8 {
+    match state {
+        "connected" => 2,
+        "discovered" => 1,
+        _ => 0,
+    }
+}
+
+impl MeshState {
+    /// Process a topology snapshot event
+    pub fn process_event(&mut self, event: &StreamEvent) {
+        if let StreamEvent::TopologySnapshot {
+            tick,
+            local,
+            taken_at_millis,
+            peers,
+            realms,
+            links,
+        } = event
+        {
+            self.views.insert(
+                local.clone(),
+                MeshView {
+                    tick: *tick,
+                    taken_at_millis: *taken_at_millis,
+                    peers: peers.clone(),
+                    realms: realms.clone(),
+                    links: links.clone(),
+                },
+            );
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.views.is_empty()
+    }
+
+    /// Every observer and every peer they reported, sorted
+    pub fn nodes(&self) -> Vec<String> {
+        let mut nodes: BTreeSet<String> = self.views.keys().cloned().collect();
+        for view in self.views.values() {
+            nodes.extend(view.peers.iter().map(|p| p.id.clone()));
+        }
+        nodes.into_iter().collect()
+    }
+
+    /// Display label for a node, preferring the short id peers reported
+    pub fn label(&self, id: &str) -> String {
+        self.views
+            .values()
+            .flat_map(|v| v.peers.iter())
+            .find(|p| p.id == id && !p.short_id.is_empty())
+            .map(|p| p.short_id.clone())
+            .unwrap_or_else(|| short_id(id))
+    }
+
+    /// Undirected edges, merging what both ends reported
+    pub fn edges(&self) -> Vec<MeshEdge> {
+        let mut edges: BTreeMap<(String, String), MeshEdge> = BTreeMap::new();
+        for (local, view) in &self.views {
+            for peer in &view.peers {
+                let key = if *local < peer.id {
+                    (local.clone(), peer.id.clone())
+                } else {
+                    (peer.id.clone(), local.clone())
+                };
+                let links: Vec<&MeshLink> =
+                    view.links.iter().filter(|l| l.peer == peer.id).collect();
+                let last_sync = links.iter().filter_map(|l| l.last_sync_millis).max();
+                let edge = edges.entry(key.clone()).or_insert_with(|| MeshEdge {
+                    a: key.0.clone(),
+                    b: key.1.clone(),
+                    state: peer.state.clone(),
+                    shared_realms: 0,
+                    last_sync_millis: None,
+                });
+                if state_rank(&peer.state) > state_rank(&edge.state) {
+                    edge.state = peer.state.clone();
+                }
+                edge.shared_realms = edge.shared_realms.max(links.len());
+                edge.last_sync_millis = edge.last_sync_millis.max(last_sync);
+            }
+        }
+        edges.into_values().collect()
+    }
+}
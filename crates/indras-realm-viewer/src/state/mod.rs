@@ -1,6 +1,6 @@
 //! State management for Realm Viewer
 //!
-//! Provides reactive state tracking for realms, intentions, attention, contacts, chat, artifacts, and proof folders.
+//! Provides reactive state tracking for realms, intentions, attention, contacts, chat, artifacts, proof folders, and mesh topology.
 
 pub mod app_state;
 pub mod artifact_state;
@@ -9,6 +9,7 @@ pub mod chat_state;
 pub mod contacts_state;
 pub mod document_state;
 pub mod intention_state;
+pub mod mesh_state;
 pub mod proof_folder_state;
 pub mod realm_state;
 pub mod token_state;
@@ -20,6 +21,7 @@ pub use chat_state::*;
 pub use contacts_state::*;
 pub use document_state::*;
 pub use intention_state::*;
+pub use mesh_state::*;
 pub use proof_folder_state::*;
 pub use realm_state::*;
 pub use token_state::*;
@@ -0,0 +1,116 @@
+//! Byte sink behind the append-only logs
+//!
+//! Both [`EventLog`](super::EventLog) and [`NodeLog`](crate::NodeLog) write
+//! length-prefixed frames and address them by byte offset. [`LogBacking`]
+//! lets the same framing run against a file or a volatile buffer.
+
+use std::io::SeekFrom;
+
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+use crate::error::StorageError;
+
+/// Where a log's frames live
+pub(crate) enum LogBacking {
+    /// An open file, appended at the end
+    File(File),
+    /// An in-memory buffer; lost on drop
+    Memory(Vec<u8>),
+}
+
+impl LogBacking {
+    /// Append a length-prefixed frame, returning the offset it was written at
+    pub(crate) async fn append_frame(
+        &mut self,
+        payload: &[u8],
+        sync: bool,
+    ) -> Result<u64, StorageError> {
+        let len_bytes = (payload.len() as u32).to_be_bytes();
+
+        match self {
+            Self::File(file) => {
+                let offset = file
+                    .seek(SeekFrom::End(0))
+                    .await
+                    .map_err(|e| StorageError::Io(e.to_string()))?;
+
+                file.write_all(&len_bytes)
+                    .await
+                    .map_err(|e| StorageError::Io(e.to_string()))?;
+                file.write_all(payload)
+                    .await
+                    .map_err(|e| StorageError::Io(e.to_string()))?;
+
+                if sync {
+                    file.sync_data()
+                        .await
+                        .map_err(|e| StorageError::Io(e.to_string()))?;
+                }
+
+                Ok(offset)
+            }
+            Self::Memory(buf) => {
+                let offset = buf.len() as u64;
+                buf.extend_from_slice(&len_bytes);
+                buf.extend_from_slice(payload);
+                Ok(offset)
+            }
+        }
+    }
+
+    /// Read the frame starting at `offset`
+    pub(crate) async fn read_frame(&self, offset: u64) -> Result<Vec<u8>, StorageError> {
+        match self {
+            Self::File(file) => {
+                let mut file = file
+                    .try_clone()
+                    .await
+                    .map_err(|e| StorageError::Io(e.to_string()))?;
+
+                file.seek(SeekFrom::Start(offset))
+                    .await
+                    .map_err(|e| StorageError::Io(e.to_string()))?;
+
+                // Read length
+                let mut len_buf = [0u8; 4];
+                file.read_exact(&mut len_buf)
+                    .await
+                    .map_err(|e| StorageError::Io(e.to_string()))?;
+
+                let entry_len = u32::from_be_bytes(len_buf) as usize;
+
+                // Read entry
+                let mut entry_buf = vec![0u8; entry_len];
+                file.read_exact(&mut entry_buf)
+                    .await
+                    .map_err(|e| StorageError::Io(e.to_string()))?;
+
+                Ok(entry_buf)
+            }
+            Self::Memory(buf) => {
+                let start = offset as usize;
+                let len_bytes: [u8; 4] = buf
+                    .get(start..start + 4)
+                    .and_then(|b| b.try_into().ok())
+                    .ok_or_else(|| StorageError::Io("offset past end of log".into()))?;
+                let entry_len = u32::from_be_bytes(len_bytes) as usize;
+
+                buf.get(start + 4..start + 4 + entry_len)
+                    .map(<[u8]>::to_vec)
+                    .ok_or_else(|| StorageError::Io("truncated log entry".into()))
+            }
+        }
+    }
+
+    /// Flush everything to durable storage (no-op in memory)
+    pub(crate) async fn sync_all(&self) -> Result<(), StorageError> {
+        match self {
+            Self::File(file) => file
+                .sync_all()
+                .await
+                .map_err(|e| StorageError::Io(e.to_string())),
+            Self::Memory(_) => Ok(()),
+        }
+    }
+}
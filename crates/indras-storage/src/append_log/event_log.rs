@@ -3,18 +3,18 @@
 //! Provides per-interface append-only event logs with efficient seeking.
 
 use std::collections::BTreeMap;
-use std::io::SeekFrom;
 use std::path::PathBuf;
 
 use bytes::Bytes;
 use serde::{Deserialize, Serialize};
 use tokio::fs::{File, OpenOptions};
-use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt, BufReader};
+use tokio::io::{AsyncReadExt, BufReader};
 use tokio::sync::RwLock;
 use tracing::{debug, info, instrument, warn};
 
 use indras_core::{EventId, InterfaceId, PeerIdentity};
 
+use super::backing::LogBacking;
 use crate::error::StorageError;
 
 /// Configuration for an event log
@@ -28,6 +28,8 @@ pub struct EventLogConfig {
     pub sync_on_write: bool,
     /// Index entries to keep in memory
     pub index_cache_size: usize,
+    /// Keep log entries in memory instead of on disk; `base_dir` is ignored
+    pub in_memory: bool,
 }
 
 impl Default for EventLogConfig {
@@ -37,6 +39,7 @@ impl Default for EventLogConfig {
             max_segment_size: 100 * 1024 * 1024, // 100MB
             sync_on_write: true,
             index_cache_size: 10000,
+            in_memory: false,
         }
     }
 }
@@ -112,8 +115,8 @@ pub struct EventLog<I: PeerIdentity> {
     interface_id: InterfaceId,
     /// Configuration
     config: EventLogConfig,
-    /// Current log file
-    log_file: RwLock<Option<File>>,
+    /// Current log file (or in-memory buffer)
+    log_file: RwLock<Option<LogBacking>>,
     /// Path to the current log file
     log_path: PathBuf,
     /// In-memory index: event_id -> file offset
@@ -134,15 +137,21 @@ impl<I: PeerIdentity> EventLog<I> {
         config: EventLogConfig,
     ) -> Result<Self, StorageError> {
         // Ensure base directory exists
-        tokio::fs::create_dir_all(&config.base_dir)
-            .await
-            .map_err(|e| StorageError::Io(e.to_string()))?;
+        if !config.in_memory {
+            tokio::fs::create_dir_all(&config.base_dir)
+                .await
+                .map_err(|e| StorageError::Io(e.to_string()))?;
+        }
 
         let log_path = config
             .base_dir
             .join(format!("{}.log", hex::encode(interface_id.as_bytes())));
 
-        info!(path = %log_path.display(), "Opening event log");
+        if config.in_memory {
+            debug!("Opening in-memory event log");
+        } else {
+            info!(path = %log_path.display(), "Opening event log");
+        }
 
         let log = Self {
             interface_id,
@@ -163,6 +172,11 @@ impl<I: PeerIdentity> EventLog<I> {
 
     /// Open the log file and replay to build index
     async fn open_and_replay(&self) -> Result<(), StorageError> {
+        if self.config.in_memory {
+            *self.log_file.write().await = Some(LogBacking::Memory(Vec::new()));
+            return Ok(());
+        }
+
         let file = OpenOptions::new()
             .read(true)
             .write(true)
@@ -184,7 +198,7 @@ impl<I: PeerIdentity> EventLog<I> {
             self.replay_from_file(&file, file_size).await?;
         }
 
-        *self.log_file.write().await = Some(file);
+        *self.log_file.write().await = Some(LogBacking::File(file));
         *self.offset.write().await = file_size;
 
         let seq = *self.sequence.read().await;
@@ -298,27 +312,9 @@ impl<I: PeerIdentity> EventLog<I> {
             .as_mut()
             .ok_or_else(|| StorageError::Io("Log file not open".into()))?;
 
-        // Write length prefix
-        let len_bytes = (serialized.len() as u32).to_be_bytes();
-
-        // Seek to end
         let offset = file
-            .seek(SeekFrom::End(0))
-            .await
-            .map_err(|e| StorageError::Io(e.to_string()))?;
-
-        file.write_all(&len_bytes)
-            .await
-            .map_err(|e| StorageError::Io(e.to_string()))?;
-        file.write_all(&serialized)
-            .await
-            .map_err(|e| StorageError::Io(e.to_string()))?;
-
-        if self.config.sync_on_write {
-            file.sync_data()
-                .await
-                .map_err(|e| StorageError::Io(e.to_string()))?;
-        }
+            .append_frame(&serialized, self.config.sync_on_write)
+            .await?;
 
         // Update index
         self.index.write().await.insert(entry.event_id, offset);
@@ -350,28 +346,7 @@ impl<I: PeerIdentity> EventLog<I> {
             .as_ref()
             .ok_or_else(|| StorageError::Io("Log file not open".into()))?;
 
-        let mut file = file
-            .try_clone()
-            .await
-            .map_err(|e| StorageError::Io(e.to_string()))?;
-
-        file.seek(SeekFrom::Start(offset))
-            .await
-            .map_err(|e| StorageError::Io(e.to_string()))?;
-
-        // Read length
-        let mut len_buf = [0u8; 4];
-        file.read_exact(&mut len_buf)
-            .await
-            .map_err(|e| StorageError::Io(e.to_string()))?;
-
-        let entry_len = u32::from_be_bytes(len_buf) as usize;
-
-        // Read entry
-        let mut entry_buf = vec![0u8; entry_len];
-        file.read_exact(&mut entry_buf)
-            .await
-            .map_err(|e| StorageError::Io(e.to_string()))?;
+        let entry_buf = file.read_frame(offset).await?;
 
         postcard::from_bytes(&entry_buf).map_err(|e| StorageError::Deserialization(e.to_string()))
     }
@@ -431,9 +406,7 @@ impl<I: PeerIdentity> EventLog<I> {
     pub async fn close(&self) -> Result<(), StorageError> {
         let mut file_guard = self.log_file.write().await;
         if let Some(file) = file_guard.take() {
-            file.sync_all()
-                .await
-                .map_err(|e| StorageError::Io(e.to_string()))?;
+            file.sync_all().await?;
         }
         Ok(())
     }
@@ -547,4 +520,28 @@ mod tests {
             assert_eq!(entry.sequence, 2);
         }
     }
+
+    #[tokio::test]
+    async fn test_in_memory_log() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_dir = temp_dir.path().join("logs");
+        let config = EventLogConfig {
+            base_dir: base_dir.clone(),
+            in_memory: true,
+            ..Default::default()
+        };
+        let log: EventLog<SimulationIdentity> =
+            EventLog::new(InterfaceId::new([0x07; 32]), config).await.unwrap();
+
+        for i in 0..4 {
+            log.append(EventId::new(1, i), Bytes::from(format!("m{}", i)))
+                .await
+                .unwrap();
+        }
+
+        let entry = log.read_event(EventId::new(1, 2)).await.unwrap().unwrap();
+        assert_eq!(entry.payload, Bytes::from("m2"));
+        assert_eq!(log.read_since(1).await.unwrap().len(), 3);
+        assert!(!base_dir.exists());
+    }
 }
@@ -16,7 +16,10 @@
 //! ```text
 //! [4 bytes: len][len bytes: serialized event][4 bytes: len][...]
 //! ```
+//!
+//! With `EventLogConfig::in_memory` the same frames go to a volatile buffer.
 
+pub(crate) mod backing;
 mod compaction;
 pub mod event_log;
 
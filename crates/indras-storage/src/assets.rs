@@ -0,0 +1,354 @@
+//! Content-addressed asset cache
+//!
+//! Small shared assets such as profile avatars and custom realm emoji are
+//! referenced by their BLAKE3 hash and fetched from peers on demand. The
+//! [`AssetCache`] keeps fetched assets in a dedicated [`BlobStore`] so each
+//! one is downloaded once and reused across realms and restarts.
+//!
+//! The cache is bounded by total size and evicts the least recently used
+//! assets first. Fetched bytes are checked against the requested hash
+//! before they are stored, so a misbehaving peer cannot poison the cache,
+//! and concurrent requests for the same asset share a single fetch.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! let cache = AssetCache::new(AssetCacheConfig::with_base_dir("./data/assets")).await?;
+//!
+//! let avatar = cache
+//!     .get_or_fetch(hash, || async move { fetch_from_peer(hash).await })
+//!     .await?;
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use dashmap::DashMap;
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+use crate::blobs::{BlobStore, BlobStoreConfig, ContentRef};
+use crate::error::StorageError;
+
+/// Configuration for the asset cache
+#[derive(Debug, Clone)]
+pub struct AssetCacheConfig {
+    /// Backing blob store (kept separate from the node's main blobs)
+    pub blobs: BlobStoreConfig,
+    /// Total size of cached assets before eviction (bytes)
+    pub max_bytes: u64,
+    /// Largest single asset accepted (bytes)
+    pub max_asset_size: u64,
+}
+
+impl Default for AssetCacheConfig {
+    fn default() -> Self {
+        Self {
+            blobs: BlobStoreConfig {
+                base_dir: PathBuf::from("./data/assets"),
+                shard_depth: 1,
+                ..Default::default()
+            },
+            max_bytes: 64 * 1024 * 1024,     // 64MB
+            max_asset_size: 4 * 1024 * 1024, // 4MB
+        }
+    }
+}
+
+impl AssetCacheConfig {
+    /// Store assets under `base_dir`
+    pub fn with_base_dir(base_dir: impl Into<PathBuf>) -> Self {
+        let mut config = Self::default();
+        config.blobs.base_dir = base_dir.into();
+        config
+    }
+
+    /// Keep assets in memory only
+    pub fn in_memory() -> Self {
+        let mut config = Self::default();
+        config.blobs.in_memory = true;
+        config
+    }
+}
+
+/// Recency index over cached assets
+#[derive(Default)]
+struct LruIndex {
+    /// Size and last-use tick per asset
+    entries: HashMap<[u8; 32], (u64, u64)>,
+    /// Assets by last-use tick, oldest first
+    order: BTreeMap<u64, [u8; 32]>,
+    /// Sum of cached asset sizes
+    total_bytes: u64,
+    /// Monotonic use counter
+    tick: u64,
+}
+
+impl LruIndex {
+    /// Record a use of an asset, adding it if new
+    fn touch(&mut self, hash: [u8; 32], size: u64) {
+        self.tick += 1;
+        match self.entries.insert(hash, (size, self.tick)) {
+            Some((_, old_tick)) => {
+                self.order.remove(&old_tick);
+            }
+            None => self.total_bytes += size,
+        }
+        self.order.insert(self.tick, hash);
+    }
+
+    fn contains(&self, hash: &[u8; 32]) -> bool {
+        self.entries.contains_key(hash)
+    }
+
+    /// Remove least recently used assets until `max_bytes` is respected
+    fn evict_to(&mut self, max_bytes: u64) -> Vec<ContentRef> {
+        let mut evicted = Vec::new();
+        while self.total_bytes > max_bytes {
+            let Some((_, hash)) = self.order.pop_first() else {
+                break;
+            };
+            if let Some((size, _)) = self.entries.remove(&hash) {
+                self.total_bytes -= size;
+                evicted.push(ContentRef::new(hash, size));
+            }
+        }
+        evicted
+    }
+}
+
+/// Size-bounded, LRU-evicting cache of content-addressed assets
+pub struct AssetCache {
+    config: AssetCacheConfig,
+    blobs: BlobStore,
+    index: Mutex<LruIndex>,
+    /// One lock per asset being fetched, so concurrent misses fetch once
+    inflight: DashMap<[u8; 32], Arc<Mutex<()>>>,
+}
+
+impl AssetCache {
+    /// Open the cache, indexing assets left by a previous run
+    ///
+    /// Recency is not persisted, so existing assets start out in arbitrary
+    /// order and the oldest are evicted if the limit shrank.
+    pub async fn new(config: AssetCacheConfig) -> Result<Self, StorageError> {
+        let blobs = BlobStore::new(config.blobs.clone()).await?;
+
+        let mut index = LruIndex::default();
+        for content_ref in blobs.list_all().await? {
+            index.touch(content_ref.hash, content_ref.size);
+        }
+        let evicted = index.evict_to(config.max_bytes);
+
+        let cache = Self {
+            config,
+            blobs,
+            index: Mutex::new(index),
+            inflight: DashMap::new(),
+        };
+        cache.delete_all(evicted).await;
+        Ok(cache)
+    }
+
+    /// Get a cached asset, marking it recently used
+    pub async fn get(&self, hash: &[u8; 32]) -> Option<Bytes> {
+        let size = {
+            let index = self.index.lock().await;
+            index.entries.get(hash).map(|(size, _)| *size)?
+        };
+        match self.blobs.load(&ContentRef::new(*hash, size)).await {
+            Ok(data) => {
+                self.index.lock().await.touch(*hash, size);
+                Some(data)
+            }
+            Err(e) => {
+                // Lost or corrupted on disk: forget it so it is refetched
+                warn!(hash = %hex::encode(&hash[..4]), error = %e, "Dropping unreadable asset");
+                let mut index = self.index.lock().await;
+                if let Some((size, tick)) = index.entries.remove(hash) {
+                    index.order.remove(&tick);
+                    index.total_bytes -= size;
+                }
+                None
+            }
+        }
+    }
+
+    /// Whether an asset is cached
+    pub async fn contains(&self, hash: &[u8; 32]) -> bool {
+        self.index.lock().await.contains(hash)
+    }
+
+    /// Add an asset, returning its content reference
+    pub async fn insert(&self, data: &[u8]) -> Result<ContentRef, StorageError> {
+        if data.len() as u64 > self.config.max_asset_size {
+            return Err(StorageError::CapacityExceeded);
+        }
+        let content_ref = self.blobs.store(data).await?;
+
+        let evicted = {
+            let mut index = self.index.lock().await;
+            index.touch(content_ref.hash, content_ref.size);
+            index.evict_to(self.config.max_bytes)
+        };
+        self.delete_all(evicted).await;
+        Ok(content_ref)
+    }
+
+    /// Get an asset, calling `fetch` to obtain it on a miss
+    ///
+    /// The fetched bytes must hash to `hash`; otherwise they are discarded
+    /// and a deserialization error is returned. Concurrent calls for the
+    /// same hash wait for the first fetch instead of starting their own.
+    pub async fn get_or_fetch<F, Fut>(&self, hash: [u8; 32], fetch: F) -> Result<Bytes, StorageError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Bytes, StorageError>>,
+    {
+        if let Some(data) = self.get(&hash).await {
+            return Ok(data);
+        }
+
+        let lock = self.inflight.entry(hash).or_default().clone();
+        let _guard = lock.lock().await;
+
+        // Another caller may have fetched it while we waited
+        if let Some(data) = self.get(&hash).await {
+            return Ok(data);
+        }
+
+        let result = self.fetch_and_insert(hash, fetch).await;
+        self.inflight.remove(&hash);
+        result
+    }
+
+    async fn fetch_and_insert<F, Fut>(&self, hash: [u8; 32], fetch: F) -> Result<Bytes, StorageError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Bytes, StorageError>>,
+    {
+        let data = fetch().await?;
+        if ContentRef::from_data(&data).hash != hash {
+            warn!(hash = %hex::encode(&hash[..4]), "Fetched asset does not match its hash");
+            return Err(StorageError::Deserialization("Hash mismatch".into()));
+        }
+        self.insert(&data).await?;
+        debug!(hash = %hex::encode(&hash[..4]), size = data.len(), "Fetched asset");
+        Ok(data)
+    }
+
+    /// Drop an asset from the cache. Returns `true` if it was cached.
+    pub async fn remove(&self, hash: &[u8; 32]) -> Result<bool, StorageError> {
+        let removed = {
+            let mut index = self.index.lock().await;
+            index.entries.remove(hash).map(|(size, tick)| {
+                index.order.remove(&tick);
+                index.total_bytes -= size;
+                size
+            })
+        };
+        match removed {
+            Some(size) => self.blobs.delete(&ContentRef::new(*hash, size)).await,
+            None => Ok(false),
+        }
+    }
+
+    /// Total size of cached assets (bytes)
+    pub async fn total_bytes(&self) -> u64 {
+        self.index.lock().await.total_bytes
+    }
+
+    /// Number of cached assets
+    pub async fn len(&self) -> usize {
+        self.index.lock().await.entries.len()
+    }
+
+    /// Whether the cache holds no assets
+    pub async fn is_empty(&self) -> bool {
+        self.len().await == 0
+    }
+
+    async fn delete_all(&self, evicted: Vec<ContentRef>) {
+        for content_ref in evicted {
+            debug!(hash = %content_ref.short_hash(), size = content_ref.size, "Evicted asset");
+            if let Err(e) = self.blobs.delete(&content_ref).await {
+                warn!(hash = %content_ref.short_hash(), error = %e, "Failed to delete evicted asset");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn config(max_bytes: u64) -> AssetCacheConfig {
+        AssetCacheConfig {
+            max_bytes,
+            ..AssetCacheConfig::in_memory()
+        }
+    }
+
+    fn hash(data: &[u8]) -> [u8; 32] {
+        ContentRef::from_data(data).hash
+    }
+
+    #[tokio::test]
+    async fn test_fetches_once() {
+        let cache = AssetCache::new(config(1024)).await.unwrap();
+        let calls = AtomicUsize::new(0);
+        let fetch = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Ok(Bytes::from_static(b"avatar"))
+        };
+
+        let a = cache.get_or_fetch(hash(b"avatar"), fetch).await.unwrap();
+        let b = cache.get_or_fetch(hash(b"avatar"), fetch).await.unwrap();
+        assert_eq!(a, b);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_mismatched_content() {
+        let cache = AssetCache::new(config(1024)).await.unwrap();
+        let result = cache
+            .get_or_fetch(hash(b"real"), || async { Ok(Bytes::from_static(b"forged")) })
+            .await;
+        assert!(result.is_err());
+        assert!(cache.is_empty().await);
+    }
+
+    #[tokio::test]
+    async fn test_evicts_least_recently_used() {
+        let cache = AssetCache::new(config(10)).await.unwrap();
+        cache.insert(b"aaaa").await.unwrap();
+        cache.insert(b"bbbb").await.unwrap();
+
+        // Touch "aaaa" so "bbbb" is the oldest
+        assert!(cache.get(&hash(b"aaaa")).await.is_some());
+        cache.insert(b"cccc").await.unwrap();
+
+        assert!(cache.contains(&hash(b"aaaa")).await);
+        assert!(!cache.contains(&hash(b"bbbb")).await);
+        assert!(cache.contains(&hash(b"cccc")).await);
+        assert_eq!(cache.total_bytes().await, 8);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_asset_rejected() {
+        let cache = AssetCache::new(AssetCacheConfig {
+            max_asset_size: 2,
+            ..config(1024)
+        })
+        .await
+        .unwrap();
+        assert!(matches!(
+            cache.insert(b"abc").await,
+            Err(StorageError::CapacityExceeded)
+        ));
+    }
+}
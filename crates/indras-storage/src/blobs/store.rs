@@ -1,11 +1,13 @@
 //! Blob store implementation
 //!
-//! File-based content-addressed storage using BLAKE3 hashing.
+//! File-based content-addressed storage using BLAKE3 hashing, with an
+//! optional volatile in-memory mode.
 
 use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
 
 use bytes::Bytes;
+use dashmap::DashMap;
 use tokio::fs::{self, File};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tracing::{debug, info, instrument, warn};
@@ -22,6 +24,8 @@ pub struct BlobStoreConfig {
     pub shard_depth: u8,
     /// Maximum blob size (bytes)
     pub max_blob_size: u64,
+    /// Keep blobs in memory instead of on disk; `base_dir` is ignored
+    pub in_memory: bool,
 }
 
 impl Default for BlobStoreConfig {
@@ -30,6 +34,7 @@ impl Default for BlobStoreConfig {
             base_dir: PathBuf::from("./data/blobs"),
             shard_depth: 2,                   // e.g., ab/cd/abcdef...
             max_blob_size: 100 * 1024 * 1024, // 100MB
+            in_memory: false,
         }
     }
 }
@@ -37,11 +42,21 @@ impl Default for BlobStoreConfig {
 /// Content-addressed blob store
 pub struct BlobStore {
     config: BlobStoreConfig,
+    /// Blob contents when running in memory
+    memory: Option<DashMap<[u8; 32], Bytes>>,
 }
 
 impl BlobStore {
     /// Create a new blob store
     pub async fn new(config: BlobStoreConfig) -> Result<Self, StorageError> {
+        if config.in_memory {
+            debug!("In-memory blob store initialized");
+            return Ok(Self {
+                config,
+                memory: Some(DashMap::new()),
+            });
+        }
+
         // Ensure base directory exists
         fs::create_dir_all(&config.base_dir)
             .await
@@ -49,7 +64,10 @@ impl BlobStore {
 
         info!(path = %config.base_dir.display(), "Blob store initialized");
 
-        Ok(Self { config })
+        Ok(Self {
+            config,
+            memory: None,
+        })
     }
 
     /// Store content and return its reference
@@ -67,6 +85,12 @@ impl BlobStore {
             return Ok(content_ref);
         }
 
+        if let Some(memory) = &self.memory {
+            memory.insert(content_ref.hash, Bytes::copy_from_slice(data));
+            debug!(hash = %content_ref.short_hash(), "Stored blob in memory");
+            return Ok(content_ref);
+        }
+
         let path = self.blob_path(&content_ref);
 
         // Ensure parent directory exists
@@ -108,6 +132,13 @@ impl BlobStore {
     /// Load content by reference
     #[instrument(skip(self), fields(hash = %content_ref.short_hash()))]
     pub async fn load(&self, content_ref: &ContentRef) -> Result<Bytes, StorageError> {
+        if let Some(memory) = &self.memory {
+            return memory
+                .get(&content_ref.hash)
+                .map(|data| data.clone())
+                .ok_or_else(|| StorageError::PacketNotFound(content_ref.hash_hex()));
+        }
+
         let path = self.blob_path(content_ref);
 
         let mut file = File::open(&path).await.map_err(|e| {
@@ -139,6 +170,10 @@ impl BlobStore {
 
     /// Check if content exists
     pub async fn exists(&self, content_ref: &ContentRef) -> Result<bool, StorageError> {
+        if let Some(memory) = &self.memory {
+            return Ok(memory.contains_key(&content_ref.hash));
+        }
+
         let path = self.blob_path(content_ref);
         Ok(path.exists())
     }
@@ -146,6 +181,10 @@ impl BlobStore {
     /// Delete content by reference
     #[instrument(skip(self), fields(hash = %content_ref.short_hash()))]
     pub async fn delete(&self, content_ref: &ContentRef) -> Result<bool, StorageError> {
+        if let Some(memory) = &self.memory {
+            return Ok(memory.remove(&content_ref.hash).is_some());
+        }
+
         let path = self.blob_path(content_ref);
 
         match fs::remove_file(&path).await {
@@ -158,6 +197,11 @@ impl BlobStore {
         }
     }
 
+    /// Whether blobs are held in memory rather than on disk
+    pub fn is_in_memory(&self) -> bool {
+        self.memory.is_some()
+    }
+
     /// Get the file path for a content reference
     fn blob_path(&self, content_ref: &ContentRef) -> PathBuf {
         let hash_hex = content_ref.hash_hex();
@@ -180,6 +224,13 @@ impl BlobStore {
 
     /// List all blobs (for debugging/maintenance)
     pub async fn list_all(&self) -> Result<Vec<ContentRef>, StorageError> {
+        if let Some(memory) = &self.memory {
+            return Ok(memory
+                .iter()
+                .map(|entry| ContentRef::new(*entry.key(), entry.value().len() as u64))
+                .collect());
+        }
+
         let mut refs = Vec::new();
         self.collect_blobs(&self.config.base_dir, &mut refs).await?;
         Ok(refs)
@@ -385,4 +436,31 @@ mod tests {
         assert!(!store.exists(&ref2).await.unwrap());
         assert!(store.exists(&ref3).await.unwrap());
     }
+
+    #[tokio::test]
+    async fn test_in_memory_store() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_dir = temp_dir.path().join("blobs");
+        let store = BlobStore::new(BlobStoreConfig {
+            base_dir: base_dir.clone(),
+            in_memory: true,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        assert!(store.is_in_memory());
+
+        let keep = store.store(b"resident").await.unwrap();
+        let drop = store.store(b"transient").await.unwrap();
+        assert_eq!(store.load(&keep).await.unwrap(), Bytes::from_static(b"resident"));
+        assert_eq!(store.total_size().await.unwrap(), 17);
+
+        let result = store.gc(|r| r.content_equals(&keep)).await.unwrap();
+        assert_eq!(result.deleted_count, 1);
+        assert!(matches!(
+            store.load(&drop).await,
+            Err(StorageError::PacketNotFound(_))
+        ));
+        assert!(!base_dir.exists());
+    }
 }
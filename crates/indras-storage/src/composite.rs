@@ -2,7 +2,7 @@
 //!
 //! This module provides [`CompositeStorage`], which unifies:
 //! - Append-only event logs
-//! - Structured storage (redb)
+//! - Structured storage (redb by default, see [`StructuredBackend`])
 //! - Content-addressed blobs
 //!
 //! ## Storage Flow
@@ -11,13 +11,20 @@
 //! Event arrives → CompositeStorage.append_event()
 //!   ├─ If payload > 4KB: store in BlobStore, get ContentRef
 //!   ├─ Append to EventLog (with ContentRef if applicable)
-//!   └─ Update redb indices (event_index, pending_delivery)
+//!   └─ Update structured indices (event_index, pending_delivery)
 //!
 //! Sync with peer → CompositeStorage.events_since()
-//!   ├─ Query redb for event IDs since their heads
+//!   ├─ Query structured store for event IDs since their heads
 //!   ├─ Read events from EventLog
 //!   └─ Resolve ContentRefs from BlobStore if needed
 //!
+//! Crash recovery → CompositeStorage.recover_intents()
+//!   └─ Roll forward / back whatever the IntentJournal still holds
+//!
+//! Corruption → CompositeStorage.open_with_recovery()
+//!   ├─ Quarantine corrupted log tails and an unopenable database
+//!   └─ Rebuild interface records and the node log index from surviving logs
+//!
 //! Bootstrap new peer → CompositeStorage.load_interface()
 //!   ├─ Load latest snapshot from BlobStore
 //!   ├─ Replay recent events from EventLog since snapshot
@@ -26,22 +33,39 @@
 
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Instant;
 
 use bytes::Bytes;
 use dashmap::DashMap;
-use tracing::{debug, info, instrument};
+use tracing::{debug, info, instrument, warn};
 
 use indras_core::{EventId, InterfaceId, PeerIdentity};
+use indras_logging::metrics;
 
 use crate::append_log::{EventLog, EventLogConfig, EventLogEntry};
-use crate::node_log::NodeLog;
+use crate::node_log::{NodeLog, NodeLogEntry};
 use crate::blobs::{BlobStore, BlobStoreConfig, ContentRef};
 use crate::error::StorageError;
+use crate::journal::{Intent, IntentJournal, IntentRecord, RecoveryReport};
+use crate::repair::{self, RepairReport, StorageComponent};
+use crate::seen_events::SeenEventStore;
+use crate::translations::TranslationStore;
+use crate::job_runs::JobRunStore;
 use crate::structured::{
-    InterfaceRecord, InterfaceStore, MembershipRecord, PeerRecord, PeerRegistry, RedbStorage,
-    RedbStorageConfig, SyncStateStore,
+    ALL_TABLES, InMemoryStructuredStore, InterfaceRecord, InterfaceStore, MembershipRecord,
+    PeerRecord, PeerRegistry, RedbStorage, RedbStorageConfig, ScanResults, StructuredBackend,
+    StructuredStore, SyncStateStore, Table, INTENT_JOURNAL, INTERFACES,
 };
 
+/// A copy of the structured tables without interface keys
+#[derive(Debug, Clone, Default)]
+pub struct StructuredExport {
+    /// Every entry of every table
+    pub tables: Vec<(Table, ScanResults)>,
+    /// Entries an interface key was removed from, as `<table>/<hex key>`
+    pub keys_removed: Vec<String>,
+}
+
 /// Configuration for composite storage
 #[derive(Debug, Clone)]
 pub struct CompositeStorageConfig {
@@ -49,7 +73,9 @@ pub struct CompositeStorageConfig {
     pub base_dir: PathBuf,
     /// Event log configuration
     pub event_log: EventLogConfig,
-    /// redb configuration
+    /// Structured storage backend
+    pub structured: StructuredBackend,
+    /// redb configuration, used when `structured` is [`StructuredBackend::Redb`]
     pub redb: RedbStorageConfig,
     /// Blob store configuration
     pub blobs: BlobStoreConfig,
@@ -66,6 +92,7 @@ impl Default for CompositeStorageConfig {
                 base_dir: base_dir.join("logs"),
                 ..Default::default()
             },
+            structured: StructuredBackend::Redb,
             redb: RedbStorageConfig {
                 db_path: base_dir.join("indras.redb"),
                 ..Default::default()
@@ -89,6 +116,7 @@ impl CompositeStorageConfig {
                 base_dir: base_dir.join("logs"),
                 ..Default::default()
             },
+            structured: StructuredBackend::Redb,
             redb: RedbStorageConfig {
                 db_path: base_dir.join("indras.redb"),
                 ..Default::default()
@@ -100,6 +128,32 @@ impl CompositeStorageConfig {
             blob_threshold: 4096,
         }
     }
+
+    /// Create a fully volatile configuration
+    ///
+    /// Event logs, the node log, blobs and structured tables all live in
+    /// memory; nothing is read from or written to disk and all data is lost
+    /// when the storage is dropped.
+    pub fn in_memory() -> Self {
+        let mut config = Self::with_base_dir(PathBuf::new());
+        config.event_log.in_memory = true;
+        config.blobs.in_memory = true;
+        config.structured = StructuredBackend::InMemory;
+        config
+    }
+
+    /// Whether every layer is configured to stay off disk
+    pub fn is_in_memory(&self) -> bool {
+        self.event_log.in_memory
+            && self.blobs.in_memory
+            && matches!(self.structured, StructuredBackend::InMemory)
+    }
+
+    /// Select the structured storage backend
+    pub fn with_structured_backend(mut self, backend: StructuredBackend) -> Self {
+        self.structured = backend;
+        self
+    }
 }
 
 /// Composite storage unifying all three storage layers
@@ -107,7 +161,7 @@ pub struct CompositeStorage<I: PeerIdentity> {
     /// Per-interface event logs
     event_logs: DashMap<InterfaceId, Arc<EventLog<I>>>,
     /// Structured storage
-    redb: Arc<RedbStorage>,
+    structured: Arc<dyn StructuredStore>,
     /// Peer registry
     peer_registry: PeerRegistry,
     /// Interface store
@@ -118,6 +172,16 @@ pub struct CompositeStorage<I: PeerIdentity> {
     blobs: Arc<BlobStore>,
     /// Node-level event log
     node_log: Arc<NodeLog>,
+    /// Write-ahead journal for multi-step operations
+    journal: IntentJournal,
+    /// Recently delivered event IDs, for duplicate suppression
+    seen_events: SeenEventStore,
+    /// Locally cached message translations
+    translations: TranslationStore,
+    /// Last-run times of the node's background jobs
+    job_runs: JobRunStore,
+    /// What recovery mode found, if storage was opened with it
+    repair_report: Option<RepairReport>,
     /// Configuration
     config: CompositeStorageConfig,
 }
@@ -126,36 +190,134 @@ impl<I: PeerIdentity> CompositeStorage<I> {
     /// Create a new composite storage
     #[instrument(skip(config), fields(base_dir = %config.base_dir.display()))]
     pub async fn new(config: CompositeStorageConfig) -> Result<Self, StorageError> {
-        // Ensure base directory exists
-        tokio::fs::create_dir_all(&config.base_dir)
-            .await
-            .map_err(|e| StorageError::Io(e.to_string()))?;
+        Self::create_base_dir(&config).await?;
+
+        // Open structured storage
+        let structured: Arc<dyn StructuredStore> = match &config.structured {
+            StructuredBackend::Redb => Arc::new(RedbStorage::open(config.redb.clone())?),
+            StructuredBackend::InMemory => Arc::new(InMemoryStructuredStore::new()),
+            StructuredBackend::Custom(store) => Arc::clone(store),
+        };
+        Self::assemble(config, structured, None).await
+    }
+
+    /// Open storage, quarantining corrupted components instead of failing
+    ///
+    /// Scans every event log and the node log, cuts corrupted tails back to
+    /// their valid prefix and replaces a database that fails to open with an
+    /// empty one. Corrupted files are kept next to the originals; see
+    /// [`repair`](crate::repair). The outcome is available from
+    /// [`repair_report`](Self::repair_report).
+    ///
+    /// Slower than [`new`](Self::new), since every log is read in full.
+    #[instrument(skip(config), fields(base_dir = %config.base_dir.display()))]
+    pub async fn open_with_recovery(config: CompositeStorageConfig) -> Result<Self, StorageError> {
+        if config.is_in_memory() {
+            return Self::new(config).await;
+        }
+        Self::create_base_dir(&config).await?;
+        let mut report = RepairReport::default();
+
+        let (event_logs, node_log) = if config.event_log.in_memory {
+            (Vec::new(), None)
+        } else {
+            let event_logs =
+                repair::repair_event_logs::<I>(&config.event_log.base_dir, &mut report).await?;
+            let node_log = repair::repair_log::<NodeLogEntry>(
+                &config.base_dir.join("node_log.bin"),
+                StorageComponent::NodeLog,
+                &mut report,
+            )
+            .await?;
+            if node_log.truncated {
+                report.lost.push("tail of the node log".to_string());
+            }
+            (event_logs, Some(node_log))
+        };
+
+        let (structured, replaced): (Arc<dyn StructuredStore>, bool) = match &config.structured {
+            StructuredBackend::Redb => {
+                let (db, replaced) = repair::open_redb(&config.redb, &mut report).await?;
+                (Arc::new(db), replaced)
+            }
+            StructuredBackend::InMemory => (Arc::new(InMemoryStructuredStore::new()), false),
+            StructuredBackend::Custom(store) => (Arc::clone(store), false),
+        };
+
+        // Rebuild what the surviving logs can tell us
+        if replaced {
+            let interface_store = InterfaceStore::new(structured.clone());
+            repair::rebuild_interfaces(&interface_store, &event_logs, &mut report)?;
+        }
+        if let Some(node_log) = node_log
+            && (replaced || node_log.truncated)
+        {
+            report.node_log_entries_reindexed = NodeLog::reindex(structured.as_ref(), &node_log)?;
+        }
+
+        if !report.is_clean() {
+            warn!(
+                quarantined = report.quarantined.len(),
+                interfaces_rebuilt = report.interfaces_rebuilt,
+                lost = ?report.lost,
+                "Opened storage in recovery mode"
+            );
+        }
+        Self::assemble(config, structured, Some(report)).await
+    }
+
+    async fn create_base_dir(config: &CompositeStorageConfig) -> Result<(), StorageError> {
+        if !config.is_in_memory() {
+            tokio::fs::create_dir_all(&config.base_dir)
+                .await
+                .map_err(|e| StorageError::Io(e.to_string()))?;
+        }
+        Ok(())
+    }
 
-        // Open redb
-        let redb = Arc::new(RedbStorage::open(config.redb.clone())?);
+    async fn assemble(
+        config: CompositeStorageConfig,
+        structured: Arc<dyn StructuredStore>,
+        repair_report: Option<RepairReport>,
+    ) -> Result<Self, StorageError> {
+        debug!(backend = structured.backend_name(), "Structured storage opened");
 
         // Create stores
-        let peer_registry = PeerRegistry::new(redb.clone());
-        let interface_store = InterfaceStore::new(redb.clone());
-        let sync_state = SyncStateStore::new(redb.clone());
+        let peer_registry = PeerRegistry::new(structured.clone());
+        let interface_store = InterfaceStore::new(structured.clone());
+        let sync_state = SyncStateStore::new(structured.clone());
 
         // Open blob store
         let blobs = Arc::new(BlobStore::new(config.blobs.clone()).await?);
 
         // Open node log
-        let node_log = NodeLog::open(&config.base_dir, redb.clone()).await?;
+        let node_log = if config.event_log.in_memory {
+            NodeLog::in_memory(structured.clone())
+        } else {
+            NodeLog::open(&config.base_dir, structured.clone()).await?
+        };
         let node_log = Arc::new(node_log);
 
+        let journal = IntentJournal::open(structured.clone())?;
+        let seen_events = SeenEventStore::open(structured.clone())?;
+        let translations = TranslationStore::new(structured.clone());
+        let job_runs = JobRunStore::new(structured.clone());
+
         info!("Composite storage initialized");
 
         Ok(Self {
             event_logs: DashMap::new(),
-            redb,
+            structured,
             peer_registry,
             interface_store,
             sync_state,
             blobs,
             node_log,
+            journal,
+            seen_events,
+            translations,
+            job_runs,
+            repair_report,
             config,
         })
     }
@@ -184,6 +346,18 @@ impl<I: PeerIdentity> CompositeStorage<I> {
         interface_id: &InterfaceId,
         event_id: EventId,
         payload: Bytes,
+    ) -> Result<u64, StorageError> {
+        let began = Instant::now();
+        let result = self.append_event_inner(interface_id, event_id, payload).await;
+        metrics::record_storage_op("append_event", began.elapsed(), result.is_ok());
+        result
+    }
+
+    async fn append_event_inner(
+        &self,
+        interface_id: &InterfaceId,
+        event_id: EventId,
+        payload: Bytes,
     ) -> Result<u64, StorageError> {
         let log = self.event_log(*interface_id).await?;
 
@@ -226,12 +400,18 @@ impl<I: PeerIdentity> CompositeStorage<I> {
 
     /// Resolve a blob reference to its content
     pub async fn resolve_blob(&self, content_ref: &ContentRef) -> Result<Bytes, StorageError> {
-        self.blobs.load(content_ref).await
+        let began = Instant::now();
+        let result = self.blobs.load(content_ref).await;
+        metrics::record_storage_op("resolve_blob", began.elapsed(), result.is_ok());
+        result
     }
 
     /// Store content in the blob store
     pub async fn store_blob(&self, data: &[u8]) -> Result<ContentRef, StorageError> {
-        self.blobs.store(data).await
+        let began = Instant::now();
+        let result = self.blobs.store(data).await;
+        metrics::record_storage_op("store_blob", began.elapsed(), result.is_ok());
+        result
     }
 
     /// Delete a blob by its content reference
@@ -286,6 +466,115 @@ impl<I: PeerIdentity> CompositeStorage<I> {
         self.peer_registry.upsert(peer, &record)
     }
 
+    /// Get the intent journal
+    pub fn journal(&self) -> &IntentJournal {
+        &self.journal
+    }
+
+    /// Get the store of recently delivered event IDs
+    pub fn seen_events(&self) -> &SeenEventStore {
+        &self.seen_events
+    }
+
+    /// Get the local cache of message translations
+    pub fn translations(&self) -> &TranslationStore {
+        &self.translations
+    }
+
+    /// Get the last-run times of background jobs
+    pub fn job_runs(&self) -> &JobRunStore {
+        &self.job_runs
+    }
+
+    /// What was quarantined and lost, if opened with
+    /// [`open_with_recovery`](Self::open_with_recovery)
+    pub fn repair_report(&self) -> Option<&RepairReport> {
+        self.repair_report.as_ref()
+    }
+
+    /// Persist a joined interface: record, local membership and key
+    ///
+    /// Idempotent, so it is safe to re-run from crash recovery. An existing
+    /// record keeps its metadata and only gains the key if it lacked one.
+    pub fn record_joined_interface(
+        &self,
+        interface_id: InterfaceId,
+        member: &I,
+        encrypted_key: Option<Vec<u8>>,
+    ) -> Result<(), StorageError> {
+        let mut record = self
+            .interface_store
+            .get(&interface_id)?
+            .unwrap_or_else(|| InterfaceRecord::new(interface_id));
+        if record.encrypted_key.is_none() {
+            record.encrypted_key = encrypted_key;
+        }
+        self.interface_store.upsert(&record)?;
+        self.register_peer(member, None)?;
+        if !self.interface_store.is_member(&interface_id, member)? {
+            self.add_member(&interface_id, member)?;
+        }
+        Ok(())
+    }
+
+    /// Replay the intent journal after an unclean shutdown
+    ///
+    /// Event appends are completed unless already in the log, and dropped if
+    /// their interface was never persisted. Joins are completed unless the
+    /// recorded member identity no longer parses. Every intent is cleared.
+    #[instrument(skip(self))]
+    pub async fn recover_intents(&self) -> Result<RecoveryReport, StorageError> {
+        let mut report = RecoveryReport::default();
+
+        for record in self.journal.pending()? {
+            let completed = match record.intent {
+                Intent::AppendEvent {
+                    interface_id,
+                    event_id,
+                    payload,
+                } => {
+                    if self.interface_store.get(&interface_id)?.is_none() {
+                        false
+                    } else {
+                        if self.get_event(&interface_id, event_id).await?.is_none() {
+                            self.append_event(&interface_id, event_id, Bytes::from(payload))
+                                .await?;
+                        }
+                        true
+                    }
+                }
+                Intent::JoinInterface {
+                    interface_id,
+                    member,
+                    encrypted_key,
+                } => match I::from_bytes(&member) {
+                    Ok(member) => {
+                        self.record_joined_interface(interface_id, &member, encrypted_key)?;
+                        true
+                    }
+                    Err(_) => false,
+                },
+            };
+
+            if completed {
+                report.completed += 1;
+            } else {
+                report.rolled_back += 1;
+            }
+            self.journal.complete(record.id)?;
+            debug!(intent = record.id, completed, "Recovered intent");
+        }
+
+        if report.total() > 0 {
+            info!(
+                completed = report.completed,
+                rolled_back = report.rolled_back,
+                "Recovered intents from journal"
+            );
+        }
+        Ok(report)
+    }
+
     /// Add a member to an interface
     pub fn add_member(&self, interface_id: &InterfaceId, peer: &I) -> Result<(), StorageError> {
         let membership = MembershipRecord::new(peer.as_bytes());
@@ -334,9 +623,33 @@ impl<I: PeerIdentity> CompositeStorage<I> {
         Ok(pending.into_iter().map(|p| p.event_id).collect())
     }
 
+    /// Copy every entry of every structured table, minus interface keys
+    ///
+    /// Used for backups: each table is read in one pass, so the copy is
+    /// consistent per table even while the node keeps writing. Interface
+    /// keys are stored unencrypted on [`InterfaceRecord::encrypted_key`] and
+    /// on journaled [`Intent::JoinInterface`]s; both are cleared in the copy
+    /// and listed in [`StructuredExport::keys_removed`].
+    pub fn export_structured(&self) -> Result<StructuredExport, StorageError> {
+        let mut export = StructuredExport::default();
+        for table in ALL_TABLES {
+            let mut entries = self.structured.scan_prefix(*table, &[])?;
+            for (key, value) in &mut entries {
+                if let Some(redacted) = without_interface_key(*table, value)? {
+                    *value = redacted;
+                    export
+                        .keys_removed
+                        .push(format!("{}/{}", table.name(), hex::encode(key)));
+                }
+            }
+            export.tables.push((*table, entries));
+        }
+        Ok(export)
+    }
+
     /// Compact the database
     pub fn compact(&self) -> Result<(), StorageError> {
-        self.redb.compact()
+        self.structured.compact()
     }
 
     /// Close all storage
@@ -349,6 +662,30 @@ impl<I: PeerIdentity> CompositeStorage<I> {
     }
 }
 
+/// `value` re-encoded without its interface key, if it carried one
+fn without_interface_key(table: Table, value: &[u8]) -> Result<Option<Vec<u8>>, StorageError> {
+    let decode = |e: postcard::Error| StorageError::Deserialization(e.to_string());
+    let encode = |e: postcard::Error| StorageError::Serialization(e.to_string());
+    if table == INTERFACES {
+        let mut record: InterfaceRecord = postcard::from_bytes(value).map_err(decode)?;
+        if record.encrypted_key.take().is_none() {
+            return Ok(None);
+        }
+        return postcard::to_allocvec(&record).map(Some).map_err(encode);
+    }
+    if table == INTENT_JOURNAL {
+        let mut record: IntentRecord = postcard::from_bytes(value).map_err(decode)?;
+        let Intent::JoinInterface { encrypted_key, .. } = &mut record.intent else {
+            return Ok(None);
+        };
+        if encrypted_key.take().is_none() {
+            return Ok(None);
+        }
+        return postcard::to_allocvec(&record).map(Some).map_err(encode);
+    }
+    Ok(None)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -386,6 +723,288 @@ mod tests {
         assert_eq!(events.len(), 5);
     }
 
+    #[tokio::test]
+    async fn test_in_memory_structured_backend() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = CompositeStorageConfig::with_base_dir(temp_dir.path())
+            .with_structured_backend(StructuredBackend::InMemory);
+        let storage = CompositeStorage::<SimulationIdentity>::new(config)
+            .await
+            .unwrap();
+
+        let peer = SimulationIdentity::new('A').unwrap();
+        let interface_id = InterfaceId::new([0x17; 32]);
+        storage.create_interface(interface_id, None).unwrap();
+        storage.add_member(&interface_id, &peer).unwrap();
+        storage
+            .append_event(&interface_id, EventId::new(1, 1), Bytes::from("hi"))
+            .await
+            .unwrap();
+
+        assert_eq!(storage.interface_store().get_members(&interface_id).unwrap().len(), 1);
+        assert!(!temp_dir.path().join("indras.redb").exists());
+    }
+
+    #[tokio::test]
+    async fn test_fully_in_memory_storage() {
+        let config = CompositeStorageConfig::in_memory();
+        assert!(config.is_in_memory());
+        let storage = CompositeStorage::<SimulationIdentity>::new(config)
+            .await
+            .unwrap();
+
+        let interface_id = InterfaceId::new([0x18; 32]);
+        storage.create_interface(interface_id, None).unwrap();
+        let large = Bytes::from(vec![0x5A; 8192]);
+        storage
+            .append_event(&interface_id, EventId::new(1, 1), large.clone())
+            .await
+            .unwrap();
+        storage
+            .node_log()
+            .append(crate::NodeEvent::InterfaceCreated {
+                interface_id,
+                name: None,
+            })
+            .await
+            .unwrap();
+
+        let event = storage
+            .get_event(&interface_id, EventId::new(1, 1))
+            .await
+            .unwrap()
+            .unwrap();
+        let blob_ref = event.blob_ref.unwrap();
+        let resolved = storage
+            .resolve_blob(&ContentRef::new(blob_ref.hash, blob_ref.size))
+            .await
+            .unwrap();
+        assert_eq!(resolved, large);
+        assert_eq!(storage.node_log().current_sequence(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_recover_intents_after_crash() {
+        let temp_dir = TempDir::new().unwrap();
+        let known = InterfaceId::new([0x21; 32]);
+        let joined = InterfaceId::new([0x22; 32]);
+        let forgotten = InterfaceId::new([0x23; 32]);
+        let peer = SimulationIdentity::new('A').unwrap();
+
+        {
+            let storage = CompositeStorage::<SimulationIdentity>::new(
+                CompositeStorageConfig::with_base_dir(temp_dir.path()),
+            )
+            .await
+            .unwrap();
+            storage.create_interface(known, None).unwrap();
+
+            // Crash after journaling, before any step ran
+            let journal = storage.journal();
+            journal
+                .begin(Intent::AppendEvent {
+                    interface_id: known,
+                    event_id: EventId::new(1, 1),
+                    payload: b"half-sent".to_vec(),
+                })
+                .unwrap();
+            journal
+                .begin(Intent::JoinInterface {
+                    interface_id: joined,
+                    member: peer.as_bytes(),
+                    encrypted_key: Some(vec![0x42; 32]),
+                })
+                .unwrap();
+            journal
+                .begin(Intent::AppendEvent {
+                    interface_id: forgotten,
+                    event_id: EventId::new(1, 1),
+                    payload: b"orphan".to_vec(),
+                })
+                .unwrap();
+
+            // Crash after the step ran, before the intent was cleared
+            storage
+                .append_event(&known, EventId::new(1, 2), Bytes::from("done"))
+                .await
+                .unwrap();
+            journal
+                .begin(Intent::AppendEvent {
+                    interface_id: known,
+                    event_id: EventId::new(1, 2),
+                    payload: b"done".to_vec(),
+                })
+                .unwrap();
+        }
+
+        let storage = CompositeStorage::<SimulationIdentity>::new(
+            CompositeStorageConfig::with_base_dir(temp_dir.path()),
+        )
+        .await
+        .unwrap();
+        let report = storage.recover_intents().await.unwrap();
+        assert_eq!(report.completed, 3);
+        assert_eq!(report.rolled_back, 1);
+        assert!(storage.journal().pending().unwrap().is_empty());
+
+        let events = storage.events_since(&known, 0).await.unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(storage.interface_store().is_member(&joined, &peer).unwrap());
+        let record = storage.interface_store().get(&joined).unwrap().unwrap();
+        assert_eq!(record.encrypted_key, Some(vec![0x42; 32]));
+        assert!(storage.interface_store().get(&forgotten).unwrap().is_none());
+
+        // A second pass finds nothing to do
+        assert_eq!(storage.recover_intents().await.unwrap().total(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_export_structured_covers_every_table() {
+        let (storage, _temp) = create_test_storage().await;
+        storage
+            .create_interface(InterfaceId::new([0x41; 32]), None)
+            .unwrap();
+
+        let export = storage.export_structured().unwrap();
+        assert_eq!(export.tables.len(), ALL_TABLES.len());
+        let (_, interfaces) = export
+            .tables
+            .iter()
+            .find(|(table, _)| table.name() == "interfaces")
+            .unwrap();
+        assert_eq!(interfaces.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_export_structured_strips_interface_keys() {
+        let (storage, _temp) = create_test_storage().await;
+        let interface_id = InterfaceId::new([0x43; 32]);
+        let key = vec![0x5a; 32];
+        let mut record = InterfaceRecord::new(interface_id);
+        record.encrypted_key = Some(key.clone());
+        storage.interface_store().upsert(&record).unwrap();
+        storage
+            .journal()
+            .begin(Intent::JoinInterface {
+                interface_id,
+                member: vec![1; 32],
+                encrypted_key: Some(key.clone()),
+            })
+            .unwrap();
+
+        let export = storage.export_structured().unwrap();
+        assert_eq!(export.keys_removed.len(), 2);
+        for (_, entries) in &export.tables {
+            for (_, value) in entries {
+                assert!(!value.windows(key.len()).any(|w| w == key.as_slice()));
+            }
+        }
+        // The live store keeps its key
+        let stored = storage.interface_store().get(&interface_id).unwrap().unwrap();
+        assert_eq!(stored.encrypted_key, Some(key));
+    }
+
+    #[tokio::test]
+    async fn test_recovery_truncates_corrupted_log_tail() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = CompositeStorageConfig::with_base_dir(temp_dir.path());
+        let interface_id = InterfaceId::new([0x31; 32]);
+
+        {
+            let storage = CompositeStorage::<SimulationIdentity>::new(config.clone())
+                .await
+                .unwrap();
+            storage.create_interface(interface_id, None).unwrap();
+            for i in 1..=3 {
+                storage
+                    .append_event(&interface_id, EventId::new(1, i), Bytes::from("ok"))
+                    .await
+                    .unwrap();
+            }
+            storage.close().await.unwrap();
+        }
+
+        // A torn write leaves a frame whose length runs past the end
+        let log_path = config
+            .event_log
+            .base_dir
+            .join(format!("{}.log", hex::encode(interface_id.as_bytes())));
+        let mut bytes = std::fs::read(&log_path).unwrap();
+        bytes.extend_from_slice(&[0, 0, 1, 0, 0xde, 0xad]);
+        std::fs::write(&log_path, &bytes).unwrap();
+
+        let storage = CompositeStorage::<SimulationIdentity>::open_with_recovery(config)
+            .await
+            .unwrap();
+        let report = storage.repair_report().unwrap();
+        assert_eq!(report.quarantined.len(), 1);
+        let quarantined = &report.quarantined[0];
+        assert_eq!(quarantined.component, StorageComponent::EventLog(interface_id));
+        assert_eq!(quarantined.bytes_lost, 6);
+        assert!(quarantined.quarantined.exists());
+        assert!(!report.database_replaced());
+
+        // Appends land after the valid prefix and are readable again
+        storage
+            .append_event(&interface_id, EventId::new(1, 4), Bytes::from("ok"))
+            .await
+            .unwrap();
+        assert_eq!(storage.events_since(&interface_id, 0).await.unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_recovery_replaces_corrupted_database() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = CompositeStorageConfig::with_base_dir(temp_dir.path());
+        let interface_id = InterfaceId::new([0x32; 32]);
+
+        {
+            let storage = CompositeStorage::<SimulationIdentity>::new(config.clone())
+                .await
+                .unwrap();
+            storage
+                .create_interface(interface_id, Some("Lost name".to_string()))
+                .unwrap();
+            for i in 1..=2 {
+                storage
+                    .append_event(&interface_id, EventId::new(1, i), Bytes::from("ok"))
+                    .await
+                    .unwrap();
+            }
+            for i in 0..3 {
+                storage
+                    .node_log()
+                    .append(crate::NodeEvent::InterfaceCreated {
+                        interface_id: InterfaceId::new([i; 32]),
+                        name: None,
+                    })
+                    .await
+                    .unwrap();
+            }
+            storage.close().await.unwrap();
+        }
+
+        std::fs::write(&config.redb.db_path, b"not a redb file").unwrap();
+        assert!(CompositeStorage::<SimulationIdentity>::new(config.clone()).await.is_err());
+
+        let storage = CompositeStorage::<SimulationIdentity>::open_with_recovery(config)
+            .await
+            .unwrap();
+        let report = storage.repair_report().unwrap();
+        assert!(report.database_replaced());
+        assert_eq!(report.interfaces_rebuilt, 1);
+        assert_eq!(report.node_log_entries_reindexed, 3);
+        assert!(!report.lost.is_empty());
+
+        // Interface and node log come back; the name lived only in the database
+        let record = storage.interface_store().get(&interface_id).unwrap().unwrap();
+        assert_eq!(record.event_count, 2);
+        assert_eq!(record.name, None);
+        assert_eq!(storage.node_log().current_sequence(), 3);
+        assert!(storage.node_log().verify_chain(0, 2).await.unwrap());
+        assert!(storage.node_log().read_entry(2).await.unwrap().is_some());
+    }
+
     #[tokio::test]
     async fn test_large_payload_stored_as_blob() {
         let (storage, _temp) = create_test_storage().await;
@@ -2,6 +2,7 @@
 //!
 //! This module defines the error types used throughout the storage crate.
 
+use indras_core::error_code::{ErrorCode, ErrorInfo};
 use thiserror::Error;
 
 /// Errors that can occur in storage operations
@@ -82,6 +83,21 @@ impl StorageError {
     }
 }
 
+impl ErrorInfo for StorageError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            StorageError::Io(_) => ErrorCode::StorageIo,
+            StorageError::NotFound(_) | StorageError::PacketNotFound(_) => ErrorCode::NotFound,
+            StorageError::CapacityExceeded => ErrorCode::CapacityExceeded,
+            StorageError::Serialization(_) => ErrorCode::Serialization,
+            StorageError::Deserialization(_) => ErrorCode::CorruptData,
+            StorageError::Identity(_) => ErrorCode::InvalidIdentity,
+            StorageError::Database(_) => ErrorCode::Database,
+            StorageError::DatabaseLocked => ErrorCode::DatabaseLocked,
+        }
+    }
+}
+
 /// Convert from postcard Error to StorageError
 impl From<postcard::Error> for StorageError {
     fn from(err: postcard::Error) -> Self {
@@ -119,6 +135,17 @@ mod tests {
         assert!(matches!(err, StorageError::Serialization(_)));
     }
 
+    #[test]
+    fn test_error_codes() {
+        assert_eq!(
+            StorageError::DatabaseLocked.code(),
+            ErrorCode::DatabaseLocked
+        );
+        assert!(StorageError::DatabaseLocked.is_retryable());
+        assert!(!StorageError::deserialization("bad").is_retryable());
+        assert_eq!(StorageError::not_found("x").code(), ErrorCode::NotFound);
+    }
+
     #[test]
     fn test_deserialization_error() {
         let err = StorageError::deserialization("corrupted data");
@@ -0,0 +1,115 @@
+//! Last-run times of the node's background jobs
+//!
+//! The node's job scheduler records when each job last finished so a
+//! restart doesn't re-run everything at once. One row per job and scope:
+//!
+//! ```text
+//! key:   scope tag (1 byte) ‖ interface_id (32 bytes, interface scope only) ‖ job name
+//! value: finish time in Unix millis (8 bytes BE)
+//! ```
+//!
+//! Node-wide jobs use tag 0 and no interface id; per-interface jobs use
+//! tag 1, so all of one interface's rows share a prefix and can be
+//! dropped together when the interface is left.
+
+use std::sync::Arc;
+
+use indras_core::InterfaceId;
+
+use crate::error::StorageError;
+use crate::structured::{JOB_RUNS, StructuredStore};
+
+const NODE_SCOPE: u8 = 0;
+const INTERFACE_SCOPE: u8 = 1;
+
+/// Durable record of when each background job last ran
+pub struct JobRunStore {
+    structured: Arc<dyn StructuredStore>,
+}
+
+impl JobRunStore {
+    /// Open the store
+    pub fn new(structured: Arc<dyn StructuredStore>) -> Self {
+        Self { structured }
+    }
+
+    /// When a job last ran, node-wide or for one interface
+    pub fn last_run(
+        &self,
+        job: &str,
+        interface_id: Option<&InterfaceId>,
+    ) -> Result<Option<u64>, StorageError> {
+        let value = self.structured.get(JOB_RUNS, &row_key(job, interface_id))?;
+        Ok(value.and_then(|v| Some(u64::from_be_bytes(v.as_slice().try_into().ok()?))))
+    }
+
+    /// Record that a job ran at `at_millis`
+    pub fn record_run(
+        &self,
+        job: &str,
+        interface_id: Option<&InterfaceId>,
+        at_millis: u64,
+    ) -> Result<(), StorageError> {
+        self.structured.put(
+            JOB_RUNS,
+            &row_key(job, interface_id),
+            &at_millis.to_be_bytes(),
+        )
+    }
+
+    /// Drop every record for an interface, returning how many
+    pub fn forget_interface(&self, interface_id: &InterfaceId) -> Result<usize, StorageError> {
+        let rows = self
+            .structured
+            .scan_prefix(JOB_RUNS, &scope_prefix(Some(interface_id)))?;
+        for (key, _) in &rows {
+            self.structured.delete(JOB_RUNS, key)?;
+        }
+        Ok(rows.len())
+    }
+}
+
+fn scope_prefix(interface_id: Option<&InterfaceId>) -> Vec<u8> {
+    match interface_id {
+        Some(id) => {
+            let mut prefix = Vec::with_capacity(33);
+            prefix.push(INTERFACE_SCOPE);
+            prefix.extend_from_slice(id.as_bytes());
+            prefix
+        }
+        None => vec![NODE_SCOPE],
+    }
+}
+
+fn row_key(job: &str, interface_id: Option<&InterfaceId>) -> Vec<u8> {
+    let mut key = scope_prefix(interface_id);
+    key.extend_from_slice(job.as_bytes());
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structured::InMemoryStructuredStore;
+
+    #[test]
+    fn test_runs_are_scoped() {
+        let store = JobRunStore::new(Arc::new(InMemoryStructuredStore::new()));
+        let a = InterfaceId::new([1; 32]);
+        let b = InterfaceId::new([2; 32]);
+
+        store.record_run("prune", None, 10).unwrap();
+        store.record_run("prune", Some(&a), 20).unwrap();
+        store.record_run("digest", Some(&a), 30).unwrap();
+        store.record_run("prune", Some(&b), 40).unwrap();
+
+        assert_eq!(store.last_run("prune", None).unwrap(), Some(10));
+        assert_eq!(store.last_run("prune", Some(&a)).unwrap(), Some(20));
+        assert_eq!(store.last_run("digest", None).unwrap(), None);
+
+        assert_eq!(store.forget_interface(&a).unwrap(), 2);
+        assert_eq!(store.last_run("prune", Some(&a)).unwrap(), None);
+        assert_eq!(store.last_run("prune", Some(&b)).unwrap(), Some(40));
+        assert_eq!(store.last_run("prune", None).unwrap(), Some(10));
+    }
+}
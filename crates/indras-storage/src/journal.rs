@@ -0,0 +1,181 @@
+//! Write-ahead intent journal
+//!
+//! Multi-step operations (persisting an event, recording a joined interface)
+//! touch several storage layers. A crash between steps would leave them
+//! inconsistent, so callers first record an [`Intent`] describing the target
+//! state, perform the steps, then clear it:
+//!
+//! ```text
+//! let id = journal.begin(Intent::AppendEvent { .. })?;   // durable
+//! storage.append_event(..).await?;                       // step(s)
+//! journal.complete(id)?;                                 // clear
+//! ```
+//!
+//! On startup [`CompositeStorage::recover_intents`](crate::CompositeStorage::recover_intents)
+//! replays whatever is left: intents whose steps are idempotent are rolled
+//! forward, intents that can no longer apply are rolled back.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use indras_core::{EventId, InterfaceId};
+
+use crate::error::StorageError;
+use crate::structured::{INTENT_JOURNAL, StructuredStore};
+
+/// Identifier of a journaled intent
+pub type IntentId = u64;
+
+/// An operation that has been started but not yet confirmed complete
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Intent {
+    /// An event applied in memory that must reach the event log
+    AppendEvent {
+        /// Interface the event belongs to
+        interface_id: InterfaceId,
+        /// Event identifier (used to detect an already-persisted event)
+        event_id: EventId,
+        /// Raw event payload
+        payload: Vec<u8>,
+    },
+    /// A joined interface whose record and membership must be persisted
+    JoinInterface {
+        /// Interface being joined
+        interface_id: InterfaceId,
+        /// Identity bytes of the local member
+        member: Vec<u8>,
+        /// Interface key to store on the record, if one was distributed
+        encrypted_key: Option<Vec<u8>>,
+    },
+}
+
+/// A journaled intent with its bookkeeping
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntentRecord {
+    /// Journal-assigned identifier, monotonically increasing
+    pub id: IntentId,
+    /// When the intent was recorded
+    pub created_at_millis: i64,
+    /// What was being done
+    pub intent: Intent,
+}
+
+/// Outcome of replaying the journal on startup
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RecoveryReport {
+    /// Intents whose remaining steps were applied
+    pub completed: usize,
+    /// Intents discarded because they could no longer apply
+    pub rolled_back: usize,
+}
+
+impl RecoveryReport {
+    /// Total number of intents found in the journal
+    pub fn total(&self) -> usize {
+        self.completed + self.rolled_back
+    }
+}
+
+/// Durable record of in-flight multi-step operations
+pub struct IntentJournal {
+    structured: Arc<dyn StructuredStore>,
+    next_id: AtomicU64,
+}
+
+impl IntentJournal {
+    /// Open the journal, continuing numbering after any existing intents
+    pub fn open(structured: Arc<dyn StructuredStore>) -> Result<Self, StorageError> {
+        let next_id = structured
+            .scan_prefix(INTENT_JOURNAL, &[])?
+            .last()
+            .and_then(|(key, _)| <[u8; 8]>::try_from(key.as_slice()).ok())
+            .map(|key| u64::from_be_bytes(key) + 1)
+            .unwrap_or(0);
+
+        Ok(Self {
+            structured,
+            next_id: AtomicU64::new(next_id),
+        })
+    }
+
+    /// Durably record an intent before performing its steps
+    pub fn begin(&self, intent: Intent) -> Result<IntentId, StorageError> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let record = IntentRecord {
+            id,
+            created_at_millis: chrono::Utc::now().timestamp_millis(),
+            intent,
+        };
+        let value = postcard::to_allocvec(&record)
+            .map_err(|e| StorageError::Serialization(e.to_string()))?;
+
+        self.structured.put(INTENT_JOURNAL, &id.to_be_bytes(), &value)?;
+        debug!(intent = id, "Journaled intent");
+        Ok(id)
+    }
+
+    /// Clear an intent once all its steps have been applied
+    pub fn complete(&self, id: IntentId) -> Result<(), StorageError> {
+        self.structured.delete(INTENT_JOURNAL, &id.to_be_bytes())?;
+        Ok(())
+    }
+
+    /// All outstanding intents, oldest first
+    pub fn pending(&self) -> Result<Vec<IntentRecord>, StorageError> {
+        self.structured
+            .scan_prefix(INTENT_JOURNAL, &[])?
+            .into_iter()
+            .map(|(_, value)| {
+                postcard::from_bytes(&value)
+                    .map_err(|e| StorageError::Deserialization(e.to_string()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structured::InMemoryStructuredStore;
+
+    fn append_intent(n: u32) -> Intent {
+        Intent::AppendEvent {
+            interface_id: InterfaceId::new([0x11; 32]),
+            event_id: EventId::new(1, n as u64),
+            payload: vec![n as u8],
+        }
+    }
+
+    #[test]
+    fn test_begin_complete_pending() {
+        let journal = IntentJournal::open(Arc::new(InMemoryStructuredStore::new())).unwrap();
+
+        let a = journal.begin(append_intent(1)).unwrap();
+        let b = journal.begin(append_intent(2)).unwrap();
+        assert!(b > a);
+
+        let pending = journal.pending().unwrap();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].intent, append_intent(1));
+
+        journal.complete(a).unwrap();
+        let pending = journal.pending().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].id, b);
+    }
+
+    #[test]
+    fn test_reopen_continues_numbering() {
+        let store: Arc<dyn StructuredStore> = Arc::new(InMemoryStructuredStore::new());
+        let first = IntentJournal::open(store.clone()).unwrap();
+        let id = first.begin(append_intent(1)).unwrap();
+        drop(first);
+
+        let reopened = IntentJournal::open(store).unwrap();
+        assert_eq!(reopened.pending().unwrap().len(), 1);
+        assert!(reopened.begin(append_intent(2)).unwrap() > id);
+    }
+}
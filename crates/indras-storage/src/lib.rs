@@ -25,9 +25,11 @@
 //!
 //! - **PendingStore trait**: Abstraction for tracking pending event delivery
 //! - **EventLog**: Append-only per-interface event logs
-//! - **RedbStorage**: Fast key-value storage with range queries
+//! - **StructuredStore trait**: Pluggable backend for the structured layer
+//! - **RedbStorage**: Fast key-value storage with range queries (default backend)
 //! - **BlobStore**: Content-addressed storage for large payloads
 //! - **CompositeStorage**: Unified interface for all three layers
+//! - **AssetCache**: Size-bounded LRU cache for avatars, emoji and other shared assets
 //!
 //! ## Example
 //!
@@ -56,10 +58,16 @@ pub mod quota;
 
 // New tri-layer storage
 pub mod append_log;
+pub mod assets;
 pub mod blobs;
 pub mod composite;
+pub mod job_runs;
+pub mod journal;
 pub mod node_log;
+pub mod repair;
+pub mod seen_events;
 pub mod structured;
+pub mod translations;
 
 // Re-exports
 pub use error::StorageError;
@@ -69,12 +77,19 @@ pub use quota::{EvictionPolicy, QuotaManager, QuotaManagerBuilder};
 
 // Tri-layer storage re-exports
 pub use append_log::{CompactionConfig, EventLog, EventLogConfig, EventLogEntry};
+pub use assets::{AssetCache, AssetCacheConfig};
 pub use blobs::{BlobStore, BlobStoreConfig, ContentRef, GcResult};
-pub use composite::{CompositeStorage, CompositeStorageConfig};
+pub use composite::{CompositeStorage, CompositeStorageConfig, StructuredExport};
+pub use job_runs::JobRunStore;
+pub use journal::{Intent, IntentId, IntentJournal, IntentRecord, RecoveryReport};
 pub use node_log::{NodeEvent, NodeLog, NodeLogEntry, NodeLogMeta, NodeSequence};
+pub use repair::{QuarantinedFile, RepairReport, StorageComponent};
+pub use seen_events::SeenEventStore;
+pub use translations::{TranslationRecord, TranslationStore};
 pub use structured::{
-    InterfaceRecord, InterfaceStore, PeerRecord, PeerRegistry, RedbStorage, RedbStorageConfig,
-    SyncStateRecord, SyncStateStore,
+    InMemoryStructuredStore, InterfaceRecord, InterfaceStore, PeerRecord, PeerRegistry,
+    RedbStorage, RedbStorageConfig, StructuredBackend, StructuredStore, SyncStateRecord,
+    SyncStateStore, Table,
 };
 
 // Re-export PacketStore trait from indras-core for convenience
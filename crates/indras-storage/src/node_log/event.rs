@@ -139,4 +139,61 @@ pub enum NodeEvent {
         /// Whether the bundle was for us (true) or relayed custody (false)
         for_us: bool,
     },
+
+    // — Delegation —
+
+    /// A delegation token was issued to a peer
+    DelegationIssued {
+        /// Delegation token ID
+        token_id: [u8; 16],
+        /// The interface the token is scoped to
+        interface_id: InterfaceId,
+        /// Delegate's public key bytes
+        delegate: Vec<u8>,
+        /// When the token lapses (milliseconds since epoch)
+        expires_at_millis: u64,
+    },
+    /// A delegation token was revoked before expiry
+    DelegationRevoked {
+        /// Delegation token ID
+        token_id: [u8; 16],
+    },
+    /// A peer acted, or tried to act, under a delegation token
+    DelegatedAction {
+        /// Delegation token ID
+        token_id: [u8; 16],
+        /// The interface the action targeted
+        interface_id: InterfaceId,
+        /// Sender's public key bytes
+        sender: Vec<u8>,
+        /// Capability the action needed
+        capability: String,
+        /// Why the action was refused, or `None` if it was allowed
+        denied: Option<String>,
+    },
+
+    // — Health —
+
+    /// A supervised background task panicked or exited unexpectedly
+    TaskIncident {
+        /// Task name (e.g. "sync")
+        task: String,
+        /// Panic message or exit reason
+        reason: String,
+        /// Restarts of this task so far
+        restarts: u32,
+        /// Whether the supervisor gave up instead of restarting it
+        gave_up: bool,
+    },
+    /// A corrupted storage file was moved aside when opening storage
+    StorageQuarantined {
+        /// Component the file belonged to (e.g. "database", "event_log")
+        component: String,
+        /// Path the corrupted file was moved to
+        path: String,
+        /// What was wrong with it
+        reason: String,
+        /// Bytes that did not survive
+        bytes_lost: u64,
+    },
 }
@@ -27,13 +27,14 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 
-use tokio::fs::{File, OpenOptions};
-use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio::fs::OpenOptions;
 use tokio::sync::Mutex;
 use tracing::{debug, info};
 
+use crate::append_log::backing::LogBacking;
 use crate::error::StorageError;
-use crate::structured::{NODE_LOG_INDEX, NODE_LOG_META, RedbStorage};
+use crate::repair::LogScan;
+use crate::structured::{NODE_LOG_INDEX, NODE_LOG_META, StructuredStore};
 
 /// Node-level event log
 ///
@@ -43,22 +44,25 @@ pub struct NodeLog {
     /// Path to the log file (reserved for future snapshot/compaction)
     #[allow(dead_code)]
     log_path: PathBuf,
-    /// The log file handle (mutex for exclusive write access)
-    log_file: Mutex<File>,
+    /// The log file handle or in-memory buffer (mutex for exclusive write access)
+    log_file: Mutex<LogBacking>,
     /// Current sequence number (atomic for lock-free reads)
     sequence: AtomicU64,
     /// BLAKE3 hash of the last entry (mutex-protected)
     last_hash: Mutex<[u8; 32]>,
-    /// redb storage for index and metadata
-    redb: Arc<RedbStorage>,
+    /// Structured storage for index and metadata
+    structured: Arc<dyn StructuredStore>,
 }
 
 impl NodeLog {
     /// Open or create the node log
     ///
-    /// Loads metadata from redb to recover sequence and hash state.
+    /// Loads metadata from structured storage to recover sequence and hash state.
     /// If no metadata exists, starts a fresh log.
-    pub async fn open(base_dir: &std::path::Path, redb: Arc<RedbStorage>) -> Result<Self, StorageError> {
+    pub async fn open(
+        base_dir: &std::path::Path,
+        structured: Arc<dyn StructuredStore>,
+    ) -> Result<Self, StorageError> {
         tokio::fs::create_dir_all(base_dir)
             .await
             .map_err(|e| StorageError::Io(e.to_string()))?;
@@ -74,8 +78,8 @@ impl NodeLog {
             .await
             .map_err(|e| StorageError::Io(e.to_string()))?;
 
-        // Try to load metadata from redb
-        let (sequence, last_hash) = match redb.get(NODE_LOG_META, b"meta")? {
+        // Try to load metadata from structured storage
+        let (sequence, last_hash) = match structured.get(NODE_LOG_META, b"meta")? {
             Some(meta_bytes) => {
                 let meta: NodeLogMeta = postcard::from_bytes(&meta_bytes)
                     .map_err(|e| StorageError::Deserialization(e.to_string()))?;
@@ -92,17 +96,75 @@ impl NodeLog {
 
         Ok(Self {
             log_path,
-            log_file: Mutex::new(file),
+            log_file: Mutex::new(LogBacking::File(file)),
             sequence: AtomicU64::new(sequence),
             last_hash: Mutex::new(last_hash),
-            redb,
+            structured,
         })
     }
 
+    /// Create a volatile node log that never touches disk
+    ///
+    /// Always starts at sequence 0; any metadata already in `structured` is
+    /// ignored and overwritten as entries are appended.
+    pub fn in_memory(structured: Arc<dyn StructuredStore>) -> Self {
+        debug!("Node log opened in memory");
+        Self {
+            log_path: PathBuf::new(),
+            log_file: Mutex::new(LogBacking::Memory(Vec::new())),
+            sequence: AtomicU64::new(0),
+            last_hash: Mutex::new([0u8; 32]),
+            structured,
+        }
+    }
+
+    /// Rebuild the index and metadata from entries that survived a repair
+    ///
+    /// Must run before [`open`](Self::open), which trusts the metadata.
+    /// Index entries past the surviving tail are removed. Returns the number
+    /// of entries indexed.
+    pub(crate) fn reindex(
+        structured: &dyn StructuredStore,
+        scan: &LogScan<NodeLogEntry>,
+    ) -> Result<u64, StorageError> {
+        let previous = match structured.get(NODE_LOG_META, b"meta")? {
+            Some(bytes) => postcard::from_bytes::<NodeLogMeta>(&bytes)
+                .map(|meta| meta.current_sequence)
+                .unwrap_or(0),
+            None => 0,
+        };
+
+        for (offset, entry) in &scan.entries {
+            structured.put(
+                NODE_LOG_INDEX,
+                &entry.sequence.to_be_bytes(),
+                &offset.to_be_bytes(),
+            )?;
+        }
+        let current_sequence = scan.entries.last().map_or(0, |(_, e)| e.sequence + 1);
+        for sequence in current_sequence..previous {
+            structured.delete(NODE_LOG_INDEX, &sequence.to_be_bytes())?;
+        }
+
+        let meta = NodeLogMeta {
+            current_sequence,
+            last_hash: scan.last_hash,
+            entry_count: scan.entries.len() as u64,
+            file_size: scan.valid_len,
+            last_snapshot_sequence: None,
+        };
+        let meta_bytes = postcard::to_allocvec(&meta)
+            .map_err(|e| StorageError::Serialization(e.to_string()))?;
+        structured.put(NODE_LOG_META, b"meta", &meta_bytes)?;
+
+        debug!(entries = scan.entries.len(), "Reindexed node log");
+        Ok(scan.entries.len() as u64)
+    }
+
     /// Append an event to the node log
     ///
     /// Atomically: increments sequence, computes hash chain, serializes entry,
-    /// writes to file with fsync, and updates redb index + metadata.
+    /// writes to file with fsync, and updates the structured index + metadata.
     ///
     /// Returns the sequence number assigned to this entry.
     pub async fn append(&self, event: NodeEvent) -> Result<NodeSequence, StorageError> {
@@ -126,32 +188,18 @@ impl NodeLog {
         let entry_hash_bytes: [u8; 32] = *entry_hash.as_bytes();
 
         // Write length-prefixed entry to file
-        let offset = file
-            .seek(std::io::SeekFrom::End(0))
-            .await
-            .map_err(|e| StorageError::Io(e.to_string()))?;
-
-        let len_bytes = (serialized.len() as u32).to_be_bytes();
-        file.write_all(&len_bytes)
-            .await
-            .map_err(|e| StorageError::Io(e.to_string()))?;
-        file.write_all(&serialized)
-            .await
-            .map_err(|e| StorageError::Io(e.to_string()))?;
-        file.sync_data()
-            .await
-            .map_err(|e| StorageError::Io(e.to_string()))?;
+        let offset = file.append_frame(&serialized, true).await?;
 
         let new_file_size = offset + 4 + serialized.len() as u64;
 
-        // Update redb index: sequence -> file offset
-        self.redb.put(
+        // Update index: sequence -> file offset
+        self.structured.put(
             NODE_LOG_INDEX,
             &sequence.to_be_bytes(),
             &offset.to_be_bytes(),
         )?;
 
-        // Update redb metadata
+        // Update metadata
         let new_sequence = sequence + 1;
         let meta = NodeLogMeta {
             current_sequence: new_sequence,
@@ -162,7 +210,7 @@ impl NodeLog {
         };
         let meta_bytes = postcard::to_allocvec(&meta)
             .map_err(|e| StorageError::Serialization(e.to_string()))?;
-        self.redb.put(NODE_LOG_META, b"meta", &meta_bytes)?;
+        self.structured.put(NODE_LOG_META, b"meta", &meta_bytes)?;
 
         // Update in-memory state
         *last_hash = entry_hash_bytes;
@@ -173,9 +221,9 @@ impl NodeLog {
 
     /// Read a single entry by sequence number
     pub async fn read_entry(&self, sequence: NodeSequence) -> Result<Option<NodeLogEntry>, StorageError> {
-        // Look up offset in redb index
+        // Look up offset in index
         let seq_bytes = sequence.to_be_bytes();
-        let offset_bytes: Vec<u8> = match self.redb.get(NODE_LOG_INDEX, &seq_bytes)? {
+        let offset_bytes: Vec<u8> = match self.structured.get(NODE_LOG_INDEX, &seq_bytes)? {
             Some(b) => b,
             None => return Ok(None),
         };
@@ -247,25 +295,7 @@ impl NodeLog {
 
     /// Read an entry at a specific file offset
     async fn read_at_offset(&self, offset: u64) -> Result<NodeLogEntry, StorageError> {
-        let mut file = self.log_file.lock().await;
-
-        file.seek(std::io::SeekFrom::Start(offset))
-            .await
-            .map_err(|e| StorageError::Io(e.to_string()))?;
-
-        // Read length prefix
-        let mut len_buf = [0u8; 4];
-        file.read_exact(&mut len_buf)
-            .await
-            .map_err(|e| StorageError::Io(e.to_string()))?;
-
-        let entry_len = u32::from_be_bytes(len_buf) as usize;
-
-        // Read entry bytes
-        let mut entry_buf = vec![0u8; entry_len];
-        file.read_exact(&mut entry_buf)
-            .await
-            .map_err(|e| StorageError::Io(e.to_string()))?;
+        let entry_buf = self.log_file.lock().await.read_frame(offset).await?;
 
         postcard::from_bytes(&entry_buf)
             .map_err(|e| StorageError::Deserialization(e.to_string()))
@@ -276,7 +306,7 @@ impl NodeLog {
 mod tests {
     use super::*;
     use tempfile::TempDir;
-    use crate::structured::RedbStorageConfig;
+    use crate::structured::{RedbStorage, RedbStorageConfig};
 
     async fn create_test_log() -> (NodeLog, TempDir) {
         let temp_dir = TempDir::new().unwrap();
@@ -396,4 +426,21 @@ mod tests {
         let all = log.read_since(0).await.unwrap();
         assert_eq!(all.len(), 10);
     }
+
+    #[tokio::test]
+    async fn test_in_memory_log() {
+        let structured = Arc::new(crate::structured::InMemoryStructuredStore::new());
+        let log = NodeLog::in_memory(structured);
+
+        for i in 0..3 {
+            log.append(NodeEvent::InterfaceCreated {
+                interface_id: indras_core::InterfaceId::new([i as u8; 32]),
+                name: None,
+            }).await.unwrap();
+        }
+
+        assert_eq!(log.current_sequence(), 3);
+        assert_eq!(log.read_entry(1).await.unwrap().unwrap().sequence, 1);
+        assert!(log.verify_chain(0, 2).await.unwrap());
+    }
 }
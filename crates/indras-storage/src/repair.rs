@@ -0,0 +1,304 @@
+//! Corruption detection and quarantine on open
+//!
+//! [`CompositeStorage::open_with_recovery`](crate::CompositeStorage::open_with_recovery)
+//! checks each on-disk component before opening it instead of failing on the
+//! first bad one:
+//!
+//! - **Event logs and the node log** are scanned frame by frame. A log whose
+//!   tail does not decode is moved aside and rewritten with its valid prefix.
+//! - **The redb database** is moved aside if it fails to open, and a fresh one
+//!   is created in its place.
+//!
+//! Quarantined files are renamed to `<name>.corrupt-<unix-millis>` next to the
+//! original; nothing is deleted. State derivable from the surviving logs
+//! (interface records, the node log index) is rebuilt, and whatever could not
+//! be rebuilt is listed in the [`RepairReport`].
+
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use tracing::warn;
+
+use indras_core::{InterfaceId, PeerIdentity};
+
+use crate::append_log::EventLogEntry;
+use crate::error::StorageError;
+use crate::structured::{InterfaceRecord, InterfaceStore, RedbStorage, RedbStorageConfig};
+
+/// Largest frame a log may contain, matching [`EventLog`](crate::EventLog) replay
+const MAX_FRAME_LEN: usize = 10 * 1024 * 1024;
+
+/// What is lost when the database has to be quarantined
+const DATABASE_LOSSES: &[&str] = &[
+    "interface names, members and keys",
+    "peer registry",
+    "sync state (peers resync from scratch)",
+    "document data and translations",
+    "pending delivery queues and journaled intents",
+];
+
+/// An on-disk storage component
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StorageComponent {
+    /// The redb database holding structured state
+    Database,
+    /// The event log of one interface
+    EventLog(InterfaceId),
+    /// The node-level audit log
+    NodeLog,
+}
+
+/// A corrupted file that was moved aside
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuarantinedFile {
+    /// Which component the file belonged to
+    pub component: StorageComponent,
+    /// Where the file was
+    pub original: PathBuf,
+    /// Where the corrupted file now is
+    pub quarantined: PathBuf,
+    /// What was wrong with it
+    pub reason: String,
+    /// Bytes that did not survive; the whole file for the database
+    pub bytes_lost: u64,
+}
+
+/// Outcome of opening storage in recovery mode
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepairReport {
+    /// Files moved aside
+    pub quarantined: Vec<QuarantinedFile>,
+    /// Interface records recreated from surviving event logs
+    pub interfaces_rebuilt: usize,
+    /// Node log entries whose index was rebuilt
+    pub node_log_entries_reindexed: u64,
+    /// Human-readable description of what could not be recovered
+    pub lost: Vec<String>,
+}
+
+impl RepairReport {
+    /// Whether every component opened without quarantine
+    pub fn is_clean(&self) -> bool {
+        self.quarantined.is_empty()
+    }
+
+    /// Whether the database was quarantined and recreated empty
+    pub fn database_replaced(&self) -> bool {
+        self.quarantined
+            .iter()
+            .any(|q| q.component == StorageComponent::Database)
+    }
+}
+
+/// Frames of a log up to the first one that fails to decode
+pub(crate) struct LogScan<T> {
+    /// Decoded entries with their frame offsets
+    pub entries: Vec<(u64, T)>,
+    /// BLAKE3 hash of the last valid frame, `[0; 32]` if there is none
+    pub last_hash: [u8; 32],
+    /// Length of the valid prefix in bytes
+    pub valid_len: u64,
+    /// Whether the file was cut back to its valid prefix
+    pub truncated: bool,
+}
+
+/// Summary of an event log that survived the scan
+pub(crate) struct SurvivingLog {
+    pub interface_id: InterfaceId,
+    pub events: u64,
+    pub first_millis: Option<i64>,
+    pub last_millis: Option<i64>,
+}
+
+/// Scan a length-prefixed log, quarantining it if its tail is corrupted
+///
+/// A missing file scans as empty.
+pub(crate) async fn repair_log<T: DeserializeOwned>(
+    path: &Path,
+    component: StorageComponent,
+    report: &mut RepairReport,
+) -> Result<LogScan<T>, StorageError> {
+    let bytes = match tokio::fs::read(path).await {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(StorageError::Io(e.to_string())),
+    };
+
+    let mut entries = Vec::new();
+    let mut last_hash = [0u8; 32];
+    let mut offset = 0usize;
+    let mut error = None;
+    while offset < bytes.len() {
+        let Some(len_bytes) = bytes.get(offset..offset + 4) else {
+            error = Some(format!("truncated length prefix at offset {offset}"));
+            break;
+        };
+        let len = u32::from_be_bytes(len_bytes.try_into().unwrap()) as usize;
+        if len == 0 || len > MAX_FRAME_LEN {
+            error = Some(format!("invalid frame length {len} at offset {offset}"));
+            break;
+        }
+        let Some(frame) = bytes.get(offset + 4..offset + 4 + len) else {
+            error = Some(format!("truncated frame at offset {offset}"));
+            break;
+        };
+        match postcard::from_bytes::<T>(frame) {
+            Ok(entry) => entries.push((offset as u64, entry)),
+            Err(e) => {
+                error = Some(format!("undecodable frame at offset {offset}: {e}"));
+                break;
+            }
+        }
+        last_hash = *blake3::hash(frame).as_bytes();
+        offset += 4 + len;
+    }
+
+    let truncated = error.is_some();
+    if let Some(reason) = error {
+        let quarantined = quarantine(path).await?;
+        tokio::fs::write(path, &bytes[..offset])
+            .await
+            .map_err(|e| StorageError::Io(e.to_string()))?;
+        warn!(path = %path.display(), %reason, kept = entries.len(), "Quarantined corrupted log");
+        report.quarantined.push(QuarantinedFile {
+            component,
+            original: path.to_path_buf(),
+            quarantined,
+            reason,
+            bytes_lost: (bytes.len() - offset) as u64,
+        });
+    }
+
+    Ok(LogScan {
+        entries,
+        last_hash,
+        valid_len: offset as u64,
+        truncated,
+    })
+}
+
+/// Scan every interface event log under `dir`
+pub(crate) async fn repair_event_logs<I: PeerIdentity>(
+    dir: &Path,
+    report: &mut RepairReport,
+) -> Result<Vec<SurvivingLog>, StorageError> {
+    let mut surviving = Vec::new();
+    let mut read_dir = match tokio::fs::read_dir(dir).await {
+        Ok(read_dir) => read_dir,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(surviving),
+        Err(e) => return Err(StorageError::Io(e.to_string())),
+    };
+
+    while let Some(dir_entry) = read_dir
+        .next_entry()
+        .await
+        .map_err(|e| StorageError::Io(e.to_string()))?
+    {
+        let path = dir_entry.path();
+        let Some(interface_id) = log_interface_id(&path) else {
+            continue;
+        };
+        let scan =
+            repair_log::<EventLogEntry<I>>(&path, StorageComponent::EventLog(interface_id), report)
+                .await?;
+        if scan.truncated {
+            report.lost.push(format!(
+                "tail of the event log for interface {}",
+                hex::encode(interface_id.as_bytes())
+            ));
+        }
+        surviving.push(SurvivingLog {
+            interface_id,
+            events: scan.entries.len() as u64,
+            first_millis: scan.entries.first().map(|(_, e)| e.timestamp_millis),
+            last_millis: scan.entries.last().map(|(_, e)| e.timestamp_millis),
+        });
+    }
+    Ok(surviving)
+}
+
+/// Open the redb database, replacing it with an empty one if it is corrupted
+///
+/// A locked database is never quarantined. Returns whether it was replaced.
+pub(crate) async fn open_redb(
+    config: &RedbStorageConfig,
+    report: &mut RepairReport,
+) -> Result<(RedbStorage, bool), StorageError> {
+    let error = match RedbStorage::open(config.clone()) {
+        Ok(db) => return Ok((db, false)),
+        Err(e) if e.is_locked() || !config.db_path.exists() => return Err(e),
+        Err(e) => e,
+    };
+
+    let bytes_lost = tokio::fs::metadata(&config.db_path)
+        .await
+        .map(|m| m.len())
+        .unwrap_or(0);
+    let quarantined = quarantine(&config.db_path).await?;
+    warn!(path = %config.db_path.display(), error = %error, "Quarantined corrupted database");
+    report.quarantined.push(QuarantinedFile {
+        component: StorageComponent::Database,
+        original: config.db_path.clone(),
+        quarantined,
+        reason: error.to_string(),
+        bytes_lost,
+    });
+    report
+        .lost
+        .extend(DATABASE_LOSSES.iter().map(|s| s.to_string()));
+
+    Ok((RedbStorage::open(config.clone())?, true))
+}
+
+/// Recreate interface records from surviving event logs
+///
+/// Names, members and keys lived only in the database, so the records carry
+/// just the event count and activity times.
+pub(crate) fn rebuild_interfaces(
+    store: &InterfaceStore,
+    logs: &[SurvivingLog],
+    report: &mut RepairReport,
+) -> Result<(), StorageError> {
+    for log in logs {
+        if store.get(&log.interface_id)?.is_some() {
+            continue;
+        }
+        let mut record = InterfaceRecord::new(log.interface_id);
+        record.event_count = log.events;
+        if let Some(first) = log.first_millis {
+            record.created_at_millis = first;
+        }
+        if let Some(last) = log.last_millis {
+            record.last_activity_millis = last;
+        }
+        store.upsert(&record)?;
+        report.interfaces_rebuilt += 1;
+    }
+    Ok(())
+}
+
+/// Rename `path` to `<name>.corrupt-<unix-millis>` and return the new path
+async fn quarantine(path: &Path) -> Result<PathBuf, StorageError> {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let millis = chrono::Utc::now().timestamp_millis();
+    let target = path.with_file_name(format!("{name}.corrupt-{millis}"));
+    tokio::fs::rename(path, &target)
+        .await
+        .map_err(|e| StorageError::Io(e.to_string()))?;
+    Ok(target)
+}
+
+/// Interface ID of an event log file named `<hex>.log`
+fn log_interface_id(path: &Path) -> Option<InterfaceId> {
+    if path.extension()? != "log" {
+        return None;
+    }
+    let bytes: [u8; 32] = hex::decode(path.file_stem()?.to_str()?)
+        .ok()?
+        .try_into()
+        .ok()?;
+    Some(InterfaceId::new(bytes))
+}
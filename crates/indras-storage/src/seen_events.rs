@@ -0,0 +1,137 @@
+//! Persisted window of recently delivered event IDs
+//!
+//! The node drops incoming events it has already delivered. The in-memory
+//! window it checks against is mirrored here, one row per event, so the
+//! window survives a restart and replays after reconnect are still
+//! recognized:
+//!
+//! ```text
+//! key:   interface_id (32 bytes) ‖ event_id (16 bytes)
+//! value: insertion stamp (8 bytes BE)
+//! ```
+//!
+//! Stamps only order rows within an interface so the window can be rebuilt
+//! oldest first; eviction deletes the oldest row.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use indras_core::{EventId, InterfaceId};
+
+use crate::error::StorageError;
+use crate::structured::{SEEN_EVENTS, StructuredStore};
+
+/// Durable per-interface record of delivered event IDs
+pub struct SeenEventStore {
+    structured: Arc<dyn StructuredStore>,
+    next_stamp: AtomicU64,
+}
+
+impl SeenEventStore {
+    /// Open the store, continuing stamps after any existing rows
+    pub fn open(structured: Arc<dyn StructuredStore>) -> Result<Self, StorageError> {
+        let next_stamp = structured
+            .scan_prefix(SEEN_EVENTS, &[])?
+            .iter()
+            .filter_map(|(_, value)| decode_stamp(value))
+            .max()
+            .map_or(0, |stamp| stamp + 1);
+
+        Ok(Self {
+            structured,
+            next_stamp: AtomicU64::new(next_stamp),
+        })
+    }
+
+    /// Record an event as delivered
+    pub fn insert(
+        &self,
+        interface_id: &InterfaceId,
+        event_id: EventId,
+    ) -> Result<(), StorageError> {
+        let stamp = self.next_stamp.fetch_add(1, Ordering::SeqCst);
+        self.structured.put(
+            SEEN_EVENTS,
+            &row_key(interface_id, &event_id),
+            &stamp.to_be_bytes(),
+        )
+    }
+
+    /// Forget an event, returning whether it was recorded
+    pub fn remove(
+        &self,
+        interface_id: &InterfaceId,
+        event_id: &EventId,
+    ) -> Result<bool, StorageError> {
+        self.structured
+            .delete(SEEN_EVENTS, &row_key(interface_id, event_id))
+    }
+
+    /// Every recorded event of an interface, oldest first
+    pub fn load(&self, interface_id: &InterfaceId) -> Result<Vec<EventId>, StorageError> {
+        let mut rows: Vec<(u64, EventId)> = self
+            .structured
+            .scan_prefix(SEEN_EVENTS, interface_id.as_bytes())?
+            .into_iter()
+            .filter_map(|(key, value)| {
+                let event_id = <[u8; 16]>::try_from(key.get(32..)?).ok()?;
+                Some((decode_stamp(&value)?, EventId::from_bytes(&event_id)))
+            })
+            .collect();
+        rows.sort_by_key(|(stamp, _)| *stamp);
+        Ok(rows.into_iter().map(|(_, id)| id).collect())
+    }
+}
+
+fn row_key(interface_id: &InterfaceId, event_id: &EventId) -> Vec<u8> {
+    let mut key = Vec::with_capacity(48);
+    key.extend_from_slice(interface_id.as_bytes());
+    key.extend_from_slice(&event_id.to_bytes());
+    key
+}
+
+fn decode_stamp(value: &[u8]) -> Option<u64> {
+    <[u8; 8]>::try_from(value).ok().map(u64::from_be_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structured::InMemoryStructuredStore;
+
+    #[test]
+    fn test_load_is_per_interface_and_ordered() {
+        let store = SeenEventStore::open(Arc::new(InMemoryStructuredStore::new())).unwrap();
+        let a = InterfaceId::new([0x11; 32]);
+        let b = InterfaceId::new([0x22; 32]);
+
+        store.insert(&a, EventId::new(9, 1)).unwrap();
+        store.insert(&b, EventId::new(1, 1)).unwrap();
+        store.insert(&a, EventId::new(1, 2)).unwrap();
+
+        assert_eq!(
+            store.load(&a).unwrap(),
+            vec![EventId::new(9, 1), EventId::new(1, 2)]
+        );
+        assert_eq!(store.load(&b).unwrap(), vec![EventId::new(1, 1)]);
+
+        assert!(store.remove(&a, &EventId::new(9, 1)).unwrap());
+        assert_eq!(store.load(&a).unwrap(), vec![EventId::new(1, 2)]);
+    }
+
+    #[test]
+    fn test_reopen_keeps_order() {
+        let structured: Arc<dyn StructuredStore> = Arc::new(InMemoryStructuredStore::new());
+        let a = InterfaceId::new([0x11; 32]);
+        let first = SeenEventStore::open(structured.clone()).unwrap();
+        first.insert(&a, EventId::new(5, 5)).unwrap();
+        drop(first);
+
+        let reopened = SeenEventStore::open(structured).unwrap();
+        reopened.insert(&a, EventId::new(1, 1)).unwrap();
+        assert_eq!(
+            reopened.load(&a).unwrap(),
+            vec![EventId::new(5, 5), EventId::new(1, 1)]
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! Backend abstraction for the structured storage layer
+//!
+//! The typed stores ([`PeerRegistry`](super::PeerRegistry),
+//! [`InterfaceStore`](super::InterfaceStore), [`SyncStateStore`](super::SyncStateStore)
+//! and the node log index) only need ordered byte-keyed tables with point
+//! reads, writes, deletes and prefix scans. [`StructuredStore`] captures that
+//! contract so redb can be swapped for another engine on platforms where it
+//! performs poorly.
+//!
+//! Backends shipped with this crate:
+//! - [`RedbStorage`](super::RedbStorage) — the default, file-backed
+//! - [`InMemoryStructuredStore`](super::InMemoryStructuredStore) — volatile, for
+//!   tests and ephemeral nodes
+//!
+//! Other engines (sqlite, sled, ...) implement the trait out of tree and are
+//! selected through [`StructuredBackend::Custom`]. Every implementation should
+//! pass the shared suite in [`conformance`](super::conformance).
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::error::StorageError;
+
+/// Type alias for scan results to simplify complex type
+pub type ScanResults = Vec<(Vec<u8>, Vec<u8>)>;
+
+/// A named table in the structured store
+///
+/// Tables are identified purely by name so that every backend can map them
+/// onto its own namespace (a redb table, a sqlite table, a sled tree, ...).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Table(&'static str);
+
+impl Table {
+    /// Declare a table with the given name
+    pub const fn new(name: &'static str) -> Self {
+        Self(name)
+    }
+
+    /// The table name
+    pub const fn name(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+/// Ordered key-value storage used by the structured layer
+///
+/// Keys within a table are compared lexicographically as raw bytes, and
+/// [`scan_prefix`](Self::scan_prefix) must return entries in that order.
+/// Each call is atomic on its own; no multi-call transactions are assumed.
+pub trait StructuredStore: Send + Sync {
+    /// Short backend identifier, used in logs
+    fn backend_name(&self) -> &'static str;
+
+    /// Put a key-value pair in a table, replacing any existing value
+    fn put(&self, table: Table, key: &[u8], value: &[u8]) -> Result<(), StorageError>;
+
+    /// Get a value from a table
+    fn get(&self, table: Table, key: &[u8]) -> Result<Option<Vec<u8>>, StorageError>;
+
+    /// Delete a key from a table, returning whether it existed
+    fn delete(&self, table: Table, key: &[u8]) -> Result<bool, StorageError>;
+
+    /// All entries in a table whose key starts with `prefix`, in key order
+    fn scan_prefix(&self, table: Table, prefix: &[u8]) -> Result<ScanResults, StorageError>;
+
+    /// Count entries with a prefix
+    fn count_prefix(&self, table: Table, prefix: &[u8]) -> Result<usize, StorageError> {
+        self.scan_prefix(table, prefix).map(|v| v.len())
+    }
+
+    /// Reclaim space, if the backend supports it
+    fn compact(&self) -> Result<(), StorageError> {
+        Ok(())
+    }
+}
+
+/// Which structured backend [`CompositeStorage`](crate::CompositeStorage) opens
+#[derive(Clone, Default)]
+pub enum StructuredBackend {
+    /// redb file at `CompositeStorageConfig::redb.db_path`
+    #[default]
+    Redb,
+    /// Volatile in-memory tables; contents are lost on drop
+    InMemory,
+    /// A caller-provided backend
+    Custom(Arc<dyn StructuredStore>),
+}
+
+impl fmt::Debug for StructuredBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Redb => f.write_str("Redb"),
+            Self::InMemory => f.write_str("InMemory"),
+            Self::Custom(store) => f
+                .debug_tuple("Custom")
+                .field(&store.backend_name())
+                .finish(),
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Conformance suite for [`StructuredStore`] backends
+//!
+//! Each check exercises one piece of the contract the typed stores rely on
+//! and panics on violation. Out-of-tree backends call [`run_all`] from their
+//! own tests; the backends in this crate run every check individually below.
+
+use super::backend::{StructuredStore, Table};
+use super::tables::{INTERFACES, PEER_REGISTRY};
+
+const TABLE: Table = PEER_REGISTRY;
+const OTHER_TABLE: Table = INTERFACES;
+
+/// Run every conformance check against a fresh, empty store
+pub fn run_all(store: &dyn StructuredStore) {
+    put_then_get(store);
+    get_missing_is_none(store);
+    put_overwrites(store);
+    delete_reports_existence(store);
+    tables_are_isolated(store);
+    scan_prefix_is_ordered_and_bounded(store);
+    scan_empty_prefix_returns_table(store);
+    count_matches_scan(store);
+    binary_keys_round_trip(store);
+}
+
+/// A written value can be read back
+pub fn put_then_get(store: &dyn StructuredStore) {
+    store.put(TABLE, b"conf:put", b"value").unwrap();
+    assert_eq!(store.get(TABLE, b"conf:put").unwrap(), Some(b"value".to_vec()));
+}
+
+/// Reading an absent key yields `None`, not an error
+pub fn get_missing_is_none(store: &dyn StructuredStore) {
+    assert_eq!(store.get(TABLE, b"conf:absent").unwrap(), None);
+}
+
+/// A second put replaces the first
+pub fn put_overwrites(store: &dyn StructuredStore) {
+    store.put(TABLE, b"conf:over", b"first").unwrap();
+    store.put(TABLE, b"conf:over", b"second").unwrap();
+    assert_eq!(store.get(TABLE, b"conf:over").unwrap(), Some(b"second".to_vec()));
+    assert_eq!(store.count_prefix(TABLE, b"conf:over").unwrap(), 1);
+}
+
+/// Delete returns `true` only when the key existed
+pub fn delete_reports_existence(store: &dyn StructuredStore) {
+    store.put(TABLE, b"conf:del", b"value").unwrap();
+    assert!(store.delete(TABLE, b"conf:del").unwrap());
+    assert!(!store.delete(TABLE, b"conf:del").unwrap());
+    assert_eq!(store.get(TABLE, b"conf:del").unwrap(), None);
+}
+
+/// The same key in different tables holds independent values
+pub fn tables_are_isolated(store: &dyn StructuredStore) {
+    store.put(TABLE, b"conf:iso", b"a").unwrap();
+    store.put(OTHER_TABLE, b"conf:iso", b"b").unwrap();
+    assert_eq!(store.get(TABLE, b"conf:iso").unwrap(), Some(b"a".to_vec()));
+    assert_eq!(store.get(OTHER_TABLE, b"conf:iso").unwrap(), Some(b"b".to_vec()));
+
+    store.delete(OTHER_TABLE, b"conf:iso").unwrap();
+    assert_eq!(store.get(TABLE, b"conf:iso").unwrap(), Some(b"a".to_vec()));
+}
+
+/// Prefix scans return exactly the matching keys in byte order
+pub fn scan_prefix_is_ordered_and_bounded(store: &dyn StructuredStore) {
+    store.put(TABLE, b"scan:c", b"3").unwrap();
+    store.put(TABLE, b"scan:a", b"1").unwrap();
+    store.put(TABLE, b"scan:b", b"2").unwrap();
+    store.put(TABLE, b"scan", b"shorter").unwrap();
+    store.put(TABLE, b"scao", b"after").unwrap();
+    store.put(TABLE, b"scam:z", b"before").unwrap();
+
+    let entries = store.scan_prefix(TABLE, b"scan:").unwrap();
+    let keys: Vec<&[u8]> = entries.iter().map(|(k, _)| k.as_slice()).collect();
+    assert_eq!(keys, vec![&b"scan:a"[..], b"scan:b", b"scan:c"]);
+    assert_eq!(entries[1].1, b"2".to_vec());
+}
+
+/// An empty prefix scans the whole table and nothing else
+pub fn scan_empty_prefix_returns_table(store: &dyn StructuredStore) {
+    store.put(OTHER_TABLE, b"all:1", b"x").unwrap();
+    store.put(OTHER_TABLE, b"all:2", b"y").unwrap();
+
+    let entries = store.scan_prefix(OTHER_TABLE, b"").unwrap();
+    assert!(entries.iter().any(|(k, _)| k == b"all:1"));
+    assert!(entries.iter().any(|(k, _)| k == b"all:2"));
+    assert!(entries.windows(2).all(|w| w[0].0 < w[1].0));
+    assert_eq!(store.get(TABLE, b"all:1").unwrap(), None);
+}
+
+/// `count_prefix` agrees with `scan_prefix`
+pub fn count_matches_scan(store: &dyn StructuredStore) {
+    for i in 0u8..5 {
+        store.put(TABLE, &[b'n', b':', i], &[i]).unwrap();
+    }
+    assert_eq!(store.count_prefix(TABLE, b"n:").unwrap(), 5);
+    assert_eq!(
+        store.count_prefix(TABLE, b"n:").unwrap(),
+        store.scan_prefix(TABLE, b"n:").unwrap().len()
+    );
+    assert_eq!(store.count_prefix(TABLE, b"none:").unwrap(), 0);
+}
+
+/// Keys and values with arbitrary bytes survive intact and sort unsigned
+pub fn binary_keys_round_trip(store: &dyn StructuredStore) {
+    let low = [0xB0, 0x00, 0x01];
+    let high = [0xB0, 0xFF, 0x00];
+    store.put(TABLE, &high, &[0u8; 0]).unwrap();
+    store.put(TABLE, &low, &[0xFF; 64]).unwrap();
+
+    assert_eq!(store.get(TABLE, &low).unwrap(), Some(vec![0xFF; 64]));
+    assert_eq!(store.get(TABLE, &high).unwrap(), Some(Vec::new()));
+
+    let keys: Vec<Vec<u8>> = store
+        .scan_prefix(TABLE, &[0xB0])
+        .unwrap()
+        .into_iter()
+        .map(|(k, _)| k)
+        .collect();
+    assert_eq!(keys, vec![low.to_vec(), high.to_vec()]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! conformance_tests {
+        ($backend:ident, $make:expr) => {
+            mod $backend {
+                use super::*;
+
+                #[allow(unused_imports)]
+                use crate::structured::{InMemoryStructuredStore, RedbStorage, RedbStorageConfig};
+
+                conformance_tests!(@checks $make;
+                    put_then_get,
+                    get_missing_is_none,
+                    put_overwrites,
+                    delete_reports_existence,
+                    tables_are_isolated,
+                    scan_prefix_is_ordered_and_bounded,
+                    scan_empty_prefix_returns_table,
+                    count_matches_scan,
+                    binary_keys_round_trip,
+                    run_all
+                );
+            }
+        };
+        (@checks $make:expr; $($check:ident),*) => {
+            $(
+                #[test]
+                fn $check() {
+                    let (store, _guard) = $make;
+                    super::$check(&*store);
+                }
+            )*
+        };
+    }
+
+    conformance_tests!(redb, {
+        let temp = tempfile::TempDir::new().unwrap();
+        let store = RedbStorage::open(RedbStorageConfig {
+            db_path: temp.path().join("conformance.redb"),
+            ..Default::default()
+        })
+        .unwrap();
+        (Box::new(store) as Box<dyn StructuredStore>, Some(temp))
+    });
+
+    conformance_tests!(memory, {
+        (
+            Box::new(InMemoryStructuredStore::new()) as Box<dyn StructuredStore>,
+            None::<tempfile::TempDir>,
+        )
+    });
+}
@@ -9,7 +9,8 @@ use tracing::debug;
 
 use indras_core::{InterfaceId, PeerIdentity};
 
-use super::tables::{INTERFACE_MEMBERS, INTERFACES, RedbStorage, SNAPSHOTS};
+use super::backend::StructuredStore;
+use super::tables::{INTERFACE_MEMBERS, INTERFACES, SNAPSHOTS};
 use crate::error::StorageError;
 
 /// Metadata about an interface
@@ -108,12 +109,12 @@ impl MembershipRecord {
 
 /// Interface storage manager
 pub struct InterfaceStore {
-    storage: Arc<RedbStorage>,
+    storage: Arc<dyn StructuredStore>,
 }
 
 impl InterfaceStore {
     /// Create a new interface store
-    pub fn new(storage: Arc<RedbStorage>) -> Self {
+    pub fn new(storage: Arc<dyn StructuredStore>) -> Self {
         Self { storage }
     }
 
@@ -335,7 +336,7 @@ mod tests {
     use indras_core::SimulationIdentity;
     use tempfile::TempDir;
 
-    use crate::structured::tables::RedbStorageConfig;
+    use crate::structured::tables::{RedbStorage, RedbStorageConfig};
 
     fn create_test_store() -> (InterfaceStore, TempDir) {
         let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,70 @@
+//! In-memory structured store
+//!
+//! A [`StructuredStore`] backed by one `BTreeMap` per table. Nothing is
+//! persisted; useful for tests and ephemeral nodes.
+
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Bound;
+use std::sync::RwLock;
+
+use super::backend::{ScanResults, StructuredStore, Table};
+use crate::error::StorageError;
+
+type TableMap = BTreeMap<Vec<u8>, Vec<u8>>;
+
+/// Volatile [`StructuredStore`] implementation
+#[derive(Default)]
+pub struct InMemoryStructuredStore {
+    tables: RwLock<HashMap<&'static str, TableMap>>,
+}
+
+impl InMemoryStructuredStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn poisoned() -> StorageError {
+        StorageError::Io("in-memory structured store lock poisoned".to_string())
+    }
+}
+
+impl StructuredStore for InMemoryStructuredStore {
+    fn backend_name(&self) -> &'static str {
+        "memory"
+    }
+
+    fn put(&self, table: Table, key: &[u8], value: &[u8]) -> Result<(), StorageError> {
+        let mut tables = self.tables.write().map_err(|_| Self::poisoned())?;
+        tables
+            .entry(table.name())
+            .or_default()
+            .insert(key.to_vec(), value.to_vec());
+        Ok(())
+    }
+
+    fn get(&self, table: Table, key: &[u8]) -> Result<Option<Vec<u8>>, StorageError> {
+        let tables = self.tables.read().map_err(|_| Self::poisoned())?;
+        Ok(tables.get(table.name()).and_then(|t| t.get(key).cloned()))
+    }
+
+    fn delete(&self, table: Table, key: &[u8]) -> Result<bool, StorageError> {
+        let mut tables = self.tables.write().map_err(|_| Self::poisoned())?;
+        Ok(tables
+            .get_mut(table.name())
+            .is_some_and(|t| t.remove(key).is_some()))
+    }
+
+    fn scan_prefix(&self, table: Table, prefix: &[u8]) -> Result<ScanResults, StorageError> {
+        let tables = self.tables.read().map_err(|_| Self::poisoned())?;
+        let Some(entries) = tables.get(table.name()) else {
+            return Ok(Vec::new());
+        };
+
+        Ok(entries
+            .range::<[u8], _>((Bound::Included(prefix), Bound::Unbounded))
+            .take_while(|(key, _)| key.starts_with(prefix))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect())
+    }
+}
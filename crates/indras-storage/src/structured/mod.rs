@@ -1,4 +1,4 @@
-//! Structured storage using redb
+//! Structured storage behind a pluggable backend
 //!
 //! This module provides queryable, mutable storage for:
 //! - Peer registry (peer metadata, last seen times)
@@ -7,15 +7,26 @@
 //! - Event indices
 //!
 //! Unlike the append-only log, this storage supports updates and deletions.
+//!
+//! The typed stores sit on top of the [`StructuredStore`] trait. redb is the
+//! default backend; [`InMemoryStructuredStore`] and caller-provided backends
+//! are selected through [`StructuredBackend`].
 
+mod backend;
+pub mod conformance;
 pub mod interface_store;
+mod memory;
 mod peer_registry;
 mod sync_state;
 mod tables;
 
+pub use backend::{ScanResults, StructuredBackend, StructuredStore, Table};
+
 pub use interface_store::{InterfaceRecord, InterfaceStore, MembershipRecord};
 pub use peer_registry::{PeerRecord, PeerRegistry};
+pub use memory::InMemoryStructuredStore;
 pub use sync_state::{SyncStateRecord, SyncStateStore};
 pub use tables::{
-    RedbStorage, RedbStorageConfig, NODE_LOG_INDEX, NODE_LOG_META,
+    RedbStorage, RedbStorageConfig, ALL_TABLES, INTENT_JOURNAL, INTERFACES, JOB_RUNS,
+    NODE_LOG_INDEX, NODE_LOG_META, SEEN_EVENTS, TRANSLATIONS,
 };
@@ -9,7 +9,8 @@ use tracing::debug;
 
 use indras_core::PeerIdentity;
 
-use super::tables::{PEER_REGISTRY, RedbStorage};
+use super::backend::StructuredStore;
+use super::tables::PEER_REGISTRY;
 use crate::error::StorageError;
 
 /// Metadata about a peer
@@ -67,12 +68,12 @@ impl PeerRecord {
 
 /// Peer registry for managing peer metadata
 pub struct PeerRegistry {
-    storage: Arc<RedbStorage>,
+    storage: Arc<dyn StructuredStore>,
 }
 
 impl PeerRegistry {
     /// Create a new peer registry
-    pub fn new(storage: Arc<RedbStorage>) -> Self {
+    pub fn new(storage: Arc<dyn StructuredStore>) -> Self {
         Self { storage }
     }
 
@@ -176,7 +177,7 @@ mod tests {
     use indras_core::SimulationIdentity;
     use tempfile::TempDir;
 
-    use crate::structured::tables::RedbStorageConfig;
+    use crate::structured::tables::{RedbStorage, RedbStorageConfig};
 
     fn create_test_registry() -> (PeerRegistry, TempDir) {
         let temp_dir = TempDir::new().unwrap();
@@ -9,7 +9,8 @@ use tracing::debug;
 
 use indras_core::{EventId, InterfaceId, PeerIdentity};
 
-use super::tables::{PENDING_DELIVERY, RedbStorage, SYNC_STATE};
+use super::backend::StructuredStore;
+use super::tables::{PENDING_DELIVERY, SYNC_STATE};
 use crate::error::StorageError;
 
 /// Sync state for a (peer, interface) pair
@@ -85,12 +86,12 @@ impl PendingDeliveryRecord {
 
 /// Sync state storage manager
 pub struct SyncStateStore {
-    storage: Arc<RedbStorage>,
+    storage: Arc<dyn StructuredStore>,
 }
 
 impl SyncStateStore {
     /// Create a new sync state store
-    pub fn new(storage: Arc<RedbStorage>) -> Self {
+    pub fn new(storage: Arc<dyn StructuredStore>) -> Self {
         Self { storage }
     }
 
@@ -313,7 +314,7 @@ mod tests {
     use indras_core::SimulationIdentity;
     use tempfile::TempDir;
 
-    use crate::structured::tables::RedbStorageConfig;
+    use crate::structured::tables::{RedbStorage, RedbStorageConfig};
 
     fn create_test_store() -> (SyncStateStore, TempDir) {
         let temp_dir = TempDir::new().unwrap();
@@ -1,6 +1,7 @@
-//! redb table definitions and storage manager
+//! Table definitions and the redb storage backend
 //!
-//! Defines all tables used for structured storage.
+//! Defines all tables used for structured storage and the default
+//! [`StructuredStore`] implementation on top of redb.
 
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -8,40 +9,70 @@ use std::sync::Arc;
 use redb::{Database, TableDefinition};
 use tracing::{debug, info, instrument};
 
+use super::backend::{ScanResults, StructuredStore, Table};
 use crate::error::StorageError;
 
-/// Type alias for scan results to simplify complex type
-pub type ScanResults = Vec<(Vec<u8>, Vec<u8>)>;
-
 // Table definitions
 // Key: peer_id bytes, Value: serialized PeerRecord
-pub const PEER_REGISTRY: TableDefinition<&[u8], &[u8]> = TableDefinition::new("peer_registry");
+pub const PEER_REGISTRY: Table = Table::new("peer_registry");
 
 // Key: interface_id bytes, Value: serialized InterfaceRecord
-pub const INTERFACES: TableDefinition<&[u8], &[u8]> = TableDefinition::new("interfaces");
+pub const INTERFACES: Table = Table::new("interfaces");
 
 // Key: (interface_id, peer_id) concatenated, Value: membership info
-pub const INTERFACE_MEMBERS: TableDefinition<&[u8], &[u8]> =
-    TableDefinition::new("interface_members");
+pub const INTERFACE_MEMBERS: Table = Table::new("interface_members");
 
 // Key: (peer_id, interface_id) concatenated, Value: serialized SyncStateRecord
-pub const SYNC_STATE: TableDefinition<&[u8], &[u8]> = TableDefinition::new("sync_state");
+pub const SYNC_STATE: Table = Table::new("sync_state");
 
 // Key: (interface_id, event_id) concatenated, Value: log offset (u64 as bytes)
-pub const EVENT_INDEX: TableDefinition<&[u8], &[u8]> = TableDefinition::new("event_index");
+pub const EVENT_INDEX: Table = Table::new("event_index");
 
 // Key: (peer_id, interface_id, event_id) concatenated, Value: pending delivery metadata
-pub const PENDING_DELIVERY: TableDefinition<&[u8], &[u8]> =
-    TableDefinition::new("pending_delivery");
+pub const PENDING_DELIVERY: Table = Table::new("pending_delivery");
 
 // Key: interface_id, Value: serialized SnapshotMetadata
-pub const SNAPSHOTS: TableDefinition<&[u8], &[u8]> = TableDefinition::new("snapshots");
+pub const SNAPSHOTS: Table = Table::new("snapshots");
 
 // Key: sequence (8 bytes BE), Value: file offset (8 bytes BE)
-pub const NODE_LOG_INDEX: TableDefinition<&[u8], &[u8]> = TableDefinition::new("node_log_index");
+pub const NODE_LOG_INDEX: Table = Table::new("node_log_index");
 
 // Key: b"meta", Value: postcard NodeLogMeta
-pub const NODE_LOG_META: TableDefinition<&[u8], &[u8]> = TableDefinition::new("node_log_meta");
+pub const NODE_LOG_META: Table = Table::new("node_log_meta");
+
+// Key: intent id (8 bytes BE), Value: postcard IntentRecord
+pub const INTENT_JOURNAL: Table = Table::new("intent_journal");
+
+// Key: (interface_id, event_id) concatenated, Value: insertion stamp (8 bytes BE)
+pub const SEEN_EVENTS: Table = Table::new("seen_events");
+
+// Key: (message_id length, message_id, language) concatenated, Value: postcard TranslationRecord
+pub const TRANSLATIONS: Table = Table::new("translations");
+
+// Key: (scope tag, optional interface_id, job name) concatenated, Value: last run millis (8 bytes BE)
+pub const JOB_RUNS: Table = Table::new("job_runs");
+
+/// Every table the structured layer uses
+pub const ALL_TABLES: &[Table] = &[
+    PEER_REGISTRY,
+    INTERFACES,
+    INTERFACE_MEMBERS,
+    SYNC_STATE,
+    EVENT_INDEX,
+    PENDING_DELIVERY,
+    SNAPSHOTS,
+    NODE_LOG_INDEX,
+    NODE_LOG_META,
+    INTENT_JOURNAL,
+    SEEN_EVENTS,
+    TRANSLATIONS,
+    JOB_RUNS,
+];
+
+/// Map a backend-neutral table onto its redb definition
+fn definition(table: Table) -> TableDefinition<'static, &'static [u8], &'static [u8]> {
+    TableDefinition::new(table.name())
+}
 
 /// Configuration for redb storage
 #[derive(Debug, Clone)]
@@ -106,33 +137,11 @@ impl RedbStorage {
             .map_err(|e| StorageError::Io(e.to_string()))?;
 
         // Create tables if they don't exist
-        write_txn
-            .open_table(PEER_REGISTRY)
-            .map_err(|e| StorageError::Io(e.to_string()))?;
-        write_txn
-            .open_table(INTERFACES)
-            .map_err(|e| StorageError::Io(e.to_string()))?;
-        write_txn
-            .open_table(INTERFACE_MEMBERS)
-            .map_err(|e| StorageError::Io(e.to_string()))?;
-        write_txn
-            .open_table(SYNC_STATE)
-            .map_err(|e| StorageError::Io(e.to_string()))?;
-        write_txn
-            .open_table(EVENT_INDEX)
-            .map_err(|e| StorageError::Io(e.to_string()))?;
-        write_txn
-            .open_table(PENDING_DELIVERY)
-            .map_err(|e| StorageError::Io(e.to_string()))?;
-        write_txn
-            .open_table(SNAPSHOTS)
-            .map_err(|e| StorageError::Io(e.to_string()))?;
-        write_txn
-            .open_table(NODE_LOG_INDEX)
-            .map_err(|e| StorageError::Io(e.to_string()))?;
-        write_txn
-            .open_table(NODE_LOG_META)
-            .map_err(|e| StorageError::Io(e.to_string()))?;
+        for table in ALL_TABLES {
+            write_txn
+                .open_table(definition(*table))
+                .map_err(|e| StorageError::Io(e.to_string()))?;
+        }
 
         write_txn
             .commit()
@@ -151,14 +160,14 @@ impl RedbStorage {
     pub fn config(&self) -> &RedbStorageConfig {
         &self.config
     }
+}
+
+impl StructuredStore for RedbStorage {
+    fn backend_name(&self) -> &'static str {
+        "redb"
+    }
 
-    /// Put a key-value pair in a table
-    pub fn put(
-        &self,
-        table: TableDefinition<&[u8], &[u8]>,
-        key: &[u8],
-        value: &[u8],
-    ) -> Result<(), StorageError> {
+    fn put(&self, table: Table, key: &[u8], value: &[u8]) -> Result<(), StorageError> {
         let write_txn = self
             .db
             .begin_write()
@@ -166,7 +175,7 @@ impl RedbStorage {
 
         {
             let mut table = write_txn
-                .open_table(table)
+                .open_table(definition(table))
                 .map_err(|e| StorageError::Io(e.to_string()))?;
             table
                 .insert(key, value)
@@ -180,19 +189,14 @@ impl RedbStorage {
         Ok(())
     }
 
-    /// Get a value from a table
-    pub fn get(
-        &self,
-        table: TableDefinition<&[u8], &[u8]>,
-        key: &[u8],
-    ) -> Result<Option<Vec<u8>>, StorageError> {
+    fn get(&self, table: Table, key: &[u8]) -> Result<Option<Vec<u8>>, StorageError> {
         let read_txn = self
             .db
             .begin_read()
             .map_err(|e| StorageError::Io(e.to_string()))?;
 
         let table = read_txn
-            .open_table(table)
+            .open_table(definition(table))
             .map_err(|e| StorageError::Io(e.to_string()))?;
 
         let value = table
@@ -203,12 +207,7 @@ impl RedbStorage {
         Ok(value)
     }
 
-    /// Delete a key from a table
-    pub fn delete(
-        &self,
-        table: TableDefinition<&[u8], &[u8]>,
-        key: &[u8],
-    ) -> Result<bool, StorageError> {
+    fn delete(&self, table: Table, key: &[u8]) -> Result<bool, StorageError> {
         let write_txn = self
             .db
             .begin_write()
@@ -216,7 +215,7 @@ impl RedbStorage {
 
         let removed = {
             let mut table = write_txn
-                .open_table(table)
+                .open_table(definition(table))
                 .map_err(|e| StorageError::Io(e.to_string()))?;
             table
                 .remove(key)
@@ -231,19 +230,14 @@ impl RedbStorage {
         Ok(removed)
     }
 
-    /// Iterate over all entries in a table with a prefix
-    pub fn scan_prefix(
-        &self,
-        table: TableDefinition<&[u8], &[u8]>,
-        prefix: &[u8],
-    ) -> Result<ScanResults, StorageError> {
+    fn scan_prefix(&self, table: Table, prefix: &[u8]) -> Result<ScanResults, StorageError> {
         let read_txn = self
             .db
             .begin_read()
             .map_err(|e| StorageError::Io(e.to_string()))?;
 
         let table = read_txn
-            .open_table(table)
+            .open_table(definition(table))
             .map_err(|e| StorageError::Io(e.to_string()))?;
 
         let mut results = Vec::new();
@@ -268,21 +262,12 @@ impl RedbStorage {
         Ok(results)
     }
 
-    /// Count entries with a prefix
-    pub fn count_prefix(
-        &self,
-        table: TableDefinition<&[u8], &[u8]>,
-        prefix: &[u8],
-    ) -> Result<usize, StorageError> {
-        self.scan_prefix(table, prefix).map(|v| v.len())
-    }
-
     /// Compact the database
     ///
     /// Note: redb's compact() requires exclusive access. This may not be
     /// possible with our Arc<Database> design. Consider running this
     /// during maintenance windows.
-    pub fn compact(&self) -> Result<(), StorageError> {
+    fn compact(&self) -> Result<(), StorageError> {
         // redb 2.x compact() requires &mut self, which isn't possible with Arc
         // For now, we skip compaction. In production, close and reopen the DB
         // or use a different approach.
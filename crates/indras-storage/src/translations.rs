@@ -0,0 +1,143 @@
+//! Local cache of machine-translated chat messages
+//!
+//! Translations are produced on this device for display only, so they are
+//! kept here rather than synced. One row per message and target language:
+//!
+//! ```text
+//! key:   message_id length (2 bytes BE) ‖ message_id ‖ language
+//! value: postcard TranslationRecord
+//! ```
+//!
+//! Each record remembers a hash of the text it was made from, so a cached
+//! translation of a message that has since been edited can be recognized
+//! as stale.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::StorageError;
+use crate::structured::{StructuredStore, TRANSLATIONS};
+
+/// A cached translation of one message into one language
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranslationRecord {
+    /// blake3 hash of the source text
+    pub source_hash: [u8; 32],
+    /// The translated text
+    pub text: String,
+}
+
+impl TranslationRecord {
+    /// Create a record for a translation of `source`
+    pub fn new(source: &str, text: String) -> Self {
+        Self {
+            source_hash: *blake3::hash(source.as_bytes()).as_bytes(),
+            text,
+        }
+    }
+
+    /// Whether the record was made from exactly `source`
+    pub fn matches(&self, source: &str) -> bool {
+        self.source_hash == *blake3::hash(source.as_bytes()).as_bytes()
+    }
+}
+
+/// Durable per-message, per-language translation cache
+pub struct TranslationStore {
+    structured: Arc<dyn StructuredStore>,
+}
+
+impl TranslationStore {
+    /// Open the store
+    pub fn new(structured: Arc<dyn StructuredStore>) -> Self {
+        Self { structured }
+    }
+
+    /// The cached translation of a message into a language, if any
+    pub fn get(
+        &self,
+        message_id: &str,
+        language: &str,
+    ) -> Result<Option<TranslationRecord>, StorageError> {
+        self.structured
+            .get(TRANSLATIONS, &row_key(message_id, language))?
+            .map(|value| {
+                postcard::from_bytes(&value)
+                    .map_err(|e| StorageError::Deserialization(e.to_string()))
+            })
+            .transpose()
+    }
+
+    /// Cache a translation, replacing any earlier one
+    pub fn put(
+        &self,
+        message_id: &str,
+        language: &str,
+        record: &TranslationRecord,
+    ) -> Result<(), StorageError> {
+        let value = postcard::to_allocvec(record)
+            .map_err(|e| StorageError::Serialization(e.to_string()))?;
+        self.structured
+            .put(TRANSLATIONS, &row_key(message_id, language), &value)
+    }
+
+    /// Drop every cached translation of a message, returning how many
+    pub fn remove_message(&self, message_id: &str) -> Result<usize, StorageError> {
+        let rows = self
+            .structured
+            .scan_prefix(TRANSLATIONS, &message_prefix(message_id))?;
+        for (key, _) in &rows {
+            self.structured.delete(TRANSLATIONS, key)?;
+        }
+        Ok(rows.len())
+    }
+}
+
+fn message_prefix(message_id: &str) -> Vec<u8> {
+    let id = message_id.as_bytes();
+    let mut prefix = Vec::with_capacity(2 + id.len());
+    prefix.extend_from_slice(&(id.len() as u16).to_be_bytes());
+    prefix.extend_from_slice(id);
+    prefix
+}
+
+fn row_key(message_id: &str, language: &str) -> Vec<u8> {
+    let mut key = message_prefix(message_id);
+    key.extend_from_slice(language.as_bytes());
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structured::InMemoryStructuredStore;
+
+    #[test]
+    fn test_put_get_per_language() {
+        let store = TranslationStore::new(Arc::new(InMemoryStructuredStore::new()));
+        let record = TranslationRecord::new("Hello", "Hola".to_string());
+        store.put("msg-1", "es", &record).unwrap();
+
+        let cached = store.get("msg-1", "es").unwrap().unwrap();
+        assert_eq!(cached.text, "Hola");
+        assert!(cached.matches("Hello"));
+        assert!(!cached.matches("Hello, edited"));
+        assert!(store.get("msg-1", "fr").unwrap().is_none());
+        assert!(store.get("msg-10", "es").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_remove_message_keeps_others() {
+        let store = TranslationStore::new(Arc::new(InMemoryStructuredStore::new()));
+        for (id, lang) in [("msg-1", "es"), ("msg-1", "fr"), ("msg-10", "es")] {
+            store
+                .put(id, lang, &TranslationRecord::new("Hi", "..".to_string()))
+                .unwrap();
+        }
+
+        assert_eq!(store.remove_message("msg-1").unwrap(), 2);
+        assert!(store.get("msg-1", "fr").unwrap().is_none());
+        assert!(store.get("msg-10", "es").unwrap().is_some());
+    }
+}
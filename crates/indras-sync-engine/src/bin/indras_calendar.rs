@@ -0,0 +1,255 @@
+//! Serve realm calendars as ICS feeds.
+//!
+//! Opens the node in `--data-dir` and builds one iCalendar feed per realm
+//! with its calendar entries, announcements and quest deadlines. Feeds are
+//! written to `--out` as `<realm-id>.ics` and/or served over HTTP on
+//! `--listen` at `/<realm-id>.ics`, so a calendar app can subscribe to
+//! them. While running, feeds are regenerated whenever their realm
+//! changes.
+//!
+//! Usage:
+//!
+//! ```bash
+//! # Export every conversation realm's feed once
+//! indras-calendar --data-dir ~/.indras --out calendars/
+//!
+//! # Serve one realm's feed on localhost, checking for changes every 30 seconds
+//! indras-calendar --data-dir ~/.indras --realm 3fa1... --listen 127.0.0.1:8417
+//! ```
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use indras_network::{CalendarExporter, IndrasNetwork, RealmId, RealmSnapshot};
+use indras_sync_engine::IntentionDocument;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+const USAGE: &str = "usage: indras-calendar --data-dir DIR [--realm REALM_ID]... \
+                     [--out DIR] [--listen ADDR] [--interval SECONDS]";
+
+/// How often realms are checked for changes when none is given.
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Rendered feeds by file name (`<realm-id>.ics`).
+type Feeds = Arc<RwLock<HashMap<String, String>>>;
+
+struct Args {
+    data_dir: PathBuf,
+    realms: Vec<RealmId>,
+    out: Option<PathBuf>,
+    listen: Option<SocketAddr>,
+    interval: Option<Duration>,
+}
+
+fn parse_args(args: &[String]) -> Result<Args, String> {
+    let mut data_dir = None;
+    let mut realms = Vec::new();
+    let mut out = None;
+    let mut listen = None;
+    let mut interval = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().cloned().ok_or_else(|| USAGE.to_string());
+        match arg.as_str() {
+            "--data-dir" => data_dir = Some(PathBuf::from(value()?)),
+            "--realm" => realms.push(parse_realm_id(&value()?)?),
+            "--out" => out = Some(PathBuf::from(value()?)),
+            "--listen" => {
+                let addr = value()?;
+                listen = Some(
+                    addr.parse()
+                        .map_err(|_| format!("invalid listen address: {addr}"))?,
+                );
+            }
+            "--interval" => {
+                let secs: u64 = value()?
+                    .parse()
+                    .map_err(|_| "--interval expects a number of seconds".to_string())?;
+                interval = Some(Duration::from_secs(secs.max(1)));
+            }
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+    if out.is_none() && listen.is_none() {
+        return Err(USAGE.to_string());
+    }
+
+    Ok(Args {
+        data_dir: data_dir.ok_or(USAGE)?,
+        realms,
+        out,
+        listen,
+        interval,
+    })
+}
+
+fn parse_realm_id(hex_id: &str) -> Result<RealmId, String> {
+    let bytes: [u8; 32] = hex::decode(hex_id)
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| format!("invalid realm id: {hex_id}"))?;
+    Ok(RealmId::new(bytes))
+}
+
+fn exporter() -> CalendarExporter {
+    CalendarExporter::new().with_source(|snapshot: &RealmSnapshot| {
+        snapshot
+            .document::<IntentionDocument>("intentions")
+            .deadline_events(&snapshot.realm_id())
+    })
+}
+
+fn feed_name(realm_id: &RealmId) -> String {
+    format!("{}.ics", hex::encode(realm_id.as_bytes()))
+}
+
+/// Store a regenerated feed and write it to `out`, if given.
+fn publish(feeds: &Feeds, out: Option<&PathBuf>, name: String, ics: String) -> Result<(), String> {
+    if let Some(out) = out {
+        std::fs::create_dir_all(out).map_err(|e| format!("{}: {e}", out.display()))?;
+        let path = out.join(&name);
+        std::fs::write(&path, &ics).map_err(|e| format!("{}: {e}", path.display()))?;
+        println!("{}: written", path.display());
+    }
+    feeds
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(name, ics);
+    Ok(())
+}
+
+async fn serve(listener: TcpListener, feeds: Feeds) {
+    loop {
+        let Ok((stream, _)) = listener.accept().await else {
+            continue;
+        };
+        let feeds = Arc::clone(&feeds);
+        tokio::spawn(async move {
+            if let Err(e) = respond(stream, &feeds).await {
+                tracing::debug!(error = %e, "calendar request failed");
+            }
+        });
+    }
+}
+
+/// Answer one `GET /<realm-id>.ics` request.
+async fn respond(mut stream: TcpStream, feeds: &Feeds) -> std::io::Result<()> {
+    let mut request = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < 8192 {
+        let n = stream.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        request.extend_from_slice(&buf[..n]);
+    }
+    let request = String::from_utf8_lossy(&request);
+    let mut parts = request.split_whitespace();
+    let (method, path) = (parts.next(), parts.next().unwrap_or("/"));
+    let name = path.trim_start_matches('/').split('?').next().unwrap_or("");
+
+    let feed = feeds
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(name)
+        .cloned();
+    let response = match (method, feed) {
+        (Some("GET"), Some(ics)) => format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/calendar; charset=utf-8\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{ics}",
+            ics.len()
+        ),
+        (Some("GET"), None) => {
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+        }
+        _ => "HTTP/1.1 405 Method Not Allowed\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+            .to_string(),
+    };
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+async fn run(args: Args) -> Result<(), String> {
+    let network = IndrasNetwork::new(&args.data_dir)
+        .await
+        .map_err(|e| e.to_string())?;
+    network.start().await.map_err(|e| e.to_string())?;
+
+    let realm_ids = if args.realms.is_empty() {
+        network.conversation_realms()
+    } else {
+        args.realms.clone()
+    };
+    let mut realms = Vec::new();
+    for id in realm_ids {
+        let realm = network.get_realm_by_id(&id).ok_or_else(|| {
+            format!(
+                "realm {} not found on this node",
+                hex::encode(id.as_bytes())
+            )
+        })?;
+        realms.push(realm);
+    }
+
+    let exporter = exporter();
+    let feeds: Feeds = Arc::default();
+    let mut last: Vec<Option<RealmSnapshot>> = realms.iter().map(|_| None).collect();
+    let watch = args.interval.or(args.listen.map(|_| DEFAULT_INTERVAL));
+
+    let server = match args.listen {
+        Some(addr) => {
+            let listener = TcpListener::bind(addr)
+                .await
+                .map_err(|e| format!("{addr}: {e}"))?;
+            println!("serving calendars on http://{addr}/<realm-id>.ics");
+            Some(tokio::spawn(serve(listener, Arc::clone(&feeds))))
+        }
+        None => None,
+    };
+
+    loop {
+        for (realm, last) in realms.iter().zip(last.iter_mut()) {
+            let snapshot = realm.snapshot().await.map_err(|e| e.to_string())?;
+            if last.as_ref().is_some_and(|l| snapshot.same_state_as(l)) {
+                continue;
+            }
+            let name = realm.name().map(String::from);
+            let ics = exporter.feed(&snapshot, name).to_ics();
+            publish(&feeds, args.out.as_ref(), feed_name(&realm.id()), ics)?;
+            *last = Some(snapshot);
+        }
+
+        let Some(interval) = watch else { break };
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            _ = tokio::time::sleep(interval) => {}
+        }
+    }
+
+    if let Some(server) = server {
+        server.abort();
+    }
+    network.stop().await.map_err(|e| e.to_string())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match parse_args(&args) {
+        Ok(args) => run(args).await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Drive a realm from the shell.
+//!
+//! Opens the node in `--data-dir` and keeps two files in `--dir` for the
+//! realm: `events.jsonl`, the realm's messages one JSON object per line,
+//! extended as new ones arrive; and `control`, where each line appended is
+//! run as a command. Besides `send`, `reply` and `react`, `quest` (or
+//! `intention`) creates a quest.
+//!
+//! Usage:
+//!
+//! ```bash
+//! indras-feed --data-dir ~/.indras --realm 3fa1... --dir ~/realms/garden &
+//!
+//! tail -f ~/realms/garden/events.jsonl | jq -r .text
+//! echo "send Watering done" >> ~/realms/garden/control
+//! echo "quest Fix the fence | Posts on the north side are loose" >> ~/realms/garden/control
+//! ```
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use indras_network::{ControlCommand, ControlFile, IndrasNetwork, Realm, RealmId};
+use indras_sync_engine::{CommandOutcome, RealmIntentions};
+
+const USAGE: &str = "usage: indras-feed --data-dir DIR --realm REALM_ID --dir DIR [--poll MILLIS]";
+
+/// Milliseconds between checks of the control file unless `--poll` says otherwise.
+const DEFAULT_POLL_MILLIS: u64 = 500;
+
+struct Args {
+    data_dir: PathBuf,
+    realm: RealmId,
+    dir: PathBuf,
+    poll: Duration,
+}
+
+fn parse_args(args: &[String]) -> Result<Args, String> {
+    let mut data_dir = None;
+    let mut realm = None;
+    let mut dir = None;
+    let mut poll = Duration::from_millis(DEFAULT_POLL_MILLIS);
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().cloned().ok_or_else(|| USAGE.to_string());
+        match arg.as_str() {
+            "--data-dir" => data_dir = Some(PathBuf::from(value()?)),
+            "--realm" => realm = Some(parse_realm_id(&value()?)?),
+            "--dir" => dir = Some(PathBuf::from(value()?)),
+            "--poll" => {
+                let millis: u64 = value()?
+                    .parse()
+                    .map_err(|_| "--poll expects a number of milliseconds".to_string())?;
+                poll = Duration::from_millis(millis.max(10));
+            }
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    Ok(Args {
+        data_dir: data_dir.ok_or(USAGE)?,
+        realm: realm.ok_or(USAGE)?,
+        dir: dir.ok_or(USAGE)?,
+        poll,
+    })
+}
+
+fn parse_realm_id(hex_id: &str) -> Result<RealmId, String> {
+    let bytes: [u8; 32] = hex::decode(hex_id)
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| format!("invalid realm id: {hex_id}"))?;
+    Ok(RealmId::new(bytes))
+}
+
+/// Run the commands appended to the control file since the last check.
+async fn run_commands(network: &IndrasNetwork, realm: &Realm, control: &mut ControlFile) {
+    let lines = match control.read_lines().await {
+        Ok(lines) => lines,
+        Err(e) => {
+            eprintln!("control: {e}");
+            return;
+        }
+    };
+    for line in lines {
+        let outcome = match ControlCommand::parse(&line) {
+            Ok(command) => realm.execute_command(command, network.id()).await,
+            Err(e) => Err(e),
+        };
+        match outcome {
+            Ok(CommandOutcome::Message(_)) => {}
+            Ok(CommandOutcome::Intention(id)) => println!("quest {} created", hex::encode(id)),
+            Err(e) => eprintln!("{line}: {e}"),
+        }
+    }
+}
+
+async fn run(args: Args) -> Result<(), String> {
+    let network = IndrasNetwork::new(&args.data_dir)
+        .await
+        .map_err(|e| e.to_string())?;
+    network.start().await.map_err(|e| e.to_string())?;
+    let realm = network
+        .get_realm_by_id(&args.realm)
+        .ok_or_else(|| "realm not found on this node".to_string())?;
+
+    tokio::fs::create_dir_all(&args.dir)
+        .await
+        .map_err(|e| format!("{}: {e}", args.dir.display()))?;
+    let feed_path = args.dir.join("events.jsonl");
+    let mut control = ControlFile::open(args.dir.join("control"))
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut feed = std::pin::pin!(realm.follow_event_feed(&feed_path));
+
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            result = &mut feed => {
+                result.map_err(|e| format!("{}: {e}", feed_path.display()))?;
+                break;
+            }
+            _ = tokio::time::sleep(args.poll) => {
+                run_commands(&network, &realm, &mut control).await;
+            }
+        }
+    }
+
+    network.stop().await.map_err(|e| e.to_string())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match parse_args(&args) {
+        Ok(args) => run(args).await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
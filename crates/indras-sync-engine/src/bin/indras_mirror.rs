@@ -0,0 +1,141 @@
+//! Publish a realm as a read-only static site.
+//!
+//! Opens the node in `--data-dir`, renders the parts of the realm its
+//! moderators published (see `Realm::publish_chat` and
+//! `Realm::publish_document`) into `--out`, and exits. With `--watch`, it
+//! stays online and regenerates the mirror whenever the realm changes,
+//! rewriting only the files that changed.
+//!
+//! Usage:
+//!
+//! ```bash
+//! # One-off export
+//! indras-mirror --data-dir ~/.indras --realm 3fa1... --out site/
+//!
+//! # Keep the mirror current, checking every 30 seconds
+//! indras-mirror --data-dir ~/.indras --realm 3fa1... --out site/ --watch 30
+//! ```
+//!
+//! Besides the chat, the notes (`notes`) and intentions (`intentions`)
+//! documents can be published.
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+use indras_network::{IndrasNetwork, MirrorExporter, RealmId, RealmSnapshot, write_mirror};
+use indras_sync_engine::{IntentionDocument, NoteDocument};
+
+const USAGE: &str =
+    "usage: indras-mirror --data-dir DIR --realm REALM_ID --out DIR [--watch SECONDS]";
+
+struct Args {
+    data_dir: PathBuf,
+    realm: RealmId,
+    out: PathBuf,
+    watch: Option<Duration>,
+}
+
+fn parse_args(args: &[String]) -> Result<Args, String> {
+    let mut data_dir = None;
+    let mut realm = None;
+    let mut out = None;
+    let mut watch = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().cloned().ok_or_else(|| USAGE.to_string());
+        match arg.as_str() {
+            "--data-dir" => data_dir = Some(PathBuf::from(value()?)),
+            "--realm" => realm = Some(parse_realm_id(&value()?)?),
+            "--out" => out = Some(PathBuf::from(value()?)),
+            "--watch" => {
+                let secs: u64 = value()?
+                    .parse()
+                    .map_err(|_| "--watch expects a number of seconds".to_string())?;
+                watch = Some(Duration::from_secs(secs.max(1)));
+            }
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    Ok(Args {
+        data_dir: data_dir.ok_or(USAGE)?,
+        realm: realm.ok_or(USAGE)?,
+        out: out.ok_or(USAGE)?,
+        watch,
+    })
+}
+
+fn parse_realm_id(hex_id: &str) -> Result<RealmId, String> {
+    let bytes: [u8; 32] = hex::decode(hex_id)
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| format!("invalid realm id: {hex_id}"))?;
+    Ok(RealmId::new(bytes))
+}
+
+fn exporter() -> MirrorExporter {
+    MirrorExporter::new()
+        .with_document::<NoteDocument>("notes")
+        .with_document::<IntentionDocument>("intentions")
+}
+
+fn write(exporter: &MirrorExporter, snapshot: &RealmSnapshot, out: &Path) -> Result<(), String> {
+    let files = exporter.render(snapshot).map_err(|e| e.to_string())?;
+    let report = write_mirror(out, &files).map_err(|e| format!("{}: {e}", out.display()))?;
+    println!(
+        "{}: {} written, {} unchanged, {} removed",
+        out.display(),
+        report.written.len(),
+        report.unchanged,
+        report.removed.len()
+    );
+    Ok(())
+}
+
+async fn run(args: Args) -> Result<(), String> {
+    let network = IndrasNetwork::new(&args.data_dir)
+        .await
+        .map_err(|e| e.to_string())?;
+    network.start().await.map_err(|e| e.to_string())?;
+    let realm = network
+        .get_realm_by_id(&args.realm)
+        .ok_or_else(|| "realm not found on this node".to_string())?;
+    let exporter = exporter();
+
+    let mut last = realm.snapshot().await.map_err(|e| e.to_string())?;
+    write(&exporter, &last, &args.out)?;
+
+    if let Some(interval) = args.watch {
+        loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => break,
+                _ = tokio::time::sleep(interval) => {}
+            }
+            let snapshot = realm.snapshot().await.map_err(|e| e.to_string())?;
+            if !snapshot.same_state_as(&last) {
+                write(&exporter, &snapshot, &args.out)?;
+                last = snapshot;
+            }
+        }
+    }
+
+    network.stop().await.map_err(|e| e.to_string())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match parse_args(&args) {
+        Ok(args) => run(args).await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,460 @@
+//! Shared bioregion directory — community nodes and realm discovery.
+//!
+//! The compiled-in [`BioregionalCatalog`] covers Root → Realms → Subrealms
+//! → Bioregions. A [`BioregionDirectoryDocument`] extends it over the
+//! network with:
+//!
+//! - **Community nodes**: finer levels (ecoregions, watersheds) that a
+//!   community adds beneath a catalog entry, each exactly one level below
+//!   its parent per [`BioregionalLevel::depth`]
+//! - **Realm listings**: realms attached to a node, with an invite so that
+//!   anyone browsing the directory can join, and an optional coarse
+//!   geohash for proximity discovery
+//!
+//! Like alias handles, the directory is an ordinary realm document. A
+//! community keeps its own in its realm; listings are also published to
+//! every conversation realm so contacts discover them. The SyncEngine merges
+//! every directory it can see into one [`BioregionHierarchy`] for browsing.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use indras_network::document::DocumentSchema;
+use indras_network::member::MemberId;
+use indras_network::{GeoLocation, Geohash, RealmId};
+use serde::{Deserialize, Serialize};
+
+use crate::bioregion_catalog::{BioregionalCatalog, CatalogEntry};
+use crate::humanness::BioregionalLevel;
+
+/// Document name for the bioregion directory in each realm.
+pub const BIOREGION_DIRECTORY_DOC: &str = "bioregion_directory";
+
+/// A node in the bioregional hierarchy, from the catalog or a community.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BioregionNode {
+    /// Unique code, e.g. "NA19" or "NA19/skagit-watershed".
+    pub code: String,
+    /// Human-readable name.
+    pub name: String,
+    /// Level in the hierarchy.
+    pub level: BioregionalLevel,
+    /// Parent node's code. Empty for root.
+    pub parent_code: String,
+    /// Who added the node (`None` for compiled-in catalog entries).
+    pub added_by: Option<MemberId>,
+    /// When the node was added (Unix timestamp in milliseconds, 0 for catalog entries).
+    pub added_at_millis: i64,
+}
+
+impl From<&CatalogEntry> for BioregionNode {
+    fn from(entry: &CatalogEntry) -> Self {
+        Self {
+            code: entry.code.to_string(),
+            name: entry.name.to_string(),
+            level: entry.level,
+            parent_code: entry.parent_code.to_string(),
+            added_by: None,
+            added_at_millis: 0,
+        }
+    }
+}
+
+/// A realm attached to a bioregion node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RealmListing {
+    /// The listed realm.
+    pub realm_id: RealmId,
+    /// Display name of the realm.
+    pub name: String,
+    /// Code of the node the realm is attached to.
+    pub bioregion: String,
+    /// Invite URI for joining, if the lister shared one.
+    pub invite: Option<String>,
+    /// Who listed the realm.
+    pub listed_by: MemberId,
+    /// When the listing last changed (Unix timestamp in milliseconds).
+    pub updated_at_millis: i64,
+    /// Whether the listing was withdrawn.
+    pub withdrawn: bool,
+    /// Coarse location of the realm, for proximity discovery.
+    #[serde(default)]
+    pub geotag: Option<Geohash>,
+}
+
+/// Errors from directory operations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DirectoryError {
+    /// The code does not name a catalog entry or community node.
+    UnknownNode(String),
+    /// The code is already taken by another node.
+    CodeTaken(String),
+    /// The node is not exactly one level below its parent.
+    InvalidLevel {
+        /// The node's requested level.
+        level: BioregionalLevel,
+        /// The parent's level.
+        parent: BioregionalLevel,
+    },
+}
+
+impl std::fmt::Display for DirectoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DirectoryError::UnknownNode(code) => write!(f, "Unknown bioregion node: {code}"),
+            DirectoryError::CodeTaken(code) => write!(f, "Bioregion code already in use: {code}"),
+            DirectoryError::InvalidLevel { level, parent } => {
+                write!(f, "A {level:?} node cannot sit directly below a {parent:?} node")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DirectoryError {}
+
+/// CRDT document holding community nodes and realm listings.
+///
+/// Nodes are add-only (first writer wins a code); listings merge
+/// last-writer-wins per realm.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BioregionDirectoryDocument {
+    /// Community-added nodes keyed by code.
+    pub nodes: BTreeMap<String, BioregionNode>,
+    /// Realm listings keyed by realm.
+    pub listings: BTreeMap<RealmId, RealmListing>,
+}
+
+impl BioregionDirectoryDocument {
+    /// Create an empty directory.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a community node beneath an existing node.
+    pub fn add_node(&mut self, node: BioregionNode) -> Result<(), DirectoryError> {
+        let catalog = BioregionalCatalog::global();
+        if catalog.get(&node.code).is_some() || self.nodes.contains_key(&node.code) {
+            return Err(DirectoryError::CodeTaken(node.code));
+        }
+        let parent_level = match catalog.get(&node.parent_code) {
+            Some(entry) => entry.level,
+            None => self
+                .nodes
+                .get(&node.parent_code)
+                .map(|n| n.level)
+                .ok_or_else(|| DirectoryError::UnknownNode(node.parent_code.clone()))?,
+        };
+        if node.level.parent_level() != Some(parent_level) || node.level == BioregionalLevel::Individual {
+            return Err(DirectoryError::InvalidLevel {
+                level: node.level,
+                parent: parent_level,
+            });
+        }
+        self.nodes.insert(node.code.clone(), node);
+        Ok(())
+    }
+
+    /// Attach (or re-attach) a realm to a node.
+    pub fn list(&mut self, listing: RealmListing) -> Result<(), DirectoryError> {
+        if !self.knows(&listing.bioregion) {
+            return Err(DirectoryError::UnknownNode(listing.bioregion));
+        }
+        self.listings.insert(listing.realm_id, listing);
+        Ok(())
+    }
+
+    /// Withdraw a realm's listing. Returns `false` if it had no live listing.
+    pub fn withdraw(&mut self, realm_id: &RealmId, now_millis: i64) -> bool {
+        match self.listings.get_mut(realm_id).filter(|l| !l.withdrawn) {
+            Some(listing) => {
+                listing.withdrawn = true;
+                listing.updated_at_millis = now_millis;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Set or clear a live listing's geo-tag. Returns `false` if the realm
+    /// has no live listing.
+    pub fn set_geotag(
+        &mut self,
+        realm_id: &RealmId,
+        geotag: Option<Geohash>,
+        now_millis: i64,
+    ) -> bool {
+        match self.listings.get_mut(realm_id).filter(|l| !l.withdrawn) {
+            Some(listing) => {
+                listing.geotag = geotag;
+                listing.updated_at_millis = now_millis;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Whether a code names a catalog entry or a node in this directory.
+    pub fn knows(&self, code: &str) -> bool {
+        BioregionalCatalog::global().get(code).is_some() || self.nodes.contains_key(code)
+    }
+}
+
+impl DocumentSchema for BioregionDirectoryDocument {
+    fn merge(&mut self, remote: Self) {
+        for (code, node) in remote.nodes {
+            match self.nodes.get(&code) {
+                Some(local) if local.added_at_millis <= node.added_at_millis => {}
+                _ => {
+                    self.nodes.insert(code, node);
+                }
+            }
+        }
+        for (realm_id, listing) in remote.listings {
+            match self.listings.get(&realm_id) {
+                Some(local) if local.updated_at_millis >= listing.updated_at_millis => {}
+                _ => {
+                    self.listings.insert(realm_id, listing);
+                }
+            }
+        }
+    }
+}
+
+/// Browsable view of the catalog plus every directory merged together.
+///
+/// # Example
+///
+/// ```ignore
+/// let hierarchy = engine.bioregion_hierarchy().await?;
+/// for child in hierarchy.children_of("NA19") {
+///     println!("{} ({} realms)", child.name, hierarchy.realms_under(&child.code).len());
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BioregionHierarchy {
+    directory: BioregionDirectoryDocument,
+}
+
+impl BioregionHierarchy {
+    /// Build a view from a merged directory.
+    pub fn new(directory: BioregionDirectoryDocument) -> Self {
+        Self { directory }
+    }
+
+    /// Look up a node by code.
+    pub fn get(&self, code: &str) -> Option<BioregionNode> {
+        BioregionalCatalog::global()
+            .get(code)
+            .map(BioregionNode::from)
+            .or_else(|| self.directory.nodes.get(code).cloned())
+    }
+
+    /// Direct children of a node, catalog entries first, then by name.
+    pub fn children_of(&self, code: &str) -> Vec<BioregionNode> {
+        let mut children: Vec<BioregionNode> = BioregionalCatalog::global()
+            .children_of(code)
+            .into_iter()
+            .map(BioregionNode::from)
+            .collect();
+        let mut community: Vec<BioregionNode> = self
+            .directory
+            .nodes
+            .values()
+            .filter(|n| n.parent_code == code)
+            .cloned()
+            .collect();
+        community.sort_by(|a, b| a.name.cmp(&b.name));
+        children.extend(community);
+        children
+    }
+
+    /// Path from a node up to the root (inclusive). Empty if unknown.
+    pub fn path_to_root(&self, code: &str) -> Vec<BioregionNode> {
+        let mut path = Vec::new();
+        let mut current = code.to_string();
+        while let Some(node) = self.get(&current) {
+            current = node.parent_code.clone();
+            path.push(node);
+            if current.is_empty() || path.len() > BioregionalLevel::Individual.depth() as usize {
+                break;
+            }
+        }
+        path
+    }
+
+    /// Live listings attached directly to a node.
+    pub fn realms_at(&self, code: &str) -> Vec<&RealmListing> {
+        self.directory
+            .listings
+            .values()
+            .filter(|l| !l.withdrawn && l.bioregion == code)
+            .collect()
+    }
+
+    /// Live listings attached to a node or anything beneath it.
+    pub fn realms_under(&self, code: &str) -> Vec<&RealmListing> {
+        let mut subtree: BTreeSet<String> = BTreeSet::new();
+        let mut frontier = vec![code.to_string()];
+        while let Some(next) = frontier.pop() {
+            if subtree.insert(next.clone()) {
+                frontier.extend(self.children_of(&next).into_iter().map(|n| n.code));
+            }
+        }
+        self.directory
+            .listings
+            .values()
+            .filter(|l| !l.withdrawn && subtree.contains(&l.bioregion))
+            .collect()
+    }
+
+    /// Live geo-tagged listings within `radius_km` of `center`, nearest first.
+    ///
+    /// A listing matches if any part of its geohash cell is in range, so
+    /// coarsely tagged realms are not missed; the returned distance is to
+    /// the cell center.
+    pub fn realms_near(&self, center: &GeoLocation, radius_km: f64) -> Vec<(&RealmListing, f64)> {
+        let mut found: Vec<_> = self
+            .directory
+            .listings
+            .values()
+            .filter(|l| !l.withdrawn)
+            .filter_map(|l| {
+                let tag = l.geotag.as_ref()?;
+                tag.within_km(center, radius_km)
+                    .then(|| (l, tag.distance_km(center)))
+            })
+            .collect();
+        found.sort_by(|a, b| a.1.total_cmp(&b.1));
+        found
+    }
+
+    /// The node a realm is attached to, if it is listed.
+    pub fn bioregion_of(&self, realm_id: &RealmId) -> Option<BioregionNode> {
+        self.directory
+            .listings
+            .get(realm_id)
+            .filter(|l| !l.withdrawn)
+            .and_then(|l| self.get(&l.bioregion))
+    }
+
+    /// The merged directory backing this view.
+    pub fn directory(&self) -> &BioregionDirectoryDocument {
+        &self.directory
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indras_core::InterfaceId;
+
+    fn watershed(code: &str, parent: &str, at: i64) -> BioregionNode {
+        BioregionNode {
+            code: code.to_string(),
+            name: format!("{code} watershed"),
+            level: BioregionalLevel::Ecoregion,
+            parent_code: parent.to_string(),
+            added_by: Some([1u8; 32]),
+            added_at_millis: at,
+        }
+    }
+
+    fn listing(n: u8, bioregion: &str, at: i64) -> RealmListing {
+        RealmListing {
+            realm_id: InterfaceId::new([n; 32]),
+            name: format!("Realm {n}"),
+            bioregion: bioregion.to_string(),
+            invite: None,
+            listed_by: [1u8; 32],
+            updated_at_millis: at,
+            withdrawn: false,
+            geotag: None,
+        }
+    }
+
+    #[test]
+    fn test_add_node_checks_level_and_parent() {
+        let mut doc = BioregionDirectoryDocument::new();
+        assert!(doc.add_node(watershed("NA19/skagit", "NA19", 1)).is_ok());
+        assert_eq!(
+            doc.add_node(watershed("NA19/skagit", "NA19", 2)),
+            Err(DirectoryError::CodeTaken("NA19/skagit".into()))
+        );
+        assert_eq!(
+            doc.add_node(watershed("NA19", "northern-america", 2)),
+            Err(DirectoryError::CodeTaken("NA19".into()))
+        );
+        assert!(matches!(
+            doc.add_node(watershed("x", "northern-america", 2)),
+            Err(DirectoryError::InvalidLevel { .. })
+        ));
+        assert!(matches!(doc.add_node(watershed("y", "nowhere", 2)), Err(DirectoryError::UnknownNode(_))));
+    }
+
+    #[test]
+    fn test_hierarchy_browsing() {
+        let mut doc = BioregionDirectoryDocument::new();
+        doc.add_node(watershed("NA19/skagit", "NA19", 1)).unwrap();
+        doc.list(listing(1, "NA19/skagit", 1)).unwrap();
+        doc.list(listing(2, "NA19", 1)).unwrap();
+        doc.list(listing(3, "NA21", 1)).unwrap();
+        assert!(doc.list(listing(4, "nowhere", 1)).is_err());
+
+        let hierarchy = BioregionHierarchy::new(doc);
+        let path: Vec<String> = hierarchy.path_to_root("NA19/skagit").into_iter().map(|n| n.code).collect();
+        assert_eq!(path, ["NA19/skagit", "NA19", "northern-america/north-pacific-coast", "northern-america", "ROOT"]);
+
+        assert_eq!(hierarchy.children_of("NA19").len(), 1);
+        assert_eq!(hierarchy.realms_at("NA19").len(), 1);
+        assert_eq!(hierarchy.realms_under("NA19").len(), 2);
+        assert_eq!(hierarchy.realms_under("northern-america").len(), 3);
+        assert_eq!(
+            hierarchy.bioregion_of(&InterfaceId::new([1; 32])).map(|n| n.code),
+            Some("NA19/skagit".to_string())
+        );
+    }
+
+    #[test]
+    fn test_merge_nodes_first_wins_listings_lww() {
+        let mut a = BioregionDirectoryDocument::new();
+        a.add_node(watershed("NA19/skagit", "NA19", 5)).unwrap();
+        a.list(listing(1, "NA19", 10)).unwrap();
+
+        let mut b = BioregionDirectoryDocument::new();
+        let mut earlier = watershed("NA19/skagit", "NA19", 3);
+        earlier.name = "Skagit".into();
+        b.add_node(earlier).unwrap();
+        b.list(listing(1, "NA21", 10)).unwrap();
+        b.withdraw(&InterfaceId::new([1; 32]), 20);
+
+        a.merge(b);
+        assert_eq!(a.nodes["NA19/skagit"].name, "Skagit");
+        assert!(a.listings[&InterfaceId::new([1; 32])].withdrawn);
+    }
+
+    #[test]
+    fn test_realms_near_uses_geotags() {
+        use indras_network::GeoPrecision;
+
+        let seattle = GeoLocation { lat: 47.6062, lng: -122.3321 };
+        let tacoma = GeoLocation { lat: 47.2529, lng: -122.4443 };
+        let portland = GeoLocation { lat: 45.5152, lng: -122.6784 };
+
+        let mut doc = BioregionDirectoryDocument::new();
+        for n in 1..=4 {
+            doc.list(listing(n, "NA19", 1)).unwrap();
+        }
+        doc.set_geotag(&InterfaceId::new([1; 32]), Some(tacoma.geohash(GeoPrecision::Locality)), 2);
+        doc.set_geotag(&InterfaceId::new([2; 32]), Some(seattle.geohash(GeoPrecision::Area)), 2);
+        doc.set_geotag(&InterfaceId::new([3; 32]), Some(portland.geohash(GeoPrecision::Locality)), 2);
+        doc.withdraw(&InterfaceId::new([4; 32]), 2);
+        assert!(!doc.set_geotag(&InterfaceId::new([4; 32]), None, 3));
+
+        let hierarchy = BioregionHierarchy::new(doc);
+        let near: Vec<RealmId> = hierarchy
+            .realms_near(&seattle, 50.0)
+            .into_iter()
+            .map(|(l, _)| l.realm_id)
+            .collect();
+        assert_eq!(near, [InterfaceId::new([2; 32]), InterfaceId::new([1; 32])]);
+    }
+}
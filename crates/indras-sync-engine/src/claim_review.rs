@@ -0,0 +1,412 @@
+//! Claim review — reviewer assignment, review states, and structured feedback.
+//!
+//! Every [`ServiceClaim`](crate::intention::ServiceClaim) carries a
+//! [`ClaimReview`] that tracks who is reviewing it, where the review stands,
+//! and the feedback left so far:
+//!
+//! 1. A reviewer is assigned, manually or by [`rotate_reviewer`]
+//! 2. The reviewer leaves feedback; each comment may carry a verdict
+//! 3. `RequestChanges` moves the claim to `ChangesRequested`; resubmitting a
+//!    proof folder moves it back to `Pending`
+//! 4. `Approve` moves it to `Approved`, ready for the creator to verify
+//!
+//! Review state lives inside the intentions document, so it syncs with the
+//! claim. [`ReviewEvent::diff`] turns document changes into events that
+//! drive reviewer and claimant notifications.
+
+use indras_network::artifact::ArtifactId;
+use indras_network::member::MemberId;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::intention::{IntentionDocument, IntentionId};
+
+/// Where a claim's review stands.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum ReviewState {
+    /// Awaiting review (initial state, and after resubmission).
+    #[default]
+    Pending,
+    /// The reviewer asked the claimant for changes.
+    ChangesRequested,
+    /// The reviewer approved the claim.
+    Approved,
+}
+
+impl ReviewState {
+    /// Whether the review still needs reviewer attention.
+    pub fn is_pending(&self) -> bool {
+        matches!(self, ReviewState::Pending)
+    }
+
+    /// Display label for UI.
+    pub fn label(&self) -> &str {
+        match self {
+            ReviewState::Pending => "Pending",
+            ReviewState::ChangesRequested => "Changes requested",
+            ReviewState::Approved => "Approved",
+        }
+    }
+}
+
+/// What a feedback comment decides, if anything.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum ReviewVerdict {
+    /// A plain comment; the review state is unchanged.
+    #[default]
+    Comment,
+    /// Ask the claimant for changes.
+    RequestChanges,
+    /// Approve the claim.
+    Approve,
+}
+
+impl ReviewVerdict {
+    /// The review state this verdict leads to, if it changes it.
+    pub fn resulting_state(&self) -> Option<ReviewState> {
+        match self {
+            ReviewVerdict::Comment => None,
+            ReviewVerdict::RequestChanges => Some(ReviewState::ChangesRequested),
+            ReviewVerdict::Approve => Some(ReviewState::Approved),
+        }
+    }
+}
+
+/// A structured feedback comment attached to a claim.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReviewComment {
+    /// Who left the comment.
+    pub author: MemberId,
+    /// Markdown body.
+    pub body: String,
+    /// What the comment decides.
+    pub verdict: ReviewVerdict,
+    /// The proof artifact the comment refers to, if any.
+    pub artifact: Option<ArtifactId>,
+    /// When the comment was left (Unix timestamp in milliseconds).
+    pub created_at_millis: i64,
+}
+
+impl ReviewComment {
+    /// Create a comment stamped with the current time.
+    pub fn new(author: MemberId, body: impl Into<String>, verdict: ReviewVerdict) -> Self {
+        Self {
+            author,
+            body: body.into(),
+            verdict,
+            artifact: None,
+            created_at_millis: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+
+    /// Attach the comment to a specific proof artifact.
+    pub fn on_artifact(mut self, artifact: ArtifactId) -> Self {
+        self.artifact = Some(artifact);
+        self
+    }
+}
+
+/// Review status of one claim.
+///
+/// Reviewer and state merge last-writer-wins by `updated_at_millis`;
+/// feedback merges as a set union.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ClaimReview {
+    /// The assigned reviewer, if any.
+    pub reviewer: Option<MemberId>,
+    /// When the current reviewer was assigned (Unix timestamp in milliseconds).
+    pub assigned_at_millis: Option<i64>,
+    /// Where the review stands.
+    pub state: ReviewState,
+    /// Feedback comments, oldest first.
+    pub feedback: Vec<ReviewComment>,
+    /// When the reviewer or state last changed (Unix timestamp in milliseconds).
+    pub updated_at_millis: i64,
+}
+
+impl ClaimReview {
+    /// Assign a reviewer.
+    pub fn assign(&mut self, reviewer: MemberId, now_millis: i64) {
+        self.reviewer = Some(reviewer);
+        self.assigned_at_millis = Some(now_millis);
+        self.updated_at_millis = now_millis;
+    }
+
+    /// Record a feedback comment and apply its verdict.
+    pub fn add_feedback(&mut self, comment: ReviewComment) {
+        if let Some(state) = comment.verdict.resulting_state() {
+            self.state = state;
+            self.updated_at_millis = self.updated_at_millis.max(comment.created_at_millis);
+        }
+        self.feedback.push(comment);
+    }
+
+    /// Return the claim to review after the claimant addressed feedback.
+    ///
+    /// Returns `true` if the state changed.
+    pub fn resubmit(&mut self, now_millis: i64) -> bool {
+        if self.state != ReviewState::ChangesRequested {
+            return false;
+        }
+        self.state = ReviewState::Pending;
+        self.updated_at_millis = now_millis;
+        true
+    }
+
+    /// Whether `member` is the assigned reviewer.
+    pub fn is_reviewer(&self, member: &MemberId) -> bool {
+        self.reviewer.as_ref() == Some(member)
+    }
+
+    /// Merge a remote copy of this review.
+    pub fn merge(&mut self, remote: &ClaimReview) {
+        if remote.updated_at_millis > self.updated_at_millis {
+            self.reviewer = remote.reviewer;
+            self.assigned_at_millis = remote.assigned_at_millis;
+            self.state = remote.state;
+            self.updated_at_millis = remote.updated_at_millis;
+        }
+        for comment in &remote.feedback {
+            let known = self.feedback.iter().any(|c| {
+                c.author == comment.author && c.created_at_millis == comment.created_at_millis
+            });
+            if !known {
+                self.feedback.push(comment.clone());
+            }
+        }
+        self.feedback.sort_by_key(|c| c.created_at_millis);
+    }
+}
+
+/// A review change worth notifying someone about.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReviewEvent {
+    /// A reviewer was assigned to a claim. Notifies the reviewer.
+    ReviewerAssigned {
+        /// The intention the claim belongs to.
+        intention_id: IntentionId,
+        /// Who submitted the claim.
+        claimant: MemberId,
+        /// The new reviewer.
+        reviewer: MemberId,
+    },
+    /// Feedback was left on a claim. Notifies the claimant.
+    FeedbackAdded {
+        /// The intention the claim belongs to.
+        intention_id: IntentionId,
+        /// Who submitted the claim.
+        claimant: MemberId,
+        /// The new comment.
+        comment: ReviewComment,
+    },
+    /// A claim's review state changed. Notifies the reviewer and claimant.
+    StateChanged {
+        /// The intention the claim belongs to.
+        intention_id: IntentionId,
+        /// Who submitted the claim.
+        claimant: MemberId,
+        /// The new state.
+        state: ReviewState,
+    },
+}
+
+impl ReviewEvent {
+    /// Review events between two states of an intentions document.
+    pub fn diff(old: &IntentionDocument, new: &IntentionDocument) -> Vec<ReviewEvent> {
+        let mut events = Vec::new();
+        for intention in &new.intentions {
+            let old_intention = old.intentions.iter().find(|i| i.id == intention.id);
+            for claim in &intention.claims {
+                let before = old_intention
+                    .and_then(|i| i.claims.iter().find(|c| c.claimant == claim.claimant))
+                    .map(|c| &c.review);
+                let default = ClaimReview::default();
+                let before = before.unwrap_or(&default);
+                let after = &claim.review;
+
+                if let Some(reviewer) = after.reviewer {
+                    if before.reviewer != Some(reviewer) {
+                        events.push(ReviewEvent::ReviewerAssigned {
+                            intention_id: intention.id,
+                            claimant: claim.claimant,
+                            reviewer,
+                        });
+                    }
+                }
+                for comment in after.feedback.iter().filter(|c| !before.feedback.contains(*c)) {
+                    events.push(ReviewEvent::FeedbackAdded {
+                        intention_id: intention.id,
+                        claimant: claim.claimant,
+                        comment: comment.clone(),
+                    });
+                }
+                if after.state != before.state {
+                    events.push(ReviewEvent::StateChanged {
+                        intention_id: intention.id,
+                        claimant: claim.claimant,
+                        state: after.state,
+                    });
+                }
+            }
+        }
+        events
+    }
+
+    /// Whether `member` should be notified about this event.
+    ///
+    /// `reviewer` is the claim's current reviewer, if any.
+    pub fn notifies(&self, member: &MemberId, reviewer: Option<&MemberId>) -> bool {
+        match self {
+            ReviewEvent::ReviewerAssigned { reviewer, .. } => reviewer == member,
+            ReviewEvent::FeedbackAdded {
+                claimant, comment, ..
+            } => claimant == member && &comment.author != member,
+            ReviewEvent::StateChanged {
+                claimant, state, ..
+            } => match state {
+                ReviewState::Pending => reviewer == Some(member),
+                _ => claimant == member,
+            },
+        }
+    }
+}
+
+/// Pick the next reviewer for a claim by rotation.
+///
+/// Excludes the claimant, then prefers the candidate with the fewest open
+/// (not yet approved, not verified) reviews in the document; ties go to
+/// whoever was assigned least recently, with never-assigned candidates
+/// first. Returns `None` if no candidate is eligible.
+pub fn rotate_reviewer(
+    doc: &IntentionDocument,
+    candidates: &[MemberId],
+    claimant: &MemberId,
+) -> Option<MemberId> {
+    let mut open: HashMap<MemberId, usize> = HashMap::new();
+    let mut last_assigned: HashMap<MemberId, i64> = HashMap::new();
+    for claim in doc
+        .intentions
+        .iter()
+        .filter(|i| !i.deleted)
+        .flat_map(|i| &i.claims)
+    {
+        let Some(reviewer) = claim.review.reviewer else {
+            continue;
+        };
+        if !claim.verified && claim.review.state != ReviewState::Approved {
+            *open.entry(reviewer).or_default() += 1;
+        }
+        if let Some(at) = claim.review.assigned_at_millis {
+            let last = last_assigned.entry(reviewer).or_insert(at);
+            *last = (*last).max(at);
+        }
+    }
+
+    candidates
+        .iter()
+        .filter(|c| *c != claimant)
+        .min_by_key(|c| {
+            (
+                open.get(*c).copied().unwrap_or(0),
+                last_assigned.get(*c).copied(),
+            )
+        })
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intention::Intention;
+
+    const CREATOR: MemberId = [1u8; 32];
+    const CLAIMANT: MemberId = [2u8; 32];
+    const ALICE: MemberId = [3u8; 32];
+    const BOB: MemberId = [4u8; 32];
+
+    fn doc_with_claim() -> (IntentionDocument, IntentionId) {
+        let mut intention = Intention::new("Fix fence", "", None, CREATOR);
+        intention.submit_claim(CLAIMANT, None).unwrap();
+        let id = intention.id;
+        let mut doc = IntentionDocument::new();
+        doc.add(intention);
+        (doc, id)
+    }
+
+    #[test]
+    fn test_verdicts_drive_state() {
+        let mut review = ClaimReview::default();
+        review.add_feedback(ReviewComment::new(ALICE, "Looks close", ReviewVerdict::Comment));
+        assert_eq!(review.state, ReviewState::Pending);
+
+        review.add_feedback(ReviewComment::new(ALICE, "Add photos", ReviewVerdict::RequestChanges));
+        assert_eq!(review.state, ReviewState::ChangesRequested);
+
+        assert!(review.resubmit(review.updated_at_millis + 1));
+        assert_eq!(review.state, ReviewState::Pending);
+        assert!(!review.resubmit(review.updated_at_millis + 2));
+
+        review.add_feedback(ReviewComment::new(ALICE, "Great", ReviewVerdict::Approve));
+        assert_eq!(review.state, ReviewState::Approved);
+        assert_eq!(review.feedback.len(), 3);
+    }
+
+    #[test]
+    fn test_merge_unions_feedback_and_keeps_newer_state() {
+        let mut a = ClaimReview::default();
+        a.assign(ALICE, 10);
+        let mut b = a.clone();
+
+        let mut comment = ReviewComment::new(ALICE, "Needs work", ReviewVerdict::RequestChanges);
+        comment.created_at_millis = 20;
+        b.add_feedback(comment);
+        let mut note = ReviewComment::new(BOB, "FYI", ReviewVerdict::Comment);
+        note.created_at_millis = 15;
+        a.add_feedback(note);
+
+        a.merge(&b);
+        assert_eq!(a.state, ReviewState::ChangesRequested);
+        assert_eq!(a.feedback.len(), 2);
+        assert_eq!(a.feedback[0].author, BOB);
+    }
+
+    #[test]
+    fn test_diff_emits_events() {
+        let (old, id) = doc_with_claim();
+        let mut new = old.clone();
+        let claim = &mut new.find_mut(&id).unwrap().claims[0];
+        claim.review.assign(ALICE, 10);
+        claim
+            .review
+            .add_feedback(ReviewComment::new(ALICE, "Add photos", ReviewVerdict::RequestChanges));
+
+        let events = ReviewEvent::diff(&old, &new);
+        assert_eq!(events.len(), 3);
+        assert!(events[0].notifies(&ALICE, Some(&ALICE)));
+        assert!(events[1].notifies(&CLAIMANT, Some(&ALICE)));
+        assert!(!events[1].notifies(&ALICE, Some(&ALICE)));
+        assert_eq!(
+            events[2],
+            ReviewEvent::StateChanged {
+                intention_id: id,
+                claimant: CLAIMANT,
+                state: ReviewState::ChangesRequested,
+            }
+        );
+        assert!(ReviewEvent::diff(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn test_rotation_balances_load() {
+        let (mut doc, id) = doc_with_claim();
+        let candidates = [CLAIMANT, ALICE, BOB];
+
+        let first = rotate_reviewer(&doc, &candidates, &CLAIMANT).unwrap();
+        assert_eq!(first, ALICE);
+        doc.find_mut(&id).unwrap().claims[0].review.assign(first, 10);
+
+        // Alice now has an open review, so Bob is next
+        assert_eq!(rotate_reviewer(&doc, &candidates, &CLAIMANT), Some(BOB));
+        assert_eq!(rotate_reviewer(&doc, &[CLAIMANT], &CLAIMANT), None);
+    }
+}
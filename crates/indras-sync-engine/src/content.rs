@@ -6,6 +6,7 @@
 use crate::proof_folder::ProofFolderId;
 use crate::intention::IntentionId;
 use crate::token_of_gratitude::TokenOfGratitudeId;
+use indras_network::digest::{DefaultDigestClassifier, DigestClassifier, DigestItem};
 use indras_network::member::MemberId;
 use indras_network::message::{ContentReference, Content};
 use serde::{Deserialize, Serialize};
@@ -133,6 +134,31 @@ impl SyncContent {
     }
 }
 
+/// Digest classifier that recognizes SyncEngine content.
+///
+/// Released gratitude marks a quest as fulfilled, and submitted proofs count
+/// as new artifacts. Other SyncEngine events are left out of digests;
+/// built-in content falls through to [`DefaultDigestClassifier`].
+///
+/// ```ignore
+/// network.digests().set_classifier(SyncDigestClassifier);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SyncDigestClassifier;
+
+impl DigestClassifier for SyncDigestClassifier {
+    fn classify(&self, content: &Content) -> Option<DigestItem> {
+        match SyncContent::from_content(content) {
+            Some(SyncContent::GratitudeReleased { .. }) => Some(DigestItem::QuestCompleted),
+            Some(SyncContent::ProofSubmitted { .. } | SyncContent::ProofFolderSubmitted { .. }) => {
+                Some(DigestItem::Artifact)
+            }
+            Some(_) => None,
+            None => DefaultDigestClassifier.classify(content),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -167,4 +193,23 @@ mod tests {
         };
         assert!(SyncContent::from_content(&content).is_none());
     }
+
+    #[test]
+    fn test_digest_classifier() {
+        let released = SyncContent::GratitudeReleased {
+            token_id: [1u8; 16],
+            from_steward: [2u8; 32],
+            to_steward: [4u8; 32],
+            target_intention_id: [3u8; 16],
+        };
+        let classifier = SyncDigestClassifier;
+        assert_eq!(
+            classifier.classify(&released.to_content()),
+            Some(DigestItem::QuestCompleted)
+        );
+        assert_eq!(
+            classifier.classify(&Content::Text("hi".to_string())),
+            Some(DigestItem::Message)
+        );
+    }
 }
@@ -0,0 +1,770 @@
+//! Intention exchange — publishing offers and needs, matching, negotiation.
+//!
+//! Members publish Need and Offering intentions as [`ExchangeListing`]s to
+//! the realms they choose. Each listing carries free-form tags; a need and
+//! an offer from different members that share tags form a
+//! [`MatchProposal`], scored by tag overlap.
+//!
+//! Negotiation happens privately, in the DM realm between the two owners,
+//! under [`negotiation_doc_key`]. Either party proposes terms; the other
+//! accepts, counters with new terms, or declines. Once terms are agreed
+//! both parties confirm, and the [`ExchangeRecord`] finalizes the trade.
+//!
+//! Matching is pure and local; [`SyncEngine::exchange_matches`](crate::SyncEngine::exchange_matches)
+//! gathers the boards from each loaded realm.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use indras_network::member::MemberId;
+use serde::{Deserialize, Serialize};
+
+use crate::intention::{generate_intention_id, Intention, IntentionId, IntentionKind};
+
+/// Document name for the exchange board in each realm.
+pub const EXCHANGE_BOARD_DOC: &str = "exchange_board";
+
+/// Doc key prefix for negotiation documents in DM realms.
+pub const NEGOTIATION_KEY_PREFIX: &str = "_exchange:";
+
+/// Unique identifier for a listing (16 bytes).
+pub type ListingId = [u8; 16];
+
+/// The doc key for the negotiation between a need and an offer.
+///
+/// Both owners derive the same key, so they meet on one document in
+/// their shared DM realm.
+pub fn negotiation_doc_key(need: &ListingId, offer: &ListingId) -> String {
+    format!(
+        "{}{}{}",
+        NEGOTIATION_KEY_PREFIX,
+        hex::encode(need),
+        hex::encode(offer)
+    )
+}
+
+/// Normalize tags for matching: trimmed, lowercase, no leading `#`,
+/// deduplicated and sorted.
+pub fn normalize_tags<I, S>(tags: I) -> Vec<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    tags.into_iter()
+        .map(|t| t.as_ref().trim().trim_start_matches('#').to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Which side of a trade a listing is on.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum ListingSide {
+    /// Something the owner needs.
+    Need,
+    /// Something the owner offers.
+    Offer,
+}
+
+impl ListingSide {
+    /// Side for an intention kind; plain intentions are calls for help.
+    pub fn from_kind(kind: IntentionKind) -> Self {
+        match kind {
+            IntentionKind::Offering => ListingSide::Offer,
+            IntentionKind::Need | IntentionKind::Intention => ListingSide::Need,
+        }
+    }
+}
+
+/// An intention published to a realm's exchange board.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExchangeListing {
+    /// Unique listing ID, shared by every realm the listing is published to.
+    pub id: ListingId,
+    /// The intention this listing advertises.
+    pub intention_id: IntentionId,
+    /// Who published the listing.
+    pub owner: MemberId,
+    /// Need or offer.
+    pub side: ListingSide,
+    /// Intention title at publish time.
+    pub title: String,
+    /// Intention description at publish time.
+    pub description: String,
+    /// Normalized tags used for matching.
+    pub tags: Vec<String>,
+    /// When the listing was published (Unix timestamp in milliseconds).
+    pub published_at_millis: i64,
+    /// Whether the owner has withdrawn the listing (tombstone for CRDT).
+    #[serde(default)]
+    pub withdrawn: bool,
+}
+
+impl ExchangeListing {
+    /// Create a listing for an intention.
+    pub fn from_intention<I, S>(intention: &Intention, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            id: generate_intention_id(),
+            intention_id: intention.id,
+            owner: intention.creator,
+            side: ListingSide::from_kind(intention.kind),
+            title: intention.title.clone(),
+            description: intention.description.clone(),
+            tags: normalize_tags(tags),
+            published_at_millis: chrono::Utc::now().timestamp_millis(),
+            withdrawn: false,
+        }
+    }
+
+    /// Whether the listing can still be matched.
+    pub fn is_active(&self) -> bool {
+        !self.withdrawn
+    }
+}
+
+/// Document schema for a realm's exchange board.
+///
+/// # CRDT Semantics
+///
+/// - Set-union by listing ID
+/// - Withdrawal is sticky: once withdrawn on any replica, withdrawn everywhere
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExchangeBoardDocument {
+    /// All listings published to this realm.
+    pub listings: Vec<ExchangeListing>,
+}
+
+impl indras_network::document::DocumentSchema for ExchangeBoardDocument {
+    fn merge(&mut self, remote: Self) {
+        for listing in remote.listings {
+            match self.listings.iter_mut().find(|l| l.id == listing.id) {
+                Some(local) => local.withdrawn |= listing.withdrawn,
+                None => self.listings.push(listing),
+            }
+        }
+    }
+}
+
+impl ExchangeBoardDocument {
+    /// Add a listing (idempotent by listing ID).
+    pub fn add(&mut self, listing: ExchangeListing) {
+        if !self.listings.iter().any(|l| l.id == listing.id) {
+            self.listings.push(listing);
+        }
+    }
+
+    /// Find a listing by ID.
+    pub fn find(&self, id: &ListingId) -> Option<&ExchangeListing> {
+        self.listings.iter().find(|l| &l.id == id)
+    }
+
+    /// Withdraw a listing. Only its owner may withdraw it.
+    pub fn withdraw(&mut self, id: &ListingId, caller: MemberId) -> Result<(), ExchangeError> {
+        let listing = self
+            .listings
+            .iter_mut()
+            .find(|l| &l.id == id)
+            .ok_or(ExchangeError::ListingNotFound)?;
+        if listing.owner != caller {
+            return Err(ExchangeError::NotAuthorized);
+        }
+        listing.withdrawn = true;
+        Ok(())
+    }
+
+    /// Listings that can still be matched.
+    pub fn active_listings(&self) -> impl Iterator<Item = &ExchangeListing> {
+        self.listings.iter().filter(|l| l.is_active())
+    }
+}
+
+/// A proposed pairing of a need with an offer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchProposal {
+    /// The need being met.
+    pub need: ExchangeListing,
+    /// The offer meeting it.
+    pub offer: ExchangeListing,
+    /// Tags both listings carry.
+    pub shared_tags: Vec<String>,
+    /// Tag overlap (Jaccard index), in `0.0..=1.0`.
+    pub score: f64,
+}
+
+impl MatchProposal {
+    /// The other party, from `me`'s point of view.
+    pub fn counterparty(&self, me: &MemberId) -> MemberId {
+        if &self.need.owner == me {
+            self.offer.owner
+        } else {
+            self.need.owner
+        }
+    }
+
+    /// Doc key for negotiating this match.
+    pub fn negotiation_key(&self) -> String {
+        negotiation_doc_key(&self.need.id, &self.offer.id)
+    }
+}
+
+/// Propose matches between active needs and offers.
+///
+/// A need and an offer match when they belong to different members and
+/// share at least one tag. Listings seen in several realms are counted
+/// once. Results are sorted best first.
+pub fn find_matches<'a>(
+    listings: impl IntoIterator<Item = &'a ExchangeListing>,
+) -> Vec<MatchProposal> {
+    let mut unique: HashMap<ListingId, &ExchangeListing> = HashMap::new();
+    for listing in listings {
+        // A withdrawal seen in any realm wins
+        let replace = match unique.get(&listing.id) {
+            Some(seen) => !seen.withdrawn && listing.withdrawn,
+            None => true,
+        };
+        if replace {
+            unique.insert(listing.id, listing);
+        }
+    }
+
+    let active: Vec<&ExchangeListing> = unique.into_values().filter(|l| l.is_active()).collect();
+    let needs = active.iter().filter(|l| l.side == ListingSide::Need);
+    let mut proposals = Vec::new();
+
+    for need in needs {
+        let need_tags: BTreeSet<&String> = need.tags.iter().collect();
+        for offer in active.iter().filter(|l| l.side == ListingSide::Offer) {
+            if offer.owner == need.owner {
+                continue;
+            }
+            let offer_tags: BTreeSet<&String> = offer.tags.iter().collect();
+            let shared: Vec<String> = need_tags
+                .intersection(&offer_tags)
+                .map(|t| (*t).clone())
+                .collect();
+            if shared.is_empty() {
+                continue;
+            }
+            let union = need_tags.union(&offer_tags).count();
+            proposals.push(MatchProposal {
+                need: (*need).clone(),
+                offer: (*offer).clone(),
+                score: shared.len() as f64 / union as f64,
+                shared_tags: shared,
+            });
+        }
+    }
+
+    proposals.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(b.shared_tags.len().cmp(&a.shared_tags.len()))
+            .then(a.need.id.cmp(&b.need.id))
+            .then(a.offer.id.cmp(&b.offer.id))
+    });
+    proposals
+}
+
+/// A step in a negotiation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum NegotiationAction {
+    /// Propose terms; proposing again counters the standing terms.
+    Propose {
+        /// Plain-language terms of the trade.
+        terms: String,
+    },
+    /// Accept the standing terms proposed by the other party.
+    Accept,
+    /// End the negotiation without a trade.
+    Decline,
+}
+
+/// One message in a negotiation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NegotiationMessage {
+    /// Who sent it.
+    pub from: MemberId,
+    /// What they did.
+    pub action: NegotiationAction,
+    /// When it was sent (Unix timestamp in milliseconds).
+    pub sent_at_millis: i64,
+}
+
+/// Where a negotiation stands, derived from its messages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NegotiationState {
+    /// No terms proposed yet.
+    Open,
+    /// Terms proposed, waiting for the other party.
+    Proposed {
+        /// Who proposed the standing terms.
+        by: MemberId,
+        /// The standing terms.
+        terms: String,
+    },
+    /// Both parties agreed to the terms.
+    Agreed {
+        /// The agreed terms.
+        terms: String,
+        /// When the terms were accepted.
+        at_millis: i64,
+    },
+    /// A party declined.
+    Declined {
+        /// Who declined.
+        by: MemberId,
+    },
+}
+
+/// The finalized record of a trade.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExchangeRecord {
+    /// The need that was met.
+    pub need: ListingId,
+    /// The offer that met it.
+    pub offer: ListingId,
+    /// Offer owner.
+    pub giver: MemberId,
+    /// Need owner.
+    pub receiver: MemberId,
+    /// The agreed terms.
+    pub terms: String,
+    /// When the terms were agreed (Unix timestamp in milliseconds).
+    pub agreed_at_millis: i64,
+    /// Each party's confirmation time.
+    pub confirmations: BTreeMap<MemberId, i64>,
+}
+
+impl ExchangeRecord {
+    /// Whether both parties have confirmed the trade.
+    pub fn is_final(&self) -> bool {
+        self.confirmations.contains_key(&self.giver)
+            && self.confirmations.contains_key(&self.receiver)
+    }
+}
+
+/// Negotiation between a need's owner and an offer's owner.
+///
+/// Stored in their DM realm under [`negotiation_doc_key`].
+///
+/// # CRDT Semantics
+///
+/// - Messages are append-only, set-union, replayed in send order
+/// - The exchange record's confirmations are a union; earliest time wins
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NegotiationDocument {
+    /// The need being negotiated.
+    pub need: ListingId,
+    /// The offer being negotiated.
+    pub offer: ListingId,
+    /// Need owner.
+    pub need_owner: MemberId,
+    /// Offer owner.
+    pub offer_owner: MemberId,
+    /// All negotiation messages.
+    pub messages: Vec<NegotiationMessage>,
+    /// The finalized trade, once a party confirms agreed terms.
+    pub record: Option<ExchangeRecord>,
+}
+
+impl indras_network::document::DocumentSchema for NegotiationDocument {
+    fn merge(&mut self, remote: Self) {
+        if self.messages.is_empty() && self.record.is_none() {
+            self.need = remote.need;
+            self.offer = remote.offer;
+            self.need_owner = remote.need_owner;
+            self.offer_owner = remote.offer_owner;
+        }
+
+        for message in remote.messages {
+            if !self.messages.contains(&message) {
+                self.messages.push(message);
+            }
+        }
+        self.messages.sort_by(|a, b| {
+            a.sent_at_millis
+                .cmp(&b.sent_at_millis)
+                .then(a.from.cmp(&b.from))
+        });
+
+        match (&mut self.record, remote.record) {
+            (None, Some(record)) => self.record = Some(record),
+            (Some(local), Some(record)) => {
+                for (member, at) in record.confirmations {
+                    let entry = local.confirmations.entry(member).or_insert(at);
+                    *entry = (*entry).min(at);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl NegotiationDocument {
+    /// Start a negotiation for a match.
+    pub fn for_match(proposal: &MatchProposal) -> Self {
+        Self {
+            need: proposal.need.id,
+            offer: proposal.offer.id,
+            need_owner: proposal.need.owner,
+            offer_owner: proposal.offer.owner,
+            messages: Vec::new(),
+            record: None,
+        }
+    }
+
+    /// Whether `member` is one of the two parties.
+    pub fn is_party(&self, member: &MemberId) -> bool {
+        member == &self.need_owner || member == &self.offer_owner
+    }
+
+    /// Replay the messages to find where the negotiation stands.
+    ///
+    /// Messages from non-parties are ignored, as is anything after the
+    /// negotiation settles.
+    pub fn state(&self) -> NegotiationState {
+        let mut state = NegotiationState::Open;
+        for message in self.messages.iter().filter(|m| self.is_party(&m.from)) {
+            state = match (state, &message.action) {
+                (
+                    settled @ (NegotiationState::Agreed { .. } | NegotiationState::Declined { .. }),
+                    _,
+                ) => {
+                    return settled;
+                }
+                (_, NegotiationAction::Decline) => NegotiationState::Declined { by: message.from },
+                (_, NegotiationAction::Propose { terms }) => NegotiationState::Proposed {
+                    by: message.from,
+                    terms: terms.clone(),
+                },
+                (NegotiationState::Proposed { by, terms }, NegotiationAction::Accept)
+                    if by != message.from =>
+                {
+                    NegotiationState::Agreed {
+                        terms,
+                        at_millis: message.sent_at_millis,
+                    }
+                }
+                (state, NegotiationAction::Accept) => state,
+            };
+        }
+        state
+    }
+
+    fn push(
+        &mut self,
+        from: MemberId,
+        action: NegotiationAction,
+        now: i64,
+    ) -> Result<(), ExchangeError> {
+        if !self.is_party(&from) {
+            return Err(ExchangeError::NotAuthorized);
+        }
+        if matches!(
+            self.state(),
+            NegotiationState::Agreed { .. } | NegotiationState::Declined { .. }
+        ) {
+            return Err(ExchangeError::NegotiationClosed);
+        }
+        // Keep send order strictly increasing so replay is unambiguous
+        let now = self
+            .messages
+            .last()
+            .map_or(now, |last| now.max(last.sent_at_millis + 1));
+        self.messages.push(NegotiationMessage {
+            from,
+            action,
+            sent_at_millis: now,
+        });
+        Ok(())
+    }
+
+    /// Propose (or counter with) terms.
+    pub fn propose(
+        &mut self,
+        from: MemberId,
+        terms: impl Into<String>,
+        now: i64,
+    ) -> Result<(), ExchangeError> {
+        self.push(
+            from,
+            NegotiationAction::Propose {
+                terms: terms.into(),
+            },
+            now,
+        )
+    }
+
+    /// Accept the other party's standing terms.
+    pub fn accept(&mut self, from: MemberId, now: i64) -> Result<(), ExchangeError> {
+        match self.state() {
+            NegotiationState::Proposed { by, .. } if by != from => {
+                self.push(from, NegotiationAction::Accept, now)
+            }
+            _ => Err(ExchangeError::NothingToAccept),
+        }
+    }
+
+    /// Decline, ending the negotiation.
+    pub fn decline(&mut self, from: MemberId, now: i64) -> Result<(), ExchangeError> {
+        self.push(from, NegotiationAction::Decline, now)
+    }
+
+    /// Confirm the agreed trade, creating the exchange record if needed.
+    ///
+    /// Returns the record; it is final once both parties have confirmed.
+    pub fn confirm(&mut self, from: MemberId, now: i64) -> Result<&ExchangeRecord, ExchangeError> {
+        if !self.is_party(&from) {
+            return Err(ExchangeError::NotAuthorized);
+        }
+        let NegotiationState::Agreed { terms, at_millis } = self.state() else {
+            return Err(ExchangeError::NotAgreed);
+        };
+        let record = self.record.get_or_insert_with(|| ExchangeRecord {
+            need: self.need,
+            offer: self.offer,
+            giver: self.offer_owner,
+            receiver: self.need_owner,
+            terms,
+            agreed_at_millis: at_millis,
+            confirmations: BTreeMap::new(),
+        });
+        record.confirmations.entry(from).or_insert(now);
+        Ok(record)
+    }
+}
+
+/// Errors that can occur during exchange operations.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ExchangeError {
+    /// The listing was not found.
+    #[error("Listing not found")]
+    ListingNotFound,
+    /// The caller is not authorized for this operation.
+    #[error("Not authorized for this operation")]
+    NotAuthorized,
+    /// The negotiation already ended in agreement or a decline.
+    #[error("Negotiation is already closed")]
+    NegotiationClosed,
+    /// There are no terms from the other party to accept.
+    #[error("No terms from the other party to accept")]
+    NothingToAccept,
+    /// The trade cannot be confirmed before terms are agreed.
+    #[error("Terms have not been agreed")]
+    NotAgreed,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indras_network::document::DocumentSchema;
+
+    const ALICE: MemberId = [1u8; 32];
+    const BOB: MemberId = [2u8; 32];
+    const CAROL: MemberId = [3u8; 32];
+
+    fn listing(owner: MemberId, side: ListingSide, tags: &[&str]) -> ExchangeListing {
+        let kind = match side {
+            ListingSide::Need => IntentionKind::Need,
+            ListingSide::Offer => IntentionKind::Offering,
+        };
+        let mut intention = Intention::new("title", "description", None, owner);
+        intention.kind = kind;
+        ExchangeListing::from_intention(&intention, tags.iter().copied())
+    }
+
+    fn agreed(need: &ExchangeListing, offer: &ExchangeListing) -> NegotiationDocument {
+        let proposal = find_matches([need, offer]).remove(0);
+        let mut doc = NegotiationDocument::for_match(&proposal);
+        doc.propose(ALICE, "two hours of tutoring", 10).unwrap();
+        doc.accept(BOB, 20).unwrap();
+        doc
+    }
+
+    #[test]
+    fn test_normalize_tags() {
+        let tags = normalize_tags(["#Garden", " garden ", "Tools", ""]);
+        assert_eq!(tags, vec!["garden".to_string(), "tools".to_string()]);
+    }
+
+    #[test]
+    fn test_side_from_kind() {
+        assert_eq!(
+            ListingSide::from_kind(IntentionKind::Offering),
+            ListingSide::Offer
+        );
+        assert_eq!(
+            ListingSide::from_kind(IntentionKind::Need),
+            ListingSide::Need
+        );
+        assert_eq!(
+            ListingSide::from_kind(IntentionKind::Intention),
+            ListingSide::Need
+        );
+    }
+
+    #[test]
+    fn test_matches_by_shared_tags() {
+        let need = listing(ALICE, ListingSide::Need, &["garden", "tools"]);
+        let good = listing(BOB, ListingSide::Offer, &["garden", "tools"]);
+        let partial = listing(CAROL, ListingSide::Offer, &["tools", "bikes"]);
+        let unrelated = listing(CAROL, ListingSide::Offer, &["music"]);
+
+        let matches = find_matches([&need, &good, &partial, &unrelated]);
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].offer.id, good.id);
+        assert_eq!(matches[0].score, 1.0);
+        assert_eq!(matches[1].offer.id, partial.id);
+        assert_eq!(matches[1].shared_tags, vec!["tools".to_string()]);
+        assert_eq!(matches[0].counterparty(&ALICE), BOB);
+    }
+
+    #[test]
+    fn test_no_self_or_withdrawn_matches() {
+        let need = listing(ALICE, ListingSide::Need, &["garden"]);
+        let own_offer = listing(ALICE, ListingSide::Offer, &["garden"]);
+        let mut withdrawn = listing(BOB, ListingSide::Offer, &["garden"]);
+        withdrawn.withdrawn = true;
+
+        assert!(find_matches([&need, &own_offer, &withdrawn]).is_empty());
+    }
+
+    #[test]
+    fn test_listing_in_several_realms_matches_once() {
+        let need = listing(ALICE, ListingSide::Need, &["garden"]);
+        let offer = listing(BOB, ListingSide::Offer, &["garden"]);
+        let mut withdrawn_copy = offer.clone();
+        withdrawn_copy.withdrawn = true;
+
+        assert_eq!(find_matches([&need, &offer, &need, &offer]).len(), 1);
+        assert!(find_matches([&need, &offer, &withdrawn_copy]).is_empty());
+    }
+
+    #[test]
+    fn test_board_merge_withdrawal_is_sticky() {
+        let offer = listing(BOB, ListingSide::Offer, &["garden"]);
+        let mut local = ExchangeBoardDocument::default();
+        local.add(offer.clone());
+
+        let mut remote = local.clone();
+        remote.withdraw(&offer.id, BOB).unwrap();
+        remote.add(listing(CAROL, ListingSide::Need, &["garden"]));
+
+        local.merge(remote);
+        assert_eq!(local.listings.len(), 2);
+        assert!(local.find(&offer.id).unwrap().withdrawn);
+    }
+
+    #[test]
+    fn test_only_owner_withdraws() {
+        let offer = listing(BOB, ListingSide::Offer, &["garden"]);
+        let mut board = ExchangeBoardDocument::default();
+        board.add(offer.clone());
+        assert_eq!(
+            board.withdraw(&offer.id, ALICE),
+            Err(ExchangeError::NotAuthorized)
+        );
+    }
+
+    #[test]
+    fn test_counter_then_accept() {
+        let need = listing(ALICE, ListingSide::Need, &["garden"]);
+        let offer = listing(BOB, ListingSide::Offer, &["garden"]);
+        let proposal = find_matches([&need, &offer]).remove(0);
+        let mut doc = NegotiationDocument::for_match(&proposal);
+
+        doc.propose(ALICE, "one hour", 10).unwrap();
+        assert_eq!(doc.accept(ALICE, 11), Err(ExchangeError::NothingToAccept));
+        doc.propose(BOB, "two hours", 20).unwrap();
+        doc.accept(ALICE, 30).unwrap();
+
+        assert_eq!(
+            doc.state(),
+            NegotiationState::Agreed {
+                terms: "two hours".to_string(),
+                at_millis: 30
+            }
+        );
+        assert_eq!(
+            doc.propose(BOB, "three hours", 40),
+            Err(ExchangeError::NegotiationClosed)
+        );
+    }
+
+    #[test]
+    fn test_outsider_cannot_negotiate() {
+        let need = listing(ALICE, ListingSide::Need, &["garden"]);
+        let offer = listing(BOB, ListingSide::Offer, &["garden"]);
+        let mut doc = NegotiationDocument::for_match(&find_matches([&need, &offer]).remove(0));
+        assert_eq!(
+            doc.propose(CAROL, "mine", 10),
+            Err(ExchangeError::NotAuthorized)
+        );
+    }
+
+    #[test]
+    fn test_decline_closes() {
+        let need = listing(ALICE, ListingSide::Need, &["garden"]);
+        let offer = listing(BOB, ListingSide::Offer, &["garden"]);
+        let mut doc = NegotiationDocument::for_match(&find_matches([&need, &offer]).remove(0));
+        doc.propose(ALICE, "one hour", 10).unwrap();
+        doc.decline(BOB, 20).unwrap();
+
+        assert_eq!(doc.state(), NegotiationState::Declined { by: BOB });
+        assert_eq!(
+            doc.confirm(ALICE, 30).unwrap_err(),
+            ExchangeError::NotAgreed
+        );
+    }
+
+    #[test]
+    fn test_record_final_after_both_confirm() {
+        let need = listing(ALICE, ListingSide::Need, &["garden"]);
+        let offer = listing(BOB, ListingSide::Offer, &["garden"]);
+        let mut alice_doc = agreed(&need, &offer);
+        let mut bob_doc = alice_doc.clone();
+
+        assert!(!alice_doc.confirm(ALICE, 30).unwrap().is_final());
+        bob_doc.confirm(BOB, 31).unwrap();
+        alice_doc.merge(bob_doc);
+
+        let record = alice_doc.record.as_ref().unwrap();
+        assert!(record.is_final());
+        assert_eq!(record.giver, BOB);
+        assert_eq!(record.receiver, ALICE);
+        assert_eq!(record.terms, "two hours of tutoring");
+    }
+
+    #[test]
+    fn test_negotiation_merge_replays_in_order() {
+        let need = listing(ALICE, ListingSide::Need, &["garden"]);
+        let offer = listing(BOB, ListingSide::Offer, &["garden"]);
+        let proposal = find_matches([&need, &offer]).remove(0);
+        let mut alice_doc = NegotiationDocument::for_match(&proposal);
+        alice_doc.propose(ALICE, "one hour", 10).unwrap();
+
+        let mut bob_doc = NegotiationDocument::default();
+        bob_doc.merge(alice_doc.clone());
+        bob_doc.accept(BOB, 20).unwrap();
+
+        alice_doc.merge(bob_doc.clone());
+        alice_doc.merge(bob_doc);
+        assert_eq!(alice_doc.messages.len(), 2);
+        assert!(matches!(alice_doc.state(), NegotiationState::Agreed { .. }));
+    }
+
+    #[test]
+    fn test_negotiation_key_is_stable() {
+        let need = [1u8; 16];
+        let offer = [2u8; 16];
+        assert_eq!(
+            negotiation_doc_key(&need, &offer),
+            negotiation_doc_key(&need, &offer)
+        );
+        assert!(negotiation_doc_key(&need, &offer).starts_with(NEGOTIATION_KEY_PREFIX));
+    }
+}
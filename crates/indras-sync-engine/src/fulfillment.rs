@@ -0,0 +1,604 @@
+//! Request fulfillment — asking a realm for something and accepting offers.
+//!
+//! A member broadcasts an [`ArtifactRequest`] to a realm's request board.
+//! Other members attach [`RequestOffer`]s: either a reference to an artifact
+//! they hold, or a pledge to do the work. The requester accepts one offer:
+//!
+//! - **Artifact offers** are delivered by the offerer, who grants the
+//!   requester access from their home realm and marks the request fulfilled
+//!   (see [`SyncEngine::deliver_accepted_request_offers`](crate::SyncEngine::deliver_accepted_request_offers)).
+//! - **Pledge offers** become a quest in the realm; the requester marks the
+//!   request fulfilled once the work is done.
+//!
+//! Status is derived from the request's offers and acceptance, see
+//! [`RequestStatus`].
+
+use indras_network::artifact::ArtifactId;
+use indras_network::member::MemberId;
+use serde::{Deserialize, Serialize};
+
+use crate::intention::{generate_intention_id, IntentionId};
+
+/// Document name for the request board in each realm.
+pub const REQUEST_BOARD_DOC: &str = "requests";
+
+/// Unique identifier for a request (16 bytes).
+pub type RequestId = [u8; 16];
+
+/// Unique identifier for an offer on a request (16 bytes).
+pub type OfferId = [u8; 16];
+
+/// What an offer puts forward.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum OfferContent {
+    /// An artifact the offerer holds in their home realm.
+    Artifact {
+        /// The offered artifact.
+        artifact_id: ArtifactId,
+        /// Display name of the artifact.
+        name: String,
+    },
+    /// A pledge to do the work, which becomes a quest on acceptance.
+    Pledge {
+        /// What the offerer commits to.
+        description: String,
+    },
+}
+
+impl OfferContent {
+    /// Short human-readable summary.
+    pub fn summary(&self) -> &str {
+        match self {
+            OfferContent::Artifact { name, .. } => name,
+            OfferContent::Pledge { description } => description,
+        }
+    }
+}
+
+/// An offer attached to a request.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RequestOffer {
+    /// Unique offer ID.
+    pub id: OfferId,
+    /// Who made the offer.
+    pub offerer: MemberId,
+    /// What is offered.
+    pub content: OfferContent,
+    /// When the offer was made (Unix timestamp in milliseconds).
+    pub offered_at_millis: i64,
+    /// Whether the offerer withdrew it (tombstone for CRDT).
+    #[serde(default)]
+    pub withdrawn: bool,
+}
+
+/// The requester's acceptance of one offer.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RequestAcceptance {
+    /// The accepted offer.
+    pub offer_id: OfferId,
+    /// When the offer was accepted (Unix timestamp in milliseconds).
+    pub accepted_at_millis: i64,
+    /// The quest created for an accepted pledge.
+    #[serde(default)]
+    pub quest: Option<IntentionId>,
+    /// When the request was fulfilled (Unix timestamp in milliseconds).
+    #[serde(default)]
+    pub fulfilled_at_millis: Option<i64>,
+}
+
+/// Lifecycle status of a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RequestStatus {
+    /// No active offers yet.
+    Open,
+    /// At least one active offer, none accepted.
+    Offered,
+    /// An offer was accepted and is being delivered.
+    Accepted,
+    /// The accepted offer was delivered.
+    Fulfilled,
+    /// The requester cancelled the request.
+    Cancelled,
+}
+
+impl RequestStatus {
+    /// Display label.
+    pub fn label(&self) -> &'static str {
+        match self {
+            RequestStatus::Open => "Open",
+            RequestStatus::Offered => "Offered",
+            RequestStatus::Accepted => "Accepted",
+            RequestStatus::Fulfilled => "Fulfilled",
+            RequestStatus::Cancelled => "Cancelled",
+        }
+    }
+
+    /// Whether the request still takes offers.
+    pub fn is_open(&self) -> bool {
+        matches!(self, RequestStatus::Open | RequestStatus::Offered)
+    }
+}
+
+/// A request broadcast to a realm.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ArtifactRequest {
+    /// Unique request ID.
+    pub id: RequestId,
+    /// Who is asking.
+    pub requester: MemberId,
+    /// What is being asked for.
+    pub description: String,
+    /// When the request was broadcast (Unix timestamp in milliseconds).
+    pub created_at_millis: i64,
+    /// Offers from other members.
+    pub offers: Vec<RequestOffer>,
+    /// The accepted offer, if any.
+    pub acceptance: Option<RequestAcceptance>,
+    /// Whether the requester cancelled the request.
+    #[serde(default)]
+    pub cancelled: bool,
+}
+
+impl ArtifactRequest {
+    /// Create a new request.
+    pub fn new(requester: MemberId, description: impl Into<String>, now: i64) -> Self {
+        Self {
+            id: generate_intention_id(),
+            requester,
+            description: description.into(),
+            created_at_millis: now,
+            offers: Vec::new(),
+            acceptance: None,
+            cancelled: false,
+        }
+    }
+
+    /// Current status.
+    pub fn status(&self) -> RequestStatus {
+        if self.cancelled {
+            return RequestStatus::Cancelled;
+        }
+        match &self.acceptance {
+            Some(a) if a.fulfilled_at_millis.is_some() => RequestStatus::Fulfilled,
+            Some(_) => RequestStatus::Accepted,
+            None if self.active_offers().next().is_some() => RequestStatus::Offered,
+            None => RequestStatus::Open,
+        }
+    }
+
+    /// Offers that have not been withdrawn.
+    pub fn active_offers(&self) -> impl Iterator<Item = &RequestOffer> {
+        self.offers.iter().filter(|o| !o.withdrawn)
+    }
+
+    /// Find an offer by ID.
+    pub fn find_offer(&self, offer_id: &OfferId) -> Option<&RequestOffer> {
+        self.offers.iter().find(|o| &o.id == offer_id)
+    }
+
+    /// The accepted offer, if any.
+    pub fn accepted_offer(&self) -> Option<&RequestOffer> {
+        self.acceptance
+            .as_ref()
+            .and_then(|a| self.find_offer(&a.offer_id))
+    }
+
+    /// Attach an offer. The requester cannot offer on their own request.
+    pub fn add_offer(
+        &mut self,
+        offerer: MemberId,
+        content: OfferContent,
+        now: i64,
+    ) -> Result<OfferId, RequestError> {
+        if !self.status().is_open() {
+            return Err(RequestError::RequestClosed);
+        }
+        if offerer == self.requester {
+            return Err(RequestError::OwnRequest);
+        }
+        let id = generate_intention_id();
+        self.offers.push(RequestOffer {
+            id,
+            offerer,
+            content,
+            offered_at_millis: now,
+            withdrawn: false,
+        });
+        Ok(id)
+    }
+
+    /// Withdraw an offer. Only its offerer may, and only before acceptance.
+    pub fn withdraw_offer(
+        &mut self,
+        offer_id: &OfferId,
+        caller: MemberId,
+    ) -> Result<(), RequestError> {
+        if self.acceptance.is_some() {
+            return Err(RequestError::AlreadyAccepted);
+        }
+        let offer = self
+            .offers
+            .iter_mut()
+            .find(|o| &o.id == offer_id)
+            .ok_or(RequestError::OfferNotFound)?;
+        if offer.offerer != caller {
+            return Err(RequestError::NotAuthorized);
+        }
+        offer.withdrawn = true;
+        Ok(())
+    }
+
+    /// Accept an offer. Only the requester may.
+    pub fn accept(
+        &mut self,
+        offer_id: &OfferId,
+        caller: MemberId,
+        now: i64,
+    ) -> Result<&RequestOffer, RequestError> {
+        if caller != self.requester {
+            return Err(RequestError::NotAuthorized);
+        }
+        if self.acceptance.is_some() {
+            return Err(RequestError::AlreadyAccepted);
+        }
+        if self.cancelled {
+            return Err(RequestError::RequestClosed);
+        }
+        match self.find_offer(offer_id) {
+            Some(offer) if !offer.withdrawn => {}
+            _ => return Err(RequestError::OfferNotFound),
+        }
+        self.acceptance = Some(RequestAcceptance {
+            offer_id: *offer_id,
+            accepted_at_millis: now,
+            quest: None,
+            fulfilled_at_millis: None,
+        });
+        self.find_offer(offer_id).ok_or(RequestError::OfferNotFound)
+    }
+
+    /// Record the quest created for an accepted pledge.
+    pub fn set_quest(&mut self, quest: IntentionId) -> Result<(), RequestError> {
+        let acceptance = self.acceptance.as_mut().ok_or(RequestError::NotAccepted)?;
+        acceptance.quest = Some(quest);
+        Ok(())
+    }
+
+    /// Mark the request fulfilled. The requester or the accepted offerer may.
+    pub fn mark_fulfilled(&mut self, caller: MemberId, now: i64) -> Result<(), RequestError> {
+        let offerer = self
+            .accepted_offer()
+            .map(|o| o.offerer)
+            .ok_or(RequestError::NotAccepted)?;
+        if caller != self.requester && caller != offerer {
+            return Err(RequestError::NotAuthorized);
+        }
+        if let Some(acceptance) = self.acceptance.as_mut() {
+            acceptance.fulfilled_at_millis.get_or_insert(now);
+        }
+        Ok(())
+    }
+
+    /// Cancel the request. Only the requester may, and only before it is fulfilled.
+    pub fn cancel(&mut self, caller: MemberId) -> Result<(), RequestError> {
+        if caller != self.requester {
+            return Err(RequestError::NotAuthorized);
+        }
+        if self.status() == RequestStatus::Fulfilled {
+            return Err(RequestError::RequestClosed);
+        }
+        self.cancelled = true;
+        Ok(())
+    }
+
+    fn merge(&mut self, remote: ArtifactRequest) {
+        self.cancelled |= remote.cancelled;
+
+        for offer in remote.offers {
+            match self.offers.iter_mut().find(|o| o.id == offer.id) {
+                Some(local) => local.withdrawn |= offer.withdrawn,
+                None => self.offers.push(offer),
+            }
+        }
+        self.offers.sort_by_key(|o| (o.offered_at_millis, o.id));
+
+        match (&mut self.acceptance, remote.acceptance) {
+            (_, None) => {}
+            (None, Some(remote)) => self.acceptance = Some(remote),
+            (Some(local), Some(remote)) if local.offer_id == remote.offer_id => {
+                local.quest = local.quest.or(remote.quest);
+                local.fulfilled_at_millis =
+                    match (local.fulfilled_at_millis, remote.fulfilled_at_millis) {
+                        (Some(a), Some(b)) => Some(a.min(b)),
+                        (a, b) => a.or(b),
+                    };
+            }
+            // Concurrent acceptances of different offers: earliest wins
+            (Some(local), Some(remote)) => {
+                if (remote.accepted_at_millis, remote.offer_id)
+                    < (local.accepted_at_millis, local.offer_id)
+                {
+                    *local = remote;
+                }
+            }
+        }
+    }
+}
+
+/// Document schema for a realm's request board.
+///
+/// # CRDT Semantics
+///
+/// - Set-union of requests by ID, and of offers within each request
+/// - Cancellation and offer withdrawal are sticky
+/// - Acceptance: the earliest acceptance wins; quest and fulfillment merge in
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RequestBoardDocument {
+    /// All requests broadcast to this realm.
+    pub requests: Vec<ArtifactRequest>,
+}
+
+impl indras_network::document::DocumentSchema for RequestBoardDocument {
+    fn merge(&mut self, remote: Self) {
+        for request in remote.requests {
+            match self.requests.iter_mut().find(|r| r.id == request.id) {
+                Some(local) => local.merge(request),
+                None => self.requests.push(request),
+            }
+        }
+    }
+}
+
+impl RequestBoardDocument {
+    /// Add a request (idempotent by ID).
+    pub fn add(&mut self, request: ArtifactRequest) {
+        if !self.requests.iter().any(|r| r.id == request.id) {
+            self.requests.push(request);
+        }
+    }
+
+    /// Find a request by ID.
+    pub fn find(&self, id: &RequestId) -> Option<&ArtifactRequest> {
+        self.requests.iter().find(|r| &r.id == id)
+    }
+
+    /// Find a request by ID (mutable).
+    pub fn find_mut(&mut self, id: &RequestId) -> Option<&mut ArtifactRequest> {
+        self.requests.iter_mut().find(|r| &r.id == id)
+    }
+
+    /// Requests still taking offers.
+    pub fn open_requests(&self) -> impl Iterator<Item = &ArtifactRequest> {
+        self.requests.iter().filter(|r| r.status().is_open())
+    }
+
+    /// Accepted artifact offers from `offerer` that still need delivering.
+    ///
+    /// Returns `(request, artifact_id)` pairs.
+    pub fn pending_deliveries(&self, offerer: &MemberId) -> Vec<(&ArtifactRequest, &ArtifactId)> {
+        self.requests
+            .iter()
+            .filter(|r| r.status() == RequestStatus::Accepted)
+            .filter_map(|r| match r.accepted_offer() {
+                Some(RequestOffer {
+                    offerer: o,
+                    content: OfferContent::Artifact { artifact_id, .. },
+                    ..
+                }) if o == offerer => Some((r, artifact_id)),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Errors that can occur during request fulfillment.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RequestError {
+    /// The request was not found.
+    #[error("Request not found")]
+    RequestNotFound,
+    /// The offer was not found or has been withdrawn.
+    #[error("Offer not found")]
+    OfferNotFound,
+    /// The caller is not authorized for this operation.
+    #[error("Not authorized for this operation")]
+    NotAuthorized,
+    /// The requester cannot offer on their own request.
+    #[error("Cannot offer on your own request")]
+    OwnRequest,
+    /// The request no longer takes changes of this kind.
+    #[error("Request is closed")]
+    RequestClosed,
+    /// An offer was already accepted.
+    #[error("An offer was already accepted")]
+    AlreadyAccepted,
+    /// No offer has been accepted yet.
+    #[error("No offer has been accepted")]
+    NotAccepted,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indras_network::document::DocumentSchema;
+
+    const ALICE: MemberId = [1u8; 32];
+    const BOB: MemberId = [2u8; 32];
+    const CAROL: MemberId = [3u8; 32];
+
+    fn pledge(text: &str) -> OfferContent {
+        OfferContent::Pledge {
+            description: text.to_string(),
+        }
+    }
+
+    fn artifact(name: &str) -> OfferContent {
+        OfferContent::Artifact {
+            artifact_id: ArtifactId::Blob([7u8; 32]),
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_status_lifecycle() {
+        let mut request = ArtifactRequest::new(ALICE, "Need a ladder", 0);
+        assert_eq!(request.status(), RequestStatus::Open);
+
+        let offer = request.add_offer(BOB, artifact("ladder.jpg"), 10).unwrap();
+        assert_eq!(request.status(), RequestStatus::Offered);
+
+        request.accept(&offer, ALICE, 20).unwrap();
+        assert_eq!(request.status(), RequestStatus::Accepted);
+
+        request.mark_fulfilled(BOB, 30).unwrap();
+        assert_eq!(request.status(), RequestStatus::Fulfilled);
+    }
+
+    #[test]
+    fn test_cannot_offer_on_own_request() {
+        let mut request = ArtifactRequest::new(ALICE, "Need a ladder", 0);
+        assert_eq!(
+            request.add_offer(ALICE, pledge("me"), 10),
+            Err(RequestError::OwnRequest)
+        );
+    }
+
+    #[test]
+    fn test_only_requester_accepts() {
+        let mut request = ArtifactRequest::new(ALICE, "Need a ladder", 0);
+        let offer = request
+            .add_offer(BOB, pledge("I'll build one"), 10)
+            .unwrap();
+        assert_eq!(
+            request.accept(&offer, CAROL, 20).unwrap_err(),
+            RequestError::NotAuthorized
+        );
+    }
+
+    #[test]
+    fn test_withdrawn_offer_cannot_be_accepted() {
+        let mut request = ArtifactRequest::new(ALICE, "Need a ladder", 0);
+        let offer = request
+            .add_offer(BOB, pledge("I'll build one"), 10)
+            .unwrap();
+        assert_eq!(
+            request.withdraw_offer(&offer, CAROL),
+            Err(RequestError::NotAuthorized)
+        );
+        request.withdraw_offer(&offer, BOB).unwrap();
+
+        assert_eq!(request.status(), RequestStatus::Open);
+        assert_eq!(
+            request.accept(&offer, ALICE, 20).unwrap_err(),
+            RequestError::OfferNotFound
+        );
+    }
+
+    #[test]
+    fn test_closed_after_acceptance() {
+        let mut request = ArtifactRequest::new(ALICE, "Need a ladder", 0);
+        let offer = request
+            .add_offer(BOB, pledge("I'll build one"), 10)
+            .unwrap();
+        request.accept(&offer, ALICE, 20).unwrap();
+
+        assert_eq!(
+            request.add_offer(CAROL, pledge("me too"), 30),
+            Err(RequestError::RequestClosed)
+        );
+        assert_eq!(
+            request.withdraw_offer(&offer, BOB),
+            Err(RequestError::AlreadyAccepted)
+        );
+        assert_eq!(
+            request.mark_fulfilled(CAROL, 40),
+            Err(RequestError::NotAuthorized)
+        );
+    }
+
+    #[test]
+    fn test_cancel() {
+        let mut request = ArtifactRequest::new(ALICE, "Need a ladder", 0);
+        assert_eq!(request.cancel(BOB), Err(RequestError::NotAuthorized));
+        request.cancel(ALICE).unwrap();
+        assert_eq!(request.status(), RequestStatus::Cancelled);
+        assert_eq!(
+            request.add_offer(BOB, pledge("late"), 10),
+            Err(RequestError::RequestClosed)
+        );
+    }
+
+    #[test]
+    fn test_merge_unions_offers() {
+        let mut local = RequestBoardDocument::default();
+        local.add(ArtifactRequest::new(ALICE, "Need a ladder", 0));
+        let id = local.requests[0].id;
+        let mut remote = local.clone();
+
+        local
+            .find_mut(&id)
+            .unwrap()
+            .add_offer(BOB, pledge("build"), 10)
+            .unwrap();
+        remote
+            .find_mut(&id)
+            .unwrap()
+            .add_offer(CAROL, artifact("ladder"), 11)
+            .unwrap();
+
+        local.merge(remote);
+        assert_eq!(local.find(&id).unwrap().offers.len(), 2);
+    }
+
+    #[test]
+    fn test_merge_earliest_acceptance_wins() {
+        let mut request = ArtifactRequest::new(ALICE, "Need a ladder", 0);
+        let bob = request.add_offer(BOB, pledge("build"), 10).unwrap();
+        let carol = request.add_offer(CAROL, artifact("ladder"), 11).unwrap();
+
+        let mut local = request.clone();
+        let mut remote = request;
+        local.accept(&carol, ALICE, 30).unwrap();
+        remote.accept(&bob, ALICE, 20).unwrap();
+
+        local.merge(remote);
+        assert_eq!(local.acceptance.as_ref().unwrap().offer_id, bob);
+    }
+
+    #[test]
+    fn test_merge_carries_quest_and_fulfillment() {
+        let mut request = ArtifactRequest::new(ALICE, "Need a ladder", 0);
+        let offer = request.add_offer(BOB, pledge("build"), 10).unwrap();
+        request.accept(&offer, ALICE, 20).unwrap();
+
+        let mut local = request.clone();
+        let mut remote = request;
+        local.set_quest([9u8; 16]).unwrap();
+        remote.mark_fulfilled(BOB, 40).unwrap();
+
+        local.merge(remote);
+        let acceptance = local.acceptance.as_ref().unwrap();
+        assert_eq!(acceptance.quest, Some([9u8; 16]));
+        assert_eq!(acceptance.fulfilled_at_millis, Some(40));
+    }
+
+    #[test]
+    fn test_pending_deliveries() {
+        let mut board = RequestBoardDocument::default();
+        let mut wants_artifact = ArtifactRequest::new(ALICE, "Photo of the creek", 0);
+        let offer = wants_artifact
+            .add_offer(BOB, artifact("creek.jpg"), 10)
+            .unwrap();
+        wants_artifact.accept(&offer, ALICE, 20).unwrap();
+        let mut wants_work = ArtifactRequest::new(ALICE, "Fix the fence", 0);
+        let offer = wants_work.add_offer(BOB, pledge("Saturday"), 10).unwrap();
+        wants_work.accept(&offer, ALICE, 20).unwrap();
+        board.add(wants_artifact.clone());
+        board.add(wants_work);
+
+        let pending = board.pending_deliveries(&BOB);
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].0.id, wants_artifact.id);
+        assert!(board.pending_deliveries(&CAROL).is_empty());
+    }
+}
@@ -0,0 +1,524 @@
+//! Galleries — ordered media collections with captions and a cover image.
+//!
+//! A gallery references artifacts already in someone's home realm; it never
+//! holds the media itself. Items are ordered by the same fractional position
+//! keys stories use (see [`position_between`]), so moving an item rewrites
+//! only that item's position.
+//!
+//! Each gallery lives in its own document under [`gallery_doc_key`]; the
+//! realm's [`GalleryIndexDocument`] lists them.
+
+use indras_network::artifact::ArtifactId;
+use indras_network::member::MemberId;
+use serde::{Deserialize, Serialize};
+
+use crate::intention::generate_intention_id;
+use crate::story::position_between;
+
+/// Document name for the realm's gallery index.
+pub const GALLERY_INDEX_DOC: &str = "galleries";
+
+/// Doc key prefix for individual gallery documents.
+pub const GALLERY_KEY_PREFIX: &str = "_gallery:";
+
+/// Unique identifier for a gallery (16 bytes).
+pub type GalleryId = [u8; 16];
+
+/// The doc key for a gallery's document.
+pub fn gallery_doc_key(gallery_id: &GalleryId) -> String {
+    format!("{}{}", GALLERY_KEY_PREFIX, hex::encode(gallery_id))
+}
+
+/// An artifact to place in a gallery.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GalleryMedia {
+    /// The media artifact.
+    pub artifact_id: ArtifactId,
+    /// File name.
+    pub name: String,
+    /// MIME type if known.
+    pub mime_type: Option<String>,
+    /// Size in bytes.
+    pub size: u64,
+}
+
+impl GalleryMedia {
+    /// Whether the media is an image.
+    pub fn is_image(&self) -> bool {
+        self.mime_type
+            .as_deref()
+            .is_some_and(|m| m.starts_with("image/"))
+    }
+}
+
+/// One item in a gallery.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GalleryItem {
+    /// The media being shown.
+    pub media: GalleryMedia,
+    /// Who added the item.
+    pub added_by: MemberId,
+    /// When the item was added (Unix timestamp in milliseconds).
+    pub added_at_millis: i64,
+    /// Fractional position key (see [`position_between`]).
+    pub position: String,
+    /// When the position was last set (Unix timestamp in milliseconds).
+    pub moved_at_millis: i64,
+    /// Caption shown under the item.
+    pub caption: Option<String>,
+    /// When the caption was last set (Unix timestamp in milliseconds).
+    pub captioned_at_millis: i64,
+    /// Whether the item was removed (tombstone for CRDT).
+    #[serde(default)]
+    pub removed: bool,
+}
+
+impl GalleryItem {
+    /// Whether `caller` may caption or remove this item.
+    fn editable_by(&self, caller: MemberId, creator: MemberId) -> bool {
+        caller == self.added_by || caller == creator
+    }
+
+    fn merge(&mut self, remote: GalleryItem) {
+        if (remote.moved_at_millis, &remote.position) > (self.moved_at_millis, &self.position) {
+            self.position = remote.position;
+            self.moved_at_millis = remote.moved_at_millis;
+        }
+        if (remote.captioned_at_millis, &remote.caption) > (self.captioned_at_millis, &self.caption)
+        {
+            self.caption = remote.caption;
+            self.captioned_at_millis = remote.captioned_at_millis;
+        }
+        self.removed |= remote.removed;
+    }
+}
+
+/// A gallery and its items.
+///
+/// # CRDT Semantics
+///
+/// - Set-union of items by artifact ID; removal is sticky
+/// - Position, caption and cover are last-writer-wins by timestamp
+/// - Items are kept sorted by `(position, artifact id)` so every replica agrees
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GalleryDocument {
+    /// Gallery ID.
+    pub id: GalleryId,
+    /// Gallery title.
+    pub title: String,
+    /// Who created the gallery.
+    pub creator: MemberId,
+    /// When the gallery was created (Unix timestamp in milliseconds).
+    pub created_at_millis: i64,
+    /// All items, including removed ones, in gallery order.
+    pub items: Vec<GalleryItem>,
+    /// Explicit cover image, if the creator picked one.
+    pub cover: Option<ArtifactId>,
+    /// When the cover was last set (Unix timestamp in milliseconds).
+    pub cover_set_at_millis: i64,
+}
+
+impl indras_network::document::DocumentSchema for GalleryDocument {
+    fn merge(&mut self, remote: Self) {
+        if self.id == GalleryId::default() {
+            self.id = remote.id;
+            self.title = remote.title;
+            self.creator = remote.creator;
+            self.created_at_millis = remote.created_at_millis;
+        }
+        for item in remote.items {
+            let id = item.media.artifact_id;
+            match self.items.iter_mut().find(|i| i.media.artifact_id == id) {
+                Some(local) => local.merge(item),
+                None => self.items.push(item),
+            }
+        }
+        if (remote.cover_set_at_millis, remote.cover) > (self.cover_set_at_millis, self.cover) {
+            self.cover = remote.cover;
+            self.cover_set_at_millis = remote.cover_set_at_millis;
+        }
+        self.sort();
+    }
+}
+
+impl GalleryDocument {
+    /// Create an empty gallery.
+    pub fn new(title: impl Into<String>, creator: MemberId, now: i64) -> Self {
+        Self {
+            id: generate_intention_id(),
+            title: title.into(),
+            creator,
+            created_at_millis: now,
+            ..Default::default()
+        }
+    }
+
+    fn sort(&mut self) {
+        self.items.sort_by(|a, b| {
+            a.position
+                .cmp(&b.position)
+                .then(a.media.artifact_id.cmp(&b.media.artifact_id))
+        });
+    }
+
+    /// Items that have not been removed, in gallery order.
+    pub fn ordered_items(&self) -> impl Iterator<Item = &GalleryItem> {
+        self.items.iter().filter(|i| !i.removed)
+    }
+
+    /// Find a live item by artifact ID.
+    pub fn find(&self, artifact_id: &ArtifactId) -> Option<&GalleryItem> {
+        self.ordered_items()
+            .find(|i| &i.media.artifact_id == artifact_id)
+    }
+
+    fn find_mut(&mut self, artifact_id: &ArtifactId) -> Result<&mut GalleryItem, GalleryError> {
+        self.items
+            .iter_mut()
+            .find(|i| !i.removed && &i.media.artifact_id == artifact_id)
+            .ok_or(GalleryError::ItemNotFound)
+    }
+
+    /// The cover image: the chosen cover if still present, otherwise the
+    /// first image in the gallery.
+    pub fn cover_item(&self) -> Option<&GalleryItem> {
+        self.cover
+            .and_then(|id| self.find(&id))
+            .or_else(|| self.ordered_items().find(|i| i.media.is_image()))
+    }
+
+    /// Add media at the end of the gallery.
+    ///
+    /// An artifact appears at most once; a removed artifact stays removed.
+    pub fn add(
+        &mut self,
+        media: GalleryMedia,
+        added_by: MemberId,
+        caption: Option<String>,
+        now: i64,
+    ) -> Result<(), GalleryError> {
+        if self
+            .items
+            .iter()
+            .any(|i| i.media.artifact_id == media.artifact_id)
+        {
+            return Err(GalleryError::AlreadyInGallery);
+        }
+        let position = position_between(self.items.last().map(|i| i.position.as_str()), None);
+        self.items.push(GalleryItem {
+            media,
+            added_by,
+            added_at_millis: now,
+            position,
+            moved_at_millis: now,
+            caption,
+            captioned_at_millis: now,
+            removed: false,
+        });
+        self.sort();
+        Ok(())
+    }
+
+    /// Move an item to just after `after`, or to the front when `None`.
+    pub fn move_after(
+        &mut self,
+        artifact_id: &ArtifactId,
+        after: Option<&ArtifactId>,
+        now: i64,
+    ) -> Result<(), GalleryError> {
+        if after == Some(artifact_id) {
+            return Ok(());
+        }
+        self.find_mut(artifact_id)?;
+        let before = match after {
+            Some(id) => Some(self.find_mut(id)?.position.clone()),
+            None => None,
+        };
+        let next = self
+            .items
+            .iter()
+            .filter(|i| &i.media.artifact_id != artifact_id)
+            .map(|i| i.position.as_str())
+            .find(|p| before.as_deref().is_none_or(|b| *p > b));
+        let position = position_between(before.as_deref(), next);
+
+        let item = self.find_mut(artifact_id)?;
+        item.position = position;
+        item.moved_at_millis = now;
+        self.sort();
+        Ok(())
+    }
+
+    /// Set or clear an item's caption, as its adder or the gallery creator.
+    pub fn set_caption(
+        &mut self,
+        artifact_id: &ArtifactId,
+        caption: Option<String>,
+        caller: MemberId,
+        now: i64,
+    ) -> Result<(), GalleryError> {
+        let creator = self.creator;
+        let item = self.find_mut(artifact_id)?;
+        if !item.editable_by(caller, creator) {
+            return Err(GalleryError::NotAuthorized);
+        }
+        item.caption = caption;
+        item.captioned_at_millis = now;
+        Ok(())
+    }
+
+    /// Choose the cover image, or clear it, as the gallery creator.
+    pub fn set_cover(
+        &mut self,
+        artifact_id: Option<ArtifactId>,
+        caller: MemberId,
+        now: i64,
+    ) -> Result<(), GalleryError> {
+        if caller != self.creator {
+            return Err(GalleryError::NotAuthorized);
+        }
+        if let Some(id) = &artifact_id {
+            self.find_mut(id)?;
+        }
+        self.cover = artifact_id;
+        self.cover_set_at_millis = now;
+        Ok(())
+    }
+
+    /// Remove an item, as its adder or the gallery creator.
+    pub fn remove(
+        &mut self,
+        artifact_id: &ArtifactId,
+        caller: MemberId,
+    ) -> Result<(), GalleryError> {
+        let creator = self.creator;
+        let item = self.find_mut(artifact_id)?;
+        if !item.editable_by(caller, creator) {
+            return Err(GalleryError::NotAuthorized);
+        }
+        item.removed = true;
+        Ok(())
+    }
+}
+
+/// A gallery listed in the realm's index.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GallerySummary {
+    /// Gallery ID.
+    pub id: GalleryId,
+    /// Gallery title.
+    pub title: String,
+    /// Who created the gallery.
+    pub creator: MemberId,
+    /// When the gallery was created (Unix timestamp in milliseconds).
+    pub created_at_millis: i64,
+}
+
+/// Index of the galleries in a realm.
+///
+/// # CRDT Semantics
+///
+/// - Set-union by gallery ID
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GalleryIndexDocument {
+    /// All galleries, oldest first.
+    pub galleries: Vec<GallerySummary>,
+}
+
+impl indras_network::document::DocumentSchema for GalleryIndexDocument {
+    fn merge(&mut self, remote: Self) {
+        for gallery in remote.galleries {
+            self.add(gallery);
+        }
+    }
+}
+
+impl GalleryIndexDocument {
+    /// Add a gallery (idempotent by ID), keeping the index oldest first.
+    pub fn add(&mut self, gallery: GallerySummary) {
+        if self.galleries.iter().any(|g| g.id == gallery.id) {
+            return;
+        }
+        self.galleries.push(gallery);
+        self.galleries.sort_by(|a, b| {
+            a.created_at_millis
+                .cmp(&b.created_at_millis)
+                .then(a.id.cmp(&b.id))
+        });
+    }
+
+    /// Find a gallery by ID.
+    pub fn find(&self, id: &GalleryId) -> Option<&GallerySummary> {
+        self.galleries.iter().find(|g| &g.id == id)
+    }
+}
+
+/// Errors that can occur when editing a gallery.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum GalleryError {
+    /// The item was not found.
+    #[error("Item not found in gallery")]
+    ItemNotFound,
+    /// The artifact is already in the gallery (or was removed from it).
+    #[error("Artifact is already in this gallery")]
+    AlreadyInGallery,
+    /// The caller is not authorized for this operation.
+    #[error("Not authorized for this operation")]
+    NotAuthorized,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indras_network::document::DocumentSchema;
+
+    const ALICE: MemberId = [1u8; 32];
+    const BOB: MemberId = [2u8; 32];
+
+    fn media(n: u8, mime: &str) -> GalleryMedia {
+        GalleryMedia {
+            artifact_id: ArtifactId::Blob([n; 32]),
+            name: format!("item-{n}"),
+            mime_type: Some(mime.to_string()),
+            size: 1024,
+        }
+    }
+
+    fn order(doc: &GalleryDocument) -> Vec<String> {
+        doc.ordered_items().map(|i| i.media.name.clone()).collect()
+    }
+
+    fn gallery(n: u8) -> GalleryDocument {
+        let mut doc = GalleryDocument::new("Harvest", ALICE, 0);
+        for i in 1..=n {
+            doc.add(media(i, "image/png"), ALICE, None, 1).unwrap();
+        }
+        doc
+    }
+
+    #[test]
+    fn test_add_keeps_insertion_order() {
+        let doc = gallery(3);
+        assert_eq!(order(&doc), vec!["item-1", "item-2", "item-3"]);
+    }
+
+    #[test]
+    fn test_add_rejects_duplicates() {
+        let mut doc = gallery(1);
+        assert_eq!(
+            doc.add(media(1, "image/png"), BOB, None, 2),
+            Err(GalleryError::AlreadyInGallery)
+        );
+    }
+
+    #[test]
+    fn test_move_after() {
+        let mut doc = gallery(3);
+        let third = ArtifactId::Blob([3; 32]);
+        let first = ArtifactId::Blob([1; 32]);
+
+        doc.move_after(&third, None, 2).unwrap();
+        assert_eq!(order(&doc), vec!["item-3", "item-1", "item-2"]);
+
+        doc.move_after(&third, Some(&first), 3).unwrap();
+        assert_eq!(order(&doc), vec!["item-1", "item-3", "item-2"]);
+    }
+
+    #[test]
+    fn test_concurrent_moves_converge() {
+        let base = gallery(3);
+        let mut alice = base.clone();
+        let mut bob = base;
+        let first = ArtifactId::Blob([1; 32]);
+        let third = ArtifactId::Blob([3; 32]);
+
+        alice.move_after(&first, Some(&third), 2).unwrap();
+        bob.move_after(&first, None, 3).unwrap();
+        bob.move_after(&third, None, 4).unwrap();
+
+        let mut merged_a = alice.clone();
+        merged_a.merge(bob.clone());
+        let mut merged_b = bob;
+        merged_b.merge(alice);
+
+        assert_eq!(order(&merged_a), order(&merged_b));
+        // Bob's later move of item 1 wins over Alice's
+        assert_eq!(order(&merged_a), vec!["item-3", "item-1", "item-2"]);
+    }
+
+    #[test]
+    fn test_caption_lww_and_permissions() {
+        let mut doc = gallery(1);
+        doc.add(media(2, "image/png"), BOB, None, 1).unwrap();
+        let alices = ArtifactId::Blob([1; 32]);
+        let bobs = ArtifactId::Blob([2; 32]);
+
+        assert_eq!(
+            doc.set_caption(&alices, Some("mine".into()), BOB, 2),
+            Err(GalleryError::NotAuthorized)
+        );
+        doc.set_caption(&bobs, Some("old".into()), BOB, 2).unwrap();
+
+        let mut other = doc.clone();
+        other
+            .set_caption(&bobs, Some("new".into()), ALICE, 5)
+            .unwrap();
+        doc.merge(other);
+        assert_eq!(doc.find(&bobs).unwrap().caption.as_deref(), Some("new"));
+    }
+
+    #[test]
+    fn test_cover_defaults_to_first_image() {
+        let mut doc = GalleryDocument::new("Mixed", ALICE, 0);
+        doc.add(media(1, "video/mp4"), ALICE, None, 1).unwrap();
+        doc.add(media(2, "image/jpeg"), ALICE, None, 1).unwrap();
+        doc.add(media(3, "image/png"), ALICE, None, 1).unwrap();
+
+        assert_eq!(doc.cover_item().unwrap().media.name, "item-2");
+
+        let chosen = ArtifactId::Blob([3; 32]);
+        assert_eq!(
+            doc.set_cover(Some(chosen), BOB, 2),
+            Err(GalleryError::NotAuthorized)
+        );
+        doc.set_cover(Some(chosen), ALICE, 2).unwrap();
+        assert_eq!(doc.cover_item().unwrap().media.name, "item-3");
+
+        // Removing the cover falls back to the first image again
+        doc.remove(&chosen, ALICE).unwrap();
+        assert_eq!(doc.cover_item().unwrap().media.name, "item-2");
+    }
+
+    #[test]
+    fn test_removal_is_sticky() {
+        let mut doc = gallery(2);
+        let stale = doc.clone();
+        let first = ArtifactId::Blob([1; 32]);
+
+        assert_eq!(doc.remove(&first, BOB), Err(GalleryError::NotAuthorized));
+        doc.remove(&first, ALICE).unwrap();
+        doc.merge(stale);
+        assert_eq!(order(&doc), vec!["item-2"]);
+        assert_eq!(
+            doc.add(media(1, "image/png"), ALICE, None, 3),
+            Err(GalleryError::AlreadyInGallery)
+        );
+    }
+
+    #[test]
+    fn test_concurrent_adds_converge() {
+        let base = gallery(1);
+        let mut alice = base.clone();
+        let mut bob = base;
+        alice.add(media(2, "image/png"), ALICE, None, 2).unwrap();
+        bob.add(media(3, "image/png"), BOB, None, 2).unwrap();
+
+        let mut merged_a = alice.clone();
+        merged_a.merge(bob.clone());
+        let mut merged_b = bob;
+        merged_b.merge(alice);
+
+        assert_eq!(order(&merged_a), order(&merged_b));
+        assert_eq!(merged_a.ordered_items().count(), 3);
+    }
+}
@@ -0,0 +1,308 @@
+//! Persisted artifact heat — snapshots maintained from attention chains.
+//!
+//! [`compute_heat`] is exact but scans every attention event, so calling it
+//! for each artifact on every render does not scale. A realm's
+//! [`HeatDocument`] keeps the last computed heat per artifact:
+//!
+//! - **Incremental recomputation**: each author's chain has a watermark
+//!   (last processed `seq`). A refresh recomputes only the artifacts touched
+//!   by events past the watermark.
+//! - **Decay**: between recomputations, heat decays with the same time
+//!   constant `compute_heat` uses for recency, so a snapshot can be read at
+//!   any later time without rescanning.
+
+use std::collections::{HashMap, HashSet};
+
+use indras_artifacts::attention::{compute_heat, AttentionSwitchEvent as ChainedSwitchEvent};
+use indras_network::artifact::ArtifactId;
+use indras_network::member::MemberId;
+use serde::{Deserialize, Serialize};
+
+/// Document name for a realm's heat snapshots.
+pub const HEAT_DOC: &str = "heat";
+
+/// Recency time constant used by `compute_heat` (milliseconds).
+pub const HEAT_DECAY_MILLIS: f32 = 300_000.0;
+
+/// Snapshots that decay below this heat are dropped.
+pub const HEAT_FLOOR: f32 = 0.01;
+
+/// Heat of one artifact as of its last computation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HeatSnapshot {
+    /// The artifact.
+    pub artifact_id: ArtifactId,
+    /// Heat (0.0–1.0) as of `computed_at_millis`.
+    pub heat: f32,
+    /// Total dwell across the audience, in milliseconds.
+    pub total_dwell_ms: i64,
+    /// When the heat was computed or last decayed (Unix timestamp in milliseconds).
+    pub computed_at_millis: i64,
+}
+
+impl HeatSnapshot {
+    /// Heat at `now`, decayed from the snapshot.
+    pub fn heat_at(&self, now: i64) -> f32 {
+        let age = (now - self.computed_at_millis).max(0) as f32;
+        self.heat * (-age / HEAT_DECAY_MILLIS).exp()
+    }
+}
+
+/// Per-realm heat snapshots.
+///
+/// # CRDT Semantics
+///
+/// - Snapshots are last-writer-wins per artifact by `computed_at_millis`
+/// - Watermarks take the per-author maximum
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HeatDocument {
+    /// Latest snapshot per artifact.
+    pub snapshots: HashMap<ArtifactId, HeatSnapshot>,
+    /// Highest chain `seq` already folded in, per author.
+    pub watermarks: HashMap<MemberId, u64>,
+}
+
+impl indras_network::document::DocumentSchema for HeatDocument {
+    fn merge(&mut self, remote: Self) {
+        for (id, snapshot) in remote.snapshots {
+            match self.snapshots.get(&id) {
+                Some(local) if local.computed_at_millis >= snapshot.computed_at_millis => {}
+                _ => {
+                    self.snapshots.insert(id, snapshot);
+                }
+            }
+        }
+        for (author, seq) in remote.watermarks {
+            let local = self.watermarks.entry(author).or_default();
+            *local = (*local).max(seq);
+        }
+    }
+}
+
+impl HeatDocument {
+    /// Recompute heat for artifacts touched by events past the watermarks.
+    ///
+    /// `audience` should list the local member first, as `compute_heat`
+    /// expects. Returns the number of artifacts recomputed.
+    pub fn refresh(
+        &mut self,
+        chains: &HashMap<MemberId, Vec<ChainedSwitchEvent>>,
+        audience: &[MemberId],
+        now: i64,
+    ) -> usize {
+        let mut touched: HashSet<ArtifactId> = HashSet::new();
+        for (author, events) in chains {
+            let watermark = self.watermarks.get(author).copied();
+            let fresh = events
+                .iter()
+                .filter(|e| watermark.is_none_or(|w| e.seq > w));
+            let mut high = watermark;
+            for event in fresh {
+                touched.extend(event.from);
+                touched.extend(event.to);
+                high = Some(high.map_or(event.seq, |h| h.max(event.seq)));
+            }
+            if let Some(high) = high {
+                self.watermarks.insert(*author, high);
+            }
+        }
+        if touched.is_empty() {
+            return 0;
+        }
+
+        let logs: Vec<(MemberId, &[ChainedSwitchEvent])> = chains
+            .iter()
+            .map(|(author, events)| (*author, events.as_slice()))
+            .collect();
+        for artifact_id in &touched {
+            let value = compute_heat(artifact_id, &logs, audience, now);
+            self.snapshots.insert(
+                *artifact_id,
+                HeatSnapshot {
+                    artifact_id: *artifact_id,
+                    heat: value.heat,
+                    total_dwell_ms: value.total_dwell_ms,
+                    computed_at_millis: now,
+                },
+            );
+        }
+        touched.len()
+    }
+
+    /// Fold decay into the stored snapshots and drop those below
+    /// [`HEAT_FLOOR`]. Returns the number dropped.
+    pub fn apply_decay(&mut self, now: i64) -> usize {
+        let before = self.snapshots.len();
+        for snapshot in self.snapshots.values_mut() {
+            if snapshot.computed_at_millis < now {
+                snapshot.heat = snapshot.heat_at(now);
+                snapshot.computed_at_millis = now;
+            }
+        }
+        self.snapshots.retain(|_, s| s.heat >= HEAT_FLOOR);
+        before - self.snapshots.len()
+    }
+
+    /// Decayed heat of an artifact at `now` (0.0 if never seen).
+    pub fn heat_of(&self, artifact_id: &ArtifactId, now: i64) -> f32 {
+        self.snapshots
+            .get(artifact_id)
+            .map_or(0.0, |s| s.heat_at(now))
+    }
+
+    /// The hottest artifacts at `now`, hottest first, with decayed heat.
+    pub fn hottest(&self, limit: usize, now: i64) -> Vec<HeatSnapshot> {
+        let mut hot: Vec<HeatSnapshot> = self
+            .snapshots
+            .values()
+            .map(|s| HeatSnapshot {
+                heat: s.heat_at(now),
+                computed_at_millis: now,
+                ..s.clone()
+            })
+            .filter(|s| s.heat >= HEAT_FLOOR)
+            .collect();
+        hot.sort_by(|a, b| {
+            b.heat
+                .total_cmp(&a.heat)
+                .then(a.artifact_id.cmp(&b.artifact_id))
+        });
+        hot.truncate(limit);
+        hot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indras_network::document::DocumentSchema;
+
+    const ME: MemberId = [1u8; 32];
+    const PEER: MemberId = [2u8; 32];
+
+    fn artifact(n: u8) -> ArtifactId {
+        ArtifactId::Blob([n; 32])
+    }
+
+    fn event(
+        author: MemberId,
+        seq: u64,
+        at: i64,
+        from: Option<u8>,
+        to: Option<u8>,
+    ) -> ChainedSwitchEvent {
+        let (from, to) = (from.map(artifact), to.map(artifact));
+        ChainedSwitchEvent::new(author, seq, at, from, to, [0u8; 32])
+    }
+
+    fn chains(events: Vec<ChainedSwitchEvent>) -> HashMap<MemberId, Vec<ChainedSwitchEvent>> {
+        let mut map: HashMap<MemberId, Vec<ChainedSwitchEvent>> = HashMap::new();
+        for e in events {
+            map.entry(e.author).or_default().push(e);
+        }
+        map
+    }
+
+    #[test]
+    fn test_refresh_matches_compute_heat() {
+        let chains = chains(vec![
+            event(ME, 0, 0, None, Some(1)),
+            event(ME, 1, 120_000, Some(1), Some(2)),
+        ]);
+        let mut doc = HeatDocument::default();
+        assert_eq!(doc.refresh(&chains, &[ME, PEER], 130_000), 2);
+
+        let logs: Vec<_> = chains.iter().map(|(a, e)| (*a, e.as_slice())).collect();
+        let expected = compute_heat(&artifact(1), &logs, &[ME, PEER], 130_000);
+        assert_eq!(doc.heat_of(&artifact(1), 130_000), expected.heat);
+        assert_eq!(doc.watermarks[&ME], 1);
+    }
+
+    #[test]
+    fn test_refresh_is_incremental() {
+        let mut events = vec![
+            event(ME, 0, 0, None, Some(1)),
+            event(ME, 1, 60_000, Some(1), Some(2)),
+        ];
+        let mut doc = HeatDocument::default();
+        doc.refresh(&chains(events.clone()), &[ME], 60_000);
+
+        // Nothing new: nothing recomputed
+        assert_eq!(doc.refresh(&chains(events.clone()), &[ME], 70_000), 0);
+
+        // A new event touching only artifact 2 and 3
+        events.push(event(ME, 2, 90_000, Some(2), Some(3)));
+        let before = doc.snapshots[&artifact(1)].clone();
+        assert_eq!(doc.refresh(&chains(events), &[ME], 90_000), 2);
+        assert_eq!(doc.snapshots[&artifact(1)], before);
+        assert!(doc.snapshots.contains_key(&artifact(3)));
+    }
+
+    #[test]
+    fn test_decay_and_floor() {
+        let mut doc = HeatDocument::default();
+        doc.snapshots.insert(
+            artifact(1),
+            HeatSnapshot {
+                artifact_id: artifact(1),
+                heat: 0.5,
+                total_dwell_ms: 60_000,
+                computed_at_millis: 0,
+            },
+        );
+        let later = doc.heat_of(&artifact(1), 300_000);
+        assert!((later - 0.5 * (-1.0f32).exp()).abs() < 1e-6);
+
+        assert_eq!(doc.apply_decay(300_000), 0);
+        assert_eq!(doc.snapshots[&artifact(1)].computed_at_millis, 300_000);
+        assert!((doc.heat_of(&artifact(1), 300_000) - later).abs() < 1e-6);
+
+        // Long after, the snapshot falls below the floor and is dropped
+        assert_eq!(doc.apply_decay(3_000_000), 1);
+        assert!(doc.snapshots.is_empty());
+    }
+
+    #[test]
+    fn test_hottest_orders_by_decayed_heat() {
+        let mut doc = HeatDocument::default();
+        for (n, heat, at) in [(1, 0.9, 0), (2, 0.6, 600_000), (3, 0.001, 600_000)] {
+            doc.snapshots.insert(
+                artifact(n),
+                HeatSnapshot {
+                    artifact_id: artifact(n),
+                    heat,
+                    total_dwell_ms: 0,
+                    computed_at_millis: at,
+                },
+            );
+        }
+        // Artifact 1 was hotter but has decayed for ten minutes
+        let hot = doc.hottest(5, 600_000);
+        let ids: Vec<_> = hot.iter().map(|s| s.artifact_id).collect();
+        assert_eq!(ids, vec![artifact(2), artifact(1)]);
+        assert_eq!(doc.hottest(1, 600_000).len(), 1);
+    }
+
+    #[test]
+    fn test_merge_keeps_newest_snapshot() {
+        let snapshot = |heat, at| HeatSnapshot {
+            artifact_id: artifact(1),
+            heat,
+            total_dwell_ms: 0,
+            computed_at_millis: at,
+        };
+        let mut local = HeatDocument::default();
+        local.snapshots.insert(artifact(1), snapshot(0.2, 10));
+        local.watermarks.insert(ME, 4);
+
+        let mut remote = HeatDocument::default();
+        remote.snapshots.insert(artifact(1), snapshot(0.7, 20));
+        remote.watermarks.insert(ME, 2);
+        remote.watermarks.insert(PEER, 3);
+
+        local.merge(remote);
+        assert_eq!(local.snapshots[&artifact(1)].heat, 0.7);
+        assert_eq!(local.watermarks[&ME], 4);
+        assert_eq!(local.watermarks[&PEER], 3);
+    }
+}
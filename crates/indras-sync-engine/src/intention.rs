@@ -14,7 +14,12 @@
 //! Intentions are CRDT-synchronized across all realm members.
 
 use indras_network::artifact::ArtifactId;
+use indras_network::calendar::IcsEvent;
 use indras_network::member::MemberId;
+use indras_network::network::RealmId;
+use indras_network::tombstone::TombstoneCompaction;
+use crate::claim_review::ClaimReview;
+use crate::proof_bundle::BundleRoot;
 use crate::proof_folder::ProofFolderId;
 
 use serde::{Deserialize, Serialize};
@@ -137,12 +142,18 @@ pub struct ServiceClaim {
     /// Proof folder reference (optional, new multi-artifact proof with narrative)
     #[serde(default)]
     pub proof_folder: Option<ProofFolderId>,
+    /// Merkle root of the proof folder's sealed bundle, if one was sealed
+    #[serde(default)]
+    pub proof_bundle_root: Option<BundleRoot>,
     /// When the claim was submitted (Unix timestamp in milliseconds)
     pub submitted_at_millis: i64,
     /// Whether the creator has verified this claim
     pub verified: bool,
     /// When the claim was verified (None if not yet verified)
     pub verified_at_millis: Option<i64>,
+    /// Reviewer assignment, review state, and feedback
+    #[serde(default)]
+    pub review: ClaimReview,
 }
 
 impl ServiceClaim {
@@ -152,9 +163,11 @@ impl ServiceClaim {
             claimant,
             proof,
             proof_folder: None,
+            proof_bundle_root: None,
             submitted_at_millis: chrono::Utc::now().timestamp_millis(),
             verified: false,
             verified_at_millis: None,
+            review: ClaimReview::default(),
         }
     }
 
@@ -164,9 +177,11 @@ impl ServiceClaim {
             claimant,
             proof: None,
             proof_folder: Some(proof_folder),
+            proof_bundle_root: None,
             submitted_at_millis: chrono::Utc::now().timestamp_millis(),
             verified: false,
             verified_at_millis: None,
+            review: ClaimReview::default(),
         }
     }
 
@@ -197,6 +212,12 @@ impl ServiceClaim {
     pub fn set_proof_folder(&mut self, folder_id: ProofFolderId) {
         self.proof_folder = Some(folder_id);
     }
+
+    /// Set the proof folder and the root of its sealed bundle.
+    pub fn set_proof_bundle(&mut self, folder_id: ProofFolderId, root: Option<BundleRoot>) {
+        self.proof_folder = Some(folder_id);
+        self.proof_bundle_root = root;
+    }
 }
 
 /// An intention - a lightweight intention or task within a realm.
@@ -255,6 +276,11 @@ pub struct Intention {
     /// Whether this intention has been deleted (tombstone for CRDT).
     #[serde(default)]
     pub deleted: bool,
+    /// When the intention was deleted (Unix timestamp in milliseconds).
+    ///
+    /// Used to decide when the tombstone can be garbage-collected.
+    #[serde(default)]
+    pub deleted_at_millis: Option<i64>,
 }
 
 impl Intention {
@@ -278,6 +304,7 @@ impl Intention {
             deadline_millis: None,
             priority: IntentionPriority::default(),
             deleted: false,
+            deleted_at_millis: None,
         }
     }
 
@@ -414,6 +441,8 @@ impl Intention {
     /// Mark this intention as deleted (tombstone).
     pub fn delete(&mut self) {
         self.deleted = true;
+        self.deleted_at_millis
+            .get_or_insert_with(|| chrono::Utc::now().timestamp_millis());
     }
 
     // === Legacy compatibility methods ===
@@ -510,11 +539,23 @@ impl indras_network::document::DocumentSchema for IntentionDocument {
             if let Some(&idx) = by_id.get(&remote_intention.id) {
                 let local = &mut self.intentions[idx];
 
-                // Merge claims: union by claimant, prefer verified over unverified.
+                // Merge claims: union by claimant, prefer verified over unverified;
+                // reviews always merge so feedback is never lost.
                 for remote_claim in &remote_intention.claims {
                     if let Some(local_claim) = local.claims.iter_mut().find(|c| c.claimant == remote_claim.claimant) {
                         if remote_claim.verified && !local_claim.verified {
+                            let review = std::mem::take(&mut local_claim.review);
                             *local_claim = remote_claim.clone();
+                            local_claim.review.merge(&review);
+                        } else {
+                            local_claim.review.merge(&remote_claim.review);
+                            // An anchored folder replaces an unanchored one
+                            if local_claim.proof_bundle_root.is_none()
+                                && remote_claim.proof_bundle_root.is_some()
+                            {
+                                local_claim.proof_folder = remote_claim.proof_folder;
+                                local_claim.proof_bundle_root = remote_claim.proof_bundle_root;
+                            }
                         }
                     } else {
                         local.claims.push(remote_claim.clone());
@@ -544,6 +585,13 @@ impl indras_network::document::DocumentSchema for IntentionDocument {
                 if remote_intention.deleted {
                     local.deleted = true;
                 }
+
+                // Deletion time: keep the earliest known.
+                match (local.deleted_at_millis, remote_intention.deleted_at_millis) {
+                    (None, Some(t)) => local.deleted_at_millis = Some(t),
+                    (Some(a), Some(b)) if b < a => local.deleted_at_millis = Some(b),
+                    _ => {}
+                }
             } else {
                 by_id.insert(remote_intention.id, self.intentions.len());
                 self.intentions.push(remote_intention);
@@ -649,7 +697,7 @@ impl IntentionDocument {
     /// Returns the deleted intention if found, or None if not found/already deleted.
     pub fn delete(&mut self, id: &IntentionId) -> Option<&Intention> {
         if let Some(intention) = self.intentions.iter_mut().find(|q| &q.id == id && !q.deleted) {
-            intention.deleted = true;
+            intention.delete();
             Some(intention)
         } else {
             None
@@ -674,6 +722,31 @@ impl IntentionDocument {
             .collect()
     }
 
+    /// Deadlines of open intentions as calendar events.
+    ///
+    /// Used with [`CalendarExporter::with_source`](indras_network::CalendarExporter::with_source)
+    /// to put quest deadlines in a realm's ICS feed.
+    pub fn deadline_events(&self, realm_id: &RealmId) -> Vec<IcsEvent> {
+        let realm_hex = hex::encode(realm_id.as_bytes());
+        self.intentions
+            .iter()
+            .filter(|q| !q.deleted && !q.is_complete())
+            .filter_map(|q| {
+                let deadline = u64::try_from(q.deadline_millis?).ok()?;
+                Some(IcsEvent {
+                    uid: format!("intention-{}@{realm_hex}", hex::encode(q.id)),
+                    summary: format!("{} due: {}", q.kind.label(), q.title),
+                    description: Some(q.description.clone()),
+                    location: None,
+                    start_millis: deadline,
+                    end_millis: None,
+                    all_day: false,
+                    stamp_millis: u64::try_from(q.created_at_millis).unwrap_or_default(),
+                })
+            })
+            .collect()
+    }
+
     /// Legacy compatibility: Get intentions claimed by a specific member.
     #[deprecated(since = "0.2.0", note = "Use intentions_by_claimant() instead")]
     pub fn intentions_by_doer(&self, doer: &MemberId) -> Vec<&Intention> {
@@ -681,6 +754,27 @@ impl IntentionDocument {
     }
 }
 
+impl TombstoneCompaction for IntentionDocument {
+    fn tombstone_count(&self) -> usize {
+        self.intentions.iter().filter(|q| q.deleted).count()
+    }
+
+    /// Tombstones from before deletion times were recorded are stamped
+    /// with the current time, so they become prunable on a later pass.
+    fn compact_tombstones(&mut self, horizon_millis: u64) -> usize {
+        let now = chrono::Utc::now().timestamp_millis();
+        let horizon = i64::try_from(horizon_millis).unwrap_or(i64::MAX);
+        let before = self.intentions.len();
+        self.intentions.retain_mut(|q| {
+            if !q.deleted {
+                return true;
+            }
+            *q.deleted_at_millis.get_or_insert(now) >= horizon
+        });
+        before - self.intentions.len()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -863,6 +957,7 @@ mod tests {
             deadline_millis: None,
             priority: IntentionPriority::default(),
             deleted: false,
+            deleted_at_millis: None,
         }
     }
 
@@ -899,9 +994,11 @@ mod tests {
             claimant: another_member_id(),
             proof: None,
             proof_folder: None,
+            proof_bundle_root: None,
             submitted_at_millis: 100,
             verified: false,
             verified_at_millis: None,
+            review: ClaimReview::default(),
         });
 
         let mut int_b = make_intention(id, test_member_id());
@@ -909,9 +1006,11 @@ mod tests {
             claimant: third_member_id(),
             proof: None,
             proof_folder: None,
+            proof_bundle_root: None,
             submitted_at_millis: 200,
             verified: false,
             verified_at_millis: None,
+            review: ClaimReview::default(),
         });
 
         let mut doc_a = IntentionDocument::new();
@@ -934,9 +1033,11 @@ mod tests {
             claimant,
             proof: None,
             proof_folder: None,
+            proof_bundle_root: None,
             submitted_at_millis: 100,
             verified: false,
             verified_at_millis: None,
+            review: ClaimReview::default(),
         });
 
         let mut int_b = make_intention(id, test_member_id());
@@ -944,9 +1045,11 @@ mod tests {
             claimant,
             proof: None,
             proof_folder: None,
+            proof_bundle_root: None,
             submitted_at_millis: 100,
             verified: true,
             verified_at_millis: Some(200),
+            review: ClaimReview::default(),
         });
 
         let mut doc_a = IntentionDocument::new();
@@ -961,6 +1064,41 @@ mod tests {
         assert_eq!(merged.claims[0].verified_at_millis, Some(200));
     }
 
+    #[test]
+    fn merge_claim_takes_bundle_root() {
+        let id = [8u8; 16];
+        let claimant = another_member_id();
+
+        let mut int_a = make_intention(id, test_member_id());
+        int_a.claims.push(ServiceClaim::with_proof_folder(claimant, [1u8; 16]));
+
+        let mut int_b = make_intention(id, test_member_id());
+        let mut anchored = ServiceClaim::new(claimant, None);
+        anchored.set_proof_bundle([2u8; 16], Some([9u8; 32]));
+        int_b.claims.push(anchored);
+
+        let mut doc_a = IntentionDocument::new();
+        let mut doc_b = IntentionDocument::new();
+        doc_a.add(int_a);
+        doc_b.add(int_b);
+
+        doc_a.merge(doc_b);
+        let claim = &doc_a.find(&id).unwrap().claims[0];
+        assert_eq!(claim.proof_folder, Some([2u8; 16]));
+        assert_eq!(claim.proof_bundle_root, Some([9u8; 32]));
+
+        // An unanchored claim never clears the root
+        let mut int_c = make_intention(id, test_member_id());
+        int_c.claims.push(ServiceClaim::with_proof_folder(claimant, [3u8; 16]));
+        let mut doc_c = IntentionDocument::new();
+        doc_c.add(int_c);
+
+        doc_a.merge(doc_c);
+        let claim = &doc_a.find(&id).unwrap().claims[0];
+        assert_eq!(claim.proof_folder, Some([2u8; 16]));
+        assert_eq!(claim.proof_bundle_root, Some([9u8; 32]));
+    }
+
     #[test]
     fn merge_completion_propagates() {
         let id = [6u8; 16];
@@ -1009,6 +1147,26 @@ mod tests {
         assert_eq!(doc_a.find(&id).unwrap().deadline_millis, Some(200));
     }
 
+    #[test]
+    fn deadline_events_cover_open_intentions_with_deadlines() {
+        let mut doc = IntentionDocument::new();
+        let mut due = make_intention([1u8; 16], test_member_id());
+        due.title = "Fix the fence".to_string();
+        due.deadline_millis = Some(1_000);
+        let mut done = make_intention([2u8; 16], test_member_id());
+        done.deadline_millis = Some(2_000);
+        done.complete().unwrap();
+        doc.add(due);
+        doc.add(done);
+        doc.add(make_intention([3u8; 16], test_member_id()));
+
+        let events = doc.deadline_events(&RealmId::new([9u8; 32]));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].start_millis, 1_000);
+        assert_eq!(events[0].summary, "Intention due: Fix the fence");
+        assert!(events[0].uid.starts_with(&format!("intention-{}@", hex::encode([1u8; 16]))));
+    }
+
     #[test]
     fn merge_priority_max_wins() {
         let id = [8u8; 16];
@@ -1065,4 +1223,35 @@ mod tests {
         // find() should exclude deleted
         assert!(doc_a.find(&id).is_none());
     }
+
+    #[test]
+    fn compact_tombstones_prunes_only_old_deletes() {
+        let mut doc = IntentionDocument::new();
+        let mut old = make_intention([1u8; 16], test_member_id());
+        old.deleted = true;
+        old.deleted_at_millis = Some(2_000);
+        let mut recent = make_intention([2u8; 16], test_member_id());
+        recent.deleted = true;
+        recent.deleted_at_millis = Some(9_000);
+        doc.add(old);
+        doc.add(recent);
+        doc.add(make_intention([3u8; 16], test_member_id()));
+
+        assert_eq!(doc.tombstone_count(), 2);
+        assert_eq!(doc.compact_tombstones(5_000), 1);
+        assert_eq!(doc.intentions.len(), 2);
+        assert_eq!(doc.intention_count(), 1);
+    }
+
+    #[test]
+    fn compact_tombstones_stamps_legacy_deletes() {
+        let mut doc = IntentionDocument::new();
+        let mut legacy = make_intention([1u8; 16], test_member_id());
+        legacy.deleted = true;
+        doc.add(legacy);
+
+        // No deletion time recorded yet, so nothing is pruned this pass
+        assert_eq!(doc.compact_tombstones(5_000), 0);
+        assert!(doc.intentions[0].deleted_at_millis.is_some());
+    }
 }
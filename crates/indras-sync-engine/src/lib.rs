@@ -26,6 +26,7 @@
 
 // Domain modules (moved from indras-network)
 pub mod intention;
+pub mod claim_review;
 pub mod note;
 pub mod blessing;
 pub mod attention;
@@ -39,6 +40,7 @@ pub mod token_valuation;
 pub mod humanness;
 pub mod sentiment;
 pub mod proof_folder;
+pub mod proof_bundle;
 pub mod story_auth;
 pub mod steward_recovery;
 pub mod share_delivery;
@@ -53,8 +55,16 @@ pub mod file_shard;
 pub mod file_backup_index;
 pub mod rehearsal;
 pub mod bioregion_catalog;
+pub mod bioregion_directory;
 pub mod profile_identity;
 pub mod homepage_profile;
+pub mod recommendation;
+pub mod session_recording;
+pub mod exchange;
+pub mod fulfillment;
+pub mod story;
+pub mod gallery;
+pub mod heat;
 
 // SyncContent extension type
 pub mod content;
@@ -68,6 +78,11 @@ pub mod realm_attention;
 pub mod realm_tokens;
 pub mod realm_humanness;
 pub mod realm_proof_folders;
+pub mod realm_exchange;
+pub mod realm_requests;
+pub mod realm_stories;
+pub mod realm_galleries;
+pub mod realm_heat;
 
 // Extension traits on HomeRealm
 pub mod home_realm_intentions;
@@ -102,6 +117,9 @@ pub mod prelude;
 
 // Re-export main types at crate root
 pub use intention::{Intention, IntentionKind, ServiceClaim, IntentionDocument, IntentionError, IntentionId, IntentionPriority};
+pub use claim_review::{
+    ClaimReview, ReviewComment, ReviewEvent, ReviewState, ReviewVerdict, rotate_reviewer,
+};
 pub use note::{Note, NoteDocument, NoteId};
 pub use blessing::{Blessing, BlessingDocument, BlessingError, BlessingId, ClaimId};
 pub use attention::{
@@ -118,7 +136,10 @@ pub use certificate::CertificateDocument;
 pub use token_of_gratitude::{
     TokenError, TokenEvent, TokenOfGratitude, TokenOfGratitudeDocument, TokenOfGratitudeId,
 };
-pub use token_valuation::{SubjectiveTokenValue, STEWARD_CHAIN_DECAY, subjective_value};
+pub use token_valuation::{
+    FlowPoint, SubjectiveTokenValue, ValueSnapshot, STEWARD_CHAIN_DECAY, gratitude_flow,
+    subjective_value,
+};
 pub use humanness::{
     BioregionalLevel, Delegation, DelegationError, HumannessAttestation, HumannessDocument,
     HumannessEvent, humanness_freshness, validate_delegation_chain, FRESHNESS_DECAY_RATE,
@@ -128,6 +149,9 @@ pub use proof_folder::{
     ProofFolder, ProofFolderArtifact, ProofFolderDocument, ProofFolderError, ProofFolderId,
     ProofFolderStatus,
 };
+pub use proof_bundle::{
+    BundleRoot, BundleVerification, ProofBundle, ProofBundleError, ProofStep, verify_inclusion,
+};
 pub use sentiment::{
     RelayedSentiment, SentimentRelayDocument, SentimentView, DEFAULT_RELAY_ATTENUATION,
 };
@@ -135,7 +159,34 @@ pub use rehearsal::RehearsalState;
 pub use story_auth::{AuthResult, StoryAuth};
 pub use profile_identity::ProfileIdentityDocument;
 pub use homepage_profile::{HomepageProfileDocument, HomepageField};
-pub use content::SyncContent;
+pub use content::{SyncContent, SyncDigestClassifier};
+pub use bioregion_directory::{
+    BioregionDirectoryDocument, BioregionHierarchy, BioregionNode, DirectoryError, RealmListing,
+    BIOREGION_DIRECTORY_DOC,
+};
+pub use recommendation::{Recommendation, RecommendationWeights, ScoreBreakdown};
+pub use session_recording::{
+    Redaction, RecordingError, SessionEvent, SessionRecorder, SessionSource, ViewerFormat,
+    record_session,
+};
+pub use exchange::{
+    ExchangeBoardDocument, ExchangeError, ExchangeListing, ExchangeRecord, ListingId, ListingSide,
+    MatchProposal, NegotiationAction, NegotiationDocument, NegotiationMessage, NegotiationState,
+    EXCHANGE_BOARD_DOC,
+};
+pub use fulfillment::{
+    ArtifactRequest, OfferContent, OfferId, RequestAcceptance, RequestBoardDocument, RequestError,
+    RequestId, RequestOffer, RequestStatus, REQUEST_BOARD_DOC,
+};
+pub use story::{
+    position_between, LeafId, LeafKind, StoryDocument, StoryError, StoryId, StoryIndexDocument,
+    StoryLeaf, StorySummary, STORY_INDEX_DOC,
+};
+pub use gallery::{
+    GalleryDocument, GalleryError, GalleryId, GalleryIndexDocument, GalleryItem, GalleryMedia,
+    GallerySummary, GALLERY_INDEX_DOC,
+};
+pub use heat::{HeatDocument, HeatSnapshot, HEAT_DECAY_MILLIS, HEAT_DOC, HEAT_FLOOR};
 pub use sync_engine::SyncEngine;
 
 // Explicit DocumentSchema impls for indras-sync-engine types (default merge = replacement).
@@ -172,7 +223,7 @@ impl indras_network::document::DocumentSchema for FraudEvidenceDocument {
 }
 
 // Re-export extension traits
-pub use realm_intentions::RealmIntentions;
+pub use realm_intentions::{CommandOutcome, RealmIntentions};
 pub use realm_notes::RealmNotes;
 pub use realm_chat::RealmChat;
 pub use realm_blessings::RealmBlessings;
@@ -180,6 +231,11 @@ pub use realm_attention::{RealmAttention, WeightedIntentionAttention};
 pub use realm_tokens::RealmTokens;
 pub use realm_humanness::RealmHumanness;
 pub use realm_proof_folders::RealmProofFolders;
+pub use realm_exchange::RealmExchange;
+pub use realm_requests::RealmRequests;
+pub use realm_stories::RealmStories;
+pub use realm_galleries::RealmGalleries;
+pub use realm_heat::RealmHeat;
 pub use home_realm_intentions::HomeRealmIntentions;
 pub use home_realm_notes::HomeRealmNotes;
 pub use vault::Vault as VaultSync;
@@ -6,8 +6,9 @@
 
 pub use crate::{
     // Extension traits on Realm
-    RealmAttention, RealmBlessings, RealmChat, RealmHumanness, RealmNotes, RealmProofFolders,
-    RealmIntentions, RealmTokens,
+    RealmAttention, RealmBlessings, RealmChat, RealmExchange, RealmGalleries, RealmHeat,
+    RealmHumanness, RealmNotes, RealmProofFolders, RealmIntentions, RealmRequests, RealmStories,
+    RealmTokens,
     // Extension traits on HomeRealm
     HomeRealmIntentions, HomeRealmNotes,
     // SyncEngine struct
@@ -19,5 +20,12 @@ pub use crate::{
     Note, NoteDocument,
     Blessing, BlessingDocument, ClaimId, TokenOfGratitude, TokenOfGratitudeDocument,
     ProofFolder, ProofFolderArtifact, ProofFolderDocument, ProofFolderId,
+    BundleVerification, ProofBundle,
     HumannessDocument, SentimentView, StoryAuth, AuthResult, RehearsalState,
+    ExchangeBoardDocument, ExchangeListing, ExchangeRecord, ListingSide, MatchProposal,
+    NegotiationDocument,
+    ArtifactRequest, OfferContent, RequestBoardDocument, RequestStatus,
+    LeafKind, StoryDocument, StoryId, StoryIndexDocument, StoryLeaf,
+    GalleryDocument, GalleryId, GalleryIndexDocument, GalleryItem, GalleryMedia,
+    HeatDocument, HeatSnapshot,
 };
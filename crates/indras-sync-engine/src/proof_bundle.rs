@@ -0,0 +1,387 @@
+//! Proof Bundle - content-hash anchoring for quest claim proofs.
+//!
+//! When a claimant submits a proof folder they can seal a bundle: the
+//! selected artifacts are hashed into a Merkle root. The proof folder keeps
+//! the bundle (root plus the ordered artifact list) and the quest claim
+//! stores the root, so reviewers and later verifiers can confirm that the
+//! artifacts they look at are the ones that were reviewed.
+//!
+//! Each leaf commits to an artifact's content hash, size and name. Only
+//! content-addressed artifacts (`ArtifactId::Blob`) can be bundled; a
+//! `Doc` artifact has a random ID and nothing to anchor.
+//!
+//! Verification has two parts:
+//! - the folder's listing must still hash to the claim's root
+//!   ([`ProofBundle::recompute_root`]);
+//! - each artifact's bytes must still hash to its ID
+//!   ([`content_matches`]), checked against local blobs by
+//!   `RealmProofFolders::verify_claim_bundle`.
+
+use crate::proof_folder::{ProofFolder, ProofFolderArtifact};
+use indras_network::artifact::ArtifactId;
+use serde::{Deserialize, Serialize};
+
+/// BLAKE3 key-derivation context for bundle leaves.
+const LEAF_CONTEXT: &str = "indras proof bundle leaf v1";
+
+/// BLAKE3 key-derivation context for interior Merkle nodes.
+const NODE_CONTEXT: &str = "indras proof bundle node v1";
+
+/// Merkle root over a claim's bundled artifacts.
+pub type BundleRoot = [u8; 32];
+
+/// A sealed set of proof artifacts.
+///
+/// # Example
+///
+/// ```ignore
+/// // Bundle the before/after photos and submit the folder
+/// let claim_index = realm
+///     .submit_proof_bundle(folder_id, vec![before_id, after_id])
+///     .await?;
+///
+/// // Later, anyone holding the blobs can check nothing was swapped
+/// let check = realm.verify_claim_bundle(intention_id, claimant).await?;
+/// assert!(check.is_intact());
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProofBundle {
+    /// Merkle root over the bundled artifacts, in order.
+    pub root: BundleRoot,
+    /// The bundled artifacts, in leaf order.
+    pub artifacts: Vec<ArtifactId>,
+    /// When the bundle was sealed (Unix timestamp in milliseconds).
+    pub sealed_at_millis: i64,
+}
+
+impl ProofBundle {
+    /// Seal the `selected` artifacts of a folder into a bundle.
+    ///
+    /// Artifacts keep the order they are selected in.
+    pub fn seal(
+        folder_artifacts: &[ProofFolderArtifact],
+        selected: &[ArtifactId],
+    ) -> Result<Self, ProofBundleError> {
+        let leaves = leaves_for(folder_artifacts, selected)?;
+        let mut seen = std::collections::BTreeSet::new();
+        if !selected.iter().all(|id| seen.insert(*id)) {
+            return Err(ProofBundleError::Duplicate);
+        }
+        Ok(Self {
+            root: merkle_root(&leaves),
+            artifacts: selected.to_vec(),
+            sealed_at_millis: chrono::Utc::now().timestamp_millis(),
+        })
+    }
+
+    /// Recompute the root from a folder's current artifact listing.
+    ///
+    /// Fails if a bundled artifact is no longer in the folder.
+    pub fn recompute_root(&self, folder: &ProofFolder) -> Result<BundleRoot, ProofBundleError> {
+        let leaves = leaves_for(&folder.artifacts, &self.artifacts)?;
+        Ok(merkle_root(&leaves))
+    }
+
+    /// Inclusion proof for one bundled artifact, checkable with
+    /// [`verify_inclusion`] against the root alone.
+    pub fn inclusion_proof(
+        &self,
+        folder: &ProofFolder,
+        artifact_id: &ArtifactId,
+    ) -> Result<Vec<ProofStep>, ProofBundleError> {
+        let index = self
+            .artifacts
+            .iter()
+            .position(|a| a == artifact_id)
+            .ok_or(ProofBundleError::NotInFolder(*artifact_id))?;
+        let leaves = leaves_for(&folder.artifacts, &self.artifacts)?;
+        Ok(inclusion_proof(&leaves, index))
+    }
+}
+
+/// Errors that can occur while sealing or checking a bundle.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProofBundleError {
+    /// No artifacts were selected.
+    Empty,
+    /// An artifact was selected more than once.
+    Duplicate,
+    /// The artifact is not content-addressed.
+    NotContentAddressed(ArtifactId),
+    /// The artifact is not in the proof folder.
+    NotInFolder(ArtifactId),
+}
+
+impl std::fmt::Display for ProofBundleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProofBundleError::Empty => write!(f, "Proof bundle needs at least one artifact"),
+            ProofBundleError::Duplicate => write!(f, "Artifact selected more than once"),
+            ProofBundleError::NotContentAddressed(id) => {
+                write!(f, "Artifact {:?} is not content-addressed", id)
+            }
+            ProofBundleError::NotInFolder(id) => {
+                write!(f, "Artifact {:?} is not in the proof folder", id)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProofBundleError {}
+
+/// One step of an inclusion proof: the sibling hash at that level.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProofStep {
+    /// Hash of the sibling node.
+    pub sibling: [u8; 32],
+    /// Whether the sibling is the left child.
+    pub sibling_is_left: bool,
+}
+
+/// Outcome of checking a claim's bundle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BundleVerification {
+    /// The claim has no bundle to check.
+    NoBundle,
+    /// The proof folder no longer matches the root stored in the claim.
+    RootMismatch {
+        /// Root stored in the claim.
+        expected: BundleRoot,
+        /// Root of the folder's listing, or `None` if a bundled artifact
+        /// was removed from the folder.
+        actual: Option<BundleRoot>,
+    },
+    /// The listing matches; per-artifact content results.
+    Checked {
+        /// Artifacts whose local bytes hash to their ID.
+        verified: Vec<ArtifactId>,
+        /// Artifacts whose local bytes do not.
+        tampered: Vec<ArtifactId>,
+        /// Artifacts not available locally.
+        unavailable: Vec<ArtifactId>,
+    },
+}
+
+impl BundleVerification {
+    /// Whether the listing matches and every artifact was checked and
+    /// found unchanged.
+    pub fn is_intact(&self) -> bool {
+        matches!(
+            self,
+            BundleVerification::Checked { tampered, unavailable, .. }
+                if tampered.is_empty() && unavailable.is_empty()
+        )
+    }
+}
+
+/// Leaf hash for a folder artifact, or `None` if it is not a blob.
+pub fn leaf_hash(artifact: &ProofFolderArtifact) -> Option<[u8; 32]> {
+    let ArtifactId::Blob(content_hash) = artifact.artifact_id else {
+        return None;
+    };
+    let mut hasher = blake3::Hasher::new_derive_key(LEAF_CONTEXT);
+    hasher.update(&content_hash);
+    hasher.update(&artifact.size.to_le_bytes());
+    hasher.update(artifact.name.as_bytes());
+    Some(*hasher.finalize().as_bytes())
+}
+
+/// Whether `data` is the content of `artifact`: its BLAKE3 hash is the
+/// artifact ID and its length the recorded size.
+pub fn content_matches(artifact: &ProofFolderArtifact, data: &[u8]) -> bool {
+    let ArtifactId::Blob(hash) = artifact.artifact_id else {
+        return false;
+    };
+    data.len() as u64 == artifact.size && hash == *blake3::hash(data).as_bytes()
+}
+
+/// Merkle root of `leaves`; an unpaired node moves up a level unchanged.
+pub fn merkle_root(leaves: &[[u8; 32]]) -> BundleRoot {
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level.first().copied().unwrap_or_default()
+}
+
+/// Inclusion proof for the leaf at `index`.
+pub fn inclusion_proof(leaves: &[[u8; 32]], mut index: usize) -> Vec<ProofStep> {
+    let mut proof = Vec::new();
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        let sibling = index ^ 1;
+        if let Some(hash) = level.get(sibling) {
+            proof.push(ProofStep {
+                sibling: *hash,
+                sibling_is_left: sibling < index,
+            });
+        }
+        level = next_level(&level);
+        index /= 2;
+    }
+    proof
+}
+
+/// Check that `leaf` is under `root` by following `proof`.
+pub fn verify_inclusion(root: &BundleRoot, leaf: &[u8; 32], proof: &[ProofStep]) -> bool {
+    let computed = proof.iter().fold(*leaf, |hash, step| {
+        if step.sibling_is_left {
+            node_hash(&step.sibling, &hash)
+        } else {
+            node_hash(&hash, &step.sibling)
+        }
+    });
+    computed == *root
+}
+
+fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => node_hash(left, right),
+            [single] => *single,
+            _ => unreachable!("chunks(2) yields one or two items"),
+        })
+        .collect()
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new_derive_key(NODE_CONTEXT);
+    hasher.update(left);
+    hasher.update(right);
+    *hasher.finalize().as_bytes()
+}
+
+fn leaves_for(
+    folder_artifacts: &[ProofFolderArtifact],
+    selected: &[ArtifactId],
+) -> Result<Vec<[u8; 32]>, ProofBundleError> {
+    if selected.is_empty() {
+        return Err(ProofBundleError::Empty);
+    }
+    selected
+        .iter()
+        .map(|id| {
+            let artifact = folder_artifacts
+                .iter()
+                .find(|a| &a.artifact_id == id)
+                .ok_or(ProofBundleError::NotInFolder(*id))?;
+            leaf_hash(artifact).ok_or(ProofBundleError::NotContentAddressed(*id))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blob(data: &[u8], name: &str) -> ProofFolderArtifact {
+        ProofFolderArtifact::new(
+            ArtifactId::Blob(*blake3::hash(data).as_bytes()),
+            name,
+            data.len() as u64,
+            None,
+        )
+    }
+
+    fn folder_with(artifacts: Vec<ProofFolderArtifact>) -> ProofFolder {
+        let mut folder = ProofFolder::new([1u8; 16], [1u8; 32]);
+        for artifact in artifacts {
+            folder.add_artifact(artifact).unwrap();
+        }
+        folder
+    }
+
+    #[test]
+    fn test_seal_and_recompute() {
+        let (a, b, c) = (
+            blob(b"before", "a.jpg"),
+            blob(b"after", "b.jpg"),
+            blob(b"log", "c.txt"),
+        );
+        let folder = folder_with(vec![a.clone(), b.clone(), c.clone()]);
+
+        let bundle = ProofBundle::seal(&folder.artifacts, &[a.artifact_id, c.artifact_id]).unwrap();
+        assert_eq!(bundle.recompute_root(&folder), Ok(bundle.root));
+
+        // Order is part of the commitment
+        let reordered =
+            ProofBundle::seal(&folder.artifacts, &[c.artifact_id, a.artifact_id]).unwrap();
+        assert_ne!(bundle.root, reordered.root);
+
+        // Unselected artifacts don't affect the root
+        let smaller = folder_with(vec![a, c]);
+        assert_eq!(bundle.recompute_root(&smaller), Ok(bundle.root));
+    }
+
+    #[test]
+    fn test_swapped_artifact_changes_root() {
+        let (a, b) = (blob(b"before", "a.jpg"), blob(b"after", "b.jpg"));
+        let folder = folder_with(vec![a.clone(), b.clone()]);
+        let bundle = ProofBundle::seal(&folder.artifacts, &[a.artifact_id, b.artifact_id]).unwrap();
+
+        // Same name and position, different content
+        let swapped = blob(b"after, retouched", "b.jpg");
+        let mut tampered = folder_with(vec![a, swapped]);
+        assert_eq!(
+            bundle.recompute_root(&tampered),
+            Err(ProofBundleError::NotInFolder(b.artifact_id))
+        );
+
+        // Same ID, renamed entry
+        tampered.artifacts[1] = ProofFolderArtifact {
+            name: "c.jpg".into(),
+            ..b.clone()
+        };
+        assert_ne!(bundle.recompute_root(&tampered).unwrap(), bundle.root);
+
+        assert!(content_matches(&b, b"after"));
+        assert!(!content_matches(&b, b"after, retouched"));
+    }
+
+    #[test]
+    fn test_seal_rejects_bad_selection() {
+        let a = blob(b"before", "a.jpg");
+        let doc = ProofFolderArtifact::new(ArtifactId::Doc([9u8; 32]), "notes", 0, None);
+        let folder = folder_with(vec![a.clone(), doc.clone()]);
+
+        assert_eq!(
+            ProofBundle::seal(&folder.artifacts, &[]),
+            Err(ProofBundleError::Empty)
+        );
+        assert_eq!(
+            ProofBundle::seal(&folder.artifacts, &[doc.artifact_id]),
+            Err(ProofBundleError::NotContentAddressed(doc.artifact_id))
+        );
+        assert_eq!(
+            ProofBundle::seal(&folder.artifacts, &[a.artifact_id, a.artifact_id]),
+            Err(ProofBundleError::Duplicate)
+        );
+        let missing = ArtifactId::Blob([7u8; 32]);
+        assert_eq!(
+            ProofBundle::seal(&folder.artifacts, &[missing]),
+            Err(ProofBundleError::NotInFolder(missing))
+        );
+    }
+
+    #[test]
+    fn test_inclusion_proofs() {
+        let artifacts: Vec<_> = (0..5u8).map(|i| blob(&[i], &format!("{i}.bin"))).collect();
+        let ids: Vec<_> = artifacts.iter().map(|a| a.artifact_id).collect();
+        let folder = folder_with(artifacts.clone());
+        let bundle = ProofBundle::seal(&folder.artifacts, &ids).unwrap();
+
+        for artifact in &artifacts {
+            let proof = bundle
+                .inclusion_proof(&folder, &artifact.artifact_id)
+                .unwrap();
+            let leaf = leaf_hash(artifact).unwrap();
+            assert!(verify_inclusion(&bundle.root, &leaf, &proof));
+            assert!(!verify_inclusion(&[0u8; 32], &leaf, &proof));
+        }
+
+        let outsider = leaf_hash(&blob(b"outsider", "x.bin")).unwrap();
+        let proof = bundle.inclusion_proof(&folder, &ids[0]).unwrap();
+        assert!(!verify_inclusion(&bundle.root, &outsider, &proof));
+    }
+}
@@ -11,10 +11,14 @@
 //!
 //! A single claimant can submit multiple proof folders over the life of a quest,
 //! documenting different sub-parts of the work.
+//!
+//! Before submitting, a claimant can seal selected artifacts into a
+//! [`ProofBundle`] so the claim is anchored to their content hashes.
 
 use indras_network::artifact::ArtifactId;
 use indras_network::member::MemberId;
 use crate::intention::IntentionId;
+use crate::proof_bundle::ProofBundle;
 use serde::{Deserialize, Serialize};
 
 /// Unique identifier for a proof folder (16 bytes).
@@ -167,6 +171,9 @@ pub struct ProofFolder {
     pub created_at_millis: i64,
     /// When the folder was submitted (None if still draft).
     pub submitted_at_millis: Option<i64>,
+    /// Sealed bundle of selected artifacts, if any.
+    #[serde(default)]
+    pub bundle: Option<ProofBundle>,
 }
 
 impl ProofFolder {
@@ -181,6 +188,7 @@ impl ProofFolder {
             status: ProofFolderStatus::Draft,
             created_at_millis: chrono::Utc::now().timestamp_millis(),
             submitted_at_millis: None,
+            bundle: None,
         }
     }
 
@@ -231,6 +239,17 @@ impl ProofFolder {
         Ok(())
     }
 
+    /// Attach a sealed bundle, replacing any earlier one.
+    ///
+    /// Returns an error if the folder is not in draft status.
+    pub fn set_bundle(&mut self, bundle: ProofBundle) -> Result<(), ProofFolderError> {
+        if !self.is_draft() {
+            return Err(ProofFolderError::NotDraft);
+        }
+        self.bundle = Some(bundle);
+        Ok(())
+    }
+
     /// Submit this folder for review.
     ///
     /// Changes status to Submitted. This action is irreversible.
@@ -300,6 +319,8 @@ impl std::error::Error for ProofFolderError {}
 ///
 /// - Proof folders are identified by their unique `ProofFolderId`
 /// - Merge strategy: set-union by folder ID (no data loss on concurrent edits)
+/// - A submitted folder replaces a local draft of the same ID, so the final
+///   version (and its bundle) reaches every peer
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ProofFolderDocument {
     /// All proof folders in this realm.
@@ -308,11 +329,14 @@ pub struct ProofFolderDocument {
 
 impl indras_network::document::DocumentSchema for ProofFolderDocument {
     fn merge(&mut self, remote: Self) {
-        let existing: std::collections::HashSet<ProofFolderId> =
-            self.folders.iter().map(|f| f.id).collect();
         for folder in remote.folders {
-            if !existing.contains(&folder.id) {
-                self.folders.push(folder);
+            match self.find_mut(&folder.id) {
+                Some(local) => {
+                    if local.is_draft() && folder.is_submitted() {
+                        *local = folder;
+                    }
+                }
+                None => self.folders.push(folder),
             }
         }
     }
@@ -558,6 +582,31 @@ mod tests {
         assert_eq!(doc.submitted_folders_for_intention(&test_intention_id()).len(), 1);
     }
 
+    #[test]
+    fn test_proof_folder_merge_prefers_submitted() {
+        use indras_network::document::DocumentSchema;
+
+        let draft = ProofFolder::new(test_intention_id(), test_member_id());
+        let mut submitted = draft.clone();
+        submitted.set_narrative("Done").unwrap();
+        submitted.submit().unwrap();
+
+        let mut local = ProofFolderDocument::new();
+        local.add(draft.clone());
+        let mut remote = ProofFolderDocument::new();
+        remote.add(submitted.clone());
+
+        local.merge(remote.clone());
+        assert_eq!(local.find(&draft.id), Some(&submitted));
+
+        // A stale draft never overwrites the submitted folder
+        let mut stale = ProofFolderDocument::new();
+        stale.add(draft.clone());
+        remote.merge(stale);
+        assert_eq!(remote.find(&draft.id), Some(&submitted));
+        assert_eq!(remote.folders.len(), 1);
+    }
+
     #[test]
     fn test_generate_proof_folder_id_uniqueness() {
         let id1 = generate_proof_folder_id();
@@ -114,7 +114,8 @@ impl RealmChat for Realm {
             content,
             tick,
             EditableMessageType::Text,
-        );
+        )
+        .signed_by(self.node().pq_identity());
 
         let doc = self.chat_document().await?;
         doc.update(|d| {
@@ -188,7 +189,8 @@ impl RealmChat for Realm {
             tick,
             EditableMessageType::Text,
             reply_to.to_string(),
-        );
+        )
+        .signed_by(self.node().pq_identity());
 
         let doc = self.chat_document().await?;
         doc.update(|d| {
@@ -0,0 +1,147 @@
+//! Extension trait adding exchange methods to Realm.
+//!
+//! Board methods act on the realm's shared exchange board. Negotiation
+//! methods are meant for the DM realm between the two parties.
+
+use crate::exchange::{
+    negotiation_doc_key, ExchangeBoardDocument, ExchangeListing, ExchangeRecord, ListingId,
+    MatchProposal, NegotiationDocument, EXCHANGE_BOARD_DOC,
+};
+use indras_network::document::Document;
+use indras_network::error::{IndraError, Result};
+use indras_network::member::MemberId;
+use indras_network::Realm;
+
+/// Exchange extension trait for Realm.
+pub trait RealmExchange {
+    /// Get the exchange board document for this realm.
+    async fn exchange_board(&self) -> Result<Document<ExchangeBoardDocument>>;
+
+    /// Publish a listing to this realm's board.
+    async fn publish_listing(&self, listing: ExchangeListing) -> Result<ListingId>;
+
+    /// Withdraw one of the caller's listings from this realm's board.
+    async fn withdraw_listing(&self, listing_id: ListingId, caller: MemberId) -> Result<()>;
+
+    /// Get the negotiation document for a need and an offer.
+    async fn exchange_negotiation(
+        &self,
+        need: &ListingId,
+        offer: &ListingId,
+    ) -> Result<Document<NegotiationDocument>>;
+
+    /// Open a negotiation for a match and propose the first terms.
+    async fn propose_exchange_terms(
+        &self,
+        proposal: &MatchProposal,
+        from: MemberId,
+        terms: impl Into<String> + Send,
+    ) -> Result<()>;
+
+    /// Accept the standing terms, or decline the negotiation.
+    async fn respond_to_exchange(
+        &self,
+        need: &ListingId,
+        offer: &ListingId,
+        from: MemberId,
+        accept: bool,
+    ) -> Result<()>;
+
+    /// Confirm an agreed exchange. Returns the record, final once both
+    /// parties confirm.
+    async fn confirm_exchange(
+        &self,
+        need: &ListingId,
+        offer: &ListingId,
+        from: MemberId,
+    ) -> Result<ExchangeRecord>;
+}
+
+impl RealmExchange for Realm {
+    async fn exchange_board(&self) -> Result<Document<ExchangeBoardDocument>> {
+        self.document::<ExchangeBoardDocument>(EXCHANGE_BOARD_DOC)
+            .await
+    }
+
+    async fn publish_listing(&self, listing: ExchangeListing) -> Result<ListingId> {
+        let listing_id = listing.id;
+        let doc = self.exchange_board().await?;
+        doc.update(|d| d.add(listing)).await?;
+        Ok(listing_id)
+    }
+
+    async fn withdraw_listing(&self, listing_id: ListingId, caller: MemberId) -> Result<()> {
+        let doc = self.exchange_board().await?;
+        doc.try_update(|d| {
+            d.withdraw(&listing_id, caller)
+                .map_err(|e| IndraError::InvalidOperation(e.to_string()))
+        })
+        .await
+    }
+
+    async fn exchange_negotiation(
+        &self,
+        need: &ListingId,
+        offer: &ListingId,
+    ) -> Result<Document<NegotiationDocument>> {
+        self.document::<NegotiationDocument>(&negotiation_doc_key(need, offer))
+            .await
+    }
+
+    async fn propose_exchange_terms(
+        &self,
+        proposal: &MatchProposal,
+        from: MemberId,
+        terms: impl Into<String> + Send,
+    ) -> Result<()> {
+        let terms = terms.into();
+        let now = chrono::Utc::now().timestamp_millis();
+        let doc = self
+            .exchange_negotiation(&proposal.need.id, &proposal.offer.id)
+            .await?;
+        doc.try_update(|d| {
+            if d.messages.is_empty() && d.record.is_none() {
+                *d = NegotiationDocument::for_match(proposal);
+            }
+            d.propose(from, terms, now)
+                .map_err(|e| IndraError::InvalidOperation(e.to_string()))
+        })
+        .await
+    }
+
+    async fn respond_to_exchange(
+        &self,
+        need: &ListingId,
+        offer: &ListingId,
+        from: MemberId,
+        accept: bool,
+    ) -> Result<()> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let doc = self.exchange_negotiation(need, offer).await?;
+        doc.try_update(|d| {
+            let result = if accept {
+                d.accept(from, now)
+            } else {
+                d.decline(from, now)
+            };
+            result.map_err(|e| IndraError::InvalidOperation(e.to_string()))
+        })
+        .await
+    }
+
+    async fn confirm_exchange(
+        &self,
+        need: &ListingId,
+        offer: &ListingId,
+        from: MemberId,
+    ) -> Result<ExchangeRecord> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let doc = self.exchange_negotiation(need, offer).await?;
+        doc.try_update(|d| {
+            d.confirm(from, now)
+                .cloned()
+                .map_err(|e| IndraError::InvalidOperation(e.to_string()))
+        })
+        .await
+    }
+}
@@ -0,0 +1,188 @@
+//! Extension trait adding gallery methods to Realm.
+
+use crate::gallery::{
+    gallery_doc_key, GalleryDocument, GalleryId, GalleryIndexDocument, GalleryMedia,
+    GallerySummary, GALLERY_INDEX_DOC,
+};
+use indras_network::artifact::ArtifactId;
+use indras_network::document::Document;
+use indras_network::error::{IndraError, Result};
+use indras_network::member::MemberId;
+use indras_network::Realm;
+
+/// Gallery extension trait for Realm.
+pub trait RealmGalleries {
+    /// Get the gallery index document for this realm.
+    async fn galleries(&self) -> Result<Document<GalleryIndexDocument>>;
+
+    /// Get a gallery's document.
+    async fn gallery(&self, gallery_id: &GalleryId) -> Result<Document<GalleryDocument>>;
+
+    /// Create a gallery holding `media`, in order, and list it in the
+    /// realm's index.
+    async fn create_gallery(
+        &self,
+        title: impl Into<String> + Send,
+        creator: MemberId,
+        media: Vec<GalleryMedia>,
+    ) -> Result<GalleryId>;
+
+    /// Add media at the end of a gallery.
+    async fn add_to_gallery(
+        &self,
+        gallery_id: &GalleryId,
+        media: GalleryMedia,
+        added_by: MemberId,
+        caption: Option<String>,
+    ) -> Result<()>;
+
+    /// Move an item to just after `after`, or to the front when `None`.
+    async fn move_gallery_item(
+        &self,
+        gallery_id: &GalleryId,
+        artifact_id: ArtifactId,
+        after: Option<ArtifactId>,
+    ) -> Result<()>;
+
+    /// Set or clear an item's caption.
+    async fn set_gallery_caption(
+        &self,
+        gallery_id: &GalleryId,
+        artifact_id: ArtifactId,
+        caption: Option<String>,
+        caller: MemberId,
+    ) -> Result<()>;
+
+    /// Choose or clear a gallery's cover image.
+    async fn set_gallery_cover(
+        &self,
+        gallery_id: &GalleryId,
+        artifact_id: Option<ArtifactId>,
+        caller: MemberId,
+    ) -> Result<()>;
+
+    /// Remove an item from a gallery.
+    async fn remove_from_gallery(
+        &self,
+        gallery_id: &GalleryId,
+        artifact_id: ArtifactId,
+        caller: MemberId,
+    ) -> Result<()>;
+}
+
+impl RealmGalleries for Realm {
+    async fn galleries(&self) -> Result<Document<GalleryIndexDocument>> {
+        self.document::<GalleryIndexDocument>(GALLERY_INDEX_DOC)
+            .await
+    }
+
+    async fn gallery(&self, gallery_id: &GalleryId) -> Result<Document<GalleryDocument>> {
+        self.document::<GalleryDocument>(&gallery_doc_key(gallery_id))
+            .await
+    }
+
+    async fn create_gallery(
+        &self,
+        title: impl Into<String> + Send,
+        creator: MemberId,
+        media: Vec<GalleryMedia>,
+    ) -> Result<GalleryId> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut gallery = GalleryDocument::new(title, creator, now);
+        for item in media {
+            // Repeated files in one batch collapse into a single item
+            let _ = gallery.add(item, creator, None, now);
+        }
+        let summary = GallerySummary {
+            id: gallery.id,
+            title: gallery.title.clone(),
+            creator,
+            created_at_millis: now,
+        };
+        let gallery_id = gallery.id;
+
+        let doc = self.gallery(&gallery_id).await?;
+        doc.update(|d| *d = gallery).await?;
+
+        let index = self.galleries().await?;
+        index.update(|d| d.add(summary)).await?;
+
+        Ok(gallery_id)
+    }
+
+    async fn add_to_gallery(
+        &self,
+        gallery_id: &GalleryId,
+        media: GalleryMedia,
+        added_by: MemberId,
+        caption: Option<String>,
+    ) -> Result<()> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let doc = self.gallery(gallery_id).await?;
+        doc.try_update(|d| {
+            d.add(media, added_by, caption, now)
+                .map_err(|e| IndraError::InvalidOperation(e.to_string()))
+        })
+        .await
+    }
+
+    async fn move_gallery_item(
+        &self,
+        gallery_id: &GalleryId,
+        artifact_id: ArtifactId,
+        after: Option<ArtifactId>,
+    ) -> Result<()> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let doc = self.gallery(gallery_id).await?;
+        doc.try_update(|d| {
+            d.move_after(&artifact_id, after.as_ref(), now)
+                .map_err(|e| IndraError::InvalidOperation(e.to_string()))
+        })
+        .await
+    }
+
+    async fn set_gallery_caption(
+        &self,
+        gallery_id: &GalleryId,
+        artifact_id: ArtifactId,
+        caption: Option<String>,
+        caller: MemberId,
+    ) -> Result<()> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let doc = self.gallery(gallery_id).await?;
+        doc.try_update(|d| {
+            d.set_caption(&artifact_id, caption, caller, now)
+                .map_err(|e| IndraError::InvalidOperation(e.to_string()))
+        })
+        .await
+    }
+
+    async fn set_gallery_cover(
+        &self,
+        gallery_id: &GalleryId,
+        artifact_id: Option<ArtifactId>,
+        caller: MemberId,
+    ) -> Result<()> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let doc = self.gallery(gallery_id).await?;
+        doc.try_update(|d| {
+            d.set_cover(artifact_id, caller, now)
+                .map_err(|e| IndraError::InvalidOperation(e.to_string()))
+        })
+        .await
+    }
+
+    async fn remove_from_gallery(
+        &self,
+        gallery_id: &GalleryId,
+        artifact_id: ArtifactId,
+        caller: MemberId,
+    ) -> Result<()> {
+        let doc = self.gallery(gallery_id).await?;
+        doc.try_update(|d| {
+            d.remove(&artifact_id, caller)
+                .map_err(|e| IndraError::InvalidOperation(e.to_string()))
+        })
+        .await
+    }
+}
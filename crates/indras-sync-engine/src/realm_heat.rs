@@ -0,0 +1,60 @@
+//! Extension trait adding artifact heat maintenance to Realm.
+
+use crate::heat::{HeatDocument, HeatSnapshot, HEAT_DOC};
+use crate::realm_attention::RealmAttention;
+use indras_network::document::Document;
+use indras_network::error::Result;
+use indras_network::member::MemberId;
+use indras_network::Realm;
+
+/// Artifact heat extension trait for Realm.
+pub trait RealmHeat {
+    /// Get the heat snapshot document for this realm.
+    async fn heat_snapshots(&self) -> Result<Document<HeatDocument>>;
+
+    /// Fold new attention events into the heat snapshots and apply decay.
+    ///
+    /// Returns the number of artifacts recomputed.
+    async fn refresh_heat(&self) -> Result<usize>;
+
+    /// The hottest artifacts in this realm, hottest first.
+    async fn hot_artifacts(&self, limit: usize) -> Result<Vec<HeatSnapshot>>;
+}
+
+impl RealmHeat for Realm {
+    async fn heat_snapshots(&self) -> Result<Document<HeatDocument>> {
+        self.document::<HeatDocument>(HEAT_DOC).await
+    }
+
+    async fn refresh_heat(&self) -> Result<usize> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let attention = self.attention().await?;
+        let chains = attention.read().await.all_chain_events().clone();
+
+        // compute_heat treats the first audience member as self
+        let me = self.member_id();
+        let mut audience: Vec<MemberId> = vec![me];
+        audience.extend(
+            self.member_list()
+                .await?
+                .into_iter()
+                .map(|m| m.id())
+                .filter(|id| *id != me),
+        );
+
+        let doc = self.heat_snapshots().await?;
+        doc.try_update(|d| {
+            let recomputed = d.refresh(&chains, &audience, now);
+            d.apply_decay(now);
+            Ok(recomputed)
+        })
+        .await
+    }
+
+    async fn hot_artifacts(&self, limit: usize) -> Result<Vec<HeatSnapshot>> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let doc = self.heat_snapshots().await?;
+        let hot = doc.read().await.hottest(limit, now);
+        Ok(hot)
+    }
+}
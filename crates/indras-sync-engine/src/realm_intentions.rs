@@ -1,14 +1,25 @@
 //! Extension trait adding quest methods to Realm.
 
+use crate::claim_review::{rotate_reviewer, ReviewComment, ReviewEvent};
 use crate::content::SyncContent;
 use crate::intention::{Intention, IntentionDocument, IntentionId, IntentionPriority};
 use indras_network::artifact::ArtifactId;
 use indras_network::document::Document;
 use indras_network::error::{IndraError, Result};
+use indras_network::event_feed::ControlCommand;
 use indras_network::member::MemberId;
-use indras_network::message::ContentReference;
+use indras_network::message::{ContentReference, MessageId};
 use indras_network::Realm;
 
+/// What a control command created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandOutcome {
+    /// A message was sent.
+    Message(MessageId),
+    /// A quest was created.
+    Intention(IntentionId),
+}
+
 /// Quest management extension trait for Realm.
 pub trait RealmIntentions {
     /// Get the quests document for this realm.
@@ -85,6 +96,60 @@ pub trait RealmIntentions {
         &self,
         intention_id: IntentionId,
     ) -> Result<()>;
+
+    /// Assign a reviewer to a claim.
+    ///
+    /// Only the intention's creator is authorized to assign reviewers, and
+    /// a claimant cannot review their own claim.
+    async fn assign_claim_reviewer(
+        &self,
+        intention_id: IntentionId,
+        claimant: MemberId,
+        reviewer: MemberId,
+        caller: MemberId,
+    ) -> Result<()>;
+
+    /// Assign a reviewer to a claim by rotation among realm members.
+    ///
+    /// Only the intention's creator is authorized. Returns the chosen
+    /// reviewer; see [`rotate_reviewer`] for how they are picked.
+    async fn auto_assign_claim_reviewer(
+        &self,
+        intention_id: IntentionId,
+        claimant: MemberId,
+        caller: MemberId,
+    ) -> Result<MemberId>;
+
+    /// Leave structured feedback on a claim.
+    ///
+    /// Only the assigned reviewer or the intention's creator may review.
+    /// The comment's verdict drives the claim's review state.
+    async fn review_claim(
+        &self,
+        intention_id: IntentionId,
+        claimant: MemberId,
+        comment: ReviewComment,
+    ) -> Result<()>;
+
+    /// Stream of review events (assignments, feedback, state changes).
+    ///
+    /// Use [`ReviewEvent::notifies`] to pick out the ones for this member.
+    async fn review_events(&self) -> Result<impl futures::Stream<Item = ReviewEvent> + Send + 'static>;
+
+    /// Carry out a control command, adding quests to the verbs
+    /// [`Realm::execute`] knows.
+    ///
+    /// `intention <title>` (or `quest <title>`) creates a quest by `caller`;
+    /// a description can follow the title after ` | `.
+    ///
+    /// ```text
+    /// quest Fix the fence | Posts on the north side are loose
+    /// ```
+    async fn execute_command(
+        &self,
+        command: ControlCommand,
+        caller: MemberId,
+    ) -> Result<CommandOutcome>;
 }
 
 impl RealmIntentions for Realm {
@@ -269,4 +334,134 @@ impl RealmIntentions for Realm {
 
         Ok(())
     }
+
+    async fn assign_claim_reviewer(
+        &self,
+        intention_id: IntentionId,
+        claimant: MemberId,
+        reviewer: MemberId,
+        caller: MemberId,
+    ) -> Result<()> {
+        if reviewer == claimant {
+            return Err(IndraError::InvalidOperation(
+                "A claimant cannot review their own claim".into(),
+            ));
+        }
+
+        let doc = self.intentions().await?;
+        doc.try_update(|d| {
+            let intention = d
+                .find_mut(&intention_id)
+                .ok_or_else(|| IndraError::InvalidOperation("Intention not found".into()))?;
+            if intention.creator != caller {
+                return Err(IndraError::InvalidOperation(
+                    "Not authorized: only the intention creator can assign reviewers".into(),
+                ));
+            }
+            let claim = intention
+                .claims
+                .iter_mut()
+                .find(|c| c.claimant == claimant)
+                .ok_or_else(|| IndraError::InvalidOperation("Claim not found".into()))?;
+            claim.review.assign(reviewer, chrono::Utc::now().timestamp_millis());
+            Ok(())
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    async fn auto_assign_claim_reviewer(
+        &self,
+        intention_id: IntentionId,
+        claimant: MemberId,
+        caller: MemberId,
+    ) -> Result<MemberId> {
+        let candidates: Vec<MemberId> = self.member_list().await?.iter().map(|m| m.id()).collect();
+        let doc = self.intentions().await?;
+        let reviewer = {
+            let guard = doc.read().await;
+            rotate_reviewer(&guard, &candidates, &claimant)
+        }
+        .ok_or_else(|| IndraError::InvalidOperation("No eligible reviewer".into()))?;
+
+        self.assign_claim_reviewer(intention_id, claimant, reviewer, caller)
+            .await?;
+
+        Ok(reviewer)
+    }
+
+    async fn review_claim(
+        &self,
+        intention_id: IntentionId,
+        claimant: MemberId,
+        comment: ReviewComment,
+    ) -> Result<()> {
+        let doc = self.intentions().await?;
+        doc.try_update(|d| {
+            let intention = d
+                .find_mut(&intention_id)
+                .ok_or_else(|| IndraError::InvalidOperation("Intention not found".into()))?;
+            let creator = intention.creator;
+            let claim = intention
+                .claims
+                .iter_mut()
+                .find(|c| c.claimant == claimant)
+                .ok_or_else(|| IndraError::InvalidOperation("Claim not found".into()))?;
+            if !claim.review.is_reviewer(&comment.author) && comment.author != creator {
+                return Err(IndraError::InvalidOperation(
+                    "Not authorized: only the assigned reviewer or the intention creator can review".into(),
+                ));
+            }
+            claim.review.add_feedback(comment);
+            Ok(())
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    async fn review_events(&self) -> Result<impl futures::Stream<Item = ReviewEvent> + Send + 'static> {
+        let doc = self.intentions().await?;
+        let mut rx = doc.subscribe();
+        let mut last = doc.read().await.clone();
+
+        Ok(async_stream::stream! {
+            // Hold the document so its change channel stays open.
+            let _doc = doc;
+            loop {
+                match rx.recv().await {
+                    Ok(change) => {
+                        for event in ReviewEvent::diff(&last, &change.new_state) {
+                            yield event;
+                        }
+                        last = change.new_state;
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    async fn execute_command(
+        &self,
+        command: ControlCommand,
+        caller: MemberId,
+    ) -> Result<CommandOutcome> {
+        match command {
+            ControlCommand::Other { verb, args } if verb == "intention" || verb == "quest" => {
+                let (title, description) = args.split_once(" | ").unwrap_or((&args, ""));
+                let title = title.trim();
+                if title.is_empty() {
+                    return Err(IndraError::InvalidOperation(format!("{verb}: missing title")));
+                }
+                let id = self
+                    .create_intention(title, description.trim(), None, caller)
+                    .await?;
+                Ok(CommandOutcome::Intention(id))
+            }
+            command => Ok(CommandOutcome::Message(self.execute(command).await?)),
+        }
+    }
 }
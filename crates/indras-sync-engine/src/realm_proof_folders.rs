@@ -1,6 +1,7 @@
 //! Extension trait adding proof folder methods to Realm.
 
 use crate::content::SyncContent;
+use crate::proof_bundle::{content_matches, BundleVerification, ProofBundle};
 use crate::proof_folder::{
     ProofFolder, ProofFolderArtifact, ProofFolderDocument, ProofFolderError, ProofFolderId,
 };
@@ -11,6 +12,7 @@ use indras_network::document::Document;
 use indras_network::error::{IndraError, Result};
 use indras_network::member::MemberId;
 use indras_network::Realm;
+use indras_storage::{ContentRef, StorageError};
 
 /// Proof folder management extension trait for Realm.
 pub trait RealmProofFolders {
@@ -50,6 +52,24 @@ pub trait RealmProofFolders {
         &self,
         folder_id: ProofFolderId,
     ) -> Result<usize>;
+
+    /// Seal selected artifacts of a draft folder into a bundle, then submit it.
+    ///
+    /// Each artifact must be a blob held locally whose content matches its
+    /// hash. The bundle's Merkle root is stored in the quest claim.
+    async fn submit_proof_bundle(
+        &self,
+        folder_id: ProofFolderId,
+        artifacts: Vec<ArtifactId>,
+    ) -> Result<usize>;
+
+    /// Check a claim's proof folder against the bundle root in the claim,
+    /// re-hashing every bundled artifact held locally.
+    async fn verify_claim_bundle(
+        &self,
+        intention_id: IntentionId,
+        claimant: MemberId,
+    ) -> Result<BundleVerification>;
 }
 
 impl RealmProofFolders for Realm {
@@ -171,6 +191,7 @@ impl RealmProofFolders for Realm {
         let claimant = folder.claimant;
         let narrative_preview = folder.narrative_preview();
         let artifact_count = folder.artifact_count();
+        let bundle_root = folder.bundle.as_ref().map(|b| b.root);
 
         drop(guard);
 
@@ -202,10 +223,13 @@ impl RealmProofFolders for Realm {
                         .enumerate()
                         .find(|(_, c)| c.claimant == claimant)
                     {
-                        claim.set_proof_folder(folder_id);
+                        claim.set_proof_bundle(folder_id, bundle_root);
+                        // Resubmitting after requested changes puts the claim back in review
+                        claim.review.resubmit(chrono::Utc::now().timestamp_millis());
                         claim_index = idx;
                     } else {
-                        let claim = crate::intention::ServiceClaim::with_proof_folder(claimant, folder_id);
+                        let mut claim = crate::intention::ServiceClaim::new(claimant, None);
+                        claim.set_proof_bundle(folder_id, bundle_root);
                         intention.claims.push(claim);
                         claim_index = intention.claims.len() - 1;
                     }
@@ -225,4 +249,129 @@ impl RealmProofFolders for Realm {
 
         Ok(claim_index)
     }
+
+    async fn submit_proof_bundle(
+        &self,
+        folder_id: ProofFolderId,
+        artifacts: Vec<ArtifactId>,
+    ) -> Result<usize> {
+        let doc = self.proof_folders().await?;
+        let folder = doc
+            .read()
+            .await
+            .find(&folder_id)
+            .cloned()
+            .ok_or_else(|| IndraError::InvalidOperation("Proof folder not found".into()))?;
+        if !folder.is_draft() {
+            return Err(IndraError::InvalidOperation(
+                "Proof folder has already been submitted".into(),
+            ));
+        }
+
+        let bundle = ProofBundle::seal(&folder.artifacts, &artifacts)
+            .map_err(|e| IndraError::InvalidOperation(e.to_string()))?;
+
+        // Only anchor content we actually hold
+        for artifact in folder.artifacts.iter().filter(|a| artifacts.contains(&a.artifact_id)) {
+            match load_artifact(self, artifact).await? {
+                Some(data) if content_matches(artifact, &data) => {}
+                Some(_) => {
+                    return Err(IndraError::InvalidOperation(format!(
+                        "Artifact {} does not match its content hash",
+                        artifact.name
+                    )));
+                }
+                None => {
+                    return Err(IndraError::InvalidOperation(format!(
+                        "Artifact {} is not stored locally",
+                        artifact.name
+                    )));
+                }
+            }
+        }
+
+        let mut result = Ok(());
+        doc.update(|d| {
+            if let Some(f) = d.find_mut(&folder_id) {
+                result = f
+                    .set_bundle(bundle.clone())
+                    .map_err(|e| IndraError::InvalidOperation(e.to_string()));
+            }
+        })
+        .await?;
+        result?;
+
+        self.submit_proof_folder(folder_id).await
+    }
+
+    async fn verify_claim_bundle(
+        &self,
+        intention_id: IntentionId,
+        claimant: MemberId,
+    ) -> Result<BundleVerification> {
+        let claim = {
+            let intentions = self.intentions().await?;
+            let guard = intentions.read().await;
+            let intention = guard
+                .find(&intention_id)
+                .ok_or_else(|| IndraError::InvalidOperation("Intention not found".into()))?;
+            intention
+                .claims
+                .iter()
+                .find(|c| c.claimant == claimant)
+                .cloned()
+                .ok_or_else(|| IndraError::InvalidOperation("Claim not found".into()))?
+        };
+        let (Some(expected), Some(folder_id)) = (claim.proof_bundle_root, claim.proof_folder) else {
+            return Ok(BundleVerification::NoBundle);
+        };
+
+        let folder = self.proof_folders().await?.read().await.find(&folder_id).cloned();
+        let Some((folder, bundle)) = folder.and_then(|f| f.bundle.clone().map(|b| (f, b))) else {
+            return Ok(BundleVerification::RootMismatch {
+                expected,
+                actual: None,
+            });
+        };
+        let actual = bundle.recompute_root(&folder).ok();
+        if actual != Some(expected) {
+            return Ok(BundleVerification::RootMismatch { expected, actual });
+        }
+
+        let mut verified = Vec::new();
+        let mut tampered = Vec::new();
+        let mut unavailable = Vec::new();
+        for id in &bundle.artifacts {
+            let Some(artifact) = folder.find_artifact(id) else {
+                continue;
+            };
+            match load_artifact(self, artifact).await {
+                Ok(Some(data)) if content_matches(artifact, &data) => verified.push(*id),
+                Ok(Some(_)) => tampered.push(*id),
+                Ok(None) => unavailable.push(*id),
+                Err(IndraError::Storage(StorageError::Deserialization(_))) => tampered.push(*id),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(BundleVerification::Checked {
+            verified,
+            tampered,
+            unavailable,
+        })
+    }
+}
+
+/// Load a bundled artifact's bytes from the local blob store.
+///
+/// Returns `None` if the blob is not stored here.
+async fn load_artifact(realm: &Realm, artifact: &ProofFolderArtifact) -> Result<Option<Vec<u8>>> {
+    let ArtifactId::Blob(hash) = artifact.artifact_id else {
+        return Ok(None);
+    };
+    match realm.node().storage().resolve_blob(&ContentRef::new(hash, artifact.size)).await {
+        Ok(data) => Ok(Some(data.to_vec())),
+        Err(StorageError::PacketNotFound(_)) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
 }
@@ -0,0 +1,183 @@
+//! Extension trait adding request fulfillment methods to Realm.
+
+use crate::fulfillment::{
+    ArtifactRequest, OfferContent, OfferId, RequestAcceptance, RequestBoardDocument, RequestError,
+    RequestId, REQUEST_BOARD_DOC,
+};
+use crate::intention::{Intention, IntentionKind};
+use crate::realm_intentions::RealmIntentions;
+use indras_network::document::Document;
+use indras_network::error::{IndraError, Result};
+use indras_network::member::MemberId;
+use indras_network::Realm;
+
+/// Request fulfillment extension trait for Realm.
+pub trait RealmRequests {
+    /// Get the request board document for this realm.
+    async fn request_board(&self) -> Result<Document<RequestBoardDocument>>;
+
+    /// Broadcast a request to this realm.
+    async fn broadcast_request(
+        &self,
+        requester: MemberId,
+        description: impl Into<String> + Send,
+    ) -> Result<RequestId>;
+
+    /// Attach an offer to a request.
+    async fn offer_to_request(
+        &self,
+        request_id: RequestId,
+        offerer: MemberId,
+        content: OfferContent,
+    ) -> Result<OfferId>;
+
+    /// Withdraw an offer before it is accepted.
+    async fn withdraw_request_offer(
+        &self,
+        request_id: RequestId,
+        offer_id: OfferId,
+        caller: MemberId,
+    ) -> Result<()>;
+
+    /// Accept an offer as the requester.
+    ///
+    /// Accepting a pledge creates a quest in this realm for the pledged
+    /// work. Accepting an artifact leaves delivery to the offerer.
+    async fn accept_request_offer(
+        &self,
+        request_id: RequestId,
+        offer_id: OfferId,
+        requester: MemberId,
+    ) -> Result<RequestAcceptance>;
+
+    /// Mark a request fulfilled, as the requester or the accepted offerer.
+    async fn mark_request_fulfilled(&self, request_id: RequestId, caller: MemberId) -> Result<()>;
+
+    /// Cancel a request as the requester.
+    async fn cancel_request(&self, request_id: RequestId, caller: MemberId) -> Result<()>;
+}
+
+impl RealmRequests for Realm {
+    async fn request_board(&self) -> Result<Document<RequestBoardDocument>> {
+        self.document::<RequestBoardDocument>(REQUEST_BOARD_DOC)
+            .await
+    }
+
+    async fn broadcast_request(
+        &self,
+        requester: MemberId,
+        description: impl Into<String> + Send,
+    ) -> Result<RequestId> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let request = ArtifactRequest::new(requester, description, now);
+        let request_id = request.id;
+
+        let doc = self.request_board().await?;
+        doc.update(|d| d.add(request)).await?;
+
+        Ok(request_id)
+    }
+
+    async fn offer_to_request(
+        &self,
+        request_id: RequestId,
+        offerer: MemberId,
+        content: OfferContent,
+    ) -> Result<OfferId> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let doc = self.request_board().await?;
+        doc.try_update(|d| {
+            d.find_mut(&request_id)
+                .ok_or(RequestError::RequestNotFound)
+                .and_then(|r| r.add_offer(offerer, content, now))
+                .map_err(|e| IndraError::InvalidOperation(e.to_string()))
+        })
+        .await
+    }
+
+    async fn withdraw_request_offer(
+        &self,
+        request_id: RequestId,
+        offer_id: OfferId,
+        caller: MemberId,
+    ) -> Result<()> {
+        let doc = self.request_board().await?;
+        doc.try_update(|d| {
+            d.find_mut(&request_id)
+                .ok_or(RequestError::RequestNotFound)
+                .and_then(|r| r.withdraw_offer(&offer_id, caller))
+                .map_err(|e| IndraError::InvalidOperation(e.to_string()))
+        })
+        .await
+    }
+
+    async fn accept_request_offer(
+        &self,
+        request_id: RequestId,
+        offer_id: OfferId,
+        requester: MemberId,
+    ) -> Result<RequestAcceptance> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let doc = self.request_board().await?;
+        let (description, content) = doc
+            .try_update(|d| {
+                let request = d
+                    .find_mut(&request_id)
+                    .ok_or_else(|| IndraError::InvalidOperation("Request not found".into()))?;
+                let offer = request
+                    .accept(&offer_id, requester, now)
+                    .map_err(|e| IndraError::InvalidOperation(e.to_string()))?;
+                Ok((request.description.clone(), offer.content.clone()))
+            })
+            .await?;
+
+        // A pledge turns into a quest the offerer can claim.
+        if let OfferContent::Pledge {
+            description: pledge,
+        } = content
+        {
+            let mut quest = Intention::new(description, pledge, None, requester);
+            quest.kind = IntentionKind::Need;
+            let quest_id = quest.id;
+            let intentions = self.intentions().await?;
+            intentions.update(|d| d.add(quest)).await?;
+
+            doc.try_update(|d| {
+                d.find_mut(&request_id)
+                    .ok_or(RequestError::RequestNotFound)
+                    .and_then(|r| r.set_quest(quest_id))
+                    .map_err(|e| IndraError::InvalidOperation(e.to_string()))
+            })
+            .await?;
+        }
+
+        let board = doc.read().await;
+        board
+            .find(&request_id)
+            .and_then(|r| r.acceptance.clone())
+            .ok_or_else(|| IndraError::InvalidOperation("Request not found".into()))
+    }
+
+    async fn mark_request_fulfilled(&self, request_id: RequestId, caller: MemberId) -> Result<()> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let doc = self.request_board().await?;
+        doc.try_update(|d| {
+            d.find_mut(&request_id)
+                .ok_or(RequestError::RequestNotFound)
+                .and_then(|r| r.mark_fulfilled(caller, now))
+                .map_err(|e| IndraError::InvalidOperation(e.to_string()))
+        })
+        .await
+    }
+
+    async fn cancel_request(&self, request_id: RequestId, caller: MemberId) -> Result<()> {
+        let doc = self.request_board().await?;
+        doc.try_update(|d| {
+            d.find_mut(&request_id)
+                .ok_or(RequestError::RequestNotFound)
+                .and_then(|r| r.cancel(caller))
+                .map_err(|e| IndraError::InvalidOperation(e.to_string()))
+        })
+        .await
+    }
+}
@@ -0,0 +1,127 @@
+//! Extension trait adding story thread methods to Realm.
+
+use crate::story::{
+    story_doc_key, LeafId, LeafKind, StoryDocument, StoryId, StoryIndexDocument, StorySummary,
+    STORY_INDEX_DOC,
+};
+use indras_network::document::Document;
+use indras_network::error::{IndraError, Result};
+use indras_network::member::MemberId;
+use indras_network::Realm;
+
+/// Story thread extension trait for Realm.
+pub trait RealmStories {
+    /// Get the story index document for this realm.
+    async fn stories(&self) -> Result<Document<StoryIndexDocument>>;
+
+    /// Get a story's document.
+    async fn story(&self, story_id: &StoryId) -> Result<Document<StoryDocument>>;
+
+    /// Start a new story and list it in the realm's index.
+    async fn create_story(
+        &self,
+        title: impl Into<String> + Send,
+        creator: MemberId,
+    ) -> Result<StoryId>;
+
+    /// Append a leaf at the end of a story.
+    async fn append_to_story(
+        &self,
+        story_id: &StoryId,
+        author: MemberId,
+        kind: LeafKind,
+    ) -> Result<LeafId>;
+
+    /// Insert a leaf right after `after`, or at the start when `None`.
+    async fn insert_into_story(
+        &self,
+        story_id: &StoryId,
+        after: Option<LeafId>,
+        author: MemberId,
+        kind: LeafKind,
+    ) -> Result<LeafId>;
+
+    /// Remove one of the caller's leaves from a story.
+    async fn remove_story_leaf(
+        &self,
+        story_id: &StoryId,
+        leaf_id: LeafId,
+        caller: MemberId,
+    ) -> Result<()>;
+}
+
+impl RealmStories for Realm {
+    async fn stories(&self) -> Result<Document<StoryIndexDocument>> {
+        self.document::<StoryIndexDocument>(STORY_INDEX_DOC).await
+    }
+
+    async fn story(&self, story_id: &StoryId) -> Result<Document<StoryDocument>> {
+        self.document::<StoryDocument>(&story_doc_key(story_id))
+            .await
+    }
+
+    async fn create_story(
+        &self,
+        title: impl Into<String> + Send,
+        creator: MemberId,
+    ) -> Result<StoryId> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let story = StoryDocument::new(title, creator, now);
+        let summary = StorySummary {
+            id: story.id,
+            title: story.title.clone(),
+            creator,
+            created_at_millis: now,
+        };
+        let story_id = story.id;
+
+        let doc = self.story(&story_id).await?;
+        doc.update(|d| *d = story).await?;
+
+        let index = self.stories().await?;
+        index.update(|d| d.add(summary)).await?;
+
+        Ok(story_id)
+    }
+
+    async fn append_to_story(
+        &self,
+        story_id: &StoryId,
+        author: MemberId,
+        kind: LeafKind,
+    ) -> Result<LeafId> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let doc = self.story(story_id).await?;
+        doc.try_update(|d| Ok(d.append(author, kind, now))).await
+    }
+
+    async fn insert_into_story(
+        &self,
+        story_id: &StoryId,
+        after: Option<LeafId>,
+        author: MemberId,
+        kind: LeafKind,
+    ) -> Result<LeafId> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let doc = self.story(story_id).await?;
+        doc.try_update(|d| {
+            d.insert_after(after.as_ref(), author, kind, now)
+                .map_err(|e| IndraError::InvalidOperation(e.to_string()))
+        })
+        .await
+    }
+
+    async fn remove_story_leaf(
+        &self,
+        story_id: &StoryId,
+        leaf_id: LeafId,
+        caller: MemberId,
+    ) -> Result<()> {
+        let doc = self.story(story_id).await?;
+        doc.try_update(|d| {
+            d.remove(&leaf_id, caller)
+                .map_err(|e| IndraError::InvalidOperation(e.to_string()))
+        })
+        .await
+    }
+}
@@ -3,13 +3,17 @@
 //! Includes the attention→gratitude bridge: `token_attention_millis()` computes
 //! the raw attention duration backing a token, and `token_subjective_value()`
 //! combines it with trust and humanness for observer-specific valuation.
+//! `record_token_value()` stores that valuation as a snapshot so it can be
+//! charted over time with `token_value_history()` and `gratitude_flow()`.
 
 use crate::attention::AttentionDocument;
 use crate::content::SyncContent;
 use crate::humanness::HumannessDocument;
 use crate::intention::IntentionId;
 use crate::token_of_gratitude::{TokenOfGratitude, TokenOfGratitudeDocument, TokenOfGratitudeId};
-use crate::token_valuation::{SubjectiveTokenValue, subjective_value};
+use crate::token_valuation::{
+    FlowPoint, SubjectiveTokenValue, ValueSnapshot, gratitude_flow, subjective_value,
+};
 use indras_network::document::Document;
 use indras_network::error::{IndraError, Result};
 use indras_network::member::MemberId;
@@ -85,6 +89,31 @@ pub trait RealmTokens {
         token_id: &TokenOfGratitudeId,
         sentiment_fn: &dyn Fn(&MemberId) -> Option<f64>,
     ) -> Result<SubjectiveTokenValue>;
+
+    /// Compute a token's subjective value and record it as a snapshot.
+    ///
+    /// `observer` is the member whose `sentiment_fn` is used; snapshots are
+    /// kept per observer so each member charts their own view.
+    async fn record_token_value(
+        &self,
+        token_id: &TokenOfGratitudeId,
+        observer: MemberId,
+        sentiment_fn: &dyn Fn(&MemberId) -> Option<f64>,
+    ) -> Result<SubjectiveTokenValue>;
+
+    /// Recorded value snapshots of a token from one observer, oldest first.
+    async fn token_value_history(
+        &self,
+        token_id: &TokenOfGratitudeId,
+        observer: &MemberId,
+    ) -> Result<Vec<ValueSnapshot>>;
+
+    /// Value a member has stewarded over time, from one observer's snapshots.
+    async fn gratitude_flow(
+        &self,
+        member: &MemberId,
+        observer: &MemberId,
+    ) -> Result<Vec<FlowPoint>>;
 }
 
 impl RealmTokens for Realm {
@@ -290,4 +319,47 @@ impl RealmTokens for Realm {
 
         Ok(value)
     }
+
+    async fn record_token_value(
+        &self,
+        token_id: &TokenOfGratitudeId,
+        observer: MemberId,
+        sentiment_fn: &dyn Fn(&MemberId) -> Option<f64>,
+    ) -> Result<SubjectiveTokenValue> {
+        let value = self.token_subjective_value(token_id, sentiment_fn).await?;
+
+        let token_doc = self.tokens().await?;
+        token_doc
+            .try_update(|d| {
+                d.record_value(*token_id, observer, &value)
+                    .map_err(|e| IndraError::InvalidOperation(e.to_string()))
+            })
+            .await?;
+
+        Ok(value)
+    }
+
+    async fn token_value_history(
+        &self,
+        token_id: &TokenOfGratitudeId,
+        observer: &MemberId,
+    ) -> Result<Vec<ValueSnapshot>> {
+        let token_doc = self.tokens().await?;
+        let guard = token_doc.read().await;
+        Ok(guard
+            .value_history(token_id, observer)
+            .into_iter()
+            .cloned()
+            .collect())
+    }
+
+    async fn gratitude_flow(
+        &self,
+        member: &MemberId,
+        observer: &MemberId,
+    ) -> Result<Vec<FlowPoint>> {
+        let token_doc = self.tokens().await?;
+        let guard = token_doc.read().await;
+        Ok(gratitude_flow(&guard, member, observer))
+    }
 }
@@ -0,0 +1,319 @@
+//! Attention-weighted recommendations — "what should I work on next".
+//!
+//! Ranks open intentions across realms by four signals, each squashed into
+//! `0.0..1.0` so no single signal can dominate:
+//!
+//! - **Attention heat**: focus time on the intention, decayed by age so
+//!   recent sessions count most. The member's own focus is weighted above
+//!   everyone else's.
+//! - **Deadline urgency**: rises as the deadline approaches; overdue is 1.0.
+//! - **Priority**: `Low` through `Urgent`.
+//! - **Pledged blessings**: attention backing the tokens of gratitude
+//!   pledged to the intention as bounty.
+//!
+//! Scoring is pure and local; [`SyncEngine::recommendations`](crate::SyncEngine::recommendations)
+//! gathers the documents from each loaded realm and ranks the results.
+
+use std::collections::HashMap;
+
+use indras_network::member::MemberId;
+use indras_network::RealmId;
+
+use crate::attention::AttentionDocument;
+use crate::intention::{IntentionDocument, IntentionId, IntentionPriority};
+use crate::token_of_gratitude::TokenOfGratitudeDocument;
+
+/// Focus time loses half its heat every three days.
+pub const ATTENTION_HALF_LIFE_MILLIS: i64 = 3 * 24 * 60 * 60 * 1000;
+
+const HOUR_MILLIS: f64 = 60.0 * 60.0 * 1000.0;
+const DAY_MILLIS: f64 = 24.0 * HOUR_MILLIS;
+
+/// Relative weight of each ranking signal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RecommendationWeights {
+    /// Weight of the member's own decayed focus time.
+    pub own_attention: f64,
+    /// Weight of everyone else's decayed focus time.
+    pub community_attention: f64,
+    /// Weight of deadline urgency.
+    pub deadline: f64,
+    /// Weight of the intention's priority.
+    pub priority: f64,
+    /// Weight of attention pledged as tokens of gratitude.
+    pub pledges: f64,
+}
+
+impl Default for RecommendationWeights {
+    fn default() -> Self {
+        Self {
+            own_attention: 2.0,
+            community_attention: 1.0,
+            deadline: 1.5,
+            priority: 1.0,
+            pledges: 1.0,
+        }
+    }
+}
+
+/// Per-signal contributions to a recommendation, each in `0.0..1.0`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ScoreBreakdown {
+    /// The member's own recent focus.
+    pub own_attention: f64,
+    /// Other members' recent focus.
+    pub community_attention: f64,
+    /// How close the deadline is.
+    pub deadline: f64,
+    /// The intention's priority.
+    pub priority: f64,
+    /// Attention pledged as bounty.
+    pub pledges: f64,
+}
+
+impl ScoreBreakdown {
+    /// Weighted total.
+    pub fn score(&self, weights: &RecommendationWeights) -> f64 {
+        self.own_attention * weights.own_attention
+            + self.community_attention * weights.community_attention
+            + self.deadline * weights.deadline
+            + self.priority * weights.priority
+            + self.pledges * weights.pledges
+    }
+}
+
+/// A ranked suggestion for the workspace home screen.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recommendation {
+    /// The realm the intention lives in.
+    pub realm_id: RealmId,
+    /// The recommended intention.
+    pub intention_id: IntentionId,
+    /// The intention's title.
+    pub title: String,
+    /// Weighted total score (higher is better).
+    pub score: f64,
+    /// What contributed to the score.
+    pub breakdown: ScoreBreakdown,
+}
+
+/// Score the open intentions of one realm for `member`.
+///
+/// Skips intentions that are deleted or complete, that `member` created,
+/// or where `member` already has a verified claim.
+pub fn score_intentions(
+    realm_id: RealmId,
+    intentions: &IntentionDocument,
+    attention: &AttentionDocument,
+    tokens: &TokenOfGratitudeDocument,
+    member: &MemberId,
+    now_millis: i64,
+    weights: &RecommendationWeights,
+) -> Vec<Recommendation> {
+    let heat = attention_heat(attention, member, now_millis);
+
+    intentions
+        .intentions
+        .iter()
+        .filter(|i| !i.is_deleted() && !i.is_complete() && &i.creator != member)
+        .filter(|i| !i.claims.iter().any(|c| &c.claimant == member && c.verified))
+        .map(|intention| {
+            let (own, community) = heat.get(&intention.id).copied().unwrap_or_default();
+            let pledged: u64 = tokens
+                .pledged_tokens_for_intention(&intention.id)
+                .iter()
+                .map(|t| attention.compute_attention_millis(&t.event_indices, Some(now_millis)))
+                .sum();
+
+            let breakdown = ScoreBreakdown {
+                own_attention: saturate(own / HOUR_MILLIS),
+                community_attention: saturate(community / HOUR_MILLIS),
+                deadline: deadline_urgency(intention.deadline_millis, now_millis),
+                priority: priority_weight(intention.priority),
+                pledges: saturate(pledged as f64 / HOUR_MILLIS),
+            };
+
+            Recommendation {
+                realm_id,
+                intention_id: intention.id,
+                title: intention.title.clone(),
+                score: breakdown.score(weights),
+                breakdown,
+            }
+        })
+        .collect()
+}
+
+/// Sort recommendations best-first and keep the top `limit`.
+pub fn rank(mut recommendations: Vec<Recommendation>, limit: usize) -> Vec<Recommendation> {
+    recommendations.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.intention_id.cmp(&b.intention_id))
+    });
+    recommendations.truncate(limit);
+    recommendations
+}
+
+/// Decayed focus time per intention, split into `(own, everyone else)`.
+///
+/// Each focus window counts in full while fresh and halves every
+/// [`ATTENTION_HALF_LIFE_MILLIS`] after it ended.
+fn attention_heat(
+    attention: &AttentionDocument,
+    member: &MemberId,
+    now_millis: i64,
+) -> HashMap<IntentionId, (f64, f64)> {
+    let mut events = attention.events().to_vec();
+    events.sort();
+
+    let mut open: HashMap<MemberId, (IntentionId, i64)> = HashMap::new();
+    let mut heat: HashMap<IntentionId, (f64, f64)> = HashMap::new();
+    let mut add = |who: &MemberId, intention: IntentionId, start: i64, end: i64| {
+        let duration = (end - start).max(0) as f64;
+        let age = (now_millis - end).max(0) as f64;
+        let decayed = duration * 0.5f64.powf(age / ATTENTION_HALF_LIFE_MILLIS as f64);
+        let entry = heat.entry(intention).or_default();
+        if who == member {
+            entry.0 += decayed;
+        } else {
+            entry.1 += decayed;
+        }
+    };
+
+    for event in events.iter().filter(|e| e.timestamp_millis <= now_millis) {
+        if let Some((intention, start)) = open.remove(&event.member) {
+            add(&event.member, intention, start, event.timestamp_millis);
+        }
+        if let Some(intention) = event.intention_id {
+            open.insert(event.member, (intention, event.timestamp_millis));
+        }
+    }
+    for (who, (intention, start)) in open {
+        add(&who, intention, start, now_millis);
+    }
+
+    heat
+}
+
+/// Urgency of a deadline: 1.0 when due or overdue, halving with each
+/// extra day of slack.
+fn deadline_urgency(deadline_millis: Option<i64>, now_millis: i64) -> f64 {
+    match deadline_millis {
+        None => 0.0,
+        Some(deadline) => {
+            let days_left = (deadline - now_millis).max(0) as f64 / DAY_MILLIS;
+            0.5f64.powf(days_left)
+        }
+    }
+}
+
+fn priority_weight(priority: IntentionPriority) -> f64 {
+    match priority {
+        IntentionPriority::Low => 0.0,
+        IntentionPriority::Normal => 0.33,
+        IntentionPriority::High => 0.67,
+        IntentionPriority::Urgent => 1.0,
+    }
+}
+
+/// Map `0..∞` onto `0..1`, reaching 0.5 at `x = 1`.
+fn saturate(x: f64) -> f64 {
+    x / (x + 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attention::AttentionSwitchEvent;
+    use crate::intention::Intention;
+    use indras_core::InterfaceId;
+
+    const ME: MemberId = [1u8; 32];
+    const OTHER: MemberId = [2u8; 32];
+    const CREATOR: MemberId = [3u8; 32];
+    const NOW: i64 = 100 * 24 * 60 * 60 * 1000;
+
+    fn realm() -> RealmId {
+        InterfaceId::new([9u8; 32])
+    }
+
+    fn focus(member: MemberId, intention: Option<IntentionId>, at: i64) -> AttentionSwitchEvent {
+        let mut event = AttentionSwitchEvent::new(member, intention);
+        event.timestamp_millis = at;
+        event
+    }
+
+    #[test]
+    fn test_recent_own_focus_ranks_first() {
+        let mut doc = IntentionDocument::new();
+        let focused = Intention::new("Focused", "", None, CREATOR);
+        let ignored = Intention::new("Ignored", "", None, CREATOR);
+        let (focused_id, ignored_id) = (focused.id, ignored.id);
+        doc.add(focused);
+        doc.add(ignored);
+
+        let mut attention = AttentionDocument::new();
+        attention.insert_event(focus(ME, Some(focused_id), NOW - 2 * 3_600_000));
+        attention.insert_event(focus(ME, None, NOW - 3_600_000));
+
+        let ranked = rank(
+            score_intentions(
+                realm(),
+                &doc,
+                &attention,
+                &TokenOfGratitudeDocument::new(),
+                &ME,
+                NOW,
+                &RecommendationWeights::default(),
+            ),
+            10,
+        );
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].intention_id, focused_id);
+        assert!(ranked[0].breakdown.own_attention > 0.4);
+        assert_eq!(ranked[1].intention_id, ignored_id);
+        assert_eq!(rank(ranked, 1).len(), 1);
+    }
+
+    #[test]
+    fn test_heat_decays_and_splits_by_member() {
+        let id = [7u8; 16];
+        let mut attention = AttentionDocument::new();
+        attention.insert_event(focus(OTHER, Some(id), NOW - ATTENTION_HALF_LIFE_MILLIS - 1_000));
+        attention.insert_event(focus(OTHER, None, NOW - ATTENTION_HALF_LIFE_MILLIS));
+
+        let (own, community) = attention_heat(&attention, &ME, NOW)[&id];
+        assert_eq!(own, 0.0);
+        assert!((community - 500.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_excludes_own_and_finished_intentions() {
+        let mut doc = IntentionDocument::new();
+        doc.add(Intention::new("Mine", "", None, ME));
+        let mut done = Intention::new("Done", "", None, CREATOR);
+        done.submit_claim(ME, None).unwrap();
+        done.verify_claim(0).unwrap();
+        doc.add(done);
+
+        let scored = score_intentions(
+            realm(),
+            &doc,
+            &AttentionDocument::new(),
+            &TokenOfGratitudeDocument::new(),
+            &ME,
+            NOW,
+            &RecommendationWeights::default(),
+        );
+        assert!(scored.is_empty());
+    }
+
+    #[test]
+    fn test_deadline_and_priority_signals() {
+        assert_eq!(deadline_urgency(None, NOW), 0.0);
+        assert_eq!(deadline_urgency(Some(NOW - 1), NOW), 1.0);
+        assert!((deadline_urgency(Some(NOW + DAY_MILLIS as i64), NOW) - 0.5).abs() < 1e-9);
+        assert!(priority_weight(IntentionPriority::Urgent) > priority_weight(IntentionPriority::High));
+    }
+}
@@ -14,11 +14,26 @@
 //! concurrent recoveries across unrelated peers don't collide. Only
 //! one active recovery per new device is expected; re-initiating
 //! overwrites the prior request via last-writer-wins.
+//!
+//! On the new device, a [`RecoveryCeremony`] gathers the releases:
+//! it decrypts each one, drops duplicate share indices, rejects
+//! releases for a different source account or split version, and
+//! recombines the subkey once the threshold carried by the shares
+//! themselves is met.
+
+use std::collections::{BTreeMap, BTreeSet};
 
 use serde::{Deserialize, Serialize};
 
+use indras_crypto::pq_kem::PQKemKeyPair;
+use indras_crypto::shamir::{ShamirShare, SHAMIR_SECRET_SIZE};
+use indras_crypto::steward_share::EncryptedStewardShare;
 use indras_network::document::DocumentSchema;
 
+use crate::steward_recovery::{
+    recover_encryption_subkey, StewardRecoveryError, StewardRecoveryResult,
+};
+
 /// Key prefix for a recovery request doc.
 pub const RECOVERY_REQUEST_KEY_PREFIX: &str = "_recovery_request:";
 
@@ -99,9 +114,172 @@ impl DocumentSchema for ShareRelease {
     }
 }
 
+impl ShareRelease {
+    /// Whether the steward has actually released a share.
+    pub fn is_released(&self) -> bool {
+        self.approved_at_millis > 0 && !self.encrypted_share_bytes.is_empty()
+    }
+}
+
+/// New-device collector for [`ShareRelease`]s.
+///
+/// The first accepted release pins the source account, split version,
+/// and threshold; later releases must agree. Stewards releasing twice,
+/// or two stewards holding the same share index, count once.
+#[derive(Default)]
+pub struct RecoveryCeremony {
+    source_account_uid: Option<[u8; 32]>,
+    secret_version: Option<u64>,
+    threshold: Option<u8>,
+    shares: BTreeMap<u8, ShamirShare>,
+    released_by: BTreeSet<[u8; 32]>,
+}
+
+impl RecoveryCeremony {
+    /// Start an empty ceremony.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decrypt a release with the new device's KEM keypair and add its
+    /// share. Returns `false` if it added nothing new.
+    pub fn accept(
+        &mut self,
+        release: &ShareRelease,
+        recipient: &PQKemKeyPair,
+    ) -> StewardRecoveryResult<bool> {
+        if !release.is_released() {
+            return Ok(false);
+        }
+        let encrypted = EncryptedStewardShare::from_bytes(&release.encrypted_share_bytes)?;
+        if !pinned(self.source_account_uid, release.source_account_uid)
+            || !pinned(self.secret_version, encrypted.secret_version)
+            || !pinned(self.threshold, encrypted.threshold)
+        {
+            return Err(StewardRecoveryError::MismatchedRelease);
+        }
+
+        let share = encrypted.decrypt(recipient)?;
+        self.source_account_uid = Some(release.source_account_uid);
+        self.secret_version = Some(encrypted.secret_version);
+        self.threshold = Some(encrypted.threshold);
+        self.released_by.insert(release.steward_uid);
+        Ok(self.shares.insert(share.index(), share).is_none())
+    }
+
+    /// The account being recovered, once any release is accepted.
+    pub fn source_account_uid(&self) -> Option<[u8; 32]> {
+        self.source_account_uid
+    }
+
+    /// Shares needed (K), once any release is accepted.
+    pub fn threshold(&self) -> Option<u8> {
+        self.threshold
+    }
+
+    /// Distinct shares collected so far.
+    pub fn collected(&self) -> usize {
+        self.shares.len()
+    }
+
+    /// Stewards whose release was accepted.
+    pub fn released_by(&self) -> impl Iterator<Item = &[u8; 32]> {
+        self.released_by.iter()
+    }
+
+    /// Whether enough shares are in to recombine.
+    pub fn is_ready(&self) -> bool {
+        self.threshold.is_some_and(|k| self.shares.len() >= k as usize)
+    }
+
+    /// Recombine the encryption subkey.
+    pub fn combine(&self) -> StewardRecoveryResult<[u8; SHAMIR_SECRET_SIZE]> {
+        let need = self.threshold.unwrap_or(0);
+        if !self.is_ready() {
+            return Err(StewardRecoveryError::NotEnoughShares {
+                have: self.shares.len(),
+                need,
+            });
+        }
+        let shares: Vec<ShamirShare> = self.shares.values().cloned().collect();
+        recover_encryption_subkey(&shares, need)
+    }
+}
+
+/// Whether `value` agrees with a field pinned by an earlier release.
+fn pinned<T: PartialEq>(field: Option<T>, value: T) -> bool {
+    field.is_none_or(|f| f == value)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::steward_recovery::{prepare_recovery, StewardId};
+    use indras_crypto::steward_share::encrypt_share_for_steward;
+
+    /// Split `subkey` 2-of-3 and have every steward release to `device`.
+    fn releases(subkey: &[u8; 32], source: [u8; 32], device: &PQKemKeyPair) -> Vec<ShareRelease> {
+        let stewards: Vec<PQKemKeyPair> = (0..3).map(|_| PQKemKeyPair::generate()).collect();
+        let eks: Vec<_> = stewards
+            .iter()
+            .enumerate()
+            .map(|(i, kp)| (StewardId::new(vec![i as u8]), kp.encapsulation_key()))
+            .collect();
+        let prepared = prepare_recovery(subkey, &eks, 2, 1).unwrap();
+        prepared
+            .encrypted_shares
+            .iter()
+            .zip(&stewards)
+            .enumerate()
+            .map(|(i, (held, kp))| {
+                let share = held.decrypt(kp).unwrap();
+                let rewrapped =
+                    encrypt_share_for_steward(&share, 2, 1, &device.encapsulation_key()).unwrap();
+                ShareRelease {
+                    steward_uid: [i as u8; 32],
+                    source_account_uid: source,
+                    encrypted_share_bytes: rewrapped.to_bytes().unwrap(),
+                    approved_at_millis: 10,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ceremony_combines_at_threshold() {
+        let subkey = [7u8; 32];
+        let device = PQKemKeyPair::generate();
+        let releases = releases(&subkey, [1u8; 32], &device);
+
+        let mut ceremony = RecoveryCeremony::new();
+        assert!(ceremony.accept(&ShareRelease::default(), &device).is_ok_and(|added| !added));
+        assert!(ceremony.accept(&releases[0], &device).unwrap());
+        assert!(!ceremony.accept(&releases[0], &device).unwrap());
+        assert!(matches!(
+            ceremony.combine(),
+            Err(StewardRecoveryError::NotEnoughShares { have: 1, need: 2 })
+        ));
+
+        assert!(ceremony.accept(&releases[2], &device).unwrap());
+        assert!(ceremony.is_ready());
+        assert_eq!(ceremony.source_account_uid(), Some([1u8; 32]));
+        assert_eq!(ceremony.combine().unwrap(), subkey);
+    }
+
+    #[test]
+    fn ceremony_rejects_other_account() {
+        let device = PQKemKeyPair::generate();
+        let ours = releases(&[7u8; 32], [1u8; 32], &device);
+        let theirs = releases(&[8u8; 32], [2u8; 32], &device);
+
+        let mut ceremony = RecoveryCeremony::new();
+        ceremony.accept(&ours[0], &device).unwrap();
+        assert!(matches!(
+            ceremony.accept(&theirs[1], &device),
+            Err(StewardRecoveryError::MismatchedRelease)
+        ));
+        assert_eq!(ceremony.collected(), 1);
+    }
 
     #[test]
     fn doc_keys_stable_and_distinct() {
@@ -0,0 +1,985 @@
+//! Session recording — capture a live session as viewer-compatible JSONL.
+//!
+//! Lua scenarios write JSONL event streams that realm-viewer and home-viewer
+//! replay. This module produces the same stream from a running node: it
+//! watches a realm's intentions, attention, blessings, and token documents,
+//! turns each change into [`SessionEvent`]s, and writes them one JSON object
+//! per line in the chosen [`ViewerFormat`].
+//!
+//! ```ignore
+//! let file = std::fs::File::create("session.jsonl")?;
+//! let recorder = SessionRecorder::new(file, ViewerFormat::Realm, Redaction::all(), me, realm_id);
+//! let source = SessionSource::home(&home).await?;
+//! record_session(source, recorder).await?;
+//! ```
+//!
+//! A recording of a real bug may carry member names and quest titles that
+//! should not leave the machine; [`Redaction`] replaces them with stable
+//! pseudonyms (`member-1`, `quest-1`, ...) so the event sequence stays
+//! replayable.
+//!
+//! Each document change that produces events advances the recording by one
+//! tick.
+
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use futures::StreamExt;
+use indras_network::document::Document;
+use indras_network::member::MemberId;
+use indras_network::stream::broadcast_to_stream;
+use indras_network::{HomeRealm, Realm};
+use serde_json::{json, Value};
+
+use crate::attention::{AttentionDocument, AttentionEventId};
+use crate::blessing::{BlessingDocument, BlessingId};
+use crate::intention::{IntentionDocument, IntentionId};
+use crate::token_of_gratitude::{TokenEvent, TokenOfGratitudeDocument, TokenOfGratitudeId};
+
+/// Errors from configuring or writing a recording.
+#[derive(Debug, thiserror::Error)]
+pub enum RecordingError {
+    /// Writing the JSONL output failed.
+    #[error("Failed to write recording: {0}")]
+    Io(#[from] io::Error),
+    /// Opening the recorded documents failed.
+    #[error("Failed to open recorded documents: {0}")]
+    Network(#[from] indras_network::IndraError),
+    /// Unrecognized viewer format name.
+    #[error("Unknown recording format: {0}")]
+    UnknownFormat(String),
+    /// Unrecognized redaction option.
+    #[error("Unknown redaction option: {0}")]
+    UnknownRedaction(String),
+}
+
+/// Which viewer the JSONL is written for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ViewerFormat {
+    /// realm-viewer: quests, attention, blessings, and token flow.
+    #[default]
+    Realm,
+    /// home-viewer: one member's quests, proofs, and blessings.
+    Home,
+}
+
+impl FromStr for ViewerFormat {
+    type Err = RecordingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "realm" | "realm-viewer" => Ok(ViewerFormat::Realm),
+            "home" | "home-viewer" => Ok(ViewerFormat::Home),
+            other => Err(RecordingError::UnknownFormat(other.to_string())),
+        }
+    }
+}
+
+/// What to strip from a recording before it is shared.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Redaction {
+    /// Replace member names with `member-N`.
+    pub members: bool,
+    /// Replace quest titles with `quest-N`.
+    pub text: bool,
+}
+
+impl Redaction {
+    /// Redact everything.
+    pub fn all() -> Self {
+        Self { members: true, text: true }
+    }
+}
+
+impl FromStr for Redaction {
+    type Err = RecordingError;
+
+    /// Parse a comma-separated list: `members`, `text`, `all`, or `none`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut redaction = Redaction::default();
+        for part in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            match part.to_lowercase().as_str() {
+                "none" => {}
+                "members" => redaction.members = true,
+                "text" => redaction.text = true,
+                "all" => redaction = Redaction::all(),
+                other => return Err(RecordingError::UnknownRedaction(other.to_string())),
+            }
+        }
+        Ok(redaction)
+    }
+}
+
+/// A change in a recorded realm, before it is rendered for a viewer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionEvent {
+    /// A quest was created.
+    QuestCreated { intention_id: IntentionId },
+    /// A member submitted a claim on a quest.
+    ClaimSubmitted {
+        intention_id: IntentionId,
+        claimant: MemberId,
+        claim_index: usize,
+    },
+    /// A claim was verified.
+    ClaimVerified {
+        intention_id: IntentionId,
+        claimant: MemberId,
+        claim_index: usize,
+    },
+    /// A quest was completed.
+    QuestCompleted { intention_id: IntentionId },
+    /// A member focused on a quest.
+    AttentionSwitched {
+        member: MemberId,
+        intention_id: IntentionId,
+        event_id: AttentionEventId,
+    },
+    /// A member cleared their attention.
+    AttentionCleared { member: MemberId },
+    /// A blesser released attention to a claimant.
+    BlessingGiven {
+        blessing_id: BlessingId,
+        intention_id: IntentionId,
+        claimant: MemberId,
+        blesser: MemberId,
+        event_indices: Vec<usize>,
+    },
+    /// A blessing minted a token of gratitude.
+    TokenMinted {
+        token_id: TokenOfGratitudeId,
+        steward: MemberId,
+        blesser: MemberId,
+        source_intention_id: IntentionId,
+        event_indices: Vec<usize>,
+    },
+    /// A steward pledged a token to a quest.
+    GratitudePledged {
+        token_id: TokenOfGratitudeId,
+        pledger: MemberId,
+        target_intention_id: IntentionId,
+        event_indices: Vec<usize>,
+    },
+    /// A pledged token moved to a new steward.
+    GratitudeReleased {
+        token_id: TokenOfGratitudeId,
+        from_steward: MemberId,
+        to_steward: MemberId,
+        target_intention_id: Option<IntentionId>,
+        event_indices: Vec<usize>,
+    },
+    /// A pledge was withdrawn.
+    GratitudeWithdrawn {
+        token_id: TokenOfGratitudeId,
+        steward: MemberId,
+        target_intention_id: Option<IntentionId>,
+        event_indices: Vec<usize>,
+    },
+}
+
+/// Session events between two states of an intentions document.
+pub fn diff_intentions(old: &IntentionDocument, new: &IntentionDocument) -> Vec<SessionEvent> {
+    let mut events = Vec::new();
+    for intention in new.intentions.iter().filter(|i| !i.is_deleted()) {
+        let before = old.find(&intention.id);
+        if before.is_none() {
+            events.push(SessionEvent::QuestCreated { intention_id: intention.id });
+        }
+        for (claim_index, claim) in intention.claims.iter().enumerate() {
+            let old_claim = before.and_then(|i| i.claims.get(claim_index));
+            if old_claim.is_none() {
+                events.push(SessionEvent::ClaimSubmitted {
+                    intention_id: intention.id,
+                    claimant: claim.claimant,
+                    claim_index,
+                });
+            }
+            if claim.verified && !old_claim.is_some_and(|c| c.verified) {
+                events.push(SessionEvent::ClaimVerified {
+                    intention_id: intention.id,
+                    claimant: claim.claimant,
+                    claim_index,
+                });
+            }
+        }
+        if intention.is_complete() && !before.is_some_and(|i| i.is_complete()) {
+            events.push(SessionEvent::QuestCompleted { intention_id: intention.id });
+        }
+    }
+    events
+}
+
+/// Session events for attention switches added between two documents.
+pub fn diff_attention(old: &AttentionDocument, new: &AttentionDocument) -> Vec<SessionEvent> {
+    let seen: HashSet<AttentionEventId> = old.events().iter().map(|e| e.event_id).collect();
+    let mut added: Vec<_> = new
+        .events()
+        .iter()
+        .filter(|e| !seen.contains(&e.event_id))
+        .collect();
+    added.sort_by_key(|e| (e.timestamp_millis, e.logical_clock));
+    added
+        .into_iter()
+        .map(|e| match e.intention_id {
+            Some(intention_id) => SessionEvent::AttentionSwitched {
+                member: e.member,
+                intention_id,
+                event_id: e.event_id,
+            },
+            None => SessionEvent::AttentionCleared { member: e.member },
+        })
+        .collect()
+}
+
+/// Session events for blessings added between two documents.
+pub fn diff_blessings(old: &BlessingDocument, new: &BlessingDocument) -> Vec<SessionEvent> {
+    let seen: HashSet<BlessingId> = old.blessings().iter().map(|b| b.blessing_id).collect();
+    new.blessings()
+        .iter()
+        .filter(|b| !seen.contains(&b.blessing_id))
+        .map(|b| SessionEvent::BlessingGiven {
+            blessing_id: b.blessing_id,
+            intention_id: b.intention_id(),
+            claimant: b.claimant(),
+            blesser: b.blesser,
+            event_indices: b.event_indices.clone(),
+        })
+        .collect()
+}
+
+/// Session events for token log entries added between two documents.
+///
+/// Pledge state before the change comes from `old`, so a release or
+/// withdrawal reports the quest the token was pledged to.
+pub fn diff_tokens(
+    old: &TokenOfGratitudeDocument,
+    new: &TokenOfGratitudeDocument,
+) -> Vec<SessionEvent> {
+    let indices = |id: &TokenOfGratitudeId| {
+        new.find(id)
+            .or_else(|| old.find(id))
+            .map(|t| t.event_indices.clone())
+            .unwrap_or_default()
+    };
+    new.events()
+        .iter()
+        .filter(|e| !old.events().contains(e))
+        .filter_map(|e| match e {
+            TokenEvent::Minted {
+                token_id,
+                steward,
+                blesser,
+                source_intention_id,
+                event_indices,
+                ..
+            } => Some(SessionEvent::TokenMinted {
+                token_id: *token_id,
+                steward: *steward,
+                blesser: *blesser,
+                source_intention_id: *source_intention_id,
+                event_indices: event_indices.clone(),
+            }),
+            TokenEvent::Pledged { token_id, target_intention_id, .. } => {
+                let token = new.find(token_id).or_else(|| old.find(token_id))?;
+                Some(SessionEvent::GratitudePledged {
+                    token_id: *token_id,
+                    pledger: token.steward,
+                    target_intention_id: *target_intention_id,
+                    event_indices: token.event_indices.clone(),
+                })
+            }
+            TokenEvent::Released { token_id, new_steward, previous_steward } => {
+                Some(SessionEvent::GratitudeReleased {
+                    token_id: *token_id,
+                    from_steward: *previous_steward,
+                    to_steward: *new_steward,
+                    target_intention_id: old.find(token_id).and_then(|t| t.pledged_to),
+                    event_indices: indices(token_id),
+                })
+            }
+            TokenEvent::Withdrawn { token_id } => {
+                let token = new.find(token_id).or_else(|| old.find(token_id))?;
+                Some(SessionEvent::GratitudeWithdrawn {
+                    token_id: *token_id,
+                    steward: token.steward,
+                    target_intention_id: old.find(token_id).and_then(|t| t.pledged_to),
+                    event_indices: token.event_indices.clone(),
+                })
+            }
+            TokenEvent::Valued { .. } => None,
+        })
+        .collect()
+}
+
+/// Writes session events as viewer JSONL.
+///
+/// Holds the last seen state of each recorded document; the `*_changed`
+/// methods diff against it, write the resulting events, and keep the new
+/// state.
+pub struct SessionRecorder<W: Write> {
+    writer: W,
+    format: ViewerFormat,
+    redaction: Redaction,
+    me: MemberId,
+    realm_id: String,
+    tick: u32,
+    names: HashMap<MemberId, String>,
+    member_pseudonyms: HashMap<MemberId, String>,
+    quest_pseudonyms: HashMap<IntentionId, String>,
+    intentions: IntentionDocument,
+    attention: AttentionDocument,
+    blessings: BlessingDocument,
+    tokens: TokenOfGratitudeDocument,
+}
+
+impl<W: Write> SessionRecorder<W> {
+    /// Create a recorder for the local member `me` in `realm_id`.
+    pub fn new(
+        writer: W,
+        format: ViewerFormat,
+        redaction: Redaction,
+        me: MemberId,
+        realm_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            writer,
+            format,
+            redaction,
+            me,
+            realm_id: realm_id.into(),
+            tick: 0,
+            names: HashMap::new(),
+            member_pseudonyms: HashMap::new(),
+            quest_pseudonyms: HashMap::new(),
+            intentions: IntentionDocument::default(),
+            attention: AttentionDocument::default(),
+            blessings: BlessingDocument::default(),
+            tokens: TokenOfGratitudeDocument::default(),
+        }
+    }
+
+    /// Set the display name recorded for a member.
+    pub fn set_name(&mut self, member: MemberId, name: impl Into<String>) {
+        self.names.insert(member, name.into());
+    }
+
+    /// Current tick.
+    pub fn tick(&self) -> u32 {
+        self.tick
+    }
+
+    /// Write the session start and take the documents' current state as the
+    /// baseline. Existing quests are not replayed.
+    pub fn start(
+        &mut self,
+        intentions: IntentionDocument,
+        attention: AttentionDocument,
+        blessings: BlessingDocument,
+        tokens: TokenOfGratitudeDocument,
+    ) -> io::Result<()> {
+        self.intentions = intentions;
+        self.attention = attention;
+        self.blessings = blessings;
+        self.tokens = tokens;
+
+        let event = match self.format {
+            ViewerFormat::Realm => {
+                let mut members: Vec<MemberId> = vec![self.me];
+                members.extend(self.names.keys().filter(|m| **m != self.me));
+                let members: Vec<String> = members.iter().map(|m| self.member(m)).collect();
+                json!({
+                    "event_type": "realm_created",
+                    "tick": self.tick,
+                    "realm_id": self.realm_id,
+                    "members": members.join(","),
+                    "member_count": members.len(),
+                })
+            }
+            ViewerFormat::Home => json!({
+                "event_type": "session_started",
+                "tick": self.tick,
+                "member": self.member(&self.me),
+                "realm_id": self.realm_id,
+            }),
+        };
+        self.write_line(&event)
+    }
+
+    /// Record changes to the intentions document.
+    pub fn intentions_changed(&mut self, new: IntentionDocument) -> io::Result<()> {
+        let events = diff_intentions(&self.intentions, &new);
+        self.intentions = new;
+        self.write_events(events)
+    }
+
+    /// Record changes to the attention document.
+    pub fn attention_changed(&mut self, new: AttentionDocument) -> io::Result<()> {
+        let events = diff_attention(&self.attention, &new);
+        self.attention = new;
+        self.write_events(events)
+    }
+
+    /// Record changes to the blessings document.
+    pub fn blessings_changed(&mut self, new: BlessingDocument) -> io::Result<()> {
+        let events = diff_blessings(&self.blessings, &new);
+        self.blessings = new;
+        self.write_events(events)
+    }
+
+    /// Record changes to the tokens document.
+    pub fn tokens_changed(&mut self, new: TokenOfGratitudeDocument) -> io::Result<()> {
+        let events = diff_tokens(&self.tokens, &new);
+        self.tokens = new;
+        self.write_events(events)
+    }
+
+    /// Write the session end (home-viewer only) and return the writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        if self.format == ViewerFormat::Home {
+            self.tick += 1;
+            let quests_count = self
+                .intentions
+                .intentions
+                .iter()
+                .filter(|i| !i.is_deleted() && i.creator == self.me)
+                .count();
+            let event = json!({
+                "event_type": "session_ended",
+                "tick": self.tick,
+                "member": self.member(&self.me),
+                "realm_id": self.realm_id,
+                "notes_count": 0,
+                "quests_count": quests_count,
+                "artifacts_count": 0,
+            });
+            self.write_line(&event)?;
+        }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn write_events(&mut self, events: Vec<SessionEvent>) -> io::Result<()> {
+        if events.is_empty() {
+            return Ok(());
+        }
+        self.tick += 1;
+        for event in &events {
+            if let Some(line) = self.render(event) {
+                self.write_line(&line)?;
+            }
+        }
+        self.writer.flush()
+    }
+
+    fn write_line(&mut self, value: &Value) -> io::Result<()> {
+        serde_json::to_writer(&mut self.writer, value)?;
+        self.writer.write_all(b"\n")
+    }
+
+    /// Name for a member, pseudonymized if members are redacted.
+    fn member(&mut self, id: &MemberId) -> String {
+        if self.redaction.members {
+            let next = self.member_pseudonyms.len() + 1;
+            return self
+                .member_pseudonyms
+                .entry(*id)
+                .or_insert_with(|| format!("member-{}", next))
+                .clone();
+        }
+        self.names
+            .get(id)
+            .cloned()
+            .unwrap_or_else(|| hex::encode(&id[..4]))
+    }
+
+    /// Title for a quest, pseudonymized if text is redacted.
+    fn title(&mut self, id: &IntentionId) -> String {
+        if self.redaction.text {
+            let next = self.quest_pseudonyms.len() + 1;
+            return self
+                .quest_pseudonyms
+                .entry(*id)
+                .or_insert_with(|| format!("quest-{}", next))
+                .clone();
+        }
+        self.intentions
+            .find(id)
+            .map(|i| i.title.clone())
+            .unwrap_or_default()
+    }
+
+    fn attention_millis(&self, event_indices: &[usize]) -> u64 {
+        self.attention.compute_attention_millis(event_indices, None)
+    }
+
+    /// Render an event as a viewer JSON object, or `None` if the format has
+    /// no such event.
+    fn render(&mut self, event: &SessionEvent) -> Option<Value> {
+        let tick = self.tick;
+        let realm_id = self.realm_id.clone();
+        let value = match (self.format, event) {
+            (ViewerFormat::Realm, SessionEvent::QuestCreated { intention_id }) => {
+                let creator = self.intentions.find(intention_id)?.creator;
+                json!({
+                    "event_type": "quest_created",
+                    "tick": tick,
+                    "realm_id": realm_id,
+                    "quest_id": hex::encode(intention_id),
+                    "creator": self.member(&creator),
+                    "title": self.title(intention_id),
+                })
+            }
+            (ViewerFormat::Home, SessionEvent::QuestCreated { intention_id }) => {
+                let creator = self.intentions.find(intention_id)?.creator;
+                json!({
+                    "event_type": "home_quest_created",
+                    "tick": tick,
+                    "member": self.member(&creator),
+                    "quest_id": hex::encode(intention_id),
+                    "title": self.title(intention_id),
+                    "latency_us": 0,
+                })
+            }
+            (format, SessionEvent::ClaimSubmitted { intention_id, claimant, claim_index }) => {
+                let proof = self
+                    .intentions
+                    .find(intention_id)
+                    .and_then(|i| i.claims.get(*claim_index))
+                    .and_then(|c| c.proof)
+                    .map(|p| p.to_string())
+                    .unwrap_or_default();
+                match format {
+                    ViewerFormat::Realm => json!({
+                        "event_type": "quest_claim_submitted",
+                        "tick": tick,
+                        "realm_id": realm_id,
+                        "quest_id": hex::encode(intention_id),
+                        "claimant": self.member(claimant),
+                        "claim_index": claim_index,
+                        "proof_artifact": proof,
+                    }),
+                    ViewerFormat::Home => json!({
+                        "event_type": "proof_submitted",
+                        "tick": tick,
+                        "member": self.member(claimant),
+                        "quest_id": hex::encode(intention_id),
+                        "quest_title": self.title(intention_id),
+                        "artifact_id": proof,
+                    }),
+                }
+            }
+            (ViewerFormat::Realm, SessionEvent::ClaimVerified { intention_id, claim_index, .. }) => {
+                json!({
+                    "event_type": "quest_claim_verified",
+                    "tick": tick,
+                    "realm_id": realm_id,
+                    "quest_id": hex::encode(intention_id),
+                    "claim_index": claim_index,
+                })
+            }
+            (ViewerFormat::Realm, SessionEvent::QuestCompleted { intention_id }) => {
+                let intention = self.intentions.find(intention_id)?;
+                let verified = intention.claims.iter().filter(|c| c.verified).count();
+                let pending = intention.claims.len() - verified;
+                json!({
+                    "event_type": "quest_completed",
+                    "tick": tick,
+                    "realm_id": realm_id,
+                    "quest_id": hex::encode(intention_id),
+                    "verified_claims": verified,
+                    "pending_claims": pending,
+                })
+            }
+            (ViewerFormat::Home, SessionEvent::QuestCompleted { intention_id }) => {
+                let creator = self.intentions.find(intention_id)?.creator;
+                json!({
+                    "event_type": "home_quest_completed",
+                    "tick": tick,
+                    "member": self.member(&creator),
+                    "quest_id": hex::encode(intention_id),
+                })
+            }
+            (ViewerFormat::Realm, SessionEvent::AttentionSwitched { member, intention_id, event_id }) => {
+                json!({
+                    "event_type": "attention_switched",
+                    "tick": tick,
+                    "member": self.member(member),
+                    "quest_id": hex::encode(intention_id),
+                    "event_id": hex::encode(event_id),
+                })
+            }
+            (ViewerFormat::Realm, SessionEvent::AttentionCleared { member }) => json!({
+                "event_type": "attention_cleared",
+                "tick": tick,
+                "member": self.member(member),
+                "clear_worked": true,
+            }),
+            (
+                format,
+                SessionEvent::BlessingGiven { intention_id, claimant, blesser, event_indices, .. },
+            ) => {
+                let attention_millis = self.attention_millis(event_indices);
+                let mut value = json!({
+                    "event_type": "blessing_given",
+                    "tick": tick,
+                    "quest_id": hex::encode(intention_id),
+                    "claimant": self.member(claimant),
+                    "blesser": self.member(blesser),
+                    "event_count": event_indices.len(),
+                    "attention_millis": attention_millis,
+                });
+                match format {
+                    ViewerFormat::Realm => value["realm_id"] = json!(realm_id),
+                    ViewerFormat::Home => value["quest_title"] = json!(self.title(intention_id)),
+                }
+                value
+            }
+            (
+                ViewerFormat::Realm,
+                SessionEvent::TokenMinted { token_id, steward, blesser, source_intention_id, event_indices },
+            ) => json!({
+                "event_type": "token_minted",
+                "tick": tick,
+                "realm_id": realm_id,
+                "token_id": hex::encode(token_id),
+                "steward": self.member(steward),
+                "value_millis": self.attention_millis(event_indices),
+                "blesser": self.member(blesser),
+                "source_quest_id": hex::encode(source_intention_id),
+            }),
+            (
+                ViewerFormat::Realm,
+                SessionEvent::GratitudePledged { token_id, pledger, target_intention_id, event_indices },
+            ) => json!({
+                "event_type": "gratitude_pledged",
+                "tick": tick,
+                "realm_id": realm_id,
+                "token_id": hex::encode(token_id),
+                "pledger": self.member(pledger),
+                "target_quest_id": hex::encode(target_intention_id),
+                "amount_millis": self.attention_millis(event_indices),
+            }),
+            (
+                ViewerFormat::Realm,
+                SessionEvent::GratitudeReleased {
+                    token_id,
+                    from_steward,
+                    to_steward,
+                    target_intention_id,
+                    event_indices,
+                },
+            ) => json!({
+                "event_type": "gratitude_released",
+                "tick": tick,
+                "token_id": hex::encode(token_id),
+                "from_steward": self.member(from_steward),
+                "to_steward": self.member(to_steward),
+                "target_quest_id": target_intention_id.map(hex::encode).unwrap_or_default(),
+                "amount_millis": self.attention_millis(event_indices),
+            }),
+            (
+                ViewerFormat::Realm,
+                SessionEvent::GratitudeWithdrawn { token_id, steward, target_intention_id, event_indices },
+            ) => json!({
+                "event_type": "gratitude_withdrawn",
+                "tick": tick,
+                "token_id": hex::encode(token_id),
+                "steward": self.member(steward),
+                "target_quest_id": target_intention_id.map(hex::encode).unwrap_or_default(),
+                "amount_millis": self.attention_millis(event_indices),
+            }),
+            // home-viewer has no attention, verification, or token events.
+            (ViewerFormat::Home, _) => return None,
+        };
+        Some(value)
+    }
+}
+
+/// The documents a recording watches.
+pub struct SessionSource {
+    /// Quests and claims.
+    pub intentions: Document<IntentionDocument>,
+    /// Attention switches.
+    pub attention: Document<AttentionDocument>,
+    /// Blessings.
+    pub blessings: Document<BlessingDocument>,
+    /// Tokens of gratitude.
+    pub tokens: Document<TokenOfGratitudeDocument>,
+}
+
+impl SessionSource {
+    /// Watch the documents of a shared realm.
+    pub async fn realm(realm: &Realm) -> Result<Self, RecordingError> {
+        Ok(Self {
+            intentions: realm.document("intentions").await?,
+            attention: realm.document("attention").await?,
+            blessings: realm.document("blessings").await?,
+            tokens: realm.document("_tokens").await?,
+        })
+    }
+
+    /// Watch the documents of the home realm.
+    pub async fn home(home: &HomeRealm) -> Result<Self, RecordingError> {
+        Ok(Self {
+            intentions: home.document("intentions").await?,
+            attention: home.document("attention").await?,
+            blessings: home.document("blessings").await?,
+            tokens: home.document("_tokens").await?,
+        })
+    }
+}
+
+enum Change {
+    Intentions(IntentionDocument),
+    Attention(AttentionDocument),
+    Blessings(BlessingDocument),
+    Tokens(TokenOfGratitudeDocument),
+}
+
+/// Record a session until every watched document closes.
+///
+/// Writes the start event, then one batch of lines per document change.
+/// Returns the writer after [`SessionRecorder::finish`].
+pub async fn record_session<W: Write>(
+    source: SessionSource,
+    mut recorder: SessionRecorder<W>,
+) -> Result<W, RecordingError> {
+    // Subscribe before reading so no change falls between the two.
+    let mut changes = futures::stream::select_all([
+        broadcast_to_stream(source.intentions.subscribe())
+            .map(|c| Change::Intentions(c.new_state))
+            .boxed(),
+        broadcast_to_stream(source.attention.subscribe())
+            .map(|c| Change::Attention(c.new_state))
+            .boxed(),
+        broadcast_to_stream(source.blessings.subscribe())
+            .map(|c| Change::Blessings(c.new_state))
+            .boxed(),
+        broadcast_to_stream(source.tokens.subscribe())
+            .map(|c| Change::Tokens(c.new_state))
+            .boxed(),
+    ]);
+
+    recorder.start(
+        source.intentions.read().await.clone(),
+        source.attention.read().await.clone(),
+        source.blessings.read().await.clone(),
+        source.tokens.read().await.clone(),
+    )?;
+
+    while let Some(change) = changes.next().await {
+        match change {
+            Change::Intentions(doc) => recorder.intentions_changed(doc)?,
+            Change::Attention(doc) => recorder.attention_changed(doc)?,
+            Change::Blessings(doc) => recorder.blessings_changed(doc)?,
+            Change::Tokens(doc) => recorder.tokens_changed(doc)?,
+        }
+    }
+
+    // Hold the documents until the end so their change channels stay open.
+    drop(source);
+    Ok(recorder.finish()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blessing::ClaimId;
+    use crate::intention::Intention;
+
+    fn test_member_id(n: u8) -> MemberId {
+        [n; 32]
+    }
+
+    fn lines(recorder: SessionRecorder<Vec<u8>>) -> Vec<Value> {
+        let out = recorder.finish().unwrap();
+        String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_options() {
+        assert_eq!("".parse::<Redaction>().unwrap(), Redaction::default());
+        assert_eq!(
+            "members".parse::<Redaction>().unwrap(),
+            Redaction { members: true, text: false }
+        );
+        assert_eq!("text, members".parse::<Redaction>().unwrap(), Redaction::all());
+        assert_eq!("all".parse::<Redaction>().unwrap(), Redaction::all());
+        assert!("names".parse::<Redaction>().is_err());
+
+        assert_eq!("home".parse::<ViewerFormat>().unwrap(), ViewerFormat::Home);
+        assert_eq!("realm-viewer".parse::<ViewerFormat>().unwrap(), ViewerFormat::Realm);
+        assert!("lua".parse::<ViewerFormat>().is_err());
+    }
+
+    #[test]
+    fn test_diff_intentions_lifecycle() {
+        let creator = test_member_id(1);
+        let claimant = test_member_id(2);
+        let empty = IntentionDocument::default();
+
+        let mut created = empty.clone();
+        created.add(Intention::new("Garden", "", None, creator));
+        let id = created.intentions[0].id;
+        assert_eq!(
+            diff_intentions(&empty, &created),
+            vec![SessionEvent::QuestCreated { intention_id: id }]
+        );
+
+        let mut claimed = created.clone();
+        claimed.find_mut(&id).unwrap().submit_claim(claimant, None).unwrap();
+        claimed.find_mut(&id).unwrap().verify_claim(0).unwrap();
+        assert_eq!(
+            diff_intentions(&created, &claimed),
+            vec![
+                SessionEvent::ClaimSubmitted { intention_id: id, claimant, claim_index: 0 },
+                SessionEvent::ClaimVerified { intention_id: id, claimant, claim_index: 0 },
+            ]
+        );
+
+        let mut completed = claimed.clone();
+        completed.find_mut(&id).unwrap().complete().unwrap();
+        assert_eq!(
+            diff_intentions(&claimed, &completed),
+            vec![SessionEvent::QuestCompleted { intention_id: id }]
+        );
+        assert!(diff_intentions(&completed, &completed).is_empty());
+    }
+
+    #[test]
+    fn test_diff_attention_and_blessings() {
+        let member = test_member_id(1);
+        let claimant = test_member_id(2);
+        let intention_id = [7u8; 16];
+
+        let mut attention = AttentionDocument::default();
+        let before = attention.clone();
+        let event_id = attention.focus_on_intention(member, intention_id);
+        attention.clear_attention(member);
+        assert_eq!(
+            diff_attention(&before, &attention),
+            vec![
+                SessionEvent::AttentionSwitched { member, intention_id, event_id },
+                SessionEvent::AttentionCleared { member },
+            ]
+        );
+
+        let mut blessings = BlessingDocument::default();
+        let before = blessings.clone();
+        let blessing_id = blessings
+            .bless_claim(ClaimId::new(intention_id, claimant), member, vec![0])
+            .unwrap();
+        assert_eq!(
+            diff_blessings(&before, &blessings),
+            vec![SessionEvent::BlessingGiven {
+                blessing_id,
+                intention_id,
+                claimant,
+                blesser: member,
+                event_indices: vec![0],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_diff_tokens_reports_pledge_target() {
+        let steward = test_member_id(1);
+        let blesser = test_member_id(2);
+        let next = test_member_id(3);
+        let source = [1u8; 16];
+        let target = [2u8; 16];
+
+        let mut tokens = TokenOfGratitudeDocument::default();
+        let token_id = tokens.mint(steward, [9u8; 16], blesser, source, vec![0, 1]).unwrap();
+        tokens.pledge(token_id, target).unwrap();
+        let pledged = tokens.clone();
+        tokens.release(token_id, next).unwrap();
+
+        assert_eq!(
+            diff_tokens(&pledged, &tokens),
+            vec![SessionEvent::GratitudeReleased {
+                token_id,
+                from_steward: steward,
+                to_steward: next,
+                target_intention_id: Some(target),
+                event_indices: vec![0, 1],
+            }]
+        );
+        assert_eq!(diff_tokens(&TokenOfGratitudeDocument::default(), &pledged).len(), 2);
+    }
+
+    #[test]
+    fn test_recorder_writes_realm_viewer_lines() {
+        let me = test_member_id(1);
+        let mut recorder =
+            SessionRecorder::new(Vec::new(), ViewerFormat::Realm, Redaction::default(), me, "r1");
+        recorder.set_name(me, "Zephyr");
+        recorder
+            .start(
+                IntentionDocument::default(),
+                AttentionDocument::default(),
+                BlessingDocument::default(),
+                TokenOfGratitudeDocument::default(),
+            )
+            .unwrap();
+
+        let mut intentions = IntentionDocument::default();
+        intentions.add(Intention::new("Garden", "", None, me));
+        let id = intentions.intentions[0].id;
+        recorder.intentions_changed(intentions).unwrap();
+
+        let mut attention = AttentionDocument::default();
+        attention.focus_on_intention(me, id);
+        recorder.attention_changed(attention).unwrap();
+
+        let lines = lines(recorder);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["event_type"], "realm_created");
+        assert_eq!(lines[0]["members"], "Zephyr");
+        assert_eq!(lines[1]["event_type"], "quest_created");
+        assert_eq!(lines[1]["title"], "Garden");
+        assert_eq!(lines[1]["quest_id"], hex::encode(id));
+        assert_eq!(lines[1]["tick"], 1);
+        assert_eq!(lines[2]["event_type"], "attention_switched");
+        assert_eq!(lines[2]["member"], "Zephyr");
+        assert_eq!(lines[2]["tick"], 2);
+    }
+
+    #[test]
+    fn test_recorder_redacts_names_and_titles() {
+        let me = test_member_id(1);
+        let mut recorder =
+            SessionRecorder::new(Vec::new(), ViewerFormat::Home, Redaction::all(), me, "home");
+        recorder.set_name(me, "Zephyr");
+        recorder
+            .start(
+                IntentionDocument::default(),
+                AttentionDocument::default(),
+                BlessingDocument::default(),
+                TokenOfGratitudeDocument::default(),
+            )
+            .unwrap();
+
+        let mut intentions = IntentionDocument::default();
+        intentions.add(Intention::new("Private errand", "", None, me));
+        intentions.add(Intention::new("Another", "", None, test_member_id(2)));
+        recorder.intentions_changed(intentions).unwrap();
+
+        let lines = lines(recorder);
+        let text: String = lines.iter().map(|l| l.to_string()).collect();
+        assert!(!text.contains("Zephyr"));
+        assert!(!text.contains("Private errand"));
+        assert_eq!(lines[0]["event_type"], "session_started");
+        assert_eq!(lines[0]["member"], "member-1");
+        assert_eq!(lines[1]["title"], "quest-1");
+        assert_eq!(lines[2]["member"], "member-2");
+        assert_eq!(lines[2]["title"], "quest-2");
+        assert_eq!(lines[3]["event_type"], "session_ended");
+        assert_eq!(lines[3]["quests_count"], 1);
+    }
+}
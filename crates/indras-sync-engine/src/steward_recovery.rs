@@ -125,6 +125,12 @@ pub enum StewardRecoveryError {
 
     #[error("steward count {n} cannot satisfy threshold {k} (need k <= n <= 255)")]
     InvalidStewardCount { n: usize, k: u8 },
+
+    #[error("release belongs to a different account or split")]
+    MismatchedRelease,
+
+    #[error("only {have} of {need} shares released")]
+    NotEnoughShares { have: usize, need: u8 },
 }
 
 /// Result alias for steward-recovery operations.
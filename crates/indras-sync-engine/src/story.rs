@@ -0,0 +1,529 @@
+//! Story threads — collaborative narrative documents.
+//!
+//! A story is an ordered sequence of leaves: messages, images, tokens and
+//! attestations. Each leaf carries a fractional position key, so any member
+//! can insert between two leaves without renumbering the rest.
+//!
+//! Ordering is by `(position, leaf id)`. Concurrent inserts at the same spot
+//! produce the same position key; the leaf ID breaks the tie identically on
+//! every replica, and no existing leaf ever moves.
+//!
+//! Each story lives in its own document under [`story_doc_key`]; the realm's
+//! [`StoryIndexDocument`] lists them.
+
+use indras_network::artifact::ArtifactId;
+use indras_network::member::MemberId;
+use serde::{Deserialize, Serialize};
+
+use crate::intention::generate_intention_id;
+use crate::token_of_gratitude::TokenOfGratitudeId;
+
+/// Document name for the realm's story index.
+pub const STORY_INDEX_DOC: &str = "stories";
+
+/// Doc key prefix for individual story documents.
+pub const STORY_KEY_PREFIX: &str = "_story:";
+
+/// Unique identifier for a story (16 bytes).
+pub type StoryId = [u8; 16];
+
+/// Unique identifier for a leaf within a story (16 bytes).
+pub type LeafId = [u8; 16];
+
+/// The doc key for a story's document.
+pub fn story_doc_key(story_id: &StoryId) -> String {
+    format!("{}{}", STORY_KEY_PREFIX, hex::encode(story_id))
+}
+
+/// Digits of a position key, in ascending byte order.
+const DIGITS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+fn digit_value(c: u8) -> usize {
+    DIGITS
+        .iter()
+        .position(|&d| d == c)
+        .expect("position keys only contain base-62 digits")
+}
+
+/// Generate a position key strictly between `before` and `after`.
+///
+/// Keys are base-62 fractions compared as strings. `None` stands for the
+/// start (for `before`) or end (for `after`) of the story. If `after` does
+/// not sort after `before` it is ignored, so the result always sorts after
+/// `before`.
+pub fn position_between(before: Option<&str>, after: Option<&str>) -> String {
+    let before = before.unwrap_or("").as_bytes();
+    let after = after.map(str::as_bytes).filter(|a| *a > before);
+    let digits = midpoint(before, after);
+    String::from_utf8(digits).expect("base-62 digits are ASCII")
+}
+
+/// Midpoint of two keys, where `a < b` and neither ends in the zero digit.
+fn midpoint(a: &[u8], b: Option<&[u8]>) -> Vec<u8> {
+    let digit_a = |i: usize| a.get(i).map_or(0, |&c| digit_value(c));
+
+    if let Some(b) = b {
+        // Keep the shared prefix, then recurse on the remainder
+        let shared = b
+            .iter()
+            .enumerate()
+            .take_while(|&(i, &c)| digit_a(i) == digit_value(c))
+            .count();
+        if shared > 0 {
+            let mut key = b[..shared].to_vec();
+            key.extend(midpoint(a.get(shared..).unwrap_or(&[]), Some(&b[shared..])));
+            return key;
+        }
+    }
+
+    let low = digit_a(0);
+    let high = b.map_or(DIGITS.len(), |b| digit_value(b[0]));
+    if high - low > 1 {
+        return vec![DIGITS[(low + high) / 2]];
+    }
+    match b {
+        // `b`'s first digit alone sorts between them
+        Some(b) if b.len() > 1 => vec![b[0]],
+        _ => {
+            let mut key = vec![DIGITS[low]];
+            key.extend(midpoint(a.get(1..).unwrap_or(&[]), None));
+            key
+        }
+    }
+}
+
+/// What a leaf holds.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum LeafKind {
+    /// A text message.
+    Message {
+        /// Message text.
+        text: String,
+    },
+    /// An image artifact.
+    Image {
+        /// The image artifact.
+        artifact_id: ArtifactId,
+        /// Optional caption.
+        caption: Option<String>,
+    },
+    /// A token of gratitude woven into the story.
+    Token {
+        /// The token.
+        token_id: TokenOfGratitudeId,
+        /// Optional note.
+        note: Option<String>,
+    },
+    /// An attestation vouching for something in the story.
+    Attestation {
+        /// What is being attested.
+        statement: String,
+        /// The leaf being attested to, if any.
+        subject: Option<LeafId>,
+    },
+}
+
+impl LeafKind {
+    /// Display label for the kind.
+    pub fn label(&self) -> &'static str {
+        match self {
+            LeafKind::Message { .. } => "Message",
+            LeafKind::Image { .. } => "Image",
+            LeafKind::Token { .. } => "Token",
+            LeafKind::Attestation { .. } => "Attestation",
+        }
+    }
+}
+
+/// One entry in a story.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StoryLeaf {
+    /// Unique leaf ID.
+    pub id: LeafId,
+    /// Who added the leaf.
+    pub author: MemberId,
+    /// What the leaf holds.
+    pub kind: LeafKind,
+    /// Fractional position key (see [`position_between`]).
+    pub position: String,
+    /// When the leaf was added (Unix timestamp in milliseconds).
+    pub created_at_millis: i64,
+    /// Whether the author removed the leaf (tombstone for CRDT).
+    #[serde(default)]
+    pub deleted: bool,
+}
+
+/// A story and its leaves.
+///
+/// # CRDT Semantics
+///
+/// - Set-union of leaves by ID; removal is sticky
+/// - Leaves are kept sorted by `(position, id)` so every replica agrees
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StoryDocument {
+    /// Story ID.
+    pub id: StoryId,
+    /// Story title.
+    pub title: String,
+    /// Who started the story.
+    pub creator: MemberId,
+    /// When the story was started (Unix timestamp in milliseconds).
+    pub created_at_millis: i64,
+    /// All leaves, including removed ones, in story order.
+    pub leaves: Vec<StoryLeaf>,
+}
+
+impl indras_network::document::DocumentSchema for StoryDocument {
+    fn merge(&mut self, remote: Self) {
+        if self.id == StoryId::default() {
+            self.id = remote.id;
+            self.title = remote.title;
+            self.creator = remote.creator;
+            self.created_at_millis = remote.created_at_millis;
+        }
+        for leaf in remote.leaves {
+            match self.leaves.iter_mut().find(|l| l.id == leaf.id) {
+                Some(local) => local.deleted |= leaf.deleted,
+                None => self.leaves.push(leaf),
+            }
+        }
+        self.sort();
+    }
+}
+
+impl StoryDocument {
+    /// Start a new story.
+    pub fn new(title: impl Into<String>, creator: MemberId, now: i64) -> Self {
+        Self {
+            id: generate_intention_id(),
+            title: title.into(),
+            creator,
+            created_at_millis: now,
+            leaves: Vec::new(),
+        }
+    }
+
+    fn sort(&mut self) {
+        self.leaves
+            .sort_by(|a, b| a.position.cmp(&b.position).then(a.id.cmp(&b.id)));
+    }
+
+    /// Leaves that have not been removed, in story order.
+    pub fn ordered_leaves(&self) -> impl Iterator<Item = &StoryLeaf> {
+        self.leaves.iter().filter(|l| !l.deleted)
+    }
+
+    /// Find a leaf by ID.
+    pub fn find(&self, id: &LeafId) -> Option<&StoryLeaf> {
+        self.leaves.iter().find(|l| &l.id == id)
+    }
+
+    /// Append a leaf at the end of the story.
+    pub fn append(&mut self, author: MemberId, kind: LeafKind, now: i64) -> LeafId {
+        let position = position_between(self.leaves.last().map(|l| l.position.as_str()), None);
+        self.push(author, kind, position, now)
+    }
+
+    /// Insert a leaf right after `after`, or at the start when `None`.
+    ///
+    /// If other leaves share `after`'s position, the new leaf lands after
+    /// all of them.
+    pub fn insert_after(
+        &mut self,
+        after: Option<&LeafId>,
+        author: MemberId,
+        kind: LeafKind,
+        now: i64,
+    ) -> Result<LeafId, StoryError> {
+        let before = match after {
+            Some(id) => {
+                let leaf = self.find(id).ok_or(StoryError::LeafNotFound)?;
+                Some(leaf.position.clone())
+            }
+            None => None,
+        };
+        let next = self
+            .leaves
+            .iter()
+            .map(|l| l.position.as_str())
+            .find(|p| before.as_deref().is_none_or(|b| *p > b));
+        let position = position_between(before.as_deref(), next);
+        Ok(self.push(author, kind, position, now))
+    }
+
+    fn push(&mut self, author: MemberId, kind: LeafKind, position: String, now: i64) -> LeafId {
+        let id = generate_intention_id();
+        self.leaves.push(StoryLeaf {
+            id,
+            author,
+            kind,
+            position,
+            created_at_millis: now,
+            deleted: false,
+        });
+        self.sort();
+        id
+    }
+
+    /// Remove a leaf. Only its author may.
+    pub fn remove(&mut self, id: &LeafId, caller: MemberId) -> Result<(), StoryError> {
+        let leaf = self
+            .leaves
+            .iter_mut()
+            .find(|l| &l.id == id)
+            .ok_or(StoryError::LeafNotFound)?;
+        if leaf.author != caller {
+            return Err(StoryError::NotAuthorized);
+        }
+        leaf.deleted = true;
+        Ok(())
+    }
+}
+
+/// A story listed in the realm's index.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StorySummary {
+    /// Story ID.
+    pub id: StoryId,
+    /// Story title.
+    pub title: String,
+    /// Who started the story.
+    pub creator: MemberId,
+    /// When the story was started (Unix timestamp in milliseconds).
+    pub created_at_millis: i64,
+}
+
+/// Index of the stories in a realm.
+///
+/// # CRDT Semantics
+///
+/// - Set-union by story ID
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StoryIndexDocument {
+    /// All stories, oldest first.
+    pub stories: Vec<StorySummary>,
+}
+
+impl indras_network::document::DocumentSchema for StoryIndexDocument {
+    fn merge(&mut self, remote: Self) {
+        for story in remote.stories {
+            self.add(story);
+        }
+    }
+}
+
+impl StoryIndexDocument {
+    /// Add a story (idempotent by ID), keeping the index oldest first.
+    pub fn add(&mut self, story: StorySummary) {
+        if self.stories.iter().any(|s| s.id == story.id) {
+            return;
+        }
+        self.stories.push(story);
+        self.stories.sort_by(|a, b| {
+            a.created_at_millis
+                .cmp(&b.created_at_millis)
+                .then(a.id.cmp(&b.id))
+        });
+    }
+
+    /// Find a story by ID.
+    pub fn find(&self, id: &StoryId) -> Option<&StorySummary> {
+        self.stories.iter().find(|s| &s.id == id)
+    }
+}
+
+/// Errors that can occur when editing a story.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum StoryError {
+    /// The leaf was not found.
+    #[error("Leaf not found")]
+    LeafNotFound,
+    /// The caller is not authorized for this operation.
+    #[error("Not authorized for this operation")]
+    NotAuthorized,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indras_network::document::DocumentSchema;
+
+    const ALICE: MemberId = [1u8; 32];
+    const BOB: MemberId = [2u8; 32];
+
+    fn message(text: &str) -> LeafKind {
+        LeafKind::Message {
+            text: text.to_string(),
+        }
+    }
+
+    fn texts(doc: &StoryDocument) -> Vec<String> {
+        doc.ordered_leaves()
+            .map(|l| match &l.kind {
+                LeafKind::Message { text } => text.clone(),
+                other => other.label().to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_position_between_orders() {
+        let first = position_between(None, None);
+        let after = position_between(Some(&first), None);
+        let before = position_between(None, Some(&first));
+        let middle = position_between(Some(&before), Some(&first));
+        assert!(before < middle && middle < first && first < after);
+    }
+
+    #[test]
+    fn test_position_between_adjacent_keys() {
+        // Adjacent digits force a longer key
+        let key = position_between(Some("A"), Some("B"));
+        assert!("A" < key.as_str() && key.as_str() < "B");
+        let key = position_between(Some("A"), Some("AV"));
+        assert!("A" < key.as_str() && key.as_str() < "AV");
+        let key = position_between(Some("z"), None);
+        assert!(key.as_str() > "z");
+    }
+
+    #[test]
+    fn test_repeated_inserts_stay_ordered() {
+        let mut low = position_between(None, None);
+        let high = position_between(Some(&low), None);
+        for _ in 0..200 {
+            let key = position_between(Some(&low), Some(&high));
+            assert!(low < key && key < high);
+            assert!(!key.ends_with('0'));
+            low = key;
+        }
+    }
+
+    #[test]
+    fn test_append_and_insert() {
+        let mut doc = StoryDocument::new("Creek restoration", ALICE, 0);
+        let first = doc.append(ALICE, message("one"), 1);
+        doc.append(ALICE, message("three"), 2);
+        doc.insert_after(Some(&first), BOB, message("two"), 3)
+            .unwrap();
+        doc.insert_after(None, BOB, message("zero"), 4).unwrap();
+
+        assert_eq!(texts(&doc), vec!["zero", "one", "two", "three"]);
+    }
+
+    #[test]
+    fn test_insert_after_missing_leaf() {
+        let mut doc = StoryDocument::new("Story", ALICE, 0);
+        assert_eq!(
+            doc.insert_after(Some(&[9u8; 16]), ALICE, message("x"), 1),
+            Err(StoryError::LeafNotFound)
+        );
+    }
+
+    #[test]
+    fn test_concurrent_appends_converge() {
+        let mut alice = StoryDocument::new("Story", ALICE, 0);
+        alice.append(ALICE, message("start"), 1);
+        let mut bob = alice.clone();
+
+        alice.append(ALICE, message("alice"), 2);
+        bob.append(BOB, message("bob"), 2);
+
+        let mut merged_a = alice.clone();
+        merged_a.merge(bob.clone());
+        let mut merged_b = bob;
+        merged_b.merge(alice);
+
+        assert_eq!(texts(&merged_a), texts(&merged_b));
+        assert_eq!(texts(&merged_a)[0], "start");
+    }
+
+    #[test]
+    fn test_concurrent_inserts_keep_existing_order() {
+        let mut alice = StoryDocument::new("Story", ALICE, 0);
+        let first = alice.append(ALICE, message("first"), 1);
+        alice.append(ALICE, message("last"), 2);
+        let mut bob = alice.clone();
+
+        alice
+            .insert_after(Some(&first), ALICE, message("a"), 3)
+            .unwrap();
+        bob.insert_after(Some(&first), BOB, message("b"), 3)
+            .unwrap();
+        alice.merge(bob);
+
+        let order = texts(&alice);
+        assert_eq!(order.first().map(String::as_str), Some("first"));
+        assert_eq!(order.last().map(String::as_str), Some("last"));
+        assert_eq!(order.len(), 4);
+    }
+
+    #[test]
+    fn test_remove_is_author_only_and_sticky() {
+        let mut doc = StoryDocument::new("Story", ALICE, 0);
+        let leaf = doc.append(ALICE, message("oops"), 1);
+        let stale = doc.clone();
+
+        assert_eq!(doc.remove(&leaf, BOB), Err(StoryError::NotAuthorized));
+        doc.remove(&leaf, ALICE).unwrap();
+        doc.merge(stale);
+        assert_eq!(doc.ordered_leaves().count(), 0);
+    }
+
+    #[test]
+    fn test_merge_into_empty_takes_metadata() {
+        let story = StoryDocument::new("Story", ALICE, 5);
+        let mut empty = StoryDocument::default();
+        empty.merge(story.clone());
+        assert_eq!(empty.id, story.id);
+        assert_eq!(empty.title, "Story");
+    }
+
+    #[test]
+    fn test_leaf_kinds() {
+        let mut doc = StoryDocument::new("Story", ALICE, 0);
+        let said = doc.append(ALICE, message("we planted 40 trees"), 1);
+        doc.append(
+            ALICE,
+            LeafKind::Image {
+                artifact_id: ArtifactId::Blob([3u8; 32]),
+                caption: None,
+            },
+            2,
+        );
+        doc.append(
+            BOB,
+            LeafKind::Token {
+                token_id: [4u8; 16],
+                note: Some("thank you".into()),
+            },
+            3,
+        );
+        doc.append(
+            BOB,
+            LeafKind::Attestation {
+                statement: "I was there".into(),
+                subject: Some(said),
+            },
+            4,
+        );
+        let labels: Vec<_> = doc.ordered_leaves().map(|l| l.kind.label()).collect();
+        assert_eq!(labels, vec!["Message", "Image", "Token", "Attestation"]);
+    }
+
+    #[test]
+    fn test_index_is_idempotent() {
+        let mut index = StoryIndexDocument::default();
+        let story = StoryDocument::new("Story", ALICE, 0);
+        let summary = StorySummary {
+            id: story.id,
+            title: story.title.clone(),
+            creator: ALICE,
+            created_at_millis: 0,
+        };
+        index.add(summary.clone());
+        index.merge(StoryIndexDocument {
+            stories: vec![summary],
+        });
+        assert_eq!(index.stories.len(), 1);
+    }
+}
@@ -4,13 +4,36 @@
 //! that don't belong on individual Realms (e.g., cross-realm sentiment queries).
 
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 
+use crate::bioregion_directory::{
+    BioregionDirectoryDocument, BioregionHierarchy, BioregionNode, RealmListing,
+    BIOREGION_DIRECTORY_DOC,
+};
+use crate::exchange::{self, ExchangeListing, ListingId, MatchProposal};
+use crate::fulfillment::RequestId;
+use crate::gallery::{GalleryId, GalleryMedia};
+use crate::heat::HeatSnapshot;
+use crate::humanness::BioregionalLevel;
+use crate::intention::Intention;
+use crate::realm_attention::RealmAttention;
+use crate::realm_exchange::RealmExchange;
+use crate::realm_galleries::RealmGalleries;
+use crate::realm_heat::RealmHeat;
+use crate::realm_intentions::RealmIntentions;
+use crate::realm_requests::RealmRequests;
+use crate::realm_tokens::RealmTokens;
+use crate::recommendation::{self, Recommendation, RecommendationWeights};
 use crate::sentiment::{RelayedSentiment, SentimentRelayDocument, SentimentView};
 use crate::story_auth::StoryAuth;
 use indras_network::error::{IndraError, Result};
 use indras_network::member::MemberId;
-use indras_network::IndrasNetwork;
+use indras_network::message::{Content, GalleryItemRef};
+use indras_network::{AccessMode, ArtifactId, GeoLocation, GeoPrecision, IndrasNetwork, Realm};
+use tokio::task::JoinHandle;
+use tracing::debug;
 
 /// The SyncEngine app layer.
 ///
@@ -108,6 +131,458 @@ impl SyncEngine {
         Ok(view)
     }
 
+    /// Rank open intentions across all loaded realms: "what should I work on next".
+    ///
+    /// Uses default [`RecommendationWeights`]; see [`recommendations_with`](Self::recommendations_with).
+    pub async fn recommendations(&self, limit: usize) -> Result<Vec<Recommendation>> {
+        self.recommendations_with(limit, &RecommendationWeights::default())
+            .await
+    }
+
+    /// Rank open intentions across all loaded realms with custom weights.
+    ///
+    /// Scores each realm's intentions from its attention and token documents
+    /// (see [`recommendation`](crate::recommendation)) and returns the top
+    /// `limit`. Realms whose documents cannot be opened are skipped.
+    pub async fn recommendations_with(
+        &self,
+        limit: usize,
+        weights: &RecommendationWeights,
+    ) -> Result<Vec<Recommendation>> {
+        let me = self.network.id();
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut scored = Vec::new();
+
+        for realm_id in self.network.conversation_realms() {
+            let Some(realm) = self.network.get_realm_by_id(&realm_id) else {
+                continue;
+            };
+            let (Ok(intentions), Ok(attention), Ok(tokens)) =
+                (realm.intentions().await, realm.attention().await, realm.tokens().await)
+            else {
+                continue;
+            };
+            let intentions = intentions.read().await;
+            if intentions.intentions.is_empty() {
+                continue;
+            }
+            let attention = attention.read().await;
+            let tokens = tokens.read().await;
+            scored.extend(recommendation::score_intentions(
+                realm_id,
+                &intentions,
+                &attention,
+                &tokens,
+                &me,
+                now,
+                weights,
+            ));
+        }
+
+        Ok(recommendation::rank(scored, limit))
+    }
+
+    /// Publish an intention to the exchange boards of the given realms.
+    ///
+    /// Needs and offerings become listings on their side; plain intentions
+    /// are listed as needs. The same listing ID is used in every realm, so
+    /// matching counts it once. Returns the listing ID.
+    pub async fn publish_intention_to_exchange(
+        &self,
+        intention: &Intention,
+        tags: Vec<String>,
+        realms: &[Realm],
+    ) -> Result<ListingId> {
+        if intention.creator != self.network.id() {
+            return Err(IndraError::InvalidOperation(
+                "Only the creator can publish an intention to the exchange".into(),
+            ));
+        }
+        let listing = ExchangeListing::from_intention(intention, tags);
+        for realm in realms {
+            realm.publish_listing(listing.clone()).await?;
+        }
+        Ok(listing.id)
+    }
+
+    /// Withdraw a listing from every loaded realm's exchange board.
+    pub async fn withdraw_from_exchange(&self, listing_id: ListingId) -> Result<()> {
+        let me = self.network.id();
+        for realm_id in self.network.conversation_realms() {
+            let Some(realm) = self.network.get_realm_by_id(&realm_id) else {
+                continue;
+            };
+            let board = realm.exchange_board().await?;
+            let listed = board.read().await.find(&listing_id).is_some();
+            if listed {
+                realm.withdraw_listing(listing_id, me).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Matches involving you, gathered from every loaded realm's exchange board.
+    ///
+    /// Best matches first; see [`exchange::find_matches`] for scoring.
+    pub async fn exchange_matches(&self) -> Result<Vec<MatchProposal>> {
+        let me = self.network.id();
+        let mut listings = Vec::new();
+
+        for realm_id in self.network.conversation_realms() {
+            let Some(realm) = self.network.get_realm_by_id(&realm_id) else {
+                continue;
+            };
+            let Ok(board) = realm.exchange_board().await else {
+                continue;
+            };
+            listings.extend(board.read().await.listings.iter().cloned());
+        }
+
+        Ok(exchange::find_matches(&listings)
+            .into_iter()
+            .filter(|m| m.need.owner == me || m.offer.owner == me)
+            .collect())
+    }
+
+    /// Open a negotiation for a match in the DM realm with the counterparty.
+    ///
+    /// Proposes `terms` as the opening offer and returns the DM realm, where
+    /// the negotiation continues via [`RealmExchange`].
+    pub async fn open_exchange_negotiation(
+        &self,
+        proposal: &MatchProposal,
+        terms: impl Into<String> + Send,
+    ) -> Result<Realm> {
+        let me = self.network.id();
+        if proposal.need.owner != me && proposal.offer.owner != me {
+            return Err(IndraError::InvalidOperation(
+                "Not a party to this match".into(),
+            ));
+        }
+        let (dm, _) = self.network.connect(proposal.counterparty(&me)).await?;
+        dm.propose_exchange_terms(proposal, me, terms).await?;
+        Ok(dm)
+    }
+
+    /// Deliver artifacts from your accepted request offers.
+    ///
+    /// Scans every loaded realm's request board for accepted artifact
+    /// offers you made, grants the requester permanent access to the
+    /// artifact from your home realm, and marks the request fulfilled.
+    /// Returns the IDs of the requests delivered.
+    pub async fn deliver_accepted_request_offers(&self) -> Result<Vec<RequestId>> {
+        let me = self.network.id();
+        let mut delivered = Vec::new();
+
+        for realm_id in self.network.conversation_realms() {
+            let Some(realm) = self.network.get_realm_by_id(&realm_id) else {
+                continue;
+            };
+            let Ok(board) = realm.request_board().await else {
+                continue;
+            };
+            let pending: Vec<_> = board
+                .read()
+                .await
+                .pending_deliveries(&me)
+                .into_iter()
+                .map(|(request, artifact_id)| (request.id, request.requester, *artifact_id))
+                .collect();
+            if pending.is_empty() {
+                continue;
+            }
+
+            let home = self.network.home_realm().await?;
+            for (request_id, requester, artifact_id) in pending {
+                let granted = home
+                    .artifact_index()
+                    .await?
+                    .read()
+                    .await
+                    .get(&artifact_id)
+                    .is_some_and(|entry| entry.grant_for(&requester).is_some());
+                if !granted {
+                    home.grant_access(&artifact_id, requester, AccessMode::Permanent)
+                        .await?;
+                }
+                realm.mark_request_fulfilled(request_id, me).await?;
+                delivered.push(request_id);
+            }
+        }
+
+        Ok(delivered)
+    }
+
+    /// Upload files and share them with a realm as a gallery, in one call.
+    ///
+    /// Each file is uploaded to your home realm and granted (revocably) to
+    /// the realm's current members. The gallery keeps the files in the
+    /// order given, and a single gallery message announces it in chat.
+    pub async fn share_gallery(
+        &self,
+        realm: &Realm,
+        title: impl Into<String> + Send,
+        paths: impl IntoIterator<Item = impl AsRef<Path>>,
+    ) -> Result<GalleryId> {
+        let home = self.network.home_realm().await?;
+        let mut artifacts = Vec::new();
+        for path in paths {
+            artifacts.push(home.upload(path).await?);
+        }
+        self.announce_gallery(realm, title, &artifacts).await
+    }
+
+    /// Share artifacts already in your home realm with a realm as a gallery.
+    ///
+    /// The second half of [`share_gallery`](Self::share_gallery), for callers
+    /// that upload on their own (e.g. in parallel): grants each artifact
+    /// revocably to the realm's current members, then creates the gallery
+    /// and announces it with a single gallery message.
+    pub async fn announce_gallery(
+        &self,
+        realm: &Realm,
+        title: impl Into<String> + Send,
+        artifacts: &[ArtifactId],
+    ) -> Result<GalleryId> {
+        let title = title.into();
+        let me = self.network.id();
+        let home = self.network.home_realm().await?;
+        let members: Vec<MemberId> = realm
+            .member_list()
+            .await?
+            .into_iter()
+            .map(|m| m.id())
+            .filter(|id| *id != me)
+            .collect();
+
+        let mut media = Vec::new();
+        for artifact_id in artifacts {
+            for member in &members {
+                let _ = home
+                    .grant_access(artifact_id, *member, AccessMode::Revocable)
+                    .await;
+            }
+            let entry = home
+                .artifact_index()
+                .await?
+                .read()
+                .await
+                .get(artifact_id)
+                .cloned()
+                .ok_or_else(|| IndraError::Artifact("Uploaded artifact not indexed".into()))?;
+            media.push(GalleryMedia {
+                artifact_id: *artifact_id,
+                name: entry.name,
+                mime_type: entry.mime_type,
+                size: entry.size,
+            });
+        }
+
+        let items = media
+            .iter()
+            .map(|m| GalleryItemRef {
+                name: m.name.clone(),
+                mime_type: m.mime_type.clone().unwrap_or_default(),
+                size: m.size,
+                thumbnail_data: None,
+                hash: *m.artifact_id.bytes(),
+                dimensions: None,
+            })
+            .collect();
+        let gallery_id = realm.create_gallery(title.clone(), me, media).await?;
+
+        realm
+            .send(Content::Gallery {
+                folder_id: hex::encode(gallery_id),
+                title: Some(title),
+                items,
+            })
+            .await?;
+
+        Ok(gallery_id)
+    }
+
+    /// Refresh artifact heat in every loaded realm.
+    ///
+    /// Folds new attention events into each realm's heat snapshots and
+    /// applies decay. Returns the number of artifacts recomputed. Realms
+    /// whose documents cannot be opened are skipped.
+    pub async fn maintain_heat(&self) -> Result<usize> {
+        Ok(refresh_all_heat(&self.network).await)
+    }
+
+    /// Spawn a background task that runs [`maintain_heat`](Self::maintain_heat)
+    /// every `interval`. Abort the returned handle to stop it.
+    pub fn spawn_heat_maintenance(&self, interval: Duration) -> JoinHandle<()> {
+        let network = Arc::clone(&self.network);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let recomputed = refresh_all_heat(&network).await;
+                debug!(recomputed, "Heat maintenance pass");
+            }
+        })
+    }
+
+    /// The hottest artifacts in a realm, hottest first.
+    ///
+    /// Reads the persisted snapshots with decay applied up to now; it
+    /// never rescans attention events.
+    pub async fn hot_artifacts(&self, realm: &Realm, limit: usize) -> Result<Vec<HeatSnapshot>> {
+        realm.hot_artifacts(limit).await
+    }
+
+    /// Add a community node (e.g. a watershed) to a community realm's directory.
+    ///
+    /// The node must sit exactly one level below `parent_code`, which may be
+    /// a catalog entry or a node already in the directory.
+    pub async fn add_bioregion_node(
+        &self,
+        community: &Realm,
+        code: impl Into<String>,
+        name: impl Into<String>,
+        level: BioregionalLevel,
+        parent_code: impl Into<String>,
+    ) -> Result<BioregionNode> {
+        let node = BioregionNode {
+            code: code.into(),
+            name: name.into(),
+            level,
+            parent_code: parent_code.into(),
+            added_by: Some(self.network.id()),
+            added_at_millis: chrono::Utc::now().timestamp_millis(),
+        };
+
+        let dir = community
+            .document::<BioregionDirectoryDocument>(BIOREGION_DIRECTORY_DOC)
+            .await?;
+        dir.try_update(|d| {
+            d.add_node(node.clone())
+                .map_err(|e| IndraError::InvalidOperation(e.to_string()))
+        })
+        .await?;
+
+        Ok(node)
+    }
+
+    /// Attach a realm to a bioregion node so others can discover it.
+    ///
+    /// The listing (with the realm's invite) is published to the realm's own
+    /// directory and to every conversation realm, like alias handles. If
+    /// `bioregion` is a community node, it is copied along so the listing
+    /// resolves everywhere it lands.
+    pub async fn attach_realm_to_bioregion(&self, realm: &Realm, bioregion: &str) -> Result<()> {
+        let hierarchy = self.bioregion_hierarchy().await?;
+        let path = hierarchy.path_to_root(bioregion);
+        if path.is_empty() {
+            return Err(IndraError::InvalidOperation(format!(
+                "Unknown bioregion node: {bioregion}"
+            )));
+        }
+        let community_nodes: Vec<BioregionNode> =
+            path.into_iter().rev().filter(|n| n.added_by.is_some()).collect();
+
+        let listing = RealmListing {
+            realm_id: realm.id(),
+            name: realm.name().unwrap_or_default().to_string(),
+            bioregion: bioregion.to_string(),
+            invite: realm.invite_code().map(|c| c.to_uri()),
+            listed_by: self.network.id(),
+            updated_at_millis: chrono::Utc::now().timestamp_millis(),
+            withdrawn: false,
+            geotag: None,
+        };
+
+        self.publish_to_directories(realm, |d| {
+            for node in &community_nodes {
+                if !d.knows(&node.code) {
+                    d.add_node(node.clone())
+                        .map_err(|e| IndraError::InvalidOperation(e.to_string()))?;
+                }
+            }
+            d.list(listing.clone())
+                .map_err(|e| IndraError::InvalidOperation(e.to_string()))
+        })
+        .await
+    }
+
+    /// Tag a realm's bioregion listing with a coarse location, or clear it.
+    ///
+    /// Only the geohash cell at `precision` is published. The realm must
+    /// already be attached with [`Self::attach_realm_to_bioregion`].
+    pub async fn geotag_realm_listing(
+        &self,
+        realm: &Realm,
+        location: Option<&GeoLocation>,
+        precision: GeoPrecision,
+    ) -> Result<()> {
+        let realm_id = realm.id();
+        let geotag = location.map(|l| l.geohash(precision));
+        let now = chrono::Utc::now().timestamp_millis();
+        self.publish_to_directories(realm, |d| {
+            d.set_geotag(&realm_id, geotag.clone(), now);
+            Ok(())
+        })
+        .await
+    }
+
+    /// Withdraw a realm's bioregion listing everywhere it was published.
+    pub async fn detach_realm_from_bioregion(&self, realm: &Realm) -> Result<()> {
+        let realm_id = realm.id();
+        let now = chrono::Utc::now().timestamp_millis();
+        self.publish_to_directories(realm, |d| {
+            d.withdraw(&realm_id, now);
+            Ok(())
+        })
+        .await
+    }
+
+    /// Browse the bioregional hierarchy with every directory we can see.
+    ///
+    /// Merges the bioregion directories of all loaded realms over the
+    /// compiled-in catalog.
+    pub async fn bioregion_hierarchy(&self) -> Result<BioregionHierarchy> {
+        use indras_network::document::DocumentSchema;
+
+        let mut merged = BioregionDirectoryDocument::new();
+        for realm_id in self.network.realms() {
+            let Some(realm) = self.network.get_realm_by_id(&realm_id) else {
+                continue;
+            };
+            let Ok(dir) = realm
+                .document::<BioregionDirectoryDocument>(BIOREGION_DIRECTORY_DOC)
+                .await
+            else {
+                continue;
+            };
+            let snapshot = dir.read().await.clone();
+            merged.merge(snapshot);
+        }
+        Ok(BioregionHierarchy::new(merged))
+    }
+
+    /// Apply a directory update to `realm` and every conversation realm.
+    async fn publish_to_directories<F>(&self, realm: &Realm, update: F) -> Result<()>
+    where
+        F: Fn(&mut BioregionDirectoryDocument) -> Result<()>,
+    {
+        let mut targets = self.network.conversation_realms();
+        if !targets.contains(&realm.id()) {
+            targets.push(realm.id());
+        }
+        for realm_id in targets {
+            let Some(target) = self.network.get_realm_by_id(&realm_id) else {
+                continue;
+            };
+            let dir = target
+                .document::<BioregionDirectoryDocument>(BIOREGION_DIRECTORY_DOC)
+                .await?;
+            dir.try_update(&update).await?;
+        }
+        Ok(())
+    }
+
     /// Create a story-based account.
     ///
     /// Delegates to `StoryAuth::create_account` with the network's data directory.
@@ -138,3 +613,18 @@ impl Clone for SyncEngine {
         }
     }
 }
+
+/// One heat maintenance pass over every loaded realm.
+async fn refresh_all_heat(network: &IndrasNetwork) -> usize {
+    let mut recomputed = 0;
+    for realm_id in network.conversation_realms() {
+        let Some(realm) = network.get_realm_by_id(&realm_id) else {
+            continue;
+        };
+        match realm.refresh_heat().await {
+            Ok(n) => recomputed += n,
+            Err(e) => debug!(error = %e, "Skipping heat refresh for realm"),
+        }
+    }
+    recomputed
+}
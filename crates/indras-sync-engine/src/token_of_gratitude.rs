@@ -14,11 +14,14 @@
 //! - **Steward is mutable**: The only mutable field; changes on release
 //! - **Append-only event log**: CRDT document with `Minted`, `Pledged`, `Released`,
 //!   `Withdrawn` entries; current state derived from log replay
+//! - **Value history**: `Valued` entries record observer-specific valuation
+//!   snapshots for charting (see `token_valuation`)
 //! - **Realm-scoped**: Tokens live within the realm where they were minted
 
 use crate::blessing::BlessingId;
 use indras_network::member::MemberId;
 use crate::intention::IntentionId;
+use crate::token_valuation::{SubjectiveTokenValue, ValueSnapshot};
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -83,7 +86,7 @@ pub struct TokenOfGratitude {
 }
 
 /// Events in the token lifecycle (append-only log entries).
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum TokenEvent {
     /// A new token was minted from a blessing.
     Minted {
@@ -113,6 +116,11 @@ pub enum TokenEvent {
     Withdrawn {
         token_id: TokenOfGratitudeId,
     },
+    /// An observer recorded the token's subjective value.
+    Valued {
+        token_id: TokenOfGratitudeId,
+        snapshot: ValueSnapshot,
+    },
 }
 
 /// Errors that can occur during token operations.
@@ -320,6 +328,53 @@ impl TokenOfGratitudeDocument {
         Ok(())
     }
 
+    /// Record an observer's valuation of a token, stamped with the current time.
+    pub fn record_value(
+        &mut self,
+        token_id: TokenOfGratitudeId,
+        observer: MemberId,
+        value: &SubjectiveTokenValue,
+    ) -> Result<(), TokenError> {
+        self.record_value_at(token_id, observer, value, chrono::Utc::now().timestamp_millis())
+    }
+
+    /// Record an observer's valuation of a token at a given time.
+    pub fn record_value_at(
+        &mut self,
+        token_id: TokenOfGratitudeId,
+        observer: MemberId,
+        value: &SubjectiveTokenValue,
+        recorded_at_millis: i64,
+    ) -> Result<(), TokenError> {
+        let token = self.tokens.get(&token_id).ok_or(TokenError::TokenNotFound)?;
+        let snapshot = ValueSnapshot::new(token, observer, value, recorded_at_millis);
+        self.events.push(TokenEvent::Valued { token_id, snapshot });
+        Ok(())
+    }
+
+    /// Recorded valuations of a token by one observer, oldest first.
+    pub fn value_history(
+        &self,
+        token_id: &TokenOfGratitudeId,
+        observer: &MemberId,
+    ) -> Vec<&ValueSnapshot> {
+        let mut history: Vec<&ValueSnapshot> = self
+            .value_snapshots()
+            .filter(|(id, s)| *id == token_id && &s.observer == observer)
+            .map(|(_, s)| s)
+            .collect();
+        history.sort_by_key(|s| s.recorded_at_millis);
+        history
+    }
+
+    /// All recorded valuations, in log order.
+    pub fn value_snapshots(&self) -> impl Iterator<Item = (&TokenOfGratitudeId, &ValueSnapshot)> {
+        self.events.iter().filter_map(|e| match e {
+            TokenEvent::Valued { token_id, snapshot } => Some((token_id, snapshot)),
+            _ => None,
+        })
+    }
+
     /// Find a token by ID.
     pub fn find(&self, token_id: &TokenOfGratitudeId) -> Option<&TokenOfGratitude> {
         self.tokens.get(token_id)
@@ -436,6 +491,7 @@ impl TokenOfGratitudeDocument {
                         token.pledged_at_millis = None;
                     }
                 }
+                TokenEvent::Valued { .. } => {}
             }
         }
     }
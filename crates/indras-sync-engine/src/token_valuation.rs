@@ -15,9 +15,22 @@
 //! Only positive sentiment counts — negative and unknown sentiment produce
 //! a weight of zero. This means sybil-minted tokens are invisible to
 //! observers who don't trust the sybil accounts.
+//!
+//! # History
+//!
+//! A valuation is a point in time. Recording it with
+//! `TokenOfGratitudeDocument::record_value` appends a [`ValueSnapshot`] to
+//! the token's event log, so [`TokenOfGratitudeDocument::value_history`]
+//! yields a chartable series per observer, and [`gratitude_flow`] sums the
+//! latest snapshots of everything a member stewards into one series.
+//! Snapshots carry the trust chain weight of the moment, so each release
+//! (another hop of [`STEWARD_CHAIN_DECAY`]) shows up as a step in the chart.
 
 use indras_network::member::MemberId;
-use crate::token_of_gratitude::TokenOfGratitude;
+use crate::token_of_gratitude::{TokenOfGratitude, TokenOfGratitudeDocument, TokenOfGratitudeId};
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Trust decay factor per hop in the steward chain.
 ///
@@ -104,6 +117,98 @@ pub fn subjective_value(
     }
 }
 
+/// A recorded valuation of a token, as one observer saw it at one moment.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ValueSnapshot {
+    /// Whose perspective the value was computed from.
+    pub observer: MemberId,
+    /// The token's steward when the value was recorded.
+    pub steward: MemberId,
+    /// Length of the steward chain when the value was recorded.
+    pub chain_len: usize,
+    /// Raw attention duration in milliseconds.
+    pub raw_attention_millis: u64,
+    /// Trust weight after steward chain decay (0.0 to 1.0).
+    pub trust_chain_weight: f64,
+    /// Humanness freshness of the blesser (0.0 to 1.0).
+    pub humanness_freshness: f64,
+    /// Final subjective value.
+    pub subjective_millis: f64,
+    /// When the value was recorded (Unix timestamp in milliseconds).
+    pub recorded_at_millis: i64,
+}
+
+impl ValueSnapshot {
+    /// Capture a computed value for `token` as seen by `observer`.
+    pub fn new(
+        token: &TokenOfGratitude,
+        observer: MemberId,
+        value: &SubjectiveTokenValue,
+        recorded_at_millis: i64,
+    ) -> Self {
+        Self {
+            observer,
+            steward: token.steward,
+            chain_len: token.steward_chain.len(),
+            raw_attention_millis: value.raw_attention_millis,
+            trust_chain_weight: value.trust_chain_weight,
+            humanness_freshness: value.humanness_freshness,
+            subjective_millis: value.subjective_millis,
+            recorded_at_millis,
+        }
+    }
+}
+
+/// One point of a member's gratitude flow series.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlowPoint {
+    /// When the value changed (Unix timestamp in milliseconds).
+    pub at_millis: i64,
+    /// Total subjective value the member stewarded from this point on.
+    pub subjective_millis: f64,
+}
+
+/// Value a member stewards over time, as seen by `observer`.
+///
+/// Replays the observer's snapshots in time order, keeping the latest one
+/// per token, and emits a point whenever the member's total changes — when
+/// a token is revalued, or moves to or away from the member.
+pub fn gratitude_flow(
+    doc: &TokenOfGratitudeDocument,
+    member: &MemberId,
+    observer: &MemberId,
+) -> Vec<FlowPoint> {
+    let mut snapshots: Vec<(&TokenOfGratitudeId, &ValueSnapshot)> = doc
+        .value_snapshots()
+        .filter(|(_, s)| &s.observer == observer)
+        .collect();
+    snapshots.sort_by_key(|(_, s)| s.recorded_at_millis);
+
+    let mut latest: HashMap<TokenOfGratitudeId, &ValueSnapshot> = HashMap::new();
+    let mut series: Vec<FlowPoint> = Vec::new();
+    for (token_id, snapshot) in snapshots {
+        latest.insert(*token_id, snapshot);
+        let total: f64 = latest
+            .values()
+            .filter(|s| &s.steward == member)
+            .map(|s| s.subjective_millis)
+            .sum();
+
+        match series.last_mut() {
+            Some(last) if last.at_millis == snapshot.recorded_at_millis => {
+                last.subjective_millis = total;
+            }
+            Some(last) if last.subjective_millis == total => {}
+            None if total == 0.0 => {}
+            _ => series.push(FlowPoint {
+                at_millis: snapshot.recorded_at_millis,
+                subjective_millis: total,
+            }),
+        }
+    }
+    series
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -301,4 +406,56 @@ mod tests {
         // weight = 0.8 * 0.7^0 = 0.8
         assert!((val.trust_chain_weight - 0.8).abs() < 0.001);
     }
+
+    fn value(subjective_millis: f64) -> SubjectiveTokenValue {
+        SubjectiveTokenValue {
+            raw_attention_millis: 60_000,
+            trust_chain_weight: 1.0,
+            humanness_freshness: 1.0,
+            subjective_millis,
+        }
+    }
+
+    #[test]
+    fn test_value_history_per_observer() {
+        let observer = member(9);
+        let mut doc = TokenOfGratitudeDocument::new();
+        let token_id = doc.mint(member(1), blessing(1), member(2), intention(1), vec![0]).unwrap();
+
+        doc.record_value_at(token_id, observer, &value(60_000.0), 10).unwrap();
+        doc.record_value_at(token_id, member(8), &value(1.0), 15).unwrap();
+        doc.pledge(token_id, intention(2)).unwrap();
+        doc.release(token_id, member(3)).unwrap();
+        doc.record_value_at(token_id, observer, &value(42_000.0), 20).unwrap();
+
+        let history = doc.value_history(&token_id, &observer);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].chain_len, 1);
+        assert_eq!(history[1].chain_len, 2);
+        assert_eq!(history[1].steward, member(3));
+        assert!(doc.record_value_at([0xFF; 16], observer, &value(1.0), 30).is_err());
+    }
+
+    #[test]
+    fn test_gratitude_flow_follows_steward() {
+        let observer = member(9);
+        let mut doc = TokenOfGratitudeDocument::new();
+        let token_id = doc.mint(member(1), blessing(1), member(2), intention(1), vec![0]).unwrap();
+        doc.record_value_at(token_id, observer, &value(60_000.0), 10).unwrap();
+        doc.pledge(token_id, intention(2)).unwrap();
+        doc.release(token_id, member(3)).unwrap();
+        doc.record_value_at(token_id, observer, &value(42_000.0), 20).unwrap();
+
+        let giver = gratitude_flow(&doc, &member(1), &observer);
+        assert_eq!(
+            giver,
+            vec![
+                FlowPoint { at_millis: 10, subjective_millis: 60_000.0 },
+                FlowPoint { at_millis: 20, subjective_millis: 0.0 },
+            ]
+        );
+        let receiver = gratitude_flow(&doc, &member(3), &observer);
+        assert_eq!(receiver, vec![FlowPoint { at_millis: 20, subjective_millis: 42_000.0 }]);
+        assert!(gratitude_flow(&doc, &member(3), &member(7)).is_empty());
+    }
 }
@@ -240,17 +240,24 @@ impl InterfaceDocument {
     }
 
     /// Get all events from the log
+    ///
+    /// An event appended by several peers independently (the sender, and a
+    /// recipient that received it directly before syncing) is stored once
+    /// per peer; it is returned once, at its first position.
     pub fn events<I: PeerIdentity>(&self) -> Vec<InterfaceEvent<I>> {
         let events_obj = self.events_obj();
         let len = self.doc.length(&events_obj);
         let mut events = Vec::new();
+        let mut seen = HashSet::new();
 
         for i in 0..len {
             if let Ok(Some((value, _))) = self.doc.get(&events_obj, i) {
                 if let Value::Scalar(cow) = value {
                     if let ScalarValue::Bytes(buf) = cow.as_ref() {
                         if let Ok(event) = postcard::from_bytes::<InterfaceEvent<I>>(buf) {
-                            events.push(event);
+                            if event.event_id().is_none_or(|id| seen.insert(id)) {
+                                events.push(event);
+                            }
                         }
                     }
                 }
@@ -429,6 +436,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_events_skips_concurrent_duplicates() {
+        let peer_a = SimulationIdentity::new('A').unwrap();
+        let mut doc1 = InterfaceDocument::new();
+        doc1.add_member(&peer_a);
+        let mut doc2 = doc1.fork().unwrap();
+
+        // Sender and recipient both append the same event, then sync
+        let event = InterfaceEvent::message(peer_a, 1, b"Once".to_vec());
+        doc1.append_event(&event).unwrap();
+        doc2.append_event(&event).unwrap();
+        doc1.merge(&mut doc2).unwrap();
+
+        assert_eq!(doc1.event_count(), 2);
+        let events: Vec<InterfaceEvent<SimulationIdentity>> = doc1.events();
+        assert_eq!(events.len(), 1);
+    }
+
     #[test]
     fn test_automerge_sync_protocol() {
         // Test the full automerge sync protocol with per-peer state.
@@ -1,5 +1,6 @@
 //! Error types for indras-sync
 
+use indras_core::error_code::{ErrorCode, ErrorInfo};
 use thiserror::Error;
 
 /// Errors that can occur during synchronization
@@ -36,6 +37,36 @@ pub enum SyncError {
     LockPoisoned,
 }
 
+impl ErrorInfo for SyncError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            SyncError::DocumentLoad(_) => ErrorCode::DocumentLoad,
+            SyncError::DocumentOperation(_) | SyncError::SyncMerge(_) => ErrorCode::SyncFailed,
+            SyncError::Serialization(_) => ErrorCode::Serialization,
+            SyncError::Deserialization(_) => ErrorCode::CorruptData,
+            SyncError::InterfaceNotFound(_) => ErrorCode::InterfaceNotFound,
+            SyncError::NotMember => ErrorCode::NotMember,
+            SyncError::PeerNotFound(_) => ErrorCode::PeerNotFound,
+            SyncError::Protocol(_) => ErrorCode::ProtocolViolation,
+            SyncError::LockPoisoned => ErrorCode::Internal,
+        }
+    }
+
+    fn peer(&self) -> Option<&str> {
+        match self {
+            SyncError::PeerNotFound(peer) => Some(peer),
+            _ => None,
+        }
+    }
+
+    fn interface(&self) -> Option<&str> {
+        match self {
+            SyncError::InterfaceNotFound(id) => Some(id),
+            _ => None,
+        }
+    }
+}
+
 /// Result type for sync operations
 pub type SyncResult<T> = Result<T, SyncError>;
 
@@ -75,6 +106,18 @@ mod tests {
         assert!(format!("{}", err).contains("Protocol error"));
     }
 
+    #[test]
+    fn test_sync_error_info() {
+        let err = SyncError::PeerNotFound("peer_x".to_string());
+        assert_eq!(err.code(), ErrorCode::PeerNotFound);
+        assert!(err.is_retryable());
+        assert_eq!(err.peer(), Some("peer_x"));
+
+        let err = SyncError::InterfaceNotFound("iface123".to_string());
+        assert_eq!(err.interface(), Some("iface123"));
+        assert!(!err.is_retryable());
+    }
+
     #[test]
     fn test_sync_error_debug() {
         // Ensure Debug is implemented and doesn't panic
@@ -17,6 +17,9 @@ use indras_core::{EventId, InterfaceEvent, PeerIdentity};
 pub struct EventStore<I: PeerIdentity> {
     /// All events in append order
     events: Vec<InterfaceEvent<I>>,
+    /// IDs of every event ever appended, kept after pruning so replays
+    /// of pruned events are still recognized
+    ids: HashSet<EventId>,
     /// Global sequence counter
     sequence: u64,
     /// Indices of events pending for each peer
@@ -34,6 +37,7 @@ impl<I: PeerIdentity> EventStore<I> {
     pub fn new() -> Self {
         Self {
             events: Vec::new(),
+            ids: HashSet::new(),
             sequence: 0,
             pending: HashMap::new(),
             delivered: HashMap::new(),
@@ -46,6 +50,7 @@ impl<I: PeerIdentity> EventStore<I> {
     pub fn with_peer_hash(peer_hash: u64) -> Self {
         Self {
             events: Vec::new(),
+            ids: HashSet::new(),
             sequence: 0,
             pending: HashMap::new(),
             delivered: HashMap::new(),
@@ -59,6 +64,7 @@ impl<I: PeerIdentity> EventStore<I> {
         let pending = members.iter().map(|p| (p.clone(), Vec::new())).collect();
         Self {
             events: Vec::new(),
+            ids: HashSet::new(),
             sequence: 0,
             pending,
             delivered: HashMap::new(),
@@ -72,6 +78,7 @@ impl<I: PeerIdentity> EventStore<I> {
         let pending = members.iter().map(|p| (p.clone(), Vec::new())).collect();
         Self {
             events: Vec::new(),
+            ids: HashSet::new(),
             sequence: 0,
             pending,
             delivered: HashMap::new(),
@@ -117,8 +124,16 @@ impl<I: PeerIdentity> EventStore<I> {
 
     /// Append an event and track for all members except the sender
     ///
-    /// Returns the EventId of the appended event.
+    /// Appending an event whose ID is already known is a no-op, so the
+    /// same event arriving twice (gossip and direct send, or a replay
+    /// after reconnect) is stored once. Returns the EventId of the event.
     pub fn append(&mut self, event: InterfaceEvent<I>) -> EventId {
+        if let Some(id) = event.event_id()
+            && !self.ids.insert(id)
+        {
+            return id;
+        }
+
         let sender = event.sender().cloned();
         let idx = self.events.len();
 
@@ -287,6 +302,11 @@ impl<I: PeerIdentity> EventStore<I> {
         &self.events
     }
 
+    /// Whether an event with this ID has been appended
+    pub fn contains(&self, id: &EventId) -> bool {
+        self.ids.contains(id)
+    }
+
     /// Get the total number of events
     pub fn len(&self) -> usize {
         self.events.len()
@@ -316,6 +336,7 @@ impl<I: PeerIdentity> EventStore<I> {
     #[cfg(test)]
     pub fn clear(&mut self) {
         self.events.clear();
+        self.ids.clear();
         self.pending.clear();
         self.delivered.clear();
         self.sequence = 0;
@@ -380,6 +401,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_duplicate_append_is_noop() {
+        let (a, b, _c) = create_peers();
+        let mut members = HashSet::new();
+        members.insert(a);
+        members.insert(b);
+
+        let mut store = EventStore::with_members(members);
+        let event = InterfaceEvent::message(a, 1, b"Hello".to_vec());
+        let first = store.append(event.clone());
+        let second = store.append(event);
+
+        assert_eq!(first, second);
+        assert!(store.contains(&first));
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.pending_count(&b), 1);
+    }
+
     #[test]
     fn test_mark_delivered() {
         let (a, b, _c) = create_peers();
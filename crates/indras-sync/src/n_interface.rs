@@ -8,7 +8,7 @@
 //! This provides a complete implementation of the [`NInterfaceTrait`] from indras-core.
 
 use std::collections::HashSet;
-use std::sync::RwLock;
+use std::sync::{OnceLock, RwLock};
 
 use async_trait::async_trait;
 use indras_core::{
@@ -67,7 +67,9 @@ pub struct NInterface<I: PeerIdentity> {
     interface_id: InterfaceId,
     /// Automerge document for CRDT synchronization (wrapped in RwLock for thread-safe access)
     /// Automerge's AutoCommit requires &mut self, so write locks are needed for mutations.
-    document: RwLock<InterfaceDocument>,
+    ///
+    /// Empty until first use for interfaces created with [`NInterface::deferred`].
+    document: OnceLock<RwLock<InterfaceDocument>>,
     /// Event store for pending delivery tracking
     event_store: EventStore<I>,
     /// Sync state with peers
@@ -105,7 +107,7 @@ where
 
         Self {
             interface_id,
-            document: RwLock::new(document),
+            document: OnceLock::from(RwLock::new(document)),
             event_store,
             sync_state,
             members,
@@ -137,13 +139,54 @@ where
 
         Self {
             interface_id,
-            document: RwLock::new(document),
+            document: OnceLock::from(RwLock::new(document)),
+            event_store,
+            sync_state,
+            members,
+        }
+    }
+
+    /// Create with a known ID and member set, deferring Automerge hydration
+    ///
+    /// The Automerge document is only built the first time it is needed
+    /// (append, sync, or direct document access). Membership and pending
+    /// tracking are available immediately. Use this on startup paths that
+    /// restore many interfaces, most of which may never be touched.
+    ///
+    /// # Arguments
+    ///
+    /// * `interface_id` - The known InterfaceId
+    /// * `members` - All known members, including ourselves
+    pub fn deferred(interface_id: InterfaceId, members: impl IntoIterator<Item = I>) -> Self {
+        let members: HashSet<I> = members.into_iter().collect();
+        let event_store = EventStore::with_members(members.clone());
+        let sync_state = SyncState::new(interface_id);
+
+        Self {
+            interface_id,
+            document: OnceLock::new(),
             event_store,
             sync_state,
             members,
         }
     }
 
+    /// Whether the Automerge document has been built yet
+    pub fn is_hydrated(&self) -> bool {
+        self.document.get().is_some()
+    }
+
+    /// Get the document lock, hydrating it from the member set on first use
+    fn doc(&self) -> &RwLock<InterfaceDocument> {
+        self.document.get_or_init(|| {
+            let mut document = InterfaceDocument::new();
+            for member in &self.members {
+                document.add_member(member);
+            }
+            RwLock::new(document)
+        })
+    }
+
     /// Load from existing Automerge document bytes
     ///
     /// Reconstructs an NInterface from previously saved document bytes.
@@ -168,7 +211,7 @@ where
 
         Ok(Self {
             interface_id,
-            document: RwLock::new(document),
+            document: OnceLock::from(RwLock::new(document)),
             event_store,
             sync_state,
             members,
@@ -184,7 +227,7 @@ where
     ///
     /// The serialized document bytes
     pub fn save(&self) -> Result<Vec<u8>, SyncError> {
-        Ok(self.document.write().map_err(|_| SyncError::LockPoisoned)?.save())
+        Ok(self.doc().write().map_err(|_| SyncError::LockPoisoned)?.save())
     }
 
     /// Add a member to the interface
@@ -205,7 +248,7 @@ where
         }
 
         // Add to Automerge document
-        self.document.write().map_err(|_| SyncError::LockPoisoned)?.add_member(&peer);
+        self.doc().write().map_err(|_| SyncError::LockPoisoned)?.add_member(&peer);
 
         // Add to local members set
         self.members.insert(peer.clone());
@@ -230,7 +273,7 @@ where
     /// Ok(()) on success
     pub fn remove_member(&mut self, peer: &I) -> Result<(), SyncError> {
         // Remove from Automerge document
-        self.document.write().map_err(|_| SyncError::LockPoisoned)?.remove_member(peer);
+        self.doc().write().map_err(|_| SyncError::LockPoisoned)?.remove_member(peer);
 
         // Remove from local members set
         self.members.remove(peer);
@@ -263,7 +306,7 @@ where
     /// Provides access to the underlying Automerge document via RwLock.
     /// Returns a read guard for the document.
     pub fn document(&self) -> Result<std::sync::RwLockReadGuard<'_, InterfaceDocument>, SyncError> {
-        self.document.read().map_err(|_| SyncError::LockPoisoned)
+        self.doc().read().map_err(|_| SyncError::LockPoisoned)
     }
 
     /// Get mutable document (for direct Automerge operations)
@@ -272,7 +315,7 @@ where
     /// Use with caution - modifications may desync the members set.
     /// Returns a write guard for the document.
     pub fn document_mut(&self) -> Result<std::sync::RwLockWriteGuard<'_, InterfaceDocument>, SyncError> {
-        self.document.write().map_err(|_| SyncError::LockPoisoned)
+        self.doc().write().map_err(|_| SyncError::LockPoisoned)
     }
 
     /// Get the event store (for direct event operations)
@@ -294,7 +337,7 @@ where
     /// Call this after merging external sync data to ensure
     /// the members set is up to date.
     pub fn sync_members(&mut self) -> Result<(), SyncError> {
-        let doc_members: HashSet<I> = self.document.read().map_err(|_| SyncError::LockPoisoned)?.members();
+        let doc_members: HashSet<I> = self.doc().read().map_err(|_| SyncError::LockPoisoned)?.members();
 
         // Update event store members
         self.event_store.set_members(doc_members.clone());
@@ -333,6 +376,8 @@ where
     /// 1. Added to the event store (tracks pending for other members)
     /// 2. Added to the Automerge document for CRDT sync
     ///
+    /// Appending an event that was already appended is a no-op.
+    ///
     /// # Arguments
     ///
     /// * `event` - The event to append
@@ -341,11 +386,17 @@ where
     ///
     /// The EventId of the appended event
     async fn append(&mut self, event: InterfaceEvent<I>) -> Result<EventId, InterfaceError> {
+        if let Some(id) = event.event_id()
+            && self.event_store.contains(&id)
+        {
+            return Ok(id);
+        }
+
         // 1. Append to event store (tracks pending for other members)
         let event_id = self.event_store.append(event.clone());
 
         // 2. Also add to Automerge document for CRDT sync
-        self.document
+        self.doc()
             .write()
             .map_err(|_| InterfaceError::AppendFailed("Lock poisoned".to_string()))?
             .append_event(&event)
@@ -389,7 +440,7 @@ where
 
         // Merge the full document bytes if present
         if !sync_msg.sync_data.is_empty() {
-            self.document
+            self.doc()
                 .write()
                 .map_err(|_| InterfaceError::SyncFailed("Lock poisoned".to_string()))?
                 .apply_update(&sync_msg.sync_data)
@@ -409,14 +460,14 @@ where
     /// For more efficient multi-round sync, use `SyncProtocol` directly.
     fn generate_sync(&mut self, _for_peer: &I) -> SyncMessage {
         // generate_sync cannot return Result per trait, unwrap is acceptable
-        let bytes = self.document.write().unwrap().save();
+        let bytes = self.doc().write().unwrap().save();
         SyncMessage::request(self.interface_id, bytes, vec![])
     }
 
     /// Get the current document state as bytes (for sync protocol)
     fn state_vector(&mut self) -> Vec<u8> {
         // state_vector cannot return Result per trait, unwrap is acceptable
-        self.document.write().unwrap().state_vector()
+        self.doc().write().unwrap().state_vector()
     }
 
     /// Check if we have pending events for any peer
@@ -464,6 +515,25 @@ mod tests {
         assert!(interface.members().contains(&alice));
     }
 
+    #[test]
+    fn test_deferred_hydrates_on_first_use() {
+        let (alice, bob, carol) = create_peers();
+        let interface_id = InterfaceId::new([0x42; 32]);
+        let mut interface = NInterface::deferred(interface_id, [alice, bob]);
+
+        assert!(!interface.is_hydrated());
+        assert_eq!(interface.members().len(), 2);
+        assert!(!interface.is_hydrated());
+
+        // Adding a member hydrates with the existing set first
+        interface.add_member(carol).unwrap();
+        assert!(interface.is_hydrated());
+        let doc_members: HashSet<SimulationIdentity> = interface.document().unwrap().members();
+        assert_eq!(doc_members.len(), 3);
+        assert!(doc_members.contains(&alice));
+        assert!(doc_members.contains(&bob));
+    }
+
     #[test]
     fn test_add_remove_member() {
         let (alice, bob, _) = create_peers();
@@ -520,6 +590,21 @@ mod tests {
         assert_eq!(event_id.sequence, 1);
     }
 
+    #[tokio::test]
+    async fn test_append_is_idempotent() {
+        let (alice, bob, _) = create_peers();
+        let mut interface = NInterface::new(alice);
+        interface.add_member(bob).unwrap();
+
+        let event = InterfaceEvent::message(bob, 1, b"Hello Alice!".to_vec());
+        let first = interface.append(event.clone()).await.unwrap();
+        let second = interface.append(event).await.unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(interface.event_count(), 1);
+        assert_eq!(interface.document().unwrap().event_count(), 1);
+    }
+
     #[tokio::test]
     async fn test_mark_delivered() {
         let (alice, bob, _) = create_peers();
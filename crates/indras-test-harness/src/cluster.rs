@@ -0,0 +1,387 @@
+//! Clusters of real nodes on localhost
+//!
+//! A [`Cluster`] owns N started [`IndrasNode`]s, each with its own temp
+//! data directory, dialed to each other by full endpoint address so no
+//! discovery is needed. Scenario actions address nodes by index and
+//! realms by the id [`Cluster::create_realm`] returned; the cluster tracks
+//! who joined which realm so assertions know whose copies to compare.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use indras_core::{EventId, InterfaceId};
+use indras_node::{IndrasNode, NodeConfig, NodeError};
+use indras_transport::AccessMode;
+use tempfile::TempDir;
+use tracing::{debug, info};
+
+use crate::error::{HarnessError, HarnessResult};
+use crate::fault::Partition;
+use crate::report::{self, NodeView, Report};
+
+/// How often assertions re-read node state
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// One node in a cluster
+pub struct TestNode {
+    name: String,
+    node: Arc<IndrasNode>,
+    _data_dir: TempDir,
+}
+
+impl TestNode {
+    /// Name used in reports (`node-0`, `node-1`, ...)
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The underlying node, for anything the harness doesn't wrap
+    pub fn node(&self) -> &Arc<IndrasNode> {
+        &self.node
+    }
+
+    fn err(&self) -> impl FnOnce(NodeError) -> HarnessError + '_ {
+        move |source| HarnessError::Node {
+            node: self.name.clone(),
+            source,
+        }
+    }
+}
+
+/// N started nodes plus the realms and faults a scenario set up
+pub struct Cluster {
+    nodes: Vec<TestNode>,
+    realms: BTreeMap<InterfaceId, BTreeSet<usize>>,
+    partition: Option<Partition>,
+    poll_interval: Duration,
+}
+
+fn short_realm(realm: &InterfaceId) -> String {
+    hex::encode(&realm.as_bytes()[..4])
+}
+
+impl Cluster {
+    /// Start `count` nodes with default config and connect them all
+    pub async fn start(count: usize) -> HarnessResult<Self> {
+        Self::start_with(count, |_, config| config).await
+    }
+
+    /// Start `count` nodes, letting `configure` adjust each node's config
+    ///
+    /// Nodes must start in open access mode; partitions take over the
+    /// access policy while they are in effect.
+    pub async fn start_with(
+        count: usize,
+        configure: impl Fn(usize, NodeConfig) -> NodeConfig,
+    ) -> HarnessResult<Self> {
+        let mut nodes = Vec::with_capacity(count);
+        for index in 0..count {
+            let name = format!("node-{index}");
+            let data_dir = TempDir::new()
+                .map_err(|e| HarnessError::Setup(format!("{name}: temp dir: {e}")))?;
+            let config = configure(
+                index,
+                NodeConfig::with_data_dir(data_dir.path()).with_display_name(name.clone()),
+            );
+            let node = IndrasNode::new(config)
+                .await
+                .map_err(|source| HarnessError::Node {
+                    node: name.clone(),
+                    source,
+                })?;
+            node.start().await.map_err(|source| HarnessError::Node {
+                node: name.clone(),
+                source,
+            })?;
+            nodes.push(TestNode {
+                name,
+                node: Arc::new(node),
+                _data_dir: data_dir,
+            });
+        }
+
+        let cluster = Self {
+            nodes,
+            realms: BTreeMap::new(),
+            partition: None,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        };
+        cluster.connect_all().await?;
+        info!(nodes = count, "Test cluster started");
+        Ok(cluster)
+    }
+
+    /// Change how often assertions re-read node state
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Number of nodes
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Node at `index`
+    pub fn node(&self, index: usize) -> HarnessResult<&TestNode> {
+        self.nodes.get(index).ok_or(HarnessError::NoSuchNode(index))
+    }
+
+    /// All nodes, in index order
+    pub fn nodes(&self) -> &[TestNode] {
+        &self.nodes
+    }
+
+    /// Nodes that created or joined `realm`, sorted
+    pub fn members(&self, realm: &InterfaceId) -> Vec<usize> {
+        self.realms
+            .get(realm)
+            .map(|m| m.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// The partition in effect, if any
+    pub fn partition(&self) -> Option<&Partition> {
+        self.partition.as_ref()
+    }
+
+    /// Dial every pair of nodes the current partition allows
+    pub async fn connect_all(&self) -> HarnessResult<()> {
+        for (i, dialer) in self.nodes.iter().enumerate() {
+            for j in (i + 1)..self.nodes.len() {
+                if self.partition.as_ref().is_some_and(|p| !p.can_reach(i, j)) {
+                    continue;
+                }
+                let target = &self.nodes[j];
+                let addr = target
+                    .node
+                    .endpoint_addr()
+                    .await
+                    .ok_or_else(|| HarnessError::NoEndpoint(target.name.clone()))?;
+                dialer
+                    .node
+                    .connect_by_addr(addr)
+                    .await
+                    .map_err(dialer.err())?;
+                debug!(from = %dialer.name, to = %target.name, "Connected");
+            }
+        }
+        Ok(())
+    }
+
+    // ============================================================
+    // Actions
+    // ============================================================
+
+    /// Create a realm on `creator`
+    pub async fn create_realm(&mut self, creator: usize, name: &str) -> HarnessResult<InterfaceId> {
+        let node = self.node(creator)?;
+        let (realm, _) = node
+            .node
+            .create_interface(Some(name))
+            .await
+            .map_err(node.err())?;
+        info!(node = %node.name, realm = %short_realm(&realm), name, "Realm created");
+        self.realms.entry(realm).or_default().insert(creator);
+        Ok(realm)
+    }
+
+    /// Have `from` invite `to` into `realm`, and `to` join
+    pub async fn invite(
+        &mut self,
+        realm: &InterfaceId,
+        from: usize,
+        to: usize,
+    ) -> HarnessResult<()> {
+        if !self.realms.contains_key(realm) {
+            return Err(HarnessError::UnknownRealm(short_realm(realm)));
+        }
+        let inviter = self.node(from)?;
+        let invitee = self.node(to)?;
+        let invite = inviter
+            .node
+            .create_invite_for(realm, &invitee.node.encapsulation_key())
+            .await
+            .map_err(inviter.err())?;
+        invitee
+            .node
+            .join_interface(invite)
+            .await
+            .map_err(invitee.err())?;
+        info!(from = %inviter.name, to = %invitee.name, realm = %short_realm(realm), "Invited");
+        self.realms.entry(*realm).or_default().insert(to);
+        Ok(())
+    }
+
+    /// Create a realm on `creator` and invite every other node
+    pub async fn create_shared_realm(
+        &mut self,
+        creator: usize,
+        name: &str,
+    ) -> HarnessResult<InterfaceId> {
+        let realm = self.create_realm(creator, name).await?;
+        for to in (0..self.nodes.len()).filter(|i| *i != creator) {
+            self.invite(&realm, creator, to).await?;
+        }
+        Ok(realm)
+    }
+
+    /// Post a message to `realm` from `sender`
+    pub async fn send(
+        &self,
+        sender: usize,
+        realm: &InterfaceId,
+        content: impl Into<Vec<u8>>,
+    ) -> HarnessResult<EventId> {
+        let node = self.node(sender)?;
+        node.node
+            .send_message(realm, content.into())
+            .await
+            .map_err(node.err())
+    }
+
+    // ============================================================
+    // Faults
+    // ============================================================
+
+    /// Split the cluster into `groups` that cannot reach each other
+    ///
+    /// Replaces any earlier partition. Nodes not listed are isolated.
+    pub async fn partition_into(&mut self, groups: &[&[usize]]) -> HarnessResult<()> {
+        let partition = Partition::new(groups);
+        for (index, node) in self.nodes.iter().enumerate() {
+            let access = node.node.access_control().await.map_err(node.err())?;
+            for key in access.allowed_peers() {
+                access.revoke(&key);
+            }
+            for peer in partition.reachable_from(index, self.nodes.len()) {
+                access.allow(*self.nodes[peer].node.identity().public_key());
+            }
+            access.set_mode(AccessMode::AllowList);
+            if let Some(transport) = node.node.transport().await {
+                transport.enforce_access();
+            }
+        }
+        info!(groups = ?partition.groups(), "Cluster partitioned");
+        self.partition = Some(partition);
+        Ok(())
+    }
+
+    /// Cut one node off from everyone else
+    pub async fn isolate(&mut self, node: usize) -> HarnessResult<()> {
+        self.node(node)?;
+        let others: Vec<usize> = (0..self.nodes.len()).filter(|i| *i != node).collect();
+        self.partition_into(&[&[node], &others]).await
+    }
+
+    /// Remove the partition and reconnect every pair
+    pub async fn heal(&mut self) -> HarnessResult<()> {
+        for node in &self.nodes {
+            let access = node.node.access_control().await.map_err(node.err())?;
+            for key in access.allowed_peers() {
+                access.revoke(&key);
+            }
+            access.set_mode(AccessMode::Open);
+        }
+        self.partition = None;
+        self.connect_all().await?;
+        info!("Cluster healed");
+        Ok(())
+    }
+
+    // ============================================================
+    // Assertions
+    // ============================================================
+
+    /// Every member's copy of `realm`, in index order
+    pub async fn views(&self, realm: &InterfaceId) -> HarnessResult<Vec<NodeView>> {
+        let mut views = Vec::new();
+        for index in self.members(realm) {
+            let node = &self.nodes[index];
+            let snapshot = node
+                .node
+                .document_snapshot(realm)
+                .await
+                .map_err(node.err())?;
+            views.push(NodeView::from_snapshot(node.name.clone(), &snapshot));
+        }
+        Ok(views)
+    }
+
+    async fn eventually(
+        &self,
+        assertion: &'static str,
+        realm: &InterfaceId,
+        timeout: Duration,
+        check: impl Fn(&[NodeView]) -> Vec<String>,
+    ) -> HarnessResult<()> {
+        if !self.realms.contains_key(realm) {
+            return Err(HarnessError::UnknownRealm(short_realm(realm)));
+        }
+        let started = Instant::now();
+        loop {
+            let lines = check(&self.views(realm).await?);
+            if lines.is_empty() {
+                debug!(assertion, realm = %short_realm(realm), waited = ?started.elapsed(), "Assertion held");
+                return Ok(());
+            }
+            if started.elapsed() >= timeout {
+                return Err(Report {
+                    assertion,
+                    realm: short_realm(realm),
+                    waited: started.elapsed(),
+                    lines,
+                }
+                .into());
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    /// Wait until every member of `realm` has the same document heads
+    pub async fn eventually_converged(
+        &self,
+        realm: &InterfaceId,
+        timeout: Duration,
+    ) -> HarnessResult<()> {
+        self.eventually("eventually_converged", realm, timeout, report::divergence)
+            .await
+    }
+
+    /// Wait until every member of `realm` has `event`
+    pub async fn delivered_within(
+        &self,
+        realm: &InterfaceId,
+        event: &EventId,
+        timeout: Duration,
+    ) -> HarnessResult<()> {
+        self.eventually("delivered_within", realm, timeout, |views| {
+            report::undelivered(views, event)
+        })
+        .await
+    }
+
+    /// Check that no member holds any event of `realm` twice
+    pub async fn no_duplicate_events(&self, realm: &InterfaceId) -> HarnessResult<()> {
+        self.eventually(
+            "no_duplicate_events",
+            realm,
+            Duration::ZERO,
+            report::duplicates,
+        )
+        .await
+    }
+
+    /// Stop every node
+    pub async fn stop(self) -> HarnessResult<()> {
+        for node in &self.nodes {
+            node.node.stop().await.map_err(node.err())?;
+        }
+        Ok(())
+    }
+}
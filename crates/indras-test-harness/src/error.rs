@@ -0,0 +1,54 @@
+//! Error types for the test harness
+
+use std::fmt;
+
+use thiserror::Error;
+
+use indras_node::NodeError;
+
+use crate::report::Report;
+
+/// Errors that can occur while running a scenario
+///
+/// `Debug` prints the same text as `Display`, so `.unwrap()` and `?` in a
+/// test show the full failure report instead of a struct dump.
+#[derive(Error)]
+pub enum HarnessError {
+    /// A node operation failed
+    #[error("{node}: {source}")]
+    Node {
+        /// Name of the node that failed
+        node: String,
+        /// The underlying node error
+        source: NodeError,
+    },
+
+    /// Index does not name a node in the cluster
+    #[error("No node at index {0}")]
+    NoSuchNode(usize),
+
+    /// Realm was not created through this cluster
+    #[error("Unknown realm: {0}")]
+    UnknownRealm(String),
+
+    /// Started node has no endpoint address
+    #[error("{0} has no endpoint address")]
+    NoEndpoint(String),
+
+    /// Scenario setup error
+    #[error("Setup error: {0}")]
+    Setup(String),
+
+    /// A high-level assertion did not hold
+    #[error("{0}")]
+    Assertion(#[from] Report),
+}
+
+impl fmt::Debug for HarnessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Result type for harness operations
+pub type HarnessResult<T> = Result<T, HarnessError>;
@@ -0,0 +1,83 @@
+//! Network partitions
+//!
+//! A [`Partition`] splits the cluster into groups that can only reach
+//! nodes in their own group. The cluster enforces it with each node's
+//! transport access control: every node switches to allow-list mode with
+//! only its group listed, and connections across the split are closed.
+//! Both dialing and accepting are refused, so gossip and sync stop
+//! crossing the split until [`Cluster::heal`](crate::Cluster::heal).
+
+use std::collections::BTreeSet;
+
+/// Groups of node indices that can reach each other
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Partition {
+    groups: Vec<BTreeSet<usize>>,
+}
+
+impl Partition {
+    /// Split nodes into `groups`
+    ///
+    /// Nodes not listed in any group are isolated from everyone. A node
+    /// listed in several groups joins the first.
+    pub fn new(groups: &[&[usize]]) -> Self {
+        let mut seen = BTreeSet::new();
+        let groups = groups
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .copied()
+                    .filter(|node| seen.insert(*node))
+                    .collect::<BTreeSet<usize>>()
+            })
+            .filter(|group| !group.is_empty())
+            .collect();
+        Self { groups }
+    }
+
+    /// Index of the group containing `node`
+    pub fn group_of(&self, node: usize) -> Option<usize> {
+        self.groups.iter().position(|g| g.contains(&node))
+    }
+
+    /// Whether `a` and `b` are on the same side
+    pub fn can_reach(&self, a: usize, b: usize) -> bool {
+        a == b || matches!((self.group_of(a), self.group_of(b)), (Some(x), Some(y)) if x == y)
+    }
+
+    /// Other nodes among the first `count` that `node` can reach
+    pub fn reachable_from(&self, node: usize, count: usize) -> Vec<usize> {
+        (0..count)
+            .filter(|other| *other != node && self.can_reach(node, *other))
+            .collect()
+    }
+
+    /// The groups, each sorted
+    pub fn groups(&self) -> &[BTreeSet<usize>] {
+        &self.groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_groups_and_reachability() {
+        let partition = Partition::new(&[&[0, 1], &[2, 1], &[]]);
+        assert_eq!(partition.groups().len(), 2);
+        assert_eq!(partition.group_of(1), Some(0));
+        assert!(partition.can_reach(0, 1));
+        assert!(!partition.can_reach(1, 2));
+
+        // Node 3 was not listed, so it is cut off from everyone
+        assert_eq!(partition.group_of(3), None);
+        assert!(partition.can_reach(3, 3));
+        assert!(!partition.can_reach(3, 0));
+
+        assert_eq!(partition.reachable_from(0, 4), vec![1]);
+        assert!(partition.reachable_from(2, 4).is_empty());
+        assert!(partition.reachable_from(3, 4).is_empty());
+    }
+}
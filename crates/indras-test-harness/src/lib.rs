@@ -0,0 +1,37 @@
+//! End-to-end scenario harness for Indra's Network.
+//!
+//! Spins up N real [`IndrasNode`](indras_node::IndrasNode)s on localhost,
+//! scripts what users do (create a realm, invite, message), injects faults
+//! (network partitions), and asserts on the outcome across every node:
+//!
+//! - [`Cluster::eventually_converged`] — all members reach the same
+//!   document heads within a timeout
+//! - [`Cluster::delivered_within`] — an event reaches every member within
+//!   a timeout
+//! - [`Cluster::no_duplicate_events`] — no member holds an event twice
+//!
+//! A failed assertion returns a [`Report`] with one line per offending
+//! node (event counts, heads, which events are missing or repeated), and
+//! [`HarnessError`]'s `Debug` prints it verbatim, so `.unwrap()` in a test
+//! shows something readable.
+//!
+//! ```ignore
+//! let mut cluster = Cluster::start(3).await?;
+//! let realm = cluster.create_shared_realm(0, "team").await?;
+//! cluster.isolate(2).await?;
+//! let event = cluster.send(0, &realm, "while 2 is away").await?;
+//! cluster.heal().await?;
+//! cluster.delivered_within(&realm, &event, Duration::from_secs(10)).await?;
+//! cluster.eventually_converged(&realm, Duration::from_secs(10)).await?;
+//! cluster.no_duplicate_events(&realm).await?;
+//! ```
+
+pub mod cluster;
+pub mod error;
+pub mod fault;
+pub mod report;
+
+pub use cluster::{Cluster, TestNode};
+pub use error::{HarnessError, HarnessResult};
+pub use fault::Partition;
+pub use report::{NodeView, Report};
@@ -0,0 +1,220 @@
+//! Assertion checks and failure reports
+//!
+//! Checks run against [`NodeView`]s — each node's copy of one realm reduced
+//! to event ids and document heads — so they are pure and can be tested
+//! without starting nodes. A failed check yields one line per node; the
+//! cluster wraps those lines in a [`Report`] naming the assertion, the
+//! realm, and how long it waited.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::time::Duration;
+
+use indras_core::EventId;
+use indras_node::InterfaceSnapshot;
+
+/// Event ids listed per line before the rest are summarized
+const MAX_LISTED: usize = 5;
+
+/// One node's copy of a realm
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeView {
+    /// Node name
+    pub node: String,
+    /// Ids of every event in the document, in document order
+    pub events: Vec<EventId>,
+    /// Automerge heads, sorted
+    pub heads: Vec<[u8; 32]>,
+}
+
+impl NodeView {
+    /// Reduce a document snapshot to what the checks compare
+    pub fn from_snapshot(node: impl Into<String>, snapshot: &InterfaceSnapshot) -> Self {
+        let mut heads = snapshot.heads.clone();
+        heads.sort();
+        Self {
+            node: node.into(),
+            events: snapshot
+                .events
+                .iter()
+                .filter_map(|e| e.event_id())
+                .collect(),
+            heads,
+        }
+    }
+
+    fn event_set(&self) -> BTreeSet<EventId> {
+        self.events.iter().copied().collect()
+    }
+}
+
+/// A failed assertion, formatted for humans
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    /// Assertion name, e.g. `eventually_converged`
+    pub assertion: &'static str,
+    /// Short hex id of the realm checked
+    pub realm: String,
+    /// How long the assertion waited before giving up
+    pub waited: Duration,
+    /// One line per offending node
+    pub lines: Vec<String>,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "assertion `{}` failed for realm {} after {:.2}s",
+            self.assertion,
+            self.realm,
+            self.waited.as_secs_f64()
+        )?;
+        for line in &self.lines {
+            write!(f, "\n  {line}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Report {}
+
+fn list_ids<'a>(ids: impl IntoIterator<Item = &'a EventId>) -> String {
+    let ids: Vec<&EventId> = ids.into_iter().collect();
+    let mut listed: Vec<String> = ids
+        .iter()
+        .take(MAX_LISTED)
+        .map(|id| id.to_string())
+        .collect();
+    if ids.len() > MAX_LISTED {
+        listed.push(format!("and {} more", ids.len() - MAX_LISTED));
+    }
+    listed.join(", ")
+}
+
+fn short_heads(heads: &[[u8; 32]]) -> String {
+    let heads: Vec<String> = heads.iter().map(|h| hex::encode(&h[..4])).collect();
+    format!("[{}]", heads.join(", "))
+}
+
+/// Why the views have not converged; empty when they have
+///
+/// Views converge when every node reports the same heads. Otherwise each
+/// node gets a line with its event count, heads, and the events other
+/// nodes have that it lacks.
+pub fn divergence(views: &[NodeView]) -> Vec<String> {
+    let Some(first) = views.first() else {
+        return Vec::new();
+    };
+    if views.iter().all(|v| v.heads == first.heads) {
+        return Vec::new();
+    }
+    let union: BTreeSet<EventId> = views
+        .iter()
+        .flat_map(|v| v.events.iter().copied())
+        .collect();
+    views
+        .iter()
+        .map(|view| {
+            let have = view.event_set();
+            let missing: Vec<&EventId> = union.difference(&have).collect();
+            let mut line = format!(
+                "{}: {} events, heads {}",
+                view.node,
+                have.len(),
+                short_heads(&view.heads)
+            );
+            if !missing.is_empty() {
+                line.push_str(&format!(", missing {}", list_ids(missing)));
+            }
+            line
+        })
+        .collect()
+}
+
+/// Nodes that have not received `event`; empty when all have
+pub fn undelivered(views: &[NodeView], event: &EventId) -> Vec<String> {
+    views
+        .iter()
+        .filter(|v| !v.events.contains(event))
+        .map(|v| format!("{}: no {event} among {} events", v.node, v.events.len()))
+        .collect()
+}
+
+/// Events any node holds more than once; empty when none do
+pub fn duplicates(views: &[NodeView]) -> Vec<String> {
+    views
+        .iter()
+        .filter_map(|view| {
+            let mut counts: BTreeMap<EventId, usize> = BTreeMap::new();
+            for id in &view.events {
+                *counts.entry(*id).or_default() += 1;
+            }
+            let repeated: Vec<String> = counts
+                .into_iter()
+                .filter(|(_, n)| *n > 1)
+                .map(|(id, n)| format!("{id} x{n}"))
+                .collect();
+            (!repeated.is_empty()).then(|| format!("{}: {}", view.node, repeated.join(", ")))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn view(node: &str, seqs: &[u64], head: u8) -> NodeView {
+        NodeView {
+            node: node.to_string(),
+            events: seqs.iter().map(|s| EventId::new(0xabcd, *s)).collect(),
+            heads: vec![[head; 32]],
+        }
+    }
+
+    #[test]
+    fn test_divergence_lists_missing_events() {
+        let converged = [view("node-0", &[1, 2], 7), view("node-1", &[2, 1], 7)];
+        assert!(divergence(&converged).is_empty());
+
+        let split = [view("node-0", &[1, 2, 3], 7), view("node-1", &[1], 9)];
+        let lines = divergence(&split);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], "node-0: 3 events, heads [07070707]");
+        assert_eq!(
+            lines[1],
+            "node-1: 1 events, heads [09090909], missing 0000abcd#2, 0000abcd#3"
+        );
+    }
+
+    #[test]
+    fn test_undelivered_and_duplicates() {
+        let views = [view("node-0", &[1, 2, 2, 2], 1), view("node-1", &[1], 1)];
+        assert_eq!(
+            undelivered(&views, &EventId::new(0xabcd, 2)),
+            vec!["node-1: no 0000abcd#2 among 1 events".to_string()]
+        );
+        assert_eq!(
+            duplicates(&views),
+            vec!["node-0: 0000abcd#2 x3".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_report_format() {
+        let many: Vec<u64> = (1..=8).collect();
+        let lines = divergence(&[view("a", &many, 1), view("b", &[], 2)]);
+        assert!(lines[1].ends_with("and 3 more"));
+
+        let report = Report {
+            assertion: "eventually_converged",
+            realm: "1a2b3c4d".to_string(),
+            waited: Duration::from_millis(2500),
+            lines: vec!["a: fine".to_string(), "b: behind".to_string()],
+        };
+        assert_eq!(
+            report.to_string(),
+            "assertion `eventually_converged` failed for realm 1a2b3c4d after 2.50s\n  a: fine\n  b: behind"
+        );
+    }
+}
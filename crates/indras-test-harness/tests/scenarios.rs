@@ -0,0 +1,80 @@
+//! End-to-end scenarios across real nodes.
+//!
+//! Marked `#[ignore]` by default because they bind real QUIC endpoints and
+//! rely on gossip and sync timing. Run explicitly with:
+//!
+//! ```sh
+//! cargo test -p indras-test-harness --test scenarios -- --ignored
+//! ```
+
+use std::time::Duration;
+
+use indras_test_harness::{Cluster, HarnessResult};
+
+const TIMEOUT: Duration = Duration::from_secs(20);
+
+#[tokio::test]
+#[ignore] // requires network transport
+async fn test_message_reaches_every_member() -> HarnessResult<()> {
+    let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+
+    let mut cluster = Cluster::start(3).await?;
+    let realm = cluster.create_shared_realm(0, "team").await?;
+
+    let event = cluster.send(1, &realm, "hello from node-1").await?;
+
+    cluster.delivered_within(&realm, &event, TIMEOUT).await?;
+    cluster.eventually_converged(&realm, TIMEOUT).await?;
+    cluster.no_duplicate_events(&realm).await?;
+    cluster.stop().await
+}
+
+#[tokio::test]
+#[ignore] // requires network transport
+async fn test_partition_heals_and_converges() -> HarnessResult<()> {
+    let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+
+    let mut cluster = Cluster::start(4).await?;
+    let realm = cluster.create_shared_realm(0, "split").await?;
+    cluster.eventually_converged(&realm, TIMEOUT).await?;
+
+    cluster.partition_into(&[&[0, 1], &[2, 3]]).await?;
+    let left = cluster.send(0, &realm, "left side").await?;
+    let right = cluster.send(3, &realm, "right side").await?;
+
+    // Neither side can see the other's message during the split
+    let blocked = cluster
+        .delivered_within(&realm, &left, Duration::from_secs(2))
+        .await;
+    assert!(blocked.is_err(), "partition should block delivery");
+
+    cluster.heal().await?;
+    cluster.delivered_within(&realm, &left, TIMEOUT).await?;
+    cluster.delivered_within(&realm, &right, TIMEOUT).await?;
+    cluster.eventually_converged(&realm, TIMEOUT).await?;
+    cluster.no_duplicate_events(&realm).await?;
+    cluster.stop().await
+}
+
+#[tokio::test]
+#[ignore] // requires network transport
+async fn test_isolated_node_catches_up() -> HarnessResult<()> {
+    let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+
+    let mut cluster = Cluster::start(3).await?;
+    let realm = cluster.create_shared_realm(0, "catch-up").await?;
+
+    cluster.isolate(2).await?;
+    let mut events = Vec::new();
+    for i in 0..5 {
+        events.push(cluster.send(i % 2, &realm, format!("msg {i}")).await?);
+    }
+
+    cluster.heal().await?;
+    for event in &events {
+        cluster.delivered_within(&realm, event, TIMEOUT).await?;
+    }
+    cluster.eventually_converged(&realm, TIMEOUT).await?;
+    cluster.no_duplicate_events(&realm).await?;
+    cluster.stop().await
+}
@@ -0,0 +1,488 @@
+//! Connection access control for closed deployments
+//!
+//! By default any peer may connect. In [`AccessMode::AllowList`] mode the
+//! node only talks to peers whose iroh public key is either:
+//!
+//! - **Listed** explicitly with [`AccessControl::allow`], or
+//! - **Certified** by a [`PeerCertificate`] signed by a trusted issuer (an
+//!   organisation CA key) and admitted with
+//!   [`AccessControl::admit_certificate`].
+//!
+//! The policy is enforced on both paths: [`AccessGuard`] wraps each Router
+//! protocol handler so unlisted peers are closed with [`ACCESS_DENIED_CODE`]
+//! before any stream is read, and [`ConnectionManager`] refuses to dial them.
+//! Every rejection is logged at `warn` and published as an [`AccessEvent`].
+//!
+//! Access control limits who this node connects to; it does not stop an
+//! allowed peer from relaying gossip it heard elsewhere.
+//!
+//! [`ConnectionManager`]: crate::connection::ConnectionManager
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use dashmap::{DashMap, DashSet};
+use iroh::endpoint::Connection;
+use iroh::protocol::{AcceptError, ProtocolHandler};
+use iroh::{PublicKey, SecretKey, Signature};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+
+use indras_core::identity::PeerIdentity;
+
+use crate::capture::Direction;
+use crate::identity::IrohIdentity;
+
+/// QUIC close code sent to rejected peers
+pub const ACCESS_DENIED_CODE: u32 = 403;
+
+/// Domain separator for certificate signatures
+const CERTIFICATE_DOMAIN: &[u8] = b"indras-peer-certificate-v1";
+
+/// Capacity of the access event channel
+const EVENT_CHANNEL_SIZE: usize = 256;
+
+/// Who may connect
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AccessMode {
+    /// Any peer may connect
+    #[default]
+    Open,
+    /// Only listed or certified peers may connect
+    AllowList,
+}
+
+/// Initial access policy
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccessConfig {
+    /// Enforcement mode
+    pub mode: AccessMode,
+    /// Explicitly allowed peer keys
+    pub allowed: Vec<PublicKey>,
+    /// Issuer keys whose certificates are accepted
+    pub trusted_issuers: Vec<PublicKey>,
+    /// Certificates to admit at startup
+    pub certificates: Vec<PeerCertificate>,
+}
+
+impl AccessConfig {
+    /// Allow-list mode admitting only `keys`
+    pub fn allow_list(keys: impl IntoIterator<Item = PublicKey>) -> Self {
+        Self {
+            mode: AccessMode::AllowList,
+            allowed: keys.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Trust certificates signed by `issuer`
+    pub fn with_trusted_issuer(mut self, issuer: PublicKey) -> Self {
+        self.trusted_issuers.push(issuer);
+        self
+    }
+
+    /// Admit a certificate at startup
+    pub fn with_certificate(mut self, certificate: PeerCertificate) -> Self {
+        self.certificates.push(certificate);
+        self
+    }
+}
+
+/// An issuer's statement that a peer key belongs to the deployment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerCertificate {
+    /// The certified peer key
+    pub subject: PublicKey,
+    /// The issuing (CA) key
+    pub issuer: PublicKey,
+    /// Human-readable name for the subject (e.g. "room-12 laptop")
+    pub label: String,
+    /// Expiry in Unix milliseconds, if any
+    pub expires_at_millis: Option<u64>,
+    /// Issuer's signature over the fields above
+    pub signature: Signature,
+}
+
+impl PeerCertificate {
+    /// Issue a certificate for `subject`
+    pub fn issue(
+        issuer: &SecretKey,
+        subject: PublicKey,
+        label: impl Into<String>,
+        expires_at_millis: Option<u64>,
+    ) -> Self {
+        let label = label.into();
+        let issuer_key = issuer.public();
+        let signature = issuer.sign(&Self::signing_bytes(
+            &subject,
+            &issuer_key,
+            &label,
+            expires_at_millis,
+        ));
+        Self {
+            subject,
+            issuer: issuer_key,
+            label,
+            expires_at_millis,
+            signature,
+        }
+    }
+
+    fn signing_bytes(
+        subject: &PublicKey,
+        issuer: &PublicKey,
+        label: &str,
+        expires_at_millis: Option<u64>,
+    ) -> Vec<u8> {
+        let mut bytes = CERTIFICATE_DOMAIN.to_vec();
+        bytes.extend_from_slice(subject.as_bytes());
+        bytes.extend_from_slice(issuer.as_bytes());
+        bytes.extend_from_slice(&expires_at_millis.unwrap_or(0).to_le_bytes());
+        bytes.extend_from_slice(label.as_bytes());
+        bytes
+    }
+
+    /// Check the signature and expiry
+    pub fn verify(&self, now_millis: u64) -> Result<(), AccessError> {
+        let bytes =
+            Self::signing_bytes(&self.subject, &self.issuer, &self.label, self.expires_at_millis);
+        self.issuer
+            .verify(&bytes, &self.signature)
+            .map_err(|_| AccessError::BadSignature)?;
+        if self.is_expired(now_millis) {
+            return Err(AccessError::Expired);
+        }
+        Ok(())
+    }
+
+    /// Whether the certificate has expired
+    pub fn is_expired(&self, now_millis: u64) -> bool {
+        self.expires_at_millis.is_some_and(|at| now_millis >= at)
+    }
+}
+
+/// Access control errors
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum AccessError {
+    #[error("certificate signature is invalid")]
+    BadSignature,
+
+    #[error("certificate has expired")]
+    Expired,
+
+    #[error("certificate issuer {0} is not trusted")]
+    UntrustedIssuer(String),
+}
+
+/// How a peer passed the access check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Admission {
+    /// Access control is off
+    Open,
+    /// Explicitly allowed
+    Listed,
+    /// Holds a valid certificate from a trusted issuer
+    Certified,
+}
+
+/// Access control changes and rejections
+#[derive(Debug, Clone)]
+pub enum AccessEvent {
+    /// A peer was refused
+    Rejected {
+        peer: IrohIdentity,
+        direction: Direction,
+    },
+    /// A peer key was allowed or certified
+    Allowed { peer: IrohIdentity, admission: Admission },
+    /// A peer key lost access
+    Revoked { peer: IrohIdentity },
+    /// Enforcement mode changed
+    ModeChanged(AccessMode),
+}
+
+/// Current time in Unix milliseconds
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// Live access policy, shared by the adapter and connection manager
+#[derive(Debug)]
+pub struct AccessControl {
+    allow_list: AtomicBool,
+    allowed: DashSet<PublicKey>,
+    trusted_issuers: DashSet<PublicKey>,
+    certified: DashMap<PublicKey, PeerCertificate>,
+    events: broadcast::Sender<AccessEvent>,
+}
+
+impl Default for AccessControl {
+    fn default() -> Self {
+        Self::new(AccessConfig::default())
+    }
+}
+
+impl AccessControl {
+    /// Build from a config; invalid certificates are logged and skipped
+    pub fn new(config: AccessConfig) -> Self {
+        let (events, _) = broadcast::channel(EVENT_CHANNEL_SIZE);
+        let access = Self {
+            allow_list: AtomicBool::new(config.mode == AccessMode::AllowList),
+            allowed: config.allowed.into_iter().collect(),
+            trusted_issuers: config.trusted_issuers.into_iter().collect(),
+            certified: DashMap::new(),
+            events,
+        };
+        for certificate in config.certificates {
+            let subject = IrohIdentity::new(certificate.subject);
+            if let Err(e) = access.admit_certificate(certificate) {
+                warn!(peer = %subject.short_id(), error = %e, "Skipping invalid peer certificate");
+            }
+        }
+        access
+    }
+
+    /// Subscribe to access events
+    pub fn subscribe(&self) -> broadcast::Receiver<AccessEvent> {
+        self.events.subscribe()
+    }
+
+    /// Current enforcement mode
+    pub fn mode(&self) -> AccessMode {
+        if self.allow_list.load(Ordering::Acquire) {
+            AccessMode::AllowList
+        } else {
+            AccessMode::Open
+        }
+    }
+
+    /// Change the enforcement mode
+    ///
+    /// Existing connections are not closed here; see
+    /// [`IrohNetworkAdapter::enforce_access`](crate::IrohNetworkAdapter::enforce_access).
+    pub fn set_mode(&self, mode: AccessMode) {
+        let previous = self
+            .allow_list
+            .swap(mode == AccessMode::AllowList, Ordering::AcqRel);
+        if previous != (mode == AccessMode::AllowList) {
+            info!(?mode, "Access mode changed");
+            let _ = self.events.send(AccessEvent::ModeChanged(mode));
+        }
+    }
+
+    /// Allow a peer key
+    pub fn allow(&self, key: PublicKey) {
+        if self.allowed.insert(key) {
+            let _ = self.events.send(AccessEvent::Allowed {
+                peer: IrohIdentity::new(key),
+                admission: Admission::Listed,
+            });
+        }
+    }
+
+    /// Remove a peer's access, whether listed or certified
+    ///
+    /// Returns `true` if the peer had access.
+    pub fn revoke(&self, key: &PublicKey) -> bool {
+        let listed = self.allowed.remove(key).is_some();
+        let certified = self.certified.remove(key).is_some();
+        let revoked = listed || certified;
+        if revoked {
+            let peer = IrohIdentity::new(*key);
+            info!(peer = %peer.short_id(), "Peer access revoked");
+            let _ = self.events.send(AccessEvent::Revoked { peer });
+        }
+        revoked
+    }
+
+    /// Accept certificates signed by `issuer`
+    pub fn trust_issuer(&self, issuer: PublicKey) {
+        self.trusted_issuers.insert(issuer);
+    }
+
+    /// Stop accepting certificates from `issuer`, revoking peers it certified
+    pub fn distrust_issuer(&self, issuer: &PublicKey) {
+        self.trusted_issuers.remove(issuer);
+        let subjects: Vec<PublicKey> = self
+            .certified
+            .iter()
+            .filter(|c| c.value().issuer == *issuer)
+            .map(|c| *c.key())
+            .collect();
+        for subject in subjects {
+            self.certified.remove(&subject);
+            let _ = self.events.send(AccessEvent::Revoked {
+                peer: IrohIdentity::new(subject),
+            });
+        }
+    }
+
+    /// Admit a peer by certificate
+    pub fn admit_certificate(&self, certificate: PeerCertificate) -> Result<(), AccessError> {
+        if !self.trusted_issuers.contains(&certificate.issuer) {
+            return Err(AccessError::UntrustedIssuer(
+                IrohIdentity::new(certificate.issuer).short_id(),
+            ));
+        }
+        certificate.verify(now_millis())?;
+        let subject = certificate.subject;
+        self.certified.insert(subject, certificate);
+        let _ = self.events.send(AccessEvent::Allowed {
+            peer: IrohIdentity::new(subject),
+            admission: Admission::Certified,
+        });
+        Ok(())
+    }
+
+    /// Explicitly allowed keys
+    pub fn allowed_peers(&self) -> Vec<PublicKey> {
+        self.allowed.iter().map(|k| *k).collect()
+    }
+
+    /// Admitted certificates
+    pub fn certificates(&self) -> Vec<PeerCertificate> {
+        self.certified.iter().map(|c| c.value().clone()).collect()
+    }
+
+    /// How a peer would be admitted, or `None` if it would be refused
+    pub fn admission(&self, key: &PublicKey) -> Option<Admission> {
+        if self.mode() == AccessMode::Open {
+            return Some(Admission::Open);
+        }
+        if self.allowed.contains(key) {
+            return Some(Admission::Listed);
+        }
+        let certificate = self.certified.get(key)?;
+        (self.trusted_issuers.contains(&certificate.issuer)
+            && !certificate.is_expired(now_millis()))
+        .then_some(Admission::Certified)
+    }
+
+    /// Whether a peer may connect, reporting a rejection if not
+    pub fn check(&self, peer: &IrohIdentity, direction: Direction) -> bool {
+        if self.admission(peer.public_key()).is_some() {
+            return true;
+        }
+        warn!(peer = %peer.short_id(), %direction, "Connection rejected by access control");
+        let _ = self.events.send(AccessEvent::Rejected {
+            peer: *peer,
+            direction,
+        });
+        false
+    }
+}
+
+/// Router protocol handler that applies [`AccessControl`] before delegating
+#[derive(Debug, Clone)]
+pub struct AccessGuard<H> {
+    access: Arc<AccessControl>,
+    inner: H,
+}
+
+impl<H> AccessGuard<H> {
+    /// Wrap `inner` with an access check
+    pub fn new(access: Arc<AccessControl>, inner: H) -> Self {
+        Self { access, inner }
+    }
+}
+
+impl<H: ProtocolHandler> ProtocolHandler for AccessGuard<H> {
+    async fn accept(&self, connection: Connection) -> Result<(), AcceptError> {
+        let peer = IrohIdentity::new(connection.remote_id());
+        if !self.access.check(&peer, Direction::Inbound) {
+            connection.close(ACCESS_DENIED_CODE.into(), b"access denied");
+            return Err(AcceptError::from(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                "peer is not on the allow-list",
+            )));
+        }
+        self.inner.accept(connection).await
+    }
+
+    async fn shutdown(&self) {
+        self.inner.shutdown().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key() -> SecretKey {
+        SecretKey::generate(&mut rand::rng())
+    }
+
+    #[test]
+    fn test_open_mode_admits_everyone() {
+        let access = AccessControl::default();
+        let peer = IrohIdentity::new(key().public());
+        assert_eq!(access.admission(peer.public_key()), Some(Admission::Open));
+        assert!(access.check(&peer, Direction::Inbound));
+    }
+
+    #[test]
+    fn test_allow_list_and_revoke() {
+        let listed = key().public();
+        let stranger = IrohIdentity::new(key().public());
+        let access = AccessControl::new(AccessConfig::allow_list([listed]));
+        let mut events = access.subscribe();
+
+        assert_eq!(access.admission(&listed), Some(Admission::Listed));
+        assert!(!access.check(&stranger, Direction::Outbound));
+        assert!(matches!(
+            events.try_recv(),
+            Ok(AccessEvent::Rejected { direction: Direction::Outbound, .. })
+        ));
+
+        assert!(access.revoke(&listed));
+        assert_eq!(access.admission(&listed), None);
+        assert!(!access.revoke(&listed));
+    }
+
+    #[test]
+    fn test_certificates_from_trusted_issuer() {
+        let ca = key();
+        let subject = key().public();
+        let access = AccessControl::new(
+            AccessConfig::allow_list([]).with_trusted_issuer(ca.public()),
+        );
+
+        let rogue = PeerCertificate::issue(&key(), subject, "rogue", None);
+        assert!(matches!(
+            access.admit_certificate(rogue),
+            Err(AccessError::UntrustedIssuer(_))
+        ));
+
+        let mut forged = PeerCertificate::issue(&ca, subject, "laptop", None);
+        forged.label = "someone else".into();
+        assert_eq!(access.admit_certificate(forged), Err(AccessError::BadSignature));
+
+        let expired = PeerCertificate::issue(&ca, subject, "laptop", Some(1));
+        assert_eq!(access.admit_certificate(expired), Err(AccessError::Expired));
+
+        let valid = PeerCertificate::issue(&ca, subject, "laptop", None);
+        access.admit_certificate(valid).unwrap();
+        assert_eq!(access.admission(&subject), Some(Admission::Certified));
+
+        access.distrust_issuer(&ca.public());
+        assert_eq!(access.admission(&subject), None);
+    }
+
+    #[test]
+    fn test_mode_switch() {
+        let access = AccessControl::default();
+        let peer = key().public();
+        access.set_mode(AccessMode::AllowList);
+        assert_eq!(access.admission(&peer), None);
+        access.allow(peer);
+        assert_eq!(access.admission(&peer), Some(Admission::Listed));
+        access.set_mode(AccessMode::Open);
+        assert_eq!(access.mode(), AccessMode::Open);
+    }
+}
@@ -42,6 +42,8 @@ use indras_core::identity::PeerIdentity;
 use indras_core::traits::NetworkTopology;
 use indras_core::transport::Transport;
 
+use crate::access::{AccessConfig, AccessControl, AccessGuard};
+use crate::capture::{CaptureConfig, CaptureDecryptor, Direction, PacketCapture};
 use crate::connection::{ConnectionConfig, ConnectionError, ConnectionManager};
 use crate::discovery::{DiscoveryConfig, DiscoveryError, DiscoveryService, PeerEvent, PeerInfo};
 use crate::identity::IrohIdentity;
@@ -60,6 +62,10 @@ pub struct AdapterConfig {
     pub auto_connect: bool,
     /// Maximum reconnection attempts
     pub max_reconnect_attempts: u32,
+    /// Packet capture for protocol debugging (off by default)
+    pub capture: Option<CaptureConfig>,
+    /// Who may connect (open by default)
+    pub access: AccessConfig,
 }
 
 impl Default for AdapterConfig {
@@ -70,6 +76,8 @@ impl Default for AdapterConfig {
             message_buffer_size: 1024,
             auto_connect: false,
             max_reconnect_attempts: 3,
+            capture: None,
+            access: AccessConfig::default(),
         }
     }
 }
@@ -119,6 +127,10 @@ pub struct IrohNetworkAdapter {
     bi_stream_tx: mpsc::Sender<(IrohIdentity, SendStream, RecvStream)>,
     /// Receiver for incoming bidirectional streams (taken once by the relay service).
     bi_stream_rx: Mutex<Option<mpsc::Receiver<(IrohIdentity, SendStream, RecvStream)>>>,
+    /// Packet capture, if enabled in the config
+    capture: Option<Arc<PacketCapture>>,
+    /// Connection allow-list, shared with the connection manager and Router guards
+    access: Arc<AccessControl>,
 }
 
 impl IrohNetworkAdapter {
@@ -127,11 +139,14 @@ impl IrohNetworkAdapter {
     /// This initializes the iroh endpoint, connection manager, gossip, and
     /// discovery service.
     pub async fn new(secret_key: SecretKey, config: AdapterConfig) -> Result<Self, AdapterError> {
+        let access = Arc::new(AccessControl::new(config.access.clone()));
+
         // Create connection manager (endpoint created without ALPNs — Router registers them)
         let connection_manager =
             ConnectionManager::new(secret_key.clone(), config.connection.clone())
                 .await
-                .map_err(|e| AdapterError::ConnectionManager(e.to_string()))?;
+                .map_err(|e| AdapterError::ConnectionManager(e.to_string()))?
+                .with_access_control(access.clone());
 
         let local_identity = connection_manager.local_identity();
         let endpoint = connection_manager.endpoint().clone();
@@ -143,12 +158,12 @@ impl IrohNetworkAdapter {
         let (conn_tx, conn_rx) = mpsc::channel(64);
         let indras_handler = IndrasProtocolHandler::new(conn_tx);
 
-        // Create Router for multi-ALPN dispatch:
+        // Create Router for multi-ALPN dispatch, both behind the access guard:
         // - indras/1: our custom protocol for direct peer messaging
         // - /iroh-gossip/1: gossip protocol for topic-based pub/sub
         let router = Router::builder(endpoint)
-            .accept(ALPN_INDRAS, indras_handler)
-            .accept(GOSSIP_ALPN, gossip.clone())
+            .accept(ALPN_INDRAS, AccessGuard::new(access.clone(), indras_handler))
+            .accept(GOSSIP_ALPN, AccessGuard::new(access.clone(), gossip.clone()))
             .spawn();
 
         // Create discovery service
@@ -162,6 +177,20 @@ impl IrohNetworkAdapter {
         // Create bidirectional stream channel (for relay protocol)
         let (bi_stream_tx, bi_stream_rx) = mpsc::channel(256);
 
+        let capture = match &config.capture {
+            Some(capture_config) => {
+                let capture = PacketCapture::new(capture_config.clone(), local_identity)
+                    .map_err(|e| AdapterError::Capture(e.to_string()))?;
+                info!(
+                    directory = %capture_config.directory.display(),
+                    sample_rate = capture_config.sample_rate,
+                    "Packet capture enabled"
+                );
+                Some(Arc::new(capture))
+            }
+            None => None,
+        };
+
         info!(
             identity = %local_identity.short_id(),
             "IrohNetworkAdapter created with Router (indras/1 + gossip ALPNs)"
@@ -183,9 +212,57 @@ impl IrohNetworkAdapter {
             handled_peers: Arc::new(DashSet::new()),
             bi_stream_tx,
             bi_stream_rx: Mutex::new(Some(bi_stream_rx)),
+            capture,
+            access,
         })
     }
 
+    /// Install a decryptor so captures include decrypted payloads
+    ///
+    /// No-op unless capture is enabled with `include_plaintext`.
+    pub fn set_capture_decryptor(&self, decryptor: Arc<dyn CaptureDecryptor>) {
+        if let Some(capture) = &self.capture {
+            capture.set_decryptor(decryptor);
+        }
+    }
+
+    fn capture(&self, direction: Direction, peer: &IrohIdentity, data: &[u8]) {
+        if let Some(capture) = &self.capture {
+            capture.record(direction, peer, data);
+        }
+    }
+
+    /// The live access policy
+    ///
+    /// Changes apply to new connections immediately; call
+    /// [`enforce_access`](Self::enforce_access) to drop existing ones.
+    pub fn access_control(&self) -> &Arc<AccessControl> {
+        &self.access
+    }
+
+    /// Revoke a peer's access and close any connection to it
+    pub fn revoke_peer(&self, key: &PublicKey) -> bool {
+        let revoked = self.access.revoke(key);
+        self.enforce_access();
+        revoked
+    }
+
+    /// Close connections to peers the access policy no longer admits
+    ///
+    /// Returns the number of connections closed.
+    pub fn enforce_access(&self) -> usize {
+        let mut closed = 0;
+        for peer in self.connection_manager.connected_peers() {
+            if self.access.admission(peer.public_key()).is_none() {
+                info!(peer = %peer.short_id(), "Closing connection to peer without access");
+                self.connection_manager.close_connection(&peer);
+                self.handled_peers.remove(&peer);
+                closed += 1;
+            }
+        }
+        closed
+    }
+
     /// Start the adapter
     ///
     /// Starts the discovery service and begins accepting connections.
@@ -585,6 +662,8 @@ impl IrohNetworkAdapter {
         send.finish()
             .map_err(|e| TransportError::SendFailed(e.to_string()))?;
 
+        self.capture(Direction::Outbound, peer, &data);
+
         Ok(())
     }
 }
@@ -601,6 +680,7 @@ impl Transport<IrohIdentity> for IrohNetworkAdapter {
             .recv()
             .await
             .ok_or_else(|| TransportError::ReceiveFailed("channel closed".into()))?;
+        self.capture(Direction::Inbound, &msg.sender, &msg.data);
         Ok((msg.sender, msg.data))
     }
 
@@ -637,7 +717,10 @@ impl Transport<IrohIdentity> for IrohNetworkAdapter {
     async fn try_recv(&self) -> Result<Option<(IrohIdentity, Vec<u8>)>, TransportError> {
         let mut rx = self.message_rx.write().await;
         match rx.try_recv() {
-            Ok(msg) => Ok(Some((msg.sender, msg.data))),
+            Ok(msg) => {
+                self.capture(Direction::Inbound, &msg.sender, &msg.data);
+                Ok(Some((msg.sender, msg.data)))
+            }
             Err(mpsc::error::TryRecvError::Empty) => Ok(None),
             Err(mpsc::error::TryRecvError::Disconnected) => {
                 Err(TransportError::ReceiveFailed("channel disconnected".into()))
@@ -715,6 +798,9 @@ pub enum AdapterError {
 
     #[error("Not running")]
     NotRunning,
+
+    #[error("Packet capture error: {0}")]
+    Capture(String),
 }
 
 impl From<ConnectionError> for AdapterError {
@@ -0,0 +1,456 @@
+//! Sampled packet capture for wire protocol debugging
+//!
+//! When enabled via [`AdapterConfig::capture`](crate::AdapterConfig), the
+//! adapter records the bytes of every sampled message it sends or receives
+//! into a rotating capture file, pcap-style. Each record carries a
+//! timestamp, direction, both peer ids, and the raw message. A
+//! [`CaptureDecryptor`] supplied by the layer that owns interface keys can
+//! attach the decrypted payload as well.
+//!
+//! ## File format
+//!
+//! A capture file starts with [`CAPTURE_MAGIC`], followed by records, each a
+//! 4-byte big-endian length and a postcard-encoded [`CaptureRecord`].
+//! Read them back with [`CaptureReader`].
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use indras_transport::{AdapterConfig, CaptureConfig};
+//!
+//! let config = AdapterConfig {
+//!     capture: Some(CaptureConfig::new("./captures").with_sample_rate(0.1)),
+//!     ..AdapterConfig::default()
+//! };
+//! ```
+
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::warn;
+
+use indras_core::identity::PeerIdentity;
+
+use crate::identity::IrohIdentity;
+
+/// Magic bytes at the start of every capture file (includes format version)
+pub const CAPTURE_MAGIC: &[u8; 8] = b"INDRCAP1";
+
+/// File extension for capture files
+pub const CAPTURE_EXTENSION: &str = "icap";
+
+/// Errors from writing or reading capture files
+#[derive(Debug, Error)]
+pub enum CaptureError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Not a capture file (bad magic)")]
+    BadMagic,
+
+    #[error("Corrupt capture record: {0}")]
+    Corrupt(String),
+}
+
+/// Packet capture configuration
+#[derive(Debug, Clone)]
+pub struct CaptureConfig {
+    /// Directory capture files are written to
+    pub directory: PathBuf,
+    /// File name prefix
+    pub prefix: String,
+    /// Fraction of messages recorded (1.0 = all)
+    pub sample_rate: f64,
+    /// Rotate to a new file once the current one exceeds this size
+    pub max_file_bytes: u64,
+    /// Number of capture files kept; older ones are deleted on rotation
+    pub max_files: usize,
+    /// Attach decrypted payloads when a decryptor is installed
+    pub include_plaintext: bool,
+}
+
+impl CaptureConfig {
+    /// Capture everything into `directory` with default rotation limits
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            prefix: "capture".to_string(),
+            sample_rate: 1.0,
+            max_file_bytes: 64 * 1024 * 1024,
+            max_files: 8,
+            include_plaintext: false,
+        }
+    }
+
+    /// Set the file name prefix
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Set the sample rate (clamped to 0.0..=1.0)
+    pub fn with_sample_rate(mut self, rate: f64) -> Self {
+        self.sample_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set rotation limits
+    pub fn with_rotation(mut self, max_file_bytes: u64, max_files: usize) -> Self {
+        self.max_file_bytes = max_file_bytes;
+        self.max_files = max_files.max(1);
+        self
+    }
+
+    /// Record decrypted payloads alongside the ciphertext
+    ///
+    /// Only takes effect if the owner of the interface keys installs a
+    /// [`CaptureDecryptor`]. Capture files then contain plaintext, so keep
+    /// them local.
+    pub fn with_plaintext(mut self, include: bool) -> Self {
+        self.include_plaintext = include;
+        self
+    }
+}
+
+/// Which way a captured message travelled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Direction {
+    /// Sent by the capturing node
+    Outbound,
+    /// Received by the capturing node
+    Inbound,
+}
+
+impl std::fmt::Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Direction::Outbound => write!(f, "->"),
+            Direction::Inbound => write!(f, "<-"),
+        }
+    }
+}
+
+/// A single captured message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureRecord {
+    /// When the message was sent or received
+    pub timestamp: DateTime<Utc>,
+    /// Direction relative to the capturing node
+    pub direction: Direction,
+    /// The capturing node's public key
+    pub local: Vec<u8>,
+    /// The remote peer's public key
+    pub peer: Vec<u8>,
+    /// Message bytes exactly as carried on the stream
+    pub data: Vec<u8>,
+    /// Decrypted payload, if a decryptor was installed and could open it
+    pub plaintext: Option<Vec<u8>>,
+}
+
+impl CaptureRecord {
+    /// Short hex id of the capturing node
+    pub fn local_short_id(&self) -> String {
+        short_hex(&self.local)
+    }
+
+    /// Short hex id of the remote peer
+    pub fn peer_short_id(&self) -> String {
+        short_hex(&self.peer)
+    }
+}
+
+fn short_hex(bytes: &[u8]) -> String {
+    bytes.iter().take(4).map(|b| format!("{b:02x}")).collect()
+}
+
+/// Decrypts captured message bytes using locally held keys
+///
+/// The transport doesn't know the message format or hold interface keys,
+/// so the node layer implements this and installs it with
+/// [`IrohNetworkAdapter::set_capture_decryptor`](crate::IrohNetworkAdapter::set_capture_decryptor).
+pub trait CaptureDecryptor: Send + Sync {
+    /// Return the decrypted payload, or `None` if the message isn't
+    /// encrypted or no key is held for it
+    fn decrypt(&self, data: &[u8]) -> Option<Vec<u8>>;
+}
+
+/// Current output file and its size
+struct CaptureFile {
+    writer: BufWriter<File>,
+    written: u64,
+}
+
+/// Writes sampled messages to rotating capture files
+pub struct PacketCapture {
+    config: CaptureConfig,
+    local: IrohIdentity,
+    file: Mutex<Option<CaptureFile>>,
+    decryptor: RwLock<Option<Arc<dyn CaptureDecryptor>>>,
+}
+
+impl PacketCapture {
+    /// Create a capture writer; the first file is opened on the first record
+    pub fn new(config: CaptureConfig, local: IrohIdentity) -> Result<Self, CaptureError> {
+        fs::create_dir_all(&config.directory)?;
+        Ok(Self {
+            config,
+            local,
+            file: Mutex::new(None),
+            decryptor: RwLock::new(None),
+        })
+    }
+
+    /// The configuration this capture was created with
+    pub fn config(&self) -> &CaptureConfig {
+        &self.config
+    }
+
+    /// Install the decryptor used when `include_plaintext` is set
+    pub fn set_decryptor(&self, decryptor: Arc<dyn CaptureDecryptor>) {
+        *self.decryptor.write().unwrap() = Some(decryptor);
+    }
+
+    /// Record a message if it is sampled
+    ///
+    /// Failures are logged and otherwise ignored: capture must never
+    /// interfere with delivery.
+    pub fn record(&self, direction: Direction, peer: &IrohIdentity, data: &[u8]) {
+        if self.config.sample_rate < 1.0 && rand::random::<f64>() >= self.config.sample_rate {
+            return;
+        }
+
+        let plaintext = if self.config.include_plaintext {
+            self.decryptor
+                .read()
+                .unwrap()
+                .as_ref()
+                .and_then(|d| d.decrypt(data))
+        } else {
+            None
+        };
+
+        let record = CaptureRecord {
+            timestamp: Utc::now(),
+            direction,
+            local: self.local.as_bytes(),
+            peer: peer.as_bytes(),
+            data: data.to_vec(),
+            plaintext,
+        };
+
+        if let Err(e) = self.write_record(&record) {
+            warn!(error = %e, "Failed to write capture record");
+        }
+    }
+
+    fn write_record(&self, record: &CaptureRecord) -> Result<(), CaptureError> {
+        let encoded =
+            postcard::to_allocvec(record).map_err(|e| CaptureError::Corrupt(e.to_string()))?;
+
+        let mut guard = self.file.lock().unwrap();
+        let needs_rotation = guard
+            .as_ref()
+            .is_none_or(|f| f.written >= self.config.max_file_bytes);
+        if needs_rotation {
+            if let Some(mut old) = guard.take() {
+                old.writer.flush()?;
+            }
+            *guard = Some(self.open_next_file()?);
+        }
+
+        let file = guard.as_mut().expect("capture file opened above");
+        file.writer.write_all(&(encoded.len() as u32).to_be_bytes())?;
+        file.writer.write_all(&encoded)?;
+        file.writer.flush()?;
+        file.written += 4 + encoded.len() as u64;
+        Ok(())
+    }
+
+    fn open_next_file(&self) -> Result<CaptureFile, CaptureError> {
+        let name = format!(
+            "{}-{}.{}",
+            self.config.prefix,
+            Utc::now().format("%Y%m%dT%H%M%S%.6f"),
+            CAPTURE_EXTENSION
+        );
+        let mut writer = BufWriter::new(File::create(self.config.directory.join(name))?);
+        writer.write_all(CAPTURE_MAGIC)?;
+        self.prune_old_files()?;
+        Ok(CaptureFile {
+            writer,
+            written: CAPTURE_MAGIC.len() as u64,
+        })
+    }
+
+    /// Delete the oldest capture files beyond `max_files`
+    fn prune_old_files(&self) -> Result<(), CaptureError> {
+        let mut files = capture_files(&self.config.directory, &self.config.prefix)?;
+        if files.len() > self.config.max_files {
+            let excess = files.len() - self.config.max_files;
+            for path in files.drain(..excess) {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Capture files in `directory` with the given prefix, oldest first
+pub fn capture_files(directory: &Path, prefix: &str) -> Result<Vec<PathBuf>, CaptureError> {
+    let mut files: Vec<PathBuf> = fs::read_dir(directory)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.extension().is_some_and(|ext| ext == CAPTURE_EXTENSION)
+                && path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.starts_with(&format!("{prefix}-")))
+        })
+        .collect();
+    // Timestamped names sort chronologically
+    files.sort();
+    Ok(files)
+}
+
+/// Iterates over the records in a capture file
+pub struct CaptureReader<R: Read> {
+    reader: R,
+}
+
+impl CaptureReader<BufReader<File>> {
+    /// Open a capture file and check its header
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, CaptureError> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> CaptureReader<R> {
+    /// Wrap a reader positioned at the start of a capture file
+    pub fn new(mut reader: R) -> Result<Self, CaptureError> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != CAPTURE_MAGIC {
+            return Err(CaptureError::BadMagic);
+        }
+        Ok(Self { reader })
+    }
+
+    fn next_record(&mut self) -> Result<Option<CaptureRecord>, CaptureError> {
+        let mut len = [0u8; 4];
+        match self.reader.read_exact(&mut len) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e.into()),
+        }
+        let mut buf = vec![0u8; u32::from_be_bytes(len) as usize];
+        self.reader
+            .read_exact(&mut buf)
+            .map_err(|e| CaptureError::Corrupt(format!("truncated record: {e}")))?;
+        postcard::from_bytes(&buf)
+            .map(Some)
+            .map_err(|e| CaptureError::Corrupt(e.to_string()))
+    }
+}
+
+impl<R: Read> Iterator for CaptureReader<R> {
+    type Item = Result<CaptureRecord, CaptureError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_record().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iroh::SecretKey;
+
+    fn identity() -> IrohIdentity {
+        IrohIdentity::new(SecretKey::generate(&mut rand::rng()).public())
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("indras-capture-{name}-{}", rand::random::<u64>()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    struct Reverse;
+    impl CaptureDecryptor for Reverse {
+        fn decrypt(&self, data: &[u8]) -> Option<Vec<u8>> {
+            Some(data.iter().rev().copied().collect())
+        }
+    }
+
+    #[test]
+    fn test_round_trip_with_plaintext() {
+        let dir = temp_dir("roundtrip");
+        let capture =
+            PacketCapture::new(CaptureConfig::new(&dir).with_plaintext(true), identity()).unwrap();
+        capture.set_decryptor(Arc::new(Reverse));
+
+        let peer = identity();
+        capture.record(Direction::Outbound, &peer, b"abc");
+        capture.record(Direction::Inbound, &peer, b"xyz");
+
+        let files = capture_files(&dir, "capture").unwrap();
+        assert_eq!(files.len(), 1);
+        let records: Vec<_> = CaptureReader::open(&files[0])
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].direction, Direction::Outbound);
+        assert_eq!(records[0].data, b"abc");
+        assert_eq!(records[0].plaintext.as_deref(), Some(&b"cba"[..]));
+        assert_eq!(records[1].peer, peer.as_bytes());
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_rotation_keeps_max_files() {
+        let dir = temp_dir("rotate");
+        let capture =
+            PacketCapture::new(CaptureConfig::new(&dir).with_rotation(1, 2), identity()).unwrap();
+
+        let peer = identity();
+        for _ in 0..5 {
+            capture.record(Direction::Inbound, &peer, b"payload");
+            // Distinct timestamps in file names
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+
+        let files = capture_files(&dir, "capture").unwrap();
+        assert_eq!(files.len(), 2);
+        // Newest file holds the last record only
+        let last: Vec<_> = CaptureReader::open(files.last().unwrap())
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(last.len(), 1);
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_zero_sample_rate_records_nothing() {
+        let dir = temp_dir("sample");
+        let capture =
+            PacketCapture::new(CaptureConfig::new(&dir).with_sample_rate(0.0), identity()).unwrap();
+        capture.record(Direction::Inbound, &identity(), b"dropped");
+        assert!(capture_files(&dir, "capture").unwrap().is_empty());
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_bad_magic_rejected() {
+        let result = CaptureReader::new(&b"NOTACAPTURE"[..]);
+        assert!(matches!(result, Err(CaptureError::BadMagic)));
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! Handles endpoint lifecycle, connection pooling, and stream management.
 
+use std::sync::Arc;
+
 use dashmap::DashMap;
 use iroh::endpoint::Connection;
 use iroh::{Endpoint, EndpointAddr, PublicKey, SecretKey};
@@ -11,6 +13,8 @@ use tracing::{debug, info, instrument, warn};
 
 use indras_core::identity::PeerIdentity;
 
+use crate::access::{ACCESS_DENIED_CODE, AccessControl};
+use crate::capture::Direction;
 use crate::identity::IrohIdentity;
 use crate::protocol::ALPN_INDRAS;
 
@@ -68,6 +72,9 @@ pub enum ConnectionError {
 
     #[error("Iroh error: {0}")]
     IrohError(String),
+
+    #[error("Peer {0} is not allowed by access control")]
+    AccessDenied(String),
 }
 
 /// Manages iroh endpoint and connections
@@ -83,6 +90,8 @@ pub struct ConnectionManager {
     connections: DashMap<IrohIdentity, Connection>,
     /// Configuration
     config: ConnectionConfig,
+    /// Who we may connect to
+    access: Arc<AccessControl>,
     /// Shutdown flag
     shutdown: RwLock<bool>,
 }
@@ -126,10 +135,22 @@ impl ConnectionManager {
             identity,
             connections: DashMap::new(),
             config,
+            access: Arc::new(AccessControl::default()),
             shutdown: RwLock::new(false),
         })
     }
 
+    /// Use a shared access policy instead of the default open one
+    pub fn with_access_control(mut self, access: Arc<AccessControl>) -> Self {
+        self.access = access;
+        self
+    }
+
+    /// The access policy applied to connections
+    pub fn access_control(&self) -> &Arc<AccessControl> {
+        &self.access
+    }
+
     /// Get our local identity
     pub fn local_identity(&self) -> IrohIdentity {
         self.identity
@@ -150,6 +171,10 @@ impl ConnectionManager {
     pub async fn connect(&self, addr: EndpointAddr) -> Result<Connection, ConnectionError> {
         let peer_id = IrohIdentity::new(addr.id);
 
+        if !self.access.check(&peer_id, Direction::Outbound) {
+            return Err(ConnectionError::AccessDenied(peer_id.short_id()));
+        }
+
         // Check if we already have a connection
         if let Some(conn) = self.connections.get(&peer_id) {
             if conn.close_reason().is_none() {
@@ -240,6 +265,11 @@ impl ConnectionManager {
         let peer_key = conn.remote_id();
         let peer_id = IrohIdentity::new(peer_key);
 
+        if !self.access.check(&peer_id, Direction::Inbound) {
+            conn.close(ACCESS_DENIED_CODE.into(), b"access denied");
+            return Err(ConnectionError::AccessDenied(peer_id.short_id()));
+        }
+
         info!(remote_peer = %peer_id.short_id(), "Accepted incoming connection");
 
         // Store the connection (replacing any existing one from this peer)
@@ -269,6 +299,14 @@ impl ConnectionManager {
             .unwrap_or(false)
     }
 
+    /// Current round-trip time estimate for an open connection to a peer
+    pub fn rtt(&self, peer: &IrohIdentity) -> Option<std::time::Duration> {
+        self.connections
+            .get(peer)
+            .filter(|c| c.close_reason().is_none())
+            .map(|c| c.rtt())
+    }
+
     /// Get all connected peer identities
     pub fn connected_peers(&self) -> Vec<IrohIdentity> {
         self.connections
@@ -374,6 +412,23 @@ mod tests {
         assert_eq!(manager.local_identity(), expected_id);
     }
 
+    #[tokio::test]
+    async fn test_connect_refused_by_access_control() {
+        let secret = SecretKey::generate(&mut rand::rng());
+        let stranger = SecretKey::generate(&mut rand::rng()).public();
+        let access = Arc::new(AccessControl::new(crate::access::AccessConfig::allow_list([])));
+
+        let manager = ConnectionManager::new(secret, ConnectionConfig::default())
+            .await
+            .unwrap()
+            .with_access_control(access);
+
+        assert!(matches!(
+            manager.connect_by_key(stranger).await,
+            Err(ConnectionError::AccessDenied(_))
+        ));
+    }
+
     #[test]
     fn test_connection_config_default() {
         let config = ConnectionConfig::default();
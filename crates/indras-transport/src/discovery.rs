@@ -8,7 +8,7 @@ use std::time::Instant;
 use dashmap::DashMap;
 use iroh::PublicKey;
 use iroh_gossip::Gossip;
-use iroh_gossip::api::GossipTopic;
+use iroh_gossip::api::{GossipReceiver, GossipSender, GossipTopic};
 use iroh_gossip::proto::TopicId;
 use thiserror::Error;
 use tokio::sync::{RwLock, broadcast};
@@ -134,6 +134,14 @@ pub struct PeerInfo {
 /// Rate limit duration for introduction responses (30 seconds)
 const INTRODUCTION_RATE_LIMIT_SECS: u64 = 30;
 
+/// A joined realm topic
+///
+/// Both halves are kept so the topic stays joined until this is dropped.
+struct RealmTopic {
+    sender: GossipSender,
+    _receiver: GossipReceiver,
+}
+
 /// Peer discovery service using iroh-gossip
 pub struct DiscoveryService {
     /// Gossip handle
@@ -153,7 +161,7 @@ pub struct DiscoveryService {
 
     // ========== Per-realm discovery ==========
     /// Topics for each realm we're a member of
-    realm_topics: DashMap<InterfaceId, GossipTopic>,
+    realm_topics: DashMap<InterfaceId, RealmTopic>,
     /// Known peers per realm (InterfaceId -> (PeerId -> PeerInfo))
     realm_peers: DashMap<InterfaceId, DashMap<IrohIdentity, RealmPeerInfo>>,
     /// Rate limiting for introduction responses: (InterfaceId, PeerId) -> last_response_time
@@ -295,7 +303,14 @@ impl DiscoveryService {
             .map_err(|e| DiscoveryError::JoinError(e.to_string()))?;
 
         // Store the topic handle
-        self.realm_topics.insert(interface_id, topic);
+        let (sender, receiver) = topic.split();
+        self.realm_topics.insert(
+            interface_id,
+            RealmTopic {
+                sender,
+                _receiver: receiver,
+            },
+        );
 
         // Initialize peer tracking for this realm
         self.realm_peers
@@ -342,15 +357,18 @@ impl DiscoveryService {
         interface_id: &InterfaceId,
         msg: &WireMessage,
     ) -> Result<(), DiscoveryError> {
-        let mut topic = self
+        // Clone the sender so no map guard is held across the await;
+        // otherwise concurrent joins on the same shard deadlock.
+        let sender = self
             .realm_topics
-            .get_mut(interface_id)
+            .get(interface_id)
+            .map(|topic| topic.sender.clone())
             .ok_or(DiscoveryError::NotRunning)?;
 
         let framed =
             frame_message(msg).map_err(|e| DiscoveryError::SerializationError(e.to_string()))?;
 
-        topic
+        sender
             .broadcast(framed)
             .await
             .map_err(|e| DiscoveryError::BroadcastError(e.to_string()))?;
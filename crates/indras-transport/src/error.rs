@@ -4,8 +4,11 @@ pub use crate::connection::ConnectionError;
 pub use crate::discovery::DiscoveryError;
 pub use crate::protocol::FramingError;
 
+use indras_core::error_code::{ErrorCode, ErrorInfo};
 use thiserror::Error;
 
+use crate::adapter::AdapterError;
+
 /// Unified transport error type
 #[derive(Debug, Error)]
 pub enum TransportError {
@@ -33,3 +36,103 @@ pub enum TransportError {
     #[error("Protocol error: {0}")]
     Protocol(String),
 }
+
+impl ErrorInfo for TransportError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            TransportError::Connection(e) => e.code(),
+            TransportError::Discovery(e) => e.code(),
+            TransportError::Framing(e) => e.code(),
+            TransportError::Io(_) => ErrorCode::Io,
+            TransportError::StreamClosed => ErrorCode::ConnectionClosed,
+            TransportError::InvalidAddress => ErrorCode::InvalidAddress,
+            TransportError::ConnectionFailed(_) => ErrorCode::ConnectionFailed,
+            TransportError::Protocol(_) => ErrorCode::ProtocolViolation,
+        }
+    }
+
+    fn peer(&self) -> Option<&str> {
+        match self {
+            TransportError::Connection(e) => e.peer(),
+            _ => None,
+        }
+    }
+}
+
+impl ErrorInfo for ConnectionError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            ConnectionError::BindError(_) => ErrorCode::BindFailed,
+            ConnectionError::ConnectError(_)
+            | ConnectionError::IrohError(_)
+            | ConnectionError::NoIncomingConnection => ErrorCode::ConnectionFailed,
+            ConnectionError::ConnectionClosed => ErrorCode::ConnectionClosed,
+            ConnectionError::TooManyConnections { .. } => ErrorCode::TooManyConnections,
+            ConnectionError::Timeout => ErrorCode::Timeout,
+            ConnectionError::AccessDenied(_) => ErrorCode::AccessDenied,
+        }
+    }
+
+    fn peer(&self) -> Option<&str> {
+        match self {
+            ConnectionError::AccessDenied(peer) => Some(peer),
+            _ => None,
+        }
+    }
+}
+
+impl ErrorInfo for DiscoveryError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            DiscoveryError::JoinError(_)
+            | DiscoveryError::BroadcastError(_)
+            | DiscoveryError::GossipError(_) => ErrorCode::DiscoveryFailed,
+            DiscoveryError::SerializationError(_) => ErrorCode::Serialization,
+            DiscoveryError::DeserializationError(_) => ErrorCode::ProtocolViolation,
+            DiscoveryError::NotRunning => ErrorCode::TransportNotRunning,
+        }
+    }
+}
+
+impl ErrorInfo for FramingError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            FramingError::MessageTooLarge { .. } => ErrorCode::MessageTooLarge,
+            FramingError::InsufficientData { .. } | FramingError::Deserialization(_) => {
+                ErrorCode::ProtocolViolation
+            }
+            FramingError::Serialization(_) => ErrorCode::Serialization,
+        }
+    }
+}
+
+impl ErrorInfo for AdapterError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            AdapterError::ConnectionManager(_) => ErrorCode::ConnectionFailed,
+            AdapterError::Discovery(_) | AdapterError::Gossip(_) => ErrorCode::DiscoveryFailed,
+            AdapterError::NotRunning => ErrorCode::TransportNotRunning,
+            AdapterError::Capture(_) => ErrorCode::Internal,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codes() {
+        let err = TransportError::from(ConnectionError::AccessDenied("ab12".to_string()));
+        assert_eq!(err.code(), ErrorCode::AccessDenied);
+        assert!(!err.is_retryable());
+        assert_eq!(err.peer(), Some("ab12"));
+
+        assert!(ConnectionError::Timeout.is_retryable());
+        assert!(AdapterError::NotRunning.is_retryable());
+        assert_eq!(
+            FramingError::MessageTooLarge { size: 10, max: 5 }.code(),
+            ErrorCode::MessageTooLarge
+        );
+    }
+}
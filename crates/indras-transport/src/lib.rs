@@ -11,6 +11,8 @@
 //! - Connection pooling and lifecycle management
 //! - Peer discovery via iroh-gossip
 //! - Wire protocol framing with postcard serialization
+//! - Opt-in sampled packet capture for protocol debugging
+//! - Allow-list access control for closed deployments
 //!
 //! ## Example
 //!
@@ -32,7 +34,9 @@
 //! let conn = manager.connect(peer_addr).await?;
 //! ```
 
+pub mod access;
 pub mod adapter;
+pub mod capture;
 pub mod connection;
 pub mod discovery;
 pub mod error;
@@ -40,7 +44,15 @@ pub mod identity;
 pub mod protocol;
 pub mod relay_client;
 // Re-export main types
+pub use access::{
+    ACCESS_DENIED_CODE, AccessConfig, AccessControl, AccessError, AccessEvent, AccessGuard,
+    AccessMode, Admission, PeerCertificate,
+};
 pub use adapter::{AdapterConfig, AdapterError, IrohNetworkAdapter};
+pub use capture::{
+    CaptureConfig, CaptureDecryptor, CaptureError, CaptureReader, CaptureRecord, Direction,
+    PacketCapture,
+};
 pub use connection::{ConnectionConfig, ConnectionError, ConnectionManager, ConnectionStats};
 pub use discovery::{
     DiscoveryConfig, DiscoveryError, DiscoveryService, DiscoveryStats, PeerEvent, PeerInfo,
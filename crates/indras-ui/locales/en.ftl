@@ -0,0 +1,107 @@
+# Indras Network UI strings — English (reference catalog).
+#
+# Every message here must also exist in the other catalogs; the i18n tests
+# check that the key sets match.
+
+## Shared controls
+
+language-label = Language
+skin-label = Skin
+
+## Chat (indras-ui chat components)
+
+chat-messages-label = Messages
+chat-loading = Loading messages...
+chat-empty = No messages yet. Send the first message!
+chat-load-earlier = Load earlier messages ({ $count })
+chat-load-later = Load newer messages ({ $count })
+chat-typing =
+    { $count ->
+        [one] is typing
+       *[other] are typing
+    }
+chat-jump-to-date = Jump to date
+chat-latest = Latest ↓
+
+chat-placeholder = Type a message...
+chat-reply-placeholder = Reply...
+chat-message-label = Message
+chat-reply-label = Reply
+chat-send = Send
+chat-sending = Sending...
+chat-dismiss-error = Dismiss error
+chat-replying-to = Replying to { $name }
+chat-cancel-reply = Cancel reply
+chat-more-actions = More actions
+chat-action-artifact = 📎 Artifact
+chat-action-document = 📄 Document
+chat-action-proof = ✓ Proof of Service
+chat-insert-emoji = Insert emoji
+chat-emoji = Emoji
+
+chat-deleted = This message was deleted
+chat-deleted-label = Deleted message from { $name }
+chat-edited = edited
+chat-delivery-read = Read
+chat-delivery-sent = Sent
+chat-reply = Reply
+chat-react = React
+chat-edit = Edit
+chat-delete = Delete
+chat-edit-message = Edit message
+chat-save = Save
+chat-cancel = Cancel
+chat-add-reaction = Add reaction
+chat-react-with = React with { $emoji }
+chat-in-reply-to = In reply to { $name }: { $snippet }
+chat-poll-voters =
+    { $count ->
+        [one] { $count } vote
+       *[other] { $count } votes
+    }
+chat-poll-multiple = Multiple choice
+chat-poll-closed = Closed
+chat-show-original = Show original
+chat-show-translation = Show translation
+chat-spam-collapsed = Possible spam
+chat-spam-label = Possible spam from { $name }
+chat-spam-show = Show
+
+## Chat app (indras-chat)
+
+chat-app-loading = Loading...
+chat-anonymous = Anonymous
+chat-click-to-copy = Click to copy
+chat-sidebar-title = Chats
+chat-add-contact = + Add Contact
+chat-no-conversations =
+    No conversations yet.
+    Add a contact to start chatting!
+chat-no-messages = No messages yet
+
+## Workspace settings
+
+settings-appearance = Appearance
+settings-reload-themes = Reload Themes
+settings-reload-themes-hint = Rescan the themes folder in the data directory
+
+## Genesis home realm
+
+genesis-no-quests = No quests yet.
+genesis-no-notes = No notes yet.
+genesis-no-notes-hint = Create your first note to get started.
+genesis-no-artifacts = No artifacts yet.
+genesis-no-artifacts-hint = Share files in a realm to see them here.
+genesis-tokens-title = Tokens of Gratitude
+genesis-no-tokens = No tokens yet.
+genesis-no-tokens-hint = Receive blessings to earn tokens of gratitude.
+genesis-no-connections = No connections yet. Share your invite link to get started.
+genesis-no-events = No events yet.
+
+## Realm viewer
+
+viewer-title = Realm Viewer
+viewer-stat-tick = Tick:
+viewer-stat-events = Events:
+viewer-stat-realms = Realms:
+viewer-stat-members = Members:
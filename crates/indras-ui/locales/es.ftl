@@ -0,0 +1,104 @@
+# Indras Network UI strings — Spanish.
+
+## Shared controls
+
+language-label = Idioma
+skin-label = Apariencia
+
+## Chat (indras-ui chat components)
+
+chat-messages-label = Mensajes
+chat-loading = Cargando mensajes...
+chat-empty = Aún no hay mensajes. ¡Envía el primero!
+chat-load-earlier = Cargar mensajes anteriores ({ $count })
+chat-load-later = Cargar mensajes más recientes ({ $count })
+chat-typing =
+    { $count ->
+        [one] está escribiendo
+       *[other] están escribiendo
+    }
+chat-jump-to-date = Ir a la fecha
+chat-latest = Más recientes ↓
+
+chat-placeholder = Escribe un mensaje...
+chat-reply-placeholder = Responder...
+chat-message-label = Mensaje
+chat-reply-label = Respuesta
+chat-send = Enviar
+chat-sending = Enviando...
+chat-dismiss-error = Descartar error
+chat-replying-to = Respondiendo a { $name }
+chat-cancel-reply = Cancelar respuesta
+chat-more-actions = Más acciones
+chat-action-artifact = 📎 Artefacto
+chat-action-document = 📄 Documento
+chat-action-proof = ✓ Prueba de servicio
+chat-insert-emoji = Insertar emoji
+chat-emoji = Emoji
+
+chat-deleted = Este mensaje fue eliminado
+chat-deleted-label = Mensaje eliminado de { $name }
+chat-edited = editado
+chat-delivery-read = Leído
+chat-delivery-sent = Enviado
+chat-reply = Responder
+chat-react = Reaccionar
+chat-edit = Editar
+chat-delete = Eliminar
+chat-edit-message = Editar mensaje
+chat-save = Guardar
+chat-cancel = Cancelar
+chat-add-reaction = Añadir reacción
+chat-react-with = Reaccionar con { $emoji }
+chat-in-reply-to = En respuesta a { $name }: { $snippet }
+chat-poll-voters =
+    { $count ->
+        [one] { $count } voto
+       *[other] { $count } votos
+    }
+chat-poll-multiple = Opción múltiple
+chat-poll-closed = Cerrada
+chat-show-original = Ver original
+chat-show-translation = Ver traducción
+chat-spam-collapsed = Posible spam
+chat-spam-label = Posible spam de { $name }
+chat-spam-show = Mostrar
+
+## Chat app (indras-chat)
+
+chat-app-loading = Cargando...
+chat-anonymous = Anónimo
+chat-click-to-copy = Haz clic para copiar
+chat-sidebar-title = Chats
+chat-add-contact = + Añadir contacto
+chat-no-conversations =
+    Aún no hay conversaciones.
+    ¡Añade un contacto para empezar a chatear!
+chat-no-messages = Aún no hay mensajes
+
+## Workspace settings
+
+settings-appearance = Apariencia
+settings-reload-themes = Recargar temas
+settings-reload-themes-hint = Volver a buscar temas en la carpeta de datos
+
+## Genesis home realm
+
+genesis-no-quests = Aún no hay misiones.
+genesis-no-notes = Aún no hay notas.
+genesis-no-notes-hint = Crea tu primera nota para empezar.
+genesis-no-artifacts = Aún no hay artefactos.
+genesis-no-artifacts-hint = Comparte archivos en un reino para verlos aquí.
+genesis-tokens-title = Fichas de gratitud
+genesis-no-tokens = Aún no hay fichas.
+genesis-no-tokens-hint = Recibe bendiciones para ganar fichas de gratitud.
+genesis-no-connections = Aún no hay conexiones. Comparte tu enlace de invitación para empezar.
+genesis-no-events = Aún no hay eventos.
+
+## Realm viewer
+
+viewer-title = Visor de reinos
+viewer-stat-tick = Tic:
+viewer-stat-events = Eventos:
+viewer-stat-realms = Reinos:
+viewer-stat-members = Miembros:
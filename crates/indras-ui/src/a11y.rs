@@ -0,0 +1,259 @@
+//! Accessibility helpers: keyboard navigation, focus management, and
+//! screen-reader announcements.
+//!
+//! The shared components use these themselves; apps can adopt them for
+//! their own lists and overlays.
+//!
+//! - [`NavKey`] maps arrow/Home/End keys onto list positions.
+//! - [`focus_element`] moves keyboard focus to an element by id.
+//! - [`FocusTrap`] / [`use_focus_trap`] keep Tab inside an overlay and hand
+//!   focus back to where it was when the overlay closes.
+//! - [`Announcer`] speaks updates through the live region that
+//!   [`ThemedRoot`](crate::ThemedRoot) renders.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use dioxus::prelude::*;
+
+/// Axis along which a list is navigated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    /// Up/Down arrows move between items.
+    Vertical,
+    /// Left/Right arrows move between items.
+    Horizontal,
+}
+
+/// A navigation key pressed in a list.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NavKey {
+    Prev,
+    Next,
+    First,
+    Last,
+}
+
+impl NavKey {
+    /// Interpret a key for a list with the given orientation.
+    pub fn from_key(key: &Key, orientation: Orientation) -> Option<Self> {
+        match (key, orientation) {
+            (Key::ArrowUp, Orientation::Vertical) | (Key::ArrowLeft, Orientation::Horizontal) => {
+                Some(Self::Prev)
+            }
+            (Key::ArrowDown, Orientation::Vertical)
+            | (Key::ArrowRight, Orientation::Horizontal) => Some(Self::Next),
+            (Key::Home, _) => Some(Self::First),
+            (Key::End, _) => Some(Self::Last),
+            _ => None,
+        }
+    }
+
+    /// The position this key moves to in a list of `len` items, wrapping at
+    /// the ends. With nothing focused yet, Next starts at the first item and
+    /// Prev at the last.
+    pub fn apply(self, current: Option<usize>, len: usize) -> Option<usize> {
+        if len == 0 {
+            return None;
+        }
+        let last = len - 1;
+        Some(match (self, current) {
+            (Self::First, _) => 0,
+            (Self::Last, _) => last,
+            (Self::Next, None) => 0,
+            (Self::Prev, None) => last,
+            (Self::Next, Some(i)) if i >= last => 0,
+            (Self::Next, Some(i)) => i + 1,
+            (Self::Prev, Some(0)) => last,
+            (Self::Prev, Some(i)) => (i - 1).min(last),
+        })
+    }
+}
+
+/// Move keyboard focus to the element with this id.
+pub fn focus_element(id: &str) {
+    document::eval(&format!(
+        "document.getElementById({:?})?.focus({{preventScroll:false}})",
+        id
+    ));
+}
+
+/// Elements Tab can reach inside a focus trap.
+const FOCUSABLE: &str = "a[href],button:not([disabled]),input:not([disabled]),\
+textarea:not([disabled]),select:not([disabled]),[tabindex]:not([tabindex=\"-1\"])";
+
+/// Keep Tab focus inside the element with `container_id` while the calling
+/// component is mounted.
+///
+/// On mount the first focusable element (or one marked `autofocus`) is
+/// focused; on unmount focus returns to the element that had it before.
+pub fn use_focus_trap(container_id: String) {
+    let install = container_id.clone();
+    use_effect(move || {
+        document::eval(&format!(
+            r#"(function() {{
+                const id = {id:?};
+                const root = document.getElementById(id);
+                if (!root) return;
+                const traps = window.__indrasFocusTraps = window.__indrasFocusTraps || {{}};
+                if (traps[id]) return;
+                const sel = {sel:?};
+                const items = () => [...root.querySelectorAll(sel)].filter(el => el.offsetParent !== null);
+                const onKey = (e) => {{
+                    if (e.key !== 'Tab') return;
+                    const list = items();
+                    if (!list.length) {{ e.preventDefault(); root.focus(); return; }}
+                    const first = list[0], last = list[list.length - 1];
+                    const active = document.activeElement;
+                    if (!root.contains(active)) {{ e.preventDefault(); first.focus(); }}
+                    else if (e.shiftKey && active === first) {{ e.preventDefault(); last.focus(); }}
+                    else if (!e.shiftKey && active === last) {{ e.preventDefault(); first.focus(); }}
+                }};
+                document.addEventListener('keydown', onKey, true);
+                traps[id] = {{ onKey, previous: document.activeElement }};
+                (root.querySelector('[autofocus]') || items()[0] || root).focus();
+            }})()"#,
+            id = install,
+            sel = FOCUSABLE,
+        ));
+    });
+    use_drop(move || {
+        document::eval(&format!(
+            r#"(function() {{
+                const traps = window.__indrasFocusTraps || {{}};
+                const trap = traps[{id:?}];
+                if (!trap) return;
+                document.removeEventListener('keydown', trap.onKey, true);
+                delete traps[{id:?}];
+                if (trap.previous && trap.previous.focus) trap.previous.focus();
+            }})()"#,
+            id = container_id,
+        ));
+    });
+}
+
+static NEXT_TRAP_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Wrapper that traps focus inside its children while mounted.
+///
+/// Render it only while the overlay is open. Escape calls `on_escape`.
+#[component]
+pub fn FocusTrap(
+    #[props(default)]
+    on_escape: Option<EventHandler<()>>,
+    #[props(default)]
+    class: String,
+    children: Element,
+) -> Element {
+    let id = use_hook(|| format!("focus-trap-{}", NEXT_TRAP_ID.fetch_add(1, Ordering::Relaxed)));
+    use_focus_trap(id.clone());
+
+    rsx! {
+        div {
+            id: "{id}",
+            class: "focus-trap {class}",
+            tabindex: "-1",
+            onkeydown: move |evt: KeyboardEvent| {
+                if evt.key() == Key::Escape
+                    && let Some(handler) = on_escape
+                {
+                    evt.stop_propagation();
+                    handler.call(());
+                }
+            },
+            {children}
+        }
+    }
+}
+
+/// Speaks text to screen readers through the live region in `ThemedRoot`.
+#[derive(Clone, Copy)]
+pub struct Announcer {
+    message: Signal<String>,
+    assertive: Signal<bool>,
+}
+
+impl Announcer {
+    /// Announce when the reader is idle (new messages, status changes).
+    pub fn announce(self, text: impl Into<String>) {
+        self.speak(text.into(), false);
+    }
+
+    /// Announce immediately, interrupting the reader (errors).
+    pub fn alert(self, text: impl Into<String>) {
+        self.speak(text.into(), true);
+    }
+
+    fn speak(mut self, text: String, assertive: bool) {
+        // Clear first so repeating the same text is announced again.
+        self.message.set(String::new());
+        self.assertive.set(assertive);
+        spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            self.message.set(text);
+        });
+    }
+}
+
+/// Provide an [`Announcer`] to the components below this one.
+pub fn use_announcer_provider() -> Announcer {
+    use_context_provider(|| Announcer {
+        message: Signal::new(String::new()),
+        assertive: Signal::new(false),
+    })
+}
+
+/// The nearest [`Announcer`], if an ancestor provides one.
+pub fn use_announcer() -> Option<Announcer> {
+    try_use_context::<Announcer>()
+}
+
+/// Visually hidden live region reading out the nearest [`Announcer`].
+#[component]
+pub fn LiveRegion() -> Element {
+    let Some(announcer) = use_announcer() else {
+        return rsx! {};
+    };
+    let message = announcer.message.read().clone();
+    let assertive = *announcer.assertive.read();
+
+    rsx! {
+        div {
+            class: "sr-only",
+            role: if assertive { "alert" } else { "status" },
+            "aria-live": if assertive { "assertive" } else { "polite" },
+            "aria-atomic": "true",
+            "{message}"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nav_key_wraps() {
+        assert_eq!(NavKey::Next.apply(Some(2), 3), Some(0));
+        assert_eq!(NavKey::Prev.apply(Some(0), 3), Some(2));
+        assert_eq!(NavKey::Next.apply(Some(0), 3), Some(1));
+        assert_eq!(NavKey::Next.apply(None, 3), Some(0));
+        assert_eq!(NavKey::Prev.apply(None, 3), Some(2));
+        assert_eq!(NavKey::Last.apply(Some(0), 3), Some(2));
+        assert_eq!(NavKey::First.apply(None, 0), None);
+    }
+
+    #[test]
+    fn test_nav_key_clamps_stale_index() {
+        // The list shrank since the index was recorded.
+        assert_eq!(NavKey::Prev.apply(Some(10), 3), Some(2));
+        assert_eq!(NavKey::Next.apply(Some(10), 3), Some(0));
+    }
+
+    #[test]
+    fn test_nav_key_orientation() {
+        assert_eq!(NavKey::from_key(&Key::ArrowDown, Orientation::Vertical), Some(NavKey::Next));
+        assert_eq!(NavKey::from_key(&Key::ArrowDown, Orientation::Horizontal), None);
+        assert_eq!(NavKey::from_key(&Key::ArrowLeft, Orientation::Horizontal), Some(NavKey::Prev));
+        assert_eq!(NavKey::from_key(&Key::End, Orientation::Vertical), Some(NavKey::Last));
+    }
+}
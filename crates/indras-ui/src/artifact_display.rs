@@ -272,3 +272,288 @@ fn ArtifactCard(
         }
     }
 }
+
+/// One item in a gallery view.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GalleryItemDisplay {
+    pub artifact: ArtifactDisplayInfo,
+    pub caption: Option<String>,
+}
+
+/// Presentational view model for an ordered gallery.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GalleryDisplayInfo {
+    pub id: String,
+    pub title: String,
+    /// Artifact ID of the cover item, if any.
+    pub cover_id: Option<String>,
+    /// Items in gallery order.
+    pub items: Vec<GalleryItemDisplay>,
+}
+
+impl GalleryDisplayInfo {
+    /// The cover item, if it is still in the gallery.
+    pub fn cover(&self) -> Option<&GalleryItemDisplay> {
+        let cover_id = self.cover_id.as_deref()?;
+        self.items.iter().find(|i| i.artifact.id == cover_id)
+    }
+
+    /// Where to move an item one step back or forward.
+    ///
+    /// Returns the ID of the item it should follow (`None` for the front),
+    /// or `None` overall if the item cannot move that way.
+    pub fn step_target(&self, id: &str, forward: bool) -> Option<Option<String>> {
+        let idx = self.items.iter().position(|i| i.artifact.id == id)?;
+        let after = if forward {
+            self.items.get(idx + 1)?
+        } else {
+            match idx {
+                0 => return None,
+                1 => return Some(None),
+                _ => &self.items[idx - 2],
+            }
+        };
+        Some(Some(after.artifact.id.clone()))
+    }
+}
+
+/// An ordered gallery: cover image, title, and captioned items.
+///
+/// # Props
+/// - `gallery`: The gallery to display
+/// - `on_move`: Reorder callback `(artifact ID, ID to follow or None for the front)`
+/// - `on_set_cover`: Callback when an item is chosen as the cover
+/// - `on_click`: Callback when an item is clicked
+#[component]
+pub fn GalleryView(
+    gallery: GalleryDisplayInfo,
+    #[props(default)]
+    on_move: Option<EventHandler<(String, Option<String>)>>,
+    #[props(default)]
+    on_set_cover: Option<EventHandler<String>>,
+    #[props(default)]
+    on_click: Option<EventHandler<ArtifactDisplayInfo>>,
+) -> Element {
+    let cover = gallery.cover().cloned();
+    let count = gallery.items.len();
+
+    rsx! {
+        div {
+            class: "gallery-view",
+            if let Some(cover) = cover {
+                div {
+                    class: "gallery-cover",
+                    if let Some(ref url) = cover.artifact.data_url {
+                        img {
+                            class: "gallery-cover-img",
+                            src: "{url}",
+                            alt: "{cover.artifact.name}",
+                        }
+                    }
+                }
+            }
+            div {
+                class: "gallery-header",
+                span { class: "gallery-title", "{gallery.title}" }
+                span { class: "gallery-count", "{count} items" }
+            }
+            div {
+                class: "gallery-strip",
+                for item in gallery.items.iter() {
+                    GalleryItemCard {
+                        item: item.clone(),
+                        is_cover: gallery.cover_id.as_deref() == Some(item.artifact.id.as_str()),
+                        back: gallery.step_target(&item.artifact.id, false),
+                        forward: gallery.step_target(&item.artifact.id, true),
+                        on_move: on_move.clone(),
+                        on_set_cover: on_set_cover.clone(),
+                        on_click: on_click.clone(),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A single captioned item in a gallery strip.
+#[component]
+fn GalleryItemCard(
+    item: GalleryItemDisplay,
+    is_cover: bool,
+    back: Option<Option<String>>,
+    forward: Option<Option<String>>,
+    #[props(default)]
+    on_move: Option<EventHandler<(String, Option<String>)>>,
+    #[props(default)]
+    on_set_cover: Option<EventHandler<String>>,
+    #[props(default)]
+    on_click: Option<EventHandler<ArtifactDisplayInfo>>,
+) -> Element {
+    let artifact = item.artifact.clone();
+    let has_image = artifact.has_displayable_image() && artifact.data_url.is_some();
+    let icon = artifact.icon();
+
+    rsx! {
+        div {
+            class: if is_cover { "gallery-item is-cover" } else { "gallery-item" },
+            div {
+                class: "artifact-gallery-thumb",
+                onclick: {
+                    let handler = on_click.clone();
+                    let info = artifact.clone();
+                    move |_| {
+                        if let Some(ref h) = handler {
+                            h.call(info.clone());
+                        }
+                    }
+                },
+                if has_image {
+                    if let Some(ref url) = artifact.data_url {
+                        img {
+                            class: "artifact-gallery-thumb-img",
+                            src: "{url}",
+                            alt: "{artifact.name}",
+                        }
+                    }
+                } else {
+                    span { class: "artifact-gallery-icon", "{icon}" }
+                }
+            }
+            if let Some(ref caption) = item.caption {
+                div { class: "gallery-caption", "{caption}" }
+            }
+
+            if on_move.is_some() || on_set_cover.is_some() {
+                div {
+                    class: "gallery-item-actions",
+                    if let Some(ref handler) = on_move {
+                        button {
+                            class: "artifact-action-btn",
+                            title: "Move earlier",
+                            disabled: back.is_none(),
+                            onclick: {
+                                let handler = handler.clone();
+                                let id = artifact.id.clone();
+                                let target = back.clone();
+                                move |_| {
+                                    if let Some(after) = target.clone() {
+                                        handler.call((id.clone(), after));
+                                    }
+                                }
+                            },
+                            "\u{2190}" // Left arrow
+                        }
+                        button {
+                            class: "artifact-action-btn",
+                            title: "Move later",
+                            disabled: forward.is_none(),
+                            onclick: {
+                                let handler = handler.clone();
+                                let id = artifact.id.clone();
+                                let target = forward.clone();
+                                move |_| {
+                                    if let Some(after) = target.clone() {
+                                        handler.call((id.clone(), after));
+                                    }
+                                }
+                            },
+                            "\u{2192}" // Right arrow
+                        }
+                    }
+                    if let Some(ref handler) = on_set_cover {
+                        if !is_cover && has_image {
+                            button {
+                                class: "artifact-action-btn",
+                                title: "Use as cover",
+                                onclick: {
+                                    let handler = handler.clone();
+                                    let id = artifact.id.clone();
+                                    move |_| handler.call(id.clone())
+                                },
+                                "\u{2605}" // Star
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Compact card for a gallery in a list: cover thumbnail, title, item count.
+#[component]
+pub fn GalleryCoverCard(
+    gallery: GalleryDisplayInfo,
+    #[props(default)]
+    on_click: Option<EventHandler<String>>,
+) -> Element {
+    let cover_url = gallery.cover().and_then(|c| c.artifact.data_url.clone());
+    let count = gallery.items.len();
+    let gallery_id = gallery.id.clone();
+
+    rsx! {
+        div {
+            class: "artifact-gallery-card gallery-cover-card",
+            onclick: move |_| {
+                if let Some(ref h) = on_click {
+                    h.call(gallery_id.clone());
+                }
+            },
+            div {
+                class: "artifact-gallery-thumb",
+                if let Some(url) = cover_url {
+                    img { class: "artifact-gallery-thumb-img", src: "{url}", alt: "{gallery.title}" }
+                } else {
+                    span { class: "artifact-gallery-icon", "\u{1f5bc}\u{fe0f}" }
+                }
+            }
+            div {
+                class: "artifact-gallery-info",
+                div { class: "artifact-gallery-name", "{gallery.title}" }
+                div { class: "artifact-gallery-meta", "{count} items" }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gallery(ids: &[&str]) -> GalleryDisplayInfo {
+        GalleryDisplayInfo {
+            items: ids
+                .iter()
+                .map(|id| GalleryItemDisplay {
+                    artifact: ArtifactDisplayInfo {
+                        id: id.to_string(),
+                        ..Default::default()
+                    },
+                    caption: None,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_step_target_moves_one_place() {
+        let g = gallery(&["a", "b", "c"]);
+        assert_eq!(g.step_target("a", false), None);
+        assert_eq!(g.step_target("b", false), Some(None));
+        assert_eq!(g.step_target("c", false), Some(Some("a".into())));
+        assert_eq!(g.step_target("a", true), Some(Some("b".into())));
+        assert_eq!(g.step_target("c", true), None);
+        assert_eq!(g.step_target("missing", true), None);
+    }
+
+    #[test]
+    fn test_cover_requires_item_in_gallery() {
+        let mut g = gallery(&["a", "b"]);
+        assert!(g.cover().is_none());
+        g.cover_id = Some("b".into());
+        assert_eq!(g.cover().map(|c| c.artifact.id.as_str()), Some("b"));
+        g.cover_id = Some("gone".into());
+        assert!(g.cover().is_none());
+    }
+}
@@ -5,9 +5,12 @@
 
 use dioxus::prelude::*;
 
+use crate::t;
+
 use super::chat_state::{
     ChatMessageView, ChatViewType, DeliveryStatus, ReactionView, ReplyPreview,
 };
+use super::translation::use_translation;
 
 /// Telegram-style message bubble.
 #[component]
@@ -25,8 +28,37 @@ pub fn ChatBubble(
     on_edit_draft_change: EventHandler<String>,
     on_reaction_picker_toggle: EventHandler<String>,
     reaction_picker_open: bool,
+    /// Called with (message id, option index) when a poll option is clicked.
+    #[props(default)]
+    on_vote: Option<EventHandler<(String, usize)>>,
 ) -> Element {
     let is_editing = editing_id.as_deref() == Some(&msg.id);
+
+    // Other members' text messages are shown translated when an ancestor
+    // provides a translation language.
+    let translation = use_translation();
+    let mut translated = use_signal(|| None::<String>);
+    let mut show_original = use_signal(|| false);
+    let translatable = !msg.is_me && matches!(msg.message_type, ChatViewType::Text);
+    use_effect(use_reactive(
+        (&msg.id, &msg.content, &translatable),
+        move |(id, content, translatable)| {
+            translated.set(None);
+            let Some(translation) = translation.clone() else {
+                return;
+            };
+            if !translatable || translation.language.read().is_none() {
+                return;
+            }
+            spawn(async move {
+                translated.set(translation.translate(&id, &content).await);
+            });
+        },
+    ));
+
+    // Probable spam stays collapsed until the reader asks to see it.
+    let mut spam_revealed = use_signal(|| false);
+
     let row_class = if msg.is_me { "chat-bubble-row bubble-right" } else { "chat-bubble-row bubble-left" };
     let bubble_class = if msg.is_me { "chat-bubble chat-bubble-sent" } else { "chat-bubble chat-bubble-received" };
 
@@ -34,7 +66,10 @@ pub fn ChatBubble(
     if matches!(msg.message_type, ChatViewType::System) {
         return rsx! {
             div {
+                id: "msg-{msg.id}",
                 class: "chat-bubble-row bubble-center",
+                role: "article",
+                tabindex: "-1",
                 div {
                     class: "chat-system-msg",
                     "{msg.content}"
@@ -47,10 +82,36 @@ pub fn ChatBubble(
     if matches!(msg.message_type, ChatViewType::Deleted) {
         return rsx! {
             div {
+                id: "msg-{msg.id}",
                 class: "{row_class}",
+                role: "article",
+                tabindex: "-1",
+                "aria-label": t!("chat-deleted-label", name = msg.author_name.clone()),
                 div {
                     class: "{bubble_class} bubble-deleted",
-                    span { class: "bubble-deleted-text", "This message was deleted" }
+                    span { class: "bubble-deleted-text", {t!("chat-deleted")} }
+                }
+            }
+        };
+    }
+
+    // Probable spam, collapsed
+    if msg.probable_spam && !*spam_revealed.read() {
+        return rsx! {
+            div {
+                id: "msg-{msg.id}",
+                class: "{row_class}",
+                role: "article",
+                tabindex: "-1",
+                "aria-label": t!("chat-spam-label", name = msg.author_name.clone()),
+                div {
+                    class: "{bubble_class} bubble-spam",
+                    span { class: "bubble-spam-text", {t!("chat-spam-collapsed")} }
+                    button {
+                        class: "bubble-spam-show",
+                        onclick: move |_| spam_revealed.set(true),
+                        {t!("chat-spam-show")}
+                    }
                 }
             }
         };
@@ -63,16 +124,23 @@ pub fn ChatBubble(
     let msg_id_edit = msg.id.clone();
     let msg_id_save = msg.id.clone();
     let content_for_edit = msg.content.clone();
+    let translated_text = translated.read().clone();
+    let showing_original = *show_original.read();
 
     rsx! {
         div {
             key: "{msg.id}",
+            id: "msg-{msg.id}",
             class: "{row_class}",
+            role: "article",
+            tabindex: "-1",
+            "aria-label": "{msg.author_name}, {msg.timestamp_display}",
 
             // Avatar (left side, received messages, not grouped)
             if !msg.is_me && !is_grouped {
                 div {
                     class: "bubble-avatar {msg.author_color_class}",
+                    "aria-hidden": "true",
                     "{msg.author_letter}"
                 }
             }
@@ -89,26 +157,31 @@ pub fn ChatBubble(
                     class: "bubble-actions",
                     button {
                         class: "bubble-action-btn",
-                        title: "Reply",
+                        title: t!("chat-reply"),
+                        "aria-label": t!("chat-reply"),
                         onclick: move |_| on_reply.call(msg_id_reply.clone()),
                         "\u{21a9}"
                     }
                     button {
                         class: "bubble-action-btn",
-                        title: "React",
+                        title: t!("chat-react"),
+                        "aria-label": t!("chat-react"),
+                        "aria-expanded": if reaction_picker_open { "true" } else { "false" },
                         onclick: move |_| on_reaction_picker_toggle.call(msg_id_react.clone()),
                         "\u{263a}"
                     }
                     if msg.is_me {
                         button {
                             class: "bubble-action-btn",
-                            title: "Edit",
+                            title: t!("chat-edit"),
+                            "aria-label": t!("chat-edit"),
                             onclick: move |_| on_edit_start.call((msg_id_edit.clone(), content_for_edit.clone())),
                             "\u{270e}"
                         }
                         button {
                             class: "bubble-action-btn bubble-action-delete",
-                            title: "Delete",
+                            title: t!("chat-delete"),
+                            "aria-label": t!("chat-delete"),
                             onclick: move |_| on_delete.call(msg_id_delete.clone()),
                             "\u{2717}"
                         }
@@ -128,9 +201,9 @@ pub fn ChatBubble(
                     ReplyPreviewBar {
                         preview: preview.clone(),
                         on_scroll_to: move |id: String| {
-                            // Scroll to the original message
+                            // Scroll to the original message and move focus to it
                             let js = format!(
-                                "document.querySelector('[data-msg-id=\"{}\"]')?.scrollIntoView({{behavior:'smooth',block:'center'}})",
+                                "const el = document.getElementById('msg-{}'); el?.focus({{preventScroll:true}}); el?.scrollIntoView({{behavior:'smooth',block:'center'}})",
                                 id
                             );
                             document::eval(&js);
@@ -144,6 +217,7 @@ pub fn ChatBubble(
                         class: "bubble-edit-form",
                         textarea {
                             class: "bubble-edit-input",
+                            "aria-label": t!("chat-edit-message"),
                             value: "{edit_draft}",
                             rows: "2",
                             oninput: move |evt| on_edit_draft_change.call(evt.value()),
@@ -151,6 +225,8 @@ pub fn ChatBubble(
                                 let save_id = msg_id_save.clone();
                                 let draft = edit_draft.clone();
                                 move |evt: KeyboardEvent| {
+                                    // Keep arrow keys for the text, not list navigation
+                                    evt.stop_propagation();
                                     if evt.key() == Key::Enter && !evt.modifiers().shift() {
                                         evt.prevent_default();
                                         on_edit_save.call((save_id.clone(), draft.clone()));
@@ -169,17 +245,34 @@ pub fn ChatBubble(
                                     let draft = edit_draft.clone();
                                     move |_| on_edit_save.call((save_id.clone(), draft.clone()))
                                 },
-                                "Save"
+                                {t!("chat-save")}
                             }
                             button {
                                 class: "bubble-edit-cancel",
                                 onclick: move |_| on_edit_cancel.call(()),
-                                "Cancel"
+                                {t!("chat-cancel")}
                             }
                         }
                     }
                 } else {
-                    {render_content(&msg)}
+                    {match translated_text.clone() {
+                        Some(text) if !showing_original => {
+                            render_content(&ChatMessageView { content: text, ..msg.clone() }, on_vote)
+                        }
+                        _ => render_content(&msg, on_vote),
+                    }}
+                    if translated_text.is_some() {
+                        button {
+                            class: "bubble-translation-toggle",
+                            "aria-pressed": if showing_original { "true" } else { "false" },
+                            onclick: move |_| show_original.set(!showing_original),
+                            if showing_original {
+                                {t!("chat-show-translation")}
+                            } else {
+                                {t!("chat-show-original")}
+                            }
+                        }
+                    }
                 }
 
                 // Footer: timestamp + edited + delivery status
@@ -187,11 +280,16 @@ pub fn ChatBubble(
                     class: "bubble-footer",
                     span { class: "bubble-time", "{msg.timestamp_display}" }
                     if msg.is_edited {
-                        span { class: "bubble-edited", "edited" }
+                        span { class: "bubble-edited", {t!("chat-edited")} }
                     }
                     if msg.is_me {
                         span {
                             class: "bubble-delivery",
+                            "aria-label": match msg.delivery_status {
+                                DeliveryStatus::Read => t!("chat-delivery-read"),
+                                DeliveryStatus::Sent => t!("chat-delivery-sent"),
+                                DeliveryStatus::None => String::new(),
+                            },
                             {match msg.delivery_status {
                                 DeliveryStatus::Read => "\u{2713}\u{2713}",
                                 DeliveryStatus::Sent => "\u{2713}",
@@ -220,6 +318,16 @@ pub fn ChatBubble(
             if reaction_picker_open {
                 div {
                     class: "bubble-reaction-picker",
+                    role: "group",
+                    "aria-label": t!("chat-add-reaction"),
+                    onkeydown: {
+                        let mid = msg.id.clone();
+                        move |evt: KeyboardEvent| {
+                            if evt.key() == Key::Escape {
+                                on_reaction_picker_toggle.call(mid.clone());
+                            }
+                        }
+                    },
                     {["👍", "❤️", "😂", "😮", "😢", "🙏", "🎉", "🔥"].iter().map(|emoji| {
                         let emoji_str = emoji.to_string();
                         let mid = msg.id.clone();
@@ -227,6 +335,7 @@ pub fn ChatBubble(
                             button {
                                 key: "{emoji}",
                                 class: "reaction-picker-btn",
+                                "aria-label": t!("chat-react-with", emoji = emoji.to_string()),
                                 onclick: move |_| {
                                     on_react.call((mid.clone(), emoji_str.clone()));
                                 },
@@ -241,7 +350,7 @@ pub fn ChatBubble(
 }
 
 /// Render message content based on type.
-fn render_content(msg: &ChatMessageView) -> Element {
+fn render_content(msg: &ChatMessageView, on_vote: Option<EventHandler<(String, usize)>>) -> Element {
     match &msg.message_type {
         ChatViewType::Text => rsx! {
             div { class: "bubble-content", "{msg.content}" }
@@ -294,6 +403,47 @@ fn render_content(msg: &ChatMessageView) -> Element {
         ChatViewType::ArtifactRecalled => rsx! {
             div { class: "bubble-content", "{msg.content}" }
         },
+        ChatViewType::Poll { question, options, multiple, voters, closed } => {
+            let can_vote = !closed && on_vote.is_some();
+            rsx! {
+                div { class: "bubble-content bubble-poll",
+                    div { class: "bubble-poll-question", "\u{1f4ca} {question}" }
+                    for (i, option) in options.iter().enumerate() {
+                        button {
+                            key: "{i}",
+                            class: if option.includes_me { "bubble-poll-option bubble-poll-chosen" } else { "bubble-poll-option" },
+                            disabled: !can_vote,
+                            "aria-pressed": if option.includes_me { "true" } else { "false" },
+                            onclick: {
+                                let msg_id = msg.id.clone();
+                                move |_| {
+                                    if let Some(handler) = on_vote {
+                                        handler.call((msg_id.clone(), i));
+                                    }
+                                }
+                            },
+                            div {
+                                class: "bubble-poll-bar",
+                                style: "width: {option.percent}%",
+                            }
+                            span { class: "bubble-poll-label", "{option.label}" }
+                            span { class: "bubble-poll-count", "{option.votes}" }
+                        }
+                    }
+                    div { class: "bubble-poll-footer",
+                        {t!("chat-poll-voters", count = *voters)}
+                        if *multiple {
+                            " \u{b7} "
+                            {t!("chat-poll-multiple")}
+                        }
+                        if *closed {
+                            " \u{b7} "
+                            {t!("chat-poll-closed")}
+                        }
+                    }
+                }
+            }
+        }
         // System and Deleted handled at the top
         _ => rsx! {
             div { class: "bubble-content", "{msg.content}" }
@@ -311,7 +461,22 @@ fn ReplyPreviewBar(
     rsx! {
         div {
             class: "bubble-reply-preview",
-            onclick: move |_| on_scroll_to.call(original_id.clone()),
+            role: "button",
+            tabindex: "0",
+            "aria-label": t!(
+                "chat-in-reply-to",
+                name = preview.author_name.clone(),
+                snippet = preview.content_snippet.clone(),
+            ),
+            onclick: {
+                let original_id = original_id.clone();
+                move |_| on_scroll_to.call(original_id.clone())
+            },
+            onkeydown: move |evt: KeyboardEvent| {
+                if evt.key() == Key::Enter {
+                    on_scroll_to.call(original_id.clone());
+                }
+            },
             div {
                 class: "bubble-reply-author {preview.author_color_class}",
                 "{preview.author_name}"
@@ -339,11 +504,14 @@ fn ReactionPill(
     let emoji = reaction.emoji.clone();
     let mid = msg_id.clone();
     let tooltip = reaction.author_names.join(", ");
+    let label = format!("{} {}, by {}", reaction.emoji, reaction.count, tooltip);
 
     rsx! {
         button {
             class: "{pill_class}",
             title: "{tooltip}",
+            "aria-label": "{label}",
+            "aria-pressed": if reaction.includes_me { "true" } else { "false" },
             onclick: move |_| on_react.call((mid.clone(), emoji.clone())),
             span { class: "reaction-emoji", "{reaction.emoji}" }
             span { class: "reaction-count", "{reaction.count}" }
@@ -2,6 +2,8 @@
 
 use dioxus::prelude::*;
 
+use crate::t;
+
 use super::chat_state::{ChatStatus, ReplyPreview};
 
 /// Chat input component with textarea, action menu, and send button.
@@ -21,6 +23,8 @@ pub fn ChatInput(
     emoji_picker_open: bool,
     on_emoji_toggle: EventHandler<()>,
     on_emoji_select: EventHandler<String>,
+    #[props(default)]
+    on_attach: Option<EventHandler<()>>,
 ) -> Element {
     let draft_empty = draft.trim().is_empty();
     let is_sending = status == ChatStatus::Sending;
@@ -30,9 +34,11 @@ pub fn ChatInput(
         if let Some(ref err) = error {
             div {
                 class: "chat-error-toast",
+                role: "alert",
                 span { "{err}" }
                 button {
                     class: "chat-error-dismiss",
+                    "aria-label": t!("chat-dismiss-error"),
                     onclick: move |_| {},
                     "\u{2717}"
                 }
@@ -43,7 +49,8 @@ pub fn ChatInput(
         if is_sending {
             div {
                 class: "chat-sending-indicator",
-                "Sending..."
+                role: "status",
+                {t!("chat-sending")}
             }
         }
 
@@ -51,6 +58,7 @@ pub fn ChatInput(
         if let Some(ref reply) = replying_to {
             div {
                 class: "reply-compose-bar",
+                "aria-label": t!("chat-replying-to", name = reply.author_name.clone()),
                 div {
                     class: "reply-compose-preview",
                     div {
@@ -64,6 +72,7 @@ pub fn ChatInput(
                 }
                 button {
                     class: "reply-compose-cancel",
+                    "aria-label": t!("chat-cancel-reply"),
                     onclick: move |_| on_cancel_reply.call(()),
                     "\u{2717}"
                 }
@@ -78,6 +87,9 @@ pub fn ChatInput(
 
                 button {
                     class: "chat-action-btn",
+                    "aria-label": t!("chat-more-actions"),
+                    "aria-haspopup": "menu",
+                    "aria-expanded": if action_menu_open { "true" } else { "false" },
                     onclick: move |_| {
                         on_action_toggle.call(());
                     },
@@ -94,27 +106,39 @@ pub fn ChatInput(
 
                     div {
                         class: "chat-action-menu",
+                        role: "menu",
+                        onkeydown: move |evt: KeyboardEvent| {
+                            if evt.key() == Key::Escape {
+                                on_action_close.call(());
+                            }
+                        },
 
                         button {
                             class: "action-menu-item",
+                            role: "menuitem",
                             onclick: move |_| {
                                 on_action_close.call(());
+                                if let Some(handler) = on_attach {
+                                    handler.call(());
+                                }
                             },
-                            "\u{1f4ce} Artifact"
+                            {t!("chat-action-artifact")}
                         }
                         button {
                             class: "action-menu-item",
+                            role: "menuitem",
                             onclick: move |_| {
                                 on_action_close.call(());
                             },
-                            "\u{1f4c4} Document"
+                            {t!("chat-action-document")}
                         }
                         button {
                             class: "action-menu-item",
+                            role: "menuitem",
                             onclick: move |_| {
                                 on_action_close.call(());
                             },
-                            "\u{2713} Proof of Service"
+                            {t!("chat-action-proof")}
                         }
                     }
                 }
@@ -122,7 +146,8 @@ pub fn ChatInput(
 
             textarea {
                 class: "chat-input chat-textarea",
-                placeholder: if replying_to.is_some() { "Reply..." } else { "Type a message..." },
+                "aria-label": if replying_to.is_some() { t!("chat-reply-label") } else { t!("chat-message-label") },
+                placeholder: if replying_to.is_some() { t!("chat-reply-placeholder") } else { t!("chat-placeholder") },
                 value: "{draft}",
                 rows: "1",
                 oninput: move |evt| {
@@ -153,6 +178,8 @@ pub fn ChatInput(
             // Emoji picker button
             button {
                 class: if emoji_picker_open { "chat-emoji-btn chat-emoji-btn-active" } else { "chat-emoji-btn" },
+                "aria-label": t!("chat-insert-emoji"),
+                "aria-expanded": if emoji_picker_open { "true" } else { "false" },
                 onclick: move |_| on_emoji_toggle.call(()),
                 "\u{263a}"
             }
@@ -161,6 +188,13 @@ pub fn ChatInput(
             if emoji_picker_open {
                 div {
                     class: "emoji-picker",
+                    role: "group",
+                    "aria-label": t!("chat-emoji"),
+                    onkeydown: move |evt: KeyboardEvent| {
+                        if evt.key() == Key::Escape {
+                            on_emoji_toggle.call(());
+                        }
+                    },
                     {["👍", "❤️", "😂", "😮", "😢", "🙏", "🎉", "🔥"].iter().map(|emoji| {
                         let e = emoji.to_string();
                         rsx! {
@@ -194,7 +228,7 @@ pub fn ChatInput(
                         }
                     }
                 },
-                "Send"
+                {t!("chat-send")}
             }
         }
     }
@@ -260,6 +260,30 @@ pub fn ChatMessageItem(
             }
         }
 
+        ChatViewType::Poll { question, voters, closed, .. } => {
+            rsx! {
+                div {
+                    key: "{msg.id}",
+                    class: "chat-message poll-message",
+
+                    div {
+                        class: "chat-message-row",
+                        span { class: "chat-tick", "{msg.timestamp_display}" }
+                        span {
+                            class: "chat-sender {color_class}",
+                            "{msg.author_name}"
+                        }
+                        span { class: "chat-icon", "\u{1f4ca}" }
+                        span { class: "chat-content", "{question}" }
+                        span { class: "gallery-count", "({voters} votes)" }
+                        if *closed {
+                            span { class: "chat-edited-indicator", "(closed)" }
+                        }
+                    }
+                }
+            }
+        }
+
         ChatViewType::Deleted => {
             rsx! {
                 div {
@@ -2,10 +2,17 @@
 
 use dioxus::prelude::*;
 
+use crate::t;
+use crate::a11y::{focus_element, use_announcer, NavKey, Orientation};
+
 use super::chat_bubble::ChatBubble;
 use super::chat_state::{ChatMessageView, ChatStatus, TypingPeerView};
 
 /// Message list component with auto-scroll, bubble layout, and typing indicator.
+///
+/// The list is a single tab stop; Up/Down/Home/End move focus between
+/// messages. New messages from others are read out through the nearest
+/// [`Announcer`](crate::a11y::Announcer).
 #[component]
 pub fn ChatMessageList(
     messages: Vec<ChatMessageView>,
@@ -23,7 +30,54 @@ pub fn ChatMessageList(
     typing_peers: Vec<TypingPeerView>,
     reaction_picker_msg_id: Option<String>,
     on_reaction_picker_toggle: EventHandler<String>,
+    /// Number of older messages not rendered.
+    #[props(default)]
+    earlier_count: usize,
+    #[props(default)]
+    on_load_earlier: Option<EventHandler<()>>,
+    /// Number of newer messages not rendered.
+    #[props(default)]
+    later_count: usize,
+    #[props(default)]
+    on_load_later: Option<EventHandler<()>>,
+    /// Called with (message id, option index) when a poll option is clicked.
+    #[props(default)]
+    on_vote: Option<EventHandler<(String, usize)>>,
 ) -> Element {
+    let mut focused = use_signal(|| None::<usize>);
+
+    // Announce messages from others as they arrive at the bottom of the list.
+    let announcer = use_announcer();
+    let mut newest_seen = use_signal(|| None::<String>);
+    let newest = messages
+        .last()
+        .filter(|_| later_count == 0)
+        .map(|m| (m.id.clone(), m.is_me, m.author_name.clone(), m.content.clone()));
+    use_effect(use_reactive((&newest,), move |(newest,)| {
+        let Some((id, is_me, author, content)) = newest else {
+            return;
+        };
+        let previous = newest_seen.peek().clone();
+        newest_seen.set(Some(id));
+        // The first message seen is history, not an arrival.
+        if previous.is_some() && !is_me && let Some(announcer) = announcer {
+            announcer.announce(format!("{author}: {content}"));
+        }
+    }));
+
+    let message_ids: Vec<String> = messages.iter().map(|m| m.id.clone()).collect();
+    let on_keydown = move |evt: KeyboardEvent| {
+        let Some(nav) = NavKey::from_key(&evt.key(), Orientation::Vertical) else {
+            return;
+        };
+        evt.prevent_default();
+        let current = *focused.peek();
+        if let Some(i) = nav.apply(current, message_ids.len()) {
+            focused.set(Some(i));
+            focus_element(&format!("msg-{}", message_ids[i]));
+        }
+    };
+
     // Auto-scroll effect
     use_effect(move || {
         if should_scroll_bottom {
@@ -38,18 +92,35 @@ pub fn ChatMessageList(
     rsx! {
         div {
             class: "chat-messages",
+            role: "log",
+            "aria-label": t!("chat-messages-label"),
+            "aria-busy": if status == ChatStatus::Loading { "true" } else { "false" },
+            tabindex: "0",
+            onkeydown: on_keydown,
 
             if status == ChatStatus::Loading {
                 div {
                     class: "chat-loading",
-                    "Loading messages..."
+                    {t!("chat-loading")}
+                }
+            }
+
+            if earlier_count > 0 {
+                button {
+                    class: "chat-load-earlier",
+                    onclick: move |_| {
+                        if let Some(handler) = on_load_earlier {
+                            handler.call(());
+                        }
+                    },
+                    {t!("chat-load-earlier", count = earlier_count)}
                 }
             }
 
             if messages.is_empty() && status != ChatStatus::Loading {
                 div {
                     class: "panel-empty",
-                    "No messages yet. Send the first message!"
+                    {t!("chat-empty")}
                 }
             }
 
@@ -81,15 +152,29 @@ pub fn ChatMessageList(
                             on_edit_draft_change: on_edit_draft_change,
                             on_reaction_picker_toggle: on_reaction_picker_toggle,
                             reaction_picker_open: picker_open,
+                            on_vote,
                         }
                     }
                 }
             }
 
+            if later_count > 0 {
+                button {
+                    class: "chat-load-earlier",
+                    onclick: move |_| {
+                        if let Some(handler) = on_load_later {
+                            handler.call(());
+                        }
+                    },
+                    {t!("chat-load-later", count = later_count)}
+                }
+            }
+
             // Typing indicator
             if !typing_peers.is_empty() {
                 div {
                     class: "typing-indicator",
+                    role: "status",
                     for peer in typing_peers.iter() {
                         span {
                             class: "typing-name {peer.color_class}",
@@ -97,7 +182,7 @@ pub fn ChatMessageList(
                         }
                     }
                     span { class: "typing-text",
-                        if typing_peers.len() == 1 { " is typing" } else { " are typing" }
+                        {format!(" {}", t!("chat-typing", count = typing_peers.len()))}
                     }
                     span { class: "typing-dots", "..." }
                 }
@@ -3,6 +3,8 @@
 //! Provides UI-layer types for rendering chat messages, plus conversion
 //! from the backend `EditableChatMessage` to the view-layer `ChatMessageView`.
 
+use std::collections::BTreeSet;
+
 use indras_network::chat_message::{EditableChatMessage, EditableMessageType, RealmChatDocument};
 use crate::identity::{member_name, member_color_class};
 
@@ -41,6 +43,8 @@ pub struct ChatMessageView {
     pub reactions: Vec<ReactionView>,
     /// Delivery status (for sent messages).
     pub delivery_status: DeliveryStatus,
+    /// Whether the message is demoted as probable spam.
+    pub probable_spam: bool,
 }
 
 /// Message type for view-layer rendering.
@@ -72,9 +76,31 @@ pub enum ChatViewType {
         artifact_type: String,
         description: String,
     },
+    /// Poll with its current results.
+    Poll {
+        question: String,
+        options: Vec<PollOptionView>,
+        multiple: bool,
+        /// Number of members who voted.
+        voters: usize,
+        closed: bool,
+    },
     Deleted,
 }
 
+/// View model for one poll option.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PollOptionView {
+    /// The option text.
+    pub label: String,
+    /// Number of votes for it.
+    pub votes: usize,
+    /// Share of voters who chose it, in percent.
+    pub percent: u32,
+    /// Whether the current user chose it.
+    pub includes_me: bool,
+}
+
 /// Preview of a replied-to message.
 #[derive(Debug, Clone, PartialEq)]
 pub struct ReplyPreview {
@@ -241,12 +267,11 @@ pub fn convert_editable_to_view(
                     description: description.clone(),
                 }
             }
+            EditableMessageType::Poll { multiple, .. } => poll_view(msg, my_id, *multiple),
         }
     };
 
-    let timestamp_display = chrono::DateTime::from_timestamp_millis(msg.created_at as i64)
-        .map(|dt| dt.format("%H:%M").to_string())
-        .unwrap_or_default();
+    let timestamp_display = crate::i18n::format_time(msg.created_at);
 
     let author_letter = member_name(&msg.author);
     let author_color_class = member_color_class(&msg.author).to_string();
@@ -303,5 +328,77 @@ pub fn convert_editable_to_view(
         reply_preview,
         reactions,
         delivery_status,
+        probable_spam: false,
+    }
+}
+
+/// Build the view of a poll message from its current tally.
+fn poll_view(msg: &EditableChatMessage, my_id: &str, multiple: bool) -> ChatViewType {
+    let now = chrono::Utc::now().timestamp_millis().max(0) as u64;
+    let Some(tally) = msg.poll_tally(now) else {
+        return ChatViewType::Text;
+    };
+    let options = tally
+        .options
+        .into_iter()
+        .map(|option| PollOptionView {
+            percent: (option.votes * 100).checked_div(tally.voters).unwrap_or(0) as u32,
+            includes_me: option.voters.iter().any(|v| v == my_id),
+            label: option.option,
+            votes: option.votes,
+        })
+        .collect();
+    ChatViewType::Poll {
+        question: tally.question,
+        options,
+        multiple,
+        voters: tally.voters,
+        closed: tally.closed,
+    }
+}
+
+/// The choices to submit when the current user clicks a poll option.
+///
+/// On a single-choice poll clicking an option selects it, or withdraws
+/// the vote if it was already selected. On a multiple-choice poll it
+/// toggles the option.
+pub fn poll_choices_after_click(
+    msg: &EditableChatMessage,
+    my_id: &str,
+    option: usize,
+) -> BTreeSet<usize> {
+    let mut choices = msg
+        .votes
+        .get(my_id)
+        .map(|ballot| ballot.choices.clone())
+        .unwrap_or_default();
+    let multiple = matches!(
+        msg.message_type,
+        EditableMessageType::Poll { multiple: true, .. }
+    );
+    if !choices.remove(&option) {
+        if !multiple {
+            choices.clear();
+        }
+        choices.insert(option);
+    }
+    choices
+}
+
+/// Convert a message of a multi-member realm to a `ChatMessageView`.
+///
+/// Messages sent through `Realm::chat_send` carry the author's display
+/// name in `author` and their member id in `author_id`, so the display
+/// name is shown as-is and ownership is decided by `author_id`.
+pub fn convert_realm_message(
+    msg: &EditableChatMessage,
+    my_id: &str,
+    doc: &RealmChatDocument,
+) -> ChatMessageView {
+    let mut view = convert_editable_to_view(msg, my_id, &msg.author, Some(doc), None);
+    if msg.author_id.as_deref() == Some(my_id) && !view.is_me {
+        view.is_me = true;
+        view.author_name = "You".to_string();
     }
+    view
 }
@@ -1,14 +1,24 @@
 //! Chat components for Indras Network applications.
 //!
 //! Provides a reusable chat panel with Telegram-style bubble layout,
-//! reply threading, emoji reactions, typing indicators, and read receipts.
+//! reply threading, emoji reactions, polls, typing indicators, read receipts,
+//! optional translation into the reader's language, and spam demotion.
+//! [`ChatPanel`] is bound to a DM peer; [`RealmChatPanel`] to any realm.
 
 pub mod chat_state;
 pub mod chat_panel;
+pub mod realm_chat_panel;
 pub mod chat_messages;
 pub mod chat_message;
 pub mod chat_bubble;
 pub mod chat_input;
+pub mod virtual_list;
+pub mod translation;
+pub mod spam;
 
 pub use chat_panel::ChatPanel;
-pub use chat_state::{ChatMessageView, ChatState, ChatStatus, ChatViewType, ReplyPreview, ReactionView, DeliveryStatus, TypingPeerView, convert_editable_to_view};
+pub use realm_chat_panel::{RealmChatPanel, RealmRef};
+pub use virtual_list::{ChatWindow, VirtualMessageList, WINDOW_SIZE};
+pub use translation::{use_translation, use_translation_provider, TranslationContext};
+pub use spam::{use_spam_filter, use_spam_filter_provider, SpamFilterContext};
+pub use chat_state::{ChatMessageView, ChatState, ChatStatus, ChatViewType, PollOptionView, ReplyPreview, ReactionView, DeliveryStatus, TypingPeerView, convert_editable_to_view, convert_realm_message, poll_choices_after_click};
@@ -0,0 +1,360 @@
+//! Chat panel bound to an arbitrary realm.
+//!
+//! Unlike [`ChatPanel`](super::ChatPanel), which resolves a DM realm from a
+//! peer id, this panel is handed a [`Realm`] and talks to it only through
+//! the realm's public chat API (`chat_doc`, `chat_send`, `chat_reply`,
+//! `chat_react`, `chat_edit`, `chat_delete`, `chat_send_image`,
+//! `chat_vote`, `share_artifact`). Messages are rendered through a
+//! [`VirtualMessageList`], so long histories stay cheap to diff.
+
+use std::sync::Arc;
+
+use dioxus::prelude::*;
+use futures::StreamExt;
+use indras_network::{Realm, RealmChatDocument};
+use tracing::debug;
+
+use super::chat_input::ChatInput;
+use super::chat_state::{
+    convert_realm_message, poll_choices_after_click, ChatState, ChatStatus, ReplyPreview,
+};
+use super::virtual_list::VirtualMessageList;
+use crate::file_utils::image_mime_type;
+
+/// A [`Realm`] usable as a component prop (compared by realm id).
+#[derive(Clone)]
+pub struct RealmRef(pub Realm);
+
+impl PartialEq for RealmRef {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.id() == other.0.id()
+    }
+}
+
+/// Embeddable chat panel for any realm.
+///
+/// `author` is the display name attached to sent messages.
+#[component]
+pub fn RealmChatPanel(
+    realm: RealmRef,
+    author: String,
+    #[props(default)]
+    title: Option<String>,
+) -> Element {
+    let realm = realm.0;
+    let mut chat = use_signal(ChatState::default);
+    let mut doc_snapshot = use_signal(|| Arc::new(RealmChatDocument::new()));
+    let my_id: String = realm.member_id().iter().map(|b| format!("{:02x}", b)).collect();
+
+    // Initial load + stream listener
+    use_effect({
+        let realm = realm.clone();
+        move || {
+            let realm = realm.clone();
+            spawn(async move {
+                let doc = match realm.chat_doc().await {
+                    Ok(d) => d,
+                    Err(e) => {
+                        debug!(error = %e, "RealmChatPanel: failed to get chat document");
+                        chat.write().status = ChatStatus::Idle;
+                        return;
+                    }
+                };
+
+                doc_snapshot.set(Arc::new(doc.read().await.clone()));
+                {
+                    let mut s = chat.write();
+                    s.should_scroll_bottom = true;
+                    s.status = ChatStatus::Idle;
+                }
+
+                let mut changes = doc.changes();
+                while let Some(change) = changes.next().await {
+                    doc_snapshot.set(Arc::new(change.new_state));
+                    chat.write().should_scroll_bottom = true;
+                }
+            });
+        }
+    });
+
+    // Error auto-dismiss after 5 seconds
+    let has_error = chat.read().error.is_some();
+    use_effect(move || {
+        if has_error {
+            spawn(async move {
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                chat.write().error = None;
+            });
+        }
+    });
+
+    // Read state for rendering
+    let total = doc_snapshot.read().visible_count();
+    let s = chat.read();
+    let draft = s.draft.clone();
+    let status = s.status.clone();
+    let error = s.error.clone();
+    let action_menu_open = s.action_menu_open;
+    let editing_id = s.editing_id.clone();
+    let edit_draft = s.edit_draft.clone();
+    let should_scroll = s.should_scroll_bottom;
+    let replying_to = s.replying_to.clone();
+    let typing_peers = s.typing_peers.clone();
+    let emoji_picker_open = s.emoji_picker_open;
+    let reaction_picker_msg_id = s.reaction_picker_msg_id.clone();
+    drop(s);
+
+    let header_title = title.unwrap_or_else(|| realm.name().unwrap_or("Chat").to_string());
+
+    // Event handlers
+    let on_send = {
+        let realm = realm.clone();
+        let author = author.clone();
+        move |text: String| {
+            let realm = realm.clone();
+            let author = author.clone();
+            spawn(async move {
+                chat.write().status = ChatStatus::Sending;
+                chat.write().draft.clear();
+                let result = realm.chat_send(&author, text.clone()).await;
+                let mut s = chat.write();
+                s.status = ChatStatus::Idle;
+                if let Err(e) = result {
+                    s.draft = text;
+                    s.error = Some(e.to_string());
+                }
+            });
+        }
+    };
+
+    let on_send_reply = {
+        let realm = realm.clone();
+        let author = author.clone();
+        move |(text, reply_to_id): (String, String)| {
+            let realm = realm.clone();
+            let author = author.clone();
+            spawn(async move {
+                chat.write().status = ChatStatus::Sending;
+                chat.write().draft.clear();
+                let result = realm.chat_reply(&author, &reply_to_id, text.clone()).await;
+                let mut s = chat.write();
+                s.status = ChatStatus::Idle;
+                match result {
+                    Ok(_) => s.replying_to = None,
+                    Err(e) => {
+                        s.draft = text;
+                        s.error = Some(e.to_string());
+                    }
+                }
+            });
+        }
+    };
+
+    let on_attach = {
+        let realm = realm.clone();
+        let author = author.clone();
+        move |_: ()| {
+            let realm = realm.clone();
+            let author = author.clone();
+            spawn(async move {
+                let Some(file) = rfd::AsyncFileDialog::new().pick_file().await else {
+                    return;
+                };
+                chat.write().status = ChatStatus::Sending;
+                let path = file.path().to_path_buf();
+                let name = file.file_name();
+                let result = match image_mime_type(&path) {
+                    Some(mime) => {
+                        let data = file.read().await;
+                        realm.chat_send_image(&author, data, mime, Some(name)).await.map(|_| ())
+                    }
+                    None => match realm.share_artifact(&path).await {
+                        Ok(_) => realm
+                            .chat_send(&author, format!("\u{1f4ce} {}", name))
+                            .await
+                            .map(|_| ()),
+                        Err(e) => Err(e),
+                    },
+                };
+                let mut s = chat.write();
+                s.status = ChatStatus::Idle;
+                if let Err(e) = result {
+                    s.error = Some(e.to_string());
+                }
+            });
+        }
+    };
+
+    let on_edit_start = move |(id, content): (String, String)| {
+        let mut s = chat.write();
+        s.editing_id = Some(id);
+        s.edit_draft = content;
+    };
+
+    let on_edit_save = {
+        let realm = realm.clone();
+        move |(msg_id, new_content): (String, String)| {
+            let realm = realm.clone();
+            spawn(async move {
+                match realm.chat_edit(&msg_id, new_content).await {
+                    Ok(true) => {
+                        let mut s = chat.write();
+                        s.editing_id = None;
+                        s.edit_draft.clear();
+                    }
+                    Ok(false) => chat.write().error = Some("Edit failed".into()),
+                    Err(e) => chat.write().error = Some(e.to_string()),
+                }
+            });
+        }
+    };
+
+    let on_edit_cancel = move |_: ()| {
+        let mut s = chat.write();
+        s.editing_id = None;
+        s.edit_draft.clear();
+    };
+
+    let on_delete = {
+        let realm = realm.clone();
+        move |msg_id: String| {
+            let realm = realm.clone();
+            spawn(async move {
+                if let Err(e) = realm.chat_delete(&msg_id).await {
+                    chat.write().error = Some(e.to_string());
+                }
+            });
+        }
+    };
+
+    let on_react = {
+        let realm = realm.clone();
+        let my_id = my_id.clone();
+        move |(msg_id, emoji): (String, String)| {
+            let realm = realm.clone();
+            let my_id = my_id.clone();
+            spawn(async move {
+                let already_reacted = doc_snapshot
+                    .read()
+                    .get_message(&msg_id)
+                    .and_then(|m| m.reactions.get(&emoji))
+                    .is_some_and(|authors| authors.contains(&my_id));
+                let result = if already_reacted {
+                    realm.chat_unreact(&my_id, &msg_id, &emoji).await
+                } else {
+                    realm.chat_react(&my_id, &msg_id, &emoji).await
+                };
+                let mut s = chat.write();
+                s.reaction_picker_msg_id = None;
+                if let Err(e) = result {
+                    s.error = Some(e.to_string());
+                }
+            });
+        }
+    };
+
+    let on_vote = {
+        let realm = realm.clone();
+        let my_id = my_id.clone();
+        move |(msg_id, option): (String, usize)| {
+            let Some(choices) = doc_snapshot
+                .read()
+                .get_message(&msg_id)
+                .map(|m| poll_choices_after_click(m, &my_id, option))
+            else {
+                return;
+            };
+            let realm = realm.clone();
+            spawn(async move {
+                match realm.chat_vote(&msg_id, choices).await {
+                    Ok(true) => {}
+                    Ok(false) => chat.write().error = Some("Poll is closed".into()),
+                    Err(e) => chat.write().error = Some(e.to_string()),
+                }
+            });
+        }
+    };
+
+    let on_reply = {
+        let my_id = my_id.clone();
+        move |msg_id: String| {
+            let doc = doc_snapshot.read().clone();
+            if let Some(msg) = doc.get_message(&msg_id) {
+                let msg = convert_realm_message(msg, &my_id, &doc);
+                let preview = ReplyPreview {
+                    original_id: msg.id.clone(),
+                    author_name: msg.author_name.clone(),
+                    author_color_class: msg.author_color_class.clone(),
+                    content_snippet: match msg.content.char_indices().nth(97) {
+                        Some((end, _)) => format!("{}...", &msg.content[..end]),
+                        None => msg.content.clone(),
+                    },
+                };
+                chat.write().replying_to = Some(preview);
+            }
+        }
+    };
+
+    rsx! {
+        div {
+            class: "chat-panel-header",
+            h2 { class: "panel-title", "{header_title}" }
+            span { class: "panel-count", "{total}" }
+        }
+
+        VirtualMessageList {
+            doc: doc_snapshot,
+            my_id,
+            status: status.clone(),
+            on_reply,
+            on_react,
+            on_edit_start,
+            on_edit_save,
+            on_edit_cancel,
+            on_delete,
+            editing_id,
+            edit_draft,
+            on_edit_draft_change: move |text: String| chat.write().edit_draft = text,
+            should_scroll_bottom: should_scroll,
+            typing_peers,
+            reaction_picker_msg_id,
+            on_reaction_picker_toggle: move |msg_id: String| {
+                let mut s = chat.write();
+                if s.reaction_picker_msg_id.as_deref() == Some(&msg_id) {
+                    s.reaction_picker_msg_id = None;
+                } else {
+                    s.reaction_picker_msg_id = Some(msg_id);
+                }
+            },
+            on_vote,
+        }
+
+        ChatInput {
+            draft,
+            status,
+            error,
+            on_send,
+            on_draft_change: move |text: String| chat.write().draft = text,
+            action_menu_open,
+            on_action_toggle: move |_: ()| {
+                let mut s = chat.write();
+                s.action_menu_open = !s.action_menu_open;
+            },
+            on_action_close: move |_: ()| chat.write().action_menu_open = false,
+            replying_to,
+            on_send_reply,
+            on_cancel_reply: move |_: ()| chat.write().replying_to = None,
+            emoji_picker_open,
+            on_emoji_toggle: move |_: ()| {
+                let mut s = chat.write();
+                s.emoji_picker_open = !s.emoji_picker_open;
+            },
+            on_emoji_select: move |emoji: String| {
+                let mut s = chat.write();
+                s.draft.push_str(&emoji);
+                s.emoji_picker_open = false;
+            },
+            on_attach,
+        }
+    }
+}
@@ -0,0 +1,35 @@
+//! Demoting probable spam in chat lists.
+//!
+//! An app calls [`use_spam_filter_provider`] above its chat panels to have
+//! other members' messages scored by its network's spam detector (see
+//! `IndrasNetwork::spam`). Messages flagged as probable spam are collapsed
+//! behind a "show" button rather than hidden. Without a provider, every
+//! message is shown as usual.
+
+use std::sync::Arc;
+
+use dioxus::prelude::*;
+use indras_network::{EditableChatMessage, IndrasNetwork};
+
+/// Spam scoring shared with the chat components below a provider.
+#[derive(Clone)]
+pub struct SpamFilterContext {
+    network: Arc<IndrasNetwork>,
+}
+
+impl SpamFilterContext {
+    /// Whether a message should be demoted as probable spam.
+    pub fn is_probable_spam(&self, msg: &EditableChatMessage) -> bool {
+        self.network.spam().score(msg).probable
+    }
+}
+
+/// Provide a [`SpamFilterContext`] to the components below this one.
+pub fn use_spam_filter_provider(network: Arc<IndrasNetwork>) -> SpamFilterContext {
+    use_context_provider(|| SpamFilterContext { network })
+}
+
+/// The nearest [`SpamFilterContext`], if an ancestor provides one.
+pub fn use_spam_filter() -> Option<SpamFilterContext> {
+    try_use_context::<SpamFilterContext>()
+}
@@ -0,0 +1,58 @@
+//! Showing chat messages in the reader's language.
+//!
+//! An app that registered a translator on its network (see
+//! `IndrasNetwork::translations`) calls [`use_translation_provider`] above
+//! its chat panels. Chat bubbles below it then translate other members'
+//! text messages into the chosen language and offer a toggle back to the
+//! original. Without a provider, or with no language chosen, messages are
+//! shown as written.
+
+use std::sync::Arc;
+
+use dioxus::prelude::*;
+use indras_network::IndrasNetwork;
+use tracing::debug;
+
+/// Translation settings shared with the chat components below a provider.
+#[derive(Clone)]
+pub struct TranslationContext {
+    network: Arc<IndrasNetwork>,
+    /// Language to translate into (a BCP 47 tag such as `"es"`), or `None`
+    /// to show originals.
+    pub language: Signal<Option<String>>,
+}
+
+impl TranslationContext {
+    /// A message's text in the chosen language.
+    ///
+    /// Returns `None` when no language is chosen or translation failed.
+    pub async fn translate(&self, message_id: &str, text: &str) -> Option<String> {
+        let language = self.language.peek().clone()?;
+        if let Some(cached) = self.network.cached_translation(message_id, text, &language) {
+            return Some(cached);
+        }
+        match self.network.translate_message(message_id, text, &language).await {
+            Ok(translated) => Some(translated),
+            Err(e) => {
+                debug!(error = %e, message_id, "translation failed");
+                None
+            }
+        }
+    }
+}
+
+/// Provide a [`TranslationContext`] to the components below this one.
+pub fn use_translation_provider(
+    network: Arc<IndrasNetwork>,
+    language: Option<String>,
+) -> TranslationContext {
+    use_context_provider(|| TranslationContext {
+        network,
+        language: Signal::new(language),
+    })
+}
+
+/// The nearest [`TranslationContext`], if an ancestor provides one.
+pub fn use_translation() -> Option<TranslationContext> {
+    try_use_context::<TranslationContext>()
+}
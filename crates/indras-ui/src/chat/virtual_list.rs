@@ -0,0 +1,206 @@
+//! Windowed message list for long chat histories.
+//!
+//! Renders at most [`WINDOW_SIZE`] messages at a time, paged out of the
+//! chat document with its cursor API (`page_before`, `page_after`,
+//! `page_around`). Paging moves the window by half its size, so the message
+//! the reader was looking at stays rendered and is scrolled back into place.
+//! While the window is at the newest message it follows new arrivals.
+
+use std::sync::Arc;
+
+use chrono::{Local, NaiveDate, TimeZone};
+use dioxus::prelude::*;
+use indras_network::{ChatCursor, ChatPage, RealmChatDocument};
+
+use crate::t;
+
+use super::chat_messages::ChatMessageList;
+use super::chat_state::{convert_realm_message, ChatStatus, TypingPeerView};
+use super::spam::use_spam_filter;
+
+/// Most messages rendered at once.
+pub const WINDOW_SIZE: usize = 100;
+
+/// Which part of the history the list shows.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ChatWindow {
+    /// The newest messages, following new arrivals.
+    Latest,
+    /// Messages ending just before a cursor.
+    Before(ChatCursor),
+    /// Messages starting just after a cursor.
+    After(ChatCursor),
+    /// Messages centred on a time (milliseconds since epoch).
+    Around(u64),
+}
+
+impl ChatWindow {
+    /// The page this window selects from a document.
+    pub fn page<'a>(&self, doc: &'a RealmChatDocument) -> ChatPage<'a> {
+        match self {
+            Self::Latest => doc.page_before(None, WINDOW_SIZE),
+            Self::Before(cursor) => doc.page_before(Some(cursor), WINDOW_SIZE),
+            Self::After(cursor) => doc.page_after(cursor, WINDOW_SIZE),
+            Self::Around(millis) => doc.page_around(*millis, WINDOW_SIZE),
+        }
+    }
+}
+
+/// Start of a `YYYY-MM-DD` day in local time, as milliseconds since epoch.
+fn local_day_start_millis(date: &str) -> Option<u64> {
+    let midnight = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?.and_hms_opt(0, 0, 0)?;
+    let millis = Local.from_local_datetime(&midnight).earliest()?.timestamp_millis();
+    u64::try_from(millis).ok()
+}
+
+/// Windowed message list with paging, scroll anchoring, and jump-to-date.
+///
+/// Takes the same bubble callbacks as [`ChatMessageList`], plus the chat
+/// document snapshot to page through.
+#[component]
+pub fn VirtualMessageList(
+    doc: Signal<Arc<RealmChatDocument>>,
+    my_id: String,
+    status: ChatStatus,
+    on_reply: EventHandler<String>,
+    on_react: EventHandler<(String, String)>,
+    on_edit_start: EventHandler<(String, String)>,
+    on_edit_save: EventHandler<(String, String)>,
+    on_edit_cancel: EventHandler<()>,
+    on_delete: EventHandler<String>,
+    editing_id: Option<String>,
+    edit_draft: String,
+    on_edit_draft_change: EventHandler<String>,
+    should_scroll_bottom: bool,
+    typing_peers: Vec<TypingPeerView>,
+    reaction_picker_msg_id: Option<String>,
+    on_reaction_picker_toggle: EventHandler<String>,
+    /// Called with (message id, option index) when a poll option is clicked.
+    #[props(default)]
+    on_vote: Option<EventHandler<(String, usize)>>,
+) -> Element {
+    let mut window = use_signal(|| ChatWindow::Latest);
+    // Message to scroll into view after the window moves, with its
+    // `scrollIntoView` block alignment.
+    let mut anchor = use_signal(|| None::<(String, &'static str)>);
+    let spam = use_spam_filter();
+
+    use_effect(move || {
+        let Some((id, block)) = anchor.read().clone() else {
+            return;
+        };
+        spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(30)).await;
+            let js = format!(
+                "document.getElementById({:?})?.scrollIntoView({{block:'{}'}})",
+                format!("msg-{id}"),
+                block
+            );
+            document::eval(&js);
+            anchor.set(None);
+        });
+    });
+
+    let snapshot = doc.read().clone();
+    let current = window.read().clone();
+    let page = current.page(&snapshot);
+    let following = current == ChatWindow::Latest;
+    let messages: Vec<_> = page
+        .messages
+        .iter()
+        .map(|m| {
+            let mut view = convert_realm_message(m, &my_id, &snapshot);
+            view.probable_spam =
+                !view.is_me && spam.as_ref().is_some_and(|s| s.is_probable_spam(m));
+            view
+        })
+        .collect();
+    let earlier = page.earlier;
+    let later = page.later;
+    let middle = page.messages.get(page.messages.len() / 2).map(|m| ChatCursor::of(m));
+    let first_id = page.messages.first().map(|m| m.id.clone());
+    let last_id = page.messages.last().map(|m| m.id.clone());
+    drop(page);
+
+    let on_load_earlier = {
+        let middle = middle.clone();
+        move |_: ()| {
+            if let Some(cursor) = middle.clone() {
+                window.set(ChatWindow::Before(cursor));
+                anchor.set(first_id.clone().map(|id| (id, "start")));
+            }
+        }
+    };
+
+    let on_load_later = move |_: ()| {
+        let Some(cursor) = middle.clone() else {
+            return;
+        };
+        // The next page reaches the newest message: follow again.
+        if later <= WINDOW_SIZE / 2 {
+            window.set(ChatWindow::Latest);
+        } else {
+            window.set(ChatWindow::After(cursor));
+        }
+        anchor.set(last_id.clone().map(|id| (id, "end")));
+    };
+
+    let on_jump_to_date = move |evt: FormEvent| {
+        let Some(millis) = local_day_start_millis(&evt.value()) else {
+            return;
+        };
+        let target = doc.read().page_around(millis, 1).first_cursor();
+        window.set(ChatWindow::Around(millis));
+        anchor.set(target.map(|c| (c.id, "center")));
+    };
+
+    let on_jump_to_latest = move |_| {
+        let newest = doc.read().page_before(None, 1).last_cursor();
+        window.set(ChatWindow::Latest);
+        anchor.set(newest.map(|c| (c.id, "end")));
+    };
+
+    rsx! {
+        div {
+            class: "chat-history-bar",
+            input {
+                class: "chat-history-date",
+                r#type: "date",
+                title: t!("chat-jump-to-date"),
+                "aria-label": t!("chat-jump-to-date"),
+                onchange: on_jump_to_date,
+            }
+            if !following {
+                button {
+                    class: "chat-history-latest",
+                    onclick: on_jump_to_latest,
+                    {t!("chat-latest")}
+                }
+            }
+        }
+
+        ChatMessageList {
+            messages,
+            status,
+            on_reply,
+            on_react,
+            on_edit_start,
+            on_edit_save,
+            on_edit_cancel,
+            on_delete,
+            editing_id,
+            edit_draft,
+            on_edit_draft_change,
+            should_scroll_bottom: should_scroll_bottom && following,
+            typing_peers,
+            reaction_picker_msg_id,
+            on_reaction_picker_toggle,
+            earlier_count: earlier,
+            on_load_earlier,
+            later_count: later,
+            on_load_later,
+            on_vote,
+        }
+    }
+}
+
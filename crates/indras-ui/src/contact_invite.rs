@@ -5,10 +5,13 @@
 
 use dioxus::prelude::*;
 
+use crate::a11y::FocusTrap;
+
 /// Overlay for sharing and accepting contact invite links.
 ///
 /// Follows the same overlay pattern as `MarkdownPreviewOverlay`:
 /// backdrop click closes, stop propagation on dialog, close button.
+/// Focus is trapped in the dialog while open and Escape closes it.
 #[component]
 pub fn ContactInviteOverlay(
     mut is_open: Signal<bool>,
@@ -62,88 +65,92 @@ pub fn ContactInviteOverlay(
             class: "contact-invite-overlay",
             onclick: move |_| is_open.set(false),
 
-            div {
-                class: "contact-invite-dialog",
-                role: "dialog",
-                "aria-modal": "true",
-                onclick: move |e| e.stop_propagation(),
-
-                // Header
-                div {
-                    class: "contact-invite-header",
-                    h2 { "Connections" }
-                    button {
-                        class: "contact-invite-close",
-                        "aria-label": "Close",
-                        onclick: move |_| is_open.set(false),
-                        "\u{00d7}"
-                    }
-                }
-
-                // Content
+            FocusTrap {
+                on_escape: move |_| is_open.set(false),
                 div {
-                    class: "contact-invite-content",
+                    class: "contact-invite-dialog",
+                    role: "dialog",
+                    "aria-modal": "true",
+                    "aria-labelledby": "contact-invite-title",
+                    onclick: move |e| e.stop_propagation(),
 
-                    // Share section
-                    section {
-                        class: "contact-invite-share",
-                        h3 { "Share Your Link" }
-                        p {
-                            class: "contact-invite-identity",
-                            span { class: "contact-invite-name", "{name}" }
-                            " "
-                            span { class: "contact-invite-mid", "{mid}" }
-                        }
-                        div {
-                            class: "contact-invite-uri",
-                            "{uri}"
-                        }
+                    // Header
+                    div {
+                        class: "contact-invite-header",
+                        h2 { id: "contact-invite-title", "Connections" }
                         button {
-                            class: "contact-invite-copy-btn",
-                            onclick: move |_| on_copy.call(()),
-                            if copied { "Copied!" } else { "Copy Link" }
+                            class: "contact-invite-close",
+                            "aria-label": "Close",
+                            onclick: move |_| is_open.set(false),
+                            "\u{00d7}"
                         }
                     }
 
-                    // Connect section
-                    section {
-                        class: "contact-invite-connect",
-                        h3 { "Connect with Someone" }
-                        input {
-                            class: "contact-invite-input",
-                            r#type: "text",
-                            placeholder: "Paste an indra1... identity code",
-                            "aria-label": "Paste invite link",
-                            value: "{input_val}",
-                            oninput: move |evt| {
-                                let val = evt.value();
-                                connect_input.set(val.clone());
-                                on_parse_input.call(val);
-                            },
-                        }
+                    // Content
+                    div {
+                        class: "contact-invite-content",
 
-                        if let Some(ref inviter) = parsed_name {
+                        // Share section
+                        section {
+                            class: "contact-invite-share",
+                            h3 { "Share Your Link" }
+                            p {
+                                class: "contact-invite-identity",
+                                span { class: "contact-invite-name", "{name}" }
+                                " "
+                                span { class: "contact-invite-mid", "{mid}" }
+                            }
                             div {
-                                class: "contact-invite-preview",
-                                "Invite from: {inviter}"
+                                class: "contact-invite-uri",
+                                "{uri}"
+                            }
+                            button {
+                                class: "contact-invite-copy-btn",
+                                onclick: move |_| on_copy.call(()),
+                                if copied { "Copied!" } else { "Copy Link" }
                             }
                         }
 
-                        if let (Some(cls), Some(txt)) = (status_class, &status_text) {
-                            div {
-                                class: "{cls}",
-                                role: "alert",
-                                "{txt}"
+                        // Connect section
+                        section {
+                            class: "contact-invite-connect",
+                            h3 { "Connect with Someone" }
+                            input {
+                                class: "contact-invite-input",
+                                r#type: "text",
+                                placeholder: "Paste an indra1... identity code",
+                                "aria-label": "Paste invite link",
+                                value: "{input_val}",
+                                oninput: move |evt| {
+                                    let val = evt.value();
+                                    connect_input.set(val.clone());
+                                    on_parse_input.call(val);
+                                },
                             }
-                        }
 
-                        button {
-                            class: "contact-invite-connect-btn",
-                            disabled: input_val.trim().is_empty(),
-                            onclick: move |_| {
-                                on_connect.call(connect_input());
-                            },
-                            "Connect"
+                            if let Some(ref inviter) = parsed_name {
+                                div {
+                                    class: "contact-invite-preview",
+                                    "Invite from: {inviter}"
+                                }
+                            }
+
+                            if let (Some(cls), Some(txt)) = (status_class, &status_text) {
+                                div {
+                                    class: "{cls}",
+                                    role: "alert",
+                                    "{txt}"
+                                }
+                            }
+
+                            button {
+                                class: "contact-invite-connect-btn",
+                                disabled: input_val.trim().is_empty(),
+                                onclick: move |_| {
+                                    on_connect.call(connect_input());
+                                },
+                                "Connect"
+                            }
                         }
                     }
                 }
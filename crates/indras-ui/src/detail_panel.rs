@@ -1,6 +1,7 @@
 //! Detail panel with tabbed views for Properties, Audience, and Heat.
 
 use dioxus::prelude::*;
+use crate::a11y::{focus_element, NavKey, Orientation};
 use crate::heat_display::HeatBar;
 
 /// Property row data for the Properties tab.
@@ -53,6 +54,9 @@ pub struct TrailEvent {
 }
 
 /// Detail panel with 3 tabs: Properties, Audience, Heat.
+///
+/// The tabs follow the ARIA tabs pattern: Left/Right/Home/End switch tabs
+/// and Escape closes the panel.
 #[component]
 pub fn DetailPanel(
     active_tab: usize,
@@ -96,12 +100,20 @@ pub fn DetailPanel(
     rsx! {
         div {
             class: "detail-panel",
+            role: "complementary",
+            "aria-label": "Artifact properties",
+            onkeydown: move |evt: KeyboardEvent| {
+                if evt.key() == Key::Escape {
+                    on_close.call(());
+                }
+            },
 
             div {
                 class: "detail-header",
                 span { class: "detail-title", "Artifact Properties" }
                 button {
                     class: "detail-close",
+                    "aria-label": "Close properties",
                     onclick: move |_| on_close.call(()),
                     "\u{2715}"
                 }
@@ -109,12 +121,29 @@ pub fn DetailPanel(
 
             div {
                 class: "detail-tabs",
+                role: "tablist",
+                "aria-label": "Property views",
+                onkeydown: move |evt: KeyboardEvent| {
+                    if let Some(nav) = NavKey::from_key(&evt.key(), Orientation::Horizontal)
+                        && let Some(i) = nav.apply(Some(active_tab), tab_names.len())
+                    {
+                        evt.prevent_default();
+                        on_tab_change.call(i);
+                        focus_element(&format!("detail-tab-{i}"));
+                    }
+                },
                 for (i, tab_name) in tab_names.iter().enumerate() {
                     {
-                        let active_class = if i == active_tab { " active" } else { "" };
+                        let selected = i == active_tab;
+                        let active_class = if selected { " active" } else { "" };
                         rsx! {
                             button {
+                                id: "detail-tab-{i}",
                                 class: "detail-tab{active_class}",
+                                role: "tab",
+                                tabindex: if selected { "0" } else { "-1" },
+                                "aria-selected": if selected { "true" } else { "false" },
+                                "aria-controls": "detail-tabpanel",
                                 onclick: move |_| on_tab_change.call(i),
                                 "{tab_name}"
                             }
@@ -127,6 +156,9 @@ pub fn DetailPanel(
             if active_tab == 0 {
                 div {
                     class: "detail-tab-content active",
+                    id: "detail-tabpanel",
+                    role: "tabpanel",
+                    "aria-labelledby": "detail-tab-{active_tab}",
                     // Identity section
                     div {
                         class: "detail-section",
@@ -201,6 +233,9 @@ pub fn DetailPanel(
             if active_tab == 1 {
                 div {
                     class: "detail-tab-content active",
+                    id: "detail-tabpanel",
+                    role: "tabpanel",
+                    "aria-labelledby": "detail-tab-{active_tab}",
                     // Search bar
                     div {
                         class: "audience-search",
@@ -276,6 +311,7 @@ pub fn DetailPanel(
                                             button {
                                                 class: "audience-remove-btn",
                                                 title: "Remove from audience",
+                                                "aria-label": "Remove {member.name} from audience",
                                                 onclick: move |_| {
                                                     if let Some(ref h) = handler {
                                                         h.call(member_id.clone());
@@ -329,6 +365,9 @@ pub fn DetailPanel(
                     rsx! {
                         div {
                             class: "detail-tab-content active",
+                            id: "detail-tabpanel",
+                            role: "tabpanel",
+                            "aria-labelledby": "detail-tab-{active_tab}",
                             div {
                                 class: "detail-section",
                                 div { class: "detail-section-title", "Per-Peer Heat" }
@@ -12,19 +12,24 @@ pub fn load_image_as_data_url(path: &str) -> Option<String> {
 
     let data = std::fs::read(&full_path).ok()?;
 
-    let mime = match full_path.extension().and_then(|e| e.to_str()) {
-        Some("png") => "image/png",
-        Some("jpg") | Some("jpeg") => "image/jpeg",
-        Some("gif") => "image/gif",
-        Some("webp") => "image/webp",
-        Some("svg") => "image/svg+xml",
-        _ => "application/octet-stream",
-    };
+    let mime = image_mime_type(&full_path).unwrap_or("application/octet-stream");
 
     let encoded = STANDARD.encode(&data);
     Some(format!("data:{};base64,{}", mime, encoded))
 }
 
+/// MIME type of an image file, judged by its extension.
+pub fn image_mime_type(path: &std::path::Path) -> Option<&'static str> {
+    match path.extension().and_then(|e| e.to_str())?.to_ascii_lowercase().as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        "svg" => Some("image/svg+xml"),
+        _ => None,
+    }
+}
+
 /// Load a text file's full content from an asset path.
 pub fn load_text_file_content(path: &str) -> Option<String> {
     let full_path = if path.starts_with('/') {
@@ -0,0 +1,369 @@
+//! Localization for Indras Network apps.
+//!
+//! UI strings live in Fluent catalogs (`locales/<code>.ftl`), one per
+//! supported [`Locale`], compiled into the crate. The active locale is the
+//! [`CURRENT_LOCALE`] signal: it starts from the OS locale and can be
+//! switched at runtime, and every component that looked up a string through
+//! [`t!`](crate::t) re-renders in the new language.
+//!
+//! Dates and numbers go through the `format_*` helpers so they follow the
+//! same locale.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use chrono::{DateTime, Local, Utc};
+use dioxus::prelude::*;
+use fluent_bundle::{FluentBundle, FluentResource};
+use unic_langid::LanguageIdentifier;
+
+pub use fluent_bundle::{FluentArgs, FluentValue};
+
+/// Supported UI languages.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Locale {
+    #[default]
+    English,
+    Spanish,
+}
+
+impl Locale {
+    /// BCP 47 language code.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::Spanish => "es",
+        }
+    }
+
+    /// Name of the language in that language.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Locale::English => "English",
+            Locale::Spanish => "Español",
+        }
+    }
+
+    /// Returns all supported locales.
+    pub fn all() -> &'static [Locale] {
+        &[Locale::English, Locale::Spanish]
+    }
+
+    /// Match a language tag (`es`, `es-MX`, `es_ES.UTF-8`) to a supported
+    /// locale by its language subtag.
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let language = tag
+            .split(['-', '_', '.', '@'])
+            .next()?
+            .to_ascii_lowercase();
+        Self::all().iter().copied().find(|l| l.code() == language)
+    }
+
+    fn catalog(&self) -> &'static str {
+        match self {
+            Locale::English => include_str!("../locales/en.ftl"),
+            Locale::Spanish => include_str!("../locales/es.ftl"),
+        }
+    }
+
+    fn chrono_locale(&self) -> chrono::Locale {
+        match self {
+            Locale::English => chrono::Locale::en_US,
+            Locale::Spanish => chrono::Locale::es_ES,
+        }
+    }
+
+    /// Look up a message, falling back to English and then to the key.
+    pub fn translate(&self, key: &str, args: Option<&FluentArgs>) -> String {
+        with_bundle(*self, |bundle| format_message(bundle, key, args))
+            .or_else(|| with_bundle(Locale::English, |bundle| format_message(bundle, key, args)))
+            .unwrap_or_else(|| key.to_string())
+    }
+
+    /// Time of day, e.g. `14:05`.
+    pub fn format_time(&self, millis: u64) -> String {
+        local_time(millis)
+            .map(|dt| dt.format_localized("%H:%M", self.chrono_locale()).to_string())
+            .unwrap_or_default()
+    }
+
+    /// Calendar date, e.g. `Mar 1, 2024` / `1 mar 2024`.
+    pub fn format_date(&self, millis: u64) -> String {
+        let pattern = match self {
+            Locale::English => "%b %-d, %Y",
+            Locale::Spanish => "%-d %b %Y",
+        };
+        local_time(millis)
+            .map(|dt| dt.format_localized(pattern, self.chrono_locale()).to_string())
+            .unwrap_or_default()
+    }
+
+    /// Date and time of day.
+    pub fn format_datetime(&self, millis: u64) -> String {
+        format!("{} {}", self.format_date(millis), self.format_time(millis))
+    }
+
+    /// Time of day for today's timestamps, otherwise the date.
+    pub fn format_timestamp(&self, millis: u64) -> String {
+        let today = Local::now().date_naive();
+        match local_time(millis) {
+            Some(dt) if dt.date_naive() == today => self.format_time(millis),
+            Some(_) => self.format_date(millis),
+            None => String::new(),
+        }
+    }
+
+    /// Integer with locale digit grouping, e.g. `12,345` / `12.345`.
+    pub fn format_number(&self, n: i64) -> String {
+        let digits = n.unsigned_abs().to_string();
+        let mut grouped = String::with_capacity(digits.len() + digits.len() / 3 + 1);
+        if n < 0 {
+            grouped.push('-');
+        }
+        for (i, c) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                grouped.push(self.group_separator());
+            }
+            grouped.push(c);
+        }
+        grouped
+    }
+
+    /// Decimal with locale separators and a fixed number of places.
+    pub fn format_decimal(&self, value: f64, places: usize) -> String {
+        let fixed = format!("{:.*}", places, value.abs());
+        let (whole, fraction) = fixed.split_once('.').unwrap_or((&fixed, ""));
+        let whole: i64 = whole.parse().unwrap_or(0);
+        let sign = if value < 0.0 && fixed.chars().any(|c| c.is_ascii_digit() && c != '0') {
+            "-"
+        } else {
+            ""
+        };
+        let mut out = format!("{sign}{}", self.format_number(whole));
+        if !fraction.is_empty() {
+            out.push(self.decimal_separator());
+            out.push_str(fraction);
+        }
+        out
+    }
+
+    fn group_separator(&self) -> char {
+        match self {
+            Locale::English => ',',
+            Locale::Spanish => '.',
+        }
+    }
+
+    fn decimal_separator(&self) -> char {
+        match self {
+            Locale::English => '.',
+            Locale::Spanish => ',',
+        }
+    }
+}
+
+fn local_time(millis: u64) -> Option<DateTime<Local>> {
+    DateTime::<Utc>::from_timestamp_millis(i64::try_from(millis).ok()?)
+        .map(|dt| dt.with_timezone(&Local))
+}
+
+thread_local! {
+    /// Parsed catalogs, built on first use per thread.
+    static BUNDLES: RefCell<HashMap<Locale, FluentBundle<FluentResource>>> =
+        RefCell::new(HashMap::new());
+}
+
+fn with_bundle<R>(
+    locale: Locale,
+    f: impl FnOnce(&FluentBundle<FluentResource>) -> Option<R>,
+) -> Option<R> {
+    BUNDLES.with(|bundles| {
+        let mut bundles = bundles.borrow_mut();
+        let bundle = bundles.entry(locale).or_insert_with(|| build_bundle(locale));
+        f(bundle)
+    })
+}
+
+fn build_bundle(locale: Locale) -> FluentBundle<FluentResource> {
+    let langid: LanguageIdentifier = locale.code().parse().unwrap_or_default();
+    let mut bundle = FluentBundle::new(vec![langid]);
+    // Bidi isolation marks would show up in plain-text contexts like titles.
+    bundle.set_use_isolating(false);
+    let resource = match FluentResource::try_new(locale.catalog().to_string()) {
+        Ok(resource) => resource,
+        Err((resource, errors)) => {
+            tracing::warn!(locale = locale.code(), ?errors, "Catalog has syntax errors");
+            resource
+        }
+    };
+    if let Err(errors) = bundle.add_resource(resource) {
+        tracing::warn!(locale = locale.code(), ?errors, "Catalog has duplicate messages");
+    }
+    bundle
+}
+
+fn format_message(
+    bundle: &FluentBundle<FluentResource>,
+    key: &str,
+    args: Option<&FluentArgs>,
+) -> Option<String> {
+    let pattern = bundle.get_message(key)?.value()?;
+    let mut errors = Vec::new();
+    let text = bundle.format_pattern(pattern, args, &mut errors);
+    if !errors.is_empty() {
+        tracing::debug!(key, ?errors, "Message formatted with errors");
+    }
+    Some(text.into_owned())
+}
+
+/// Locale from the OS environment (`LC_ALL`, `LC_MESSAGES`, `LANG`), or
+/// English if none is supported.
+pub fn detect_locale() -> Locale {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| Locale::from_tag(&value))
+        .unwrap_or_default()
+}
+
+/// Global signal for the current UI language.
+pub static CURRENT_LOCALE: GlobalSignal<Locale> = Signal::global(detect_locale);
+
+fn current_locale() -> Locale {
+    *CURRENT_LOCALE.read()
+}
+
+/// Look up a message in the current locale.
+pub fn tr(key: &str) -> String {
+    current_locale().translate(key, None)
+}
+
+/// Look up a message with arguments in the current locale.
+pub fn tr_args(key: &str, args: &FluentArgs) -> String {
+    current_locale().translate(key, Some(args))
+}
+
+/// Look up a UI string in the current locale.
+///
+/// ```ignore
+/// t!("chat-send")
+/// t!("chat-load-earlier", count = earlier_count)
+/// ```
+#[macro_export]
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::tr($key)
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = $crate::i18n::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $crate::i18n::tr_args($key, &args)
+    }};
+}
+
+/// [`Locale::format_time`] in the current locale.
+pub fn format_time(millis: u64) -> String {
+    current_locale().format_time(millis)
+}
+
+/// [`Locale::format_date`] in the current locale.
+pub fn format_date(millis: u64) -> String {
+    current_locale().format_date(millis)
+}
+
+/// [`Locale::format_datetime`] in the current locale.
+pub fn format_datetime(millis: u64) -> String {
+    current_locale().format_datetime(millis)
+}
+
+/// [`Locale::format_timestamp`] in the current locale.
+pub fn format_timestamp(millis: u64) -> String {
+    current_locale().format_timestamp(millis)
+}
+
+/// [`Locale::format_number`] in the current locale.
+pub fn format_number(n: i64) -> String {
+    current_locale().format_number(n)
+}
+
+/// Language picker bound to [`CURRENT_LOCALE`].
+#[component]
+pub fn LocaleSwitcher() -> Element {
+    let current = *CURRENT_LOCALE.read();
+    let label = tr("language-label");
+
+    rsx! {
+        div { class: "locale-switcher",
+            select {
+                "aria-label": "{label}",
+                value: "{current.code()}",
+                onchange: move |evt| {
+                    if let Some(locale) = Locale::from_tag(&evt.value()) {
+                        *CURRENT_LOCALE.write() = locale;
+                    }
+                },
+                for l in Locale::all() {
+                    option {
+                        value: "{l.code()}",
+                        selected: *l == current,
+                        "{l.display_name()}"
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_tag() {
+        assert_eq!(Locale::from_tag("es_ES.UTF-8"), Some(Locale::Spanish));
+        assert_eq!(Locale::from_tag("en-GB"), Some(Locale::English));
+        assert_eq!(Locale::from_tag("ES"), Some(Locale::Spanish));
+        assert_eq!(Locale::from_tag("fr_FR"), None);
+        assert_eq!(Locale::from_tag("C"), None);
+    }
+
+    #[test]
+    fn test_translate_with_fallback() {
+        assert_eq!(Locale::English.translate("chat-send", None), "Send");
+        assert_eq!(Locale::Spanish.translate("chat-send", None), "Enviar");
+        assert_eq!(Locale::Spanish.translate("no-such-key", None), "no-such-key");
+
+        let mut args = FluentArgs::new();
+        args.set("count", 3);
+        assert_eq!(
+            Locale::English.translate("chat-load-earlier", Some(&args)),
+            "Load earlier messages (3)"
+        );
+    }
+
+    #[test]
+    fn test_catalogs_define_the_same_keys() {
+        let keys = |locale: Locale| -> Vec<String> {
+            let mut keys: Vec<String> = locale
+                .catalog()
+                .lines()
+                .filter(|l| l.chars().next().is_some_and(|c| c.is_ascii_lowercase()))
+                .filter_map(|l| l.split_once(" =").map(|(k, _)| k.to_string()))
+                .collect();
+            keys.sort();
+            keys
+        };
+        assert_eq!(keys(Locale::English), keys(Locale::Spanish));
+    }
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(Locale::English.format_number(1234567), "1,234,567");
+        assert_eq!(Locale::Spanish.format_number(-1234), "-1.234");
+        assert_eq!(Locale::English.format_number(999), "999");
+        assert_eq!(Locale::English.format_decimal(1234.5, 2), "1,234.50");
+        assert_eq!(Locale::Spanish.format_decimal(-1234.56, 1), "-1.234,6");
+        assert_eq!(Locale::English.format_decimal(-0.001, 2), "0.00");
+    }
+}
@@ -3,7 +3,10 @@
 //! Provides themes, markdown rendering, file utilities, identity display,
 //! and preview overlay components shared between genesis and realm-viewer.
 
+pub mod a11y;
 pub mod theme;
+pub mod i18n;
+pub mod user_theme;
 pub mod markdown;
 pub mod file_utils;
 pub mod identity;
@@ -16,22 +19,42 @@ pub mod peer_strip;
 pub mod heat_display;
 pub mod navigation_sidebar;
 pub mod slash_menu;
+pub mod vault_sidebar;
 pub mod detail_panel;
 
+pub use a11y::{
+    focus_element, use_announcer, use_announcer_provider, use_focus_trap, Announcer, FocusTrap,
+    LiveRegion, NavKey, Orientation,
+};
+pub use i18n::{
+    detect_locale, format_date, format_datetime, format_number, format_time, format_timestamp,
+    tr, tr_args, Locale, LocaleSwitcher, CURRENT_LOCALE,
+};
 pub use theme::{Skin, ThemedRoot, SkinSwitcher, CURRENT_SKIN};
+pub use user_theme::{
+    install_user_themes, load_user_themes, select_user_theme, themes_dir, ThemeColors,
+    ThemeError, ThemeFonts, ThemeManifest, ThemeSpacing, UserTheme, USER_THEMES,
+};
 pub use markdown::{render_markdown_to_html, is_markdown_file};
-pub use file_utils::{load_image_as_data_url, load_text_file_content};
+pub use file_utils::{image_mime_type, load_image_as_data_url, load_text_file_content};
 pub use identity::{member_name, reset_member_names, short_id, format_duration_millis, member_color_class, member_color_var};
 pub use preview::{PreviewFile, PreviewViewMode, PreviewContext, MarkdownPreviewOverlay};
 pub use contact_invite::ContactInviteOverlay;
-pub use artifact_display::{ArtifactDisplayInfo, ArtifactDisplayStatus, ArtifactGallery};
+pub use artifact_display::{
+    ArtifactDisplayInfo, ArtifactDisplayStatus, ArtifactGallery, GalleryCoverCard,
+    GalleryDisplayInfo, GalleryItemDisplay, GalleryView,
+};
 pub use identity_row::IdentityRow;
 pub use peer_strip::{PeerStrip, PeerDisplayInfo};
 pub use heat_display::{HeatDot, HeatBar, heat_level};
 pub use navigation_sidebar::{NavigationSidebar, NavDestination, CreateAction, RecentItem};
 pub use slash_menu::{SlashMenu, SlashAction};
+pub use vault_sidebar::{VaultSidebar, TreeNode};
 pub use detail_panel::{DetailPanel, PropertyRow, AudienceMember, HeatEntry, TrailEvent, ReferenceItem, SyncEntry};
-pub use chat::ChatPanel;
+pub use chat::{
+    use_spam_filter, use_spam_filter_provider, use_translation, use_translation_provider,
+    ChatPanel, RealmChatPanel, RealmRef, SpamFilterContext, TranslationContext,
+};
 
 /// Shared CSS containing design tokens, theme definitions, and base styles.
 pub const SHARED_CSS: &str = include_str!("../assets/shared.css");
@@ -2,6 +2,7 @@
 
 use dioxus::prelude::*;
 
+use crate::a11y::FocusTrap;
 use crate::markdown::{is_markdown_file, render_markdown_to_html};
 
 /// File being previewed in overlay.
@@ -34,6 +35,8 @@ pub struct PreviewContext {
 }
 
 /// File preview overlay -- handles markdown (rendered/raw) and images.
+///
+/// Focus is trapped in the dialog while open and Escape closes it.
 #[component]
 pub fn MarkdownPreviewOverlay(
     is_open: Signal<bool>,
@@ -61,51 +64,58 @@ pub fn MarkdownPreviewOverlay(
         div {
             class: "markdown-preview-overlay",
             onclick: move |_| is_open.set(false),
-            div {
-                class: "markdown-preview-dialog",
-                onclick: move |e| e.stop_propagation(),
+            FocusTrap {
+                on_escape: move |_| is_open.set(false),
+                div {
+                    class: "markdown-preview-dialog",
+                    role: "dialog",
+                    "aria-modal": "true",
+                    "aria-label": "{file_data.name}",
+                    onclick: move |e| e.stop_propagation(),
 
-                // Header
-                div { class: "markdown-preview-header",
-                    span { class: "markdown-preview-filename", "{file_data.name}" }
-                    div { class: "markdown-preview-controls",
-                        if is_md {
+                    // Header
+                    div { class: "markdown-preview-header",
+                        span { class: "markdown-preview-filename", "{file_data.name}" }
+                        div { class: "markdown-preview-controls",
+                            if is_md {
+                                button {
+                                    class: "markdown-preview-toggle",
+                                    onclick: move |_| {
+                                        view_mode.set(if mode == PreviewViewMode::Rendered {
+                                            PreviewViewMode::Raw
+                                        } else {
+                                            PreviewViewMode::Rendered
+                                        });
+                                    },
+                                    if mode == PreviewViewMode::Rendered { "View Raw" } else { "View Rendered" }
+                                }
+                            }
                             button {
-                                class: "markdown-preview-toggle",
-                                onclick: move |_| {
-                                    view_mode.set(if mode == PreviewViewMode::Rendered {
-                                        PreviewViewMode::Raw
-                                    } else {
-                                        PreviewViewMode::Rendered
-                                    });
-                                },
-                                if mode == PreviewViewMode::Rendered { "View Raw" } else { "View Rendered" }
+                                class: "markdown-preview-close",
+                                "aria-label": "Close preview",
+                                onclick: move |_| is_open.set(false),
+                                "\u{00d7}"
                             }
                         }
-                        button {
-                            class: "markdown-preview-close",
-                            onclick: move |_| is_open.set(false),
-                            "\u{00d7}"
-                        }
                     }
-                }
 
-                // Content
-                div { class: "markdown-preview-content",
-                    if is_image {
-                        if let Some(ref url) = file_data.data_url {
-                            div { class: "image-preview",
-                                img {
-                                    class: "image-preview-img",
-                                    src: "{url}",
-                                    alt: "{file_data.name}",
+                    // Content
+                    div { class: "markdown-preview-content",
+                        if is_image {
+                            if let Some(ref url) = file_data.data_url {
+                                div { class: "image-preview",
+                                    img {
+                                        class: "image-preview-img",
+                                        src: "{url}",
+                                        alt: "{file_data.name}",
+                                    }
                                 }
                             }
+                        } else if let Some(ref html) = rendered_html {
+                            div { class: "markdown-rendered", dangerous_inner_html: "{html}" }
+                        } else {
+                            pre { class: "markdown-raw", "{file_data.raw_content}" }
                         }
-                    } else if let Some(ref html) = rendered_html {
-                        div { class: "markdown-rendered", dangerous_inner_html: "{html}" }
-                    } else {
-                        pre { class: "markdown-raw", "{file_data.raw_content}" }
                     }
                 }
             }
@@ -2,6 +2,8 @@
 
 use dioxus::prelude::*;
 
+use crate::a11y::{NavKey, Orientation};
+
 /// Action types available in the slash menu.
 #[derive(Clone, Debug, PartialEq)]
 pub enum SlashAction {
@@ -75,12 +77,17 @@ impl SlashAction {
 }
 
 /// Slash command menu overlay.
+///
+/// Takes focus when shown. Up/Down/Home/End move the highlight, Enter
+/// selects, and Escape closes.
 #[component]
 pub fn SlashMenu(
     visible: bool,
     on_select: EventHandler<SlashAction>,
     on_close: EventHandler<()>,
 ) -> Element {
+    let mut highlighted = use_signal(|| 0usize);
+
     if !visible {
         return rsx! {};
     }
@@ -103,38 +110,58 @@ pub fn SlashMenu(
         SlashAction::Offering,
     ];
 
+    let all_items: Vec<SlashAction> = leaf_items.iter().chain(tree_items.iter()).cloned().collect();
+    let current = (*highlighted.read()).min(all_items.len() - 1);
+    let active_id = format!("slash-item-{current}");
+    let tree_offset = leaf_items.len();
+    let groups = [("Leaf Blocks", leaf_items, 0), ("Tree Artifacts", tree_items, tree_offset)];
+
     rsx! {
         div {
             class: "slash-menu visible",
-            div { class: "slash-menu-title", "Leaf Blocks" }
-            for item in leaf_items.iter() {
-                {
-                    let action = item.clone();
-                    rsx! {
-                        div {
-                            class: "slash-item",
-                            onclick: move |_| on_select.call(action.clone()),
-                            div { class: "slash-item-icon", "{item.icon()}" }
-                            div {
-                                div { class: "slash-item-name", "{item.label()}" }
-                                div { class: "slash-item-desc", "{item.description()}" }
-                            }
-                        }
+            role: "listbox",
+            "aria-label": "Insert block",
+            "aria-activedescendant": "{active_id}",
+            tabindex: "0",
+            onmounted: move |evt: MountedEvent| async move {
+                highlighted.set(0);
+                let _ = evt.set_focus(true).await;
+            },
+            onkeydown: move |evt: KeyboardEvent| {
+                let key = evt.key();
+                if let Some(nav) = NavKey::from_key(&key, Orientation::Vertical) {
+                    evt.prevent_default();
+                    if let Some(i) = nav.apply(Some(current), all_items.len()) {
+                        highlighted.set(i);
                     }
+                } else if key == Key::Enter {
+                    evt.prevent_default();
+                    on_select.call(all_items[current].clone());
+                } else if key == Key::Escape {
+                    evt.prevent_default();
+                    on_close.call(());
                 }
-            }
-            div { class: "slash-menu-title", "Tree Artifacts" }
-            for item in tree_items.iter() {
-                {
-                    let action = item.clone();
-                    rsx! {
-                        div {
-                            class: "slash-item",
-                            onclick: move |_| on_select.call(action.clone()),
-                            div { class: "slash-item-icon", "{item.icon()}" }
+            },
+            for (title, items, offset) in groups {
+                div { class: "slash-menu-title", role: "presentation", "{title}" }
+                for (i, item) in items.into_iter().enumerate() {
+                    {
+                        let index = offset + i;
+                        let selected = index == current;
+                        let action = item.clone();
+                        rsx! {
                             div {
-                                div { class: "slash-item-name", "{item.label()}" }
-                                div { class: "slash-item-desc", "{item.description()}" }
+                                id: "slash-item-{index}",
+                                class: if selected { "slash-item selected" } else { "slash-item" },
+                                role: "option",
+                                "aria-selected": if selected { "true" } else { "false" },
+                                onclick: move |_| on_select.call(action.clone()),
+                                onmouseenter: move |_| highlighted.set(index),
+                                div { class: "slash-item-icon", "aria-hidden": "true", "{item.icon()}" }
+                                div {
+                                    div { class: "slash-item-name", "{item.label()}" }
+                                    div { class: "slash-item-desc", "{item.description()}" }
+                                }
                             }
                         }
                     }
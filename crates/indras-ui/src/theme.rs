@@ -2,9 +2,15 @@
 //!
 //! Provides 7 skins: Technical, Organic, Botanical, Jewels, Modern, Contemplative, and Solarpunk.
 //! Each skin is a self-contained visual identity: colors, fonts, border radii, accent.
+//! User themes loaded at runtime (see [`user_theme`](crate::user_theme)) are selected
+//! through the same [`CURRENT_SKIN`] signal as [`Skin::Custom`].
 
 use dioxus::prelude::*;
 
+use crate::a11y::{use_announcer_provider, LiveRegion};
+use crate::i18n::tr;
+use crate::user_theme::USER_THEMES;
+
 /// Available skins for the application.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum Skin {
@@ -16,6 +22,8 @@ pub enum Skin {
     Modern,
     Contemplative,
     Solarpunk,
+    /// A user theme, by index into [`USER_THEMES`].
+    Custom(usize),
 }
 
 impl Skin {
@@ -29,6 +37,7 @@ impl Skin {
             Skin::Modern => "modern",
             Skin::Contemplative => "contemplative",
             Skin::Solarpunk => "solarpunk",
+            Skin::Custom(_) => "custom",
         }
     }
 
@@ -42,10 +51,11 @@ impl Skin {
             Skin::Modern => "Modern",
             Skin::Contemplative => "Contemplative",
             Skin::Solarpunk => "Solarpunk",
+            Skin::Custom(_) => "Custom",
         }
     }
 
-    /// Returns all available skins.
+    /// Returns all built-in skins.
     pub fn all() -> &'static [Skin] {
         &[
             Skin::Technical,
@@ -63,15 +73,36 @@ impl Skin {
 pub static CURRENT_SKIN: GlobalSignal<Skin> = GlobalSignal::new(|| Skin::default());
 
 /// Themed root wrapper component.
+///
+/// A user theme renders as its base skin plus a scoped stylesheet of its
+/// token overrides. Also hosts the screen-reader live region used by
+/// [`Announcer`](crate::a11y::Announcer).
 #[component]
 pub fn ThemedRoot(children: Element) -> Element {
+    use_announcer_provider();
     let skin = *CURRENT_SKIN.read();
+    let user_theme = match skin {
+        Skin::Custom(i) => USER_THEMES.read().get(i).cloned(),
+        _ => None,
+    };
+    let base = match &user_theme {
+        Some(theme) => theme.base_skin(),
+        None if matches!(skin, Skin::Custom(_)) => Skin::default(),
+        None => skin,
+    };
+    let slug = user_theme.as_ref().map(|t| t.slug.clone());
+    let css = user_theme.as_ref().map(|t| t.css());
 
     rsx! {
         div {
             class: "themed-root",
-            "data-skin": "{skin.css_value()}",
+            "data-skin": "{base.css_value()}",
+            "data-user-theme": slug,
+            if let Some(css) = css {
+                style { "{css}" }
+            }
             {children}
+            LiveRegion {}
         }
     }
 }
@@ -80,13 +111,21 @@ pub fn ThemedRoot(children: Element) -> Element {
 #[component]
 pub fn SkinSwitcher() -> Element {
     let current_skin = *CURRENT_SKIN.read();
+    let user_themes = USER_THEMES.read().clone();
 
     rsx! {
         div { class: "skin-switcher",
             select {
+                "aria-label": tr("skin-label"),
                 value: "{current_skin.css_value()}",
                 onchange: move |evt| {
                     let value = evt.value();
+                    if let Some(index) = value.strip_prefix("user:") {
+                        if let Ok(i) = index.parse() {
+                            *CURRENT_SKIN.write() = Skin::Custom(i);
+                        }
+                        return;
+                    }
                     let new_skin = match value.as_str() {
                         "organic" => Skin::Organic,
                         "botanical" => Skin::Botanical,
@@ -105,6 +144,13 @@ pub fn SkinSwitcher() -> Element {
                         "{s.display_name()}"
                     }
                 }
+                for (i, theme) in user_themes.iter().enumerate() {
+                    option {
+                        value: "user:{i}",
+                        selected: current_skin == Skin::Custom(i),
+                        "{theme.manifest.name}"
+                    }
+                }
             }
         }
     }
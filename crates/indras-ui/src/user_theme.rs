@@ -0,0 +1,361 @@
+//! User-defined themes loaded at runtime.
+//!
+//! A theme is a TOML manifest in the `themes/` directory of an app's data
+//! dir. It names a built-in skin to start from and overrides any of its
+//! color, font, and spacing tokens:
+//!
+//! ```toml
+//! name = "Midnight"
+//! base = "technical"
+//!
+//! [colors]
+//! bg = "#05070d"
+//! accent = "#7aa2f7"
+//!
+//! [fonts]
+//! body = "'Inter', sans-serif"
+//!
+//! [spacing]
+//! radius = "6px"
+//! ```
+//!
+//! Loaded themes are kept in [`USER_THEMES`] and selected like any other
+//! skin by setting [`CURRENT_SKIN`] to [`Skin::Custom`]; [`ThemedRoot`]
+//! injects the overrides so every component below it picks them up.
+//!
+//! [`ThemedRoot`]: crate::ThemedRoot
+
+use std::path::{Path, PathBuf};
+
+use dioxus::prelude::*;
+use serde::Deserialize;
+
+use crate::theme::{Skin, CURRENT_SKIN};
+
+/// Errors from loading a theme manifest.
+#[derive(Debug, thiserror::Error)]
+pub enum ThemeError {
+    /// The manifest file could not be read.
+    #[error("Failed to read theme {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    /// The manifest is not valid TOML or has unknown fields.
+    #[error("Invalid theme manifest: {0}")]
+    Parse(#[from] toml::de::Error),
+
+    /// `base` does not name a built-in skin.
+    #[error("Unknown base skin: {0}")]
+    UnknownBase(String),
+
+    /// A token value could break out of its CSS declaration.
+    #[error("Invalid value for {token}: {value:?}")]
+    InvalidValue { token: String, value: String },
+}
+
+/// Color tokens. Each maps to one `--s-*` skin variable.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ThemeColors {
+    pub bg: Option<String>,
+    pub bg2: Option<String>,
+    pub card: Option<String>,
+    pub hover: Option<String>,
+    pub text: Option<String>,
+    pub text2: Option<String>,
+    pub muted: Option<String>,
+    pub ghost: Option<String>,
+    pub border: Option<String>,
+    pub border2: Option<String>,
+    pub accent: Option<String>,
+    pub accent2: Option<String>,
+    pub success: Option<String>,
+    pub warning: Option<String>,
+    pub error: Option<String>,
+    pub glass: Option<String>,
+    pub glass_border: Option<String>,
+}
+
+/// Font stacks.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ThemeFonts {
+    pub body: Option<String>,
+    pub display: Option<String>,
+    pub mono: Option<String>,
+}
+
+/// Corner radius and spacing scale.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ThemeSpacing {
+    pub radius: Option<String>,
+    pub space_1: Option<String>,
+    pub space_2: Option<String>,
+    pub space_3: Option<String>,
+    pub space_4: Option<String>,
+    pub space_5: Option<String>,
+    pub space_6: Option<String>,
+    pub space_8: Option<String>,
+}
+
+/// Parsed contents of a theme file.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ThemeManifest {
+    /// Name shown in the skin switcher.
+    pub name: String,
+    /// Built-in skin supplying every token the theme leaves out.
+    #[serde(default)]
+    pub base: Option<String>,
+    #[serde(default)]
+    pub colors: ThemeColors,
+    #[serde(default)]
+    pub fonts: ThemeFonts,
+    #[serde(default)]
+    pub spacing: ThemeSpacing,
+}
+
+impl ThemeManifest {
+    /// Parse and validate a TOML manifest.
+    pub fn parse(source: &str) -> Result<Self, ThemeError> {
+        let manifest: Self = toml::from_str(source)?;
+        manifest.base_skin()?;
+        for (token, value) in manifest.tokens() {
+            if value.is_empty() || value.contains([';', '{', '}', '<', '>', '\n', '\r']) {
+                return Err(ThemeError::InvalidValue {
+                    token: token.to_string(),
+                    value: value.to_string(),
+                });
+            }
+        }
+        Ok(manifest)
+    }
+
+    /// The built-in skin this theme starts from (Technical if unset).
+    pub fn base_skin(&self) -> Result<Skin, ThemeError> {
+        match &self.base {
+            None => Ok(Skin::default()),
+            Some(base) => Skin::all()
+                .iter()
+                .copied()
+                .find(|s| s.css_value() == base.to_lowercase())
+                .ok_or_else(|| ThemeError::UnknownBase(base.clone())),
+        }
+    }
+
+    /// The CSS variables this theme sets, with their values.
+    pub fn tokens(&self) -> Vec<(&'static str, &str)> {
+        let c = &self.colors;
+        let f = &self.fonts;
+        let s = &self.spacing;
+        [
+            ("--s-bg", &c.bg),
+            ("--s-bg2", &c.bg2),
+            ("--s-card", &c.card),
+            ("--s-hover", &c.hover),
+            ("--s-tx", &c.text),
+            ("--s-t2", &c.text2),
+            ("--s-mt", &c.muted),
+            ("--s-gh", &c.ghost),
+            ("--s-bd", &c.border),
+            ("--s-b2", &c.border2),
+            ("--s-ac", &c.accent),
+            ("--s-a2", &c.accent2),
+            ("--s-ok", &c.success),
+            ("--s-wn", &c.warning),
+            ("--s-er", &c.error),
+            ("--s-glass", &c.glass),
+            ("--s-glass-bd", &c.glass_border),
+            ("--s-font", &f.body),
+            ("--s-display", &f.display),
+            ("--font-mono", &f.mono),
+            ("--s-radius", &s.radius),
+            ("--space-1", &s.space_1),
+            ("--space-2", &s.space_2),
+            ("--space-3", &s.space_3),
+            ("--space-4", &s.space_4),
+            ("--space-5", &s.space_5),
+            ("--space-6", &s.space_6),
+            ("--space-8", &s.space_8),
+        ]
+        .into_iter()
+        .filter_map(|(token, value)| value.as_deref().map(|v| (token, v.trim())))
+        .collect()
+    }
+}
+
+/// A theme loaded from the themes directory.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UserTheme {
+    /// File stem of the manifest (ASCII letters, digits, `-`, `_`);
+    /// identifies the theme across reloads.
+    pub slug: String,
+    pub manifest: ThemeManifest,
+}
+
+impl UserTheme {
+    /// Load one manifest file.
+    pub fn load(path: &Path) -> Result<Self, ThemeError> {
+        let source = std::fs::read_to_string(path).map_err(|source| ThemeError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let slug = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let valid_slug = !slug.is_empty()
+            && slug.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid_slug {
+            return Err(ThemeError::InvalidValue {
+                token: "file name".to_string(),
+                value: slug,
+            });
+        }
+        Ok(Self {
+            slug,
+            manifest: ThemeManifest::parse(&source)?,
+        })
+    }
+
+    /// The built-in skin underneath this theme.
+    pub fn base_skin(&self) -> Skin {
+        self.manifest.base_skin().unwrap_or_default()
+    }
+
+    /// Stylesheet applying this theme's overrides.
+    ///
+    /// Scoped to `[data-skin][data-user-theme="<slug>"]`, which outranks the
+    /// base skin's `[data-skin="..."]` block.
+    pub fn css(&self) -> String {
+        let mut css = format!("[data-skin][data-user-theme=\"{}\"] {{\n", self.slug);
+        for (token, value) in self.manifest.tokens() {
+            css.push_str(&format!("  {token}: {value};\n"));
+        }
+        css.push_str("}\n");
+        css
+    }
+}
+
+/// Themes loaded by [`install_user_themes`], indexed by [`Skin::Custom`].
+pub static USER_THEMES: GlobalSignal<Vec<UserTheme>> = Signal::global(Vec::new);
+
+/// Themes directory inside an app data dir.
+pub fn themes_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("themes")
+}
+
+/// Load every `*.toml` manifest in `dir`, sorted by slug.
+///
+/// Invalid manifests are logged and skipped; a missing directory yields no
+/// themes.
+pub fn load_user_themes(dir: &Path) -> Vec<UserTheme> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut themes: Vec<UserTheme> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "toml"))
+        .filter_map(|p| match UserTheme::load(&p) {
+            Ok(theme) => Some(theme),
+            Err(e) => {
+                tracing::warn!(path = %p.display(), error = %e, "Skipping theme");
+                None
+            }
+        })
+        .collect();
+    themes.sort_by(|a, b| a.slug.cmp(&b.slug));
+    themes
+}
+
+/// (Re)load the themes in `dir` into [`USER_THEMES`].
+///
+/// If a user theme is active it stays selected when it still exists, and
+/// falls back to the default skin when it was removed.
+pub fn install_user_themes(dir: &Path) {
+    let themes = load_user_themes(dir);
+    let active = match *CURRENT_SKIN.peek() {
+        Skin::Custom(i) => USER_THEMES.peek().get(i).map(|t| t.slug.clone()),
+        _ => None,
+    };
+    let reselect = active.map(|slug| themes.iter().position(|t| t.slug == slug));
+    *USER_THEMES.write() = themes;
+    match reselect {
+        Some(Some(i)) => *CURRENT_SKIN.write() = Skin::Custom(i),
+        Some(None) => *CURRENT_SKIN.write() = Skin::default(),
+        None => {}
+    }
+}
+
+/// Switch to a loaded user theme. Returns false if no theme has that slug.
+pub fn select_user_theme(slug: &str) -> bool {
+    let index = USER_THEMES.peek().iter().position(|t| t.slug == slug);
+    match index {
+        Some(i) => {
+            *CURRENT_SKIN.write() = Skin::Custom(i);
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = ThemeManifest::parse(
+            r##"
+            name = "Midnight"
+            base = "Organic"
+
+            [colors]
+            bg = "#05070d"
+            accent = "#7aa2f7"
+
+            [spacing]
+            radius = "6px"
+            "##,
+        )
+        .unwrap();
+        assert_eq!(manifest.base_skin().unwrap(), Skin::Organic);
+        assert_eq!(
+            manifest.tokens(),
+            vec![("--s-bg", "#05070d"), ("--s-ac", "#7aa2f7"), ("--s-radius", "6px")]
+        );
+    }
+
+    #[test]
+    fn test_rejects_bad_manifests() {
+        assert!(matches!(
+            ThemeManifest::parse("name = \"x\"\nbase = \"neon\""),
+            Err(ThemeError::UnknownBase(_))
+        ));
+        assert!(matches!(
+            ThemeManifest::parse("name = \"x\"\n[colors]\nbg = \"red; } body { display: none\""),
+            Err(ThemeError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            ThemeManifest::parse("name = \"x\"\n[colors]\nbackground = \"red\""),
+            Err(ThemeError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_css_is_scoped_to_slug() {
+        let theme = UserTheme {
+            slug: "midnight".to_string(),
+            manifest: ThemeManifest::parse("name = \"Midnight\"\n[fonts]\nmono = \"Iosevka\"")
+                .unwrap(),
+        };
+        assert_eq!(
+            theme.css(),
+            "[data-skin][data-user-theme=\"midnight\"] {\n  --font-mono: Iosevka;\n}\n"
+        );
+    }
+}
@@ -2,6 +2,8 @@
 
 use dioxus::prelude::*;
 
+use crate::a11y::{focus_element, NavKey, Orientation};
+
 /// A node in the vault tree for sidebar display.
 #[derive(Clone, Debug, PartialEq)]
 pub struct TreeNode {
@@ -18,18 +20,77 @@ pub struct TreeNode {
 }
 
 /// Vault sidebar tree with sections, expand/collapse, heat dots.
+///
+/// Keyboard: Up/Down/Home/End move between items, Right expands (or steps
+/// into the first child), Left collapses (or steps out to the parent), and
+/// Enter or Space opens the item.
 #[component]
 pub fn VaultSidebar(
     nodes: Vec<TreeNode>,
     on_click: EventHandler<String>,
     on_toggle: EventHandler<String>,
 ) -> Element {
+    let mut focused = use_signal(|| None::<usize>);
+    // The one item in the tab order: the focused item, else the active one.
+    let focused_index = *focused.read();
+    let tab_stop = focused_index
+        .filter(|&i| i < nodes.len())
+        .or_else(|| nodes.iter().position(|n| n.active))
+        .unwrap_or(0);
+
+    let on_keydown = {
+        let nodes = nodes.clone();
+        move |evt: KeyboardEvent| {
+            let key = evt.key();
+            let current = tab_stop;
+            let Some(node) = nodes.get(current) else {
+                return;
+            };
+            let target = if let Some(nav) = NavKey::from_key(&key, Orientation::Vertical) {
+                nav.apply(Some(current), nodes.len())
+            } else {
+                match key {
+                    Key::ArrowRight if node.has_children && !node.expanded => {
+                        on_toggle.call(node.id.clone());
+                        None
+                    }
+                    Key::ArrowRight if node.has_children => nodes
+                        .get(current + 1)
+                        .filter(|n| n.depth > node.depth)
+                        .map(|_| current + 1),
+                    Key::ArrowLeft if node.has_children && node.expanded => {
+                        on_toggle.call(node.id.clone());
+                        None
+                    }
+                    Key::ArrowLeft => nodes[..current].iter().rposition(|n| n.depth < node.depth),
+                    Key::Enter => {
+                        on_click.call(node.id.clone());
+                        None
+                    }
+                    Key::Character(ref c) if c == " " => {
+                        on_click.call(node.id.clone());
+                        None
+                    }
+                    _ => return,
+                }
+            };
+            evt.prevent_default();
+            if let Some(i) = target {
+                focused.set(Some(i));
+                focus_element(&format!("tree-item-{}", nodes[i].id));
+            }
+        }
+    };
+
     rsx! {
         div {
             class: "vault-tree",
-            for node in nodes.iter() {
+            role: "tree",
+            "aria-label": "Vault",
+            onkeydown: on_keydown,
+            for (index, node) in nodes.iter().enumerate() {
                 if let Some(ref section) = node.section {
-                    div { class: "tree-section-label", "{section}" }
+                    div { class: "tree-section-label", role: "presentation", "{section}" }
                 }
                 {
                     let active_class = if node.active { " active" } else { "" };
@@ -38,8 +99,16 @@ pub fn VaultSidebar(
                     let node_id2 = node.id.clone();
                     rsx! {
                         div {
+                            id: "tree-item-{node.id}",
                             class: "tree-item{active_class}",
+                            role: "treeitem",
+                            tabindex: if index == tab_stop { "0" } else { "-1" },
+                            "aria-level": "{node.depth + 1}",
+                            "aria-selected": if node.active { "true" } else { "false" },
+                            "aria-expanded": if node.has_children { Some(if node.expanded { "true" } else { "false" }) } else { None },
+                            "aria-label": "{node.label}",
                             "data-heat": "{heat_attr}",
+                            onfocus: move |_| focused.set(Some(index)),
                             onclick: move |_| on_click.call(node_id.clone()),
                             // Indentation
                             for _ in 0..node.depth {
@@ -49,6 +118,7 @@ pub fn VaultSidebar(
                             if node.has_children {
                                 div {
                                     class: if node.expanded { "tree-toggle open" } else { "tree-toggle" },
+                                    "aria-hidden": "true",
                                     onclick: move |evt| {
                                         evt.stop_propagation();
                                         on_toggle.call(node_id2.clone());
@@ -58,9 +128,9 @@ pub fn VaultSidebar(
                             } else {
                                 div { class: "tree-toggle" }
                             }
-                            div { class: "tree-icon", "{node.icon}" }
+                            div { class: "tree-icon", "aria-hidden": "true", "{node.icon}" }
                             div { class: "tree-label", "{node.label}" }
-                            div { class: "heat-dot heat-{node.heat_level}" }
+                            div { class: "heat-dot heat-{node.heat_level}", "aria-hidden": "true" }
                         }
                     }
                 }
@@ -1,7 +1,7 @@
 //! Shared infrastructure for Indras viewer crates.
 //!
-//! This crate provides the common building blocks used by `indras-home-viewer`
-//! and `indras-realm-viewer`:
+//! This crate provides the common building blocks used by `indras-home-viewer`,
+//! `indras-realm-viewer`, and `indras-collaboration-viewer`:
 //!
 //! - [`playback`] — global atomic playback controls (pause, speed, step,
 //!   reset, seek, shutdown).  A single module replaces the duplicated
@@ -12,10 +12,11 @@
 //!
 //! # What is NOT shared
 //!
-//! Event types (`HomeRealmEvent`, `StreamEvent`) remain in their respective
-//! viewer crates because they are domain-specific and unrelated to each other.
-//! The collaboration-viewer uses a tick-based state machine rather than JSONL
-//! streaming, so it does not use this crate at all.
+//! Event types (`HomeRealmEvent`, `StreamEvent`, `CollabEvent`) remain in their
+//! respective viewer crates because they are domain-specific. The
+//! collaboration-viewer reads the realm-viewer schema but keeps its own subset,
+//! and drives playback from its own state rather than [`playback`], so it only
+//! uses [`stream`].
 
 pub mod playback;
 pub mod stream;
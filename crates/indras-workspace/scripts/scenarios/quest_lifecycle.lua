@@ -0,0 +1,51 @@
+-- Quest lifecycle on the home realm
+-- Verifies: create a quest, focus attention on it, claim and verify it,
+-- then bless the claim and receive a token of gratitude.
+
+indras.wait_for("app_ready")
+indras.log.info("Starting quest lifecycle scenario")
+
+local title = "Plant the community garden"
+local tokens_before = indras.query("token_count")
+
+-- Create
+indras.create_quest({ title = title, description = "Tomatoes and beans", kind = "need" })
+indras.wait_for("quest_created", title, 10)
+indras.assert.matches(indras.quest(title), { status = "open", kind = "need", claim_count = 0 })
+
+-- Attention
+indras.focus_attention(title)
+indras.wait_for("attention_switched", title, 10)
+indras.assert.eq(indras.query("attention_focus"), title, "Attention should be on the quest")
+indras.wait(1)
+
+-- Claim and verify
+indras.claim_quest(title)
+indras.wait_for("quest_claimed", title, 10)
+indras.assert.matches(indras.quest(title), { status = "claimed", claim_count = 1 })
+
+indras.verify_claim(title, "me")
+indras.wait_for("claim_verified", title, 10)
+indras.assert.matches(indras.quest(title), { status = "verified", claims = { { verified = true } } })
+
+-- Bless: releases the attention spent above and mints a token
+indras.clear_attention()
+indras.wait_for("attention_switched", nil, 10)
+indras.bless(title, "me")
+indras.wait_for("blessing_granted", title, 10)
+indras.assert.matches(indras.quest(title), { blessing_count = 1 })
+indras.assert.eq(indras.query("token_count"), tokens_before + 1, "Blessing should mint one token")
+
+local tokens = indras.query("tokens")
+local minted = false
+for _, token in ipairs(tokens) do
+    if token.source_quest == title then minted = true end
+end
+indras.assert.truthy(minted, "Token should trace back to the quest")
+
+-- Complete
+indras.complete_quest(title)
+indras.wait_for("quest_completed", title, 10)
+indras.assert.matches(indras.quest(title), { status = "completed" })
+
+indras.log.info("Quest lifecycle scenario passed")
@@ -1,3 +1,5 @@
 pub mod vault_bridge;
 pub mod network_bridge;
 pub mod realm_bridge;
+pub mod window_manager;
+pub mod upload_pipeline;
@@ -16,10 +16,11 @@ use indras_sync_engine::{
     AttentionDocument, AttentionEventId, BlessingDocument, BlessingId,
     ClaimId, Intention, IntentionDocument, IntentionId, IntentionKind,
     TokenOfGratitudeDocument, TokenOfGratitudeId,
-    HomeRealmIntentions,
+    HomeRealmIntentions, OfferContent, OfferId, RealmIntentions, RealmRequests, RequestId,
+    SyncEngine,
 };
 use std::sync::Arc;
-use indras_network::IndrasNetwork;
+use indras_network::{IndrasNetwork, Realm, RealmId};
 
 /// Handle wrapping a HomeRealm and local member identity for UI actions.
 #[derive(Clone)]
@@ -322,4 +323,104 @@ impl RealmHandle {
             .await?;
         Ok(())
     }
+
+    // --- Requests (via RealmRequests trait) ---
+
+    /// Look up a loaded realm by ID.
+    fn realm(&self, realm_id: &RealmId) -> Result<Realm> {
+        self.network
+            .get_realm_by_id(realm_id)
+            .ok_or_else(|| IndraError::InvalidOperation("Realm not loaded".into()))
+    }
+
+    /// Broadcast a request to a realm.
+    pub async fn broadcast_request(
+        &self,
+        realm_id: RealmId,
+        description: &str,
+    ) -> Result<RequestId> {
+        self.realm(&realm_id)?
+            .broadcast_request(self.member_id, description)
+            .await
+    }
+
+    /// Offer an artifact or a pledge on someone's request.
+    pub async fn offer_to_request(
+        &self,
+        realm_id: RealmId,
+        request_id: RequestId,
+        content: OfferContent,
+    ) -> Result<OfferId> {
+        self.realm(&realm_id)?
+            .offer_to_request(request_id, self.member_id, content)
+            .await
+    }
+
+    /// Withdraw one of our offers before it is accepted.
+    pub async fn withdraw_request_offer(
+        &self,
+        realm_id: RealmId,
+        request_id: RequestId,
+        offer_id: OfferId,
+    ) -> Result<()> {
+        self.realm(&realm_id)?
+            .withdraw_request_offer(request_id, offer_id, self.member_id)
+            .await
+    }
+
+    /// Accept an offer on one of our requests.
+    ///
+    /// An accepted pledge becomes a quest in the realm; it is also
+    /// copied to the home realm so it appears in My Intentions.
+    pub async fn accept_request_offer(
+        &self,
+        realm_id: RealmId,
+        request_id: RequestId,
+        offer_id: OfferId,
+    ) -> Result<()> {
+        let realm = self.realm(&realm_id)?;
+        let acceptance = realm
+            .accept_request_offer(request_id, offer_id, self.member_id)
+            .await?;
+
+        if let Some(quest_id) = acceptance.quest {
+            let quest = {
+                let doc = realm.intentions().await?;
+                let data = doc.read().await;
+                data.find(&quest_id).cloned()
+            };
+            if let Some(quest) = quest {
+                let home_doc = self.home.document::<IntentionDocument>("intentions").await?;
+                home_doc.update(|d| {
+                    d.add(quest);
+                }).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Mark one of our requests fulfilled.
+    pub async fn mark_request_fulfilled(
+        &self,
+        realm_id: RealmId,
+        request_id: RequestId,
+    ) -> Result<()> {
+        self.realm(&realm_id)?
+            .mark_request_fulfilled(request_id, self.member_id)
+            .await
+    }
+
+    /// Cancel one of our requests.
+    pub async fn cancel_request(&self, realm_id: RealmId, request_id: RequestId) -> Result<()> {
+        self.realm(&realm_id)?
+            .cancel_request(request_id, self.member_id)
+            .await
+    }
+
+    /// Share artifacts for any of our offers that were accepted.
+    pub async fn deliver_request_artifacts(&self) -> Result<Vec<RequestId>> {
+        SyncEngine::new(Arc::clone(&self.network))
+            .deliver_accepted_request_offers()
+            .await
+    }
 }
@@ -0,0 +1,422 @@
+//! Parallel upload pipeline for multi-file drops.
+//!
+//! Dropped files are uploaded to the home realm a few at a time instead of
+//! one after another: each file is read, hashed off the runtime, written to
+//! the blob store and indexed by `HomeRealm::upload_data`. Progress for the
+//! whole batch is broadcast as an [`UploadEvent`] after every change.
+//!
+//! Files can be cancelled while queued or reading (a blob write in progress
+//! always finishes), and failed or cancelled files can be retried. When a
+//! batch targets a realm, the files that finished are announced there with
+//! a single gallery message each time the batch settles.
+//!
+//! Blobs are stored as-is, like `HomeRealm::upload`; access is controlled by
+//! the grants `SyncEngine::announce_gallery` adds for realm members.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use indras_network::{ArtifactId, IndrasNetwork, Realm, RealmId};
+use indras_sync_engine::SyncEngine;
+use tokio::sync::{Semaphore, broadcast, watch};
+
+/// Files uploaded at once when no other limit is given.
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Identifies one drop of files.
+pub type BatchId = u64;
+
+/// Where a file is in the pipeline.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FileStage {
+    /// Waiting for an upload slot.
+    Queued,
+    /// Reading the file from disk.
+    Reading,
+    /// Hashing, writing the blob, and indexing it.
+    Storing,
+    /// Uploaded to the home realm.
+    Done(ArtifactId),
+    Failed(String),
+    Cancelled,
+}
+
+impl FileStage {
+    /// Whether the file has stopped moving through the pipeline.
+    pub fn is_settled(&self) -> bool {
+        matches!(self, Self::Done(_) | Self::Failed(_) | Self::Cancelled)
+    }
+
+    /// Whether [`UploadPipeline::retry`] accepts the file.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::Failed(_) | Self::Cancelled)
+    }
+}
+
+/// Progress of one file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FileProgress {
+    pub path: PathBuf,
+    pub name: String,
+    /// Size on disk when the batch started.
+    pub size: u64,
+    pub stage: FileStage,
+}
+
+/// Aggregate progress of a batch.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BatchProgress {
+    pub batch_id: BatchId,
+    /// Realm the batch is announced to, if any.
+    pub realm_id: Option<RealmId>,
+    pub files: Vec<FileProgress>,
+}
+
+impl BatchProgress {
+    pub fn total_bytes(&self) -> u64 {
+        self.files.iter().map(|f| f.size).sum()
+    }
+
+    /// Bytes of the files that finished uploading.
+    pub fn uploaded_bytes(&self) -> u64 {
+        self.files
+            .iter()
+            .filter(|f| matches!(f.stage, FileStage::Done(_)))
+            .map(|f| f.size)
+            .sum()
+    }
+
+    /// Number of files whose stage matches.
+    pub fn count(&self, matches: impl Fn(&FileStage) -> bool) -> usize {
+        self.files.iter().filter(|f| matches(&f.stage)).count()
+    }
+
+    /// Whether no file is queued or in flight.
+    pub fn is_settled(&self) -> bool {
+        self.files.iter().all(|f| f.stage.is_settled())
+    }
+
+    /// Completed share of the batch by bytes, 0.0 to 1.0.
+    pub fn fraction(&self) -> f32 {
+        let total = self.total_bytes();
+        if total == 0 {
+            let done = self.count(FileStage::is_settled);
+            return done as f32 / self.files.len().max(1) as f32;
+        }
+        self.uploaded_bytes() as f32 / total as f32
+    }
+}
+
+/// Broadcast by the pipeline.
+#[derive(Clone, Debug)]
+pub enum UploadEvent {
+    /// A file in the batch changed stage.
+    Progress(BatchProgress),
+    /// Finished files were announced to the batch's realm.
+    Announced {
+        batch_id: BatchId,
+        realm_id: RealmId,
+        files: usize,
+    },
+    /// Announcing to the realm failed; the files stay uploaded.
+    AnnounceFailed {
+        batch_id: BatchId,
+        realm_id: RealmId,
+        error: String,
+    },
+}
+
+/// Pipeline-side state of one file.
+struct FileSlot {
+    progress: FileProgress,
+    cancel: watch::Sender<bool>,
+    /// Whether a task currently owns the file.
+    running: bool,
+    announced: bool,
+}
+
+struct Batch {
+    realm: Option<Realm>,
+    files: Mutex<Vec<FileSlot>>,
+}
+
+impl Batch {
+    fn progress(&self, batch_id: BatchId) -> BatchProgress {
+        let files = self.files.lock().unwrap_or_else(|e| e.into_inner());
+        BatchProgress {
+            batch_id,
+            realm_id: self.realm.as_ref().map(Realm::id),
+            files: files.iter().map(|s| s.progress.clone()).collect(),
+        }
+    }
+}
+
+/// Uploads dropped files with bounded concurrency.
+pub struct UploadPipeline {
+    network: Arc<IndrasNetwork>,
+    permits: Arc<Semaphore>,
+    batches: Mutex<HashMap<BatchId, Arc<Batch>>>,
+    next_batch: AtomicU64,
+    events: broadcast::Sender<UploadEvent>,
+}
+
+impl UploadPipeline {
+    /// Create a pipeline uploading at most `concurrency` files at a time.
+    pub fn new(network: Arc<IndrasNetwork>, concurrency: usize) -> Arc<Self> {
+        let (events, _) = broadcast::channel(256);
+        Arc::new(Self {
+            network,
+            permits: Arc::new(Semaphore::new(concurrency.max(1))),
+            batches: Mutex::new(HashMap::new()),
+            next_batch: AtomicU64::new(1),
+            events,
+        })
+    }
+
+    /// Subscribe to progress and announcement events.
+    pub fn subscribe(&self) -> broadcast::Receiver<UploadEvent> {
+        self.events.subscribe()
+    }
+
+    /// Current progress of a batch.
+    pub fn progress(&self, batch_id: BatchId) -> Option<BatchProgress> {
+        self.batch(batch_id).map(|b| b.progress(batch_id))
+    }
+
+    /// Progress of every batch that has not been cleared.
+    pub fn batches(&self) -> Vec<BatchProgress> {
+        let batches = self.batches.lock().unwrap_or_else(|e| e.into_inner());
+        let mut all: Vec<_> = batches.iter().map(|(id, b)| b.progress(*id)).collect();
+        all.sort_by_key(|p| p.batch_id);
+        all
+    }
+
+    /// Queue `paths` as one batch, optionally announced to `realm`.
+    ///
+    /// Must be called from within the Tokio runtime.
+    pub fn start(self: &Arc<Self>, paths: Vec<PathBuf>, realm: Option<Realm>) -> BatchId {
+        let batch_id = self.next_batch.fetch_add(1, Ordering::Relaxed);
+        let slots = paths
+            .into_iter()
+            .map(|path| {
+                let name = path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("unnamed")
+                    .to_string();
+                let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+                FileSlot {
+                    progress: FileProgress {
+                        path,
+                        name,
+                        size,
+                        stage: FileStage::Queued,
+                    },
+                    cancel: watch::channel(false).0,
+                    running: true,
+                    announced: false,
+                }
+            })
+            .collect::<Vec<_>>();
+        let count = slots.len();
+        let batch = Arc::new(Batch {
+            realm,
+            files: Mutex::new(slots),
+        });
+        self.batches
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(batch_id, Arc::clone(&batch));
+
+        self.emit_progress(batch_id, &batch);
+        for index in 0..count {
+            self.spawn_file(batch_id, Arc::clone(&batch), index);
+        }
+        batch_id
+    }
+
+    /// Cancel one file. Returns whether it was still queued or in flight.
+    pub fn cancel(&self, batch_id: BatchId, index: usize) -> bool {
+        let Some(batch) = self.batch(batch_id) else {
+            return false;
+        };
+        let files = batch.files.lock().unwrap_or_else(|e| e.into_inner());
+        match files.get(index) {
+            Some(slot) if !slot.progress.stage.is_settled() => {
+                slot.cancel.send_replace(true);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Cancel every file of a batch that has not settled.
+    pub fn cancel_batch(&self, batch_id: BatchId) {
+        let Some(batch) = self.batch(batch_id) else {
+            return;
+        };
+        let files = batch.files.lock().unwrap_or_else(|e| e.into_inner());
+        for slot in files.iter().filter(|s| !s.progress.stage.is_settled()) {
+            slot.cancel.send_replace(true);
+        }
+    }
+
+    /// Queue a failed or cancelled file again. Returns whether it was queued.
+    pub fn retry(self: &Arc<Self>, batch_id: BatchId, index: usize) -> bool {
+        let Some(batch) = self.batch(batch_id) else {
+            return false;
+        };
+        {
+            let mut files = batch.files.lock().unwrap_or_else(|e| e.into_inner());
+            let Some(slot) = files.get_mut(index) else {
+                return false;
+            };
+            if slot.running || !slot.progress.stage.is_retryable() {
+                return false;
+            }
+            slot.cancel.send_replace(false);
+            slot.running = true;
+            slot.progress.stage = FileStage::Queued;
+        }
+        self.emit_progress(batch_id, &batch);
+        self.spawn_file(batch_id, batch, index);
+        true
+    }
+
+    /// Forget batches whose files have all settled.
+    pub fn clear_settled(&self) {
+        self.batches
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|id, batch| !batch.progress(*id).is_settled());
+    }
+
+    fn batch(&self, batch_id: BatchId) -> Option<Arc<Batch>> {
+        self.batches
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&batch_id)
+            .cloned()
+    }
+
+    fn emit_progress(&self, batch_id: BatchId, batch: &Batch) {
+        let _ = self
+            .events
+            .send(UploadEvent::Progress(batch.progress(batch_id)));
+    }
+
+    fn set_stage(&self, batch_id: BatchId, batch: &Batch, index: usize, stage: FileStage) {
+        {
+            let mut files = batch.files.lock().unwrap_or_else(|e| e.into_inner());
+            let slot = &mut files[index];
+            if stage.is_settled() {
+                slot.running = false;
+            }
+            slot.progress.stage = stage;
+        }
+        self.emit_progress(batch_id, batch);
+    }
+
+    fn spawn_file(self: &Arc<Self>, batch_id: BatchId, batch: Arc<Batch>, index: usize) {
+        let pipeline = Arc::clone(self);
+        tokio::spawn(async move {
+            let stage = pipeline.upload_file(batch_id, &batch, index).await;
+            pipeline.set_stage(batch_id, &batch, index, stage);
+            pipeline.announce_if_settled(batch_id, &batch).await;
+        });
+    }
+
+    /// Run one file through the pipeline, returning its final stage.
+    async fn upload_file(&self, batch_id: BatchId, batch: &Batch, index: usize) -> FileStage {
+        let (path, name, mut cancelled) = {
+            let files = batch.files.lock().unwrap_or_else(|e| e.into_inner());
+            let slot = &files[index];
+            (
+                slot.progress.path.clone(),
+                slot.progress.name.clone(),
+                slot.cancel.subscribe(),
+            )
+        };
+
+        let _permit = tokio::select! {
+            permit = Arc::clone(&self.permits).acquire_owned() => match permit {
+                Ok(permit) => permit,
+                Err(e) => return FileStage::Failed(e.to_string()),
+            },
+            _ = cancelled.wait_for(|c| *c) => return FileStage::Cancelled,
+        };
+        if *cancelled.borrow() {
+            return FileStage::Cancelled;
+        }
+
+        self.set_stage(batch_id, batch, index, FileStage::Reading);
+        let data = tokio::select! {
+            read = tokio::fs::read(&path) => match read {
+                Ok(data) => data,
+                Err(e) => return FileStage::Failed(format!("Failed to read file: {e}")),
+            },
+            _ = cancelled.wait_for(|c| *c) => return FileStage::Cancelled,
+        };
+
+        self.set_stage(batch_id, batch, index, FileStage::Storing);
+        let home = match self.network.home_realm().await {
+            Ok(home) => home,
+            Err(e) => return FileStage::Failed(e.to_string()),
+        };
+        match home.upload_data(name, data).await {
+            Ok(id) => FileStage::Done(id),
+            Err(e) => FileStage::Failed(e.to_string()),
+        }
+    }
+
+    /// Once no file is in flight, announce finished files not yet announced.
+    async fn announce_if_settled(&self, batch_id: BatchId, batch: &Batch) {
+        let Some(realm) = &batch.realm else {
+            return;
+        };
+        let artifacts: Vec<ArtifactId> = {
+            let mut files = batch.files.lock().unwrap_or_else(|e| e.into_inner());
+            if files.iter().any(|s| s.running) {
+                return;
+            }
+            files
+                .iter_mut()
+                .filter(|s| !s.announced)
+                .filter_map(|s| match s.progress.stage {
+                    FileStage::Done(id) => {
+                        s.announced = true;
+                        Some(id)
+                    }
+                    _ => None,
+                })
+                .collect()
+        };
+        if artifacts.is_empty() {
+            return;
+        }
+
+        let title = match artifacts.len() {
+            1 => "1 file".to_string(),
+            n => format!("{n} files"),
+        };
+        let engine = SyncEngine::new(Arc::clone(&self.network));
+        let event = match engine.announce_gallery(realm, title, &artifacts).await {
+            Ok(_) => UploadEvent::Announced {
+                batch_id,
+                realm_id: realm.id(),
+                files: artifacts.len(),
+            },
+            Err(e) => {
+                tracing::warn!(batch_id, error = %e, "Failed to announce uploaded files");
+                UploadEvent::AnnounceFailed {
+                    batch_id,
+                    realm_id: realm.id(),
+                    error: e.to_string(),
+                }
+            }
+        };
+        let _ = self.events.send(event);
+    }
+}
@@ -0,0 +1,204 @@
+//! Window manager — several Dioxus windows sharing one IndrasNetwork.
+//!
+//! Every desktop window runs its own VirtualDom, so signals and context do
+//! not cross window boundaries. The manager is what the windows share: the
+//! network handle, a registry of which window shows what, and a broadcast
+//! channel windows use to tell each other about changes their own signals
+//! cannot observe. File drops in any window go through the one shared
+//! upload pipeline.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use dioxus::desktop::{Config, LogicalSize, WindowBuilder};
+use dioxus::prelude::*;
+use indras_network::RealmId;
+use indras_ui::Skin;
+use tokio::sync::broadcast;
+
+use super::network_bridge::NetworkHandle;
+use super::upload_pipeline::{DEFAULT_CONCURRENCY, UploadPipeline};
+use crate::components::realm_window::{RealmWindow, RealmWindowProps};
+
+/// Workspace-specific CSS embedded at compile time.
+pub const WORKSPACE_CSS: &str = include_str!("../../assets/workspace.css");
+
+/// Custom `<head>` content shared by every workspace window.
+pub fn custom_head() -> String {
+    format!(
+        r#"
+        <link rel="preconnect" href="https://fonts.googleapis.com">
+        <link rel="preconnect" href="https://fonts.gstatic.com" crossorigin>
+        <link href="https://fonts.googleapis.com/css2?family=Cinzel:wght@400;500;600;700&family=Cormorant+Garamond:ital,wght@0,300;0,400;0,500;0,600;0,700;1,300;1,400;1,500&family=DM+Sans:ital,opsz,wght@0,9..40,300;0,9..40,400;0,9..40,500;0,9..40,600;0,9..40,700;1,9..40,400&family=Fraunces:ital,opsz,wght@0,9..144,300;0,9..144,400;0,9..144,500;0,9..144,600;0,9..144,700;0,9..144,900;1,9..144,400&family=Inter:wght@300;400;500;600;700&family=Instrument+Sans:wght@400;500;600&family=JetBrains+Mono:wght@300;400;500&family=Outfit:wght@300;400;500;600&family=Playfair+Display:wght@700;900&family=Plus+Jakarta+Sans:wght@300;400;500;600;700&family=Raleway:ital,wght@0,200;0,300;0,400;0,500;0,600;1,300;1,400&display=swap" rel="stylesheet">
+        <style>{}</style>
+        <style>{}</style>
+        <style>{}</style>
+        "#,
+        indras_ui::SHARED_CSS,
+        WORKSPACE_CSS,
+        indras_chat::CHAT_CSS,
+    )
+}
+
+/// What a window is showing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WindowRoute {
+    /// The main workspace window (sidebar, dashboard, settings).
+    Main,
+    /// A window pinned to one realm's conversation.
+    Realm(RealmId),
+}
+
+/// Identifies one open window in the manager's registry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WindowToken(u64);
+
+/// A change one window announces to the others.
+#[derive(Clone, Debug)]
+pub enum WindowEvent {
+    /// The window showing this route should come to the front.
+    Focus(WindowRoute),
+    /// A window opened or closed.
+    WindowsChanged,
+    /// Realm names or membership changed; windows should refresh titles.
+    RealmsChanged,
+    /// The skin changed (or user themes were reloaded); windows should
+    /// reload user themes and apply it.
+    SkinChanged(Skin),
+    /// The main window is closing; every other window should close too.
+    Closing,
+}
+
+/// Shared state for all windows of one workspace process.
+pub struct WindowManager {
+    network: NetworkHandle,
+    routes: Mutex<HashMap<WindowToken, WindowRoute>>,
+    next_token: AtomicU64,
+    skin: Mutex<Skin>,
+    events: broadcast::Sender<WindowEvent>,
+    uploads: Arc<UploadPipeline>,
+}
+
+impl WindowManager {
+    /// Create the manager for a running network, registering the main window.
+    pub fn new(network: NetworkHandle) -> Arc<Self> {
+        let (events, _) = broadcast::channel(64);
+        let uploads = UploadPipeline::new(Arc::clone(&network.network), DEFAULT_CONCURRENCY);
+        let manager = Arc::new(Self {
+            network,
+            routes: Mutex::new(HashMap::new()),
+            next_token: AtomicU64::new(0),
+            skin: Mutex::new(Skin::default()),
+            events,
+            uploads,
+        });
+        manager.register(WindowRoute::Main);
+        manager
+    }
+
+    /// The network every window shares.
+    pub fn network(&self) -> &NetworkHandle {
+        &self.network
+    }
+
+    /// The upload pipeline every window drops files into.
+    pub fn uploads(&self) -> &Arc<UploadPipeline> {
+        &self.uploads
+    }
+
+    /// Routes of all open windows.
+    pub fn open_windows(&self) -> Vec<WindowRoute> {
+        self.routes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .copied()
+            .collect()
+    }
+
+    /// The skin every window should show.
+    pub fn skin(&self) -> Skin {
+        *self.skin.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Record the skin chosen in the main window and announce it.
+    pub fn set_skin(&self, skin: Skin) {
+        *self.skin.lock().unwrap_or_else(|e| e.into_inner()) = skin;
+        self.notify(WindowEvent::SkinChanged(skin));
+    }
+
+    /// Subscribe to events announced by any window.
+    pub fn subscribe(&self) -> broadcast::Receiver<WindowEvent> {
+        self.events.subscribe()
+    }
+
+    /// Announce an event to every window.
+    pub fn notify(&self, event: WindowEvent) {
+        let _ = self.events.send(event);
+    }
+
+    /// Open a window for a realm, or focus it if one is already open.
+    ///
+    /// Must be called from a component running in a desktop window.
+    pub fn open_realm(self: &Arc<Self>, realm_id: RealmId) {
+        let route = WindowRoute::Realm(realm_id);
+        if self.open_windows().contains(&route) {
+            self.notify(WindowEvent::Focus(route));
+            return;
+        }
+        let Some(realm) = self.network.network.get_realm_by_id(&realm_id) else {
+            tracing::warn!("Cannot open window for unknown realm");
+            return;
+        };
+        let title = realm.name().unwrap_or("Realm").to_string();
+
+        let token = self.register(route);
+        let dom = VirtualDom::new_with_props(
+            RealmWindow,
+            RealmWindowProps::builder()
+                .manager(WindowManagerArc(Arc::clone(self)))
+                .realm_id(realm_id)
+                .token(token)
+                .build(),
+        );
+        let cfg = Config::new()
+            .with_window(
+                WindowBuilder::new()
+                    .with_title(format!("Indras — {title}"))
+                    .with_inner_size(LogicalSize::new(720.0, 820.0)),
+            )
+            .with_custom_head(custom_head());
+        let _ = dioxus::desktop::window().new_window(dom, cfg);
+    }
+
+    /// Add a window to the registry.
+    pub(crate) fn register(&self, route: WindowRoute) -> WindowToken {
+        let token = WindowToken(self.next_token.fetch_add(1, Ordering::Relaxed));
+        self.routes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(token, route);
+        self.notify(WindowEvent::WindowsChanged);
+        token
+    }
+
+    /// Remove a closed window from the registry.
+    pub(crate) fn unregister(&self, token: WindowToken) {
+        self.routes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&token);
+        self.notify(WindowEvent::WindowsChanged);
+    }
+}
+
+/// `Arc<WindowManager>` wrapper usable as a component prop.
+#[derive(Clone)]
+pub struct WindowManagerArc(pub Arc<WindowManager>);
+
+impl PartialEq for WindowManagerArc {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
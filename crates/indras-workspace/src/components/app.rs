@@ -9,6 +9,7 @@ use tokio::sync::Mutex;
 use crate::bridge::vault_bridge::{VaultHandle, InMemoryVault};
 use crate::bridge::network_bridge::{NetworkHandle, create_identity};
 use crate::bridge::realm_bridge::RealmHandle;
+use crate::bridge::window_manager::{WindowEvent, WindowManager, WindowManagerArc};
 use crate::components::topbar::Topbar;
 use crate::components::document::DocumentView;
 use crate::components::intention_view::{IntentionView, PeerOption, IntentionCreateOverlay, ProofEntry, AttentionItem, AttentionPeerSummary, PledgedToken, StewardshipChainEntry, format_duration_secs};
@@ -20,12 +21,19 @@ use crate::components::artifact_browser::{BrowsableArtifact, GrantDisplay, MimeC
 use crate::state::workspace::{EventDirection, log_event};
 use crate::state::workspace::{WorkspaceState, ViewType, AppPhase, PeerDisplayInfo, DashboardTab};
 use crate::components::intention_board::{IntentionBoard, IntentionCardData};
+use crate::components::request_board::RequestsTab;
+use crate::components::command_palette::CommandPaletteOverlay;
+use crate::components::upload_tray::{UploadTray, dropped_paths};
 use crate::state::navigation::{NavigationState, VaultTreeNode};
 use crate::state::editor::{EditorState, DocumentMeta, BlockDocumentSchema};
 use crate::services::boot::{run_boot_sequence, BootError};
 use crate::services::realm_data::{IntentionViewData, build_intention_view, build_intention_cards, build_community_intention_cards};
 use crate::services::event_subscription::subscribe_network_events;
+use crate::services::background;
+use crate::services::recording;
 use crate::services::polling::{poll_contacts, check_dm_invites, join_invite, store_in_artifact_index};
+use crate::services::command_palette::{CommandOutcome, CommandPalette, PaletteAction, PaletteItem, PaletteTarget};
+use crate::state::palette::PaletteState;
 
 use indras_sync_engine::{IntentionId, IntentionKind};
 use indras_artifacts::Intention;
@@ -229,10 +237,13 @@ pub fn RootApp() -> Element {
     let preview_file = use_signal(|| None::<PreviewFile>);
     let preview_view_mode = use_signal(|| PreviewViewMode::Rendered);
     let mut network_handle = use_signal(|| None::<NetworkHandle>);
+    let mut window_manager = use_signal(|| None::<Arc<WindowManager>>);
     let mut peering_started = use_signal(|| false);
     let mut setup_error = use_signal(|| None::<String>);
     let mut setup_loading = use_signal(|| false);
     let mut pass_story_open = use_signal(|| false);
+    let mut palette = use_signal(PaletteState::new);
+    let command_palette = use_signal(CommandPalette::with_defaults);
     let mut intention_create_open = use_signal(|| false);
     let mut contact_invite_open = use_signal(|| false);
     let mut contact_invite_input = use_signal(String::new);
@@ -250,6 +261,7 @@ pub fn RootApp() -> Element {
     let mut intention_cards = use_signal(Vec::<IntentionCardData>::new);
     let mut community_cards = use_signal(Vec::<IntentionCardData>::new);
     let mut token_cards = use_signal(Vec::<crate::services::realm_data::TokenCardData>::new);
+    let mut request_board = use_signal(crate::services::realm_data::RequestBoardData::default);
 
     // Artifact browser state
     let mut browser_artifacts = use_signal(Vec::<BrowsableArtifact>::new);
@@ -293,6 +305,7 @@ pub fn RootApp() -> Element {
                             home_realm_handle,
                             user_location,
                             network_handle,
+                            realm_handle,
                         );
                     }
                 }
@@ -300,10 +313,39 @@ pub fn RootApp() -> Element {
         });
     }
 
+    // Share the network with any windows opened from this one.
+    use_effect(move || {
+        if let Some(nh) = network_handle.read().clone() && window_manager.peek().is_none() {
+            window_manager.set(Some(WindowManager::new(nh)));
+        }
+    });
+
+    // Realm windows title themselves from realm aliases; tell them when
+    // the realm map is rebuilt.
+    use_effect(move || {
+        let _ = realm_map.read();
+        if let Some(manager) = window_manager.peek().as_ref() {
+            manager.notify(WindowEvent::RealmsChanged);
+        }
+    });
+
+    // Realm windows run their own copy of the skin signal; keep them in
+    // step with this window's skin and user themes.
+    use_effect(move || {
+        let skin = *indras_ui::CURRENT_SKIN.read();
+        let _ = indras_ui::USER_THEMES.read();
+        if let Some(manager) = window_manager.read().as_ref() {
+            manager.set_skin(skin);
+        }
+    });
+
     // Gracefully stop the network (cancels peering tasks, saves world view,
-    // stops transport).
+    // stops transport). Realm windows close first.
     let network_for_cleanup = network_handle;
     use_drop(move || {
+        if let Some(manager) = window_manager.peek().as_ref() {
+            manager.notify(WindowEvent::Closing);
+        }
         let nh = network_for_cleanup.read().clone();
         std::thread::spawn(move || {
             let rt = tokio::runtime::Runtime::new().unwrap();
@@ -358,6 +400,9 @@ pub fn RootApp() -> Element {
 
                     // Set realm handle for CRDT intention operations
                     if let Some(rh) = result.realm_handle {
+                        if let Some(options) = recording::recording_options() {
+                            spawn(recording::record_home_realm(rh.clone(), options));
+                        }
                         realm_handle.set(Some(rh));
                         log_event(&mut workspace.write(), EventDirection::System, "Intentions realm ready".to_string());
                     }
@@ -720,6 +765,33 @@ pub fn RootApp() -> Element {
         });
     });
 
+    // Background mode: serve the agent socket and re-show the window on request
+    use_effect(move || {
+        if !indras_network::background_mode() {
+            return;
+        }
+        spawn(async move {
+            loop {
+                tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+                if workspace.read().phase == AppPhase::Workspace {
+                    break;
+                }
+            }
+
+            let net = {
+                let guard = network_handle.read();
+                guard.as_ref().map(|nh| nh.network.clone())
+            };
+            let Some(net) = net else { return; };
+
+            background::run_agent_host(net, || {
+                let window = dioxus::desktop::window();
+                window.set_visible(true);
+                window.set_focus();
+            }).await;
+        });
+    });
+
     // Poll intention cards from home realm CRDT every 2 seconds
     use_effect(move || {
         spawn(async move {
@@ -737,6 +809,14 @@ pub fn RootApp() -> Element {
                     // Poll community intentions from DM realms
                     let comm = build_community_intention_cards(&rh.network, rh.member_id).await;
                     community_cards.set(comm);
+                    // Share artifacts for accepted offers, then refresh the request board
+                    if let Err(e) = rh.deliver_request_artifacts().await {
+                        tracing::warn!(error = %e, "Failed to deliver request artifacts");
+                    }
+                    let board = crate::services::realm_data::build_request_board(&rh.network, &rh.home, rh.member_id, &rh.player_name).await;
+                    if *request_board.peek() != board {
+                        request_board.set(board);
+                    }
                 }
             }
         });
@@ -1460,7 +1540,7 @@ pub fn RootApp() -> Element {
     };
 
     // Navigation hub: map NavDestination → ViewType (with async artifact loading for Artifacts)
-    let on_navigate = move |dest: NavDestination| {
+    let mut on_navigate = move |dest: NavDestination| {
         match dest {
             NavDestination::Home => {
                 workspace.write().ui.active_view = ViewType::Document;
@@ -1552,6 +1632,77 @@ pub fn RootApp() -> Element {
         }
     };
 
+    // Command palette: navigate to a chosen target
+    let mut open_palette_target = move |target: PaletteTarget| {
+        match target {
+            PaletteTarget::View(view) => workspace.write().ui.active_view = view,
+            PaletteTarget::Tab(tab) => {
+                on_navigate(NavDestination::Home);
+                workspace.write().ui.active_tab = tab;
+            }
+            PaletteTarget::Realm(realm_id) => {
+                if let Some(manager) = window_manager.read().as_ref() {
+                    manager.open_realm(realm_id);
+                }
+            }
+            PaletteTarget::Contact(_) => on_navigate(NavDestination::Contacts),
+            PaletteTarget::Artifact(_) => on_navigate(NavDestination::Artifacts),
+            PaletteTarget::Quest(id) => {
+                let Some(intention_id) = parse_hex_intention_id(&id) else {
+                    return;
+                };
+                workspace.write().ui.active_view = ViewType::IntentionDetail;
+                spawn(async move {
+                    let rh = realm_handle.read().clone();
+                    if let Some(rh) = rh
+                        && let Some(vd) = build_intention_view(&rh.home, intention_id, rh.member_id, &rh.player_name).await
+                    {
+                        workspace.write().editor.meta.steward_name = vd.steward_name.clone();
+                        workspace.write().editor.meta.audience_count = vd.audience_count;
+                        workspace.write().editor.title = vd.title.clone();
+                        quest_data.set(Some(vd));
+                    }
+                });
+            }
+        }
+    };
+
+    // Command palette: open targets directly, run commands on their provider
+    let on_palette_choose = move |item: PaletteItem| {
+        if let PaletteAction::Open(target) = &item.action {
+            open_palette_target(target.clone());
+            return;
+        }
+        let Some(network) = network_handle.read().as_ref().map(|nh| Arc::clone(&nh.network)) else {
+            return;
+        };
+        let registry = command_palette.read().clone();
+        spawn(async move {
+            match registry.dispatch(&item.action, network).await {
+                Ok(CommandOutcome::Open(target)) => open_palette_target(target),
+                Ok(CommandOutcome::Done(message)) => {
+                    log_event(&mut workspace.write(), EventDirection::System, message);
+                }
+                Err(e) => {
+                    log_event(&mut workspace.write(), EventDirection::System, format!("{}: {e}", item.title));
+                }
+            }
+        });
+    };
+
+    // Command palette: gather fresh items from the network and show them
+    let open_palette = move || {
+        let Some(network) = network_handle.read().as_ref().map(|nh| Arc::clone(&nh.network)) else {
+            return;
+        };
+        let registry = command_palette.read().clone();
+        let recent = workspace.read().nav.recent_artifact_ids.clone();
+        spawn(async move {
+            let items = registry.gather(&network, &recent).await;
+            palette.write().open_with(items);
+        });
+    };
+
     // Create hub: map CreateAction → SlashAction and delegate to on_slash_select
     let on_create = move |action: CreateAction| {
         let slash = match action {
@@ -1717,10 +1868,32 @@ pub fn RootApp() -> Element {
         app_class.push_str(" sidebar-closed");
     }
 
-    // Keyboard handler for / and Escape
+    // Files dropped on the window upload to the home realm in parallel
+    let on_drop = move |evt: DragEvent| {
+        evt.prevent_default();
+        let paths = dropped_paths(&evt);
+        if paths.is_empty() {
+            return;
+        }
+        if let Some(manager) = window_manager.read().as_ref() {
+            let count = paths.len();
+            manager.uploads().start(paths, None);
+            log_event(&mut workspace.write(), EventDirection::System, format!("Uploading {count} file(s)"));
+        }
+    };
+
+    // Keyboard handler for /, Ctrl/Cmd+K and Escape
     let on_keydown = move |evt: KeyboardEvent| {
         let key = evt.key();
+        let mods = evt.modifiers();
+        let ctrl_or_meta = mods.contains(Modifiers::CONTROL) || mods.contains(Modifiers::META);
         match key {
+            Key::Character(ref c) if ctrl_or_meta && c.eq_ignore_ascii_case("k") => {
+                evt.prevent_default();
+                if !palette.read().open {
+                    open_palette();
+                }
+            }
             Key::Character(ref c) if c == "/" => {
                 // Only open if not already typing in an input/textarea
                 let slash_open = workspace.read().ui.slash_menu_open;
@@ -1825,6 +1998,8 @@ pub fn RootApp() -> Element {
                 class: "{app_class}",
                 tabindex: "0",
                 onkeydown: on_keydown,
+                ondragover: move |evt: DragEvent| evt.prevent_default(),
+                ondrop: on_drop,
 
                 if active_view != ViewType::Chat {
                     // Sidebar backdrop (mobile overlay)
@@ -1936,6 +2111,11 @@ pub fn RootApp() -> Element {
                                     rsx! {
                                         indras_chat::components::app::ChatLayout {
                                             runtime: indras_chat::components::app::NetworkArc(network),
+                                            on_pop_out: move |realm_id| {
+                                                if let Some(manager) = window_manager.read().as_ref() {
+                                                    manager.open_realm(realm_id);
+                                                }
+                                            },
                                         }
                                     }
                                 } else {
@@ -1969,6 +2149,12 @@ pub fn RootApp() -> Element {
                                         on_create_intention: move |_| {
                                             intention_create_open.set(true);
                                         },
+                                        requests_element: rsx! {
+                                            RequestsTab {
+                                                board: request_board.read().clone(),
+                                                realm_handle: realm_handle,
+                                            }
+                                        },
                                         chat_element: chat_el,
                                     }
                                 }
@@ -1990,6 +2176,11 @@ pub fn RootApp() -> Element {
                                 rsx! {
                                     indras_chat::components::app::ChatLayout {
                                         runtime: indras_chat::components::app::NetworkArc(Arc::clone(&network)),
+                                        on_pop_out: move |realm_id| {
+                                            if let Some(manager) = window_manager.read().as_ref() {
+                                                manager.open_realm(realm_id);
+                                            }
+                                        },
                                     }
                                 }
                             } else {
@@ -2178,6 +2369,20 @@ pub fn RootApp() -> Element {
                     on_close: on_slash_close,
                 }
 
+                // Upload progress for dropped files
+                if let Some(manager) = window_manager.read().as_ref() {
+                    UploadTray {
+                        manager: WindowManagerArc(Arc::clone(manager)),
+                        realm_id: None,
+                    }
+                }
+
+                // Command palette overlay
+                CommandPaletteOverlay {
+                    palette: palette,
+                    on_choose: on_palette_choose,
+                }
+
                 // Preview overlay
                 MarkdownPreviewOverlay {
                     is_open: preview_open,
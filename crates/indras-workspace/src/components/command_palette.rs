@@ -0,0 +1,105 @@
+//! Command palette overlay (Ctrl/Cmd+K).
+
+use dioxus::prelude::*;
+use indras_ui::FocusTrap;
+
+use crate::services::command_palette::{PaletteItem, PaletteMatch};
+use crate::state::palette::{PaletteKey, PaletteState};
+
+#[component]
+pub fn CommandPaletteOverlay(
+    palette: Signal<PaletteState>,
+    on_choose: EventHandler<PaletteItem>,
+) -> Element {
+    let state = palette.read();
+    if !state.open {
+        return rsx! {};
+    }
+    let query = state.query.clone();
+    let selected = state.selected;
+    let results: Vec<PaletteMatch> = state.results.clone();
+    drop(state);
+    let active_id = format!("palette-item-{selected}");
+
+    rsx! {
+        div {
+            class: "command-palette-backdrop",
+            onclick: move |_| palette.write().close(),
+            FocusTrap {
+                class: "command-palette",
+                on_escape: move |_| palette.write().close(),
+                div { class: "command-palette-inner",
+                    // Clicks inside must not reach the backdrop.
+                    onclick: move |evt| evt.stop_propagation(),
+                    input {
+                        class: "command-palette-input",
+                        r#type: "text",
+                        placeholder: "Search realms, contacts, quests, artifacts, commands…",
+                        autofocus: true,
+                        role: "combobox",
+                        "aria-controls": "command-palette-list",
+                        "aria-activedescendant": "{active_id}",
+                        value: "{query}",
+                        oninput: move |evt| palette.write().set_query(evt.value()),
+                        onkeydown: move |evt: KeyboardEvent| {
+                            // Keep typed keys away from the app-wide shortcuts.
+                            evt.stop_propagation();
+                            let outcome = palette.write().handle_key(&evt.key());
+                            match outcome {
+                                PaletteKey::Ignored => {}
+                                PaletteKey::Handled => evt.prevent_default(),
+                                PaletteKey::Choose(item) => {
+                                    evt.prevent_default();
+                                    palette.write().close();
+                                    on_choose.call(item);
+                                }
+                                PaletteKey::Close => {
+                                    evt.prevent_default();
+                                    palette.write().close();
+                                }
+                            }
+                        },
+                    }
+                    div { id: "command-palette-list", class: "command-palette-list", role: "listbox",
+                        if results.is_empty() {
+                            div { class: "command-palette-empty", "No matches" }
+                        }
+                        for (i, m) in results.into_iter().enumerate() {
+                            {
+                                let item = m.item.clone();
+                                let class = if i == selected { "command-palette-item selected" } else { "command-palette-item" };
+                                rsx! {
+                                    div {
+                                        id: "palette-item-{i}",
+                                        class: "{class}",
+                                        role: "option",
+                                        "aria-selected": if i == selected { "true" } else { "false" },
+                                        onmouseenter: move |_| palette.write().selected = i,
+                                        onclick: move |_| {
+                                            palette.write().close();
+                                            on_choose.call(item.clone());
+                                        },
+                                        span { class: "command-palette-icon", "aria-hidden": "true", "{m.item.icon}" }
+                                        span { class: "command-palette-title",
+                                            for (pos, ch) in m.item.title.chars().enumerate() {
+                                                if m.highlights.contains(&pos) {
+                                                    mark { "{ch}" }
+                                                } else {
+                                                    "{ch}"
+                                                }
+                                            }
+                                        }
+                                        if let Some(subtitle) = &m.item.subtitle {
+                                            span { class: "command-palette-subtitle", "{subtitle}" }
+                                        }
+                                        span { class: "command-palette-category", "{m.item.category.label()}" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -28,6 +28,7 @@ fn DashboardTabs(
     let tabs = [
         DashboardTab::MyIntentions,
         DashboardTab::Community,
+        DashboardTab::Requests,
         DashboardTab::Tokens,
         DashboardTab::Chat,
     ];
@@ -369,6 +370,7 @@ pub fn IntentionBoard(
     tokens: Vec<TokenCardData>,
     on_intention_click: EventHandler<String>,
     on_create_intention: EventHandler<()>,
+    requests_element: Element,
     chat_element: Element,
 ) -> Element {
     rsx! {
@@ -394,6 +396,9 @@ pub fn IntentionBoard(
                             on_click: on_intention_click,
                         }
                     },
+                    DashboardTab::Requests => rsx! {
+                        {requests_element}
+                    },
                     DashboardTab::Tokens => rsx! {
                         TokensTab { tokens: tokens }
                     },
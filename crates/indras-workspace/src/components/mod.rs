@@ -9,3 +9,7 @@ pub mod pass_story;
 pub mod event_log;
 pub mod artifact_browser;
 pub mod intention_board;
+pub mod request_board;
+pub mod realm_window;
+pub mod command_palette;
+pub mod upload_tray;
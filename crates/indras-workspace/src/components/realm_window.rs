@@ -0,0 +1,104 @@
+//! Secondary window pinned to one realm's conversation.
+
+use std::sync::Arc;
+
+use dioxus::prelude::*;
+use indras_network::{IndrasNetwork, RealmId};
+use indras_ui::{install_user_themes, themes_dir, Skin, ThemedRoot, CURRENT_SKIN};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::bridge::network_bridge::default_data_dir;
+use crate::bridge::window_manager::{WindowEvent, WindowManagerArc, WindowRoute, WindowToken};
+use crate::components::upload_tray::{UploadTray, dropped_paths};
+
+/// Load user themes into this window and show `skin`.
+fn apply_skin(skin: Skin) {
+    install_user_themes(&themes_dir(&default_data_dir()));
+    *CURRENT_SKIN.write() = skin;
+}
+
+/// Root component of a realm window opened by the window manager.
+#[component]
+pub fn RealmWindow(manager: WindowManagerArc, realm_id: RealmId, token: WindowToken) -> Element {
+    let manager = manager.0;
+
+    // Start with the skin the main window shows.
+    let mgr = Arc::clone(&manager);
+    use_hook(move || apply_skin(mgr.skin()));
+
+    // Leave the registry when the window closes.
+    let mgr = Arc::clone(&manager);
+    use_drop(move || mgr.unregister(token));
+
+    // Follow events announced by the other windows.
+    let mgr = Arc::clone(&manager);
+    use_effect(move || {
+        let mgr = Arc::clone(&mgr);
+        spawn(async move {
+            let network = Arc::clone(&mgr.network().network);
+            refresh_title(&network, realm_id).await;
+
+            let mut rx = mgr.subscribe();
+            loop {
+                match rx.recv().await {
+                    Ok(WindowEvent::Focus(WindowRoute::Realm(id))) if id == realm_id => {
+                        let window = dioxus::desktop::window();
+                        window.set_visible(true);
+                        window.set_focus();
+                    }
+                    Ok(WindowEvent::RealmsChanged) => refresh_title(&network, realm_id).await,
+                    Ok(WindowEvent::SkinChanged(skin)) => apply_skin(skin),
+                    Ok(WindowEvent::Closing) => {
+                        dioxus::desktop::window().close();
+                        break;
+                    }
+                    Ok(_) | Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
+    });
+
+    // Files dropped here are uploaded in parallel and announced to the realm.
+    let mgr = Arc::clone(&manager);
+    let on_drop = move |evt: DragEvent| {
+        evt.prevent_default();
+        let paths = dropped_paths(&evt);
+        if paths.is_empty() {
+            return;
+        }
+        let realm = mgr.network().network.get_realm_by_id(&realm_id);
+        mgr.uploads().start(paths, realm);
+    };
+
+    let network = Arc::clone(&manager.network().network);
+    rsx! {
+        ThemedRoot {
+            div {
+                class: "realm-window-drop",
+                ondragover: move |evt: DragEvent| evt.prevent_default(),
+                ondrop: on_drop,
+                indras_chat::components::app::ChatLayout {
+                    runtime: indras_chat::components::app::NetworkArc(network),
+                    realm: Some(realm_id),
+                }
+                UploadTray {
+                    manager: WindowManagerArc(Arc::clone(&manager)),
+                    realm_id: Some(realm_id),
+                }
+            }
+        }
+    }
+}
+
+/// Title the window after the realm's alias, falling back to its name.
+async fn refresh_title(network: &IndrasNetwork, realm_id: RealmId) {
+    let Some(realm) = network.get_realm_by_id(&realm_id) else {
+        return;
+    };
+    let name = match realm.get_alias().await {
+        Ok(Some(alias)) => alias,
+        _ => realm.name().unwrap_or("Realm").to_string(),
+    };
+    dioxus::desktop::window().set_title(&format!("Indras — {name}"));
+}
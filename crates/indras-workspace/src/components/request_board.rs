@@ -0,0 +1,325 @@
+//! Requests tab — ask a realm for something, offer on others' requests,
+//! and track each request from open through fulfillment.
+
+use dioxus::prelude::*;
+use indras_network::RealmId;
+use indras_sync_engine::{OfferContent, RequestStatus};
+
+use crate::bridge::realm_bridge::RealmHandle;
+use crate::services::realm_data::{RequestBoardData, RequestCardData};
+
+/// CSS class for a request status pill.
+fn status_class(status: RequestStatus) -> &'static str {
+    match status {
+        RequestStatus::Open => "request-status-open",
+        RequestStatus::Offered => "request-status-offered",
+        RequestStatus::Accepted => "request-status-accepted",
+        RequestStatus::Fulfilled => "request-status-fulfilled",
+        RequestStatus::Cancelled => "request-status-cancelled",
+    }
+}
+
+/// Run a request action against the realm handle, logging failures.
+fn run_action<F, Fut>(realm_handle: Signal<Option<RealmHandle>>, action: F)
+where
+    F: FnOnce(RealmHandle) -> Fut + 'static,
+    Fut: std::future::Future<Output = indras_network::error::Result<()>> + 'static,
+{
+    spawn(async move {
+        let rh = realm_handle.read().clone();
+        if let Some(rh) = rh {
+            if let Err(e) = action(rh).await {
+                tracing::warn!(error = %e, "Request action failed");
+            }
+        }
+    });
+}
+
+/// Form for broadcasting a new request to a realm.
+#[component]
+fn RequestComposer(
+    realms: Vec<(RealmId, String)>,
+    realm_handle: Signal<Option<RealmHandle>>,
+) -> Element {
+    let mut description = use_signal(String::new);
+    let mut selected = use_signal(|| 0usize);
+    let target = realms.get(*selected.read()).map(|(id, _)| *id);
+    let can_send = target.is_some() && !description.read().trim().is_empty();
+
+    rsx! {
+        div {
+            class: "request-composer",
+            textarea {
+                class: "request-composer-input",
+                placeholder: "What do you need? A file, a tool, a hand with something…",
+                value: "{description}",
+                oninput: move |e| description.set(e.value()),
+            }
+            div {
+                class: "request-composer-row",
+                if realms.is_empty() {
+                    span { class: "intentions-empty-hint", "Join a realm to broadcast requests" }
+                } else {
+                    select {
+                        class: "request-select",
+                        onchange: move |e| {
+                            if let Ok(idx) = e.value().parse() {
+                                selected.set(idx);
+                            }
+                        },
+                        for (idx, (_, name)) in realms.iter().enumerate() {
+                            option { value: "{idx}", selected: idx == *selected.read(), "{name}" }
+                        }
+                    }
+                }
+                button {
+                    class: "request-btn primary",
+                    disabled: !can_send,
+                    onclick: move |_| {
+                        let Some(realm_id) = target else { return };
+                        let text = description.read().trim().to_string();
+                        description.set(String::new());
+                        run_action(realm_handle, move |rh| async move {
+                            rh.broadcast_request(realm_id, &text).await.map(|_| ())
+                        });
+                    },
+                    "Broadcast Request"
+                }
+            }
+        }
+    }
+}
+
+/// Form for offering an artifact or a pledge on someone's request.
+#[component]
+fn OfferForm(
+    card: RequestCardData,
+    artifacts: Vec<(indras_network::ArtifactId, String)>,
+    realm_handle: Signal<Option<RealmHandle>>,
+) -> Element {
+    let mut pledge = use_signal(String::new);
+    let mut artifact_idx = use_signal(|| None::<usize>);
+    let realm_id = card.realm_id;
+    let request_id = card.request_id;
+
+    let content = match *artifact_idx.read() {
+        Some(idx) => artifacts.get(idx).map(|(id, name)| OfferContent::Artifact {
+            artifact_id: *id,
+            name: name.clone(),
+        }),
+        None => {
+            let text = pledge.read().trim().to_string();
+            (!text.is_empty()).then_some(OfferContent::Pledge { description: text })
+        }
+    };
+    let can_offer = content.is_some();
+
+    rsx! {
+        div {
+            class: "request-offer-form",
+            if !artifacts.is_empty() {
+                select {
+                    class: "request-select",
+                    onchange: move |e| artifact_idx.set(e.value().parse().ok()),
+                    option { value: "", "Pledge to help…" }
+                    for (idx, (_, name)) in artifacts.iter().enumerate() {
+                        option { value: "{idx}", "Offer {name}" }
+                    }
+                }
+            }
+            if artifact_idx.read().is_none() {
+                input {
+                    class: "request-offer-input",
+                    placeholder: "What will you do?",
+                    value: "{pledge}",
+                    oninput: move |e| pledge.set(e.value()),
+                }
+            }
+            button {
+                class: "request-btn",
+                disabled: !can_offer,
+                onclick: move |_| {
+                    let Some(content) = content.clone() else { return };
+                    pledge.set(String::new());
+                    run_action(realm_handle, move |rh| async move {
+                        rh.offer_to_request(realm_id, request_id, content).await.map(|_| ())
+                    });
+                },
+                "Offer"
+            }
+        }
+    }
+}
+
+/// A single request with its offers and the actions open to the viewer.
+#[component]
+fn RequestCard(
+    card: RequestCardData,
+    artifacts: Vec<(indras_network::ArtifactId, String)>,
+    realm_handle: Signal<Option<RealmHandle>>,
+) -> Element {
+    let realm_id = card.realm_id;
+    let request_id = card.request_id;
+    let status = card.status;
+    let can_accept = card.is_mine && status.is_open();
+    let can_offer = !card.is_mine && status.is_open() && !card.offered_by_me;
+    let accepted_pledge = status == RequestStatus::Accepted
+        && card.offers.iter().any(|o| o.is_accepted && o.is_pledge);
+    let awaiting_pledge = card.is_mine && accepted_pledge;
+
+    rsx! {
+        div {
+            class: "request-card",
+            div {
+                class: "request-card-header",
+                span { class: "request-card-title", "{card.description}" }
+                span { class: "request-status-pill {status_class(status)}", "{status.label()}" }
+            }
+            div {
+                class: "intention-card-meta",
+                span { "{card.requester_name}" }
+                span { "{card.realm_name}" }
+                span { "{card.posted_ago}" }
+            }
+
+            if !card.offers.is_empty() {
+                div {
+                    class: "request-offers",
+                    for offer in card.offers.iter().cloned() {
+                        div {
+                            class: if offer.is_accepted { "request-offer accepted" } else { "request-offer" },
+                            span {
+                                class: "request-offer-kind",
+                                if offer.is_pledge { "Pledge" } else { "Artifact" }
+                            }
+                            span { class: "request-offer-summary", "{offer.summary}" }
+                            span { class: "request-offer-from", "from {offer.offerer_name}" }
+                            if can_accept {
+                                button {
+                                    class: "request-btn primary",
+                                    onclick: move |_| {
+                                        run_action(realm_handle, move |rh| async move {
+                                            rh.accept_request_offer(realm_id, request_id, offer.offer_id).await
+                                        });
+                                    },
+                                    "Accept"
+                                }
+                            } else if offer.is_mine && status.is_open() {
+                                button {
+                                    class: "request-btn",
+                                    onclick: move |_| {
+                                        run_action(realm_handle, move |rh| async move {
+                                            rh.withdraw_request_offer(realm_id, request_id, offer.offer_id).await
+                                        });
+                                    },
+                                    "Withdraw"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            if accepted_pledge {
+                div { class: "intentions-empty-hint", "Pledge accepted \u{2014} tracked as a quest" }
+            } else if status == RequestStatus::Accepted {
+                div { class: "intentions-empty-hint", "Waiting for the artifact to be shared" }
+            }
+
+            div {
+                class: "request-card-actions",
+                if can_offer {
+                    OfferForm { card: card.clone(), artifacts, realm_handle }
+                }
+                if awaiting_pledge {
+                    button {
+                        class: "request-btn primary",
+                        onclick: move |_| {
+                            run_action(realm_handle, move |rh| async move {
+                                rh.mark_request_fulfilled(realm_id, request_id).await
+                            });
+                        },
+                        "Mark Fulfilled"
+                    }
+                }
+                if card.is_mine && status.is_open() {
+                    button {
+                        class: "request-btn",
+                        onclick: move |_| {
+                            run_action(realm_handle, move |rh| async move {
+                                rh.cancel_request(realm_id, request_id).await
+                            });
+                        },
+                        "Cancel"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Requests tab — composer plus every request on the loaded realms' boards.
+#[component]
+pub fn RequestsTab(board: RequestBoardData, realm_handle: Signal<Option<RealmHandle>>) -> Element {
+    let (open, closed): (Vec<_>, Vec<_>) = board
+        .requests
+        .iter()
+        .cloned()
+        .partition(|r| r.status.is_open() || r.status == RequestStatus::Accepted);
+    let closed_count = closed.len();
+    let mut show_closed = use_signal(|| false);
+
+    rsx! {
+        div {
+            class: "requests-tab",
+            RequestComposer { realms: board.realms.clone(), realm_handle }
+
+            if open.is_empty() {
+                div {
+                    class: "intentions-empty",
+                    div { class: "intentions-empty-icon", "\u{1F4CB}" }
+                    div { class: "intentions-empty-text", "No open requests" }
+                    div { class: "intentions-empty-hint", "Broadcast a request and your realms can offer to help" }
+                }
+            } else {
+                div {
+                    class: "intentions-list",
+                    for card in open {
+                        RequestCard {
+                            card,
+                            artifacts: board.artifacts.clone(),
+                            realm_handle,
+                        }
+                    }
+                }
+            }
+
+            if closed_count > 0 {
+                div {
+                    class: "intentions-section",
+                    div {
+                        class: "intentions-section-title clickable",
+                        onclick: move |_| {
+                            let cur = *show_closed.read();
+                            show_closed.set(!cur);
+                        },
+                        if *show_closed.read() { "\u{25BC}" } else { "\u{25B6}" }
+                        " Closed ({closed_count})"
+                    }
+                    if *show_closed.read() {
+                        div {
+                            class: "intentions-list",
+                            for card in closed {
+                                RequestCard {
+                                            card,
+                                    artifacts: board.artifacts.clone(),
+                                    realm_handle,
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,8 +1,8 @@
 //! Settings view with identity display, connect, PassStory trigger, and theme switcher.
 
 use dioxus::prelude::*;
-use indras_ui::SkinSwitcher;
-use crate::bridge::network_bridge::NetworkHandle;
+use indras_ui::{install_user_themes, themes_dir, tr, LocaleSwitcher, SkinSwitcher};
+use crate::bridge::network_bridge::{default_data_dir, NetworkHandle};
 use indras_network::{EncounterHandle, GeoLocation};
 
 #[component]
@@ -327,8 +327,15 @@ pub fn SettingsView(
                     // Appearance section
                     div {
                         class: "settings-section",
-                        div { class: "settings-section-title", "Appearance" }
+                        div { class: "settings-section-title", {tr("settings-appearance")} }
                         SkinSwitcher {}
+                        LocaleSwitcher {}
+                        button {
+                            class: "settings-action-btn",
+                            title: tr("settings-reload-themes-hint"),
+                            onclick: move |_| install_user_themes(&themes_dir(&default_data_dir())),
+                            {tr("settings-reload-themes")}
+                        }
                     }
                 }
             }
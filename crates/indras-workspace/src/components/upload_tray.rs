@@ -0,0 +1,139 @@
+//! Upload tray — progress, cancel and retry for dropped files.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use dioxus::prelude::*;
+use indras_network::RealmId;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::bridge::upload_pipeline::{BatchProgress, FileStage, UploadEvent};
+use crate::bridge::window_manager::WindowManagerArc;
+
+/// Paths of the files dropped from the OS file manager.
+pub fn dropped_paths(evt: &DragEvent) -> Vec<PathBuf> {
+    evt.files().into_iter().map(|file| file.path()).collect()
+}
+
+fn stage_label(stage: &FileStage) -> String {
+    match stage {
+        FileStage::Queued => "Queued".to_string(),
+        FileStage::Reading => "Reading…".to_string(),
+        FileStage::Storing => "Storing…".to_string(),
+        FileStage::Done(_) => "Done".to_string(),
+        FileStage::Failed(e) => format!("Failed: {e}"),
+        FileStage::Cancelled => "Cancelled".to_string(),
+    }
+}
+
+/// Floating list of upload batches. With `realm_id`, only that realm's.
+#[component]
+pub fn UploadTray(manager: WindowManagerArc, realm_id: Option<RealmId>) -> Element {
+    let uploads = Arc::clone(manager.0.uploads());
+    let mut batches = use_signal(Vec::<BatchProgress>::new);
+
+    let pipeline = Arc::clone(&uploads);
+    use_effect(move || {
+        let pipeline = Arc::clone(&pipeline);
+        spawn(async move {
+            let mut rx = pipeline.subscribe();
+            batches.set(pipeline.batches());
+            loop {
+                match rx.recv().await {
+                    Ok(UploadEvent::Progress(_)) | Err(RecvError::Lagged(_)) => {
+                        batches.set(pipeline.batches());
+                    }
+                    Ok(UploadEvent::Announced { .. }) => {}
+                    Ok(UploadEvent::AnnounceFailed { error, .. }) => {
+                        tracing::warn!(%error, "Upload batch was not announced");
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
+    });
+
+    let shown: Vec<BatchProgress> = batches
+        .read()
+        .iter()
+        .filter(|b| realm_id.is_none() || b.realm_id == realm_id)
+        .cloned()
+        .collect();
+    if shown.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div { class: "upload-tray", role: "status", "aria-live": "polite",
+            for batch in shown {
+                {
+                    let batch_id = batch.batch_id;
+                    let percent = (batch.fraction() * 100.0).round() as u32;
+                    let done = batch.count(|s| matches!(s, FileStage::Done(_)));
+                    let settled = batch.is_settled();
+                    let cancel_all = Arc::clone(&uploads);
+                    let clear = Arc::clone(&uploads);
+                    rsx! {
+                        div { class: "upload-batch",
+                            div { class: "upload-batch-header",
+                                span { class: "upload-batch-title",
+                                    "{done}/{batch.files.len()} files · {percent}%"
+                                }
+                                if settled {
+                                    button {
+                                        class: "upload-action",
+                                        onclick: move |_| {
+                                            clear.clear_settled();
+                                            batches.set(clear.batches());
+                                        },
+                                        "Dismiss"
+                                    }
+                                } else {
+                                    button {
+                                        class: "upload-action",
+                                        onclick: move |_| cancel_all.cancel_batch(batch_id),
+                                        "Cancel all"
+                                    }
+                                }
+                            }
+                            div { class: "upload-bar",
+                                div { class: "upload-bar-fill", style: "width: {percent}%" }
+                            }
+                            for (index, file) in batch.files.into_iter().enumerate() {
+                                {
+                                    let cancel = Arc::clone(&uploads);
+                                    let retry = Arc::clone(&uploads);
+                                    let failed = matches!(file.stage, FileStage::Failed(_));
+                                    let row_class = if failed { "upload-file failed" } else { "upload-file" };
+                                    rsx! {
+                                        div { class: "{row_class}",
+                                            span { class: "upload-file-name", "{file.name}" }
+                                            span { class: "upload-file-stage", "{stage_label(&file.stage)}" }
+                                            if file.stage.is_retryable() {
+                                                button {
+                                                    class: "upload-action",
+                                                    onclick: move |_| {
+                                                        retry.retry(batch_id, index);
+                                                    },
+                                                    "Retry"
+                                                }
+                                            } else if !file.stage.is_settled() {
+                                                button {
+                                                    class: "upload-action",
+                                                    onclick: move |_| {
+                                                        cancel.cancel(batch_id, index);
+                                                    },
+                                                    "Cancel"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
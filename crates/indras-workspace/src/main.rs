@@ -4,19 +4,17 @@ use std::path::PathBuf;
 #[cfg(feature = "lua-scripting")]
 use std::sync::Arc;
 
-use dioxus::desktop::{Config, LogicalPosition, LogicalSize, WindowBuilder};
+use dioxus::desktop::{Config, LogicalPosition, LogicalSize, WindowBuilder, WindowCloseBehaviour};
 
+use indras_workspace::bridge::window_manager::custom_head;
 use indras_workspace::components::app::RootApp;
-use indras_ui::ThemedRoot;
+use indras_ui::{install_user_themes, themes_dir, ThemedRoot};
 
 #[cfg(feature = "lua-scripting")]
 use indras_workspace::scripting::channels::{create_test_channels, AppTestChannels};
 #[cfg(feature = "lua-scripting")]
 use indras_workspace::scripting::lua_runtime::LuaTestRuntime;
 
-/// Workspace-specific CSS embedded at compile time.
-const WORKSPACE_CSS: &str = include_str!("../assets/workspace.css");
-
 /// Get the default data directory (mirrors network_bridge.rs logic).
 fn default_data_dir() -> PathBuf {
     if let Ok(dir) = std::env::var("INDRAS_DATA_DIR") {
@@ -72,6 +70,13 @@ fn main() {
         }
     }
 
+    // A background instance already owns the node: bring it forward instead
+    let background = indras_network::background_mode();
+    if background && indras_network::show_running_instance(&default_data_dir()) {
+        tracing::info!("Showing running background instance");
+        return;
+    }
+
     let window_title = match &name {
         Some(n) => format!("Indras Workspace - {}", n),
         None => "Indras Workspace".to_string(),
@@ -194,24 +199,15 @@ fn main() {
         *TEST_CHANNELS.lock().unwrap() = Some(channels);
     }
 
+    let mut cfg = Config::new()
+        .with_window(wb)
+        .with_custom_head(custom_head());
+    if background {
+        cfg = cfg.with_close_behaviour(WindowCloseBehaviour::WindowHides);
+    }
+
     dioxus::LaunchBuilder::desktop()
-        .with_cfg(
-            Config::new()
-                .with_window(wb)
-                .with_custom_head(format!(
-                    r#"
-                    <link rel="preconnect" href="https://fonts.googleapis.com">
-                    <link rel="preconnect" href="https://fonts.gstatic.com" crossorigin>
-                    <link href="https://fonts.googleapis.com/css2?family=Cinzel:wght@400;500;600;700&family=Cormorant+Garamond:ital,wght@0,300;0,400;0,500;0,600;0,700;1,300;1,400;1,500&family=DM+Sans:ital,opsz,wght@0,9..40,300;0,9..40,400;0,9..40,500;0,9..40,600;0,9..40,700;1,9..40,400&family=Fraunces:ital,opsz,wght@0,9..144,300;0,9..144,400;0,9..144,500;0,9..144,600;0,9..144,700;0,9..144,900;1,9..144,400&family=Inter:wght@300;400;500;600;700&family=Instrument+Sans:wght@400;500;600&family=JetBrains+Mono:wght@300;400;500&family=Outfit:wght@300;400;500;600&family=Playfair+Display:wght@700;900&family=Plus+Jakarta+Sans:wght@300;400;500;600;700&family=Raleway:ital,wght@0,200;0,300;0,400;0,500;0,600;1,300;1,400&display=swap" rel="stylesheet">
-                    <style>{}</style>
-                    <style>{}</style>
-                    <style>{}</style>
-                    "#,
-                    indras_ui::SHARED_CSS,
-                    WORKSPACE_CSS,
-                    indras_chat::CHAT_CSS,
-                )),
-        )
+        .with_cfg(cfg)
         .launch(App);
 }
 
@@ -227,6 +223,8 @@ fn App() -> Element {
         use_context_provider(|| TEST_CHANNELS.lock().unwrap().take());
     }
 
+    use_hook(|| install_user_themes(&themes_dir(&default_data_dir())));
+
     rsx! {
         ThemedRoot {
             RootApp {}
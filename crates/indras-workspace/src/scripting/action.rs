@@ -56,6 +56,27 @@ pub enum Action {
     /// Connect to a peer using their identity URI.
     ConnectToPeer { uri: String },
 
+    // Quests (home realm, addressed by title)
+    /// Create a quest. `kind` is `intention`, `need`, or `offering`.
+    CreateQuest { title: String, description: String, kind: String },
+    /// Submit a service claim on a quest as the local member.
+    ClaimQuest { quest_title: String },
+    /// Verify a member's pending claim (as the quest's creator).
+    VerifyClaim { quest_title: String, claimant: String },
+    /// Mark a quest as complete.
+    CompleteQuest { quest_title: String },
+
+    // Attention
+    /// Switch the local member's attention to a quest.
+    FocusAttention { quest_title: String },
+    /// Clear the local member's attention.
+    ClearAttention,
+
+    // Blessings
+    /// Bless a member's claim with all of the local member's unblessed
+    /// attention on the quest, minting them a token of gratitude.
+    BlessClaim { quest_title: String, claimant: String },
+
     // Intention game loop
     SubmitProof { intention_label: String, body: String },
     ReleaseAttention { intention_label: String },
@@ -99,6 +120,47 @@ impl Action {
                     .parse::<f64>().map_err(|e| e.to_string())?;
                 Ok(Action::Wait(secs))
             }
+            "create_quest" => {
+                let arg = arg.ok_or("create_quest requires 'title|description|kind'")?;
+                let mut parts = arg.splitn(3, '|');
+                Ok(Action::CreateQuest {
+                    title: parts.next().unwrap_or_default().to_string(),
+                    description: parts.next().unwrap_or_default().to_string(),
+                    kind: parts.next().unwrap_or_default().to_string(),
+                })
+            }
+            "claim_quest" => Ok(Action::ClaimQuest {
+                quest_title: arg.ok_or("claim_quest requires a quest title")?,
+            }),
+            "verify_claim" => {
+                let arg = arg.ok_or("verify_claim requires 'quest_title|claimant'")?;
+                let parts: Vec<&str> = arg.splitn(2, '|').collect();
+                if parts.len() != 2 {
+                    return Err("verify_claim requires 'quest_title|claimant' format".into());
+                }
+                Ok(Action::VerifyClaim {
+                    quest_title: parts[0].to_string(),
+                    claimant: parts[1].to_string(),
+                })
+            }
+            "complete_quest" => Ok(Action::CompleteQuest {
+                quest_title: arg.ok_or("complete_quest requires a quest title")?,
+            }),
+            "focus_attention" => Ok(Action::FocusAttention {
+                quest_title: arg.ok_or("focus_attention requires a quest title")?,
+            }),
+            "clear_attention" => Ok(Action::ClearAttention),
+            "bless_claim" => {
+                let arg = arg.ok_or("bless_claim requires 'quest_title|claimant'")?;
+                let parts: Vec<&str> = arg.splitn(2, '|').collect();
+                if parts.len() != 2 {
+                    return Err("bless_claim requires 'quest_title|claimant' format".into());
+                }
+                Ok(Action::BlessClaim {
+                    quest_title: parts[0].to_string(),
+                    claimant: parts[1].to_string(),
+                })
+            }
             "submit_proof" => {
                 let arg = arg.ok_or("submit_proof requires 'intention_label|body'")?;
                 let parts: Vec<&str> = arg.splitn(2, '|').collect();
@@ -12,6 +12,8 @@ use tokio::sync::{broadcast, mpsc, oneshot};
 use super::action::Action;
 use super::event::AppEvent;
 use super::query::{Query, QueryResult};
+use super::quests;
+use crate::bridge::realm_bridge::RealmHandle;
 use crate::state::workspace::{WorkspaceState, ViewType, AppPhase, EventDirection, log_event};
 use crate::bridge::network_bridge::NetworkHandle;
 use indras_network::{
@@ -38,6 +40,7 @@ pub fn spawn_dispatcher(
     home_realm_handle: Signal<Option<HomeRealm>>,
     mut user_location: Signal<Option<GeoLocation>>,
     network_handle: Signal<Option<NetworkHandle>>,
+    realm_handle: Signal<Option<RealmHandle>>,
 ) {
     // Action dispatcher — owns action_rx directly
     let action_event_tx = event_tx.clone();
@@ -329,6 +332,34 @@ pub fn spawn_dispatcher(
                     }
                 }
 
+                action @ (Action::CreateQuest { .. }
+                | Action::ClaimQuest { .. }
+                | Action::VerifyClaim { .. }
+                | Action::CompleteQuest { .. }
+                | Action::FocusAttention { .. }
+                | Action::ClearAttention
+                | Action::BlessClaim { .. }) => {
+                    let name = quests::action_name(&action);
+                    let rh = realm_handle.read().clone();
+                    let peers = workspace.read().peers.entries.clone();
+                    let result = match rh {
+                        Some(rh) => quests::run_action(&rh, &peers, action).await,
+                        None => Err("Home realm not initialized".to_string()),
+                    };
+                    match result {
+                        Ok(event) => {
+                            let _ = action_event_tx.send(event);
+                        }
+                        Err(error) => {
+                            tracing::warn!("Lua: {} failed: {}", name, error);
+                            let _ = action_event_tx.send(AppEvent::ActionFailed {
+                                action: name.into(),
+                                error,
+                            });
+                        }
+                    }
+                }
+
                 Action::SubmitProof { intention_label, body } => {
                     log_event(&mut workspace.write(), EventDirection::System,
                         format!("Lua: submit_proof(\"{}\", \"{}\")", intention_label, body));
@@ -527,6 +558,23 @@ pub fn spawn_dispatcher(
                     }
                 }
 
+                query @ (Query::Quests
+                | Query::Quest(_)
+                | Query::QuestCount
+                | Query::AttentionFocus
+                | Query::Blessings
+                | Query::Tokens
+                | Query::TokenCount) => {
+                    let rh = realm_handle.read().clone();
+                    let peers = workspace.read().peers.entries.clone();
+                    match rh {
+                        Some(rh) => quests::run_query(&rh, &peers, query)
+                            .await
+                            .unwrap_or_else(QueryResult::Error),
+                        None => QueryResult::Error("Home realm not initialized".into()),
+                    }
+                }
+
                 Query::Custom(name) => {
                     QueryResult::Error(format!("Unknown query: {}", name))
                 }
@@ -27,6 +27,17 @@ pub enum AppEvent {
     ArtifactStored(String),
     ArtifactGranted { artifact_name: String, peer_name: String },
 
+    // Quests
+    QuestCreated(String),
+    QuestClaimed { quest_title: String, claimant: String },
+    ClaimVerified { quest_title: String, claimant: String },
+    QuestCompleted(String),
+
+    // Attention and blessings
+    /// The local member's focus moved to a quest (empty when cleared).
+    AttentionSwitched(String),
+    BlessingGranted { quest_title: String, claimant: String },
+
     // Errors
     ActionFailed { action: String, error: String },
 }
@@ -47,6 +58,12 @@ impl AppEvent {
             AppEvent::OverlayClosed(_) => "overlay_closed",
             AppEvent::ArtifactStored(_) => "artifact_stored",
             AppEvent::ArtifactGranted { .. } => "artifact_granted",
+            AppEvent::QuestCreated(_) => "quest_created",
+            AppEvent::QuestClaimed { .. } => "quest_claimed",
+            AppEvent::ClaimVerified { .. } => "claim_verified",
+            AppEvent::QuestCompleted(_) => "quest_completed",
+            AppEvent::AttentionSwitched(_) => "attention_switched",
+            AppEvent::BlessingGranted { .. } => "blessing_granted",
             AppEvent::ActionFailed { .. } => "action_failed",
         }
     }
@@ -69,6 +86,12 @@ impl AppEvent {
             AppEvent::OverlayClosed(name) => name == filter,
             AppEvent::ArtifactStored(name) => name == filter,
             AppEvent::ArtifactGranted { artifact_name, .. } => artifact_name == filter,
+            AppEvent::QuestCreated(title) => title == filter,
+            AppEvent::QuestClaimed { quest_title, .. } => quest_title == filter,
+            AppEvent::ClaimVerified { quest_title, .. } => quest_title == filter,
+            AppEvent::QuestCompleted(title) => title == filter,
+            AppEvent::AttentionSwitched(title) => title == filter,
+            AppEvent::BlessingGranted { quest_title, .. } => quest_title == filter,
             _ => true,
         }
     }
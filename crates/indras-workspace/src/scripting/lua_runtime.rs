@@ -91,6 +91,10 @@ impl LuaTestRuntime {
         register_action_wrapper(&lua, &indras, &action_tx, "set_name", false);
         register_action_wrapper(&lua, &indras, &action_tx, "create_identity", false);
         register_action_wrapper(&lua, &indras, &action_tx, "open_slash_menu", false);
+        register_action_wrapper(&lua, &indras, &action_tx, "claim_quest", true);
+        register_action_wrapper(&lua, &indras, &action_tx, "complete_quest", true);
+        register_action_wrapper(&lua, &indras, &action_tx, "focus_attention", true);
+        register_action_wrapper(&lua, &indras, &action_tx, "clear_attention", false);
 
         // indras.paste_code(uri) -> paste_connect_code
         let tx = action_tx.clone();
@@ -192,6 +196,61 @@ impl LuaTestRuntime {
             )
             .unwrap();
 
+        // indras.create_quest({ title, description?, kind? })
+        let tx = action_tx.clone();
+        indras
+            .set(
+                "create_quest",
+                lua.create_function(move |_, tbl: mlua::Table| {
+                    let title: String = tbl.get("title")?;
+                    let description: String = tbl.get::<Option<String>>("description")?.unwrap_or_default();
+                    let kind: String = tbl.get::<Option<String>>("kind")?.unwrap_or_default();
+                    tx.blocking_send(Action::CreateQuest {
+                        title,
+                        description,
+                        kind,
+                    })
+                    .map_err(|e| mlua::Error::external(e))?;
+                    Ok(())
+                })
+                .unwrap(),
+            )
+            .unwrap();
+
+        // indras.verify_claim(quest_title, claimant)
+        let tx = action_tx.clone();
+        indras
+            .set(
+                "verify_claim",
+                lua.create_function(move |_, (quest_title, claimant): (String, String)| {
+                    tx.blocking_send(Action::VerifyClaim {
+                        quest_title,
+                        claimant,
+                    })
+                    .map_err(|e| mlua::Error::external(e))?;
+                    Ok(())
+                })
+                .unwrap(),
+            )
+            .unwrap();
+
+        // indras.bless(quest_title, claimant)
+        let tx = action_tx.clone();
+        indras
+            .set(
+                "bless",
+                lua.create_function(move |_, (quest_title, claimant): (String, String)| {
+                    tx.blocking_send(Action::BlessClaim {
+                        quest_title,
+                        claimant,
+                    })
+                    .map_err(|e| mlua::Error::external(e))?;
+                    Ok(())
+                })
+                .unwrap(),
+            )
+            .unwrap();
+
         // indras.file_write(path, content) — restricted to /tmp/
         indras
             .set(
@@ -309,6 +368,24 @@ impl LuaTestRuntime {
 
         // --- Queries ---
 
+        // indras.quest(title) -> quest table, or nil if there is none
+        let qtx = query_tx.clone();
+        indras
+            .set(
+                "quest",
+                lua.create_function(move |lua, title: String| {
+                    let (tx, rx) = oneshot::channel();
+                    qtx.blocking_send((Query::Quest(title), tx))
+                        .map_err(|e| mlua::Error::external(e))?;
+                    let result = rx
+                        .blocking_recv()
+                        .map_err(|e| mlua::Error::external(e))?;
+                    query_result_to_lua(lua, result)
+                })
+                .unwrap(),
+            )
+            .unwrap();
+
         // indras.query(name)
         let qtx = query_tx;
         indras
@@ -606,6 +683,25 @@ fn register_assertions(lua: &Lua, indras: &mlua::Table) {
         )
         .unwrap();
 
+    // assert.matches(actual, expected, msg?) — every key in `expected` must
+    // match in `actual`, recursing into nested tables.
+    assert_table
+        .set(
+            "matches",
+            lua.create_function(|_, (actual, expected, msg): (Value, mlua::Table, Option<String>)| {
+                if let Some(mismatch) = table_mismatch(&actual, &expected, "")? {
+                    let message = match msg {
+                        Some(msg) => format!("{}: {}", msg, mismatch),
+                        None => format!("Assertion failed: {}", mismatch),
+                    };
+                    return Err(mlua::Error::external(message));
+                }
+                Ok(())
+            })
+            .unwrap(),
+        )
+        .unwrap();
+
     indras.set("assert", assert_table).unwrap();
 }
 
@@ -623,6 +719,35 @@ fn values_equal(a: &Value, b: &Value) -> bool {
     }
 }
 
+/// First key in `expected` that `actual` does not match, as a description.
+fn table_mismatch(actual: &Value, expected: &mlua::Table, path: &str) -> Result<Option<String>> {
+    let Value::Table(actual) = actual else {
+        return Ok(Some(format!("{} is {:?}, expected a table", display_path(path), actual)));
+    };
+    for pair in expected.pairs::<Value, Value>() {
+        let (key, want) = pair?;
+        let key_str = match &key {
+            Value::String(s) => s.to_str()?.to_string(),
+            other => format!("{:?}", other),
+        };
+        let path = if path.is_empty() { key_str } else { format!("{}.{}", path, key_str) };
+        let got: Value = actual.get(key)?;
+        let mismatch = match &want {
+            Value::Table(nested) => table_mismatch(&got, nested, &path)?,
+            _ if values_equal(&got, &want) => None,
+            _ => Some(format!("{} is {:?}, expected {:?}", path, got, want)),
+        };
+        if mismatch.is_some() {
+            return Ok(mismatch);
+        }
+    }
+    Ok(None)
+}
+
+fn display_path(path: &str) -> &str {
+    if path.is_empty() { "value" } else { path }
+}
+
 /// Register logging helpers (mirrors simulation/src/lua/bindings/logging.rs pattern).
 fn register_logging(lua: &Lua, indras: &mlua::Table) {
     let log = lua.create_table().unwrap();
@@ -10,3 +10,5 @@ pub mod channels;
 pub mod lua_runtime;
 #[cfg(feature = "lua-scripting")]
 pub mod dispatcher;
+#[cfg(feature = "lua-scripting")]
+pub mod quests;
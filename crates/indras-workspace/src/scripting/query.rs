@@ -17,6 +17,14 @@ pub enum Query {
     ArtifactCount,
     Artifacts,
     IdentityUri,
+    Quests,
+    /// One quest by title (sent by `indras.quest(title)`, not by name).
+    Quest(String),
+    QuestCount,
+    AttentionFocus,
+    Blessings,
+    Tokens,
+    TokenCount,
     Custom(String),
 }
 
@@ -38,6 +46,12 @@ impl Query {
             "artifact_count" => Ok(Query::ArtifactCount),
             "artifacts" => Ok(Query::Artifacts),
             "identity_uri" => Ok(Query::IdentityUri),
+            "quests" => Ok(Query::Quests),
+            "quest_count" => Ok(Query::QuestCount),
+            "attention_focus" => Ok(Query::AttentionFocus),
+            "blessings" => Ok(Query::Blessings),
+            "tokens" => Ok(Query::Tokens),
+            "token_count" => Ok(Query::TokenCount),
             other => Ok(Query::Custom(other.to_string())),
         }
     }
@@ -0,0 +1,369 @@
+//! Quest, attention, blessing, and token handling for the dispatcher.
+//!
+//! Scripts refer to quests by title and to members by display name; these
+//! helpers resolve both against the home realm documents and then go through
+//! the same [`RealmHandle`] calls the UI uses, so a scripted scenario leaves
+//! the documents exactly as clicking through it would.
+
+use std::collections::HashMap;
+
+use indras_network::member::MemberId;
+use indras_sync_engine::{
+    AttentionDocument, BlessingDocument, Intention, IntentionDocument, IntentionId,
+    IntentionKind, TokenOfGratitudeDocument,
+};
+
+use super::action::Action;
+use super::event::AppEvent;
+use super::query::{Query, QueryResult};
+use crate::bridge::realm_bridge::RealmHandle;
+use crate::state::workspace::PeerDisplayInfo;
+
+/// Resolves member ids to display names and back.
+///
+/// `"me"` and the local player's name both refer to the local member.
+pub struct MemberNames {
+    me: MemberId,
+    my_name: String,
+    peers: Vec<(MemberId, String)>,
+}
+
+impl MemberNames {
+    pub fn new(rh: &RealmHandle, peers: &[PeerDisplayInfo]) -> Self {
+        Self {
+            me: rh.member_id,
+            my_name: rh.player_name.clone(),
+            peers: peers.iter().map(|p| (p.player_id, p.name.clone())).collect(),
+        }
+    }
+
+    /// Display name for a member, or a short hex id if unknown.
+    pub fn name(&self, id: &MemberId) -> String {
+        if *id == self.me {
+            return self.my_name.clone();
+        }
+        self.peers
+            .iter()
+            .find(|(pid, _)| pid == id)
+            .map(|(_, name)| name.clone())
+            .unwrap_or_else(|| id.iter().take(4).map(|b| format!("{:02x}", b)).collect())
+    }
+
+    /// Member id for a display name.
+    pub fn resolve(&self, name: &str) -> Result<MemberId, String> {
+        if name == "me" || name == self.my_name {
+            return Ok(self.me);
+        }
+        self.peers
+            .iter()
+            .find(|(_, n)| n == name)
+            .map(|(id, _)| *id)
+            .ok_or_else(|| format!("No member named '{}'", name))
+    }
+}
+
+/// Parse an intention kind name (`intention`, `need`, `offering`).
+pub fn parse_kind(kind: &str) -> Result<IntentionKind, String> {
+    match kind.to_lowercase().as_str() {
+        "" | "intention" | "quest" => Ok(IntentionKind::Intention),
+        "need" => Ok(IntentionKind::Need),
+        "offering" => Ok(IntentionKind::Offering),
+        other => Err(format!("Unknown quest kind: {}", other)),
+    }
+}
+
+/// Lifecycle state of a quest as scripts see it.
+fn quest_status(intention: &Intention) -> &'static str {
+    if intention.is_complete() {
+        "completed"
+    } else if intention.has_verified_claims() {
+        "verified"
+    } else if intention.has_claims() {
+        "claimed"
+    } else {
+        "open"
+    }
+}
+
+/// Name used in `ActionFailed` events for a quest action.
+pub fn action_name(action: &Action) -> &'static str {
+    match action {
+        Action::CreateQuest { .. } => "create_quest",
+        Action::ClaimQuest { .. } => "claim_quest",
+        Action::VerifyClaim { .. } => "verify_claim",
+        Action::CompleteQuest { .. } => "complete_quest",
+        Action::FocusAttention { .. } => "focus_attention",
+        Action::ClearAttention => "clear_attention",
+        Action::BlessClaim { .. } => "bless_claim",
+        _ => "unknown",
+    }
+}
+
+async fn intentions(rh: &RealmHandle) -> Result<IntentionDocument, String> {
+    let doc = rh
+        .home
+        .document::<IntentionDocument>("intentions")
+        .await
+        .map_err(|e| e.to_string())?;
+    let data = doc.read().await.clone();
+    Ok(data)
+}
+
+/// Id of the live quest with this title.
+async fn find_quest(rh: &RealmHandle, title: &str) -> Result<IntentionId, String> {
+    intentions(rh)
+        .await?
+        .intentions
+        .iter()
+        .find(|i| !i.is_deleted() && i.title == title)
+        .map(|i| i.id)
+        .ok_or_else(|| format!("No quest titled '{}'", title))
+}
+
+/// Run a quest, attention, or blessing action. Returns the event to emit.
+pub async fn run_action(
+    rh: &RealmHandle,
+    peers: &[PeerDisplayInfo],
+    action: Action,
+) -> Result<AppEvent, String> {
+    let names = MemberNames::new(rh, peers);
+    match action {
+        Action::CreateQuest { title, description, kind } => {
+            let kind = parse_kind(&kind)?;
+            rh.create_intention(&title, &description, kind)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(AppEvent::QuestCreated(title))
+        }
+
+        Action::ClaimQuest { quest_title } => {
+            let id = find_quest(rh, &quest_title).await?;
+            rh.submit_proof(id).await.map_err(|e| e.to_string())?;
+            Ok(AppEvent::QuestClaimed {
+                quest_title,
+                claimant: rh.player_name.clone(),
+            })
+        }
+
+        Action::VerifyClaim { quest_title, claimant } => {
+            let id = find_quest(rh, &quest_title).await?;
+            let member = names.resolve(&claimant)?;
+            let claim_index = intentions(rh)
+                .await?
+                .find(&id)
+                .and_then(|i| i.claims.iter().position(|c| c.claimant == member && !c.verified))
+                .ok_or_else(|| format!("No unverified claim by '{}' on '{}'", claimant, quest_title))?;
+            rh.verify_claim(id, claim_index).await.map_err(|e| e.to_string())?;
+            Ok(AppEvent::ClaimVerified { quest_title, claimant })
+        }
+
+        Action::CompleteQuest { quest_title } => {
+            let id = find_quest(rh, &quest_title).await?;
+            rh.complete_intention(id).await.map_err(|e| e.to_string())?;
+            Ok(AppEvent::QuestCompleted(quest_title))
+        }
+
+        Action::FocusAttention { quest_title } => {
+            let id = find_quest(rh, &quest_title).await?;
+            rh.focus_attention(id).await.map_err(|e| e.to_string())?;
+            Ok(AppEvent::AttentionSwitched(quest_title))
+        }
+
+        Action::ClearAttention => {
+            rh.clear_attention().await.map_err(|e| e.to_string())?;
+            Ok(AppEvent::AttentionSwitched(String::new()))
+        }
+
+        Action::BlessClaim { quest_title, claimant } => {
+            let id = find_quest(rh, &quest_title).await?;
+            let member = names.resolve(&claimant)?;
+
+            // Release every attention event of ours on this quest that has
+            // not been blessed yet.
+            let attention = rh
+                .home
+                .document::<AttentionDocument>("attention")
+                .await
+                .map_err(|e| e.to_string())?;
+            let candidates: Vec<usize> = attention
+                .read()
+                .await
+                .events()
+                .iter()
+                .enumerate()
+                .filter(|(_, e)| e.member == rh.member_id && e.intention_id == Some(id))
+                .map(|(i, _)| i)
+                .collect();
+            let blessings = rh
+                .home
+                .document::<BlessingDocument>("blessings")
+                .await
+                .map_err(|e| e.to_string())?;
+            let indices = blessings
+                .read()
+                .await
+                .unblessed_event_indices(&rh.member_id, &id, &candidates);
+            if indices.is_empty() {
+                return Err(format!("No unblessed attention on '{}' to release", quest_title));
+            }
+
+            rh.bless_claim(id, member, indices).await.map_err(|e| e.to_string())?;
+            Ok(AppEvent::BlessingGranted { quest_title, claimant })
+        }
+
+        other => Err(format!("Not a quest action: {:?}", other)),
+    }
+}
+
+/// Answer a quest, attention, blessing, or token query.
+pub async fn run_query(
+    rh: &RealmHandle,
+    peers: &[PeerDisplayInfo],
+    query: Query,
+) -> Result<QueryResult, String> {
+    let names = MemberNames::new(rh, peers);
+    let quests = intentions(rh).await?;
+    let titles: HashMap<IntentionId, String> = quests
+        .intentions
+        .iter()
+        .map(|i| (i.id, i.title.clone()))
+        .collect();
+    let title_of = |id: &IntentionId| titles.get(id).cloned().unwrap_or_default();
+
+    match query {
+        Query::QuestCount => {
+            let count = quests.intentions.iter().filter(|i| !i.is_deleted()).count();
+            Ok(QueryResult::Number(count as f64))
+        }
+
+        Query::Quests | Query::Quest(_) => {
+            let attention = rh
+                .home
+                .document::<AttentionDocument>("attention")
+                .await
+                .map_err(|e| e.to_string())?
+                .read()
+                .await
+                .clone();
+            let blessings = rh
+                .home
+                .document::<BlessingDocument>("blessings")
+                .await
+                .map_err(|e| e.to_string())?
+                .read()
+                .await
+                .clone();
+
+            let only = match &query {
+                Query::Quest(title) => Some(title.as_str()),
+                _ => None,
+            };
+            let items: Vec<serde_json::Value> = quests
+                .intentions
+                .iter()
+                .filter(|i| !i.is_deleted() && only.is_none_or(|t| i.title == t))
+                .map(|i| {
+                    let claims: Vec<serde_json::Value> = i
+                        .claims
+                        .iter()
+                        .map(|c| {
+                            serde_json::json!({
+                                "claimant": names.name(&c.claimant),
+                                "verified": c.verified,
+                                "has_proof": c.has_proof(),
+                            })
+                        })
+                        .collect();
+                    let focusers: Vec<String> = attention
+                        .members_focusing_on(&i.id)
+                        .iter()
+                        .map(|m| names.name(m))
+                        .collect();
+                    serde_json::json!({
+                        "title": i.title,
+                        "description": i.description,
+                        "kind": i.kind.label().to_lowercase(),
+                        "creator": names.name(&i.creator),
+                        "status": quest_status(i),
+                        "claim_count": i.claims.len(),
+                        "claims": claims,
+                        "focusers": focusers,
+                        "blessing_count": blessings.blessings_for_intention(&i.id).len(),
+                    })
+                })
+                .collect();
+
+            // A single missing quest comes back as nil.
+            match only {
+                Some(_) => Ok(QueryResult::Json(
+                    items.into_iter().next().unwrap_or(serde_json::Value::Null),
+                )),
+                None => Ok(QueryResult::Json(serde_json::Value::Array(items))),
+            }
+        }
+
+        Query::AttentionFocus => {
+            let attention = rh
+                .home
+                .document::<AttentionDocument>("attention")
+                .await
+                .map_err(|e| e.to_string())?;
+            let focus = attention.read().await.current_focus(&rh.member_id);
+            Ok(QueryResult::String(focus.map(|id| title_of(&id)).unwrap_or_default()))
+        }
+
+        Query::Blessings => {
+            let blessings = rh
+                .home
+                .document::<BlessingDocument>("blessings")
+                .await
+                .map_err(|e| e.to_string())?;
+            let items: Vec<serde_json::Value> = blessings
+                .read()
+                .await
+                .blessings()
+                .iter()
+                .map(|b| {
+                    serde_json::json!({
+                        "quest": title_of(&b.intention_id()),
+                        "claimant": names.name(&b.claimant()),
+                        "blesser": names.name(&b.blesser),
+                        "event_count": b.event_indices.len(),
+                    })
+                })
+                .collect();
+            Ok(QueryResult::Json(serde_json::Value::Array(items)))
+        }
+
+        Query::TokenCount | Query::Tokens => {
+            let tokens = rh
+                .home
+                .document::<TokenOfGratitudeDocument>("_tokens")
+                .await
+                .map_err(|e| e.to_string())?
+                .read()
+                .await
+                .clone();
+            if matches!(query, Query::TokenCount) {
+                let count = tokens.token_count_for_steward(&rh.member_id);
+                return Ok(QueryResult::Number(count as f64));
+            }
+            let items: Vec<serde_json::Value> = tokens
+                .all_tokens()
+                .iter()
+                .map(|t| {
+                    serde_json::json!({
+                        "steward": names.name(&t.steward),
+                        "blesser": names.name(&t.blesser),
+                        "source_quest": title_of(&t.source_intention_id),
+                        "pledged_to": t.pledged_to.as_ref().map(&title_of),
+                        "event_count": t.event_indices.len(),
+                    })
+                })
+                .collect();
+            Ok(QueryResult::Json(serde_json::Value::Array(items)))
+        }
+
+        other => Err(format!("Not a quest query: {:?}", other)),
+    }
+}
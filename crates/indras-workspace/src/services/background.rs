@@ -0,0 +1,55 @@
+//! Background agent mode.
+//!
+//! With `--background` (or `INDRAS_BACKGROUND=1`) closing the workspace
+//! window hides it and the node keeps running. The process serves the
+//! agent socket in the data directory so a second launch can bring the
+//! window back, and tools can attach to follow events or send commands.
+//! There is no tray icon.
+
+use std::sync::Arc;
+
+use indras_network::IndrasNetwork;
+
+/// Serve the agent socket until a client asks the process to shut down.
+///
+/// `on_show` is called whenever a client asks for the window.
+#[cfg(unix)]
+pub async fn run_agent_host<F>(network: Arc<IndrasNetwork>, mut on_show: F)
+where
+    F: FnMut(),
+{
+    let data_dir = crate::bridge::network_bridge::default_data_dir();
+    let host = match indras_network::AgentHost::start(Arc::clone(&network), &data_dir).await {
+        Ok(host) => host,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to start agent host");
+            return;
+        }
+    };
+
+    let mut show = host.show_requests();
+    loop {
+        tokio::select! {
+            r = show.recv() => match r {
+                Ok(()) | Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => on_show(),
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+            },
+            _ = host.shutdown_requested() => {
+                tracing::info!("Agent shutdown requested");
+                if let Err(e) = network.stop().await {
+                    tracing::warn!(error = %e, "Failed to stop network");
+                }
+                std::process::exit(0);
+            }
+        }
+    }
+}
+
+/// Background mode needs the agent socket, which is Unix-only for now.
+#[cfg(not(unix))]
+pub async fn run_agent_host<F>(_network: Arc<IndrasNetwork>, _on_show: F)
+where
+    F: FnMut(),
+{
+    tracing::warn!("Background agent mode is not supported on this platform");
+}
@@ -0,0 +1,626 @@
+//! Global quick-switcher and command palette.
+//!
+//! Gathers realms, contacts, quests, and recent artifacts from the network
+//! into one flat list of [`PaletteItem`]s, ranks them against the typed
+//! query with [`search`], and dispatches the chosen item.
+//!
+//! Commands come from [`CommandProvider`]s registered on a
+//! [`CommandPalette`]. `indras-chat` and `indras-sync-engine` sit below this
+//! crate in the dependency graph, so their providers ([`ChatCommands`],
+//! [`CaptureCommands`], [`QuestCommands`]) live here as thin adapters over
+//! their public APIs; anything that depends on the workspace can register
+//! its own.
+
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use indras_network::member::MemberId;
+use indras_chat::clipboard::{read_clipboard_image, share_image};
+use indras_network::{ArtifactId, IndrasNetwork, RealmId};
+use indras_sync_engine::{HomeRealmIntentions, SyncEngine};
+
+use crate::state::navigation::NavigationState;
+use crate::state::workspace::{DashboardTab, ViewType};
+
+/// How many artifacts the switcher offers besides recently opened ones.
+const MAX_ARTIFACTS: usize = 20;
+
+/// Dashboard tabs reachable through [`QuestCommands`], by command name.
+const QUEST_TABS: &[(&str, DashboardTab)] = &[
+    ("tab.intentions", DashboardTab::MyIntentions),
+    ("tab.community", DashboardTab::Community),
+    ("tab.requests", DashboardTab::Requests),
+    ("tab.tokens", DashboardTab::Tokens),
+];
+
+/// Which group a palette entry belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PaletteCategory {
+    Command,
+    Realm,
+    Contact,
+    Quest,
+    Artifact,
+}
+
+impl PaletteCategory {
+    /// Human-readable group heading.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Command => "Commands",
+            Self::Realm => "Realms",
+            Self::Contact => "Contacts",
+            Self::Quest => "Quests",
+            Self::Artifact => "Artifacts",
+        }
+    }
+
+    /// Tie-breaker when two entries score the same: commands first.
+    fn rank(&self) -> u8 {
+        match self {
+            Self::Command => 0,
+            Self::Realm => 1,
+            Self::Contact => 2,
+            Self::Quest => 3,
+            Self::Artifact => 4,
+        }
+    }
+}
+
+/// Somewhere the palette can take the user.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PaletteTarget {
+    /// A top-level view.
+    View(ViewType),
+    /// A dashboard tab.
+    Tab(DashboardTab),
+    /// A realm, opened in its own window.
+    Realm(RealmId),
+    /// A contact's conversation.
+    Contact(MemberId),
+    /// A home-realm intention, by hex-encoded ID.
+    Quest(String),
+    /// An artifact in the home artifact index.
+    Artifact(ArtifactId),
+}
+
+/// What happens when a palette entry is chosen.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PaletteAction {
+    /// Navigate without running anything.
+    Open(PaletteTarget),
+    /// Run a command on the provider that contributed it.
+    Run {
+        provider: &'static str,
+        command: String,
+    },
+}
+
+/// Result of running a provider command.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CommandOutcome {
+    /// Navigate somewhere.
+    Open(PaletteTarget),
+    /// The command finished; the message is shown in the event log.
+    Done(String),
+}
+
+/// A single entry in the palette.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PaletteItem {
+    pub category: PaletteCategory,
+    pub title: String,
+    /// Secondary text, also searched.
+    pub subtitle: Option<String>,
+    pub icon: String,
+    pub action: PaletteAction,
+    /// Added to the match score, e.g. for recently used entries.
+    pub boost: i64,
+}
+
+impl PaletteItem {
+    /// An entry that navigates to `target` when chosen.
+    pub fn open(
+        category: PaletteCategory,
+        title: impl Into<String>,
+        icon: impl Into<String>,
+        target: PaletteTarget,
+    ) -> Self {
+        Self {
+            category,
+            title: title.into(),
+            subtitle: None,
+            icon: icon.into(),
+            action: PaletteAction::Open(target),
+            boost: 0,
+        }
+    }
+
+    /// A command entry dispatched back to `provider`.
+    pub fn command(
+        provider: &'static str,
+        command: impl Into<String>,
+        title: impl Into<String>,
+        icon: impl Into<String>,
+    ) -> Self {
+        Self {
+            category: PaletteCategory::Command,
+            title: title.into(),
+            subtitle: None,
+            icon: icon.into(),
+            action: PaletteAction::Run {
+                provider,
+                command: command.into(),
+            },
+            boost: 0,
+        }
+    }
+
+    /// Set the secondary text.
+    pub fn with_subtitle(mut self, subtitle: impl Into<String>) -> Self {
+        self.subtitle = Some(subtitle.into());
+        self
+    }
+}
+
+/// A palette entry that matched the query.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PaletteMatch {
+    pub item: PaletteItem,
+    pub score: i64,
+    /// Character positions in `item.title` that matched, for highlighting.
+    pub highlights: Vec<usize>,
+}
+
+/// Contributes commands to the palette and runs them.
+pub trait CommandProvider: Send + Sync {
+    /// Stable name used to route [`PaletteAction::Run`] back here.
+    fn id(&self) -> &'static str;
+
+    /// Commands currently available.
+    fn commands(&self, network: Arc<IndrasNetwork>) -> BoxFuture<'static, Vec<PaletteItem>>;
+
+    /// Run one of the commands returned by [`commands`](Self::commands).
+    fn run(
+        &self,
+        command: &str,
+        network: Arc<IndrasNetwork>,
+    ) -> BoxFuture<'static, Result<CommandOutcome, String>>;
+}
+
+/// Registry of command providers plus the built-in network queries.
+#[derive(Clone, Default)]
+pub struct CommandPalette {
+    providers: Vec<Arc<dyn CommandProvider>>,
+}
+
+impl CommandPalette {
+    /// A palette with no command providers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A palette with the chat, capture and quest providers registered.
+    pub fn with_defaults() -> Self {
+        let mut palette = Self::new();
+        palette.register(ChatCommands);
+        palette.register(CaptureCommands);
+        palette.register(QuestCommands);
+        palette
+    }
+
+    /// Register a provider, replacing any with the same ID.
+    pub fn register(&mut self, provider: impl CommandProvider + 'static) {
+        self.providers.retain(|p| p.id() != provider.id());
+        self.providers.push(Arc::new(provider));
+    }
+
+    /// Collect every entry the palette can offer.
+    ///
+    /// `recent_artifacts` holds hex artifact IDs, newest first; those are
+    /// boosted so they surface on an empty query.
+    pub async fn gather(
+        &self,
+        network: &Arc<IndrasNetwork>,
+        recent_artifacts: &[String],
+    ) -> Vec<PaletteItem> {
+        let mut items = Vec::new();
+        for provider in &self.providers {
+            items.extend(provider.commands(Arc::clone(network)).await);
+        }
+        items.extend(gather_realms(network));
+        items.extend(gather_contacts(network).await);
+        items.extend(gather_quests(network).await);
+        items.extend(gather_artifacts(network, recent_artifacts).await);
+        items
+    }
+
+    /// Resolve a chosen entry to an outcome, running provider commands.
+    pub async fn dispatch(
+        &self,
+        action: &PaletteAction,
+        network: Arc<IndrasNetwork>,
+    ) -> Result<CommandOutcome, String> {
+        match action {
+            PaletteAction::Open(target) => Ok(CommandOutcome::Open(target.clone())),
+            PaletteAction::Run { provider, command } => {
+                let provider = self
+                    .providers
+                    .iter()
+                    .find(|p| p.id() == *provider)
+                    .ok_or_else(|| format!("No command provider named {provider}"))?;
+                provider.run(command, network).await
+            }
+        }
+    }
+}
+
+/// Rank `items` against `query`, best first, keeping at most `limit`.
+///
+/// An empty query keeps every item, ordered by boost.
+pub fn search(items: &[PaletteItem], query: &str, limit: usize) -> Vec<PaletteMatch> {
+    let mut matches: Vec<PaletteMatch> = items
+        .iter()
+        .filter_map(|item| {
+            let (score, highlights) = match fuzzy_score(&item.title, query) {
+                Some(hit) => hit,
+                None => {
+                    // Subtitle matches rank below any title match.
+                    let subtitle = item.subtitle.as_deref()?;
+                    let (score, _) = fuzzy_score(subtitle, query)?;
+                    (score / 2, Vec::new())
+                }
+            };
+            Some(PaletteMatch {
+                item: item.clone(),
+                score: score + item.boost,
+                highlights,
+            })
+        })
+        .collect();
+
+    matches.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.item.category.rank().cmp(&b.item.category.rank()))
+            .then_with(|| a.item.title.cmp(&b.item.title))
+    });
+    matches.truncate(limit);
+    matches
+}
+
+/// Score `candidate` as a case-insensitive subsequence match of `query`.
+///
+/// Returns `None` when some query character is missing. Consecutive runs,
+/// word starts, and a prefix match score higher; skipped characters cost a
+/// little. Whitespace in the query is ignored.
+pub fn fuzzy_score(candidate: &str, query: &str) -> Option<(i64, Vec<usize>)> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if query.is_empty() {
+        return Some((0, Vec::new()));
+    }
+
+    let chars: Vec<char> = candidate.chars().collect();
+    let mut score = 0i64;
+    let mut highlights = Vec::with_capacity(query.len());
+    let mut next = 0usize;
+    let mut prev_match: Option<usize> = None;
+
+    for q in &query {
+        let pos = (next..chars.len()).find(|&i| chars[i].to_lowercase().eq([*q]))?;
+        let word_start = pos == 0 || !chars[pos - 1].is_alphanumeric();
+        score += 10;
+        if word_start {
+            score += 15;
+        }
+        match prev_match {
+            Some(prev) if prev + 1 == pos => score += 20,
+            _ => score -= (pos - next) as i64,
+        }
+        highlights.push(pos);
+        prev_match = Some(pos);
+        next = pos + 1;
+    }
+
+    if highlights.first() == Some(&0) {
+        score += 25;
+    }
+    Some((score, highlights))
+}
+
+// ================================================================
+// Network queries
+// ================================================================
+
+/// Shared realms, excluding DMs (those come from [`ChatCommands`]).
+fn gather_realms(network: &IndrasNetwork) -> Vec<PaletteItem> {
+    network
+        .conversation_realms()
+        .into_iter()
+        .filter(|id| network.dm_peer_for_realm(id).is_none())
+        .filter_map(|id| {
+            let realm = network.get_realm_by_id(&id)?;
+            let name = realm.name().unwrap_or("Unnamed realm").to_string();
+            Some(PaletteItem::open(
+                PaletteCategory::Realm,
+                name,
+                "🌐",
+                PaletteTarget::Realm(id),
+            ))
+        })
+        .collect()
+}
+
+async fn gather_contacts(network: &IndrasNetwork) -> Vec<PaletteItem> {
+    let Some(contacts_realm) = network.contacts_realm().await else {
+        return Vec::new();
+    };
+    let Ok(doc) = contacts_realm.contacts().await else {
+        return Vec::new();
+    };
+    let data = doc.read().await;
+    data.contacts
+        .iter()
+        .map(|(mid, entry)| {
+            let short: String = mid.iter().take(4).map(|b| format!("{b:02x}")).collect();
+            let name = entry.display_name.clone().unwrap_or_else(|| short.clone());
+            PaletteItem::open(
+                PaletteCategory::Contact,
+                name,
+                NavigationState::icon_for_type("contact"),
+                PaletteTarget::Contact(*mid),
+            )
+            .with_subtitle(short)
+        })
+        .collect()
+}
+
+/// Open home-realm intentions of every kind.
+async fn gather_quests(network: &IndrasNetwork) -> Vec<PaletteItem> {
+    let Some(home) = network.get_home_realm().await else {
+        return Vec::new();
+    };
+    let doc = match home.intentions().await {
+        Ok(doc) => doc,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to read intentions for the palette");
+            return Vec::new();
+        }
+    };
+    let data = doc.read().await;
+    data.intentions
+        .iter()
+        .filter(|i| !i.deleted && !i.is_complete())
+        .map(|i| {
+            let id_hex: String = i.id.iter().map(|b| format!("{b:02x}")).collect();
+            PaletteItem::open(
+                PaletteCategory::Quest,
+                i.title.clone(),
+                i.kind.icon(),
+                PaletteTarget::Quest(id_hex),
+            )
+            .with_subtitle(i.kind.to_string())
+        })
+        .collect()
+}
+
+/// Recently opened artifacts first, then the newest from the index.
+async fn gather_artifacts(network: &IndrasNetwork, recent: &[String]) -> Vec<PaletteItem> {
+    let Some(home) = network.get_home_realm().await else {
+        return Vec::new();
+    };
+    let Ok(index) = home.artifact_index().await else {
+        return Vec::new();
+    };
+    let data = index.read().await;
+    let mut entries: Vec<_> = data.active_artifacts().collect();
+    entries.sort_by_key(|e| std::cmp::Reverse(e.created_at));
+
+    entries
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, entry)| {
+            // Keep every recent artifact plus the newest few.
+            let hex = entry.hash_hex();
+            let recency = recent.iter().position(|r| *r == hex);
+            (recency.is_some() || i < MAX_ARTIFACTS).then_some((recency, entry))
+        })
+        .map(|(recency, entry)| {
+            let mut item = PaletteItem::open(
+                PaletteCategory::Artifact,
+                entry.name.clone(),
+                "📄",
+                PaletteTarget::Artifact(entry.id),
+            );
+            if let Some(mime) = &entry.mime_type {
+                item = item.with_subtitle(mime.clone());
+            }
+            if let Some(pos) = recency {
+                item.boost = 50 - pos as i64;
+            }
+            item
+        })
+        .collect()
+}
+
+// ================================================================
+// Built-in providers
+// ================================================================
+
+/// Chat navigation and one "Message …" entry per direct conversation.
+pub struct ChatCommands;
+
+impl CommandProvider for ChatCommands {
+    fn id(&self) -> &'static str {
+        "chat"
+    }
+
+    fn commands(&self, network: Arc<IndrasNetwork>) -> BoxFuture<'static, Vec<PaletteItem>> {
+        let id = self.id();
+        Box::pin(async move {
+            let mut items = vec![PaletteItem::command(id, "open", "Open chat", "💬")];
+            let names = contact_names(&network).await;
+            for realm_id in network.conversation_realms() {
+                let Some(peer) = network.dm_peer_for_realm(&realm_id) else {
+                    continue;
+                };
+                let name = names
+                    .iter()
+                    .find(|(mid, _)| *mid == peer)
+                    .map(|(_, name)| name.clone())
+                    .unwrap_or_else(|| peer.iter().take(4).map(|b| format!("{b:02x}")).collect());
+                items.push(PaletteItem::open(
+                    PaletteCategory::Command,
+                    format!("Message {name}"),
+                    "💬",
+                    PaletteTarget::Realm(realm_id),
+                ));
+            }
+            items
+        })
+    }
+
+    fn run(
+        &self,
+        command: &str,
+        _network: Arc<IndrasNetwork>,
+    ) -> BoxFuture<'static, Result<CommandOutcome, String>> {
+        let outcome = match command {
+            "open" => Ok(CommandOutcome::Open(PaletteTarget::View(ViewType::Chat))),
+            other => Err(format!("Unknown chat command: {other}")),
+        };
+        Box::pin(async move { outcome })
+    }
+}
+
+/// One-step screenshot sharing through `indras_chat::clipboard`.
+///
+/// Screenshot tools leave their capture on the clipboard; "Share
+/// screenshot to …" trims its solid border and posts it into the realm.
+pub struct CaptureCommands;
+
+impl CommandProvider for CaptureCommands {
+    fn id(&self) -> &'static str {
+        "capture"
+    }
+
+    fn commands(&self, network: Arc<IndrasNetwork>) -> BoxFuture<'static, Vec<PaletteItem>> {
+        let id = self.id();
+        let items = network
+            .conversation_realms()
+            .into_iter()
+            .filter_map(|realm_id| {
+                let realm = network.get_realm_by_id(&realm_id)?;
+                let name = realm.name().unwrap_or("Unnamed realm").to_string();
+                Some(PaletteItem::command(
+                    id,
+                    format!("share:{}", realm_key(&realm_id)),
+                    format!("Share screenshot to {name}"),
+                    "📸",
+                ))
+            })
+            .collect();
+        Box::pin(async move { items })
+    }
+
+    fn run(
+        &self,
+        command: &str,
+        network: Arc<IndrasNetwork>,
+    ) -> BoxFuture<'static, Result<CommandOutcome, String>> {
+        let command = command.to_string();
+        Box::pin(async move {
+            let key = command
+                .strip_prefix("share:")
+                .ok_or_else(|| format!("Unknown capture command: {command}"))?;
+            let realm = network
+                .conversation_realms()
+                .into_iter()
+                .find(|id| realm_key(id) == key)
+                .and_then(|id| network.get_realm_by_id(&id))
+                .ok_or("Realm not found")?;
+            let image = read_clipboard_image()?.ok_or("No image on the clipboard")?;
+            let author = network.display_name().unwrap_or_else(|| "Anonymous".to_string());
+            share_image(&network, &realm, &author, image.trimmed()).await?;
+            Ok(CommandOutcome::Done(format!(
+                "Shared screenshot to {}",
+                realm.name().unwrap_or("realm")
+            )))
+        })
+    }
+}
+
+fn realm_key(id: &RealmId) -> String {
+    id.as_bytes().iter().map(|b| format!("{b:02x}")).collect()
+}
+
+async fn contact_names(network: &IndrasNetwork) -> Vec<(MemberId, String)> {
+    let Some(contacts_realm) = network.contacts_realm().await else {
+        return Vec::new();
+    };
+    let Ok(doc) = contacts_realm.contacts().await else {
+        return Vec::new();
+    };
+    let data = doc.read().await;
+    data.contacts
+        .iter()
+        .filter_map(|(mid, entry)| Some((*mid, entry.display_name.clone()?)))
+        .collect()
+}
+
+/// Dashboard tabs and request-board actions from `indras-sync-engine`.
+pub struct QuestCommands;
+
+impl CommandProvider for QuestCommands {
+    fn id(&self) -> &'static str {
+        "quests"
+    }
+
+    fn commands(&self, _network: Arc<IndrasNetwork>) -> BoxFuture<'static, Vec<PaletteItem>> {
+        let id = self.id();
+        let mut items: Vec<PaletteItem> = QUEST_TABS
+            .iter()
+            .map(|(command, tab)| {
+                PaletteItem::command(id, *command, format!("Go to {}", tab.label()), "⚔")
+            })
+            .collect();
+        items.push(PaletteItem::command(
+            id,
+            "deliver",
+            "Deliver artifacts for accepted offers",
+            "📦",
+        ));
+        Box::pin(async move { items })
+    }
+
+    fn run(
+        &self,
+        command: &str,
+        network: Arc<IndrasNetwork>,
+    ) -> BoxFuture<'static, Result<CommandOutcome, String>> {
+        if let Some((_, tab)) = QUEST_TABS.iter().find(|(c, _)| *c == command) {
+            let outcome = CommandOutcome::Open(PaletteTarget::Tab(*tab));
+            return Box::pin(async move { Ok(outcome) });
+        }
+        match command {
+            "deliver" => Box::pin(async move {
+                let delivered = SyncEngine::new(network)
+                    .deliver_accepted_request_offers()
+                    .await
+                    .map_err(|e| e.to_string())?;
+                Ok(CommandOutcome::Done(format!(
+                    "Delivered artifacts for {} request(s)",
+                    delivered.len()
+                )))
+            }),
+            other => {
+                let error = format!("Unknown quest command: {other}");
+                Box::pin(async move { Err(error) })
+            }
+        }
+    }
+}
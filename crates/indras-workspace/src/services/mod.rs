@@ -9,3 +9,6 @@ pub mod polling;
 pub mod intention_data;
 pub mod realm_data;
 pub mod event_subscription;
+pub mod background;
+pub mod recording;
+pub mod command_palette;
@@ -9,10 +9,11 @@ use std::collections::HashMap;
 
 use indras_network::home_realm::HomeRealm;
 use indras_network::member::MemberId;
-use indras_network::IndrasNetwork;
+use indras_network::{ArtifactId, IndrasNetwork, RealmId};
 use indras_sync_engine::{
     AttentionDocument, BlessingDocument, ClaimId, IntentionDocument, IntentionId, IntentionKind,
-    HomeRealmIntentions, TokenOfGratitude, TokenOfGratitudeDocument,
+    HomeRealmIntentions, OfferContent, OfferId, RealmRequests, RequestId, RequestStatus,
+    TokenOfGratitude, TokenOfGratitudeDocument,
 };
 
 use crate::components::intention_board::IntentionCardData;
@@ -103,6 +104,61 @@ pub struct StewardChainDot {
     pub name: String,
 }
 
+/// A request on a realm's request board, ready for display.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RequestCardData {
+    /// The realm the request was broadcast to.
+    pub realm_id: RealmId,
+    /// Display name of that realm.
+    pub realm_name: String,
+    /// The request ID.
+    pub request_id: RequestId,
+    /// What is being asked for.
+    pub description: String,
+    /// Display name of the requester.
+    pub requester_name: String,
+    /// Whether the local member made the request.
+    pub is_mine: bool,
+    /// Current lifecycle status.
+    pub status: RequestStatus,
+    /// How long ago the request was broadcast.
+    pub posted_ago: String,
+    /// When the request was broadcast (Unix timestamp in milliseconds).
+    pub created_at_millis: i64,
+    /// Active offers, plus the accepted one.
+    pub offers: Vec<RequestOfferData>,
+    /// Whether the local member has an active offer on it.
+    pub offered_by_me: bool,
+}
+
+/// An offer on a request, ready for display.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RequestOfferData {
+    /// The offer ID.
+    pub offer_id: OfferId,
+    /// Display name of the offerer.
+    pub offerer_name: String,
+    /// Whether the local member made the offer.
+    pub is_mine: bool,
+    /// Whether this is a pledge rather than an artifact.
+    pub is_pledge: bool,
+    /// Artifact name or pledge text.
+    pub summary: String,
+    /// Whether this is the accepted offer.
+    pub is_accepted: bool,
+}
+
+/// Everything the Requests tab shows.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RequestBoardData {
+    /// Requests from every loaded realm, newest first.
+    pub requests: Vec<RequestCardData>,
+    /// Realms a request can be broadcast to, as `(id, name)`.
+    pub realms: Vec<(RealmId, String)>,
+    /// Home artifacts the local member can offer, as `(id, name)`.
+    pub artifacts: Vec<(ArtifactId, String)>,
+}
+
 // ================================================================
 // Helpers
 // ================================================================
@@ -587,3 +643,88 @@ pub async fn build_community_intention_cards(
 
     cards
 }
+
+/// Build the Requests tab from every loaded realm's request board.
+///
+/// Also lists the realms a request can be broadcast to and the home
+/// artifacts the local member can offer.
+pub async fn build_request_board(
+    network: &IndrasNetwork,
+    home: &HomeRealm,
+    my_member_id: MemberId,
+    local_name: &str,
+) -> RequestBoardData {
+    let mut board = RequestBoardData::default();
+
+    for realm_id in network.conversation_realms() {
+        let Some(realm) = network.get_realm_by_id(&realm_id) else {
+            continue;
+        };
+        let realm_name = match (realm.name(), network.dm_peer_for_realm(&realm_id)) {
+            (_, Some(peer)) => {
+                let (peer_name, _, _) = member_display(&peer, &my_member_id, local_name, 0);
+                format!("DM with {peer_name}")
+            }
+            (Some(name), None) => name.to_string(),
+            (None, None) => "Realm".to_string(),
+        };
+        board.realms.push((realm_id, realm_name.clone()));
+
+        let Ok(doc) = realm.request_board().await else {
+            continue;
+        };
+        let data = doc.read().await;
+        for request in &data.requests {
+            let accepted = request.acceptance.as_ref().map(|a| a.offer_id);
+            let offers: Vec<RequestOfferData> = request
+                .offers
+                .iter()
+                .filter(|o| !o.withdrawn || Some(o.id) == accepted)
+                .enumerate()
+                .map(|(idx, o)| {
+                    let (offerer_name, _, _) =
+                        member_display(&o.offerer, &my_member_id, local_name, idx);
+                    RequestOfferData {
+                        offer_id: o.id,
+                        offerer_name,
+                        is_mine: o.offerer == my_member_id,
+                        is_pledge: matches!(o.content, OfferContent::Pledge { .. }),
+                        summary: o.content.summary().to_string(),
+                        is_accepted: Some(o.id) == accepted,
+                    }
+                })
+                .collect();
+            let (requester_name, _, _) =
+                member_display(&request.requester, &my_member_id, local_name, 0);
+
+            board.requests.push(RequestCardData {
+                realm_id,
+                realm_name: realm_name.clone(),
+                request_id: request.id,
+                description: request.description.clone(),
+                requester_name,
+                is_mine: request.requester == my_member_id,
+                status: request.status(),
+                posted_ago: time_ago(request.created_at_millis),
+                created_at_millis: request.created_at_millis,
+                offered_by_me: offers.iter().any(|o| o.is_mine),
+                offers,
+            });
+        }
+    }
+    // Open requests first, newest first within each group
+    board
+        .requests
+        .sort_by_key(|r| (!r.status.is_open(), std::cmp::Reverse(r.created_at_millis)));
+
+    if let Ok(index) = home.artifact_index().await {
+        let data = index.read().await;
+        board.artifacts = data
+            .active_artifacts()
+            .map(|entry| (entry.id, entry.name.clone()))
+            .collect();
+        board.artifacts.sort_by(|a, b| a.1.cmp(&b.1));
+    }
+
+    board
+}
@@ -0,0 +1,77 @@
+//! Session recording.
+//!
+//! With `--record=<path>` the workspace writes every quest, attention,
+//! blessing, and token change in the home realm to `<path>` as JSONL that
+//! realm-viewer (or, with `--record-format=home`, home-viewer) can replay.
+//! `--record-redact=members,text` (or `all`) replaces member names and quest
+//! titles with pseudonyms before anything is written.
+
+use indras_sync_engine::{record_session, Redaction, SessionRecorder, SessionSource, ViewerFormat};
+
+use crate::bridge::realm_bridge::RealmHandle;
+
+/// Recording settings from the command line.
+#[derive(Clone, Debug)]
+pub struct RecordingOptions {
+    pub path: std::path::PathBuf,
+    pub format: ViewerFormat,
+    pub redaction: Redaction,
+}
+
+fn arg_value(prefix: &str) -> Option<String> {
+    std::env::args()
+        .find(|a| a.starts_with(prefix))
+        .map(|a| a.trim_start_matches(prefix).to_string())
+}
+
+/// Recording settings, or `None` if recording is off or misconfigured.
+pub fn recording_options() -> Option<RecordingOptions> {
+    let path = arg_value("--record=")?;
+    let format = match arg_value("--record-format=").map(|f| f.parse()).transpose() {
+        Ok(format) => format.unwrap_or_default(),
+        Err(e) => {
+            tracing::error!(error = %e, "Recording disabled");
+            return None;
+        }
+    };
+    let redaction = match arg_value("--record-redact=").map(|r| r.parse()).transpose() {
+        Ok(redaction) => redaction.unwrap_or_default(),
+        Err(e) => {
+            tracing::error!(error = %e, "Recording disabled");
+            return None;
+        }
+    };
+    Some(RecordingOptions { path: path.into(), format, redaction })
+}
+
+/// Record the home realm until the app exits.
+pub async fn record_home_realm(rh: RealmHandle, options: RecordingOptions) {
+    let source = match SessionSource::home(&rh.home).await {
+        Ok(source) => source,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to start recording");
+            return;
+        }
+    };
+    let file = match std::fs::File::create(&options.path) {
+        Ok(file) => std::io::BufWriter::new(file),
+        Err(e) => {
+            tracing::error!(path = %options.path.display(), error = %e, "Failed to create recording");
+            return;
+        }
+    };
+
+    let mut recorder = SessionRecorder::new(
+        file,
+        options.format,
+        options.redaction,
+        rh.member_id,
+        rh.home.id().to_string(),
+    );
+    recorder.set_name(rh.member_id, rh.player_name.clone());
+
+    tracing::info!(path = %options.path.display(), "Recording session");
+    if let Err(e) = record_session(source, recorder).await {
+        tracing::error!(error = %e, "Recording stopped");
+    }
+}
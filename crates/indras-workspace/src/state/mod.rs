@@ -1,3 +1,4 @@
 pub mod workspace;
 pub mod navigation;
 pub mod editor;
+pub mod palette;
@@ -0,0 +1,88 @@
+//! Command palette state — query, ranked results, keyboard selection.
+
+use dioxus::prelude::Key;
+use indras_ui::{NavKey, Orientation};
+
+use crate::services::command_palette::{PaletteItem, PaletteMatch, search};
+
+/// Most results shown at once.
+pub const MAX_RESULTS: usize = 50;
+
+/// What a key press in the palette asks the app to do.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PaletteKey {
+    /// Not a palette key; let the input handle it.
+    Ignored,
+    /// The palette consumed the key.
+    Handled,
+    /// Run the selected entry.
+    Choose(PaletteItem),
+    /// Close the palette.
+    Close,
+}
+
+/// State of the command palette overlay.
+#[derive(Clone, Debug, Default)]
+pub struct PaletteState {
+    pub open: bool,
+    pub query: String,
+    /// Everything the palette can offer, refreshed on open.
+    pub items: Vec<PaletteItem>,
+    /// `items` ranked against `query`.
+    pub results: Vec<PaletteMatch>,
+    pub selected: usize,
+}
+
+impl PaletteState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open with an empty query and a fresh item list.
+    pub fn open_with(&mut self, items: Vec<PaletteItem>) {
+        self.open = true;
+        self.query.clear();
+        self.items = items;
+        self.refresh();
+    }
+
+    /// Close and drop the gathered items.
+    pub fn close(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Replace the query and re-rank, selecting the best match.
+    pub fn set_query(&mut self, query: String) {
+        self.query = query;
+        self.refresh();
+    }
+
+    /// The entry Enter would choose.
+    pub fn selected_item(&self) -> Option<&PaletteItem> {
+        self.results.get(self.selected).map(|m| &m.item)
+    }
+
+    /// Arrow keys, Home and End move the selection (wrapping), Enter
+    /// chooses, Escape closes.
+    pub fn handle_key(&mut self, key: &Key) -> PaletteKey {
+        if let Some(nav) = NavKey::from_key(key, Orientation::Vertical) {
+            if let Some(i) = nav.apply(Some(self.selected), self.results.len()) {
+                self.selected = i;
+            }
+            return PaletteKey::Handled;
+        }
+        match key {
+            Key::Enter => match self.selected_item() {
+                Some(item) => PaletteKey::Choose(item.clone()),
+                None => PaletteKey::Handled,
+            },
+            Key::Escape => PaletteKey::Close,
+            _ => PaletteKey::Ignored,
+        }
+    }
+
+    fn refresh(&mut self) {
+        self.results = search(&self.items, &self.query, MAX_RESULTS);
+        self.selected = 0;
+    }
+}
@@ -19,11 +19,12 @@ pub enum ViewType {
     IntentionDetail,
 }
 
-/// The four main dashboard tabs.
+/// The main dashboard tabs.
 #[derive(Clone, Debug, PartialEq, Copy)]
 pub enum DashboardTab {
     MyIntentions,
     Community,
+    Requests,
     Tokens,
     Chat,
 }
@@ -34,6 +35,7 @@ impl DashboardTab {
         match self {
             Self::MyIntentions => "My Intentions",
             Self::Community => "Community",
+            Self::Requests => "Requests",
             Self::Tokens => "Tokens",
             Self::Chat => "Chat",
         }
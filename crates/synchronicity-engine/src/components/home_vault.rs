@@ -376,6 +376,7 @@ pub fn HomeVault(
                         display_name,
                         category,
                         member_count: 0,
+                        archived: net.get_realm_by_id(rid).is_some_and(|r| r.is_archived()),
                         files,
                     });
                 }
@@ -284,6 +284,13 @@ pub fn RealmColumn(
                                             }
                                         }
                                     }
+                                    if realm.archived {
+                                        span {
+                                            class: "realm-entry-archived",
+                                            title: "Archived \u{2014} read-only",
+                                            "archived"
+                                        }
+                                    }
                                     {
                                         // Only render the sparkline if the currently-cached
                                         // braid view belongs to this realm (otherwise we'd
@@ -1204,8 +1204,16 @@ pub async fn assemble_and_authenticate(
     let my_uid = network.node().pq_identity().user_id();
     let key = recovery_protocol_key_release(&my_uid);
 
-    // Collect EncryptedStewardShare payloads from every DM realm.
-    let mut encrypted_shares = Vec::new();
+    let my_kp = {
+        let dk = network.node().pq_kem_keypair().decapsulation_key_bytes();
+        let ek = network.node().pq_kem_keypair().encapsulation_key_bytes();
+        indras_crypto::pq_kem::PQKemKeyPair::from_keypair_bytes(dk.as_slice(), &ek)
+            .map_err(|e| format!("Couldn't rebuild KEM keypair: {}", e))?
+    };
+
+    // Feed every DM realm's release into the ceremony, which decrypts
+    // each piece and refuses pieces for a different account or split.
+    let mut ceremony = indras_sync_engine::recovery_protocol::RecoveryCeremony::new();
     for realm_id in network.conversation_realms() {
         let Some(peer_mid) = network.dm_peer_for_realm(&realm_id) else {
             continue;
@@ -1220,7 +1228,7 @@ pub async fn assemble_and_authenticate(
             continue;
         };
         let snap = doc.read().await.clone();
-        if snap.approved_at_millis == 0 || snap.encrypted_share_bytes.is_empty() {
+        if !snap.is_released() {
             continue;
         }
         // Gate the release on the steward's own device roster —
@@ -1235,36 +1243,23 @@ pub async fn assemble_and_authenticate(
         {
             continue;
         }
-        encrypted_shares.push(snap.encrypted_share_bytes);
+        ceremony
+            .accept(&snap, &my_kp)
+            .map_err(|e| format!("Couldn't use a piece: {}", e))?;
     }
 
-    if encrypted_shares.len() < threshold_k as usize {
+    let need = ceremony.threshold().unwrap_or(threshold_k);
+    if !ceremony.is_ready() {
         return Err(format!(
             "Only {} friends have released a piece — need {}.",
-            encrypted_shares.len(),
-            threshold_k
+            ceremony.collected(),
+            need
         ));
     }
 
-    let my_kp = {
-        let dk = network.node().pq_kem_keypair().decapsulation_key_bytes();
-        let ek = network.node().pq_kem_keypair().encapsulation_key_bytes();
-        indras_crypto::pq_kem::PQKemKeyPair::from_keypair_bytes(dk.as_slice(), &ek)
-            .map_err(|e| format!("Couldn't rebuild KEM keypair: {}", e))?
-    };
-
-    let mut shamir_shares = Vec::with_capacity(encrypted_shares.len());
-    for bytes in &encrypted_shares {
-        let encrypted = indras_crypto::steward_share::EncryptedStewardShare::from_bytes(bytes)
-            .map_err(|e| format!("Couldn't read a piece: {}", e))?;
-        let share = encrypted
-            .decrypt(&my_kp)
-            .map_err(|e| format!("Couldn't decrypt a piece: {}", e))?;
-        shamir_shares.push(share);
-    }
-
     let data_dir = crate::state::default_data_dir();
-    let subkey = steward_recovery::recover_encryption_subkey(&shamir_shares, threshold_k)
+    let subkey = ceremony
+        .combine()
         .map_err(|e| format!("Couldn't reassemble the backup: {}", e))?;
 
     // Plan-B path: if the account published an AccountRoot envelope
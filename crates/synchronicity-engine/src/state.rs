@@ -171,6 +171,8 @@ pub struct RealmView {
     pub category: RealmCategory,
     /// Number of members in the realm.
     pub member_count: usize,
+    /// Whether the realm is archived (read-only).
+    pub archived: bool,
     /// Files in this realm (loaded lazily on accordion expand).
     pub files: Vec<FileView>,
 }
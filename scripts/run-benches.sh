@@ -0,0 +1,17 @@
+#!/usr/bin/env bash
+# Run the workspace hot-path benchmarks and write a JSONL summary.
+#
+# Usage: ./scripts/run-benches.sh [criterion filter...]
+#   ./scripts/run-benches.sh              # all groups
+#   ./scripts/run-benches.sh sync redb    # only matching benchmarks
+#
+# Results: target/criterion/report/index.html (HTML)
+#          target/criterion/summary.jsonl    (one record per benchmark)
+set -euo pipefail
+
+cd "$(dirname "$0")/.."
+
+cargo bench -p indras-benches --bench hot_paths -- "$@"
+cargo run -q -p indras-benches --bin bench-report -- target/criterion > target/criterion/summary.jsonl
+
+echo "Wrote $(wc -l < target/criterion/summary.jsonl) results to target/criterion/summary.jsonl"
@@ -103,6 +103,7 @@ quest.EVENTS = {
 
     -- Quest lifecycle
     QUEST_CREATED = "quest_created",
+    QUEST_DEPENDENCY_ADDED = "quest_dependency_added",
     QUEST_CLAIM_SUBMITTED = "quest_claim_submitted",
     QUEST_CLAIM_VERIFIED = "quest_claim_verified",
     QUEST_COMPLETED = "quest_completed",
@@ -9,11 +9,20 @@
 -- 3. Shared document editing (project plan)
 -- 4. Cross-peer synchronization
 -- 5. Document convergence verification
+--
+-- Emits realm-viewer JSONL events on stdout, so the run can be played back in
+-- the collaboration viewer:
+--   lua_runner scenarios/collaboration_trio.lua | collaboration-viewer
+
+local quest_helpers = require("lib.quest_helpers")
 
 -- Create correlation context
 local ctx = indras.correlation.new_root()
 ctx = ctx:with_tag("scenario", "collaboration_trio")
 
+-- JSONL event emitter for viewers
+local emit = quest_helpers.create_logger(ctx).event
+
 indras.log.info("Starting Collaboration Trio scenario", {
     trace_id = ctx.trace_id,
     scenario = "collaboration_trio",
@@ -117,6 +126,14 @@ function ProjectPlan:add_section(author, content)
     return section
 end
 
+function ProjectPlan:content()
+    local parts = {}
+    for _, section in ipairs(self.sections) do
+        table.insert(parts, section.content)
+    end
+    return table.concat(parts, "\n\n")
+end
+
 function ProjectPlan:section_count()
     return #self.sections
 end
@@ -174,15 +191,24 @@ indras.log.info("Phase 1: Setup - Establishing connections", {
     realm = REALM_NAME,
     members = PEER_NAMES
 })
+emit("info", { tick = sim.tick, phase = 1, message = "Phase 1: Setup" })
+emit("realm_created", {
+    tick = sim.tick,
+    realm_id = REALM_NAME,
+    members = table.concat(PEER_NAMES, ","),
+    member_count = #PEER_NAMES,
+})
 
--- Bring all peers online
-for name, peer in pairs(peer_map) do
+-- Bring all peers online (in name order, so viewers see a stable join order)
+for _, name in ipairs(PEER_NAMES) do
+    local peer = peer_map[name]
     sim:force_online(peer)
     indras.log.debug("Peer online", {
         trace_id = ctx.trace_id,
         peer = name,
         peer_id = tostring(peer)
     })
+    emit("member_joined", { tick = sim.tick, realm_id = REALM_NAME, member = name })
 end
 
 -- Verify all online
@@ -206,6 +232,7 @@ indras.log.info("Phase 1 complete: All peers connected", {
 indras.log.info("Phase 2: Quest creation - Each peer creates 2 quests", {
     trace_id = ctx.trace_id
 })
+emit("info", { tick = sim.tick, phase = 2, message = "Phase 2: Quest Creation" })
 
 -- Define quests to create
 local quests_to_create = {
@@ -237,6 +264,14 @@ for _, q in ipairs(quests_to_create) do
         creator = q.creator,
         assignee = q.assignee
     })
+    emit("quest_created", {
+        tick = sim.tick,
+        realm_id = REALM_NAME,
+        quest_id = tostring(quest.id),
+        creator = q.creator,
+        title = quest.title,
+        assignee = q.assignee,
+    })
 end
 
 -- Sync after quest creation
@@ -257,6 +292,7 @@ indras.log.info("Phase 3: Document collaboration - Building project plan", {
     trace_id = ctx.trace_id,
     document = project_plan.title
 })
+emit("info", { tick = sim.tick, phase = 3, message = "Phase 3: Document Collaboration" })
 
 -- Each peer adds content to the project plan
 local contributions = {
@@ -295,6 +331,14 @@ for _, contrib in ipairs(contributions) do
         author = contrib.author,
         content_length = string.len(contrib.content)
     })
+    emit("document_edit", {
+        tick = sim.tick,
+        realm_id = REALM_NAME,
+        document_id = "project_plan",
+        editor = contrib.author,
+        content = project_plan:content(),
+        title = project_plan.title,
+    })
 
     -- Sync between each contribution
     sim:run_ticks(SYNC_TICKS)
@@ -315,6 +359,7 @@ indras.log.info("Phase 3 complete: Project plan collaboratively written", {
 indras.log.info("Phase 4: Quest updates - Progressing and completing quests", {
     trace_id = ctx.trace_id
 })
+emit("info", { tick = sim.tick, phase = 4, message = "Phase 4: Quest Updates" })
 
 -- Move some quests to in_progress
 local in_progress_updates = {1, 3, 5}
@@ -338,6 +383,12 @@ for _, quest_id in ipairs(in_progress_updates) do
             title = quest.title,
             assignee = quest.assignee
         })
+        emit("quest_claim_submitted", {
+            tick = sim.tick,
+            realm_id = REALM_NAME,
+            quest_id = tostring(quest_id),
+            claimant = quest.assignee,
+        })
     end
 end
 
@@ -365,6 +416,11 @@ for _, quest_id in ipairs(completed_updates) do
             title = quest.title,
             completed_by = quest.assignee
         })
+        emit("quest_completed", {
+            tick = sim.tick,
+            realm_id = REALM_NAME,
+            quest_id = tostring(quest_id),
+        })
     end
 end
 
@@ -385,6 +441,7 @@ indras.log.info("Phase 4 complete: Quest statuses updated", {
 indras.log.info("Phase 5: Verification - Checking document convergence", {
     trace_id = ctx.trace_id
 })
+emit("info", { tick = sim.tick, phase = 5, message = "Phase 5: Verification" })
 
 -- Verify quest log
 indras.assert.eq(quest_log:count(), 6, "Should have 6 quests total")
@@ -403,6 +460,12 @@ indras.assert.gt(stats.messages_sent, 0, "Messages should have been sent")
 -- Calculate convergence (all messages delivered)
 local delivery_rate = stats:delivery_rate()
 
+emit("crdt_converged", {
+    tick = sim.tick,
+    folder_id = "project_plan",
+    members_synced = #PEER_NAMES,
+})
+
 indras.log.info("Phase 5 complete: Verification passed", {
     trace_id = ctx.trace_id,
     quests_verified = quest_log:count(),
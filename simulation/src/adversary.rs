@@ -0,0 +1,307 @@
+//! Adversarial peers and misbehavior detection
+//!
+//! Peers listed in [`SimConfig::adversaries`](crate::SimConfig::adversaries)
+//! misbehave in the ways real attackers would:
+//!
+//! - **Replay**: re-send every packet they forward, unchanged
+//! - **Equivocate**: forward a tampered copy under the same packet id to a
+//!   second relay, so different peers see different contents
+//! - **Drop acks**: swallow back-propagation confirmations passing through
+//! - **Spam**: flood random peers with junk messages every tick
+//!
+//! Honest peers run a [`MisbehaviorDetector`]: they reject packet ids they
+//! have already accepted, flag conflicting contents for the same id, blame
+//! stalled confirmations on the peer holding them, and (with
+//! [`SimConfig::send_rate_limit`](crate::SimConfig::send_rate_limit)) drop
+//! sends beyond the limit. Every detection is recorded as a
+//! [`NetworkEvent::Misbehavior`](crate::NetworkEvent::Misbehavior) and
+//! counted against the suspect.
+
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::hash::{Hash, Hasher};
+
+use crate::types::{PacketId, PeerId, SealedPacket};
+
+/// One way an adversarial peer misbehaves
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdversaryBehavior {
+    /// Re-send each forwarded packet `copies` extra times
+    Replay { copies: u32 },
+    /// Forward a tampered copy alongside the real one
+    Equivocate,
+    /// Swallow delivery confirmations instead of passing them back
+    DropAcks,
+    /// Send `per_tick` junk messages to random peers every tick
+    Spam { per_tick: u32 },
+}
+
+/// Kinds of misbehavior the detector reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Misbehavior {
+    /// Re-sent a packet the receiver had already accepted
+    Replay,
+    /// Sent conflicting contents under one packet id
+    Equivocation,
+    /// Held a delivery confirmation until it timed out
+    DroppedAck,
+    /// Exceeded the per-tick send limit
+    Spam,
+}
+
+impl Misbehavior {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Misbehavior::Replay => "Replay",
+            Misbehavior::Equivocation => "Equivocation",
+            Misbehavior::DroppedAck => "DroppedAck",
+            Misbehavior::Spam => "Spam",
+        }
+    }
+}
+
+impl std::fmt::Display for Misbehavior {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Which peers are adversarial and how
+#[derive(Debug, Clone, Default)]
+pub struct Adversaries {
+    behaviors: BTreeMap<PeerId, Vec<AdversaryBehavior>>,
+}
+
+impl Adversaries {
+    /// Make `peer` misbehave in one more way
+    pub fn add(&mut self, peer: PeerId, behavior: AdversaryBehavior) {
+        let behaviors = self.behaviors.entry(peer).or_default();
+        if !behaviors.contains(&behavior) {
+            behaviors.push(behavior);
+        }
+    }
+
+    /// Make `peer` honest again
+    pub fn remove(&mut self, peer: PeerId) {
+        self.behaviors.remove(&peer);
+    }
+
+    pub fn is_adversary(&self, peer: PeerId) -> bool {
+        self.behaviors.contains_key(&peer)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.behaviors.is_empty()
+    }
+
+    /// Adversarial peers, sorted
+    pub fn peers(&self) -> Vec<PeerId> {
+        self.behaviors.keys().copied().collect()
+    }
+
+    pub fn behaviors(&self, peer: PeerId) -> &[AdversaryBehavior] {
+        self.behaviors.get(&peer).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Extra copies `peer` sends of each packet it forwards
+    pub fn replay_copies(&self, peer: PeerId) -> u32 {
+        self.behaviors(peer)
+            .iter()
+            .map(|b| match b {
+                AdversaryBehavior::Replay { copies } => *copies,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    pub fn equivocates(&self, peer: PeerId) -> bool {
+        self.behaviors(peer).contains(&AdversaryBehavior::Equivocate)
+    }
+
+    pub fn drops_acks(&self, peer: PeerId) -> bool {
+        self.behaviors(peer).contains(&AdversaryBehavior::DropAcks)
+    }
+
+    /// Junk messages `peer` sends per tick
+    pub fn spam_rate(&self, peer: PeerId) -> u32 {
+        self.behaviors(peer)
+            .iter()
+            .map(|b| match b {
+                AdversaryBehavior::Spam { per_tick } => *per_tick,
+                _ => 0,
+            })
+            .sum()
+    }
+}
+
+/// Result of checking a packet arriving at an honest peer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Arrival {
+    /// First time this peer sees the packet id
+    Fresh,
+    /// Same id and contents as a packet already accepted
+    Replay,
+    /// Same id as a packet already accepted but different contents;
+    /// carries the peers on both paths (the likely equivocators)
+    Equivocation(BTreeSet<PeerId>),
+}
+
+#[derive(Debug, Clone)]
+struct Accepted {
+    digest: u64,
+    path: BTreeSet<PeerId>,
+}
+
+/// Misbehavior bookkeeping for honest peers
+#[derive(Debug, Clone, Default)]
+pub struct MisbehaviorDetector {
+    accepted: HashMap<(PeerId, PacketId), Accepted>,
+    reports: BTreeMap<PeerId, BTreeMap<Misbehavior, u64>>,
+}
+
+fn digest(payload: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    payload.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl MisbehaviorDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check a packet arriving at `at`, remembering it if fresh
+    pub fn check_arrival(&mut self, at: PeerId, packet: &SealedPacket) -> Arrival {
+        let digest = digest(&packet.payload);
+        match self.accepted.get(&(at, packet.id)) {
+            None => {
+                self.accepted.insert(
+                    (at, packet.id),
+                    Accepted {
+                        digest,
+                        path: packet.visited.clone(),
+                    },
+                );
+                Arrival::Fresh
+            }
+            Some(first) if first.digest == digest => Arrival::Replay,
+            Some(first) => {
+                // Peers on both paths forked the packet; the source is only
+                // blamed when nobody else is common to both
+                let common: BTreeSet<PeerId> =
+                    first.path.intersection(&packet.visited).copied().collect();
+                let relays: BTreeSet<PeerId> = common
+                    .iter()
+                    .copied()
+                    .filter(|p| *p != packet.source)
+                    .collect();
+                Arrival::Equivocation(if relays.is_empty() { common } else { relays })
+            }
+        }
+    }
+
+    /// Count one misbehavior against `suspect`
+    pub fn report(&mut self, suspect: PeerId, kind: Misbehavior) {
+        *self
+            .reports
+            .entry(suspect)
+            .or_default()
+            .entry(kind)
+            .or_default() += 1;
+    }
+
+    /// How often `suspect` was caught doing `kind`
+    pub fn count(&self, suspect: PeerId, kind: Misbehavior) -> u64 {
+        self.reports
+            .get(&suspect)
+            .and_then(|r| r.get(&kind))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// All reports, by suspect
+    pub fn reports(&self) -> &BTreeMap<PeerId, BTreeMap<Misbehavior, u64>> {
+        &self.reports
+    }
+
+    /// Peers reported at least once, sorted
+    pub fn suspects(&self) -> Vec<PeerId> {
+        self.reports.keys().copied().collect()
+    }
+}
+
+/// Flip every payload byte so the tampered copy differs from the original
+pub fn tamper(packet: &SealedPacket) -> SealedPacket {
+    let mut forged = packet.clone();
+    if forged.payload.is_empty() {
+        forged.payload.push(0xff);
+    } else {
+        for byte in &mut forged.payload {
+            *byte = !*byte;
+        }
+    }
+    forged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packet(payload: &[u8]) -> SealedPacket {
+        SealedPacket::new(
+            PacketId {
+                source: PeerId('A'),
+                sequence: 1,
+            },
+            PeerId('A'),
+            PeerId('D'),
+            payload.to_vec(),
+            BTreeSet::new(),
+            0,
+        )
+    }
+
+    #[test]
+    fn test_behaviors_accumulate() {
+        let mut adversaries = Adversaries::default();
+        adversaries.add(PeerId('E'), AdversaryBehavior::Replay { copies: 2 });
+        adversaries.add(PeerId('E'), AdversaryBehavior::DropAcks);
+        adversaries.add(PeerId('E'), AdversaryBehavior::DropAcks);
+
+        assert_eq!(adversaries.behaviors(PeerId('E')).len(), 2);
+        assert_eq!(adversaries.replay_copies(PeerId('E')), 2);
+        assert!(adversaries.drops_acks(PeerId('E')));
+        assert!(!adversaries.equivocates(PeerId('E')));
+        assert_eq!(adversaries.spam_rate(PeerId('A')), 0);
+
+        adversaries.remove(PeerId('E'));
+        assert!(adversaries.is_empty());
+    }
+
+    #[test]
+    fn test_replay_and_equivocation_detection() {
+        let mut detector = MisbehaviorDetector::new();
+        let mut original = packet(b"pay B");
+        original.mark_visited(PeerId('E'));
+        original.mark_visited(PeerId('B'));
+
+        assert_eq!(detector.check_arrival(PeerId('D'), &original), Arrival::Fresh);
+        assert_eq!(detector.check_arrival(PeerId('D'), &original), Arrival::Replay);
+        // Other peers track their own arrivals
+        assert_eq!(detector.check_arrival(PeerId('B'), &original), Arrival::Fresh);
+
+        let mut forged = tamper(&packet(b"pay B"));
+        forged.mark_visited(PeerId('E'));
+        forged.mark_visited(PeerId('C'));
+        assert_eq!(
+            detector.check_arrival(PeerId('D'), &forged),
+            Arrival::Equivocation(BTreeSet::from([PeerId('E')]))
+        );
+
+        detector.report(PeerId('E'), Misbehavior::Equivocation);
+        detector.report(PeerId('E'), Misbehavior::Equivocation);
+        assert_eq!(detector.count(PeerId('E'), Misbehavior::Equivocation), 2);
+        assert_eq!(detector.suspects(), vec![PeerId('E')]);
+    }
+}
@@ -3,8 +3,14 @@
 //! This module provides trait implementations that allow the simulation
 //! to use the real routing and storage implementations from indras-routing
 //! and indras-storage.
+//!
+//! It also plays simulated adversaries against the real CRDT layer
+//! ([`play_crdt_equivocation`]) to check the protocol resists them.
+
+use std::collections::HashMap;
 
-use indras_core::{NetworkTopology, SimulationIdentity};
+use indras_core::{EventId, InterfaceEvent, NetworkTopology, SimulationIdentity};
+use indras_sync::{InterfaceDocument, SyncError};
 
 use crate::topology::Mesh;
 use crate::types::PeerId;
@@ -106,6 +112,108 @@ pub type SimulationRouter = indras_routing::StoreForwardRouter<
     indras_storage::InMemoryPacketStore<SimulationIdentity>,
 >;
 
+/// What honest replicas saw after an equivocating write
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrdtEquivocationOutcome {
+    /// Every honest replica ended with the same heads
+    pub converged: bool,
+    /// Events each replica holds after syncing
+    pub event_count: usize,
+    /// Event ids that appeared with more than one content
+    pub conflicting_events: Vec<EventId>,
+    /// Senders of the conflicting events
+    pub equivocators: Vec<PeerId>,
+}
+
+/// Play an equivocating writer against the real [`InterfaceDocument`]
+///
+/// `adversary` writes one event per honest peer, all with the same event id
+/// but different content, into the copy it hands that peer. Each honest peer
+/// also writes an event of its own. The honest replicas then sync with each
+/// other. The CRDT should converge with every version present, so the
+/// conflict is visible to everyone rather than splitting the group.
+pub fn play_crdt_equivocation(
+    adversary: PeerId,
+    honest: &[PeerId],
+) -> Result<CrdtEquivocationOutcome, SyncError> {
+    let adversary_id: SimulationIdentity = adversary.into();
+    let mut base = InterfaceDocument::new();
+    base.add_member(&adversary_id);
+    for peer in honest {
+        base.add_member(&SimulationIdentity::from(*peer));
+    }
+
+    let mut replicas = Vec::with_capacity(honest.len());
+    for peer in honest {
+        let peer_id: SimulationIdentity = (*peer).into();
+        let mut replica = base.fork()?;
+        let forged =
+            InterfaceEvent::message(adversary_id, 1, format!("for {}", peer).into_bytes());
+        replica.append_event(&forged)?;
+        replica.append_event(&InterfaceEvent::message(peer_id, 1, b"honest".to_vec()))?;
+        replicas.push(replica);
+    }
+
+    // Full pairwise sync among honest peers
+    for i in 0..replicas.len() {
+        for j in 0..replicas.len() {
+            if i != j {
+                let mut other = replicas[j].fork()?;
+                replicas[i].merge(&mut other)?;
+            }
+        }
+    }
+
+    let heads: Vec<_> = replicas.iter_mut().map(|r| r.get_heads()).collect();
+    let converged = heads.windows(2).all(|w| {
+        let (mut a, mut b) = (w[0].clone(), w[1].clone());
+        a.sort();
+        b.sort();
+        a == b
+    });
+
+    let events: Vec<InterfaceEvent<SimulationIdentity>> = replicas
+        .first()
+        .map(|r| r.events())
+        .unwrap_or_default();
+    let mut by_id: HashMap<EventId, (SimulationIdentity, Vec<Vec<u8>>)> = HashMap::new();
+    for event in &events {
+        if let InterfaceEvent::Message {
+            id,
+            sender,
+            content,
+            ..
+        } = event
+        {
+            let (_, contents) = by_id.entry(*id).or_insert_with(|| (*sender, Vec::new()));
+            if !contents.contains(content) {
+                contents.push(content.clone());
+            }
+        }
+    }
+
+    let mut conflicting_events = Vec::new();
+    let mut equivocators = Vec::new();
+    for (id, (sender, contents)) in by_id {
+        if contents.len() > 1 {
+            conflicting_events.push(id);
+            let sender: PeerId = sender.into();
+            if !equivocators.contains(&sender) {
+                equivocators.push(sender);
+            }
+        }
+    }
+    conflicting_events.sort();
+    equivocators.sort();
+
+    Ok(CrdtEquivocationOutcome {
+        converged,
+        event_count: events.len(),
+        conflicting_events,
+        equivocators,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -235,4 +343,18 @@ mod tests {
         let neighbors = mesh.neighbors_sim(&b);
         assert_eq!(neighbors.len(), 2);
     }
+
+    #[test]
+    fn test_crdt_equivocation_is_exposed_not_split() {
+        let outcome =
+            play_crdt_equivocation(PeerId('E'), &[PeerId('A'), PeerId('B'), PeerId('C')])
+                .unwrap();
+
+        assert!(outcome.converged);
+        // Three forged versions plus one honest event per peer
+        assert_eq!(outcome.event_count, 6);
+        assert_eq!(outcome.conflicting_events.len(), 1);
+        assert_eq!(outcome.equivocators, vec![PeerId('E')]);
+    }
+
 }
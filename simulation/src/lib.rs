@@ -20,6 +20,8 @@
 //! - **Types** (`types.rs`): Core data structures (PeerId, SealedPacket, NetworkEvent)
 //! - **Topology** (`topology.rs`): Mesh network construction (ring, full, random, etc.)
 //! - **Simulation** (`simulation.rs`): Discrete-time simulation engine
+//! - **Link model** (`link_model.rs`): Per-edge latency, jitter, bandwidth, and loss
+//! - **Adversaries** (`adversary.rs`): Replaying, equivocating, ack-dropping, and spamming peers
 //! - **Scenarios** (`scenarios.rs`): Pre-built test scenarios
 //!
 //! ## Example: A-B-C Scenario
@@ -68,7 +70,9 @@
 //! 3. **Sealed packets**: Messages to offline peers are sealed (encrypted for destination)
 //! 4. **Back-propagation**: Delivery confirmations travel back through the relay path
 
+pub mod adversary;
 pub mod bridge;
+pub mod link_model;
 pub mod lua;
 pub mod scenarios;
 pub mod simulation;
@@ -88,7 +92,11 @@ pub use topology::{Mesh, MeshBuilder, from_edges};
 
 pub use simulation::{SimConfig, SimStats, Simulation};
 
-pub use bridge::{MeshBridge, SimulationRouter};
+pub use link_model::{LinkModel, LinkProfile};
+
+pub use adversary::{Adversaries, AdversaryBehavior, Misbehavior, MisbehaviorDetector};
+
+pub use bridge::{CrdtEquivocationOutcome, MeshBridge, SimulationRouter, play_crdt_equivocation};
 
 // Re-export Lua runtime
 pub use lua::LuaRuntime;
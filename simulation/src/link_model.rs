@@ -0,0 +1,282 @@
+//! Link characteristics for simulated connections
+//!
+//! Every hop a packet takes to its destination crosses a link with a
+//! [`LinkProfile`]: one-way latency, jitter, bandwidth, and loss. A
+//! [`LinkModel`] holds a default profile, per-edge overrides, and optional
+//! peer positions; when both ends of an edge are placed, propagation delay
+//! for the great-circle distance between them is added on top.
+//!
+//! The default model is [`LinkProfile::ideal`], which costs nothing and never
+//! loses packets, so simulations that don't configure links behave exactly
+//! as before.
+
+use rand::Rng;
+use std::collections::HashMap;
+
+use crate::types::PeerId;
+
+/// Signal speed in fiber, in km per millisecond (about 2/3 of c)
+const FIBER_KM_PER_MS: f64 = 200.0;
+
+/// Mean Earth radius in km
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Latency, jitter, bandwidth, and loss for one link
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinkProfile {
+    /// One-way base latency in milliseconds
+    pub latency_ms: f64,
+    /// Maximum deviation from the base latency in milliseconds (uniform)
+    pub jitter_ms: f64,
+    /// Throughput in kilobits per second (0 = unlimited)
+    pub bandwidth_kbps: u64,
+    /// Probability a transmission is lost and must be retried
+    pub loss_probability: f64,
+}
+
+impl Default for LinkProfile {
+    fn default() -> Self {
+        Self::ideal()
+    }
+}
+
+impl LinkProfile {
+    /// Instant, unlimited, lossless link
+    pub fn ideal() -> Self {
+        Self {
+            latency_ms: 0.0,
+            jitter_ms: 0.0,
+            bandwidth_kbps: 0,
+            loss_probability: 0.0,
+        }
+    }
+
+    /// Same-rack link between servers
+    pub fn datacenter() -> Self {
+        Self {
+            latency_ms: 0.5,
+            jitter_ms: 0.1,
+            bandwidth_kbps: 10_000_000,
+            loss_probability: 0.0,
+        }
+    }
+
+    /// Home fiber or cable connection
+    pub fn broadband() -> Self {
+        Self {
+            latency_ms: 20.0,
+            jitter_ms: 5.0,
+            bandwidth_kbps: 50_000,
+            loss_probability: 0.001,
+        }
+    }
+
+    /// Congested cellular link at the edge of coverage
+    pub fn rural_lte() -> Self {
+        Self {
+            latency_ms: 80.0,
+            jitter_ms: 40.0,
+            bandwidth_kbps: 2_000,
+            loss_probability: 0.02,
+        }
+    }
+
+    /// Geostationary satellite link
+    pub fn satellite() -> Self {
+        Self {
+            latency_ms: 300.0,
+            jitter_ms: 50.0,
+            bandwidth_kbps: 10_000,
+            loss_probability: 0.01,
+        }
+    }
+
+    /// Names accepted by [`LinkProfile::preset`]
+    pub const PRESETS: &'static [&'static str] =
+        &["ideal", "datacenter", "broadband", "rural_lte", "satellite"];
+
+    /// Look up a preset by name
+    ///
+    /// Case-insensitive; spaces and dashes count as underscores, so
+    /// `"rural LTE"` and `"rural-lte"` both work.
+    pub fn preset(name: &str) -> Option<Self> {
+        let name = name.trim().to_ascii_lowercase().replace([' ', '-'], "_");
+        match name.as_str() {
+            "ideal" => Some(Self::ideal()),
+            "datacenter" => Some(Self::datacenter()),
+            "broadband" => Some(Self::broadband()),
+            "rural_lte" => Some(Self::rural_lte()),
+            "satellite" => Some(Self::satellite()),
+            _ => None,
+        }
+    }
+
+    /// Whether this link costs nothing and never loses packets
+    pub fn is_ideal(&self) -> bool {
+        self.latency_ms <= 0.0
+            && self.jitter_ms <= 0.0
+            && self.bandwidth_kbps == 0
+            && self.loss_probability <= 0.0
+    }
+
+    /// Sample the time to move `payload_len` bytes across the link, in ms
+    pub fn transit_millis(&self, payload_len: usize, rng: &mut impl Rng) -> f64 {
+        let jitter = if self.jitter_ms > 0.0 {
+            rng.random_range(-self.jitter_ms..=self.jitter_ms)
+        } else {
+            0.0
+        };
+        // kbps is bits per millisecond
+        let serialization = if self.bandwidth_kbps > 0 {
+            (payload_len as f64 * 8.0) / self.bandwidth_kbps as f64
+        } else {
+            0.0
+        };
+        (self.latency_ms + jitter).max(0.0) + serialization
+    }
+
+    /// Sample whether one transmission is lost
+    pub fn sample_loss(&self, rng: &mut impl Rng) -> bool {
+        self.loss_probability > 0.0 && rng.random::<f64>() < self.loss_probability
+    }
+}
+
+/// Link profiles for every edge in a mesh
+///
+/// Links are undirected: the profile for A–B is the profile for B–A.
+#[derive(Debug, Clone, Default)]
+pub struct LinkModel {
+    /// Profile for edges without an override
+    pub default: LinkProfile,
+    edges: HashMap<(PeerId, PeerId), LinkProfile>,
+    locations: HashMap<PeerId, (f64, f64)>,
+}
+
+fn edge_key(a: PeerId, b: PeerId) -> (PeerId, PeerId) {
+    if a <= b { (a, b) } else { (b, a) }
+}
+
+impl LinkModel {
+    /// Model where every edge uses `default`
+    pub fn uniform(default: LinkProfile) -> Self {
+        Self {
+            default,
+            ..Default::default()
+        }
+    }
+
+    /// Override the profile for one edge
+    pub fn set_edge(&mut self, a: PeerId, b: PeerId, profile: LinkProfile) {
+        self.edges.insert(edge_key(a, b), profile);
+    }
+
+    /// Remove an edge override, returning it to the default profile
+    pub fn clear_edge(&mut self, a: PeerId, b: PeerId) {
+        self.edges.remove(&edge_key(a, b));
+    }
+
+    /// Place a peer for geographic propagation delay
+    pub fn set_location(&mut self, peer: PeerId, lat: f64, lng: f64) {
+        self.locations.insert(peer, (lat, lng));
+    }
+
+    /// Where a peer was placed, if anywhere
+    pub fn location(&self, peer: PeerId) -> Option<(f64, f64)> {
+        self.locations.get(&peer).copied()
+    }
+
+    /// Great-circle distance between two placed peers, in km
+    pub fn distance_km(&self, a: PeerId, b: PeerId) -> Option<f64> {
+        let (lat1, lng1) = self.location(a)?;
+        let (lat2, lng2) = self.location(b)?;
+        let (phi1, phi2) = (lat1.to_radians(), lat2.to_radians());
+        let dphi = (lat2 - lat1).to_radians();
+        let dlambda = (lng2 - lng1).to_radians();
+        let h = (dphi / 2.0).sin().powi(2)
+            + phi1.cos() * phi2.cos() * (dlambda / 2.0).sin().powi(2);
+        Some(2.0 * EARTH_RADIUS_KM * h.sqrt().asin())
+    }
+
+    /// Effective profile for the edge between `a` and `b`
+    ///
+    /// The edge override (or default) plus propagation delay when both
+    /// peers have been placed.
+    pub fn profile(&self, a: PeerId, b: PeerId) -> LinkProfile {
+        let mut profile = self
+            .edges
+            .get(&edge_key(a, b))
+            .copied()
+            .unwrap_or(self.default);
+        if let Some(km) = self.distance_km(a, b) {
+            profile.latency_ms += km / FIBER_KM_PER_MS;
+        }
+        profile
+    }
+
+    /// Whether no edge can add delay or loss
+    pub fn is_ideal(&self) -> bool {
+        self.default.is_ideal()
+            && self.edges.values().all(LinkProfile::is_ideal)
+            && self.locations.len() < 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preset_names() {
+        for name in LinkProfile::PRESETS {
+            assert!(LinkProfile::preset(name).is_some(), "{name}");
+        }
+        assert_eq!(LinkProfile::preset("rural LTE"), Some(LinkProfile::rural_lte()));
+        assert_eq!(LinkProfile::preset("Rural-LTE"), Some(LinkProfile::rural_lte()));
+        assert_eq!(LinkProfile::preset("carrier pigeon"), None);
+        assert!(LinkProfile::default().is_ideal());
+        assert!(!LinkProfile::satellite().is_ideal());
+    }
+
+    #[test]
+    fn test_transit_includes_serialization() {
+        let mut rng = rand::rng();
+        let link = LinkProfile {
+            latency_ms: 10.0,
+            jitter_ms: 0.0,
+            bandwidth_kbps: 8,
+            loss_probability: 0.0,
+        };
+        // 100 bytes at 8 kbps = 100 ms on the wire
+        assert!((link.transit_millis(100, &mut rng) - 110.0).abs() < 1e-9);
+        assert!(!link.sample_loss(&mut rng));
+
+        let jittery = LinkProfile::satellite();
+        for _ in 0..100 {
+            let t = jittery.transit_millis(0, &mut rng);
+            assert!((250.0..=350.0).contains(&t));
+        }
+    }
+
+    #[test]
+    fn test_edges_are_undirected_and_geo_adds_delay() {
+        let (a, b, c) = (PeerId('A'), PeerId('B'), PeerId('C'));
+        let mut model = LinkModel::default();
+        assert!(model.is_ideal());
+
+        model.set_edge(b, a, LinkProfile::broadband());
+        assert_eq!(model.profile(a, b), LinkProfile::broadband());
+        assert_eq!(model.profile(a, c), LinkProfile::ideal());
+
+        // London to New York is ~5570 km, ~28 ms in fiber
+        model.set_location(a, 51.5074, -0.1278);
+        model.set_location(c, 40.7128, -74.0060);
+        let km = model.distance_km(a, c).unwrap();
+        assert!((5500.0..5650.0).contains(&km));
+        let latency = model.profile(c, a).latency_ms;
+        assert!((27.0..29.0).contains(&latency));
+        assert!(!model.is_ideal());
+
+        model.clear_edge(a, b);
+        assert_eq!(model.profile(a, b), LinkProfile::ideal());
+    }
+}
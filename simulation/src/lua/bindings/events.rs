@@ -167,6 +167,22 @@ pub fn network_event_to_table(lua: &Lua, event: &NetworkEvent) -> Result<Table>
             t.set("reason", reason.clone())?;
             t.set("tick", *tick)?;
         }
+        NetworkEvent::Misbehavior {
+            suspect,
+            observer,
+            kind,
+            packet_id,
+            tick,
+        } => {
+            t.set("type", "Misbehavior")?;
+            t.set("suspect", suspect.to_string())?;
+            t.set("observer", observer.to_string())?;
+            t.set("kind", kind.as_str())?;
+            if let Some(packet_id) = packet_id {
+                t.set("packet_id", packet_id.to_string())?;
+            }
+            t.set("tick", *tick)?;
+        }
     }
 
     Ok(t)
@@ -180,6 +196,7 @@ fn drop_reason_to_string(reason: &DropReason) -> &'static str {
         DropReason::Duplicate => "Duplicate",
         DropReason::Expired => "Expired",
         DropReason::SenderOffline => "SenderOffline",
+        DropReason::RateLimited => "RateLimited",
     }
 }
 
@@ -203,6 +220,7 @@ pub fn register(lua: &Lua, indras: &Table) -> Result<()> {
     events.set("INVITE_CREATED", "InviteCreated")?;
     events.set("INVITE_ACCEPTED", "InviteAccepted")?;
     events.set("INVITE_FAILED", "InviteFailed")?;
+    events.set("MISBEHAVIOR", "Misbehavior")?;
 
     // Drop reason constants
     let drop_reasons = lua.create_table()?;
@@ -211,6 +229,7 @@ pub fn register(lua: &Lua, indras: &Table) -> Result<()> {
     drop_reasons.set("DUPLICATE", "Duplicate")?;
     drop_reasons.set("EXPIRED", "Expired")?;
     drop_reasons.set("SENDER_OFFLINE", "SenderOffline")?;
+    drop_reasons.set("RATE_LIMITED", "RateLimited")?;
     events.set("DropReason", drop_reasons)?;
 
     indras.set("events", events)?;
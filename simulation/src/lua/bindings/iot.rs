@@ -357,6 +357,10 @@ impl FromLua for LuaCompactMessageType {
                     "presence" | "Presence" => {
                         Ok(LuaCompactMessageType(CompactMessageType::Presence))
                     }
+                    "hello" | "Hello" => Ok(LuaCompactMessageType(CompactMessageType::Hello)),
+                    "batch_signature" | "BatchSignature" => {
+                        Ok(LuaCompactMessageType(CompactMessageType::BatchSignature))
+                    }
                     other => Err(mlua::Error::external(format!(
                         "Unknown message type: {}",
                         other
@@ -381,6 +385,8 @@ impl UserData for LuaCompactMessageType {
                 CompactMessageType::SyncRequest => "sync_request",
                 CompactMessageType::SyncResponse => "sync_response",
                 CompactMessageType::Presence => "presence",
+                CompactMessageType::Hello => "hello",
+                CompactMessageType::BatchSignature => "batch_signature",
             })
         });
     }
@@ -395,6 +401,8 @@ impl UserData for LuaCompactMessageType {
                 CompactMessageType::SyncRequest => "sync_request",
                 CompactMessageType::SyncResponse => "sync_response",
                 CompactMessageType::Presence => "presence",
+                CompactMessageType::Hello => "hello",
+                CompactMessageType::BatchSignature => "batch_signature",
             })
         });
 
@@ -973,6 +981,14 @@ pub fn register(lua: &Lua, indras: &Table) -> Result<()> {
         "presence",
         lua.create_function(|_, ()| Ok(LuaCompactMessageType(CompactMessageType::Presence)))?,
     )?;
+    msg_type.set(
+        "hello",
+        lua.create_function(|_, ()| Ok(LuaCompactMessageType(CompactMessageType::Hello)))?,
+    )?;
+    msg_type.set(
+        "batch_signature",
+        lua.create_function(|_, ()| Ok(LuaCompactMessageType(CompactMessageType::BatchSignature)))?,
+    )?;
     iot.set("MessageType", msg_type)?;
 
     // =================================
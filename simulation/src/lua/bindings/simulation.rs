@@ -6,12 +6,92 @@ use mlua::{FromLua, Lua, MetaMethod, Result, Table, UserData, UserDataMethods, V
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use crate::adversary::AdversaryBehavior;
+use crate::bridge::play_crdt_equivocation;
+use crate::link_model::LinkProfile;
 use crate::simulation::{SimConfig, Simulation};
 
 use super::mesh::LuaMesh;
 use super::stats::LuaSimStats;
 use super::types::LuaPeerId;
 
+/// Read a link profile from a preset name or a table
+///
+/// Tables may name a `preset` to start from and override `latency_ms`,
+/// `jitter_ms`, `bandwidth_kbps`, or `loss_probability`.
+fn link_profile_from_lua(value: Value) -> Result<LinkProfile> {
+    let unknown = |name: &str| {
+        mlua::Error::external(format!(
+            "Unknown link preset '{}' (expected one of: {})",
+            name,
+            LinkProfile::PRESETS.join(", ")
+        ))
+    };
+    match value {
+        Value::String(s) => {
+            let name = s.to_str()?.to_string();
+            LinkProfile::preset(&name).ok_or_else(|| unknown(&name))
+        }
+        Value::Table(t) => {
+            let mut profile = match t.get::<Option<String>>("preset")? {
+                Some(name) => LinkProfile::preset(&name).ok_or_else(|| unknown(&name))?,
+                None => LinkProfile::ideal(),
+            };
+            if let Ok(v) = t.get::<f64>("latency_ms") {
+                profile.latency_ms = v;
+            }
+            if let Ok(v) = t.get::<f64>("jitter_ms") {
+                profile.jitter_ms = v;
+            }
+            if let Ok(v) = t.get::<u64>("bandwidth_kbps") {
+                profile.bandwidth_kbps = v;
+            }
+            if let Ok(v) = t.get::<f64>("loss_probability") {
+                profile.loss_probability = v;
+            }
+            Ok(profile)
+        }
+        _ => Err(mlua::Error::external(
+            "Link profile must be a preset name or table",
+        )),
+    }
+}
+
+/// Read an adversary behavior from a name or a table
+///
+/// Names: `"replay"`, `"equivocate"`, `"drop_acks"`, `"spam"`. Tables name
+/// the `behavior` and may set `copies` (replay, default 1) or `per_tick`
+/// (spam, default 5).
+fn adversary_behavior_from_lua(value: Value) -> Result<AdversaryBehavior> {
+    let (name, copies, per_tick) = match value {
+        Value::String(s) => (s.to_str()?.to_string(), None, None),
+        Value::Table(t) => (
+            t.get::<String>("behavior")?,
+            t.get::<Option<u32>>("copies")?,
+            t.get::<Option<u32>>("per_tick")?,
+        ),
+        _ => {
+            return Err(mlua::Error::external(
+                "Adversary behavior must be a name or table",
+            ));
+        }
+    };
+    match name.as_str() {
+        "replay" => Ok(AdversaryBehavior::Replay {
+            copies: copies.unwrap_or(1),
+        }),
+        "equivocate" => Ok(AdversaryBehavior::Equivocate),
+        "drop_acks" => Ok(AdversaryBehavior::DropAcks),
+        "spam" => Ok(AdversaryBehavior::Spam {
+            per_tick: per_tick.unwrap_or(5),
+        }),
+        other => Err(mlua::Error::external(format!(
+            "Unknown adversary behavior '{}' (expected replay, equivocate, drop_acks, or spam)",
+            other
+        ))),
+    }
+}
+
 /// Lua wrapper for SimConfig
 #[derive(Debug, Clone)]
 pub struct LuaSimConfig(pub SimConfig);
@@ -40,6 +120,8 @@ impl UserData for LuaSimConfig {
             Ok(this.0.initial_online_probability)
         });
         fields.add_field_method_get("trace_routing", |_, this| Ok(this.0.trace_routing));
+        fields.add_field_method_get("tick_millis", |_, this| Ok(this.0.tick_millis));
+        fields.add_field_method_get("send_rate_limit", |_, this| Ok(this.0.send_rate_limit));
 
         fields.add_field_method_set("wake_probability", |_, this, val: f64| {
             this.0.wake_probability = val;
@@ -61,9 +143,52 @@ impl UserData for LuaSimConfig {
             this.0.trace_routing = val;
             Ok(())
         });
+        fields.add_field_method_set("tick_millis", |_, this, val: u64| {
+            this.0.tick_millis = val;
+            Ok(())
+        });
+        fields.add_field_method_set("send_rate_limit", |_, this, val: Option<u32>| {
+            this.0.send_rate_limit = val;
+            Ok(())
+        });
     }
 
     fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        // set_default_link(preset_or_table) - profile for edges without an override
+        methods.add_method_mut("set_default_link", |_, this, profile: Value| {
+            this.0.links.default = link_profile_from_lua(profile)?;
+            Ok(())
+        });
+
+        // set_link(a, b, preset_or_table) - override one edge
+        methods.add_method_mut(
+            "set_link",
+            |_, this, (a, b, profile): (LuaPeerId, LuaPeerId, Value)| {
+                this.0.links.set_edge(a.0, b.0, link_profile_from_lua(profile)?);
+                Ok(())
+            },
+        );
+
+        // set_location(peer, lat, lng) - place a peer for propagation delay
+        methods.add_method_mut(
+            "set_location",
+            |_, this, (peer, lat, lng): (LuaPeerId, f64, f64)| {
+                this.0.links.set_location(peer.0, lat, lng);
+                Ok(())
+            },
+        );
+
+        // add_adversary(peer, behavior) - make a peer misbehave
+        methods.add_method_mut(
+            "add_adversary",
+            |_, this, (peer, behavior): (LuaPeerId, Value)| {
+                this.0
+                    .adversaries
+                    .add(peer.0, adversary_behavior_from_lua(behavior)?);
+                Ok(())
+            },
+        );
+
         methods.add_meta_method(MetaMethod::ToString, |_, this, ()| {
             Ok(format!(
                 "SimConfig(wake={}, sleep={}, max_ticks={})",
@@ -175,6 +300,52 @@ impl UserData for LuaSimulation {
             },
         );
 
+        // set_link(a, b, preset_or_table) - change an edge mid-run
+        methods.add_method(
+            "set_link",
+            |_, this, (a, b, profile): (LuaPeerId, LuaPeerId, Value)| {
+                let profile = link_profile_from_lua(profile)?;
+                this.0.borrow_mut().config.links.set_edge(a.0, b.0, profile);
+                Ok(())
+            },
+        );
+
+        // add_adversary(peer, behavior) - turn a peer adversarial mid-run
+        methods.add_method(
+            "add_adversary",
+            |_, this, (peer, behavior): (LuaPeerId, Value)| {
+                let behavior = adversary_behavior_from_lua(behavior)?;
+                this.0.borrow_mut().config.adversaries.add(peer.0, behavior);
+                Ok(())
+            },
+        );
+
+        // suspects() -> [PeerId] caught misbehaving
+        methods.add_method("suspects", |_, this, ()| {
+            let suspects: Vec<LuaPeerId> =
+                this.0.borrow().suspects().into_iter().map(LuaPeerId).collect();
+            Ok(suspects)
+        });
+
+        // misbehavior_report() -> { [peer] = { [kind] = count } }
+        methods.add_method("misbehavior_report", |lua, this, ()| {
+            let sim = this.0.borrow();
+            let table = lua.create_table()?;
+            for (suspect, kinds) in sim.misbehavior_reports() {
+                let counts = lua.create_table()?;
+                for (kind, count) in kinds {
+                    counts.set(kind.as_str(), *count)?;
+                }
+                table.set(suspect.to_string(), counts)?;
+            }
+            Ok(table)
+        });
+
+        // in_flight_count() -> number of packets still crossing a link
+        methods.add_method("in_flight_count", |_, this, ()| {
+            Ok(this.0.borrow().in_flight_count())
+        });
+
         // state_summary() -> string
         methods.add_method("state_summary", |_, this, ()| {
             Ok(this.0.borrow().state_summary())
@@ -429,6 +600,16 @@ pub fn register_config(lua: &Lua, indras: &Table) -> Result<()> {
                 if let Ok(v) = opts.get::<u32>("max_sender_retries") {
                     cfg.max_sender_retries = Some(v);
                 }
+                if let Ok(v) = opts.get::<u64>("tick_millis") {
+                    cfg.tick_millis = v;
+                }
+                if let Ok(v) = opts.get::<u32>("send_rate_limit") {
+                    cfg.send_rate_limit = Some(v);
+                }
+                let link = opts.get::<Value>("link")?;
+                if !link.is_nil() {
+                    cfg.links.default = link_profile_from_lua(link)?;
+                }
             }
 
             Ok(LuaSimConfig(cfg))
@@ -460,6 +641,28 @@ pub fn register(lua: &Lua, indras: &Table) -> Result<()> {
 
     indras.set("Simulation", simulation)?;
 
+    let adversary = lua.create_table()?;
+
+    // Adversary.crdt_equivocation(adversary, {honest...}) -> outcome table
+    adversary.set(
+        "crdt_equivocation",
+        lua.create_function(|lua, (adversary, honest): (LuaPeerId, Vec<LuaPeerId>)| {
+            let honest: Vec<_> = honest.into_iter().map(|p| p.0).collect();
+            let outcome =
+                play_crdt_equivocation(adversary.0, &honest).map_err(mlua::Error::external)?;
+            let t = lua.create_table()?;
+            t.set("converged", outcome.converged)?;
+            t.set("event_count", outcome.event_count)?;
+            t.set("conflicting_events", outcome.conflicting_events.len())?;
+            let equivocators: Vec<LuaPeerId> =
+                outcome.equivocators.into_iter().map(LuaPeerId).collect();
+            t.set("equivocators", equivocators)?;
+            Ok(t)
+        })?,
+    )?;
+
+    indras.set("Adversary", adversary)?;
+
     Ok(())
 }
 
@@ -611,4 +814,90 @@ mod tests {
             .unwrap();
         assert!(summary.contains("1 online"));
     }
+
+    #[test]
+    fn test_sim_config_link_presets() {
+        let lua = setup_lua();
+
+        let (delivered, in_flight): (u64, usize) = lua
+            .load(
+                r#"
+                local cfg = indras.SimConfig.new({ tick_millis = 100, link = "satellite" })
+                cfg.wake_probability = 0.0
+                cfg.sleep_probability = 0.0
+                cfg:set_link(indras.PeerId.new('A'), indras.PeerId.new('B'),
+                    { preset = "satellite", jitter_ms = 0, loss_probability = 0 })
+
+                local mesh = indras.MeshBuilder.new(2):full_mesh()
+                local sim = indras.Simulation.new(mesh, cfg)
+                local a = indras.PeerId.new('A')
+                local b = indras.PeerId.new('B')
+                sim:force_online(a)
+                sim:force_online(b)
+                sim:send_message(a, b, "Hello!")
+                sim:step()
+                return sim.stats.messages_delivered, sim:in_flight_count()
+            "#,
+            )
+            .eval()
+            .unwrap();
+        assert_eq!(delivered, 0);
+        assert_eq!(in_flight, 1);
+
+        let err = lua
+            .load(r#"indras.SimConfig.new({ link = "carrier pigeon" })"#)
+            .exec()
+            .unwrap_err();
+        assert!(err.to_string().contains("Unknown link preset"));
+    }
+
+
+    #[test]
+    fn test_adversary_replays_are_rejected() {
+        let lua = setup_lua();
+
+        let (delivered, rejected, suspect): (u64, u64, String) = lua
+            .load(
+                r#"
+                local cfg = indras.SimConfig.manual()
+                cfg:add_adversary('B', { behavior = "replay", copies = 2 })
+
+                local mesh = indras.MeshBuilder.new(3):line()
+                local sim = indras.Simulation.new(mesh, cfg)
+                for _, p in ipairs({ 'A', 'B', 'C' }) do
+                    sim:force_online(indras.PeerId.new(p))
+                end
+                sim:send_message(indras.PeerId.new('A'), indras.PeerId.new('C'), "Hello!")
+                sim:run_ticks(5)
+
+                local report = sim:misbehavior_report()
+                return sim.stats.messages_delivered, sim.stats.replays_rejected,
+                    tostring(sim:suspects()[1]) .. ":" .. report.B.Replay
+            "#,
+            )
+            .eval()
+            .unwrap();
+        assert_eq!(delivered, 1);
+        assert_eq!(rejected, 2);
+        assert!(suspect.contains("B"));
+        assert!(suspect.ends_with(":2"));
+    }
+
+    #[test]
+    fn test_crdt_equivocation_binding() {
+        let lua = setup_lua();
+
+        let (converged, conflicts): (bool, usize) = lua
+            .load(
+                r#"
+                local outcome = indras.Adversary.crdt_equivocation('E', { 'A', 'B' })
+                return outcome.converged, outcome.conflicting_events
+            "#,
+            )
+            .eval()
+            .unwrap();
+        assert!(converged);
+        assert_eq!(conflicts, 1);
+    }
+
 }
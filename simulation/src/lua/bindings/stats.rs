@@ -85,6 +85,24 @@ impl UserData for LuaSimStats {
         fields.add_field_method_get("invites_created", |_, this| Ok(this.0.invites_created));
         fields.add_field_method_get("invites_accepted", |_, this| Ok(this.0.invites_accepted));
         fields.add_field_method_get("invites_failed", |_, this| Ok(this.0.invites_failed));
+        // Link model metrics
+        fields.add_field_method_get("link_transits", |_, this| Ok(this.0.link_transits));
+        fields.add_field_method_get("link_losses", |_, this| Ok(this.0.link_losses));
+        fields.add_field_method_get("total_link_delay_ms", |_, this| {
+            Ok(this.0.total_link_delay_ms)
+        });
+        // Adversary metrics
+        fields.add_field_method_get("replays_sent", |_, this| Ok(this.0.replays_sent));
+        fields.add_field_method_get("replays_rejected", |_, this| Ok(this.0.replays_rejected));
+        fields.add_field_method_get("equivocations_sent", |_, this| {
+            Ok(this.0.equivocations_sent)
+        });
+        fields.add_field_method_get("equivocations_detected", |_, this| {
+            Ok(this.0.equivocations_detected)
+        });
+        fields.add_field_method_get("acks_dropped", |_, this| Ok(this.0.acks_dropped));
+        fields.add_field_method_get("spam_sent", |_, this| Ok(this.0.spam_sent));
+        fields.add_field_method_get("rate_limited", |_, this| Ok(this.0.rate_limited));
     }
 
     fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
@@ -198,6 +216,11 @@ impl UserData for LuaSimStats {
             Ok(this.0.invites_accepted as f64 / total as f64)
         });
 
+        // avg_link_delay_ms() -> float
+        methods.add_method("avg_link_delay_ms", |_, this, ()| {
+            Ok(this.0.avg_link_delay_ms())
+        });
+
         // to_table() -> table with all stats
         methods.add_method("to_table", |lua, this, ()| {
             let t = lua.create_table()?;
@@ -234,6 +257,18 @@ impl UserData for LuaSimStats {
             t.set("invites_created", this.0.invites_created)?;
             t.set("invites_accepted", this.0.invites_accepted)?;
             t.set("invites_failed", this.0.invites_failed)?;
+            // Link model metrics
+            t.set("link_transits", this.0.link_transits)?;
+            t.set("link_losses", this.0.link_losses)?;
+            t.set("total_link_delay_ms", this.0.total_link_delay_ms)?;
+            // Adversary metrics
+            t.set("replays_sent", this.0.replays_sent)?;
+            t.set("replays_rejected", this.0.replays_rejected)?;
+            t.set("equivocations_sent", this.0.equivocations_sent)?;
+            t.set("equivocations_detected", this.0.equivocations_detected)?;
+            t.set("acks_dropped", this.0.acks_dropped)?;
+            t.set("spam_sent", this.0.spam_sent)?;
+            t.set("rate_limited", this.0.rate_limited)?;
             Ok(t)
         });
 
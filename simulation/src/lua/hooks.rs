@@ -112,6 +112,7 @@ fn event_type_name(event: &NetworkEvent) -> &'static str {
         NetworkEvent::InviteCreated { .. } => "InviteCreated",
         NetworkEvent::InviteAccepted { .. } => "InviteAccepted",
         NetworkEvent::InviteFailed { .. } => "InviteFailed",
+        NetworkEvent::Misbehavior { .. } => "Misbehavior",
     }
 }
 
@@ -5,11 +5,15 @@
 //! - Awake signals and update requests
 //! - Store-and-forward routing for offline peers
 //! - Back-propagation of delivery confirmations
+//! - Per-edge latency, bandwidth, and loss (see [`LinkModel`])
+//! - Adversarial peers and misbehavior detection (see [`Adversaries`])
 
 use rand::Rng;
-use std::collections::{BTreeSet, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 use tracing::{debug, info, trace, warn};
 
+use crate::adversary::{Adversaries, Arrival, Misbehavior, MisbehaviorDetector, tamper};
+use crate::link_model::LinkModel;
 use crate::topology::Mesh;
 use crate::types::*;
 
@@ -34,6 +38,14 @@ pub struct SimConfig {
     pub max_sender_retries: Option<u32>,
     /// How often to apply PRoPHET aging (in ticks)
     pub prophet_aging_interval: u64,
+    /// Wall-clock milliseconds represented by one tick, for converting link delay
+    pub tick_millis: u64,
+    /// Latency, bandwidth, and loss for each edge (ideal by default)
+    pub links: LinkModel,
+    /// Peers that misbehave, and how (none by default)
+    pub adversaries: Adversaries,
+    /// Maximum new messages one peer may send per tick (None = no limit)
+    pub send_rate_limit: Option<u32>,
 }
 
 impl Default for SimConfig {
@@ -48,6 +60,10 @@ impl Default for SimConfig {
             backprop_timeout: Some(100),
             max_sender_retries: Some(10),
             prophet_aging_interval: 100,
+            tick_millis: 100,
+            links: LinkModel::default(),
+            adversaries: Adversaries::default(),
+            send_rate_limit: None,
         }
     }
 }
@@ -67,6 +83,12 @@ pub struct Simulation {
     pending_sends: VecDeque<PendingSend>,
     /// Active back-propagations
     backprops: Vec<BackPropState>,
+    /// Packets still crossing a slow link to their destination
+    in_flight: Vec<InFlight>,
+    /// Copies adversaries will re-send next tick
+    replays: Vec<InFlight>,
+    /// Duplicate, equivocation, and stalled-ack tracking for honest peers
+    detector: MisbehaviorDetector,
     /// Statistics
     pub stats: SimStats,
 }
@@ -81,6 +103,16 @@ struct PendingSend {
     retry_count: u32,
 }
 
+#[derive(Debug, Clone)]
+struct InFlight {
+    packet: SealedPacket,
+    /// Peer that transmitted the packet
+    from: PeerId,
+    /// Peer receiving it (the destination or the next relay)
+    to: PeerId,
+    arrive_tick: u64,
+}
+
 #[derive(Debug, Clone)]
 struct BackPropState {
     packet_id: PacketId,
@@ -89,6 +121,8 @@ struct BackPropState {
     /// Current position in back-propagation (index in path, going backwards)
     backprop_index: usize,
     delivered_tick: u64,
+    /// An adversary on the path is withholding the confirmation
+    swallowed: bool,
 }
 
 /// Simulation statistics
@@ -143,6 +177,41 @@ pub struct SimStats {
     pub invites_accepted: u64,
     /// Invites failed
     pub invites_failed: u64,
+
+    // Link model metrics
+    /// Transmissions that crossed a link (relay hops and final deliveries)
+    pub link_transits: u64,
+    /// Transmissions lost on a link and requeued for retry
+    pub link_losses: u64,
+    /// Total sampled link delay in milliseconds
+    pub total_link_delay_ms: f64,
+
+    // Adversary metrics
+    /// Packet copies adversaries re-sent
+    pub replays_sent: u64,
+    /// Re-sent packets honest peers rejected as duplicates
+    pub replays_rejected: u64,
+    /// Tampered copies adversaries forwarded
+    pub equivocations_sent: u64,
+    /// Conflicting copies honest peers caught
+    pub equivocations_detected: u64,
+    /// Delivery confirmations adversaries swallowed
+    pub acks_dropped: u64,
+    /// Junk messages adversaries sent
+    pub spam_sent: u64,
+    /// Sends dropped for exceeding the per-tick limit
+    pub rate_limited: u64,
+}
+
+impl SimStats {
+    /// Average sampled link delay per transit in milliseconds
+    pub fn avg_link_delay_ms(&self) -> f64 {
+        if self.link_transits == 0 {
+            0.0
+        } else {
+            self.total_link_delay_ms / self.link_transits as f64
+        }
+    }
 }
 
 impl Simulation {
@@ -155,6 +224,9 @@ impl Simulation {
             event_log: Vec::new(),
             pending_sends: VecDeque::new(),
             backprops: Vec::new(),
+            in_flight: Vec::new(),
+            replays: Vec::new(),
+            detector: MisbehaviorDetector::new(),
             stats: SimStats::default(),
         }
     }
@@ -197,6 +269,10 @@ impl Simulation {
         self.tick += 1;
         trace!("=== Tick {} ===", self.tick);
 
+        // 0. Land packets whose link delay has elapsed, then let adversaries act
+        self.process_in_flight();
+        self.process_adversaries();
+
         // 1. Process wake/sleep transitions
         self.process_state_transitions();
 
@@ -329,8 +405,234 @@ impl Simulation {
         }
     }
 
+    /// Sample one transmission from `from` to `to`
+    ///
+    /// Returns `None` if the link lost it, otherwise how many ticks it
+    /// takes to cross.
+    fn sample_link(&mut self, from: PeerId, to: PeerId, payload_len: usize) -> Option<u64> {
+        let link = self.config.links.profile(from, to);
+        if link.is_ideal() {
+            return Some(0);
+        }
+
+        let mut rng = rand::rng();
+        if link.sample_loss(&mut rng) {
+            debug!("Transmission lost on link {} -> {}", from, to);
+            self.stats.link_losses += 1;
+            return None;
+        }
+
+        let delay_ms = link.transit_millis(payload_len, &mut rng);
+        self.stats.link_transits += 1;
+        self.stats.total_link_delay_ms += delay_ms;
+        Some((delay_ms / self.config.tick_millis.max(1) as f64).round() as u64)
+    }
+
+    /// Hold a packet on a link for `ticks` before it reaches `to`
+    fn send_in_flight(&mut self, packet: SealedPacket, from: PeerId, to: PeerId, ticks: u64) {
+        trace!(
+            "Packet {} in flight {} -> {} for {} ticks",
+            packet.id, from, to, ticks
+        );
+        self.in_flight.push(InFlight {
+            packet,
+            from,
+            to,
+            arrive_tick: self.tick + ticks,
+        });
+    }
+
+    /// Send a packet across the final link from `via` to its destination
+    ///
+    /// Lost transmissions go back into `via`'s relay queue for retry.
     fn deliver_packet(&mut self, packet: SealedPacket, via: PeerId) {
         let dest_id = packet.destination;
+        self.schedule_replays(&packet, via, dest_id);
+        match self.sample_link(via, dest_id, packet.payload.len()) {
+            Some(0) => self.complete_delivery(packet, via),
+            Some(ticks) => self.send_in_flight(packet, via, dest_id, ticks),
+            None => {
+                let via_peer = self.mesh.peers.get_mut(&via).unwrap();
+                via_peer.relay_queue.push(packet);
+            }
+        }
+    }
+
+    /// Land packets whose link delay has elapsed
+    ///
+    /// Relay hops join the relay's queue. A final delivery whose
+    /// destination went offline mid-transit returns to the sender's queue.
+    fn process_in_flight(&mut self) {
+        let (landed, still_flying): (Vec<InFlight>, Vec<InFlight>) = self
+            .in_flight
+            .drain(..)
+            .partition(|f| f.arrive_tick <= self.tick);
+        self.in_flight = still_flying;
+
+        for flight in landed {
+            if flight.to != flight.packet.destination {
+                self.arrive_at_relay(flight.packet, flight.from, flight.to);
+                continue;
+            }
+            let dest_online = self
+                .mesh
+                .peers
+                .get(&flight.to)
+                .map(|p| p.online)
+                .unwrap_or(false);
+            if dest_online {
+                self.complete_delivery(flight.packet, flight.from);
+            } else {
+                let from_peer = self.mesh.peers.get_mut(&flight.from).unwrap();
+                from_peer.relay_queue.push(flight.packet);
+            }
+        }
+    }
+
+    /// Number of packets currently crossing a link
+    pub fn in_flight_count(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Put a packet in a relay's queue unless the relay rejects it
+    fn arrive_at_relay(&mut self, packet: SealedPacket, from: PeerId, relay: PeerId) {
+        if self.accept_arrival(&packet, from, relay) {
+            let relay_peer = self.mesh.peers.get_mut(&relay).unwrap();
+            relay_peer.relay_queue.push(packet);
+        }
+    }
+
+    /// Run an honest peer's duplicate and equivocation checks
+    ///
+    /// Adversaries accept everything.
+    fn accept_arrival(&mut self, packet: &SealedPacket, from: PeerId, at: PeerId) -> bool {
+        if self.config.adversaries.is_adversary(at) {
+            return true;
+        }
+        match self.detector.check_arrival(at, packet) {
+            Arrival::Fresh => true,
+            Arrival::Replay => {
+                debug!("{} rejected replay of {} from {}", at, packet.id, from);
+                self.stats.replays_rejected += 1;
+                self.emit_event(NetworkEvent::Dropped {
+                    packet_id: packet.id,
+                    reason: DropReason::Duplicate,
+                    tick: self.tick,
+                });
+                self.flag(from, at, Misbehavior::Replay, Some(packet.id));
+                false
+            }
+            Arrival::Equivocation(suspects) => {
+                warn!(
+                    "{} received conflicting copies of {} (suspects: {:?})",
+                    at, packet.id, suspects
+                );
+                self.stats.equivocations_detected += 1;
+                for suspect in suspects {
+                    self.flag(suspect, at, Misbehavior::Equivocation, Some(packet.id));
+                }
+                false
+            }
+        }
+    }
+
+    /// Record a detection against `suspect`
+    fn flag(
+        &mut self,
+        suspect: PeerId,
+        observer: PeerId,
+        kind: Misbehavior,
+        packet_id: Option<PacketId>,
+    ) {
+        self.detector.report(suspect, kind);
+        self.emit_event(NetworkEvent::Misbehavior {
+            suspect,
+            observer,
+            kind,
+            packet_id,
+            tick: self.tick,
+        });
+    }
+
+    /// Queue the extra copies a replaying adversary sends next tick
+    fn schedule_replays(&mut self, packet: &SealedPacket, from: PeerId, to: PeerId) {
+        if self.config.adversaries.is_adversary(to) {
+            return;
+        }
+        let copies = self.config.adversaries.replay_copies(from);
+        for _ in 0..copies {
+            self.replays.push(InFlight {
+                packet: packet.clone(),
+                from,
+                to,
+                arrive_tick: self.tick + 1,
+            });
+        }
+    }
+
+    /// Send adversaries' replays and spam
+    fn process_adversaries(&mut self) {
+        if self.config.adversaries.is_empty() {
+            return;
+        }
+
+        for replay in std::mem::take(&mut self.replays) {
+            let to_online = self
+                .mesh
+                .peers
+                .get(&replay.to)
+                .map(|p| p.online)
+                .unwrap_or(false);
+            if !to_online || !self.is_online(replay.from) {
+                continue;
+            }
+            self.stats.replays_sent += 1;
+            if replay.to == replay.packet.destination {
+                self.complete_delivery(replay.packet, replay.from);
+            } else {
+                self.arrive_at_relay(replay.packet, replay.from, replay.to);
+            }
+        }
+
+        let mut rng = rand::rng();
+        for spammer in self.config.adversaries.peers() {
+            let per_tick = self.config.adversaries.spam_rate(spammer);
+            if per_tick == 0 || !self.is_online(spammer) {
+                continue;
+            }
+            let targets: Vec<PeerId> = self
+                .mesh
+                .peer_ids()
+                .into_iter()
+                .filter(|p| *p != spammer)
+                .collect();
+            if targets.is_empty() {
+                continue;
+            }
+            for _ in 0..per_tick {
+                let target = targets[rng.random_range(0..targets.len())];
+                let junk: Vec<u8> = (0..32).map(|_| rng.random()).collect();
+                self.send_message(spammer, target, junk);
+                self.stats.spam_sent += 1;
+            }
+        }
+    }
+
+    /// Misbehavior detected so far, by suspect
+    pub fn misbehavior_reports(&self) -> &BTreeMap<PeerId, BTreeMap<Misbehavior, u64>> {
+        self.detector.reports()
+    }
+
+    /// Peers caught misbehaving at least once, sorted
+    pub fn suspects(&self) -> Vec<PeerId> {
+        self.detector.suspects()
+    }
+
+    fn complete_delivery(&mut self, packet: SealedPacket, via: PeerId) {
+        let dest_id = packet.destination;
+        if !self.accept_arrival(&packet, via, dest_id) {
+            return;
+        }
         let packet_id = packet.id;
         let created_at = packet.created_at;
 
@@ -378,6 +680,7 @@ impl Simulation {
                 path: path.clone(),
                 backprop_index: 0,
                 delivered_tick: self.tick,
+                swallowed: false,
             });
 
             // Store packet_id in path for relay queue cleanup later
@@ -387,6 +690,7 @@ impl Simulation {
 
     fn process_pending_sends(&mut self) {
         let sends: Vec<PendingSend> = self.pending_sends.drain(..).collect();
+        let mut sent_this_tick: HashMap<PeerId, u32> = HashMap::new();
 
         for send in sends {
             // Check for message expiration
@@ -414,6 +718,32 @@ impl Simulation {
                 continue;
             }
 
+            // Enforce the per-tick send limit on new messages
+            if let Some(limit) = self.config.send_rate_limit
+                && send.retry_count == 0
+            {
+                let count = sent_this_tick.entry(send.from).or_default();
+                *count += 1;
+                if *count > limit {
+                    let first_over = *count == limit + 1;
+                    self.stats.rate_limited += 1;
+                    self.stats.messages_dropped += 1;
+                    let packet_id = PacketId {
+                        source: send.from,
+                        sequence: 0,
+                    };
+                    self.emit_event(NetworkEvent::Dropped {
+                        packet_id,
+                        reason: DropReason::RateLimited,
+                        tick: self.tick,
+                    });
+                    if first_over {
+                        self.flag(send.from, send.to, Misbehavior::Spam, None);
+                    }
+                    continue;
+                }
+            }
+
             self.emit_event(NetworkEvent::Send {
                 from: send.from,
                 to: send.to,
@@ -560,6 +890,13 @@ impl Simulation {
 
         match best_relay {
             Some(relay) => {
+                let Some(ticks) = self.sample_link(current, relay, packet.payload.len()) else {
+                    // Lost on the link - keep it here and retry next tick
+                    let current_peer = self.mesh.peers.get_mut(&current).unwrap();
+                    current_peer.relay_queue.push(packet);
+                    return;
+                };
+
                 debug!("Routing packet {} through relay {}", packet.id, relay);
 
                 if !packet.decrement_ttl() {
@@ -572,6 +909,17 @@ impl Simulation {
                     return;
                 }
 
+                // An equivocating adversary forks the packet to a second relay
+                let forged = if self.config.adversaries.equivocates(current) {
+                    candidates
+                        .iter()
+                        .copied()
+                        .find(|c| *c != relay && *c != dest)
+                        .map(|alt| (alt, tamper(&packet)))
+                } else {
+                    None
+                };
+
                 packet.mark_visited(relay);
 
                 // Record encounter: current peer successfully forwarded to relay
@@ -588,9 +936,31 @@ impl Simulation {
                     tick: self.tick,
                 });
 
-                // Add to relay peer's queue
-                let relay_peer = self.mesh.peers.get_mut(&relay).unwrap();
-                relay_peer.relay_queue.push(packet);
+                self.schedule_replays(&packet, current, relay);
+
+                // Add to relay peer's queue once the link has been crossed
+                if ticks == 0 {
+                    self.arrive_at_relay(packet, current, relay);
+                } else {
+                    self.send_in_flight(packet, current, relay, ticks);
+                }
+
+                if let Some((alt, mut forged)) = forged {
+                    forged.mark_visited(alt);
+                    self.stats.equivocations_sent += 1;
+                    self.emit_event(NetworkEvent::Relay {
+                        from: current,
+                        via: alt,
+                        to: dest,
+                        packet_id: forged.id,
+                        tick: self.tick,
+                    });
+                    match self.sample_link(current, alt, forged.payload.len()) {
+                        Some(0) => self.arrive_at_relay(forged, current, alt),
+                        Some(ticks) => self.send_in_flight(forged, current, alt, ticks),
+                        None => {}
+                    }
+                }
             }
             None => {
                 // No online relay available - store at current peer for later
@@ -704,6 +1074,15 @@ impl Simulation {
                 .map(|p| p.online)
                 .unwrap_or(false);
 
+            if self.config.adversaries.drops_acks(current) {
+                if !backprop.swallowed {
+                    backprop.swallowed = true;
+                    self.stats.acks_dropped += 1;
+                    debug!("{} swallowed back-prop for {}", current, backprop.packet_id);
+                }
+                continue;
+            }
+
             if current_online && next_online {
                 events_to_emit.push((backprop.packet_id, current, next, self.tick));
                 backprop.backprop_index += 1;
@@ -729,6 +1108,16 @@ impl Simulation {
         for idx in timed_out_indices.into_iter().rev() {
             let bp = self.backprops.remove(idx);
             self.stats.backprops_timed_out += 1;
+
+            // A confirmation stuck between two online peers was withheld
+            let holder_idx = bp.path.len() - 1 - bp.backprop_index.min(bp.path.len() - 1);
+            if holder_idx > 0 {
+                let (holder, next) = (bp.path[holder_idx], bp.path[holder_idx - 1]);
+                if self.is_online(holder) && self.is_online(next) {
+                    self.flag(holder, next, Misbehavior::DroppedAck, Some(bp.packet_id));
+                }
+            }
+
             warn!(
                 "Back-propagation timed out for packet {} after {} ticks",
                 bp.packet_id,
@@ -1034,4 +1423,176 @@ mod tests {
         // Now it should be delivered
         assert_eq!(sim.stats.messages_delivered, 1);
     }
+
+    #[test]
+    fn test_link_latency_delays_delivery() {
+        use crate::link_model::LinkProfile;
+
+        let mesh = MeshBuilder::new(2).full_mesh();
+        let mut links = LinkModel::default();
+        links.set_edge(
+            PeerId('A'),
+            PeerId('B'),
+            LinkProfile {
+                latency_ms: 300.0,
+                ..LinkProfile::ideal()
+            },
+        );
+        let mut sim = Simulation::new(
+            mesh,
+            SimConfig {
+                wake_probability: 0.0,
+                sleep_probability: 0.0,
+                tick_millis: 100,
+                links,
+                ..Default::default()
+            },
+        );
+        sim.force_online(PeerId('A'));
+        sim.force_online(PeerId('B'));
+
+        sim.send_message(PeerId('A'), PeerId('B'), vec![1, 2, 3]);
+        sim.step();
+        assert_eq!(sim.stats.messages_delivered, 0);
+        assert_eq!(sim.in_flight_count(), 1);
+
+        sim.run_ticks(3);
+        assert_eq!(sim.stats.messages_delivered, 1);
+        assert_eq!(sim.in_flight_count(), 0);
+        assert!((sim.stats.avg_link_delay_ms() - 300.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_lost_transmissions_are_retried() {
+        use crate::link_model::LinkProfile;
+
+        let mesh = MeshBuilder::new(2).full_mesh();
+        let mut sim = Simulation::new(
+            mesh,
+            SimConfig {
+                wake_probability: 0.0,
+                sleep_probability: 0.0,
+                links: LinkModel::uniform(LinkProfile {
+                    loss_probability: 1.0,
+                    ..LinkProfile::ideal()
+                }),
+                ..Default::default()
+            },
+        );
+        sim.force_online(PeerId('A'));
+        sim.force_online(PeerId('B'));
+
+        sim.send_message(PeerId('A'), PeerId('B'), vec![1]);
+        sim.run_ticks(3);
+        assert_eq!(sim.stats.messages_delivered, 0);
+        assert!(sim.stats.link_losses >= 3);
+
+        // Link recovers; the held packet goes through
+        sim.config.links = LinkModel::default();
+        sim.run_ticks(2);
+        assert_eq!(sim.stats.messages_delivered, 1);
+    }
+
+
+    fn adversarial_sim(
+        mesh: Mesh,
+        adversary: PeerId,
+        behavior: crate::adversary::AdversaryBehavior,
+        config: SimConfig,
+    ) -> Simulation {
+        let mut config = SimConfig {
+            wake_probability: 0.0,
+            sleep_probability: 0.0,
+            ..config
+        };
+        config.adversaries.add(adversary, behavior);
+        let mut sim = Simulation::new(mesh, config);
+        for peer in sim.mesh.peer_ids() {
+            sim.force_online(peer);
+        }
+        sim
+    }
+
+    #[test]
+    fn test_equivocation_is_detected() {
+        use crate::adversary::AdversaryBehavior;
+
+        //     B
+        //    / \
+        // A-E   D
+        //    \ /
+        //     C
+        let mesh = crate::from_edges(&[
+            ('A', 'E'),
+            ('E', 'B'),
+            ('E', 'C'),
+            ('B', 'D'),
+            ('C', 'D'),
+        ]);
+        let mut sim = adversarial_sim(
+            mesh,
+            PeerId('E'),
+            AdversaryBehavior::Equivocate,
+            SimConfig::default(),
+        );
+
+        sim.send_message(PeerId('A'), PeerId('D'), b"pay D".to_vec());
+        sim.run_ticks(4);
+
+        assert_eq!(sim.stats.messages_delivered, 1);
+        assert_eq!(sim.stats.equivocations_sent, 1);
+        assert_eq!(sim.stats.equivocations_detected, 1);
+        assert_eq!(sim.suspects(), vec![PeerId('E')]);
+    }
+
+    #[test]
+    fn test_dropped_acks_are_blamed() {
+        use crate::adversary::AdversaryBehavior;
+
+        let mesh = MeshBuilder::new(3).line();
+        let mut sim = adversarial_sim(
+            mesh,
+            PeerId('B'),
+            AdversaryBehavior::DropAcks,
+            SimConfig {
+                backprop_timeout: Some(3),
+                ..Default::default()
+            },
+        );
+
+        sim.send_message(PeerId('A'), PeerId('C'), vec![1, 2, 3]);
+        sim.run_ticks(6);
+
+        assert_eq!(sim.stats.messages_delivered, 1);
+        assert_eq!(sim.stats.acks_dropped, 1);
+        assert_eq!(sim.stats.backprops_timed_out, 1);
+        assert_eq!(
+            sim.misbehavior_reports()[&PeerId('B')][&Misbehavior::DroppedAck],
+            1
+        );
+    }
+
+    #[test]
+    fn test_spam_is_rate_limited() {
+        use crate::adversary::AdversaryBehavior;
+
+        let mesh = MeshBuilder::new(3).full_mesh();
+        let mut sim = adversarial_sim(
+            mesh,
+            PeerId('A'),
+            AdversaryBehavior::Spam { per_tick: 10 },
+            SimConfig {
+                send_rate_limit: Some(4),
+                ..Default::default()
+            },
+        );
+
+        sim.step();
+
+        assert_eq!(sim.stats.spam_sent, 10);
+        assert_eq!(sim.stats.rate_limited, 6);
+        assert_eq!(sim.stats.messages_delivered, 4);
+        assert_eq!(sim.suspects(), vec![PeerId('A')]);
+    }
+
 }
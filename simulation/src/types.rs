@@ -9,6 +9,8 @@ use indras_core::{IdentityError, PeerIdentity};
 use indras_dtn::prophet::ProphetState;
 use serde::{Deserialize, Serialize};
 
+use crate::adversary::Misbehavior;
+
 /// Unique identifier for a peer in the network (A-Z)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct PeerId(pub char);
@@ -234,6 +236,14 @@ pub enum NetworkEvent {
         reason: String,
         tick: u64,
     },
+    /// An honest peer caught another peer misbehaving
+    Misbehavior {
+        suspect: PeerId,
+        observer: PeerId,
+        kind: Misbehavior,
+        packet_id: Option<PacketId>,
+        tick: u64,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -245,6 +255,8 @@ pub enum DropReason {
     Expired,
     /// Sender never came online after max retries
     SenderOffline,
+    /// Sender exceeded the per-tick send limit
+    RateLimited,
 }
 
 /// State of a peer in the network